@@ -181,25 +181,37 @@ install_tools() {
   local tools=(
     "how"
     "aiignore"
+    "archive"
     "b64"
+    "clip"
     "csvn"
+    "dirdiff"
     "distro-cc"
     "eh-export"
     "eh-read"
+    "eh-send"
     "get-lines"
     "gitignore"
+    "gitstat"
     "guid"
+    "hashx"
     "http"
     "imgx"
     "jwt"
+    "keyvault"
     "lookup"
     "pingx"
     "mock"
     "mqtt"
+    "netcatx"
     "qrcode"
+    "regexlab"
     "remove-zw"
+    "rtb"
     "split"
     "ts"
+    "toolbox-update"
+    "unitconv"
     "whisper"
     "whurl"
   )
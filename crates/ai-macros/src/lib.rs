@@ -2,7 +2,12 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, ItemFn};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, Expr, FnArg, GenericArgument, Ident, ItemFn, Lit, Meta, Pat, PathArguments,
+    Token, Type,
+};
 
 /// Attribute macro that makes any Rust function both callable as normal *and*
 /// introspectable by generating a hidden “_as_string” helper that returns
@@ -44,18 +49,54 @@ use syn::{parse_macro_input, ItemFn};
 ///    - the original function tokens (so its behavior is unchanged),  
 ///    - a hidden, `dead_code`‐allowed function that calls `stringify!` on those tokens,
 ///      yielding a compile‐time `&'static str`.
+///
+/// # Tools
+///
+/// With `#[ai_function(tool)]`, a `<name>_tool()` helper is generated as well. It returns an
+/// `ai_shared::tools::ToolDefinition`, so the function can be registered as a tool the AI calls
+/// (`requester.set_tools(...)`): the doc comments become the description, and the parameters
+/// are read from the JSON arguments of the call. The crate using it must depend on `ai-shared`.
+///
+/// Parameters can be strings (`String`, `&str`), numbers, `bool`, `Vec<T>`, or `Option<T>` (not
+/// required). The function must return something `ai_shared::tools::IntoToolOutput` accepts
+/// (strings, numbers, `bool`, or a `Result` of those).
 #[proc_macro_attribute]
-pub fn ai_function(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn ai_function(attr: TokenStream, item: TokenStream) -> TokenStream {
     // Convert the incoming TokenStream into proc_macro2 for quoting:
     let original_ts: TokenStream2 = item.clone().into();
 
+    let options = match Punctuated::<Ident, Token![,]>::parse_terminated.parse(attr) {
+        Ok(options) => options,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let mut as_tool = false;
+    for option in &options {
+        if option == "tool" {
+            as_tool = true;
+        } else {
+            return syn::Error::new(option.span(), "unknown option, expected `tool`")
+                .to_compile_error()
+                .into();
+        }
+    }
+
     // Parse into a syn::ItemFn so we can grab `vis` and `ident`:
     let input_fn: ItemFn = parse_macro_input!(item as ItemFn);
     let vis = &input_fn.vis;
     let name = &input_fn.sig.ident;
     let helper = format_ident!("{}_as_string", name, span = name.span());
 
-    // Re-emit the original fn + hidden `*_as_string` helper:
+    let tool_helper = if as_tool {
+        match build_tool_helper(&input_fn) {
+            Ok(tool_helper) => tool_helper,
+            Err(e) => return e.to_compile_error().into(),
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    // Re-emit the original fn + hidden `*_as_string` helper (and `*_tool`, when asked):
     let expanded = quote! {
         #original_ts
 
@@ -64,8 +105,209 @@ pub fn ai_function(_attr: TokenStream, item: TokenStream) -> TokenStream {
         #vis fn #helper() -> &'static str {
             stringify!(#original_ts)
         }
+
+        #tool_helper
     };
 
     // Convert *back* into the compiler’s TokenStream:
     TokenStream::from(expanded)
 }
+
+/// Generates the `<name>_tool()` helper: the tool definition, with a `call` that reads each
+/// parameter from the JSON arguments and calls the function.
+fn build_tool_helper(input_fn: &ItemFn) -> syn::Result<TokenStream2> {
+    let vis = &input_fn.vis;
+    let name = &input_fn.sig.ident;
+    let tool_name = name.to_string();
+    let helper = format_ident!("{}_tool", name, span = name.span());
+
+    if let Some(asyncness) = &input_fn.sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span,
+            "async functions can't be used as tools",
+        ));
+    }
+
+    let description = doc_comment(input_fn).unwrap_or_else(|| tool_name.clone());
+
+    let mut parameters = Vec::new();
+    let mut reads = Vec::new();
+    let mut call_args = Vec::new();
+
+    for input in &input_fn.sig.inputs {
+        let FnArg::Typed(typed) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "methods can't be used as tools",
+            ));
+        };
+
+        let Pat::Ident(pat_ident) = typed.pat.as_ref() else {
+            return Err(syn::Error::new_spanned(
+                &typed.pat,
+                "tool parameters must be plain names",
+            ));
+        };
+
+        let ident = &pat_ident.ident;
+        let param_name = ident.to_string();
+
+        // References are read as owned values, then borrowed in the call.
+        let (owned_type, borrowed) = match typed.ty.as_ref() {
+            Type::Reference(reference) if is_str(&reference.elem) => {
+                (syn::parse_quote!(String), true)
+            }
+            Type::Reference(reference) => (reference.elem.as_ref().clone(), true),
+            other => (other.clone(), false),
+        };
+
+        let (kind, optional) = match option_inner(&owned_type) {
+            Some(inner) => (schema_kind(inner), true),
+            None => (schema_kind(&owned_type), false),
+        };
+        let required = !optional;
+
+        parameters.push(quote! {
+            ::ai_shared::tools::ToolParameter {
+                name: #param_name,
+                kind: #kind,
+                required: #required,
+            }
+        });
+
+        reads.push(if optional {
+            quote! {
+                let #ident: #owned_type = ::ai_shared::tools::parse_optional_arg(arguments, #param_name)?;
+            }
+        } else {
+            quote! {
+                let #ident: #owned_type = ::ai_shared::tools::parse_arg(arguments, #param_name)?;
+            }
+        });
+
+        call_args.push(if borrowed {
+            quote! { &#ident }
+        } else {
+            quote! { #ident }
+        });
+    }
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        #vis fn #helper() -> ::ai_shared::tools::ToolDefinition {
+            fn call(
+                arguments: &::ai_shared::tools::ToolArguments,
+            ) -> ::ai_shared::tools::ToolCallResult {
+                #(#reads)*
+                ::ai_shared::tools::IntoToolOutput::into_tool_output(#name(#(#call_args),*))
+            }
+
+            ::ai_shared::tools::ToolDefinition {
+                name: #tool_name,
+                description: #description,
+                parameters: &[#(#parameters),*],
+                call,
+            }
+        }
+    })
+}
+
+/// The `///` comments of the function, joined in a single line.
+fn doc_comment(input_fn: &ItemFn) -> Option<String> {
+    let lines: Vec<String> = input_fn
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(name_value) => match &name_value.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(text) => Some(text.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+fn last_segment(ty: &Type) -> Option<&syn::PathSegment> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last(),
+        _ => None,
+    }
+}
+
+fn is_str(ty: &Type) -> bool {
+    last_segment(ty).is_some_and(|segment| segment.ident == "str")
+}
+
+/// `T`, when the type is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = last_segment(ty).filter(|segment| segment.ident == "Option")?;
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// JSON schema type of a parameter.
+fn schema_kind(ty: &Type) -> &'static str {
+    let Some(segment) = last_segment(ty) else {
+        return match ty {
+            Type::Array(_) | Type::Slice(_) => "array",
+            _ => "object",
+        };
+    };
+
+    match segment.ident.to_string().as_str() {
+        "String" | "str" | "char" | "PathBuf" => "string",
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => "integer",
+        "f32" | "f64" => "number",
+        "bool" => "boolean",
+        "Vec" | "HashSet" | "BTreeSet" => "array",
+        _ => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_kind_and_option_inner() {
+        let kind = |source: &str| schema_kind(&syn::parse_str::<Type>(source).unwrap());
+
+        assert_eq!(kind("String"), "string");
+        assert_eq!(kind("u64"), "integer");
+        assert_eq!(kind("f32"), "number");
+        assert_eq!(kind("Vec<String>"), "array");
+        assert_eq!(kind("std::collections::HashMap<String, String>"), "object");
+
+        let optional: Type = syn::parse_str("Option<bool>").unwrap();
+        assert_eq!(option_inner(&optional).map(schema_kind), Some("boolean"));
+        assert!(option_inner(&syn::parse_str::<Type>("bool").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_doc_comment_is_the_description() {
+        let input_fn: ItemFn = syn::parse_str(
+            "/// Lists the files.\n/// Read-only.\nfn list_files(path: String) -> String { path }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc_comment(&input_fn),
+            Some("Lists the files. Read-only.".to_string())
+        );
+    }
+}
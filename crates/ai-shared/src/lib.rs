@@ -1,4 +1,6 @@
 pub mod message_builders;
 pub mod models;
+pub mod providers;
 pub mod request_wrappers;
+pub mod tools;
 pub mod utils;
@@ -2,10 +2,43 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Message {
     pub role: String,
     pub content: String,
+    /// Tools the assistant asked to call.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+    /// The call a `tool` message answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl Message {
+    pub fn new(role: &str, content: String) -> Self {
+        Self {
+            role: role.to_string(),
+            content,
+            tool_calls: Vec::new(),
+            tool_call_id: None,
+        }
+    }
+
+    /// The output of a tool, sent back to the model.
+    pub fn tool_result(call: &ToolCall, output: String) -> Self {
+        Self {
+            tool_call_id: Some(call.id.clone()),
+            ..Self::new("tool", output)
+        }
+    }
+}
+
+/// A call to a local tool, requested by the model. `arguments` is the raw JSON object.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -31,6 +64,39 @@ pub struct ApiResponse {
     pub usage: Option<HashMap<String, Value>>,
 }
 
+#[derive(Debug, Default, Clone, Copy, Serialize, PartialEq)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn new(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    }
+
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+
+    /// Accumulates the `usage` block returned by OpenAI-compatible APIs.
+    /// Missing or non-numeric fields are ignored, since not every provider reports all of them.
+    pub fn add_usage(&mut self, usage: &HashMap<String, Value>) {
+        let read = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+
+        self.prompt_tokens += read("prompt_tokens");
+        self.completion_tokens += read("completion_tokens");
+        self.total_tokens += read("total_tokens");
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AiResponse {
     pub success: bool,
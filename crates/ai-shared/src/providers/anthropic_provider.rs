@@ -0,0 +1,304 @@
+use crate::models::{ChatCompletion, Message, TokenUsage, ToolCall};
+use crate::providers::provider_traits::{
+    sse_data, ChatProvider, ProviderReply, StreamEvent, ToolCallDelta,
+};
+use crate::tools::ToolDefinition;
+use anyhow::{bail, Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::{json, Value};
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Anthropic messages API. The system prompt goes in its own field, and `max_tokens` is required.
+pub struct AnthropicProvider {
+    api_key: String,
+    max_tokens: u32,
+}
+
+impl AnthropicProvider {
+    pub const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+    pub fn new(api_key: String, max_tokens: u32) -> Self {
+        Self {
+            api_key,
+            max_tokens,
+        }
+    }
+}
+
+impl ChatProvider for AnthropicProvider {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-api-key",
+            HeaderValue::from_str(&self.api_key).context("Failed to build api key header")?,
+        );
+        headers.insert(
+            "anthropic-version",
+            HeaderValue::from_static(ANTHROPIC_VERSION),
+        );
+
+        Ok(headers)
+    }
+
+    fn build_body(
+        &self,
+        request: &ChatCompletion,
+        tools: &[ToolDefinition],
+        stream: bool,
+    ) -> Value {
+        let system: Vec<&str> = request
+            .messages
+            .iter()
+            .filter(|message| message.role == "system")
+            .map(|message| message.content.as_str())
+            .collect();
+
+        let mut body = json!({
+            "model": request.model,
+            "max_tokens": self.max_tokens,
+            "messages": to_api_messages(&request.messages),
+            "temperature": request.temperature,
+        });
+
+        if !system.is_empty() {
+            body["system"] = json!(system.join("\n\n"));
+        }
+
+        if !tools.is_empty() {
+            body["tools"] = tools
+                .iter()
+                .map(|tool| {
+                    json!({
+                        "name": tool.name,
+                        "description": tool.description,
+                        "input_schema": tool.parameters_schema(),
+                    })
+                })
+                .collect();
+        }
+
+        if stream {
+            body["stream"] = json!(true);
+        }
+
+        body
+    }
+
+    fn parse_response(&self, raw_text_response: &str) -> Result<ProviderReply> {
+        let response: Value =
+            serde_json::from_str(raw_text_response).context("Failed to parse response")?;
+
+        let blocks = response["content"]
+            .as_array()
+            .context("No response returned")?;
+
+        let content = blocks
+            .iter()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("");
+
+        let tool_calls = blocks
+            .iter()
+            .filter(|block| block["type"] == "tool_use")
+            .map(|block| ToolCall {
+                id: block["id"].as_str().unwrap_or_default().to_string(),
+                name: block["name"].as_str().unwrap_or_default().to_string(),
+                arguments: block["input"].to_string(),
+            })
+            .collect();
+
+        Ok(ProviderReply {
+            content,
+            tool_calls,
+            usage: read_usage(&response["usage"]),
+        })
+    }
+
+    fn parse_stream_line(&self, line: &str) -> Result<Vec<StreamEvent>> {
+        // The `event:` lines repeat the `type` of the data that follows them.
+        let Some(data) = sse_data(line) else {
+            return Ok(Vec::new());
+        };
+
+        let event: Value = serde_json::from_str(data).context("Failed to parse stream event")?;
+        let index = event["index"].as_u64().unwrap_or(0) as usize;
+
+        let parsed = match event["type"].as_str().unwrap_or_default() {
+            "content_block_start" if event["content_block"]["type"] == "tool_use" => {
+                Some(StreamEvent::ToolCall(ToolCallDelta {
+                    index,
+                    id: event["content_block"]["id"].as_str().map(str::to_string),
+                    name: event["content_block"]["name"].as_str().map(str::to_string),
+                    arguments: String::new(),
+                }))
+            }
+            "content_block_delta" => match event["delta"]["type"].as_str() {
+                Some("input_json_delta") => Some(StreamEvent::ToolCall(ToolCallDelta {
+                    index,
+                    arguments: event["delta"]["partial_json"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                    ..Default::default()
+                })),
+                _ => event["delta"]["text"]
+                    .as_str()
+                    .map(|text| StreamEvent::Token(text.to_string())),
+            },
+            "message_start" => Some(StreamEvent::Usage(read_usage(&event["message"]["usage"]))),
+            "message_delta" => Some(StreamEvent::Usage(read_usage(&event["usage"]))),
+            "message_stop" => Some(StreamEvent::Done),
+            "error" => bail!("Error while streaming: {}", event["error"]),
+            _ => None,
+        };
+
+        Ok(parsed.into_iter().collect())
+    }
+}
+
+/// Anthropic has no `tool` role: tool calls are content blocks of the assistant message, and their
+/// results go in the next user message (all of them in the same one).
+fn to_api_messages(messages: &[Message]) -> Vec<Value> {
+    let mut api_messages: Vec<Value> = Vec::new();
+
+    for message in messages.iter().filter(|message| message.role != "system") {
+        if message.role == "tool" {
+            let result = json!({
+                "type": "tool_result",
+                "tool_use_id": message.tool_call_id,
+                "content": message.content,
+            });
+
+            let previous_is_result = api_messages.last().is_some_and(|previous| {
+                previous["role"] == "user" && previous["content"][0]["type"] == "tool_result"
+            });
+
+            match api_messages.last_mut() {
+                Some(previous) if previous_is_result => {
+                    if let Some(content) = previous["content"].as_array_mut() {
+                        content.push(result);
+                    }
+                }
+                _ => api_messages.push(json!({ "role": "user", "content": [result] })),
+            }
+
+            continue;
+        }
+
+        if message.tool_calls.is_empty() {
+            api_messages.push(json!({ "role": message.role, "content": message.content }));
+            continue;
+        }
+
+        let mut content: Vec<Value> = Vec::new();
+        if !message.content.is_empty() {
+            content.push(json!({ "type": "text", "text": message.content }));
+        }
+        for call in &message.tool_calls {
+            let input: Value = serde_json::from_str(&call.arguments).unwrap_or_else(|_| json!({}));
+            content.push(json!({
+                "type": "tool_use",
+                "id": call.id,
+                "name": call.name,
+                "input": input,
+            }));
+        }
+
+        api_messages.push(json!({ "role": message.role, "content": content }));
+    }
+
+    api_messages
+}
+
+fn read_usage(usage: &Value) -> TokenUsage {
+    TokenUsage::new(
+        usage["input_tokens"].as_u64().unwrap_or(0),
+        usage["output_tokens"].as_u64().unwrap_or(0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_body_moves_system_message_and_tool_results() {
+        let provider = AnthropicProvider::new("key".to_string(), 1024);
+        let call = ToolCall {
+            id: "toolu_1".to_string(),
+            name: "get_current_date_time".to_string(),
+            arguments: "{}".to_string(),
+        };
+        let request = ChatCompletion {
+            model: "claude".to_string(),
+            messages: vec![
+                Message::new("system", "Be brief.".to_string()),
+                Message::new("user", "What time is it?".to_string()),
+                Message {
+                    tool_calls: vec![call.clone()],
+                    ..Message::new("assistant", String::new())
+                },
+                Message::tool_result(&call, "10:00".to_string()),
+            ],
+            temperature: 0.5,
+        };
+
+        let body = provider.build_body(&request, &[], true);
+
+        assert_eq!(body["system"], "Be brief.");
+        assert_eq!(body["max_tokens"], 1024);
+        assert_eq!(body["stream"], true);
+
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1]["content"][0]["type"], "tool_use");
+        assert_eq!(messages[2]["role"], "user");
+        assert_eq!(messages[2]["content"][0]["tool_use_id"], "toolu_1");
+    }
+
+    #[test]
+    fn test_parse_stream_lines() {
+        let provider = AnthropicProvider::new("key".to_string(), 1024);
+
+        assert!(provider
+            .parse_stream_line("event: content_block_delta")
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            provider
+                .parse_stream_line(
+                    r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#
+                )
+                .unwrap(),
+            vec![StreamEvent::Token("Hi".to_string())]
+        );
+        assert_eq!(
+            provider
+                .parse_stream_line(
+                    r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"pa"}}"#
+                )
+                .unwrap(),
+            vec![StreamEvent::ToolCall(ToolCallDelta {
+                index: 1,
+                arguments: "{\"pa".to_string(),
+                ..Default::default()
+            })]
+        );
+        assert_eq!(
+            provider
+                .parse_stream_line(r#"data: {"type":"message_delta","usage":{"output_tokens":7}}"#)
+                .unwrap(),
+            vec![StreamEvent::Usage(TokenUsage::new(0, 7))]
+        );
+        assert!(provider
+            .parse_stream_line(r#"data: {"type":"error","error":{"type":"overloaded_error"}}"#)
+            .is_err());
+    }
+}
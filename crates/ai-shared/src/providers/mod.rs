@@ -0,0 +1,7 @@
+pub mod anthropic_provider;
+
+pub mod ollama_provider;
+
+pub mod openai_provider;
+
+pub mod provider_traits;
@@ -0,0 +1,207 @@
+use crate::models::{ChatCompletion, Message, TokenUsage, ToolCall};
+use crate::providers::provider_traits::{ChatProvider, ProviderReply, StreamEvent, ToolCallDelta};
+use crate::tools::ToolDefinition;
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::{json, Value};
+
+/// Ollama's native chat API (`/api/chat`). Streams newline-delimited JSON instead of SSE.
+pub struct OllamaProvider {
+    /// Only needed when the server is behind a proxy that asks for one.
+    api_key: Option<String>,
+}
+
+impl OllamaProvider {
+    pub fn new(api_key: Option<String>) -> Self {
+        Self { api_key }
+    }
+}
+
+impl ChatProvider for OllamaProvider {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        if let Some(api_key) = &self.api_key {
+            headers.insert(
+                "authorization",
+                HeaderValue::from_str(&format!("Bearer {}", api_key))
+                    .context("Failed to build authorization header")?,
+            );
+        }
+
+        Ok(headers)
+    }
+
+    fn build_body(
+        &self,
+        request: &ChatCompletion,
+        tools: &[ToolDefinition],
+        stream: bool,
+    ) -> Value {
+        let messages: Vec<Value> = request.messages.iter().map(to_api_message).collect();
+
+        let mut body = json!({
+            "model": request.model,
+            "messages": messages,
+            "stream": stream,
+            "options": { "temperature": request.temperature },
+        });
+
+        if !tools.is_empty() {
+            body["tools"] = tools
+                .iter()
+                .map(|tool| {
+                    json!({
+                        "type": "function",
+                        "function": {
+                            "name": tool.name,
+                            "description": tool.description,
+                            "parameters": tool.parameters_schema(),
+                        },
+                    })
+                })
+                .collect();
+        }
+
+        body
+    }
+
+    fn parse_response(&self, raw_text_response: &str) -> Result<ProviderReply> {
+        let response: Value =
+            serde_json::from_str(raw_text_response).context("Failed to parse response")?;
+
+        let message = &response["message"];
+        if !message.is_object() {
+            anyhow::bail!("No response returned");
+        }
+
+        let tool_calls = message["tool_calls"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, call)| read_tool_call(index, call))
+            .collect();
+
+        Ok(ProviderReply {
+            content: message["content"].as_str().unwrap_or_default().to_string(),
+            tool_calls,
+            usage: read_usage(&response),
+        })
+    }
+
+    fn parse_stream_line(&self, line: &str) -> Result<Vec<StreamEvent>> {
+        let chunk: Value = serde_json::from_str(line).context("Failed to parse stream chunk")?;
+        let mut events = Vec::new();
+
+        if let Some(token) = chunk["message"]["content"].as_str() {
+            if !token.is_empty() {
+                events.push(StreamEvent::Token(token.to_string()));
+            }
+        }
+
+        // Tool calls aren't split in pieces: each one comes whole in a single chunk.
+        for (index, call) in chunk["message"]["tool_calls"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            let call = read_tool_call(index, call);
+            events.push(StreamEvent::ToolCall(ToolCallDelta {
+                index,
+                id: Some(call.id),
+                name: Some(call.name),
+                arguments: call.arguments,
+            }));
+        }
+
+        if chunk["done"].as_bool().unwrap_or(false) {
+            // Only the last chunk has the token counts.
+            events.push(StreamEvent::Usage(read_usage(&chunk)));
+            events.push(StreamEvent::Done);
+        }
+
+        Ok(events)
+    }
+}
+
+/// Same as OpenAI, except that the tool call arguments are an object instead of a string.
+fn to_api_message(message: &Message) -> Value {
+    let mut api_message = json!({ "role": message.role, "content": message.content });
+
+    if !message.tool_calls.is_empty() {
+        api_message["tool_calls"] = message
+            .tool_calls
+            .iter()
+            .map(|call| {
+                let arguments: Value =
+                    serde_json::from_str(&call.arguments).unwrap_or_else(|_| json!({}));
+                json!({ "function": { "name": call.name, "arguments": arguments } })
+            })
+            .collect();
+    }
+
+    api_message
+}
+
+/// Ollama doesn't give the calls an id, so the position is used.
+fn read_tool_call(index: usize, call: &Value) -> ToolCall {
+    ToolCall {
+        id: format!("call_{}", index),
+        name: call["function"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        arguments: call["function"]["arguments"].to_string(),
+    }
+}
+
+fn read_usage(response: &Value) -> TokenUsage {
+    TokenUsage::new(
+        response["prompt_eval_count"].as_u64().unwrap_or(0),
+        response["eval_count"].as_u64().unwrap_or(0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_lines() {
+        let provider = OllamaProvider::new(None);
+
+        assert_eq!(
+            provider
+                .parse_stream_line(
+                    r#"{"message":{"role":"assistant","content":"Hi"},"done":false}"#
+                )
+                .unwrap(),
+            vec![StreamEvent::Token("Hi".to_string())]
+        );
+        assert_eq!(
+            provider
+                .parse_stream_line(r#"{"message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":12,"eval_count":4}"#)
+                .unwrap(),
+            vec![
+                StreamEvent::Usage(TokenUsage::new(12, 4)),
+                StreamEvent::Done
+            ]
+        );
+        assert_eq!(
+            provider
+                .parse_stream_line(r#"{"message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"list_directory","arguments":{"path":"."}}}]},"done":false}"#)
+                .unwrap(),
+            vec![StreamEvent::ToolCall(ToolCallDelta {
+                index: 0,
+                id: Some("call_0".to_string()),
+                name: Some("list_directory".to_string()),
+                arguments: r#"{"path":"."}"#.to_string(),
+            })]
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crate::models::{ChatCompletion, Message, TokenUsage, ToolCall};
+use crate::providers::provider_traits::{
+    sse_data, ChatProvider, ProviderReply, StreamEvent, ToolCallDelta,
+};
+use crate::tools::ToolDefinition;
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::{json, Value};
+
+/// OpenAI chat completions, and the servers that mimic it (OpenRouter, OpenWebUI, ...).
+pub struct OpenAiProvider {
+    api_key: String,
+    api_org: Option<String>,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: String, api_org: Option<String>) -> Self {
+        Self { api_key, api_org }
+    }
+}
+
+impl ChatProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "authorization",
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key))
+                .context("Failed to build authorization header")?,
+        );
+
+        if let Some(org) = &self.api_org {
+            headers.insert(
+                "OpenAI-Organization",
+                HeaderValue::from_str(org).context("Failed to build organization header")?,
+            );
+        }
+
+        Ok(headers)
+    }
+
+    fn build_body(
+        &self,
+        request: &ChatCompletion,
+        tools: &[ToolDefinition],
+        stream: bool,
+    ) -> Value {
+        let messages: Vec<Value> = request.messages.iter().map(to_api_message).collect();
+
+        let mut body = json!({
+            "model": request.model,
+            "messages": messages,
+            "temperature": request.temperature,
+        });
+
+        if !tools.is_empty() {
+            body["tools"] = tools.iter().map(to_api_tool).collect();
+        }
+
+        if stream {
+            body["stream"] = json!(true);
+            // Without this, the token usage isn't sent when streaming.
+            body["stream_options"] = json!({ "include_usage": true });
+        }
+
+        body
+    }
+
+    fn parse_response(&self, raw_text_response: &str) -> Result<ProviderReply> {
+        let response: Value =
+            serde_json::from_str(raw_text_response).context("Failed to parse response")?;
+
+        let message = response["choices"]
+            .get(0)
+            .map(|choice| &choice["message"])
+            .context("No response returned")?;
+
+        let tool_calls = message["tool_calls"]
+            .as_array()
+            .map(|calls| calls.iter().map(read_tool_call).collect())
+            .unwrap_or_default();
+
+        Ok(ProviderReply {
+            content: message["content"].as_str().unwrap_or_default().to_string(),
+            tool_calls,
+            usage: read_usage(&response["usage"]),
+        })
+    }
+
+    fn parse_stream_line(&self, line: &str) -> Result<Vec<StreamEvent>> {
+        let Some(data) = sse_data(line) else {
+            return Ok(Vec::new());
+        };
+
+        if data == "[DONE]" {
+            return Ok(vec![StreamEvent::Done]);
+        }
+
+        let chunk: Value = serde_json::from_str(data).context("Failed to parse stream chunk")?;
+        let delta = &chunk["choices"][0]["delta"];
+        let mut events = Vec::new();
+
+        if let Some(token) = delta["content"].as_str() {
+            events.push(StreamEvent::Token(token.to_string()));
+        }
+
+        for call in delta["tool_calls"].as_array().into_iter().flatten() {
+            events.push(StreamEvent::ToolCall(ToolCallDelta {
+                index: call["index"].as_u64().unwrap_or(0) as usize,
+                id: call["id"].as_str().map(str::to_string),
+                name: call["function"]["name"].as_str().map(str::to_string),
+                arguments: call["function"]["arguments"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            }));
+        }
+
+        if chunk["usage"].is_object() {
+            events.push(StreamEvent::Usage(read_usage(&chunk["usage"])));
+        }
+
+        Ok(events)
+    }
+}
+
+fn to_api_message(message: &Message) -> Value {
+    let mut api_message = json!({ "role": message.role, "content": message.content });
+
+    if !message.tool_calls.is_empty() {
+        api_message["tool_calls"] = message
+            .tool_calls
+            .iter()
+            .map(|call| {
+                json!({
+                    "id": call.id,
+                    "type": "function",
+                    "function": { "name": call.name, "arguments": call.arguments },
+                })
+            })
+            .collect();
+    }
+
+    if let Some(id) = &message.tool_call_id {
+        api_message["tool_call_id"] = json!(id);
+    }
+
+    api_message
+}
+
+fn to_api_tool(tool: &ToolDefinition) -> Value {
+    json!({
+        "type": "function",
+        "function": {
+            "name": tool.name,
+            "description": tool.description,
+            "parameters": tool.parameters_schema(),
+        },
+    })
+}
+
+fn read_tool_call(call: &Value) -> ToolCall {
+    ToolCall {
+        id: call["id"].as_str().unwrap_or_default().to_string(),
+        name: call["function"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        arguments: call["function"]["arguments"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// Missing or non-numeric fields are ignored, since not every provider reports all of them.
+fn read_usage(usage: &Value) -> TokenUsage {
+    let read = |key: &str| usage[key].as_u64().unwrap_or(0);
+
+    TokenUsage {
+        prompt_tokens: read("prompt_tokens"),
+        completion_tokens: read("completion_tokens"),
+        total_tokens: read("total_tokens"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_lines() {
+        let provider = OpenAiProvider::new("key".to_string(), None);
+
+        let token = provider
+            .parse_stream_line(r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#)
+            .unwrap();
+        assert_eq!(token, vec![StreamEvent::Token("Hel".to_string())]);
+
+        let usage = provider
+            .parse_stream_line(
+                r#"data: {"choices":[],"usage":{"prompt_tokens":3,"completion_tokens":2,"total_tokens":5}}"#,
+            )
+            .unwrap();
+        assert_eq!(usage, vec![StreamEvent::Usage(TokenUsage::new(3, 2))]);
+
+        assert_eq!(
+            provider.parse_stream_line("data: [DONE]").unwrap(),
+            vec![StreamEvent::Done]
+        );
+        assert!(provider
+            .parse_stream_line(": keep-alive")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_response_with_tool_calls() {
+        let provider = OpenAiProvider::new("key".to_string(), None);
+
+        let reply = provider
+            .parse_response(
+                r#"{"choices":[{"message":{"role":"assistant","content":null,"tool_calls":[
+                    {"id":"call_1","type":"function","function":{"name":"get_current_date_time","arguments":"{}"}}
+                ]}}]}"#,
+            )
+            .unwrap();
+
+        assert_eq!(reply.content, "");
+        assert_eq!(reply.tool_calls[0].name, "get_current_date_time");
+        assert_eq!(reply.tool_calls[0].id, "call_1");
+    }
+}
@@ -0,0 +1,120 @@
+use crate::models::{ChatCompletion, TokenUsage, ToolCall};
+use crate::tools::ToolDefinition;
+use anyhow::Result;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+/// A reply parsed from a (non-streamed) response.
+#[derive(Debug, Default)]
+pub struct ProviderReply {
+    pub content: String,
+    pub tool_calls: Vec<ToolCall>,
+    pub usage: TokenUsage,
+}
+
+/// A piece of a tool call, as streamed. The pieces with the same `index` make up one call.
+#[derive(Debug, Default, PartialEq)]
+pub struct ToolCallDelta {
+    pub index: usize,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    /// Fragment of the JSON arguments.
+    pub arguments: String,
+}
+
+/// Something found in one line of a streamed response.
+#[derive(Debug, PartialEq)]
+pub enum StreamEvent {
+    Token(String),
+    ToolCall(ToolCallDelta),
+    Usage(TokenUsage),
+    Done,
+}
+
+/// The API shape of an AI provider: how requests are authenticated and built, and how the
+/// responses are read. The requester does the HTTP and keeps the history.
+pub trait ChatProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn headers(&self) -> Result<HeaderMap>;
+    fn build_body(&self, request: &ChatCompletion, tools: &[ToolDefinition], stream: bool)
+        -> Value;
+    fn parse_response(&self, raw_text_response: &str) -> Result<ProviderReply>;
+    /// Parses one line of a streamed response (SSE or newline-delimited JSON). Lines that don't
+    /// carry anything (keep-alives, event names) return no events.
+    fn parse_stream_line(&self, line: &str) -> Result<Vec<StreamEvent>>;
+}
+
+/// The payload of a server-sent events `data:` line.
+pub(crate) fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(str::trim)
+}
+
+/// Joins the streamed pieces of the tool calls into the calls.
+pub(crate) fn collect_tool_calls(deltas: Vec<ToolCallDelta>) -> Vec<ToolCall> {
+    let mut calls: Vec<(usize, ToolCall)> = Vec::new();
+
+    for delta in deltas {
+        let position = match calls.iter().position(|(index, _)| *index == delta.index) {
+            Some(position) => position,
+            None => {
+                let id = format!("call_{}", delta.index);
+                calls.push((
+                    delta.index,
+                    ToolCall {
+                        id,
+                        name: String::new(),
+                        arguments: String::new(),
+                    },
+                ));
+                calls.len() - 1
+            }
+        };
+
+        let call = &mut calls[position].1;
+        if let Some(id) = delta.id {
+            call.id = id;
+        }
+        if let Some(name) = delta.name {
+            call.name.push_str(&name);
+        }
+        call.arguments.push_str(&delta.arguments);
+    }
+
+    calls.into_iter().map(|(_, call)| call).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_tool_calls_joins_fragments() {
+        let deltas = vec![
+            ToolCallDelta {
+                index: 0,
+                id: Some("call_abc".to_string()),
+                name: Some("list_directory".to_string()),
+                arguments: String::new(),
+            },
+            ToolCallDelta {
+                index: 0,
+                arguments: r#"{"path":"#.to_string(),
+                ..Default::default()
+            },
+            ToolCallDelta {
+                index: 0,
+                arguments: r#""src"}"#.to_string(),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            collect_tool_calls(deltas),
+            vec![ToolCall {
+                id: "call_abc".to_string(),
+                name: "list_directory".to_string(),
+                arguments: r#"{"path":"src"}"#.to_string(),
+            }]
+        );
+    }
+}
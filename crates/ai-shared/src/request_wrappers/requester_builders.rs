@@ -1,17 +1,34 @@
+use crate::providers::anthropic_provider::AnthropicProvider;
+use crate::providers::ollama_provider::OllamaProvider;
 use crate::request_wrappers::requester_implementations::OpenAiRequester;
 use crate::request_wrappers::requester_traits::OpenAiRequesterTraits;
 use anyhow::{Context, Result};
 use std::env;
 
+const DEFAULT_ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const DEFAULT_OLLAMA_API_URL: &str = "http://localhost:11434/api/chat";
+
+/// Platforms accepted by `AI_PLATFORM`.
+pub const AI_PLATFORMS: &[&str] = &["openai", "local", "openrouter", "anthropic", "ollama"];
+
 pub fn build_requester_for_ai() -> Result<OpenAiRequester> {
     let ai_platform = env::var("AI_PLATFORM").context("AI_PLATFORM must be set")?;
 
+    build_requester_for_platform(&ai_platform)
+}
+
+/// Builds the requester for the platform (one of [`AI_PLATFORMS`]), configured by its
+/// environment variables.
+pub fn build_requester_for_platform(ai_platform: &str) -> Result<OpenAiRequester> {
     match ai_platform.to_lowercase().trim() {
         "openai" => build_requester_for_openai(),
         "local" => build_requester_for_openwebui(),
         "openrouter" => build_requester_for_open_router(),
+        "anthropic" => build_requester_for_anthropic(),
+        "ollama" => build_requester_for_ollama(),
         _ => Err(anyhow::anyhow!(
-            "AI_PLATFORM must be set to one of: openai, openwebui, openrouter"
+            "AI_PLATFORM must be set to one of: {}",
+            AI_PLATFORMS.join(", ")
         )),
     }
 }
@@ -109,3 +126,75 @@ fn build_requester_for_open_router() -> Result<OpenAiRequester> {
 
     Ok(requester)
 }
+
+fn build_requester_for_anthropic() -> Result<OpenAiRequester> {
+    let request_history_path = env::var("ANTHROPIC_CHAT_REQUEST_HISTORY_PATH").ok();
+
+    let api_key = env::var("ANTHROPIC_API_KEY").context("ANTHROPIC_API_KEY must be set")?;
+
+    let ai_model = env::var("ANTHROPIC_MODEL").context("ANTHROPIC_MODEL must be set")?;
+
+    let api_url =
+        env::var("ANTHROPIC_API_URL").unwrap_or_else(|_| DEFAULT_ANTHROPIC_API_URL.to_string());
+
+    let temperature = match env::var("ANTHROPIC_TEMPERATURE") {
+        Ok(temperature) => Some(
+            temperature
+                .parse::<f32>()
+                .context("ANTHROPIC_TEMPERATURE must be a float")?,
+        ),
+        Err(_) => None,
+    };
+
+    let max_tokens = match env::var("ANTHROPIC_MAX_TOKENS") {
+        Ok(max_tokens) => max_tokens
+            .parse::<u32>()
+            .context("ANTHROPIC_MAX_TOKENS must be a positive integer")?,
+        Err(_) => AnthropicProvider::DEFAULT_MAX_TOKENS,
+    };
+
+    let mut requester = OpenAiRequester::with_provider(
+        api_url,
+        Box::new(AnthropicProvider::new(api_key, max_tokens)),
+        temperature,
+        request_history_path,
+    )?;
+
+    requester
+        .set_model(ai_model.as_str())?
+        .initialize_api_client()?;
+
+    Ok(requester)
+}
+
+fn build_requester_for_ollama() -> Result<OpenAiRequester> {
+    let request_history_path = env::var("OLLAMA_CHAT_REQUEST_HISTORY_PATH").ok();
+
+    let api_key = env::var("OLLAMA_API_KEY").ok();
+
+    let ai_model = env::var("OLLAMA_MODEL").context("OLLAMA_MODEL must be set")?;
+
+    let api_url = env::var("OLLAMA_API_URL").unwrap_or_else(|_| DEFAULT_OLLAMA_API_URL.to_string());
+
+    let temperature = match env::var("OLLAMA_TEMPERATURE") {
+        Ok(temperature) => Some(
+            temperature
+                .parse::<f32>()
+                .context("OLLAMA_TEMPERATURE must be a float")?,
+        ),
+        Err(_) => None,
+    };
+
+    let mut requester = OpenAiRequester::with_provider(
+        api_url,
+        Box::new(OllamaProvider::new(api_key)),
+        temperature,
+        request_history_path,
+    )?;
+
+    requester
+        .set_model(ai_model.as_str())?
+        .initialize_api_client()?;
+
+    Ok(requester)
+}
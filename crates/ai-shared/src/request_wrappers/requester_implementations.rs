@@ -1,15 +1,23 @@
-use crate::models::{AiResponse, ApiResponse, ChatCompletion, Message};
+use crate::models::{AiResponse, ChatCompletion, Message, TokenUsage};
+use crate::providers::openai_provider::OpenAiProvider;
+use crate::providers::provider_traits::{
+    collect_tool_calls, ChatProvider, ProviderReply, StreamEvent, ToolCallDelta,
+};
 use crate::request_wrappers::requester_traits::{MessageVecExt, OpenAiRequesterTraits};
+use crate::tools::{execute_tool_call, ToolDefinition};
+use crate::utils::context_window::trim_to_context_window;
 use crate::utils::request_loggers::RequestLogger;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::header::HeaderMap;
 use reqwest::{Client, Response, StatusCode};
+use serde_json::Value;
 
+/// Sends chat requests and keeps the conversation history. The API shape comes from the
+/// provider (OpenAI-compatible by default).
 pub struct OpenAiRequester {
     api_url: String,
-    api_key: String,
-    api_org: Option<String>,
+    provider: Box<dyn ChatProvider>,
     headers: HeaderMap,
     current_model: String,
     system_message: Option<Message>,
@@ -17,12 +25,17 @@ pub struct OpenAiRequester {
     temperature: f32,
     request_logger: RequestLogger,
     api_client: Option<Client>,
+    token_usage: TokenUsage,
+    context_window: Option<usize>,
+    tools: Vec<ToolDefinition>,
 }
 
 impl OpenAiRequester {
     const USER_ROLE: &'static str = "user";
     const SYSTEM_ROLE: &'static str = "system";
     const ASSISTANT_ROLE: &'static str = "assistant";
+    /// Requests in a row where the AI may call tools, before giving up on a reply.
+    const MAX_TOOL_ROUNDS: usize = 8;
 
     pub fn new(
         api_url: String,
@@ -30,14 +43,28 @@ impl OpenAiRequester {
         api_org: Option<String>,
         temperature: Option<f32>,
         request_history_path: Option<String>,
+    ) -> Result<Self> {
+        Self::with_provider(
+            api_url,
+            Box::new(OpenAiProvider::new(api_key, api_org)),
+            temperature,
+            request_history_path,
+        )
+    }
+
+    /// Requester for a provider other than OpenAI-compatible (e.g.: Anthropic, Ollama).
+    pub fn with_provider(
+        api_url: String,
+        provider: Box<dyn ChatProvider>,
+        temperature: Option<f32>,
+        request_history_path: Option<String>,
     ) -> Result<Self> {
         let resolved_request_history_path =
             request_history_path.unwrap_or_else(|| ".request_history".to_string());
 
         Ok(Self {
             api_url,
-            api_key,
-            api_org,
+            provider,
             headers: Default::default(),
             current_model: "".to_string(),
             system_message: None,
@@ -45,34 +72,33 @@ impl OpenAiRequester {
             temperature: temperature.unwrap_or(1.0),
             request_logger: RequestLogger::new(resolved_request_history_path)?,
             api_client: None,
+            token_usage: TokenUsage::default(),
+            context_window: None,
+            tools: Vec::new(),
         })
     }
 
-    fn build_request_payload(&mut self, new_message: String, use_history: bool) -> Vec<Message> {
-        let new_user_message = Message {
-            role: Self::USER_ROLE.to_string(),
-            content: new_message,
-        };
-
+    /// Messages sent in a request: the history (trimmed to the context window), or only the
+    /// system message and the messages of the current turn.
+    fn build_request_payload(&mut self, turn: &[Message], use_history: bool) -> Vec<Message> {
         if use_history {
             self.ensure_first_message_is_from_system_if_available();
 
-            let mut full_history = self.message_history.clone();
+            // The messages of the turn are already in the history.
+            let full_history = self.message_history.clone();
 
-            full_history.push(new_user_message);
+            if let Some(max_tokens) = self.context_window {
+                let (trimmed, _) = trim_to_context_window(&full_history, max_tokens);
+                return trimmed;
+            }
 
             return full_history;
         };
 
-        match &self.system_message {
-            Some(system_message) => {
-                vec![system_message.clone(), new_user_message]
-            }
-            None => {
-                // Adding the system message with instructions is always a good idea.
-                vec![new_user_message]
-            }
-        }
+        // Adding the system message with instructions is always a good idea.
+        let mut payload: Vec<Message> = self.system_message.iter().cloned().collect();
+        payload.extend_from_slice(turn);
+        payload
     }
 
     fn ensure_first_message_is_from_system_if_available(&mut self) {
@@ -85,16 +111,6 @@ impl OpenAiRequester {
         }
     }
 
-    fn save_user_request_to_message_history(&mut self, payload: &[Message]) -> Result<()> {
-        if payload.is_empty() {
-            anyhow::bail!("Payload cannot be empty.");
-        }
-
-        self.message_history.push(payload.last().unwrap().clone());
-
-        Ok(())
-    }
-
     fn build_openai_request(&mut self, payload: Vec<Message>) -> ChatCompletion {
         let temperature_str = format!("{:.1}", self.temperature);
         let temp_float = temperature_str.parse::<f32>().unwrap();
@@ -117,10 +133,7 @@ impl OpenAiRequester {
         Ok(())
     }
 
-    async fn send_api_request(
-        &mut self,
-        chat_completion_request: &ChatCompletion,
-    ) -> Result<(Response, StatusCode, bool)> {
+    async fn send_api_request(&mut self, body: &Value) -> Result<(Response, StatusCode, bool)> {
         let api_client = match &self.api_client {
             Some(api_client) => api_client,
             None => {
@@ -130,7 +143,7 @@ impl OpenAiRequester {
 
         let api_response = api_client
             .post(&self.api_url)
-            .json(&chat_completion_request)
+            .json(body)
             .send()
             .await
             .context("Failed to send request")?;
@@ -146,20 +159,170 @@ impl OpenAiRequester {
             .context("Failed to parse error response")
     }
 
-    fn extract_ai_response_from_text(raw_text_response: &str) -> Result<Message> {
-        let api_response_obj: ApiResponse =
-            serde_json::from_str(raw_text_response).context("Failed to parse response")?;
+    /// Builds the request body and logs it.
+    fn build_request_body(
+        &mut self,
+        turn: &[Message],
+        use_history: bool,
+        stream: bool,
+    ) -> Result<Value> {
+        let payload = self.build_request_payload(turn, use_history);
+
+        let chat_completion_request = self.build_openai_request(payload);
 
-        let ai_response = api_response_obj
-            .choices
-            .first()
-            .map(|choice| choice.message.content.clone())
-            .context("No response returned")?;
+        let body = self
+            .provider
+            .build_body(&chat_completion_request, &self.tools, stream);
 
-        Ok(Message {
-            role: Self::ASSISTANT_ROLE.to_string(),
-            content: ai_response.clone(),
-        })
+        self.request_logger.set_request_timestamp_local();
+
+        self.request_logger.save_request(&body)?;
+
+        Ok(body)
+    }
+
+    /// Sends the request and reads the reply. Failed requests are never streamed, so their error
+    /// is read the same way in both cases.
+    async fn request_reply<F: FnMut(&str) + Send + ?Sized>(
+        &mut self,
+        body: &Value,
+        stream: bool,
+        on_token: &mut F,
+    ) -> Result<ProviderReply> {
+        let (api_response, status_code, success) = self.send_api_request(body).await?;
+
+        if stream && success {
+            let (reply, raw_response) = self.read_stream(api_response, on_token).await?;
+
+            self.request_logger
+                .save_response(&raw_response, status_code.as_u16())?;
+
+            return Ok(reply);
+        }
+
+        // Instead of immediately parsing the response, I'm getting the text so we know what went
+        // wrong in case of failure.
+        let raw_text_response = Self::extract_response_text(api_response).await?;
+
+        self.request_logger
+            .save_response(&raw_text_response, status_code.as_u16())?;
+
+        if !success {
+            anyhow::bail!("Error [{}]: {}", status_code, raw_text_response);
+        }
+
+        let reply = self.provider.parse_response(&raw_text_response)?;
+
+        self.token_usage.add(&reply.usage);
+
+        Ok(reply)
+    }
+
+    /// Sends the message and returns the AI reply. When the AI calls tools, they are run and
+    /// their results are sent back, until the AI replies without calling any.
+    async fn send_turn<F: FnMut(&str) + Send + ?Sized>(
+        &mut self,
+        new_message: String,
+        use_history: bool,
+        stream: bool,
+        on_token: &mut F,
+    ) -> Result<AiResponse> {
+        let user_message = Message::new(Self::USER_ROLE, new_message);
+        self.message_history.push(user_message.clone());
+
+        let mut turn = vec![user_message];
+
+        for _ in 0..=Self::MAX_TOOL_ROUNDS {
+            let body = self.build_request_body(&turn, use_history, stream)?;
+
+            let reply = self.request_reply(&body, stream, on_token).await?;
+
+            let ai_response = Message {
+                tool_calls: reply.tool_calls,
+                ..Message::new(Self::ASSISTANT_ROLE, reply.content)
+            };
+
+            self.save_ai_response_to_message_history(&ai_response);
+
+            if ai_response.tool_calls.is_empty() {
+                return Ok(AiResponse {
+                    success: true,
+                    message: ai_response
+                        .content
+                        .trim_end_matches(&['\n', '\r'][..])
+                        .to_string(),
+                });
+            }
+
+            let tool_calls = ai_response.tool_calls.clone();
+            turn.push(ai_response);
+
+            for call in &tool_calls {
+                let output = execute_tool_call(&self.tools, call);
+                let tool_result = Message::tool_result(call, output);
+
+                self.message_history.push(tool_result.clone());
+                turn.push(tool_result);
+            }
+        }
+
+        anyhow::bail!(
+            "The AI was still calling tools after {} rounds.",
+            Self::MAX_TOOL_ROUNDS
+        )
+    }
+
+    /// Reads the streamed response line by line, calling `on_token` for every piece of text.
+    /// Returns the reply, and the raw response (for the request log).
+    async fn read_stream<F: FnMut(&str) + Send + ?Sized>(
+        &mut self,
+        mut api_response: Response,
+        on_token: &mut F,
+    ) -> Result<(ProviderReply, String)> {
+        let mut raw_response = String::new();
+        let mut reply = ProviderReply::default();
+        let mut tool_call_deltas: Vec<ToolCallDelta> = Vec::new();
+        let mut pending: Vec<u8> = Vec::new();
+
+        'reading: while let Some(chunk) = api_response
+            .chunk()
+            .await
+            .context("Failed to read streamed response")?
+        {
+            pending.extend_from_slice(&chunk);
+
+            // Lines are only parsed once complete, since a chunk can end anywhere (even in the
+            // middle of a character).
+            while let Some(end) = pending.iter().position(|byte| *byte == b'\n') {
+                let line_bytes: Vec<u8> = pending.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line_bytes);
+                raw_response.push_str(&line);
+
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+
+                for event in self.provider.parse_stream_line(line)? {
+                    match event {
+                        StreamEvent::Token(token) => {
+                            on_token(&token);
+                            reply.content.push_str(&token);
+                        }
+                        StreamEvent::ToolCall(delta) => tool_call_deltas.push(delta),
+                        StreamEvent::Usage(usage) => {
+                            self.token_usage.add(&usage);
+                            reply.usage.add(&usage);
+                        }
+                        StreamEvent::Done => break 'reading,
+                    }
+                }
+            }
+        }
+
+        reply.tool_calls = collect_tool_calls(tool_call_deltas);
+
+        Ok((reply, raw_response))
     }
 
     fn save_ai_response_to_message_history(&mut self, ai_response: &Message) {
@@ -167,21 +330,7 @@ impl OpenAiRequester {
     }
 
     fn build_headers(&mut self) -> Result<()> {
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            "authorization",
-            HeaderValue::from_str(&format!("Bearer {}", self.api_key))
-                .context("Failed to build authorization header")?,
-        );
-
-        if let Some(org) = &self.api_org {
-            headers.insert(
-                "OpenAI-Organization",
-                HeaderValue::from_str(org).context("Failed to build organization header")?,
-            );
-        }
-
-        self.headers = headers;
+        self.headers = self.provider.headers()?;
 
         Ok(())
     }
@@ -218,49 +367,64 @@ impl OpenAiRequesterTraits for OpenAiRequester {
             anyhow::bail!("System message cannot be empty.");
         }
 
-        self.system_message = Some(Message {
-            role: Self::SYSTEM_ROLE.to_string(),
-            content: system_message,
-        });
+        self.system_message = Some(Message::new(Self::SYSTEM_ROLE, system_message));
 
         Ok(self)
     }
 
-    async fn send_request(&mut self, new_message: String, use_history: bool) -> Result<AiResponse> {
-        let payload = self.build_request_payload(new_message, use_history);
+    fn get_model(&self) -> &str {
+        &self.current_model
+    }
 
-        self.save_user_request_to_message_history(&payload)?;
+    fn get_provider_name(&self) -> &str {
+        self.provider.name()
+    }
 
-        let chat_completion_request = self.build_openai_request(payload);
+    fn set_tools(&mut self, tools: Vec<ToolDefinition>) -> Result<&mut Self> {
+        self.tools = tools;
 
-        self.request_logger.set_request_timestamp_local();
+        Ok(self)
+    }
 
-        self.request_logger.save_request(&chat_completion_request)?;
+    fn get_token_usage(&self) -> TokenUsage {
+        self.token_usage
+    }
 
-        let (api_response, status_code, success) =
-            self.send_api_request(&chat_completion_request).await?;
+    fn set_context_window(&mut self, max_tokens: usize) -> Result<&mut Self> {
+        if max_tokens == 0 {
+            anyhow::bail!("Context window must be greater than zero.");
+        }
 
-        // Instead of immediately parsing the response, I'm getting the text so we know what went
-        // wrong in case of failure.
-        let raw_text_response = Self::extract_response_text(api_response).await?;
+        self.context_window = Some(max_tokens);
 
-        self.request_logger
-            .save_response(&raw_text_response, status_code.as_u16())?;
+        Ok(self)
+    }
 
-        if !success {
-            anyhow::bail!("Error [{}]: {}", status_code, raw_text_response);
-        }
+    fn get_message_history(&self) -> &[Message] {
+        &self.message_history
+    }
 
-        let ai_response = Self::extract_ai_response_from_text(&raw_text_response)?;
+    fn set_message_history(&mut self, history: Vec<Message>) -> Result<&mut Self> {
+        self.message_history = history
+            .into_iter()
+            .filter(|message| message.role != Self::SYSTEM_ROLE)
+            .collect();
 
-        self.save_ai_response_to_message_history(&ai_response);
+        Ok(self)
+    }
 
-        Ok(AiResponse {
-            success,
-            message: ai_response
-                .content
-                .trim_end_matches(&['\n', '\r'][..])
-                .to_string(),
-        })
+    async fn send_request(&mut self, new_message: String, use_history: bool) -> Result<AiResponse> {
+        self.send_turn(new_message, use_history, false, &mut |_: &str| {})
+            .await
+    }
+
+    async fn send_request_streaming(
+        &mut self,
+        new_message: String,
+        use_history: bool,
+        on_token: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<AiResponse> {
+        self.send_turn(new_message, use_history, true, on_token)
+            .await
     }
 }
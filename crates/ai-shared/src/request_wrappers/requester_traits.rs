@@ -1,4 +1,5 @@
-use crate::models::{AiResponse, Message};
+use crate::models::{AiResponse, Message, TokenUsage};
+use crate::tools::ToolDefinition;
 use anyhow::Result;
 use async_trait::async_trait;
 
@@ -18,5 +19,26 @@ pub trait OpenAiRequesterTraits {
     fn set_temperature(&mut self, temperature: &f32) -> Result<&mut Self>;
     fn initialize_api_client(&mut self) -> Result<&mut Self>;
     fn set_system_message(&mut self, system_message: String) -> Result<&mut Self>;
+    fn get_model(&self) -> &str;
+    fn get_provider_name(&self) -> &str;
+    /// Local functions the AI may call (see `#[ai_function(tool)]`). They are run when the AI asks,
+    /// and their output is sent back before the reply is returned.
+    fn set_tools(&mut self, tools: Vec<ToolDefinition>) -> Result<&mut Self>;
+    fn get_token_usage(&self) -> TokenUsage;
+    /// Limits the (estimated) tokens of the history sent with each request. The oldest messages
+    /// are left out of the request when the history grows past it; the history itself is kept.
+    fn set_context_window(&mut self, max_tokens: usize) -> Result<&mut Self>;
+    fn get_message_history(&self) -> &[Message];
+    /// Replaces the history (e.g.: when resuming a conversation). System messages are dropped,
+    /// since the current system message is the one used.
+    fn set_message_history(&mut self, history: Vec<Message>) -> Result<&mut Self>;
     async fn send_request(&mut self, new_message: String, use_history: bool) -> Result<AiResponse>;
+    /// Same as `send_request`, but the reply is streamed: `on_token` gets each piece of text as
+    /// it arrives.
+    async fn send_request_streaming(
+        &mut self,
+        new_message: String,
+        use_history: bool,
+        on_token: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<AiResponse>;
 }
@@ -0,0 +1,187 @@
+use crate::models::ToolCall;
+use anyhow::{anyhow, Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::fmt::Display;
+
+/// Arguments of a tool call: the JSON object sent by the model.
+pub type ToolArguments = Value;
+
+/// What a tool returns to the model (errors are sent back as text, so the model can recover).
+pub type ToolCallResult = Result<String>;
+
+/// A parameter of a tool, as described to the model.
+#[derive(Debug, Clone, Copy)]
+pub struct ToolParameter {
+    pub name: &'static str,
+    /// JSON schema type: `string`, `integer`, `number`, `boolean`, `array` or `object`.
+    pub kind: &'static str,
+    pub required: bool,
+}
+
+/// A local function the model can call. Built by `#[ai_function(tool)]` (the `<name>_tool()`
+/// helper it generates).
+#[derive(Clone, Copy)]
+pub struct ToolDefinition {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub parameters: &'static [ToolParameter],
+    pub call: fn(&ToolArguments) -> ToolCallResult,
+}
+
+impl ToolDefinition {
+    /// JSON schema of the parameters, the way every provider expects it.
+    pub fn parameters_schema(&self) -> Value {
+        let properties: Map<String, Value> = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter.name.to_string(),
+                    json!({ "type": parameter.kind }),
+                )
+            })
+            .collect();
+
+        let required: Vec<&str> = self
+            .parameters
+            .iter()
+            .filter(|parameter| parameter.required)
+            .map(|parameter| parameter.name)
+            .collect();
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+}
+
+/// Runs the tool the call asks for. Unknown tools, bad arguments and failures become the text
+/// returned to the model.
+pub fn execute_tool_call(tools: &[ToolDefinition], call: &ToolCall) -> String {
+    let Some(tool) = tools.iter().find(|tool| tool.name == call.name) else {
+        return format!("Error: there is no tool named '{}'.", call.name);
+    };
+
+    let arguments: Value = if call.arguments.trim().is_empty() {
+        json!({})
+    } else {
+        match serde_json::from_str(&call.arguments) {
+            Ok(arguments) => arguments,
+            Err(e) => return format!("Error: the arguments are not valid JSON ({}).", e),
+        }
+    };
+
+    match (tool.call)(&arguments) {
+        Ok(output) => output,
+        Err(e) => format!("Error: {:#}", e),
+    }
+}
+
+/// Reads a required argument. Used by the code `#[ai_function(tool)]` generates.
+pub fn parse_arg<T: DeserializeOwned>(arguments: &ToolArguments, name: &str) -> Result<T> {
+    let value = arguments
+        .get(name)
+        .filter(|value| !value.is_null())
+        .with_context(|| format!("Missing argument '{}'", name))?;
+
+    serde_json::from_value(value.clone()).with_context(|| format!("Invalid argument '{}'", name))
+}
+
+/// Reads an optional argument. Used by the code `#[ai_function(tool)]` generates.
+pub fn parse_optional_arg<T: DeserializeOwned>(
+    arguments: &ToolArguments,
+    name: &str,
+) -> Result<Option<T>> {
+    match arguments.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(_) => parse_arg(arguments, name).map(Some),
+    }
+}
+
+/// Converts what a tool function returns into the text sent back to the model.
+pub trait IntoToolOutput {
+    fn into_tool_output(self) -> ToolCallResult;
+}
+
+macro_rules! impl_into_tool_output_with_to_string {
+    ($($t:ty),*) => {
+        $(impl IntoToolOutput for $t {
+            fn into_tool_output(self) -> ToolCallResult {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+impl_into_tool_output_with_to_string!(
+    String, &str, bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32,
+    f64
+);
+
+impl<T: IntoToolOutput, E: Display> IntoToolOutput for std::result::Result<T, E> {
+    fn into_tool_output(self) -> ToolCallResult {
+        self.map_err(|e| anyhow!("{}", e))?.into_tool_output()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repeat_tool(arguments: &ToolArguments) -> ToolCallResult {
+        let text: String = parse_arg(arguments, "text")?;
+        let times: Option<usize> = parse_optional_arg(arguments, "times")?;
+        text.repeat(times.unwrap_or(1)).into_tool_output()
+    }
+
+    const TOOLS: &[ToolDefinition] = &[ToolDefinition {
+        name: "repeat",
+        description: "Repeats the text.",
+        parameters: &[
+            ToolParameter {
+                name: "text",
+                kind: "string",
+                required: true,
+            },
+            ToolParameter {
+                name: "times",
+                kind: "integer",
+                required: false,
+            },
+        ],
+        call: repeat_tool,
+    }];
+
+    fn call(name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            id: "call_1".to_string(),
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_execute_tool_call() {
+        assert_eq!(
+            execute_tool_call(TOOLS, &call("repeat", r#"{"text":"ab","times":2}"#)),
+            "abab"
+        );
+        assert_eq!(
+            execute_tool_call(TOOLS, &call("repeat", r#"{"text":"ab"}"#)),
+            "ab"
+        );
+        assert!(execute_tool_call(TOOLS, &call("repeat", "{}")).starts_with("Error: Missing"));
+        assert!(execute_tool_call(TOOLS, &call("nope", "{}")).starts_with("Error: there is no"));
+    }
+
+    #[test]
+    fn test_parameters_schema() {
+        let schema = TOOLS[0].parameters_schema();
+
+        assert_eq!(schema["properties"]["times"]["type"], "integer");
+        assert_eq!(schema["required"], json!(["text"]));
+    }
+}
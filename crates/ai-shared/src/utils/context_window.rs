@@ -0,0 +1,92 @@
+use crate::models::Message;
+
+/// Rough number of characters per token. Good enough to keep a replayed history under the limit
+/// without pulling a tokenizer for every model.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens added by the message envelope (role and separators).
+const TOKENS_PER_MESSAGE: usize = 4;
+
+/// Estimated number of tokens the message takes in the context window.
+pub fn estimate_tokens(message: &Message) -> usize {
+    message.content.chars().count().div_ceil(CHARS_PER_TOKEN) + TOKENS_PER_MESSAGE
+}
+
+/// Drops the oldest messages until the estimated size fits `max_tokens`.
+///
+/// A leading system message and the newest message are always kept, even when they alone go over
+/// the limit, and the kept messages never start with a tool result. Returns the messages kept and
+/// how many were dropped.
+pub fn trim_to_context_window(messages: &[Message], max_tokens: usize) -> (Vec<Message>, usize) {
+    let (system, rest) = match messages.split_first() {
+        Some((first, rest)) if first.role == "system" => (Some(first), rest),
+        _ => (None, messages),
+    };
+
+    let mut budget = max_tokens.saturating_sub(system.map(estimate_tokens).unwrap_or(0));
+    let mut kept = 0;
+
+    for message in rest.iter().rev() {
+        let tokens = estimate_tokens(message);
+        if kept > 0 && tokens > budget {
+            break;
+        }
+
+        budget = budget.saturating_sub(tokens);
+        kept += 1;
+    }
+
+    let mut dropped = rest.len() - kept;
+
+    // A tool result can't be sent without the assistant message that asked for it.
+    while dropped + 1 < rest.len() && rest[dropped].role == "tool" {
+        dropped += 1;
+    }
+    let trimmed = system
+        .into_iter()
+        .chain(rest[dropped..].iter())
+        .cloned()
+        .collect();
+
+    (trimmed, dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str) -> Message {
+        Message::new(role, content.to_string())
+    }
+
+    #[test]
+    fn test_trim_keeps_system_message_and_newest_messages() {
+        let messages = vec![
+            message("system", "Be nice."),    // 2 + 4
+            message("user", &"a".repeat(40)), // 10 + 4
+            message("assistant", &"b".repeat(40)),
+            message("user", &"c".repeat(40)),
+        ];
+
+        let (trimmed, dropped) = trim_to_context_window(&messages, 6 + 14 * 2);
+
+        assert_eq!(dropped, 1);
+        assert_eq!(trimmed.len(), 3);
+        assert_eq!(trimmed[0].role, "system");
+        assert!(trimmed[1].content.starts_with('b'));
+
+        let (untouched, dropped) = trim_to_context_window(&messages, 1000);
+        assert_eq!(dropped, 0);
+        assert_eq!(untouched.len(), 4);
+    }
+
+    #[test]
+    fn test_trim_always_keeps_the_newest_message() {
+        let messages = vec![message("user", &"a".repeat(400))];
+
+        let (trimmed, dropped) = trim_to_context_window(&messages, 10);
+
+        assert_eq!(dropped, 0);
+        assert_eq!(trimmed.len(), 1);
+    }
+}
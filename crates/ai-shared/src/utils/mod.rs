@@ -1 +1,2 @@
+pub mod context_window;
 pub mod request_loggers;
@@ -1,6 +1,6 @@
-use crate::models::ChatCompletion;
 use anyhow::{Context, Result};
 use chrono::Local;
+use serde::Serialize;
 use shared::system::ensure_directory_exists::EnsureDirectoryExists;
 use shared::system::get_current_working_dir::get_current_working_dir_str;
 use shared::system::resolve_path_with_base::resolve_path_with_base;
@@ -32,7 +32,7 @@ impl RequestLogger {
         self.request_timestamp = Local::now().get_datetime_as_filename_safe_string();
     }
 
-    pub fn save_request(&self, new_request: &ChatCompletion) -> Result<()> {
+    pub fn save_request<T: Serialize>(&self, new_request: &T) -> Result<()> {
         let filename = self.get_new_request_file();
 
         // Since this is used only for error logging, no need to bail out in case of error.
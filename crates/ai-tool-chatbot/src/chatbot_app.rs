@@ -1,45 +1,268 @@
+use crate::chatbot_tools::chatbot_tools;
 use crate::cli_utils::get_user_response;
-use crate::models::ChatBotAgent;
+use crate::conversation_exporter::export_conversation;
+use crate::models::{ChatBotAgent, ChatSession, ConversationEntry, ExportFormat, ExportMetadata};
+use crate::session_store::{new_session_id, save_session};
 use ai_shared::models::AiResponse;
-use ai_shared::request_wrappers::requester_builders::build_requester_for_ai;
+use ai_shared::request_wrappers::requester_builders::{
+    build_requester_for_ai, build_requester_for_platform,
+};
+use ai_shared::request_wrappers::requester_implementations::OpenAiRequester;
 use ai_shared::request_wrappers::requester_traits::OpenAiRequesterTraits;
 use anyhow::{Context, Result};
-use tracing::error;
+use chrono::Local;
+use std::io::{stdout, Write};
+use tracing::{error, warn};
+
+const EXPORT_COMMAND: &str = "/export";
+const EXIT_COMMAND: &str = "/exit";
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
 pub async fn start_chatbot(options: ChatBotAgent) -> Result<()> {
-    let mut requester = build_requester_for_ai()?;
+    let mut requester = match &options.platform {
+        Some(platform) => build_requester_for_platform(platform)?,
+        None => build_requester_for_ai()?,
+    };
 
     requester
         .initialize_api_client()
         .context("Failed to initialize API client")?
         .set_system_message(options.ai_personality.clone())
-        .context("Failed to set system message")?;
+        .context("Failed to set system message")?
+        .set_context_window(options.context_tokens)
+        .context("Failed to set context window")?;
+
+    if options.enable_tools {
+        requester
+            .set_tools(chatbot_tools())
+            .context("Failed to register the tools")?;
+    }
 
     let mut ai_response = AiResponse::new_empty(true);
+    let mut conversation: Vec<ConversationEntry> = Vec::new();
+    let now = Local::now();
+    let mut session_id = new_session_id(&now);
+    let mut started_at = now.format(DATE_FORMAT).to_string();
+
+    let user_printer = &options.user_printer;
+
+    if let Some(session) = &options.resumed_session {
+        requester
+            .set_message_history(session.messages.clone())
+            .context("Failed to restore the conversation")?;
+        conversation = session.conversation.clone();
+        session_id = session.id.clone();
+        started_at = session.started_at.clone();
+
+        print_last_exchange(&options, &conversation);
+    }
 
-    let user_printer = options.user_printer;
-    let ai_printer = options.agent_printer;
+    if let Some(first_message_to_ai) = options.first_message_to_ai.clone() {
+        ai_response = ask_ai(&options, &mut requester, first_message_to_ai).await?;
+        conversation.push(ConversationEntry {
+            speaker: options.ai_name.clone(),
+            content: ai_response.message.clone(),
+        });
 
-    if let Some(first_message_to_ai) = options.first_message_to_ai {
-        ai_response = requester.send_request(first_message_to_ai, true).await?;
-        let _ = &ai_printer.print(ai_response.message.to_string());
+        persist_session(
+            &options,
+            &requester,
+            &session_id,
+            &started_at,
+            &conversation,
+        );
     }
 
     while ai_response.success {
         user_printer.print_tag();
         let user_request = get_user_response(true);
 
-        ai_response = requester
-            .send_request(format!("The user replied: {}", user_request), true)
-            .await?;
+        if user_request == EXIT_COMMAND {
+            break;
+        }
+
+        if let Some(format_arg) = parse_export_command(&user_request) {
+            let format = match format_arg.trim() {
+                "" => ExportFormat::Markdown,
+                value => match ExportFormat::from_str_value(value) {
+                    Some(format) => format,
+                    None => {
+                        warn!("Unknown export format '{}'. Use md or html.", value);
+                        continue;
+                    }
+                },
+            };
+
+            save_conversation(&options, &requester, &started_at, format, &conversation);
+            continue;
+        }
+
+        ai_response = ask_ai(
+            &options,
+            &mut requester,
+            format!("The user replied: {}", user_request),
+        )
+        .await?;
 
         if !ai_response.success {
             error!("Request to AI failed! {}", ai_response.message);
             break;
         }
 
-        ai_printer.print(ai_response.message.to_string());
+        conversation.push(ConversationEntry {
+            speaker: options.user_name.clone(),
+            content: user_request,
+        });
+        conversation.push(ConversationEntry {
+            speaker: options.ai_name.clone(),
+            content: ai_response.message.clone(),
+        });
+
+        persist_session(
+            &options,
+            &requester,
+            &session_id,
+            &started_at,
+            &conversation,
+        );
+    }
+
+    if options.sessions_dir.is_some() && !conversation.is_empty() {
+        println!(
+            "Resume this conversation with: {} --resume {}",
+            env!("CARGO_PKG_NAME"),
+            session_id
+        );
+    }
+
+    if let Some(format) = options.export_on_exit {
+        save_conversation(&options, &requester, &started_at, format, &conversation);
     }
 
     Ok(())
 }
+
+/// Sends the message and prints the reply: as it arrives when streaming, or all at once.
+async fn ask_ai(
+    options: &ChatBotAgent,
+    requester: &mut OpenAiRequester,
+    message: String,
+) -> Result<AiResponse> {
+    if !options.stream {
+        let ai_response = requester.send_request(message, true).await?;
+        if ai_response.success {
+            options.agent_printer.print(ai_response.message.to_string());
+        }
+
+        return Ok(ai_response);
+    }
+
+    options.agent_printer.print_tag();
+
+    let mut print_token = |token: &str| {
+        print!("{}", token);
+        let _ = stdout().flush();
+    };
+
+    let ai_response = requester
+        .send_request_streaming(message, true, &mut print_token)
+        .await;
+
+    println!();
+
+    ai_response
+}
+
+/// Returns the argument of `/export` (empty without one), when the request is that command.
+/// `/exporter` and the like are sent to the AI, like any other message.
+fn parse_export_command(user_request: &str) -> Option<&str> {
+    let argument = user_request.strip_prefix(EXPORT_COMMAND)?;
+
+    if argument.is_empty() || argument.starts_with(char::is_whitespace) {
+        Some(argument)
+    } else {
+        None
+    }
+}
+
+fn save_conversation(
+    options: &ChatBotAgent,
+    requester: &OpenAiRequester,
+    started_at: &str,
+    format: ExportFormat,
+    conversation: &[ConversationEntry],
+) {
+    let token_usage = requester.get_token_usage();
+
+    let metadata = ExportMetadata {
+        user_name: options.user_name.clone(),
+        ai_name: options.ai_name.clone(),
+        model: requester.get_model().to_string(),
+        started_at: started_at.to_string(),
+        prompt_tokens: token_usage.prompt_tokens,
+        completion_tokens: token_usage.completion_tokens,
+        total_tokens: token_usage.total_tokens,
+    };
+
+    // A failed export shouldn't end the chat, so we just let the user know.
+    match export_conversation(&options.notes_dir, format, &metadata, conversation) {
+        Ok(path) => println!("Conversation exported to: {}", path.display()),
+        Err(e) => warn!("Failed to export conversation: {:#}", e),
+    }
+}
+
+/// Saves the conversation after every exchange, so nothing is lost if the tool is closed with
+/// `Ctrl+C`.
+fn persist_session(
+    options: &ChatBotAgent,
+    requester: &OpenAiRequester,
+    session_id: &str,
+    started_at: &str,
+    conversation: &[ConversationEntry],
+) {
+    let Some(sessions_dir) = &options.sessions_dir else {
+        return;
+    };
+
+    let session = ChatSession {
+        id: session_id.to_string(),
+        user_name: options.user_name.clone(),
+        ai_name: options.ai_name.clone(),
+        personality: options.ai_personality.clone(),
+        model: requester.get_model().to_string(),
+        started_at: started_at.to_string(),
+        updated_at: Local::now().format(DATE_FORMAT).to_string(),
+        messages: requester.get_message_history().to_vec(),
+        conversation: conversation.to_vec(),
+    };
+
+    // Same as the export: a failed save shouldn't end the chat.
+    if let Err(e) = save_session(sessions_dir, &session) {
+        warn!("Failed to save the conversation: {:#}", e);
+    }
+}
+
+/// Shows where a resumed conversation stopped.
+fn print_last_exchange(options: &ChatBotAgent, conversation: &[ConversationEntry]) {
+    let start = conversation.len().saturating_sub(2);
+    for entry in &conversation[start..] {
+        if entry.speaker == options.user_name {
+            options.user_printer.print(entry.content.clone());
+        } else {
+            options.agent_printer.print(entry.content.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_export_command() {
+        assert_eq!(parse_export_command("/export"), Some(""));
+        assert_eq!(parse_export_command("/export html"), Some(" html"));
+        assert_eq!(parse_export_command("/exporter foo"), None);
+        assert_eq!(parse_export_command("please /export"), None);
+    }
+}
@@ -0,0 +1,149 @@
+use ai_macros::ai_function;
+use ai_shared::tools::ToolDefinition;
+use chrono::Local;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Lines returned by `read_text_file` when the AI doesn't ask for a number.
+const DEFAULT_MAX_LINES: usize = 200;
+
+/// Entries returned by `list_directory`, so a huge folder doesn't fill the context window.
+const MAX_ENTRIES: usize = 200;
+
+/// Tools the AI can call with `--tools`. File access is read-only, and limited to the folder the
+/// chatbot was started in.
+pub fn chatbot_tools() -> Vec<ToolDefinition> {
+    vec![
+        get_current_date_time_tool(),
+        get_current_directory_tool(),
+        list_directory_tool(),
+        read_text_file_tool(),
+    ]
+}
+
+/// Returns the current local date and time, with the UTC offset.
+#[ai_function(tool)]
+fn get_current_date_time() -> String {
+    Local::now()
+        .format("%Y-%m-%d %H:%M:%S %:z (%A)")
+        .to_string()
+}
+
+/// Returns the folder the chatbot is running in. Paths given to the other tools are relative to it.
+#[ai_function(tool)]
+fn get_current_directory() -> Result<String, String> {
+    env::current_dir()
+        .map(|dir| dir.display().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Lists the files and folders (ending with /) in a folder under the current directory.
+#[ai_function(tool)]
+fn list_directory(path: &str) -> Result<String, String> {
+    let folder = resolve_allowed_path(path)?;
+    let entries = fs::read_dir(&folder).map_err(|e| e.to_string())?;
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                format!("{}/", name)
+            } else {
+                name
+            }
+        })
+        .collect();
+    names.sort();
+
+    let total = names.len();
+    names.truncate(MAX_ENTRIES);
+    if total > MAX_ENTRIES {
+        names.push(format!("... and {} more", total - MAX_ENTRIES));
+    }
+
+    if names.is_empty() {
+        return Ok("(empty folder)".to_string());
+    }
+
+    Ok(names.join("\n"))
+}
+
+/// Reads a text file under the current directory. Returns the first max_lines lines (default 200).
+#[ai_function(tool)]
+fn read_text_file(path: &str, max_lines: Option<usize>) -> Result<String, String> {
+    let file = resolve_allowed_path(path)?;
+    let bytes = fs::read(&file).map_err(|e| e.to_string())?;
+
+    if bytes.contains(&0) {
+        return Err(format!("{} is not a text file", path));
+    }
+
+    let content = String::from_utf8_lossy(&bytes);
+    let max_lines = max_lines.unwrap_or(DEFAULT_MAX_LINES);
+    let total = content.lines().count();
+
+    let mut output = content
+        .lines()
+        .take(max_lines)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if total > max_lines {
+        output.push_str(&format!("\n... ({} more lines)", total - max_lines));
+    }
+
+    Ok(output)
+}
+
+/// Resolves the path against the current directory, refusing anything outside of it.
+fn resolve_allowed_path(path: &str) -> Result<PathBuf, String> {
+    let base = env::current_dir()
+        .and_then(|dir| dir.canonicalize())
+        .map_err(|e| e.to_string())?;
+
+    let resolved = base
+        .join(path)
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", path, e))?;
+
+    if !resolved.starts_with(&base) {
+        return Err(format!("{} is outside of the current directory", path));
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ai_shared::models::ToolCall;
+    use ai_shared::tools::execute_tool_call;
+
+    #[test]
+    fn test_tools_refuse_paths_outside_the_current_directory() {
+        let call = ToolCall {
+            id: "call_0".to_string(),
+            name: "read_text_file".to_string(),
+            arguments: r#"{"path":"../../../../../../etc/hostname"}"#.to_string(),
+        };
+
+        let output = execute_tool_call(&chatbot_tools(), &call);
+
+        assert!(output.starts_with("Error:"), "{}", output);
+    }
+
+    #[test]
+    fn test_read_text_file_limits_lines() {
+        let call = ToolCall {
+            id: "call_0".to_string(),
+            name: "read_text_file".to_string(),
+            arguments: r#"{"path":"Cargo.toml","max_lines":1}"#.to_string(),
+        };
+
+        let output = execute_tool_call(&chatbot_tools(), &call);
+
+        assert!(output.starts_with("[package]\n... ("), "{}", output);
+    }
+}
@@ -1,12 +1,21 @@
 use anyhow::{Context, Result};
 
-use crate::models::ChatBotAgent;
+use crate::models::{ChatBotAgent, ExportFormat};
+use crate::session_store::{list_sessions, load_session};
+use ai_shared::request_wrappers::requester_builders::AI_PLATFORMS;
+use clap::{Arg, ArgAction, Command};
+use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
+use shared::system::load_global_config::user_data_dir;
 use shared::system::select_file_from_path::select_file_from_path;
 use shared::utils::role_printer::{Role, RolePrinter};
+use shared::utils::table_output::Table;
 use std::env;
+use std::ffi::OsString;
 use std::io::stdin;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_CONTEXT_TOKENS: usize = 8000;
 
 /// Displays runtime configuration information.
 ///
@@ -17,26 +26,165 @@ pub fn print_runtime_info(args: &ChatBotAgent) {
 
     println!("- User: {}", &args.user_name);
     println!("- Ai: {}", &args.ai_name);
+    println!("- Notes folder: {}", args.notes_dir.display());
+
+    if let Some(format) = args.export_on_exit {
+        println!("- Export on exit: {}", format.file_extension());
+    }
+
+    if let Some(platform) = &args.platform {
+        println!("- Platform: {}", platform);
+    }
+
+    if args.enable_tools {
+        println!("- Tools: enabled");
+    }
+
+    match &args.sessions_dir {
+        Some(dir) => println!("- Sessions folder: {}", dir.display()),
+        None => println!("- Sessions folder: (not saving)"),
+    }
+
+    if let Some(session) = &args.resumed_session {
+        println!(
+            "- Resuming: {} ({} messages)",
+            session.id,
+            session.conversation.len()
+        );
+    }
 
     println!();
 }
 
-pub fn get_runtime_config() -> Result<ChatBotAgent> {
-    let user_name = env::var("AI_CHAT_USER_NAME").unwrap_or_else(|_| {
-        println!("What is your name?");
-        get_user_response(true)
-    });
+/// Reads the settings from the arguments and the environment variables. Returns `None` when there
+/// is no chat to start: the sessions were only listed (`--list-sessions`).
+pub fn get_runtime_config(argv: Vec<OsString>) -> Result<Option<ChatBotAgent>> {
+    let matches = Command::new(env!("CARGO_PKG_NAME"))
+        .add_basic_metadata(
+            env!("CARGO_PKG_VERSION"),
+            env!("CARGO_PKG_DESCRIPTION"),
+            "Interactive AI chatbot. Most of the configuration comes from environment variables. While chatting, type /export [md|html] to save the conversation, or /exit to leave. Conversations are saved automatically and can be resumed with --resume.",
+        )
+        .arg(
+            Arg::new("export-on-exit")
+                .long("export-on-exit")
+                .short('e')
+                .value_name("FORMAT")
+                .value_parser(["md", "html"])
+                .help("Exports the conversation to the notes folder when the chat ends."),
+        )
+        .arg(
+            Arg::new("notes-dir")
+                .long("notes-dir")
+                .short('n')
+                .value_name("DIR")
+                .help("Folder where exported conversations are saved. (Default: AI_CHAT_NOTES_FOLDER or current folder)"),
+        )
+        .arg(
+            Arg::new("resume")
+                .long("resume")
+                .short('r')
+                .value_name("ID")
+                .conflicts_with("list-sessions")
+                .help("Resumes a saved conversation. Use --list-sessions to see the ids."),
+        )
+        .arg(
+            Arg::new("list-sessions")
+                .long("list-sessions")
+                .short('l')
+                .action(ArgAction::SetTrue)
+                .help("Lists the saved conversations and exits."),
+        )
+        .arg(
+            Arg::new("sessions-dir")
+                .long("sessions-dir")
+                .value_name("DIR")
+                .help("Folder where conversations are saved. (Default: AI_CHAT_SESSIONS_FOLDER or the user data folder)"),
+        )
+        .arg(
+            Arg::new("no-save")
+                .long("no-save")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["resume", "list-sessions"])
+                .help("Doesn't save the conversation."),
+        )
+        .arg(
+            Arg::new("platform")
+                .long("platform")
+                .short('p')
+                .value_name("PLATFORM")
+                .value_parser(AI_PLATFORMS.to_vec())
+                .help("AI platform to chat with. Overrides AI_PLATFORM."),
+        )
+        .arg(
+            Arg::new("no-stream")
+                .long("no-stream")
+                .action(ArgAction::SetTrue)
+                .help("Waits for the whole reply instead of printing it as it is generated."),
+        )
+        .arg(
+            Arg::new("tools")
+                .long("tools")
+                .action(ArgAction::SetTrue)
+                .help("Lets the AI call the built-in tools: the date and time, and read-only access to the files under the current folder. The model must support tool calling."),
+        )
+        .arg(
+            Arg::new("context-tokens")
+                .long("context-tokens")
+                .value_name("TOKENS")
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Maximum (estimated) tokens of history sent to the AI with each message. Older messages are left out. (Default: {})", DEFAULT_CONTEXT_TOKENS)),
+        )
+        .get_matches_from(argv);
 
-    let personality_path = env::var("AI_CHAT_PERSONALITIES_FOLDER")
-        .context("AI_CHAT_PERSONALITIES_FOLDER must be set")?;
+    let sessions_dir = matches
+        .get_one::<String>("sessions-dir")
+        .cloned()
+        .or_else(|| env::var("AI_CHAT_SESSIONS_FOLDER").ok())
+        .map(PathBuf::from)
+        .or_else(|| user_data_dir(env!("CARGO_PKG_NAME")).map(|dir| dir.join("sessions")))
+        .context("Could not find the user data folder. Use --sessions-dir to set one.")?;
 
-    let (personality, personality_name) = load_chat_personality_prompt(personality_path.as_str())?;
+    if matches.get_flag("list-sessions") {
+        print_sessions(&sessions_dir)?;
+        return Ok(None);
+    }
 
-    let ai_name = if personality_name.is_empty() {
-        println!("What is the AI's name?");
-        get_user_response(true)
-    } else {
-        personality_name.to_string()
+    let resumed_session = match matches.get_one::<String>("resume") {
+        Some(id) => Some(load_session(&sessions_dir, id.trim())?),
+        None => None,
+    };
+
+    let context_tokens = matches
+        .get_one::<usize>("context-tokens")
+        .copied()
+        .unwrap_or(DEFAULT_CONTEXT_TOKENS);
+
+    if context_tokens == 0 {
+        anyhow::bail!("--context-tokens must be greater than zero.");
+    }
+
+    let sessions_dir = (!matches.get_flag("no-save")).then_some(sessions_dir);
+
+    let export_on_exit = matches
+        .get_one::<String>("export-on-exit")
+        .and_then(|format| ExportFormat::from_str_value(format));
+
+    let notes_dir = matches
+        .get_one::<String>("notes-dir")
+        .cloned()
+        .or_else(|| env::var("AI_CHAT_NOTES_FOLDER").ok())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    // A resumed conversation keeps the names and personality it started with.
+    let (user_name, ai_name, personality) = match &resumed_session {
+        Some(session) => (
+            session.user_name.clone(),
+            session.ai_name.clone(),
+            session.personality.clone(),
+        ),
+        None => ask_for_chat_setup()?,
     };
 
     let tag_padding_size = if user_name.len() > ai_name.len() {
@@ -49,16 +197,94 @@ pub fn get_runtime_config() -> Result<ChatBotAgent> {
 
     let user_printer = RolePrinter::new(Role::User, user_name.clone(), Some(tag_padding_size));
 
-    let first_message_to_ai = env::var("AI_CHAT_INITIAL_MSG_TO_AI").ok();
+    let first_message_to_ai = match resumed_session {
+        Some(_) => None,
+        None => env::var("AI_CHAT_INITIAL_MSG_TO_AI").ok(),
+    };
 
-    Ok(ChatBotAgent {
+    Ok(Some(ChatBotAgent {
         user_name,
         ai_name,
         ai_personality: personality,
         agent_printer,
         user_printer,
         first_message_to_ai,
-    })
+        export_on_exit,
+        notes_dir,
+        sessions_dir,
+        resumed_session,
+        context_tokens,
+        platform: matches.get_one::<String>("platform").cloned(),
+        stream: !matches.get_flag("no-stream"),
+        enable_tools: matches.get_flag("tools"),
+    }))
+}
+
+/// Asks for the user name and the personality, when they aren't set by environment variables.
+/// Returns the user name, the AI name, and the personality prompt.
+fn ask_for_chat_setup() -> Result<(String, String, String)> {
+    let user_name = env::var("AI_CHAT_USER_NAME").unwrap_or_else(|_| {
+        println!("What is your name?");
+        get_user_response(true)
+    });
+
+    let personality_path = env::var("AI_CHAT_PERSONALITIES_FOLDER")
+        .context("AI_CHAT_PERSONALITIES_FOLDER must be set")?;
+
+    let (personality, personality_name) = load_chat_personality_prompt(personality_path.as_str())?;
+
+    let ai_name = if personality_name.is_empty() {
+        println!("What is the AI's name?");
+        get_user_response(true)
+    } else {
+        personality_name.to_string()
+    };
+
+    Ok((user_name, ai_name, personality))
+}
+
+fn print_sessions(sessions_dir: &Path) -> Result<()> {
+    let sessions = list_sessions(sessions_dir)?;
+    if sessions.is_empty() {
+        println!("No saved sessions in {}", sessions_dir.display());
+        return Ok(());
+    }
+
+    let mut table = Table::new(&["ID", "AI", "MODEL", "MESSAGES", "UPDATED", "LAST MESSAGE"])
+        .with_title(format!("Sessions in {}", sessions_dir.display()))
+        .align_right(3);
+
+    for session in sessions {
+        let last_message = session
+            .conversation
+            .last()
+            .map(|entry| preview(&entry.content, 40))
+            .unwrap_or_default();
+
+        table.add_row(vec![
+            session.id,
+            session.ai_name,
+            session.model,
+            session.conversation.len().to_string(),
+            session.updated_at,
+            last_message,
+        ]);
+    }
+
+    print!("{}", table.render_text());
+
+    Ok(())
+}
+
+/// First line of the message, cut at `max_chars`.
+fn preview(message: &str, max_chars: usize) -> String {
+    let line = message.lines().next().unwrap_or("").trim();
+    if line.chars().count() <= max_chars {
+        return line.to_string();
+    }
+
+    let cut: String = line.chars().take(max_chars).collect();
+    format!("{}...", cut)
 }
 
 pub fn get_user_response(required: bool) -> String {
@@ -0,0 +1,217 @@
+use crate::models::{ConversationEntry, ExportFormat, ExportMetadata};
+use anyhow::{Context, Result};
+use chrono::Local;
+use shared::system::ensure_directory_exists::EnsureDirectoryExists;
+use shared::utils::datetime_utc_utils::DateTimeUtilsExt;
+use shared::utils::sanitize_string_for_filename::sanitize_string_for_filename;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Writes the conversation to a new file inside `notes_dir` and returns the file path.
+pub fn export_conversation(
+    notes_dir: &Path,
+    format: ExportFormat,
+    metadata: &ExportMetadata,
+    entries: &[ConversationEntry],
+) -> Result<PathBuf> {
+    let notes_dir = notes_dir.to_path_buf();
+    notes_dir
+        .ensure_directory_exists()
+        .context("Failed to create notes directory")?;
+
+    let filename = format!(
+        "chat--{}--{}.{}",
+        sanitize_string_for_filename(&metadata.ai_name),
+        Local::now().get_datetime_as_filename_safe_string(),
+        format.file_extension()
+    );
+
+    let file_path = notes_dir.join(filename);
+
+    let content = match format {
+        ExportFormat::Markdown => render_markdown(metadata, entries),
+        ExportFormat::Html => render_html(metadata, entries),
+    };
+
+    fs::write(&file_path, content)
+        .with_context(|| format!("Failed to write conversation to {}", file_path.display()))?;
+
+    Ok(file_path)
+}
+
+fn render_markdown(metadata: &ExportMetadata, entries: &[ConversationEntry]) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "# Conversation: {} & {}\n\n",
+        metadata.user_name, metadata.ai_name
+    ));
+    output.push_str(&format!("- **Model**: {}\n", metadata.model));
+    output.push_str(&format!("- **Date**: {}\n", metadata.started_at));
+    output.push_str(&format!(
+        "- **Tokens**: {} prompt / {} completion / {} total\n\n",
+        metadata.prompt_tokens, metadata.completion_tokens, metadata.total_tokens
+    ));
+    output.push_str("---\n\n");
+
+    for entry in entries {
+        // Content goes in untouched, so fenced code blocks are kept as they came from the AI.
+        output.push_str(&format!("### {}\n\n{}\n\n", entry.speaker, entry.content));
+    }
+
+    output
+}
+
+fn render_html(metadata: &ExportMetadata, entries: &[ConversationEntry]) -> String {
+    let title = format!(
+        "Conversation: {} &amp; {}",
+        escape_html(&metadata.user_name),
+        escape_html(&metadata.ai_name)
+    );
+
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str(&format!("<title>{}</title>\n", title));
+    output.push_str(
+        "<style>body{font-family:sans-serif;max-width:900px;margin:auto;padding:1em;}\
+         pre{background:#f4f4f4;padding:.75em;overflow-x:auto;}\
+         .speaker{font-weight:bold;margin-top:1.5em;}</style>\n",
+    );
+    output.push_str("</head>\n<body>\n");
+    output.push_str(&format!("<h1>{}</h1>\n<ul>\n", title));
+    output.push_str(&format!(
+        "<li><b>Model</b>: {}</li>\n",
+        escape_html(&metadata.model)
+    ));
+    output.push_str(&format!(
+        "<li><b>Date</b>: {}</li>\n",
+        escape_html(&metadata.started_at)
+    ));
+    output.push_str(&format!(
+        "<li><b>Tokens</b>: {} prompt / {} completion / {} total</li>\n</ul>\n<hr>\n",
+        metadata.prompt_tokens, metadata.completion_tokens, metadata.total_tokens
+    ));
+
+    for entry in entries {
+        output.push_str(&format!(
+            "<div class=\"speaker\">{}</div>\n{}\n",
+            escape_html(&entry.speaker),
+            render_html_content(&entry.content)
+        ));
+    }
+
+    output.push_str("</body>\n</html>\n");
+
+    output
+}
+
+/// Converts a message into HTML, turning fenced code blocks into `<pre><code>` and everything
+/// else into paragraphs.
+fn render_html_content(content: &str) -> String {
+    let mut output = String::new();
+    let mut in_code_block = false;
+    let mut paragraph: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        if let Some(language) = line.trim_start().strip_prefix("```") {
+            if in_code_block {
+                output.push_str("</code></pre>\n");
+            } else {
+                flush_paragraph(&mut output, &mut paragraph);
+                let language = language.trim();
+                if language.is_empty() {
+                    output.push_str("<pre><code>");
+                } else {
+                    output.push_str(&format!(
+                        "<pre><code class=\"language-{}\">",
+                        escape_html(language)
+                    ));
+                }
+            }
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            output.push_str(&escape_html(line));
+            output.push('\n');
+        } else if line.trim().is_empty() {
+            flush_paragraph(&mut output, &mut paragraph);
+        } else {
+            paragraph.push(escape_html(line));
+        }
+    }
+
+    if in_code_block {
+        // Unterminated fence; close it so the document stays valid.
+        output.push_str("</code></pre>\n");
+    }
+
+    flush_paragraph(&mut output, &mut paragraph);
+
+    output
+}
+
+fn flush_paragraph(output: &mut String, paragraph: &mut Vec<String>) {
+    if paragraph.is_empty() {
+        return;
+    }
+
+    output.push_str(&format!("<p>{}</p>\n", paragraph.join("<br>\n")));
+    paragraph.clear();
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_html_content_preserves_code_blocks() {
+        let content = "Try this:\n```rust\nlet x = a < b;\n```\nDone.";
+
+        let html = render_html_content(content);
+
+        assert_eq!(
+            html,
+            "<p>Try this:</p>\n<pre><code class=\"language-rust\">let x = a &lt; b;\n</code></pre>\n<p>Done.</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_html_content_closes_unterminated_block() {
+        let html = render_html_content("```\nopen");
+
+        assert!(html.ends_with("</code></pre>\n"));
+    }
+
+    #[test]
+    fn test_render_markdown_includes_metadata() {
+        let metadata = ExportMetadata {
+            user_name: "John".to_string(),
+            ai_name: "Minsc".to_string(),
+            model: "gpt-4".to_string(),
+            started_at: "2025-01-01 10:00:00".to_string(),
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+        };
+        let entries = vec![ConversationEntry {
+            speaker: "John".to_string(),
+            content: "Hi!".to_string(),
+        }];
+
+        let markdown = render_markdown(&metadata, &entries);
+
+        assert!(markdown.contains("- **Model**: gpt-4"));
+        assert!(markdown.contains("10 prompt / 5 completion / 15 total"));
+        assert!(markdown.contains("### John\n\nHi!"));
+    }
+}
@@ -0,0 +1,69 @@
+mod chatbot_app;
+mod chatbot_tools;
+mod cli_utils;
+mod conversation_exporter;
+mod models;
+mod session_store;
+
+use crate::chatbot_app::start_chatbot;
+use crate::cli_utils::{get_runtime_config, print_runtime_info};
+use anyhow::Result;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use shared::system::load_env_variables::load_env_variables;
+use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use std::ffi::OsString;
+
+/// AI chatbot.
+///
+/// Loads the environment variables and the settings, then starts the chat session.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    match runtime.block_on(run_tool(argv)) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+async fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    load_env_variables()?;
+
+    let _ = setup_graceful_shutdown(true);
+
+    // Nothing to chat about when the sessions were only listed.
+    let Some(runtime_config) = get_runtime_config(argv)? else {
+        return Ok(());
+    };
+
+    print_runtime_info(&runtime_config);
+
+    start_chatbot(runtime_config).await?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
+use ai_shared::models::Message;
+use serde::{Deserialize, Serialize};
 use shared::utils::role_printer::RolePrinter;
+use std::path::PathBuf;
 
 pub struct ChatBotAgent {
     pub user_name: String,
@@ -7,4 +10,76 @@ pub struct ChatBotAgent {
     pub agent_printer: RolePrinter,
     pub user_printer: RolePrinter,
     pub first_message_to_ai: Option<String>,
+    pub export_on_exit: Option<ExportFormat>,
+    pub notes_dir: PathBuf,
+    /// Folder where the sessions are saved, or `None` when they shouldn't be (`--no-save`).
+    pub sessions_dir: Option<PathBuf>,
+    /// Session picked with `--resume`.
+    pub resumed_session: Option<ChatSession>,
+    /// Estimated tokens of history sent with each request.
+    pub context_tokens: usize,
+    /// Overrides `AI_PLATFORM`.
+    pub platform: Option<String>,
+    /// Prints the AI replies as they are generated.
+    pub stream: bool,
+    /// Lets the AI call the built-in tools (`--tools`).
+    pub enable_tools: bool,
+}
+
+/// File formats supported when exporting a conversation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn from_str_value(value: &str) -> Option<Self> {
+        match value.to_lowercase().trim() {
+            "md" | "markdown" => Some(ExportFormat::Markdown),
+            "html" | "htm" => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// A single exchange line kept by the chatbot so the conversation can be exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationEntry {
+    pub speaker: String,
+    pub content: String,
+}
+
+/// Information written at the top of an exported conversation.
+pub struct ExportMetadata {
+    pub user_name: String,
+    pub ai_name: String,
+    pub model: String,
+    pub started_at: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+/// A conversation saved to disk, so it can be resumed with `--resume`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChatSession {
+    pub id: String,
+    pub user_name: String,
+    pub ai_name: String,
+    pub personality: String,
+    pub model: String,
+    pub started_at: String,
+    pub updated_at: String,
+    /// History replayed to the model when the session is resumed.
+    pub messages: Vec<Message>,
+    /// Conversation as shown to the user (and exported).
+    pub conversation: Vec<ConversationEntry>,
 }
@@ -0,0 +1,125 @@
+use crate::models::ChatSession;
+use anyhow::{bail, Context, Result};
+use shared::system::ensure_directory_exists::EnsureDirectoryExists;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SESSION_EXTENSION: &str = "json";
+
+/// Id of a new session: the local start time, so the ids sort in the order the chats started.
+pub fn new_session_id(started_at: &chrono::DateTime<chrono::Local>) -> String {
+    started_at.format("%Y%m%d-%H%M%S").to_string()
+}
+
+pub fn save_session(sessions_dir: &Path, session: &ChatSession) -> Result<PathBuf> {
+    let sessions_dir = sessions_dir.to_path_buf();
+    sessions_dir
+        .ensure_directory_exists()
+        .context("Failed to create sessions directory")?;
+
+    let path = session_path(&sessions_dir, &session.id);
+    let content = serde_json::to_string_pretty(session).context("Failed to serialize session")?;
+
+    // Written to a temporary file first, so a crash mid-write doesn't corrupt the session.
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, content)
+        .with_context(|| format!("Failed to write session to {}", temp_path.display()))?;
+    fs::rename(&temp_path, &path)
+        .with_context(|| format!("Failed to write session to {}", path.display()))?;
+
+    Ok(path)
+}
+
+pub fn load_session(sessions_dir: &Path, id: &str) -> Result<ChatSession> {
+    let path = session_path(sessions_dir, id);
+    if !path.is_file() {
+        bail!(
+            "Session '{}' not found in {}. Use --list-sessions to see the saved ones.",
+            id,
+            sessions_dir.display()
+        );
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read session {}", path.display()))?;
+
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse session {}", path.display()))
+}
+
+/// Saved sessions, most recently updated first. Files that can't be parsed are skipped.
+pub fn list_sessions(sessions_dir: &Path) -> Result<Vec<ChatSession>> {
+    if !sessions_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(sessions_dir)
+        .with_context(|| format!("Failed to read {}", sessions_dir.display()))?;
+
+    let mut sessions: Vec<ChatSession> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == SESSION_EXTENSION)
+        })
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+
+    sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+    Ok(sessions)
+}
+
+fn session_path(sessions_dir: &Path, id: &str) -> PathBuf {
+    sessions_dir.join(format!("{}.{}", id, SESSION_EXTENSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ConversationEntry;
+    use ai_shared::models::Message;
+    use tempfile::tempdir;
+
+    fn session(id: &str, updated_at: &str) -> ChatSession {
+        ChatSession {
+            id: id.to_string(),
+            user_name: "John".to_string(),
+            ai_name: "Minsc".to_string(),
+            personality: "You are Minsc.".to_string(),
+            model: "gpt-4".to_string(),
+            started_at: "2026-10-16 10:00:00".to_string(),
+            updated_at: updated_at.to_string(),
+            messages: vec![Message::new("user", "The user replied: Hi!".to_string())],
+            conversation: vec![ConversationEntry {
+                speaker: "John".to_string(),
+                content: "Hi!".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_save_load_and_list_sessions() {
+        let dir = tempdir().unwrap();
+        let sessions_dir = dir.path().join("sessions");
+
+        save_session(&sessions_dir, &session("older", "2026-10-16 10:05:00")).unwrap();
+        save_session(&sessions_dir, &session("newer", "2026-10-16 11:00:00")).unwrap();
+        fs::write(sessions_dir.join("broken.json"), "{").unwrap();
+
+        let loaded = load_session(&sessions_dir, "older").unwrap();
+        assert_eq!(loaded.messages[0].content, "The user replied: Hi!");
+        assert_eq!(loaded.conversation[0].speaker, "John");
+
+        let ids: Vec<String> = list_sessions(&sessions_dir)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, vec!["newer", "older"]);
+
+        assert!(load_session(&sessions_dir, "missing").is_err());
+    }
+}
@@ -4,7 +4,7 @@ use ai_macros::ai_function;
 const OUTPUT: &str = "";
 
 #[ai_function]
-pub fn fix_cli_command(_command: &str, _os: &str) -> &'static str {
+pub fn fix_cli_command(_command: &str, _os: &str, _context: &str) -> &'static str {
     /// ROLE
     /// - Repair one possibly broken CLI command so it runs on the specified OS/shell.
     /// - Output EXACTLY ONE corrected command string.
@@ -12,6 +12,9 @@ pub fn fix_cli_command(_command: &str, _os: &str) -> &'static str {
     /// INPUTS
     /// - `command`: a single command line (may include stray words or code fences; extract the command).
     /// - `os`: "windows (shell: powershell|cmd)" | "linux (shell: bash)" | "macos (shell: zsh)" | "unknown (value)".
+    /// - `context` (optional): the current directory, its listing, and the user's recent commands (oldest first).
+    ///   Use it to fill in real file/folder names and to match the user's tools; never let it change what the
+    ///   command is meant to do. `[REDACTED]` marks removed secrets: never put it in the output.
     ///
     /// OS/SHELL RESOLUTION
     /// - If `unknown (...)`, infer from the text in parentheses:
@@ -21,6 +24,8 @@ pub fn fix_cli_command(_command: &str, _os: &str) -> &'static str {
     ///   - mentions "darwin" or "mac" -> macos (zsh)
     ///   - mentions "git-bash"/"msys"/"mingw"/"cygwin" -> treat as POSIX (bash) unless paths are clearly Windows
     /// - If shell unspecified: windows→powershell; linux/macos→bash/zsh (POSIX).
+    /// - If a shell is specified, it wins over the OS default: the command MUST use that shell's syntax
+    ///   (e.g., "linux (shell: powershell)" → PowerShell cmdlets; "windows (shell: bash)" → POSIX).
     ///
     /// REPAIR STRATEGY (FOLLOW IN ORDER)
     /// 1) Normalize: strip code fences/backticks, collapse extra whitespace.
@@ -107,7 +112,7 @@ pub fn fix_cli_command(_command: &str, _os: &str) -> &'static str {
 }
 
 #[ai_function]
-pub fn suggest_cli_command(_request: &str, _os: &str) -> &'static str {
+pub fn suggest_cli_command(_request: &str, _os: &str, _context: &str) -> &'static str {
     /// ROLE
     /// - Generate OS/shell-appropriate command(s) that achieve the natural-language request.
     /// - Do all reasoning internally; OUTPUT ONLY commands.
@@ -116,6 +121,10 @@ pub fn suggest_cli_command(_request: &str, _os: &str) -> &'static str {
     /// INPUTS
     /// - `request`: a single natural-language task description (maybe brief/vague).
     /// - `os`: "windows (shell: powershell|cmd)" | "linux (shell: bash)" | "macos (shell: zsh)" | "unknown (value)".
+    /// - `context` (optional): the current directory, its listing, and the user's recent commands (oldest first).
+    ///   When present, prefer concrete names from the listing over placeholders (e.g., the actual archive or
+    ///   project file), and the tools the user already uses (e.g., `rg` if they ran it). `[REDACTED]` marks
+    ///   removed secrets: never put it in the output.
     ///
     /// OS/SHELL RESOLUTION
     /// - If `os` is "unknown (...)", infer:
@@ -125,6 +134,8 @@ pub fn suggest_cli_command(_request: &str, _os: &str) -> &'static str {
     ///   - contains "darwin" or "mac" -> macos (zsh)
     ///   - contains "git-bash"/"msys"/"mingw"/"cygwin" -> treat as POSIX (bash) unless paths are clearly Windows
     /// - If shell unspecified: windows→powershell; linux/macos→bash/zsh (POSIX).
+    /// - If a shell is specified, it wins over the OS default: commands MUST use that shell's syntax
+    ///   (e.g., "linux (shell: powershell)" → PowerShell cmdlets; "windows (shell: bash)" → POSIX).
     ///
     /// GENERATION STRATEGY (FOLLOW IN ORDER)
     /// 1) Extract concrete tokens from the request (paths, filenames, patterns, URLs, ports, process names).
@@ -139,7 +150,7 @@ pub fn suggest_cli_command(_request: &str, _os: &str) -> &'static str {
     ///
     /// OUTPUT CONTRACT (STRICT)
     /// - Return command(s) ONLY—no prose, no code fences, no leading/trailing whitespace, no backticks -- only the command.
-    /// - Use literal newlines between commands (max 3 total).
+    /// - Use literal newlines between commands (max 3 total). The first line must be the main command: it may be copied alone.
     /// - Use concrete placeholders **only** if the request is generic, with obvious tokens: `<pattern>`, `<name>`, `<file>`, `<dir>`, `<url>`, `<outfile>`, `<port>`, `<archive>`.
     ///
     /// QUOTING
@@ -225,3 +236,35 @@ pub fn suggest_cli_command(_request: &str, _os: &str) -> &'static str {
     /// - Return commands only. No explanations, no extra whitespace.
     OUTPUT
 }
+
+#[ai_function]
+pub fn explain_cli_command(_command: &str, _os: &str) -> &'static str {
+    /// ROLE
+    /// - Explain, part by part, what one CLI command does on the specified OS/shell, so the user can decide if it is
+    ///   safe to run.
+    /// - Do NOT fix, improve, or rewrite the command. Explain it exactly as given.
+    ///
+    /// INPUTS
+    /// - `command`: a single command line (may include pipes, redirections, and chained commands).
+    /// - `os`: "windows (shell: powershell|cmd)" | "linux (shell: bash)" | "macos (shell: zsh)" | "unknown (value)".
+    ///
+    /// EXPLANATION STRATEGY (FOLLOW IN ORDER)
+    /// 1) Split the command into its parts, left to right: programs/cmdlets, subcommands, flags/options (with their
+    ///    values), arguments, pipes, redirections, and chaining operators (`&&`, `||`, `;`).
+    /// 2) Explain each part in plain language, in the context of the resolved OS/shell.
+    /// 3) Call out side effects: files created, overwritten, or deleted; processes killed; network access; elevated
+    ///    privileges (`sudo`, `runas`); anything that cannot be undone.
+    ///
+    /// OUTPUT CONTRACT (STRICT)
+    /// - One line per part, in order, formatted as: `<part> - <what it does>`.
+    /// - Last line: `Summary - <what the whole command does, and its side effects (or "no side effects")>`.
+    /// - No code fences, no backticks, no blank lines, no markdown.
+    ///
+    /// OUTPUT EXAMPLE (BEHAVIORAL, NOT LITERAL)
+    /// - `find . -iname "*bacon*"` on linux →
+    ///   find - searches for files and folders
+    ///   . - starts the search in the current folder (and its subfolders)
+    ///   -iname "*bacon*" - matches names containing "bacon", ignoring case
+    ///   Summary - lists every file and folder under the current folder with "bacon" in the name; no side effects
+    OUTPUT
+}
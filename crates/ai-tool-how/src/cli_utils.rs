@@ -1,9 +1,12 @@
-use crate::models::{HowMode, HowRuntimeConfig};
+use crate::models::{
+    HowMode, HowRuntimeConfig, AUTO_COPY_ENV, DEFAULT_HISTORY_SIZE, SUPPORTED_SHELLS,
+};
 use anyhow::Result;
 use clap::{Arg, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
 use std::env;
+use std::ffi::OsString;
 
 /// Displays runtime configuration information.
 pub fn print_runtime_info(config: &HowRuntimeConfig) {
@@ -25,14 +28,26 @@ pub fn print_runtime_info(config: &HowRuntimeConfig) {
     if let Some(shell) = &config.shell {
         println!("- Shell: {}", shell);
     }
-    println!("- Copy to clipboard: {}\n\n", config.copy_to_clipboard);
+    println!("- Copy to clipboard: {}", config.copy_to_clipboard);
+    println!("- Run command: {}", config.run_command);
+    println!("- Dry run: {}", config.dry_run);
+    if config.include_context {
+        println!(
+            "- Context: current folder and last {} history entries",
+            config.history_size
+        );
+    } else {
+        println!("- Context: none");
+    }
+    println!();
+    println!();
 }
 
 /// Parses command-line arguments into How configuration.
 ///
 /// # Errors
 /// Returns error if arguments are invalid or conflicting
-pub fn get_cli_arguments() -> Result<HowRuntimeConfig> {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<HowRuntimeConfig> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -48,17 +63,47 @@ pub fn get_cli_arguments() -> Result<HowRuntimeConfig> {
             .long("copy")
             .short('c')
             .action(clap::ArgAction::SetTrue)
-            .help("Copy the result to clipboard (Default: false)"))
+            .help(format!("Copy the top suggested command to clipboard. Set {}=true to always copy it. (Default: false)", AUTO_COPY_ENV)))
+        .arg(Arg::new("shell")
+            .long("shell")
+            .short('s')
+            .value_name("SHELL")
+            .value_parser(SUPPORTED_SHELLS)
+            .help("Shell the commands are generated for. (Default: detected from the environment)"))
+        .arg(Arg::new("run")
+            .long("run")
+            .short('r')
+            .action(clap::ArgAction::SetTrue)
+            .help("After showing the result, asks to run the top suggested command (press Enter to run it). Risky commands need 'yes' typed, and obviously destructive ones are never run. (Default: false)"))
+        .arg(Arg::new("dry-run")
+            .long("dry-run")
+            .short('d')
+            .action(clap::ArgAction::SetTrue)
+            .help("Explains what each part of the top suggested command does, and checks if it is safe to run, without running it. With --run, asks to run it after the explanation. (Default: false)"))
+        .arg(Arg::new("context")
+            .long("context")
+            .short('x')
+            .action(clap::ArgAction::SetTrue)
+            .help("Sends the current folder, its contents and the last shell history entries (bash, zsh, fish) along with the request, for more relevant suggestions. Obvious secrets are redacted first. (Default: false)"))
+        .arg(Arg::new("history")
+            .long("history")
+            .short('n')
+            .value_name("COUNT")
+            .value_parser(clap::value_parser!(usize))
+            .help(format!("How many shell history entries --context sends. 0 sends only the folder. (Default: {})", DEFAULT_HISTORY_SIZE)))
         .arg(Arg::new("command")
             .help("Command to fix (if not using --ask)")
             .num_args(0..)
             .trailing_var_arg(true)
             .action(clap::ArgAction::Append))
-        .get_matches();
+        .get_matches_from(argv);
 
     // Detect OS and shell
     let os = detect_os();
-    let shell = detect_shell();
+    let shell = matches
+        .get_one::<String>("shell")
+        .cloned()
+        .or_else(detect_shell);
 
     // Determine mode based on arguments
     let mode = if let Some(request) = matches.get_one::<String>("ask") {
@@ -77,9 +122,33 @@ pub fn get_cli_arguments() -> Result<HowRuntimeConfig> {
         ));
     };
 
-    let copy_to_clipboard = matches.get_flag("copy");
+    let copy_to_clipboard = matches.get_flag("copy") || is_auto_copy_enabled();
+
+    let run_command = matches.get_flag("run");
+    let dry_run = matches.get_flag("dry-run");
+    let include_context = matches.get_flag("context");
+    let history_size = matches
+        .get_one::<usize>("history")
+        .copied()
+        .unwrap_or(DEFAULT_HISTORY_SIZE);
+
+    Ok(HowRuntimeConfig::new(
+        mode,
+        copy_to_clipboard,
+        os,
+        shell,
+        run_command,
+        dry_run,
+        include_context,
+        history_size,
+    ))
+}
 
-    Ok(HowRuntimeConfig::new(mode, copy_to_clipboard, os, shell))
+/// Checks if the top command should be copied even without `--copy`.
+fn is_auto_copy_enabled() -> bool {
+    env::var(AUTO_COPY_ENV)
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+        .unwrap_or(false)
 }
 
 /// Detects the operating system.
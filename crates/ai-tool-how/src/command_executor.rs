@@ -0,0 +1,370 @@
+use anyhow::{Context, Result};
+use std::io::{stdin, stdout, IsTerminal, Write};
+use std::process::Command;
+
+/// Programs that can't be undone, stop things, or need elevation. Running them needs a typed `yes`.
+const DANGEROUS_PROGRAMS: [&str; 30] = [
+    "rm",
+    "rmdir",
+    "del",
+    "erase",
+    "rd",
+    "remove-item",
+    "shred",
+    "truncate",
+    "format",
+    "diskpart",
+    "fdisk",
+    "parted",
+    "kill",
+    "pkill",
+    "killall",
+    "taskkill",
+    "stop-process",
+    "shutdown",
+    "reboot",
+    "halt",
+    "poweroff",
+    "stop-computer",
+    "restart-computer",
+    "chmod",
+    "chown",
+    "icacls",
+    "sudo",
+    "su",
+    "doas",
+    "runas",
+];
+
+/// Shells that, at the end of a pipe, run whatever was downloaded.
+const PIPE_TO_SHELL_TARGETS: [&str; 6] =
+    ["sh", "bash", "zsh", "iex", "invoke-expression", "python"];
+
+/// Paths that are never a valid target of a recursive delete.
+const PROTECTED_PATHS: [&str; 8] = ["/", "/*", "~", "~/", "$home", "c:\\", "c:/", "*"];
+
+/// How risky a command is to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandRisk {
+    /// Runs after pressing Enter.
+    Safe,
+    /// Runs only after typing `yes`. Has the reason.
+    Dangerous(String),
+    /// Never runs from here. Has the reason.
+    Blocked(String),
+}
+
+/// Checks if a command is safe enough to run, before asking the user.
+///
+/// The check is a list of well-known patterns, not a sandbox: it stops the obvious mistakes (unfilled
+/// placeholders, wiping a disk, deleting the root folder) and flags commands that can't be undone.
+pub fn assess_command(command: &str) -> CommandRisk {
+    let lowered = command.to_lowercase();
+    let tokens = tokenize(&lowered);
+
+    if let Some(placeholder) = find_placeholder(command) {
+        return CommandRisk::Blocked(format!(
+            "it has a placeholder ({}) that must be replaced first",
+            placeholder
+        ));
+    }
+
+    if lowered.replace(' ', "").contains(":(){") {
+        return CommandRisk::Blocked("it looks like a fork bomb".to_string());
+    }
+
+    if tokens.iter().any(|token| token.starts_with("mkfs")) {
+        return CommandRisk::Blocked("it formats a disk".to_string());
+    }
+
+    if tokens.iter().any(|token| token.starts_with("of=/dev/"))
+        || lowered.contains("> /dev/sd")
+        || lowered.contains(">/dev/sd")
+        || lowered.contains("> /dev/nvme")
+    {
+        return CommandRisk::Blocked("it writes directly to a device".to_string());
+    }
+
+    if is_recursive_delete_of_protected_path(&tokens) {
+        return CommandRisk::Blocked("it recursively deletes a root or home folder".to_string());
+    }
+
+    if let Some(program) = tokens
+        .iter()
+        .map(|token| program_name(token))
+        .find(|program| DANGEROUS_PROGRAMS.contains(program))
+    {
+        return CommandRisk::Dangerous(format!(
+            "'{}' can delete data, stop processes, or needs elevated privileges",
+            program
+        ));
+    }
+
+    if is_pipe_to_shell(&tokens) {
+        return CommandRisk::Dangerous(
+            "it pipes content into a shell, running whatever it receives".to_string(),
+        );
+    }
+
+    if lowered.contains("git push") && (lowered.contains("--force") || lowered.contains(" -f")) {
+        return CommandRisk::Dangerous("it force-pushes, rewriting the remote history".to_string());
+    }
+
+    if lowered.contains("git reset --hard") || lowered.contains("git clean") {
+        return CommandRisk::Dangerous("it discards local changes".to_string());
+    }
+
+    CommandRisk::Safe
+}
+
+/// Asks the user to confirm, then runs the command in the given shell.
+///
+/// Safe commands run after pressing Enter. Dangerous ones need `yes` typed. Blocked ones never run.
+///
+/// # Errors
+/// Returns error if there's no terminal to ask on, or if the shell can't be started
+pub fn confirm_and_run(command: &str, shell: &Option<String>) -> Result<()> {
+    if !stdin().is_terminal() {
+        return Err(anyhow::anyhow!(
+            "Running the command needs an interactive terminal, to confirm it first."
+        ));
+    }
+
+    let risk = assess_command(command);
+    let expected_answer = match &risk {
+        CommandRisk::Blocked(reason) => {
+            eprintln!("Not running the command: {}.", reason);
+            return Ok(());
+        }
+        CommandRisk::Dangerous(reason) => {
+            eprintln!("Warning: {}.", reason);
+            print!("Type 'yes' to run it anyway, or anything else to cancel: ");
+            "yes"
+        }
+        CommandRisk::Safe => {
+            print!("Press Enter to run it, or type anything and press Enter to cancel: ");
+            ""
+        }
+    };
+    stdout().flush()?;
+
+    let mut answer = String::new();
+    stdin()
+        .read_line(&mut answer)
+        .context("Failed to read the confirmation")?;
+
+    if !answer.trim().eq_ignore_ascii_case(expected_answer) {
+        eprintln!("Cancelled. The command was not executed.");
+        return Ok(());
+    }
+
+    let exit_code = run_command(command, shell)?;
+    if exit_code != 0 {
+        eprintln!("Command exited with code {}.", exit_code);
+    }
+
+    Ok(())
+}
+
+/// Runs the command in the given shell (or the OS default), attached to the current terminal.
+///
+/// Returns the exit code of the command (`-1` if it was terminated by a signal).
+fn run_command(command: &str, shell: &Option<String>) -> Result<i32> {
+    let (program, args) = shell_invocation(shell);
+
+    let status = Command::new(program)
+        .args(args)
+        .arg(command)
+        .status()
+        .with_context(|| format!("Failed to start '{}' to run the command", program))?;
+
+    Ok(status.code().unwrap_or(-1))
+}
+
+/// Program and arguments that run a command line in the given shell.
+fn shell_invocation(shell: &Option<String>) -> (&'static str, &'static [&'static str]) {
+    match shell.as_deref() {
+        Some("powershell") if cfg!(windows) => ("powershell", &["-NoProfile", "-Command"]),
+        Some("powershell") | Some("pwsh") => ("pwsh", &["-NoProfile", "-Command"]),
+        Some("cmd") => ("cmd", &["/C"]),
+        Some("bash") => ("bash", &["-c"]),
+        Some("zsh") => ("zsh", &["-c"]),
+        Some("fish") => ("fish", &["-c"]),
+        _ if cfg!(windows) => ("cmd", &["/C"]),
+        _ => ("sh", &["-c"]),
+    }
+}
+
+/// Splits a command line into words and shell operators (`|`, `;`, `&&`, ...), ignoring quotes.
+fn tokenize(command: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (index, ch) in command.char_indices() {
+        let is_operator = matches!(ch, '|' | ';' | '&' | '(' | ')');
+        if ch.is_whitespace() || is_operator {
+            if let Some(token_start) = start.take() {
+                tokens.push(&command[token_start..index]);
+            }
+            if is_operator {
+                tokens.push(&command[index..index + ch.len_utf8()]);
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+
+    if let Some(token_start) = start {
+        tokens.push(&command[token_start..]);
+    }
+
+    tokens
+        .into_iter()
+        .map(|token| token.trim_matches(|c| c == '"' || c == '\''))
+        .collect()
+}
+
+/// Name of the program in a token, without its folder or `.exe` (`/bin/rm` -> `rm`).
+fn program_name(token: &str) -> &str {
+    let name = token.rsplit(['/', '\\']).next().unwrap_or(token);
+    name.strip_suffix(".exe").unwrap_or(name)
+}
+
+/// Finds placeholders left by the suggestions, like `<file>` or `<package_name>`.
+fn find_placeholder(command: &str) -> Option<&str> {
+    let mut rest = command;
+    while let Some(open) = rest.find('<') {
+        let after_open = &rest[open + 1..];
+        let Some(close) = after_open.find('>') else {
+            break;
+        };
+
+        let name = &after_open[..close];
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Some(&rest[open..open + close + 2]);
+        }
+
+        rest = after_open;
+    }
+
+    None
+}
+
+/// Checks for `rm -r`, `Remove-Item -Recurse` and friends pointed at `/`, `~` or a drive root.
+fn is_recursive_delete_of_protected_path(tokens: &[&str]) -> bool {
+    tokens
+        .split(|token| matches!(*token, "|" | ";" | "&"))
+        .any(|segment| {
+            let Some(program) = segment.iter().find(|token| !is_elevation(token)) else {
+                return false;
+            };
+
+            let recursive = match program_name(program) {
+                "rm" => segment.iter().any(|token| {
+                    (token.starts_with('-') && !token.starts_with("--") && token.contains('r'))
+                        || *token == "--recursive"
+                }),
+                "remove-item" | "rd" | "rmdir" | "del" => segment
+                    .iter()
+                    .any(|token| *token == "-recurse" || *token == "/s"),
+                _ => false,
+            };
+
+            recursive
+                && segment
+                    .iter()
+                    .skip(1)
+                    .any(|token| PROTECTED_PATHS.contains(token))
+        })
+}
+
+/// Checks for a download piped into a shell, like `curl ... | sh`.
+fn is_pipe_to_shell(tokens: &[&str]) -> bool {
+    tokens
+        .windows(2)
+        .any(|pair| pair[0] == "|" && PIPE_TO_SHELL_TARGETS.contains(&program_name(pair[1])))
+        || tokens.windows(3).any(|triple| {
+            triple[0] == "|"
+                && is_elevation(triple[1])
+                && PIPE_TO_SHELL_TARGETS.contains(&triple[2])
+        })
+}
+
+fn is_elevation(token: &str) -> bool {
+    matches!(token, "sudo" | "doas")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assess_command_sorts_commands_by_risk() {
+        assert_eq!(
+            assess_command("find . -iname \"*bacon*\""),
+            CommandRisk::Safe
+        );
+        assert_eq!(assess_command("ps aux | grep chrome"), CommandRisk::Safe);
+        assert_eq!(assess_command("Get-ChildItem -Recurse"), CommandRisk::Safe);
+
+        assert!(matches!(
+            assess_command("rm -ri old_logs"),
+            CommandRisk::Dangerous(_)
+        ));
+        assert!(matches!(
+            assess_command("sudo apt install ripgrep"),
+            CommandRisk::Dangerous(_)
+        ));
+        assert!(matches!(
+            assess_command("Stop-Process -Name chrome -Force"),
+            CommandRisk::Dangerous(_)
+        ));
+        assert!(matches!(
+            assess_command("curl -fsSL https://example.com/install.sh | sh"),
+            CommandRisk::Dangerous(_)
+        ));
+
+        assert!(matches!(
+            assess_command("sudo rm -rf /"),
+            CommandRisk::Blocked(_)
+        ));
+        assert!(matches!(
+            assess_command("rm -fr ~"),
+            CommandRisk::Blocked(_)
+        ));
+        assert!(matches!(
+            assess_command("Remove-Item -Recurse -Force C:\\"),
+            CommandRisk::Blocked(_)
+        ));
+        assert!(matches!(
+            assess_command("mkfs.ext4 /dev/sdb1"),
+            CommandRisk::Blocked(_)
+        ));
+        assert!(matches!(
+            assess_command("dd if=/dev/zero of=/dev/sda"),
+            CommandRisk::Blocked(_)
+        ));
+        assert!(matches!(
+            assess_command(":(){ :|:& };:"),
+            CommandRisk::Blocked(_)
+        ));
+    }
+
+    #[test]
+    fn assess_command_blocks_unfilled_placeholders() {
+        assert_eq!(
+            assess_command("winget install <package_name>"),
+            CommandRisk::Blocked(
+                "it has a placeholder (<package_name>) that must be replaced first".to_string()
+            )
+        );
+        assert_eq!(
+            assess_command("sort < input.txt > sorted.txt"),
+            CommandRisk::Safe
+        );
+    }
+}
@@ -1,5 +1,9 @@
-use crate::ai_functions::{fix_cli_command_as_string, suggest_cli_command_as_string};
+use crate::ai_functions::{
+    explain_cli_command_as_string, fix_cli_command_as_string, suggest_cli_command_as_string,
+};
+use crate::command_executor::{assess_command, confirm_and_run, CommandRisk};
 use crate::models::{HowMode, HowRuntimeConfig};
+use crate::shell_context::build_shell_context;
 use ai_shared::message_builders::system_message_builders::{
     build_rust_ai_function_system_message, build_rust_ai_function_user_message,
 };
@@ -11,7 +15,9 @@ use shared::utils::copy_string_to_clipboard::copy_to_clipboard;
 /// Starts the How application with the given configuration.
 ///
 /// This function handles both command fixing and command suggestion modes,
-/// optionally copying results to clipboard.
+/// optionally sending the shell context (current folder and history) along,
+/// copying results to clipboard, explaining the top command (dry run) and running
+/// it after confirmation.
 ///
 /// # Arguments
 /// * `config` - Runtime configuration containing mode, OS info, and options
@@ -19,10 +25,18 @@ use shared::utils::copy_string_to_clipboard::copy_to_clipboard;
 /// # Errors
 /// Returns error if AI request fails, clipboard operation fails, or other system errors
 pub async fn start_how_app(config: HowRuntimeConfig) -> Result<()> {
+    let context = if config.include_context {
+        Some(build_shell_context(&config.shell, config.history_size)?)
+    } else {
+        None
+    };
+
     let result = match &config.mode {
-        HowMode::FixCommand(command) => fix_command(command, &config.os, &config.shell).await?,
+        HowMode::FixCommand(command) => {
+            fix_command(command, &config.os, &config.shell, &context).await?
+        }
         HowMode::SuggestCommand(request) => {
-            suggest_command(request, &config.os, &config.shell).await?
+            suggest_command(request, &config.os, &config.shell, &context).await?
         }
     };
 
@@ -30,11 +44,34 @@ pub async fn start_how_app(config: HowRuntimeConfig) -> Result<()> {
     println!("Result:");
     println!("{}", result);
 
-    // Copy to clipboard if requested
+    // Copy the top command to clipboard if requested, so it can be pasted right away
     if config.copy_to_clipboard {
-        copy_to_clipboard(&result)
+        let command = top_command(&result);
+        copy_to_clipboard(command)
             .map_err(|e| anyhow::anyhow!("Failed to copy result to clipboard: {}", e))?;
-        eprintln!("Copied to clipboard");
+        eprintln!("Copied to clipboard: {}", command);
+    }
+
+    if config.dry_run {
+        let command = top_command(&result);
+        let explanation = explain_command(command, &config.os, &config.shell).await?;
+
+        println!("\nExplanation of: {}", command);
+        println!("{}", explanation);
+        println!(
+            "\nSafety check: {}",
+            describe_risk(&assess_command(command))
+        );
+
+        if !config.run_command {
+            eprintln!("\nDry run: the command was not executed. Use --run to run it.");
+        }
+    }
+
+    if config.run_command {
+        let command = top_command(&result);
+        println!("\nCommand to run: {}", command);
+        confirm_and_run(command, &config.shell)?;
     }
 
     Ok(())
@@ -46,12 +83,18 @@ pub async fn start_how_app(config: HowRuntimeConfig) -> Result<()> {
 /// * `command` - The command to fix
 /// * `os` - The target operating system
 /// * `shell` - Optional shell information
+/// * `context` - Optional shell context (current folder and recent commands)
 ///
 /// # Errors
 /// Returns error if AI request fails or returns an invalid response
-async fn fix_command(command: &str, os: &str, shell: &Option<String>) -> Result<String> {
+async fn fix_command(
+    command: &str,
+    os: &str,
+    shell: &Option<String>,
+    context: &Option<String>,
+) -> Result<String> {
     let os_info = build_os_info(os, shell);
-    let input = format!("command: {}\n{}", command, os_info);
+    let input = append_context(format!("command: {}\n{}", command, os_info), context);
 
     let mut requester = build_requester_for_ai().context("Failed to build AI requester")?;
 
@@ -82,12 +125,18 @@ async fn fix_command(command: &str, os: &str, shell: &Option<String>) -> Result<
 /// * `request` - The natural language request
 /// * `os` - The target operating system
 /// * `shell` - Optional shell information
+/// * `context` - Optional shell context (current folder and recent commands)
 ///
 /// # Errors
 /// Returns error if AI request fails or returns invalid response
-async fn suggest_command(request: &str, os: &str, shell: &Option<String>) -> Result<String> {
+async fn suggest_command(
+    request: &str,
+    os: &str,
+    shell: &Option<String>,
+    context: &Option<String>,
+) -> Result<String> {
     let os_info = build_os_info(os, shell);
-    let input = format!("request: {}\n: {}", request, os_info);
+    let input = append_context(format!("request: {}\n{}", request, os_info), context);
 
     let mut requester = build_requester_for_ai().context("Failed to build AI requester")?;
 
@@ -112,6 +161,70 @@ async fn suggest_command(request: &str, os: &str, shell: &Option<String>) -> Res
     Ok(response.message.trim().to_string())
 }
 
+/// Explains what each part of a command does using AI.
+///
+/// # Arguments
+/// * `command` - The command to explain
+/// * `os` - The target operating system
+/// * `shell` - Optional shell information
+///
+/// # Errors
+/// Returns error if AI request fails or returns an invalid response
+async fn explain_command(command: &str, os: &str, shell: &Option<String>) -> Result<String> {
+    let os_info = build_os_info(os, shell);
+    let input = format!("command: {}\n{}", command, os_info);
+
+    let mut requester = build_requester_for_ai().context("Failed to build AI requester")?;
+
+    let system_message = build_rust_ai_function_system_message();
+    let user_message = build_rust_ai_function_user_message(explain_cli_command_as_string, &input);
+
+    requester
+        .set_system_message(system_message)?
+        .initialize_api_client()?;
+
+    let response = requester
+        .send_request(user_message, false)
+        .await
+        .context("Failed to get AI response for command explanation")?;
+
+    if !response.success || response.message.trim().is_empty() {
+        return Err(anyhow::anyhow!(
+            "AI returned empty response. Please try again or report this issue."
+        ));
+    }
+
+    Ok(response.message.trim().to_string())
+}
+
+/// Describes the result of the safety check, for the dry run.
+fn describe_risk(risk: &CommandRisk) -> String {
+    match risk {
+        CommandRisk::Safe => "no known risks".to_string(),
+        CommandRisk::Dangerous(reason) => format!("risky, {} (needs 'yes' to run)", reason),
+        CommandRisk::Blocked(reason) => format!("blocked, {} (will not run)", reason),
+    }
+}
+
+/// Returns the first command of the result.
+///
+/// Suggestions can have up to three commands, one per line. Stray backticks are removed.
+fn top_command(result: &str) -> &str {
+    result
+        .lines()
+        .map(|line| line.trim().trim_matches('`').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or(result)
+}
+
+/// Adds the shell context, if any, to the input of an AI function.
+fn append_context(input: String, context: &Option<String>) -> String {
+    match context {
+        Some(context) => format!("{}\ncontext:\n{}", input, context),
+        None => input,
+    }
+}
+
 /// Builds OS information string for AI functions.
 ///
 /// Combines OS name with shell information if available.
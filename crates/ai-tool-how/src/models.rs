@@ -1,9 +1,25 @@
+/// Environment variable that, when set to `true`, copies the top command without `--copy`.
+pub const AUTO_COPY_ENV: &str = "HOW_AUTO_COPY";
+
+/// History entries sent with `--context`, when `--history` is not set.
+pub const DEFAULT_HISTORY_SIZE: usize = 10;
+
+/// Names of the current folder sent with `--context`, at most.
+pub const MAX_DIRECTORY_ENTRIES: usize = 50;
+
+/// Shells accepted by `--shell`.
+pub const SUPPORTED_SHELLS: [&str; 4] = ["bash", "zsh", "powershell", "cmd"];
+
 #[derive(Debug)]
 pub struct HowRuntimeConfig {
     pub mode: HowMode,
     pub copy_to_clipboard: bool,
     pub os: String,
     pub shell: Option<String>,
+    pub run_command: bool,
+    pub dry_run: bool,
+    pub include_context: bool,
+    pub history_size: usize,
 }
 
 #[derive(Debug)]
@@ -13,12 +29,26 @@ pub enum HowMode {
 }
 
 impl HowRuntimeConfig {
-    pub fn new(mode: HowMode, copy_to_clipboard: bool, os: String, shell: Option<String>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mode: HowMode,
+        copy_to_clipboard: bool,
+        os: String,
+        shell: Option<String>,
+        run_command: bool,
+        dry_run: bool,
+        include_context: bool,
+        history_size: usize,
+    ) -> Self {
         Self {
             mode,
             copy_to_clipboard,
             os,
             shell,
+            run_command,
+            dry_run,
+            include_context,
+            history_size,
         }
     }
 }
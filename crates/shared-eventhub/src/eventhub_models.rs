@@ -1,12 +1,14 @@
 use crate::eventhub_traits::{
     default_base_data_folder, default_consumer_group, default_database_path,
     default_export_base_data_folder, default_export_folder, default_export_format,
-    default_feedback_interval, default_include_metadata, default_partition_id,
-    default_received_msg_path,
+    default_export_workers, default_feedback_interval, default_include_metadata,
+    default_partition_id, default_received_msg_path, default_send_batch_size,
 };
+use crate::utils::partition_selection::{PartitionList, PartitionSelection};
 
 use anyhow::{anyhow, Error};
 use azeventhubs::consumer::{EventHubConsumerClient, EventHubConsumerClientOptions};
+use azeventhubs::producer::{EventHubProducerClient, EventHubProducerClientOptions};
 use azeventhubs::BasicRetryPolicy;
 use chrono::{DateTime, Local, Utc};
 use serde::{Deserialize, Serialize};
@@ -27,6 +29,9 @@ pub struct EventHubConfig {
 
     #[serde(default)]
     pub export_config: ExportConfig,
+
+    #[serde(default)]
+    pub outbound_config: OutboundConfig,
 }
 
 impl EventHubConfig {
@@ -45,6 +50,21 @@ impl EventHubConfig {
             Err(err) => Err(err.into()),
         }
     }
+
+    pub async fn create_producer_client(
+        &self,
+    ) -> Result<EventHubProducerClient<BasicRetryPolicy>, Error> {
+        match EventHubProducerClient::new_from_connection_string(
+            &self.connection_string,
+            self.entity_path.clone(),
+            EventHubProducerClientOptions::default(),
+        )
+        .await
+        {
+            Ok(client) => Ok(client),
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,8 +72,13 @@ pub struct InboundConfig {
     #[serde(default = "default_consumer_group")]
     pub consumer_group: String,
 
+    /// Partitions to read: `-1` for all, a single id, or a list/range like `"0-3,7"`.
     #[serde(default = "default_partition_id")]
-    pub partition_id: i32,
+    pub partition_id: PartitionSelection,
+
+    /// Partitions to skip, even if selected by `partition_id` (e.g.: `"4,6-7"`).
+    #[serde(default)]
+    pub exclude_partition_ids: PartitionList,
 
     #[serde(default = "default_received_msg_path")]
     pub received_msg_path: String,
@@ -78,18 +103,151 @@ pub struct InboundConfig {
 
     #[serde(default)]
     pub dump_filter: Option<Vec<String>>,
+
+    /// Steps applied, in order, to the body of each message before it is filtered and stored.
+    #[serde(default)]
+    pub transforms: Vec<TransformStep>,
+
+    /// Address (`host:port`) of the Prometheus `/metrics` endpoint. Disabled when not set.
+    #[serde(default)]
+    pub metrics_address: Option<String>,
 }
 
 impl InboundConfig {
     pub fn get_partition_id_label(&self) -> String {
-        if self.partition_id == -1 {
-            "ALL".to_string()
+        let label = self.partition_id.label();
+
+        if self.exclude_partition_ids.is_empty() {
+            label
         } else {
-            self.partition_id.to_string()
+            format!(
+                "{} (excluding {})",
+                label,
+                self.exclude_partition_ids.label()
+            )
         }
     }
 }
 
+/// Settings of `eh-send`, used to publish messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboundConfig {
+    /// Messages sent together, in a single batch.
+    #[serde(default = "default_send_batch_size")]
+    pub batch_size: usize,
+
+    /// Partition key of every message.
+    #[serde(default)]
+    pub partition_key: Option<String>,
+
+    /// Field of the JSON body used as partition key (e.g.: `device.id`). Messages without the
+    /// field are sent without a key.
+    #[serde(default)]
+    pub partition_key_field: Option<String>,
+
+    /// Partition every message is sent to. Can't be used with a partition key.
+    #[serde(default)]
+    pub partition_id: Option<String>,
+
+    /// Maximum messages sent per second. No limit when not set.
+    #[serde(default)]
+    pub rate_limit: Option<f64>,
+
+    #[serde(default = "default_feedback_interval")]
+    pub feedback_interval: u64,
+}
+
+/// A transformation applied to the body of the messages read.
+///
+/// In the config file, each step is an object with its `type` and settings:
+/// `{ "type": "regex_replace", "pattern": "\\d{16}", "replacement": "****" }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransformStep {
+    /// Decompresses a gzip body.
+    GzipDecompress,
+    /// Decodes a base64 body (padding is optional).
+    Base64Decode,
+    /// Keeps only these fields of a JSON body. Nested fields are separated by dots (`device.id`).
+    JsonProject { fields: Vec<String> },
+    /// Replaces every match of `pattern`. The replacement can use the groups (`$1`, `${name}`).
+    RegexReplace {
+        pattern: String,
+        #[serde(default)]
+        replacement: String,
+    },
+}
+
+impl TransformStep {
+    pub fn label(&self) -> String {
+        match self {
+            TransformStep::GzipDecompress => "gzip_decompress".to_string(),
+            TransformStep::Base64Decode => "base64_decode".to_string(),
+            TransformStep::JsonProject { fields } => {
+                format!("json_project({})", fields.join(", "))
+            }
+            TransformStep::RegexReplace { pattern, .. } => format!("regex_replace({})", pattern),
+        }
+    }
+}
+
+/// Object storage the exported files are written to.
+///
+/// In the config file, an object with its `type` and settings:
+/// `{ "type": "s3", "bucket": "exports", "region": "us-east-1", "prefix": "eventhub" }`.
+/// Credentials left out are read from the environment (e.g.: `AWS_ACCESS_KEY_ID`).
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ObjectStorageConfig {
+    AzureBlob {
+        /// Storage account connection string (`AccountName=...;AccountKey=...`, or with a
+        /// `SharedAccessSignature`).
+        connection_string: String,
+        container: String,
+        /// Folder, in the container, the files are written to.
+        #[serde(default)]
+        prefix: String,
+    },
+    S3 {
+        bucket: String,
+        #[serde(default)]
+        region: Option<String>,
+        #[serde(default)]
+        access_key_id: Option<String>,
+        #[serde(default)]
+        secret_access_key: Option<String>,
+        /// For S3 compatible storages (e.g.: `http://localhost:9000` for MinIO).
+        #[serde(default)]
+        endpoint: Option<String>,
+        /// Folder, in the bucket, the files are written to.
+        #[serde(default)]
+        prefix: String,
+    },
+}
+
+impl ObjectStorageConfig {
+    /// Where the files go, without the credentials.
+    pub fn label(&self) -> String {
+        let (kind, location, prefix) = match self {
+            ObjectStorageConfig::AzureBlob {
+                container, prefix, ..
+            } => ("Azure Blob", container, prefix),
+            ObjectStorageConfig::S3 { bucket, prefix, .. } => ("S3", bucket, prefix),
+        };
+
+        format!("{}: {}/{}", kind, location, prefix.trim_matches('/'))
+            .trim_end_matches('/')
+            .to_string()
+    }
+}
+
+/// Shows only the label, so the credentials don't end up in logs.
+impl std::fmt::Debug for ObjectStorageConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ObjectStorageConfig({})", self.label())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportConfig {
     #[serde(default = "default_export_base_data_folder")]
@@ -121,6 +279,24 @@ pub struct ExportConfig {
 
     #[serde(default = "default_database_path")]
     pub database_path: String,
+
+    #[serde(default = "default_export_workers")]
+    pub workers: usize,
+
+    /// Database to export from, used as-is instead of the one `eh-read` derives from the
+    /// connection string (e.g.: a capture made by `mqtt read --persist`).
+    #[serde(default)]
+    pub source_database: Option<String>,
+
+    /// Predicates a message must meet to be exported (e.g.: `$.device.id=abc`,
+    /// `queued>=2026-10-01`, `partition=0-3`). Parsed by `eh-export`.
+    #[serde(default)]
+    pub where_filter: Option<Vec<String>>,
+
+    /// Bucket or container the exported files are uploaded to, instead of being kept in the
+    /// export folder.
+    #[serde(default)]
+    pub object_storage: Option<ObjectStorageConfig>,
 }
 
 impl ExportConfig {
@@ -140,6 +316,10 @@ impl ExportConfig {
             ));
         }
 
+        if self.workers == 0 {
+            return Err(anyhow!("workers must be at least 1"));
+        }
+
         Ok(())
     }
 }
@@ -1,10 +1,12 @@
-use crate::eventhub_models::{ExportConfig, InboundConfig};
+use crate::eventhub_models::{ExportConfig, InboundConfig, OutboundConfig};
+use crate::utils::partition_selection::{PartitionList, PartitionSelection};
 
 impl Default for InboundConfig {
     fn default() -> Self {
         Self {
             consumer_group: default_consumer_group(),
             partition_id: default_partition_id(),
+            exclude_partition_ids: PartitionList::default(),
             received_msg_path: default_received_msg_path(),
             database_path: default_database_path(),
             base_data_folder: default_base_data_folder(),
@@ -13,6 +15,8 @@ impl Default for InboundConfig {
             ignore_checkpoint: false,
             dump_content_only: false,
             dump_filter: None,
+            transforms: Vec::new(),
+            metrics_address: None,
         }
     }
 }
@@ -30,6 +34,23 @@ impl Default for ExportConfig {
             feedback_interval: default_feedback_interval(),
             use_local_time: false,
             database_path: default_database_path(),
+            workers: default_export_workers(),
+            source_database: None,
+            where_filter: None,
+            object_storage: None,
+        }
+    }
+}
+
+impl Default for OutboundConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: default_send_batch_size(),
+            partition_key: None,
+            partition_key_field: None,
+            partition_id: None,
+            rate_limit: None,
+            feedback_interval: default_feedback_interval(),
         }
     }
 }
@@ -40,8 +61,8 @@ pub fn default_consumer_group() -> String {
 }
 
 #[allow(dead_code)] //Being used as the default value
-pub fn default_partition_id() -> i32 {
-    -1
+pub fn default_partition_id() -> PartitionSelection {
+    PartitionSelection::All
 }
 
 #[allow(dead_code)] //Being used as the default value
@@ -83,3 +104,15 @@ pub fn default_include_metadata() -> bool {
 pub fn default_export_folder() -> String {
     "exports".to_string()
 }
+
+#[allow(dead_code)] //Being used as the default value
+pub fn default_export_workers() -> usize {
+    std::thread::available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(4)
+}
+
+#[allow(dead_code)] //Being used as the default value
+pub fn default_send_batch_size() -> usize {
+    100
+}
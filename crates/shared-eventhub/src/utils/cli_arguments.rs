@@ -5,6 +5,7 @@ pub trait CommandCommonExt {
     fn add_eh_base_shared_args(self) -> Self;
     fn add_eh_reader_args(self) -> Self;
     fn add_eh_export_args(self) -> Self;
+    fn add_eh_send_args(self) -> Self;
 }
 
 impl CommandCommonExt for Command {
@@ -67,9 +68,15 @@ impl CommandCommonExt for Command {
                 Arg::new("partition-id")
                     .short('p')
                     .long("partition-id")
-                    .value_name("NUMBER")
-                    .help("Partition ID to read from (-1 for all partitions, default: -1)")
-                    .value_parser(clap::value_parser!(i32)),
+                    .value_name("LIST")
+                    .help("Partition(s) to read from: -1 for all, an ID, or a list/range like 0-3,7 (default: -1)")
+                    .allow_hyphen_values(true),
+            )
+            .arg(
+                Arg::new("exclude-partitions")
+                    .long("exclude-partitions")
+                    .value_name("LIST")
+                    .help("Partition(s) to skip, as an ID or a list/range like 4,6-7"),
             )
             .arg(
                 Arg::new("received-msg-path")
@@ -93,6 +100,12 @@ impl CommandCommonExt for Command {
                     .action(clap::ArgAction::SetTrue)
                     .help("When exporting to file, save only message content (not metadata)"),
             )
+            .arg(
+                Arg::new("metrics-address")
+                    .long("metrics-address")
+                    .value_name("HOST:PORT")
+                    .help("Serves Prometheus metrics on http://HOST:PORT/metrics (default: disabled)"),
+            )
     }
 
     fn add_eh_export_args(self) -> Self {
@@ -144,5 +157,83 @@ impl CommandCommonExt for Command {
                     "Relative path within base folder for export tracking database (default: db)",
                 ),
         )
+        .arg(
+            Arg::new("workers")
+                .short('w')
+                .long("workers")
+                .value_name("N")
+                .help("Number of threads formatting messages in parallel (default: number of CPU cores)")
+                .value_parser(clap::value_parser!(usize).range(1..)),
+        )
+        .arg(
+            Arg::new("source-db")
+                .long("source-db")
+                .value_name("PATH")
+                .help("Database to export from, instead of the one eh-read created for the connection string (e.g.: one created by mqtt read --persist). The connection string is not needed"),
+        )
+        .arg(
+            Arg::new("where")
+                .long("where")
+                .value_name("PREDICATE")
+                .action(clap::ArgAction::Append)
+                .help("Only exports messages matching the predicate. Can be repeated, and all must match. Examples: $.device.id=abc, queued>=2026-10-01, partition=0-3"),
+        )
+    }
+    fn add_eh_send_args(self) -> Self {
+        self.preset_arg_connection_string("EventHub connection string")
+            .arg(
+                Arg::new("entity-path")
+                    .short('e')
+                    .long("entity-path")
+                    .value_name("STRING")
+                    .help("EventHub entity path (name)"),
+            )
+            .arg(
+                Arg::new("input")
+                    .value_name("PATH")
+                    .help("File with one message per line, or folder with one message per file. Reads from stdin when not informed, or when it is -"),
+            )
+            .arg(
+                Arg::new("batch-size")
+                    .long("batch-size")
+                    .value_name("N")
+                    .help("Messages sent together, in a single batch (default: 100)")
+                    .value_parser(clap::value_parser!(usize).range(1..)),
+            )
+            .arg(
+                Arg::new("partition-key")
+                    .short('k')
+                    .long("partition-key")
+                    .value_name("KEY")
+                    .help("Partition key of every message"),
+            )
+            .arg(
+                Arg::new("partition-key-field")
+                    .long("partition-key-field")
+                    .value_name("FIELD")
+                    .help("Field of the JSON messages used as partition key (e.g.: device.id)"),
+            )
+            .arg(
+                Arg::new("partition-id")
+                    .short('p')
+                    .long("partition-id")
+                    .value_name("ID")
+                    .help("Partition every message is sent to (default: chosen by the EventHub)"),
+            )
+            .arg(
+                Arg::new("rate")
+                    .short('r')
+                    .long("rate")
+                    .value_name("MSG/S")
+                    .help("Maximum messages sent per second (default: no limit)")
+                    .value_parser(clap::value_parser!(f64)),
+            )
+            .arg(
+                Arg::new("feedback-interval")
+                    .long("feedback-interval")
+                    .value_name("SECONDS")
+                    .help("Show progress feedback every N seconds (default: 1)")
+                    .value_parser(clap::value_parser!(u64)),
+            )
     }
 }
@@ -28,6 +28,7 @@ pub async fn get_base_config_object(
             verbose: false,
             inbound_config: InboundConfig::default(),
             export_config: Default::default(),
+            outbound_config: Default::default(),
         })
     }
 }
@@ -2,3 +2,4 @@ pub mod cli_arguments;
 pub mod config_utils;
 pub mod extract_eventhub_endpoint_from_connection_string;
 pub mod get_eventhub_database_path;
+pub mod partition_selection;
@@ -0,0 +1,356 @@
+use anyhow::{anyhow, bail, Context, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Raw value of a partition setting in the config file. Accepts both the legacy number
+/// (`"partition_id": 0` or `-1`) and the list/range text (`"partition_id": "0-3,7"`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum PartitionIdValue {
+    Number(i64),
+    Text(String),
+}
+
+/// Partitions a reader should consume: all of them, or only the ones listed.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(try_from = "PartitionIdValue", into = "PartitionIdValue")]
+pub enum PartitionSelection {
+    #[default]
+    All,
+    Only(BTreeSet<u32>),
+}
+
+/// List of partitions, used to exclude partitions from the selection.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(try_from = "PartitionIdValue", into = "PartitionIdValue")]
+pub struct PartitionList(BTreeSet<u32>);
+
+impl PartitionSelection {
+    /// Parses `-1`, `all`, or `*` as all partitions; anything else as a list/range (e.g.: `0-3,7`).
+    pub fn parse(value: &str) -> Result<Self> {
+        let trimmed = value.trim();
+
+        if trimmed == "-1" || trimmed == "*" || trimmed.eq_ignore_ascii_case("all") {
+            return Ok(PartitionSelection::All);
+        }
+
+        Ok(PartitionSelection::Only(parse_partition_list(trimmed)?))
+    }
+
+    /// Returns the partition to read when the selection boils down to exactly one partition.
+    pub fn single_partition(&self, excluded: &PartitionList) -> Option<String> {
+        match self {
+            PartitionSelection::Only(ids) => {
+                let mut remaining = ids.iter().filter(|id| !excluded.contains(**id));
+                match (remaining.next(), remaining.next()) {
+                    (Some(id), None) => Some(id.to_string()),
+                    _ => None,
+                }
+            }
+            PartitionSelection::All => None,
+        }
+    }
+
+    /// Applies the selection and the exclusions to the partitions available in the EventHub.
+    ///
+    /// # Errors
+    /// Returns an error if a selected partition doesn't exist, or if nothing is left to read.
+    pub fn resolve(&self, available: &[String], excluded: &PartitionList) -> Result<Vec<String>> {
+        if let PartitionSelection::Only(ids) = self {
+            let missing: BTreeSet<u32> = ids
+                .iter()
+                .filter(|id| !available.contains(&id.to_string()))
+                .copied()
+                .collect();
+
+            if !missing.is_empty() {
+                bail!(
+                    "Partition(s) [{}] not found. Available partitions: [{}]",
+                    format_partition_list(&missing),
+                    available.join(",")
+                );
+            }
+        }
+
+        let selected: Vec<String> = available
+            .iter()
+            .filter(|id| self.includes(id))
+            .filter(|id| !excluded.contains_str(id))
+            .cloned()
+            .collect();
+
+        if selected.is_empty() {
+            bail!(
+                "No partitions left to read after applying the partition selection and exclusions."
+            );
+        }
+
+        Ok(selected)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            PartitionSelection::All => "ALL".to_string(),
+            PartitionSelection::Only(ids) => format_partition_list(ids),
+        }
+    }
+
+    fn includes(&self, partition_id: &str) -> bool {
+        match self {
+            PartitionSelection::All => true,
+            PartitionSelection::Only(ids) => partition_id
+                .parse::<u32>()
+                .map(|id| ids.contains(&id))
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl PartitionList {
+    /// Parses a list/range like `4,6-7`. An empty value means no partitions.
+    pub fn parse(value: &str) -> Result<Self> {
+        if value.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        Ok(Self(parse_partition_list(value)?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, partition_id: u32) -> bool {
+        self.0.contains(&partition_id)
+    }
+
+    pub fn contains_str(&self, partition_id: &str) -> bool {
+        partition_id
+            .parse::<u32>()
+            .map(|id| self.contains(id))
+            .unwrap_or(false)
+    }
+
+    pub fn label(&self) -> String {
+        format_partition_list(&self.0)
+    }
+}
+
+impl TryFrom<PartitionIdValue> for PartitionSelection {
+    type Error = Error;
+
+    fn try_from(value: PartitionIdValue) -> Result<Self> {
+        match value {
+            PartitionIdValue::Number(-1) => Ok(PartitionSelection::All),
+            PartitionIdValue::Number(n) => {
+                let id = u32::try_from(n).map_err(|_| {
+                    anyhow!("Invalid partition_id: {}. Use -1 for all partitions.", n)
+                })?;
+                Ok(PartitionSelection::Only(BTreeSet::from([id])))
+            }
+            PartitionIdValue::Text(text) => PartitionSelection::parse(&text),
+        }
+    }
+}
+
+impl From<PartitionSelection> for PartitionIdValue {
+    fn from(value: PartitionSelection) -> Self {
+        match value {
+            PartitionSelection::All => PartitionIdValue::Number(-1),
+            PartitionSelection::Only(ids) if ids.len() == 1 => {
+                PartitionIdValue::Number(ids.iter().next().map(|id| *id as i64).unwrap_or(-1))
+            }
+            PartitionSelection::Only(ids) => PartitionIdValue::Text(format_partition_list(&ids)),
+        }
+    }
+}
+
+impl TryFrom<PartitionIdValue> for PartitionList {
+    type Error = Error;
+
+    fn try_from(value: PartitionIdValue) -> Result<Self> {
+        match value {
+            PartitionIdValue::Number(n) => {
+                let id = u32::try_from(n).map_err(|_| anyhow!("Invalid partition id: {}", n))?;
+                Ok(Self(BTreeSet::from([id])))
+            }
+            PartitionIdValue::Text(text) => PartitionList::parse(&text),
+        }
+    }
+}
+
+impl From<PartitionList> for PartitionIdValue {
+    fn from(value: PartitionList) -> Self {
+        PartitionIdValue::Text(value.label())
+    }
+}
+
+/// Parses a comma-separated list of partition ids and inclusive ranges, like `0-3,7`.
+///
+/// # Errors
+/// Returns an error if an entry is not a number/range, a range is reversed, or the list is empty.
+pub fn parse_partition_list(value: &str) -> Result<BTreeSet<u32>> {
+    let mut ids = BTreeSet::new();
+
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once('-') {
+            Some((start, end)) => {
+                let start = parse_partition_number(start, entry)?;
+                let end = parse_partition_number(end, entry)?;
+
+                if start > end {
+                    bail!(
+                        "Invalid partition range [{}]: start is greater than end.",
+                        entry
+                    );
+                }
+
+                ids.extend(start..=end);
+            }
+            None => {
+                ids.insert(parse_partition_number(entry, entry)?);
+            }
+        }
+    }
+
+    if ids.is_empty() {
+        bail!("No partitions informed in [{}].", value);
+    }
+
+    Ok(ids)
+}
+
+fn parse_partition_number(value: &str, entry: &str) -> Result<u32> {
+    value.trim().parse::<u32>().context(format!(
+        "Invalid partition [{}]. Use ids or ranges like 0-3,7.",
+        entry
+    ))
+}
+
+/// Formats partition ids compressing consecutive ids into ranges: `{0,1,2,3,7}` -> `0-3,7`.
+pub fn format_partition_list(ids: &BTreeSet<u32>) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut iter = ids.iter().copied().peekable();
+
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end += 1;
+            iter.next();
+        }
+
+        if start == end {
+            parts.push(start.to_string());
+        } else {
+            parts.push(format!("{}-{}", start, end));
+        }
+    }
+
+    parts.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn available(count: u32) -> Vec<String> {
+        (0..count).map(|id| id.to_string()).collect()
+    }
+
+    #[rstest]
+    #[case("0-3,7", vec![0, 1, 2, 3, 7])]
+    #[case(" 5 ", vec![5])]
+    #[case("2,1,1-2", vec![1, 2])]
+    #[case("0-0,9", vec![0, 9])]
+    fn test_parse_partition_list(#[case] input: &str, #[case] expected: Vec<u32>) {
+        let ids = parse_partition_list(input).unwrap();
+
+        assert_eq!(ids.into_iter().collect::<Vec<u32>>(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("a")]
+    #[case("3-1")]
+    #[case("1-x")]
+    #[case("-2")]
+    fn test_parse_partition_list_rejects_invalid_values(#[case] input: &str) {
+        assert!(parse_partition_list(input).is_err());
+    }
+
+    #[rstest]
+    #[case("-1")]
+    #[case("all")]
+    #[case("*")]
+    fn test_selection_parse_all(#[case] input: &str) {
+        assert_eq!(
+            PartitionSelection::parse(input).unwrap(),
+            PartitionSelection::All
+        );
+    }
+
+    #[test]
+    fn test_format_partition_list_compresses_ranges() {
+        let ids = BTreeSet::from([0, 1, 2, 3, 7, 9, 10]);
+
+        assert_eq!(format_partition_list(&ids), "0-3,7,9-10");
+    }
+
+    #[test]
+    fn test_resolve_applies_selection_and_exclusion() {
+        let selection = PartitionSelection::parse("0-3,7").unwrap();
+        let excluded = PartitionList::parse("2").unwrap();
+
+        let partitions = selection.resolve(&available(8), &excluded).unwrap();
+
+        assert_eq!(partitions, vec!["0", "1", "3", "7"]);
+    }
+
+    #[test]
+    fn test_resolve_all_with_exclusion() {
+        let excluded = PartitionList::parse("0-1").unwrap();
+
+        let partitions = PartitionSelection::All
+            .resolve(&available(4), &excluded)
+            .unwrap();
+
+        assert_eq!(partitions, vec!["2", "3"]);
+    }
+
+    #[test]
+    fn test_resolve_fails_for_unknown_partitions() {
+        let selection = PartitionSelection::parse("3-5").unwrap();
+
+        assert!(selection
+            .resolve(&available(4), &PartitionList::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_resolve_fails_when_everything_is_excluded() {
+        let selection = PartitionSelection::parse("1").unwrap();
+        let excluded = PartitionList::parse("1").unwrap();
+
+        assert!(selection.resolve(&available(4), &excluded).is_err());
+    }
+
+    #[test]
+    fn test_single_partition() {
+        let excluded = PartitionList::parse("1").unwrap();
+
+        assert_eq!(
+            PartitionSelection::parse("0-1")
+                .unwrap()
+                .single_partition(&excluded),
+            Some("0".to_string())
+        );
+        assert_eq!(
+            PartitionSelection::parse("0-2")
+                .unwrap()
+                .single_partition(&excluded),
+            None
+        );
+        assert_eq!(PartitionSelection::All.single_partition(&excluded), None);
+    }
+}
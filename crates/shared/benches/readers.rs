@@ -0,0 +1,155 @@
+//! Benchmarks for the readers and hot paths shared by get-lines, csvn, split, and lookup.
+//!
+//! Run with: `cargo bench -p shared`
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use shared::constants::general::SIZE_128KB;
+use shared::system::folder_walkthrough::list_all_files_recursively;
+use shared::system::mmap_csv_reader::{CsvReaderOptions, CsvRecordReader, ReadMode};
+use shared::utils::sanitize_str_regex::clean_str_regex;
+use std::fs;
+use std::hint::black_box;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const ROW_COUNTS: [usize; 2] = [1_000, 100_000];
+
+/// Rows of the large file (~50MB), where the read mode matters the most.
+const LARGE_ROW_COUNT: usize = 1_000_000;
+
+fn write_csv_file(dir: &Path, rows: usize) -> PathBuf {
+    let path = dir.join(format!("bench_{}.csv", rows));
+    let mut file = fs::File::create(&path).expect("Failed to create benchmark CSV file");
+
+    writeln!(file, "id,name,email,city,amount").expect("Failed to write CSV header");
+    for i in 0..rows {
+        // Every third row has missing fields, like the files csvn is used on.
+        if i % 3 == 0 {
+            writeln!(file, "{},,user{}@example.com,,{}.50", i, i, i)
+        } else {
+            writeln!(
+                file,
+                "{},User {},user{}@example.com,City {},{}.50",
+                i,
+                i,
+                i,
+                i % 50,
+                i
+            )
+        }
+        .expect("Failed to write CSV row");
+    }
+
+    path
+}
+
+fn write_folder_tree(dir: &Path, folders: usize, files_per_folder: usize) {
+    for f in 0..folders {
+        let folder = dir.join(format!("folder_{}", f)).join("nested");
+        fs::create_dir_all(&folder).expect("Failed to create benchmark folder");
+
+        for i in 0..files_per_folder {
+            fs::write(folder.join(format!("file_{}.txt", i)), "content")
+                .expect("Failed to write benchmark file");
+        }
+    }
+}
+
+/// Compares the read modes of `CsvRecordReader` with a plain `csv::Reader` over a `File` (what the
+/// tools used to do), from small files to a large one.
+fn bench_csv_record_reader(c: &mut Criterion) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut group = c.benchmark_group("csv_record_reader");
+
+    for rows in ROW_COUNTS.into_iter().chain([LARGE_ROW_COUNT]) {
+        let path = write_csv_file(temp_dir.path(), rows);
+        group.throughput(Throughput::Elements(rows as u64));
+
+        for (name, mode) in [("mmap", ReadMode::Mmap), ("buffered", ReadMode::Buffered)] {
+            let options = CsvReaderOptions::default().with_mode(mode);
+            group.bench_with_input(BenchmarkId::new(name, rows), &path, |b, path| {
+                b.iter(|| {
+                    let reader =
+                        CsvRecordReader::open(path, &options).expect("Failed to open CSV file");
+                    let fields: usize = reader.filter_map(Result::ok).map(|r| r.len()).sum();
+                    black_box(fields)
+                })
+            });
+        }
+
+        group.bench_with_input(BenchmarkId::new("csv_from_path", rows), &path, |b, path| {
+            b.iter(|| {
+                let mut reader = csv::Reader::from_path(path).expect("Failed to open CSV file");
+                let fields: usize = reader
+                    .records()
+                    .filter_map(Result::ok)
+                    .map(|r| r.len())
+                    .sum();
+                black_box(fields)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Mirrors the get-lines/lookup pipeline: read line by line, lowercase, look for the terms.
+fn bench_buffered_line_search(c: &mut Criterion) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let terms = ["city 7", "user42@", "not-there"];
+    let mut group = c.benchmark_group("buffered_line_search");
+
+    for rows in ROW_COUNTS {
+        let path = write_csv_file(temp_dir.path(), rows);
+        group.throughput(Throughput::Elements(rows as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(rows), &path, |b, path| {
+            b.iter(|| {
+                let file = fs::File::open(path).expect("Failed to open file");
+                let reader = BufReader::with_capacity(SIZE_128KB, file);
+                let mut matches = 0usize;
+
+                for line in reader.lines().map_while(Result::ok) {
+                    let lower = line.to_lowercase();
+                    matches += terms.iter().filter(|t| lower.contains(*t)).count();
+                }
+
+                black_box(matches)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_list_all_files_recursively(c: &mut Criterion) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_folder_tree(temp_dir.path(), 50, 20);
+    let root = temp_dir.path().to_path_buf();
+
+    c.bench_function("list_all_files_recursively/1000_files", |b| {
+        b.iter(|| black_box(list_all_files_recursively(&root).count()))
+    });
+}
+
+fn bench_clean_str_regex(c: &mut Criterion) {
+    let dirty = "Name\u{0}With\u{7}Control\tChars and a long tail of printable text, 12345";
+    let clean = "Name With No Control Chars and a long tail of printable text, 12345";
+
+    let mut group = c.benchmark_group("clean_str_regex");
+    group.bench_function("dirty", |b| {
+        b.iter(|| black_box(clean_str_regex(black_box(dirty))))
+    });
+    group.bench_function("clean", |b| {
+        b.iter(|| black_box(clean_str_regex(black_box(clean))))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_csv_record_reader,
+    bench_buffered_line_search,
+    bench_list_all_files_recursively,
+    bench_clean_str_regex
+);
+criterion_main!(benches);
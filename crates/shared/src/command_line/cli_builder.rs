@@ -13,6 +13,8 @@ pub trait CommandExt {
     fn preset_arg_config(self, alt_help_text: Option<&str>) -> Self;
     fn preset_arg_connection_string(self, help_text: &'static str) -> Self;
     fn preset_arg_verbose(self, alt_help_text: Option<&str>) -> Self;
+    fn preset_arg_profile(self) -> Self;
+    fn preset_args_log_file(self) -> Self;
 }
 
 impl CommandExt for Command {
@@ -68,4 +70,49 @@ impl CommandExt for Command {
                 .help(help_text),
         )
     }
+
+    fn preset_arg_profile(self) -> Self {
+        self.arg(
+            Arg::new("profile")
+                .long("profile")
+                .action(clap::ArgAction::SetTrue)
+                .help("Print how long each phase took when the tool exits. (Default: false)"),
+        )
+    }
+
+    fn preset_args_log_file(self) -> Self {
+        self.arg(
+            Arg::new("log-dir")
+                .long("log-dir")
+                .value_name("FOLDER")
+                .global(true)
+                .help("Also writes the log to files in this folder. (Default: console only)"),
+        )
+        .arg(
+            Arg::new("log-format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .global(true)
+                .value_parser(["text", "json"])
+                .help("Format of the log lines: text or json. (Default: text)"),
+        )
+        .arg(
+            Arg::new("log-rotate")
+                .long("log-rotate")
+                .value_name("RULE")
+                .global(true)
+                .help(
+                    "When the log file is rotated: never, daily, hourly, or a size (e.g.: 10MB). \
+                    (Default: daily)",
+                ),
+        )
+        .arg(
+            Arg::new("log-keep")
+                .long("log-keep")
+                .value_name("COUNT")
+                .global(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("How many log files are kept. 0 keeps them all. (Default: 7)"),
+        )
+    }
 }
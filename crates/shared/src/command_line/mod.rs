@@ -1 +1,2 @@
 pub mod cli_builder;
+pub mod tool_cli;
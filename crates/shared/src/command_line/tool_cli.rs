@@ -0,0 +1,529 @@
+use crate::constants::general::{DASH_LINE, EXIT_CODE_ERROR};
+use crate::system::tool_exit_helpers::exit_with_code;
+use anyhow::Result;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COLORS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Flags shared by every tool. They are long-only (except `-q`), so they never clash with the
+/// short flags the tools already use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalFlag {
+    /// `--quiet`/`-q`: don't print the runtime info header.
+    Quiet,
+    /// `--no-color`: disable colored output (also honors the `NO_COLOR` environment variable).
+    NoColor,
+    /// `--output FILE`: write the result to a file instead of stdout.
+    Output,
+    /// `--config FILE`: read settings from a configuration file.
+    Config,
+}
+
+impl GlobalFlag {
+    pub fn id(&self) -> &'static str {
+        match self {
+            GlobalFlag::Quiet => "quiet",
+            GlobalFlag::NoColor => "no-color",
+            GlobalFlag::Output => "output",
+            GlobalFlag::Config => "config",
+        }
+    }
+
+    fn to_arg(self) -> Arg {
+        let arg = Arg::new(self.id()).long(self.id());
+
+        match self {
+            GlobalFlag::Quiet => arg
+                .short('q')
+                .action(ArgAction::SetTrue)
+                .help("Don't print the runtime info header. (Default: false)"),
+            GlobalFlag::NoColor => arg
+                .action(ArgAction::SetTrue)
+                .help("Disable colored output. Same as setting the NO_COLOR environment variable. (Default: false)"),
+            GlobalFlag::Output => arg
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Write the output to FILE instead of stdout."),
+            GlobalFlag::Config => arg
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Configuration file path (JSON format)"),
+        }
+    }
+}
+
+/// Values of the global flags. Flags the tool didn't opt into keep their defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GlobalArgs {
+    pub quiet: bool,
+    pub no_color: bool,
+    pub output: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+}
+
+impl GlobalArgs {
+    fn from_matches(matches: &ArgMatches, flags: &[GlobalFlag]) -> Self {
+        let has = |flag: GlobalFlag| flags.contains(&flag);
+
+        Self {
+            quiet: has(GlobalFlag::Quiet) && matches.get_flag(GlobalFlag::Quiet.id()),
+            no_color: has(GlobalFlag::NoColor) && matches.get_flag(GlobalFlag::NoColor.id()),
+            output: has(GlobalFlag::Output)
+                .then(|| matches.get_one::<PathBuf>(GlobalFlag::Output.id()).cloned())
+                .flatten(),
+            config: has(GlobalFlag::Config)
+                .then(|| matches.get_one::<PathBuf>(GlobalFlag::Config.id()).cloned())
+                .flatten(),
+        }
+    }
+}
+
+/// Another name for a flag (e.g.: `-o` for `--output`).
+///
+/// Plain aliases are registered in clap and listed in the help. Deprecated aliases are hidden:
+/// they are rewritten to the new flag before parsing, with a warning on stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagAlias {
+    pub alias: &'static str,
+    pub target: &'static str,
+    pub deprecated_since: Option<&'static str>,
+}
+
+impl FlagAlias {
+    /// `alias` and `target` include the dashes: `FlagAlias::alias("-o", "--output")`.
+    pub const fn alias(alias: &'static str, target: &'static str) -> Self {
+        Self {
+            alias,
+            target,
+            deprecated_since: None,
+        }
+    }
+
+    /// `version` is the tool version that deprecated the alias.
+    pub const fn deprecated(
+        alias: &'static str,
+        target: &'static str,
+        version: &'static str,
+    ) -> Self {
+        Self {
+            alias,
+            target,
+            deprecated_since: Some(version),
+        }
+    }
+
+    fn warning(&self) -> String {
+        format!(
+            "Warning: {} is deprecated since v{} and will be removed in a future version. Use {} instead.",
+            self.alias,
+            self.deprecated_since.unwrap_or_default(),
+            self.target
+        )
+    }
+}
+
+/// Command line of a tool, parsed with the shared global flags, aliases, and deprecations.
+pub trait ToolCli: Sized {
+    /// Name shown in the runtime info header (e.g.: `Timestamp Converter`).
+    const DISPLAY_NAME: &'static str;
+
+    /// The tool's own arguments. The global flags are added by the framework.
+    fn command() -> Command;
+
+    fn global_flags() -> &'static [GlobalFlag] {
+        &[GlobalFlag::Quiet, GlobalFlag::NoColor]
+    }
+
+    fn flag_aliases() -> &'static [FlagAlias] {
+        &[]
+    }
+
+    fn from_matches(matches: &ArgMatches, global: &GlobalArgs) -> Result<Self>;
+
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Rows printed below the header, as `- key: value`.
+    fn runtime_info(&self) -> Vec<(String, String)>;
+}
+
+/// Parsed tool arguments and the global flags.
+pub struct ToolInvocation<T: ToolCli> {
+    pub args: T,
+    pub global: GlobalArgs,
+}
+
+impl<T: ToolCli> ToolInvocation<T> {
+    /// Prints the tool name, version, and runtime info. Skipped with `--quiet`.
+    pub fn print_runtime_info(&self) {
+        if self.global.quiet {
+            return;
+        }
+
+        let command = T::command();
+        println!(
+            "{} v{}",
+            T::DISPLAY_NAME,
+            command.get_version().unwrap_or_default()
+        );
+        println!("{}", DASH_LINE);
+
+        for (key, value) in self.args.runtime_info() {
+            println!("- {}: {}", key, value);
+        }
+
+        println!();
+    }
+}
+
+/// Parses the process arguments. Prints the error and exits on invalid arguments, and exits
+/// after printing `--help` or `--version`.
+pub fn parse_tool_cli<T: ToolCli>() -> ToolInvocation<T> {
+    match parse_tool_cli_from::<T, _, _>(std::env::args_os()) {
+        Ok(invocation) => invocation,
+        Err(code) => {
+            exit_with_code(code);
+            unreachable!();
+        }
+    }
+}
+
+/// Same as [`parse_tool_cli`], but parses `argv` instead of the process arguments (e.g.: when the
+/// tool is started by the `rtb` launcher), and returns the exit code instead of exiting. `argv[0]`
+/// is the binary name.
+///
+/// The error is the exit code to return: `0` after printing `--help` or `--version`, and an error
+/// code after printing why the arguments are invalid.
+pub fn parse_tool_cli_from<T, I, S>(argv: I) -> std::result::Result<ToolInvocation<T>, i32>
+where
+    T: ToolCli,
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<String> = argv
+        .into_iter()
+        .map(|arg| arg.into().to_string_lossy().to_string())
+        .collect();
+
+    let (argv, warnings) = rewrite_deprecated_args(argv, T::flag_aliases());
+    for warning in warnings {
+        eprintln!("{}", warning);
+    }
+
+    // clap reports --help and --version as errors too, with a success exit code.
+    let matches = build_command::<T>()
+        .try_get_matches_from(argv)
+        .map_err(|e| {
+            let _ = e.print();
+            e.exit_code()
+        })?;
+
+    invocation_from_matches::<T>(&matches).map_err(|e| {
+        eprintln!("{}: {}", T::command().get_name(), e);
+        EXIT_CODE_ERROR
+    })
+}
+
+/// Same as [`parse_tool_cli`], but returns errors instead of exiting. `argv[0]` is the binary name.
+pub fn try_parse_tool_cli_from<T, I, S>(argv: I) -> Result<ToolInvocation<T>>
+where
+    T: ToolCli,
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<String> = argv
+        .into_iter()
+        .map(|arg| arg.into().to_string_lossy().to_string())
+        .collect();
+
+    let (argv, _) = rewrite_deprecated_args(argv, T::flag_aliases());
+    let matches = build_command::<T>().try_get_matches_from(argv)?;
+
+    invocation_from_matches::<T>(&matches)
+}
+
+/// Whether colored output is allowed: not disabled with `--no-color` or `NO_COLOR`.
+pub fn colors_enabled() -> bool {
+    !COLORS_DISABLED.load(Ordering::Relaxed)
+        && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+pub fn disable_colors() {
+    COLORS_DISABLED.store(true, Ordering::Relaxed);
+}
+
+fn invocation_from_matches<T: ToolCli>(matches: &ArgMatches) -> Result<ToolInvocation<T>> {
+    let global = GlobalArgs::from_matches(matches, T::global_flags());
+
+    if global.no_color {
+        disable_colors();
+    }
+
+    let args = T::from_matches(matches, &global)?;
+    args.validate()?;
+
+    Ok(ToolInvocation { args, global })
+}
+
+fn build_command<T: ToolCli>() -> Command {
+    let mut command = T::command();
+
+    for flag in T::global_flags() {
+        command = command.arg(flag.to_arg());
+    }
+
+    for alias in T::flag_aliases()
+        .iter()
+        .filter(|alias| alias.deprecated_since.is_none())
+    {
+        command = add_visible_alias(command, alias);
+    }
+
+    command
+}
+
+fn add_visible_alias(command: Command, alias: &FlagAlias) -> Command {
+    let target = command
+        .get_arguments()
+        .find(|arg| match flag_name(alias.target) {
+            FlagName::Long(long) => arg.get_long() == Some(long),
+            FlagName::Short(short) => arg.get_short() == Some(short),
+        });
+
+    let Some(id) = target.map(|arg| arg.get_id().to_string()) else {
+        panic!(
+            "Alias {} targets unknown flag {}",
+            alias.alias, alias.target
+        );
+    };
+
+    match flag_name(alias.alias) {
+        FlagName::Long(long) => command.mut_arg(id, |arg| arg.visible_alias(long)),
+        FlagName::Short(short) => command.mut_arg(id, |arg| arg.visible_short_alias(short)),
+    }
+}
+
+enum FlagName<'a> {
+    Long(&'a str),
+    Short(char),
+}
+
+fn flag_name(flag: &str) -> FlagName<'_> {
+    match flag.strip_prefix("--") {
+        Some(long) => FlagName::Long(long),
+        None => FlagName::Short(
+            flag.trim_start_matches('-')
+                .chars()
+                .next()
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Replaces deprecated flags with their targets, and returns a warning for each one used.
+/// Handles `--old`, `--old=value`, and `-o`. Everything after `--` is left alone.
+fn rewrite_deprecated_args(argv: Vec<String>, aliases: &[FlagAlias]) -> (Vec<String>, Vec<String>) {
+    let deprecated: Vec<&FlagAlias> = aliases
+        .iter()
+        .filter(|alias| alias.deprecated_since.is_some())
+        .collect();
+
+    let mut warnings = Vec::new();
+    let mut rewritten = Vec::with_capacity(argv.len());
+    let mut end_of_flags = false;
+
+    for (index, arg) in argv.into_iter().enumerate() {
+        if index == 0 || end_of_flags || deprecated.is_empty() {
+            rewritten.push(arg);
+            continue;
+        }
+
+        if arg == "--" {
+            end_of_flags = true;
+            rewritten.push(arg);
+            continue;
+        }
+
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (arg.as_str(), None),
+        };
+
+        let Some(alias) = deprecated.iter().find(|alias| alias.alias == flag) else {
+            rewritten.push(arg);
+            continue;
+        };
+
+        let warning = alias.warning();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+
+        rewritten.push(match value {
+            Some(value) => format!("{}={}", alias.target, value),
+            None => alias.target.to_string(),
+        });
+    }
+
+    (rewritten, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SampleCli {
+        name: String,
+        verbose: bool,
+    }
+
+    const FLAG_ALIASES: &[FlagAlias] = &[
+        FlagAlias::alias("--title", "--name"),
+        FlagAlias::alias("-o", "--output"),
+        FlagAlias::deprecated("--silent", "--quiet", "1.1.0"),
+        FlagAlias::deprecated("-s", "--quiet", "1.1.0"),
+        FlagAlias::deprecated("--label", "--name", "1.1.0"),
+    ];
+
+    impl ToolCli for SampleCli {
+        const DISPLAY_NAME: &'static str = "Sample";
+
+        fn command() -> Command {
+            Command::new("sample")
+                .version("1.0.0")
+                .arg(Arg::new("name").long("name").short('n'))
+                .arg(
+                    Arg::new("verbose")
+                        .long("verbose")
+                        .action(ArgAction::SetTrue),
+                )
+        }
+
+        fn global_flags() -> &'static [GlobalFlag] {
+            &[GlobalFlag::Quiet, GlobalFlag::NoColor, GlobalFlag::Output]
+        }
+
+        fn flag_aliases() -> &'static [FlagAlias] {
+            FLAG_ALIASES
+        }
+
+        fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+            Ok(Self {
+                name: matches
+                    .get_one::<String>("name")
+                    .cloned()
+                    .unwrap_or_default(),
+                verbose: matches.get_flag("verbose"),
+            })
+        }
+
+        fn validate(&self) -> Result<()> {
+            if self.name == "invalid" {
+                anyhow::bail!("invalid name");
+            }
+            Ok(())
+        }
+
+        fn runtime_info(&self) -> Vec<(String, String)> {
+            vec![("Name".to_string(), self.name.clone())]
+        }
+    }
+
+    fn parse(args: &[&str]) -> Result<ToolInvocation<SampleCli>> {
+        try_parse_tool_cli_from::<SampleCli, _, _>(
+            std::iter::once("sample").chain(args.iter().copied()),
+        )
+    }
+
+    #[test]
+    fn global_flags_are_parsed() {
+        let invocation = parse(&["-q", "--output", "out.txt", "--name", "x"]).unwrap();
+
+        assert_eq!(
+            invocation.global,
+            GlobalArgs {
+                quiet: true,
+                no_color: false,
+                output: Some(PathBuf::from("out.txt")),
+                config: None,
+            }
+        );
+        assert_eq!(invocation.args.name, "x");
+    }
+
+    #[test]
+    fn flags_the_tool_did_not_opt_into_are_rejected() {
+        assert!(parse(&["--config", "settings.json"]).is_err());
+    }
+
+    #[test]
+    fn visible_aliases_map_to_the_target_flag() {
+        let invocation = parse(&["--title", "x", "-o", "out.txt"]).unwrap();
+
+        assert_eq!(invocation.args.name, "x");
+        assert_eq!(invocation.global.output, Some(PathBuf::from("out.txt")));
+    }
+
+    #[test]
+    fn deprecated_aliases_are_rewritten_with_a_warning() {
+        let argv = ["sample", "-s", "--label=x", "--silent", "--", "-s"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+
+        let (rewritten, warnings) = rewrite_deprecated_args(argv, SampleCli::flag_aliases());
+
+        assert_eq!(
+            rewritten,
+            vec!["sample", "--quiet", "--name=x", "--quiet", "--", "-s"]
+        );
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(
+            warnings[0],
+            "Warning: -s is deprecated since v1.1.0 and will be removed in a future version. Use --quiet instead."
+        );
+    }
+
+    #[test]
+    fn deprecated_aliases_still_work() {
+        let invocation = parse(&["--silent", "--label", "x", "--verbose"]).unwrap();
+
+        assert!(invocation.global.quiet);
+        assert!(invocation.args.verbose);
+        assert_eq!(invocation.args.name, "x");
+    }
+
+    #[test]
+    fn validation_errors_are_returned() {
+        let error = parse(&["--name", "invalid"]).err().unwrap();
+
+        assert_eq!(error.to_string(), "invalid name");
+    }
+
+    #[test]
+    fn parse_from_returns_the_exit_code_instead_of_exiting() {
+        let exit_code = |args: &[&str]| {
+            parse_tool_cli_from::<SampleCli, _, _>(
+                std::iter::once("sample").chain(args.iter().copied()),
+            )
+            .err()
+        };
+
+        assert_eq!(exit_code(&["--name", "x"]), None);
+        assert_eq!(exit_code(&["--version"]), Some(0));
+        assert_eq!(exit_code(&["--unknown"]), Some(2));
+        assert_eq!(exit_code(&["--name", "invalid"]), Some(EXIT_CODE_ERROR));
+    }
+
+    #[test]
+    fn no_color_disables_colors() {
+        let invocation = parse(&["--no-color"]).unwrap();
+
+        assert!(invocation.global.no_color);
+        assert!(!colors_enabled());
+    }
+}
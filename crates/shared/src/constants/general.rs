@@ -8,4 +8,6 @@ pub const SIZE_32KB: usize = 32 * 1024;
 pub const SIZE_64KB: usize = 64 * 1024;
 pub const SIZE_128KB: usize = 128 * 1024;
 
+pub const EXIT_CODE_SUCCESS: i32 = 0;
+pub const EXIT_CODE_ERROR: i32 = 1;
 pub const EXIT_CODE_INTERRUPTED_BY_USER: i32 = 130;
@@ -1,6 +1,12 @@
 pub mod command_line;
 pub mod constants;
 pub mod logging;
+pub mod secrets;
+#[cfg(feature = "selfupdate")]
+pub mod selfupdate;
 pub mod sqlite;
 pub mod system;
+pub mod ui;
 pub mod utils;
+#[cfg(feature = "web")]
+pub mod web;
@@ -1,5 +1,9 @@
 #![allow(dead_code)] // This module is used by other modules, so the code is not really dead.
 
+use crate::command_line::tool_cli::colors_enabled;
+use crate::logging::log_rotation::{LogRotation, RotatingFileWriter};
+use std::str::FromStr;
+use std::sync::Mutex;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::Layer;
@@ -74,6 +78,29 @@ impl LogLevel {
     }
 }
 
+/// How each log line is written.
+///
+/// - `Text`: human-readable lines (the default).
+/// - `Json`: one JSON object per line, with the timestamp, level, target, message, and fields. Meant
+///   for log collectors (Loki, Elastic, CloudWatch, ...).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => anyhow::bail!("'{}' is not a valid log format. Use text or json", other),
+        }
+    }
+}
+
 /// The `AppLogger` struct is a customizable logging utility designed to manage
 /// logging configurations for an application. It allows for toggling log outputs,
 /// log level control, file-based logging, and log file rotation.
@@ -100,6 +127,17 @@ impl LogLevel {
 ///   - `true`: A separate log file will be created for each day, ensuring logs are split by date.
 ///   - `false`: Log file rotation by day is disabled.
 ///
+/// - `rotation` (`Option<LogRotation>`):
+///   When the log file is rotated (daily, hourly, or by size). Takes precedence over
+///   `rotate_file_by_day`.
+///
+/// - `max_log_files` (`Option<usize>`):
+///   How many log files are kept, including the current one. The oldest are removed on rotation.
+///   `None` keeps them all.
+///
+/// - `format` (`LogFormat`):
+///   Whether the log lines are plain text or JSON.
+///
 /// - `log_level` (`LogLevel`):
 ///   Represents the minimum level of logs to record. Higher priority logs (e.g., errors)
 ///   will also be logged, but lower priority logs will be filtered out.
@@ -117,6 +155,9 @@ pub struct AppLogger {
     to_console: bool, //stdout
     to_file: bool,
     rotate_file_by_day: bool,
+    rotation: Option<LogRotation>,
+    max_log_files: Option<usize>,
+    format: LogFormat,
     log_level: LogLevel,
     log_folder: String,
     app_name: String,
@@ -134,6 +175,9 @@ impl AppLogger {
     /// - `to_console`: `true` - Logs will be outputted to the console by default.
     /// - `to_file`: `false` - File logging is disabled by default.
     /// - `rotate_file_by_day`: `false` - Log file rotation by day is disabled by default.
+    /// - `rotation`: `None` - No rotation other than `rotate_file_by_day`.
+    /// - `max_log_files`: `None` - Every log file is kept.
+    /// - `format`: `LogFormat::Text` - Human-readable log lines.
     /// - `log_level`: `LogLevel::Info` - Default logging level is set to `Info`.
     /// - `log_folder`: `".logs"` - Default folder name for storing log files.
     /// - `app_name`: `"env!("CARGO_PKG_NAME")"` - Default application name for the logging system.
@@ -143,6 +187,9 @@ impl AppLogger {
             to_console: true,
             to_file: false,
             rotate_file_by_day: false,
+            rotation: None,
+            max_log_files: None,
+            format: LogFormat::Text,
             log_level: LogLevel::Info,
             log_folder: ".logs".to_string(),
             app_name: env!("CARGO_PKG_NAME").to_string(),
@@ -184,6 +231,44 @@ impl AppLogger {
         self
     }
 
+    /// Sets when the log file is rotated. Overrides the daily rotation of `log_to_file`.
+    ///
+    /// # Parameters
+    /// - `rotation`: `LogRotation::Daily`, `LogRotation::Hourly`, `LogRotation::Size(bytes)`, or
+    ///   `LogRotation::Never`.
+    ///
+    /// # Returns
+    /// A mutable reference to the current instance, allowing for method chaining.
+    pub fn rotate_log(&mut self, rotation: LogRotation) -> &mut Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    /// Sets how many log files are kept, including the current one. When the log rotates, the
+    /// oldest files are removed.
+    ///
+    /// # Parameters
+    /// - `max_files`: Number of files to keep. `None` keeps them all.
+    ///
+    /// # Returns
+    /// A mutable reference to the current instance, allowing for method chaining.
+    pub fn keep_log_files(&mut self, max_files: Option<usize>) -> &mut Self {
+        self.max_log_files = max_files;
+        self
+    }
+
+    /// Sets the format of the log lines, for both the console and the file.
+    ///
+    /// # Parameters
+    /// - `format`: `LogFormat::Text` or `LogFormat::Json`.
+    ///
+    /// # Returns
+    /// A mutable reference to the current instance, allowing for method chaining.
+    pub fn log_format(&mut self, format: LogFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+
     /// Sets the log folder path for the instance.
     ///
     /// This method allows you to specify the folder path where logs will be stored.
@@ -247,9 +332,12 @@ impl AppLogger {
     /// - If `self.enabled` is `false`, logging is skipped, and no further setup is performed.
     /// - Reads the log level configuration from the environment variable or uses a default value.
     /// - Configures logging layers for console output and/or file output based on user preferences.
-    /// - Rotates log files by day if `self.rotate_file_by_day` is `true`.
+    /// - Rotates log files by day if `self.rotate_file_by_day` is `true`, or as set by `rotate_log`,
+    ///   removing the oldest files beyond `keep_log_files`.
+    /// - Writes JSON lines instead of text when the format is `LogFormat::Json`.
     /// - Ensures the `log_folder` directory exists; creates it if missing.
-    /// - Disables ANSI color codes for logs written to files.
+    /// - Disables ANSI color codes for logs written to files, and for the console when colors are
+    ///   disabled (`--no-color` or the `NO_COLOR` environment variable).
     ///
     /// # Requirements
     /// - The `self.app_name` field should contain a valid name for the application, which is used
@@ -296,43 +384,47 @@ impl AppLogger {
 
         // Add console layer if enabled
         if self.to_console {
-            layers.push(tracing_subscriber::fmt::layer().boxed());
+            let console_layer = tracing_subscriber::fmt::layer().with_ansi(colors_enabled());
+            layers.push(match self.format {
+                LogFormat::Text => console_layer.boxed(),
+                LogFormat::Json => console_layer.json().boxed(),
+            });
         }
 
         // Add file layer if enabled
         if self.to_file {
-            let file_name = if self.rotate_file_by_day {
-                format!(
-                    "{}-{}.log",
-                    self.app_name,
-                    chrono::Utc::now().format("%Y-%m-%d")
-                )
-            } else {
-                format!("{}.log", self.app_name)
-            };
-
             // Create a log directory if it doesn't exist
             if let Ok(log_dir) = std::env::current_dir().map(|d| d.join(&self.log_folder)) {
-                let _ = std::fs::create_dir_all(&log_dir);
-
-                if let Ok(file) = std::fs::OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open(log_dir.join(file_name))
-                {
+                if let Ok(writer) = RotatingFileWriter::new(
+                    &log_dir,
+                    &self.app_name,
+                    self.effective_rotation(),
+                    self.max_log_files,
+                ) {
                     let file_layer = tracing_subscriber::fmt::layer()
-                        .with_writer(file)
-                        .with_ansi(false) // Disabling ANSI colors for file output
-                        .boxed();
-                    layers.push(file_layer);
+                        .with_writer(Mutex::new(writer))
+                        .with_ansi(false); // Disabling ANSI colors for file output
+                    layers.push(match self.format {
+                        LogFormat::Text => file_layer.boxed(),
+                        LogFormat::Json => file_layer.json().boxed(),
+                    });
                 }
             }
         }
 
-        // Build and initialize the subscriber
+        // Build and initialize the subscriber. It's already set when a tool runs more than once in
+        // the same process (e.g.: by `run(argv)` in tests), and the first one is kept.
         let subscriber = tracing_subscriber::registry().with(env_filter).with(layers);
 
-        subscriber.init();
+        let _ = subscriber.try_init();
+    }
+
+    fn effective_rotation(&self) -> LogRotation {
+        match self.rotation {
+            Some(rotation) => rotation,
+            None if self.rotate_file_by_day => LogRotation::Daily,
+            None => LogRotation::Never,
+        }
     }
 
     /*
@@ -362,6 +454,16 @@ impl AppLogger {
         self.rotate_file_by_day
     }
 
+    #[cfg(test)]
+    pub fn get_log_format(&self) -> LogFormat {
+        self.format
+    }
+
+    #[cfg(test)]
+    pub fn get_max_log_files(&self) -> Option<usize> {
+        self.max_log_files
+    }
+
     #[cfg(test)]
     pub fn get_log_folder(&self) -> &str {
         &self.log_folder
@@ -534,6 +636,28 @@ mod tests {
         assert!(logger.rotates_file_by_day());
     }
 
+    #[test]
+    fn test_rotation_and_format_settings() {
+        let mut logger = AppLogger::new(true);
+        assert_eq!(logger.effective_rotation(), LogRotation::Never);
+        assert_eq!(logger.get_log_format(), LogFormat::Text);
+
+        logger.log_to_file(true, true);
+        assert_eq!(logger.effective_rotation(), LogRotation::Daily);
+
+        logger
+            .rotate_log(LogRotation::Size(1024))
+            .keep_log_files(Some(5))
+            .log_format(LogFormat::Json);
+
+        assert_eq!(logger.effective_rotation(), LogRotation::Size(1024));
+        assert_eq!(logger.get_max_log_files(), Some(5));
+        assert_eq!(logger.get_log_format(), LogFormat::Json);
+
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
     #[test]
     fn test_build_returns_early_when_disabled() {
         let logger = AppLogger::new(false);
@@ -0,0 +1,334 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// When the log file is rotated.
+///
+/// Time-based rotation writes to `<app>-<date>.log` (`<app>-<date>-<hour>.log` for hourly), so
+/// each day (or hour) has its own file. Size-based rotation writes to `<app>.log`, and renames
+/// it to `<app>-<timestamp>.log` when the next line would go over the limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogRotation {
+    /// A single `<app>.log`, growing forever.
+    Never,
+    Daily,
+    Hourly,
+    /// Maximum size of the log file, in bytes.
+    Size(u64),
+}
+
+impl FromStr for LogRotation {
+    type Err = anyhow::Error;
+
+    /// Parses `never`, `daily`, `hourly`, or a size (`10MB`, `512KiB`, `1048576`).
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "never" | "none" => Ok(LogRotation::Never),
+            "daily" | "day" => Ok(LogRotation::Daily),
+            "hourly" | "hour" => Ok(LogRotation::Hourly),
+            size => parse_size(size).map(LogRotation::Size),
+        }
+    }
+}
+
+/// Parses a size in bytes, with an optional unit (`KB`, `MB`, `GB` or `KiB`, `MiB`, `GiB`).
+fn parse_size(value: &str) -> Result<u64> {
+    let split_at = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split_at);
+
+    let number: u64 = number.parse().with_context(|| {
+        format!(
+            "'{}' is not a valid log rotation. Use never, daily, hourly, or a size (e.g.: 10MB)",
+            value
+        )
+    })?;
+
+    let multiplier: u64 = match unit.trim() {
+        "" | "b" => 1,
+        "kb" | "k" => 1_000,
+        "mb" | "m" => 1_000_000,
+        "gb" | "g" => 1_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        other => bail!("'{}' is not a valid size unit for the log rotation", other),
+    };
+
+    let size = number.saturating_mul(multiplier);
+    if size == 0 {
+        bail!("The log rotation size must be greater than zero");
+    }
+
+    Ok(size)
+}
+
+/// Log file writer that rotates the file and removes the oldest ones.
+///
+/// Meant to be wrapped in a `Mutex` and given to a `tracing_subscriber` layer as its writer.
+pub struct RotatingFileWriter {
+    folder: PathBuf,
+    app_name: String,
+    rotation: LogRotation,
+    max_files: Option<usize>,
+    file: Option<File>,
+    file_path: PathBuf,
+    file_size: u64,
+}
+
+impl RotatingFileWriter {
+    /// Opens (or creates) the current log file of `app_name` in `folder`.
+    ///
+    /// `max_files` is how many log files are kept, including the current one. `None` keeps them
+    /// all.
+    ///
+    /// # Errors
+    /// Returns an error if the folder can't be created or the log file can't be opened.
+    pub fn new(
+        folder: &Path,
+        app_name: &str,
+        rotation: LogRotation,
+        max_files: Option<usize>,
+    ) -> Result<Self> {
+        fs::create_dir_all(folder)
+            .with_context(|| format!("Failed to create the log folder {}", folder.display()))?;
+
+        let mut writer = Self {
+            folder: folder.to_path_buf(),
+            app_name: app_name.to_string(),
+            rotation,
+            max_files,
+            file: None,
+            file_path: PathBuf::new(),
+            file_size: 0,
+        };
+
+        writer.open(writer.active_path(Utc::now()))?;
+        writer.remove_old_files()?;
+
+        Ok(writer)
+    }
+
+    pub fn current_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    fn active_path(&self, now: DateTime<Utc>) -> PathBuf {
+        let file_name = match self.rotation {
+            LogRotation::Never | LogRotation::Size(_) => format!("{}.log", self.app_name),
+            LogRotation::Daily => format!("{}-{}.log", self.app_name, now.format("%Y-%m-%d")),
+            LogRotation::Hourly => format!("{}-{}.log", self.app_name, now.format("%Y-%m-%d-%H")),
+        };
+
+        self.folder.join(file_name)
+    }
+
+    fn open(&mut self, path: PathBuf) -> io::Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.file_size = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        self.file = Some(file);
+        self.file_path = path;
+        Ok(())
+    }
+
+    fn rotate_if_needed(&mut self, incoming: usize, now: DateTime<Utc>) -> io::Result<()> {
+        match self.rotation {
+            LogRotation::Never => Ok(()),
+            LogRotation::Daily | LogRotation::Hourly => {
+                let path = self.active_path(now);
+                if path == self.file_path {
+                    return Ok(());
+                }
+
+                self.open(path)?;
+                self.remove_old_files()
+            }
+            LogRotation::Size(max_size) => {
+                if self.file_size == 0 || self.file_size + incoming as u64 <= max_size {
+                    return Ok(());
+                }
+
+                if let Some(mut file) = self.file.take() {
+                    file.flush()?;
+                }
+                fs::rename(&self.file_path, self.archive_path(now))?;
+
+                self.open(self.active_path(now))?;
+                self.remove_old_files()
+            }
+        }
+    }
+
+    /// Name for a file rotated by size: `<app>-<timestamp>.log`, numbered when more than one is
+    /// rotated in the same second.
+    fn archive_path(&self, now: DateTime<Utc>) -> PathBuf {
+        let stamp = now.format("%Y-%m-%d-%H%M%S").to_string();
+        let mut path = self.folder.join(format!("{}-{}.log", self.app_name, stamp));
+
+        let mut counter = 1;
+        while path.exists() {
+            path = self
+                .folder
+                .join(format!("{}-{}-{}.log", self.app_name, stamp, counter));
+            counter += 1;
+        }
+
+        path
+    }
+
+    /// Removes the oldest rotated files of the app, so at most `max_files` are left.
+    fn remove_old_files(&self) -> io::Result<()> {
+        let Some(max_files) = self.max_files else {
+            return Ok(());
+        };
+
+        let mut rotated: Vec<PathBuf> = fs::read_dir(&self.folder)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| *path != self.file_path && self.is_rotated_file(path))
+            .collect();
+
+        // The names end with the date, so sorting them sorts by age.
+        rotated.sort();
+
+        let keep = max_files.saturating_sub(1);
+        let excess = rotated.len().saturating_sub(keep);
+        for path in rotated.into_iter().take(excess) {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+
+    /// `<app>-<date...>.log`. Checking for the digit keeps `eh-read-...` from being taken as a
+    /// file of `eh`.
+    fn is_rotated_file(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(&self.app_name))
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|rest| {
+                rest.ends_with(".log") && rest.starts_with(|c: char| c.is_ascii_digit())
+            })
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.rotate_if_needed(buf.len(), Utc::now())?;
+
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => return Err(io::Error::other("The log file is closed")),
+        };
+
+        let written = file.write(buf)?;
+        self.file_size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn log_files(folder: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(folder)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_parse_log_rotation() {
+        assert_eq!("daily".parse::<LogRotation>().unwrap(), LogRotation::Daily);
+        assert_eq!(
+            "Hourly".parse::<LogRotation>().unwrap(),
+            LogRotation::Hourly
+        );
+        assert_eq!("never".parse::<LogRotation>().unwrap(), LogRotation::Never);
+        assert_eq!(
+            "10MB".parse::<LogRotation>().unwrap(),
+            LogRotation::Size(10_000_000)
+        );
+        assert_eq!(
+            "512KiB".parse::<LogRotation>().unwrap(),
+            LogRotation::Size(512 * 1024)
+        );
+        assert_eq!(
+            "2048".parse::<LogRotation>().unwrap(),
+            LogRotation::Size(2048)
+        );
+        assert!("weekly".parse::<LogRotation>().is_err());
+        assert!("10XB".parse::<LogRotation>().is_err());
+        assert!("0".parse::<LogRotation>().is_err());
+    }
+
+    #[test]
+    fn test_size_rotation_keeps_the_newest_files() {
+        let dir = tempfile::tempdir().unwrap();
+        // A file of another app, with a name starting like ours, must be left alone.
+        fs::write(dir.path().join("app-read-2020-01-01.log"), "other").unwrap();
+
+        let mut writer =
+            RotatingFileWriter::new(dir.path(), "app", LogRotation::Size(10), Some(3)).unwrap();
+
+        for second in 0..4 {
+            let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, second).unwrap();
+            writer.rotate_if_needed(8, now).unwrap();
+            writer
+                .file
+                .as_mut()
+                .unwrap()
+                .write_all(b"12345678")
+                .unwrap();
+            writer.file_size += 8;
+        }
+
+        assert_eq!(
+            log_files(dir.path()),
+            vec![
+                "app-2026-10-16-120002.log",
+                "app-2026-10-16-120003.log",
+                "app-read-2020-01-01.log",
+                "app.log",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_daily_rotation_switches_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("app-2020-01-01.log"), "old").unwrap();
+        fs::write(dir.path().join("app-2020-01-02.log"), "old").unwrap();
+
+        let mut writer =
+            RotatingFileWriter::new(dir.path(), "app", LogRotation::Daily, Some(2)).unwrap();
+        let today = writer.current_path().to_path_buf();
+
+        let tomorrow = Utc::now() + chrono::Duration::days(1);
+        writer.rotate_if_needed(1, tomorrow).unwrap();
+
+        assert_ne!(writer.current_path(), today);
+        assert_eq!(
+            log_files(dir.path()),
+            vec![
+                today.file_name().unwrap().to_string_lossy().to_string(),
+                format!("app-{}.log", tomorrow.format("%Y-%m-%d")),
+            ]
+        );
+    }
+}
@@ -1,4 +1,13 @@
-use crate::logging::app_logger::{AppLogger, LogLevel};
+use crate::logging::app_logger::{AppLogger, LogFormat, LogLevel};
+use crate::logging::log_rotation::LogRotation;
+use anyhow::Result;
+use clap::ArgMatches;
+
+/// Default rotation of the log files, when `--log-rotate` is not used.
+pub const DEFAULT_LOG_ROTATION: LogRotation = LogRotation::Daily;
+
+/// Default number of log files kept, when `--log-keep` is not used.
+pub const DEFAULT_LOG_FILES_KEPT: usize = 7;
 
 /// Opaque guard that keeps OTel providers alive while held.
 ///
@@ -13,6 +22,76 @@ pub struct OtelGuard {
     _private: (),
 }
 
+/// Log format and file settings, from the flags added by `preset_args_log_file`.
+///
+/// The default logs text to the console only, like `initialize_log`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogSettings {
+    pub format: LogFormat,
+    /// Folder for the log files. `None` logs to the console only.
+    pub folder: Option<String>,
+    pub rotation: LogRotation,
+    /// How many log files are kept. `None` keeps them all.
+    pub max_files: Option<usize>,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Text,
+            folder: None,
+            rotation: DEFAULT_LOG_ROTATION,
+            max_files: Some(DEFAULT_LOG_FILES_KEPT),
+        }
+    }
+}
+
+impl LogSettings {
+    /// Reads `--log-dir`, `--log-format`, `--log-rotate` and `--log-keep`.
+    ///
+    /// # Errors
+    /// Returns an error if the format or the rotation rule are not valid.
+    pub fn from_matches(matches: &ArgMatches) -> Result<Self> {
+        let defaults = Self::default();
+
+        let format = match matches.get_one::<String>("log-format") {
+            Some(format) => format.parse()?,
+            None => defaults.format,
+        };
+
+        let rotation = match matches.get_one::<String>("log-rotate") {
+            Some(rotation) => rotation.parse()?,
+            None => defaults.rotation,
+        };
+
+        let max_files = match matches.get_one::<usize>("log-keep") {
+            Some(0) => None,
+            Some(count) => Some(*count),
+            None => defaults.max_files,
+        };
+
+        Ok(Self {
+            format,
+            folder: matches.get_one::<String>("log-dir").cloned(),
+            rotation,
+            max_files,
+        })
+    }
+
+    /// Applies the settings to a log builder.
+    pub fn apply(&self, builder: &mut AppLogger) {
+        builder.log_format(self.format);
+
+        if let Some(folder) = &self.folder {
+            builder
+                .log_to_file(true, false)
+                .log_folder(folder)
+                .rotate_log(self.rotation)
+                .keep_log_files(self.max_files);
+        }
+    }
+}
+
 /// Initializes the logging system for the application with the specified settings.
 ///
 /// This function sets up a logger for the application using the provided application name and log level.
@@ -37,6 +116,14 @@ pub fn initialize_log(app_name: &str, log_level: LogLevel) {
     get_default_log_builder(app_name, log_level).init();
 }
 
+/// Same as `initialize_log`, with the log format and files set by `settings` (usually from the
+/// `--log-*` flags).
+pub fn initialize_log_with_settings(app_name: &str, log_level: LogLevel, settings: &LogSettings) {
+    let mut builder = get_default_log_builder(app_name, log_level);
+    settings.apply(&mut builder);
+    builder.init();
+}
+
 /// Initializes logging with optional OpenTelemetry support.
 ///
 /// Resolves the OTel endpoint from the explicit parameter first, then falls back to
@@ -44,7 +131,7 @@ pub fn initialize_log(app_name: &str, log_level: LogLevel) {
 /// and the `otel` feature is enabled, delegates to [`raccoon_otel::setup_otel`] which
 /// sets up the global tracing subscriber with fmt + OTel layers.
 ///
-/// Otherwise, falls through to the standard [`initialize_log`] path.
+/// Otherwise, falls through to the standard [`initialize_log_with_settings`] path.
 ///
 /// # Returns
 ///
@@ -55,6 +142,7 @@ pub fn initialize_log_with_otel(
     app_name: &str,
     log_level: LogLevel,
     otel_endpoint: Option<&str>,
+    settings: &LogSettings,
 ) -> Option<OtelGuard> {
     let endpoint = otel_endpoint
         .map(String::from)
@@ -100,7 +188,7 @@ pub fn initialize_log_with_otel(
     }
 
     // Fall back to standard logging
-    initialize_log(app_name, log_level);
+    initialize_log_with_settings(app_name, log_level, settings);
     None
 }
 
@@ -114,3 +202,43 @@ pub fn get_default_log_builder(app_name: &str, log_level: LogLevel) -> AppLogger
 
     builder
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_line::cli_builder::CommandExt;
+    use clap::Command;
+
+    fn settings(argv: &[&str]) -> Result<LogSettings> {
+        let matches = Command::new("tool")
+            .preset_args_log_file()
+            .try_get_matches_from(argv)?;
+        LogSettings::from_matches(&matches)
+    }
+
+    #[test]
+    fn test_log_settings_from_flags() {
+        assert_eq!(settings(&["tool"]).unwrap(), LogSettings::default());
+
+        let parsed = settings(&[
+            "tool",
+            "--log-dir",
+            "logs",
+            "--log-format",
+            "json",
+            "--log-rotate",
+            "50MB",
+            "--log-keep",
+            "0",
+        ])
+        .unwrap();
+
+        assert_eq!(parsed.folder.as_deref(), Some("logs"));
+        assert_eq!(parsed.format, LogFormat::Json);
+        assert_eq!(parsed.rotation, LogRotation::Size(50_000_000));
+        assert_eq!(parsed.max_files, None);
+
+        assert!(settings(&["tool", "--log-rotate", "weekly"]).is_err());
+        assert!(settings(&["tool", "--log-format", "xml"]).is_err());
+    }
+}
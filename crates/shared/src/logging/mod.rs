@@ -1,2 +1,3 @@
 pub mod app_logger;
+pub mod log_rotation;
 pub mod logging_helpers;
@@ -0,0 +1,2 @@
+pub mod resolve_secrets;
+pub mod secret_vault;
@@ -0,0 +1,152 @@
+use crate::secrets::secret_vault::{default_vault_path, read_vault_passphrase, SecretVault};
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+/// Reference to a vault secret inside a config value: `${keyvault:NAME}`.
+static SECRET_REFERENCE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{keyvault:([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+/// Returns true if the value references a vault secret (`${keyvault:NAME}`).
+pub fn contains_secret_references(value: &str) -> bool {
+    SECRET_REFERENCE_RE.is_match(value)
+}
+
+/// Replaces the `${keyvault:NAME}` references of a value with the secrets of the default vault
+/// (see `keyvault`). References can be part of a larger value, like a connection string.
+///
+/// The vault is only opened (and the passphrase only asked for) if there are references.
+///
+/// # Errors
+/// Returns error if the vault can't be opened, or a referenced secret doesn't exist
+pub fn resolve_secret_references(value: &str) -> Result<String> {
+    if !contains_secret_references(value) {
+        return Ok(value.to_string());
+    }
+
+    replace_references(value, &open_default_vault()?)
+}
+
+/// Same as `resolve_secret_references`, for every string of a JSON document (e.g.: a config file
+/// loaded as a `Value`). The vault is opened once, on the first reference found.
+pub fn resolve_secrets_in_json(value: &mut Value) -> Result<()> {
+    let mut vault = None;
+    resolve_json_value(value, &mut vault)
+}
+
+fn resolve_json_value(value: &mut Value, vault: &mut Option<SecretVault>) -> Result<()> {
+    match value {
+        Value::String(text) if contains_secret_references(text) => {
+            if vault.is_none() {
+                *vault = Some(open_default_vault()?);
+            }
+
+            if let Some(vault) = vault {
+                *text = replace_references(text, vault)?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                resolve_json_value(item, vault)?;
+            }
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                resolve_json_value(field, vault)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn open_default_vault() -> Result<SecretVault> {
+    let path = default_vault_path()?;
+    if !path.is_file() {
+        return Err(anyhow!(
+            "Found a reference to a keyvault secret, but there's no vault at: {}",
+            path.display()
+        ));
+    }
+
+    SecretVault::open(&path, read_vault_passphrase(false)?)
+}
+
+fn replace_references(value: &str, vault: &SecretVault) -> Result<String> {
+    let mut missing = Vec::new();
+
+    let resolved = SECRET_REFERENCE_RE.replace_all(value, |captures: &Captures| {
+        let name = &captures[1];
+        match vault.get(name) {
+            Some(secret) => secret.to_string(),
+            None => {
+                missing.push(name.to_string());
+                String::new()
+            }
+        }
+    });
+
+    if !missing.is_empty() {
+        return Err(anyhow!(
+            "Secret(s) not found in vault {}: {}",
+            vault.path().display(),
+            missing.join(", ")
+        ));
+    }
+
+    Ok(resolved.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::SecretString;
+    use std::path::Path;
+
+    fn vault_with(secrets: &[(&str, &str)]) -> SecretVault {
+        let mut vault = SecretVault::open(
+            Path::new("does-not-exist.age"),
+            SecretString::from("unused".to_string()),
+        )
+        .unwrap();
+
+        for (name, value) in secrets {
+            vault.set(name, value).unwrap();
+        }
+
+        vault
+    }
+
+    #[test]
+    fn test_replace_references() {
+        let vault = vault_with(&[("SB_KEY", "s3cr3t"), ("USER", "admin")]);
+
+        assert_eq!(
+            replace_references(
+                "Endpoint=sb://ns/;SharedAccessKeyName=${keyvault:USER};SharedAccessKey=${keyvault:SB_KEY}",
+                &vault
+            )
+            .unwrap(),
+            "Endpoint=sb://ns/;SharedAccessKeyName=admin;SharedAccessKey=s3cr3t"
+        );
+
+        let error = replace_references("${keyvault:MISSING} ${keyvault:OTHER}", &vault)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("MISSING, OTHER"), "{}", error);
+    }
+
+    #[test]
+    fn test_values_without_references_dont_need_a_vault() {
+        let mut config =
+            serde_json::json!({ "host": "localhost", "ports": [1, 2], "literal": "${HOME}" });
+        let expected = config.clone();
+
+        resolve_secrets_in_json(&mut config).unwrap();
+
+        assert_eq!(config, expected);
+        assert_eq!(resolve_secret_references("plain").unwrap(), "plain");
+    }
+}
@@ -0,0 +1,243 @@
+use crate::system::load_global_config::user_config_dir;
+use age::secrecy::{ExposeSecret, SecretString};
+use age::DecryptError;
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// Environment variable with the path of the vault. Wins over the default location.
+pub const KEYVAULT_FILE_ENV: &str = "KEYVAULT_FILE";
+
+/// Environment variable with the master passphrase, for scripts and CI. Asked for otherwise.
+pub const KEYVAULT_PASSPHRASE_ENV: &str = "KEYVAULT_PASSPHRASE";
+
+const VAULT_FILE_NAME: &str = "keyvault.age";
+
+/// Local secret store: names and values, saved as a single file encrypted with a master
+/// passphrase (age format: scrypt to derive the key, ChaCha20-Poly1305 for the data).
+///
+/// The whole vault is decrypted in memory when opened, and encrypted again on `save`.
+pub struct SecretVault {
+    path: PathBuf,
+    passphrase: SecretString,
+    secrets: BTreeMap<String, String>,
+}
+
+impl SecretVault {
+    /// Opens the vault. A missing file is an empty vault, created on the first `save`.
+    ///
+    /// # Errors
+    /// Returns error if the file can't be read, the passphrase is wrong, or the vault is damaged
+    pub fn open(path: &Path, passphrase: SecretString) -> Result<Self> {
+        let secrets = if path.is_file() {
+            let encrypted =
+                fs::read(path).context(format!("Failed to read vault: {}", path.display()))?;
+
+            let identity = age::scrypt::Identity::new(copy_secret(&passphrase));
+            let decrypted = age::decrypt(&identity, &encrypted).map_err(|e| match e {
+                DecryptError::DecryptionFailed | DecryptError::NoMatchingKeys => {
+                    anyhow!("Wrong passphrase for vault: {}", path.display())
+                }
+                e => anyhow!("Failed to decrypt vault {}: {}", path.display(), e),
+            })?;
+
+            serde_json::from_slice(&decrypted)
+                .context(format!("Vault is damaged: {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            passphrase,
+            secrets,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.secrets.get(name).map(String::as_str)
+    }
+
+    /// Adds or replaces a secret. Returns true if it replaced an existing one.
+    ///
+    /// # Errors
+    /// Returns error if the name can't be used as an environment variable
+    pub fn set(&mut self, name: &str, value: &str) -> Result<bool> {
+        if !is_valid_secret_name(name) {
+            return Err(anyhow!(
+                "Invalid secret name: '{}'. Use letters, digits and '_', not starting with a digit.",
+                name
+            ));
+        }
+
+        Ok(self
+            .secrets
+            .insert(name.to_string(), value.to_string())
+            .is_some())
+    }
+
+    /// Removes a secret. Returns false if it didn't exist.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.secrets.remove(name).is_some()
+    }
+
+    /// Secrets, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.secrets
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.secrets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    /// Encrypts and writes the vault. The data goes to a temporary file first, which then replaces
+    /// the vault, so a failure never leaves it half-written.
+    ///
+    /// # Errors
+    /// Returns error if the vault can't be encrypted or written
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).context(format!(
+                    "Failed to create vault folder: {}",
+                    parent.display()
+                ))?;
+            }
+        }
+
+        let plain = serde_json::to_vec(&self.secrets).context("Failed to serialize vault")?;
+        let recipient = age::scrypt::Recipient::new(copy_secret(&self.passphrase));
+        let encrypted = age::encrypt(&recipient, &plain)
+            .map_err(|e| anyhow!("Failed to encrypt vault: {}", e))?;
+
+        let temp_path = self.path.with_extension("age.tmp");
+        fs::write(&temp_path, encrypted)
+            .context(format!("Failed to write vault: {}", temp_path.display()))?;
+        fs::rename(&temp_path, &self.path)
+            .context(format!("Failed to replace vault: {}", self.path.display()))?;
+
+        Ok(())
+    }
+}
+
+/// Secret names are used as environment variable names by `keyvault export`, so they follow the
+/// same rules: letters, digits and `_`, not starting with a digit.
+pub fn is_valid_secret_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Path of the vault: the `KEYVAULT_FILE` environment variable, or `rusted-toolbox/keyvault.age`
+/// in the user's config directory (`%APPDATA%` on Windows, `$XDG_CONFIG_HOME` or `~/.config`
+/// elsewhere).
+pub fn default_vault_path() -> Result<PathBuf> {
+    if let Some(path) = env::var_os(KEYVAULT_FILE_ENV) {
+        return Ok(PathBuf::from(path));
+    }
+
+    user_config_dir()
+        .map(|dir| dir.join("rusted-toolbox").join(VAULT_FILE_NAME))
+        .ok_or_else(|| {
+            anyhow!(
+                "Failed to find the user config directory. Set {} with the path of the vault.",
+                KEYVAULT_FILE_ENV
+            )
+        })
+}
+
+/// Reads the master passphrase from `KEYVAULT_PASSPHRASE`, or asks for it. With `confirm`, it's
+/// asked twice (used when creating a vault, since a typo would lock it).
+///
+/// # Errors
+/// Returns error if the variable is not set and there's no terminal to ask
+pub fn read_vault_passphrase(confirm: bool) -> Result<SecretString> {
+    if let Ok(passphrase) = env::var(KEYVAULT_PASSPHRASE_ENV) {
+        if !passphrase.is_empty() {
+            return Ok(SecretString::from(passphrase));
+        }
+    }
+
+    if !std::io::stderr().is_terminal() {
+        return Err(anyhow!(
+            "No passphrase for the vault. Set {} or run it in a terminal.",
+            KEYVAULT_PASSPHRASE_ENV
+        ));
+    }
+
+    let mut prompt = dialoguer::Password::new().with_prompt("Vault passphrase");
+    if confirm {
+        prompt = prompt.with_confirmation("Confirm passphrase", "Passphrases don't match");
+    }
+
+    let passphrase = prompt
+        .interact()
+        .context("Failed to read the vault passphrase")?;
+
+    Ok(SecretString::from(passphrase))
+}
+
+fn copy_secret(secret: &SecretString) -> SecretString {
+    SecretString::from(secret.expose_secret().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passphrase(value: &str) -> SecretString {
+        SecretString::from(value.to_string())
+    }
+
+    #[test]
+    fn test_secrets_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("vault.age");
+
+        let mut vault = SecretVault::open(&path, passphrase("correct horse")).unwrap();
+        assert!(vault.is_empty());
+        assert!(!vault.set("DB_PASSWORD", "p@ss'word").unwrap());
+        assert!(vault.set("DB_PASSWORD", "p@ss'word 2").unwrap());
+        vault.set("API_KEY", "abc123").unwrap();
+        vault.save().unwrap();
+
+        let content = fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&content).contains("abc123"));
+
+        let mut vault = SecretVault::open(&path, passphrase("correct horse")).unwrap();
+        assert_eq!(vault.get("DB_PASSWORD"), Some("p@ss'word 2"));
+        assert_eq!(
+            vault.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!["API_KEY", "DB_PASSWORD"]
+        );
+        assert!(vault.remove("API_KEY"));
+        assert!(!vault.remove("API_KEY"));
+
+        assert!(SecretVault::open(&path, passphrase("wrong horse")).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_secret_name() {
+        assert!(is_valid_secret_name("DB_PASSWORD"));
+        assert!(is_valid_secret_name("_token2"));
+        assert!(!is_valid_secret_name("2FA"));
+        assert!(!is_valid_secret_name("db-password"));
+        assert!(!is_valid_secret_name(""));
+    }
+}
@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Replaces the binary at `path` with `new_binary`, keeping its permissions.
+///
+/// The new binary is written next to the old one, then swapped in with renames, so a failed
+/// update leaves the old binary in place. Works on the running binary too: on Windows, where it
+/// can't be deleted, it's left as `<name>.old` and removed by [`remove_leftover_binary`] on the
+/// next update.
+///
+/// # Errors
+/// Returns an error if the binary can't be written or swapped (e.g.: no write permission on the
+/// folder).
+pub fn replace_binary(path: &Path, new_binary: &[u8]) -> Result<()> {
+    let new_path = with_suffix(path, ".new");
+    let old_path = with_suffix(path, ".old");
+
+    let permissions = fs::metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .permissions();
+
+    fs::write(&new_path, new_binary)
+        .with_context(|| format!("Failed to write {}", new_path.display()))?;
+    fs::set_permissions(&new_path, permissions)
+        .with_context(|| format!("Failed to set the permissions of {}", new_path.display()))?;
+
+    remove_leftover_binary(path);
+
+    if let Err(e) = fs::rename(path, &old_path) {
+        let _ = fs::remove_file(&new_path);
+        return Err(e).with_context(|| format!("Failed to move {} aside", path.display()));
+    }
+
+    if let Err(e) = fs::rename(&new_path, path) {
+        let _ = fs::rename(&old_path, path);
+        let _ = fs::remove_file(&new_path);
+        return Err(e).with_context(|| format!("Failed to install {}", path.display()));
+    }
+
+    remove_leftover_binary(path);
+    Ok(())
+}
+
+/// Removes the `<name>.old` left by a previous update, if any. Failures are ignored: the binary
+/// may still be running.
+pub fn remove_leftover_binary(path: &Path) {
+    let old_path = with_suffix(path, ".old");
+    if old_path.exists() {
+        let _ = fs::remove_file(old_path);
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_replace_binary_swaps_the_content() {
+        let dir = TempDir::new().unwrap();
+        let binary = dir.path().join("tool");
+        fs::write(&binary, b"old version").unwrap();
+
+        replace_binary(&binary, b"new version").unwrap();
+
+        assert_eq!(fs::read(&binary).unwrap(), b"new version");
+        assert!(!with_suffix(&binary, ".new").exists());
+        assert!(!with_suffix(&binary, ".old").exists());
+    }
+
+    #[test]
+    fn test_replace_binary_fails_without_a_binary() {
+        let dir = TempDir::new().unwrap();
+        let binary = dir.path().join("missing");
+
+        assert!(replace_binary(&binary, b"new version").is_err());
+        assert!(!with_suffix(&binary, ".new").exists());
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::{bail, Result};
+use sha2::{Digest, Sha256};
+
+/// Release asset with the checksums of every binary, in `sha256sum` format.
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Extension of the asset with the checksum of a single binary (`jwt-linux-x86_64.sha256`).
+pub const CHECKSUM_EXTENSION: &str = ".sha256";
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Finds the checksum of `file_name` in a checksums file.
+///
+/// Accepts `sha256sum` output (`<hash>  <name>` or `<hash> *<name>`, one per line) and files
+/// with just the hash (as in `<asset>.sha256`).
+pub fn find_checksum(content: &str, file_name: &str) -> Option<String> {
+    let mut only_hash = None;
+
+    for line in content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        if !is_sha256(hash) {
+            continue;
+        }
+
+        match parts.next() {
+            Some(name) if name.trim_start_matches('*') == file_name => {
+                return Some(hash.to_lowercase());
+            }
+            Some(_) => continue,
+            None => only_hash = Some(hash.to_lowercase()),
+        }
+    }
+
+    only_hash
+}
+
+/// Checks the SHA-256 of `bytes` against `expected` (hex, any case).
+///
+/// # Errors
+/// Returns an error if they don't match.
+pub fn verify_checksum(bytes: &[u8], expected: &str) -> Result<()> {
+    let actual = sha256_hex(bytes);
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        bail!(
+            "Checksum mismatch: expected {}, got {}. The download may be corrupted or tampered with",
+            expected.trim(),
+            actual
+        );
+    }
+
+    Ok(())
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_find_checksum_formats() {
+        let sums = format!(
+            "{}  jwt-linux-x86_64\n{} *how-linux-x86_64\n",
+            "a".repeat(64),
+            HELLO_SHA256.to_uppercase()
+        );
+
+        assert_eq!(
+            find_checksum(&sums, "jwt-linux-x86_64"),
+            Some("a".repeat(64))
+        );
+        assert_eq!(
+            find_checksum(&sums, "how-linux-x86_64").as_deref(),
+            Some(HELLO_SHA256)
+        );
+        assert_eq!(find_checksum(&sums, "guid-linux-x86_64"), None);
+
+        let single = format!("{}\n", HELLO_SHA256);
+        assert_eq!(
+            find_checksum(&single, "anything").as_deref(),
+            Some(HELLO_SHA256)
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        assert!(verify_checksum(b"hello", HELLO_SHA256).is_ok());
+        assert!(verify_checksum(b"hello!", HELLO_SHA256).is_err());
+    }
+}
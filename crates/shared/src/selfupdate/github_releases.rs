@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION};
+use reqwest::Client;
+use semver::Version;
+use serde::Deserialize;
+use std::env;
+
+/// Repository the tools are released from (`owner/name`).
+pub const DEFAULT_REPOSITORY: &str = "brenordv/rusted-toolbox";
+
+/// When set, used to authenticate with the GitHub API (higher rate limits, private forks).
+pub const GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// A GitHub release. Each tool is released on its own, tagged `<tool>-v<version>` (e.g.:
+/// `jwt-v1.2.0`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+impl Release {
+    /// Version of `tool` in the tag (`jwt-v1.2.0` -> `1.2.0`), or `None` if the release is of
+    /// another tool.
+    pub fn tool_version(&self, tool: &str) -> Option<Version> {
+        let version = self.tag_name.strip_prefix(tool)?.strip_prefix("-v")?;
+        Version::parse(version).ok()
+    }
+
+    pub fn find_asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Name of the binary built for this platform: `<tool>-<os>-<arch>[.exe]` (e.g.:
+/// `jwt-linux-x86_64`, `jwt-windows-x86_64.exe`).
+pub fn platform_asset_name(tool: &str) -> String {
+    format!(
+        "{}-{}-{}{}",
+        tool,
+        env::consts::OS,
+        env::consts::ARCH,
+        env::consts::EXE_SUFFIX
+    )
+}
+
+/// HTTP client for the GitHub API and downloads. Uses `GITHUB_TOKEN`, when set.
+///
+/// # Errors
+/// Returns an error if the token is not a valid header value, or the client can't be built.
+pub fn build_github_client() -> Result<Client> {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        ACCEPT,
+        HeaderValue::from_static("application/vnd.github+json"),
+    );
+
+    if let Ok(token) = env::var(GITHUB_TOKEN_ENV) {
+        if !token.trim().is_empty() {
+            let value = HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+                .with_context(|| format!("{} is not a valid token", GITHUB_TOKEN_ENV))?;
+            headers.insert(AUTHORIZATION, value);
+        }
+    }
+
+    Client::builder()
+        .user_agent(concat!(
+            "rusted-toolbox-selfupdate/",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .default_headers(headers)
+        .build()
+        .context("Failed to build the HTTP client")
+}
+
+/// Fetches the most recent releases (up to 100) of `repository` (`owner/name`).
+///
+/// # Errors
+/// Returns an error if GitHub can't be reached, or answers with an error status.
+pub async fn fetch_releases(client: &Client, repository: &str) -> Result<Vec<Release>> {
+    let url = format!(
+        "{}/repos/{}/releases?per_page=100",
+        GITHUB_API_URL, repository
+    );
+
+    client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach GitHub ({})", url))?
+        .error_for_status()
+        .with_context(|| format!("GitHub refused to list the releases of {}", repository))?
+        .json::<Vec<Release>>()
+        .await
+        .context("Failed to read the list of releases")
+}
+
+/// Newest release of `tool`, skipping drafts (and pre-releases, unless `include_prereleases`).
+pub fn latest_release<'a>(
+    releases: &'a [Release],
+    tool: &str,
+    include_prereleases: bool,
+) -> Option<(Version, &'a Release)> {
+    releases
+        .iter()
+        .filter(|release| !release.draft && (include_prereleases || !release.prerelease))
+        .filter_map(|release| release.tool_version(tool).map(|version| (version, release)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            draft: false,
+            prerelease,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_latest_release_picks_the_newest_of_the_tool() {
+        let releases = vec![
+            release("jwt-v1.1.0", false),
+            release("jwt-v1.10.0", false),
+            release("jwt-v2.0.0-beta.1", true),
+            release("jwt-extra-v9.0.0", false),
+            release("guid-v3.0.0", false),
+            release("jwt-vnext", false),
+        ];
+
+        let (version, latest) = latest_release(&releases, "jwt", false).unwrap();
+        assert_eq!(version, Version::new(1, 10, 0));
+        assert_eq!(latest.tag_name, "jwt-v1.10.0");
+
+        let (version, _) = latest_release(&releases, "jwt", true).unwrap();
+        assert_eq!(version, Version::parse("2.0.0-beta.1").unwrap());
+
+        assert!(latest_release(&releases, "how", true).is_none());
+    }
+
+    #[test]
+    fn test_release_list_is_parsed() {
+        let json = r#"[{"tag_name": "how-v1.1.0", "draft": false, "prerelease": false,
+            "assets": [{"name": "how-linux-x86_64", "size": 10,
+            "browser_download_url": "https://example.com/how-linux-x86_64"}]}]"#;
+
+        let releases: Vec<Release> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(releases[0].tool_version("how"), Some(Version::new(1, 1, 0)));
+        assert_eq!(releases[0].find_asset("how-linux-x86_64").unwrap().size, 10);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod binary_swap;
+pub mod checksum;
+pub mod github_releases;
+pub mod updater;
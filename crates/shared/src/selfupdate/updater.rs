@@ -0,0 +1,202 @@
+use crate::selfupdate::binary_swap::replace_binary;
+use crate::selfupdate::checksum::{
+    find_checksum, verify_checksum, CHECKSUMS_ASSET, CHECKSUM_EXTENSION,
+};
+use crate::selfupdate::github_releases::{
+    build_github_client, fetch_releases, latest_release, platform_asset_name, Release, ReleaseAsset,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::Client;
+use semver::Version;
+use std::path::Path;
+
+/// Result of checking one tool for updates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateCheck {
+    pub tool: String,
+    pub current_version: Version,
+    /// Newest release of the tool, if it was ever released.
+    pub latest: Option<(Version, Release)>,
+}
+
+impl UpdateCheck {
+    pub fn latest_version(&self) -> Option<&Version> {
+        self.latest.as_ref().map(|(version, _)| version)
+    }
+
+    pub fn is_update_available(&self) -> bool {
+        self.latest_version()
+            .is_some_and(|latest| *latest > self.current_version)
+    }
+
+    /// The binary of the latest release for this platform, if it was published.
+    pub fn platform_asset(&self) -> Option<&ReleaseAsset> {
+        let (_, release) = self.latest.as_ref()?;
+        release.find_asset(&platform_asset_name(&self.tool))
+    }
+}
+
+/// Checks and installs tool updates from the GitHub releases of a repository.
+///
+/// ```ignore
+/// let updater = SelfUpdater::connect(DEFAULT_REPOSITORY, false).await?;
+/// let check = updater.check("jwt", "1.1.0")?;
+/// if check.is_update_available() {
+///     updater.update(&check, &binary_path).await?;
+/// }
+/// ```
+///
+/// The releases are fetched once, on `connect`, so checking many tools costs a single request.
+pub struct SelfUpdater {
+    client: Client,
+    repository: String,
+    include_prereleases: bool,
+    releases: Vec<Release>,
+}
+
+impl SelfUpdater {
+    /// Fetches the releases of `repository` (`owner/name`).
+    ///
+    /// # Errors
+    /// Returns an error if GitHub can't be reached or refuses the request.
+    pub async fn connect(repository: &str, include_prereleases: bool) -> Result<Self> {
+        let client = build_github_client()?;
+        let releases = fetch_releases(&client, repository).await?;
+
+        Ok(Self {
+            client,
+            repository: repository.to_string(),
+            include_prereleases,
+            releases,
+        })
+    }
+
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    /// Compares `current_version` of `tool` with its latest release.
+    ///
+    /// # Errors
+    /// Returns an error if `current_version` is not a valid version.
+    pub fn check(&self, tool: &str, current_version: &str) -> Result<UpdateCheck> {
+        let current_version = Version::parse(current_version.trim())
+            .with_context(|| format!("'{}' is not a valid version", current_version))?;
+
+        let latest = latest_release(&self.releases, tool, self.include_prereleases)
+            .map(|(version, release)| (version, release.clone()));
+
+        Ok(UpdateCheck {
+            tool: tool.to_string(),
+            current_version,
+            latest,
+        })
+    }
+
+    /// Downloads the latest release of the tool for this platform, verifies its SHA-256, and
+    /// swaps it with the binary at `binary_path`. Returns the installed version.
+    ///
+    /// Releases without a checksum (`<asset>.sha256` or `SHA256SUMS`) are refused.
+    ///
+    /// # Errors
+    /// Returns an error if there's no binary for this platform, no checksum, the checksum doesn't
+    /// match, or the binary can't be replaced.
+    pub async fn update(&self, check: &UpdateCheck, binary_path: &Path) -> Result<Version> {
+        let (version, release) = check
+            .latest
+            .as_ref()
+            .ok_or_else(|| anyhow!("{} has no releases", check.tool))?;
+
+        let asset_name = platform_asset_name(&check.tool);
+        let asset = check.platform_asset().ok_or_else(|| {
+            anyhow!(
+                "Release {} has no binary for this platform ({})",
+                release.tag_name,
+                asset_name
+            )
+        })?;
+
+        let expected_checksum = self.fetch_checksum(release, &asset_name).await?;
+        let binary = self.download(&asset.browser_download_url).await?;
+        verify_checksum(&binary, &expected_checksum)
+            .with_context(|| format!("Refusing to install {}", asset_name))?;
+
+        replace_binary(binary_path, &binary)?;
+
+        Ok(version.clone())
+    }
+
+    async fn fetch_checksum(&self, release: &Release, asset_name: &str) -> Result<String> {
+        let checksum_asset = release
+            .find_asset(&format!("{}{}", asset_name, CHECKSUM_EXTENSION))
+            .or_else(|| release.find_asset(CHECKSUMS_ASSET));
+
+        let Some(checksum_asset) = checksum_asset else {
+            bail!(
+                "Release {} has no checksum ({}{} or {}). Refusing to install an unverified binary",
+                release.tag_name,
+                asset_name,
+                CHECKSUM_EXTENSION,
+                CHECKSUMS_ASSET
+            );
+        };
+
+        let content = self.download(&checksum_asset.browser_download_url).await?;
+        find_checksum(&String::from_utf8_lossy(&content), asset_name)
+            .ok_or_else(|| anyhow!("{} has no checksum for {}", checksum_asset.name, asset_name))
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>> {
+        let bytes = self
+            .client
+            .get(url)
+            .header(reqwest::header::ACCEPT, "application/octet-stream")
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", url))?
+            .error_for_status()
+            .with_context(|| format!("Failed to download {}", url))?
+            .bytes()
+            .await
+            .with_context(|| format!("Failed to download {}", url))?;
+
+        Ok(bytes.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_check_compares_versions() {
+        let release = Release {
+            tag_name: "jwt-v1.2.0".to_string(),
+            draft: false,
+            prerelease: false,
+            assets: vec![ReleaseAsset {
+                name: platform_asset_name("jwt"),
+                browser_download_url: "https://example.com/jwt".to_string(),
+                size: 1,
+            }],
+        };
+
+        let check = |current: &str| UpdateCheck {
+            tool: "jwt".to_string(),
+            current_version: Version::parse(current).unwrap(),
+            latest: Some((Version::new(1, 2, 0), release.clone())),
+        };
+
+        assert!(check("1.1.0").is_update_available());
+        assert!(!check("1.2.0").is_update_available());
+        assert!(!check("1.3.0").is_update_available());
+        assert!(check("1.1.0").platform_asset().is_some());
+
+        let never_released = UpdateCheck {
+            tool: "jwt".to_string(),
+            current_version: Version::new(1, 0, 0),
+            latest: None,
+        };
+        assert!(!never_released.is_update_available());
+    }
+}
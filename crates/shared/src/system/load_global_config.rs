@@ -0,0 +1,167 @@
+use crate::secrets::resolve_secrets::resolve_secrets_in_json;
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the global configuration file, shared by every tool.
+pub const GLOBAL_CONFIG_FILE_NAME: &str = "rusted-toolbox.json";
+
+/// Environment variable with the path of the global configuration file. Wins over the default
+/// locations.
+pub const GLOBAL_CONFIG_ENV: &str = "RUSTED_TOOLBOX_CONFIG";
+
+/// Finds the global configuration file, checking (in order):
+/// 1. The path in the `RUSTED_TOOLBOX_CONFIG` environment variable
+/// 2. `rusted-toolbox.json` in the executable's directory
+/// 3. `rusted-toolbox/rusted-toolbox.json` in the user's config directory (`%APPDATA%` on Windows,
+///    `$XDG_CONFIG_HOME` or `~/.config` elsewhere)
+///
+/// Returns `None` when there is no file in any of them.
+pub fn global_config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(GLOBAL_CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+
+    let exe_dir_file = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(GLOBAL_CONFIG_FILE_NAME)));
+
+    let user_dir_file =
+        user_config_dir().map(|dir| dir.join("rusted-toolbox").join(GLOBAL_CONFIG_FILE_NAME));
+
+    [exe_dir_file, user_dir_file]
+        .into_iter()
+        .flatten()
+        .find(|path| path.is_file())
+}
+
+/// Loads the section of a tool from the global configuration file.
+///
+/// The file is a JSON object with one key per tool (e.g.: `{ "whisper": { ... } }`). Values can
+/// reference vault secrets as `${keyvault:NAME}` (see `resolve_secrets`).
+///
+/// # Returns
+/// - `Ok(Some(T))`: The section exists and was parsed
+/// - `Ok(None)`: There is no global configuration file, or it has no section for the tool
+/// - `Err`: The file can't be read, isn't valid JSON, or the section doesn't match `T`
+pub fn load_global_config_section<T>(section: &str) -> Result<Option<T>>
+where
+    T: DeserializeOwned,
+{
+    match global_config_path() {
+        Some(path) => load_config_section_from_file(&path, section),
+        None => Ok(None),
+    }
+}
+
+/// Same as `load_global_config_section`, for a specific file.
+pub fn load_config_section_from_file<T>(path: &Path, section: &str) -> Result<Option<T>>
+where
+    T: DeserializeOwned,
+{
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read config file {:?}: {}", path, e))?;
+
+    // Remove a leading UTF-8 BOM if present
+    let content = content.trim_start_matches('\u{FEFF}');
+
+    let mut root: Value = serde_json::from_str(content)
+        .map_err(|e| anyhow!("Failed to parse JSON from config file {:?}: {}", path, e))?;
+
+    let Some(mut section_value) = root.get_mut(section).map(Value::take) else {
+        return Ok(None);
+    };
+
+    resolve_secrets_in_json(&mut section_value)?;
+
+    let parsed = serde_json::from_value(section_value).map_err(|e| {
+        anyhow!(
+            "Invalid `{}` section in config file {:?}: {}",
+            section,
+            path,
+            e
+        )
+    })?;
+
+    Ok(Some(parsed))
+}
+
+pub(crate) fn user_config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env::var_os("APPDATA").map(PathBuf::from);
+    }
+
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+/// Folder where tools keep their data (`%LOCALAPPDATA%` on Windows, `$XDG_DATA_HOME` or
+/// `~/.local/share` elsewhere), under `rusted-toolbox/<tool>`.
+pub fn user_data_dir(tool: &str) -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_DATA_HOME").map(PathBuf::from).or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+        })
+    };
+
+    base.map(|dir| dir.join("rusted-toolbox").join(tool))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct ToolSection {
+        theme: String,
+        #[serde(default)]
+        max_items: u32,
+    }
+
+    fn config_file(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_loads_only_the_requested_section() {
+        let file = config_file(
+            r#"{ "whisper": { "theme": "light", "maxItems": 3 }, "other": { "x": 1 } }"#,
+        );
+
+        let section: Option<ToolSection> =
+            load_config_section_from_file(file.path(), "whisper").unwrap();
+        assert_eq!(
+            section,
+            Some(ToolSection {
+                theme: "light".to_string(),
+                max_items: 3
+            })
+        );
+
+        let missing: Option<ToolSection> =
+            load_config_section_from_file(file.path(), "lookup").unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn test_invalid_section_names_the_section() {
+        let file = config_file("\u{FEFF}{ \"whisper\": { \"theme\": 42 } }");
+
+        let error = load_config_section_from_file::<ToolSection>(file.path(), "whisper")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Invalid `whisper` section"), "{}", error);
+    }
+}
@@ -1,9 +1,11 @@
+use crate::secrets::resolve_secrets::resolve_secrets_in_json;
 use anyhow::{anyhow, Result};
 use serde::de::DeserializeOwned;
 use std::path::Path;
 use tokio::fs;
 
-/// Loads a JSON file and deserializes it into the specified type.
+/// Loads a JSON file and deserializes it into the specified type. String values can reference
+/// vault secrets as `${keyvault:NAME}`, resolved before deserializing.
 ///
 /// # Arguments
 /// * `path` - The path to the JSON file to load
@@ -15,6 +17,7 @@ use tokio::fs;
 /// * Returns an error if the file cannot be read
 /// * Returns an error if the JSON cannot be parsed
 /// * Returns an error if the JSON structure doesn't match the expected type T
+/// * Returns an error if a referenced secret can't be resolved
 pub async fn load_json_file_to_object<T>(path: &Path) -> Result<T>
 where
     T: DeserializeOwned,
@@ -26,7 +29,12 @@ where
     // Remove a leading UTF-8 BOM if present
     let content = content.trim_start_matches('\u{FEFF}');
 
-    let object: T = serde_json::from_str(content)
+    let mut value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| anyhow!("Failed to parse JSON from file {:?}: {}", path, e))?;
+
+    resolve_secrets_in_json(&mut value)?;
+
+    let object: T = serde_json::from_value(value)
         .map_err(|e| anyhow!("Failed to parse JSON from file {:?}: {}", path, e))?;
 
     Ok(object)
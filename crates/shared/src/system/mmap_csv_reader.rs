@@ -1,40 +1,274 @@
+use crate::constants::general::SIZE_128KB;
 use anyhow::{Context, Result};
-use csv::{Reader, StringRecord, StringRecordsIter};
+use csv::{Reader, ReaderBuilder, StringRecord, StringRecordsIter};
+use encoding_rs::{Encoding, UTF_8};
+use encoding_rs_io::DecodeReaderBytesBuilder;
 use memmap2::Mmap;
 use std::fs::File;
-use std::io::Cursor;
-use std::path::PathBuf;
+use std::io::{BufReader, Cursor, Read};
+use std::path::Path;
 
-/// TODO: Add summary
-pub struct MmapCsvReader {
-    _mmap: Mmap,
-    reader: Reader<Cursor<&'static [u8]>>,
+/// How the file is read.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReadMode {
+    /// The file is read through a 128KB buffer.
+    #[default]
+    Buffered,
+    /// The file is memory mapped: the OS pages it in as it's read, without read calls. Opt-in:
+    /// the file must not change (or be truncated) while read.
+    Mmap,
 }
 
-impl MmapCsvReader {
-    /// TODO: Add summary
-    pub fn new(file: &PathBuf) -> Result<Self> {
-        let file = File::open(file).context("Unable to open file for memory mapping")?;
-        let mmap = unsafe { Mmap::map(&file).context("Unable to memory map file")? };
+/// How the CSV is parsed. The defaults are the RFC 4180 ones: comma, double quotes (escaped by
+/// doubling them), headers in the first record, UTF-8.
+///
+/// ```ignore
+/// let options = CsvReaderOptions::default()
+///     .with_delimiter(b';')
+///     .with_mode(ReadMode::Mmap);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CsvReaderOptions {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// Escape character inside quotes (e.g.: `\"`). Without one, quotes are escaped by doubling them.
+    pub escape: Option<u8>,
+    pub has_headers: bool,
+    /// Accepts records with a different number of fields than the headers.
+    pub flexible: bool,
+    /// Encoding of the file. Files that are not UTF-8 are decoded (and their BOM removed) while
+    /// read, so the records are always UTF-8. `None` is UTF-8.
+    pub encoding: Option<&'static Encoding>,
+    pub mode: ReadMode,
+}
+
+impl Default for CsvReaderOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            has_headers: true,
+            flexible: false,
+            encoding: None,
+            mode: ReadMode::Buffered,
+        }
+    }
+}
+
+impl CsvReaderOptions {
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn with_escape(mut self, escape: Option<u8>) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    pub fn with_flexible(mut self, flexible: bool) -> Self {
+        self.flexible = flexible;
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: Option<&'static Encoding>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ReadMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+/// Streams the records of a CSV file, one at a time, buffered or memory mapped.
+///
+/// Iterating over the reader yields the records after the headers (or every record, without
+/// headers). The headers can be read at any time.
+///
+/// ```ignore
+/// let mut reader = CsvRecordReader::open(&path, &CsvReaderOptions::default())?;
+/// let headers = reader.headers()?.clone();
+///
+/// for record in reader.by_ref().filter_map(Result::ok) {
+///     println!("{}", &record[0]);
+/// }
+/// ```
+pub struct CsvRecordReader {
+    reader: Reader<Box<dyn Read>>,
+    /// Reused for every record, so its buffers grow once instead of once per record.
+    record: StringRecord,
+    // Declared after the reader, so it's dropped after it: the reader borrows the mapped bytes.
+    _mmap: Option<Mmap>,
+    mode: ReadMode,
+}
+
+impl CsvRecordReader {
+    /// Opens the file with the given options.
+    ///
+    /// # Errors
+    /// Returns error if the file can't be opened or memory mapped.
+    pub fn open(file: &Path, options: &CsvReaderOptions) -> Result<Self> {
+        let file = File::open(file).context(format!("Unable to open file: {}", file.display()))?;
+
+        let (input, mmap): (Box<dyn Read>, Option<Mmap>) = match options.mode {
+            ReadMode::Mmap => {
+                let mmap = unsafe { Mmap::map(&file).context("Unable to memory map file")? };
+
+                // Convert to static lifetime while we hold the mmap
+                let data: &'static [u8] = unsafe { std::mem::transmute(mmap.as_ref()) };
+                (Box::new(Cursor::new(data)), Some(mmap))
+            }
+            ReadMode::Buffered => (Box::new(BufReader::with_capacity(SIZE_128KB, file)), None),
+        };
+
+        // The csv reader already skips the UTF-8 BOM, so UTF-8 is read as is.
+        let input: Box<dyn Read> = match options.encoding {
+            Some(encoding) if encoding != UTF_8 => Box::new(
+                DecodeReaderBytesBuilder::new()
+                    .encoding(Some(encoding))
+                    .strip_bom(true)
+                    .build(input),
+            ),
+            _ => input,
+        };
 
-        // Convert to static lifetime while we hold the mmap
-        let data: &'static [u8] = unsafe { std::mem::transmute(mmap.as_ref()) };
-        let cursor = Cursor::new(data);
-        let reader = Reader::from_reader(cursor);
+        let reader = ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .escape(options.escape)
+            .double_quote(options.escape.is_none())
+            .has_headers(options.has_headers)
+            .flexible(options.flexible)
+            .buffer_capacity(SIZE_128KB)
+            .from_reader(input);
 
         Ok(Self {
-            _mmap: mmap,
             reader,
+            record: StringRecord::new(),
+            _mmap: mmap,
+            mode: options.mode,
         })
     }
 
-    /// TODO: Add summary
+    /// Opens a comma-separated, UTF-8 file, memory mapped.
+    ///
+    /// # Errors
+    /// Returns error if the file can't be opened or memory mapped.
+    pub fn open_default(file: &Path) -> Result<Self> {
+        Self::open(file, &CsvReaderOptions::default())
+    }
+
+    pub fn mode(&self) -> ReadMode {
+        self.mode
+    }
+
+    /// Headers of the file (its first record). Without headers, it's the first record, which is
+    /// still returned by the iterator.
     pub fn headers(&mut self) -> csv::Result<&StringRecord> {
         self.reader.headers()
     }
 
-    /// TODO: Add summary
-    pub fn records(&'_ mut self) -> StringRecordsIter<'_, Cursor<&'static [u8]>> {
+    /// Reads the next record into `record`, reusing its buffers. The fastest way to read, when
+    /// the records don't need to be kept. Returns false at the end of the file.
+    pub fn read_record(&mut self, record: &mut StringRecord) -> csv::Result<bool> {
+        self.reader.read_record(record)
+    }
+
+    /// Iterates over the records, borrowing the reader.
+    pub fn records(&'_ mut self) -> StringRecordsIter<'_, Box<dyn Read>> {
         self.reader.records()
     }
 }
+
+impl Iterator for CsvRecordReader {
+    type Item = csv::Result<StringRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.read_record(&mut self.record) {
+            Ok(true) => Some(Ok(self.record.clone())),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::fs;
+
+    const CONTENT: &str =
+        "id,name,notes\n1,\"Doe, John\",\"said \"\"hi\"\"\"\n2,Alice,\"two\nlines\"\n3,Bob,\n";
+
+    #[rstest]
+    #[case(ReadMode::Mmap)]
+    #[case(ReadMode::Buffered)]
+    fn test_reads_quoted_records_with_headers(#[case] mode: ReadMode) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.csv");
+        fs::write(&path, CONTENT).unwrap();
+
+        let options = CsvReaderOptions::default().with_mode(mode);
+        let mut reader = CsvRecordReader::open(&path, &options).unwrap();
+        assert_eq!(reader.mode(), mode);
+        assert_eq!(reader.headers().unwrap(), vec!["id", "name", "notes"]);
+
+        let records: Vec<StringRecord> = reader.by_ref().map(Result::unwrap).collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], vec!["1", "Doe, John", "said \"hi\""]);
+        assert_eq!(records[1], vec!["2", "Alice", "two\nlines"]);
+        assert_eq!(records[2], vec!["3", "Bob", ""]);
+    }
+
+    #[test]
+    fn test_reads_other_formats() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // Escaped quotes, semicolons, no headers.
+        let path = dir.path().join("escaped.csv");
+        fs::write(&path, "1;'it\\'s';x\n2;b;y\n").unwrap();
+        let options = CsvReaderOptions::default()
+            .with_delimiter(b';')
+            .with_quote(b'\'')
+            .with_escape(Some(b'\\'))
+            .with_headers(false);
+        let records: Vec<StringRecord> = CsvRecordReader::open(&path, &options)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records[0], vec!["1", "it's", "x"]);
+        assert_eq!(records.len(), 2);
+
+        // UTF-16 with a BOM, decoded to UTF-8.
+        let path = dir.path().join("utf16.csv");
+        let bytes: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(
+                "city\nSão Paulo\n"
+                    .encode_utf16()
+                    .flat_map(u16::to_le_bytes),
+            )
+            .collect();
+        fs::write(&path, bytes).unwrap();
+        let options = CsvReaderOptions::default().with_encoding(Some(encoding_rs::UTF_16LE));
+        let mut reader = CsvRecordReader::open(&path, &options).unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["city"]);
+        assert_eq!(reader.next().unwrap().unwrap(), vec!["São Paulo"]);
+
+        assert!(CsvRecordReader::open_default(&dir.path().join("missing.csv")).is_err());
+    }
+}
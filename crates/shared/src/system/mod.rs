@@ -3,9 +3,12 @@ pub mod folder_walkthrough;
 pub mod get_current_working_dir;
 pub mod get_full_filepath_from_string;
 pub mod load_env_variables;
+pub mod load_global_config;
 pub mod load_json_file_to_object;
 pub mod mmap_csv_reader;
 pub mod monitor_folder;
+pub mod pathbuf_extensions;
+pub mod project_detection;
 pub mod resolve_path_with_base;
 pub mod select_file_from_path;
 pub mod setup_graceful_shutdown;
@@ -0,0 +1,229 @@
+use std::path::{Path, PathBuf};
+
+/// Archive or compression format of a file, detected by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionType {
+    /// `.zip`
+    Zip,
+    /// `.tar`
+    Tar,
+    /// `.tar.gz` or `.tgz`
+    TarGz,
+    /// `.gz` (a single compressed file)
+    Gzip,
+    /// `.7z`
+    SevenZip,
+    /// `.rar`
+    Rar,
+}
+
+impl CompressionType {
+    /// True for formats that hold many files (zip, tar, 7z, and rar), false for single compressed
+    /// files.
+    pub fn is_archive(&self) -> bool {
+        !matches!(self, CompressionType::Gzip)
+    }
+}
+
+/// Helpers to classify files by their extension (case-insensitive), and to name new files.
+pub trait PathBufExtensions {
+    /// Archive or compression format of the file, if it has a known extension.
+    fn compression_type(&self) -> Option<CompressionType>;
+
+    fn is_compressed(&self) -> bool {
+        self.compression_type().is_some()
+    }
+
+    /// Number of the part (starting at 1), when the file is a part of a multi-part archive:
+    /// `backup.7z.001` (or any archive followed by a 3+ digit number, as split by 7-Zip),
+    /// `movie.part1.rar`/`movie.part01.rar`, or `movie.r00` (old-style rar volumes, where
+    /// `movie.rar` is the first part).
+    fn multi_part_index(&self) -> Option<u32>;
+
+    /// True for the part to open when reading a multi-part archive (`backup.7z.001`,
+    /// `movie.part1.rar`). False for the other parts, and for files that aren't parts. A `.rar`
+    /// followed by `.r00` volumes can't be told apart from a single `.rar` by its name, so it's
+    /// false too.
+    fn is_main_file_multi_part_compression(&self) -> bool {
+        self.multi_part_index() == Some(1)
+    }
+
+    fn is_pdf(&self) -> bool;
+
+    /// Raster image formats: jpg, jpeg, png, gif, webp, avif, tiff, tif, and bmp.
+    fn is_image(&self) -> bool;
+
+    /// The path itself when nothing exists there. Otherwise, the first free path with a counter
+    /// before the extension: `notes (1).txt`, `notes (2).txt`, and so on (`.tar.gz` is kept
+    /// together: `backup (1).tar.gz`).
+    fn next_available_file(&self) -> PathBuf;
+}
+
+impl<P: AsRef<Path>> PathBufExtensions for P {
+    fn compression_type(&self) -> Option<CompressionType> {
+        let name = self
+            .as_ref()
+            .file_name()?
+            .to_string_lossy()
+            .to_ascii_lowercase();
+
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(CompressionType::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(CompressionType::Tar)
+        } else if name.ends_with(".zip") {
+            Some(CompressionType::Zip)
+        } else if name.ends_with(".gz") {
+            Some(CompressionType::Gzip)
+        } else if name.ends_with(".7z") {
+            Some(CompressionType::SevenZip)
+        } else if name.ends_with(".rar") {
+            Some(CompressionType::Rar)
+        } else {
+            None
+        }
+    }
+
+    fn multi_part_index(&self) -> Option<u32> {
+        let path = self.as_ref();
+        let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+        let (stem, extension) = name.rsplit_once('.')?;
+        let is_number =
+            |value: &str| !value.is_empty() && value.chars().all(|c| c.is_ascii_digit());
+
+        if extension == "rar" {
+            let (_, part) = stem.rsplit_once(".part")?;
+            return is_number(part).then(|| part.parse().ok()).flatten();
+        }
+
+        if let Some(volume) = extension.strip_prefix('r') {
+            if volume.len() == 2 && is_number(volume) {
+                return volume.parse::<u32>().ok().map(|volume| volume + 2);
+            }
+        }
+
+        if extension.len() >= 3 && is_number(extension) && Path::new(stem).is_compressed() {
+            return extension.parse().ok().filter(|part| *part > 0);
+        }
+
+        None
+    }
+
+    fn is_pdf(&self) -> bool {
+        self.as_ref()
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+    }
+
+    fn is_image(&self) -> bool {
+        self.as_ref().extension().is_some_and(|ext| {
+            matches!(
+                ext.to_string_lossy().to_ascii_lowercase().as_str(),
+                "jpg" | "jpeg" | "png" | "gif" | "webp" | "avif" | "tiff" | "tif" | "bmp"
+            )
+        })
+    }
+
+    fn next_available_file(&self) -> PathBuf {
+        let path = self.as_ref();
+        if !path.exists() {
+            return path.to_path_buf();
+        }
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let extension_len = if name.to_ascii_lowercase().ends_with(".tar.gz") {
+            ".tar.gz".len()
+        } else {
+            path.extension()
+                .map_or(0, |ext| ext.to_string_lossy().len() + 1)
+        };
+        let (stem, extension) = name.split_at(name.len() - extension_len);
+
+        (1u64..)
+            .map(|counter| path.with_file_name(format!("{} ({}){}", stem, counter, extension)))
+            .find(|candidate| !candidate.exists())
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    #[rstest]
+    #[case("logs.zip", Some(CompressionType::Zip))]
+    #[case("backup.TAR", Some(CompressionType::Tar))]
+    #[case("release.tar.gz", Some(CompressionType::TarGz))]
+    #[case("release.tgz", Some(CompressionType::TarGz))]
+    #[case("app.log.gz", Some(CompressionType::Gzip))]
+    #[case("photos.7z", Some(CompressionType::SevenZip))]
+    #[case("movie.part1.RAR", Some(CompressionType::Rar))]
+    #[case("photos.7z.001", None)]
+    #[case("notes.txt", None)]
+    #[case("gz", None)]
+    fn test_compression_type(#[case] file: &str, #[case] expected: Option<CompressionType>) {
+        assert_eq!(PathBuf::from(file).compression_type(), expected);
+    }
+
+    #[rstest]
+    #[case("photos.7z.001", Some(1))]
+    #[case("photos.7z.002", Some(2))]
+    #[case("backup.tar.gz.010", Some(10))]
+    #[case("movie.part1.rar", Some(1))]
+    #[case("movie.PART03.rar", Some(3))]
+    #[case("movie.r00", Some(2))]
+    #[case("movie.rar", None)]
+    #[case("photos.7z", None)]
+    #[case("notes.txt.001", None)]
+    #[case("photos.7z.000", None)]
+    #[case("report.r1", None)]
+    fn test_multi_part_index(#[case] file: &str, #[case] expected: Option<u32>) {
+        assert_eq!(PathBuf::from(file).multi_part_index(), expected);
+        assert_eq!(
+            PathBuf::from(file).is_main_file_multi_part_compression(),
+            expected == Some(1)
+        );
+    }
+
+    #[test]
+    fn test_next_available_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes.txt");
+        let backup = dir.path().join("backup.tar.gz");
+
+        assert_eq!(notes.next_available_file(), notes);
+
+        std::fs::write(&notes, "1").unwrap();
+        std::fs::write(dir.path().join("notes (1).txt"), "2").unwrap();
+        std::fs::write(&backup, "3").unwrap();
+
+        assert_eq!(
+            notes.next_available_file(),
+            dir.path().join("notes (2).txt")
+        );
+        assert_eq!(
+            backup.next_available_file(),
+            dir.path().join("backup (1).tar.gz")
+        );
+    }
+
+    #[test]
+    fn test_is_pdf() {
+        assert!(Path::new("docs/Manual.PDF").is_pdf());
+        assert!(!Path::new("docs/manual.pdf.zip").is_pdf());
+    }
+
+    #[test]
+    fn test_is_image() {
+        assert!(Path::new("photos/IMG_001.JPG").is_image());
+        assert!(Path::new("photos/scan.tif").is_image());
+        assert!(!Path::new("photos/notes.txt").is_image());
+        assert!(!Path::new("photos/jpg").is_image());
+    }
+}
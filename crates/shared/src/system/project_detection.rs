@@ -0,0 +1,246 @@
+use std::path::{Path, PathBuf};
+use walkdir::{DirEntry, WalkDir};
+
+/// Folders never scanned for projects: dependencies, build outputs, virtual environments and VCS data.
+const SKIPPED_FOLDERS: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    "node_modules",
+    "target",
+    "venv",
+    ".venv",
+    "__pycache__",
+    "build",
+    "dist",
+    "bin",
+    "obj",
+    "vendor",
+    ".dart_tool",
+    "_build",
+    "deps",
+];
+
+/// Kind of project, detected by its manifest (or build) files.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ProjectKind {
+    Rust,
+    Node,
+    Python,
+    Go,
+    Maven,
+    Gradle,
+    Dotnet,
+    Ruby,
+    Composer,
+    Dart,
+    Elixir,
+    Swift,
+}
+
+impl ProjectKind {
+    pub const ALL: [ProjectKind; 12] = [
+        ProjectKind::Rust,
+        ProjectKind::Node,
+        ProjectKind::Python,
+        ProjectKind::Go,
+        ProjectKind::Maven,
+        ProjectKind::Gradle,
+        ProjectKind::Dotnet,
+        ProjectKind::Ruby,
+        ProjectKind::Composer,
+        ProjectKind::Dart,
+        ProjectKind::Elixir,
+        ProjectKind::Swift,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProjectKind::Rust => "rust",
+            ProjectKind::Node => "node",
+            ProjectKind::Python => "python",
+            ProjectKind::Go => "go",
+            ProjectKind::Maven => "maven",
+            ProjectKind::Gradle => "gradle",
+            ProjectKind::Dotnet => "dotnet",
+            ProjectKind::Ruby => "ruby",
+            ProjectKind::Composer => "composer",
+            ProjectKind::Dart => "dart",
+            ProjectKind::Elixir => "elixir",
+            ProjectKind::Swift => "swift",
+        }
+    }
+
+    /// Files that mark the root of a project of this kind.
+    pub fn marker_files(&self) -> &'static [&'static str] {
+        match self {
+            ProjectKind::Rust => &["Cargo.toml"],
+            ProjectKind::Node => &["package.json"],
+            ProjectKind::Python => &["pyproject.toml", "setup.py", "requirements.txt", "Pipfile"],
+            ProjectKind::Go => &["go.mod"],
+            ProjectKind::Maven => &["pom.xml"],
+            ProjectKind::Gradle => &["build.gradle", "build.gradle.kts", "settings.gradle"],
+            ProjectKind::Dotnet => &[],
+            ProjectKind::Ruby => &["Gemfile"],
+            ProjectKind::Composer => &["composer.json"],
+            ProjectKind::Dart => &["pubspec.yaml"],
+            ProjectKind::Elixir => &["mix.exs"],
+            ProjectKind::Swift => &["Package.swift"],
+        }
+    }
+
+    /// Extensions (lowercase, without the dot) that mark the root of a project of this kind,
+    /// for ecosystems where the manifest is named after the project.
+    pub fn marker_extensions(&self) -> &'static [&'static str] {
+        match self {
+            ProjectKind::Dotnet => &["sln", "slnx", "csproj", "fsproj", "vbproj"],
+            _ => &[],
+        }
+    }
+
+    /// Folders generated by the toolchain (dependencies and build outputs), as ignore patterns.
+    pub fn generated_folders(&self) -> &'static [&'static str] {
+        match self {
+            ProjectKind::Rust => &["target/"],
+            ProjectKind::Node => &["node_modules/", "dist/"],
+            ProjectKind::Python => &["__pycache__/", ".venv/", "venv/"],
+            ProjectKind::Go => &[],
+            ProjectKind::Maven => &["target/"],
+            ProjectKind::Gradle => &[".gradle/", "build/"],
+            ProjectKind::Dotnet => &["bin/", "obj/"],
+            ProjectKind::Ruby => &["vendor/bundle/"],
+            ProjectKind::Composer => &["vendor/"],
+            ProjectKind::Dart => &[".dart_tool/", "build/"],
+            ProjectKind::Elixir => &["_build/", "deps/"],
+            ProjectKind::Swift => &[".build/"],
+        }
+    }
+
+    fn is_marker(&self, file_name: &str) -> bool {
+        if self.marker_files().contains(&file_name) {
+            return true;
+        }
+
+        Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.marker_extensions()
+                    .contains(&ext.to_lowercase().as_str())
+            })
+    }
+}
+
+/// A folder with the marker files of one or more project kinds.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DetectedProject {
+    pub root: PathBuf,
+    pub kinds: Vec<ProjectKind>,
+}
+
+/// Kinds of project whose marker files are directly in the folder.
+pub fn detect_project_kinds(folder: &Path) -> Vec<ProjectKind> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+
+    let file_names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+
+    ProjectKind::ALL
+        .into_iter()
+        .filter(|kind| file_names.iter().any(|name| kind.is_marker(name)))
+        .collect()
+}
+
+/// Walks the folder tree and returns the projects found, parents before their children.
+///
+/// Dependency, build and VCS folders are skipped. A project nested in another project of the same
+/// kind (e.g.: the members of a Cargo workspace) is folded into the parent, so only the kinds that
+/// are new to the subtree are reported.
+pub fn detect_projects(root: &Path, max_depth: Option<usize>) -> Vec<DetectedProject> {
+    let mut walker = WalkDir::new(root).sort_by_file_name();
+    if let Some(max_depth) = max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+
+    let mut projects: Vec<DetectedProject> = Vec::new();
+
+    for entry in walker
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_skipped_folder(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_dir())
+    {
+        let folder = entry.path();
+        let kinds: Vec<ProjectKind> = detect_project_kinds(folder)
+            .into_iter()
+            .filter(|kind| {
+                !projects
+                    .iter()
+                    .any(|parent| folder.starts_with(&parent.root) && parent.kinds.contains(kind))
+            })
+            .collect();
+
+        if !kinds.is_empty() {
+            projects.push(DetectedProject {
+                root: folder.to_path_buf(),
+                kinds,
+            });
+        }
+    }
+
+    projects
+}
+
+fn is_skipped_folder(entry: &DirEntry) -> bool {
+    entry.file_type().is_dir()
+        && entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| SKIPPED_FOLDERS.contains(&name.to_lowercase().as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detect_projects_folds_nested_projects_of_the_same_kind() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "Cargo.toml",
+            "crates/api/Cargo.toml",
+            "web/package.json",
+            "web/node_modules/left-pad/package.json",
+            "tools/App.csproj",
+            "tools/scripts/pyproject.toml",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let projects = detect_projects(root, None);
+        let found: Vec<(PathBuf, Vec<ProjectKind>)> = projects
+            .into_iter()
+            .map(|p| (p.root.strip_prefix(root).unwrap().to_path_buf(), p.kinds))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (PathBuf::new(), vec![ProjectKind::Rust]),
+                (PathBuf::from("tools"), vec![ProjectKind::Dotnet]),
+                (PathBuf::from("tools/scripts"), vec![ProjectKind::Python]),
+                (PathBuf::from("web"), vec![ProjectKind::Node]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,415 @@
+use crate::command_line::tool_cli::colors_enabled;
+use crate::utils::format_bytes_to_string::format_bytes_to_string;
+use crate::utils::format_duration_to_string::format_duration_to_string;
+use crossterm::cursor::MoveToColumn;
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::QueueableCommand;
+use std::io::{stderr, stdout, IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Where the progress line is printed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressTarget {
+    Stdout,
+    /// For tools that print their results to stdout.
+    Stderr,
+}
+
+/// Total amount of work, used for the percentage and the ETA.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressTotal {
+    /// Total of the main counter (e.g.: lines, when they are known up front).
+    Items(u64),
+    /// Total bytes to read (e.g.: the size of the input files).
+    Bytes(u64),
+}
+
+/// Progress counters with rates, ETA, and a status line that adapts to the terminal.
+///
+/// The first counter is the main one: the rate (and the ETA, with `ProgressTotal::Items`) is
+/// computed from it. Counters are atomic, so a `Progress` can be shared between tasks with an
+/// `Arc`.
+///
+/// ```ignore
+/// let progress = Progress::new("lines", &["Lines", "Files"])
+///     .with_total(ProgressTotal::Bytes(file_size));
+///
+/// for line in lines {
+///     progress.increment("Lines");
+///     progress.add_bytes(line.len() as u64);
+///     progress.tick();
+/// }
+///
+/// progress.finish();
+/// ```
+///
+/// On a terminal, the line is redrawn in place (colored, and cut to the terminal width). When the
+/// output is redirected, each update is printed as a plain line.
+pub struct Progress {
+    unit: String,
+    counters: Vec<(String, AtomicU64)>,
+    bytes: AtomicU64,
+    total: Option<ProgressTotal>,
+    started_at: Instant,
+    interval: Duration,
+    last_render: Mutex<Option<Instant>>,
+    max_rate: Mutex<f64>,
+    detail: Mutex<Option<String>>,
+    color: Color,
+    target: ProgressTarget,
+}
+
+/// The state of a `Progress` at a point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressSnapshot {
+    pub unit: String,
+    pub counters: Vec<(String, u64)>,
+    pub bytes: u64,
+    pub elapsed: Duration,
+    /// Main counter per second.
+    pub rate: f64,
+    pub bytes_per_second: f64,
+    /// Done, from 0 to 100, when the total is known.
+    pub percent: Option<f64>,
+    pub eta: Option<Duration>,
+    pub detail: Option<String>,
+}
+
+impl Progress {
+    /// Creates a progress with the counters (all starting at zero). `unit` is used in the rate
+    /// (e.g.: `msg` for `msg/s`).
+    pub fn new(unit: &str, counters: &[&str]) -> Self {
+        Self {
+            unit: unit.to_string(),
+            counters: counters
+                .iter()
+                .map(|name| (name.to_string(), AtomicU64::new(0)))
+                .collect(),
+            bytes: AtomicU64::new(0),
+            total: None,
+            started_at: Instant::now(),
+            interval: Duration::from_secs(1),
+            last_render: Mutex::new(None),
+            max_rate: Mutex::new(0.0),
+            detail: Mutex::new(None),
+            color: Color::Green,
+            target: ProgressTarget::Stdout,
+        }
+    }
+
+    pub fn with_total(mut self, total: ProgressTotal) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Minimum time between two updates printed by `tick`. (Default: 1 second)
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_target(mut self, target: ProgressTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Adds `amount` to a counter. Unknown counters are ignored.
+    pub fn add(&self, counter: &str, amount: u64) {
+        if let Some(value) = self.counter(counter) {
+            value.fetch_add(amount, Ordering::Relaxed);
+        }
+    }
+
+    pub fn increment(&self, counter: &str) {
+        self.add(counter, 1);
+    }
+
+    /// Sets a counter that is not a running total (e.g.: the current file number).
+    pub fn set(&self, counter: &str, value: u64) {
+        if let Some(current) = self.counter(counter) {
+            current.store(value, Ordering::Relaxed);
+        }
+    }
+
+    pub fn get(&self, counter: &str) -> u64 {
+        self.counter(counter)
+            .map(|value| value.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    pub fn add_bytes(&self, bytes: u64) {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Sets the bytes read so far, for readers that know their position in the input.
+    pub fn set_bytes(&self, bytes: u64) {
+        self.bytes.store(bytes, Ordering::Relaxed);
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Extra text shown at the end of the line (e.g.: the file being written).
+    pub fn set_detail(&self, detail: impl Into<String>) {
+        *self.detail.lock().unwrap() = Some(detail.into());
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Highest rate of the main counter seen in the printed updates.
+    pub fn max_rate(&self) -> f64 {
+        *self.max_rate.lock().unwrap()
+    }
+
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        let elapsed = self.elapsed();
+        let seconds = elapsed.as_secs_f64();
+        let counters: Vec<(String, u64)> = self
+            .counters
+            .iter()
+            .map(|(name, value)| (name.clone(), value.load(Ordering::Relaxed)))
+            .collect();
+        let main = counters.first().map(|(_, value)| *value).unwrap_or(0);
+        let bytes = self.bytes();
+
+        let per_second = |value: u64| {
+            if seconds > 0.0 {
+                value as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+
+        let (done, total, rate) = match self.total {
+            Some(ProgressTotal::Items(total)) => (main, Some(total), per_second(main)),
+            Some(ProgressTotal::Bytes(total)) => (bytes, Some(total), per_second(bytes)),
+            None => (0, None, 0.0),
+        };
+
+        let percent = total
+            .filter(|total| *total > 0)
+            .map(|total| (done as f64 / total as f64 * 100.0).min(100.0));
+
+        let eta = total
+            .filter(|_| rate > 0.0)
+            .map(|total| Duration::from_secs_f64(total.saturating_sub(done) as f64 / rate));
+
+        ProgressSnapshot {
+            unit: self.unit.clone(),
+            counters,
+            bytes,
+            elapsed,
+            rate: per_second(main),
+            bytes_per_second: per_second(bytes),
+            percent,
+            eta,
+            detail: self.detail.lock().unwrap().clone(),
+        }
+    }
+
+    /// Prints an update if the interval passed since the last one (the first one is always
+    /// printed).
+    pub fn tick(&self) {
+        if self.should_render() {
+            self.render();
+        }
+    }
+
+    /// Whether the interval passed since the last update. Resets the interval when it did.
+    pub fn should_render(&self) -> bool {
+        let now = Instant::now();
+        let mut last_render = self.last_render.lock().unwrap();
+
+        match *last_render {
+            Some(last) if now.duration_since(last) < self.interval => false,
+            _ => {
+                *last_render = Some(now);
+                true
+            }
+        }
+    }
+
+    /// Prints an update now.
+    pub fn render(&self) {
+        let snapshot = self.snapshot();
+        {
+            let mut max_rate = self.max_rate.lock().unwrap();
+            if snapshot.rate > *max_rate {
+                *max_rate = snapshot.rate;
+            }
+        }
+        *self.last_render.lock().unwrap() = Some(Instant::now());
+
+        let line = snapshot.format_line();
+        match self.target {
+            ProgressTarget::Stdout => self.print_line(&mut stdout(), &line),
+            ProgressTarget::Stderr => self.print_line(&mut stderr(), &line),
+        }
+    }
+
+    /// Prints the last update and ends the progress line.
+    pub fn finish(&self) {
+        self.render();
+
+        if self.is_terminal() {
+            match self.target {
+                ProgressTarget::Stdout => println!(),
+                ProgressTarget::Stderr => eprintln!(),
+            }
+        }
+    }
+
+    fn counter(&self, name: &str) -> Option<&AtomicU64> {
+        self.counters
+            .iter()
+            .find(|(counter, _)| counter == name)
+            .map(|(_, value)| value)
+    }
+
+    fn is_terminal(&self) -> bool {
+        match self.target {
+            ProgressTarget::Stdout => stdout().is_terminal(),
+            ProgressTarget::Stderr => stderr().is_terminal(),
+        }
+    }
+
+    fn print_line<W: Write>(&self, out: &mut W, line: &str) {
+        if !self.is_terminal() {
+            let _ = writeln!(out, "{}", line);
+            return;
+        }
+
+        // Leaves the last column free, so the cursor doesn't wrap to the next line.
+        let width = crossterm::terminal::size().map(|(w, _)| w).unwrap_or(80) as usize;
+        let line = truncate_to_width(line, width.saturating_sub(1));
+
+        let _ = out.queue(MoveToColumn(0));
+        let _ = out.queue(Clear(ClearType::CurrentLine));
+        if colors_enabled() {
+            let _ = out.queue(SetForegroundColor(self.color));
+            let _ = out.queue(Print(line));
+            let _ = out.queue(ResetColor);
+        } else {
+            let _ = out.queue(Print(line));
+        }
+        let _ = out.flush();
+    }
+}
+
+impl ProgressSnapshot {
+    /// `Read: 10 | Skipped: 2 | Rate: 5.00 msg/s | Data: 1.00 MB | 50.0% | ETA: 00:00:02.000 |
+    /// Elapsed: 00:00:02.000 | <detail>`. Data, percentage, ETA, and detail only show when known.
+    pub fn format_line(&self) -> String {
+        let mut parts: Vec<String> = self
+            .counters
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect();
+
+        parts.push(format!("Rate: {:.2} {}/s", self.rate, self.unit));
+
+        if self.bytes > 0 {
+            parts.push(format!("Data: {}", format_bytes_to_string(&self.bytes)));
+        }
+
+        if let Some(percent) = self.percent {
+            parts.push(format!("{:.1}%", percent));
+        }
+
+        if let Some(eta) = self.eta {
+            parts.push(format!("ETA: {}", format_std_duration(eta)));
+        }
+
+        parts.push(format!("Elapsed: {}", format_std_duration(self.elapsed)));
+
+        if let Some(detail) = &self.detail {
+            parts.push(detail.clone());
+        }
+
+        parts.join(" | ")
+    }
+}
+
+fn format_std_duration(duration: Duration) -> String {
+    format_duration_to_string(chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX))
+}
+
+/// Cuts the line to `width` characters, ending it with `...` when cut.
+fn truncate_to_width(line: &str, width: usize) -> String {
+    if line.chars().count() <= width {
+        return line.to_string();
+    }
+
+    if width <= 3 {
+        return ".".repeat(width);
+    }
+
+    let mut truncated: String = line.chars().take(width - 3).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_and_format_line() {
+        let progress = Progress::new("msg", &["Read", "Skipped"]);
+        progress.add("Read", 10);
+        progress.increment("Skipped");
+        progress.increment("Unknown");
+        progress.set_detail("partition 3");
+
+        assert_eq!(progress.get("Read"), 10);
+        assert_eq!(progress.get("Skipped"), 1);
+        assert_eq!(progress.get("Unknown"), 0);
+
+        let snapshot = ProgressSnapshot {
+            elapsed: Duration::from_secs(2),
+            rate: 5.0,
+            ..progress.snapshot()
+        };
+
+        assert_eq!(
+            snapshot.format_line(),
+            "Read: 10 | Skipped: 1 | Rate: 5.00 msg/s | Elapsed: 00:00:02.000 | partition 3"
+        );
+    }
+
+    #[test]
+    fn test_percent_and_eta_from_total() {
+        let progress =
+            Progress::new("lines", &["Lines"]).with_total(ProgressTotal::Bytes(1_000_000));
+        progress.add_bytes(250_000);
+
+        std::thread::sleep(Duration::from_millis(20));
+        let snapshot = progress.snapshot();
+
+        assert_eq!(snapshot.percent, Some(25.0));
+        // A quarter took the elapsed time, so the rest takes about three times that.
+        let eta = snapshot.eta.unwrap().as_secs_f64();
+        let expected = snapshot.elapsed.as_secs_f64() * 3.0;
+        assert!((eta - expected).abs() < expected * 0.5);
+        assert!(snapshot.format_line().contains("| 25.0% | ETA: "));
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("a longer line", 8), "a lon...");
+        assert_eq!(truncate_to_width("ção e mais", 6), "ção...");
+        assert_eq!(truncate_to_width("abc", 2), "..");
+    }
+}
@@ -0,0 +1,183 @@
+use crate::utils::format_duration_to_string::format_duration_to_string;
+use std::time::{Duration, Instant};
+
+/// Time spent in a named phase. Repeated measurements of the same phase are accumulated.
+#[derive(Debug, Clone)]
+pub struct PhaseTiming {
+    pub name: &'static str,
+    pub elapsed: Duration,
+    pub calls: u64,
+}
+
+/// Collects how long each phase of a tool took, so it can be printed when the tool exits.
+///
+/// When created disabled (the default when `--profile` is not set), every method is a no-op, so
+/// tools can call it unconditionally.
+pub struct PhaseProfiler {
+    enabled: bool,
+    started_at: Instant,
+    phases: Vec<PhaseTiming>,
+}
+
+impl PhaseProfiler {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            started_at: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Runs `f` and adds the time it took to `phase`.
+    pub fn measure<T>(&mut self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+
+        let started_at = Instant::now();
+        let result = f();
+        self.record(phase, started_at.elapsed());
+        result
+    }
+
+    /// Adds the time elapsed since `started_at` to `phase`.
+    ///
+    /// Useful when the phase can't be wrapped in a closure (e.g.: it awaits something).
+    pub fn record_since(&mut self, phase: &'static str, started_at: Instant) {
+        self.record(phase, started_at.elapsed());
+    }
+
+    /// Adds `elapsed` to `phase`. Phases keep the order in which they were first recorded.
+    pub fn record(&mut self, phase: &'static str, elapsed: Duration) {
+        if !self.enabled {
+            return;
+        }
+
+        match self.phases.iter_mut().find(|p| p.name == phase) {
+            Some(timing) => {
+                timing.elapsed += elapsed;
+                timing.calls += 1;
+            }
+            None => self.phases.push(PhaseTiming {
+                name: phase,
+                elapsed,
+                calls: 1,
+            }),
+        }
+    }
+
+    pub fn phases(&self) -> &[PhaseTiming] {
+        &self.phases
+    }
+
+    /// Builds the report with one line per phase, plus the total runtime.
+    pub fn build_report(&self) -> String {
+        let total = self.started_at.elapsed();
+        let name_width = self
+            .phases
+            .iter()
+            .map(|p| p.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("total".len());
+
+        let mut lines = vec!["Profile:".to_string()];
+
+        for phase in &self.phases {
+            lines.push(format!(
+                "- {:<width$}  {}  {:>5.1}%  ({} call{})",
+                phase.name,
+                to_duration_string(phase.elapsed),
+                percent_of(phase.elapsed, total),
+                phase.calls,
+                if phase.calls == 1 { "" } else { "s" },
+                width = name_width
+            ));
+        }
+
+        lines.push(format!(
+            "- {:<width$}  {}",
+            "total",
+            to_duration_string(total),
+            width = name_width
+        ));
+
+        lines.join("\n")
+    }
+
+    /// Prints the report to stderr, so it doesn't get mixed with the tool's output.
+    pub fn print_report(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        eprintln!();
+        eprintln!("{}", self.build_report());
+    }
+}
+
+fn to_duration_string(duration: Duration) -> String {
+    match chrono::Duration::from_std(duration) {
+        Ok(d) => format_duration_to_string(d),
+        Err(_) => format!("{:?}", duration),
+    }
+}
+
+fn percent_of(part: Duration, total: Duration) -> f64 {
+    if total.is_zero() {
+        return 0.0;
+    }
+
+    part.as_secs_f64() / total.as_secs_f64() * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_profiler_records_nothing() {
+        let mut profiler = PhaseProfiler::new(false);
+
+        let value = profiler.measure("read", || 42);
+        profiler.record("write", Duration::from_millis(10));
+
+        assert_eq!(value, 42);
+        assert!(profiler.phases().is_empty());
+    }
+
+    #[test]
+    fn test_same_phase_is_accumulated() {
+        let mut profiler = PhaseProfiler::new(true);
+
+        profiler.record("read", Duration::from_millis(10));
+        profiler.record("write", Duration::from_millis(5));
+        profiler.record("read", Duration::from_millis(15));
+
+        let phases = profiler.phases();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0].name, "read");
+        assert_eq!(phases[0].elapsed, Duration::from_millis(25));
+        assert_eq!(phases[0].calls, 2);
+        assert_eq!(phases[1].name, "write");
+        assert_eq!(phases[1].calls, 1);
+    }
+
+    #[test]
+    fn test_report_lists_every_phase_and_total() {
+        let mut profiler = PhaseProfiler::new(true);
+        profiler.record("open input", Duration::from_millis(1));
+        profiler.record("split", Duration::from_millis(2));
+
+        let report = profiler.build_report();
+
+        assert!(report.starts_with("Profile:"));
+        assert!(report.contains("- open input  00:00:00.001"));
+        assert!(report.contains("- split"));
+        assert!(report.contains("- total"));
+    }
+}
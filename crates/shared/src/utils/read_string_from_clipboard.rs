@@ -0,0 +1,6 @@
+use arboard::Clipboard;
+
+pub fn read_from_clipboard() -> Result<String, Box<dyn std::error::Error>> {
+    let mut clipboard = Clipboard::new()?;
+    Ok(clipboard.get_text()?)
+}
@@ -0,0 +1,266 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// How a report is written: an aligned text table (for the terminal), a markdown document, or
+/// JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Table,
+    Markdown,
+    Json,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [
+        ReportFormat::Table,
+        ReportFormat::Markdown,
+        ReportFormat::Json,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportFormat::Table => "table",
+            ReportFormat::Markdown => "markdown",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "table" | "text" => Ok(ReportFormat::Table),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "json" => Ok(ReportFormat::Json),
+            other => Err(anyhow!(
+                "Invalid format: {}. Valid formats: table, markdown, json",
+                other
+            )),
+        }
+    }
+}
+
+/// Column alignment. Numbers read better aligned to the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+}
+
+/// A titled table, rendered as aligned text or as markdown.
+///
+/// ```ignore
+/// let mut table = Table::new(&["AUTHOR", "COMMITS"]).with_title("Commits").align_right(1);
+/// table.add_row(vec!["Ana".to_string(), "42".to_string()]);
+/// print!("{}", table.render(ReportFormat::Table));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    title: Option<String>,
+    headers: Vec<String>,
+    alignments: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            title: None,
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            alignments: vec![Align::Left; headers.len()],
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Aligns the column (0-based) to the right. Out of range columns are ignored.
+    pub fn align_right(mut self, column: usize) -> Self {
+        if let Some(alignment) = self.alignments.get_mut(column) {
+            *alignment = Align::Right;
+        }
+        self
+    }
+
+    /// Adds a row. Missing cells are left empty, and extra cells are dropped.
+    pub fn add_row(&mut self, mut cells: Vec<String>) {
+        cells.resize(self.headers.len(), String::new());
+        self.rows.push(cells);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Renders the table in the format. JSON isn't rendered here: serialize the data behind the
+    /// table with [`render_json`] instead, so the numbers stay numbers.
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.render_markdown(),
+            ReportFormat::Table | ReportFormat::Json => self.render_text(),
+        }
+    }
+
+    /// Columns separated by two spaces, padded to the widest cell. Tables without rows show
+    /// `(none)` below the headers.
+    pub fn render_text(&self) -> String {
+        let widths = self.column_widths();
+        let mut output = String::new();
+
+        if let Some(title) = &self.title {
+            output.push_str(title);
+            output.push('\n');
+            output.push_str(&"-".repeat(title.chars().count()));
+            output.push('\n');
+        }
+
+        output.push_str(&self.text_row(&self.headers, &widths));
+        for row in &self.rows {
+            output.push_str(&self.text_row(row, &widths));
+        }
+
+        if self.rows.is_empty() {
+            output.push_str("(none)\n");
+        }
+
+        output
+    }
+
+    /// A `##` heading (when titled) and a GitHub-flavored markdown table. `|` in the cells is
+    /// escaped.
+    pub fn render_markdown(&self) -> String {
+        let mut output = String::new();
+
+        if let Some(title) = &self.title {
+            output.push_str(&format!("## {}\n\n", title));
+        }
+
+        if self.rows.is_empty() {
+            output.push_str("_(none)_\n");
+            return output;
+        }
+
+        let escape = |cell: &String| cell.replace('|', "\\|").replace('\n', " ");
+
+        output.push_str(&format!(
+            "| {} |\n",
+            self.headers
+                .iter()
+                .map(escape)
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+        output.push_str(&format!(
+            "|{}|\n",
+            self.alignments
+                .iter()
+                .map(|alignment| match alignment {
+                    Align::Left => "---",
+                    Align::Right => "---:",
+                })
+                .collect::<Vec<_>>()
+                .join("|")
+        ));
+
+        for row in &self.rows {
+            output.push_str(&format!(
+                "| {} |\n",
+                row.iter().map(escape).collect::<Vec<_>>().join(" | ")
+            ));
+        }
+
+        output
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .map(|header| header.chars().count())
+            .collect();
+
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        widths
+    }
+
+    fn text_row(&self, cells: &[String], widths: &[usize]) -> String {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .zip(&self.alignments)
+            .map(|((cell, width), alignment)| match alignment {
+                Align::Left => format!("{:<width$}", cell, width = width),
+                Align::Right => format!("{:>width$}", cell, width = width),
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        format!("{}\n", line.trim_end())
+    }
+}
+
+/// Serializes a report as pretty-printed JSON.
+///
+/// # Errors
+/// Returns an error if serialization fails.
+pub fn render_json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    serde_json::to_string_pretty(value).context("Failed to serialize the report")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_table() -> Table {
+        let mut table = Table::new(&["NAME", "COUNT"])
+            .with_title("Totals")
+            .align_right(1);
+        table.add_row(vec!["alpha".to_string(), "7".to_string()]);
+        table.add_row(vec!["b|c".to_string(), "1200".to_string()]);
+        table
+    }
+
+    #[test]
+    fn test_render_text_aligns_columns() {
+        let text = create_table().render_text();
+        assert_eq!(
+            text,
+            "Totals\n------\nNAME   COUNT\nalpha      7\nb|c     1200\n"
+        );
+
+        let empty = Table::new(&["NAME"]).render_text();
+        assert_eq!(empty, "NAME\n(none)\n");
+    }
+
+    #[test]
+    fn test_render_markdown_escapes_cells() {
+        let markdown = create_table().render_markdown();
+        assert_eq!(
+            markdown,
+            "## Totals\n\n| NAME | COUNT |\n|---|---:|\n| alpha | 7 |\n| b\\|c | 1200 |\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_report_format() {
+        assert_eq!(
+            "MD".parse::<ReportFormat>().unwrap(),
+            ReportFormat::Markdown
+        );
+        assert_eq!("json".parse::<ReportFormat>().unwrap(), ReportFormat::Json);
+        assert!("xml".parse::<ReportFormat>().is_err());
+    }
+}
@@ -0,0 +1 @@
+pub mod request_logger;
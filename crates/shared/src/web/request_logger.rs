@@ -0,0 +1,29 @@
+use tracing::info;
+
+/// Creates a warp log configuration (not a filter) that writes one access log line per request.
+///
+/// Usage: `routes.with(create_request_logger())`
+pub fn create_request_logger() -> warp::log::Log<impl Fn(warp::log::Info) + Copy> {
+    warp::log::custom(|info| {
+        let headers = info.request_headers();
+        let user_agent = headers
+            .get("user-agent")
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("unknown");
+        let content_length = headers
+            .get("content-length")
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("0");
+
+        info!(
+            target: "access_log",
+            "HTTP request completed - {} {} {} - {}ms - {} bytes - UA: {}",
+            info.method(),
+            info.path(),
+            info.status(),
+            info.elapsed().as_millis(),
+            content_length,
+            user_agent
+        );
+    })
+}
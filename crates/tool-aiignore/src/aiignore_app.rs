@@ -1,29 +1,23 @@
+use crate::models::{IgnoreSyntax, IgnoreTarget};
 use anyhow::{Context, Result};
 use reqwest::Client;
+use shared::system::project_detection::detect_projects;
 use std::collections::HashSet;
-use std::path::PathBuf;
-use tracing::{debug, info};
-
-const AI_IGNORE_KNOWN_FILES: &[&str] = &[
-    ".aiignore",
-    ".cursorignore",
-    ".cursorindexingignore",
-    ".windsurfignore",
-    ".codeiumignore",
-    ".windsurfrules",
-    ".claudeignore", //Proposed on July 2025, but not implemented yet.
-    ".aiexclude",    // Gemni/Bard
-];
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
 
 const AI_IGNORE_TEMPLATES: &[&str] = &[
     "https://raw.githubusercontent.com/brenordv/gitignore-files/refs/heads/master/get-out-of-my-land.ai"
 ];
 
-pub async fn run_aiignore_maintainer(folder: PathBuf) -> Result<()> {
+/// Builds the canonical rule set (the rules of every existing AI ignore file, the generated folders
+/// of the projects found, and the templates), and writes it to each target, in its syntax.
+pub async fn run_aiignore_maintainer(folder: PathBuf, targets: &[IgnoreTarget]) -> Result<()> {
     let mut aiignore_data: HashSet<String> = HashSet::new();
 
     info!("Checking for existing AI ignore files...");
-    for file_name in AI_IGNORE_KNOWN_FILES {
+    for target in IgnoreTarget::ALL {
+        let file_name = target.file_name();
         let file_path = folder.join(file_name);
         if !file_path.exists() {
             continue;
@@ -38,9 +32,12 @@ pub async fn run_aiignore_maintainer(folder: PathBuf) -> Result<()> {
         });
     }
 
+    let data_count_before_download = aiignore_data.len();
+
+    add_generated_folders(&folder, &mut aiignore_data);
+
     info!("Downloading AI ignore templates...");
 
-    let data_count_before_download = aiignore_data.len();
     debug!(
         "Current AI ignore line count: {}",
         data_count_before_download
@@ -76,7 +73,16 @@ pub async fn run_aiignore_maintainer(folder: PathBuf) -> Result<()> {
 
     info!("Writing {} lines to AI ignore files...", clean_data.len());
 
-    for file_name in AI_IGNORE_KNOWN_FILES {
+    for target in targets {
+        let file_name = target.file_name();
+        let rules = convert_rules(&clean_data, *target);
+
+        if rules.is_empty() && target.syntax() == IgnoreSyntax::NoNegation {
+            // An empty file would exclude the whole project.
+            warn!("No rules left for {}. Skipping it...", file_name);
+            continue;
+        }
+
         let file_path = folder.join(file_name);
         let action = if file_path.exists() {
             "Updating"
@@ -85,7 +91,7 @@ pub async fn run_aiignore_maintainer(folder: PathBuf) -> Result<()> {
         };
 
         info!("{} file: {}", action, file_name);
-        std::fs::write(&file_path, clean_data.join("\n"))
+        std::fs::write(&file_path, rules.join("\n"))
             .with_context(|| format!("Failed to write {}", file_name))?;
     }
 
@@ -93,6 +99,65 @@ pub async fn run_aiignore_maintainer(folder: PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Dependency and build output folders of the projects in the folder tree (e.g.: `target/` for
+/// Cargo, `node_modules/` for npm). They are big, generated, and never worth indexing.
+fn add_generated_folders(folder: &Path, aiignore_data: &mut HashSet<String>) {
+    for project in detect_projects(folder, None) {
+        let kinds: Vec<&str> = project.kinds.iter().map(|kind| kind.name()).collect();
+        debug!(
+            "Found {} project in: {}",
+            kinds.join(" + "),
+            project.root.display()
+        );
+
+        let mut new_lines: usize = 0;
+        for pattern in project
+            .kinds
+            .iter()
+            .flat_map(|kind| kind.generated_folders())
+        {
+            if aiignore_data.insert(pattern.to_string()) {
+                new_lines += 1;
+            }
+        }
+
+        if new_lines > 0 {
+            info!(
+                "Added {} generated folder(s) of the {} project in {}",
+                new_lines,
+                kinds.join(" + "),
+                project.root.display()
+            );
+        }
+    }
+}
+
+/// Translates the canonical rules to the syntax of the target.
+fn convert_rules(rules: &[String], target: IgnoreTarget) -> Vec<String> {
+    match target.syntax() {
+        IgnoreSyntax::Gitignore => rules.to_vec(),
+        IgnoreSyntax::NoNegation => {
+            let (negations, supported): (Vec<&String>, Vec<&String>) =
+                rules.iter().partition(|rule| rule.starts_with('!'));
+
+            if !negations.is_empty() {
+                info!(
+                    "{} doesn't support negation. Leaving out {} rule(s): {}",
+                    target.file_name(),
+                    negations.len(),
+                    negations
+                        .iter()
+                        .map(|rule| rule.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+
+            supported.into_iter().cloned().collect()
+        }
+    }
+}
+
 fn sanitize_aiignore_data(aiignore_data: &HashSet<String>) -> Vec<String> {
     let mut data = HashSet::new();
 
@@ -155,3 +220,22 @@ async fn get_aiignore_template_data(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_rules_per_target_syntax() {
+        let rules: Vec<String> = ["*.env", "!.env.example", "secrets/"]
+            .iter()
+            .map(|rule| rule.to_string())
+            .collect();
+
+        assert_eq!(convert_rules(&rules, IgnoreTarget::CursorIgnore), rules);
+        assert_eq!(
+            convert_rules(&rules, IgnoreTarget::AiExclude),
+            vec!["*.env", "secrets/"]
+        );
+    }
+}
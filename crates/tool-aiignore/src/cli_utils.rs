@@ -1,15 +1,24 @@
-use crate::models::AiIgnoreArgs;
-use anyhow::Result;
-use clap::{Arg, Command};
+use crate::models::{AiIgnoreArgs, IgnoreTarget};
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
 use shared::system::get_current_working_dir::get_current_working_dir;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 pub fn print_runtime_info(args: &AiIgnoreArgs) {
     println!("AiIgnore v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
-    println!("- Target folder: {}\n", args.target_folder.display());
+    println!("- Target folder: {}", args.target_folder.display());
+    println!(
+        "- Targets: {}\n",
+        args.targets
+            .iter()
+            .map(|target| target.file_name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
 }
 
 pub fn validate_args(args: &AiIgnoreArgs) -> Result<()> {
@@ -20,24 +29,91 @@ pub fn validate_args(args: &AiIgnoreArgs) -> Result<()> {
     Ok(())
 }
 
-pub fn get_cli_arguments() -> Result<AiIgnoreArgs> {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<AiIgnoreArgs> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
             env!("CARGO_PKG_DESCRIPTION"),
-            "Automatically creates or updates common ai-ignore files based on detected file types in your project.",
+            "Automatically creates or updates common ai-ignore files based on detected file types in your project. \
+            Every file is written from the same rule set, translated to the syntax each AI tool supports.",
         )
         .arg(Arg::new("target-dir")
             .help("Target folder to analyze")
             .num_args(1)
             .required(false)
         )
-        .get_matches();
+        .arg(Arg::new("targets")
+            .long("targets")
+            .short('t')
+            .value_name("FILES")
+            .value_delimiter(',')
+            .action(ArgAction::Append)
+            .help("Comma-separated ignore files to write (e.g.: aiignore,cursorignore,codeiumignore,aiexclude). (Default: all)")
+        )
+        .get_matches_from(argv);
 
     let target_folder = matches
         .get_one::<String>("target-dir")
         .map(|s| PathBuf::from(s))
         .unwrap_or_else(|| get_current_working_dir());
 
-    Ok(AiIgnoreArgs { target_folder })
+    let targets = match matches.get_many::<String>("targets") {
+        Some(names) => parse_targets(names.map(String::as_str))?,
+        None => IgnoreTarget::ALL.to_vec(),
+    };
+
+    Ok(AiIgnoreArgs {
+        target_folder,
+        targets,
+    })
+}
+
+fn parse_targets<'a>(names: impl Iterator<Item = &'a str>) -> Result<Vec<IgnoreTarget>> {
+    let mut targets = Vec::new();
+
+    for name in names.filter(|name| !name.trim().is_empty()) {
+        let target = IgnoreTarget::from_name(name).ok_or_else(|| {
+            anyhow!(
+                "Unknown target: {}. Use one of: {}",
+                name,
+                IgnoreTarget::ALL
+                    .iter()
+                    .map(|target| target.file_name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+
+    if targets.is_empty() {
+        return Err(anyhow!("--targets needs at least one file"));
+    }
+
+    Ok(targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        let targets =
+            parse_targets(["aiignore", ".CursorIgnore", "aiexclude", "aiignore"].into_iter());
+        assert_eq!(
+            targets.unwrap(),
+            vec![
+                IgnoreTarget::AiIgnore,
+                IgnoreTarget::CursorIgnore,
+                IgnoreTarget::AiExclude
+            ]
+        );
+
+        assert!(parse_targets(["copilotignore"].into_iter()).is_err());
+        assert!(parse_targets([" "].into_iter()).is_err());
+    }
 }
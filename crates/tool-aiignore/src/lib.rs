@@ -0,0 +1,56 @@
+use crate::aiignore_app::run_aiignore_maintainer;
+use crate::cli_utils::{get_cli_arguments, print_runtime_info, validate_args};
+use anyhow::Context;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+mod aiignore_app;
+mod cli_utils;
+mod models;
+
+/// AI ignore files maintainer.
+///
+/// Parses and validates arguments, then creates or updates the ignore files in the target folder.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    match runtime.block_on(run_tool(argv)) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+async fn run_tool(argv: Vec<OsString>) -> anyhow::Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Info);
+    let args = get_cli_arguments(argv)?;
+    validate_args(&args).context("Cannot proceed")?;
+    print_runtime_info(&args);
+
+    run_aiignore_maintainer(args.target_folder, &args.targets).await?;
+
+    Ok(())
+}
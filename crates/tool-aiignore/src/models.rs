@@ -2,4 +2,69 @@ use std::path::PathBuf;
 
 pub struct AiIgnoreArgs {
     pub target_folder: PathBuf,
+    /// Files written from the canonical rule set (`--targets`).
+    pub targets: Vec<IgnoreTarget>,
+}
+
+/// How a tool reads its ignore file.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum IgnoreSyntax {
+    /// Full `.gitignore` syntax.
+    Gitignore,
+    /// `.gitignore` syntax without negation (`!pattern`). An empty file excludes everything.
+    NoNegation,
+}
+
+/// AI tool ignore files maintained by the tool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum IgnoreTarget {
+    AiIgnore,
+    CursorIgnore,
+    CursorIndexingIgnore,
+    WindsurfIgnore,
+    CodeiumIgnore,
+    WindsurfRules,
+    ClaudeIgnore,
+    AiExclude,
+}
+
+impl IgnoreTarget {
+    pub const ALL: [IgnoreTarget; 8] = [
+        IgnoreTarget::AiIgnore,
+        IgnoreTarget::CursorIgnore,
+        IgnoreTarget::CursorIndexingIgnore,
+        IgnoreTarget::WindsurfIgnore,
+        IgnoreTarget::CodeiumIgnore,
+        IgnoreTarget::WindsurfRules,
+        IgnoreTarget::ClaudeIgnore,
+        IgnoreTarget::AiExclude,
+    ];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            IgnoreTarget::AiIgnore => ".aiignore",
+            IgnoreTarget::CursorIgnore => ".cursorignore",
+            IgnoreTarget::CursorIndexingIgnore => ".cursorindexingignore",
+            IgnoreTarget::WindsurfIgnore => ".windsurfignore",
+            IgnoreTarget::CodeiumIgnore => ".codeiumignore",
+            IgnoreTarget::WindsurfRules => ".windsurfrules",
+            IgnoreTarget::ClaudeIgnore => ".claudeignore", // Proposed on July 2025, but not implemented yet.
+            IgnoreTarget::AiExclude => ".aiexclude",       // Gemini/Bard
+        }
+    }
+
+    pub fn syntax(&self) -> IgnoreSyntax {
+        match self {
+            IgnoreTarget::AiExclude => IgnoreSyntax::NoNegation,
+            _ => IgnoreSyntax::Gitignore,
+        }
+    }
+
+    /// Finds a target by its file name, with or without the leading dot (`cursorignore`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().trim_start_matches('.').to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|target| target.file_name().trim_start_matches('.') == name)
+    }
 }
@@ -0,0 +1,200 @@
+use crate::archive_reader::{extract_archive, list_entries, FILES};
+use crate::archive_source::ArchiveSource;
+use crate::archive_writer::create_archive;
+use crate::entry_paths::EntryFilter;
+use crate::models::{ArchiveArgs, ArchiveCommand};
+use anyhow::{bail, Result};
+use shared::system::pathbuf_extensions::{CompressionType, PathBufExtensions};
+use shared::ui::progress::{Progress, ProgressTotal};
+use shared::utils::format_bytes_to_string::format_bytes_to_string;
+use shared::utils::table_output::{ReportFormat, Table};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+pub fn run(args: &ArchiveArgs) -> Result<()> {
+    match &args.command {
+        ArchiveCommand::Create {
+            archive, inputs, ..
+        } => create(archive, inputs),
+        ArchiveCommand::Extract {
+            archive,
+            destination,
+            include,
+            overwrite,
+        } => extract(archive, destination.as_deref(), include, *overwrite),
+        ArchiveCommand::List { archive, include } => list(archive, include),
+    }
+}
+
+fn create(archive: &Path, inputs: &[PathBuf]) -> Result<()> {
+    let progress = Progress::new("files", &[FILES]);
+    let summary = create_archive(archive, inputs, &progress)?;
+    progress.finish();
+
+    println!(
+        "Created {} with {} file(s) ({}).",
+        archive.display(),
+        summary.files,
+        format_bytes_to_string(&summary.bytes)
+    );
+
+    Ok(())
+}
+
+fn extract(
+    archive: &Path,
+    destination: Option<&Path>,
+    include: &[String],
+    overwrite: bool,
+) -> Result<()> {
+    let source = ArchiveSource::open(archive)?;
+    let filter = EntryFilter::new(include)?;
+    let destination = destination
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_destination(archive));
+    info!(
+        "Extracting {} ({} part(s)) to {}",
+        archive.display(),
+        source.parts.len(),
+        destination.display()
+    );
+
+    // Listing a tar means reading all of it, so its progress goes without a total.
+    let progress = Progress::new("files", &[FILES]);
+    let progress = match source.format {
+        CompressionType::Tar | CompressionType::TarGz => progress,
+        _ => {
+            let files = list_entries(&source, &filter)?
+                .iter()
+                .filter(|entry| !entry.is_dir)
+                .count();
+            progress.with_total(ProgressTotal::Items(files as u64))
+        }
+    };
+
+    let summary = extract_archive(&source, &destination, &filter, overwrite, &progress)?;
+    progress.finish();
+
+    if !include.is_empty() && summary.extracted + summary.skipped + summary.unsafe_entries == 0 {
+        bail!("No entries match: {}", include.join(", "));
+    }
+
+    println!(
+        "Extracted {} file(s) ({}) to {}.",
+        summary.extracted,
+        format_bytes_to_string(&summary.bytes),
+        destination.display()
+    );
+    if summary.skipped > 0 {
+        println!(
+            "Skipped {} file(s) that exist (use --overwrite to replace them).",
+            summary.skipped
+        );
+    }
+    if summary.unsafe_entries > 0 {
+        println!(
+            "Skipped {} unsafe entry(ies): links, or paths outside of the destination.",
+            summary.unsafe_entries
+        );
+    }
+
+    Ok(())
+}
+
+fn list(archive: &Path, include: &[String]) -> Result<()> {
+    let source = ArchiveSource::open(archive)?;
+    let entries = list_entries(&source, &EntryFilter::new(include)?)?;
+
+    let mut table = Table::new(&["PATH", "SIZE"]).align_right(1);
+    let mut files = 0u64;
+    let mut bytes = 0u64;
+    for entry in &entries {
+        if entry.is_dir {
+            table.add_row(vec![
+                format!("{}/", entry.path.trim_end_matches('/')),
+                "-".to_string(),
+            ]);
+            continue;
+        }
+
+        files += 1;
+        bytes += entry.size;
+        table.add_row(vec![
+            entry.path.clone(),
+            format_bytes_to_string(&entry.size),
+        ]);
+    }
+
+    print!("{}", table.render(ReportFormat::Table));
+    println!(
+        "\n{} file(s), {} uncompressed.",
+        files,
+        format_bytes_to_string(&bytes)
+    );
+
+    Ok(())
+}
+
+/// The name of the archive without its extensions and part numbers (`backup.tar.gz`,
+/// `backup.7z.001` and `backup.part1.rar` all give `backup`), in the current folder.
+fn default_destination(archive: &Path) -> PathBuf {
+    let mut name = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let is_rar = name.to_ascii_lowercase().ends_with(".rar");
+    if archive.multi_part_index().is_some() && !is_rar {
+        if let Some((stem, _)) = name.rsplit_once('.') {
+            name = stem.to_string();
+        }
+    }
+
+    let lowercase = name.to_ascii_lowercase();
+    if let Some(suffix) = [".tar.gz", ".tgz", ".tar", ".zip", ".7z", ".rar"]
+        .iter()
+        .find(|suffix| lowercase.ends_with(*suffix))
+    {
+        name.truncate(name.len() - suffix.len());
+    }
+
+    let lowercase = name.to_ascii_lowercase();
+    if let Some(index) = lowercase.rfind(".part") {
+        let number = &lowercase[index + ".part".len()..];
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            name.truncate(index);
+        }
+    }
+
+    if name.is_empty() {
+        name = "extracted".to_string();
+    }
+
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_destination() {
+        for (archive, expected) in [
+            ("downloads/photos.tar.gz", "photos"),
+            ("photos.TGZ", "photos"),
+            ("backup.7z.001", "backup"),
+            ("backup.zip", "backup"),
+            ("movie.part01.rar", "movie"),
+            ("movie.rar", "movie"),
+            ("v1.2.zip", "v1.2"),
+            (".zip", "extracted"),
+        ] {
+            assert_eq!(
+                default_destination(Path::new(archive)),
+                PathBuf::from(expected),
+                "{}",
+                archive
+            );
+        }
+    }
+}
@@ -0,0 +1,452 @@
+use crate::archive_source::ArchiveSource;
+use crate::entry_paths::{normalize_entry_name, safe_relative_path, EntryFilter};
+use crate::models::{ArchiveEntry, ExtractSummary};
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::read::GzDecoder;
+use sevenz_rust::{Password, SevenZReader};
+use shared::system::pathbuf_extensions::CompressionType;
+use shared::ui::progress::Progress;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Name of the progress counter of extracted files.
+pub const FILES: &str = "Files";
+
+/// Lists the entries of the archive matching the filter, in the order they are stored.
+pub fn list_entries(source: &ArchiveSource, filter: &EntryFilter) -> Result<Vec<ArchiveEntry>> {
+    let entries = match source.format {
+        CompressionType::Zip => list_zip(source)?,
+        CompressionType::Tar => list_tar(BufReader::new(source.reader()?))?,
+        CompressionType::TarGz => list_tar(GzDecoder::new(BufReader::new(source.reader()?)))?,
+        CompressionType::SevenZip => open_7z(source)?
+            .archive()
+            .files
+            .iter()
+            .map(|entry| ArchiveEntry {
+                path: normalize_entry_name(entry.name()),
+                size: entry.size(),
+                is_dir: entry.is_directory(),
+            })
+            .collect(),
+        CompressionType::Rar => list_rar(source.path())?,
+        CompressionType::Gzip => bail!("'{}' is not an archive", source.path().display()),
+    };
+
+    Ok(entries
+        .into_iter()
+        .filter(|entry| filter.matches(&entry.path))
+        .collect())
+}
+
+fn list_zip(source: &ArchiveSource) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = open_zip(source)?;
+    let mut entries = Vec::with_capacity(archive.len());
+
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)?;
+        entries.push(ArchiveEntry {
+            path: normalize_entry_name(entry.name()),
+            size: entry.size(),
+            is_dir: entry.is_dir(),
+        });
+    }
+
+    Ok(entries)
+}
+
+fn list_tar(reader: impl Read) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+
+    for entry in archive.entries().context("Invalid tar archive")? {
+        let entry = entry.context("Invalid tar archive")?;
+        let is_dir = entry.header().entry_type().is_dir();
+        entries.push(ArchiveEntry {
+            path: normalize_entry_name(&String::from_utf8_lossy(&entry.path_bytes())),
+            size: if is_dir { 0 } else { entry.size() },
+            is_dir,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn list_rar(path: &Path) -> Result<Vec<ArchiveEntry>> {
+    let archive = unrar::Archive::new(path)
+        .open_for_listing()
+        .context(format!("Failed to open {}", path.display()))?;
+
+    archive
+        .map(|entry| {
+            let entry = entry.context(format!("Failed to read {}", path.display()))?;
+            Ok(ArchiveEntry {
+                path: normalize_entry_name(&entry.filename.to_string_lossy()),
+                size: entry.unpacked_size,
+                is_dir: entry.is_directory(),
+            })
+        })
+        .collect()
+}
+
+/// Extracts the entries matching the filter into `destination`, keeping their folders. Files
+/// that exist are kept unless `overwrite`. Links, and entries that would be written outside of
+/// `destination`, are skipped with a warning.
+pub fn extract_archive(
+    source: &ArchiveSource,
+    destination: &Path,
+    filter: &EntryFilter,
+    overwrite: bool,
+    progress: &Progress,
+) -> Result<ExtractSummary> {
+    fs::create_dir_all(destination).context(format!(
+        "Failed to create the folder {}",
+        destination.display()
+    ))?;
+
+    let mut extractor = Extractor {
+        destination,
+        filter,
+        overwrite,
+        progress,
+        summary: ExtractSummary::default(),
+    };
+
+    match source.format {
+        CompressionType::Zip => extract_zip(source, &mut extractor)?,
+        CompressionType::Tar => extract_tar(BufReader::new(source.reader()?), &mut extractor)?,
+        CompressionType::TarGz => extract_tar(
+            GzDecoder::new(BufReader::new(source.reader()?)),
+            &mut extractor,
+        )?,
+        CompressionType::SevenZip => extract_7z(source, &mut extractor)?,
+        CompressionType::Rar => extract_rar(source.path(), &mut extractor)?,
+        CompressionType::Gzip => bail!("'{}' is not an archive", source.path().display()),
+    }
+
+    Ok(extractor.summary)
+}
+
+/// Decides where each entry goes, and keeps the counts.
+struct Extractor<'a> {
+    destination: &'a Path,
+    filter: &'a EntryFilter,
+    overwrite: bool,
+    progress: &'a Progress,
+    summary: ExtractSummary,
+}
+
+impl Extractor<'_> {
+    /// Creates the folder. Folders are only extracted without filter: selected files create
+    /// their own parent folders.
+    fn folder(&mut self, name: &str) -> Result<()> {
+        if !self.filter.select_all() {
+            return Ok(());
+        }
+
+        if let Some(path) = self.safe_path(name) {
+            fs::create_dir_all(&path)
+                .context(format!("Failed to create the folder {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Where the file goes (with its parent folders created), or `None` when it's skipped.
+    fn file_target(&mut self, name: &str) -> Result<Option<PathBuf>> {
+        if !self.filter.matches(&normalize_entry_name(name)) {
+            return Ok(None);
+        }
+
+        let Some(path) = self.safe_path(name) else {
+            return Ok(None);
+        };
+
+        if path.exists() && !self.overwrite {
+            warn!("Skipped {}: the file exists", path.display());
+            self.summary.skipped += 1;
+            return Ok(None);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context(format!("Failed to create the folder {}", parent.display()))?;
+        }
+
+        Ok(Some(path))
+    }
+
+    /// Links could point anywhere, so they are never extracted.
+    fn link(&mut self, name: &str) {
+        if self.filter.matches(&normalize_entry_name(name)) {
+            warn!("Skipped {}: links are not extracted", name);
+            self.summary.unsafe_entries += 1;
+        }
+    }
+
+    fn safe_path(&mut self, name: &str) -> Option<PathBuf> {
+        match safe_relative_path(name) {
+            Some(relative) => Some(self.destination.join(relative)),
+            None => {
+                warn!("Skipped {}: the path is outside of the destination", name);
+                self.summary.unsafe_entries += 1;
+                None
+            }
+        }
+    }
+
+    fn write(&mut self, reader: &mut dyn Read, path: &Path) -> Result<()> {
+        let mut file =
+            File::create(path).context(format!("Failed to create {}", path.display()))?;
+        let bytes =
+            io::copy(reader, &mut file).context(format!("Failed to extract {}", path.display()))?;
+        self.extracted(path, bytes);
+
+        Ok(())
+    }
+
+    fn extracted(&mut self, path: &Path, bytes: u64) {
+        self.summary.extracted += 1;
+        self.summary.bytes += bytes;
+        self.progress.increment(FILES);
+        self.progress.add_bytes(bytes);
+        self.progress.set_detail(path.display().to_string());
+        self.progress.tick();
+    }
+}
+
+fn open_zip(source: &ArchiveSource) -> Result<zip::ZipArchive<impl Read + io::Seek>> {
+    zip::ZipArchive::new(BufReader::new(source.reader()?))
+        .context(format!("Invalid zip archive: {}", source.path().display()))
+}
+
+fn extract_zip(source: &ArchiveSource, extractor: &mut Extractor) -> Result<()> {
+    let mut archive = open_zip(source)?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let name = entry.name().to_string();
+
+        if entry.is_dir() {
+            extractor.folder(&name)?;
+        } else if entry.is_symlink() {
+            extractor.link(&name);
+        } else if let Some(path) = extractor.file_target(&name)? {
+            extractor.write(&mut entry, &path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_tar(reader: impl Read, extractor: &mut Extractor) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+
+    for entry in archive.entries().context("Invalid tar archive")? {
+        let mut entry = entry.context("Invalid tar archive")?;
+        let name = String::from_utf8_lossy(&entry.path_bytes()).to_string();
+        let entry_type = entry.header().entry_type();
+
+        if entry_type.is_dir() {
+            extractor.folder(&name)?;
+        } else if !entry_type.is_file() {
+            extractor.link(&name);
+        } else if let Some(path) = extractor.file_target(&name)? {
+            extractor.write(&mut entry, &path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn open_7z(source: &ArchiveSource) -> Result<SevenZReader<BufReader<impl Read + io::Seek>>> {
+    let reader = source.reader()?;
+    let len = reader.len();
+
+    SevenZReader::new(BufReader::new(reader), len, Password::empty())
+        .map_err(|e| anyhow!("Invalid 7z archive: {}: {}", source.path().display(), e))
+}
+
+fn extract_7z(source: &ArchiveSource, extractor: &mut Extractor) -> Result<()> {
+    let mut archive = open_7z(source)?;
+    let mut failure = None;
+
+    // Entries are decoded in sequence, so the ones skipped are still read through.
+    archive
+        .for_each_entries(|entry, reader| {
+            let result = if entry.is_directory() {
+                extractor.folder(entry.name())
+            } else {
+                match extractor.file_target(entry.name()) {
+                    Ok(Some(path)) => extractor.write(reader, &path),
+                    Ok(None) => io::copy(reader, &mut io::sink())
+                        .map(|_| ())
+                        .map_err(anyhow::Error::from),
+                    Err(e) => Err(e),
+                }
+            };
+
+            match result {
+                Ok(()) => Ok(true),
+                Err(e) => {
+                    failure = Some(e);
+                    Ok(false)
+                }
+            }
+        })
+        .map_err(|e| anyhow!("Failed to extract {}: {}", source.path().display(), e))?;
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// The rar reader opens the next volumes (`.part2.rar`, `.r00`, ...) by itself.
+fn extract_rar(path: &Path, extractor: &mut Extractor) -> Result<()> {
+    let mut archive = unrar::Archive::new(path)
+        .open_for_processing()
+        .context(format!("Failed to open {}", path.display()))?;
+
+    while let Some(header) = archive
+        .read_header()
+        .context(format!("Failed to read {}", path.display()))?
+    {
+        let entry = header.entry();
+        let name = entry.filename.to_string_lossy().to_string();
+        let size = entry.unpacked_size;
+
+        archive = if entry.is_directory() {
+            extractor.folder(&name)?;
+            header.skip()?
+        } else if let Some(target) = extractor.file_target(&name)? {
+            let archive = header
+                .extract_to(&target)
+                .context(format!("Failed to extract {}", target.display()))?;
+            extractor.extracted(&target, size);
+            archive
+        } else {
+            header.skip()?
+        };
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive_writer::create_archive;
+    use tempfile::tempdir;
+
+    fn progress() -> Progress {
+        Progress::new("files", &[FILES])
+    }
+
+    #[test]
+    fn test_round_trip_every_writable_format() {
+        let dir = tempdir().unwrap();
+        let input = dir.path().join("photos");
+        fs::create_dir_all(input.join("2026").join("empty")).unwrap();
+        fs::write(input.join("2026").join("a.jpg"), "jpeg").unwrap();
+        fs::write(input.join("notes.txt"), "notes").unwrap();
+
+        for name in ["photos.zip", "photos.tar", "photos.tar.gz", "photos.7z"] {
+            let archive = dir.path().join(name);
+            create_archive(&archive, std::slice::from_ref(&input), &progress()).unwrap();
+            let source = ArchiveSource::open(&archive).unwrap();
+
+            let files: Vec<String> = list_entries(&source, &EntryFilter::new(&[]).unwrap())
+                .unwrap()
+                .into_iter()
+                .filter(|entry| !entry.is_dir)
+                .map(|entry| entry.path)
+                .collect();
+            assert_eq!(
+                files,
+                vec!["photos/2026/a.jpg", "photos/notes.txt"],
+                "{}",
+                name
+            );
+
+            let output = dir.path().join(format!("out-{}", name));
+            let only_jpg = EntryFilter::new(&["*.jpg".to_string()]).unwrap();
+            let summary = extract_archive(&source, &output, &only_jpg, false, &progress()).unwrap();
+            assert_eq!(summary.extracted, 1, "{}", name);
+            assert_eq!(
+                fs::read_to_string(output.join("photos").join("2026").join("a.jpg")).unwrap(),
+                "jpeg"
+            );
+            assert!(!output.join("photos").join("notes.txt").exists());
+
+            let all = EntryFilter::new(&[]).unwrap();
+            let summary = extract_archive(&source, &output, &all, false, &progress()).unwrap();
+            assert_eq!((summary.extracted, summary.skipped), (1, 1), "{}", name);
+            assert!(output.join("photos").join("2026").join("empty").is_dir());
+        }
+    }
+
+    #[test]
+    fn test_split_archive_is_read_from_its_parts() {
+        let dir = tempdir().unwrap();
+        let input = dir.path().join("data.bin");
+        fs::write(
+            &input,
+            (0..50_000u32)
+                .map(|i| (i * 7 % 251) as u8)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let archive = dir.path().join("data.7z");
+        create_archive(&archive, std::slice::from_ref(&input), &progress()).unwrap();
+
+        let bytes = fs::read(&archive).unwrap();
+        for (index, chunk) in bytes.chunks(bytes.len() / 3 + 1).enumerate() {
+            fs::write(dir.path().join(format!("data.7z.{:03}", index + 1)), chunk).unwrap();
+        }
+
+        let source = ArchiveSource::open(&dir.path().join("data.7z.001")).unwrap();
+        assert_eq!(source.parts.len(), 3);
+
+        let output = dir.path().join("out");
+        let summary = extract_archive(
+            &source,
+            &output,
+            &EntryFilter::new(&[]).unwrap(),
+            false,
+            &progress(),
+        )
+        .unwrap();
+        assert_eq!(summary.extracted, 1);
+        assert_eq!(
+            fs::read(output.join("data.bin")).unwrap(),
+            fs::read(&input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unsafe_entries_are_skipped() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("evil.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("../evil.txt", options).unwrap();
+        writer.start_file("good.txt", options).unwrap();
+        writer.finish().unwrap();
+
+        let output = dir.path().join("out");
+        let summary = extract_archive(
+            &ArchiveSource::open(&archive).unwrap(),
+            &output,
+            &EntryFilter::new(&[]).unwrap(),
+            false,
+            &progress(),
+        )
+        .unwrap();
+
+        assert_eq!((summary.extracted, summary.unsafe_entries), (1, 1));
+        assert!(!dir.path().join("evil.txt").exists());
+        assert!(output.join("good.txt").exists());
+    }
+}
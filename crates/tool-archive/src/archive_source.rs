@@ -0,0 +1,171 @@
+use crate::multi_part_reader::MultiPartReader;
+use anyhow::{bail, Result};
+use shared::system::pathbuf_extensions::{CompressionType, PathBufExtensions};
+use std::path::{Path, PathBuf};
+
+pub const SUPPORTED_FORMATS: &str = ".zip, .tar, .tar.gz (.tgz), .7z, and .rar (extract only)";
+
+/// An archive to read: its format and the files it's stored in (more than one for split
+/// archives, like `backup.7z.001`, `backup.7z.002`, ...).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveSource {
+    pub format: CompressionType,
+    pub parts: Vec<PathBuf>,
+}
+
+impl ArchiveSource {
+    /// Detects the format by the name. Multi-part archives must be opened by their first part:
+    /// the other parts are found next to it. Rar volumes (`.part2.rar`, `.r00`, ...) are found by
+    /// the rar reader itself.
+    pub fn open(path: &Path) -> Result<Self> {
+        if path.is_main_file_multi_part_compression() {
+            if path.compression_type() == Some(CompressionType::Rar) {
+                return Ok(Self {
+                    format: CompressionType::Rar,
+                    parts: vec![path.to_path_buf()],
+                });
+            }
+
+            return Ok(Self {
+                format: supported_format(&path.with_extension(""))?,
+                parts: find_parts(path),
+            });
+        }
+
+        if let Some(part) = path.multi_part_index() {
+            bail!(
+                "'{}' is part {} of a multi-part archive. Open the first part: '{}'",
+                path.display(),
+                part,
+                first_part(path).display()
+            );
+        }
+
+        Ok(Self {
+            format: supported_format(path)?,
+            parts: vec![path.to_path_buf()],
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.parts[0]
+    }
+
+    pub fn reader(&self) -> Result<MultiPartReader> {
+        MultiPartReader::open(&self.parts)
+    }
+}
+
+/// The format of an archive to create or read, by its extension.
+pub fn supported_format(path: &Path) -> Result<CompressionType> {
+    match path.compression_type() {
+        Some(CompressionType::Gzip) => bail!(
+            "'{}' is a single compressed file, not an archive. Supported archives: {}",
+            path.display(),
+            SUPPORTED_FORMATS
+        ),
+        Some(format) => Ok(format),
+        None => bail!(
+            "Unknown archive format: '{}'. Supported archives: {}",
+            path.display(),
+            SUPPORTED_FORMATS
+        ),
+    }
+}
+
+/// The first part and the ones after it (`.001`, `.002`, ...), until one is missing.
+fn find_parts(first: &Path) -> Vec<PathBuf> {
+    let width = first.extension().map_or(3, |ext| ext.len());
+
+    (1u32..)
+        .map(|part| first.with_extension(format!("{:0width$}", part, width = width)))
+        .take_while(|part| part.exists())
+        .collect()
+}
+
+/// The name of the first part of a multi-part archive, from any of its parts.
+fn first_part(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let lowercase = name.to_ascii_lowercase();
+
+    if let Some(stem) = lowercase.strip_suffix(".rar") {
+        if let Some(index) = stem.rfind(".part") {
+            let digits = stem.len() - index - ".part".len();
+            return path.with_file_name(format!(
+                "{}{:0width$}.rar",
+                &name[..index + ".part".len()],
+                1,
+                width = digits
+            ));
+        }
+    }
+
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if extension.to_ascii_lowercase().starts_with('r') {
+        return path.with_extension("rar");
+    }
+
+    path.with_extension(format!("{:0width$}", 1, width = extension.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_open_detects_formats_and_parts() {
+        let dir = tempdir().unwrap();
+        for part in ["photos.7z.001", "photos.7z.002", "photos.7z.004"] {
+            std::fs::write(dir.path().join(part), "x").unwrap();
+        }
+
+        let split = ArchiveSource::open(&dir.path().join("photos.7z.001")).unwrap();
+        assert_eq!(split.format, CompressionType::SevenZip);
+        assert_eq!(
+            split.parts,
+            vec![
+                dir.path().join("photos.7z.001"),
+                dir.path().join("photos.7z.002")
+            ]
+        );
+
+        let error = ArchiveSource::open(&dir.path().join("photos.7z.002")).unwrap_err();
+        assert!(error.to_string().contains("photos.7z.001"));
+
+        assert_eq!(
+            ArchiveSource::open(Path::new("a.tgz")).unwrap().format,
+            CompressionType::TarGz
+        );
+        assert_eq!(
+            ArchiveSource::open(Path::new("movie.part1.rar"))
+                .unwrap()
+                .parts,
+            vec![PathBuf::from("movie.part1.rar")]
+        );
+        assert!(ArchiveSource::open(Path::new("app.log.gz")).is_err());
+        assert!(ArchiveSource::open(Path::new("notes.txt")).is_err());
+    }
+
+    #[test]
+    fn test_first_part() {
+        assert_eq!(
+            first_part(Path::new("movie.part03.rar")),
+            PathBuf::from("movie.part01.rar")
+        );
+        assert_eq!(
+            first_part(Path::new("movie.r02")),
+            PathBuf::from("movie.rar")
+        );
+        assert_eq!(
+            first_part(Path::new("backup.zip.0002")),
+            PathBuf::from("backup.zip.0001")
+        );
+    }
+}
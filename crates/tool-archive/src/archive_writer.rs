@@ -0,0 +1,273 @@
+use crate::archive_reader::FILES;
+use crate::archive_source::supported_format;
+use crate::models::CreateSummary;
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sevenz_rust::{SevenZArchiveEntry, SevenZWriter};
+use shared::system::pathbuf_extensions::{CompressionType, PathBufExtensions};
+use shared::ui::progress::Progress;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// A file or folder to add, and its name inside the archive (with `/`).
+#[derive(Debug, Clone, PartialEq)]
+struct PendingEntry {
+    source: PathBuf,
+    name: String,
+    is_dir: bool,
+}
+
+/// Creates `archive` with the files and folders in `inputs`. A file is stored by its name, and a
+/// folder with its name as prefix of everything in it (empty folders included). The format comes
+/// from the extension of `archive`. If anything fails, the partial archive is removed.
+pub fn create_archive(
+    archive: &Path,
+    inputs: &[PathBuf],
+    progress: &Progress,
+) -> Result<CreateSummary> {
+    if archive.multi_part_index().is_some() {
+        bail!(
+            "Split archives can be read, but not created: '{}'",
+            archive.display()
+        );
+    }
+
+    let format = supported_format(archive)?;
+    if format == CompressionType::Rar {
+        bail!(
+            "'{}': rar archives can only be extracted",
+            archive.display()
+        );
+    }
+
+    let entries = collect_entries(archive, inputs)?;
+    let result = match format {
+        CompressionType::Zip => write_zip(archive, &entries, progress),
+        CompressionType::Tar => {
+            write_tar(BufWriter::new(File::create(archive)?), &entries, progress)
+                .and_then(|mut writer| writer.flush().map_err(anyhow::Error::from))
+        }
+        CompressionType::TarGz => write_tar(
+            GzEncoder::new(
+                BufWriter::new(File::create(archive)?),
+                Compression::default(),
+            ),
+            &entries,
+            progress,
+        )
+        .and_then(|encoder| {
+            encoder
+                .finish()
+                .and_then(|mut writer| writer.flush())
+                .map_err(anyhow::Error::from)
+        }),
+        CompressionType::SevenZip => write_7z(archive, &entries, progress),
+        CompressionType::Rar | CompressionType::Gzip => unreachable!(),
+    };
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(archive);
+        return Err(e.context(format!("Failed to create {}", archive.display())));
+    }
+
+    let files: Vec<&PendingEntry> = entries.iter().filter(|entry| !entry.is_dir).collect();
+    Ok(CreateSummary {
+        files: files.len() as u64,
+        bytes: files
+            .iter()
+            .filter_map(|entry| entry.source.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum(),
+    })
+}
+
+/// Everything to add, folders before their content. The archive itself is left out, for when
+/// it's created inside one of the folders.
+fn collect_entries(archive: &Path, inputs: &[PathBuf]) -> Result<Vec<PendingEntry>> {
+    let archive = archive
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .canonicalize()
+        .ok()
+        .zip(archive.file_name())
+        .map(|(parent, name)| parent.join(name));
+    let mut entries = Vec::new();
+
+    for input in inputs {
+        if input.is_file() {
+            entries.push(PendingEntry {
+                source: input.clone(),
+                name: input
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                is_dir: false,
+            });
+            continue;
+        }
+
+        let prefix = input
+            .canonicalize()
+            .context(format!("Failed to read {}", input.display()))?
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+
+        for entry in WalkDir::new(input).follow_links(true).sort_by_file_name() {
+            let entry = entry.context(format!("Failed to read {}", input.display()))?;
+            let relative = entry.path().strip_prefix(input)?;
+            if archive.as_deref() == entry.path().canonicalize().ok().as_deref() {
+                continue;
+            }
+
+            let name = prefix
+                .iter()
+                .cloned()
+                .chain(
+                    relative
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy().to_string()),
+                )
+                .collect::<Vec<_>>()
+                .join("/");
+            if name.is_empty() {
+                continue;
+            }
+
+            entries.push(PendingEntry {
+                source: entry.path().to_path_buf(),
+                name,
+                is_dir: entry.file_type().is_dir(),
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+fn added(entry: &PendingEntry, progress: &Progress) {
+    progress.increment(FILES);
+    if let Ok(metadata) = entry.source.metadata() {
+        progress.add_bytes(metadata.len());
+    }
+    progress.set_detail(entry.name.clone());
+    progress.tick();
+}
+
+fn write_zip(archive: &Path, entries: &[PendingEntry], progress: &Progress) -> Result<()> {
+    let mut writer = ZipWriter::new(BufWriter::new(File::create(archive)?));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for entry in entries {
+        if entry.is_dir {
+            writer.add_directory(format!("{}/", entry.name), options)?;
+            continue;
+        }
+
+        let size = entry.source.metadata()?.len();
+        writer.start_file(
+            entry.name.as_str(),
+            options.large_file(size >= u32::MAX as u64),
+        )?;
+        io::copy(&mut File::open(&entry.source)?, &mut writer)
+            .context(format!("Failed to read {}", entry.source.display()))?;
+        added(entry, progress);
+    }
+
+    writer.finish()?.flush()?;
+    Ok(())
+}
+
+fn write_tar<W: Write>(writer: W, entries: &[PendingEntry], progress: &Progress) -> Result<W> {
+    let mut builder = tar::Builder::new(writer);
+
+    for entry in entries {
+        if entry.is_dir {
+            builder.append_dir(&entry.name, &entry.source)?;
+            continue;
+        }
+
+        builder
+            .append_path_with_name(&entry.source, &entry.name)
+            .context(format!("Failed to read {}", entry.source.display()))?;
+        added(entry, progress);
+    }
+
+    Ok(builder.into_inner()?)
+}
+
+fn write_7z(archive: &Path, entries: &[PendingEntry], progress: &Progress) -> Result<()> {
+    let mut writer = SevenZWriter::create(archive).map_err(|e| anyhow!("{}", e))?;
+
+    for entry in entries {
+        let archive_entry = SevenZArchiveEntry::from_path(&entry.source, entry.name.clone());
+        if entry.is_dir {
+            writer
+                .push_archive_entry(archive_entry, None::<File>)
+                .map_err(|e| anyhow!("{}", e))?;
+            continue;
+        }
+
+        writer
+            .push_archive_entry(archive_entry, Some(File::open(&entry.source)?))
+            .map_err(|e| anyhow!("Failed to add {}: {}", entry.source.display(), e))?;
+        added(entry, progress);
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_collect_entries_names_and_skips_the_archive() {
+        let dir = tempdir().unwrap();
+        let input = dir.path().join("docs");
+        fs::create_dir_all(input.join("sub")).unwrap();
+        fs::write(input.join("sub").join("b.md"), "b").unwrap();
+        fs::write(input.join("a.md"), "a").unwrap();
+        fs::write(input.join("docs.zip"), "old").unwrap();
+        fs::write(dir.path().join("loose.txt"), "x").unwrap();
+
+        let entries = collect_entries(
+            &input.join("docs.zip"),
+            &[input, dir.path().join("loose.txt")],
+        )
+        .unwrap();
+        let names: Vec<(&str, bool)> = entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.is_dir))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("docs", true),
+                ("docs/a.md", false),
+                ("docs/sub", true),
+                ("docs/sub/b.md", false),
+                ("loose.txt", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unwritable_formats_are_rejected() {
+        let dir = tempdir().unwrap();
+        let progress = Progress::new("files", &[FILES]);
+        let input = [dir.path().to_path_buf()];
+
+        assert!(create_archive(&dir.path().join("a.rar"), &input, &progress).is_err());
+        assert!(create_archive(&dir.path().join("a.7z.001"), &input, &progress).is_err());
+        assert!(!dir.path().join("a.rar").exists());
+    }
+}
@@ -0,0 +1,247 @@
+use crate::models::{ArchiveArgs, ArchiveCommand};
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{GlobalArgs, ToolCli};
+use std::path::PathBuf;
+
+impl ToolCli for ArchiveArgs {
+    const DISPLAY_NAME: &'static str = "Archive";
+
+    fn command() -> Command {
+        let archive = || {
+            Arg::new("archive")
+                .value_name("ARCHIVE")
+                .required(true)
+                .value_parser(clap::value_parser!(PathBuf))
+        };
+        let include = || {
+            Arg::new("include")
+                .long("include")
+                .short('i')
+                .value_name("GLOB")
+                .action(ArgAction::Append)
+                .help("Only entries matching the glob (repeatable). Globs with a '/' match the path inside the archive, the others match the file name. (Default: all)")
+        };
+        let overwrite = || {
+            Arg::new("overwrite")
+                .long("overwrite")
+                .action(ArgAction::SetTrue)
+        };
+
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Creates, extracts and lists archives: .zip, .tar, .tar.gz (.tgz), .7z, and .rar \
+                (extract only). The format comes from the extension. Split archives \
+                (backup.7z.001, backup.7z.002, ..., or movie.part1.rar, movie.part2.rar, ...) are \
+                read by their first part.",
+            )
+            .arg_required_else_help(true)
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("create")
+                    .about("Creates an archive with files and folders")
+                    .arg(archive().help("Archive to create (.zip, .tar, .tar.gz, .tgz or .7z)"))
+                    .arg(
+                        Arg::new("inputs")
+                            .value_name("PATH")
+                            .required(true)
+                            .num_args(1..)
+                            .value_parser(clap::value_parser!(PathBuf))
+                            .help("Files and folders to add. Folders are added with their name, and everything in them"),
+                    )
+                    .arg(overwrite().help("Replaces the archive, if it exists. (Default: false)")),
+            )
+            .subcommand(
+                Command::new("extract")
+                    .visible_alias("x")
+                    .about("Extracts the files of an archive, keeping their folders")
+                    .arg(archive().help("Archive to extract (the first part, for split archives)"))
+                    .arg(
+                        Arg::new("dest")
+                            .long("dest")
+                            .short('d')
+                            .value_name("DIR")
+                            .value_parser(clap::value_parser!(PathBuf))
+                            .help("Folder to extract to. (Default: a folder named after the archive, in the current folder)"),
+                    )
+                    .arg(include())
+                    .arg(overwrite().help("Replaces files that exist. Without it, they are skipped. (Default: false)")),
+            )
+            .subcommand(
+                Command::new("list")
+                    .visible_alias("ls")
+                    .about("Lists the entries of an archive")
+                    .arg(archive().help("Archive to list (the first part, for split archives)"))
+                    .arg(include()),
+            )
+    }
+
+    fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+        let archive = |sub_matches: &ArgMatches| {
+            sub_matches
+                .get_one::<PathBuf>("archive")
+                .cloned()
+                .unwrap_or_default()
+        };
+        let include = |sub_matches: &ArgMatches| {
+            sub_matches
+                .get_many::<String>("include")
+                .unwrap_or_default()
+                .cloned()
+                .collect()
+        };
+
+        let command = match matches.subcommand() {
+            Some(("create", sub_matches)) => ArchiveCommand::Create {
+                archive: archive(sub_matches),
+                inputs: sub_matches
+                    .get_many::<PathBuf>("inputs")
+                    .unwrap_or_default()
+                    .cloned()
+                    .collect(),
+                overwrite: sub_matches.get_flag("overwrite"),
+            },
+            Some(("extract", sub_matches)) => ArchiveCommand::Extract {
+                archive: archive(sub_matches),
+                destination: sub_matches.get_one::<PathBuf>("dest").cloned(),
+                include: include(sub_matches),
+                overwrite: sub_matches.get_flag("overwrite"),
+            },
+            Some(("list", sub_matches)) => ArchiveCommand::List {
+                archive: archive(sub_matches),
+                include: include(sub_matches),
+            },
+            _ => unreachable!("clap enforces one of the known subcommands"),
+        };
+
+        Ok(ArchiveArgs { command })
+    }
+
+    fn validate(&self) -> Result<()> {
+        match &self.command {
+            ArchiveCommand::Create {
+                archive,
+                inputs,
+                overwrite,
+            } => {
+                if let Some(missing) = inputs.iter().find(|input| !input.exists()) {
+                    bail!("'{}' does not exist", missing.display());
+                }
+
+                if archive.is_dir() {
+                    bail!("Archive '{}' is a folder", archive.display());
+                }
+
+                if archive.exists() && !overwrite {
+                    bail!(
+                        "Archive '{}' exists. Use --overwrite to replace it",
+                        archive.display()
+                    );
+                }
+            }
+            ArchiveCommand::Extract { archive, .. } | ArchiveCommand::List { archive, .. } => {
+                if !archive.is_file() {
+                    bail!("Archive '{}' does not exist", archive.display());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let include = |include: &Vec<String>| {
+            if include.is_empty() {
+                "all".to_string()
+            } else {
+                include.join(", ")
+            }
+        };
+
+        match &self.command {
+            ArchiveCommand::Create {
+                archive, inputs, ..
+            } => vec![
+                ("Command".to_string(), "create".to_string()),
+                ("Archive".to_string(), archive.display().to_string()),
+                (
+                    "Inputs".to_string(),
+                    inputs
+                        .iter()
+                        .map(|input| input.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            ],
+            ArchiveCommand::Extract {
+                archive,
+                destination,
+                include: patterns,
+                overwrite,
+            } => vec![
+                ("Command".to_string(), "extract".to_string()),
+                ("Archive".to_string(), archive.display().to_string()),
+                (
+                    "Destination".to_string(),
+                    destination
+                        .as_ref()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_else(|| "(named after the archive)".to_string()),
+                ),
+                ("Include".to_string(), include(patterns)),
+                ("Overwrite".to_string(), overwrite.to_string()),
+            ],
+            ArchiveCommand::List {
+                archive,
+                include: patterns,
+            } => vec![
+                ("Command".to_string(), "list".to_string()),
+                ("Archive".to_string(), archive.display().to_string()),
+                ("Include".to_string(), include(patterns)),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+    use tempfile::tempdir;
+
+    fn parse(argv: &[&str]) -> Result<ArchiveArgs> {
+        try_parse_tool_cli_from::<ArchiveArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("photos.zip");
+        std::fs::write(&existing, "zip").unwrap();
+        let existing = existing.to_str().unwrap();
+        let folder = dir.path().to_str().unwrap();
+
+        let args = parse(&[
+            "archive", "x", existing, "-d", "out", "-i", "*.jpg", "-i", "docs/*",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            ArchiveCommand::Extract {
+                archive: PathBuf::from(existing),
+                destination: Some(PathBuf::from("out")),
+                include: vec!["*.jpg".to_string(), "docs/*".to_string()],
+                overwrite: false,
+            }
+        );
+
+        assert!(parse(&["archive", "create", existing, folder]).is_err());
+        assert!(parse(&["archive", "create", existing, folder, "--overwrite"]).is_ok());
+        assert!(parse(&["archive", "create", "new.zip", "missing-folder"]).is_err());
+        assert!(parse(&["archive", "list", "missing.zip"]).is_err());
+        assert!(parse(&["archive"]).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::PathBuf;
+
+/// Selects entries by glob. Patterns with a `/` match the whole path inside the archive
+/// (`docs/**/*.md`), the others match the file name (`*.jpg`). No patterns selects everything.
+#[derive(Debug, Clone)]
+pub struct EntryFilter {
+    path_globs: GlobSet,
+    name_globs: GlobSet,
+    select_all: bool,
+}
+
+impl EntryFilter {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut path_globs = GlobSetBuilder::new();
+        let mut name_globs = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            let glob = Glob::new(pattern).context(format!("Invalid glob: '{}'", pattern))?;
+            if pattern.contains('/') {
+                path_globs.add(glob);
+            } else {
+                name_globs.add(glob);
+            }
+        }
+
+        Ok(Self {
+            path_globs: path_globs.build()?,
+            name_globs: name_globs.build()?,
+            select_all: patterns.is_empty(),
+        })
+    }
+
+    pub fn select_all(&self) -> bool {
+        self.select_all
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        if self.select_all {
+            return true;
+        }
+
+        let path = path.trim_end_matches('/');
+        let name = path.rsplit('/').next().unwrap_or(path);
+
+        self.path_globs.is_match(path) || self.name_globs.is_match(name)
+    }
+}
+
+/// The path of the entry, relative to the destination. `None` for paths that could write outside
+/// of it: absolute paths, drive letters, and `..`.
+pub fn safe_relative_path(name: &str) -> Option<PathBuf> {
+    let name = name.replace('\\', "/");
+    if name.starts_with('/') {
+        return None;
+    }
+
+    let mut path = PathBuf::new();
+    for component in name.split('/') {
+        match component {
+            "" | "." => continue,
+            ".." => return None,
+            _ if component.contains(':') => return None,
+            _ => path.push(component),
+        }
+    }
+
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+/// The name of the entry, with `/`, as listed and matched.
+pub fn normalize_entry_name(name: &str) -> String {
+    name.replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_filter() {
+        let filter = EntryFilter::new(&["*.JPG".to_string(), "docs/**/*.md".to_string()]).unwrap();
+
+        assert!(filter.matches("photos/2026/IMG_1.JPG"));
+        assert!(filter.matches("docs/guide/intro.md"));
+        assert!(!filter.matches("readme.md"));
+        assert!(!filter.matches("photos/IMG_1.png"));
+        assert!(EntryFilter::new(&[]).unwrap().matches("anything"));
+        assert!(EntryFilter::new(&["[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_safe_relative_path() {
+        assert_eq!(
+            safe_relative_path("./docs\\a.txt"),
+            Some(PathBuf::from("docs").join("a.txt"))
+        );
+        assert_eq!(safe_relative_path("../evil.sh"), None);
+        assert_eq!(safe_relative_path("docs/../../evil.sh"), None);
+        assert_eq!(safe_relative_path("/etc/passwd"), None);
+        assert_eq!(safe_relative_path("C:/Windows/evil.dll"), None);
+        assert_eq!(safe_relative_path("./"), None);
+    }
+}
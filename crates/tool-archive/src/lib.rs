@@ -0,0 +1,45 @@
+use crate::models::ArchiveArgs;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod archive_app;
+mod archive_reader;
+mod archive_source;
+mod archive_writer;
+mod cli_utils;
+mod entry_paths;
+mod models;
+mod multi_part_reader;
+
+/// Archive creation, extraction and listing.
+///
+/// Parses arguments and runs the subcommand: `create`, `extract` or `list`.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<ArchiveArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    invocation.print_runtime_info();
+
+    if let Err(e) = archive_app::run(&invocation.args) {
+        eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+        error!("{}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveArgs {
+    pub command: ArchiveCommand,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArchiveCommand {
+    /// Creates `archive` (the format comes from its extension) with the files and folders.
+    Create {
+        archive: PathBuf,
+        inputs: Vec<PathBuf>,
+        overwrite: bool,
+    },
+    /// Extracts the entries matching `include` (all, when empty) into `destination`.
+    Extract {
+        archive: PathBuf,
+        /// `None` extracts to a folder named after the archive, in the current folder.
+        destination: Option<PathBuf>,
+        include: Vec<String>,
+        overwrite: bool,
+    },
+    /// Lists the entries matching `include` (all, when empty).
+    List {
+        archive: PathBuf,
+        include: Vec<String>,
+    },
+}
+
+/// A file or folder inside an archive. `path` uses `/`, as stored in the archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub size: u64,
+    pub is_dir: bool,
+}
+
+/// What the creation of an archive did.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateSummary {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// What an extraction did.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtractSummary {
+    pub extracted: u64,
+    pub bytes: u64,
+    /// Files that already existed (without `--overwrite`).
+    pub skipped: u64,
+    /// Entries with absolute paths, or paths leaving the destination (`../`), and links.
+    pub unsafe_entries: u64,
+}
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// Reads the parts of a split archive (`backup.7z.001`, `backup.7z.002`, ...) as if they were a
+/// single file, so the zip and 7z readers can seek through them.
+pub struct MultiPartReader {
+    parts: Vec<(File, u64)>,
+    len: u64,
+    position: u64,
+}
+
+impl MultiPartReader {
+    pub fn open(paths: &[PathBuf]) -> Result<Self> {
+        let mut parts = Vec::with_capacity(paths.len());
+        for path in paths {
+            let file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+            let len = file
+                .metadata()
+                .context(format!("Failed to read {}", path.display()))?
+                .len();
+            parts.push((file, len));
+        }
+
+        Ok(Self {
+            len: parts.iter().map(|(_, len)| len).sum(),
+            parts,
+            position: 0,
+        })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// The part holding `position`, and the offset inside it.
+    fn locate(&self, position: u64) -> Option<(usize, u64)> {
+        let mut start = 0;
+        for (index, (_, len)) in self.parts.iter().enumerate() {
+            if position < start + len {
+                return Some((index, position - start));
+            }
+            start += len;
+        }
+
+        None
+    }
+}
+
+impl Read for MultiPartReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let Some((index, offset)) = self.locate(self.position) else {
+            return Ok(0);
+        };
+
+        let (file, len) = &mut self.parts[index];
+        let available = (*len - offset).min(buf.len() as u64) as usize;
+        file.seek(SeekFrom::Start(offset))?;
+        let read = file.read(&mut buf[..available])?;
+        self.position += read as u64;
+
+        Ok(read)
+    }
+}
+
+impl Seek for MultiPartReader {
+    fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+        let target = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+
+        match target {
+            Some(target) => {
+                self.position = target;
+                Ok(target)
+            }
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid seek to a negative position",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_reads_and_seeks_across_parts() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = ["abc", "defg", "h"]
+            .iter()
+            .enumerate()
+            .map(|(index, content)| {
+                let path = dir.path().join(format!("data.7z.{:03}", index + 1));
+                std::fs::write(&path, content).unwrap();
+                path
+            })
+            .collect();
+
+        let mut reader = MultiPartReader::open(&paths).unwrap();
+        assert_eq!(reader.len(), 8);
+
+        let mut all = String::new();
+        reader.read_to_string(&mut all).unwrap();
+        assert_eq!(all, "abcdefgh");
+
+        reader.seek(SeekFrom::End(-6)).unwrap();
+        let mut middle = [0u8; 4];
+        reader.read_exact(&mut middle).unwrap();
+        assert_eq!(&middle, b"cdef");
+        assert!(reader.seek(SeekFrom::Current(-10)).is_err());
+    }
+}
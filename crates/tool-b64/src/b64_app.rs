@@ -1,6 +1,11 @@
-use crate::models::{B64Config, B64Mode, InputSource, OutputTarget};
+use crate::inspector::{inspect, render};
+use crate::models::{
+    B64Config, B64Mode, DigestFormat, InputSource, KeySource, OutputTarget, KEYRING_SERVICE,
+};
 use base64::engine::general_purpose::STANDARD;
 use base64::{DecodeSliceError, Engine};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
 use shared::constants::general::SIZE_64KB;
 use std::fs::File;
 use std::io::{self, BufReader, BufWriter, Cursor, Read, Write};
@@ -12,6 +17,8 @@ pub fn run(config: &B64Config) -> Result<(), AppError> {
     match config.mode {
         B64Mode::Encode => encode(config),
         B64Mode::Decode => decode(config),
+        B64Mode::Hash | B64Mode::Hmac => digest(config),
+        B64Mode::Inspect => inspect_layers(config),
     }
 }
 
@@ -174,6 +181,108 @@ fn decode(config: &B64Config) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Computes the SHA-256 (or HMAC-SHA256) of the input and writes it in a single line.
+fn digest(config: &B64Config) -> Result<(), AppError> {
+    let mut reader = BufReader::new(open_reader(&config.input)?);
+
+    let digest = if config.mode == B64Mode::Hmac {
+        let key = resolve_key(config.key.as_ref())?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(&key)
+            .map_err(|err| AppError::new(format!("invalid HMAC key: {}", err), 1))?;
+        read_chunks(&mut reader, &config.input, |chunk| mac.update(chunk))?;
+        mac.finalize().into_bytes().to_vec()
+    } else {
+        let mut hasher = Sha256::new();
+        read_chunks(&mut reader, &config.input, |chunk| hasher.update(chunk))?;
+        hasher.finalize().to_vec()
+    };
+
+    let mut line = format_digest(&digest, config.digest_format);
+    line.push('\n');
+
+    let mut writer = open_writer(&config.output)?;
+    write_all(&mut writer, line.as_bytes(), &config.output)?;
+    writer
+        .flush()
+        .map_err(|err| map_write_error(&config.output, err))?;
+
+    Ok(())
+}
+
+/// Decodes the input layer by layer and writes a report with a section per layer.
+fn inspect_layers(config: &B64Config) -> Result<(), AppError> {
+    let mut reader = BufReader::new(open_reader(&config.input)?);
+    let mut input = Vec::new();
+    read_chunks(&mut reader, &config.input, |chunk| {
+        input.extend_from_slice(chunk)
+    })?;
+
+    let layers = inspect(&input).map_err(|message| AppError::new(message, 2))?;
+    let report = render(&layers, chrono::Utc::now());
+
+    let mut writer = open_writer(&config.output)?;
+    write_all(&mut writer, report.as_bytes(), &config.output)?;
+    writer
+        .flush()
+        .map_err(|err| map_write_error(&config.output, err))?;
+
+    Ok(())
+}
+
+fn read_chunks<R: Read>(
+    reader: &mut R,
+    source: &InputSource,
+    mut consume: impl FnMut(&[u8]),
+) -> Result<(), AppError> {
+    let mut buffer = vec![0u8; SIZE_64KB];
+
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|err| AppError::read_error(source, err))?;
+
+        if read == 0 {
+            return Ok(());
+        }
+
+        consume(&buffer[..read]);
+    }
+}
+
+fn resolve_key(source: Option<&KeySource>) -> Result<Vec<u8>, AppError> {
+    let key = match source {
+        None => return Err(AppError::missing_key("no key source informed")),
+        Some(KeySource::Text(key)) => key.clone(),
+        Some(KeySource::Env(variable)) => std::env::var(variable).map_err(|_| {
+            AppError::missing_key(&format!(
+                "environment variable '{}' is not set. Use --key, --key-env, or --key-keyring",
+                variable
+            ))
+        })?,
+        Some(KeySource::Keyring(entry)) => keyring::Entry::new(KEYRING_SERVICE, entry)
+            .and_then(|entry| entry.get_password())
+            .map_err(|err| {
+                AppError::missing_key(&format!(
+                    "cannot read '{}' from the keyring (service '{}'): {}",
+                    entry, KEYRING_SERVICE, err
+                ))
+            })?,
+    };
+
+    if key.is_empty() {
+        return Err(AppError::missing_key("the key is empty"));
+    }
+
+    Ok(key.into_bytes())
+}
+
+fn format_digest(digest: &[u8], format: DigestFormat) -> String {
+    match format {
+        DigestFormat::Base64 => STANDARD.encode(digest),
+        DigestFormat::Hex => digest.iter().map(|byte| format!("{:02x}", byte)).collect(),
+    }
+}
+
 fn open_reader(source: &InputSource) -> Result<Box<dyn Read>, AppError> {
     match source.clone() {
         InputSource::Stdin => Ok(Box::new(io::stdin())),
@@ -283,6 +392,10 @@ impl AppError {
         }
     }
 
+    fn missing_key(reason: &str) -> Self {
+        Self::new(format!("HMAC key error: {}", reason), 1)
+    }
+
     fn encode_error() -> Self {
         Self::new("encode error: internal encoder failure", 1)
     }
@@ -357,6 +470,8 @@ mod tests {
             ignore_garbage: false,
             input: InputSource::File(input.to_path_buf()),
             output: OutputTarget::File(output.to_path_buf()),
+            digest_format: DigestFormat::Base64,
+            key: None,
         }
     }
 
@@ -367,6 +482,8 @@ mod tests {
             ignore_garbage,
             input: InputSource::File(input.to_path_buf()),
             output: OutputTarget::File(output.to_path_buf()),
+            digest_format: DigestFormat::Base64,
+            key: None,
         }
     }
 
@@ -377,6 +494,8 @@ mod tests {
             ignore_garbage: false,
             input: InputSource::Text(text.to_string()),
             output: OutputTarget::File(output.to_path_buf()),
+            digest_format: DigestFormat::Base64,
+            key: None,
         }
     }
 
@@ -387,6 +506,8 @@ mod tests {
             ignore_garbage,
             input: InputSource::Text(text.to_string()),
             output: OutputTarget::File(output.to_path_buf()),
+            digest_format: DigestFormat::Base64,
+            key: None,
         }
     }
 
@@ -510,4 +631,93 @@ mod tests {
         assert_eq!(err.exit_code, 2);
         assert!(err.message.contains("decode error"));
     }
+
+    fn digest_config(mode: B64Mode, text: &str, output: &Path, key: Option<&str>) -> B64Config {
+        B64Config {
+            mode,
+            wrap_columns: NonZeroUsize::new(76),
+            ignore_garbage: false,
+            input: InputSource::Text(text.to_string()),
+            output: OutputTarget::File(output.to_path_buf()),
+            digest_format: DigestFormat::Base64,
+            key: key.map(|k| KeySource::Text(k.to_string())),
+        }
+    }
+
+    #[test]
+    fn hash_outputs_base64_sha256() {
+        let dir = tempdir().unwrap();
+        let output_path = dir.path().join("output.txt");
+
+        run(&digest_config(
+            B64Mode::Hash,
+            "hello world",
+            &output_path,
+            None,
+        ))
+        .unwrap();
+
+        let digest = fs::read_to_string(&output_path).unwrap();
+        assert_eq!(digest, "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=\n");
+    }
+
+    #[test]
+    fn hmac_outputs_hex_when_requested() {
+        let dir = tempdir().unwrap();
+        let output_path = dir.path().join("output.txt");
+
+        // RFC 4231, test case 2.
+        let mut config = digest_config(
+            B64Mode::Hmac,
+            "what do ya want for nothing?",
+            &output_path,
+            Some("Jefe"),
+        );
+        config.digest_format = DigestFormat::Hex;
+
+        run(&config).unwrap();
+
+        let digest = fs::read_to_string(&output_path).unwrap();
+        assert_eq!(
+            digest,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843\n"
+        );
+    }
+
+    #[test]
+    fn hmac_missing_env_key_is_an_error() {
+        let dir = tempdir().unwrap();
+        let output_path = dir.path().join("output.txt");
+
+        let mut config = digest_config(B64Mode::Hmac, "payload", &output_path, None);
+        config.key = Some(KeySource::Env("B64_TEST_KEY_THAT_IS_NOT_SET".to_string()));
+
+        let err = run(&config).err().unwrap();
+        assert_eq!(err.exit_code, 1);
+        assert!(err.message.contains("B64_TEST_KEY_THAT_IS_NOT_SET"));
+    }
+
+    #[test]
+    fn inspect_reports_each_layer() {
+        let dir = tempdir().unwrap();
+        let output_path = dir.path().join("report.txt");
+
+        // {"user":"raccoon"}, encoded twice.
+        let mut config =
+            decode_text_config("ZXlKMWMyVnlJam9pY21GalkyOXZiaUo5", &output_path, false);
+        config.mode = B64Mode::Inspect;
+
+        run(&config).unwrap();
+
+        let report = fs::read_to_string(&output_path).unwrap();
+        assert_eq!(
+            report,
+            "== [1] Base64 (standard): 24 bytes decoded ==\n\
+             == [2] Base64 (standard): 18 bytes decoded ==\n\
+             == [3] JSON ==\n{\n  \"user\": \"raccoon\"\n}\n"
+        );
+
+        config.input = InputSource::Text("%%%".to_string());
+        assert_eq!(run(&config).err().unwrap().exit_code, 2);
+    }
 }
@@ -1,14 +1,15 @@
 use crate::models::B64Config;
-use clap::{builder::NonEmptyStringValueParser, Arg, ArgAction, Command};
+use clap::{builder::NonEmptyStringValueParser, Arg, ArgAction, ArgGroup, Command};
 use shared::command_line::cli_builder::CommandExt;
+use std::ffi::OsString;
 
 /// Parses command-line arguments and returns the runtime configuration.
-pub fn get_cli_arguments() -> B64Config {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> B64Config {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
             "Encode or decode data using Base64.",
-            "Encode or decode data using Base64. With no FILE, or when FILE is -, read standard input.",
+            "Encode or decode data using Base64, inspect nested payloads and JWTs, or compute its SHA-256/HMAC-SHA256. With no FILE, or when FILE is -, read standard input.",
         )
         .arg(
             Arg::new("decode")
@@ -17,6 +18,58 @@ pub fn get_cli_arguments() -> B64Config {
                 .action(ArgAction::SetTrue)
                 .help("Decode Base64 input to binary"),
         )
+        .arg(
+            Arg::new("inspect")
+                .long("inspect")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["hash", "hmac"])
+                .help("Decode the input layer by layer (nested Base64, gzip, JSON, JWT), describing each one"),
+        )
+        .arg(
+            Arg::new("hash")
+                .long("hash")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["decode", "hmac"])
+                .help("Output the SHA-256 of the input (Base64 by default)"),
+        )
+        .arg(
+            Arg::new("hmac")
+                .long("hmac")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("decode")
+                .help("Output the HMAC-SHA256 of the input. Key from --key, --key-env, --key-keyring, or the B64_HMAC_KEY variable"),
+        )
+        .arg(
+            Arg::new("hex")
+                .long("hex")
+                .action(ArgAction::SetTrue)
+                .requires("digest")
+                .help("Output the digest as lowercase hex instead of Base64"),
+        )
+        .arg(
+            Arg::new("key")
+                .long("key")
+                .value_name("KEY")
+                .value_parser(NonEmptyStringValueParser::new())
+                .requires("hmac")
+                .help("HMAC key (visible in the shell history; prefer --key-env or --key-keyring)"),
+        )
+        .arg(
+            Arg::new("key-env")
+                .long("key-env")
+                .value_name("VAR")
+                .value_parser(NonEmptyStringValueParser::new())
+                .requires("hmac")
+                .help("Read the HMAC key from the environment variable VAR"),
+        )
+        .arg(
+            Arg::new("key-keyring")
+                .long("key-keyring")
+                .value_name("ENTRY")
+                .value_parser(NonEmptyStringValueParser::new())
+                .requires("hmac")
+                .help("Read the HMAC key from the OS keyring (service 'b64', user ENTRY)"),
+        )
         .arg(
             Arg::new("wrap")
                 .short('w')
@@ -77,7 +130,9 @@ pub fn get_cli_arguments() -> B64Config {
                 .conflicts_with_all(["text", "file"])
                 .help("Auto-detect INPUT as file (if it exists) or literal text"),
         )
-        .get_matches();
+        .group(ArgGroup::new("digest").args(["hash", "hmac"]))
+        .group(ArgGroup::new("key-source").args(["key", "key-env", "key-keyring"]))
+        .get_matches_from(argv);
 
     B64Config::from_matches(&matches)
 }
@@ -0,0 +1,365 @@
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use serde_json::Value;
+use std::io::Read;
+
+/// Layers decoded before giving up, so a payload can't send the inspector down a rabbit hole.
+const MAX_LAYERS: usize = 16;
+
+/// Shortest text tried as a nested Base64 layer. Shorter words (e.g.: `test`, `cafe`) are
+/// valid Base64 too, but hardly ever meant to be decoded.
+const MIN_NESTED_BASE64_LEN: usize = 16;
+
+/// Bytes of a binary layer shown in the hex preview.
+const BINARY_PREVIEW_LEN: usize = 64;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// JWT claims holding a timestamp (seconds since the epoch).
+const TIME_CLAIMS: [&str; 4] = ["exp", "nbf", "iat", "auth_time"];
+
+const LENIENT: GeneralPurposeConfig =
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+const STANDARD_LENIENT: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, LENIENT);
+const URL_SAFE_LENIENT: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, LENIENT);
+
+/// A layer of the inspected payload, from the outermost to the innermost.
+#[derive(Debug)]
+pub enum Layer {
+    /// Base64, decoded into the next layer.
+    Base64 {
+        variant: &'static str,
+        len: usize,
+    },
+    /// Gzip, decompressed into the next layer.
+    Gzip {
+        len: usize,
+    },
+    Jwt(Jwt),
+    Json(Value),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// A JSON Web Token (JWS compact serialization). The signature is not verified.
+#[derive(Debug)]
+pub struct Jwt {
+    pub header: Value,
+    pub payload: Value,
+    pub signature_len: usize,
+}
+
+/// Decodes the input layer by layer: Base64 (standard or URL-safe, padded or not), gzip, JSON,
+/// and JWTs, until it reaches text, binary data, or JSON.
+///
+/// # Errors
+/// Returns error if the input is neither Base64 nor a JWT.
+pub fn inspect(input: &[u8]) -> Result<Vec<Layer>, String> {
+    let text = std::str::from_utf8(input)
+        .map(str::trim)
+        .map_err(|_| "inspect error: the input is not Base64 text".to_string())?;
+
+    if let Some(jwt) = parse_jwt(text) {
+        return Ok(vec![Layer::Jwt(jwt)]);
+    }
+
+    let (variant, mut data) = decode_base64(text)
+        .ok_or_else(|| "inspect error: the input is neither Base64 nor a JWT".to_string())?;
+
+    let mut layers = vec![Layer::Base64 {
+        variant,
+        len: data.len(),
+    }];
+
+    while layers.len() < MAX_LAYERS {
+        if data.starts_with(&GZIP_MAGIC) {
+            let mut decompressed = Vec::new();
+            if GzDecoder::new(data.as_slice())
+                .read_to_end(&mut decompressed)
+                .is_ok()
+            {
+                layers.push(Layer::Gzip {
+                    len: decompressed.len(),
+                });
+                data = decompressed;
+                continue;
+            }
+        }
+
+        let Some(text) = std::str::from_utf8(&data)
+            .ok()
+            .filter(|text| is_printable(text))
+        else {
+            break;
+        };
+        let trimmed = text.trim();
+
+        if let Some(jwt) = parse_jwt(trimmed) {
+            layers.push(Layer::Jwt(jwt));
+            return Ok(layers);
+        }
+
+        if let Some(json) = parse_json(trimmed) {
+            layers.push(Layer::Json(json));
+            return Ok(layers);
+        }
+
+        if let Some((variant, decoded)) = decode_nested_base64(trimmed) {
+            layers.push(Layer::Base64 {
+                variant,
+                len: decoded.len(),
+            });
+            data = decoded;
+            continue;
+        }
+
+        layers.push(Layer::Text(text.to_string()));
+        return Ok(layers);
+    }
+
+    layers.push(Layer::Binary(data));
+    Ok(layers)
+}
+
+/// Writes the layers as labeled sections, numbered from the outermost.
+pub fn render(layers: &[Layer], now: DateTime<Utc>) -> String {
+    let mut output = String::new();
+
+    for (index, layer) in layers.iter().enumerate() {
+        let number = index + 1;
+        match layer {
+            Layer::Base64 { variant, len } => {
+                output.push_str(&format!(
+                    "== [{}] Base64 ({}): {} bytes decoded ==\n",
+                    number, variant, len
+                ));
+            }
+            Layer::Gzip { len } => {
+                output.push_str(&format!(
+                    "== [{}] Gzip: {} bytes decompressed ==\n",
+                    number, len
+                ));
+            }
+            Layer::Jwt(jwt) => {
+                output.push_str(&format!("== [{}] JWT ==\n", number));
+                render_jwt(&mut output, jwt, now);
+            }
+            Layer::Json(json) => {
+                output.push_str(&format!("== [{}] JSON ==\n", number));
+                output.push_str(&pretty(json));
+                output.push('\n');
+            }
+            Layer::Text(text) => {
+                output.push_str(&format!("== [{}] Text ==\n", number));
+                output.push_str(text.trim_end());
+                output.push('\n');
+            }
+            Layer::Binary(data) => {
+                output.push_str(&format!(
+                    "== [{}] Binary: {} bytes ==\n",
+                    number,
+                    data.len()
+                ));
+                render_hex_preview(&mut output, data);
+            }
+        }
+    }
+
+    output
+}
+
+fn render_jwt(output: &mut String, jwt: &Jwt, now: DateTime<Utc>) {
+    output.push_str("-- Header --\n");
+    output.push_str(&pretty(&jwt.header));
+    output.push_str("\n-- Payload --\n");
+    output.push_str(&pretty(&jwt.payload));
+    output.push('\n');
+
+    let claims: Vec<String> = TIME_CLAIMS
+        .iter()
+        .filter_map(|claim| {
+            let seconds = jwt.payload.get(claim)?.as_i64()?;
+            let time = DateTime::from_timestamp(seconds, 0)?;
+            let status = match *claim {
+                "exp" if time <= now => " (expired)",
+                "exp" => " (not expired)",
+                "nbf" if time > now => " (not valid yet)",
+                _ => "",
+            };
+            Some(format!(
+                "{}: {}{}\n",
+                claim,
+                time.format("%Y-%m-%d %H:%M:%S UTC"),
+                status
+            ))
+        })
+        .collect();
+
+    if !claims.is_empty() {
+        output.push_str("-- Claims --\n");
+        output.push_str(&claims.concat());
+    }
+
+    output.push_str(&format!(
+        "-- Signature --\n{} bytes (not verified)\n",
+        jwt.signature_len
+    ));
+}
+
+fn render_hex_preview(output: &mut String, data: &[u8]) {
+    for row in data[..data.len().min(BINARY_PREVIEW_LEN)].chunks(16) {
+        let hex: Vec<String> = row.iter().map(|byte| format!("{:02x}", byte)).collect();
+        output.push_str(&hex.join(" "));
+        output.push('\n');
+    }
+
+    if data.len() > BINARY_PREVIEW_LEN {
+        output.push_str(&format!(
+            "... ({} more bytes)\n",
+            data.len() - BINARY_PREVIEW_LEN
+        ));
+    }
+}
+
+fn pretty(json: &Value) -> String {
+    serde_json::to_string_pretty(json).unwrap_or_else(|_| json.to_string())
+}
+
+/// Only objects and arrays: numbers, strings, and literals like `true` are left as text.
+fn parse_json(text: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(text)
+        .ok()
+        .filter(|json| json.is_object() || json.is_array())
+}
+
+/// A JWT has three Base64url parts separated by dots, and its header is a JSON object with an
+/// `alg`. The signature is empty for unsecured tokens (`alg: none`).
+fn parse_jwt(text: &str) -> Option<Jwt> {
+    let mut parts = text.split('.');
+    let (header, payload, signature) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let header = parse_json(&String::from_utf8(URL_SAFE_LENIENT.decode(header).ok()?).ok()?)?;
+    header.get("alg")?;
+
+    let payload = URL_SAFE_LENIENT.decode(payload).ok()?;
+    let payload = serde_json::from_slice(&payload)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&payload).into_owned()));
+
+    let signature_len = URL_SAFE_LENIENT.decode(signature).ok()?.len();
+
+    Some(Jwt {
+        header,
+        payload,
+        signature_len,
+    })
+}
+
+/// Decodes Base64 text, wrapped or not. `-` or `_` in the text means the URL-safe alphabet.
+fn decode_base64(text: &str) -> Option<(&'static str, Vec<u8>)> {
+    let compact: String = text.split_ascii_whitespace().collect();
+    if compact.is_empty() {
+        return None;
+    }
+
+    if compact.contains(['-', '_']) {
+        URL_SAFE_LENIENT
+            .decode(&compact)
+            .ok()
+            .map(|data| ("URL-safe", data))
+    } else {
+        STANDARD_LENIENT
+            .decode(&compact)
+            .ok()
+            .map(|data| ("standard", data))
+    }
+}
+
+/// Text decoded as a nested layer only when the result looks like something: gzip, or text
+/// without control characters. Otherwise, plain words would be "decoded" into noise.
+fn decode_nested_base64(text: &str) -> Option<(&'static str, Vec<u8>)> {
+    if text.len() < MIN_NESTED_BASE64_LEN {
+        return None;
+    }
+
+    let (variant, decoded) = decode_base64(text)?;
+    let meaningful = decoded.starts_with(&GZIP_MAGIC)
+        || std::str::from_utf8(&decoded)
+            .is_ok_and(|decoded| !decoded.trim().is_empty() && is_printable(decoded));
+
+    meaningful.then_some((variant, decoded))
+}
+
+/// UTF-8 without control characters, except for whitespace (e.g.: line breaks and tabs).
+fn is_printable(text: &str) -> bool {
+    text.chars()
+        .all(|c| !c.is_control() || c.is_ascii_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn jwt(header: &str, payload: &str, signature: &[u8]) -> String {
+        format!(
+            "{}.{}.{}",
+            URL_SAFE_NO_PAD.encode(header),
+            URL_SAFE_NO_PAD.encode(payload),
+            URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    #[test]
+    fn inspect_jwt_shows_sections_and_claims() {
+        let token = jwt(
+            r#"{"alg":"HS256","typ":"JWT"}"#,
+            r#"{"sub":"raccoon","exp":1700000000,"iat":1690000000}"#,
+            &[7u8; 32],
+        );
+        let now = DateTime::from_timestamp(1_750_000_000, 0).unwrap();
+
+        let report = render(&inspect(token.as_bytes()).unwrap(), now);
+
+        assert!(report.starts_with("== [1] JWT ==\n-- Header --\n"));
+        assert!(report.contains("\"sub\": \"raccoon\""));
+        assert!(report.contains("exp: 2023-11-14 22:13:20 UTC (expired)\n"));
+        assert!(report.contains("iat: 2023-07-22 04:26:40 UTC\n"));
+        assert!(report.ends_with("-- Signature --\n32 bytes (not verified)\n"));
+    }
+
+    #[test]
+    fn inspect_nested_layers_until_json() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(br#"{"event":"ping","ids":[1,2]}"#).unwrap();
+        let inner = STANDARD.encode(gzip.finish().unwrap());
+        let outer = STANDARD.encode(&inner);
+
+        let layers = inspect(outer.as_bytes()).unwrap();
+
+        assert!(
+            matches!(layers[0], Layer::Base64 { variant: "standard", len } if len == inner.len())
+        );
+        assert!(matches!(layers[1], Layer::Base64 { .. }));
+        assert!(matches!(layers[2], Layer::Gzip { len: 28 }));
+        assert!(matches!(layers[3], Layer::Json(ref json) if json["event"] == "ping"));
+        assert_eq!(layers.len(), 4);
+
+        let text = inspect(b"aGVsbG8gd29ybGQ=").unwrap();
+        assert!(matches!(text[1], Layer::Text(ref text) if text == "hello world"));
+
+        let binary = render(&inspect(b"AAECAw==").unwrap(), Utc::now());
+        assert!(binary.ends_with("== [2] Binary: 4 bytes ==\n00 01 02 03\n"));
+
+        assert!(inspect(b"not base64!").is_err());
+    }
+}
@@ -0,0 +1,38 @@
+mod b64_app;
+mod cli_utils;
+mod inspector;
+mod models;
+
+use crate::cli_utils::get_cli_arguments;
+use shared::constants::general::EXIT_CODE_SUCCESS;
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+/// Base64 encoder and decoder.
+///
+/// Parses arguments, then encodes, decodes, hashes, or inspects the input.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    let config = get_cli_arguments(argv.into_iter().map(Into::into).collect());
+
+    match b64_app::run(&config) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(app_error) => {
+            if !app_error.message.is_empty() {
+                eprintln!("{}: {}", env!("CARGO_PKG_NAME"), app_error.message);
+                error!("{}", app_error.message);
+            }
+            app_error.exit_code
+        }
+    }
+}
@@ -4,11 +4,39 @@ use std::io;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
-/// Indicates whether the tool should encode or decode.
+/// Environment variable used for the HMAC key when no other key source is informed.
+pub const DEFAULT_HMAC_KEY_ENV: &str = "B64_HMAC_KEY";
+
+/// Keyring service under which HMAC keys are looked up.
+pub const KEYRING_SERVICE: &str = "b64";
+
+/// Indicates whether the tool should encode, decode, or compute a digest of the input.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum B64Mode {
     Encode,
     Decode,
+    /// SHA-256 of the input.
+    Hash,
+    /// HMAC-SHA256 of the input.
+    Hmac,
+    /// Decodes the input layer by layer (Base64, gzip, JSON, JWT), describing each one.
+    Inspect,
+}
+
+/// How a digest is written.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DigestFormat {
+    Base64,
+    Hex,
+}
+
+/// Where the HMAC key comes from. Resolved only when the digest is computed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum KeySource {
+    Text(String),
+    Env(String),
+    /// Entry (user) in the OS keyring, under the `b64` service.
+    Keyring(String),
 }
 
 /// Source of the input data.
@@ -34,13 +62,21 @@ pub struct B64Config {
     pub ignore_garbage: bool,
     pub input: InputSource,
     pub output: OutputTarget,
+    pub digest_format: DigestFormat,
+    pub key: Option<KeySource>,
 }
 
 impl B64Config {
     /// Creates the runtime configuration from clap matches.
     pub fn from_matches(matches: &ArgMatches) -> Self {
-        let mode = if matches.get_flag("decode") {
+        let mode = if matches.get_flag("inspect") {
+            B64Mode::Inspect
+        } else if matches.get_flag("decode") {
             B64Mode::Decode
+        } else if matches.get_flag("hmac") {
+            B64Mode::Hmac
+        } else if matches.get_flag("hash") {
+            B64Mode::Hash
         } else {
             B64Mode::Encode
         };
@@ -62,16 +98,42 @@ impl B64Config {
             .map(|value| value.as_str())
             .map_or(OutputTarget::Stdout, parse_output_target);
 
+        let digest_format = if matches.get_flag("hex") {
+            DigestFormat::Hex
+        } else {
+            DigestFormat::Base64
+        };
+
+        let key = (mode == B64Mode::Hmac).then(|| determine_key_source(matches));
+
         B64Config {
             mode,
             wrap_columns,
             ignore_garbage,
             input,
             output,
+            digest_format,
+            key,
         }
     }
 }
 
+fn determine_key_source(matches: &ArgMatches) -> KeySource {
+    if let Some(key) = matches.get_one::<String>("key") {
+        return KeySource::Text(key.to_string());
+    }
+
+    if let Some(entry) = matches.get_one::<String>("key-keyring") {
+        return KeySource::Keyring(entry.to_string());
+    }
+
+    let variable = matches
+        .get_one::<String>("key-env")
+        .map_or(DEFAULT_HMAC_KEY_ENV, |value| value.as_str());
+
+    KeySource::Env(variable.to_string())
+}
+
 fn parse_output_target(path: &str) -> OutputTarget {
     if path == "-" {
         OutputTarget::Stdout
@@ -136,6 +198,29 @@ mod tests {
                     .long("decode")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(
+                clap::Arg::new("inspect")
+                    .long("inspect")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                clap::Arg::new("hash")
+                    .long("hash")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                clap::Arg::new("hmac")
+                    .long("hmac")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                clap::Arg::new("hex")
+                    .long("hex")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(clap::Arg::new("key").long("key"))
+            .arg(clap::Arg::new("key-env").long("key-env"))
+            .arg(clap::Arg::new("key-keyring").long("key-keyring"))
             .arg(
                 clap::Arg::new("wrap")
                     .short('w')
@@ -219,4 +304,31 @@ mod tests {
             InputSource::File(ref path) if path == &PathBuf::from("Cargo.toml")
         ));
     }
+
+    #[test]
+    fn hmac_key_defaults_to_env_variable() {
+        let matches = matches_from(&["b64", "--hmac", "--text", "payload"]);
+        let config = B64Config::from_matches(&matches);
+        assert_eq!(config.mode, B64Mode::Hmac);
+        assert_eq!(
+            config.key,
+            Some(KeySource::Env(DEFAULT_HMAC_KEY_ENV.to_string()))
+        );
+    }
+
+    #[test]
+    fn hash_has_no_key_and_honors_hex() {
+        let matches = matches_from(&["b64", "--hash", "--hex", "--text", "payload"]);
+        let config = B64Config::from_matches(&matches);
+        assert_eq!(config.mode, B64Mode::Hash);
+        assert_eq!(config.digest_format, DigestFormat::Hex);
+        assert_eq!(config.key, None);
+    }
+
+    #[test]
+    fn inspect_takes_priority_over_decode() {
+        let matches = matches_from(&["b64", "-d", "--inspect", "--text", "payload"]);
+        let config = B64Config::from_matches(&matches);
+        assert_eq!(config.mode, B64Mode::Inspect);
+    }
 }
@@ -1,6 +1,7 @@
 use crate::models::CatArgs;
 use clap::{Arg, Command};
 use shared::command_line::cli_builder::CommandExt;
+use std::ffi::OsString;
 
 /// Parses command-line arguments for cat application.
 ///
@@ -35,7 +36,7 @@ use shared::command_line::cli_builder::CommandExt;
 ///
 /// # Notes
 /// If no files are provided, the program will default to reading from `stdin`.
-pub fn get_cli_arguments() -> CatArgs {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> CatArgs {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -117,7 +118,7 @@ pub fn get_cli_arguments() -> CatArgs {
                 .action(clap::ArgAction::Append)
                 .num_args(0..),
         )
-        .get_matches();
+        .get_matches_from(argv);
 
     CatArgs::parse(&matches)
 }
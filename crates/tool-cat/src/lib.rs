@@ -0,0 +1,55 @@
+use crate::cat_app::cat_file;
+use crate::cli_utils::get_cli_arguments;
+use crate::models::CatOptions;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use std::io;
+use std::io::Write;
+use tracing::error;
+
+mod cat_app;
+mod cli_utils;
+mod models;
+
+/// Port of the Unix `cat` command.
+///
+/// Prints the files informed (or stdin, without them) to stdout.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    let args = get_cli_arguments(argv.into_iter().map(Into::into).collect());
+
+    let options = CatOptions::from_args(&args);
+
+    // Handle -u flag by setting stdout to unbuffered
+    if args.u_flag {
+        io::stdout().flush().unwrap_or(());
+    }
+
+    if args.files.is_empty() {
+        // No files specified, read from stdin
+        if let Err(e) = cat_file(None, &options) {
+            error!("Failed to run CAT from stdin: {}", e);
+            return EXIT_CODE_ERROR;
+        }
+    } else {
+        // Process each file
+        for filename in &args.files {
+            if let Err(e) = cat_file(Some(filename), &options) {
+                error!("Failed to run CAT from file [{}]: {}", filename, e);
+                return EXIT_CODE_ERROR;
+            }
+        }
+    }
+
+    EXIT_CODE_SUCCESS
+}
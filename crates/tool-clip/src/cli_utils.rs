@@ -0,0 +1,177 @@
+use crate::clip_history::default_history_path;
+use crate::models::{ClipArgs, ClipCommand};
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{GlobalArgs, ToolCli};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+impl ToolCli for ClipArgs {
+    const DISPLAY_NAME: &'static str = "Clip";
+
+    fn command() -> Command {
+        let no_history = || {
+            Arg::new("no-history")
+                .long("no-history")
+                .action(ArgAction::SetTrue)
+                .help("Doesn't add the text to the history (e.g.: for passwords). (Default: false)")
+        };
+
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Copies text to the clipboard and pastes it, keeping a history of the last 20 texts \
+                copied or pasted, so older ones can be printed (clip get 3) or copied again. \
+                Without a command, copies stdin when it's piped, and pastes otherwise.",
+            )
+            .arg(
+                Arg::new("history")
+                    .long("history")
+                    .value_name("FILE")
+                    .global(true)
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("History file. (Default: history.json in the rusted-toolbox/clip data folder)"),
+            )
+            .subcommand(
+                Command::new("copy")
+                    .about("Copies the text, or stdin, to the clipboard")
+                    .arg(
+                        Arg::new("text")
+                            .value_name("TEXT")
+                            .help("Text to copy. (Default: stdin, without its last line break)"),
+                    )
+                    .arg(no_history()),
+            )
+            .subcommand(
+                Command::new("paste")
+                    .about("Prints the clipboard")
+                    .arg(no_history()),
+            )
+            .subcommand(
+                Command::new("get")
+                    .about("Prints an entry of the history")
+                    .arg(
+                        Arg::new("index")
+                            .value_name("N")
+                            .required(true)
+                            .value_parser(clap::value_parser!(u64).range(1..))
+                            .help("Entry to print: 1 is the most recent"),
+                    )
+                    .arg(
+                        Arg::new("copy")
+                            .long("copy")
+                            .short('c')
+                            .action(ArgAction::SetTrue)
+                            .help("Also copies the entry to the clipboard, making it the most recent. (Default: false)"),
+                    ),
+            )
+            .subcommand(
+                Command::new("history")
+                    .visible_alias("ls")
+                    .about("Lists the history, most recent first"),
+            )
+            .subcommand(Command::new("clear").about("Removes every entry of the history"))
+    }
+
+    fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+        let no_history = |sub_matches: &ArgMatches| sub_matches.get_flag("no-history");
+
+        let command = match matches.subcommand() {
+            Some(("copy", sub_matches)) => ClipCommand::Copy {
+                text: sub_matches.get_one::<String>("text").cloned(),
+                no_history: no_history(sub_matches),
+            },
+            Some(("paste", sub_matches)) => ClipCommand::Paste {
+                no_history: no_history(sub_matches),
+            },
+            Some(("get", sub_matches)) => ClipCommand::Get {
+                index: sub_matches.get_one::<u64>("index").copied().unwrap_or(1) as usize,
+                copy: sub_matches.get_flag("copy"),
+            },
+            Some(("history", _)) => ClipCommand::History,
+            Some(("clear", _)) => ClipCommand::Clear,
+            _ if std::io::stdin().is_terminal() => ClipCommand::Paste { no_history: false },
+            _ => ClipCommand::Copy {
+                text: None,
+                no_history: false,
+            },
+        };
+
+        let history = match matches.get_one::<PathBuf>("history") {
+            Some(history) => history.clone(),
+            None => default_history_path()?,
+        };
+
+        Ok(ClipArgs { history, command })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.history.is_dir() {
+            bail!("History '{}' is a directory", self.history.display());
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let command = match &self.command {
+            ClipCommand::Copy { .. } => "copy".to_string(),
+            ClipCommand::Paste { .. } => "paste".to_string(),
+            ClipCommand::Get { index, .. } => format!("get {}", index),
+            ClipCommand::History => "history".to_string(),
+            ClipCommand::Clear => "clear".to_string(),
+        };
+
+        vec![
+            ("History".to_string(), self.history.display().to_string()),
+            ("Command".to_string(), command),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    fn parse(argv: &[&str]) -> Result<ClipArgs> {
+        try_parse_tool_cli_from::<ClipArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let args = parse(&["clip", "get", "3", "-c", "--history", "clips.json"]).unwrap();
+        assert_eq!(
+            args,
+            ClipArgs {
+                history: PathBuf::from("clips.json"),
+                command: ClipCommand::Get {
+                    index: 3,
+                    copy: true
+                },
+            }
+        );
+
+        let args = parse(&[
+            "clip",
+            "--history",
+            "clips.json",
+            "copy",
+            "--no-history",
+            "pw",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            ClipCommand::Copy {
+                text: Some("pw".to_string()),
+                no_history: true
+            }
+        );
+
+        assert!(parse(&["clip", "get", "0", "--history", "clips.json"]).is_err());
+        assert!(parse(&["clip", "get", "--history", "clips.json"]).is_err());
+    }
+}
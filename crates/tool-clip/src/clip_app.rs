@@ -0,0 +1,146 @@
+use crate::clip_history::ClipHistory;
+use crate::models::{ClipArgs, ClipCommand};
+use anyhow::{anyhow, bail, Context, Result};
+use shared::utils::copy_string_to_clipboard::copy_to_clipboard;
+use shared::utils::read_string_from_clipboard::read_from_clipboard;
+use shared::utils::table_output::{ReportFormat, Table};
+use std::io::{self, IsTerminal, Read, Write};
+
+/// Longest text shown for each entry by `history`.
+const PREVIEW_LENGTH: usize = 60;
+
+pub fn run(args: &ClipArgs) -> Result<()> {
+    match &args.command {
+        ClipCommand::Copy { text, no_history } => {
+            let text = match text {
+                Some(text) => text.clone(),
+                None => read_stdin()?,
+            };
+            copy(&text)?;
+            remember(args, &text, *no_history)
+        }
+        ClipCommand::Paste { no_history } => {
+            let text = read_from_clipboard()
+                .map_err(|e| anyhow!("Failed to read the clipboard: {}", e))?;
+            print_text(&text)?;
+            remember(args, &text, *no_history)
+        }
+        ClipCommand::Get { index, copy: again } => {
+            let text = ClipHistory::load(&args.history)?.get(*index)?.text.clone();
+            print_text(&text)?;
+            if *again {
+                copy(&text)?;
+                remember(args, &text, false)?;
+            }
+            Ok(())
+        }
+        ClipCommand::History => {
+            print_history(&ClipHistory::load(&args.history)?);
+            Ok(())
+        }
+        ClipCommand::Clear => {
+            let mut history = ClipHistory::load(&args.history)?;
+            let removed = history.clear();
+            history.save()?;
+            println!("Removed {} entry(ies) from the history.", removed);
+            Ok(())
+        }
+    }
+}
+
+/// Reads stdin, without the last line break: `echo text | clip` copies `text`.
+fn read_stdin() -> Result<String> {
+    if io::stdin().is_terminal() {
+        bail!("Nothing to copy: pass the text, or pipe it to clip");
+    }
+
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .context("Failed to read stdin")?;
+
+    let trimmed = text
+        .strip_suffix('\n')
+        .map(|text| text.strip_suffix('\r').unwrap_or(text))
+        .unwrap_or(&text);
+
+    Ok(trimmed.to_string())
+}
+
+fn copy(text: &str) -> Result<()> {
+    if text.is_empty() {
+        bail!("Nothing to copy: the text is empty");
+    }
+
+    copy_to_clipboard(text).map_err(|e| anyhow!("Failed to copy to the clipboard: {}", e))
+}
+
+fn remember(args: &ClipArgs, text: &str, no_history: bool) -> Result<()> {
+    if no_history {
+        return Ok(());
+    }
+
+    let mut history = ClipHistory::load(&args.history)?;
+    history.push(text);
+    history.save()
+}
+
+/// Prints the text as-is, adding a line break only on terminals, so the prompt starts on its own
+/// line, while pipes get exactly what was copied.
+fn print_text(text: &str) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(text.as_bytes())?;
+    if stdout.is_terminal() && !text.ends_with('\n') {
+        writeln!(stdout)?;
+    }
+
+    stdout.flush()?;
+    Ok(())
+}
+
+fn print_history(history: &ClipHistory) {
+    if history.entries().is_empty() {
+        println!("The history is empty.");
+        return;
+    }
+
+    let mut table = Table::new(&["#", "COPIED AT", "TEXT"]).align_right(0);
+    for (index, entry) in history.entries().iter().enumerate() {
+        table.add_row(vec![
+            (index + 1).to_string(),
+            entry.copied_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            preview(&entry.text),
+        ]);
+    }
+
+    print!("{}", table.render(ReportFormat::Table));
+}
+
+/// The text in a single line (whitespace collapsed), cut at `PREVIEW_LENGTH` characters.
+fn preview(text: &str) -> String {
+    let single_line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if single_line.chars().count() <= PREVIEW_LENGTH {
+        return single_line;
+    }
+
+    let cut: String = single_line.chars().take(PREVIEW_LENGTH - 3).collect();
+    format!("{}...", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(
+            preview("  SELECT *\n  FROM users\t;\n"),
+            "SELECT * FROM users ;"
+        );
+
+        let long = "é".repeat(PREVIEW_LENGTH + 1);
+        let cut = preview(&long);
+        assert_eq!(cut.chars().count(), PREVIEW_LENGTH);
+        assert!(cut.ends_with("..."));
+    }
+}
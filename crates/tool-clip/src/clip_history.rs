@@ -0,0 +1,158 @@
+use crate::models::HistoryEntry;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Local;
+use shared::system::ensure_directory_exists::EnsureDirectoryExists;
+use shared::system::load_global_config::user_data_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How many entries the history keeps. Older ones are dropped.
+pub const HISTORY_SIZE: usize = 20;
+
+const HISTORY_FILE_NAME: &str = "history.json";
+
+/// `history.json`, in the data folder of the tool.
+pub fn default_history_path() -> Result<PathBuf> {
+    user_data_dir(env!("CARGO_PKG_NAME"))
+        .map(|dir| dir.join(HISTORY_FILE_NAME))
+        .ok_or_else(|| {
+            anyhow!(
+                "Failed to find the user data directory. Use --history to set the history file."
+            )
+        })
+}
+
+/// The text copied (and pasted) with the tool, most recent first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipHistory {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+}
+
+impl ClipHistory {
+    /// Reads the history. A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        let entries = if path.is_file() {
+            let content = fs::read_to_string(path)
+                .context(format!("Failed to read the history {}", path.display()))?;
+            serde_json::from_str(&content)
+                .context(format!("Invalid history file: {}", path.display()))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Adds the text as the most recent entry. Text already in the history is moved to the top,
+    /// instead of being added twice. Blank text isn't kept.
+    pub fn push(&mut self, text: &str) {
+        if text.trim().is_empty() {
+            return;
+        }
+
+        self.entries.retain(|entry| entry.text != text);
+        self.entries.insert(
+            0,
+            HistoryEntry {
+                text: text.to_string(),
+                copied_at: Local::now(),
+            },
+        );
+        self.entries.truncate(HISTORY_SIZE);
+    }
+
+    /// The entry at `index`, starting at 1 for the most recent.
+    pub fn get(&self, index: usize) -> Result<&HistoryEntry> {
+        if self.entries.is_empty() {
+            bail!("The history is empty");
+        }
+
+        index
+            .checked_sub(1)
+            .and_then(|position| self.entries.get(position))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No entry {} in the history: it has {} (1 is the most recent)",
+                    index,
+                    self.entries.len()
+                )
+            })
+    }
+
+    /// Removes every entry, and returns how many there were.
+    pub fn clear(&mut self) -> usize {
+        let count = self.entries.len();
+        self.entries.clear();
+        count
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.path
+            .ensure_parent_exists()
+            .context("Failed to create the history directory")?;
+
+        let content =
+            serde_json::to_string_pretty(&self.entries).context("Failed to serialize history")?;
+
+        // Written to a temporary file first, so a crash mid-write doesn't lose the history.
+        let temp_path = self.path.with_extension("tmp");
+        fs::write(&temp_path, content).context(format!(
+            "Failed to write the history to {}",
+            temp_path.display()
+        ))?;
+        fs::rename(&temp_path, &self.path).context(format!(
+            "Failed to write the history to {}",
+            self.path.display()
+        ))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_push_moves_duplicates_and_keeps_the_size() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("clip").join(HISTORY_FILE_NAME);
+        let mut history = ClipHistory::load(&path).unwrap();
+
+        for index in 0..HISTORY_SIZE + 5 {
+            history.push(&format!("text {}", index));
+        }
+        history.push("text 22");
+        history.push("  \n");
+        history.save().unwrap();
+
+        let history = ClipHistory::load(&path).unwrap();
+        assert_eq!(history.entries().len(), HISTORY_SIZE);
+        assert_eq!(history.get(1).unwrap().text, "text 22");
+        assert_eq!(history.get(2).unwrap().text, "text 24");
+        assert_eq!(history.get(3).unwrap().text, "text 23");
+        assert_eq!(history.get(4).unwrap().text, "text 21");
+        assert!(history.get(0).is_err());
+        assert!(history.get(HISTORY_SIZE + 1).is_err());
+    }
+
+    #[test]
+    fn test_clear() {
+        let dir = tempdir().unwrap();
+        let mut history = ClipHistory::load(&dir.path().join(HISTORY_FILE_NAME)).unwrap();
+        history.push("a");
+        history.push("b");
+
+        assert_eq!(history.clear(), 2);
+        assert!(history.get(1).unwrap_err().to_string().contains("empty"));
+    }
+}
@@ -0,0 +1,45 @@
+use crate::models::{ClipArgs, ClipCommand};
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod clip_app;
+mod clip_history;
+mod models;
+
+/// Clipboard copy, paste, and history.
+///
+/// Parses arguments and runs the command: `copy`, `paste`, `get`, `history` or `clear`.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<ClipArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    // Copied and pasted text is meant to be piped, so the header would get in the way.
+    let args = &invocation.args;
+    if matches!(args.command, ClipCommand::History | ClipCommand::Clear) {
+        invocation.print_runtime_info();
+    }
+
+    if let Err(e) = clip_app::run(args) {
+        eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+        error!("{}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipArgs {
+    /// File with the history of copied text.
+    pub history: PathBuf,
+    pub command: ClipCommand,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipCommand {
+    /// Copies `text` (stdin, when `None`) to the clipboard.
+    Copy {
+        text: Option<String>,
+        no_history: bool,
+    },
+    /// Prints the clipboard.
+    Paste { no_history: bool },
+    /// Prints the entry of the history at `index` (1 is the most recent) and, with `copy`, puts
+    /// it back in the clipboard.
+    Get { index: usize, copy: bool },
+    /// Lists the history, most recent first.
+    History,
+    /// Removes every entry of the history.
+    Clear,
+}
+
+/// Text that went through the clipboard, and when.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub text: String,
+    pub copied_at: DateTime<Local>,
+}
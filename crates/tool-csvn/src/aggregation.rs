@@ -0,0 +1,499 @@
+use anyhow::{anyhow, bail, Context, Result};
+use csv::{StringRecord, Writer, WriterBuilder};
+use shared::system::mmap_csv_reader::{CsvReaderOptions, CsvRecordReader};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Aggregation functions accepted in `--agg`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AggFunction {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl AggFunction {
+    fn name(&self) -> &'static str {
+        match self {
+            AggFunction::Count => "count",
+            AggFunction::Sum => "sum",
+            AggFunction::Min => "min",
+            AggFunction::Max => "max",
+            AggFunction::Avg => "avg",
+        }
+    }
+}
+
+/// One aggregation of `--agg`, e.g.: `sum(amount)`. Only `count()` has no column (counts the rows).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregation {
+    pub function: AggFunction,
+    pub column: Option<String>,
+}
+
+impl Aggregation {
+    /// Header of the aggregation in the output file.
+    pub fn label(&self) -> String {
+        format!(
+            "{}({})",
+            self.function.name(),
+            self.column.as_deref().unwrap_or("")
+        )
+    }
+}
+
+/// `--group-by` and `--agg` options.
+pub struct AggregationConfig {
+    pub group_by: Vec<String>,
+    pub aggregations: Vec<Aggregation>,
+    /// Groups kept in memory. Past that, the groups are spilled to a sorted file on disk.
+    pub max_groups: usize,
+}
+
+/// Parses a list of aggregations, like `sum(amount),count(),max(price)`.
+///
+/// # Errors
+/// Returns error if a function is unknown, or it is missing its column.
+pub fn parse_aggregations(spec: &str) -> Result<Vec<Aggregation>> {
+    let mut aggregations = Vec::new();
+
+    for raw in spec.split(',').map(str::trim).filter(|raw| !raw.is_empty()) {
+        let (name, column) = raw
+            .strip_suffix(')')
+            .and_then(|raw| raw.split_once('('))
+            .ok_or_else(|| anyhow!("Invalid aggregation [{}]. Expected: function(column)", raw))?;
+
+        let function = match name.trim().to_lowercase().as_str() {
+            "count" => AggFunction::Count,
+            "sum" => AggFunction::Sum,
+            "min" => AggFunction::Min,
+            "max" => AggFunction::Max,
+            "avg" => AggFunction::Avg,
+            other => bail!(
+                "Unknown aggregation function [{}]. Use: count, sum, min, max, or avg",
+                other
+            ),
+        };
+
+        let column = Some(column.trim().to_string()).filter(|column| !column.is_empty());
+        if column.is_none() && function != AggFunction::Count {
+            bail!("The aggregation [{}] needs a column", raw);
+        }
+
+        aggregations.push(Aggregation { function, column });
+    }
+
+    if aggregations.is_empty() {
+        bail!("At least one aggregation is required (e.g.: count())");
+    }
+
+    Ok(aggregations)
+}
+
+/// Running state of one aggregation in one group. Every function is computed from these, so
+/// the states of the same group can be merged (when reading back the spilled groups).
+#[derive(Debug, Clone, PartialEq)]
+struct AggState {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for AggState {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl AggState {
+    fn add_number(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    fn merge(&mut self, other: &AggState) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Value written to the output. Min, max and avg are empty when no value was a number.
+    fn result(&self, function: AggFunction) -> String {
+        match function {
+            AggFunction::Count => self.count.to_string(),
+            AggFunction::Sum => self.sum.to_string(),
+            _ if self.count == 0 => String::new(),
+            AggFunction::Min => self.min.to_string(),
+            AggFunction::Max => self.max.to_string(),
+            AggFunction::Avg => (self.sum / self.count as f64).to_string(),
+        }
+    }
+}
+
+type Group = (Vec<String>, Vec<AggState>);
+
+/// Computes the aggregations per group in a single pass over the records.
+///
+/// Memory is bounded by `max_groups`: when there are more groups than that, they are sorted and
+/// spilled to a file (a run), and the aggregation starts over with an empty table. In the end,
+/// the runs are merged (like a merge sort), adding up the states of the groups found in more
+/// than one run. The output is sorted by the group columns either way.
+pub struct GroupAggregator {
+    group_columns: Vec<usize>,
+    /// Column of each aggregation (`None` for `count()`).
+    value_columns: Vec<Option<usize>>,
+    functions: Vec<AggFunction>,
+    groups: HashMap<Vec<String>, Vec<AggState>>,
+    max_groups: usize,
+    spill_dir: PathBuf,
+    runs: Vec<PathBuf>,
+    /// Values that should be numbers (sum, min, max, avg), but aren't. They are ignored.
+    pub skipped_values: u64,
+}
+
+impl GroupAggregator {
+    /// Finds the columns of the group and of the aggregations in the headers (case-insensitive).
+    /// Spilled runs are written in `spill_dir`, which is created if needed, and removed at the end.
+    ///
+    /// # Errors
+    /// Returns error if a column is not in the headers.
+    pub fn new(config: &AggregationConfig, headers: &[String], spill_dir: PathBuf) -> Result<Self> {
+        let find_column = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(column))
+                .ok_or_else(|| anyhow!("Column [{}] not found in the headers", column))
+        };
+
+        let group_columns = config
+            .group_by
+            .iter()
+            .map(|column| find_column(column))
+            .collect::<Result<Vec<_>>>()?;
+
+        let value_columns = config
+            .aggregations
+            .iter()
+            .map(|aggregation| aggregation.column.as_deref().map(find_column).transpose())
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            group_columns,
+            value_columns,
+            functions: config.aggregations.iter().map(|a| a.function).collect(),
+            groups: HashMap::new(),
+            max_groups: config.max_groups.max(1),
+            spill_dir,
+            runs: Vec::new(),
+            skipped_values: 0,
+        })
+    }
+
+    /// Number of runs spilled to disk so far.
+    pub fn spilled_runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Adds the record to its group.
+    ///
+    /// # Errors
+    /// Returns error if the groups had to be spilled, and the run couldn't be written.
+    pub fn add(&mut self, record: &StringRecord) -> Result<()> {
+        let key: Vec<String> = self
+            .group_columns
+            .iter()
+            .map(|index| record.get(*index).unwrap_or("").trim().to_string())
+            .collect();
+
+        if !self.groups.contains_key(&key) && self.groups.len() >= self.max_groups {
+            self.spill()?;
+        }
+
+        let states = self
+            .groups
+            .entry(key)
+            .or_insert_with(|| vec![AggState::default(); self.functions.len()]);
+
+        for ((state, function), column) in states
+            .iter_mut()
+            .zip(&self.functions)
+            .zip(&self.value_columns)
+        {
+            let value = column.and_then(|index| record.get(index)).map(str::trim);
+
+            match (function, value) {
+                (AggFunction::Count, None) => state.count += 1,
+                (_, None) | (_, Some("")) => {}
+                (AggFunction::Count, Some(_)) => state.count += 1,
+                (_, Some(value)) => match value.parse::<f64>() {
+                    Ok(number) => state.add_number(number),
+                    Err(_) => self.skipped_values += 1,
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the groups (sorted by the group columns) to `writer`, merging the spilled runs.
+    /// Returns how many groups were written.
+    ///
+    /// # Errors
+    /// Returns error if a run can't be read, or the output can't be written.
+    pub fn finish<W: Write>(mut self, writer: &mut Writer<W>) -> Result<u64> {
+        if self.runs.is_empty() {
+            let mut groups: Vec<Group> = self.groups.drain().collect();
+            groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for (key, states) in &groups {
+                self.write_group(writer, key, states)?;
+            }
+
+            return Ok(groups.len() as u64);
+        }
+
+        self.spill()?;
+
+        let options = CsvReaderOptions::default().with_headers(false);
+        let mut runs = self
+            .runs
+            .iter()
+            .map(|path| {
+                CsvRecordReader::open(path, &options)
+                    .context(format!("Failed to open spilled run: {}", path.display()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Smallest key on top. The index tells which run to read the next group from.
+        let mut heads: Vec<Option<Vec<AggState>>> = vec![None; runs.len()];
+        let mut heap = BinaryHeap::new();
+        for (index, run) in runs.iter_mut().enumerate() {
+            if let Some((key, states)) = self.read_group(run)? {
+                heads[index] = Some(states);
+                heap.push(Reverse((key, index)));
+            }
+        }
+
+        let mut written = 0;
+        while let Some(Reverse((key, index))) = heap.pop() {
+            let mut states = heads[index].take().unwrap_or_default();
+            self.advance(&mut runs, &mut heads, &mut heap, index)?;
+
+            // The same group in other runs.
+            while let Some(Reverse((next_key, next_index))) = heap.peek() {
+                if *next_key != key {
+                    break;
+                }
+
+                let next_index = *next_index;
+                heap.pop();
+                if let Some(other) = heads[next_index].take() {
+                    for (state, other) in states.iter_mut().zip(&other) {
+                        state.merge(other);
+                    }
+                }
+                self.advance(&mut runs, &mut heads, &mut heap, next_index)?;
+            }
+
+            self.write_group(writer, &key, &states)?;
+            written += 1;
+        }
+
+        Ok(written)
+    }
+
+    fn advance(
+        &self,
+        runs: &mut [CsvRecordReader],
+        heads: &mut [Option<Vec<AggState>>],
+        heap: &mut BinaryHeap<Reverse<(Vec<String>, usize)>>,
+        index: usize,
+    ) -> Result<()> {
+        if let Some((key, states)) = self.read_group(&mut runs[index])? {
+            heads[index] = Some(states);
+            heap.push(Reverse((key, index)));
+        }
+
+        Ok(())
+    }
+
+    fn write_group<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        key: &[String],
+        states: &[AggState],
+    ) -> Result<()> {
+        let mut record = StringRecord::from(key.to_vec());
+        for (state, function) in states.iter().zip(&self.functions) {
+            record.push_field(&state.result(*function));
+        }
+
+        writer
+            .write_record(&record)
+            .context("Failed to write aggregated group to output file")
+    }
+
+    /// Sorts the groups in memory and writes them to a new run: the group columns, followed by
+    /// count, sum, min, and max of each aggregation.
+    fn spill(&mut self) -> Result<()> {
+        if self.groups.is_empty() {
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(&self.spill_dir).context(format!(
+            "Failed to create spill folder: {}",
+            self.spill_dir.display()
+        ))?;
+
+        let path = self
+            .spill_dir
+            .join(format!("run-{:04}.csv", self.runs.len()));
+        let mut writer = WriterBuilder::new()
+            .has_headers(false)
+            .from_path(&path)
+            .context(format!("Failed to create spilled run: {}", path.display()))?;
+
+        let mut groups: Vec<Group> = self.groups.drain().collect();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (key, states) in groups {
+            let mut record = StringRecord::from(key);
+            for state in &states {
+                record.push_field(&state.count.to_string());
+                record.push_field(&state.sum.to_string());
+                record.push_field(&state.min.to_string());
+                record.push_field(&state.max.to_string());
+            }
+            writer.write_record(&record)?;
+        }
+
+        writer.flush()?;
+        self.runs.push(path);
+
+        Ok(())
+    }
+
+    fn read_group(&self, run: &mut CsvRecordReader) -> Result<Option<Group>> {
+        let Some(record) = run.next().transpose()? else {
+            return Ok(None);
+        };
+
+        let key_length = self.group_columns.len();
+        let key = record.iter().take(key_length).map(str::to_string).collect();
+
+        let fields: Vec<&str> = record.iter().skip(key_length).collect();
+        let states = fields
+            .chunks(4)
+            .map(|state| {
+                Ok(AggState {
+                    count: state[0].parse()?,
+                    sum: state[1].parse()?,
+                    min: state[2].parse()?,
+                    max: state[3].parse()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+            .context("Spilled run is corrupted")?;
+
+        Ok(Some((key, states)))
+    }
+}
+
+impl Drop for GroupAggregator {
+    fn drop(&mut self) {
+        if !self.runs.is_empty() {
+            let _ = std::fs::remove_dir_all(&self.spill_dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_parse_aggregations() {
+        let aggregations = parse_aggregations("sum(amount), count(), AVG( price )").unwrap();
+
+        assert_eq!(aggregations.len(), 3);
+        assert_eq!(aggregations[0].label(), "sum(amount)");
+        assert_eq!(aggregations[1].label(), "count()");
+        assert_eq!(aggregations[2].function, AggFunction::Avg);
+        assert_eq!(aggregations[2].column.as_deref(), Some("price"));
+
+        assert!(parse_aggregations("sum()").is_err());
+        assert!(parse_aggregations("median(amount)").is_err());
+        assert!(parse_aggregations("amount").is_err());
+        assert!(parse_aggregations("").is_err());
+    }
+
+    fn aggregate(max_groups: usize) -> (String, usize) {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let spill_dir = std::env::temp_dir().join(format!("csvn-spill-test-{}", nanos));
+
+        let config = AggregationConfig {
+            group_by: vec!["Region".to_string()],
+            aggregations: parse_aggregations("count(),sum(amount),max(amount),avg(amount)")
+                .unwrap(),
+            max_groups,
+        };
+        let headers = vec!["region".to_string(), "amount".to_string()];
+        let mut aggregator = GroupAggregator::new(&config, &headers, spill_dir.clone()).unwrap();
+
+        let rows = [
+            ["south", "10"],
+            ["north", "5"],
+            ["east", "n/a"],
+            ["south", "2.5"],
+            ["north", ""],
+            ["west", "1"],
+            ["south", "7.5"],
+        ];
+        for row in rows {
+            aggregator.add(&StringRecord::from(row.to_vec())).unwrap();
+        }
+
+        assert_eq!(aggregator.skipped_values, 3); // "n/a" is not a number.
+        let spilled = aggregator.spilled_runs();
+
+        let mut writer = WriterBuilder::new().has_headers(false).from_writer(vec![]);
+        assert_eq!(aggregator.finish(&mut writer).unwrap(), 4);
+        assert!(!spill_dir.exists());
+
+        (
+            String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+            spilled,
+        )
+    }
+
+    #[test]
+    fn test_aggregates_groups_in_memory_and_spilled() {
+        let expected = "east,1,0,,\nnorth,2,5,5,5\nsouth,3,20,10,6.666666666666667\nwest,1,1,1,1\n";
+
+        let (in_memory, spilled) = aggregate(100);
+        assert_eq!(spilled, 0);
+        assert_eq!(in_memory, expected);
+
+        // Two groups at a time: the groups of south end up in more than one run.
+        let (merged, spilled) = aggregate(2);
+        assert!(spilled > 1);
+        assert_eq!(merged, expected);
+    }
+}
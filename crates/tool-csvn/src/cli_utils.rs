@@ -1,10 +1,15 @@
+use crate::aggregation::{parse_aggregations, AggregationConfig};
+use crate::input_detection::{
+    delimiter_name, detect_format, parse_delimiter, CsvFormat, TextEncoding,
+};
 use crate::models::CsvNConfig;
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, Result};
 use clap::{Arg, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
 use shared::system::get_current_working_dir::get_current_working_dir;
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 /// Displays runtime configuration information.
@@ -22,12 +27,26 @@ pub fn print_runtime_info(args: &CsvNConfig) {
         println!("- Headers: Will be inferred from file.");
     }
 
+    println!("- Input format: {}", describe_format(&args.format));
+    println!("- Output format: {}", describe_format(&args.output_format));
+
     println!("- Clean string: {}", args.clean_string);
 
     println!("- Default value map: {:?}", args.default_value_map);
 
+    if let Some(aggregation) = &args.aggregation {
+        let labels: Vec<String> = aggregation.aggregations.iter().map(|a| a.label()).collect();
+        println!("- Group by: {:?}", aggregation.group_by);
+        println!("- Aggregations: {}", labels.join(", "));
+        println!("- Max groups in memory: {}", aggregation.max_groups);
+    }
+
     println!("- Feedback Interval: {}", args.feedback_interval);
 
+    if args.profile {
+        println!("- Profiling: enabled");
+    }
+
     println!(
         "- Note: For performance reasons, malformed CSV lines will be skipped and not logged."
     );
@@ -39,14 +58,24 @@ pub fn print_runtime_info(args: &CsvNConfig) {
     println!();
 }
 
+fn describe_format(format: &CsvFormat) -> String {
+    format!(
+        "{}{}, delimiter {}",
+        format.encoding.name(),
+        if format.has_bom { " with BOM" } else { "" },
+        delimiter_name(format.delimiter)
+    )
+}
+
 /// Parses command-line arguments into CSV processing configuration.
 ///
-/// Required: input file path and value-map pairs.
-/// Optional: headers, feedback interval, string cleaning.
+/// Required: input file path and value-map pairs (unless aggregating).
+/// Optional: headers, feedback interval, string cleaning, profiling, aggregation, input format.
+/// The delimiter and the encoding not informed are detected from the start of the file.
 ///
 /// # Errors
-/// Returns error if required arguments are missing or parsing fails
-pub fn get_cli_arguments() -> Result<CsvNConfig> {
+/// Returns error if required arguments are missing, parsing fails, or the file can't be read
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<CsvNConfig> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -75,10 +104,40 @@ pub fn get_cli_arguments() -> Result<CsvNConfig> {
             .long("value-map")
             .short('v')
             .action(clap::ArgAction::Append)
-            .required(true)
-            .help("Key=Value pairs to be used as default values for missing fields. To add multiple parameters, use this flag multiple times. If you want a single value for all missing fields, use * as the key, and inform the value.")
+            .required_unless_present("agg")
+            .help("Key=Value pairs to be used as default values for missing fields. To add multiple parameters, use this flag multiple times. If you want a single value for all missing fields, use * as the key, and inform the value. With --agg, the empty fields are filled before aggregating.")
         )
-        .get_matches();
+        .arg(Arg::new("agg")
+            .long("agg")
+            .short('a')
+            .help("Aggregations to compute, separated by a comma (e.g.: \"sum(amount),count()\"). Functions: count, sum, min, max, avg. Writes a file with the \"_aggregated\" suffix instead of normalizing."))
+        .arg(Arg::new("group-by")
+            .long("group-by")
+            .short('g')
+            .requires("agg")
+            .help("Columns to group the aggregations by, separated by a comma. Optional: If not informed, the whole file is a single group."))
+        .arg(Arg::new("max-groups")
+            .long("max-groups")
+            .requires("agg")
+            .value_parser(clap::value_parser!(u64).range(1..))
+            .default_value("500000")
+            .help("Groups kept in memory while aggregating. Past that, groups are spilled to temporary files on disk and merged at the end."))
+        .arg(Arg::new("delimiter")
+            .long("delimiter")
+            .short('d')
+            .default_value("auto")
+            .help("Delimiter of the input file: auto, comma, semicolon, tab, pipe, or any single character. With auto, it's detected from the first lines. The output uses the same delimiter."))
+        .arg(Arg::new("encoding")
+            .long("encoding")
+            .default_value("auto")
+            .help("Encoding of the input file: auto, utf-8, utf-16le, utf-16be, or latin1. With auto, it's detected from the BOM or the content. The output keeps the same encoding, unless --utf8-output is set."))
+        .arg(Arg::new("utf8-output")
+            .long("utf8-output")
+            .short('u')
+            .action(clap::ArgAction::SetTrue)
+            .help("If set, the output file is written in UTF-8, whatever the encoding of the input."))
+        .preset_arg_profile()
+        .get_matches_from(argv);
 
     let current_working_dir = get_current_working_dir();
 
@@ -106,7 +165,7 @@ pub fn get_cli_arguments() -> Result<CsvNConfig> {
 
     let default_value_map: HashMap<String, String> = matches
         .get_many::<String>("value-map")
-        .context("Default value map is required. Please provide a valid key=value pair.")?
+        .unwrap_or_default()
         .map(|raw_value_pair| {
             let mut parts = raw_value_pair.splitn(2, '=');
             let key = parts.next().unwrap_or("").trim().to_lowercase().to_string();
@@ -130,11 +189,55 @@ pub fn get_cli_arguments() -> Result<CsvNConfig> {
             100
         };
 
+    let profile = matches.get_flag("profile");
+
+    let aggregation = match matches.get_one::<String>("agg") {
+        Some(agg) => Some(AggregationConfig {
+            group_by: matches
+                .get_one::<String>("group-by")
+                .map(|group_by| {
+                    group_by
+                        .split(',')
+                        .map(|column| column.trim().to_string())
+                        .filter(|column| !column.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            aggregations: parse_aggregations(agg)?,
+            max_groups: *matches.get_one::<u64>("max-groups").unwrap_or(&500_000) as usize,
+        }),
+        None => None,
+    };
+
+    let encoding = TextEncoding::parse(
+        matches
+            .get_one::<String>("encoding")
+            .map_or("auto", String::as_str),
+    )?;
+    let delimiter = parse_delimiter(
+        matches
+            .get_one::<String>("delimiter")
+            .map_or("auto", String::as_str),
+    )?;
+    let format = detect_format(&input_file, encoding, delimiter)?;
+    let output_format = if matches.get_flag("utf8-output") {
+        CsvFormat {
+            encoding: TextEncoding::Utf8,
+            ..format
+        }
+    } else {
+        format
+    };
+
     Ok(CsvNConfig::new(
         input_file,
         headers,
         clean_string,
         default_value_map,
         feedback_interval,
+        profile,
+        aggregation,
+        format,
+        output_format,
     ))
 }
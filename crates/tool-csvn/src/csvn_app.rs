@@ -1,19 +1,23 @@
+use crate::aggregation::GroupAggregator;
+use crate::encoded_writer::EncodedWriter;
+use crate::input_detection::{CsvFormat, TextEncoding};
 use crate::models::CsvNConfig;
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, Utc};
 use csv::{StringRecord, Writer, WriterBuilder};
-use shared::system::mmap_csv_reader::MmapCsvReader;
-use shared::utils::datetime_utc_utils::DateTimeUtcUtils;
-use shared::utils::format_duration_to_string::format_duration_to_string;
+use shared::system::mmap_csv_reader::{CsvReaderOptions, CsvRecordReader, ReadMode};
+use shared::ui::progress::{Progress, ProgressTotal};
+use shared::utils::phase_profiler::PhaseProfiler;
 use shared::utils::sanitize_str_regex::clean_str_regex;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::Write;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 use string_interner::DefaultSymbol;
 
+const LINES: &str = "Lines";
+
 /// Determines headers for CSV processing.
 ///
 /// Uses CLI headers if provided, otherwise extracts from the file's first row.
@@ -22,7 +26,7 @@ use string_interner::DefaultSymbol;
 /// Returns error if headers cannot be read from file
 pub fn ensure_headers(
     arg_headers: &Option<Vec<String>>,
-    reader: &mut MmapCsvReader,
+    reader: &mut CsvRecordReader,
 ) -> Result<Vec<String>> {
     if let Some(headers) = arg_headers {
         Ok(headers.clone())
@@ -39,13 +43,48 @@ pub fn ensure_headers(
     }
 }
 
+/// Opens the input file (memory mapped) with its delimiter and encoding.
+///
+/// # Errors
+/// Returns error if the file cannot be opened
+fn open_input_file(args: &CsvNConfig) -> Result<CsvRecordReader> {
+    let options = CsvReaderOptions::default()
+        .with_delimiter(args.format.delimiter)
+        .with_encoding(Some(args.format.encoding.encoding()))
+        .with_mode(ReadMode::Mmap);
+
+    CsvRecordReader::open(&args.input_file, &options)
+}
+
 /// Creates a buffered CSV writer for normalized output.
 ///
-/// Output file has "_normalized" suffix and 128KB buffer.
+/// Output file has "_normalized" suffix and 128KB buffer, and is written with the output format.
 ///
 /// # Errors
 /// Returns error if an output file cannot be created
-pub fn get_output_normalized_file(input_file: &Path) -> Result<Writer<File>> {
+pub fn get_output_normalized_file(
+    input_file: &Path,
+    format: &CsvFormat,
+) -> Result<Writer<EncodedWriter<File>>> {
+    get_output_file(input_file, "_normalized", format)
+}
+
+/// Creates a buffered CSV writer for the aggregated output ("_aggregated" suffix).
+///
+/// # Errors
+/// Returns error if an output file cannot be created
+pub fn get_output_aggregated_file(
+    input_file: &Path,
+    format: &CsvFormat,
+) -> Result<Writer<EncodedWriter<File>>> {
+    get_output_file(input_file, "_aggregated", format)
+}
+
+fn get_output_file(
+    input_file: &Path,
+    suffix: &str,
+    format: &CsvFormat,
+) -> Result<Writer<EncodedWriter<File>>> {
     let input_str = match input_file.to_str() {
         Some(s) => s,
         None => {
@@ -55,23 +94,26 @@ pub fn get_output_normalized_file(input_file: &Path) -> Result<Writer<File>> {
         }
     };
 
-    let normalized_path = if let Some(dot_index) = input_str.rfind('.') {
+    let output_path = if let Some(dot_index) = input_str.rfind('.') {
         format!(
-            "{}_normalized{}",
+            "{}{}{}",
             &input_str[..dot_index],
+            suffix,
             &input_str[dot_index..]
         )
     } else {
-        format!("{}_normalized", input_str)
+        format!("{}{}", input_str, suffix)
     };
 
-    let file = File::create(&normalized_path).context(format!(
-        "Unable to open file for writing: {}",
-        normalized_path
-    ))?;
+    let file = File::create(&output_path)
+        .context(format!("Unable to open file for writing: {}", output_path))?;
+
+    let file = EncodedWriter::new(file, format.encoding, format.has_bom)
+        .context(format!("Unable to write to file: {}", output_path))?;
 
     let wtr = WriterBuilder::new()
         .buffer_capacity(131_072) // 128 KiB internal buffer
+        .delimiter(format.delimiter)
         .from_writer(file);
 
     Ok(wtr)
@@ -81,30 +123,41 @@ pub fn get_output_normalized_file(input_file: &Path) -> Result<Writer<File>> {
 ///
 /// Creates a normalized output file, fills empty fields with defaults, provides progress updates.
 /// Silently skips malformed CSV lines for performance.
+/// When profiling is enabled, prints the time spent in each phase before returning.
 ///
 /// # Errors
 /// Returns error if file operations fail
 pub fn process_file(args: &mut CsvNConfig, shutdown_signal: Arc<AtomicBool>) -> Result<()> {
-    let mut reader = MmapCsvReader::new(&args.input_file)?;
+    if args.aggregation.is_some() {
+        return aggregate_file(args, shutdown_signal);
+    }
+
+    let mut profiler = PhaseProfiler::new(args.profile);
+    let phase_started_at = Instant::now();
+
+    let mut reader = open_input_file(args)?;
 
     let headers = ensure_headers(&args.headers, &mut reader)?;
 
     let value_map = update_default_value_map(args, &headers)?;
 
-    let mut output_file = get_output_normalized_file(&args.input_file)?;
+    let mut output_file = get_output_normalized_file(&args.input_file, &args.output_format)?;
 
     // Write headers first
     output_file
         .write_record(&headers)
         .context("Failed to write headers to output file")?;
 
-    let start_time = Utc::now();
+    profiler.record_since("open and read headers", phase_started_at);
+    let phase_started_at = Instant::now();
+
+    let progress = create_progress(&args.input_file, &args.format);
 
     let mut line_count: u64 = 0;
 
     let feedback_interval = args.feedback_interval;
 
-    for record in reader.records().filter_map(Result::ok) {
+    for record in reader.filter_map(Result::ok) {
         // Check for a shutdown signal
         if shutdown_signal.load(Ordering::Relaxed) {
             println!("\n- Saving progress and exiting gracefully...");
@@ -112,6 +165,10 @@ pub fn process_file(args: &mut CsvNConfig, shutdown_signal: Arc<AtomicBool>) ->
             break;
         }
 
+        if let Some(position) = record.position() {
+            progress.set_bytes(position.byte());
+        }
+
         let normalized_record =
             normalize_record(args, &value_map, &headers, record, &args.clean_string)?;
 
@@ -120,16 +177,21 @@ pub fn process_file(args: &mut CsvNConfig, shutdown_signal: Arc<AtomicBool>) ->
             .context("Failed to write normalized line to output file")?;
 
         line_count += 1;
-        if line_count % feedback_interval == 0 {
-            update_process_feedback(start_time, &line_count)?;
+        progress.increment(LINES);
+        if line_count.is_multiple_of(feedback_interval) {
+            progress.render();
         }
     }
 
-    update_process_feedback(start_time, &line_count)?;
-    println!();
+    progress.finish();
+
+    profiler.record_since("normalize records", phase_started_at);
 
     // Ensure all data is written to the disk
-    match output_file.flush() {
+    let flush_result = profiler.measure("flush output", || output_file.flush());
+    profiler.print_report();
+
+    match flush_result {
         Ok(_) => {
             if shutdown_signal.load(Ordering::Relaxed) {
                 println!(
@@ -151,6 +213,107 @@ pub fn process_file(args: &mut CsvNConfig, shutdown_signal: Arc<AtomicBool>) ->
     }
 }
 
+/// Summarizes the CSV file per group, in a single pass, writing one row per group (sorted by the
+/// group columns) to the "_aggregated" output file. With a value map, the empty fields are filled
+/// before aggregating.
+///
+/// # Errors
+/// Returns error if a column is not in the headers, or file operations fail
+fn aggregate_file(args: &mut CsvNConfig, shutdown_signal: Arc<AtomicBool>) -> Result<()> {
+    let mut profiler = PhaseProfiler::new(args.profile);
+    let phase_started_at = Instant::now();
+
+    let mut reader = open_input_file(args)?;
+
+    let headers = ensure_headers(&args.headers, &mut reader)?;
+
+    let value_map = if args.default_value_map.is_empty() {
+        None
+    } else {
+        Some(update_default_value_map(args, &headers)?)
+    };
+
+    let config = args
+        .aggregation
+        .as_ref()
+        .context("Aggregation options are required")?;
+    let spill_dir = std::env::temp_dir().join(format!("csvn-aggregation-{}", std::process::id()));
+    let mut aggregator = GroupAggregator::new(config, &headers, spill_dir)?;
+
+    let mut output_file = get_output_aggregated_file(&args.input_file, &args.output_format)?;
+    let output_headers: Vec<String> = config
+        .group_by
+        .iter()
+        .cloned()
+        .chain(config.aggregations.iter().map(|a| a.label()))
+        .collect();
+    output_file
+        .write_record(&output_headers)
+        .context("Failed to write headers to output file")?;
+
+    profiler.record_since("open and read headers", phase_started_at);
+    let phase_started_at = Instant::now();
+
+    let progress = create_progress(&args.input_file, &args.format);
+    let mut line_count: u64 = 0;
+
+    for record in reader.filter_map(Result::ok) {
+        if shutdown_signal.load(Ordering::Relaxed) {
+            println!("\n- Writing the groups aggregated so far and exiting gracefully...");
+            println!("- Aggregated [{}] lines before shutdown", line_count);
+            break;
+        }
+
+        if let Some(position) = record.position() {
+            progress.set_bytes(position.byte());
+        }
+
+        match &value_map {
+            Some(value_map) => {
+                let normalized_record =
+                    normalize_record(args, value_map, &headers, record, &args.clean_string)?;
+                aggregator.add(&normalized_record)?;
+            }
+            None => aggregator.add(&record)?,
+        }
+
+        line_count += 1;
+        progress.increment(LINES);
+        if line_count.is_multiple_of(args.feedback_interval) {
+            progress.render();
+        }
+    }
+
+    progress.finish();
+    profiler.record_since("aggregate records", phase_started_at);
+
+    let skipped_values = aggregator.skipped_values;
+    let spilled_runs = aggregator.spilled_runs();
+    let groups = profiler.measure("write groups", || aggregator.finish(&mut output_file))?;
+    let flush_result = profiler.measure("flush output", || output_file.flush());
+    profiler.print_report();
+    flush_result.context("Failed to flush output file")?;
+
+    if skipped_values > 0 {
+        eprintln!(
+            "Warning: {} value(s) were not numbers, and were left out of sum/min/max/avg.",
+            skipped_values
+        );
+    }
+
+    let spilled = if spilled_runs > 0 {
+        format!(" ({} run(s) spilled to disk)", spilled_runs)
+    } else {
+        String::new()
+    };
+    println!(
+        "[OK] {} lines aggregated into {} group(s){}.",
+        line_count, groups, spilled
+    );
+
+    Ok(())
+}
+
 /// Updates a default value map based on file headers.
 ///
 /// Expands wildcard (*) keys to all headers or uses specific column mappings.
@@ -239,26 +402,20 @@ fn normalize_record(
     Ok(normalized_record)
 }
 
-/// Displays processing progress feedback.
-///
-/// Shows lines processed, elapsed time, and processing speed.
-///
-/// # Errors
-/// Returns error if stdout cannot be flushed
-fn update_process_feedback(start_time: DateTime<Utc>, line_count: &u64) -> Result<()> {
-    let elapsed = start_time.get_elapsed_time();
-    let lines_per_second = *line_count as f64 / elapsed.as_seconds_f64();
-
-    // Feedback line has some padding to the right to make it look nicer.
-    print!(
-        "[Lines processed: {}][Elapsed Time: {}][Speed: {:.2} lines/s]                                 \r",
-        line_count,
-        format_duration_to_string(elapsed),
-        lines_per_second
-    );
-    std::io::stdout()
-        .flush()
-        .context("Failed to flush stdout.")?;
-
-    Ok(())
+/// Creates the progress of the normalization. The size of the input file gives the percentage
+/// and the ETA. The position of the records is in the decoded (UTF-8) text, which is about half
+/// the size of a UTF-16 file.
+fn create_progress(input_file: &Path, format: &CsvFormat) -> Progress {
+    let progress = Progress::new("lines", &[LINES]);
+
+    match std::fs::metadata(input_file) {
+        Ok(metadata) => {
+            let total = match format.encoding {
+                TextEncoding::Utf16Le | TextEncoding::Utf16Be => metadata.len() / 2,
+                TextEncoding::Utf8 | TextEncoding::Latin1 => metadata.len(),
+            };
+            progress.with_total(ProgressTotal::Bytes(total))
+        }
+        Err(_) => progress,
+    }
 }
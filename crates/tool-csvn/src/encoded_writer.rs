@@ -0,0 +1,110 @@
+use crate::input_detection::TextEncoding;
+use std::io::{self, Write};
+
+/// Writes the (UTF-8) output of the csv writer in another encoding, so the output file keeps
+/// the encoding of the input. UTF-8 goes straight through.
+pub struct EncodedWriter<W: Write> {
+    inner: W,
+    encoding: TextEncoding,
+    /// Start of a character split between two writes, kept until the rest of it arrives.
+    pending: Vec<u8>,
+}
+
+impl<W: Write> EncodedWriter<W> {
+    /// Writes the BOM first when `with_bom` is set (Excel needs it to read UTF-16 and UTF-8).
+    ///
+    /// # Errors
+    /// Returns error if the BOM can't be written.
+    pub fn new(mut inner: W, encoding: TextEncoding, with_bom: bool) -> io::Result<Self> {
+        if with_bom {
+            let bom: &[u8] = match encoding {
+                TextEncoding::Utf8 => &[0xEF, 0xBB, 0xBF],
+                TextEncoding::Utf16Le => &[0xFF, 0xFE],
+                TextEncoding::Utf16Be => &[0xFE, 0xFF],
+                TextEncoding::Latin1 => &[],
+            };
+            inner.write_all(bom)?;
+        }
+
+        Ok(Self {
+            inner,
+            encoding,
+            pending: Vec::new(),
+        })
+    }
+
+    fn write_text(&mut self, text: &str) -> io::Result<()> {
+        match self.encoding {
+            TextEncoding::Utf8 => self.inner.write_all(text.as_bytes()),
+            TextEncoding::Utf16Le => {
+                let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+                self.inner.write_all(&bytes)
+            }
+            TextEncoding::Utf16Be => {
+                let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+                self.inner.write_all(&bytes)
+            }
+            // Characters Windows-1252 doesn't have are written as numeric character references.
+            TextEncoding::Latin1 => {
+                let (bytes, _, _) = self.encoding.encoding().encode(text);
+                self.inner.write_all(&bytes)
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for EncodedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.encoding == TextEncoding::Utf8 {
+            return self.inner.write(buf);
+        }
+
+        self.pending.extend_from_slice(buf);
+        let pending = std::mem::take(&mut self.pending);
+
+        let valid_up_to = match std::str::from_utf8(&pending) {
+            Ok(_) => pending.len(),
+            // Incomplete character at the end: written with the next buffer.
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+
+        // Safe to unwrap: checked above.
+        let text = std::str::from_utf8(&pending[..valid_up_to]).unwrap();
+        self.write_text(text)?;
+        self.pending = pending[valid_up_to..].to_vec();
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_in_the_original_encoding() {
+        let mut writer = EncodedWriter::new(Vec::new(), TextEncoding::Utf16Le, true).unwrap();
+        let text = "nome;São Paulo\n".as_bytes();
+        // Splits the "ã" between two writes.
+        writer.write_all(&text[..7]).unwrap();
+        writer.write_all(&text[7..]).unwrap();
+        let expected: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("nome;São Paulo\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert_eq!(writer.inner, expected);
+
+        let mut writer = EncodedWriter::new(Vec::new(), TextEncoding::Latin1, false).unwrap();
+        writer.write_all("São".as_bytes()).unwrap();
+        assert_eq!(writer.inner, b"S\xE3o");
+
+        let mut writer = EncodedWriter::new(Vec::new(), TextEncoding::Utf8, true).unwrap();
+        writer.write_all(b"a,b").unwrap();
+        assert_eq!(writer.inner, b"\xEF\xBB\xBFa,b");
+    }
+}
@@ -0,0 +1,301 @@
+use anyhow::{bail, Context, Result};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of the file to detect the encoding and the delimiter.
+const SAMPLE_LENGTH: usize = 64 * 1024;
+
+/// Lines of the sample used to detect the delimiter.
+const SAMPLE_LINES: usize = 50;
+
+/// Delimiters tried by the detection, in order of preference when tied.
+const CANDIDATE_DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+/// Encodings csvn can read and write.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Latin-1, read as Windows-1252 (its superset, used by Excel on Windows).
+    Latin1,
+}
+
+impl TextEncoding {
+    /// Parses the value of `--encoding`. `auto` means it's detected (`None`).
+    ///
+    /// # Errors
+    /// Returns error if the encoding is not supported.
+    pub fn parse(value: &str) -> Result<Option<Self>> {
+        let encoding = match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "auto" => return Ok(None),
+            "utf-8" | "utf8" => TextEncoding::Utf8,
+            "utf-16" | "utf16" | "utf-16le" | "utf16le" => TextEncoding::Utf16Le,
+            "utf-16be" | "utf16be" => TextEncoding::Utf16Be,
+            "latin1" | "latin-1" | "iso-8859-1" | "windows-1252" | "cp1252" => TextEncoding::Latin1,
+            other => bail!(
+                "Unsupported encoding [{}]. Use: auto, utf-8, utf-16le, utf-16be, or latin1",
+                other
+            ),
+        };
+
+        Ok(Some(encoding))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Latin1 => "Latin-1 (Windows-1252)",
+        }
+    }
+
+    pub fn encoding(&self) -> &'static Encoding {
+        match self {
+            TextEncoding::Utf8 => UTF_8,
+            TextEncoding::Utf16Le => UTF_16LE,
+            TextEncoding::Utf16Be => UTF_16BE,
+            TextEncoding::Latin1 => WINDOWS_1252,
+        }
+    }
+}
+
+/// Encoding and delimiter of the input file, detected or informed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvFormat {
+    pub encoding: TextEncoding,
+    /// The file starts with a byte order mark. Kept in the output, since Excel relies on it.
+    pub has_bom: bool,
+    pub delimiter: u8,
+}
+
+/// Parses the value of `--delimiter`. `auto` means it's detected (`None`). Accepts a single
+/// character, or the names `comma`, `semicolon`, `tab` (or `\t`), and `pipe`.
+///
+/// # Errors
+/// Returns error if the delimiter is not a single ASCII character.
+pub fn parse_delimiter(value: &str) -> Result<Option<u8>> {
+    let delimiter = match value.to_lowercase().as_str() {
+        "auto" => return Ok(None),
+        "comma" => b',',
+        "semicolon" => b';',
+        "tab" | "\\t" => b'\t',
+        "pipe" => b'|',
+        other if other.len() == 1 && other.is_ascii() => other.as_bytes()[0],
+        other => bail!(
+            "Invalid delimiter [{}]. Use auto, a single character, or: comma, semicolon, tab, pipe",
+            other
+        ),
+    };
+
+    Ok(Some(delimiter))
+}
+
+/// Shows the delimiter in a readable way (the tab is invisible otherwise).
+pub fn delimiter_name(delimiter: u8) -> String {
+    match delimiter {
+        b'\t' => "tab".to_string(),
+        other => format!("'{}'", other as char),
+    }
+}
+
+/// Detects what was not informed, from the start of the file.
+///
+/// # Errors
+/// Returns error if the file can't be read.
+pub fn detect_format(
+    path: &Path,
+    encoding: Option<TextEncoding>,
+    delimiter: Option<u8>,
+) -> Result<CsvFormat> {
+    let mut sample = Vec::with_capacity(SAMPLE_LENGTH);
+    File::open(path)
+        .context(format!("Unable to open file: {}", path.display()))?
+        .take(SAMPLE_LENGTH as u64)
+        .read_to_end(&mut sample)
+        .context("Failed to read the start of the file")?;
+
+    let (detected_encoding, has_bom) = detect_encoding(&sample);
+    let encoding = encoding.unwrap_or(detected_encoding);
+
+    let delimiter = match delimiter {
+        Some(delimiter) => delimiter,
+        None => {
+            let (text, _, _) = encoding.encoding().decode(&sample);
+            detect_delimiter(&text)
+        }
+    };
+
+    Ok(CsvFormat {
+        encoding,
+        has_bom,
+        delimiter,
+    })
+}
+
+/// Detects the encoding from the BOM or, without one, from the bytes: UTF-16 text (of a CSV, so
+/// mostly ASCII) has a zero in every other byte, and anything that is not valid UTF-8 is taken
+/// as Latin-1. Returns the encoding and whether the sample starts with a BOM.
+pub fn detect_encoding(sample: &[u8]) -> (TextEncoding, bool) {
+    if sample.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return (TextEncoding::Utf8, true);
+    }
+    if sample.starts_with(&[0xFF, 0xFE]) {
+        return (TextEncoding::Utf16Le, true);
+    }
+    if sample.starts_with(&[0xFE, 0xFF]) {
+        return (TextEncoding::Utf16Be, true);
+    }
+
+    let pairs = sample.len() / 2;
+    if pairs > 0 {
+        let zeros_at = |offset: usize| {
+            sample
+                .chunks_exact(2)
+                .filter(|pair| pair[offset] == 0)
+                .count()
+        };
+
+        // Most of the text is ASCII, so most pairs have one zero, always on the same side.
+        if zeros_at(1) * 10 >= pairs * 7 && zeros_at(0) * 10 < pairs {
+            return (TextEncoding::Utf16Le, false);
+        }
+        if zeros_at(0) * 10 >= pairs * 7 && zeros_at(1) * 10 < pairs {
+            return (TextEncoding::Utf16Be, false);
+        }
+    }
+
+    match std::str::from_utf8(sample) {
+        Ok(_) => (TextEncoding::Utf8, false),
+        // The sample ends in the middle of a character: what was read is still valid.
+        Err(e) if e.error_len().is_none() => (TextEncoding::Utf8, false),
+        Err(_) => (TextEncoding::Latin1, false),
+    }
+}
+
+/// Picks the candidate delimiter found the same number of times in most lines (quoted fields
+/// aside). Ties go to the one found more times per line, then to the order of the candidates.
+/// Defaults to a comma when none is found.
+pub fn detect_delimiter(text: &str) -> u8 {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(SAMPLE_LINES)
+        .collect();
+
+    let mut best: Option<(usize, usize, u8)> = None;
+
+    for delimiter in CANDIDATE_DELIMITERS {
+        let counts: Vec<usize> = lines
+            .iter()
+            .map(|line| count_unquoted(line, delimiter))
+            .collect();
+
+        // Most frequent number of fields in a line, and how many lines have it.
+        let mut frequency: Vec<(usize, usize)> = Vec::new();
+        for count in counts.iter().filter(|count| **count > 0) {
+            match frequency.iter_mut().find(|(value, _)| value == count) {
+                Some((_, lines)) => *lines += 1,
+                None => frequency.push((*count, 1)),
+            }
+        }
+
+        let Some((per_line, consistent_lines)) = frequency
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)))
+        else {
+            continue;
+        };
+
+        let is_better = match best {
+            None => true,
+            Some((best_lines, best_per_line, _)) => {
+                (consistent_lines, per_line) > (best_lines, best_per_line)
+            }
+        };
+        if is_better {
+            best = Some((consistent_lines, per_line, delimiter));
+        }
+    }
+
+    best.map_or(b',', |(_, _, delimiter)| delimiter)
+}
+
+/// Times the delimiter shows up in the line, outside quotes. A line ending inside quotes is
+/// just counted up to its end.
+fn count_unquoted(line: &str, delimiter: u8) -> usize {
+    let mut in_quotes = false;
+    let mut count = 0;
+
+    for byte in line.bytes() {
+        if byte == b'"' {
+            in_quotes = !in_quotes;
+        } else if byte == delimiter && !in_quotes {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_the_delimiter() {
+        assert_eq!(detect_delimiter("name;age\nJohn;25\nAlice;30\n"), b';');
+        assert_eq!(detect_delimiter("name\tcity\nJohn\tRio, Brazil\n"), b'\t');
+        assert_eq!(
+            detect_delimiter("name,notes\nJohn,\"a;b;c\"\nAlice,\"d;e\"\n"),
+            b','
+        );
+        assert_eq!(detect_delimiter("a|b|c\n1|2|3\n"), b'|');
+        assert_eq!(detect_delimiter("single column\nvalue\n"), b',');
+
+        assert_eq!(parse_delimiter("tab").unwrap(), Some(b'\t'));
+        assert_eq!(parse_delimiter(";").unwrap(), Some(b';'));
+        assert_eq!(parse_delimiter("auto").unwrap(), None);
+        assert!(parse_delimiter("::").is_err());
+    }
+
+    #[test]
+    fn test_detects_the_encoding() {
+        let utf16le: Vec<u8> = "name;age\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let utf16be: Vec<u8> = "name;age\n"
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect();
+
+        assert_eq!(detect_encoding(&utf16le), (TextEncoding::Utf16Le, false));
+        assert_eq!(detect_encoding(&utf16be), (TextEncoding::Utf16Be, false));
+        assert_eq!(
+            detect_encoding(&[0xFF, 0xFE, b'a', 0]),
+            (TextEncoding::Utf16Le, true)
+        );
+        assert_eq!(
+            detect_encoding(b"\xEF\xBB\xBFname"),
+            (TextEncoding::Utf8, true)
+        );
+        assert_eq!(
+            detect_encoding("São Paulo".as_bytes()).0,
+            TextEncoding::Utf8
+        );
+        // "São Paulo" in Latin-1.
+        assert_eq!(detect_encoding(b"S\xE3o Paulo").0, TextEncoding::Latin1);
+        // A multibyte character cut at the end of the sample.
+        assert_eq!(detect_encoding(b"S\xC3").0, TextEncoding::Utf8);
+
+        assert_eq!(
+            TextEncoding::parse("UTF_16BE").unwrap(),
+            Some(TextEncoding::Utf16Be)
+        );
+        assert!(TextEncoding::parse("ebcdic").is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info};
+use crate::csvn_app::process_file;
+use crate::models::CsvNConfig;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use std::ffi::OsString;
+use tracing::error;
+
+mod aggregation;
+mod cli_utils;
+mod csvn_app;
+mod encoded_writer;
+mod input_detection;
+mod models;
+
+/// CSV normalizer.
+///
+/// Parses arguments, then fills the missing fields of the input file with defaults.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    const CSVN_APP_NAME: &str = env!("CARGO_PKG_NAME");
+    let mut args: CsvNConfig;
+
+    match get_cli_arguments(argv.into_iter().map(Into::into).collect()) {
+        Ok(a) => args = a,
+        Err(e) => {
+            error!("{} failed to parse arguments: {}", CSVN_APP_NAME, e);
+            return EXIT_CODE_ERROR;
+        }
+    }
+
+    print_runtime_info(&args);
+
+    // Set up a graceful shutdown
+    let shutdown_signal = setup_graceful_shutdown(false);
+
+    match process_file(&mut args, shutdown_signal) {
+        Ok(_) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            error!("{} failed to execute: {}", CSVN_APP_NAME, e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
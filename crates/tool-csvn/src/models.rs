@@ -1,3 +1,5 @@
+use crate::aggregation::AggregationConfig;
+use crate::input_detection::CsvFormat;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use string_interner::{DefaultBackend, DefaultSymbol, StringInterner};
@@ -5,24 +7,35 @@ use string_interner::{DefaultBackend, DefaultSymbol, StringInterner};
 /// CSV normalization configuration.
 ///
 /// Contains input file path, headers, default mappings, and processing options.
-/// Uses string interning for memory optimization.
+/// Uses string interning for memory optimization. With `aggregation`, the file is summarized
+/// instead of normalized. `format` is how the input is read (detected, unless informed), and
+/// `output_format` how the output is written.
 pub struct CsvNConfig {
     pub input_file: PathBuf,
     pub headers: Option<Vec<String>>,
     pub clean_string: bool,
     pub default_value_map: HashMap<String, String>,
     pub feedback_interval: u64,
+    pub profile: bool,
+    pub aggregation: Option<AggregationConfig>,
+    pub format: CsvFormat,
+    pub output_format: CsvFormat,
     pub string_interner: StringInterner<DefaultBackend>,
     pub interned_defaults: HashMap<String, DefaultSymbol>,
 }
 
 impl CsvNConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         input_file: PathBuf,
         headers: Option<Vec<String>>,
         clean_string: bool,
         default_value_map: HashMap<String, String>,
         feedback_interval: u64,
+        profile: bool,
+        aggregation: Option<AggregationConfig>,
+        format: CsvFormat,
+        output_format: CsvFormat,
     ) -> Self {
         let mut interner = StringInterner::<DefaultBackend>::new();
         let mut interned_defaults = HashMap::new();
@@ -39,6 +52,10 @@ impl CsvNConfig {
             clean_string,
             default_value_map,
             feedback_interval,
+            profile,
+            aggregation,
+            format,
+            output_format,
             string_interner: interner,
             interned_defaults,
         }
@@ -0,0 +1,195 @@
+use crate::models::{CompareMode, DirDiffArgs};
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{FlagAlias, GlobalArgs, GlobalFlag, ToolCli};
+use shared::utils::table_output::ReportFormat;
+use std::path::{Path, PathBuf};
+
+const FLAG_ALIASES: &[FlagAlias] = &[FlagAlias::alias("-o", "--output")];
+
+impl ToolCli for DirDiffArgs {
+    const DISPLAY_NAME: &'static str = "Dir Diff";
+
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Compares a source folder with a target folder, and lists the files added (only \
+                in the source), removed (only in the target), and changed. By default, this is a \
+                dry-run: with --apply, the target is synced one-way with the source, copying the \
+                added and changed files (and, with --delete, removing the files only in the \
+                target).",
+            )
+            .arg(
+                Arg::new("source")
+                    .value_name("SOURCE")
+                    .required(true)
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Folder with the files as they should be"),
+            )
+            .arg(
+                Arg::new("target")
+                    .value_name("TARGET")
+                    .required(true)
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Folder compared with (and, with --apply, synced from) the source. Doesn't need to exist"),
+            )
+            .arg(
+                Arg::new("compare")
+                    .long("compare")
+                    .short('c')
+                    .value_name("MODE")
+                    .value_parser(["size", "mtime", "hash"])
+                    .help("How files in both folders are compared: size, mtime (size and modification time), or hash (size and content). (Default: mtime)"),
+            )
+            .arg(
+                Arg::new("apply")
+                    .long("apply")
+                    .action(ArgAction::SetTrue)
+                    .help("Copies the added and changed files to the target. Without it, nothing is changed. (Default: false)"),
+            )
+            .arg(
+                Arg::new("delete")
+                    .long("delete")
+                    .action(ArgAction::SetTrue)
+                    .help("With --apply, also deletes the files only in the target. (Default: false)"),
+            )
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .short('f')
+                    .value_name("FORMAT")
+                    .value_parser(["table", "markdown", "md", "json"])
+                    .help("Report format: table, markdown (md), or json. (Default: table)"),
+            )
+    }
+
+    fn global_flags() -> &'static [GlobalFlag] {
+        &[GlobalFlag::Quiet, GlobalFlag::NoColor, GlobalFlag::Output]
+    }
+
+    fn flag_aliases() -> &'static [FlagAlias] {
+        FLAG_ALIASES
+    }
+
+    fn from_matches(matches: &ArgMatches, global: &GlobalArgs) -> Result<Self> {
+        let compare = match matches.get_one::<String>("compare") {
+            Some(compare) => compare.parse()?,
+            None => CompareMode::default(),
+        };
+        let format = match matches.get_one::<String>("format") {
+            Some(format) => format.parse()?,
+            None => ReportFormat::Table,
+        };
+
+        Ok(DirDiffArgs {
+            source: matches
+                .get_one::<PathBuf>("source")
+                .cloned()
+                .unwrap_or_default(),
+            target: matches
+                .get_one::<PathBuf>("target")
+                .cloned()
+                .unwrap_or_default(),
+            compare,
+            apply: matches.get_flag("apply"),
+            delete: matches.get_flag("delete"),
+            format,
+            output: global.output.clone(),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.source.is_dir() {
+            bail!("Source folder '{}' does not exist", self.source.display());
+        }
+
+        if self.target.exists() && !self.target.is_dir() {
+            bail!("Target '{}' is not a folder", self.target.display());
+        }
+
+        if let (Ok(source), Ok(target)) = (self.source.canonicalize(), self.target.canonicalize()) {
+            if source == target {
+                bail!("Source and target are the same folder");
+            }
+
+            if self.apply && (is_inside(&source, &target) || is_inside(&target, &source)) {
+                bail!("Can't sync a folder with a folder inside it");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        vec![
+            ("Source".to_string(), self.source.display().to_string()),
+            ("Target".to_string(), self.target.display().to_string()),
+            ("Compare".to_string(), self.compare.name().to_string()),
+            (
+                "Mode".to_string(),
+                match (self.apply, self.delete) {
+                    (false, _) => "dry-run",
+                    (true, false) => "apply",
+                    (true, true) => "apply (with delete)",
+                }
+                .to_string(),
+            ),
+            ("Format".to_string(), self.format.name().to_string()),
+            (
+                "Output".to_string(),
+                self.output
+                    .as_ref()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| "stdout".to_string()),
+            ),
+        ]
+    }
+}
+
+fn is_inside(path: &Path, folder: &Path) -> bool {
+    path != folder && path.starts_with(folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+    use tempfile::tempdir;
+
+    fn parse(argv: &[&str]) -> Result<DirDiffArgs> {
+        try_parse_tool_cli_from::<DirDiffArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source");
+        let inner = source.join("inner");
+        std::fs::create_dir_all(&inner).unwrap();
+        let source = source.to_str().unwrap();
+        let target = dir.path().join("backup");
+        let target = target.to_str().unwrap();
+
+        let defaults = parse(&["dirdiff", source, target]).unwrap();
+        assert_eq!(defaults.compare, CompareMode::Mtime);
+        assert!(!defaults.apply);
+        assert_eq!(defaults.format, ReportFormat::Table);
+
+        let args = parse(&[
+            "dirdiff", source, target, "-c", "hash", "--apply", "--delete", "-f", "json",
+        ])
+        .unwrap();
+        assert_eq!(args.compare, CompareMode::Hash);
+        assert!(args.apply && args.delete);
+        assert_eq!(args.format, ReportFormat::Json);
+
+        assert!(parse(&["dirdiff", source]).is_err());
+        assert!(parse(&["dirdiff", target, source]).is_err());
+        assert!(parse(&["dirdiff", source, source]).is_err());
+        assert!(parse(&["dirdiff", source, inner.to_str().unwrap()]).is_ok());
+        assert!(parse(&["dirdiff", source, inner.to_str().unwrap(), "--apply"]).is_err());
+    }
+}
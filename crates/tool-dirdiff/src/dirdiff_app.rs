@@ -0,0 +1,148 @@
+use crate::models::{ChangeKind, DiffReport, DirDiffArgs};
+use crate::report_printer::render_report;
+use crate::tree_comparer::compare_trees;
+use crate::tree_scanner::scan_tree;
+use crate::tree_sync::sync_trees;
+use anyhow::{Context, Result};
+use shared::utils::table_output::ReportFormat;
+use tracing::info;
+
+/// Compares the trees, writes the report to `--output` (or stdout) and, with `--apply`, syncs the
+/// target.
+pub fn run(args: &DirDiffArgs) -> Result<()> {
+    let report = build_report(args)?;
+    let rendered = render_report(&report, args.format)?;
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .context(format!("Failed to write the report to {}", path.display()))?;
+            info!("Report written to {}", path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    // Markdown and JSON printed to stdout are meant to be piped, so the rest goes to stderr.
+    let print_note = |note: String| {
+        if args.format == ReportFormat::Table || args.output.is_some() {
+            println!("{}", note);
+        } else {
+            eprintln!("{}", note);
+        }
+    };
+
+    if args.apply {
+        print_note(String::new());
+        let summary = sync_trees(
+            &report.changes,
+            &args.source,
+            &args.target,
+            args.delete,
+            |kind, path| {
+                let action = if kind == ChangeKind::Removed {
+                    "Deleted"
+                } else {
+                    "Copied"
+                };
+                print_note(format!("{} {}", action, path));
+            },
+        )?;
+        print_note(format!(
+            "Synced: {} file(s) copied, {} deleted.",
+            summary.copied, summary.deleted
+        ));
+    } else if let Some(note) = dry_run_note(&report, args.delete) {
+        print_note(String::new());
+        print_note(note);
+    }
+
+    Ok(())
+}
+
+fn build_report(args: &DirDiffArgs) -> Result<DiffReport> {
+    info!(
+        "Comparing {} with {}",
+        args.source.display(),
+        args.target.display()
+    );
+
+    let source = scan_tree(&args.source)?;
+    let target = scan_tree(&args.target)?;
+    let (changes, unchanged) =
+        compare_trees(&args.source, &source, &args.target, &target, args.compare)?;
+
+    Ok(DiffReport {
+        source: args.source.display().to_string(),
+        target: args.target.display().to_string(),
+        compare: args.compare,
+        changes,
+        unchanged,
+    })
+}
+
+/// What `--apply` would do. `None` when there's nothing to sync.
+fn dry_run_note(report: &DiffReport, delete: bool) -> Option<String> {
+    let copies = report.count(ChangeKind::Added) + report.count(ChangeKind::Changed);
+    let deletes = if delete {
+        report.count(ChangeKind::Removed)
+    } else {
+        0
+    };
+
+    let mut actions = Vec::new();
+    if copies > 0 {
+        actions.push(format!("copy {} file(s) to the target", copies));
+    }
+    if deletes > 0 {
+        actions.push(format!("delete {} file(s) from the target", deletes));
+    }
+
+    if actions.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Dry run: nothing was changed. Run with --apply to {}.",
+        actions.join(", and ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CompareMode, FileChange};
+
+    #[test]
+    fn test_dry_run_note() {
+        let change = |kind| FileChange {
+            kind,
+            path: "a.txt".to_string(),
+            source_size: None,
+            target_size: None,
+        };
+        let report = |changes| DiffReport {
+            source: "a".to_string(),
+            target: "b".to_string(),
+            compare: CompareMode::Mtime,
+            changes,
+            unchanged: 0,
+        };
+
+        assert_eq!(dry_run_note(&report(vec![]), true), None);
+        assert_eq!(
+            dry_run_note(&report(vec![change(ChangeKind::Removed)]), false),
+            None
+        );
+        assert!(
+            dry_run_note(&report(vec![change(ChangeKind::Removed)]), true)
+                .unwrap()
+                .ends_with("--apply to delete 1 file(s) from the target.")
+        );
+        assert!(dry_run_note(
+            &report(vec![change(ChangeKind::Added), change(ChangeKind::Removed)]),
+            false
+        )
+        .unwrap()
+        .ends_with("copy 1 file(s) to the target."));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::models::DirDiffArgs;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use shared::utils::table_output::ReportFormat;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod dirdiff_app;
+mod models;
+mod report_printer;
+mod tree_comparer;
+mod tree_scanner;
+mod tree_sync;
+
+/// Folder diff and one-way sync.
+///
+/// Parses arguments, compares the source and target trees, prints the differences (or writes them
+/// to `--output`) and, with `--apply`, syncs the target.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<DirDiffArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    // Markdown and JSON printed to stdout are meant to be piped, so the header would get in the way.
+    let args = &invocation.args;
+    if args.format == ReportFormat::Table || args.output.is_some() {
+        invocation.print_runtime_info();
+    }
+
+    if let Err(e) = dirdiff_app::run(args) {
+        eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+        error!("{}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
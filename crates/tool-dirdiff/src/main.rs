@@ -0,0 +1,5 @@
+use shared::system::tool_exit_helpers::exit_with_code;
+
+fn main() {
+    exit_with_code(dirdiff::run(std::env::args_os()));
+}
@@ -0,0 +1,124 @@
+use serde::Serialize;
+use shared::utils::table_output::ReportFormat;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirDiffArgs {
+    pub source: PathBuf,
+    /// Doesn't need to exist: a missing target is compared as empty (and created by `--apply`).
+    pub target: PathBuf,
+    pub compare: CompareMode,
+    /// Syncs the target with the source. Without it, the changes are only listed (dry-run).
+    pub apply: bool,
+    /// Also removes the files that only exist in the target.
+    pub delete: bool,
+    pub format: ReportFormat,
+    pub output: Option<PathBuf>,
+}
+
+/// How files present in both trees are compared. Files with different sizes are always changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompareMode {
+    /// Only the size.
+    Size,
+    /// Size and modification time (within [`MTIME_TOLERANCE_SECS`]).
+    #[default]
+    Mtime,
+    /// Size and content (BLAKE3). Slower, since every file of the same size is read.
+    Hash,
+}
+
+impl CompareMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompareMode::Size => "size",
+            CompareMode::Mtime => "mtime",
+            CompareMode::Hash => "hash",
+        }
+    }
+}
+
+impl FromStr for CompareMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "size" => Ok(CompareMode::Size),
+            "mtime" | "time" => Ok(CompareMode::Mtime),
+            "hash" | "content" => Ok(CompareMode::Hash),
+            other => Err(anyhow::anyhow!(
+                "Invalid comparison: {}. Valid comparisons: size, mtime, hash",
+                other
+            )),
+        }
+    }
+}
+
+/// Modification times this close are the same. FAT (and so most USB drives) stores them with
+/// 2 seconds of resolution.
+pub const MTIME_TOLERANCE_SECS: u64 = 2;
+
+/// A file found while scanning a tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileEntry {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Only in the source.
+    Added,
+    /// Only in the target.
+    Removed,
+    /// In both, but different.
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        }
+    }
+}
+
+/// A difference between the trees. `path` is relative to both roots, with `/`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileChange {
+    pub kind: ChangeKind,
+    pub path: String,
+    pub source_size: Option<u64>,
+    pub target_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffReport {
+    pub source: String,
+    pub target: String,
+    pub compare: CompareMode,
+    pub changes: Vec<FileChange>,
+    pub unchanged: usize,
+}
+
+impl DiffReport {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .count()
+    }
+}
+
+/// What `--apply` did.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncSummary {
+    pub copied: usize,
+    pub deleted: usize,
+}
@@ -0,0 +1,97 @@
+use crate::models::{ChangeKind, DiffReport};
+use anyhow::Result;
+use shared::utils::format_bytes_to_string::format_bytes_to_string;
+use shared::utils::table_output::{render_json, ReportFormat, Table};
+
+/// Renders the differences as a table (text or markdown) followed by the totals, or the whole
+/// report as JSON.
+pub fn render_report(report: &DiffReport, format: ReportFormat) -> Result<String> {
+    if format == ReportFormat::Json {
+        return render_json(report);
+    }
+
+    let mut output = String::new();
+
+    if format == ReportFormat::Markdown {
+        output.push_str(&format!(
+            "# Differences: {} -> {}\n\nCompared by {}.\n\n",
+            report.source,
+            report.target,
+            report.compare.name()
+        ));
+    }
+
+    let mut table = Table::new(&["CHANGE", "PATH", "SOURCE SIZE", "TARGET SIZE"])
+        .with_title("Differences")
+        .align_right(2)
+        .align_right(3);
+    for change in &report.changes {
+        table.add_row(vec![
+            change.kind.name().to_string(),
+            change.path.clone(),
+            format_size(change.source_size),
+            format_size(change.target_size),
+        ]);
+    }
+
+    output.push_str(&table.render(format));
+    output.push('\n');
+    output.push_str(&format!("{}\n", summary_line(report)));
+
+    Ok(output)
+}
+
+pub fn summary_line(report: &DiffReport) -> String {
+    format!(
+        "{} added, {} removed, {} changed, {} unchanged.",
+        report.count(ChangeKind::Added),
+        report.count(ChangeKind::Removed),
+        report.count(ChangeKind::Changed),
+        report.unchanged
+    )
+}
+
+fn format_size(size: Option<u64>) -> String {
+    size.map(|size| format_bytes_to_string(&size))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CompareMode, FileChange};
+
+    #[test]
+    fn test_render_report() {
+        let report = DiffReport {
+            source: "src".to_string(),
+            target: "backup".to_string(),
+            compare: CompareMode::Hash,
+            changes: vec![
+                FileChange {
+                    kind: ChangeKind::Added,
+                    path: "a.txt".to_string(),
+                    source_size: Some(10),
+                    target_size: None,
+                },
+                FileChange {
+                    kind: ChangeKind::Changed,
+                    path: "b.txt".to_string(),
+                    source_size: Some(3),
+                    target_size: Some(4),
+                },
+            ],
+            unchanged: 7,
+        };
+
+        let text = render_report(&report, ReportFormat::Table).unwrap();
+        assert!(text.starts_with("Differences\n"));
+        assert!(text.contains("a.txt"));
+        assert!(text.ends_with("1 added, 0 removed, 1 changed, 7 unchanged.\n"));
+
+        let json = render_report(&report, ReportFormat::Json).unwrap();
+        assert!(json.contains("\"kind\": \"added\""));
+        assert!(json.contains("\"compare\": \"hash\""));
+        assert!(json.contains("\"target_size\": null"));
+    }
+}
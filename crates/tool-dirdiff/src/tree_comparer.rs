@@ -0,0 +1,211 @@
+use crate::models::{ChangeKind, CompareMode, FileChange, FileEntry, MTIME_TOLERANCE_SECS};
+use anyhow::{Context, Result};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Compares the trees, returning the changes (sorted by path) and the number of unchanged files.
+///
+/// With [`CompareMode::Hash`], the files of the same size are hashed in parallel.
+pub fn compare_trees(
+    source_root: &Path,
+    source: &BTreeMap<String, FileEntry>,
+    target_root: &Path,
+    target: &BTreeMap<String, FileEntry>,
+    mode: CompareMode,
+) -> Result<(Vec<FileChange>, usize)> {
+    let mut changes = Vec::new();
+    let mut to_hash = Vec::new();
+
+    for (path, source_entry) in source {
+        let Some(target_entry) = target.get(path) else {
+            changes.push(change(ChangeKind::Added, path, Some(source_entry), None));
+            continue;
+        };
+
+        if source_entry.size != target_entry.size {
+            changes.push(change(
+                ChangeKind::Changed,
+                path,
+                Some(source_entry),
+                Some(target_entry),
+            ));
+            continue;
+        }
+
+        match mode {
+            CompareMode::Size => {}
+            CompareMode::Mtime => {
+                if !same_mtime(source_entry.modified, target_entry.modified) {
+                    changes.push(change(
+                        ChangeKind::Changed,
+                        path,
+                        Some(source_entry),
+                        Some(target_entry),
+                    ));
+                }
+            }
+            CompareMode::Hash => to_hash.push((path, source_entry, target_entry)),
+        }
+    }
+
+    let hashed: Vec<Result<Option<FileChange>>> = to_hash
+        .into_par_iter()
+        .map(|(path, source_entry, target_entry)| {
+            let same = hash_file(&source_root.join(path))? == hash_file(&target_root.join(path))?;
+            Ok((!same).then(|| {
+                change(
+                    ChangeKind::Changed,
+                    path,
+                    Some(source_entry),
+                    Some(target_entry),
+                )
+            }))
+        })
+        .collect();
+    for result in hashed {
+        changes.extend(result?);
+    }
+
+    changes.extend(
+        target
+            .iter()
+            .filter(|(path, _)| !source.contains_key(*path))
+            .map(|(path, target_entry)| {
+                change(ChangeKind::Removed, path, None, Some(target_entry))
+            }),
+    );
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    let unchanged = source.len() - (changes.len() - count_removed(&changes));
+
+    Ok((changes, unchanged))
+}
+
+fn change(
+    kind: ChangeKind,
+    path: &str,
+    source: Option<&FileEntry>,
+    target: Option<&FileEntry>,
+) -> FileChange {
+    FileChange {
+        kind,
+        path: path.to_string(),
+        source_size: source.map(|entry| entry.size),
+        target_size: target.map(|entry| entry.size),
+    }
+}
+
+fn count_removed(changes: &[FileChange]) -> usize {
+    changes
+        .iter()
+        .filter(|change| change.kind == ChangeKind::Removed)
+        .count()
+}
+
+/// Times within [`MTIME_TOLERANCE_SECS`] are the same. Files without a time (not supported by
+/// the file system) are always changed.
+fn same_mtime(source: Option<SystemTime>, target: Option<SystemTime>) -> bool {
+    let (Some(source), Some(target)) = (source, target) else {
+        return false;
+    };
+
+    let difference = source
+        .duration_since(target)
+        .or_else(|_| target.duration_since(source))
+        .unwrap_or_default();
+
+    difference.as_secs() < MTIME_TOLERANCE_SECS
+}
+
+fn hash_file(path: &Path) -> Result<blake3::Hash> {
+    let mut file = File::open(path).context(format!("Failed to open {}", path.display()))?;
+    let mut hasher = blake3::Hasher::new();
+    std::io::copy(&mut file, &mut hasher).context(format!("Failed to read {}", path.display()))?;
+
+    Ok(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree_scanner::scan_tree;
+    use filetime::{set_file_mtime, FileTime};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_compare_trees_by_mode() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        let write = |root: &Path, name: &str, content: &str, mtime: i64| {
+            let path = root.join(name);
+            fs::write(&path, content).unwrap();
+            set_file_mtime(&path, FileTime::from_unix_time(mtime, 0)).unwrap();
+        };
+
+        write(source.path(), "same.txt", "same", 1_000);
+        write(target.path(), "same.txt", "same", 1_001);
+        write(source.path(), "touched.txt", "abc", 1_000);
+        write(target.path(), "touched.txt", "abc", 5_000);
+        write(source.path(), "edited.txt", "new", 1_000);
+        write(target.path(), "edited.txt", "old", 1_000);
+        write(source.path(), "resized.txt", "longer", 1_000);
+        write(target.path(), "resized.txt", "short", 1_000);
+        write(source.path(), "added.txt", "a", 1_000);
+        write(target.path(), "removed.txt", "r", 1_000);
+
+        let source_files = scan_tree(source.path()).unwrap();
+        let target_files = scan_tree(target.path()).unwrap();
+        let changed = |mode| {
+            let (changes, unchanged) = compare_trees(
+                source.path(),
+                &source_files,
+                target.path(),
+                &target_files,
+                mode,
+            )
+            .unwrap();
+            let paths: Vec<String> = changes
+                .iter()
+                .map(|change| format!("{} {}", change.kind.name(), change.path))
+                .collect();
+            (paths, unchanged)
+        };
+
+        assert_eq!(
+            changed(CompareMode::Size),
+            (
+                vec![
+                    "added added.txt".to_string(),
+                    "removed removed.txt".to_string(),
+                    "changed resized.txt".to_string(),
+                ],
+                3
+            )
+        );
+        assert_eq!(
+            changed(CompareMode::Mtime).0,
+            vec![
+                "added added.txt",
+                "removed removed.txt",
+                "changed resized.txt",
+                "changed touched.txt",
+            ]
+        );
+        assert_eq!(
+            changed(CompareMode::Hash),
+            (
+                vec![
+                    "added added.txt".to_string(),
+                    "changed edited.txt".to_string(),
+                    "removed removed.txt".to_string(),
+                    "changed resized.txt".to_string(),
+                ],
+                2
+            )
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::models::FileEntry;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Lists the files of the tree, by their path relative to `root` (with `/`, so trees compare the
+/// same on every OS). Symlinks aren't followed, and a missing root is an empty tree.
+pub fn scan_tree(root: &Path) -> Result<BTreeMap<String, FileEntry>> {
+    let mut files = BTreeMap::new();
+    if !root.exists() {
+        return Ok(files);
+    }
+
+    for entry in WalkDir::new(root) {
+        let entry = entry.context(format!("Failed to scan {}", root.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let metadata = entry
+            .metadata()
+            .context(format!("Failed to read {}", entry.path().display()))?;
+        let relative = entry.path().strip_prefix(root).context(format!(
+            "{} is outside {}",
+            entry.path().display(),
+            root.display()
+        ))?;
+
+        files.insert(
+            relative_name(relative),
+            FileEntry {
+                size: metadata.len(),
+                modified: metadata.modified().ok(),
+            },
+        );
+    }
+
+    Ok(files)
+}
+
+fn relative_name(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scan_tree_lists_files_relative_to_the_root() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs").join("empty")).unwrap();
+        fs::write(dir.path().join("a.txt"), "abc").unwrap();
+        fs::write(dir.path().join("docs").join("b.md"), "b").unwrap();
+
+        let files = scan_tree(dir.path()).unwrap();
+
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec!["a.txt", "docs/b.md"]);
+        assert_eq!(files["a.txt"].size, 3);
+        assert!(scan_tree(&dir.path().join("missing")).unwrap().is_empty());
+    }
+}
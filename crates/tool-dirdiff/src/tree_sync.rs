@@ -0,0 +1,142 @@
+use crate::models::{ChangeKind, FileChange, SyncSummary};
+use anyhow::{Context, Result};
+use filetime::{set_file_mtime, FileTime};
+use std::fs;
+use std::path::Path;
+
+/// Makes the target match the source: copies the added and changed files (keeping their
+/// modification times, so the next `mtime` comparison sees them as unchanged) and, with
+/// `delete`, removes the files only in the target, and the folders left empty.
+///
+/// `on_action` is called with each file copied or deleted. Stops at the first error.
+pub fn sync_trees(
+    changes: &[FileChange],
+    source_root: &Path,
+    target_root: &Path,
+    delete: bool,
+    mut on_action: impl FnMut(ChangeKind, &str),
+) -> Result<SyncSummary> {
+    let mut summary = SyncSummary::default();
+
+    for change in changes {
+        match change.kind {
+            ChangeKind::Added | ChangeKind::Changed => {
+                copy_file(
+                    &source_root.join(&change.path),
+                    &target_root.join(&change.path),
+                )?;
+                summary.copied += 1;
+            }
+            ChangeKind::Removed if delete => {
+                delete_file(target_root, &change.path)?;
+                summary.deleted += 1;
+            }
+            ChangeKind::Removed => continue,
+        }
+
+        on_action(change.kind, &change.path);
+    }
+
+    Ok(summary)
+}
+
+fn copy_file(source: &Path, target: &Path) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .context(format!("Failed to create the folder {}", parent.display()))?;
+    }
+
+    fs::copy(source, target).context(format!(
+        "Failed to copy {} to {}",
+        source.display(),
+        target.display()
+    ))?;
+
+    let metadata = fs::metadata(source).context(format!("Failed to read {}", source.display()))?;
+    set_file_mtime(target, FileTime::from_last_modification_time(&metadata)).context(format!(
+        "Failed to set the modification time of {}",
+        target.display()
+    ))?;
+
+    Ok(())
+}
+
+/// Removes the file, then its parent folders while they are empty (up to, not including, the
+/// root).
+fn delete_file(root: &Path, relative: &str) -> Result<()> {
+    let path = root.join(relative);
+    fs::remove_file(&path).context(format!("Failed to delete {}", path.display()))?;
+
+    let mut folder = path.parent();
+    while let Some(current) = folder {
+        if current == root || fs::remove_dir(current).is_err() {
+            break;
+        }
+        folder = current.parent();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CompareMode;
+    use crate::tree_comparer::compare_trees;
+    use crate::tree_scanner::scan_tree;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sync_makes_the_target_match_the_source() {
+        let source = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        fs::create_dir_all(source.path().join("new").join("deep")).unwrap();
+        fs::create_dir_all(target.path().join("old")).unwrap();
+        fs::write(source.path().join("new").join("deep").join("a.txt"), "a").unwrap();
+        fs::write(source.path().join("b.txt"), "new content").unwrap();
+        fs::write(target.path().join("b.txt"), "old").unwrap();
+        fs::write(target.path().join("old").join("c.txt"), "c").unwrap();
+
+        let diff = |mode| {
+            compare_trees(
+                source.path(),
+                &scan_tree(source.path()).unwrap(),
+                target.path(),
+                &scan_tree(target.path()).unwrap(),
+                mode,
+            )
+            .unwrap()
+        };
+
+        let (changes, _) = diff(CompareMode::Mtime);
+        let mut actions = Vec::new();
+        let summary = sync_trees(
+            &changes,
+            source.path(),
+            target.path(),
+            false,
+            |kind, path| actions.push(format!("{} {}", kind.name(), path)),
+        )
+        .unwrap();
+        assert_eq!(
+            summary,
+            SyncSummary {
+                copied: 2,
+                deleted: 0
+            }
+        );
+        assert_eq!(actions, vec!["changed b.txt", "added new/deep/a.txt"]);
+        assert!(target.path().join("old").join("c.txt").exists());
+
+        let (changes, _) = diff(CompareMode::Mtime);
+        assert_eq!(changes.len(), 1);
+        let summary = sync_trees(&changes, source.path(), target.path(), true, |_, _| {}).unwrap();
+        assert_eq!(summary.deleted, 1);
+        assert!(!target.path().join("old").exists());
+        assert!(target.path().exists());
+
+        let (changes, unchanged) = diff(CompareMode::Hash);
+        assert!(changes.is_empty());
+        assert_eq!(unchanged, 2);
+    }
+}
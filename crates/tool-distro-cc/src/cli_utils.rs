@@ -4,9 +4,18 @@ use clap::{Arg, ArgAction, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
 use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
 
 /// Displays runtime configuration information.
-pub fn print_runtime_info(from: &str, to: &str, command: &str, no_header: bool, verbose: bool) {
+pub fn print_runtime_info(
+    from: &str,
+    to: &str,
+    command: &str,
+    no_header: bool,
+    verbose: bool,
+    offline: bool,
+) {
     println!("Distro-cc v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
     println!("- From: {}", from);
@@ -14,6 +23,7 @@ pub fn print_runtime_info(from: &str, to: &str, command: &str, no_header: bool,
     println!("- Command: {}", command);
     println!("- No header: {}", no_header);
     println!("- Verbose: {}", verbose);
+    println!("- Offline: {}", offline);
     println!(
         "Warning: package managers are not fully equivalent; flags and package names can differ.\n\n"
     );
@@ -23,7 +33,7 @@ pub fn print_runtime_info(from: &str, to: &str, command: &str, no_header: bool,
 ///
 /// # Errors
 /// Returns error if arguments are invalid or missing.
-pub fn get_cli_arguments() -> Result<DistroCcRuntimeConfig> {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<DistroCcRuntimeConfig> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -53,9 +63,25 @@ pub fn get_cli_arguments() -> Result<DistroCcRuntimeConfig> {
                 .num_args(1..)
                 .trailing_var_arg(true)
                 .action(ArgAction::Append)
-                .required(true)
+                .required_unless_present("script")
+                .conflicts_with("script")
                 .help("Command to be converted"),
         )
+        .arg(
+            Arg::new("script")
+                .long("script")
+                .short('s')
+                .value_name("FILE")
+                .help("Shell script to convert: every package manager command in it is converted, and the changed lines are marked with comments"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_name("FILE")
+                .requires("script")
+                .help("With --script, writes the converted script to FILE instead of stdout"),
+        )
         .arg(
             Arg::new("no-header")
                 .long("no-header")
@@ -63,6 +89,12 @@ pub fn get_cli_arguments() -> Result<DistroCcRuntimeConfig> {
                 .action(ArgAction::SetTrue)
                 .help("If true, suppresses the header output"),
         )
+        .arg(
+            Arg::new("offline")
+                .long("offline")
+                .action(ArgAction::SetTrue)
+                .help("Never calls the AI: uses the offline rules when the internal map can't convert the command"),
+        )
         .arg(
             Arg::new("verbose")
                 .long("verbose")
@@ -70,7 +102,7 @@ pub fn get_cli_arguments() -> Result<DistroCcRuntimeConfig> {
                 .action(ArgAction::SetTrue)
                 .help("If true, logs what the app is doing"),
         )
-        .get_matches();
+        .get_matches_from(argv);
 
     let from = matches
         .get_one::<String>("from")
@@ -85,7 +117,10 @@ pub fn get_cli_arguments() -> Result<DistroCcRuntimeConfig> {
         .cloned()
         .collect::<Vec<String>>();
 
-    if command_parts.is_empty() {
+    let script = matches.get_one::<String>("script").map(PathBuf::from);
+    let output = matches.get_one::<String>("output").map(PathBuf::from);
+
+    if command_parts.is_empty() && script.is_none() {
         return Err(anyhow::anyhow!(
             "No command provided. Use --command to pass the command to convert."
         ));
@@ -94,8 +129,9 @@ pub fn get_cli_arguments() -> Result<DistroCcRuntimeConfig> {
     let command = command_parts.join(" ");
     let no_header = matches.get_flag("no-header");
     let verbose = matches.get_flag("verbose");
+    let offline = matches.get_flag("offline");
 
     Ok(DistroCcRuntimeConfig::new(
-        from, to, command, no_header, verbose,
+        from, to, command, no_header, verbose, offline, script, output,
     ))
 }
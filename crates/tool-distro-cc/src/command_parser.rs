@@ -1,4 +1,6 @@
+use crate::models::DistroFamily;
 use anyhow::{Context, Result};
+use std::ops::Range;
 
 pub fn parse_command_parts(command: &str) -> Result<Vec<String>> {
     let trimmed = command.trim();
@@ -8,3 +10,108 @@ pub fn parse_command_parts(command: &str) -> Result<Vec<String>> {
 
     shell_words::split(trimmed).context("Failed to parse command into arguments")
 }
+
+/// Byte ranges of the commands in a shell line, split on `&&`, `||`, `;` and `|` (outside
+/// quotes). Ranges are trimmed, and empty commands are left out.
+pub fn split_command_segments(line: &str) -> Vec<Range<usize>> {
+    let bytes = line.as_bytes();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+    let mut quote: Option<u8> = None;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match quote {
+            Some(open) => {
+                if byte == b'\\' && open == b'"' {
+                    index += 1;
+                } else if byte == open {
+                    quote = None;
+                }
+            }
+            None => match byte {
+                b'\\' => index += 1,
+                b'\'' | b'"' => quote = Some(byte),
+                b'#' if index == 0 || bytes[index - 1].is_ascii_whitespace() => break,
+                b';' | b'|' | b'&' => {
+                    let doubled = bytes.get(index + 1) == Some(&byte) && byte != b';';
+                    if byte == b'&' && !doubled {
+                        // A single `&` (background, or part of a redirection) isn't a separator.
+                        index += 1;
+                        continue;
+                    }
+                    push_trimmed(line, start..index, &mut segments);
+                    index += if doubled { 2 } else { 1 };
+                    start = index;
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+
+    push_trimmed(line, start..index.min(bytes.len()), &mut segments);
+    segments
+}
+
+fn push_trimmed(line: &str, range: Range<usize>, segments: &mut Vec<Range<usize>>) {
+    let text = &line[range.clone()];
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    let start = range.start + (text.len() - text.trim_start().len());
+    segments.push(start..start + trimmed.len());
+}
+
+/// Whether the command (optionally after `sudo`) runs one of the package managers of the family.
+pub fn is_package_manager_command(parts: &[String], family: DistroFamily) -> bool {
+    let base = match parts {
+        [sudo, base, ..] if sudo == "sudo" => base,
+        [base, ..] => base,
+        [] => return false,
+    };
+
+    family
+        .package_managers()
+        .contains(&base.to_lowercase().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(line: &str) -> Vec<&str> {
+        split_command_segments(line)
+            .into_iter()
+            .map(|range| &line[range])
+            .collect()
+    }
+
+    #[test]
+    fn splits_commands_outside_quotes_and_comments() {
+        assert_eq!(
+            segments("  sudo apt update && sudo apt install -y git; echo 'a && b' # apt remove x"),
+            vec![
+                "sudo apt update",
+                "sudo apt install -y git",
+                "echo 'a && b'"
+            ]
+        );
+        assert_eq!(
+            segments("apt list --installed | grep \"x|y\" || true"),
+            vec!["apt list --installed", "grep \"x|y\"", "true"]
+        );
+        assert_eq!(segments("make > log 2>&1 &"), vec!["make > log 2>&1 &"]);
+    }
+
+    #[test]
+    fn detects_package_manager_commands() {
+        let parts = parse_command_parts("sudo apt-get install git").unwrap();
+        assert!(is_package_manager_command(&parts, DistroFamily::Debian));
+        assert!(!is_package_manager_command(&parts, DistroFamily::Arch));
+    }
+}
@@ -2,14 +2,17 @@ use crate::ai_functions::convert_distro_command_as_string;
 use crate::cli_utils::print_runtime_info;
 use crate::command_parser::parse_command_parts;
 use crate::distro_detect::{detect_target_distro, normalize_distro_name};
-use crate::distro_map::convert_parts_with_map;
+use crate::distro_map::{convert_parts_with_map, convert_parts_with_rules};
 use crate::models::{DistroCcRuntimeConfig, DistroFamily};
+use crate::script_converter::{annotate_script, collect_commands};
 use ai_shared::message_builders::system_message_builders::{
     build_rust_ai_function_system_message, build_rust_ai_function_user_message,
 };
 use ai_shared::request_wrappers::requester_builders::build_requester_for_ai;
 use ai_shared::request_wrappers::requester_traits::OpenAiRequesterTraits;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
 
 pub async fn start_distro_cc_app(config: DistroCcRuntimeConfig) -> Result<()> {
     let from_family = normalize_distro_name(&config.from)
@@ -21,6 +24,11 @@ pub async fn start_distro_cc_app(config: DistroCcRuntimeConfig) -> Result<()> {
         None => detect_target_distro()?,
     };
 
+    // The converted script goes to stdout, so there's no header in this mode.
+    if let Some(script) = config.script.as_ref() {
+        return convert_script_file(&config, script, from_family, to_family).await;
+    }
+
     if !config.no_header {
         print_runtime_info(
             from_family.as_str(),
@@ -28,24 +36,18 @@ pub async fn start_distro_cc_app(config: DistroCcRuntimeConfig) -> Result<()> {
             &config.command,
             config.no_header,
             config.verbose,
+            config.offline,
         );
     }
 
-    if config.verbose {
-        eprintln!("Attempting conversion using internal map...");
-    }
-
-    let mapped_result =
-        try_convert_with_map(from_family, to_family, &config.command, config.verbose)?;
-
-    let final_result = if let Some(mapped) = mapped_result {
-        mapped
-    } else {
-        if config.verbose {
-            eprintln!("Internal map miss. Falling back to AI.");
-        }
-        convert_with_ai(from_family, to_family, &config.command).await?
-    };
+    let final_result = convert_command(
+        from_family,
+        to_family,
+        &config.command,
+        config.offline,
+        config.verbose,
+    )
+    .await?;
 
     if config.no_header {
         println!("{}", final_result);
@@ -57,6 +59,107 @@ pub async fn start_distro_cc_app(config: DistroCcRuntimeConfig) -> Result<()> {
     Ok(())
 }
 
+/// Internal map first; then the AI (unless `offline`), and the offline rules when the AI isn't
+/// available or fails.
+async fn convert_command(
+    from: DistroFamily,
+    to: DistroFamily,
+    command: &str,
+    offline: bool,
+    verbose: bool,
+) -> Result<String> {
+    if verbose {
+        eprintln!("Attempting conversion using internal map...");
+    }
+
+    if let Some(mapped) = try_convert_with_map(from, to, command, verbose)? {
+        return Ok(mapped);
+    }
+
+    if offline {
+        if verbose {
+            eprintln!("Internal map miss. Using the offline rules.");
+        }
+        return convert_with_rules(from, to, command);
+    }
+
+    if verbose {
+        eprintln!("Internal map miss. Falling back to AI.");
+    }
+
+    match convert_with_ai(from, to, command).await {
+        Ok(converted) => Ok(converted),
+        Err(err) => {
+            eprintln!(
+                "Warning: AI conversion unavailable ({:#}). Using the offline rules.",
+                err
+            );
+            convert_with_rules(from, to, command)
+        }
+    }
+}
+
+/// Converts every package manager command of the script, and writes the annotated result.
+async fn convert_script_file(
+    config: &DistroCcRuntimeConfig,
+    path: &Path,
+    from: DistroFamily,
+    to: DistroFamily,
+) -> Result<()> {
+    let script = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read script '{}'", path.display()))?;
+
+    // Checked once, instead of warning for every command the map can't convert.
+    let offline = config.offline
+        || match build_requester_for_ai() {
+            Ok(_) => false,
+            Err(err) => {
+                eprintln!(
+                    "Warning: AI not configured ({:#}). Using the offline rules.",
+                    err
+                );
+                true
+            }
+        };
+
+    let mut conversions = HashMap::new();
+    for command in collect_commands(&script, from) {
+        match convert_command(from, to, &command, offline, config.verbose).await {
+            Ok(converted) => {
+                conversions.insert(command, converted);
+            }
+            Err(err) => {
+                if config.verbose {
+                    eprintln!("Could not convert '{}': {}", command, err);
+                }
+            }
+        }
+    }
+
+    let result = annotate_script(&script, from, &conversions);
+
+    match config.output.as_ref() {
+        Some(output) => std::fs::write(output, &result.script)
+            .with_context(|| format!("Failed to write '{}'", output.display()))?,
+        None => print!("{}", result.script),
+    }
+
+    eprintln!(
+        "Converted {} command(s) from {} to {}.",
+        result.converted,
+        from.as_str(),
+        to.as_str()
+    );
+    if result.unconverted > 0 {
+        eprintln!(
+            "{} command(s) could not be converted. They are marked with '# distro-cc: could not convert'.",
+            result.unconverted
+        );
+    }
+
+    Ok(())
+}
+
 fn try_convert_with_map(
     from: DistroFamily,
     to: DistroFamily,
@@ -76,8 +179,23 @@ fn try_convert_with_map(
     Ok(convert_parts_with_map(from, to, &parts))
 }
 
+/// Offline rule-based conversion. Prints what couldn't be carried over to stderr.
+fn convert_with_rules(from: DistroFamily, to: DistroFamily, command: &str) -> Result<String> {
+    let parts = parse_command_parts(command)?;
+    let converted = convert_parts_with_rules(from, to, &parts).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No offline rule converts this command. Configure the AI (AI_PLATFORM) to convert it."
+        )
+    })?;
+
+    for note in &converted.notes {
+        eprintln!("Note: {}", note);
+    }
+
+    Ok(converted.command)
+}
+
 async fn convert_with_ai(from: DistroFamily, to: DistroFamily, command: &str) -> Result<String> {
-    eprintln!("Warning: using AI fallback; the converted command may be imperfect.");
     let input = format!(
         "command: {}\nfrom_distro: {}\nto_distro: {}",
         command,
@@ -95,6 +213,7 @@ async fn convert_with_ai(from: DistroFamily, to: DistroFamily, command: &str) ->
         .set_system_message(system_message)?
         .initialize_api_client()?;
 
+    eprintln!("Warning: using AI fallback; the converted command may be imperfect.");
     let response = requester
         .send_request(user_message, false)
         .await
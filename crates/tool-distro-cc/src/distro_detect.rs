@@ -31,6 +31,24 @@ pub fn normalize_distro_name(input: &str) -> Option<DistroFamily> {
         return Some(DistroFamily::Arch);
     }
 
+    if value.contains("fedora")
+        || value.contains("rhel")
+        || value.contains("red hat")
+        || value.contains("redhat")
+        || value.contains("centos")
+        || value.contains("rocky")
+        || value.contains("alma")
+        || value.contains("nobara")
+        || value.contains("dnf")
+        || value.contains("yum")
+    {
+        return Some(DistroFamily::Fedora);
+    }
+
+    if value.contains("suse") || value.contains("sles") || value.contains("zypper") {
+        return Some(DistroFamily::Suse);
+    }
+
     None
 }
 
@@ -106,4 +124,22 @@ ID_LIKE=archlinux
         let distro = parse_os_release_content(content);
         assert_eq!(distro, Some(DistroFamily::Arch));
     }
+
+    #[test]
+    fn parse_os_release_fedora_and_suse() {
+        let content = r#"
+ID="rocky"
+ID_LIKE="rhel centos fedora"
+"#;
+        assert_eq!(
+            parse_os_release_content(content),
+            Some(DistroFamily::Fedora)
+        );
+
+        let content = r#"
+ID="opensuse-tumbleweed"
+ID_LIKE="opensuse suse"
+"#;
+        assert_eq!(parse_os_release_content(content), Some(DistroFamily::Suse));
+    }
 }
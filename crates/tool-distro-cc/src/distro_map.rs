@@ -10,19 +10,81 @@ enum CanonicalAction {
     UpdateUpgrade,
 }
 
+impl CanonicalAction {
+    /// Actions that change packages, where "assume yes" makes sense.
+    fn changes_packages(&self) -> bool {
+        matches!(
+            self,
+            CanonicalAction::Install | CanonicalAction::Remove | CanonicalAction::Upgrade
+        )
+    }
+}
+
 #[derive(Debug)]
 struct ParsedCommand {
     action: CanonicalAction,
     args: Vec<String>,
+    /// Option tokens (`-y`, `--noconfirm`, ...), wherever they were in the command.
+    options: Vec<String>,
 }
 
+/// Result of the offline rule-based conversion.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RuleConversion {
+    pub command: String,
+    /// What couldn't be carried over (e.g.: options without a known equivalent).
+    pub notes: Vec<String>,
+}
+
+/// Strict conversion: only plain commands (no options) are converted. Anything else is left to
+/// the AI, or to the offline rules.
 pub fn convert_parts_with_map(
     from: DistroFamily,
     to: DistroFamily,
     parts: &[String],
 ) -> Option<String> {
     let parsed = parse_command(from, parts)?;
-    build_command(to, parsed)
+    if !parsed.options.is_empty() {
+        return None;
+    }
+
+    Some(build_command(to, &parsed, false).join(" && "))
+}
+
+/// Rule-based conversion, used when the AI isn't configured or fails.
+///
+/// More lenient than `convert_parts_with_map`: a leading `sudo` is kept, "assume yes" options are
+/// translated (`-y`, `--noconfirm`, `--non-interactive`), and other options are dropped, with a
+/// note for each one.
+pub fn convert_parts_with_rules(
+    from: DistroFamily,
+    to: DistroFamily,
+    parts: &[String],
+) -> Option<RuleConversion> {
+    let (sudo, parts) = match parts.split_first() {
+        Some((first, rest)) if first == "sudo" => (true, rest),
+        _ => (false, parts),
+    };
+
+    let parsed = parse_command(from, parts)?;
+
+    let mut notes = Vec::new();
+    let mut assume_yes = false;
+    for option in &parsed.options {
+        if is_assume_yes_option(from, option) {
+            assume_yes = true;
+        } else {
+            notes.push(format!("Dropped option {} (no known equivalent)", option));
+        }
+    }
+
+    let command = build_command(to, &parsed, assume_yes)
+        .into_iter()
+        .map(|step| if sudo { format!("sudo {}", step) } else { step })
+        .collect::<Vec<_>>()
+        .join(" && ");
+
+    Some(RuleConversion { command, notes })
 }
 
 fn parse_command(from: DistroFamily, parts: &[String]) -> Option<ParsedCommand> {
@@ -30,141 +92,263 @@ fn parse_command(from: DistroFamily, parts: &[String]) -> Option<ParsedCommand>
         return None;
     }
 
-    match from {
+    let parsed = match from {
         DistroFamily::Debian => parse_debian_command(parts),
         DistroFamily::Arch => parse_arch_command(parts),
-    }
+        DistroFamily::Fedora => parse_fedora_command(parts),
+        DistroFamily::Suse => parse_suse_command(parts),
+    }?;
+
+    validate_args(parsed)
 }
 
-fn parse_debian_command(parts: &[String]) -> Option<ParsedCommand> {
-    let base = parts.get(0)?.to_lowercase();
-    if base != "apt" && base != "apt-get" {
-        return None;
-    }
+/// Splits the tokens after the package manager into the subcommand (the first non-option
+/// token), its arguments, and the options.
+fn split_subcommand(tokens: &[String]) -> Option<(String, Vec<String>, Vec<String>)> {
+    let (options, rest): (Vec<&String>, Vec<&String>) =
+        tokens.iter().partition(|token| token.starts_with('-'));
 
-    let action_token = parts.get(1)?.to_lowercase();
-    if action_token.starts_with('-') {
-        return None;
-    }
+    let (subcommand, args) = rest.split_first()?;
+    Some((
+        subcommand.to_lowercase(),
+        args.iter().map(|arg| arg.to_string()).collect(),
+        options.into_iter().cloned().collect(),
+    ))
+}
 
-    let args = parts.get(2..).unwrap_or(&[]).to_vec();
-    if has_option_tokens(&args) {
+fn parse_debian_command(parts: &[String]) -> Option<ParsedCommand> {
+    let base = parts.first()?.to_lowercase();
+    if base != "apt" && base != "apt-get" {
         return None;
     }
 
-    let action = match action_token.as_str() {
+    let (subcommand, args, options) = split_subcommand(&parts[1..])?;
+    let action = match subcommand.as_str() {
         "install" => CanonicalAction::Install,
         "remove" | "purge" => CanonicalAction::Remove,
         "search" => CanonicalAction::Search,
         "update" => CanonicalAction::Update,
-        "upgrade" | "dist-upgrade" => CanonicalAction::Upgrade,
+        "upgrade" | "dist-upgrade" | "full-upgrade" => CanonicalAction::Upgrade,
         _ => return None,
     };
 
-    if matches!(action, CanonicalAction::Update | CanonicalAction::Upgrade) && !args.is_empty() {
+    Some(ParsedCommand {
+        action,
+        args,
+        options,
+    })
+}
+
+fn parse_fedora_command(parts: &[String]) -> Option<ParsedCommand> {
+    let base = parts.first()?.to_lowercase();
+    if base != "dnf" && base != "dnf5" && base != "yum" {
         return None;
     }
 
-    if matches!(
+    let (subcommand, args, options) = split_subcommand(&parts[1..])?;
+    let action = match subcommand.as_str() {
+        "install" => CanonicalAction::Install,
+        "remove" | "erase" => CanonicalAction::Remove,
+        "search" => CanonicalAction::Search,
+        "makecache" | "check-update" => CanonicalAction::Update,
+        "upgrade" | "update" | "distro-sync" => CanonicalAction::Upgrade,
+        _ => return None,
+    };
+
+    Some(ParsedCommand {
         action,
-        CanonicalAction::Install | CanonicalAction::Remove | CanonicalAction::Search
-    ) && args.is_empty()
-    {
+        args,
+        options,
+    })
+}
+
+fn parse_suse_command(parts: &[String]) -> Option<ParsedCommand> {
+    let base = parts.first()?.to_lowercase();
+    if base != "zypper" {
         return None;
     }
 
-    Some(ParsedCommand { action, args })
+    let (subcommand, args, options) = split_subcommand(&parts[1..])?;
+    let action = match subcommand.as_str() {
+        "install" | "in" => CanonicalAction::Install,
+        "remove" | "rm" => CanonicalAction::Remove,
+        "search" | "se" => CanonicalAction::Search,
+        "refresh" | "ref" => CanonicalAction::Update,
+        "update" | "up" | "dist-upgrade" | "dup" => CanonicalAction::Upgrade,
+        _ => return None,
+    };
+
+    Some(ParsedCommand {
+        action,
+        args,
+        options,
+    })
 }
 
 fn parse_arch_command(parts: &[String]) -> Option<ParsedCommand> {
-    let base = parts.get(0)?.to_lowercase();
+    let base = parts.first()?.to_lowercase();
     if base != "pacman" {
         return None;
     }
 
     let flag = parts.get(1)?.to_lowercase();
-    let args = parts.get(2..).unwrap_or(&[]).to_vec();
-
-    if has_option_tokens(&args) {
-        return None;
-    }
+    let (options, args): (Vec<String>, Vec<String>) = parts
+        .get(2..)
+        .unwrap_or(&[])
+        .iter()
+        .cloned()
+        .partition(|token| token.starts_with('-'));
 
     let action = match flag.as_str() {
         "-s" | "--sync" => CanonicalAction::Install,
-        "-r" | "--remove" => CanonicalAction::Remove,
+        "-r" | "--remove" | "-rs" | "-rns" | "-rsn" => CanonicalAction::Remove,
         "-ss" => CanonicalAction::Search,
-        "-sy" => CanonicalAction::Update,
+        "-sy" | "-syy" => CanonicalAction::Update,
         "-su" => CanonicalAction::Upgrade,
-        "-syu" => CanonicalAction::UpdateUpgrade,
+        "-syu" | "-syyu" => CanonicalAction::UpdateUpgrade,
         _ => return None,
     };
 
-    if matches!(
+    Some(ParsedCommand {
         action,
-        CanonicalAction::Update | CanonicalAction::Upgrade | CanonicalAction::UpdateUpgrade
-    ) && !args.is_empty()
-    {
-        return None;
-    }
+        args,
+        options,
+    })
+}
 
-    if matches!(
-        action,
+/// Updates and upgrades take no packages; installs, removals and searches need at least one.
+fn validate_args(parsed: ParsedCommand) -> Option<ParsedCommand> {
+    let needs_args = matches!(
+        parsed.action,
         CanonicalAction::Install | CanonicalAction::Remove | CanonicalAction::Search
-    ) && args.is_empty()
-    {
+    );
+
+    if needs_args == parsed.args.is_empty() {
         return None;
     }
 
-    Some(ParsedCommand { action, args })
+    Some(parsed)
 }
 
-fn build_command(to: DistroFamily, parsed: ParsedCommand) -> Option<String> {
-    match to {
-        DistroFamily::Debian => build_debian_command(parsed),
-        DistroFamily::Arch => build_arch_command(parsed),
+fn is_assume_yes_option(from: DistroFamily, option: &str) -> bool {
+    match from {
+        DistroFamily::Debian => matches!(option, "-y" | "--yes" | "--assume-yes"),
+        DistroFamily::Arch => option == "--noconfirm",
+        DistroFamily::Fedora => matches!(option, "-y" | "--assumeyes"),
+        DistroFamily::Suse => matches!(option, "-n" | "-y" | "--non-interactive" | "--no-confirm"),
     }
 }
 
-fn build_debian_command(parsed: ParsedCommand) -> Option<String> {
-    let args = join_args(&parsed.args);
-    let cmd = match parsed.action {
-        CanonicalAction::Install => format!("apt install {}", args),
-        CanonicalAction::Remove => format!("apt remove {}", args),
-        CanonicalAction::Search => format!("apt search {}", args),
-        CanonicalAction::Update => "apt update".to_string(),
-        CanonicalAction::Upgrade => "apt upgrade".to_string(),
-        CanonicalAction::UpdateUpgrade => "apt update && apt upgrade".to_string(),
-    };
+/// The converted command, as one or more steps (joined with `&&`).
+fn build_command(to: DistroFamily, parsed: &ParsedCommand, assume_yes: bool) -> Vec<String> {
+    match to {
+        DistroFamily::Debian => build_debian_command(parsed, assume_yes),
+        DistroFamily::Arch => build_arch_command(parsed, assume_yes),
+        DistroFamily::Fedora => build_fedora_command(parsed, assume_yes),
+        DistroFamily::Suse => build_suse_command(parsed, assume_yes),
+    }
+}
 
-    Some(cmd)
+/// `subcommand [flag] args`, with the flag only when it applies to the action.
+fn step(command: &str, action: CanonicalAction, flag: Option<&str>, args: &[String]) -> String {
+    let mut tokens = vec![command.to_string()];
+    if let Some(flag) = flag.filter(|_| action.changes_packages()) {
+        tokens.push(flag.to_string());
+    }
+    tokens.extend(args.iter().cloned());
+    tokens.join(" ")
 }
 
-fn build_arch_command(parsed: ParsedCommand) -> Option<String> {
-    let args = join_args(&parsed.args);
-    let cmd = match parsed.action {
-        CanonicalAction::Install => format!("pacman -S {}", args),
-        CanonicalAction::Remove => format!("pacman -R {}", args),
-        CanonicalAction::Search => format!("pacman -Ss {}", args),
-        CanonicalAction::Update => "pacman -Sy".to_string(),
-        CanonicalAction::Upgrade => "pacman -Su".to_string(),
-        CanonicalAction::UpdateUpgrade => "pacman -Syu".to_string(),
-    };
+fn build_debian_command(parsed: &ParsedCommand, assume_yes: bool) -> Vec<String> {
+    let yes = assume_yes.then_some("-y");
+    let args = &parsed.args;
+    match parsed.action {
+        CanonicalAction::Install => vec![step("apt install", parsed.action, yes, args)],
+        CanonicalAction::Remove => vec![step("apt remove", parsed.action, yes, args)],
+        CanonicalAction::Search => vec![step("apt search", parsed.action, None, args)],
+        CanonicalAction::Update => vec!["apt update".to_string()],
+        CanonicalAction::Upgrade => vec![step("apt upgrade", parsed.action, yes, args)],
+        CanonicalAction::UpdateUpgrade => vec![
+            "apt update".to_string(),
+            step("apt upgrade", CanonicalAction::Upgrade, yes, args),
+        ],
+    }
+}
 
-    Some(cmd)
+fn build_arch_command(parsed: &ParsedCommand, assume_yes: bool) -> Vec<String> {
+    let yes = assume_yes.then_some("--noconfirm");
+    let args = &parsed.args;
+    match parsed.action {
+        CanonicalAction::Install => vec![step("pacman -S", parsed.action, yes, args)],
+        CanonicalAction::Remove => vec![step("pacman -R", parsed.action, yes, args)],
+        CanonicalAction::Search => vec![step("pacman -Ss", parsed.action, None, args)],
+        CanonicalAction::Update => vec!["pacman -Sy".to_string()],
+        CanonicalAction::Upgrade => vec![step("pacman -Su", parsed.action, yes, args)],
+        CanonicalAction::UpdateUpgrade => {
+            vec![step("pacman -Syu", CanonicalAction::Upgrade, yes, args)]
+        }
+    }
 }
 
-fn join_args(args: &[String]) -> String {
-    args.join(" ")
+fn build_fedora_command(parsed: &ParsedCommand, assume_yes: bool) -> Vec<String> {
+    let yes = assume_yes.then_some("-y");
+    let args = &parsed.args;
+    match parsed.action {
+        CanonicalAction::Install => vec![step("dnf install", parsed.action, yes, args)],
+        CanonicalAction::Remove => vec![step("dnf remove", parsed.action, yes, args)],
+        CanonicalAction::Search => vec![step("dnf search", parsed.action, None, args)],
+        CanonicalAction::Update => vec!["dnf makecache".to_string()],
+        CanonicalAction::Upgrade => vec![step("dnf upgrade", parsed.action, yes, args)],
+        CanonicalAction::UpdateUpgrade => vec![step(
+            "dnf upgrade --refresh",
+            CanonicalAction::Upgrade,
+            yes,
+            args,
+        )],
+    }
 }
 
-fn has_option_tokens(args: &[String]) -> bool {
-    args.iter().any(|arg| arg.starts_with('-'))
+fn build_suse_command(parsed: &ParsedCommand, assume_yes: bool) -> Vec<String> {
+    // zypper takes --non-interactive before the subcommand.
+    let zypper = |subcommand: &str, action: CanonicalAction| {
+        if assume_yes && action.changes_packages() {
+            format!("zypper --non-interactive {}", subcommand)
+        } else {
+            format!("zypper {}", subcommand)
+        }
+    };
+    let args = &parsed.args;
+    match parsed.action {
+        CanonicalAction::Install => vec![step(
+            &zypper("install", parsed.action),
+            parsed.action,
+            None,
+            args,
+        )],
+        CanonicalAction::Remove => vec![step(
+            &zypper("remove", parsed.action),
+            parsed.action,
+            None,
+            args,
+        )],
+        CanonicalAction::Search => vec![step("zypper search", parsed.action, None, args)],
+        CanonicalAction::Update => vec!["zypper refresh".to_string()],
+        CanonicalAction::Upgrade => vec![zypper("update", parsed.action)],
+        CanonicalAction::UpdateUpgrade => vec![
+            "zypper refresh".to_string(),
+            zypper("update", CanonicalAction::Upgrade),
+        ],
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parts(command: &str) -> Vec<String> {
+        command.split_whitespace().map(String::from).collect()
+    }
+
     #[test]
     fn convert_apt_install_to_pacman() {
         let parts = vec![
@@ -189,4 +373,60 @@ mod tests {
         let converted = convert_parts_with_map(DistroFamily::Arch, DistroFamily::Debian, &parts);
         assert_eq!(converted, Some("apt update && apt upgrade".to_string()));
     }
+
+    #[test]
+    fn convert_between_dnf_and_zypper() {
+        let converted = convert_parts_with_map(
+            DistroFamily::Fedora,
+            DistroFamily::Suse,
+            &parts("dnf install git curl"),
+        );
+        assert_eq!(converted, Some("zypper install git curl".to_string()));
+
+        let converted = convert_parts_with_map(
+            DistroFamily::Suse,
+            DistroFamily::Fedora,
+            &parts("zypper ref"),
+        );
+        assert_eq!(converted, Some("dnf makecache".to_string()));
+
+        let converted = convert_parts_with_map(
+            DistroFamily::Fedora,
+            DistroFamily::Debian,
+            &parts("dnf install -y git"),
+        );
+        assert_eq!(converted, None);
+    }
+
+    #[test]
+    fn convert_with_rules_keeps_sudo_and_assume_yes() {
+        let converted = convert_parts_with_rules(
+            DistroFamily::Debian,
+            DistroFamily::Suse,
+            &parts("sudo apt-get -y --no-install-recommends install nginx"),
+        );
+        assert_eq!(
+            converted,
+            Some(RuleConversion {
+                command: "sudo zypper --non-interactive install nginx".to_string(),
+                notes: vec![
+                    "Dropped option --no-install-recommends (no known equivalent)".to_string()
+                ],
+            })
+        );
+
+        let converted = convert_parts_with_rules(
+            DistroFamily::Arch,
+            DistroFamily::Debian,
+            &parts("sudo pacman -Syu --noconfirm"),
+        )
+        .unwrap();
+        assert_eq!(converted.command, "sudo apt update && sudo apt upgrade -y");
+        assert!(converted.notes.is_empty());
+
+        assert_eq!(
+            convert_parts_with_rules(DistroFamily::Debian, DistroFamily::Arch, &parts("ls -la")),
+            None
+        );
+    }
 }
@@ -0,0 +1,66 @@
+mod ai_functions;
+mod cli_utils;
+mod command_parser;
+mod distro_cc_app;
+mod distro_detect;
+mod distro_map;
+mod models;
+mod script_converter;
+
+use crate::cli_utils::get_cli_arguments;
+use crate::distro_cc_app::start_distro_cc_app;
+use anyhow::Result;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use shared::system::load_env_variables::load_env_variables;
+use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use std::ffi::OsString;
+
+/// Translates package manager commands between Linux distributions.
+///
+/// Loads the environment variables, parses arguments, then converts the command (or script).
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    match runtime.block_on(run_tool(argv)) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+async fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    load_env_variables()?;
+
+    let _ = setup_graceful_shutdown(true);
+
+    let runtime_config = get_cli_arguments(argv)?;
+
+    start_distro_cc_app(runtime_config).await?;
+
+    Ok(())
+}
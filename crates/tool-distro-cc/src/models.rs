@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 #[derive(Debug)]
 pub struct DistroCcRuntimeConfig {
     pub from: String,
@@ -5,15 +7,25 @@ pub struct DistroCcRuntimeConfig {
     pub command: String,
     pub no_header: bool,
     pub verbose: bool,
+    /// Never calls the AI: commands the internal map can't convert go to the offline rules.
+    pub offline: bool,
+    /// Shell script to convert, instead of a single command.
+    pub script: Option<PathBuf>,
+    /// Where the converted script is written (stdout when not set).
+    pub output: Option<PathBuf>,
 }
 
 impl DistroCcRuntimeConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         from: String,
         to: Option<String>,
         command: String,
         no_header: bool,
         verbose: bool,
+        offline: bool,
+        script: Option<PathBuf>,
+        output: Option<PathBuf>,
     ) -> Self {
         Self {
             from,
@@ -21,6 +33,9 @@ impl DistroCcRuntimeConfig {
             command,
             no_header,
             verbose,
+            offline,
+            script,
+            output,
         }
     }
 }
@@ -29,6 +44,8 @@ impl DistroCcRuntimeConfig {
 pub enum DistroFamily {
     Debian,
     Arch,
+    Fedora,
+    Suse,
 }
 
 impl DistroFamily {
@@ -36,6 +53,18 @@ impl DistroFamily {
         match self {
             DistroFamily::Debian => "debian",
             DistroFamily::Arch => "arch",
+            DistroFamily::Fedora => "fedora",
+            DistroFamily::Suse => "suse",
+        }
+    }
+
+    /// Package manager commands of the family.
+    pub fn package_managers(&self) -> &'static [&'static str] {
+        match self {
+            DistroFamily::Debian => &["apt", "apt-get"],
+            DistroFamily::Arch => &["pacman"],
+            DistroFamily::Fedora => &["dnf", "dnf5", "yum"],
+            DistroFamily::Suse => &["zypper"],
         }
     }
 }
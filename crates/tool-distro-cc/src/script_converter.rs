@@ -0,0 +1,294 @@
+use crate::command_parser::{
+    is_package_manager_command, parse_command_parts, split_command_segments,
+};
+use crate::models::DistroFamily;
+use std::collections::HashMap;
+
+/// Prefix of the comments added to the converted script.
+const ANNOTATION: &str = "# distro-cc:";
+
+/// A converted script, and how many package manager commands it had.
+#[derive(Debug)]
+pub struct ScriptConversion {
+    pub script: String,
+    pub converted: usize,
+    pub unconverted: usize,
+}
+
+/// A command line of the script: one physical line, or several joined by `\` continuations.
+struct LogicalLine<'a> {
+    physical: Vec<&'a str>,
+    /// `physical` joined in a single line (continuations replaced by a space).
+    joined: String,
+    /// Heredoc bodies, comments and blank lines are copied as-is.
+    verbatim: bool,
+}
+
+/// Package manager commands of the `from` family in the script, in order and without repeats.
+pub fn collect_commands(script: &str, from: DistroFamily) -> Vec<String> {
+    let mut commands: Vec<String> = Vec::new();
+
+    for line in logical_lines(script).iter().filter(|line| !line.verbatim) {
+        for command in package_manager_commands(&line.joined, from) {
+            if !commands.contains(&command) {
+                commands.push(command);
+            }
+        }
+    }
+
+    commands
+}
+
+/// Replaces the package manager commands with their conversions (keyed by the original command).
+/// Changed lines get a comment with the original line above them, and commands without a
+/// conversion are kept, with a comment flagging them.
+pub fn annotate_script(
+    script: &str,
+    from: DistroFamily,
+    conversions: &HashMap<String, String>,
+) -> ScriptConversion {
+    let mut output: Vec<String> = Vec::new();
+    let mut converted = 0;
+    let mut unconverted = 0;
+
+    for line in logical_lines(script) {
+        if line.verbatim {
+            output.extend(line.physical.iter().map(|physical| physical.to_string()));
+            continue;
+        }
+
+        let indent: String = line
+            .joined
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+
+        let mut new_line = line.joined.clone();
+        let mut changed = false;
+        let mut notes = Vec::new();
+
+        // Right to left, so the ranges of the commands not replaced yet stay valid.
+        for range in split_command_segments(&line.joined).into_iter().rev() {
+            let command = &line.joined[range.clone()];
+            if !is_convertible(command, from) {
+                continue;
+            }
+
+            match conversions.get(command) {
+                Some(conversion) if conversion != command => {
+                    new_line.replace_range(range, conversion);
+                    changed = true;
+                    converted += 1;
+                }
+                Some(_) => {}
+                None => {
+                    notes.insert(
+                        0,
+                        format!("{}{} could not convert: {}", indent, ANNOTATION, command),
+                    );
+                    unconverted += 1;
+                }
+            }
+        }
+
+        output.extend(notes);
+        if changed {
+            output.push(format!(
+                "{}{} was: {}",
+                indent,
+                ANNOTATION,
+                line.joined.trim()
+            ));
+            output.push(new_line);
+        } else {
+            output.extend(line.physical.iter().map(|physical| physical.to_string()));
+        }
+    }
+
+    let mut script_out = output.join("\n");
+    if script.ends_with('\n') {
+        script_out.push('\n');
+    }
+
+    ScriptConversion {
+        script: script_out,
+        converted,
+        unconverted,
+    }
+}
+
+fn package_manager_commands(line: &str, from: DistroFamily) -> Vec<String> {
+    split_command_segments(line)
+        .into_iter()
+        .map(|range| line[range].to_string())
+        .filter(|command| is_convertible(command, from))
+        .collect()
+}
+
+fn is_convertible(command: &str, from: DistroFamily) -> bool {
+    parse_command_parts(command).is_ok_and(|parts| is_package_manager_command(&parts, from))
+}
+
+fn logical_lines(script: &str) -> Vec<LogicalLine<'_>> {
+    let mut lines = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+    let mut heredoc_end: Option<String> = None;
+
+    for physical in script.lines() {
+        if let Some(end) = &heredoc_end {
+            if physical.trim() == end {
+                heredoc_end = None;
+            }
+            lines.push(verbatim_line(physical));
+            continue;
+        }
+
+        if pending.is_empty() {
+            let trimmed = physical.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                lines.push(verbatim_line(physical));
+                continue;
+            }
+        }
+
+        pending.push(physical);
+        if physical.ends_with('\\') {
+            continue;
+        }
+
+        let physical_lines = std::mem::take(&mut pending);
+        let joined = join_continuations(&physical_lines);
+        heredoc_end = heredoc_delimiter(&joined);
+        lines.push(LogicalLine {
+            physical: physical_lines,
+            joined,
+            verbatim: false,
+        });
+    }
+
+    if !pending.is_empty() {
+        let joined = join_continuations(&pending);
+        lines.push(LogicalLine {
+            physical: pending,
+            joined,
+            verbatim: false,
+        });
+    }
+
+    lines
+}
+
+fn verbatim_line(physical: &str) -> LogicalLine<'_> {
+    LogicalLine {
+        physical: vec![physical],
+        joined: physical.to_string(),
+        verbatim: true,
+    }
+}
+
+fn join_continuations(physical: &[&str]) -> String {
+    physical
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.strip_suffix('\\').unwrap_or(line).trim_end();
+            if index == 0 {
+                line
+            } else {
+                line.trim_start()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The delimiter of a heredoc started in the line (`<<EOF`, `<<-'EOF'`), if any.
+fn heredoc_delimiter(line: &str) -> Option<String> {
+    let start = line.find("<<")?;
+    let rest = &line[start + 2..];
+    if rest.starts_with('<') {
+        return None; // Here-string (`<<<`).
+    }
+
+    let delimiter: String = rest
+        .trim_start_matches('-')
+        .trim_start()
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != ';' && *c != '|' && *c != '&')
+        .filter(|c| *c != '\'' && *c != '"')
+        .collect();
+
+    (!delimiter.is_empty()).then_some(delimiter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"#!/usr/bin/env bash
+set -e
+# Install the tools
+sudo apt update && sudo apt install -y git curl
+if ! command -v rg; then
+    apt-get install \
+        ripgrep
+fi
+apt-cache policy git
+cat <<EOF > notes.txt
+apt install this-is-just-text
+EOF
+apt install --fix-broken
+"#;
+
+    #[test]
+    fn collects_package_manager_commands() {
+        assert_eq!(
+            collect_commands(SCRIPT, DistroFamily::Debian),
+            vec![
+                "sudo apt update",
+                "sudo apt install -y git curl",
+                "apt-get install ripgrep",
+                "apt install --fix-broken",
+            ]
+        );
+    }
+
+    #[test]
+    fn annotates_converted_and_unconverted_lines() {
+        let conversions: HashMap<String, String> = [
+            ("sudo apt update", "sudo pacman -Sy"),
+            (
+                "sudo apt install -y git curl",
+                "sudo pacman -S --noconfirm git curl",
+            ),
+            ("apt-get install ripgrep", "pacman -S ripgrep"),
+        ]
+        .into_iter()
+        .map(|(from, to)| (from.to_string(), to.to_string()))
+        .collect();
+
+        let result = annotate_script(SCRIPT, DistroFamily::Debian, &conversions);
+
+        assert_eq!(result.converted, 3);
+        assert_eq!(result.unconverted, 1);
+        assert_eq!(
+            result.script,
+            r#"#!/usr/bin/env bash
+set -e
+# Install the tools
+# distro-cc: was: sudo apt update && sudo apt install -y git curl
+sudo pacman -Sy && sudo pacman -S --noconfirm git curl
+if ! command -v rg; then
+    # distro-cc: was: apt-get install ripgrep
+    pacman -S ripgrep
+fi
+apt-cache policy git
+cat <<EOF > notes.txt
+apt install this-is-just-text
+EOF
+# distro-cc: could not convert: apt install --fix-broken
+apt install --fix-broken
+"#
+        );
+    }
+}
@@ -3,6 +3,7 @@ use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
 use shared_eventhub::eventhub_models::EventHubConfig;
 use shared_eventhub::utils::cli_arguments::CommandCommonExt;
+use std::ffi::OsString;
 
 /// Displays runtime configuration and settings for the EventHub Exporter.
 ///
@@ -16,7 +17,10 @@ pub fn print_runtime_info(config: &mut EventHubConfig) {
     println!("EventHub Exporter v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
     println!("- Verbose: {}", config.verbose);
-    println!("- Source Database: {}", config.inbound_config.database_path);
+    match &config.export_config.source_database {
+        Some(source_database) => println!("- Source Database: {}", source_database),
+        None => println!("- Source Database: {}", config.inbound_config.database_path),
+    }
     println!(
         "- Export Checkpoint Database: {}",
         config.export_config.database_path
@@ -25,6 +29,9 @@ pub fn print_runtime_info(config: &mut EventHubConfig) {
         "- Export Base Folder: {}",
         config.export_config.base_data_folder
     );
+    if let Some(object_storage) = &config.export_config.object_storage {
+        println!("- Object Storage: {}", object_storage.label());
+    }
     println!("- Export Format: {}", config.export_config.export_format);
     println!(
         "- Condense Output: {}",
@@ -46,6 +53,11 @@ pub fn print_runtime_info(config: &mut EventHubConfig) {
         };
         println!("- Dump Filter: {}", dump_filter_message);
     }
+    if let Some(where_filter) = &config.export_config.where_filter {
+        if !where_filter.is_empty() {
+            println!("- Where: {}", where_filter.join(" AND "));
+        }
+    }
     println!(
         "- Feedback: Every {} second(s)",
         &config.inbound_config.feedback_interval
@@ -55,6 +67,7 @@ pub fn print_runtime_info(config: &mut EventHubConfig) {
         config.export_config.ignore_checkpoint
     );
     println!("- Use Local Time: {}", config.export_config.use_local_time);
+    println!("- Workers: {}", config.export_config.workers);
     println!("- Binary data will be converted to base64 if encountered");
     println!();
 }
@@ -68,7 +81,7 @@ pub fn print_runtime_info(config: &mut EventHubConfig) {
 /// - JSON configuration file support with CLI override capability
 /// - Shared EventHub connection arguments
 /// - Export-specific arguments for format, filters, and output options
-pub fn get_cli_arguments() -> ArgMatches {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> ArgMatches {
     Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -81,5 +94,5 @@ pub fn get_cli_arguments() -> ArgMatches {
         .preset_arg_config(None)
         .add_eh_base_shared_args()
         .add_eh_export_args()
-        .get_matches()
+        .get_matches_from(argv)
 }
@@ -1,25 +1,52 @@
 use crate::export_progress_tracker::ExportProgressTracker;
-use crate::message_exporters::export_message_csv::export_message_csv;
-use crate::message_exporters::export_message_json::export_message_json;
-use crate::message_exporters::export_message_txt::export_message_txt;
+use crate::message_exporters::export_message_csv::format_message_csv;
+use crate::message_exporters::export_message_json::format_message_json;
+use crate::message_exporters::export_message_txt::format_message_txt;
+use crate::message_exporters::export_writer::{ExportFormat, ExportWriter, RenderedMessage};
+use crate::message_filter::MessageFilter;
+use crate::object_storage::ObjectStorageSink;
 use anyhow::{anyhow, Context, Result};
 use chrono::Utc;
 use shared::system::resolve_path_with_base::resolve_path_with_base;
 use shared::utils::message_matches_filter::message_matches_filter;
 use shared_eventhub::eventhub_models::{EventHubConfig, InboundMessage};
 use shared_eventhub::utils::extract_eventhub_endpoint_from_connection_string::extract_eventhub_endpoint_from_connection_string;
-use sled::Db;
+use sled::{Db, IVec};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use tokio::fs;
 
+/// Prefix of the message keys in the source database.
+const MESSAGE_KEY_PREFIX: &[u8] = b"msg:";
+
+/// Messages handed to a worker at a time. Big enough to keep the channel overhead low.
+const BATCH_SIZE: usize = 256;
+
+/// A numbered batch of raw (key, value) pairs read from the source database.
+type SourceBatch = (u64, Vec<(IVec, IVec)>);
+
+/// A numbered batch of formatted messages, or the error that stopped the worker.
+type FormattedBatch = (u64, Result<Vec<ExportOutcome>>);
+
+/// What a worker decided about a message.
+enum ExportOutcome {
+    Export(RenderedMessage),
+    Duplicated,
+    Skipped,
+}
+
 pub struct EventHubExporter {
     config: EventHubConfig,
     source_db: Db,
     export_db: Db,
     progress: Arc<ExportProgressTracker>,
     shutdown: Arc<AtomicBool>,
+    message_filter: MessageFilter,
+    object_storage: Option<Arc<ObjectStorageSink>>,
 }
 
 impl EventHubExporter {
@@ -37,27 +64,26 @@ impl EventHubExporter {
     /// - Source database must exist (created by eh-read with same connection string)
     /// - Write permissions required for export directory
     /// - Valid EventHub endpoint extractable from connection string
-    pub async fn new(config: EventHubConfig, shutdown: Option<Arc<AtomicBool>>) -> Result<Self> {
-        // TODO #1: Related.
-        let endpoint = extract_eventhub_endpoint_from_connection_string(&config.connection_string)?;
-
-        // Setup source database path (same as eh-read)
-        let db_base_dir = resolve_path_with_base(
-            &config.inbound_config.base_data_folder,
-            &config.inbound_config.database_path,
-        );
-
-        let source_db_path = db_base_dir.join(format!("{}.db", endpoint));
+    pub async fn new(
+        mut config: EventHubConfig,
+        shutdown: Option<Arc<AtomicBool>>,
+    ) -> Result<Self> {
+        let source_db_path = match &config.export_config.source_database {
+            Some(source_database) => {
+                let source_db_path = PathBuf::from(source_database);
+
+                // The entity path only labels the exported messages, so the database name will do.
+                if config.entity_path.trim().is_empty() {
+                    config.entity_path = source_db_path
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                }
 
-        // Verify source database exists
-        if !source_db_path.exists() {
-            return Err(anyhow!(
-                "Source database not found at: {:?}. Make sure eh-read has been run first with the same connection string and paths.\n\
-                Expected database file: {}.db\n\
-                In directory: {:?}",
-                source_db_path, endpoint, db_base_dir
-            ));
-        }
+                source_db_path
+            }
+            None => Self::get_eh_read_database_path(&config)?,
+        };
 
         // Setup export database path
         let export_db_path =
@@ -89,6 +115,29 @@ impl EventHubExporter {
             config.export_config.feedback_interval as f64,
         ));
         let shutdown = shutdown.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+        let message_filter = MessageFilter::parse(
+            config
+                .export_config
+                .where_filter
+                .as_deref()
+                .unwrap_or_default(),
+        )?;
+
+        let object_storage = match &config.export_config.object_storage {
+            Some(storage_config) => {
+                let export_root = resolve_path_with_base(
+                    &config.export_config.base_data_folder,
+                    &config.export_config.export_folder,
+                );
+                let sink = ObjectStorageSink::new(storage_config, &export_root)?;
+                println!(
+                    "[OK]  Exports will be uploaded to {}",
+                    storage_config.label()
+                );
+                Some(Arc::new(sink))
+            }
+            None => None,
+        };
 
         Ok(Self {
             config,
@@ -96,9 +145,37 @@ impl EventHubExporter {
             export_db,
             progress,
             shutdown,
+            message_filter,
+            object_storage,
         })
     }
 
+    /// Locates the database `eh-read` created for the connection string.
+    fn get_eh_read_database_path(config: &EventHubConfig) -> Result<PathBuf> {
+        // TODO #1: Related.
+        let endpoint = extract_eventhub_endpoint_from_connection_string(&config.connection_string)?;
+
+        // Setup source database path (same as eh-read)
+        let db_base_dir = resolve_path_with_base(
+            &config.inbound_config.base_data_folder,
+            &config.inbound_config.database_path,
+        );
+
+        let source_db_path = db_base_dir.join(format!("{}.db", endpoint));
+
+        // Verify source database exists
+        if !source_db_path.exists() {
+            return Err(anyhow!(
+                "Source database not found at: {:?}. Make sure eh-read has been run first with the same connection string and paths.\n\
+                Expected database file: {}.db\n\
+                In directory: {:?}",
+                source_db_path, endpoint, db_base_dir
+            ));
+        }
+
+        Ok(source_db_path)
+    }
+
     /// Tests write permissions for the export directory by creating and deleting a test file.
     ///
     /// # Arguments
@@ -138,102 +215,250 @@ impl EventHubExporter {
     /// Starts the export process, iterating through source database messages and exporting filtered ones.
     ///
     /// # Behavior
-    /// - Processes messages with "msg:" key prefix from source database
-    /// - Applies export filters and checkpoint validation
-    /// - Exports eligible messages in configured format
-    /// - Tracks progress with real-time feedback
-    /// - Supports graceful shutdown via atomic flag
+    /// - Reads messages with "msg:" key prefix from source database, in batches
+    /// - A pool of `workers` threads deserializes, filters and formats the batches in parallel
+    /// - A single writer writes the formatted messages in the source database order, so the
+    ///   order of the messages of each partition is preserved
+    /// - Tracks progress and throughput with real-time feedback
+    /// - Supports graceful shutdown via atomic flag (formatted messages are still written, and
+    ///   condensed files are closed properly)
     ///
     /// # Returns
     /// - `Ok(())`: Export completed successfully
     /// - `Err`: Database, deserialization, or export operation failed
     pub async fn start_export(&self) -> Result<()> {
-        println!("Starting export process...");
+        let workers = self.config.export_config.workers.max(1);
+        let format = ExportFormat::from_config(&self.config.export_config.export_format)?;
+
+        println!("Starting export process with {} worker(s)...", workers);
+
+        let stop = AtomicBool::new(false);
+        let (batch_tx, batch_rx) = mpsc::sync_channel::<SourceBatch>(workers * 2);
+        let (outcome_tx, outcome_rx) = mpsc::sync_channel::<FormattedBatch>(workers * 2);
+        let batch_rx = Arc::new(Mutex::new(batch_rx));
+
+        thread::scope(|scope| -> Result<()> {
+            for _ in 0..workers {
+                let batch_rx = Arc::clone(&batch_rx);
+                let outcome_tx = outcome_tx.clone();
+                let stop = &stop;
+                scope.spawn(move || self.run_worker(batch_rx, outcome_tx, stop));
+            }
+            // Only the workers hold senders now, so the writer ends once they are done.
+            drop(outcome_tx);
+            drop(batch_rx);
 
-        // Iterate through all messages in a source database
-        for item in self.source_db.iter() {
+            let writer = scope.spawn(|| self.run_writer(format, outcome_rx, &stop));
+
+            let read_result = self.read_batches(batch_tx, &stop);
+            let write_result = writer
+                .join()
+                .unwrap_or_else(|_| Err(anyhow!("Export writer thread panicked")));
+
+            read_result.and(write_result)
+        })?;
+
+        // Final progress update
+        self.progress.finish();
+        self.progress.print_throughput(workers);
+        println!("[OK]  Export completed successfully!");
+
+        Ok(())
+    }
+
+    /// Reads the source database and hands the messages to the workers, in numbered batches.
+    ///
+    /// # Arguments
+    /// - `batch_tx`: Channel to the workers. Dropped at the end, so they stop once it is drained
+    /// - `stop`: Set when the writer or a worker fails, to stop reading early
+    fn read_batches(&self, batch_tx: SyncSender<SourceBatch>, stop: &AtomicBool) -> Result<()> {
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        let mut batch_index = 0;
+
+        // In the eventhub_reader_app::process_received_event (private method) we create the
+        // key for the messages with the prefix "msg:".
+        for item in self.source_db.scan_prefix(MESSAGE_KEY_PREFIX) {
             if self.shutdown.load(Ordering::Relaxed) {
                 println!("\nShutdown signal received, stopping export...");
                 break;
             }
 
-            let (key, value) = item?;
-            let key_str = String::from_utf8_lossy(&key);
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
 
-            // Only process message keys.
-            // In the eventhub_reader_app::process_received_event (private method) we create the
-            // key for the messages with the prefix "msg:".
-            if !key_str.starts_with("msg:") {
-                continue;
+            batch.push(item?);
+            if batch.len() == BATCH_SIZE {
+                let full_batch = std::mem::replace(&mut batch, Vec::with_capacity(BATCH_SIZE));
+                if batch_tx.send((batch_index, full_batch)).is_err() {
+                    // Every worker is gone; the writer reports why.
+                    return Ok(());
+                }
+                batch_index += 1;
             }
+        }
 
-            // Deserialize the message
-            let message: InboundMessage =
-                serde_json::from_slice(&value).context("Failed to deserialize message")?;
+        if !batch.is_empty() {
+            let _ = batch_tx.send((batch_index, batch));
+        }
 
-            // Check if we should export this message
-            if self.should_export_message(&message, &key_str).await? {
-                self.export_message(&message, &key_str).await?;
-                self.progress.increment_exported();
-            } else {
-                self.progress.increment_duplicated();
+        Ok(())
+    }
+
+    /// Worker loop: deserializes, filters and formats batches until the reader is done.
+    fn run_worker(
+        &self,
+        batch_rx: Arc<Mutex<Receiver<SourceBatch>>>,
+        outcome_tx: SyncSender<FormattedBatch>,
+        stop: &AtomicBool,
+    ) {
+        loop {
+            let next = batch_rx
+                .lock()
+                .map_err(|_| anyhow!("Export worker channel poisoned"))
+                .and_then(|rx| rx.recv().map_err(anyhow::Error::from));
+
+            let Ok((batch_index, items)) = next else {
+                return;
+            };
+
+            let outcomes = items
+                .into_iter()
+                .map(|(key, value)| self.process_message(&key, &value))
+                .collect::<Result<Vec<_>>>();
+
+            if outcomes.is_err() {
+                stop.store(true, Ordering::Relaxed);
             }
 
-            // Show progress
-            if self.progress.should_show_progress() {
-                self.progress.print_progress();
+            if outcome_tx.send((batch_index, outcomes)).is_err() {
+                return;
             }
         }
+    }
 
-        // Final progress update
-        self.progress.print_progress();
-        println!();
-        println!("[OK]  Export completed successfully!");
+    /// Writer loop: writes the formatted batches in order, marks the messages as exported, and
+    /// reports progress.
+    ///
+    /// Batches can arrive out of order (workers finish at different times), so the early ones
+    /// wait in `pending` until every batch before them is written.
+    fn run_writer(
+        &self,
+        format: ExportFormat,
+        outcome_rx: Receiver<FormattedBatch>,
+        stop: &AtomicBool,
+    ) -> Result<()> {
+        let mut writer = ExportWriter::new(
+            format,
+            self.config.export_config.include_metadata,
+            self.config.export_config.condense_output,
+        );
+        if let Some(object_storage) = &self.object_storage {
+            writer = writer.with_object_storage(Arc::clone(object_storage));
+        }
+        let mut pending: BTreeMap<u64, Vec<ExportOutcome>> = BTreeMap::new();
+        let mut next_batch = 0;
+
+        let result = (|| -> Result<()> {
+            for (batch_index, outcomes) in outcome_rx.iter() {
+                pending.insert(batch_index, outcomes?);
+
+                while let Some(outcomes) = pending.remove(&next_batch) {
+                    for outcome in outcomes {
+                        self.write_outcome(&mut writer, outcome)?;
+                    }
+                    next_batch += 1;
+
+                    // Show progress
+                    if self.progress.should_show_progress() {
+                        self.progress.print_progress();
+                    }
+                }
+            }
+
+            Ok(())
+        })();
+
+        if result.is_err() {
+            stop.store(true, Ordering::Relaxed);
+        }
+
+        // Close the condensed files even after an error, so the JSON arrays stay valid.
+        result.and(writer.finish())
+    }
+
+    fn write_outcome(&self, writer: &mut ExportWriter, outcome: ExportOutcome) -> Result<()> {
+        match outcome {
+            ExportOutcome::Export(message) => {
+                let bytes = writer.write(&message)?;
+                self.mark_exported(&message.key)?;
+                self.progress.increment_exported();
+                self.progress.add_bytes_written(bytes);
+            }
+            ExportOutcome::Duplicated => self.progress.increment_duplicated(),
+            ExportOutcome::Skipped => self.progress.increment_skipped(),
+        }
 
         Ok(())
     }
 
-    /// Determines whether a message should be exported based on filters and checkpoint status.
+    /// Deserializes a message and formats it, if it should be exported.
     ///
     /// # Arguments
-    /// - `message`: InboundMessage to evaluate for export eligibility
-    /// - `key`: Unique message key for database lookup
+    /// - `key`: Message key in the source database
+    /// - `value`: Serialized InboundMessage
     ///
     /// # Returns
-    /// - `Ok(true)`: Message should be exported
-    /// - `Ok(false)`: Message should be skipped (already exported, filtered out, or duplicate)
-    /// - `Err`: Database or filter evaluation error
-    ///
-    /// # Logic
-    /// - Checks export checkpoint unless `ignore_checkpoint` is enabled
-    /// - Validates file existence for non-condensed output
-    /// - Applies dump filters if configured
-    async fn should_export_message(&self, message: &InboundMessage, key: &str) -> Result<bool> {
-        // Check if already exported (unless ignoring checkpoint)
-        if !self.config.export_config.ignore_checkpoint {
-            if let Ok(Some(_)) = self.export_db.get(key.as_bytes()) {
-                // For condense_output=false, check if a file still exists
-                if !self.config.export_config.condense_output {
-                    let file_path = self.get_message_file_path(message);
-                    if !file_path.exists() {
-                        // File was deleted, re-export
-                        return Ok(true);
-                    }
-                }
-                return Ok(false);
-            }
+    /// - `Ok(ExportOutcome)`: Formatted message, or why it was not exported
+    /// - `Err`: Deserialization, database, or formatting failed
+    fn process_message(&self, key: &[u8], value: &[u8]) -> Result<ExportOutcome> {
+        let key = String::from_utf8_lossy(key);
+
+        // Deserialize the message
+        let message: InboundMessage =
+            serde_json::from_slice(value).context("Failed to deserialize message")?;
+
+        if self.is_already_exported(&message, &key) {
+            return Ok(ExportOutcome::Duplicated);
         }
 
         // Check dump filter
         if let Some(filters) = &self.config.export_config.dump_filter {
             if !filters.is_empty() && !message_matches_filter(&message.msg_data, filters) {
-                self.progress.increment_skipped();
-                return Ok(false);
+                return Ok(ExportOutcome::Skipped);
+            }
+        }
+
+        // Check the --where predicates
+        if !self.message_filter.matches(&message) {
+            return Ok(ExportOutcome::Skipped);
+        }
+
+        Ok(ExportOutcome::Export(RenderedMessage {
+            key: key.to_string(),
+            path: self.get_message_file_path(&message),
+            record: self.format_message(&message)?,
+        }))
+    }
+
+    /// Checks the export checkpoint (unless `ignore_checkpoint` is enabled).
+    ///
+    /// For non-condensed output, a message is exported again if its file was deleted (not
+    /// checked with object storage, where the files are not kept locally).
+    fn is_already_exported(&self, message: &InboundMessage, key: &str) -> bool {
+        if self.config.export_config.ignore_checkpoint {
+            return false;
+        }
+
+        if let Ok(Some(_)) = self.export_db.get(key.as_bytes()) {
+            // For condense_output=false, check if a file still exists
+            if !self.config.export_config.condense_output && self.object_storage.is_none() {
+                return self.get_message_file_path(message).exists();
             }
-            return Ok(true);
+            return true;
         }
 
-        Ok(true)
+        false
     }
 
     /// Generates the file path for storing a message based on configuration and timestamp.
@@ -298,59 +523,29 @@ impl EventHubExporter {
         }
     }
 
-    /// Exports a message to file in the configured format and marks it as exported.
-    ///
-    /// # Arguments
-    /// - `message`: InboundMessage to export
-    /// - `key`: Message key for tracking export status
-    ///
-    /// # Returns
-    /// - `Ok(())`: Message exported and marked successfully
-    /// - `Err`: Export operation or database update failed
+    /// Formats a message in the configured format.
     ///
     /// # Formats
     /// - TXT: Plain text with optional metadata
-    /// - CSV: Comma-separated values with headers
+    /// - CSV: Comma-separated values (header is added by the writer)
     /// - JSON: JSON objects with metadata
-    async fn export_message(&self, message: &InboundMessage, key: &str) -> Result<()> {
-        let file_path = self.get_message_file_path(message);
+    fn format_message(&self, message: &InboundMessage) -> Result<String> {
         let include_metadata = self.config.export_config.include_metadata;
-        let condense_output = self.config.export_config.condense_output;
         let entity_path = &self.config.entity_path;
 
         match self.config.export_config.export_format.as_str() {
-            "txt" => {
-                export_message_txt(message, &file_path, include_metadata, condense_output).await?
-            }
-            "csv" => {
-                export_message_csv(
-                    message,
-                    &file_path,
-                    include_metadata,
-                    condense_output,
-                    entity_path,
-                )
-                .await?
-            }
-            "json" => {
-                export_message_json(
-                    message,
-                    &file_path,
-                    include_metadata,
-                    condense_output,
-                    entity_path,
-                )
-                .await?
-            }
-            _ => {
-                return Err(anyhow!(
-                    "Unsupported export format: {}",
-                    self.config.export_config.export_format
-                ))
-            }
+            "txt" => Ok(format_message_txt(message, include_metadata)),
+            "csv" => format_message_csv(message, include_metadata, entity_path),
+            "json" => format_message_json(message, include_metadata, entity_path),
+            _ => Err(anyhow!(
+                "Unsupported export format: {}",
+                self.config.export_config.export_format
+            )),
         }
+    }
 
-        // Mark as exported
+    /// Marks a message as exported in the export tracking database.
+    fn mark_exported(&self, key: &str) -> Result<()> {
         let export_timestamps = vec![Utc::now()];
         let export_data = serde_json::to_vec(&export_timestamps)?;
         self.export_db.insert(key.as_bytes(), export_data)?;
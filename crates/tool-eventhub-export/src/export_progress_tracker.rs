@@ -1,27 +1,17 @@
-use crossterm::cursor::MoveToColumn;
-use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
-use crossterm::terminal::{Clear, ClearType};
-use crossterm::ExecutableCommand;
-use std::io::{stdout, Write};
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Instant;
+use crossterm::style::Color;
+use shared::ui::progress::Progress;
+use std::time::Duration;
 
-/// Tracks export progress with counters for exported, skipped, and duplicated messages.
+const EXPORTED: &str = "Exported";
+const SKIPPED: &str = "Skipped";
+const DUPLICATED: &str = "Duplicated";
+
+/// Tracks export progress with counters for exported, skipped, and duplicated messages, and the
+/// bytes written to the export files.
 ///
-/// # Fields
-/// - `messages_exported`: Successfully exported message count
-/// - `messages_skipped`: Skipped message count (filtered out)
-/// - `messages_duplicated`: Duplicate message count (already exported)
-/// - `start_time`: Export start timestamp for rate calculation
-/// - `feedback_interval_secs`: Progress feedback interval in seconds
-/// - `last_progress_time`: Thread-safe tracking of last progress display
+/// The counters, rates, and the progress line come from the shared `Progress`.
 pub struct ExportProgressTracker {
-    pub messages_exported: AtomicU64,
-    pub messages_skipped: AtomicU64,
-    pub messages_duplicated: AtomicU64,
-    pub start_time: Instant,
-    pub feedback_interval_secs: f64,
-    pub last_progress_time: std::sync::Mutex<Instant>,
+    progress: Progress,
 }
 
 impl ExportProgressTracker {
@@ -34,28 +24,30 @@ impl ExportProgressTracker {
     /// - `Self`: Initialized progress tracker with zero counters
     pub fn new(feedback_interval_secs: f64) -> Self {
         Self {
-            messages_exported: AtomicU64::new(0),
-            messages_skipped: AtomicU64::new(0),
-            messages_duplicated: AtomicU64::new(0),
-            start_time: Instant::now(),
-            feedback_interval_secs,
-            last_progress_time: std::sync::Mutex::new(Instant::now()),
+            progress: Progress::new("msg", &[EXPORTED, SKIPPED, DUPLICATED])
+                .with_interval(Duration::from_secs_f64(feedback_interval_secs))
+                .with_color(Color::Cyan),
         }
     }
 
     /// Increments the exported message counter by 1.
     pub fn increment_exported(&self) {
-        self.messages_exported.fetch_add(1, Ordering::Relaxed);
+        self.progress.increment(EXPORTED);
     }
 
     /// Increments the skipped message counter by 1.
     pub fn increment_skipped(&self) {
-        self.messages_skipped.fetch_add(1, Ordering::Relaxed);
+        self.progress.increment(SKIPPED);
     }
 
     /// Increments the duplicated message counter by 1.
     pub fn increment_duplicated(&self) {
-        self.messages_duplicated.fetch_add(1, Ordering::Relaxed);
+        self.progress.increment(DUPLICATED);
+    }
+
+    /// Adds to the number of bytes written to the export files.
+    pub fn add_bytes_written(&self, bytes: u64) {
+        self.progress.add_bytes(bytes);
     }
 
     /// Determines if progress should be displayed based on elapsed time since last display.
@@ -64,57 +56,39 @@ impl ExportProgressTracker {
     /// - `true`: Feedback interval has elapsed, progress should be shown
     /// - `false`: Feedback interval not yet reached
     pub fn should_show_progress(&self) -> bool {
-        let mut last_time = self.last_progress_time.lock().unwrap();
-        let now = Instant::now();
-        if now.duration_since(*last_time).as_secs_f64() >= self.feedback_interval_secs {
-            *last_time = now;
-            true
-        } else {
-            false
-        }
+        self.progress.should_render()
     }
 
-    /// Formats a progress line with export statistics and runtime information.
+    /// Calculates the export throughput since the tracker was created.
     ///
     /// # Returns
-    /// - `String`: Formatted progress line with counts, rate, and runtime
-    ///
-    /// # Format
-    /// - Exported/Skipped/Duplicated counts
-    /// - Messages per second rate
-    /// - Runtime in HH:MM:SS.mmmm format
-    pub fn format_progress_line(&self) -> String {
-        let exported = self.messages_exported.load(Ordering::Relaxed);
-        let skipped = self.messages_skipped.load(Ordering::Relaxed);
-        let duplicated = self.messages_duplicated.load(Ordering::Relaxed);
-        let runtime = self.start_time.elapsed();
-
-        let rate = if runtime.as_secs_f64() > 0.0 {
-            exported as f64 / runtime.as_secs_f64()
-        } else {
-            0.0
-        };
+    /// - `(f64, f64)`: Messages exported per second, and megabytes written per second
+    pub fn throughput(&self) -> (f64, f64) {
+        let snapshot = self.progress.snapshot();
+        (snapshot.rate, snapshot.bytes_per_second / (1024.0 * 1024.0))
+    }
 
-        let hours = runtime.as_secs() / 3600;
-        let minutes = (runtime.as_secs() % 3600) / 60;
-        let seconds = runtime.as_secs() % 60;
-        let millis = runtime.subsec_millis();
+    /// Prints the final throughput report.
+    ///
+    /// # Arguments
+    /// - `workers`: Number of formatting workers used in the export
+    pub fn print_throughput(&self, workers: usize) {
+        let (messages_per_second, megabytes_per_second) = self.throughput();
+        let megabytes = self.progress.bytes() as f64 / (1024.0 * 1024.0);
 
-        format!(
-            "Exported: {} | Skipped: {} | Duplicated: {} | Rate: {:.2} msg/s | Runtime: {:02}:{:02}:{:02}.{:04}",
-            exported, skipped, duplicated, rate, hours, minutes, seconds, millis
-        )
+        println!(
+            "Throughput: {:.2} msg/s | {:.2} MB/s | {:.2} MB written | Workers: {}",
+            messages_per_second, megabytes_per_second, megabytes, workers
+        );
     }
 
-    /// Prints formatted progress to stdout with cyan color and line clearing.
+    /// Prints the progress line: counters, rate, data written, and runtime.
     pub fn print_progress(&self) {
-        let progress_line = self.format_progress_line();
-        let mut stdout = stdout();
-        let _ = stdout.execute(MoveToColumn(0));
-        let _ = stdout.execute(Clear(ClearType::CurrentLine));
-        let _ = stdout.execute(SetForegroundColor(Color::Cyan));
-        let _ = stdout.execute(Print(&progress_line));
-        let _ = stdout.execute(ResetColor);
-        let _ = stdout.flush();
+        self.progress.render();
+    }
+
+    /// Prints the last progress line, and ends it.
+    pub fn finish(&self) {
+        self.progress.finish();
     }
 }
@@ -1,62 +1,24 @@
 use anyhow::{Context, Result};
 use csv::Writer;
-use shared::system::ensure_directory_exists::EnsureDirectoryExists;
 use shared_eventhub::eventhub_models::InboundMessage;
-use std::path::PathBuf;
 
-/// Exports an InboundMessage to a CSV file with optional metadata and condensed output.
+/// Formats an InboundMessage as a CSV row (including the line terminator).
 ///
 /// # Arguments
-/// - `message`: InboundMessage to export
-/// - `file_path`: Target file path for CSV output
+/// - `message`: InboundMessage to format
 /// - `include_metadata`: Whether to include entity path, partition ID, event ID, and timestamp columns
-/// - `condense_output`: Whether to append to existing file or overwrite
 /// - `entity_path`: Entity path to include in metadata when enabled
 ///
 /// # Returns
-/// - `Ok(())`: Message exported successfully
-/// - `Err`: Directory creation, file I/O, or CSV writing failed
-///
-/// # Behavior
-/// - Headers written only for new files or non-condensed output
-/// - Condensed: Appends rows to existing file
-/// - Individual: Overwrites existing file with headers
-pub async fn export_message_csv(
+/// - `Ok(String)`: The CSV row, quoted and escaped as needed
+/// - `Err`: CSV writing failed
+pub fn format_message_csv(
     message: &InboundMessage,
-    file_path: &PathBuf,
     include_metadata: bool,
-    condense_output: bool,
-    entity_path: &String,
-) -> Result<()> {
-    file_path.ensure_directory_exists()?;
-
-    let file_exists = file_path.exists();
-
-    let file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(file_exists && condense_output)
-        .truncate(!file_exists || !condense_output)
-        .open(file_path)?;
-
-    let mut writer = Writer::from_writer(file);
-
-    // Only write a header if a file is new, or we're not condensing
-    let write_header = !file_exists || !condense_output;
-
+    entity_path: &str,
+) -> Result<String> {
     if include_metadata {
-        if write_header {
-            writer.write_record([
-                "entity_path",
-                "partition_id",
-                "event_id",
-                "timestamp",
-                "message_content",
-            ])?;
-        }
-
-        // Write data
-        writer.write_record([
+        write_csv_record(&[
             entity_path,
             &message.partition_id,
             &message.event_id,
@@ -65,19 +27,33 @@ pub async fn export_message_csv(
                 .format("%Y-%m-%dT%H:%M:%S%.9fZ")
                 .to_string(),
             &message.msg_data,
-        ])?;
+        ])
+        .context("Failed to write CSV data")
     } else {
-        if write_header {
-            writer
-                .write_record(["message_content"])
-                .context("Failed to write CSV header")?;
-        }
-        // Write data
-        writer
-            .write_record([&message.msg_data])
-            .context("Failed to write CSV data")?;
+        write_csv_record(&[&message.msg_data]).context("Failed to write CSV data")
     }
+}
+
+/// Header row written at the top of new CSV files.
+pub fn csv_header(include_metadata: bool) -> Result<String> {
+    if include_metadata {
+        write_csv_record(&[
+            "entity_path",
+            "partition_id",
+            "event_id",
+            "timestamp",
+            "message_content",
+        ])
+    } else {
+        write_csv_record(&["message_content"])
+    }
+    .context("Failed to write CSV header")
+}
+
+fn write_csv_record(fields: &[&str]) -> Result<String> {
+    let mut writer = Writer::from_writer(Vec::new());
+    writer.write_record(fields)?;
+    let bytes = writer.into_inner().context("Failed to flush CSV writer")?;
 
-    writer.flush().context("Failed to flush CSV writer")?;
-    Ok(())
+    Ok(String::from_utf8(bytes)?)
 }
@@ -0,0 +1,307 @@
+use crate::message_exporters::export_message_csv::csv_header;
+use crate::message_exporters::export_message_txt::txt_separator;
+use crate::object_storage::ObjectStorageSink;
+use anyhow::{anyhow, Context, Result};
+use shared::system::ensure_directory_exists::EnsureDirectoryExists;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Supported export formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Txt,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Parses the `export_format` configuration value.
+    pub fn from_config(value: &str) -> Result<Self> {
+        match value {
+            "txt" => Ok(Self::Txt),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => Err(anyhow!("Unsupported export format: {}", other)),
+        }
+    }
+}
+
+/// A message already formatted by a worker, waiting to be written.
+///
+/// # Fields
+/// - `key`: Source database key, used to mark the message as exported
+/// - `path`: Target file path
+/// - `record`: Formatted message (text, CSV row, or JSON object)
+pub struct RenderedMessage {
+    pub key: String,
+    pub path: PathBuf,
+    pub record: String,
+}
+
+/// A condensed output file kept open for the whole export.
+///
+/// # Fields
+/// - `writer`: Buffered handle, opened in append mode
+/// - `has_records`: Whether the file already has a message (so a separator is needed)
+struct CondensedFile {
+    writer: BufWriter<File>,
+    has_records: bool,
+}
+
+/// Writes formatted messages to the export files, in the order they are received.
+///
+/// Individual files are written in one go. Condensed files are opened once and kept open until
+/// `finish`, so appending doesn't rewrite the file for every message. Condensed JSON files are a
+/// single array: it is reopened (before the closing `]`) when the file already exists, and closed
+/// by `finish`.
+///
+/// With object storage, individual files are uploaded instead of written. Condensed files are
+/// still written to the export folder (so the next export can append to them), and uploaded by
+/// `finish`.
+pub struct ExportWriter {
+    format: ExportFormat,
+    include_metadata: bool,
+    condense_output: bool,
+    condensed_files: HashMap<PathBuf, CondensedFile>,
+    object_storage: Option<Arc<ObjectStorageSink>>,
+}
+
+impl ExportWriter {
+    pub fn new(format: ExportFormat, include_metadata: bool, condense_output: bool) -> Self {
+        Self {
+            format,
+            include_metadata,
+            condense_output,
+            condensed_files: HashMap::new(),
+            object_storage: None,
+        }
+    }
+
+    pub fn with_object_storage(mut self, object_storage: Arc<ObjectStorageSink>) -> Self {
+        self.object_storage = Some(object_storage);
+        self
+    }
+
+    /// Writes a message to its file.
+    ///
+    /// # Returns
+    /// - `Ok(u64)`: Number of bytes written
+    /// - `Err`: Directory creation or file I/O failed
+    pub fn write(&mut self, message: &RenderedMessage) -> Result<u64> {
+        if !self.condense_output {
+            return self.write_individual(message);
+        }
+
+        let (format, include_metadata) = (self.format, self.include_metadata);
+        if !self.condensed_files.contains_key(&message.path) {
+            let file = open_condensed_file(&message.path, format, include_metadata)?;
+            self.condensed_files.insert(message.path.clone(), file);
+        }
+        let file = self
+            .condensed_files
+            .get_mut(&message.path)
+            .expect("condensed file was just opened");
+
+        let separator = match (format, file.has_records) {
+            (ExportFormat::Txt, true) => txt_separator(include_metadata),
+            (ExportFormat::Json, true) => ",\n",
+            (ExportFormat::Json, false) => "\n",
+            _ => "",
+        };
+
+        file.writer
+            .write_all(separator.as_bytes())
+            .and_then(|_| file.writer.write_all(message.record.as_bytes()))
+            .context(format!("Failed to write to file [{:?}]", message.path))?;
+        file.has_records = true;
+
+        Ok((separator.len() + message.record.len()) as u64)
+    }
+
+    /// Flushes every condensed file, closing the JSON arrays, and uploads them (with object
+    /// storage).
+    pub fn finish(self) -> Result<()> {
+        let mut paths = Vec::with_capacity(self.condensed_files.len());
+
+        for (path, mut file) in self.condensed_files {
+            if self.format == ExportFormat::Json {
+                file.writer.write_all(b"\n]")?;
+            }
+
+            file.writer
+                .flush()
+                .context(format!("Failed to flush file [{:?}]", path))?;
+            paths.push(path);
+        }
+
+        if let Some(object_storage) = &self.object_storage {
+            for path in paths {
+                object_storage.upload_file(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_individual(&self, message: &RenderedMessage) -> Result<u64> {
+        let mut content = match self.format {
+            ExportFormat::Csv => csv_header(self.include_metadata)?,
+            _ => String::new(),
+        };
+        content.push_str(&message.record);
+        let size = content.len() as u64;
+
+        if let Some(object_storage) = &self.object_storage {
+            object_storage.put(&message.path, content.into_bytes())?;
+            return Ok(size);
+        }
+
+        message.path.ensure_parent_exists()?;
+        fs::write(&message.path, &content)
+            .context(format!("Failed to write to file [{:?}]", message.path))?;
+
+        Ok(size)
+    }
+}
+
+/// Opens a condensed file for appending, writing what a new file needs first (CSV header or
+/// the opening `[` of the JSON array).
+fn open_condensed_file(
+    path: &PathBuf,
+    format: ExportFormat,
+    include_metadata: bool,
+) -> Result<CondensedFile> {
+    path.ensure_parent_exists()?;
+
+    let existing_len = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let mut has_records = existing_len > 0;
+    let mut preamble = String::new();
+
+    match format {
+        ExportFormat::Txt => {}
+        ExportFormat::Csv => {
+            if !has_records {
+                preamble = csv_header(include_metadata)?;
+            }
+        }
+        ExportFormat::Json => match reopen_json_array(path)? {
+            Some(array_has_records) => has_records = array_has_records,
+            None => {
+                has_records = false;
+                preamble.push('[');
+            }
+        },
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context(format!("Failed to open file [{:?}]", path))?;
+
+    let mut writer = BufWriter::new(file);
+    writer.write_all(preamble.as_bytes())?;
+
+    Ok(CondensedFile {
+        writer,
+        has_records,
+    })
+}
+
+/// Removes the closing `]` of an existing JSON array, so new messages can be appended to it.
+///
+/// # Returns
+/// - `Ok(Some(has_records))`: The array was reopened; `has_records` tells if it has messages
+/// - `Ok(None)`: The file is new, empty, or not an array (and was truncated), so a new array
+///   must be started
+fn reopen_json_array(path: &Path) -> Result<Option<bool>> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(None);
+    };
+
+    let trimmed = content.trim_end();
+    let reopened = trimmed
+        .strip_suffix(']')
+        .filter(|_| trimmed.trim_start().starts_with('['));
+
+    let Some(reopened) = reopened else {
+        if !content.is_empty() {
+            fs::write(path, "").context("Failed to reset invalid JSON file")?;
+        }
+        return Ok(None);
+    };
+
+    let has_records = !reopened.trim_end().ends_with('[');
+    let file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .context(format!("Failed to open file [{:?}]", path))?;
+    file.set_len(reopened.len() as u64)?;
+
+    Ok(Some(has_records))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_file(name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("eh-export-{}", nanos))
+            .join(name)
+    }
+
+    fn rendered(path: &PathBuf, record: &str) -> RenderedMessage {
+        RenderedMessage {
+            key: "msg:0:1".to_string(),
+            path: path.clone(),
+            record: record.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_condensed_json_appends_to_existing_array() {
+        let path = temp_file("messages.json");
+
+        let mut writer = ExportWriter::new(ExportFormat::Json, false, true);
+        writer.write(&rendered(&path, r#"{"a":1}"#)).unwrap();
+        writer.finish().unwrap();
+
+        let mut writer = ExportWriter::new(ExportFormat::Json, false, true);
+        writer.write(&rendered(&path, r#"{"a":2}"#)).unwrap();
+        writer.write(&rendered(&path, r#"{"a":3}"#)).unwrap();
+        writer.finish().unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(parsed, serde_json::json!([{"a":1}, {"a":2}, {"a":3}]));
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_condensed_csv_writes_header_once() {
+        let path = temp_file("messages.csv");
+
+        for batch in [["one\n", "two\n"], ["three\n", "four\n"]] {
+            let mut writer = ExportWriter::new(ExportFormat::Csv, false, true);
+            for record in batch {
+                writer.write(&rendered(&path, record)).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "message_content\none\ntwo\nthree\nfour\n");
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
@@ -1,3 +1,4 @@
 pub mod export_message_csv;
 pub mod export_message_json;
 pub mod export_message_txt;
+pub mod export_writer;
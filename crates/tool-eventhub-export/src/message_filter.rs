@@ -0,0 +1,283 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::Value;
+use shared_eventhub::eventhub_models::InboundMessage;
+use shared_eventhub::utils::partition_selection::PartitionList;
+use std::str::FromStr;
+
+/// How the queued time of a message is compared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Before,
+    BeforeOrAt,
+    After,
+    AfterOrAt,
+}
+
+impl Comparison {
+    fn matches(&self, value: DateTime<Utc>, limit: DateTime<Utc>) -> bool {
+        match self {
+            Comparison::Before => value < limit,
+            Comparison::BeforeOrAt => value <= limit,
+            Comparison::After => value > limit,
+            Comparison::AfterOrAt => value >= limit,
+        }
+    }
+}
+
+/// Partitions accepted by a `partition=` predicate. EventHub partitions are numbers (and
+/// accept ranges), MQTT captures use the topic as partition id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartitionFilter {
+    Numbers(PartitionList),
+    Names(Vec<String>),
+}
+
+/// A condition a message must meet to be exported, given with `--where`.
+///
+/// - `$.device.id=abc`: the field of the JSON body equals the value (`!=` for not equal).
+///   Array items are selected by index (`$.items.0.sku`).
+/// - `queued>=2026-10-01`: the queued time is in range (`>`, `>=`, `<`, `<=`). Dates are UTC.
+/// - `partition=0,2-3`: the message came from one of the partitions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WherePredicate {
+    JsonEquals {
+        path: Vec<String>,
+        expected: String,
+        negated: bool,
+    },
+    QueuedTime {
+        comparison: Comparison,
+        limit: DateTime<Utc>,
+    },
+    Partition(PartitionFilter),
+}
+
+impl FromStr for WherePredicate {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+
+        if let Some(path) = value.strip_prefix("$.") {
+            let (path, expected, negated) = match path.split_once("!=") {
+                Some((path, expected)) => (path, expected, true),
+                None => {
+                    let (path, expected) = path.split_once('=').ok_or_else(|| {
+                        anyhow!("'{}' is missing the value, like $.field=value", value)
+                    })?;
+                    (path, expected, false)
+                }
+            };
+
+            let path: Vec<String> = path.trim().split('.').map(str::to_string).collect();
+            if path.iter().any(|key| key.is_empty()) {
+                bail!("'{}' has an empty field name in the JSON path", value);
+            }
+
+            return Ok(WherePredicate::JsonEquals {
+                path,
+                expected: expected.trim().to_string(),
+                negated,
+            });
+        }
+
+        if let Some(rest) = value.strip_prefix("queued") {
+            let (comparison, date) = [
+                (">=", Comparison::AfterOrAt),
+                ("<=", Comparison::BeforeOrAt),
+                (">", Comparison::After),
+                ("<", Comparison::Before),
+            ]
+            .into_iter()
+            .find_map(|(operator, comparison)| {
+                rest.trim_start()
+                    .strip_prefix(operator)
+                    .map(|date| (comparison, date))
+            })
+            .ok_or_else(|| anyhow!("'{}' needs one of >, >=, <, <= after queued", value))?;
+
+            return Ok(WherePredicate::QueuedTime {
+                comparison,
+                limit: parse_date(date.trim())?,
+            });
+        }
+
+        if let Some(partitions) = value.strip_prefix("partition=") {
+            let filter = match PartitionList::parse(partitions) {
+                Ok(list) if !list.is_empty() => PartitionFilter::Numbers(list),
+                _ => PartitionFilter::Names(
+                    partitions
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect(),
+                ),
+            };
+
+            if matches!(&filter, PartitionFilter::Names(names) if names.is_empty()) {
+                bail!("'{}' has no partitions", value);
+            }
+
+            return Ok(WherePredicate::Partition(filter));
+        }
+
+        bail!(
+            "'{}' is not a valid --where predicate. Use $.json.path=value, queued>=date, or partition=ids",
+            value
+        )
+    }
+}
+
+impl WherePredicate {
+    pub fn matches(&self, message: &InboundMessage) -> bool {
+        match self {
+            WherePredicate::JsonEquals {
+                path,
+                expected,
+                negated,
+            } => json_field_equals(&message.msg_data, path, expected) != *negated,
+            WherePredicate::QueuedTime { comparison, limit } => {
+                comparison.matches(message.queued_time, *limit)
+            }
+            WherePredicate::Partition(PartitionFilter::Numbers(list)) => {
+                list.contains_str(&message.partition_id)
+            }
+            WherePredicate::Partition(PartitionFilter::Names(names)) => {
+                names.contains(&message.partition_id)
+            }
+        }
+    }
+}
+
+/// All the `--where` predicates. A message is exported only if it meets every one of them.
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilter {
+    predicates: Vec<WherePredicate>,
+}
+
+impl MessageFilter {
+    /// # Errors
+    /// Returns error naming the first predicate that can't be parsed.
+    pub fn parse(predicates: &[String]) -> Result<Self> {
+        let predicates = predicates
+            .iter()
+            .map(|predicate| predicate.parse())
+            .collect::<Result<Vec<_>>>()
+            .context("Invalid --where predicate")?;
+
+        Ok(Self { predicates })
+    }
+
+    pub fn matches(&self, message: &InboundMessage) -> bool {
+        self.predicates
+            .iter()
+            .all(|predicate| predicate.matches(message))
+    }
+}
+
+/// Accepts RFC 3339 (`2026-10-01T12:00:00-03:00`), a UTC date and time (`2026-10-01T12:00:00`,
+/// or with a space), or a date (`2026-10-01`, at midnight UTC).
+fn parse_date(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.with_timezone(&Utc));
+    }
+
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(date.and_utc());
+        }
+    }
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        .map_err(|_| {
+            anyhow!(
+                "'{}' is not a valid date. Use 2026-10-01, 2026-10-01T12:00:00, or RFC 3339",
+                value
+            )
+        })
+}
+
+/// Compares the field as JSON when the expected value is JSON (`42`, `true`, `null`,
+/// `"quoted"`), and as text otherwise. Bodies that aren't JSON never match.
+fn json_field_equals(body: &str, path: &[String], expected: &str) -> bool {
+    let Ok(json) = serde_json::from_str::<Value>(body) else {
+        return false;
+    };
+
+    let found = path.iter().try_fold(&json, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => value.get(key.as_str()),
+    });
+
+    let Some(found) = found else {
+        return false;
+    };
+
+    if let Ok(expected_json) = serde_json::from_str::<Value>(expected) {
+        if *found == expected_json {
+            return true;
+        }
+    }
+
+    matches!(found, Value::String(text) if text == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_eventhub::eventhub_models::MessageStatus;
+
+    fn message(partition_id: &str, queued_time: &str, body: &str) -> InboundMessage {
+        InboundMessage {
+            id: "1".to_string(),
+            event_id: "1".to_string(),
+            partition_key: None,
+            partition_id: partition_id.to_string(),
+            queued_time: parse_date(queued_time).unwrap(),
+            event_seq_number: Some(1),
+            event_offset: None,
+            suggested_filename: None,
+            processed_at: Utc::now(),
+            msg_data: body.to_string(),
+            status: MessageStatus::Read,
+        }
+    }
+
+    fn filter(predicates: &[&str]) -> MessageFilter {
+        let predicates: Vec<String> = predicates.iter().map(|p| p.to_string()).collect();
+        MessageFilter::parse(&predicates).unwrap()
+    }
+
+    #[test]
+    fn test_json_path_predicates() {
+        let body = r#"{"device":{"id":"d-1","online":true},"temp":21.5,"items":[{"sku":"A1"}]}"#;
+        let msg = message("0", "2026-10-01", body);
+
+        assert!(filter(&["$.device.id=d-1"]).matches(&msg));
+        assert!(filter(&["$.device.online=true", "$.temp=21.5"]).matches(&msg));
+        assert!(filter(&["$.items.0.sku=A1"]).matches(&msg));
+        assert!(filter(&["$.device.id!=d-2"]).matches(&msg));
+        assert!(!filter(&["$.device.id=d-2"]).matches(&msg));
+        assert!(!filter(&["$.missing=1"]).matches(&msg));
+        assert!(!filter(&["$.device.id=d-1"]).matches(&message("0", "2026-10-01", "not json")));
+    }
+
+    #[test]
+    fn test_date_and_partition_predicates() {
+        let msg = message("3", "2026-10-05T10:00:00Z", "{}");
+
+        assert!(filter(&["queued>=2026-10-01", "queued<2026-10-06"]).matches(&msg));
+        assert!(!filter(&["queued>2026-10-05T10:00:00"]).matches(&msg));
+        assert!(filter(&["partition=0,2-3"]).matches(&msg));
+        assert!(!filter(&["partition=0-2"]).matches(&msg));
+
+        let mqtt = message("sensors/room-1", "2026-10-05", "{}");
+        assert!(filter(&["partition=sensors/room-1,sensors/room-2"]).matches(&mqtt));
+
+        let invalid = vec!["queued=2026-10-01".to_string()];
+        assert!(MessageFilter::parse(&invalid).is_err());
+        assert!(MessageFilter::parse(&["color=red".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+use anyhow::{anyhow, bail, Context, Result};
+use object_store::aws::AmazonS3Builder;
+use object_store::azure::{AzureConfigKey, MicrosoftAzureBuilder};
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, PutPayload, WriteMultipart};
+use shared_eventhub::eventhub_models::ObjectStorageConfig;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::runtime::Handle;
+
+/// Files bigger than this are sent with a multipart upload, in parts of this size.
+const MULTIPART_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Parts uploaded at the same time, in a multipart upload.
+const MULTIPART_CONCURRENCY: usize = 4;
+
+/// Uploads the exported files to an Azure Blob container or an S3 bucket.
+///
+/// Objects keep the path the file would have in the export folder (e.g.:
+/// `<prefix>/2026-10/messages-2026-10.json`). The uploads block, so the writer keeps the order
+/// of the messages; they run on the runtime the sink was created in.
+pub struct ObjectStorageSink {
+    store: Arc<dyn ObjectStore>,
+    prefix: String,
+    export_root: PathBuf,
+    runtime: Handle,
+}
+
+impl ObjectStorageSink {
+    /// Connects to the storage of the config. Must be called inside the tokio runtime.
+    ///
+    /// # Arguments
+    /// - `config`: Storage and credentials
+    /// - `export_root`: Export folder; the object paths are relative to it
+    ///
+    /// # Errors
+    /// Returns error if the connection string or the settings are invalid.
+    pub fn new(config: &ObjectStorageConfig, export_root: &Path) -> Result<Self> {
+        let (store, prefix): (Arc<dyn ObjectStore>, &str) = match config {
+            ObjectStorageConfig::AzureBlob {
+                connection_string,
+                container,
+                prefix,
+            } => (
+                Arc::new(
+                    azure_builder(connection_string)?
+                        .with_container_name(container)
+                        .build()
+                        .context("Failed to set up the Azure Blob container")?,
+                ),
+                prefix,
+            ),
+            ObjectStorageConfig::S3 {
+                bucket,
+                region,
+                access_key_id,
+                secret_access_key,
+                endpoint,
+                prefix,
+            } => {
+                let mut builder = AmazonS3Builder::from_env().with_bucket_name(bucket);
+
+                if let Some(region) = region {
+                    builder = builder.with_region(region);
+                }
+                if let Some(access_key_id) = access_key_id {
+                    builder = builder.with_access_key_id(access_key_id);
+                }
+                if let Some(secret_access_key) = secret_access_key {
+                    builder = builder.with_secret_access_key(secret_access_key);
+                }
+                if let Some(endpoint) = endpoint {
+                    builder = builder
+                        .with_allow_http(endpoint.starts_with("http://"))
+                        .with_endpoint(endpoint);
+                }
+
+                (
+                    Arc::new(builder.build().context("Failed to set up the S3 bucket")?),
+                    prefix,
+                )
+            }
+        };
+
+        Ok(Self::with_store(store, prefix, export_root))
+    }
+
+    fn with_store(store: Arc<dyn ObjectStore>, prefix: &str, export_root: &Path) -> Self {
+        Self {
+            store,
+            prefix: prefix.trim_matches('/').to_string(),
+            export_root: export_root.to_path_buf(),
+            runtime: Handle::current(),
+        }
+    }
+
+    /// The object a file of the export folder is uploaded to.
+    ///
+    /// # Errors
+    /// Returns error if the file is not in the export folder.
+    pub fn object_path(&self, local_path: &Path) -> Result<ObjectPath> {
+        let relative = local_path.strip_prefix(&self.export_root).map_err(|_| {
+            anyhow!(
+                "File [{:?}] is not in the export folder [{:?}]",
+                local_path,
+                self.export_root
+            )
+        })?;
+
+        let mut parts: Vec<String> = Vec::new();
+        if !self.prefix.is_empty() {
+            parts.extend(self.prefix.split('/').map(str::to_string));
+        }
+        parts.extend(
+            relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy().to_string()),
+        );
+
+        Ok(ObjectPath::from_iter(parts))
+    }
+
+    /// Uploads `content` as the object of `local_path`, replacing it. The file itself is not
+    /// needed (e.g.: individual message files are never written to disk).
+    ///
+    /// # Errors
+    /// Returns error if the upload fails.
+    pub fn put(&self, local_path: &Path, content: Vec<u8>) -> Result<()> {
+        let object_path = self.object_path(local_path)?;
+
+        self.runtime
+            .block_on(self.store.put(&object_path, PutPayload::from(content)))
+            .context(format!("Failed to upload [{}]", object_path))?;
+
+        Ok(())
+    }
+
+    /// Uploads a file of the export folder, replacing its object. Big files are sent in parts.
+    ///
+    /// # Errors
+    /// Returns error if the file can't be read, or the upload fails (a multipart upload that
+    /// fails is aborted).
+    pub fn upload_file(&self, local_path: &Path) -> Result<()> {
+        let size = std::fs::metadata(local_path)
+            .context(format!("Failed to read file [{:?}]", local_path))?
+            .len();
+
+        if size <= MULTIPART_CHUNK_SIZE as u64 {
+            let content = std::fs::read(local_path)
+                .context(format!("Failed to read file [{:?}]", local_path))?;
+            return self.put(local_path, content);
+        }
+
+        let object_path = self.object_path(local_path)?;
+        let mut file =
+            File::open(local_path).context(format!("Failed to open file [{:?}]", local_path))?;
+
+        self.runtime.block_on(async {
+            let upload = self
+                .store
+                .put_multipart(&object_path)
+                .await
+                .context(format!("Failed to start the upload of [{}]", object_path))?;
+            let mut writer = WriteMultipart::new_with_chunk_size(upload, MULTIPART_CHUNK_SIZE);
+            let mut buffer = vec![0; MULTIPART_CHUNK_SIZE];
+
+            let sent = loop {
+                let read = match file.read(&mut buffer) {
+                    Ok(0) => break Ok(()),
+                    Ok(read) => read,
+                    Err(e) => break Err(anyhow!(e)),
+                };
+
+                if let Err(e) = writer.wait_for_capacity(MULTIPART_CONCURRENCY).await {
+                    break Err(anyhow!(e));
+                }
+                writer.write(&buffer[..read]);
+            };
+
+            if let Err(e) = sent {
+                let _ = writer.abort().await;
+                bail!("Failed to upload [{}]: {}", object_path, e);
+            }
+
+            writer
+                .finish()
+                .await
+                .context(format!("Failed to finish the upload of [{}]", object_path))?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Reads the account, and the key or SAS token, from a storage account connection string.
+/// `UseDevelopmentStorage=true` connects to the local emulator (Azurite).
+fn azure_builder(connection_string: &str) -> Result<MicrosoftAzureBuilder> {
+    let settings: Vec<(&str, &str)> = connection_string
+        .split(';')
+        .filter_map(|part| part.trim().split_once('='))
+        .collect();
+
+    let setting = |name: &str| {
+        settings
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.to_string())
+    };
+
+    if setting("UseDevelopmentStorage").is_some_and(|value| value.eq_ignore_ascii_case("true")) {
+        return Ok(MicrosoftAzureBuilder::new().with_use_emulator(true));
+    }
+
+    let account = setting("AccountName")
+        .ok_or_else(|| anyhow!("The Azure connection string has no AccountName"))?;
+    let mut builder = MicrosoftAzureBuilder::new().with_account(account);
+
+    if let Some(account_key) = setting("AccountKey") {
+        builder = builder.with_access_key(account_key);
+    } else if let Some(sas) = setting("SharedAccessSignature") {
+        builder = builder.with_config(AzureConfigKey::SasKey, sas);
+    } else {
+        bail!("The Azure connection string needs an AccountKey or a SharedAccessSignature");
+    }
+
+    if let Some(endpoint) = setting("BlobEndpoint") {
+        builder = builder
+            .with_allow_http(endpoint.starts_with("http://"))
+            .with_endpoint(endpoint);
+    }
+
+    Ok(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_uploads_keep_the_export_folder_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(InMemory::new());
+        let sink = ObjectStorageSink::with_store(store.clone(), "/eventhub/", dir.path());
+
+        let condensed = dir.path().join("2026-10").join("messages-2026-10.json");
+        std::fs::create_dir_all(condensed.parent().unwrap()).unwrap();
+        std::fs::write(&condensed, "[\n{\"a\":1}\n]").unwrap();
+        let individual = dir.path().join("2026-10").join("16").join("msg-1.txt");
+
+        let uploaded = tokio::task::spawn_blocking(move || {
+            sink.upload_file(&condensed)?;
+            sink.put(&individual, b"hello".to_vec())?;
+            assert!(sink.object_path(Path::new("/elsewhere/file.txt")).is_err());
+            anyhow::Ok(())
+        })
+        .await
+        .unwrap();
+        uploaded.unwrap();
+
+        let read = |path: &str| {
+            let store = store.clone();
+            let path = ObjectPath::from(path);
+            async move { store.get(&path).await.unwrap().bytes().await.unwrap() }
+        };
+
+        assert_eq!(
+            read("eventhub/2026-10/messages-2026-10.json")
+                .await
+                .as_ref(),
+            b"[\n{\"a\":1}\n]"
+        );
+        assert_eq!(
+            read("eventhub/2026-10/16/msg-1.txt").await.as_ref(),
+            b"hello"
+        );
+    }
+
+    #[test]
+    fn test_azure_connection_string() {
+        assert!(azure_builder(
+            "DefaultEndpointsProtocol=https;AccountName=acc;AccountKey=a2V5;EndpointSuffix=core.windows.net"
+        )
+        .is_ok());
+        assert!(azure_builder("AccountName=acc;SharedAccessSignature=sv=2024&sig=abc").is_ok());
+        assert!(azure_builder("UseDevelopmentStorage=true").is_ok());
+        assert!(azure_builder("AccountName=acc").is_err());
+        assert!(azure_builder("AccountKey=a2V5").is_err());
+    }
+}
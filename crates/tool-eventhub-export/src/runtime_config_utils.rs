@@ -1,3 +1,4 @@
+use crate::message_filter::MessageFilter;
 use anyhow::{anyhow, Result};
 use shared_eventhub::eventhub_models::EventHubConfig;
 use std::path::{Path, PathBuf};
@@ -16,6 +17,7 @@ use std::path::{Path, PathBuf};
 /// # Override Categories
 /// - Database paths and base folders
 /// - Export format and output options
+/// - Number of export workers
 /// - Filtering and checkpoint behavior
 pub fn apply_cli_overrides(
     config: &mut EventHubConfig,
@@ -75,6 +77,10 @@ pub fn apply_cli_overrides(
         config.export_config.dump_filter = Some(dump_filter.cloned().collect());
     }
 
+    if let Some(where_filter) = matches.get_many::<String>("where") {
+        config.export_config.where_filter = Some(where_filter.cloned().collect());
+    }
+
     if let Some(export_folder) = matches.get_one::<String>("export-folder") {
         config.export_config.export_folder = export_folder.clone();
     }
@@ -87,6 +93,18 @@ pub fn apply_cli_overrides(
         config.export_config.use_local_time = true;
     }
 
+    if let Some(workers) = matches.get_one::<usize>("workers") {
+        config.export_config.workers = *workers;
+    }
+
+    if let Some(source_db) = matches.get_one::<String>("source-db") {
+        config.export_config.source_database = Some(if PathBuf::from(source_db).is_absolute() {
+            source_db.clone()
+        } else {
+            current_dir.join(source_db).to_string_lossy().to_string()
+        });
+    }
+
     Ok(())
 }
 
@@ -102,8 +120,10 @@ pub fn apply_cli_overrides(
 /// # Validation Rules
 /// - Export format must be txt, csv, or json
 /// - Feedback interval must be positive
+/// - At least one worker
+/// - `--where` predicates must be valid
 /// - Required paths must be specified and exist
-/// - Connection string and entity path cannot be empty
+/// - Connection string and entity path cannot be empty, unless a source database is informed
 pub fn validate_config(config: &EventHubConfig) -> Result<()> {
     // Validate export config
     if !["txt", "csv", "json"].contains(&config.export_config.export_format.as_str()) {
@@ -120,6 +140,26 @@ pub fn validate_config(config: &EventHubConfig) -> Result<()> {
         ));
     }
 
+    if config.export_config.workers == 0 {
+        return Err(anyhow!("workers must be at least 1"));
+    }
+
+    if let Some(where_filter) = &config.export_config.where_filter {
+        MessageFilter::parse(where_filter)?;
+    }
+
+    // With an explicit source database, there's nothing to derive from the connection string.
+    if let Some(source_database) = &config.export_config.source_database {
+        if !PathBuf::from(source_database).exists() {
+            return Err(anyhow!(
+                "Source database {:?} does not exist",
+                source_database
+            ));
+        }
+
+        return Ok(());
+    }
+
     // Validate that we have the minimum required config to locate the source database
     if config.inbound_config.base_data_folder.is_empty() {
         return Err(anyhow!(
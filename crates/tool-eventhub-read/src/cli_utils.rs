@@ -1,8 +1,9 @@
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
 use shared_eventhub::eventhub_models::EventHubConfig;
 use shared_eventhub::utils::cli_arguments::CommandCommonExt;
+use std::ffi::OsString;
 
 /// Displays EventHub Reader runtime configuration.
 ///
@@ -48,6 +49,18 @@ pub fn print_runtime_info(config: &mut EventHubConfig) {
         };
         println!("🔍 Dump Filter: {}", dump_filter_message);
     }
+    if !config.inbound_config.transforms.is_empty() {
+        let steps: Vec<String> = config
+            .inbound_config
+            .transforms
+            .iter()
+            .map(|step| step.label())
+            .collect();
+        println!("🔧 Transforms: {}", steps.join(" > "));
+    }
+    if let Some(address) = &config.inbound_config.metrics_address {
+        println!("📈 Metrics: http://{}/metrics", address);
+    }
     println!(
         "⚡ Feedback: Every {} second(s)",
         &config.inbound_config.feedback_interval
@@ -62,7 +75,7 @@ pub fn print_runtime_info(config: &mut EventHubConfig) {
 ///
 /// # Returns
 /// Parsed command-line arguments as `ArgMatches`
-pub fn get_cli_arguments() -> ArgMatches {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> ArgMatches {
     Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -72,7 +85,14 @@ pub fn get_cli_arguments() -> ArgMatches {
             Command line arguments take precedence over JSON configuration values.")
         .preset_arg_verbose(None)
         .preset_arg_config(None)
+        .preset_args_log_file()
         .add_eh_base_shared_args()
         .add_eh_reader_args()
-        .get_matches()
+        .arg(
+            Arg::new("reprocess-dead-letters")
+                .long("reprocess-dead-letters")
+                .action(ArgAction::SetTrue)
+                .help("Processes the messages that failed before (the dead letters) again, instead of reading from the EventHub"),
+        )
+        .get_matches_from(argv)
 }
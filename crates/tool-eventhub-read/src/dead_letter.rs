@@ -0,0 +1,195 @@
+use anyhow::{Context, Result};
+use azeventhubs::ReceivedEventData;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sled::{Db, IVec, Tree};
+
+/// Name of the sled tree with the messages that failed, next to the messages of the reader.
+const DEAD_LETTER_TREE: &str = "dead_letter";
+
+/// An event as received from the EventHub, before any processing. Kept as-is, so it can be
+/// processed again later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RawEvent {
+    pub partition_id: String,
+    pub sequence_number: i64,
+    pub offset: Option<String>,
+    pub partition_key: Option<String>,
+    pub enqueued_time: DateTime<Utc>,
+    /// Stored as base64, so binary (e.g.: gzip) bodies survive the JSON.
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    pub body: Vec<u8>,
+}
+
+impl RawEvent {
+    /// Copies the metadata of the event. The body is read separately, since reading it can
+    /// fail.
+    pub fn from_metadata(partition_id: &str, received_event: &ReceivedEventData) -> Self {
+        Self {
+            partition_id: partition_id.to_string(),
+            sequence_number: received_event.sequence_number(),
+            offset: received_event.offset().map(|offset| offset.to_string()),
+            partition_key: received_event.partition_key().map(|key| key.to_string()),
+            enqueued_time: DateTime::from_timestamp(
+                received_event.enqueued_time().unix_timestamp(),
+                0,
+            )
+            .unwrap_or_else(Utc::now),
+            body: Vec::new(),
+        }
+    }
+}
+
+/// A message that failed, with the reason.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub event: RawEvent,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+    /// How many times the message failed: once when read, plus once per failed reprocessing.
+    pub attempts: u32,
+}
+
+/// Messages that failed to be processed (body, transform, database, or file export errors),
+/// kept in their own sled tree until they are reprocessed.
+#[derive(Clone)]
+pub struct DeadLetterStore {
+    tree: Tree,
+    entity_path: String,
+}
+
+impl DeadLetterStore {
+    /// # Errors
+    /// Returns error if the tree can't be opened.
+    pub fn open(db: &Db, entity_path: &str) -> Result<Self> {
+        let tree = db
+            .open_tree(DEAD_LETTER_TREE)
+            .context("Failed to open the dead-letter store")?;
+
+        Ok(Self {
+            tree,
+            entity_path: entity_path.to_string(),
+        })
+    }
+
+    fn key(&self, event: &RawEvent) -> String {
+        format!(
+            "dead:{}:{}:{}",
+            self.entity_path, event.partition_id, event.sequence_number
+        )
+    }
+
+    /// Stores the event with the error. If it was already there, the error is updated and
+    /// the attempts are counted.
+    ///
+    /// # Errors
+    /// Returns error if the entry can't be read, serialized, or stored.
+    pub fn add(&self, event: &RawEvent, error: &anyhow::Error) -> Result<()> {
+        let key = self.key(event);
+
+        let attempts = match self.tree.get(&key)? {
+            Some(existing) => serde_json::from_slice::<DeadLetter>(&existing)
+                .map(|existing| existing.attempts + 1)
+                .unwrap_or(1),
+            None => 1,
+        };
+
+        let dead_letter = DeadLetter {
+            event: event.clone(),
+            error: format!("{:#}", error),
+            failed_at: Utc::now(),
+            attempts,
+        };
+
+        self.tree
+            .insert(
+                key,
+                serde_json::to_vec(&dead_letter).context("Failed to serialize dead letter")?,
+            )
+            .context("Failed to store dead letter")?;
+
+        Ok(())
+    }
+
+    /// Removes the event, after it was reprocessed.
+    ///
+    /// # Errors
+    /// Returns error if the entry can't be removed.
+    pub fn remove(&self, event: &RawEvent) -> Result<()> {
+        self.tree
+            .remove(self.key(event))
+            .context("Failed to remove dead letter")?;
+        Ok(())
+    }
+
+    /// The dead letters of the entity. Entries that can't be read are skipped.
+    ///
+    /// # Errors
+    /// Returns error if the tree can't be read.
+    pub fn entries(&self) -> Result<Vec<DeadLetter>> {
+        let prefix = format!("dead:{}:", self.entity_path);
+
+        let mut entries = Vec::new();
+        for entry in self.tree.scan_prefix(prefix) {
+            let (_, value): (IVec, IVec) = entry.context("Failed to read the dead letters")?;
+            if let Ok(dead_letter) = serde_json::from_slice::<DeadLetter>(&value) {
+                entries.push(dead_letter);
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+fn to_base64<S: Serializer>(body: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&STANDARD.encode(body))
+}
+
+fn from_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    STANDARD.decode(text).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn raw_event(sequence_number: i64) -> RawEvent {
+        RawEvent {
+            partition_id: "0".to_string(),
+            sequence_number,
+            offset: Some("1024".to_string()),
+            partition_key: None,
+            enqueued_time: Utc::now(),
+            body: vec![0x1f, 0x8b, 0x00, 0xff],
+        }
+    }
+
+    #[test]
+    fn test_dead_letters_are_stored_counted_and_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = sled::open(dir.path()).unwrap();
+        let store = DeadLetterStore::open(&db, "events").unwrap();
+        let other_entity = DeadLetterStore::open(&db, "other").unwrap();
+
+        store.add(&raw_event(1), &anyhow!("not gzip")).unwrap();
+        store.add(&raw_event(2), &anyhow!("disk full")).unwrap();
+        store
+            .add(&raw_event(1), &anyhow!("still not gzip"))
+            .unwrap();
+
+        let entries = store.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].event.sequence_number, 1);
+        assert_eq!(entries[0].event.body, vec![0x1f, 0x8b, 0x00, 0xff]);
+        assert_eq!(entries[0].attempts, 2);
+        assert_eq!(entries[0].error, "still not gzip");
+        assert!(other_entity.entries().unwrap().is_empty());
+
+        store.remove(&entries[0].event).unwrap();
+        assert_eq!(store.entries().unwrap().len(), 1);
+    }
+}
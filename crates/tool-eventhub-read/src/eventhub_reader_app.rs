@@ -1,8 +1,11 @@
+use crate::dead_letter::{DeadLetterStore, RawEvent};
+use crate::message_transform::MessageTransformer;
+use crate::metrics_server::{spawn_lag_monitor, spawn_metrics_server, ReaderMetrics};
 use crate::progress_tracker::{OperationGuard, ProgressTracker};
 use anyhow::{anyhow, Context, Result};
 use azeventhubs::consumer::{EventPosition, ReadEventOptions};
 use azeventhubs::ReceivedEventData;
-use chrono::{DateTime, Local, Utc};
+use chrono::{Local, Utc};
 use futures_util::StreamExt;
 use shared::system::resolve_path_with_base::resolve_path_with_base;
 use shared::utils::message_matches_filter::message_matches_filter;
@@ -26,6 +29,9 @@ pub struct EventHubReader {
     db: Db,
     progress: Arc<ProgressTracker>,
     shutdown: Arc<AtomicBool>,
+    transformer: Arc<MessageTransformer>,
+    metrics: Arc<ReaderMetrics>,
+    dead_letters: DeadLetterStore,
 }
 
 impl EventHubReader {
@@ -35,8 +41,14 @@ impl EventHubReader {
     /// based on provided configuration.
     ///
     /// # Errors
-    /// Returns error if database path resolution or opening fails.
+    /// Returns error if database path resolution or opening fails, or if a transform step of
+    /// the config is invalid.
     pub async fn new(config: EventHubConfig) -> Result<Self> {
+        let transformer = Arc::new(
+            MessageTransformer::new(&config.inbound_config.transforms)
+                .context("Invalid transforms in the configuration")?,
+        );
+
         let db_path = get_eventhub_database_path(
             &config.connection_string,
             &config.inbound_config.base_data_folder,
@@ -46,6 +58,8 @@ impl EventHubReader {
 
         let db = sled::open(&db_path).context("Failed to open database")?;
 
+        let dead_letters = DeadLetterStore::open(&db, &config.entity_path)?;
+
         let progress = Arc::new(ProgressTracker::new(
             config.inbound_config.feedback_interval,
         ));
@@ -57,6 +71,9 @@ impl EventHubReader {
             db,
             progress,
             shutdown,
+            transformer,
+            metrics: Arc::new(ReaderMetrics::default()),
+            dead_letters,
         })
     }
 
@@ -172,6 +189,9 @@ impl EventHubReader {
         println!("Preparing export config...");
         self.prepare_export_folders().await?;
 
+        // Step 4: Start the metrics endpoint, if enabled
+        self.start_metrics_endpoint()?;
+
         // Step 5: Give the user some feedback...
         println!("Listening for messages...");
 
@@ -184,27 +204,61 @@ impl EventHubReader {
         self.read_messages().await
     }
 
-    /// Routes message reading to single partition or all partitions.
+    /// Routes message reading to single partition or multiple partitions.
     ///
-    /// Uses partition_id configuration: -1 for all partitions,
-    /// specific ID for single partition reading.
+    /// Uses partition_id and exclude_partition_ids configuration: when they narrow
+    /// the selection down to one partition, reads it directly; otherwise, reads
+    /// every selected partition concurrently.
     ///
     /// # Errors
     /// Returns error if partition reading fails.
     async fn read_messages(&mut self) -> Result<()> {
-        if self.config.inbound_config.partition_id == -1 {
-            Ok(self
-                .read_all_partitions()
+        let inbound_config = &self.config.inbound_config;
+
+        match inbound_config
+            .partition_id
+            .single_partition(&inbound_config.exclude_partition_ids)
+        {
+            Some(partition_id) => Ok(self
+                .read_single_partition(&partition_id)
                 .await
-                .context("Failed to read messages from all partitions")?)
-        } else {
-            Ok(self
-                .read_single_partition(&self.config.inbound_config.partition_id.to_string())
+                .context("Failed to read messages from single partition")?),
+            None => Ok(self
+                .read_selected_partitions()
                 .await
-                .context("Failed to read messages from single partition")?)
+                .context("Failed to read messages from the selected partitions")?),
         }
     }
 
+    /// Serves the Prometheus metrics and starts tracking the partition lag, when a metrics
+    /// address is configured.
+    ///
+    /// # Errors
+    /// Returns error if the metrics address is invalid.
+    fn start_metrics_endpoint(&self) -> Result<()> {
+        let Some(address) = &self.config.inbound_config.metrics_address else {
+            return Ok(());
+        };
+
+        let address = address
+            .parse()
+            .with_context(|| format!("Invalid metrics address: [{}]", address))?;
+
+        spawn_metrics_server(
+            address,
+            Arc::clone(&self.metrics),
+            Arc::clone(&self.progress),
+        );
+        spawn_lag_monitor(
+            self.config.clone(),
+            Arc::clone(&self.metrics),
+            Arc::clone(&self.shutdown),
+        );
+
+        println!("Metrics available at http://{}/metrics", address);
+        Ok(())
+    }
+
     /// Prepares export directories if file output is enabled.
     ///
     /// Creates necessary folders for message export when read_to_file
@@ -483,6 +537,15 @@ impl EventHubReader {
     async fn read_single_partition(&self, partition_id: &str) -> Result<()> {
         info!("Reading from partition: {}", partition_id);
 
+        let checkpoint_sequence = if self.config.inbound_config.ignore_checkpoint {
+            None
+        } else {
+            self.load_checkpoint(partition_id)?
+                .map(|checkpoint| checkpoint.sequence_number)
+        };
+        self.metrics
+            .register_partition(partition_id, checkpoint_sequence);
+
         // Create a new consumer client for this partition
         let mut consumer_client = self
             .config
@@ -531,12 +594,16 @@ impl EventHubReader {
                                 break;
                             }
 
-                            self.process_received_event(received_event, partition_id)
+                            let sequence_number = received_event.sequence_number();
+
+                            self.handle_received_event(received_event, partition_id)
                             .await
                             .context("Failed to process received event")?;
 
+                            self.metrics.record_event(partition_id, sequence_number);
+
                             // Always show progress after processing a message (forced update)
-                            self.progress.print_progress_forced();
+                            self.progress.print_progress();
                         }
                         Ok(Some(Err(e))) => {
                             error!("Error receiving events from partition {}: {}", partition_id, e);
@@ -664,16 +731,36 @@ impl EventHubReader {
         Ok(starting_position)
     }
 
-    //TODO: Add summaries
-    async fn read_all_partitions(&self) -> Result<()> {
-        info!("Reading from all partitions...");
+    /// Reads the selected partitions concurrently, one task per partition.
+    ///
+    /// Partitions are filtered by the partition_id selection and exclude_partition_ids,
+    /// so multiple reader instances can split the partitions between them.
+    ///
+    /// # Errors
+    /// Returns error if the partition IDs can't be retrieved, or if the selection
+    /// references partitions that don't exist or leaves nothing to read.
+    async fn read_selected_partitions(&self) -> Result<()> {
+        info!(
+            "Reading from partitions: {}",
+            self.config.inbound_config.get_partition_id_label()
+        );
 
-        let partition_ids = self
+        let available_partition_ids = self
             .get_runtime_partition_ids()
             .await
             .context("Failed to get partition IDs")?;
 
-        info!("Found {} partitions", partition_ids.len());
+        let partition_ids = self.config.inbound_config.partition_id.resolve(
+            &available_partition_ids,
+            &self.config.inbound_config.exclude_partition_ids,
+        )?;
+
+        info!(
+            "Found {} partitions, reading {}: [{}]",
+            available_partition_ids.len(),
+            partition_ids.len(),
+            partition_ids.join(",")
+        );
 
         // Use JoinSet for better task management
         let mut join_set = tokio::task::JoinSet::new();
@@ -792,18 +879,55 @@ impl EventHubReader {
         })
     }
 
-    /// Processes a received event, applying filters, saving it to the database, and handling checkpoints.
+    /// Processes an event received from the EventHub. When it fails, the event is moved to the
+    /// dead-letter store (with the error) instead of stopping the reader, and the checkpoint
+    /// moves past it.
     ///
-    /// # Arguments
-    ///
-    /// * `received_event` - The event data that needs to be processed, represented by `ReceivedEventData`.
-    /// * `partition_id` - A string slice representing the partition identifier where the event originated from.
+    /// # Errors
+    /// Returns error only if the failed event can't be stored in the dead-letter store.
+    async fn handle_received_event(
+        &self,
+        received_event: ReceivedEventData,
+        partition_id: &str,
+    ) -> Result<()> {
+        let mut raw_event = RawEvent::from_metadata(partition_id, &received_event);
+
+        let result = match received_event.body() {
+            Ok(body) => {
+                raw_event.body = body.to_vec();
+                self.process_raw_event(&raw_event, false).await
+            }
+            Err(e) => Err(anyhow!("Failed to read the event body: {}", e)),
+        };
+
+        if let Err(e) = result {
+            error!(
+                "Message {} of partition {} failed and was moved to the dead-letter store: {:#}",
+                raw_event.sequence_number, partition_id, e
+            );
+
+            self.dead_letters
+                .add(&raw_event, &e)
+                .context("Failed to store the failed message in the dead-letter store")?;
+
+            self.progress.increment_failed();
+
+            // The message is safe in the dead-letter store, so the reader can move past it.
+            self.save_event_checkpoint(&raw_event);
+        }
+
+        Ok(())
+    }
+
+    /// Processes a raw event, applying transforms and filters, saving it to the database, and
+    /// handling checkpoints.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// This function returns a `Result<()>`, which will:
-    /// - Return `Ok(())` if the event is successfully processed.
-    /// - Return an `anyhow::Error` if there is an error during processing.
+    /// * `raw_event` - The event, as received from the EventHub (or kept in the dead-letter store).
+    /// * `reprocessing` - When the event comes from the dead-letter store. The duplicate check
+    ///   and the checkpoint are skipped: the message may be in the database already (e.g.: when
+    ///   only the file export failed), and the checkpoint is past it.
     ///
     /// # Behavior
     ///
@@ -814,11 +938,12 @@ impl EventHubReader {
     ///    - Verifies if the event has already been processed using its unique `message_key`.
     ///    - If the message exists in the database and checkpoints are not ignored, it increments the duplicate counter and exits early.
     ///
-    /// 3. **Message Filtering**:
+    /// 3. **Message Transformation and Filtering**:
+    ///    - Runs the transform steps of the config (e.g.: gzip decompress, JSON projection) over the body.
     ///    - Applies dump filters when configured. If the message does not match the filter conditions, it skips processing and increments the skipped counter.
     ///
     /// 4. **Message Processing**:
-    ///    - Constructs an `InboundMessage` object using the received event's metadata and message data.
+    ///    - Constructs an `InboundMessage` object using the event's metadata and message data.
     ///    - Converts the event body (binary format) to a UTF-8 string before processing.
     ///
     /// 5. **Database Storage**:
@@ -828,7 +953,7 @@ impl EventHubReader {
     ///    - If the `read_to_file` configuration is enabled, exports the processed message to an external file asynchronously.
     ///
     /// 7. **Checkpointing**:
-    ///    - Saves a checkpoint for the received event after successful processing unless checkpoint saving is ignored.
+    ///    - Saves a checkpoint for the event after successful processing unless checkpoint saving is ignored.
     ///    - Logs an error in case of checkpoint saving issues but does not stop the processing flow.
     ///
     /// 8. **Progress Tracking**:
@@ -838,43 +963,35 @@ impl EventHubReader {
     /// # Errors
     ///
     /// - Returns an `anyhow::Error` if:
-    ///   - There are issues parsing or processing the event body.
+    ///   - A transform step fails (e.g.: a body that isn't gzip, or isn't JSON).
     ///   - Database operations (insertion or key lookups) fail.
     ///   - Serialization of the `InboundMessage` object fails.
     ///   - File export encounters errors when enabled.
-    ///
-    /// # Notes
-    ///
-    /// - This function is heavily reliant on the configuration provided in `self.config`.
-    /// - Errors during checkpoint saving are logged but do not interrupt the processing flow.
-    /// - Filtering behavior is conditional on the presence and content of the dump filters.
-    ///
-    /// # Dependencies
-    ///
-    /// - External libraries for JSON serialization (`serde_json`) and date/time handling (`DateTime`, `Utc`).
-    /// - Custom types like `ReceivedEventData`, `OperationGuard`, `InboundMessage`, and `MessageStatus`.
-    async fn process_received_event(
-        &self,
-        received_event: ReceivedEventData,
-        partition_id: &str,
-    ) -> Result<()> {
+    async fn process_raw_event(&self, raw_event: &RawEvent, reprocessing: bool) -> Result<()> {
         // Use a guard to ensure we properly track active operations
         let _guard = OperationGuard::new(&self.progress);
 
-        let sequence_number = received_event.sequence_number();
-        let event_id = sequence_number.to_string();
+        let partition_id = raw_event.partition_id.as_str();
+        let event_id = raw_event.sequence_number.to_string();
         let message_key = format!(
             "msg:{}:{}:{}",
             self.config.entity_path, partition_id, event_id
         );
 
         // Check if a message already processed (unless ignoring checkpoints)
-        if !self.config.inbound_config.ignore_checkpoint && self.db.contains_key(&message_key)? {
+        if !reprocessing
+            && !self.config.inbound_config.ignore_checkpoint
+            && self.db.contains_key(&message_key)?
+        {
             self.progress.increment_duplicated();
             return Ok(());
         }
 
-        let message_data = String::from_utf8_lossy(received_event.body()?).to_string();
+        let body = self
+            .transformer
+            .apply(&raw_event.body)
+            .with_context(|| format!("Failed to transform message {}", event_id))?;
+        let message_data = String::from_utf8_lossy(&body).to_string();
 
         // Apply dump filter if configured and not empty
         if let Some(filters) = &self.config.inbound_config.dump_filter {
@@ -887,15 +1004,11 @@ impl EventHubReader {
         let message = InboundMessage {
             id: event_id.clone(),
             event_id: event_id.clone(),
-            partition_key: received_event.partition_key().map(|s| s.to_string()),
+            partition_key: raw_event.partition_key.clone(),
             partition_id: partition_id.to_string(),
-            queued_time: DateTime::from_timestamp(
-                received_event.enqueued_time().unix_timestamp(),
-                0,
-            )
-            .unwrap_or_else(Utc::now),
-            event_seq_number: Some(sequence_number),
-            event_offset: received_event.offset().map(|o| o.to_string()),
+            queued_time: raw_event.enqueued_time,
+            event_seq_number: Some(raw_event.sequence_number),
+            event_offset: raw_event.offset.clone(),
             suggested_filename: None,
             processed_at: Utc::now(),
             msg_data: message_data,
@@ -915,20 +1028,9 @@ impl EventHubReader {
                 .context("Failed to export message to file")?;
         }
 
-        // Save checkpoint after successful processing (unless ignoring checkpoints)
-        if !self.config.inbound_config.ignore_checkpoint {
-            let offset_str = received_event
-                .offset()
-                .map(|o| o.to_string())
-                .unwrap_or_else(|| sequence_number.to_string());
-
-            if let Err(e) = self.save_checkpoint(partition_id, sequence_number, &offset_str) {
-                // Logging the error and moving on. Don't want to fail the whole run here.
-                error!(
-                    "Failed to save checkpoint for partition {}: {}",
-                    partition_id, e
-                );
-            }
+        // Save checkpoint after successful processing (unless reprocessing a dead letter)
+        if !reprocessing {
+            self.save_event_checkpoint(raw_event);
         }
 
         self.progress.increment_read();
@@ -941,6 +1043,96 @@ impl EventHubReader {
         Ok(())
     }
 
+    /// Saves the checkpoint of the partition at the event, unless ignoring checkpoints.
+    ///
+    /// Errors are logged: a missing checkpoint only means some messages are read again (and
+    /// counted as duplicated).
+    fn save_event_checkpoint(&self, raw_event: &RawEvent) {
+        if self.config.inbound_config.ignore_checkpoint {
+            return;
+        }
+
+        let offset_str = raw_event
+            .offset
+            .clone()
+            .unwrap_or_else(|| raw_event.sequence_number.to_string());
+
+        if let Err(e) = self.save_checkpoint(
+            &raw_event.partition_id,
+            raw_event.sequence_number,
+            &offset_str,
+        ) {
+            // Logging the error and moving on. Don't want to fail the whole run here.
+            error!(
+                "Failed to save checkpoint for partition {}: {}",
+                raw_event.partition_id, e
+            );
+        }
+    }
+
+    /// Processes the messages of the dead-letter store again (e.g.: after fixing the transforms
+    /// or freeing disk space), instead of reading from the EventHub.
+    ///
+    /// Messages that succeed are removed from the store. The ones that fail again stay, with
+    /// the new error.
+    ///
+    /// # Errors
+    /// Returns error if the dead-letter store can't be read or updated.
+    pub async fn reprocess_dead_letters(&self) -> Result<()> {
+        println!("Checking local database access...");
+        self.process_local_database_verification().await?;
+
+        let dead_letters = self.dead_letters.entries()?;
+        if dead_letters.is_empty() {
+            println!("No dead letters to reprocess.");
+            return Ok(());
+        }
+
+        println!("Reprocessing {} dead letter(s)...", dead_letters.len());
+        println!();
+
+        let mut reprocessed = 0;
+        let mut failed = 0;
+
+        for dead_letter in dead_letters {
+            if self.shutdown.load(Ordering::Relaxed) {
+                break;
+            }
+
+            match self.process_raw_event(&dead_letter.event, true).await {
+                Ok(_) => {
+                    self.dead_letters.remove(&dead_letter.event)?;
+                    reprocessed += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Message {} of partition {} failed again (attempt {}): {:#}",
+                        dead_letter.event.sequence_number,
+                        dead_letter.event.partition_id,
+                        dead_letter.attempts + 1,
+                        e
+                    );
+                    self.dead_letters.add(&dead_letter.event, &e)?;
+                    failed += 1;
+                }
+            }
+
+            if self.progress.should_show_progress() {
+                self.progress.print_progress();
+            }
+        }
+
+        self.progress.print_progress();
+
+        println!();
+        println!(
+            "Dead letters reprocessed: {}, still failing: {}",
+            reprocessed, failed
+        );
+
+        Ok(())
+    }
+
     async fn export_message_to_file(&self, message: &InboundMessage) -> Result<()> {
         info!("Exporting message {} to file...", message.event_id);
 
@@ -1126,13 +1318,15 @@ Filename: {}
         }
 
         // Phase 3: Statistics
-        let total_messages = self.progress.messages_read.load(Ordering::Relaxed);
+        let total_messages = self.progress.messages_read();
+
+        let total_skipped = self.progress.messages_skipped();
 
-        let total_skipped = self.progress.messages_skipped.load(Ordering::Relaxed);
+        let total_duplicated = self.progress.messages_duplicated();
 
-        let total_duplicated = self.progress.messages_duplicated.load(Ordering::Relaxed);
+        let total_failed = self.progress.messages_failed();
 
-        let total_runtime = self.progress.start_time.elapsed();
+        let total_runtime = self.progress.elapsed();
 
         println!();
 
@@ -1148,6 +1342,13 @@ Filename: {}
             println!("   - Duplicate messages: {}", total_duplicated);
         }
 
+        if total_failed > 0 {
+            println!(
+                "   - Failed messages (dead letters): {}. Use --reprocess-dead-letters to retry them.",
+                total_failed
+            );
+        }
+
         println!("   - Total runtime: {:.1}s", total_runtime.as_secs_f32());
 
         if total_messages > 0 {
@@ -1172,6 +1373,9 @@ impl Clone for EventHubReader {
             db: self.db.clone(),
             progress: Arc::clone(&self.progress),
             shutdown: Arc::clone(&self.shutdown),
+            transformer: Arc::clone(&self.transformer),
+            metrics: Arc::clone(&self.metrics),
+            dead_letters: self.dead_letters.clone(),
         }
     }
 }
@@ -0,0 +1,128 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info};
+use crate::eventhub_reader_app::EventHubReader;
+use crate::graceful_shutdown::{graceful_shutdown_routine, setup_graceful_shutdown};
+use crate::runtime_config_utils::{apply_cli_overrides, validate_config};
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::{initialize_log_with_settings, LogSettings};
+use shared::system::get_current_working_dir::get_current_working_dir;
+use shared_eventhub::utils::config_utils::get_base_config_object;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod dead_letter;
+mod eventhub_reader_app;
+mod graceful_shutdown;
+mod message_transform;
+mod metrics_server;
+mod progress_tracker;
+mod runtime_config_utils;
+
+/// EventHub message reader with checkpoint/resume support.
+///
+/// Initializes logging, parses CLI arguments, loads configuration,
+/// validates settings, creates EventHub consumer, and processes messages
+/// until completion or shutdown signal.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+///
+/// # Errors
+/// Returns error if configuration is invalid, EventHub connection fails,
+/// or message processing encounters unrecoverable errors.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    runtime.block_on(run_tool(argv))
+}
+
+async fn run_tool(argv: Vec<OsString>) -> i32 {
+    // Get CLI arguments
+    let matches = get_cli_arguments(argv);
+
+    // Initialize logging for the app, with the format and files set by the --log-* flags
+    let log_settings = match LogSettings::from_matches(&matches) {
+        Ok(log_settings) => log_settings,
+        Err(e) => {
+            eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+    initialize_log_with_settings(env!("CARGO_PKG_NAME"), LogLevel::Info, &log_settings);
+
+    // Load config from the JSON file
+    let current_dir = get_current_working_dir();
+    let mut config = match get_base_config_object(&matches, &current_dir).await {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to load config from the file: {}", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    // Update config loaded from the file with the CLI arguments
+    if let Err(e) = apply_cli_overrides(&mut config, &matches, &current_dir) {
+        error!("Failed to apply CLI overrides: {}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    // Validate config
+    if let Err(e) = validate_config(&config) {
+        error!("Invalid configuration file detected: [{}]", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    // Print the runtime info so that the user knows what is going on
+    print_runtime_info(&mut config);
+
+    // Create EventHub reader Instance
+    let mut reader = match EventHubReader::new(config).await {
+        Ok(reader) => reader,
+        Err(e) => {
+            error!("Failed to create EventHub reader: {}", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    // The setup graceful shutdown
+    if let Err(e) = setup_graceful_shutdown(&mut reader) {
+        error!("Failed to setup graceful shutdown: {}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    // Start reading the messages (or reprocessing the dead letters) and wait until it finishes,
+    // an error occurs, or the user presses Ctrl+C
+    let result = if matches.get_flag("reprocess-dead-letters") {
+        reader.reprocess_dead_letters().await
+    } else {
+        reader.start_reading().await
+    };
+
+    // Final cleanup with the graceful shutdown and timeout
+    println!("[OK]  Cleaning up resources...");
+
+    // Use a timeout for graceful shutdown to prevent hanging forever
+    if let Err(e) = graceful_shutdown_routine(reader, result).await {
+        error!("Failed to gracefully shutdown: {}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
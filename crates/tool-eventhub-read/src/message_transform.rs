@@ -0,0 +1,222 @@
+use anyhow::{anyhow, Context, Result};
+use base64::alphabet::STANDARD;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use flate2::read::MultiGzDecoder;
+use regex::Regex;
+use serde_json::{Map, Value};
+use shared_eventhub::eventhub_models::TransformStep;
+use std::borrow::Cow;
+use std::io::Read;
+
+/// Standard base64, with or without padding.
+const BASE64_ANY_PADDING: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// A `TransformStep` ready to run: regexes compiled and field paths split.
+enum CompiledStep {
+    GzipDecompress,
+    Base64Decode,
+    JsonProject(Vec<Vec<String>>),
+    RegexReplace(Regex, String),
+}
+
+/// Transformation pipeline applied to the body of each message, before it is filtered and
+/// stored.
+pub struct MessageTransformer {
+    steps: Vec<(String, CompiledStep)>,
+}
+
+impl MessageTransformer {
+    /// Prepares the steps of the config file.
+    ///
+    /// # Errors
+    /// Returns an error if a regex is invalid, or a projection has no fields.
+    pub fn new(steps: &[TransformStep]) -> Result<Self> {
+        let steps = steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                compile_step(step)
+                    .map(|compiled| (step.label(), compiled))
+                    .with_context(|| format!("Invalid transform step #{}", index + 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { steps })
+    }
+
+    /// Runs the steps, in order, over the body. Without steps, the body is returned as-is.
+    ///
+    /// # Errors
+    /// Returns an error naming the step that failed (e.g.: a body that isn't gzip).
+    pub fn apply<'a>(&self, body: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+        let mut current = Cow::Borrowed(body);
+
+        for (label, step) in &self.steps {
+            current = Cow::Owned(
+                run_step(step, &current)
+                    .with_context(|| format!("Transform step {} failed", label))?,
+            );
+        }
+
+        Ok(current)
+    }
+}
+
+fn compile_step(step: &TransformStep) -> Result<CompiledStep> {
+    match step {
+        TransformStep::GzipDecompress => Ok(CompiledStep::GzipDecompress),
+        TransformStep::Base64Decode => Ok(CompiledStep::Base64Decode),
+        TransformStep::JsonProject { fields } => {
+            if fields.is_empty() {
+                return Err(anyhow!("json_project needs at least one field"));
+            }
+
+            Ok(CompiledStep::JsonProject(
+                fields
+                    .iter()
+                    .map(|field| field.split('.').map(str::to_string).collect())
+                    .collect(),
+            ))
+        }
+        TransformStep::RegexReplace {
+            pattern,
+            replacement,
+        } => {
+            let regex = Regex::new(pattern)
+                .with_context(|| format!("'{}' is not a valid regex", pattern))?;
+            Ok(CompiledStep::RegexReplace(regex, replacement.clone()))
+        }
+    }
+}
+
+fn run_step(step: &CompiledStep, body: &[u8]) -> Result<Vec<u8>> {
+    match step {
+        CompiledStep::GzipDecompress => {
+            let mut decompressed = Vec::new();
+            MultiGzDecoder::new(body)
+                .read_to_end(&mut decompressed)
+                .context("The body is not valid gzip")?;
+            Ok(decompressed)
+        }
+        CompiledStep::Base64Decode => {
+            let text: Vec<u8> = body
+                .iter()
+                .copied()
+                .filter(|byte| !byte.is_ascii_whitespace())
+                .collect();
+            BASE64_ANY_PADDING
+                .decode(text)
+                .context("The body is not valid base64")
+        }
+        CompiledStep::JsonProject(paths) => {
+            let json: Value = serde_json::from_slice(body).context("The body is not valid JSON")?;
+            Ok(serde_json::to_vec(&project_fields(&json, paths))?)
+        }
+        CompiledStep::RegexReplace(regex, replacement) => {
+            let text = String::from_utf8_lossy(body);
+            Ok(regex
+                .replace_all(&text, replacement.as_str())
+                .into_owned()
+                .into_bytes())
+        }
+    }
+}
+
+/// Copies the fields found in `json` to a new object, keeping their nesting. Missing fields are
+/// left out.
+fn project_fields(json: &Value, paths: &[Vec<String>]) -> Value {
+    let mut projected = Map::new();
+
+    for path in paths {
+        let found = path
+            .iter()
+            .try_fold(json, |value, key| value.get(key.as_str()));
+
+        if let Some(value) = found {
+            insert_at_path(&mut projected, path, value.clone());
+        }
+    }
+
+    Value::Object(projected)
+}
+
+fn insert_at_path(target: &mut Map<String, Value>, path: &[String], value: Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+
+    let mut current = target;
+    for key in parents {
+        let entry = current
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        current = match entry {
+            Value::Object(map) => map,
+            // The parent itself was projected already, and has this field.
+            _ => return,
+        };
+    }
+
+    current.insert(last.clone(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn transformer(steps: &str) -> MessageTransformer {
+        let steps: Vec<TransformStep> = serde_json::from_str(steps).unwrap();
+        MessageTransformer::new(&steps).unwrap()
+    }
+
+    #[test]
+    fn test_steps_run_in_order() {
+        let body = r#"{"device":{"id":"d-1","fw":"2.1"},"card":"4111111111111111","noise":1}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+        let message = BASE64.encode(encoder.finish().unwrap());
+
+        let transformer = transformer(
+            r#"[
+                { "type": "base64_decode" },
+                { "type": "gzip_decompress" },
+                { "type": "json_project", "fields": ["device.id", "card", "missing.field"] },
+                { "type": "regex_replace", "pattern": "\\d{12}(\\d{4})", "replacement": "************$1" }
+            ]"#,
+        );
+
+        let result: Value =
+            serde_json::from_slice(&transformer.apply(message.as_bytes()).unwrap()).unwrap();
+        assert_eq!(
+            result,
+            serde_json::json!({ "device": { "id": "d-1" }, "card": "************1111" })
+        );
+    }
+
+    #[test]
+    fn test_no_steps_and_failures() {
+        let empty = transformer("[]");
+        assert!(matches!(
+            empty.apply(b"as-is").unwrap(),
+            Cow::Borrowed(b"as-is")
+        ));
+
+        let gzip = transformer(r#"[{ "type": "gzip_decompress" }]"#);
+        let error = gzip.apply(b"plain text").unwrap_err();
+        assert!(format!("{:#}", error).contains("gzip_decompress"));
+
+        let invalid: Vec<TransformStep> =
+            serde_json::from_str(r#"[{ "type": "regex_replace", "pattern": "(" }]"#).unwrap();
+        assert!(MessageTransformer::new(&invalid).is_err());
+    }
+}
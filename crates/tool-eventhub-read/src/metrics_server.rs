@@ -0,0 +1,292 @@
+use crate::progress_tracker::ProgressTracker;
+use shared_eventhub::eventhub_models::EventHubConfig;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+use warp::Filter;
+
+/// How often the last enqueued sequence number of each partition is fetched, for the lag.
+const LAG_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Position of the reader in a partition.
+#[derive(Debug, Default, Clone)]
+struct PartitionState {
+    events: u64,
+    last_sequence_number: Option<i64>,
+    last_enqueued_sequence_number: Option<i64>,
+}
+
+impl PartitionState {
+    /// Messages enqueued in the partition that weren't read yet. Only known after the
+    /// partition was queried, and something was read (or resumed from a checkpoint).
+    fn lag(&self) -> Option<i64> {
+        match (
+            self.last_enqueued_sequence_number,
+            self.last_sequence_number,
+        ) {
+            (Some(enqueued), Some(read)) => Some((enqueued - read).max(0)),
+            _ => None,
+        }
+    }
+}
+
+/// Per-partition numbers published on `/metrics`. The message counters and rates come from
+/// the `ProgressTracker`.
+#[derive(Debug, Default)]
+pub struct ReaderMetrics {
+    partitions: Mutex<BTreeMap<String, PartitionState>>,
+}
+
+impl ReaderMetrics {
+    /// Adds a partition being read, with the sequence number of its checkpoint, if any.
+    pub fn register_partition(&self, partition_id: &str, checkpoint_sequence: Option<i64>) {
+        let mut partitions = self.partitions.lock().unwrap();
+        let state = partitions.entry(partition_id.to_string()).or_default();
+        state.last_sequence_number = state.last_sequence_number.or(checkpoint_sequence);
+    }
+
+    /// Records an event received from the partition, whether it was stored, skipped, or
+    /// duplicated.
+    pub fn record_event(&self, partition_id: &str, sequence_number: i64) {
+        let mut partitions = self.partitions.lock().unwrap();
+        let state = partitions.entry(partition_id.to_string()).or_default();
+        state.events += 1;
+        state.last_sequence_number = Some(sequence_number);
+    }
+
+    pub fn record_last_enqueued(&self, partition_id: &str, sequence_number: i64) {
+        let mut partitions = self.partitions.lock().unwrap();
+        let state = partitions.entry(partition_id.to_string()).or_default();
+        state.last_enqueued_sequence_number = Some(sequence_number);
+    }
+
+    pub fn partition_ids(&self) -> Vec<String> {
+        self.partitions.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Renders the metrics in the Prometheus text format.
+    pub fn render(&self, progress: &ProgressTracker) -> String {
+        let mut output = String::new();
+
+        write_metric(
+            &mut output,
+            "eh_read_messages_read_total",
+            "Messages read and stored.",
+            "counter",
+            &[(None, progress.messages_read() as f64)],
+        );
+        write_metric(
+            &mut output,
+            "eh_read_messages_skipped_total",
+            "Messages skipped by the dump filter.",
+            "counter",
+            &[(None, progress.messages_skipped() as f64)],
+        );
+        write_metric(
+            &mut output,
+            "eh_read_messages_duplicated_total",
+            "Messages already in the database.",
+            "counter",
+            &[(None, progress.messages_duplicated() as f64)],
+        );
+        write_metric(
+            &mut output,
+            "eh_read_messages_failed_total",
+            "Messages that failed and were moved to the dead-letter store.",
+            "counter",
+            &[(None, progress.messages_failed() as f64)],
+        );
+        write_metric(
+            &mut output,
+            "eh_read_messages_per_second",
+            "Messages read per second, since the reader started.",
+            "gauge",
+            &[(None, progress.get_rate())],
+        );
+        write_metric(
+            &mut output,
+            "eh_read_max_messages_per_second",
+            "Highest rate shown in the progress line.",
+            "gauge",
+            &[(None, progress.get_max_rate())],
+        );
+        write_metric(
+            &mut output,
+            "eh_read_uptime_seconds",
+            "Time since the reader started.",
+            "gauge",
+            &[(None, progress.elapsed().as_secs_f64())],
+        );
+
+        let partitions = self.partitions.lock().unwrap().clone();
+        let samples = |value: fn(&PartitionState) -> Option<f64>| -> Vec<(Option<&str>, f64)> {
+            partitions
+                .iter()
+                .filter_map(|(id, state)| value(state).map(|value| (Some(id.as_str()), value)))
+                .collect()
+        };
+
+        write_metric(
+            &mut output,
+            "eh_read_partition_events_total",
+            "Events received from the partition, including skipped and duplicated ones.",
+            "counter",
+            &samples(|state| Some(state.events as f64)),
+        );
+        write_metric(
+            &mut output,
+            "eh_read_partition_last_sequence_number",
+            "Sequence number of the last event received from the partition.",
+            "gauge",
+            &samples(|state| state.last_sequence_number.map(|value| value as f64)),
+        );
+        write_metric(
+            &mut output,
+            "eh_read_partition_last_enqueued_sequence_number",
+            "Sequence number of the last event enqueued in the partition.",
+            "gauge",
+            &samples(|state| {
+                state
+                    .last_enqueued_sequence_number
+                    .map(|value| value as f64)
+            }),
+        );
+        write_metric(
+            &mut output,
+            "eh_read_partition_lag_messages",
+            "Estimate of the events enqueued in the partition and not read yet.",
+            "gauge",
+            &samples(|state| state.lag().map(|value| value as f64)),
+        );
+
+        output
+    }
+}
+
+/// Writes a metric with its `HELP` and `TYPE` lines. Samples with a partition get the
+/// `partition` label. Metrics without samples are left out.
+fn write_metric(
+    output: &mut String,
+    name: &str,
+    help: &str,
+    kind: &str,
+    samples: &[(Option<&str>, f64)],
+) {
+    if samples.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(output, "# HELP {} {}", name, help);
+    let _ = writeln!(output, "# TYPE {} {}", name, kind);
+
+    for (partition, value) in samples {
+        match partition {
+            Some(partition) => {
+                let _ = writeln!(output, "{}{{partition=\"{}\"}} {}", name, partition, value);
+            }
+            None => {
+                let _ = writeln!(output, "{} {}", name, value);
+            }
+        }
+    }
+}
+
+/// Serves `GET /metrics` on `address`, in the background.
+pub fn spawn_metrics_server(
+    address: SocketAddr,
+    metrics: Arc<ReaderMetrics>,
+    progress: Arc<ProgressTracker>,
+) {
+    let route = warp::get()
+        .and(warp::path("metrics"))
+        .and(warp::path::end())
+        .map(move || {
+            warp::reply::with_header(
+                metrics.render(&progress),
+                "content-type",
+                "text/plain; version=0.0.4",
+            )
+        });
+
+    info!("Serving metrics at http://{}/metrics", address);
+    tokio::spawn(warp::serve(route).run(address));
+}
+
+/// Fetches the last enqueued sequence number of the partitions being read, every 15 seconds,
+/// until shutdown. Failures are logged and retried on the next round.
+pub fn spawn_lag_monitor(
+    config: EventHubConfig,
+    metrics: Arc<ReaderMetrics>,
+    shutdown: Arc<AtomicBool>,
+) {
+    tokio::spawn(async move {
+        let mut consumer_client = match config.create_consumer_client().await {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Partition lag is not available: {}", e);
+                return;
+            }
+        };
+
+        while !shutdown.load(Ordering::Relaxed) {
+            for partition_id in metrics.partition_ids() {
+                match consumer_client
+                    .get_partition_properties(&partition_id)
+                    .await
+                {
+                    Ok(properties) => metrics.record_last_enqueued(
+                        &partition_id,
+                        properties.last_enqueued_sequence_number(),
+                    ),
+                    Err(e) => warn!(
+                        "Failed to get the properties of partition {}: {}",
+                        partition_id, e
+                    ),
+                }
+            }
+
+            let mut waited = Duration::ZERO;
+            while waited < LAG_REFRESH_INTERVAL && !shutdown.load(Ordering::Relaxed) {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                waited += Duration::from_millis(500);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_metrics() {
+        let progress = ProgressTracker::new(1);
+        progress.increment_read();
+        progress.increment_read();
+        progress.increment_skipped();
+
+        let metrics = ReaderMetrics::default();
+        metrics.register_partition("0", Some(10));
+        metrics.register_partition("1", None);
+        metrics.record_event("0", 12);
+        metrics.record_last_enqueued("0", 20);
+        metrics.record_last_enqueued("1", 5);
+
+        let output = metrics.render(&progress);
+
+        assert!(output.contains("# TYPE eh_read_messages_read_total counter\n"));
+        assert!(output.contains("eh_read_messages_read_total 2\n"));
+        assert!(output.contains("eh_read_messages_skipped_total 1\n"));
+        assert!(output.contains("eh_read_partition_events_total{partition=\"0\"} 1\n"));
+        assert!(output.contains("eh_read_partition_lag_messages{partition=\"0\"} 8\n"));
+        // Nothing read from partition 1 yet, and no checkpoint: the lag is unknown.
+        assert!(!output.contains("eh_read_partition_lag_messages{partition=\"1\"}"));
+        assert!(
+            output.contains("eh_read_partition_last_enqueued_sequence_number{partition=\"1\"} 5\n")
+        );
+    }
+}
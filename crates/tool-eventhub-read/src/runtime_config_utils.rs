@@ -1,6 +1,8 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::ArgMatches;
 use shared_eventhub::eventhub_models::EventHubConfig;
+use shared_eventhub::utils::partition_selection::{PartitionList, PartitionSelection};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 /// Applies CLI argument overrides to EventHub configuration.
@@ -37,8 +39,14 @@ pub fn apply_cli_overrides(
         config.inbound_config.consumer_group = consumer_group.clone();
     }
 
-    if let Some(partition_id) = matches.get_one::<i32>("partition-id") {
-        config.inbound_config.partition_id = *partition_id;
+    if let Some(partition_id) = matches.get_one::<String>("partition-id") {
+        config.inbound_config.partition_id =
+            PartitionSelection::parse(partition_id).context("Invalid value for --partition-id")?;
+    }
+
+    if let Some(excluded) = matches.get_one::<String>("exclude-partitions") {
+        config.inbound_config.exclude_partition_ids =
+            PartitionList::parse(excluded).context("Invalid value for --exclude-partitions")?;
     }
 
     if let Some(received_msg_path) = matches.get_one::<String>("received-msg-path") {
@@ -69,6 +77,10 @@ pub fn apply_cli_overrides(
         config.verbose = true;
     }
 
+    if let Some(address) = matches.get_one::<String>("metrics-address") {
+        config.inbound_config.metrics_address = Some(address.clone());
+    }
+
     if let Some(filters) = matches.get_many::<String>("dump-filter") {
         config.inbound_config.dump_filter = Some(filters.cloned().collect());
     }
@@ -104,5 +116,14 @@ pub fn validate_config(config: &EventHubConfig) -> Result<()> {
         return Err(anyhow!("Consumer group cannot be empty."));
     }
 
+    if let Some(address) = &config.inbound_config.metrics_address {
+        address.parse::<SocketAddr>().map_err(|_| {
+            anyhow!(
+                "Invalid metrics address '{}'. Use host:port, like 127.0.0.1:9464.",
+                address
+            )
+        })?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,193 @@
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+/// Maximum size of the bodies in a batch. EventHub batches are limited to 1 MB (Basic and
+/// Standard tiers), so some room is left for the AMQP overhead.
+pub const MAX_BATCH_BYTES: usize = 900 * 1024;
+
+/// Messages sent together, with the same partition key.
+#[derive(Debug, PartialEq)]
+pub struct Batch {
+    pub partition_key: Option<String>,
+    pub bodies: Vec<Vec<u8>>,
+}
+
+/// Groups the messages in batches, keeping their order.
+///
+/// A batch ends when it has `batch_size` messages, when the next message would take it over
+/// `MAX_BATCH_BYTES`, or when the partition key changes (a batch is sent with a single key).
+pub struct Batcher {
+    batch_size: usize,
+    partition_key: Option<String>,
+    bodies: Vec<Vec<u8>>,
+    bytes: usize,
+}
+
+impl Batcher {
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            partition_key: None,
+            bodies: Vec::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Adds a message. Returns the batch it closed, if any, which must be sent before the next
+    /// one.
+    pub fn push(&mut self, partition_key: Option<String>, body: Vec<u8>) -> Option<Batch> {
+        let closed = if !self.bodies.is_empty()
+            && (self.partition_key != partition_key || self.bytes + body.len() > MAX_BATCH_BYTES)
+        {
+            self.flush()
+        } else {
+            None
+        };
+
+        self.partition_key = partition_key;
+        self.bytes += body.len();
+        self.bodies.push(body);
+
+        if closed.is_none() && self.bodies.len() >= self.batch_size {
+            return self.flush();
+        }
+
+        closed
+    }
+
+    /// Closes the batch being filled, if it has messages.
+    pub fn flush(&mut self) -> Option<Batch> {
+        if self.bodies.is_empty() {
+            return None;
+        }
+
+        self.bytes = 0;
+        Some(Batch {
+            partition_key: self.partition_key.take(),
+            bodies: std::mem::take(&mut self.bodies),
+        })
+    }
+}
+
+/// Reads the partition key from a field of a JSON body (e.g.: `device.id`). Strings are used
+/// as-is, other values as JSON. Bodies that aren't JSON, and missing or null fields, have no key.
+pub fn partition_key_from_field(body: &[u8], field: &str) -> Option<String> {
+    let json: Value = serde_json::from_slice(body).ok()?;
+
+    let value = field
+        .split('.')
+        .try_fold(&json, |value, key| value.get(key))?;
+
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Spaces the sends so the average rate stays under the limit.
+pub struct RateLimiter {
+    interval: Duration,
+    next_send: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// `messages_per_second` must be positive.
+    pub fn new(messages_per_second: f64) -> Self {
+        Self {
+            interval: Duration::from_secs_f64(1.0 / messages_per_second),
+            next_send: None,
+        }
+    }
+
+    /// How long to wait, at `now`, before sending `messages`. The time they take (at the rate)
+    /// is reserved, so the next send waits for it.
+    pub fn reserve(&mut self, messages: usize, now: Instant) -> Duration {
+        let send_at = self.next_send.map_or(now, |next| next.max(now));
+        self.next_send = Some(send_at + self.interval * messages as u32);
+        send_at.duration_since(now)
+    }
+
+    /// Waits until `messages` can be sent.
+    pub async fn wait(&mut self, messages: usize) {
+        let delay = self.reserve(messages, Instant::now());
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(text: &str) -> Vec<u8> {
+        text.as_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_batches_close_on_size_and_key_change() {
+        let mut batcher = Batcher::new(2);
+        let key = |k: &str| Some(k.to_string());
+
+        assert_eq!(batcher.push(key("a"), body("1")), None);
+        assert_eq!(
+            batcher.push(key("a"), body("2")),
+            Some(Batch {
+                partition_key: key("a"),
+                bodies: vec![body("1"), body("2")],
+            })
+        );
+        assert_eq!(batcher.push(key("a"), body("3")), None);
+        assert_eq!(
+            batcher.push(key("b"), body("4")),
+            Some(Batch {
+                partition_key: key("a"),
+                bodies: vec![body("3")],
+            })
+        );
+        assert_eq!(
+            batcher.flush(),
+            Some(Batch {
+                partition_key: key("b"),
+                bodies: vec![body("4")],
+            })
+        );
+        assert_eq!(batcher.flush(), None);
+
+        let mut batcher = Batcher::new(100);
+        let big = vec![b'x'; MAX_BATCH_BYTES / 2 + 1];
+        assert_eq!(batcher.push(None, big.clone()), None);
+        assert_eq!(batcher.push(None, big).map(|b| b.bodies.len()), Some(1));
+    }
+
+    #[test]
+    fn test_partition_key_from_field() {
+        let message = br#"{"device":{"id":"d-1","rack":7,"owner":null}}"#;
+
+        assert_eq!(
+            partition_key_from_field(message, "device.id"),
+            Some("d-1".to_string())
+        );
+        assert_eq!(
+            partition_key_from_field(message, "device.rack"),
+            Some("7".to_string())
+        );
+        assert_eq!(partition_key_from_field(message, "device.owner"), None);
+        assert_eq!(partition_key_from_field(message, "site"), None);
+        assert_eq!(partition_key_from_field(b"plain text", "device.id"), None);
+    }
+
+    #[test]
+    fn test_rate_limiter_spaces_the_sends() {
+        let mut limiter = RateLimiter::new(10.0);
+        let start = Instant::now();
+
+        assert_eq!(limiter.reserve(5, start), Duration::ZERO);
+        assert_eq!(limiter.reserve(1, start), Duration::from_millis(500));
+        // After a pause, there's nothing to wait for, but the pause doesn't build up credit.
+        let later = start + Duration::from_secs(5);
+        assert_eq!(limiter.reserve(1, later), Duration::ZERO);
+        assert_eq!(limiter.reserve(1, later), Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::message_source::MessageSource;
+use clap::{ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::constants::general::DASH_LINE;
+use shared_eventhub::eventhub_models::EventHubConfig;
+use shared_eventhub::utils::cli_arguments::CommandCommonExt;
+use std::ffi::OsString;
+
+/// Displays the EventHub Sender runtime configuration.
+///
+/// Shows entity path, input, batching, partition routing, and rate limit.
+pub fn print_runtime_info(config: &EventHubConfig, source: &MessageSource) {
+    let outbound = &config.outbound_config;
+
+    println!("EventHub Sender v{}", env!("CARGO_PKG_VERSION"));
+    println!("{}", DASH_LINE);
+    println!("- Entity Path: {}", config.entity_path);
+    println!("- Input: {}", source.label());
+    println!("- Batch Size: {}", outbound.batch_size);
+    if let Some(partition_id) = &outbound.partition_id {
+        println!("- Partition: {}", partition_id);
+    }
+    if let Some(partition_key) = &outbound.partition_key {
+        println!("- Partition Key: {}", partition_key);
+    }
+    if let Some(field) = &outbound.partition_key_field {
+        println!("- Partition Key Field: {}", field);
+    }
+    match outbound.rate_limit {
+        Some(rate) => println!("- Rate Limit: {} msg/s", rate),
+        None => println!("- Rate Limit: none"),
+    }
+    println!("- Feedback: Every {} second(s)", outbound.feedback_interval);
+    println!("- Verbose: {}", config.verbose);
+    println!();
+}
+
+/// Parses command-line arguments for the EventHub sender.
+///
+/// Supports JSON config file and CLI overrides for connection string, entity path, batching,
+/// partition routing, and rate limit.
+///
+/// # Returns
+/// Parsed command-line arguments as `ArgMatches`
+pub fn get_cli_arguments(argv: Vec<OsString>) -> ArgMatches {
+    Command::new(env!("CARGO_PKG_NAME"))
+        .add_basic_metadata(
+            env!("CARGO_PKG_VERSION"),
+            "Azure EventHub sender tool - sends messages from stdin, a file, or a folder to an EventHub",
+            "Azure EventHub sender tool - sends messages from stdin, a file, or a folder to an EventHub.\n\n\
+            Files (and stdin) have one message per line. Folders have one message per file.\n\
+            All configuration options can be provided via JSON configuration file and/or command line arguments.\n\
+            Command line arguments take precedence over JSON configuration values.")
+        .preset_arg_verbose(None)
+        .preset_arg_config(None)
+        .add_eh_send_args()
+        .get_matches_from(argv)
+}
@@ -0,0 +1,155 @@
+use crate::batching::{partition_key_from_field, Batch, Batcher, RateLimiter};
+use crate::message_source::MessageSource;
+use anyhow::{anyhow, Context, Result};
+use azeventhubs::producer::{EventHubProducerClient, SendEventOptions};
+use azeventhubs::{BasicRetryPolicy, EventData};
+use shared::ui::progress::Progress;
+use shared_eventhub::eventhub_models::EventHubConfig;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+const SENT: &str = "Sent";
+const BATCHES: &str = "Batches";
+
+pub struct EventHubSender {
+    config: EventHubConfig,
+    producer: EventHubProducerClient<BasicRetryPolicy>,
+    progress: Progress,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl EventHubSender {
+    /// Connects to the EventHub.
+    ///
+    /// # Errors
+    /// Returns error if the producer client can't be created (e.g.: invalid connection string).
+    pub async fn new(config: EventHubConfig, shutdown: Arc<AtomicBool>) -> Result<Self> {
+        let producer = config
+            .create_producer_client()
+            .await
+            .context("Failed to connect to the EventHub")?;
+
+        let progress = Progress::new("msg", &[SENT, BATCHES]).with_interval(Duration::from_secs(
+            config.outbound_config.feedback_interval,
+        ));
+
+        Ok(Self {
+            config,
+            producer,
+            progress,
+            shutdown,
+        })
+    }
+
+    /// Sends every message of the source, in batches, in the order they are read.
+    ///
+    /// On shutdown, reading stops and the messages already read are still sent, so the count
+    /// shown at the end tells where to resume.
+    ///
+    /// # Errors
+    /// Returns error if the source can't be read, or a batch fails to be sent. Batches sent
+    /// before the error are not rolled back.
+    pub async fn send_from(&mut self, source: MessageSource) -> Result<()> {
+        let outbound = self.config.outbound_config.clone();
+
+        // With a rate limit, big batches would go out in bursts.
+        let batch_size = match outbound.rate_limit {
+            Some(rate) => outbound.batch_size.min(rate.ceil() as usize).max(1),
+            None => outbound.batch_size,
+        };
+
+        let mut batcher = Batcher::new(batch_size);
+        let mut limiter = outbound.rate_limit.map(RateLimiter::new);
+        let mut messages = source.spawn_reader(batch_size * 2);
+
+        let result = async {
+            while let Some(message) = messages.recv().await {
+                if self.shutdown.load(Ordering::Relaxed) {
+                    println!("\nShutdown signal received, sending the messages already read...");
+                    break;
+                }
+
+                let body = message?;
+                let partition_key = match &outbound.partition_key_field {
+                    Some(field) => partition_key_from_field(&body, field),
+                    None => outbound.partition_key.clone(),
+                };
+
+                if let Some(batch) = batcher.push(partition_key, body) {
+                    self.send_batch(batch, limiter.as_mut()).await?;
+                }
+            }
+
+            Ok(())
+        }
+        .await;
+
+        // The last batch (or the one being filled at the shutdown) still goes out.
+        let flushed = match batcher.flush() {
+            Some(batch) if result.is_ok() => self.send_batch(batch, limiter.as_mut()).await,
+            _ => Ok(()),
+        };
+
+        self.progress.finish();
+        result.and(flushed)
+    }
+
+    async fn send_batch(&mut self, batch: Batch, limiter: Option<&mut RateLimiter>) -> Result<()> {
+        let count = batch.bodies.len();
+        let bytes: usize = batch.bodies.iter().map(Vec::len).sum();
+
+        if let Some(limiter) = limiter {
+            limiter.wait(count).await;
+        }
+
+        let mut options = SendEventOptions::new();
+        if let Some(partition_id) = &self.config.outbound_config.partition_id {
+            options = options.with_partition_id(partition_id.clone());
+        } else if let Some(partition_key) = &batch.partition_key {
+            options = options.with_partition_key(partition_key.clone());
+        }
+
+        let events: Vec<EventData> = batch.bodies.into_iter().map(EventData::from).collect();
+        self.producer
+            .send_events(events, options)
+            .await
+            .map_err(|e| anyhow!("Failed to send a batch of {} message(s): {}", count, e))?;
+
+        if self.config.verbose {
+            info!(
+                "Sent {} message(s) ({} bytes), partition key: {}",
+                count,
+                bytes,
+                batch.partition_key.as_deref().unwrap_or("-")
+            );
+        }
+
+        self.progress.add(SENT, count as u64);
+        self.progress.increment(BATCHES);
+        self.progress.add_bytes(bytes as u64);
+        self.progress.tick();
+
+        Ok(())
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.shutdown.load(Ordering::Relaxed)
+    }
+
+    pub fn messages_sent(&self) -> u64 {
+        self.progress.get(SENT)
+    }
+
+    /// Closes the connection to the EventHub.
+    ///
+    /// # Errors
+    /// Returns error if the producer fails to close.
+    pub async fn close(self) -> Result<()> {
+        self.producer
+            .close()
+            .await
+            .map_err(|e| anyhow!("Failed to close the EventHub producer: {}", e))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info};
+use crate::eventhub_sender_app::EventHubSender;
+use crate::message_source::MessageSource;
+use crate::runtime_config_utils::{apply_cli_overrides, validate_config};
+use shared::constants::general::{
+    EXIT_CODE_ERROR, EXIT_CODE_INTERRUPTED_BY_USER, EXIT_CODE_SUCCESS,
+};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use shared::system::get_current_working_dir::get_current_working_dir;
+use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use shared_eventhub::utils::config_utils::get_base_config_object;
+use std::ffi::OsString;
+use std::sync::Arc;
+use tracing::error;
+
+mod batching;
+mod cli_utils;
+mod eventhub_sender_app;
+mod message_source;
+mod runtime_config_utils;
+
+/// Azure EventHub sender - publishes messages from stdin, a file, or a folder.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+///
+/// # Workflow
+/// 1. Initialize logging and parse CLI arguments
+/// 2. Load and validate configuration with CLI overrides
+/// 3. Set up graceful shutdown handling
+/// 4. Connect to the EventHub and send the messages, in batches
+/// 5. Handle success, interruption, or error scenarios
+///
+/// # Exit Codes
+/// - 0: Every message was sent
+/// - 130: Interrupted by user (SIGINT), after sending the messages already read
+/// - 1: Failed due to error
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    runtime.block_on(run_tool(argv))
+}
+
+async fn run_tool(argv: Vec<OsString>) -> i32 {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Info);
+
+    // Get CLI arguments
+    let matches = get_cli_arguments(argv);
+
+    // Get the current working directory for relative paths
+    let current_dir = get_current_working_dir();
+
+    // Load configuration
+    let mut config = match get_base_config_object(&matches, &current_dir).await {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to load base configuration: [{}]", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    // Override config with CLI arguments
+    if let Err(e) = apply_cli_overrides(&mut config, &matches) {
+        error!("Failed to apply CLI overrides: [{}]", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    // Validate required configuration
+    if let Err(e) = validate_config(&config) {
+        error!("Configuration is invalid: [{}]", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    let source = match MessageSource::from_arg(
+        matches.get_one::<String>("input").map(String::as_str),
+        &current_dir,
+    ) {
+        Ok(source) => source,
+        Err(e) => {
+            error!("Invalid input: [{}]", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    // Print startup information
+    print_runtime_info(&config, &source);
+
+    // Set up a graceful shutdown
+    let shutdown = setup_graceful_shutdown(false);
+
+    // Connect and send
+    let mut sender = match EventHubSender::new(config, Arc::clone(&shutdown)).await {
+        Ok(sender) => sender,
+        Err(e) => {
+            error!("Failed to create sender: {}", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    let result = sender.send_from(source).await;
+    let sent = sender.messages_sent();
+    let interrupted = sender.is_interrupted();
+
+    if let Err(e) = sender.close().await {
+        error!("{}", e);
+    }
+
+    match result {
+        Ok(()) if interrupted => {
+            println!("Interrupted by user after sending {} message(s)", sent);
+            EXIT_CODE_INTERRUPTED_BY_USER
+        }
+        Ok(()) => {
+            println!("[OK]  {} message(s) sent successfully!", sent);
+            EXIT_CODE_SUCCESS
+        }
+        Err(e) => {
+            error!("Send failed after {} message(s): {}", sent, e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs::{self, File};
+use std::io::{stdin, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::thread;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+
+/// Where the messages to send come from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageSource {
+    /// One message per line.
+    Stdin,
+    /// One message per line.
+    File(PathBuf),
+    /// One message per file, in name order. Subfolders are ignored.
+    Folder(PathBuf),
+}
+
+impl MessageSource {
+    /// Reads from stdin without a path (or with `-`), from a folder when the path is a folder,
+    /// and from a file otherwise.
+    ///
+    /// # Errors
+    /// Returns error if the path doesn't exist.
+    pub fn from_arg(input: Option<&str>, current_dir: &Path) -> Result<Self> {
+        let Some(input) = input.filter(|input| *input != "-") else {
+            return Ok(MessageSource::Stdin);
+        };
+
+        let path = current_dir.join(input);
+        if path.is_dir() {
+            Ok(MessageSource::Folder(path))
+        } else if path.is_file() {
+            Ok(MessageSource::File(path))
+        } else {
+            Err(anyhow!("Input {:?} does not exist", path))
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            MessageSource::Stdin => "stdin (one message per line)".to_string(),
+            MessageSource::File(path) => format!("{} (one message per line)", path.display()),
+            MessageSource::Folder(path) => format!("{} (one message per file)", path.display()),
+        }
+    }
+
+    /// Reads the messages in a background thread, so reading (e.g.: waiting for stdin) doesn't
+    /// block the sends. The channel ends after the last message, or after the first error.
+    pub fn spawn_reader(self, capacity: usize) -> Receiver<Result<Vec<u8>>> {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+
+        thread::spawn(move || {
+            let result = match &self {
+                MessageSource::Stdin => read_lines(stdin().lock(), &tx),
+                MessageSource::File(path) => File::open(path)
+                    .context(format!("Failed to open {:?}", path))
+                    .and_then(|file| read_lines(BufReader::new(file), &tx)),
+                MessageSource::Folder(path) => read_folder(path, &tx),
+            };
+
+            if let Err(e) = result {
+                let _ = tx.blocking_send(Err(e));
+            }
+        });
+
+        rx
+    }
+}
+
+/// Sends each non-empty line (without the line break). Stops early if the receiver is gone.
+fn read_lines<R: BufRead>(reader: R, tx: &Sender<Result<Vec<u8>>>) -> Result<()> {
+    for line in reader.split(b'\n') {
+        let mut line = line.context("Failed to read the input")?;
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        if line.iter().all(|byte| byte.is_ascii_whitespace()) {
+            continue;
+        }
+
+        if tx.blocking_send(Ok(line)).is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends the content of each file of the folder, in name order.
+fn read_folder(folder: &Path, tx: &Sender<Result<Vec<u8>>>) -> Result<()> {
+    let mut files: Vec<PathBuf> = fs::read_dir(folder)
+        .context(format!("Failed to read folder {:?}", folder))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    for file in files {
+        let content = fs::read(&file).context(format!("Failed to read {:?}", file))?;
+        if tx.blocking_send(Ok(content)).is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect(source: MessageSource) -> Vec<String> {
+        let mut rx = source.spawn_reader(2);
+        let mut messages = Vec::new();
+        while let Some(message) = rx.recv().await {
+            messages.push(String::from_utf8(message.unwrap()).unwrap());
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn test_reads_lines_and_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("lines.txt"), "{\"a\":1}\r\n\n  \n{\"a\":2}").unwrap();
+
+        let source = MessageSource::from_arg(Some("lines.txt"), dir.path()).unwrap();
+        assert_eq!(collect(source).await, vec!["{\"a\":1}", "{\"a\":2}"]);
+
+        let folder = dir.path().join("messages");
+        fs::create_dir_all(folder.join("nested")).unwrap();
+        fs::write(folder.join("b.json"), "second\nmessage").unwrap();
+        fs::write(folder.join("a.json"), "first").unwrap();
+
+        let source = MessageSource::from_arg(Some("messages"), dir.path()).unwrap();
+        assert_eq!(collect(source).await, vec!["first", "second\nmessage"]);
+
+        assert_eq!(
+            MessageSource::from_arg(Some("-"), dir.path()).unwrap(),
+            MessageSource::Stdin
+        );
+        assert!(MessageSource::from_arg(Some("missing.txt"), dir.path()).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::{anyhow, Result};
+use clap::ArgMatches;
+use shared_eventhub::eventhub_models::EventHubConfig;
+
+/// Applies CLI argument overrides to the EventHub configuration.
+///
+/// Updates config with command-line values for connection string, entity path, batching,
+/// partition routing, and rate limit.
+///
+/// # Errors
+/// Returns error if an override can't be applied.
+pub fn apply_cli_overrides(config: &mut EventHubConfig, matches: &ArgMatches) -> Result<()> {
+    if let Some(conn_str) = matches.get_one::<String>("connection-string") {
+        config.connection_string = conn_str.clone();
+    }
+
+    if let Some(entity_path) = matches.get_one::<String>("entity-path") {
+        config.entity_path = entity_path.clone();
+    }
+
+    if let Some(batch_size) = matches.get_one::<usize>("batch-size") {
+        config.outbound_config.batch_size = *batch_size;
+    }
+
+    if let Some(partition_key) = matches.get_one::<String>("partition-key") {
+        config.outbound_config.partition_key = Some(partition_key.clone());
+    }
+
+    if let Some(field) = matches.get_one::<String>("partition-key-field") {
+        config.outbound_config.partition_key_field = Some(field.clone());
+    }
+
+    if let Some(partition_id) = matches.get_one::<String>("partition-id") {
+        config.outbound_config.partition_id = Some(partition_id.clone());
+    }
+
+    if let Some(rate) = matches.get_one::<f64>("rate") {
+        config.outbound_config.rate_limit = Some(*rate);
+    }
+
+    if let Some(feedback_interval) = matches.get_one::<u64>("feedback-interval") {
+        config.outbound_config.feedback_interval = *feedback_interval;
+    }
+
+    if matches.get_flag("verbose") {
+        config.verbose = true;
+    }
+
+    Ok(())
+}
+
+/// Validates the configuration used to send messages.
+///
+/// # Validation Rules
+/// - Connection string and entity path are required
+/// - Batch size and feedback interval must be positive
+/// - A partition id can't be combined with a partition key (or key field)
+/// - The rate limit, when set, must be positive
+pub fn validate_config(config: &EventHubConfig) -> Result<()> {
+    let outbound = &config.outbound_config;
+
+    if config.connection_string.is_empty() {
+        return Err(anyhow!("EventHub connection string is required. Use --connection-string or provide it in config file."));
+    }
+
+    if config.entity_path.is_empty() {
+        return Err(anyhow!(
+            "EventHub entity path is required. Use --entity-path or provide it in config file."
+        ));
+    }
+
+    if outbound.batch_size == 0 {
+        return Err(anyhow!("batch_size must be at least 1"));
+    }
+
+    if outbound.feedback_interval == 0 {
+        return Err(anyhow!("feedback_interval must be positive"));
+    }
+
+    if outbound.partition_key.is_some() && outbound.partition_key_field.is_some() {
+        return Err(anyhow!(
+            "Use either a partition key or a partition key field, not both."
+        ));
+    }
+
+    if outbound.partition_id.is_some()
+        && (outbound.partition_key.is_some() || outbound.partition_key_field.is_some())
+    {
+        return Err(anyhow!(
+            "A partition id can't be used with a partition key. The EventHub picks the partition from the key."
+        ));
+    }
+
+    if let Some(rate) = outbound.rate_limit {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(anyhow!("The rate limit must be positive, got: {}", rate));
+        }
+    }
+
+    Ok(())
+}
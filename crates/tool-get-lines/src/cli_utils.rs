@@ -1,17 +1,31 @@
+use crate::input_files::InputFilter;
 use crate::models::GetLinesArgs;
+use anyhow::{bail, Result};
 use clap::{Arg, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
-use shared::system::tool_exit_helpers::exit_error;
+use std::ffi::OsString;
 use std::path::Path;
 
 /// Displays runtime configuration information.
 ///
-/// Shows version, input file, output destination, worker count, and search terms.
+/// Shows version, input files, output destination, worker count, and search terms.
 pub fn print_runtime_info(args: &GetLinesArgs) {
     println!("Get-Lines v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
-    println!("- Input File: {}", args.file);
+    if args.files.len() == 1 {
+        println!("- Input File: {}", args.files[0]);
+    } else {
+        println!("- Input Files: {:?}", args.files);
+    }
+
+    if !args.include.is_empty() {
+        println!("- Include: {:?}", args.include);
+    }
+
+    if !args.exclude.is_empty() {
+        println!("- Exclude: {:?}", args.exclude);
+    }
 
     if let Some(output_folder) = args.output.as_deref() {
         println!("- Output Folder: {}", output_folder);
@@ -22,7 +36,19 @@ pub fn print_runtime_info(args: &GetLinesArgs) {
     println!("- Worker Count: {}", args.workers);
     println!("- Search: {:?}", args.search);
 
-    if args.workers > 1 {
+    if args.merge_by_timestamp {
+        println!("- Merge: by timestamp");
+    }
+
+    if args.count {
+        println!("- Mode: count only");
+    }
+
+    if args.profile {
+        println!("- Profiling: enabled");
+    }
+
+    if args.workers > 1 && !args.count {
         println!(
             "Warning: Output will not be in the same order as the input due to parallel processing."
         );
@@ -38,11 +64,16 @@ pub fn print_runtime_info(args: &GetLinesArgs) {
 ///
 /// # Arguments
 /// - `--search, -s`: Required comma-separated list of search terms (case-insensitive)
-/// - `--file, -f`: Required path to input text file
+/// - `--file, -f`: Required path(s) to input text files or folders. May be repeated.
+/// - `--include`: Optional glob(s) of the files to read from the folders
+/// - `--exclude`: Optional glob(s) of the files to skip in the folders
+/// - `--merge-by-timestamp`: Optional flag to interleave the lines of all files in chronological order
 /// - `--output, -o`: Optional output folder (defaults to console output)
+/// - `--count, -c`: Optional flag to report the match counts instead of the matched lines
 /// - `--workers, -w`: Optional worker thread count (defaults to 1)
 /// - `--hide-line-numbers, -i`: Optional flag to omit line numbers from output
 /// - `--hide-runtime-info, -d`: Optional flag to suppress runtime information display
+/// - `--profile`: Optional flag to print phase timings at exit
 ///
 /// # Returns
 /// `GetLinesArgs` struct containing parsed and processed configuration
@@ -52,13 +83,14 @@ pub fn print_runtime_info(args: &GetLinesArgs) {
 /// - Filters out empty search terms
 /// - Defaults workers to 1 if parsing fails
 /// - Panics if required arguments are missing
-pub fn get_cli_arguments() -> GetLinesArgs {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> GetLinesArgs {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
             "Extracts lines from a text file.",
             "Searches for specific text within a file and outputs the lines containing the text. Supports parallel processing for faster search.")
         .preset_arg_verbose(None)
+        .preset_arg_profile()
         .arg(Arg::new("search")
             .long("search")
             .short('s')
@@ -67,12 +99,35 @@ pub fn get_cli_arguments() -> GetLinesArgs {
         .arg(Arg::new("file")
             .long("file")
             .short('f')
-            .help("Path to the input file")
+            .num_args(1..)
+            .action(clap::ArgAction::Append)
+            .help("Path to the input file. Multiple files can be informed (e.g.: -f app.log.2 app.log.1 app.log); they are read in the order informed. Folders are read recursively, with their files sorted by name.")
             .required(true))
+        .arg(Arg::new("include")
+            .long("include")
+            .num_args(1..)
+            .action(clap::ArgAction::Append)
+            .help("Glob of the files to read from the folders (e.g.: --include '*.log'). Matched against the path relative to the folder. May be repeated. (Default: all files)"))
+        .arg(Arg::new("exclude")
+            .long("exclude")
+            .num_args(1..)
+            .action(clap::ArgAction::Append)
+            .help("Glob of the files to skip in the folders (e.g.: --exclude '*.gz'). Wins over --include. May be repeated."))
+        .arg(Arg::new("merge-by-timestamp")
+            .long("merge-by-timestamp")
+            .action(clap::ArgAction::SetTrue)
+            .conflicts_with("workers")
+            .help("Interleave the lines of all input files in chronological order, using the timestamp at the start of each line. (Default: false)"))
         .arg(Arg::new("output")
             .long("output")
             .short('o')
             .help("Output folder name. If not specified, results will be written to the console."))
+        .arg(Arg::new("count")
+            .long("count")
+            .short('c')
+            .action(clap::ArgAction::SetTrue)
+            .conflicts_with("output")
+            .help("Don't write the matched lines. Instead, report the match counts per search term and per file, the lines scanned, and the throughput. (Default: false)"))
         .arg(Arg::new("workers")
             .long("workers")
             .short('w')
@@ -90,7 +145,7 @@ pub fn get_cli_arguments() -> GetLinesArgs {
                  .action(clap::ArgAction::SetTrue)
                  .help("If set, will not print the the runtime information at the beginning of the program. (Default: false)"),
         )
-        .get_matches();
+        .get_matches_from(argv);
 
     let raw_terms: Vec<&str> = matches
         .get_one::<String>("search")
@@ -106,7 +161,21 @@ pub fn get_cli_arguments() -> GetLinesArgs {
 
     GetLinesArgs {
         search: search_terms,
-        file: matches.get_one::<String>("file").unwrap().clone(),
+        files: matches
+            .get_many::<String>("file")
+            .unwrap_or_default()
+            .cloned()
+            .collect(),
+        include: matches
+            .get_many::<String>("include")
+            .unwrap_or_default()
+            .cloned()
+            .collect(),
+        exclude: matches
+            .get_many::<String>("exclude")
+            .unwrap_or_default()
+            .cloned()
+            .collect(),
         output: matches.get_one::<String>("output").cloned(),
         workers: matches
             .get_one::<String>("workers")
@@ -115,6 +184,9 @@ pub fn get_cli_arguments() -> GetLinesArgs {
             .unwrap_or(1),
         hide_line_numbers: matches.get_flag("hide-line-numbers"),
         hide_runtime_info: matches.get_flag("hide-runtime-info"),
+        profile: matches.get_flag("profile"),
+        merge_by_timestamp: matches.get_flag("merge-by-timestamp"),
+        count: matches.get_flag("count"),
     }
 }
 
@@ -126,24 +198,25 @@ pub fn get_cli_arguments() -> GetLinesArgs {
 /// # Arguments
 /// - `args` - Parsed command-line arguments to validate
 ///
-/// # Behavior
-/// - Exits with code 1 if no valid search terms are provided, worker count <= zero, or if the input
-///   file doesn't exit.
-pub fn validate_cli_arguments(args: &GetLinesArgs) {
+/// # Errors
+/// - Fails if no valid search terms are provided, worker count <= zero, if any input file (or
+///   folder) doesn't exist, or if a glob is invalid.
+pub fn validate_cli_arguments(args: &GetLinesArgs) -> Result<()> {
     if args.search.is_empty() {
-        eprintln!("Error: No valid search terms provided.");
-        exit_error();
+        bail!("No valid search terms provided.");
     }
 
     if args.workers == 0 {
-        eprintln!("Error: --workers must be greater than 0.");
-        exit_error();
+        bail!("--workers must be greater than 0.");
     }
 
-    let input_file = Path::new(&args.file);
-
-    if !input_file.exists() {
-        eprintln!("Error: Input file does not exist: {}", args.file);
-        exit_error();
+    for file in &args.files {
+        if !Path::new(file).exists() {
+            bail!("Input file or folder does not exist: {}", file);
+        }
     }
+
+    InputFilter::new(&args.include, &args.exclude)?;
+
+    Ok(())
 }
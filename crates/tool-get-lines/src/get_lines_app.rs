@@ -1,6 +1,10 @@
+use crate::input_files::{expand_inputs, has_folders, InputFilter};
+use crate::match_counters::MatchCounters;
 use crate::models::{GetLinesArgs, LineData};
+use crate::timestamp_merge::TimestampMerge;
 use anyhow::{Context, Result};
 use futures_util::StreamExt;
+use shared::ui::progress::{Progress, ProgressTarget, ProgressTotal};
 use shared::utils::sanitize_string_for_filename::sanitize_string_for_filename;
 use std::collections::HashMap;
 use std::fs::File;
@@ -13,6 +17,42 @@ use tokio::sync::{mpsc, Mutex};
 use tokio::task::JoinHandle;
 use tokio_stream::wrappers::ReceiverStream;
 
+const LINES: &str = "Lines";
+const MATCHES: &str = "Matches";
+
+/// Lists the files to read: the files informed in `--file`, and the files in the folders informed
+/// there (recursively, filtered by `--include` and `--exclude`). The folders are walked lazily.
+///
+/// # Panics
+/// Panics if one of the globs is invalid. They are checked in `validate_cli_arguments`.
+pub fn list_input_files(args: &GetLinesArgs) -> impl Iterator<Item = String> + Send {
+    let filter = InputFilter::new(&args.include, &args.exclude).expect("Invalid glob");
+    expand_inputs(args.files.clone(), filter)
+}
+
+/// Creates the progress shown while searching, with the percentage and ETA from the size of the
+/// input files.
+///
+/// Only when the matches go to files (or are only counted) and the runtime info is shown: with
+/// the matches printed to the console, a progress line would get mixed with them. Printed to
+/// stderr.
+pub fn create_progress(args: &GetLinesArgs) -> Option<Arc<Progress>> {
+    if (args.output.is_none() && !args.count) || args.hide_runtime_info {
+        return None;
+    }
+
+    let total_bytes = list_input_files(args)
+        .filter_map(|file| std::fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    Some(Arc::new(
+        Progress::new("lines", &[LINES, MATCHES])
+            .with_total(ProgressTotal::Bytes(total_bytes))
+            .with_target(ProgressTarget::Stderr),
+    ))
+}
+
 /// Sets up file-based output channels for search term results.
 ///
 /// Creates separate output files for each search term and spawns async writer tasks
@@ -122,40 +162,49 @@ pub fn prepare_to_export_search_terms_to_console(
 
 /// Spawns async worker to read file lines and send to a processing channel.
 ///
-/// Opens the input file and reads it line by line, sending each line with its
-/// line number to the provided channel for downstream processing.
+/// Opens the input files and reads them line by line, sending each line with its
+/// line number to the provided channel for downstream processing. Files are read one after
+/// the other or, with `--merge-by-timestamp`, interleaved in chronological order. Folders are
+/// walked by the worker, and their files are read as they are found.
 ///
 /// # Arguments
 /// - `args` - Configuration containing file path and display options
 /// - `line_tx` - Channel sender for transmitting line data
 /// - `shutdown_signal` - Signal for graceful task termination
+/// - `progress` - Progress updated with each line read, if shown
 ///
 /// # Returns
-/// Join handle for the spawned file reading task
+/// Join handle for the spawned file reading task, that fails if, with `--merge-by-timestamp`, one
+/// of the input files cannot be opened
 ///
 /// # Behavior
 /// - Opens file using buffered reader for performance
-/// - Sends LineData struct containing line number and content
+/// - Sends LineData struct containing line number and content (and the file, if more than one
+///   or read from a folder)
 /// - Stops reading on shutdown signal or receiver drop
-/// - Panics if input file cannot be opened
-///
-/// # Panics
-/// Panics if the specified input file cannot be opened
+/// - Skips, with a warning, the input files that cannot be opened, unless merging by timestamp
 pub fn spawn_file_reading_workers(
     args: &GetLinesArgs,
     line_tx: &Sender<LineData>,
     shutdown_signal: Arc<AtomicBool>,
-) -> JoinHandle<()> {
+    progress: Option<Arc<Progress>>,
+) -> JoinHandle<Result<()>> {
     let reader_handle = {
-        let file_path = args.file.clone();
+        let files = list_input_files(args);
+        let tag_source = args.files.len() > 1 || has_folders(&args.files);
+        let merge_by_timestamp = args.merge_by_timestamp;
         let hide_runtime_info = args.hide_runtime_info;
         let line_tx = line_tx.clone();
         tokio::spawn(async move {
-            let file = File::open(&file_path).expect("Failed to open input file");
-            let reader = BufReader::new(file);
-            let mut line_number = 1;
+            let lines: Box<dyn Iterator<Item = LineData> + Send> = if merge_by_timestamp {
+                // Merging needs a line of every file, so the folders are walked first.
+                let files: Vec<String> = files.collect();
+                Box::new(TimestampMerge::open(&files).context("Failed to open input files")?)
+            } else {
+                Box::new(read_files_in_sequence(files, tag_source))
+            };
 
-            for line_res in reader.lines() {
+            for line_data in lines {
                 if shutdown_signal.load(Ordering::Relaxed) {
                     if !hide_runtime_info {
                         println!("File reading stopped due to shutdown signal");
@@ -163,28 +212,54 @@ pub fn spawn_file_reading_workers(
                     break;
                 }
 
-                match line_res {
-                    Ok(content) => {
-                        if line_tx
-                            .send(LineData {
-                                line_number,
-                                content,
-                            })
-                            .await
-                            .is_err()
-                        {
-                            break; // receiver dropped
-                        }
-                        line_number += 1;
-                    }
-                    Err(_) => break,
+                if let Some(progress) = &progress {
+                    progress.increment(LINES);
+                    // The line break is not in the content.
+                    progress.add_bytes(line_data.content.len() as u64 + 1);
+                    progress.tick();
+                }
+
+                if line_tx.send(line_data).await.is_err() {
+                    break; // receiver dropped
                 }
             }
+
+            Ok(())
         })
     };
     reader_handle
 }
 
+/// Reads the files one after the other. Reading a file stops at the first line that can't be read.
+/// Files that can't be opened (e.g.: no permission, found while walking a folder) are skipped, with
+/// a warning.
+fn read_files_in_sequence(
+    files: impl Iterator<Item = String>,
+    tag_source: bool,
+) -> impl Iterator<Item = LineData> {
+    files
+        .filter_map(|file_path| match File::open(&file_path) {
+            Ok(file) => Some((file_path, file)),
+            Err(e) => {
+                eprintln!("Warning: Skipped input file {}: {}", file_path, e);
+                None
+            }
+        })
+        .flat_map(move |(file_path, file)| {
+            let source: Option<Arc<str>> = tag_source.then(|| Arc::from(file_path.as_str()));
+
+            BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .enumerate()
+                .map(move |(index, content)| LineData {
+                    line_number: index + 1,
+                    content,
+                    source: source.clone(),
+                })
+        })
+}
+
 /// Processes file lines by matching against search terms and routing results.
 ///
 /// Spawns concurrent workers to process lines from input channel, perform
@@ -196,6 +271,8 @@ pub fn spawn_file_reading_workers(
 /// - `search_terms` - List of patterns to search for in each line
 /// - `line_rx` - Channel receiver for incoming line data
 /// - `shutdown_signal` - Signal for graceful task termination
+/// - `progress` - Progress updated with each match, if shown
+/// - `counters` - With `--count`: the matches are counted here instead of sent to the outputs
 ///
 /// # Returns
 /// Join handle for the spawned line processing task
@@ -211,6 +288,8 @@ pub fn process_lines_read(
     search_terms: Vec<String>,
     line_rx: Receiver<LineData>,
     shutdown_signal: Arc<AtomicBool>,
+    progress: Option<Arc<Progress>>,
+    counters: Option<Arc<MatchCounters>>,
 ) -> JoinHandle<()> {
     let processor_handle = {
         let search_terms = search_terms.clone();
@@ -224,19 +303,28 @@ pub fn process_lines_read(
                     let search_terms = search_terms.clone();
                     let output_channels = output_channels.clone();
                     let shutdown_clone = Arc::clone(&shutdown_signal);
+                    let progress = progress.clone();
+                    let counters = counters.clone();
                     async move {
                         if shutdown_clone.load(Ordering::Relaxed) {
                             return;
                         }
 
+                        if let Some(counters) = &counters {
+                            counters.add_line(&line_data);
+                        }
+
                         let lower = line_data.content.to_lowercase();
-                        for term in &search_terms {
+                        for (term_index, term) in search_terms.iter().enumerate() {
                             if lower.contains(term) {
-                                let out = if args.hide_line_numbers {
-                                    format!("{}\n", line_data.content)
-                                } else {
-                                    format!("{}\t{}\n", line_data.line_number, line_data.content)
-                                };
+                                if let Some(progress) = &progress {
+                                    progress.increment(MATCHES);
+                                }
+                                if let Some(counters) = &counters {
+                                    counters.add_match(&line_data, term_index);
+                                    continue;
+                                }
+                                let out = format_line(&line_data, args.hide_line_numbers);
                                 if let Some(tx) = output_channels.get(term) {
                                     let _ = tx.send(out).await;
                                 }
@@ -249,3 +337,16 @@ pub fn process_lines_read(
     };
     processor_handle
 }
+
+/// Formats a matching line for the output. When reading more than one file, the line number is
+/// prefixed with the file name (`file:line`).
+fn format_line(line_data: &LineData, hide_line_numbers: bool) -> String {
+    match (&line_data.source, hide_line_numbers) {
+        (_, true) => format!("{}\n", line_data.content),
+        (Some(source), false) => format!(
+            "{}:{}\t{}\n",
+            source, line_data.line_number, line_data.content
+        ),
+        (None, false) => format!("{}\t{}\n", line_data.line_number, line_data.content),
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Chooses the files read from the folders informed in `--file`, with the `--include` and
+/// `--exclude` globs. Files informed directly are always read.
+pub struct InputFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl InputFilter {
+    /// Without includes, every file is read. Excludes win over includes.
+    ///
+    /// # Errors
+    /// Returns error if one of the globs is invalid.
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: build_globset(include)?,
+            exclude: build_globset(exclude)?,
+        })
+    }
+
+    /// Checks the path of the file relative to the folder walked. `*` also matches `/`, so
+    /// `*.log` matches the logs in the subfolders too.
+    pub fn accepts(&self, relative_path: &Path) -> bool {
+        let included = self
+            .include
+            .as_ref()
+            .is_none_or(|include| include.is_match(relative_path));
+        let excluded = self
+            .exclude
+            .as_ref()
+            .is_some_and(|exclude| exclude.is_match(relative_path));
+
+        included && !excluded
+    }
+}
+
+fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).context(format!("Invalid glob: {}", pattern))?);
+    }
+
+    Ok(Some(builder.build()?))
+}
+
+/// True if any of the inputs is a folder (so more than one file may be read).
+pub fn has_folders(inputs: &[String]) -> bool {
+    inputs.iter().any(|input| Path::new(input).is_dir())
+}
+
+/// The files to read, in order: files as informed, and the files of each folder (recursively,
+/// sorted by name) that pass the filter. The folders are walked as the files are consumed.
+pub fn expand_inputs(
+    inputs: Vec<String>,
+    filter: InputFilter,
+) -> impl Iterator<Item = String> + Send {
+    inputs.into_iter().flat_map(move |input| {
+        let root = Path::new(&input).to_path_buf();
+
+        if !root.is_dir() {
+            return vec![input];
+        }
+
+        WalkDir::new(&root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| {
+                let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+                filter.accepts(relative)
+            })
+            .map(|entry| entry.path().to_string_lossy().to_string())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_folders_are_walked_with_the_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("old")).unwrap();
+        for file in ["b.log", "a.log", "notes.txt", "old/c.log", "old/d.log.gz"] {
+            fs::write(root.join(file), "line").unwrap();
+        }
+        let single = root.join("notes.txt").to_string_lossy().to_string();
+
+        let filter = InputFilter::new(&["*.log".to_string()], &["old/*".to_string()]).unwrap();
+        let files: Vec<String> = expand_inputs(
+            vec![single.clone(), root.to_string_lossy().to_string()],
+            filter,
+        )
+        .collect();
+
+        let names: Vec<String> = files
+            .iter()
+            .map(|file| {
+                Path::new(file)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        assert_eq!(names, vec!["notes.txt", "a.log", "b.log"]);
+
+        let filter = InputFilter::new(&[], &["*.gz".to_string()]).unwrap();
+        assert!(filter.accepts(Path::new("old/c.log")));
+        assert!(!filter.accepts(Path::new("old/d.log.gz")));
+        assert!(InputFilter::new(&["[".to_string()], &[]).is_err());
+        assert!(has_folders(&[single, root.to_string_lossy().to_string()]));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info, validate_cli_arguments};
+use crate::get_lines_app::{
+    create_progress, list_input_files, prepare_to_export_search_terms_to_console,
+    prepare_to_export_search_terms_to_output_files, process_lines_read, spawn_file_reading_workers,
+};
+use crate::match_counters::MatchCounters;
+use crate::models::LineData;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use shared::utils::phase_profiler::PhaseProfiler;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::create_dir_all;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::error;
+
+mod cli_utils;
+mod get_lines_app;
+mod input_files;
+mod match_counters;
+mod models;
+mod timestamp_merge;
+
+/// Main entry point for the get-lines tool.
+///
+/// Orchestrates the workflow of searching for text patterns in files and outputting matches
+/// either to console or separate files per search term.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+///
+/// # Workflow
+/// 1. Parses and validates command-line arguments
+/// 2. Sets up graceful shutdown handling
+/// 3. Prepares output channels (console or files)
+/// 4. Spawns file reading workers (merging the files by timestamp, with `--merge-by-timestamp`)
+/// 5. Processes lines concurrently with pattern matching
+/// 6. Finalizes output and displays completion status (or the match counts, with `--count`)
+/// 7. Prints phase timings, when `--profile` is set
+///
+/// # Returns
+/// The exit code: `EXIT_CODE_SUCCESS` on successful completion, `EXIT_CODE_ERROR` when the
+/// outputs cannot be created or the files cannot be read, written or processed.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    runtime.block_on(run_tool(argv))
+}
+
+async fn run_tool(argv: Vec<OsString>) -> i32 {
+    // 0) Initialize logging, but only for errors. We don't want to mess up the user's terminal.
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    // 1) Parse & validate CLI arguments
+    let args = get_cli_arguments(argv);
+
+    if let Err(e) = validate_cli_arguments(&args) {
+        eprintln!("Error: {:#}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    let hide_runtime_info = args.hide_runtime_info;
+    let mut profiler = PhaseProfiler::new(args.profile);
+
+    if !hide_runtime_info {
+        print_runtime_info(&args);
+    }
+
+    // 2) Set up the graceful shutdown
+    let shutdown_signal = setup_graceful_shutdown(false);
+
+    // 3) Prepare output channels and writer tasks
+    let phase_started_at = Instant::now();
+    let mut output_channels: HashMap<String, mpsc::Sender<String>> = HashMap::new();
+    let mut output_handles = Vec::new();
+    let search_terms = args.search.clone();
+
+    if args.count {
+        // Nothing is written: the matches are only counted.
+    } else if let Some(output_dir) = &args.output {
+        if let Err(e) = create_dir_all(output_dir) {
+            error!(
+                "Failed to create output directory [{}]: [{}]",
+                output_dir, e
+            );
+            return EXIT_CODE_ERROR;
+        }
+
+        for term in &search_terms {
+            if let Err(e) = prepare_to_export_search_terms_to_output_files(
+                &args,
+                &mut output_channels,
+                &mut output_handles,
+                output_dir,
+                term,
+                Arc::clone(&shutdown_signal),
+            ) {
+                error!(
+                    "Failed to create output file for search term [{}]: [{}]",
+                    term, e
+                );
+                return EXIT_CODE_ERROR;
+            }
+        }
+    } else {
+        prepare_to_export_search_terms_to_console(
+            &args,
+            &mut output_channels,
+            &mut output_handles,
+            &search_terms,
+            Arc::clone(&shutdown_signal),
+        );
+    }
+
+    profiler.record_since("prepare outputs", phase_started_at);
+
+    // 4) Create an MPSC channel for line streaming
+    let phase_started_at = Instant::now();
+    let (line_tx, line_rx) = mpsc::channel::<LineData>(args.workers * 2);
+    let counters = args.count.then(|| {
+        let files: Vec<String> = list_input_files(&args).collect();
+        Arc::new(MatchCounters::new(&search_terms, &files))
+    });
+
+    // 5) Spawn the file-reading task
+    let progress = create_progress(&args);
+    let reader_handle = spawn_file_reading_workers(
+        &args,
+        &line_tx,
+        Arc::clone(&shutdown_signal),
+        progress.clone(),
+    );
+
+    // 6) Process lines in parallel using for_each_concurrent
+    let processor_handle = process_lines_read(
+        args,
+        &mut output_channels,
+        search_terms,
+        line_rx,
+        Arc::clone(&shutdown_signal),
+        progress.clone(),
+        counters.clone(),
+    );
+
+    // 7) Wait for the reader, then close the sender to finish the stream
+    match reader_handle.await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            error!("Failed to read files: [{:#}]", e);
+            return EXIT_CODE_ERROR;
+        }
+        Err(e) => {
+            error!("Failed to read files: [{}]", e);
+            return EXIT_CODE_ERROR;
+        }
+    }
+
+    drop(line_tx);
+
+    // 8) Wait for processing to complete
+    if let Err(e) = processor_handle.await {
+        error!("Failed to process lines: [{}]", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    let scan_elapsed = phase_started_at.elapsed();
+    profiler.record_since("read and match", phase_started_at);
+
+    if let Some(progress) = &progress {
+        progress.finish();
+    }
+
+    // 9) Close output channels and await writer tasks
+    let phase_started_at = Instant::now();
+    for (_, tx) in output_channels {
+        drop(tx);
+    }
+    for handle in output_handles {
+        if let Err(e) = handle.await {
+            error!("Failed to write output: [{}]", e);
+            return EXIT_CODE_ERROR;
+        }
+    }
+
+    profiler.record_since("flush outputs", phase_started_at);
+
+    if let Some(counters) = &counters {
+        print!("{}", counters.render_report(scan_elapsed));
+        println!();
+    }
+
+    if !hide_runtime_info {
+        if shutdown_signal.load(std::sync::atomic::Ordering::Relaxed) {
+            println!("👋 stopping by user request..");
+        } else {
+            println!("[OK] get-lines completed successfully.");
+        }
+    }
+
+    profiler.print_report();
+
+    EXIT_CODE_SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_returns_error_when_an_input_file_does_not_exist() {
+        let exit_code = run([
+            "get-lines",
+            "--search",
+            "error",
+            "--file",
+            "no/such/file.log",
+            "--hide-runtime-info",
+        ]);
+
+        assert_eq!(exit_code, EXIT_CODE_ERROR);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::models::LineData;
+use shared::utils::format_bytes_to_string::format_bytes_to_string;
+use shared::utils::table_output::Table;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Match counts for `--count`: per search term, and per file and search term, plus the lines
+/// and bytes scanned. The workers only increment atomics; the report adds them up at the end.
+pub struct MatchCounters {
+    terms: Vec<String>,
+    files: Vec<String>,
+    /// One counter per search term, for each file (same order as `files` and `terms`).
+    matches_by_file: HashMap<String, Vec<AtomicU64>>,
+    lines: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl MatchCounters {
+    pub fn new(terms: &[String], files: &[String]) -> Self {
+        let matches_by_file = files
+            .iter()
+            .map(|file| {
+                let counters = terms.iter().map(|_| AtomicU64::new(0)).collect();
+                (file.clone(), counters)
+            })
+            .collect();
+
+        Self {
+            terms: terms.to_vec(),
+            files: files.to_vec(),
+            matches_by_file,
+            lines: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Counts a line scanned. The line break is not in the content, so it's added to the bytes.
+    pub fn add_line(&self, line_data: &LineData) {
+        self.lines.fetch_add(1, Ordering::Relaxed);
+        self.bytes
+            .fetch_add(line_data.content.len() as u64 + 1, Ordering::Relaxed);
+    }
+
+    /// Counts a match of the search term (by its position in the terms) in the file of the line.
+    /// Lines of a single input file have no source, so they count for that file.
+    pub fn add_match(&self, line_data: &LineData, term_index: usize) {
+        let file = match &line_data.source {
+            Some(source) => source.as_ref(),
+            None => match self.files.first() {
+                Some(file) => file.as_str(),
+                None => return,
+            },
+        };
+
+        if let Some(counter) = self
+            .matches_by_file
+            .get(file)
+            .and_then(|counters| counters.get(term_index))
+        {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn matches(&self, file: &str, term_index: usize) -> u64 {
+        self.matches_by_file
+            .get(file)
+            .and_then(|counters| counters.get(term_index))
+            .map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+
+    /// Total matches of each search term, in all files.
+    pub fn matches_by_term(&self) -> Vec<(String, u64)> {
+        self.terms
+            .iter()
+            .enumerate()
+            .map(|(index, term)| {
+                let total = self
+                    .files
+                    .iter()
+                    .map(|file| self.matches(file, index))
+                    .sum();
+                (term.clone(), total)
+            })
+            .collect()
+    }
+
+    /// Tables with the matches per search term and, with more than one file, per file, followed
+    /// by the lines scanned and the throughput.
+    pub fn render_report(&self, elapsed: Duration) -> String {
+        let mut by_term = Table::new(&["TERM", "MATCHES"])
+            .with_title("Matches by term")
+            .align_right(1);
+        for (term, total) in self.matches_by_term() {
+            by_term.add_row(vec![term, total.to_string()]);
+        }
+
+        let mut output = by_term.render_text();
+
+        if self.files.len() > 1 {
+            let mut by_file = Table::new(&["FILE", "TERM", "MATCHES"])
+                .with_title("Matches by file")
+                .align_right(2);
+            for file in &self.files {
+                for (index, term) in self.terms.iter().enumerate() {
+                    by_file.add_row(vec![
+                        file.clone(),
+                        term.clone(),
+                        self.matches(file, index).to_string(),
+                    ]);
+                }
+            }
+
+            output.push('\n');
+            output.push_str(&by_file.render_text());
+        }
+
+        let lines = self.lines.load(Ordering::Relaxed);
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        let seconds = elapsed.as_secs_f64().max(0.001);
+
+        output.push('\n');
+        output.push_str(&format!(
+            "Lines scanned: {} ({}) in {:.2}s\n",
+            lines,
+            format_bytes_to_string(&bytes),
+            elapsed.as_secs_f64()
+        ));
+        output.push_str(&format!(
+            "Throughput: {:.0} lines/s, {}/s\n",
+            lines as f64 / seconds,
+            format_bytes_to_string(&((bytes as f64 / seconds) as u64))
+        ));
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn line(content: &str, source: Option<&str>) -> LineData {
+        LineData {
+            line_number: 1,
+            content: content.to_string(),
+            source: source.map(Arc::from),
+        }
+    }
+
+    #[test]
+    fn test_counts_by_term_and_file() {
+        let terms = vec!["error".to_string(), "warn".to_string()];
+        let files = vec!["a.log".to_string(), "b.log".to_string()];
+        let counters = MatchCounters::new(&terms, &files);
+
+        let first = line("ERROR and WARN", Some("a.log"));
+        counters.add_line(&first);
+        counters.add_match(&first, 0);
+        counters.add_match(&first, 1);
+
+        let second = line("error", Some("b.log"));
+        counters.add_line(&second);
+        counters.add_match(&second, 0);
+
+        assert_eq!(
+            counters.matches_by_term(),
+            vec![("error".to_string(), 2), ("warn".to_string(), 1)]
+        );
+        assert_eq!(counters.matches("b.log", 1), 0);
+        assert_eq!(counters.lines.load(Ordering::Relaxed), 2);
+        assert_eq!(counters.bytes.load(Ordering::Relaxed), 15 + 6);
+
+        let report = counters.render_report(Duration::from_secs(1));
+        assert!(report.contains("Matches by file"));
+        assert!(report.contains("Lines scanned: 2"));
+
+        // A single file: lines have no source.
+        let counters = MatchCounters::new(&terms, &files[..1]);
+        counters.add_match(&line("error", None), 0);
+        assert_eq!(counters.matches_by_term()[0], ("error".to_string(), 1));
+        assert!(!counters
+            .render_report(Duration::ZERO)
+            .contains("Matches by file"));
+    }
+}
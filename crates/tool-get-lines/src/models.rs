@@ -1,14 +1,23 @@
+use std::sync::Arc;
+
 pub struct GetLinesArgs {
     pub search: Vec<String>,
-    pub file: String,
+    pub files: Vec<String>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
     pub output: Option<String>,
     pub workers: usize,
     pub hide_line_numbers: bool,
     pub hide_runtime_info: bool,
+    pub profile: bool,
+    pub merge_by_timestamp: bool,
+    pub count: bool,
 }
 
 #[derive(Clone)]
 pub struct LineData {
     pub line_number: usize,
     pub content: String,
+    /// File the line came from. Only set when reading more than one file (or a folder).
+    pub source: Option<Arc<str>>,
 }
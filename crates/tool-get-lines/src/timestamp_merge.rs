@@ -0,0 +1,304 @@
+use crate::models::LineData;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use regex::Regex;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::sync::Arc;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Syslog timestamps have no year. A leap year is used, so `Feb 29` is valid.
+const SYSLOG_YEAR: i32 = 2000;
+
+/// Detects the timestamp at the start of a log line.
+///
+/// Supported prefixes (optionally inside `[...]`):
+/// - ISO 8601 / RFC 3339: `2024-01-15 10:30:12`, `2024-01-15T10:30:12.123Z`, `2024-01-15T10:30:12+02:00`
+/// - Slashes: `2024/01/15 10:30:12,123`
+/// - Syslog: `Jan 15 10:30:12`
+/// - Unix epoch, in seconds (`1705314612`, `1705314612.123`) or milliseconds (`1705314612123`)
+///
+/// Timestamps with an offset are converted to UTC. The others are compared as they are.
+pub struct TimestampParser {
+    iso: Regex,
+    syslog: Regex,
+    epoch: Regex,
+}
+
+impl Default for TimestampParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimestampParser {
+    pub fn new() -> Self {
+        Self {
+            iso: Regex::new(
+                r"^\[?(\d{4})[-/](\d{2})[-/](\d{2})[T ](\d{2}):(\d{2}):(\d{2})(?:[.,](\d{1,9}))?(?: ?(Z|[+-]\d{2}:?\d{2}))?",
+            )
+            .expect("valid ISO timestamp regex"),
+            syslog: Regex::new(r"^\[?([A-Z][a-z]{2}) {1,2}(\d{1,2}) (\d{2}):(\d{2}):(\d{2})")
+                .expect("valid syslog timestamp regex"),
+            epoch: Regex::new(r"^\[?(\d{10})(\d{3})?(?:\.(\d{1,9}))?(?:\D|$)")
+                .expect("valid epoch timestamp regex"),
+        }
+    }
+
+    /// Returns the timestamp at the start of the line, if there is one.
+    pub fn parse(&self, line: &str) -> Option<NaiveDateTime> {
+        self.parse_iso(line)
+            .or_else(|| self.parse_syslog(line))
+            .or_else(|| self.parse_epoch(line))
+    }
+
+    fn parse_iso(&self, line: &str) -> Option<NaiveDateTime> {
+        let caps = self.iso.captures(line)?;
+        let number = |i: usize| caps.get(i)?.as_str().parse::<u32>().ok();
+
+        let local = NaiveDate::from_ymd_opt(number(1)? as i32, number(2)?, number(3)?)?
+            .and_hms_nano_opt(
+                number(4)?,
+                number(5)?,
+                number(6)?,
+                fraction_to_nanos(caps.get(7).map(|m| m.as_str())),
+            )?;
+
+        let offset_seconds = match caps.get(8).map(|m| m.as_str()) {
+            None | Some("Z") => 0,
+            Some(offset) => parse_offset_seconds(offset)?,
+        };
+
+        Some(local - chrono::Duration::seconds(offset_seconds))
+    }
+
+    fn parse_syslog(&self, line: &str) -> Option<NaiveDateTime> {
+        let caps = self.syslog.captures(line)?;
+        let number = |i: usize| caps.get(i)?.as_str().parse::<u32>().ok();
+        let month = MONTHS.iter().position(|m| *m == &caps[1])? as u32 + 1;
+
+        NaiveDate::from_ymd_opt(SYSLOG_YEAR, month, number(2)?)?.and_hms_opt(
+            number(3)?,
+            number(4)?,
+            number(5)?,
+        )
+    }
+
+    fn parse_epoch(&self, line: &str) -> Option<NaiveDateTime> {
+        let caps = self.epoch.captures(line)?;
+        let seconds = caps[1].parse::<i64>().ok()?;
+
+        let nanos = match caps.get(2) {
+            Some(millis) => millis.as_str().parse::<u32>().ok()? * 1_000_000,
+            None => fraction_to_nanos(caps.get(3).map(|m| m.as_str())),
+        };
+
+        DateTime::from_timestamp(seconds, nanos).map(|dt| dt.naive_utc())
+    }
+}
+
+/// `"123"` (milliseconds) becomes `123_000_000` nanoseconds.
+fn fraction_to_nanos(fraction: Option<&str>) -> u32 {
+    let Some(fraction) = fraction else {
+        return 0;
+    };
+
+    format!("{:0<9}", fraction).parse().unwrap_or(0)
+}
+
+/// `+02:00`, `-0330`.
+fn parse_offset_seconds(offset: &str) -> Option<i64> {
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits: String = offset.chars().filter(char::is_ascii_digit).collect();
+    let hours = digits.get(0..2)?.parse::<i64>().ok()?;
+    let minutes = digits.get(2..4)?.parse::<i64>().ok()?;
+
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// A line waiting in the merge. Lines are ordered by timestamp, then by the order of the files,
+/// then by line number, so lines with the same timestamp keep the order they had.
+struct PendingLine {
+    timestamp: Option<NaiveDateTime>,
+    source_index: usize,
+    line: LineData,
+}
+
+impl PendingLine {
+    fn key(&self) -> (Option<NaiveDateTime>, usize, usize) {
+        (self.timestamp, self.source_index, self.line.line_number)
+    }
+}
+
+impl PartialEq for PendingLine {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for PendingLine {}
+
+impl PartialOrd for PendingLine {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PendingLine {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+struct Source<R: BufRead> {
+    name: Option<Arc<str>>,
+    lines: Lines<R>,
+    line_number: usize,
+    last_timestamp: Option<NaiveDateTime>,
+}
+
+/// Reads several (already sorted) log files at once, yielding their lines in chronological order.
+///
+/// Only one line per file is kept in memory. Lines without a timestamp (stack traces, wrapped
+/// messages) get the timestamp of the line before them, so they stay with their entry.
+pub struct TimestampMerge<R: BufRead> {
+    parser: TimestampParser,
+    sources: Vec<Source<R>>,
+    heap: BinaryHeap<Reverse<PendingLine>>,
+}
+
+impl TimestampMerge<BufReader<File>> {
+    /// Opens every file. With more than one, line data carries the file name, as informed.
+    pub fn open(files: &[String]) -> Result<Self> {
+        let readers = files
+            .iter()
+            .map(|file| {
+                let reader = File::open(file)
+                    .map(BufReader::new)
+                    .context(format!("Failed to open input file: {}", file))?;
+                Ok((file.as_str(), reader))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(readers))
+    }
+}
+
+impl<R: BufRead> TimestampMerge<R> {
+    pub fn new(readers: Vec<(&str, R)>) -> Self {
+        let tag_source = readers.len() > 1;
+        let sources = readers
+            .into_iter()
+            .map(|(name, reader)| Source {
+                name: tag_source.then(|| Arc::from(name)),
+                lines: reader.lines(),
+                line_number: 0,
+                last_timestamp: None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut merge = Self {
+            parser: TimestampParser::new(),
+            heap: BinaryHeap::with_capacity(sources.len()),
+            sources,
+        };
+
+        for source_index in 0..merge.sources.len() {
+            merge.read_next(source_index);
+        }
+
+        merge
+    }
+
+    /// Queues the next line of a source. A source that ends (or fails to read) is done.
+    fn read_next(&mut self, source_index: usize) {
+        let source = &mut self.sources[source_index];
+        let Some(Ok(content)) = source.lines.next() else {
+            return;
+        };
+
+        source.line_number += 1;
+        if let Some(timestamp) = self.parser.parse(&content) {
+            source.last_timestamp = Some(timestamp);
+        }
+
+        self.heap.push(Reverse(PendingLine {
+            timestamp: source.last_timestamp,
+            source_index,
+            line: LineData {
+                line_number: source.line_number,
+                content,
+                source: source.name.clone(),
+            },
+        }));
+    }
+}
+
+impl<R: BufRead> Iterator for TimestampMerge<R> {
+    type Item = LineData;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse(pending) = self.heap.pop()?;
+        self.read_next(pending.source_index);
+        Some(pending.line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_detects_supported_prefixes() {
+        let parser = TimestampParser::new();
+        let expected = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(10, 30, 12)
+            .unwrap();
+
+        assert_eq!(parser.parse("2024-01-15 10:30:12 INFO ok"), Some(expected));
+        assert_eq!(parser.parse("[2024/01/15 10:30:12] ok"), Some(expected));
+        assert_eq!(parser.parse("2024-01-15T12:30:12+02:00 ok"), Some(expected));
+        assert_eq!(parser.parse("1705314612 ok"), Some(expected));
+        assert_eq!(
+            parser.parse("1705314612250 ok"),
+            Some(expected + chrono::Duration::milliseconds(250))
+        );
+        assert_eq!(
+            parser.parse("2024-01-15T10:30:12.5Z ok"),
+            Some(expected + chrono::Duration::milliseconds(500))
+        );
+        assert!(parser.parse("Jan 15 10:30:12 host sshd: ok").is_some());
+        assert_eq!(parser.parse("    at com.example.Main"), None);
+    }
+
+    #[test]
+    fn test_merge_interleaves_files_and_keeps_continuations() {
+        let older = "2024-01-15 10:00:00 a1\n2024-01-15 10:02:00 a2\n    at stack.frame\n";
+        let newer = "2024-01-15 10:01:00 b1\n2024-01-15 10:03:00 b2\n";
+
+        let merged = TimestampMerge::new(vec![
+            ("app.log.1", Cursor::new(older)),
+            ("app.log", Cursor::new(newer)),
+        ])
+        .map(|line| line.content)
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            merged,
+            vec![
+                "2024-01-15 10:00:00 a1",
+                "2024-01-15 10:01:00 b1",
+                "2024-01-15 10:02:00 a2",
+                "    at stack.frame",
+                "2024-01-15 10:03:00 b2",
+            ]
+        );
+    }
+}
@@ -1,15 +1,27 @@
 use crate::models::GitIgnoreArgs;
+use crate::templates::resolve_templates;
 use anyhow::Result;
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
 use shared::system::get_current_working_dir::get_current_working_dir;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 pub fn print_runtime_info(args: &GitIgnoreArgs) {
     println!("Gitignore v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
-    println!("- Target folder: {}\n", args.target_folder.display());
+    println!("- Target folder: {}", args.target_folder.display());
+    if !args.templates.is_empty() {
+        println!("- Templates: {}", args.templates.join(", "));
+    }
+    if args.workspace {
+        println!(
+            "- Workspace scan: {}",
+            if args.apply { "apply" } else { "preview" }
+        );
+    }
+    println!();
 }
 
 pub fn validate_args(args: &GitIgnoreArgs) -> Result<()> {
@@ -17,27 +29,70 @@ pub fn validate_args(args: &GitIgnoreArgs) -> Result<()> {
         anyhow::bail!("Target folder does not exist, we don't have permission to read it or it is not a directory.");
     }
 
+    resolve_templates(&args.templates)?;
+
     Ok(())
 }
 
-pub fn get_cli_arguments() -> Result<GitIgnoreArgs> {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<GitIgnoreArgs> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
             env!("CARGO_PKG_DESCRIPTION"),
-            "Automatically creates or updates `.gitignore` files based on detected file types in your project.",
+            "Automatically creates or updates `.gitignore` files based on detected file types in your project. \
+            Use --templates to pick the templates instead of scanning the project, or --workspace to get \
+            suggestions for every project in the folder tree.",
         )
         .arg(Arg::new("target-dir")
             .help("Target folder to analyze")
             .num_args(1)
             .required(false)
         )
-        .get_matches();
+        .arg(Arg::new("templates")
+            .long("templates")
+            .short('t')
+            .value_name("TEMPLATES")
+            .value_delimiter(',')
+            .action(ArgAction::Append)
+            .help("Comma-separated templates to merge into the .gitignore (e.g.: rust,node,python), instead of scanning the folder")
+        )
+        .arg(Arg::new("workspace")
+            .long("workspace")
+            .short('w')
+            .action(ArgAction::SetTrue)
+            .conflicts_with("templates")
+            .help("Looks for projects (Cargo.toml, package.json, pyproject.toml, ...) in the folder tree and previews the additions to the .gitignore of each")
+        )
+        .arg(Arg::new("apply")
+            .long("apply")
+            .action(ArgAction::SetTrue)
+            .requires("workspace")
+            .help("With --workspace, writes the suggested additions instead of previewing them")
+        )
+        .arg(Arg::new("list-templates")
+            .long("list-templates")
+            .action(ArgAction::SetTrue)
+            .help("Lists the templates available to --templates")
+        )
+        .get_matches_from(argv);
 
     let target_folder = matches
         .get_one::<String>("target-dir")
         .map(|s| PathBuf::from(s))
         .unwrap_or_else(|| get_current_working_dir());
 
-    Ok(GitIgnoreArgs { target_folder })
+    let templates = matches
+        .get_many::<String>("templates")
+        .unwrap_or_default()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    Ok(GitIgnoreArgs {
+        target_folder,
+        templates,
+        list_templates: matches.get_flag("list-templates"),
+        workspace: matches.get_flag("workspace"),
+        apply: matches.get_flag("apply"),
+    })
 }
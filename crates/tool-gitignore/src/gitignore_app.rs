@@ -1,7 +1,10 @@
 use crate::config::Config;
+use crate::models::{GitignoreSource, MergeResult};
+use crate::templates::{find_template, resolve_templates, templates_for_project};
 use anyhow::{Context, Result};
 use reqwest::Client;
-use std::collections::HashSet;
+use shared::system::project_detection::detect_projects;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use tracing::info;
 use walkdir::WalkDir;
@@ -11,7 +14,6 @@ pub async fn run_gitignore_maintainer(folder: PathBuf) -> Result<()> {
     let config = Config::new();
     let mut keys_found: HashSet<String> = HashSet::new();
     let mut pending_urls: HashSet<String> = HashSet::new();
-    let mut gitignore_data: HashSet<String> = HashSet::new();
 
     info!("Figuring out which .gitignore files to download...");
     list_files(folder, &target_gitignore)
@@ -36,70 +38,233 @@ pub async fn run_gitignore_maintainer(folder: PathBuf) -> Result<()> {
         return Ok(());
     }
 
+    let mut pending_urls: Vec<String> = pending_urls.into_iter().collect();
+    pending_urls.sort();
+
     info!("Fetching new .gitignore data...");
     let client = Client::new();
-    for url in &pending_urls {
-        get_gitignore_data(url, &client, &mut gitignore_data).await?;
+    let mut sources = Vec::new();
+    for url in pending_urls {
+        let lines = get_gitignore_data(&url, &client).await?;
+        sources.push(GitignoreSource { label: url, lines });
+    }
+
+    dump_gitignore_data(&target_gitignore, &sources)?;
+
+    info!("All done!");
+    Ok(())
+}
+
+/// Merges the templates picked with `--templates` into the `.gitignore` of the folder.
+pub async fn run_template_merge(folder: &Path, names: &[String]) -> Result<()> {
+    let target_gitignore = folder.join(".gitignore");
+    let templates = resolve_templates(names)?;
+
+    info!("Fetching the templates...");
+    let client = Client::new();
+    let mut sources = Vec::new();
+    for template in templates {
+        let lines = get_gitignore_data(&template.url(), &client).await?;
+        sources.push(GitignoreSource {
+            label: template.name.to_string(),
+            lines,
+        });
     }
 
-    if gitignore_data.is_empty() {
-        info!("No new .gitignore data available...");
+    dump_gitignore_data(&target_gitignore, &sources)?;
+
+    info!("All done!");
+    Ok(())
+}
+
+/// Looks for projects in the folder tree and suggests the templates of each one to the
+/// `.gitignore` in the project folder. Without `apply`, only prints what would be added.
+pub async fn run_workspace_scan(folder: &Path, apply: bool) -> Result<()> {
+    info!("Looking for projects...");
+    let projects = detect_projects(folder, None);
+    if projects.is_empty() {
+        info!("No projects found. Guess I won't touch any .gitignore...");
         return Ok(());
     }
 
-    info!(
-        "Fetched {} lines of data for the .gitignore file...",
-        gitignore_data.len()
-    );
-    dump_gitignore_data(&target_gitignore, &mut gitignore_data)?;
+    let client = Client::new();
+    let mut fetched: HashMap<&'static str, Vec<String>> = HashMap::new();
+    let mut pending_changes = 0;
+
+    for project in &projects {
+        let display_root = project.root.strip_prefix(folder).unwrap_or(&project.root);
+        let display_gitignore = display_root.join(".gitignore");
+        let kinds: Vec<&str> = project.kinds.iter().map(|kind| kind.name()).collect();
+        info!(
+            "Found {} project in: {}",
+            kinds.join(" + "),
+            if display_root.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                display_root.display().to_string()
+            }
+        );
+
+        let mut sources = Vec::new();
+        for kind in &project.kinds {
+            for name in templates_for_project(*kind) {
+                let Some(template) = find_template(name) else {
+                    continue;
+                };
+                if !fetched.contains_key(template.name) {
+                    let lines = get_gitignore_data(&template.url(), &client).await?;
+                    fetched.insert(template.name, lines);
+                }
+                sources.push(GitignoreSource {
+                    label: template.name.to_string(),
+                    lines: fetched[template.name].clone(),
+                });
+            }
+        }
+
+        let target_gitignore = project.root.join(".gitignore");
+        let existing_content = if target_gitignore.exists() {
+            std::fs::read_to_string(&target_gitignore)
+                .with_context(|| format!("Failed to read {}", target_gitignore.display()))?
+        } else {
+            String::new()
+        };
+
+        let merged = merge_gitignore(&existing_content, &sources);
+        if merged.added.is_empty() {
+            info!("{} already has every entry.", display_gitignore.display());
+            continue;
+        }
+
+        pending_changes += 1;
+        if apply {
+            info!(
+                "Adding {} new entries to {}...",
+                merged.added_count(),
+                display_gitignore.display()
+            );
+            std::fs::write(&target_gitignore, &merged.content)
+                .with_context(|| format!("Failed to write {}", target_gitignore.display()))?;
+        } else {
+            print!(
+                "{}",
+                render_preview(&display_gitignore, !existing_content.is_empty(), &merged)
+            );
+        }
+    }
+
+    if pending_changes > 0 && !apply {
+        info!(
+            "{} .gitignore file(s) would change. Use --apply to write them.",
+            pending_changes
+        );
+    }
 
     info!("All done!");
     Ok(())
 }
 
-fn dump_gitignore_data(
-    target_gitignore: &PathBuf,
-    github_data: &mut HashSet<String>,
-) -> Result<()> {
-    if target_gitignore.exists() {
-        info!("The .gitignore already exists. Merging with the new data...");
-        let existing_content = std::fs::read_to_string(target_gitignore)
-            .context("Failed to read existing .gitignore")?;
+/// The changes as a unified diff. Merging only appends, so every changed line is an addition.
+fn render_preview(gitignore: &Path, exists: bool, merged: &MergeResult) -> String {
+    let old_name = if exists {
+        format!("a/{}", gitignore.display())
+    } else {
+        "/dev/null".to_string()
+    };
 
-        let existing_lines: Vec<String> = existing_content.lines().map(|s| s.to_string()).collect();
-        github_data.extend(existing_lines);
+    let mut preview = format!("--- {}\n+++ b/{}\n", old_name, gitignore.display());
+    for (index, source) in merged.added.iter().enumerate() {
+        if exists || index > 0 {
+            preview.push_str("+\n");
+        }
+        preview.push_str(&format!("+{}\n", section_header(&source.label)));
+        for line in &source.lines {
+            preview.push_str(&format!("+{}\n", line));
+        }
     }
+    preview.push('\n');
 
-    let clean_data = sanitize_gitignore_data(github_data);
+    preview
+}
 
-    info!("Writing {} lines to .gitignore...", clean_data.len());
+fn dump_gitignore_data(target_gitignore: &Path, sources: &[GitignoreSource]) -> Result<()> {
+    let existing_content = if target_gitignore.exists() {
+        info!("The .gitignore already exists. Merging with the new data...");
+        std::fs::read_to_string(target_gitignore).context("Failed to read existing .gitignore")?
+    } else {
+        String::new()
+    };
+
+    let merged = merge_gitignore(&existing_content, sources);
+    if merged.added.is_empty() {
+        info!("The .gitignore already has every entry. Guess I won't touch it...");
+        return Ok(());
+    }
+
+    info!(
+        "Adding {} new entries to .gitignore...",
+        merged.added_count()
+    );
 
-    std::fs::write(target_gitignore, clean_data.join("\n"))
-        .context("Failed to write .gitignore file")?;
+    std::fs::write(target_gitignore, merged.content).context("Failed to write .gitignore file")?;
 
     Ok(())
 }
 
-fn sanitize_gitignore_data(gitignore_data: &HashSet<String>) -> Vec<String> {
-    let mut data = HashSet::new();
+/// Appends the entries of each source that aren't in the `.gitignore` yet, under a comment with
+/// the source name. The existing content is kept as-is, and entries repeated across sources are
+/// only added once. Comments and blank lines of the sources are dropped.
+fn merge_gitignore(existing: &str, sources: &[GitignoreSource]) -> MergeResult {
+    let mut known: HashSet<&str> = existing.lines().filter_map(gitignore_entry).collect();
+    let mut content = existing.trim_end().to_string();
+    let mut added = Vec::new();
+
+    for source in sources {
+        let new_entries: Vec<&str> = source
+            .lines
+            .iter()
+            .filter_map(|line| gitignore_entry(line))
+            .filter(|entry| known.insert(*entry))
+            .collect();
 
-    for line in gitignore_data {
-        let trimmed = line.trim();
-        if !trimmed.starts_with('#') && !trimmed.is_empty() {
-            data.insert(trimmed.to_string());
+        if new_entries.is_empty() {
+            continue;
         }
+
+        if !content.is_empty() {
+            content.push_str("\n\n");
+        }
+        content.push_str(&section_header(&source.label));
+        content.push('\n');
+        content.push_str(&new_entries.join("\n"));
+        added.push(GitignoreSource {
+            label: source.label.clone(),
+            lines: new_entries.into_iter().map(String::from).collect(),
+        });
     }
 
-    let mut sorted_data: Vec<String> = data.into_iter().collect();
-    sorted_data.sort();
-    sorted_data
+    if !content.is_empty() {
+        content.push('\n');
+    }
+
+    MergeResult { content, added }
+}
+
+fn section_header(label: &str) -> String {
+    format!("# {} (added by gitignore)", label)
 }
 
-async fn get_gitignore_data(
-    url: &str,
-    client: &Client,
-    github_data: &mut HashSet<String>,
-) -> Result<()> {
+/// The pattern of a line, or `None` for comments and blank lines.
+fn gitignore_entry(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        None
+    } else {
+        Some(trimmed)
+    }
+}
+
+async fn get_gitignore_data(url: &str, client: &Client) -> Result<Vec<String>> {
     let response = client
         .get(url)
         .send()
@@ -119,23 +284,15 @@ async fn get_gitignore_data(
         .await
         .context("Failed to read response text")?;
 
-    let mut new_lines: usize = 0;
-    text.lines().map(|s| s.to_string()).for_each(|line| {
-        if github_data.insert(line) {
-            new_lines += 1;
-        }
-    });
+    let lines: Vec<String> = text.lines().map(|s| s.to_string()).collect();
 
-    if new_lines == 0 {
-        info!("No new lines of gitignore data found in {}", url);
-    } else {
-        info!(
-            "Successfully fetched {} lines of gitignore data from {}",
-            new_lines, url
-        );
-    }
+    info!(
+        "Successfully fetched {} lines of gitignore data from {}",
+        lines.len(),
+        url
+    );
 
-    Ok(())
+    Ok(lines)
 }
 
 fn list_files(base: PathBuf, target_gitignore: &PathBuf) -> Vec<PathBuf> {
@@ -194,3 +351,66 @@ fn is_in_ignorable_file(path: &Path) -> bool {
 fn is_gitignore_file(path: &Path) -> bool {
     path.file_name().and_then(|n| n.to_str()) == Some(".gitignore")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(label: &str, lines: &[&str]) -> GitignoreSource {
+        GitignoreSource {
+            label: label.to_string(),
+            lines: lines.iter().map(|line| line.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_content_and_skips_duplicates() {
+        let existing = "# My project\n/target\n.env\n\n";
+        let sources = [
+            source(
+                "rust",
+                &["# Generated by Cargo", "/target", "**/*.rs.bk", ""],
+            ),
+            source("node", &["node_modules/", ".env", "**/*.rs.bk"]),
+            source("python", &["# Nothing new", ".env"]),
+        ];
+
+        let merged = merge_gitignore(existing, &sources);
+
+        assert_eq!(merged.added_count(), 2);
+        assert_eq!(
+            merged.content,
+            "# My project\n/target\n.env\n\n\
+            # rust (added by gitignore)\n**/*.rs.bk\n\n\
+            # node (added by gitignore)\nnode_modules/\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_into_new_file() {
+        let merged = merge_gitignore("", &[source("rust", &["/target", "/target"])]);
+        assert_eq!(merged.added_count(), 1);
+        assert_eq!(merged.content, "# rust (added by gitignore)\n/target\n");
+
+        let unchanged = merge_gitignore("/target\n", &[source("rust", &["/target"])]);
+        assert!(unchanged.added.is_empty());
+    }
+
+    #[test]
+    fn test_render_preview_as_unified_diff() {
+        let merged = merge_gitignore(
+            "/target\n",
+            &[
+                source("rust", &["/target", "*.pdb"]),
+                source("node", &["node_modules/"]),
+            ],
+        );
+
+        assert_eq!(
+            render_preview(Path::new("api/.gitignore"), true, &merged),
+            "--- a/api/.gitignore\n+++ b/api/.gitignore\n\
+            +\n+# rust (added by gitignore)\n+*.pdb\n\
+            +\n+# node (added by gitignore)\n+node_modules/\n\n"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info, validate_args};
+use crate::gitignore_app::{run_gitignore_maintainer, run_template_merge, run_workspace_scan};
+use crate::templates::TEMPLATES;
+use anyhow::{Context, Result};
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+mod cli_utils;
+mod config;
+mod gitignore_app;
+mod models;
+mod templates;
+
+/// .gitignore maintainer.
+///
+/// Parses and validates arguments, then creates or updates the .gitignore files.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    match runtime.block_on(run_tool(argv)) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+async fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Info);
+    let args = get_cli_arguments(argv)?;
+    if args.list_templates {
+        print_templates();
+        return Ok(());
+    }
+
+    validate_args(&args).context("Cannot proceed")?;
+    print_runtime_info(&args);
+
+    if args.workspace {
+        run_workspace_scan(&args.target_folder, args.apply).await?;
+    } else if args.templates.is_empty() {
+        run_gitignore_maintainer(args.target_folder).await?;
+    } else {
+        run_template_merge(&args.target_folder, &args.templates).await?;
+    }
+
+    Ok(())
+}
+
+fn print_templates() {
+    for template in TEMPLATES {
+        if template.aliases.is_empty() {
+            println!("{}", template.name);
+        } else {
+            println!("{} (also: {})", template.name, template.aliases.join(", "));
+        }
+    }
+}
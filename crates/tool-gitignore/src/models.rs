@@ -2,4 +2,31 @@ use std::path::PathBuf;
 
 pub struct GitIgnoreArgs {
     pub target_folder: PathBuf,
+    /// Templates picked with `--templates`. When empty, the folder is scanned instead.
+    pub templates: Vec<String>,
+    pub list_templates: bool,
+    /// Scans the folder tree for projects and suggests additions to the `.gitignore` of each.
+    pub workspace: bool,
+    /// With `workspace`, writes the suggestions instead of previewing them.
+    pub apply: bool,
+}
+
+/// Gitignore entries fetched from one source, in the order they appear there.
+pub struct GitignoreSource {
+    /// Name of the section the entries are added under (template name or URL).
+    pub label: String,
+    pub lines: Vec<String>,
+}
+
+/// Result of merging sources into an existing `.gitignore`.
+pub struct MergeResult {
+    pub content: String,
+    /// Entries added, per source (sources with nothing new are left out).
+    pub added: Vec<GitignoreSource>,
+}
+
+impl MergeResult {
+    pub fn added_count(&self) -> usize {
+        self.added.iter().map(|source| source.lines.len()).sum()
+    }
 }
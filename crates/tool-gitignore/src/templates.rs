@@ -0,0 +1,169 @@
+use anyhow::{bail, Result};
+use shared::system::project_detection::ProjectKind;
+
+const GITHUB_GITIGNORE: &str = "https://raw.githubusercontent.com/github/gitignore/main";
+
+/// A gitignore template that can be picked with `--templates`.
+pub struct Template {
+    /// Name used in `--templates` (lowercase).
+    pub name: &'static str,
+    /// Other names accepted for the template.
+    pub aliases: &'static [&'static str],
+    /// Path of the template, relative to github/gitignore, or a full URL.
+    pub source: &'static str,
+}
+
+impl Template {
+    pub fn url(&self) -> String {
+        if self.source.starts_with("https://") {
+            self.source.to_string()
+        } else {
+            format!("{}/{}", GITHUB_GITIGNORE, self.source)
+        }
+    }
+}
+
+const fn template(
+    name: &'static str,
+    aliases: &'static [&'static str],
+    source: &'static str,
+) -> Template {
+    Template {
+        name,
+        aliases,
+        source,
+    }
+}
+
+/// Templates available to `--templates`, in the order they are listed by `--list-templates`.
+pub const TEMPLATES: &[Template] = &[
+    // Languages
+    template("c", &[], "C.gitignore"),
+    template("cpp", &["c++"], "C%2B%2B.gitignore"),
+    template("dart", &["flutter"], "Dart.gitignore"),
+    template("dotnet", &["csharp", "c#", "visualstudio"], "VisualStudio.gitignore"),
+    template("elixir", &["erlang"], "Elixir.gitignore"),
+    template("go", &["golang"], "Go.gitignore"),
+    template("haskell", &[], "Haskell.gitignore"),
+    template("java", &[], "Java.gitignore"),
+    template("julia", &[], "Julia.gitignore"),
+    template("kotlin", &[], "Kotlin.gitignore"),
+    template(
+        "node",
+        &["nodejs", "javascript", "js", "typescript", "ts", "react"],
+        "Node.gitignore",
+    ),
+    template("objective-c", &["objc"], "Objective-C.gitignore"),
+    template("perl", &[], "Perl.gitignore"),
+    template("python", &["py"], "Python.gitignore"),
+    template("r", &[], "R.gitignore"),
+    template("ruby", &["rb", "rails"], "Ruby.gitignore"),
+    template("rust", &["rs"], "Rust.gitignore"),
+    template("scala", &[], "Scala.gitignore"),
+    template("swift", &[], "Swift.gitignore"),
+    template("tex", &["latex"], "TeX.gitignore"),
+    // Frameworks and build tools
+    template("composer", &["php"], "Composer.gitignore"),
+    template("godot", &[], "Godot.gitignore"),
+    template("gradle", &[], "Gradle.gitignore"),
+    template("hugo", &[], "community/Golang/Hugo.gitignore"),
+    template("maven", &[], "Maven.gitignore"),
+    template("nextjs", &["next"], "Nextjs.gitignore"),
+    template("terraform", &["tf"], "Terraform.gitignore"),
+    template("unity", &[], "Unity.gitignore"),
+    template("unreal", &["unrealengine"], "UnrealEngine.gitignore"),
+    template(
+        "sqlite",
+        &[],
+        "https://raw.githubusercontent.com/brenordv/gitignore-files/refs/heads/master/sqlite.gitignore",
+    ),
+    // Editors and operating systems
+    template("emacs", &[], "Global/Emacs.gitignore"),
+    template("jetbrains", &["idea", "rider", "intellij"], "Global/JetBrains.gitignore"),
+    template("vim", &[], "Global/Vim.gitignore"),
+    template("vscode", &[], "Global/VisualStudioCode.gitignore"),
+    template("linux", &[], "Global/Linux.gitignore"),
+    template("macos", &["osx", "mac"], "Global/macOS.gitignore"),
+    template("windows", &["win"], "Global/Windows.gitignore"),
+];
+
+/// Finds a template by name or alias (case-insensitive).
+pub fn find_template(name: &str) -> Option<&'static Template> {
+    let name = name.trim().to_lowercase();
+    TEMPLATES
+        .iter()
+        .find(|template| template.name == name || template.aliases.contains(&name.as_str()))
+}
+
+/// Templates suggested for a detected project.
+pub fn templates_for_project(kind: ProjectKind) -> &'static [&'static str] {
+    match kind {
+        ProjectKind::Rust => &["rust"],
+        ProjectKind::Node => &["node"],
+        ProjectKind::Python => &["python"],
+        ProjectKind::Go => &["go"],
+        ProjectKind::Maven => &["java", "maven"],
+        ProjectKind::Gradle => &["java", "gradle"],
+        ProjectKind::Dotnet => &["dotnet"],
+        ProjectKind::Ruby => &["ruby"],
+        ProjectKind::Composer => &["composer"],
+        ProjectKind::Dart => &["dart"],
+        ProjectKind::Elixir => &["elixir"],
+        ProjectKind::Swift => &["swift"],
+    }
+}
+
+/// Resolves the names given to `--templates`, skipping repeated templates (`node,js`).
+pub fn resolve_templates(names: &[String]) -> Result<Vec<&'static Template>> {
+    let mut templates: Vec<&'static Template> = Vec::new();
+    let mut unknown = Vec::new();
+
+    for name in names {
+        match find_template(name) {
+            Some(template) => {
+                if !templates.iter().any(|t| t.name == template.name) {
+                    templates.push(template);
+                }
+            }
+            None => unknown.push(name.as_str()),
+        }
+    }
+
+    if !unknown.is_empty() {
+        bail!(
+            "Unknown template(s): {}. Use --list-templates to see the available ones.",
+            unknown.join(", ")
+        );
+    }
+
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_templates_by_name_and_alias() {
+        let names = ["Rust", "node", "ts", "py"].map(String::from);
+        let templates = resolve_templates(&names).unwrap();
+
+        let resolved: Vec<&str> = templates.iter().map(|t| t.name).collect();
+        assert_eq!(resolved, vec!["rust", "node", "python"]);
+        assert_eq!(
+            templates[0].url(),
+            "https://raw.githubusercontent.com/github/gitignore/main/Rust.gitignore"
+        );
+
+        assert!(resolve_templates(&["rust".to_string(), "cobol".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_every_project_kind_has_known_templates() {
+        for kind in ProjectKind::ALL {
+            for name in templates_for_project(kind) {
+                assert!(find_template(name).is_some(), "{}", name);
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::models::GitStatArgs;
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{FlagAlias, GlobalArgs, GlobalFlag, ToolCli};
+use shared::utils::table_output::ReportFormat;
+use std::path::PathBuf;
+
+const DEFAULT_TOP: usize = 10;
+const DEFAULT_WEEKS: usize = 12;
+const DEFAULT_STALE_DAYS: i64 = 90;
+
+const FLAG_ALIASES: &[FlagAlias] = &[FlagAlias::alias("-o", "--output")];
+
+impl ToolCli for GitStatArgs {
+    const DISPLAY_NAME: &'static str = "Git Stat";
+
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Analyzes a local git repository (nothing is fetched) and reports the commits per \
+                author and per week, the files that change the most (churn hotspots), the largest \
+                blobs in the history, and the branches without recent commits. The report can be \
+                printed as tables, markdown, or JSON.",
+            )
+            .arg(
+                Arg::new("repository")
+                    .value_name("PATH")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Repository (or any folder inside it) to analyze. (Default: current folder)"),
+            )
+            .arg(
+                Arg::new("since")
+                    .long("since")
+                    .short('s')
+                    .value_name("DATE")
+                    .help("Only commits after this date. Anything git understands: 2026-01-01, \"3 months ago\", ... (Default: all history)"),
+            )
+            .arg(
+                Arg::new("top")
+                    .long("top")
+                    .short('t')
+                    .value_name("N")
+                    .value_parser(clap::value_parser!(usize))
+                    .help(format!(
+                        "Number of churn hotspots and largest blobs listed. (Default: {})",
+                        DEFAULT_TOP
+                    )),
+            )
+            .arg(
+                Arg::new("weeks")
+                    .long("weeks")
+                    .short('w')
+                    .value_name("N")
+                    .value_parser(clap::value_parser!(usize))
+                    .help(format!(
+                        "Number of most recent weeks (with commits) in the commits per week. 0 lists all. (Default: {})",
+                        DEFAULT_WEEKS
+                    )),
+            )
+            .arg(
+                Arg::new("stale-days")
+                    .long("stale-days")
+                    .short('d')
+                    .value_name("DAYS")
+                    .value_parser(clap::value_parser!(i64).range(0..))
+                    .help(format!(
+                        "Branches without commits for more than this many days are stale. (Default: {})",
+                        DEFAULT_STALE_DAYS
+                    )),
+            )
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .short('f')
+                    .value_name("FORMAT")
+                    .value_parser(["table", "markdown", "md", "json"])
+                    .help("Report format: table, markdown (md), or json. (Default: table)"),
+            )
+    }
+
+    fn global_flags() -> &'static [GlobalFlag] {
+        &[GlobalFlag::Quiet, GlobalFlag::NoColor, GlobalFlag::Output]
+    }
+
+    fn flag_aliases() -> &'static [FlagAlias] {
+        FLAG_ALIASES
+    }
+
+    fn from_matches(matches: &ArgMatches, global: &GlobalArgs) -> Result<Self> {
+        let format = match matches.get_one::<String>("format") {
+            Some(format) => format.parse()?,
+            None => ReportFormat::Table,
+        };
+
+        Ok(GitStatArgs {
+            repository: matches
+                .get_one::<PathBuf>("repository")
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from(".")),
+            since: matches.get_one::<String>("since").cloned(),
+            top: matches
+                .get_one::<usize>("top")
+                .copied()
+                .unwrap_or(DEFAULT_TOP),
+            weeks: matches
+                .get_one::<usize>("weeks")
+                .copied()
+                .unwrap_or(DEFAULT_WEEKS),
+            stale_days: matches
+                .get_one::<i64>("stale-days")
+                .copied()
+                .unwrap_or(DEFAULT_STALE_DAYS),
+            format,
+            output: global.output.clone(),
+        })
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                "Repository".to_string(),
+                self.repository.display().to_string(),
+            ),
+            (
+                "Since".to_string(),
+                self.since
+                    .clone()
+                    .unwrap_or_else(|| "all history".to_string()),
+            ),
+            ("Top".to_string(), self.top.to_string()),
+            ("Weeks".to_string(), self.weeks.to_string()),
+            (
+                "Stale after (days)".to_string(),
+                self.stale_days.to_string(),
+            ),
+            ("Format".to_string(), self.format.name().to_string()),
+            (
+                "Output".to_string(),
+                self.output
+                    .as_ref()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| "stdout".to_string()),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    fn parse(argv: &[&str]) -> Result<GitStatArgs> {
+        try_parse_tool_cli_from::<GitStatArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_defaults_and_options() {
+        let defaults = parse(&["gitstat"]).unwrap();
+        assert_eq!(defaults.repository, PathBuf::from("."));
+        assert_eq!(defaults.top, DEFAULT_TOP);
+        assert_eq!(defaults.stale_days, DEFAULT_STALE_DAYS);
+        assert_eq!(defaults.format, ReportFormat::Table);
+
+        let args = parse(&[
+            "gitstat",
+            "../repo",
+            "--since",
+            "3 months ago",
+            "-t",
+            "5",
+            "-f",
+            "md",
+            "-o",
+            "report.md",
+        ])
+        .unwrap();
+        assert_eq!(args.repository, PathBuf::from("../repo"));
+        assert_eq!(args.since.as_deref(), Some("3 months ago"));
+        assert_eq!(args.top, 5);
+        assert_eq!(args.format, ReportFormat::Markdown);
+        assert_eq!(args.output, Some(PathBuf::from("report.md")));
+
+        assert!(parse(&["gitstat", "--format", "xml"]).is_err());
+    }
+}
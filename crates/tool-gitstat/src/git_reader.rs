@@ -0,0 +1,278 @@
+use crate::models::{BlobRecord, BranchRecord, CommitRecord, FileChange};
+use anyhow::{anyhow, Context, Result};
+use chrono::DateTime;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+/// Separates the commits in the `git log` output (ASCII record separator).
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// Separates the fields of a record (ASCII unit separator).
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Runs git in the repository and returns its stdout. Only local commands are used: nothing is
+/// fetched.
+///
+/// # Errors
+/// Returns an error if git isn't installed, or if the command fails (e.g.: not a repository).
+fn run_git(repository: &Path, args: &[&str]) -> Result<String> {
+    debug!("Running git {:?} in {}", args, repository.display());
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        // Paths with non-ASCII characters are printed as-is, instead of quoted and escaped.
+        .args(["-c", "core.quotePath=false"])
+        .args(args)
+        .output()
+        .context("Failed to run git. Make sure it's installed and in the PATH")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Root folder of the repository the path is in.
+pub fn resolve_repository_root(repository: &Path) -> Result<String> {
+    let root = run_git(repository, &["rev-parse", "--show-toplevel"])
+        .context(format!("{} is not a git repository", repository.display()))?;
+    Ok(root.trim().to_string())
+}
+
+/// Non-merge commits of the current branch, newest first, with the lines changed per file.
+/// Author names and emails go through `.mailmap`. Renames are counted as a delete and an add.
+pub fn read_commits(repository: &Path, since: Option<&str>) -> Result<Vec<CommitRecord>> {
+    // A repository without commits has no HEAD: that's an empty history, not an error.
+    if run_git(repository, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_err() {
+        return Ok(Vec::new());
+    }
+
+    let pretty = format!(
+        "--pretty=format:{r}%H{f}%aN{f}%aE{f}%aI",
+        r = RECORD_SEPARATOR,
+        f = FIELD_SEPARATOR
+    );
+    let since_arg = since.map(|since| format!("--since={}", since));
+
+    let mut args = vec![
+        "log",
+        "--no-merges",
+        "--no-renames",
+        "--numstat",
+        pretty.as_str(),
+    ];
+    if let Some(since_arg) = since_arg.as_deref() {
+        args.push(since_arg);
+    }
+
+    parse_log(&run_git(repository, &args)?)
+}
+
+/// Parses the output of `git log --numstat` in the format used by [`read_commits`].
+pub fn parse_log(output: &str) -> Result<Vec<CommitRecord>> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter(|record| !record.trim().is_empty())
+        .map(parse_commit)
+        .collect()
+}
+
+fn parse_commit(record: &str) -> Result<CommitRecord> {
+    let mut lines = record.lines();
+    let header = lines.next().unwrap_or_default();
+    let fields: Vec<&str> = header.split(FIELD_SEPARATOR).collect();
+
+    let [hash, author, email, date] = fields.as_slice() else {
+        return Err(anyhow!("Unexpected git log record: {}", header));
+    };
+
+    let date = DateTime::parse_from_rfc3339(date)
+        .context(format!("Invalid date in commit {}: {}", hash, date))?;
+
+    let files = lines.filter_map(parse_numstat_line).collect();
+
+    Ok(CommitRecord {
+        hash: hash.to_string(),
+        author: author.to_string(),
+        email: email.to_string(),
+        date,
+        files,
+    })
+}
+
+/// `added<TAB>deleted<TAB>path`, with `-` as the counts of binary files.
+fn parse_numstat_line(line: &str) -> Option<FileChange> {
+    let mut parts = line.splitn(3, '\t');
+    let added = parts.next()?;
+    let deleted = parts.next()?;
+    let path = parts.next()?;
+
+    Some(FileChange {
+        path: path.to_string(),
+        added: added.parse().ok(),
+        deleted: deleted.parse().ok(),
+    })
+}
+
+/// Local and remote-tracking branches, with the date and author of their last commit.
+pub fn read_branches(repository: &Path) -> Result<Vec<BranchRecord>> {
+    let format = format!(
+        "--format=%(refname:short){f}%(committerdate:iso-strict){f}%(authorname){f}%(symref)",
+        f = FIELD_SEPARATOR
+    );
+    let output = run_git(
+        repository,
+        &[
+            "for-each-ref",
+            format.as_str(),
+            "refs/heads",
+            "refs/remotes",
+        ],
+    )?;
+
+    parse_branches(&output)
+}
+
+/// Parses the output of `git for-each-ref` in the format used by [`read_branches`]. Symbolic refs
+/// (e.g.: `origin/HEAD`) are skipped.
+pub fn parse_branches(output: &str) -> Result<Vec<BranchRecord>> {
+    let mut branches = Vec::new();
+
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        let fields: Vec<&str> = line.split(FIELD_SEPARATOR).collect();
+        let [name, date, author, symref] = fields.as_slice() else {
+            return Err(anyhow!("Unexpected git for-each-ref line: {}", line));
+        };
+
+        if !symref.is_empty() {
+            continue;
+        }
+
+        branches.push(BranchRecord {
+            name: name.to_string(),
+            last_commit: DateTime::parse_from_rfc3339(date)
+                .context(format!("Invalid date in branch {}: {}", name, date))?,
+            author: author.to_string(),
+        });
+    }
+
+    Ok(branches)
+}
+
+/// Blobs reachable from any ref (the whole history, not only the current files), with the path
+/// each one was first seen at.
+pub fn read_blobs(repository: &Path) -> Result<Vec<BlobRecord>> {
+    let objects = run_git(repository, &["rev-list", "--objects", "--all"])?;
+    let sizes = run_git(
+        repository,
+        &[
+            "cat-file",
+            "--batch-all-objects",
+            "--batch-check=%(objectname) %(objecttype) %(objectsize)",
+        ],
+    )?;
+
+    Ok(parse_blobs(&objects, &sizes))
+}
+
+/// Joins the paths from `git rev-list --objects` with the sizes from `git cat-file
+/// --batch-check`. Unreachable blobs (no path) are left out.
+pub fn parse_blobs(objects: &str, sizes: &str) -> Vec<BlobRecord> {
+    let mut paths: HashMap<&str, &str> = HashMap::new();
+    for line in objects.lines() {
+        if let Some((oid, path)) = line.split_once(' ') {
+            paths.entry(oid).or_insert(path);
+        }
+    }
+
+    sizes
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split(' ');
+            let oid = parts.next()?;
+            let object_type = parts.next()?;
+            let size = parts.next()?.parse().ok()?;
+
+            if object_type != "blob" {
+                return None;
+            }
+
+            paths.get(oid).map(|path| BlobRecord {
+                path: path.to_string(),
+                oid: oid.to_string(),
+                size_bytes: size,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_with_numstat() {
+        let output = format!(
+            "{r}aaa{f}Ana{f}ana@example.com{f}2026-10-12T10:00:00+02:00\n\
+             10\t2\tsrc/main.rs\n\
+             -\t-\tlogo.png\n\
+             \n\
+             {r}bbb{f}Bo{f}bo@example.com{f}2026-10-01T08:30:00-03:00\n\
+             1\t0\treadme.md",
+            r = RECORD_SEPARATOR,
+            f = FIELD_SEPARATOR
+        );
+
+        let commits = parse_log(&output).unwrap();
+        assert_eq!(commits.len(), 2);
+
+        assert_eq!(commits[0].hash, "aaa");
+        assert_eq!(commits[0].author, "Ana");
+        assert_eq!(
+            commits[0].files,
+            vec![
+                FileChange {
+                    path: "src/main.rs".to_string(),
+                    added: Some(10),
+                    deleted: Some(2),
+                },
+                FileChange {
+                    path: "logo.png".to_string(),
+                    added: None,
+                    deleted: None,
+                },
+            ]
+        );
+        assert_eq!(commits[1].email, "bo@example.com");
+        assert_eq!(commits[1].files.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_branches_and_blobs() {
+        let output = format!(
+            "main{f}2026-10-15T09:00:00+00:00{f}Ana{f}\n\
+             origin/HEAD{f}2026-10-15T09:00:00+00:00{f}Ana{f}refs/remotes/origin/main\n\
+             origin/old{f}2025-01-02T09:00:00+00:00{f}Bo{f}",
+            f = FIELD_SEPARATOR
+        );
+
+        let branches = parse_branches(&output).unwrap();
+        let names: Vec<&str> = branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "origin/old"]);
+
+        let objects = "c1\nt1 \nb1 assets/video.mp4\nb2 src/lib.rs\n";
+        let sizes = "c1 commit 200\nt1 tree 90\nb1 blob 5000000\nb2 blob 1200\nb3 blob 999999999\n";
+        let blobs = parse_blobs(objects, sizes);
+        assert_eq!(blobs.len(), 2);
+        assert_eq!(blobs[0].path, "assets/video.mp4");
+        assert_eq!(blobs[0].size_bytes, 5_000_000);
+    }
+}
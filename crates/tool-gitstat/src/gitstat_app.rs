@@ -0,0 +1,48 @@
+use crate::git_reader::{read_blobs, read_branches, read_commits, resolve_repository_root};
+use crate::models::{GitStatArgs, RepositoryReport};
+use crate::report_builder::{
+    find_stale_branches, largest_blobs, summarize_authors, summarize_churn, summarize_weeks,
+};
+use crate::report_printer::render_report;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use tracing::info;
+
+/// Reads the repository history, builds the report, and writes it to `--output` (or stdout).
+pub fn run(args: &GitStatArgs) -> Result<()> {
+    let report = build_report(args)?;
+    let rendered = render_report(&report, args.format)?;
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .context(format!("Failed to write the report to {}", path.display()))?;
+            info!("Report written to {}", path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+fn build_report(args: &GitStatArgs) -> Result<RepositoryReport> {
+    let repository = resolve_repository_root(&args.repository)?;
+    info!("Analyzing {}", repository);
+
+    let commits = read_commits(&args.repository, args.since.as_deref())?;
+    let branches = read_branches(&args.repository)?;
+    let blobs = read_blobs(&args.repository)?;
+    let now = Utc::now();
+
+    Ok(RepositoryReport {
+        repository,
+        generated_at: now,
+        since: args.since.clone(),
+        total_commits: commits.len(),
+        authors: summarize_authors(&commits),
+        weekly_commits: summarize_weeks(&commits, args.weeks),
+        churn_hotspots: summarize_churn(&commits, args.top),
+        largest_blobs: largest_blobs(blobs, args.top),
+        stale_branches: find_stale_branches(&branches, args.stale_days, now),
+    })
+}
@@ -0,0 +1,97 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Serialize;
+use shared::utils::table_output::ReportFormat;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitStatArgs {
+    pub repository: PathBuf,
+    pub since: Option<String>,
+    pub top: usize,
+    pub weeks: usize,
+    pub stale_days: i64,
+    pub format: ReportFormat,
+    pub output: Option<PathBuf>,
+}
+
+/// A commit read from `git log`, with the lines added/deleted per file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitRecord {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    pub date: DateTime<FixedOffset>,
+    pub files: Vec<FileChange>,
+}
+
+/// One line of `--numstat`. Binary files have no line counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: String,
+    pub added: Option<u64>,
+    pub deleted: Option<u64>,
+}
+
+/// A branch (local or remote-tracking) and its last commit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchRecord {
+    pub name: String,
+    pub last_commit: DateTime<FixedOffset>,
+    pub author: String,
+}
+
+/// A blob reachable from any ref, with the path it was first seen at.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlobRecord {
+    pub path: String,
+    pub oid: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepositoryReport {
+    pub repository: String,
+    pub generated_at: DateTime<Utc>,
+    pub since: Option<String>,
+    pub total_commits: usize,
+    pub authors: Vec<AuthorActivity>,
+    pub weekly_commits: Vec<WeeklyCommits>,
+    pub churn_hotspots: Vec<FileChurn>,
+    pub largest_blobs: Vec<BlobRecord>,
+    pub stale_branches: Vec<StaleBranch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorActivity {
+    pub author: String,
+    pub email: String,
+    pub commits: usize,
+    pub lines_added: u64,
+    pub lines_deleted: u64,
+    pub last_commit: DateTime<FixedOffset>,
+}
+
+/// Commits of an author in an ISO week (e.g.: `2026-W42`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeeklyCommits {
+    pub week: String,
+    pub author: String,
+    pub commits: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileChurn {
+    pub path: String,
+    pub commits: usize,
+    pub lines_added: u64,
+    pub lines_deleted: u64,
+    pub churn: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StaleBranch {
+    pub name: String,
+    pub last_commit: DateTime<FixedOffset>,
+    pub days_since_last_commit: i64,
+    pub author: String,
+}
@@ -0,0 +1,248 @@
+use crate::models::{
+    AuthorActivity, BlobRecord, BranchRecord, CommitRecord, FileChurn, StaleBranch, WeeklyCommits,
+};
+use chrono::{DateTime, Datelike, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// Commits, lines changed, and last commit of each author. Most active authors first.
+pub fn summarize_authors(commits: &[CommitRecord]) -> Vec<AuthorActivity> {
+    let mut authors: HashMap<&str, AuthorActivity> = HashMap::new();
+
+    for commit in commits {
+        let activity = authors
+            .entry(commit.author.as_str())
+            .or_insert_with(|| AuthorActivity {
+                author: commit.author.clone(),
+                email: commit.email.clone(),
+                commits: 0,
+                lines_added: 0,
+                lines_deleted: 0,
+                last_commit: commit.date,
+            });
+
+        activity.commits += 1;
+        for file in &commit.files {
+            activity.lines_added += file.added.unwrap_or(0);
+            activity.lines_deleted += file.deleted.unwrap_or(0);
+        }
+        if commit.date > activity.last_commit {
+            activity.last_commit = commit.date;
+        }
+    }
+
+    let mut authors: Vec<AuthorActivity> = authors.into_values().collect();
+    authors.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.author.cmp(&b.author)));
+    authors
+}
+
+/// Commits per author and ISO week (`2026-W42`, by the author's date). Newest weeks first, and
+/// the most active authors first within a week.
+///
+/// # Parameters
+/// - `weeks`: Number of most recent weeks (with commits) to keep. 0 keeps all
+pub fn summarize_weeks(commits: &[CommitRecord], weeks: usize) -> Vec<WeeklyCommits> {
+    let mut counts: BTreeMap<String, HashMap<&str, usize>> = BTreeMap::new();
+
+    for commit in commits {
+        let iso_week = commit.date.iso_week();
+        let week = format!("{}-W{:02}", iso_week.year(), iso_week.week());
+        *counts
+            .entry(week)
+            .or_default()
+            .entry(commit.author.as_str())
+            .or_insert(0) += 1;
+    }
+
+    let keep = if weeks == 0 { counts.len() } else { weeks };
+
+    counts
+        .into_iter()
+        .rev()
+        .take(keep)
+        .flat_map(|(week, authors)| {
+            let mut rows: Vec<WeeklyCommits> = authors
+                .into_iter()
+                .map(|(author, commits)| WeeklyCommits {
+                    week: week.clone(),
+                    author: author.to_string(),
+                    commits,
+                })
+                .collect();
+            rows.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.author.cmp(&b.author)));
+            rows
+        })
+        .collect()
+}
+
+/// Files with the most lines changed (added + deleted), and how many commits touched them.
+/// Binary files only count commits, so they rank by churn 0.
+pub fn summarize_churn(commits: &[CommitRecord], top: usize) -> Vec<FileChurn> {
+    let mut files: HashMap<&str, FileChurn> = HashMap::new();
+
+    for commit in commits {
+        for change in &commit.files {
+            let churn = files
+                .entry(change.path.as_str())
+                .or_insert_with(|| FileChurn {
+                    path: change.path.clone(),
+                    commits: 0,
+                    lines_added: 0,
+                    lines_deleted: 0,
+                    churn: 0,
+                });
+
+            churn.commits += 1;
+            churn.lines_added += change.added.unwrap_or(0);
+            churn.lines_deleted += change.deleted.unwrap_or(0);
+            churn.churn = churn.lines_added + churn.lines_deleted;
+        }
+    }
+
+    let mut files: Vec<FileChurn> = files.into_values().collect();
+    files.sort_by(|a, b| {
+        b.churn
+            .cmp(&a.churn)
+            .then(b.commits.cmp(&a.commits))
+            .then(a.path.cmp(&b.path))
+    });
+    files.truncate(top);
+    files
+}
+
+/// The largest blobs, biggest first.
+pub fn largest_blobs(mut blobs: Vec<BlobRecord>, top: usize) -> Vec<BlobRecord> {
+    blobs.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.path.cmp(&b.path)));
+    blobs.truncate(top);
+    blobs
+}
+
+/// Branches whose last commit is older than `stale_days`. Oldest first.
+pub fn find_stale_branches(
+    branches: &[BranchRecord],
+    stale_days: i64,
+    now: DateTime<Utc>,
+) -> Vec<StaleBranch> {
+    let mut stale: Vec<StaleBranch> = branches
+        .iter()
+        .filter_map(|branch| {
+            let age = now.signed_duration_since(branch.last_commit).num_days();
+            (age > stale_days).then(|| StaleBranch {
+                name: branch.name.clone(),
+                last_commit: branch.last_commit,
+                days_since_last_commit: age,
+                author: branch.author.clone(),
+            })
+        })
+        .collect();
+
+    stale.sort_by(|a, b| a.last_commit.cmp(&b.last_commit).then(a.name.cmp(&b.name)));
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FileChange;
+
+    fn commit(
+        author: &str,
+        date: &str,
+        files: &[(&str, Option<u64>, Option<u64>)],
+    ) -> CommitRecord {
+        CommitRecord {
+            hash: format!("{}-{}", author, date),
+            author: author.to_string(),
+            email: format!("{}@example.com", author.to_lowercase()),
+            date: DateTime::parse_from_rfc3339(date).unwrap(),
+            files: files
+                .iter()
+                .map(|(path, added, deleted)| FileChange {
+                    path: path.to_string(),
+                    added: *added,
+                    deleted: *deleted,
+                })
+                .collect(),
+        }
+    }
+
+    fn create_commits() -> Vec<CommitRecord> {
+        vec![
+            commit(
+                "Ana",
+                "2026-10-14T10:00:00+00:00",
+                &[("src/main.rs", Some(30), Some(10))],
+            ),
+            commit(
+                "Bo",
+                "2026-10-13T10:00:00+00:00",
+                &[("src/main.rs", Some(5), Some(5)), ("logo.png", None, None)],
+            ),
+            commit(
+                "Ana",
+                "2026-10-05T10:00:00+00:00",
+                &[("readme.md", Some(100), Some(0))],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_summarize_authors_and_weeks() {
+        let commits = create_commits();
+
+        let authors = summarize_authors(&commits);
+        assert_eq!(authors[0].author, "Ana");
+        assert_eq!(authors[0].commits, 2);
+        assert_eq!(authors[0].lines_added, 130);
+        assert_eq!(
+            authors[0].last_commit.to_rfc3339(),
+            "2026-10-14T10:00:00+00:00"
+        );
+
+        let weeks = summarize_weeks(&commits, 0);
+        let rows: Vec<(&str, &str, usize)> = weeks
+            .iter()
+            .map(|w| (w.week.as_str(), w.author.as_str(), w.commits))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("2026-W42", "Ana", 1),
+                ("2026-W42", "Bo", 1),
+                ("2026-W41", "Ana", 1)
+            ]
+        );
+
+        assert_eq!(summarize_weeks(&commits, 1).len(), 2);
+    }
+
+    #[test]
+    fn test_summarize_churn() {
+        let churn = summarize_churn(&create_commits(), 2);
+        assert_eq!(churn.len(), 2);
+        assert_eq!(churn[0].path, "readme.md");
+        assert_eq!(churn[1].path, "src/main.rs");
+        assert_eq!((churn[1].commits, churn[1].churn), (2, 50));
+    }
+
+    #[test]
+    fn test_find_stale_branches() {
+        let branch = |name: &str, date: &str| BranchRecord {
+            name: name.to_string(),
+            last_commit: DateTime::parse_from_rfc3339(date).unwrap(),
+            author: "Ana".to_string(),
+        };
+        let branches = vec![
+            branch("main", "2026-10-15T00:00:00+00:00"),
+            branch("feature/old", "2026-03-01T00:00:00+00:00"),
+            branch("origin/older", "2025-12-01T00:00:00+00:00"),
+        ];
+        let now = DateTime::parse_from_rfc3339("2026-10-16T00:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let stale = find_stale_branches(&branches, 90, now);
+        let names: Vec<&str> = stale.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["origin/older", "feature/old"]);
+        assert_eq!(stale[1].days_since_last_commit, 229);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::models::RepositoryReport;
+use anyhow::Result;
+use shared::utils::format_bytes_to_string::format_bytes_to_string;
+use shared::utils::table_output::{render_json, ReportFormat, Table};
+
+/// Date format of the tables. JSON keeps the full timestamps.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Renders the report in the format: one table per section (text or markdown), or the whole
+/// report as JSON.
+pub fn render_report(report: &RepositoryReport, format: ReportFormat) -> Result<String> {
+    if format == ReportFormat::Json {
+        return render_json(report);
+    }
+
+    let mut output = String::new();
+
+    if format == ReportFormat::Markdown {
+        output.push_str(&format!("# Repository activity: {}\n\n", report.repository));
+        output.push_str(&format!(
+            "Generated at {} from {} commits{}.\n\n",
+            report.generated_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            report.total_commits,
+            since_suffix(report)
+        ));
+    } else {
+        output.push_str(&format!(
+            "{} commits{}\n\n",
+            report.total_commits,
+            since_suffix(report)
+        ));
+    }
+
+    for table in build_tables(report) {
+        output.push_str(&table.render(format));
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+fn since_suffix(report: &RepositoryReport) -> String {
+    report
+        .since
+        .as_ref()
+        .map(|since| format!(" since {}", since))
+        .unwrap_or_default()
+}
+
+fn build_tables(report: &RepositoryReport) -> Vec<Table> {
+    let mut authors = Table::new(&[
+        "AUTHOR",
+        "EMAIL",
+        "COMMITS",
+        "ADDED",
+        "DELETED",
+        "LAST COMMIT",
+    ])
+    .with_title("Commits per author")
+    .align_right(2)
+    .align_right(3)
+    .align_right(4);
+    for author in &report.authors {
+        authors.add_row(vec![
+            author.author.clone(),
+            author.email.clone(),
+            author.commits.to_string(),
+            author.lines_added.to_string(),
+            author.lines_deleted.to_string(),
+            author.last_commit.format(DATE_FORMAT).to_string(),
+        ]);
+    }
+
+    let mut weeks = Table::new(&["WEEK", "AUTHOR", "COMMITS"])
+        .with_title("Commits per week")
+        .align_right(2);
+    for week in &report.weekly_commits {
+        weeks.add_row(vec![
+            week.week.clone(),
+            week.author.clone(),
+            week.commits.to_string(),
+        ]);
+    }
+
+    let mut churn = Table::new(&["FILE", "COMMITS", "ADDED", "DELETED", "CHURN"])
+        .with_title("Churn hotspots")
+        .align_right(1)
+        .align_right(2)
+        .align_right(3)
+        .align_right(4);
+    for file in &report.churn_hotspots {
+        churn.add_row(vec![
+            file.path.clone(),
+            file.commits.to_string(),
+            file.lines_added.to_string(),
+            file.lines_deleted.to_string(),
+            file.churn.to_string(),
+        ]);
+    }
+
+    let mut blobs = Table::new(&["FILE", "SIZE", "BLOB"])
+        .with_title("Largest blobs (all history)")
+        .align_right(1);
+    for blob in &report.largest_blobs {
+        blobs.add_row(vec![
+            blob.path.clone(),
+            format_bytes_to_string(&blob.size_bytes),
+            blob.oid.chars().take(12).collect(),
+        ]);
+    }
+
+    let mut branches = Table::new(&["BRANCH", "LAST COMMIT", "DAYS", "AUTHOR"])
+        .with_title("Stale branches")
+        .align_right(2);
+    for branch in &report.stale_branches {
+        branches.add_row(vec![
+            branch.name.clone(),
+            branch.last_commit.format(DATE_FORMAT).to_string(),
+            branch.days_since_last_commit.to_string(),
+            branch.author.clone(),
+        ]);
+    }
+
+    vec![authors, weeks, churn, blobs, branches]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BlobRecord, FileChurn};
+    use chrono::{DateTime, Utc};
+
+    fn create_report() -> RepositoryReport {
+        RepositoryReport {
+            repository: "/work/toolbox".to_string(),
+            generated_at: DateTime::parse_from_rfc3339("2026-10-16T12:00:00+00:00")
+                .unwrap()
+                .with_timezone(&Utc),
+            since: Some("3 months ago".to_string()),
+            total_commits: 3,
+            authors: vec![],
+            weekly_commits: vec![],
+            churn_hotspots: vec![FileChurn {
+                path: "src/main.rs".to_string(),
+                commits: 2,
+                lines_added: 35,
+                lines_deleted: 15,
+                churn: 50,
+            }],
+            largest_blobs: vec![BlobRecord {
+                path: "assets/logo.png".to_string(),
+                oid: "0123456789abcdef0123".to_string(),
+                size_bytes: 2048,
+            }],
+            stale_branches: vec![],
+        }
+    }
+
+    #[test]
+    fn test_render_markdown_report() {
+        let markdown = render_report(&create_report(), ReportFormat::Markdown).unwrap();
+
+        assert!(markdown.starts_with("# Repository activity: /work/toolbox\n"));
+        assert!(markdown.contains("from 3 commits since 3 months ago."));
+        assert!(markdown.contains("| src/main.rs | 2 | 35 | 15 | 50 |"));
+        assert!(markdown.contains("| assets/logo.png | 2.00 KB | 0123456789ab |"));
+        assert!(markdown.contains("## Stale branches\n\n_(none)_"));
+    }
+
+    #[test]
+    fn test_render_json_report() {
+        let json = render_report(&create_report(), ReportFormat::Json).unwrap();
+
+        assert!(json.contains("\"total_commits\": 3"));
+        assert!(json.contains("\"size_bytes\": 2048"));
+    }
+}
@@ -1,80 +1,191 @@
 use crate::models::GuidArgs;
-use clap::{Arg, Command};
+use crate::sequential_guid::{normalize_prefix, parse_seed};
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgMatches, Command};
 use shared::command_line::cli_builder::CommandExt;
-use shared::constants::general::DASH_LINE;
-
-/// Displays runtime configuration information.
-///
-/// Shows version, silence mode, interval settings, clipboard options, and empty GUID flags.
-pub fn print_runtime_info(args: &GuidArgs) {
-    println!("Guid v{}", env!("CARGO_PKG_VERSION"));
-    println!("{}", DASH_LINE);
-    println!("- Silence: {}", args.silent);
-
-    if let Some(interval) = args.generate_on_interval {
-        println!("- Generate guid every (s): {}", interval);
-    } else {
-        println!("- Copy to clipboard: {}", args.add_to_clipboard);
-        println!("- Empty Guid: {}", args.generate_empty_guid);
+use shared::command_line::tool_cli::{FlagAlias, GlobalArgs, ToolCli};
+
+const FLAG_ALIASES: &[FlagAlias] = &[
+    FlagAlias::deprecated("--silent", "--quiet", "1.1.0"),
+    FlagAlias::deprecated("-s", "-q", "1.1.0"),
+];
+
+impl ToolCli for GuidArgs {
+    const DISPLAY_NAME: &'static str = "Guid";
+
+    /// Supports single/continuous generation, clipboard copying, empty GUIDs, and sequential mode.
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                "Generates GUIDs (uuid-v4) values, including empty.",
+                "This tool can generate a single valid guid or an empty guid and copy this to the clipboard. Alternatively, it can continuously generate valid guids and output them to the terminal.")
+            .arg(Arg::new("copy-to-clipboard")
+                .long("copy-to-clipboard")
+                .short('c')
+                .action(clap::ArgAction::SetTrue)
+                .help("If set, will copy the generated guid to the clipboard. Does not work with continuous generation. (Default: false)"))
+            .arg(Arg::new("empty")
+                .long("empty")
+                .short('e')
+                .action(clap::ArgAction::SetTrue)
+                .help("If set, will generate an empty guid. Does not work in conjunction with continuous generation. (Default: false)"))
+            .arg(Arg::new("continuous-generation")
+                .long("continuous-generation")
+                .short('i')
+                .value_parser(clap::value_parser!(f64))
+                .help("If set with positive value, will continuously generate guids, and print them to the terminal. (Default: -)"))
+            .arg(Arg::new("count")
+                .long("count")
+                .short('n')
+                .value_parser(clap::value_parser!(usize))
+                .help("How many guids to generate, one per line. Does not work with continuous generation. (Default: 1)"))
+            .arg(Arg::new("sequential")
+                .long("sequential")
+                .action(clap::ArgAction::SetTrue)
+                .help("If set, will generate deterministic, increasing guids from --seed. NOT random: use only for test fixtures and index-friendly inserts. (Default: false)"))
+            .arg(Arg::new("seed")
+                .long("seed")
+                .requires("sequential")
+                .help("Seed of the sequential guids. A number, or any text (hashed). Same seed, same guids. (Default: 0)"))
+            .arg(Arg::new("start")
+                .long("start")
+                .requires("sequential")
+                .value_parser(clap::value_parser!(u64))
+                .help("Counter value of the first sequential guid. (Default: 1)"))
+            .arg(Arg::new("prefix")
+                .long("prefix")
+                .short('p')
+                .help("Hex digits (up to 12) that replace the start of every guid, to make test ids easy to recognize. (Default: -)"))
     }
-}
 
-/// Parses command-line arguments into GUID generation configuration.
-///
-/// Supports single/continuous generation, clipboard copying, empty GUIDs, and silent mode.
-///
-/// # Errors
-/// Terminates program if invalid arguments are provided
-pub fn get_cli_arguments() -> GuidArgs {
-    let matches = Command::new(env!("CARGO_PKG_NAME"))
-        .add_basic_metadata(
-            env!("CARGO_PKG_VERSION"),
-            "Generates GUIDs (uuid-v4) values, including empty.",
-            "This tool can generate a single valid guid or an empty guid and copy this to the clipboard. Alternatively, it can continuously generate valid guids and output them to the terminal.")
-        .arg(Arg::new("copy-to-clipboard")
-            .long("copy-to-clipboard")
-            .short('c')
-            .action(clap::ArgAction::SetTrue)
-            .help("If set, will copy the generated guid to the clipboard. Does not work with continuous generation. (Default: false)"))
-        .arg(Arg::new("empty")
-            .long("empty")
-            .short('e')
-            .action(clap::ArgAction::SetTrue)
-            .help("If set, will generate an empty guid. Does not work in conjunction with continuous generation. (Default: false)"))
-        .arg(Arg::new("silent")
-            .long("silent")
-            .short('s')
-            .action(clap::ArgAction::SetTrue)
-            .help("If set, will only print the guid values. (Default: false)"))
-        .arg(Arg::new("continuous-generation")
-            .long("continuous-generation")
-            .short('i')
-            .value_parser(clap::value_parser!(f64))
-            .help("If set with positive value, will continuously generate guids, and print them to the terminal. (Default: -)"))
-        .get_matches();
-
-    GuidArgs {
-        add_to_clipboard: matches.get_flag("copy-to-clipboard"),
-        generate_empty_guid: matches.get_flag("empty"),
-        silent: matches.get_flag("silent"),
-        generate_on_interval: matches.get_one::<f64>("continuous-generation").copied(),
+    /// `--silent`/`-s` became `--quiet`/`-q`, the same flag every tool uses.
+    fn flag_aliases() -> &'static [FlagAlias] {
+        FLAG_ALIASES
     }
-}
 
-/// Validates command-line arguments for compatibility and correctness.
-///
-/// Ensures continuous generation doesn't conflict with clipboard/empty options and interval is positive.
-pub fn validate_cli_arguments(args: &GuidArgs) {
-    if args.generate_on_interval.is_some() && (args.generate_empty_guid || args.add_to_clipboard) {
-        eprintln!("Continuous generation cannot be used with empty guids or copying to clipboard.");
-        std::process::exit(1);
+    fn from_matches(matches: &ArgMatches, global: &GlobalArgs) -> Result<Self> {
+        Ok(GuidArgs {
+            add_to_clipboard: matches.get_flag("copy-to-clipboard"),
+            generate_empty_guid: matches.get_flag("empty"),
+            quiet: global.quiet,
+            generate_on_interval: matches.get_one::<f64>("continuous-generation").copied(),
+            count: matches.get_one::<usize>("count").copied().unwrap_or(1),
+            sequential: matches.get_flag("sequential"),
+            seed: matches
+                .get_one::<String>("seed")
+                .map(|seed| parse_seed(seed))
+                .unwrap_or(0),
+            start: matches.get_one::<u64>("start").copied().unwrap_or(1),
+            prefix: matches
+                .get_one::<String>("prefix")
+                .map(|prefix| prefix.trim().to_ascii_lowercase()),
+        })
+    }
+
+    /// Ensures continuous generation doesn't conflict with clipboard/empty/count options, sequential
+    /// mode isn't combined with empty guids, the prefix is valid, and interval/count are positive.
+    fn validate(&self) -> Result<()> {
+        if self.generate_on_interval.is_some()
+            && (self.generate_empty_guid || self.add_to_clipboard)
+        {
+            return Err(anyhow!(
+                "Continuous generation cannot be used with empty guids or copying to clipboard."
+            ));
+        }
+
+        if self.generate_on_interval.is_some() && self.count != 1 {
+            return Err(anyhow!(
+                "Continuous generation cannot be used with --count."
+            ));
+        }
+
+        if self.sequential && self.generate_empty_guid {
+            return Err(anyhow!(
+                "Sequential generation cannot be used with empty guids."
+            ));
+        }
+
+        if self.count == 0 {
+            return Err(anyhow!("Count must be greater than 0."));
+        }
+
+        if let Some(prefix) = &self.prefix {
+            normalize_prefix(prefix)?;
+        }
+
+        if self
+            .generate_on_interval
+            .is_some_and(|interval| interval <= 0.0)
+        {
+            return Err(anyhow!("Interval must be greater than 0."));
+        }
+
+        Ok(())
     }
 
-    if let Some(interval) = args.generate_on_interval {
-        if interval > 0.0 {
-            return;
+    /// Shows generation mode, interval settings, clipboard options, and empty GUID flags.
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let mut info = Vec::new();
+
+        if self.sequential {
+            info.push((
+                "Mode".to_string(),
+                format!(
+                    "sequential, NOT random (seed: {}, start: {})",
+                    self.seed, self.start
+                ),
+            ));
+        } else {
+            info.push(("Mode".to_string(), "random (uuid-v4)".to_string()));
+        }
+
+        if let Some(prefix) = &self.prefix {
+            info.push(("Prefix".to_string(), prefix.clone()));
         }
-        eprintln!("Interval must be greater than 0.");
-        std::process::exit(1);
+
+        if let Some(interval) = self.generate_on_interval {
+            info.push(("Generate guid every (s)".to_string(), interval.to_string()));
+        } else {
+            info.push(("Count".to_string(), self.count.to_string()));
+            info.push((
+                "Copy to clipboard".to_string(),
+                self.add_to_clipboard.to_string(),
+            ));
+            info.push((
+                "Empty Guid".to_string(),
+                self.generate_empty_guid.to_string(),
+            ));
+        }
+
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    #[test]
+    fn test_silent_is_a_deprecated_alias_of_quiet() {
+        for flag in ["--silent", "-s", "--quiet", "-q"] {
+            let invocation = try_parse_tool_cli_from::<GuidArgs, _, _>(["guid", flag]).unwrap();
+
+            assert!(invocation.global.quiet, "{}", flag);
+            assert!(invocation.args.quiet, "{}", flag);
+        }
+    }
+
+    #[test]
+    fn test_invalid_combinations_are_rejected() {
+        let error = try_parse_tool_cli_from::<GuidArgs, _, _>(["guid", "--sequential", "--empty"])
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "Sequential generation cannot be used with empty guids."
+        );
     }
 }
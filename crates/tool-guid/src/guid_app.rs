@@ -1,4 +1,6 @@
-use anyhow::{Context, Result};
+use crate::models::GuidArgs;
+use crate::sequential_guid::{apply_prefix, SequentialGuidGenerator};
+use anyhow::{anyhow, Context, Result};
 use shared::utils::copy_string_to_clipboard::copy_to_clipboard;
 use shared::utils::new_guid::new_guid;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -8,11 +10,16 @@ use std::time::Duration;
 
 /// Continuously generates GUIDs at a specified interval until interrupted.
 ///
-/// Generates a new GUID every interval second, handles Ctrl+C for graceful shutdown.
+/// Prints the next GUID from `next_guid` every interval second, handles Ctrl+C for graceful
+/// shutdown, and stops early if `next_guid` runs out of values.
 ///
 /// # Errors
 /// Returns error if Ctrl+C handler setup fails
-pub fn continuous_generation(interval: f64, silent: bool) -> Result<()> {
+pub fn continuous_generation(
+    interval: f64,
+    quiet: bool,
+    mut next_guid: impl FnMut() -> Option<String>,
+) -> Result<()> {
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
 
@@ -22,14 +29,16 @@ pub fn continuous_generation(interval: f64, silent: bool) -> Result<()> {
     })
     .context("Error setting Ctrl-C handler")?;
 
-    if !silent {
+    if !quiet {
         println!("Press Ctrl+C to stop...");
     }
 
     let duration = Duration::from_secs_f64(interval);
 
     while running.load(Ordering::SeqCst) {
-        let guid = new_guid();
+        let Some(guid) = next_guid() else {
+            break;
+        };
         print!("{}\r", guid);
 
         // Sleep for the specified interval
@@ -38,7 +47,7 @@ pub fn continuous_generation(interval: f64, silent: bool) -> Result<()> {
 
     println!();
 
-    if !silent {
+    if !quiet {
         println!("Stopping guid generation.");
     }
     Ok(())
@@ -55,12 +64,37 @@ pub fn generate_once(empty_guid: bool) -> String {
     }
 }
 
+/// Creates the GUID source for the selected mode: sequential or random, with the prefix applied.
+pub fn create_guid_source(args: &GuidArgs) -> impl FnMut() -> Option<String> {
+    let mut sequence = args
+        .sequential
+        .then(|| SequentialGuidGenerator::new(args.seed, args.start));
+    let empty_guid = args.generate_empty_guid;
+    let prefix = args.prefix.clone();
+
+    move || {
+        let guid = match sequence.as_mut() {
+            Some(sequence) => sequence.next()?,
+            None => generate_once(empty_guid),
+        };
+
+        match &prefix {
+            Some(prefix) => Some(apply_prefix(&guid, prefix)),
+            None => Some(guid),
+        }
+    }
+}
+
+/// Generates `args.count` GUIDs (fewer, if a sequence runs out of values).
+pub fn generate_guids(args: &GuidArgs) -> Vec<String> {
+    let mut next_guid = create_guid_source(args);
+
+    (0..args.count).map_while(|_| next_guid()).collect()
+}
+
 /// Copies GUID to the system clipboard.
 ///
-/// Terminates the program with an error message if the clipboard operation fails.
-pub fn copy_guid_to_clipboard(guid: String) {
-    if let Err(e) = copy_to_clipboard(&guid) {
-        eprintln!("Error copying to clipboard: {}", e);
-        std::process::exit(1);
-    }
+/// Fails with an error message if the clipboard operation fails.
+pub fn copy_guid_to_clipboard(guid: String) -> Result<()> {
+    copy_to_clipboard(&guid).map_err(|e| anyhow!("Error copying to clipboard: {}", e))
 }
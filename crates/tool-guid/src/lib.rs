@@ -0,0 +1,60 @@
+use crate::guid_app::{
+    continuous_generation, copy_guid_to_clipboard, create_guid_source, generate_guids,
+};
+use crate::models::GuidArgs;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{
+    EXIT_CODE_ERROR, EXIT_CODE_INTERRUPTED_BY_USER, EXIT_CODE_SUCCESS,
+};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod guid_app;
+mod models;
+mod sequential_guid;
+
+/// GUID generator tool.
+///
+/// Parses arguments, validates configuration, and generates GUIDs either once or continuously.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<GuidArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Info);
+
+    invocation.print_runtime_info();
+    let args = invocation.args;
+
+    if let Some(interval) = args.generate_on_interval {
+        if let Err(e) = continuous_generation(interval, args.quiet, create_guid_source(&args)) {
+            error!("Error during continuous generation: {}", e);
+            return EXIT_CODE_ERROR;
+        }
+        return EXIT_CODE_INTERRUPTED_BY_USER;
+    } else {
+        let guid = generate_guids(&args).join("\n");
+
+        print!("{}", guid);
+
+        if args.add_to_clipboard {
+            if let Err(e) = copy_guid_to_clipboard(guid) {
+                eprintln!("{}", e);
+                return EXIT_CODE_ERROR;
+            }
+        }
+    }
+
+    EXIT_CODE_SUCCESS
+}
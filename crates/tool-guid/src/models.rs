@@ -4,6 +4,14 @@
 pub struct GuidArgs {
     pub add_to_clipboard: bool,
     pub generate_empty_guid: bool,
-    pub silent: bool,
+    /// Only print the guid values (`--quiet`).
+    pub quiet: bool,
     pub generate_on_interval: Option<f64>,
+    pub count: usize,
+    /// Deterministic, increasing GUIDs instead of random ones.
+    pub sequential: bool,
+    pub seed: u64,
+    pub start: u64,
+    /// Hex digits that replace the start of every generated GUID.
+    pub prefix: Option<String>,
 }
@@ -0,0 +1,155 @@
+use anyhow::{bail, Result};
+
+/// Longest prefix accepted, in hex digits. Keeps the UUID version digit (13th) untouched.
+pub const MAX_PREFIX_LEN: usize = 12;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Generates deterministic, monotonically increasing GUIDs. **These are not random.**
+///
+/// The seed goes in the first 64 bits and the counter in the last 64 bits, so the same seed and
+/// start always produce the same sequence, and each GUID is greater than the previous one (both
+/// as a string and as a number). Different seeds produce sequences that don't overlap.
+pub struct SequentialGuidGenerator {
+    seed: u64,
+    next: Option<u64>,
+}
+
+impl SequentialGuidGenerator {
+    pub fn new(seed: u64, start: u64) -> Self {
+        Self {
+            seed,
+            next: Some(start),
+        }
+    }
+}
+
+impl Iterator for SequentialGuidGenerator {
+    type Item = String;
+
+    /// Returns the next GUID, or `None` once the counter is exhausted.
+    fn next(&mut self) -> Option<String> {
+        let current = self.next?;
+        self.next = current.checked_add(1);
+
+        Some(format_guid(((self.seed as u128) << 64) | current as u128))
+    }
+}
+
+/// Parses the seed. Numbers are used as-is; any other text is hashed (FNV-1a), so a name like
+/// `orders-tests` can be used as seed and still produce the same sequence on every machine.
+pub fn parse_seed(value: &str) -> u64 {
+    let trimmed = value.trim();
+
+    match trimmed.parse::<u64>() {
+        Ok(number) => number,
+        Err(_) => trimmed.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        }),
+    }
+}
+
+/// Formats 128 bits as a lowercase GUID string (`8-4-4-4-12`).
+pub fn format_guid(value: u128) -> String {
+    let hex = format!("{:032x}", value);
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Validates the prefix and returns it lowercased.
+///
+/// # Errors
+/// Returns an error if the prefix is empty, longer than [`MAX_PREFIX_LEN`], or not hexadecimal.
+pub fn normalize_prefix(prefix: &str) -> Result<String> {
+    let prefix = prefix.trim();
+
+    if prefix.is_empty() {
+        bail!("Prefix cannot be empty.");
+    }
+
+    if prefix.len() > MAX_PREFIX_LEN {
+        bail!(
+            "Prefix must have at most {} hex digits. Got: {}",
+            MAX_PREFIX_LEN,
+            prefix.len()
+        );
+    }
+
+    if let Some(invalid) = prefix.chars().find(|c| !c.is_ascii_hexdigit()) {
+        bail!(
+            "Prefix must only have hex digits (0-9, a-f), so the GUID stays valid. Invalid character: '{}'",
+            invalid
+        );
+    }
+
+    Ok(prefix.to_ascii_lowercase())
+}
+
+/// Replaces the first hex digits of the GUID with the (already normalized) prefix, skipping dashes.
+pub fn apply_prefix(guid: &str, prefix: &str) -> String {
+    let mut prefix_chars = prefix.chars();
+
+    guid.chars()
+        .map(|c| {
+            if c == '-' {
+                return c;
+            }
+            prefix_chars.next().unwrap_or(c)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_is_deterministic_and_increasing() {
+        let first: Vec<String> = SequentialGuidGenerator::new(42, 1).take(3).collect();
+        let second: Vec<String> = SequentialGuidGenerator::new(42, 1).take(3).collect();
+
+        assert_eq!(first, second);
+        assert_eq!(first[0], "00000000-0000-002a-0000-000000000001");
+        assert_eq!(first[2], "00000000-0000-002a-0000-000000000003");
+        assert!(first.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_sequence_stops_when_counter_is_exhausted() {
+        let guids: Vec<String> = SequentialGuidGenerator::new(0, u64::MAX).take(5).collect();
+
+        assert_eq!(guids, vec!["00000000-0000-0000-ffff-ffffffffffff"]);
+    }
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(parse_seed("42"), 42);
+        assert_eq!(parse_seed("orders-tests"), parse_seed("orders-tests"));
+        assert_ne!(parse_seed("orders-tests"), parse_seed("users-tests"));
+    }
+
+    #[test]
+    fn test_apply_prefix_skips_dashes() {
+        let prefix = normalize_prefix("DEADBEEFCAFE").unwrap();
+
+        assert_eq!(
+            apply_prefix("00000000-0000-4000-8000-000000000001", &prefix),
+            "deadbeef-cafe-4000-8000-000000000001"
+        );
+    }
+
+    #[test]
+    fn test_normalize_prefix_rejects_invalid_values() {
+        assert!(normalize_prefix("").is_err());
+        assert!(normalize_prefix("test").is_err());
+        assert!(normalize_prefix("0123456789abc").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::models::{HashAlgorithm, HashxArgs, STDIN_NAME};
+use anyhow::{anyhow, bail, Result};
+use clap::{Arg, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{FlagAlias, GlobalArgs, GlobalFlag, ToolCli};
+use std::path::PathBuf;
+
+const FLAG_ALIASES: &[FlagAlias] = &[FlagAlias::alias("-o", "--output")];
+
+impl ToolCli for HashxArgs {
+    const DISPLAY_NAME: &'static str = "Hashx";
+
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Computes checksums of files, folders (hashed recursively, in parallel), text, or \
+                stdin, printed in the format of sha256sum and friends, so they can be saved as a \
+                manifest. With --check, verifies the files listed in a manifest.",
+            )
+            .arg(
+                Arg::new("inputs")
+                    .value_name("PATH")
+                    .num_args(1..)
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Files or folders to hash. '-' is stdin. (Default: stdin)"),
+            )
+            .arg(
+                Arg::new("algorithm")
+                    .long("algorithm")
+                    .short('a')
+                    .value_name("ALGORITHM")
+                    .help("md5, sha1, sha256, sha512, or blake3. (Default: sha256, or the manifest's algorithm with --check)"),
+            )
+            .arg(
+                Arg::new("text")
+                    .long("text")
+                    .short('t')
+                    .value_name("TEXT")
+                    .conflicts_with_all(["inputs", "check"])
+                    .help("Hashes this text (as UTF-8, without a line break) instead of files"),
+            )
+            .arg(
+                Arg::new("check")
+                    .long("check")
+                    .short('c')
+                    .value_name("MANIFEST")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .conflicts_with("inputs")
+                    .help("Verifies the files listed in this manifest. Relative paths are relative to the manifest's folder"),
+            )
+            .arg(
+                Arg::new("jobs")
+                    .long("jobs")
+                    .short('j')
+                    .value_name("N")
+                    .value_parser(clap::builder::ValueParser::new(parse_jobs))
+                    .help("Number of files hashed in parallel. (Default: number of CPUs)"),
+            )
+    }
+
+    fn global_flags() -> &'static [GlobalFlag] {
+        &[GlobalFlag::Quiet, GlobalFlag::NoColor, GlobalFlag::Output]
+    }
+
+    fn flag_aliases() -> &'static [FlagAlias] {
+        FLAG_ALIASES
+    }
+
+    fn from_matches(matches: &ArgMatches, global: &GlobalArgs) -> Result<Self> {
+        let algorithm = match matches.get_one::<String>("algorithm") {
+            Some(name) => Some(HashAlgorithm::from_name(name).ok_or_else(|| {
+                anyhow!(
+                    "Unknown algorithm: '{}'. Use one of: {}",
+                    name,
+                    HashAlgorithm::ALL
+                        .map(|algorithm| algorithm.name())
+                        .join(", ")
+                )
+            })?),
+            None => None,
+        };
+
+        Ok(HashxArgs {
+            inputs: matches
+                .get_many::<PathBuf>("inputs")
+                .unwrap_or_default()
+                .cloned()
+                .collect(),
+            text: matches.get_one::<String>("text").cloned(),
+            check: matches.get_one::<PathBuf>("check").cloned(),
+            algorithm,
+            jobs: matches
+                .get_one::<usize>("jobs")
+                .copied()
+                .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get())),
+            output: global.output.clone(),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(manifest) = &self.check {
+            if !manifest.is_file() {
+                bail!("Manifest '{}' does not exist", manifest.display());
+            }
+
+            if self.output.is_some() {
+                bail!("--output can't be used with --check");
+            }
+        }
+
+        if let Some(missing) = self
+            .inputs
+            .iter()
+            .find(|input| input.as_os_str() != STDIN_NAME && !input.exists())
+        {
+            bail!("'{}' does not exist", missing.display());
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let algorithm = self
+            .algorithm
+            .map(|algorithm| algorithm.name().to_string())
+            .unwrap_or_else(|| match self.check {
+                Some(_) => "from the manifest".to_string(),
+                None => HashAlgorithm::Sha256.name().to_string(),
+            });
+
+        let mut info = vec![("Algorithm".to_string(), algorithm)];
+
+        match &self.check {
+            Some(manifest) => info.push(("Check".to_string(), manifest.display().to_string())),
+            None => info.push((
+                "Inputs".to_string(),
+                if self.text.is_some() {
+                    "text".to_string()
+                } else if self.inputs.is_empty() {
+                    "stdin".to_string()
+                } else {
+                    self.inputs
+                        .iter()
+                        .map(|input| input.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+            )),
+        }
+
+        info.push(("Parallel jobs".to_string(), self.jobs.to_string()));
+
+        if let Some(output) = &self.output {
+            info.push(("Output".to_string(), output.display().to_string()));
+        }
+
+        info
+    }
+}
+
+fn parse_jobs(value: &str) -> Result<usize, String> {
+    match value.trim().parse::<usize>() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(format!(
+            "Invalid number of jobs: '{}'. Expected a number greater than 0.",
+            value.trim()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    fn parse(argv: &[&str]) -> Result<HashxArgs> {
+        try_parse_tool_cli_from::<HashxArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let defaults = parse(&["hashx"]).unwrap();
+        assert!(defaults.inputs.is_empty());
+        assert_eq!(defaults.algorithm, None);
+
+        let text = parse(&["hashx", "-t", "abc", "-a", "SHA-512"]).unwrap();
+        assert_eq!(text.text.as_deref(), Some("abc"));
+        assert_eq!(text.algorithm, Some(HashAlgorithm::Sha512));
+
+        let stdin = parse(&["hashx", "-", "-j", "2", "-o", "SHA256SUMS"]).unwrap();
+        assert_eq!(stdin.inputs, vec![PathBuf::from("-")]);
+        assert_eq!(stdin.jobs, 2);
+        assert_eq!(stdin.output, Some(PathBuf::from("SHA256SUMS")));
+
+        assert!(parse(&["hashx", "-a", "crc32"]).is_err());
+        assert!(parse(&["hashx", "-j", "0"]).is_err());
+        assert!(parse(&["hashx", "does-not-exist.bin"]).is_err());
+        assert!(parse(&["hashx", "-t", "abc", "-"]).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use crate::hashing::{hash_bytes, hash_file, hash_reader};
+use crate::manifest::{format_line, parse_manifest};
+use crate::models::{CheckStatus, HashAlgorithm, HashxArgs, ManifestEntry, STDIN_NAME};
+use anyhow::{anyhow, Context, Result};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::ThreadPool;
+use shared::system::folder_walkthrough::list_all_files_recursively;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A file (or stdin) to hash, as listed in the output.
+#[derive(Debug, Clone, PartialEq)]
+enum HashInput {
+    Stdin,
+    File(PathBuf),
+}
+
+pub fn run(args: &HashxArgs) -> Result<()> {
+    match &args.check {
+        Some(manifest) => verify_manifest(manifest, args),
+        None => print_checksums(args),
+    }
+}
+
+/// Hashes the inputs and prints one manifest line per file (or writes them to `--output`).
+/// Files that can't be read are reported on stderr, and make the run fail at the end.
+fn print_checksums(args: &HashxArgs) -> Result<()> {
+    let algorithm = args.algorithm.unwrap_or(HashAlgorithm::Sha256);
+
+    if let Some(text) = &args.text {
+        let line = format_line(&hash_bytes(text.as_bytes(), algorithm), STDIN_NAME);
+        return write_lines(&[line], args.output.as_deref());
+    }
+
+    let inputs = expand_inputs(&args.inputs, args.output.as_deref());
+    let manifest_dir = args
+        .output
+        .as_ref()
+        .and_then(|output| output.parent())
+        .and_then(absolute_dir);
+    let pool = build_pool(args.jobs)?;
+    let results: Vec<(String, Result<String>)> = pool.install(|| {
+        inputs
+            .par_iter()
+            .map(|input| match input {
+                HashInput::Stdin => (
+                    STDIN_NAME.to_string(),
+                    hash_reader(std::io::stdin().lock(), algorithm).context("Failed to read stdin"),
+                ),
+                HashInput::File(path) => (
+                    manifest_name(path, manifest_dir.as_deref()),
+                    hash_file(path, algorithm),
+                ),
+            })
+            .collect()
+    });
+
+    let mut lines = Vec::with_capacity(results.len());
+    let mut error_count = 0;
+    for (name, result) in results {
+        match result {
+            Ok(checksum) => lines.push(format_line(&checksum, &name)),
+            Err(e) => {
+                eprintln!("{}: {:#}", env!("CARGO_PKG_NAME"), e);
+                error_count += 1;
+            }
+        }
+    }
+
+    write_lines(&lines, args.output.as_deref())?;
+
+    if error_count > 0 {
+        return Err(anyhow!("{} file(s) could not be read", error_count));
+    }
+
+    Ok(())
+}
+
+/// Files to hash, in order: files as informed, then the files of each folder, sorted. The output
+/// file is skipped, since it's being written.
+fn expand_inputs(inputs: &[PathBuf], output: Option<&Path>) -> Vec<HashInput> {
+    if inputs.is_empty() {
+        return vec![HashInput::Stdin];
+    }
+
+    let output = output.and_then(|path| path.canonicalize().ok());
+    let is_output = |path: &Path| {
+        output
+            .as_ref()
+            .is_some_and(|output| path.canonicalize().ok().as_ref() == Some(output))
+    };
+
+    let mut expanded = Vec::new();
+    for input in inputs {
+        if input.as_os_str() == STDIN_NAME {
+            expanded.push(HashInput::Stdin);
+        } else if input.is_dir() {
+            let mut files: Vec<PathBuf> = list_all_files_recursively(input)
+                .filter(|file| !is_output(file))
+                .collect();
+            files.sort();
+            expanded.extend(files.into_iter().map(HashInput::File));
+        } else if !is_output(input) {
+            expanded.push(HashInput::File(input.clone()));
+        }
+    }
+
+    expanded
+}
+
+fn write_lines(lines: &[String], output: Option<&Path>) -> Result<()> {
+    let content: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+
+    match output {
+        Some(path) => {
+            fs::write(path, content).context(format!(
+                "Failed to write the checksums to {}",
+                path.display()
+            ))?;
+            println!("{} checksums written to {}", lines.len(), path.display());
+        }
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(content.as_bytes())?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Hashes every file listed in the manifest, and prints `<file>: OK`, `FAILED`, or `MISSING`.
+/// Fails when any of them doesn't match.
+fn verify_manifest(manifest: &Path, args: &HashxArgs) -> Result<()> {
+    let content = fs::read_to_string(manifest).context(format!(
+        "Failed to read the manifest {}",
+        manifest.display()
+    ))?;
+    let entries = parse_manifest(&content)?;
+    if entries.is_empty() {
+        return Err(anyhow!("No checksums in {}", manifest.display()));
+    }
+
+    let base_dir = manifest.parent().unwrap_or(Path::new(""));
+    let manifest_algorithm = args
+        .algorithm
+        .or_else(|| HashAlgorithm::from_manifest_name(manifest));
+
+    let pool = build_pool(args.jobs)?;
+    let statuses: Vec<Result<CheckStatus>> = pool.install(|| {
+        entries
+            .par_iter()
+            .map(|entry| check_entry(entry, base_dir, manifest_algorithm))
+            .collect()
+    });
+
+    let mut failed_count = 0;
+    let mut missing_count = 0;
+    for (entry, status) in entries.iter().zip(statuses) {
+        let status = status?;
+        let name = display_path(&entry.path);
+        match status {
+            CheckStatus::Ok => println!("{}: OK", name),
+            CheckStatus::Failed => {
+                println!("{}: FAILED", name);
+                failed_count += 1;
+            }
+            CheckStatus::Missing => {
+                println!("{}: MISSING", name);
+                missing_count += 1;
+            }
+            CheckStatus::Unreadable(error) => {
+                println!("{}: FAILED ({})", name, error);
+                failed_count += 1;
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} OK, {} failed, {} missing.",
+        entries.len() - failed_count - missing_count,
+        failed_count,
+        missing_count
+    );
+
+    if failed_count + missing_count > 0 {
+        return Err(anyhow!(
+            "{} of {} files did not match the manifest",
+            failed_count + missing_count,
+            entries.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// The algorithm is the one named by the line (BSD format), then `--algorithm` or the manifest's
+/// name, then the one the length of the checksum points to.
+fn check_entry(
+    entry: &ManifestEntry,
+    base_dir: &Path,
+    manifest_algorithm: Option<HashAlgorithm>,
+) -> Result<CheckStatus> {
+    let algorithm = entry
+        .algorithm
+        .or(manifest_algorithm)
+        .or_else(|| HashAlgorithm::from_hex_length(entry.expected.len()))
+        .ok_or_else(|| {
+            anyhow!(
+                "Can't tell the algorithm of the checksum of {} ({} hex digits). Use --algorithm",
+                entry.path.display(),
+                entry.expected.len()
+            )
+        })?;
+
+    let path = base_dir.join(&entry.path);
+    if !path.is_file() {
+        return Ok(CheckStatus::Missing);
+    }
+
+    Ok(match hash_file(&path, algorithm) {
+        Ok(checksum) if checksum == entry.expected => CheckStatus::Ok,
+        Ok(_) => CheckStatus::Failed,
+        Err(e) => CheckStatus::Unreadable(format!("{:#}", e)),
+    })
+}
+
+fn build_pool(jobs: usize) -> Result<ThreadPool> {
+    Ok(rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?)
+}
+
+/// The path of the file relative to the folder of the manifest being written (`--output`), so
+/// `--check` finds the files from any folder. Files outside of it keep the path informed.
+fn manifest_name(path: &Path, manifest_dir: Option<&Path>) -> String {
+    let relative = manifest_dir.and_then(|dir| {
+        path.canonicalize()
+            .ok()
+            .and_then(|file| file.strip_prefix(dir).ok().map(Path::to_path_buf))
+    });
+
+    display_path(relative.as_deref().unwrap_or(path))
+}
+
+/// `dir` as an absolute path. An empty parent (`-o SHA256SUMS`) is the current folder.
+fn absolute_dir(dir: &Path) -> Option<PathBuf> {
+    if dir.as_os_str().is_empty() {
+        return std::env::current_dir().ok()?.canonicalize().ok();
+    }
+
+    dir.canonicalize().ok()
+}
+
+/// Paths with `/`, so manifests made on Windows can be checked anywhere (and the other way around).
+fn display_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn args(inputs: Vec<PathBuf>) -> HashxArgs {
+        HashxArgs {
+            inputs,
+            text: None,
+            check: None,
+            algorithm: None,
+            jobs: 2,
+            output: None,
+        }
+    }
+
+    #[test]
+    fn test_expand_inputs_sorts_folders_and_skips_the_output() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("b.txt"), "b").unwrap();
+        fs::write(dir.path().join("sub").join("a.txt"), "a").unwrap();
+        fs::write(dir.path().join("SHA256SUMS"), "").unwrap();
+
+        let output = dir.path().join("SHA256SUMS");
+        let inputs = expand_inputs(
+            &[dir.path().to_path_buf(), PathBuf::from("-")],
+            Some(&output),
+        );
+
+        assert_eq!(
+            inputs,
+            vec![
+                HashInput::File(dir.path().join("b.txt")),
+                HashInput::File(dir.path().join("sub").join("a.txt")),
+                HashInput::Stdin,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manifest_name_is_relative_to_the_manifest() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        let file = dir.path().join("sub").join("a.txt");
+        fs::write(&file, "a").unwrap();
+        let manifest_dir = dir.path().canonicalize().unwrap();
+
+        assert_eq!(manifest_name(&file, Some(&manifest_dir)), "sub/a.txt");
+        assert_eq!(
+            manifest_name(&file, Some(&manifest_dir.join("sub"))),
+            "a.txt"
+        );
+        assert_eq!(manifest_name(&file, None), display_path(&file));
+    }
+
+    #[test]
+    fn test_written_manifest_verifies_and_detects_changes() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "abc").unwrap();
+        fs::write(dir.path().join("b.txt"), "def").unwrap();
+        let manifest = dir.path().join("files.md5");
+        fs::write(
+            &manifest,
+            format!(
+                "{}\n{}\n",
+                format_line(&hash_bytes(b"abc", HashAlgorithm::Md5), "a.txt"),
+                format_line(&hash_bytes(b"def", HashAlgorithm::Md5), "b.txt")
+            ),
+        )
+        .unwrap();
+
+        let mut check = args(Vec::new());
+        check.check = Some(manifest.clone());
+        assert!(verify_manifest(&manifest, &check).is_ok());
+
+        fs::write(dir.path().join("b.txt"), "changed").unwrap();
+        assert!(verify_manifest(&manifest, &check).is_err());
+
+        let entry = parse_manifest(&fs::read_to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(
+            check_entry(&entry[1], dir.path(), Some(HashAlgorithm::Md5)).unwrap(),
+            CheckStatus::Failed
+        );
+        fs::remove_file(dir.path().join("a.txt")).unwrap();
+        assert_eq!(
+            check_entry(&entry[0], dir.path(), Some(HashAlgorithm::Md5)).unwrap(),
+            CheckStatus::Missing
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use crate::models::HashxArgs;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod hashing;
+mod hashx_app;
+mod manifest;
+mod models;
+
+/// Checksum tool.
+///
+/// Hashes the inputs (or verifies a manifest, with `--check`) and prints the results.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<HashxArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    // Checksums printed to stdout are a manifest, meant to be piped or saved, so the header would
+    // get in the way.
+    let args = &invocation.args;
+    if args.check.is_some() || args.output.is_some() {
+        invocation.print_runtime_info();
+    }
+
+    if let Err(e) = hashx_app::run(args) {
+        eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+        error!("{}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
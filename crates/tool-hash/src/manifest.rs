@@ -0,0 +1,101 @@
+use crate::models::{HashAlgorithm, ManifestEntry};
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+/// Parses a manifest, in the format of `sha256sum` and friends (`<checksum>  <file>`, or
+/// `<checksum> *<file>` for binary mode), or in the BSD format (`SHA256 (<file>) = <checksum>`).
+/// Blank lines and lines starting with `#` are skipped.
+pub fn parse_manifest(content: &str) -> Result<Vec<ManifestEntry>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            parse_line(line.trim_end_matches('\r')).ok_or_else(|| {
+                anyhow!(
+                    "Invalid manifest line {}: '{}'. Expected '<checksum>  <file>' or 'ALGORITHM (<file>) = <checksum>'",
+                    index + 1,
+                    line.trim()
+                )
+            })
+        })
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<ManifestEntry> {
+    parse_bsd_line(line).or_else(|| parse_gnu_line(line))
+}
+
+/// `<checksum>  <file>` or `<checksum> *<file>`.
+fn parse_gnu_line(line: &str) -> Option<ManifestEntry> {
+    let (checksum, rest) = line.trim_start().split_once(' ')?;
+    let path = rest
+        .strip_prefix(' ')
+        .or_else(|| rest.strip_prefix('*'))
+        .unwrap_or(rest);
+
+    if !is_hex(checksum) || path.is_empty() {
+        return None;
+    }
+
+    Some(ManifestEntry {
+        algorithm: None,
+        expected: checksum.to_lowercase(),
+        path: PathBuf::from(path),
+    })
+}
+
+/// `SHA256 (<file>) = <checksum>`.
+fn parse_bsd_line(line: &str) -> Option<ManifestEntry> {
+    let (tag, rest) = line.trim_start().split_once(" (")?;
+    let (path, checksum) = rest.rsplit_once(") = ")?;
+    let algorithm = HashAlgorithm::from_name(tag)?;
+
+    if !is_hex(checksum.trim()) || path.is_empty() {
+        return None;
+    }
+
+    Some(ManifestEntry {
+        algorithm: Some(algorithm),
+        expected: checksum.trim().to_lowercase(),
+        path: PathBuf::from(path),
+    })
+}
+
+fn is_hex(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// A manifest line, in the format `sha256sum -c` (and `hashx --check`) reads.
+pub fn format_line(checksum: &str, name: &str) -> String {
+    format!("{}  {}", checksum, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let sha256 = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        let content = format!(
+            "# Release files\n\
+             {}  docs/read me.txt\n\
+             {} *setup.exe\r\n\
+             \n\
+             MD5 (notes (old).txt) = 900150983CD24FB0D6963F7D28E17F72\n",
+            sha256, sha256
+        );
+
+        let entries = parse_manifest(&content).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].path, PathBuf::from("docs/read me.txt"));
+        assert_eq!(entries[0].algorithm, None);
+        assert_eq!(entries[1].path, PathBuf::from("setup.exe"));
+        assert_eq!(entries[2].algorithm, Some(HashAlgorithm::Md5));
+        assert_eq!(entries[2].path, PathBuf::from("notes (old).txt"));
+        assert_eq!(entries[2].expected, "900150983cd24fb0d6963f7d28e17f72");
+
+        assert!(parse_manifest("not a checksum line").is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+/// Name used for stdin, in the output and in the inputs.
+pub const STDIN_NAME: &str = "-";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashxArgs {
+    /// Files and folders (hashed recursively). `-` is stdin. Empty means stdin.
+    pub inputs: Vec<PathBuf>,
+    /// Text to hash, instead of the inputs.
+    pub text: Option<String>,
+    /// Manifest to verify, instead of hashing the inputs.
+    pub check: Option<PathBuf>,
+    /// `None` uses SHA-256 or, when verifying, what the manifest tells.
+    pub algorithm: Option<HashAlgorithm>,
+    /// Number of files hashed in parallel.
+    pub jobs: usize,
+    /// Writes the checksums (a manifest) here, instead of stdout.
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 5] = [
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Blake3,
+    ];
+
+    /// Parses the name, in any case, with or without a dash (`sha256`, `SHA-256`). `b3` is BLAKE3,
+    /// as in `b3sum`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized = name.trim().to_lowercase().replace('-', "");
+        if normalized == "b3" {
+            return Some(HashAlgorithm::Blake3);
+        }
+
+        HashAlgorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == normalized)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// The algorithm of a manifest named after it: `SHA256SUMS`, `files.sha256sum`,
+    /// `release.sha512`, `image.md5`, `files.b3sum`, ...
+    pub fn from_manifest_name(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        let stem = name
+            .rsplit('.')
+            .next()
+            .unwrap_or(&name)
+            .trim_end_matches("sums")
+            .trim_end_matches("sum");
+
+        HashAlgorithm::from_name(stem)
+    }
+
+    /// The algorithm that makes checksums of this many hex digits. 64 digits are SHA-256 (BLAKE3
+    /// has the same length, so it has to be informed).
+    pub fn from_hex_length(length: usize) -> Option<Self> {
+        match length {
+            32 => Some(HashAlgorithm::Md5),
+            40 => Some(HashAlgorithm::Sha1),
+            64 => Some(HashAlgorithm::Sha256),
+            128 => Some(HashAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+}
+
+/// A line of a manifest: the checksum a file should have.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestEntry {
+    /// Set by BSD-style lines, which name the algorithm.
+    pub algorithm: Option<HashAlgorithm>,
+    /// Lowercase hex.
+    pub expected: String,
+    pub path: PathBuf,
+}
+
+/// Result of verifying a manifest entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckStatus {
+    Ok,
+    Failed,
+    Missing,
+    /// The file exists, but couldn't be read.
+    Unreadable(String),
+}
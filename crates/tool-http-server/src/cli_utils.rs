@@ -1,9 +1,12 @@
-use crate::models::ServerArgs;
+use crate::models::{ServerArgs, UploadRoute, WsMode};
 use clap::{Arg, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
 use std::net::IpAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 
 pub fn print_runtime_info(args: &ServerArgs) {
     println!("Simple HTTP Server v{}", env!("CARGO_PKG_VERSION"));
@@ -11,9 +14,152 @@ pub fn print_runtime_info(args: &ServerArgs) {
     println!("- Root directory: {}", args.root_path.display());
     println!("- Port: {}", args.port);
     println!("- Serve hidden files: {}", args.serve_hidden);
+
+    if let Some(ws_mode) = args.ws_mode {
+        println!("- WebSocket: /ws ({})", ws_mode.label());
+        println!("  - Clients page: /ws/clients");
+    }
+
+    if args.spa {
+        println!("- SPA fallback: index.html");
+    }
+
+    for (code, page) in &args.error_pages {
+        println!("- Error page {}: {}", code, page.display());
+    }
+
+    if let Some(upload) = &args.upload {
+        println!("- Uploads: {} -> {}", upload.route, upload.dir.display());
+    }
+
+    if let Some(bytes_per_second) = args.throttle {
+        println!("- Throttle: {} bytes/s per connection", bytes_per_second);
+    }
+
+    if let Some(latency) = args.latency {
+        println!("- Latency: {}ms", latency.as_millis());
+    }
+}
+
+/// Parses an `--error-page` mapping, like `404=404.html`.
+fn parse_error_page(value: &str) -> Result<(u16, PathBuf), String> {
+    let (code, file) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected CODE=FILE (e.g.: 404=404.html), got '{}'", value))?;
+
+    let code = code
+        .trim()
+        .parse::<u16>()
+        .ok()
+        .filter(|code| (400..=599).contains(code))
+        .ok_or_else(|| format!("'{}' is not an error status code (400-599)", code))?;
+
+    let file = file.trim();
+    if file.is_empty() {
+        return Err(format!("missing the file of the error page {}", code));
+    }
+
+    Ok((code, PathBuf::from(file)))
+}
+
+/// Parses a `--throttle` rate into bytes per second. Rates are in bits (`bps`, `kbps`, `mbps`,
+/// `gbps`) or bytes (`B/s`, `KB/s`, `MB/s`, `GB/s`) per second, with decimal multiples
+/// (`1kbps` is 1000 bits per second).
+fn parse_throttle(value: &str) -> Result<u64, String> {
+    let normalized = value.trim().to_lowercase();
+    let (unit_start, _) = normalized
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_digit() && *c != '.')
+        .ok_or_else(|| {
+            format!(
+                "missing the unit of '{}' (e.g.: 512kbps, 2mbps, 100KB/s)",
+                value
+            )
+        })?;
+    let (number, unit) = normalized.split_at(unit_start);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("'{}' is not a valid rate", value))?;
+
+    let bits_per_unit = match unit.trim() {
+        "bps" => 1.0,
+        "kbps" => 1e3,
+        "mbps" => 1e6,
+        "gbps" => 1e9,
+        "b/s" => 8.0,
+        "kb/s" => 8e3,
+        "mb/s" => 8e6,
+        "gb/s" => 8e9,
+        _ => {
+            return Err(format!(
+                "unknown unit in '{}'. Use bps, kbps, mbps, gbps, B/s, KB/s, MB/s, or GB/s",
+                value
+            ))
+        }
+    };
+
+    let bytes_per_second = (number * bits_per_unit / 8.0) as u64;
+    if bytes_per_second == 0 {
+        return Err(format!("'{}' is less than 1 byte per second", value));
+    }
+
+    Ok(bytes_per_second)
+}
+
+/// Parses a `--latency` delay: milliseconds (`200ms`, or just `200`) or seconds (`1.5s`).
+fn parse_latency(value: &str) -> Result<Duration, String> {
+    let normalized = value.trim().to_lowercase();
+    let (number, millis_per_unit) = if let Some(number) = normalized.strip_suffix("ms") {
+        (number, 1.0)
+    } else if let Some(number) = normalized.strip_suffix('s') {
+        (number, 1000.0)
+    } else {
+        (normalized.as_str(), 1.0)
+    };
+
+    let millis = number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && *number >= 0.0)
+        .map(|number| number * millis_per_unit)
+        .ok_or_else(|| format!("'{}' is not a valid latency (e.g.: 200ms, 1.5s)", value))?;
+
+    Ok(Duration::from_micros((millis * 1000.0) as u64))
+}
+
+/// Parses an `--upload` mapping, like `/upload=./received`.
+fn parse_upload_route(value: &str) -> Result<UploadRoute, String> {
+    let (route, dir) = value.split_once('=').ok_or_else(|| {
+        format!(
+            "expected ROUTE=DIR (e.g.: /upload=./received), got '{}'",
+            value
+        )
+    })?;
+
+    let route = route.trim().trim_end_matches('/');
+    if !route.starts_with('/') {
+        return Err(format!(
+            "the upload route must start with '/' and can't be the root, got '{}'",
+            value
+        ));
+    }
+
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err(format!("missing the folder of the upload route {}", route));
+    }
+
+    Ok(UploadRoute {
+        route: route.to_string(),
+        dir: PathBuf::from(dir),
+    })
 }
 
-pub fn get_cli_arguments() -> ServerArgs {
+/// Parses the arguments and checks that the folders and files they point to exist. Fails with
+/// the message to show when they don't.
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<ServerArgs, String> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -51,7 +197,56 @@ pub fn get_cli_arguments() -> ServerArgs {
                 .help("Serve hidden files and directories (names starting with '.')")
                 .action(clap::ArgAction::SetTrue),
         )
-        .get_matches();
+        .arg(
+            Arg::new("ws")
+                .long("ws")
+                .help("Enable the /ws WebSocket endpoint, and the /ws/clients page listing connected clients")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ws-mode")
+                .long("ws-mode")
+                .value_name("MODE")
+                .help("What /ws does with received messages: echo (back to the sender) or broadcast (to every other client). (Default: echo)")
+                .requires("ws")
+                .value_parser(["echo", "broadcast"]),
+        )
+        .arg(
+            Arg::new("spa")
+                .long("spa")
+                .help("Serve the root index.html for unknown paths without a file extension, so client-side routers (React, Vue, etc.) can handle them")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("error-page")
+                .long("error-page")
+                .value_name("CODE=FILE")
+                .help("Serve FILE (relative to the web root) as the body of CODE responses, e.g.: 404=404.html. Can be repeated")
+                .value_parser(parse_error_page)
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("upload")
+                .long("upload")
+                .value_name("ROUTE=DIR")
+                .help("Receive multipart file uploads (POST) at ROUTE, saving them in DIR, e.g.: /upload=./received. GET ROUTE shows an upload form")
+                .value_parser(parse_upload_route),
+        )
+        .arg(
+            Arg::new("throttle")
+                .long("throttle")
+                .value_name("RATE")
+                .help("Limit each connection to RATE, to simulate slow networks, e.g.: 512kbps, 2mbps, 100KB/s")
+                .value_parser(parse_throttle),
+        )
+        .arg(
+            Arg::new("latency")
+                .long("latency")
+                .value_name("DELAY")
+                .help("Delay every request by DELAY, to simulate slow networks, e.g.: 200ms, 1.5s")
+                .value_parser(parse_latency),
+        )
+        .get_matches_from(argv);
 
     let root_path = matches
         .get_one::<String>("path")
@@ -68,29 +263,125 @@ pub fn get_cli_arguments() -> ServerArgs {
 
     let serve_hidden = matches.get_flag("serve-hidden");
 
+    let ws_mode = matches.get_flag("ws").then(|| {
+        matches
+            .get_one::<String>("ws-mode")
+            .and_then(|mode| WsMode::from_str_value(mode))
+            .unwrap_or(WsMode::Echo)
+    });
+
+    let spa = matches.get_flag("spa");
+
+    let error_pages: BTreeMap<u16, PathBuf> = matches
+        .get_many::<(u16, PathBuf)>("error-page")
+        .unwrap_or_default()
+        .map(|(code, file)| (*code, root_path.join(file)))
+        .collect();
+
+    let upload = matches.get_one::<UploadRoute>("upload").cloned();
+
+    let throttle = matches.get_one::<u64>("throttle").copied();
+
+    let latency = matches
+        .get_one::<Duration>("latency")
+        .copied()
+        .filter(|latency| !latency.is_zero());
+
     let config = ServerArgs {
         root_path,
         port,
         host,
         serve_hidden,
+        ws_mode,
+        spa,
+        error_pages,
+        upload,
+        throttle,
+        latency,
     };
 
     // Validate root path exists
     if !config.root_path.exists() {
-        eprintln!(
-            "Error: Path '{}' does not exist",
+        return Err(format!(
+            "Path '{}' does not exist",
             config.root_path.display()
-        );
-        std::process::exit(1);
+        ));
     }
 
     if !config.root_path.is_dir() {
-        eprintln!(
-            "Error: Path '{}' is not a directory",
+        return Err(format!(
+            "Path '{}' is not a directory",
             config.root_path.display()
+        ));
+    }
+
+    for (code, page) in &config.error_pages {
+        if !page.is_file() {
+            return Err(format!(
+                "Error page {} '{}' does not exist",
+                code,
+                page.display()
+            ));
+        }
+    }
+
+    if let Some(upload) = &config.upload {
+        if let Err(e) = std::fs::create_dir_all(&upload.dir) {
+            return Err(format!(
+                "Unable to create the upload folder '{}': {}",
+                upload.dir.display(),
+                e
+            ));
+        }
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_page() {
+        assert_eq!(
+            parse_error_page("404=errors/404.html").unwrap(),
+            (404, PathBuf::from("errors/404.html"))
         );
-        std::process::exit(1);
+        assert!(parse_error_page("404").is_err());
+        assert!(parse_error_page("200=ok.html").is_err());
+        assert!(parse_error_page("abc=404.html").is_err());
+        assert!(parse_error_page("500=").is_err());
     }
 
-    config
+    #[test]
+    fn test_parse_upload_route() {
+        let upload = parse_upload_route("/files/upload/=./received").unwrap();
+        assert_eq!(upload.route, "/files/upload");
+        assert_eq!(upload.dir, PathBuf::from("./received"));
+
+        assert!(parse_upload_route("/=./received").is_err());
+        assert!(parse_upload_route("upload=./received").is_err());
+        assert!(parse_upload_route("/upload=").is_err());
+        assert!(parse_upload_route("/upload").is_err());
+    }
+
+    #[test]
+    fn test_parse_throttle() {
+        assert_eq!(parse_throttle("512kbps").unwrap(), 64_000);
+        assert_eq!(parse_throttle("1.5Mbps").unwrap(), 187_500);
+        assert_eq!(parse_throttle("100KB/s").unwrap(), 100_000);
+        assert!(parse_throttle("512").is_err());
+        assert!(parse_throttle("fast").is_err());
+        assert!(parse_throttle("2bps").is_err());
+    }
+
+    #[test]
+    fn test_parse_latency() {
+        assert_eq!(parse_latency("200ms").unwrap(), Duration::from_millis(200));
+        assert_eq!(parse_latency("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_latency("75").unwrap(), Duration::from_millis(75));
+        assert!(parse_latency("-1s").is_err());
+        assert!(parse_latency("soon").is_err());
+    }
 }
@@ -1,34 +1,157 @@
 use crate::models::{DirEntry, FileEntry, ServerArgs};
+use crate::throttle::{serve_throttled, with_latency};
+use crate::upload_app::upload_routes;
+use crate::ws_app::{ws_routes, WsHub};
 use percent_encoding::percent_decode_str;
+use shared::web::request_logger::create_request_logger;
 use std::fs;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
-use tracing::info;
+use std::sync::Arc;
+use warp::filters::BoxedFilter;
+use warp::http::{Method, StatusCode};
 use warp::{Filter, Reply};
 
-pub async fn start_server(config: ServerArgs) {
-    let root_path = config.root_path.clone();
-    let serve_hidden = config.serve_hidden;
+/// Index files served for directories, in order of preference.
+const INDEX_FILES: [&str; 2] = ["index.html", "index.htm"];
+
+/// Serves the routes until the server is stopped. Fails with the message to show when the
+/// address cannot be bound.
+pub async fn start_server(config: ServerArgs) -> Result<(), String> {
+    let config = Arc::new(config);
 
     // Create a filter for logging requests
     let log_filter = create_request_logger();
 
     // Create the main route handler
-    let routes = warp::path::full()
+    let static_routes = static_routes(config.clone());
+
+    // The upload and WebSocket routes take precedence over static files with the same path.
+    let routes = match &config.upload {
+        Some(upload) => upload_routes(Arc::new(upload.clone()))
+            .or(static_routes)
+            .unify()
+            .boxed(),
+        None => static_routes,
+    };
+
+    let routes = match config.ws_mode {
+        Some(ws_mode) => ws_routes(Arc::new(WsHub::new(ws_mode)))
+            .or(routes)
+            .unify()
+            .boxed(),
+        None => routes,
+    };
+
+    let routes = match config.latency {
+        Some(latency) => with_latency(routes, latency),
+        None => routes,
+    }
+    .with(log_filter);
+
+    let addr: SocketAddr = (config.host, config.port).into();
+
+    println!("Server running at http://{}", addr);
+    if config.ws_mode.is_some() {
+        println!("WebSocket endpoint at ws://{}/ws", addr);
+    }
+    if let Some(upload) = &config.upload {
+        println!("Upload form at http://{}{}", addr, upload.route);
+    }
+
+    match config.throttle {
+        Some(bytes_per_second) => serve_throttled(routes, addr, bytes_per_second)
+            .await
+            .map_err(|e| format!("Unable to bind to {}: {}", addr, e)),
+        None => {
+            warp::serve(routes).run(addr).await;
+            Ok(())
+        }
+    }
+}
+
+/// Serves the files of the root directory, for any path not handled by the other routes.
+fn static_routes(config: Arc<ServerArgs>) -> BoxedFilter<(warp::reply::Response,)> {
+    warp::path::full()
         .and(warp::method())
         .and_then(
             move |path: warp::path::FullPath, method: warp::http::Method| {
-                let root_path = root_path.clone();
-                async move { handle_request(root_path, path.as_str(), method, serve_hidden).await }
+                let config = config.clone();
+                async move { serve_static(config, path.as_str(), method).await }
             },
         )
-        .with(log_filter);
+        .boxed()
+}
 
-    let addr: SocketAddr = (config.host, config.port).into();
+/// Serves the requested path, then resolves the fallbacks: the root index file for unknown
+/// paths (`--spa`), and the custom error pages (`--error-page`).
+async fn serve_static(
+    config: Arc<ServerArgs>,
+    request_path: &str,
+    method: Method,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    match handle_request(
+        config.root_path.clone(),
+        request_path,
+        method,
+        config.serve_hidden,
+    )
+    .await
+    {
+        Ok(response) if config.error_pages.contains_key(&response.status().as_u16()) => {
+            Ok(error_page_response(&config, response.status()).unwrap_or(response))
+        }
+        Ok(response) => Ok(response),
+        Err(rejection) if rejection.is_not_found() => {
+            resolve_not_found(&config, request_path, rejection).await
+        }
+        Err(rejection) => Err(rejection),
+    }
+}
 
-    println!("Server running at http://{}", addr);
+async fn resolve_not_found(
+    config: &ServerArgs,
+    request_path: &str,
+    rejection: warp::Rejection,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    if config.spa && is_client_route(request_path) {
+        let index_path = INDEX_FILES
+            .iter()
+            .map(|index_file| config.root_path.join(index_file))
+            .find(|index_path| index_path.is_file());
+
+        if let Some(index_path) = index_path {
+            return serve_file(&index_path).await;
+        }
+    }
+
+    error_page_response(config, StatusCode::NOT_FOUND).ok_or(rejection)
+}
+
+/// Paths whose last segment has no extension are routes of the app (e.g.: `/users/42`).
+/// Missing assets (e.g.: `/app.js`) are still a 404, instead of getting the HTML of the index.
+fn is_client_route(request_path: &str) -> bool {
+    let last_segment = request_path.rsplit('/').next().unwrap_or_default();
+    !last_segment.contains('.')
+}
+
+/// The custom error page of the status, with that status. `None` when there's no page for it,
+/// or it can't be read anymore.
+fn error_page_response(config: &ServerArgs, status: StatusCode) -> Option<warp::reply::Response> {
+    let page = config.error_pages.get(&status.as_u16())?;
+    let contents = fs::read(page).ok()?;
 
-    warp::serve(routes).run(addr).await;
+    let mime_type = mime_guess::from_path(page)
+        .first_or_octet_stream()
+        .to_string();
+
+    Some(
+        warp::reply::with_status(
+            warp::reply::with_header(contents, "content-type", mime_type),
+            status,
+        )
+        .into_response(),
+    )
 }
 
 async fn handle_request(
@@ -87,8 +210,7 @@ async fn handle_request(
         serve_file(&canonical_file_path).await
     } else if canonical_file_path.is_dir() {
         // Check for index files
-        let index_files = ["index.html", "index.htm"];
-        for index_file in &index_files {
+        for index_file in &INDEX_FILES {
             let index_path = canonical_file_path.join(index_file);
             if index_path.exists() && index_path.is_file() {
                 return serve_file(&index_path).await;
@@ -345,32 +467,6 @@ fn format_file_size(size: u64) -> String {
     }
 }
 
-// Create a function that returns a warp log configuration (not a filter)
-fn create_request_logger() -> warp::log::Log<impl Fn(warp::log::Info) + Copy> {
-    warp::log::custom(|info| {
-        let headers = info.request_headers();
-        let user_agent = headers
-            .get("user-agent")
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("unknown");
-        let content_length = headers
-            .get("content-length")
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("0");
-
-        info!(
-            target: "http_server::access_log",
-            "HTTP request completed - {} {} {} - {}ms - {} bytes - UA: {}",
-            info.method(),
-            info.path(),
-            info.status(),
-            info.elapsed().as_millis(),
-            content_length,
-            user_agent
-        );
-    })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -562,4 +658,68 @@ mod tests {
         assert_eq!(files[0].0, "apple.txt");
         assert_eq!(files[1].0, "zebra.txt");
     }
+
+    fn server_args(root_path: &Path, spa: bool, error_pages: &[(u16, &str)]) -> Arc<ServerArgs> {
+        Arc::new(ServerArgs {
+            root_path: root_path.to_path_buf(),
+            port: 4200,
+            host: "127.0.0.1".parse().unwrap(),
+            serve_hidden: false,
+            ws_mode: None,
+            spa,
+            upload: None,
+            throttle: None,
+            latency: None,
+            error_pages: error_pages
+                .iter()
+                .map(|(code, page)| (*code, root_path.join(page)))
+                .collect(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_spa_fallback_serves_index_for_routes() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("index.html"), "<app/>").unwrap();
+        let routes = static_routes(server_args(dir.path(), true, &[]));
+
+        let route = warp::test::request().path("/users/42").reply(&routes).await;
+        assert_eq!(route.status(), 200);
+        assert_eq!(route.body(), "<app/>");
+
+        let missing_asset = warp::test::request()
+            .path("/assets/app.js")
+            .reply(&routes)
+            .await;
+        assert_eq!(
+            missing_asset.status(),
+            404,
+            "missing assets should still be a 404"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_custom_error_pages() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("404.html"), "not here").unwrap();
+        fs::write(dir.path().join("405.html"), "GET only").unwrap();
+        let routes = static_routes(server_args(
+            dir.path(),
+            false,
+            &[(404, "404.html"), (405, "405.html")],
+        ));
+
+        let not_found = warp::test::request().path("/missing").reply(&routes).await;
+        assert_eq!(not_found.status(), 404);
+        assert_eq!(not_found.body(), "not here");
+        assert_eq!(not_found.headers()["content-type"], "text/html");
+
+        let not_allowed = warp::test::request()
+            .method("POST")
+            .path("/")
+            .reply(&routes)
+            .await;
+        assert_eq!(not_allowed.status(), 405);
+        assert_eq!(not_allowed.body(), "GET only");
+    }
 }
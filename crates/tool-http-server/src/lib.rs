@@ -0,0 +1,70 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info};
+use crate::http_app::start_server;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+mod cli_utils;
+mod http_app;
+mod models;
+mod throttle;
+mod upload_app;
+mod ws_app;
+
+/// Static file HTTP server.
+///
+/// Parses arguments, then serves the folder until stopped.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    match runtime.block_on(run_tool(argv)) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+async fn run_tool(argv: Vec<OsString>) -> Result<(), String> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Info);
+
+    let args = get_cli_arguments(argv)?;
+
+    print_runtime_info(&args);
+
+    start_server(args).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_returns_error_when_the_path_does_not_exist() {
+        assert_eq!(
+            run(["http-server", "no/such/folder/to/serve"]),
+            EXIT_CODE_ERROR
+        );
+    }
+}
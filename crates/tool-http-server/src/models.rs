@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
 use std::net::IpAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct ServerArgs {
@@ -7,6 +9,53 @@ pub struct ServerArgs {
     pub(crate) port: u16,
     pub(crate) host: IpAddr,
     pub(crate) serve_hidden: bool,
+    /// WebSocket endpoint mode. `None` when `--ws` is not set.
+    pub(crate) ws_mode: Option<WsMode>,
+    /// Serves the root index file for unknown paths, so client-side routers can handle them.
+    pub(crate) spa: bool,
+    /// Files served as the body of error responses, by status code.
+    pub(crate) error_pages: BTreeMap<u16, PathBuf>,
+    /// Route receiving file uploads. `None` when `--upload` is not set.
+    pub(crate) upload: Option<UploadRoute>,
+    /// Limit of the bytes per second written to each connection. `None` when not throttled.
+    pub(crate) throttle: Option<u64>,
+    /// Delay added to every request. `None` when there's no latency.
+    pub(crate) latency: Option<Duration>,
+}
+
+/// Route that receives multipart uploads (`POST`) and shows an upload form (`GET`).
+#[derive(Debug, Clone)]
+pub struct UploadRoute {
+    /// URL path, starting with `/` and without a trailing slash (e.g.: `/upload`).
+    pub route: String,
+    /// Folder the received files are written to.
+    pub dir: PathBuf,
+}
+
+/// What the `/ws` endpoint does with the messages it receives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WsMode {
+    /// Sends each message back to the client that sent it.
+    Echo,
+    /// Sends each message to every other connected client.
+    Broadcast,
+}
+
+impl WsMode {
+    pub fn from_str_value(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "echo" => Some(WsMode::Echo),
+            "broadcast" => Some(WsMode::Broadcast),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WsMode::Echo => "Echo",
+            WsMode::Broadcast => "Broadcast",
+        }
+    }
 }
 
 /// A directory entry: `(name, relative_url_path)`.
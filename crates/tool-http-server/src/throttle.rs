@@ -0,0 +1,215 @@
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpListener;
+use tokio::time::Sleep;
+use tracing::{debug, error};
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+/// Delays every request by `latency`, before it reaches the routes.
+pub fn with_latency(
+    routes: BoxedFilter<(warp::reply::Response,)>,
+    latency: Duration,
+) -> BoxedFilter<(warp::reply::Response,)> {
+    warp::any()
+        .then(move || tokio::time::sleep(latency))
+        .untuple_one()
+        .and(routes)
+        .boxed()
+}
+
+/// Serves the routes like `warp::serve`, but writes to each connection at no more than
+/// `bytes_per_second`. Only returns when `addr` cannot be bound.
+pub async fn serve_throttled<F, R>(
+    routes: F,
+    addr: SocketAddr,
+    bytes_per_second: u64,
+) -> io::Result<()>
+where
+    F: Filter<Extract = (R,), Error = warp::Rejection> + Clone + Send + Sync + 'static,
+    R: Reply,
+{
+    let listener = TcpListener::bind(addr).await?;
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                debug!("Accept error: {}", e);
+                continue;
+            }
+        };
+
+        let service = TowerToHyperService::new(warp::service(routes.clone()));
+        let io = TokioIo::new(ThrottledStream::new(stream, bytes_per_second));
+
+        tokio::spawn(async move {
+            if let Err(e) = Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(io, service)
+                .await
+            {
+                error!("Connection error: {:?}", e);
+            }
+        });
+    }
+}
+
+/// Token bucket: `bytes_per_second` tokens are added every second, up to 100ms worth of them,
+/// so a connection that was idle doesn't get a burst faster than the limit.
+struct Throttle {
+    bytes_per_second: f64,
+    burst: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Throttle {
+    fn new(bytes_per_second: u64) -> Self {
+        let bytes_per_second = bytes_per_second.max(1) as f64;
+        let burst = (bytes_per_second / 10.0).max(1.0);
+
+        Self {
+            bytes_per_second,
+            burst,
+            tokens: burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Bytes that can be written now, or how long to wait until at least one can.
+    fn allowance(&mut self, now: Instant) -> Result<usize, Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.bytes_per_second).min(self.burst);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            Ok(self.tokens as usize)
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.bytes_per_second,
+            ))
+        }
+    }
+
+    fn consume(&mut self, bytes: usize) {
+        self.tokens -= bytes as f64;
+    }
+}
+
+/// A stream whose writes (the responses) are limited by a [`Throttle`]. Reads aren't limited.
+struct ThrottledStream<S> {
+    inner: S,
+    throttle: Throttle,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S> ThrottledStream<S> {
+    fn new(inner: S, bytes_per_second: u64) -> Self {
+        Self {
+            inner,
+            throttle: Throttle::new(bytes_per_second),
+            delay: None,
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for ThrottledStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for ThrottledStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            if let Some(delay) = self.delay.as_mut() {
+                ready!(delay.as_mut().poll(cx));
+                self.delay = None;
+            }
+
+            match self.throttle.allowance(Instant::now()) {
+                Ok(allowed) => {
+                    let len = buf.len().min(allowed);
+                    let written = ready!(Pin::new(&mut self.inner).poll_write(cx, &buf[..len]))?;
+                    self.throttle.consume(written);
+                    return Poll::Ready(Ok(written));
+                }
+                Err(wait) => {
+                    self.delay = Some(Box::pin(tokio::time::sleep(wait)));
+                }
+            }
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_throttle_allowance() {
+        let start = Instant::now();
+        let mut throttle = Throttle::new(1000);
+        throttle.last_refill = start;
+
+        // Starts with 100ms worth of bytes.
+        assert_eq!(throttle.allowance(start), Ok(100));
+        throttle.consume(100);
+        assert!(throttle.allowance(start).is_err());
+
+        let allowed = throttle
+            .allowance(start + Duration::from_millis(50))
+            .unwrap();
+        assert!((49..=50).contains(&allowed), "{}", allowed);
+        // Idle time doesn't add more than the burst.
+        assert_eq!(throttle.allowance(start + Duration::from_secs(10)), Ok(100));
+    }
+
+    #[tokio::test]
+    async fn test_throttled_stream_limits_writes() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let mut throttled = ThrottledStream::new(server, 10_000);
+
+        let started = Instant::now();
+        let writer = tokio::spawn(async move {
+            throttled.write_all(&[7u8; 3_000]).await.unwrap();
+            throttled.shutdown().await.unwrap();
+        });
+
+        let mut received = Vec::new();
+        let mut client = client;
+        client.read_to_end(&mut received).await.unwrap();
+        writer.await.unwrap();
+
+        // 1000 bytes right away (the burst), then 2000 bytes at 10000 bytes per second.
+        assert_eq!(received.len(), 3_000);
+        assert!(started.elapsed() >= Duration::from_millis(190));
+    }
+}
@@ -0,0 +1,289 @@
+use crate::models::UploadRoute;
+use futures::{pin_mut, StreamExt};
+use shared::system::pathbuf_extensions::PathBufExtensions;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::multipart::{FormData, Part};
+use warp::{Buf, Filter, Reply};
+
+/// Largest upload (the whole request) accepted: 4 GB.
+const MAX_UPLOAD_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+/// `GET <route>` (upload form) and `POST <route>` (multipart upload, saved to the upload folder).
+pub fn upload_routes(upload: Arc<UploadRoute>) -> BoxedFilter<(warp::reply::Response,)> {
+    let route = upload.route.clone();
+    let on_route = warp::path::full()
+        .and_then(move |path: warp::path::FullPath| {
+            let matches = path.as_str().trim_end_matches('/') == route;
+            async move {
+                if matches {
+                    Ok(())
+                } else {
+                    Err(warp::reject::not_found())
+                }
+            }
+        })
+        .untuple_one();
+
+    let form_route = upload.route.clone();
+    let form_page = on_route.clone().and(warp::get()).map(move || {
+        warp::reply::with_header(
+            render_upload_page(&form_route),
+            "content-type",
+            "text/html; charset=utf-8",
+        )
+        .into_response()
+    });
+
+    let receive = on_route
+        .and(warp::post())
+        .and(warp::multipart::form().max_length(MAX_UPLOAD_SIZE))
+        .then(move |form: FormData| {
+            let upload = upload.clone();
+            async move { receive_files(&upload.dir, form).await }
+        });
+
+    form_page.or(receive).unify().boxed()
+}
+
+/// Saves every file of the form. Fields that aren't files are ignored.
+async fn receive_files(dir: &Path, form: FormData) -> warp::reply::Response {
+    let mut saved = Vec::new();
+    pin_mut!(form);
+
+    while let Some(part) = form.next().await {
+        let part = match part {
+            Ok(part) => part,
+            Err(e) => {
+                warn!("Upload failed: {}", e);
+                return text_response(StatusCode::BAD_REQUEST, format!("Invalid upload: {}", e));
+            }
+        };
+
+        let Some(file_name) = part.filename().and_then(safe_file_name) else {
+            continue;
+        };
+
+        match save_part(&dir.join(&file_name), part).await {
+            Ok((path, size)) => {
+                info!("Received {} ({} bytes)", path.display(), size);
+                saved.push((path, size));
+            }
+            Err(e) => {
+                warn!("Unable to save {}: {}", file_name, e);
+                return text_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Unable to save {}: {}", file_name, e),
+                );
+            }
+        }
+    }
+
+    if saved.is_empty() {
+        return text_response(
+            StatusCode::BAD_REQUEST,
+            "No files in the upload".to_string(),
+        );
+    }
+
+    let mut message = format!("Saved {} file(s):\n", saved.len());
+    for (path, size) in &saved {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        message.push_str(&format!("- {} ({} bytes)\n", name, size));
+    }
+
+    text_response(StatusCode::CREATED, message)
+}
+
+/// Streams the part into a new file, next to the files with the same name (`photo (1).jpg`)
+/// instead of overwriting them. Incomplete files are removed.
+async fn save_part(target: &Path, part: Part) -> io::Result<(PathBuf, u64)> {
+    let (path, mut file) = create_available_file(target).await?;
+
+    let mut size = 0u64;
+    let result = async {
+        let stream = part.stream();
+        pin_mut!(stream);
+
+        while let Some(chunk) = stream.next().await {
+            let mut chunk = chunk.map_err(io::Error::other)?;
+            while chunk.has_remaining() {
+                let bytes = chunk.chunk();
+                let len = bytes.len();
+                file.write_all(bytes).await?;
+                chunk.advance(len);
+                size += len as u64;
+            }
+        }
+
+        file.flush().await
+    }
+    .await;
+
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(&path).await;
+        return Err(e);
+    }
+
+    Ok((path, size))
+}
+
+/// Creates the file at the next available path. Retries when another upload takes that path
+/// between the check and the creation.
+async fn create_available_file(target: &Path) -> io::Result<(PathBuf, File)> {
+    loop {
+        let path = target.next_available_file();
+
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Only the name of the file: browsers may send the full path, and `..` or a path would write
+/// outside the upload folder.
+fn safe_file_name(file_name: &str) -> Option<String> {
+    let name = file_name.rsplit(['/', '\\']).next()?.trim();
+
+    if name.is_empty() || name == "." || name == ".." {
+        return None;
+    }
+
+    Some(name.to_string())
+}
+
+fn text_response(status: StatusCode, message: String) -> warp::reply::Response {
+    warp::reply::with_status(message, status).into_response()
+}
+
+fn render_upload_page(route: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>Upload files</title>
+    <style>
+        body {{
+            font-family: Arial, sans-serif;
+            margin: 40px;
+            background-color: #f5f5f5;
+        }}
+        .container {{
+            background-color: white;
+            padding: 20px;
+            border-radius: 8px;
+            box-shadow: 0 2px 4px rgba(0,0,0,0.1);
+        }}
+        h1 {{
+            color: #333;
+            border-bottom: 2px solid #ddd;
+            padding-bottom: 10px;
+        }}
+        button {{
+            margin-top: 20px;
+            padding: 8px 16px;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>Upload files</h1>
+        <form method="post" action="{}" enctype="multipart/form-data">
+            <input type="file" name="files" multiple required>
+            <br>
+            <button type="submit">Upload</button>
+        </form>
+    </div>
+</body>
+</html>"#,
+        route
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn multipart_body(files: &[(&str, &str)]) -> String {
+        let mut body = String::new();
+        for (name, contents) in files {
+            body.push_str(&format!(
+                "--BOUNDARY\r\nContent-Disposition: form-data; name=\"files\"; filename=\"{}\"\r\n\
+                 Content-Type: text/plain\r\n\r\n{}\r\n",
+                name, contents
+            ));
+        }
+        body.push_str("--BOUNDARY--\r\n");
+        body
+    }
+
+    #[test]
+    fn test_safe_file_name() {
+        assert_eq!(safe_file_name("photo.jpg").as_deref(), Some("photo.jpg"));
+        assert_eq!(
+            safe_file_name("C:\\Users\\me\\photo.jpg").as_deref(),
+            Some("photo.jpg")
+        );
+        assert_eq!(
+            safe_file_name("../../etc/passwd").as_deref(),
+            Some("passwd")
+        );
+        assert_eq!(safe_file_name(".."), None);
+        assert_eq!(safe_file_name(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_upload_saves_files_without_overwriting() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("notes.txt"), "old").unwrap();
+        let routes = upload_routes(Arc::new(UploadRoute {
+            route: "/upload".to_string(),
+            dir: dir.path().to_path_buf(),
+        }));
+
+        let response = warp::test::request()
+            .method("POST")
+            .path("/upload")
+            .header("content-type", "multipart/form-data; boundary=BOUNDARY")
+            .body(multipart_body(&[
+                ("notes.txt", "new"),
+                ("../escape.txt", "x"),
+            ]))
+            .reply(&routes)
+            .await;
+
+        assert_eq!(response.status(), 201);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("notes.txt")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("notes (1).txt")).unwrap(),
+            "new"
+        );
+        assert!(dir.path().join("escape.txt").exists());
+
+        let form = warp::test::request().path("/upload").reply(&routes).await;
+        assert_eq!(form.status(), 200);
+
+        let other = warp::test::request().path("/other").reply(&routes).await;
+        assert_eq!(other.status(), 404);
+    }
+}
@@ -0,0 +1,300 @@
+use crate::models::WsMode;
+use futures::{SinkExt, StreamExt};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tracing::{debug, info};
+use warp::filters::BoxedFilter;
+use warp::ws::{Message, WebSocket, Ws};
+use warp::{Filter, Reply};
+
+/// A connected WebSocket client, as shown in the clients page.
+#[derive(Debug, Clone)]
+pub struct WsClientInfo {
+    pub id: u64,
+    pub connected_at: Instant,
+    pub messages_received: u64,
+}
+
+struct WsClient {
+    info: WsClientInfo,
+    sender: UnboundedSender<Message>,
+}
+
+/// Keeps track of the connected clients and routes their messages.
+pub struct WsHub {
+    mode: WsMode,
+    next_id: AtomicU64,
+    clients: Mutex<BTreeMap<u64, WsClient>>,
+}
+
+impl WsHub {
+    pub fn new(mode: WsMode) -> Self {
+        Self {
+            mode,
+            next_id: AtomicU64::new(1),
+            clients: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Adds a client. Messages for it arrive in the returned receiver.
+    pub fn register(&self) -> (u64, UnboundedReceiver<Message>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = unbounded_channel();
+
+        let client = WsClient {
+            info: WsClientInfo {
+                id,
+                connected_at: Instant::now(),
+                messages_received: 0,
+            },
+            sender,
+        };
+
+        self.clients.lock().unwrap().insert(id, client);
+
+        (id, receiver)
+    }
+
+    pub fn unregister(&self, id: u64) {
+        self.clients.lock().unwrap().remove(&id);
+    }
+
+    /// Echo sends the message back to the sender. Broadcast sends it to every other client.
+    pub fn route(&self, sender_id: u64, message: Message) {
+        let mut clients = self.clients.lock().unwrap();
+
+        if let Some(sender) = clients.get_mut(&sender_id) {
+            sender.info.messages_received += 1;
+        }
+
+        for (id, client) in clients.iter() {
+            let is_sender = *id == sender_id;
+            let deliver = match self.mode {
+                WsMode::Echo => is_sender,
+                WsMode::Broadcast => !is_sender,
+            };
+
+            if deliver {
+                // Fails only if the client is disconnecting, and it will be unregistered soon.
+                let _ = client.sender.send(message.clone());
+            }
+        }
+    }
+
+    pub fn clients(&self) -> Vec<WsClientInfo> {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .map(|client| client.info.clone())
+            .collect()
+    }
+
+    pub fn mode(&self) -> WsMode {
+        self.mode
+    }
+}
+
+/// `GET /ws` (WebSocket upgrade) and `GET /ws/clients` (connected clients page).
+pub fn ws_routes(hub: Arc<WsHub>) -> BoxedFilter<(warp::reply::Response,)> {
+    let upgrade_hub = hub.clone();
+    let upgrade = warp::path("ws")
+        .and(warp::path::end())
+        .and(warp::ws())
+        .map(move |ws: Ws| {
+            let hub = upgrade_hub.clone();
+            ws.on_upgrade(move |socket| handle_client(hub, socket))
+                .into_response()
+        });
+
+    let clients_page = warp::path!("ws" / "clients").and(warp::get()).map(move || {
+        let html = render_clients_page(&hub.clients(), hub.mode());
+        warp::reply::with_header(html, "content-type", "text/html; charset=utf-8").into_response()
+    });
+
+    upgrade.or(clients_page).unify().boxed()
+}
+
+async fn handle_client(hub: Arc<WsHub>, socket: WebSocket) {
+    let (id, mut receiver) = hub.register();
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+
+    info!("WebSocket client #{} connected", id);
+
+    let forward = tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            if ws_sender.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(message)) = ws_receiver.next().await {
+        if message.is_close() {
+            break;
+        }
+
+        if message.is_text() || message.is_binary() {
+            debug!(
+                "WebSocket client #{} sent {} bytes",
+                id,
+                message.as_bytes().len()
+            );
+            hub.route(id, message);
+        }
+    }
+
+    hub.unregister(id);
+    forward.abort();
+
+    info!("WebSocket client #{} disconnected", id);
+}
+
+/// Refreshes every 2 seconds, so clients show up and leave while the page is open.
+fn render_clients_page(clients: &[WsClientInfo], mode: WsMode) -> String {
+    let mut rows = String::new();
+
+    for client in clients {
+        rows.push_str(&format!(
+            r#"<tr>
+                <td>#{}</td>
+                <td>{}s</td>
+                <td>{}</td>
+            </tr>"#,
+            client.id,
+            client.connected_at.elapsed().as_secs(),
+            client.messages_received
+        ));
+    }
+
+    if clients.is_empty() {
+        rows.push_str(r#"<tr><td colspan="3">No clients connected.</td></tr>"#);
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <meta http-equiv="refresh" content="2">
+    <title>WebSocket clients</title>
+    <style>
+        body {{
+            font-family: Arial, sans-serif;
+            margin: 40px;
+            background-color: #f5f5f5;
+        }}
+        .container {{
+            background-color: white;
+            padding: 20px;
+            border-radius: 8px;
+            box-shadow: 0 2px 4px rgba(0,0,0,0.1);
+        }}
+        table {{
+            width: 100%;
+            border-collapse: collapse;
+            margin-top: 20px;
+        }}
+        th, td {{
+            padding: 12px;
+            text-align: left;
+            border-bottom: 1px solid #ddd;
+        }}
+        th {{
+            background-color: #f8f9fa;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <h1>WebSocket clients ({})</h1>
+        <p>Endpoint: <code>/ws</code> - Mode: {}</p>
+        <table>
+            <thead>
+                <tr>
+                    <th>Client</th>
+                    <th>Connected for</th>
+                    <th>Messages received</th>
+                </tr>
+            </thead>
+            <tbody>{}</tbody>
+        </table>
+    </div>
+</body>
+</html>"#,
+        clients.len(),
+        mode.label(),
+        rows
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn received(receiver: &mut UnboundedReceiver<Message>) -> Vec<String> {
+        let mut messages = Vec::new();
+        while let Ok(message) = receiver.try_recv() {
+            messages.push(message.to_str().unwrap_or_default().to_string());
+        }
+        messages
+    }
+
+    #[test]
+    fn test_echo_replies_only_to_the_sender() {
+        let hub = WsHub::new(WsMode::Echo);
+        let (first, mut first_rx) = hub.register();
+        let (_, mut second_rx) = hub.register();
+
+        hub.route(first, Message::text("hello"));
+
+        assert_eq!(received(&mut first_rx), vec!["hello"]);
+        assert!(received(&mut second_rx).is_empty());
+    }
+
+    #[test]
+    fn test_broadcast_reaches_every_other_client() {
+        let hub = WsHub::new(WsMode::Broadcast);
+        let (first, mut first_rx) = hub.register();
+        let (_, mut second_rx) = hub.register();
+        let (_, mut third_rx) = hub.register();
+
+        hub.route(first, Message::text("hello"));
+
+        assert!(received(&mut first_rx).is_empty());
+        assert_eq!(received(&mut second_rx), vec!["hello"]);
+        assert_eq!(received(&mut third_rx), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_clients_are_tracked() {
+        let hub = WsHub::new(WsMode::Echo);
+        let (first, _first_rx) = hub.register();
+        let (second, _second_rx) = hub.register();
+
+        hub.route(first, Message::text("a"));
+        hub.route(first, Message::text("b"));
+        hub.unregister(second);
+
+        let clients = hub.clients();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].id, first);
+        assert_eq!(clients[0].messages_received, 2);
+    }
+
+    #[test]
+    fn test_render_clients_page() {
+        let hub = WsHub::new(WsMode::Broadcast);
+        assert!(render_clients_page(&hub.clients(), hub.mode()).contains("No clients connected."));
+
+        let (_id, _rx) = hub.register();
+        let html = render_clients_page(&hub.clients(), hub.mode());
+
+        assert!(html.contains("WebSocket clients (1)"));
+        assert!(html.contains("Mode: Broadcast"));
+        assert!(html.contains("<td>#1</td>"));
+    }
+}
@@ -0,0 +1,217 @@
+use anyhow::{anyhow, Result};
+use globset::{GlobBuilder, GlobMatcher};
+use shared::system::folder_walkthrough::list_all_files_recursively;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, info};
+
+/// A file to edit, and the folder where its output goes (`None` means next to the file).
+pub struct BatchInput {
+    pub file: PathBuf,
+    pub output_dir: Option<PathBuf>,
+}
+
+/// Where the files of an input come from: a folder (walked recursively) and the patterns its files
+/// must match.
+struct InputSource {
+    root: PathBuf,
+    patterns: Vec<GlobMatcher>,
+    /// If true, patterns always match the path relative to `root`, as a shell would.
+    anchored: bool,
+}
+
+/// Returns true if the input has wildcards (`*`, `?`, `[`), which we expand ourselves, so quoted
+/// patterns (and shells that don't expand them, like cmd.exe) work the same way.
+pub fn is_glob_pattern(input: &Path) -> bool {
+    input
+        .to_string_lossy()
+        .chars()
+        .any(|c| matches!(c, '*' | '?' | '['))
+}
+
+/// Expands the inputs into the files to edit:
+/// - Files are used as-is.
+/// - Folders are walked recursively, keeping the files that match any of `globs`, if informed.
+/// - Patterns (e.g.: `photos/**/*.jpg`) are expanded from the folder before the first wildcard.
+///
+/// With `output_dir`, the structure below each folder (or pattern base) is mirrored inside it.
+pub fn expand_inputs(
+    inputs: &[PathBuf],
+    globs: &[String],
+    output_dir: Option<&Path>,
+    is_supported: fn(&PathBuf) -> bool,
+) -> Result<Vec<BatchInput>> {
+    let filters = globs
+        .iter()
+        .map(|glob| build_matcher(glob))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut expanded: BTreeMap<PathBuf, Option<PathBuf>> = BTreeMap::new();
+
+    for input in inputs {
+        if input.is_file() {
+            if is_supported(input) {
+                let root = input.parent().unwrap_or(Path::new(""));
+                let mirrored = output_dir.map(|dir| mirrored_output_dir(dir, root, input));
+                expanded.insert(input.clone(), mirrored);
+            }
+            continue;
+        }
+
+        let source = if input.is_dir() {
+            InputSource {
+                root: input.clone(),
+                patterns: filters.clone(),
+                anchored: false,
+            }
+        } else if is_glob_pattern(input) {
+            split_glob_input(input)?
+        } else {
+            debug!("Path does not exist: {}", input.display());
+            continue;
+        };
+
+        for file in list_all_files_recursively(&source.root) {
+            if !is_supported(&file) || !source.matches(&file) {
+                continue;
+            }
+
+            let mirrored = output_dir.map(|dir| mirrored_output_dir(dir, &source.root, &file));
+            expanded.entry(file).or_insert(mirrored);
+        }
+    }
+
+    if expanded.is_empty() {
+        return Err(anyhow!(
+            "No supported image files found. Nothing to work with."
+        ));
+    }
+
+    info!("Found {} supported image files.", expanded.len());
+    Ok(expanded
+        .into_iter()
+        .map(|(file, output_dir)| BatchInput { file, output_dir })
+        .collect())
+}
+
+impl InputSource {
+    fn matches(&self, file: &Path) -> bool {
+        if self.patterns.is_empty() {
+            return true;
+        }
+
+        let relative = file.strip_prefix(&self.root).unwrap_or(file);
+        self.patterns.iter().any(|pattern| {
+            // Like `.gitignore`, `--glob` patterns without a `/` match the file name at any depth.
+            if self.anchored || pattern.glob().glob().contains('/') {
+                pattern.is_match(relative)
+            } else {
+                file.file_name()
+                    .is_some_and(|name| pattern.is_match(Path::new(name)))
+            }
+        })
+    }
+}
+
+/// Splits `photos/2024/**/*.jpg` into the folder to walk (`photos/2024`) and the pattern
+/// (`**/*.jpg`).
+fn split_glob_input(input: &Path) -> Result<InputSource> {
+    let mut root = PathBuf::new();
+    let mut pattern_parts = Vec::new();
+
+    for component in input.components() {
+        let part = component.as_os_str();
+        if pattern_parts.is_empty()
+            && !matches!(component, Component::Normal(_) if is_glob_pattern(Path::new(part)))
+        {
+            root.push(part);
+        } else {
+            pattern_parts.push(part.to_string_lossy().into_owned());
+        }
+    }
+
+    if root.as_os_str().is_empty() {
+        root.push(".");
+    }
+
+    Ok(InputSource {
+        root,
+        patterns: vec![build_matcher(&pattern_parts.join("/"))?],
+        anchored: true,
+    })
+}
+
+fn build_matcher(pattern: &str) -> Result<GlobMatcher> {
+    Ok(GlobBuilder::new(pattern)
+        .case_insensitive(true)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| anyhow!("Invalid glob pattern '{}': {}", pattern, e))?
+        .compile_matcher())
+}
+
+/// Computes the mirrored folder of a file: `output_dir` joined with the folder of the file,
+/// relative to `root`.
+fn mirrored_output_dir(output_dir: &Path, root: &Path, file: &Path) -> PathBuf {
+    let relative_dir = file
+        .strip_prefix(root)
+        .ok()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new(""));
+
+    output_dir.join(relative_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_glob_input() {
+        let source = split_glob_input(Path::new("photos/2024/**/*.JPG")).unwrap();
+        assert_eq!(source.root, PathBuf::from("photos/2024"));
+        assert!(source.matches(Path::new("photos/2024/trip/day1/beach.jpg")));
+        assert!(!source.matches(Path::new("photos/2024/trip/beach.png")));
+
+        let source = split_glob_input(Path::new("*.png")).unwrap();
+        assert_eq!(source.root, PathBuf::from("."));
+        assert!(source.matches(Path::new("./logo.png")));
+        assert!(!source.matches(Path::new("./icons/logo.png")));
+    }
+
+    #[test]
+    fn test_glob_filters_without_separator_match_the_file_name() {
+        let source = InputSource {
+            root: PathBuf::from("photos"),
+            patterns: vec![
+                build_matcher("*.jpg").unwrap(),
+                build_matcher("raw/*.png").unwrap(),
+            ],
+            anchored: false,
+        };
+
+        assert!(source.matches(Path::new("photos/trip/day1/beach.jpg")));
+        assert!(source.matches(Path::new("photos/raw/scan.png")));
+        assert!(!source.matches(Path::new("photos/trip/raw/scan.png")));
+    }
+
+    #[test]
+    fn test_mirrored_output_dir() {
+        assert_eq!(
+            mirrored_output_dir(
+                Path::new("out"),
+                Path::new("photos"),
+                Path::new("photos/trip/day1/beach.jpg")
+            ),
+            PathBuf::from("out/trip/day1")
+        );
+        assert_eq!(
+            mirrored_output_dir(
+                Path::new("out"),
+                Path::new("photos"),
+                Path::new("photos/beach.jpg")
+            ),
+            PathBuf::from("out")
+        );
+    }
+}
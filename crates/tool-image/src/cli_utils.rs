@@ -1,9 +1,28 @@
-use crate::models::{EditArgs, ResizeSpec};
+use crate::batch_inputs::is_glob_pattern;
+use crate::models::{
+    DedupeArgs, EditArgs, ExifArgs, HashAlgorithm, ImgxCommand, MetadataEdit, OverlayPosition,
+    PageSize, PdfAssembleOptions, PdfOperation, ResizeSpec, TextSpec, WatermarkSpec, WebFormat,
+    WebOptimizeArgs,
+};
 use crate::string_traits::StringExt;
 use anyhow::Result;
-use clap::{Arg, Command};
+use chrono::{Local, NaiveDateTime};
+use clap::{Arg, Command, ValueEnum};
+use image::Rgba;
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+const DEFAULT_DPI: f32 = 150.0;
+const DEFAULT_MARGIN_MM: f32 = 10.0;
+const DEFAULT_PAGE_SIZE: &str = "a4";
+const DEFAULT_TEXT_SIZE: f32 = 32.0;
+const EXIF_DATETIME_FORMAT: &str = "%Y:%m:%d %H:%M:%S";
+const DEFAULT_DEDUPE_THRESHOLD: u32 = 5;
+const DEFAULT_WEB_QUALITY: u8 = 80;
+const DEFAULT_WEB_WIDTHS: [u32; 3] = [480, 960, 1920];
+const DEFAULT_WEB_SIZES: &str = "100vw";
 
 pub fn print_runtime_info(args: &EditArgs) {
     println!("Image v{}", env!("CARGO_PKG_VERSION"));
@@ -26,6 +45,71 @@ pub fn print_runtime_info(args: &EditArgs) {
         println!("- Convert: {:?}", convert);
     }
 
+    if let Some(watermark) = &args.watermark {
+        println!("- Watermark: {}", watermark.file.display());
+        println!("  - Position: {}", position_name(watermark.position));
+        println!("  - Opacity: {}%", watermark.opacity);
+        if let Some(scale) = watermark.scale {
+            println!("  - Scale: {}% of the image width", scale);
+        }
+    }
+
+    if args.metadata.strip {
+        println!("- Strip metadata: true");
+    }
+
+    if let Some(artist) = &args.metadata.artist {
+        println!("- Artist: {}", artist);
+    }
+
+    if let Some(copyright) = &args.metadata.copyright {
+        println!("- Copyright: {}", copyright);
+    }
+
+    if let Some(datetime) = &args.metadata.datetime {
+        println!("- Date/time: {}", datetime);
+    }
+
+    if let Some(text) = &args.text {
+        println!("- Text: {}", text.text);
+        match &text.font {
+            Some(font) => println!("  - Font: {}", font.display()),
+            None => println!("  - Font: system default"),
+        }
+        println!("  - Size: {}px", text.size);
+        let [r, g, b, a] = text.color.0;
+        println!("  - Color: #{:02x}{:02x}{:02x}{:02x}", r, g, b, a);
+        println!("  - Position: {}", position_name(text.position));
+    }
+
+    if !args.globs.is_empty() {
+        println!("- Glob: {}", args.globs.join(", "));
+    }
+
+    if let Some(output_dir) = &args.output_dir {
+        println!("- Output dir: {}", output_dir.display());
+    }
+
+    if args.pdf.is_none() {
+        println!("- Parallel jobs: {}", args.jobs);
+    }
+
+    match &args.pdf {
+        None => {}
+        Some(PdfOperation::Rasterize { dpi }) => {
+            println!("- Rasterize PDF pages to PNG");
+            println!("  - DPI: {}", dpi);
+        }
+        Some(PdfOperation::Assemble(options)) => {
+            println!(
+                "- Bundle images into PDF: {}",
+                options.output_file.display()
+            );
+            println!("  - Page size: {}", options.page_size);
+            println!("  - Margin: {} mm", options.margin_mm);
+        }
+    }
+
     println!();
 }
 
@@ -34,23 +118,196 @@ pub fn validate_args(args: &EditArgs) -> Result<()> {
         return Err(anyhow::anyhow!("No input files provided"));
     };
 
-    if args.input_files.iter().any(|file| !file.exists()) {
+    // Patterns are expanded later, when the regular edit jobs are built.
+    let is_pattern = |file: &PathBuf| args.pdf.is_none() && is_glob_pattern(file);
+    if args
+        .input_files
+        .iter()
+        .any(|file| !file.exists() && !is_pattern(file))
+    {
         return Err(anyhow::anyhow!("Some of the input file does not exist"));
     }
 
+    if let Some(output_dir) = &args.output_dir {
+        if output_dir.is_file() {
+            return Err(anyhow::anyhow!(
+                "Output directory '{}' is a file",
+                output_dir.display()
+            ));
+        }
+    }
+
+    if let Some(watermark) = &args.watermark {
+        if !watermark.file.is_file() {
+            return Err(anyhow::anyhow!(
+                "Watermark file '{}' does not exist",
+                watermark.file.display()
+            ));
+        }
+    }
+
+    if let Some(font) = args.text.as_ref().and_then(|text| text.font.as_ref()) {
+        if !font.is_file() {
+            return Err(anyhow::anyhow!(
+                "Font file '{}' does not exist",
+                font.display()
+            ));
+        }
+    }
+
+    if args.jobs == 0 {
+        return Err(anyhow::anyhow!("The number of jobs must be greater than 0"));
+    }
+
+    if let Some(PdfOperation::Assemble(options)) = &args.pdf {
+        if let PageSize::Fixed {
+            width_mm,
+            height_mm,
+        } = options.page_size
+        {
+            if options.margin_mm * 2.0 >= width_mm.min(height_mm) {
+                return Err(anyhow::anyhow!(
+                    "Margin of {} mm leaves no room for the image in a {} page",
+                    options.margin_mm,
+                    options.page_size
+                ));
+            }
+        }
+    }
+
     Ok(())
 }
 
-pub fn get_cli_arguments() -> EditArgs {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> ImgxCommand {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
             env!("CARGO_PKG_DESCRIPTION"),
             "Cli tool to perform some quick image edits.",
         )
+        .args_conflicts_with_subcommands(true)
+        .subcommand(
+            Command::new("exif")
+                .about("Prints the EXIF metadata of the images")
+                .arg(
+                    Arg::new("input-files")
+                        .help("Input files, folders (scanned recursively), or patterns like 'photos/**/*.jpg'")
+                        .num_args(1..)
+                        .required(true)
+                        .action(clap::ArgAction::Append),
+                ),
+        )
+        .subcommand(
+            Command::new("dedupe")
+                .about("Finds duplicate and near-duplicate images, by their perceptual hash")
+                .arg(
+                    Arg::new("input-files")
+                        .help("Input files, folders (scanned recursively), or patterns like 'photos/**/*.jpg'")
+                        .num_args(1..)
+                        .required(true)
+                        .action(clap::ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("hash")
+                        .long("hash")
+                        .value_name("ALGORITHM")
+                        .value_parser(clap::value_parser!(HashAlgorithm))
+                        .help("Perceptual hash used to compare the images. (Default: phash)"),
+                )
+                .arg(
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .short('t')
+                        .value_name("BITS")
+                        .value_parser(clap::value_parser!(u32).range(0..=64))
+                        .help("Largest number of different bits (0 to 64) between the hashes of similar images. 0 only groups images that look the same. (Default: 5)"),
+                )
+                .arg(
+                    Arg::new("move-to")
+                        .long("move-to")
+                        .value_name("DIR")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("Moves the duplicates to this folder, keeping the image with the most pixels (then the largest file) of each group in place"),
+                )
+                .arg(
+                    Arg::new("jobs")
+                        .long("jobs")
+                        .short('j')
+                        .value_name("N")
+                        .value_parser(clap::builder::ValueParser::new(parse_jobs))
+                        .help("Number of images hashed in parallel. (Default: number of CPUs)"),
+                ),
+        )
+        .subcommand(
+            Command::new("web-optimize")
+                .about("Makes web-ready copies of the images, in several widths, and prints the HTML (srcset) that offers them")
+                .arg(
+                    Arg::new("input-files")
+                        .help("Input files, folders (scanned recursively), or patterns like 'photos/**/*.jpg'")
+                        .num_args(1..)
+                        .required(true)
+                        .action(clap::ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .short('f')
+                        .value_name("FORMATS")
+                        .value_parser(clap::value_parser!(WebFormat))
+                        .value_delimiter(',')
+                        .action(clap::ArgAction::Append)
+                        .help("Output formats: webp, avif, or both (avif,webp), offered to browsers in a <picture>. (Default: webp)"),
+                )
+                .arg(
+                    Arg::new("quality")
+                        .long("quality")
+                        .short('q')
+                        .value_name("QUALITY")
+                        .value_parser(clap::value_parser!(u8).range(1..=100))
+                        .help("Quality target, from 1 (smallest files) to 100 (best quality). (Default: 80)"),
+                )
+                .arg(
+                    Arg::new("widths")
+                        .long("widths")
+                        .short('w')
+                        .value_name("WIDTHS")
+                        .value_parser(clap::builder::ValueParser::new(parse_width))
+                        .value_delimiter(',')
+                        .action(clap::ArgAction::Append)
+                        .help("Widths of the outputs, in pixels. Images are never enlarged. (Default: 480,960,1920)"),
+                )
+                .arg(
+                    Arg::new("sizes")
+                        .long("sizes")
+                        .value_name("SIZES")
+                        .help("'sizes' attribute of the HTML, e.g.: '(max-width: 800px) 100vw, 800px'. (Default: 100vw)"),
+                )
+                .arg(
+                    Arg::new("url-prefix")
+                        .long("url-prefix")
+                        .value_name("PREFIX")
+                        .help("Prepended to the paths of the outputs in the HTML, e.g.: '/images/'"),
+                )
+                .arg(
+                    Arg::new("output-dir")
+                        .long("output-dir")
+                        .short('o')
+                        .value_name("DIR")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("Saves the outputs in this folder, mirroring the structure of the input folders. The paths in the HTML are relative to it. (Default: next to each image)"),
+                )
+                .arg(
+                    Arg::new("jobs")
+                        .long("jobs")
+                        .short('j')
+                        .value_name("N")
+                        .value_parser(clap::builder::ValueParser::new(parse_jobs))
+                        .help("Number of images processed in parallel. (Default: number of CPUs)"),
+                ),
+        )
         .arg(
             Arg::new("input-files")
-                .help("Input files to process")
+                .help("Input files, folders (scanned recursively), or patterns like 'photos/**/*.jpg' to process")
                 .num_args(0..)
                 .required(false)
                 .action(clap::ArgAction::Append),
@@ -80,7 +337,208 @@ pub fn get_cli_arguments() -> EditArgs {
                 .value_parser(clap::value_parser!(String))
                 .help("Convert the image to the specified format"),
         )
-        .get_matches();
+        .arg(
+            Arg::new("watermark")
+                .long("watermark")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Overlays this image (e.g.: a PNG logo) on the edited images"),
+        )
+        .arg(
+            Arg::new("watermark-position")
+                .long("watermark-position")
+                .value_name("POSITION")
+                .requires("watermark")
+                .value_parser(clap::value_parser!(OverlayPosition))
+                .help("Where the watermark is placed. (Default: bottom-right)"),
+        )
+        .arg(
+            Arg::new("watermark-opacity")
+                .long("watermark-opacity")
+                .value_name("PERCENT")
+                .requires("watermark")
+                .value_parser(clap::builder::ValueParser::new(parse_opacity))
+                .help("Opacity of the watermark, from 0 to 100. (Examples: 50, 35%) (Default: 100)"),
+        )
+        .arg(
+            Arg::new("watermark-scale")
+                .long("watermark-scale")
+                .value_name("PERCENT")
+                .requires("watermark")
+                .value_parser(clap::builder::ValueParser::new(parse_watermark_scale))
+                .help("Width of the watermark, as a percentage of the image width. (Default: original size, shrunk to fit)"),
+        )
+        .arg(
+            Arg::new("text")
+                .long("text")
+                .value_name("TEXT")
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Draws this caption on the edited images. Use a line break for multiple lines."),
+        )
+        .arg(
+            Arg::new("font")
+                .long("font")
+                .value_name("FILE")
+                .requires("text")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("TrueType/OpenType font used by --text. (Default: Arial, Helvetica or DejaVu Sans, if installed)"),
+        )
+        .arg(
+            Arg::new("text-size")
+                .long("text-size")
+                .value_name("PX")
+                .requires("text")
+                .value_parser(clap::builder::ValueParser::new(parse_text_size))
+                .help("Font size of the caption, in pixels. (Default: 32)"),
+        )
+        .arg(
+            Arg::new("text-color")
+                .long("text-color")
+                .value_name("COLOR")
+                .requires("text")
+                .value_parser(clap::builder::ValueParser::new(parse_color))
+                .help("Color of the caption: #RRGGBB, #RRGGBBAA or a name (white, black, red, green, blue, yellow, gray). (Default: white)"),
+        )
+        .arg(
+            Arg::new("text-position")
+                .long("text-position")
+                .value_name("POSITION")
+                .requires("text")
+                .value_parser(clap::value_parser!(OverlayPosition))
+                .help("Where the caption is placed. (Default: bottom-left)"),
+        )
+        .arg(
+            Arg::new("strip-metadata")
+                .long("strip-metadata")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Removes the EXIF metadata (camera, GPS location, dates, ...) of the outputs, e.g.: before sharing them. The fields set with --artist, --copyright and --datetime are still written. (Default: false)"),
+        )
+        .arg(
+            Arg::new("artist")
+                .long("artist")
+                .value_name("NAME")
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Sets the Artist EXIF field of the outputs"),
+        )
+        .arg(
+            Arg::new("copyright")
+                .long("copyright")
+                .value_name("TEXT")
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Sets the Copyright EXIF field of the outputs"),
+        )
+        .arg(
+            Arg::new("datetime")
+                .long("datetime")
+                .value_name("DATETIME")
+                .value_parser(clap::builder::ValueParser::new(parse_exif_datetime))
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Sets the date the photo was taken (DateTime and DateTimeOriginal EXIF fields): 'YYYY-MM-DD HH:MM:SS', or 'now'"),
+        )
+        .arg(
+            Arg::new("glob")
+                .long("glob")
+                .value_name("PATTERN")
+                .action(clap::ArgAction::Append)
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Only process the files inside the input folders that match this pattern (e.g.: '*.jpg', 'raw/**/*.png'). Can be used multiple times."),
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .short('o')
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .conflicts_with_all(["rasterize", "to-pdf"])
+                .help("Saves the edited images in this folder, mirroring the structure of the input folders. (Default: next to each image)"),
+        )
+        .arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .short('j')
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of images processed in parallel. (Default: number of CPUs)"),
+        )
+        .arg(
+            Arg::new("rasterize")
+                .long("rasterize")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["resize", "convert", "to-pdf"])
+                .help("Renders each page of the input PDFs to PNG. (Requires the Pdfium library)"),
+        )
+        .arg(
+            Arg::new("dpi")
+                .long("dpi")
+                .value_name("DPI")
+                .requires("rasterize")
+                .value_parser(clap::builder::ValueParser::new(parse_dpi))
+                .help("Resolution used to render the PDF pages. (Default: 150)"),
+        )
+        .arg(
+            Arg::new("to-pdf")
+                .long("to-pdf")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .conflicts_with("convert")
+                .help("Bundles the input images into a single PDF, one image per page, in the order informed."),
+        )
+        .arg(
+            Arg::new("page-size")
+                .long("page-size")
+                .value_name("SIZE")
+                .requires("to-pdf")
+                .value_parser(clap::builder::ValueParser::new(parse_page_size))
+                .help("PDF page size: a3, a4, a5, letter, legal, fit, or WIDTHxHEIGHT in mm. (Default: a4)"),
+        )
+        .arg(
+            Arg::new("margin")
+                .long("margin")
+                .value_name("MM")
+                .requires("to-pdf")
+                .value_parser(clap::builder::ValueParser::new(parse_margin))
+                .help("PDF page margin, in millimeters. (Default: 10)"),
+        )
+        .get_matches_from(argv);
+
+    if let Some(("exif", sub_matches)) = matches.subcommand() {
+        return ImgxCommand::Exif(ExifArgs {
+            input_files: sub_matches
+                .get_many::<String>("input-files")
+                .unwrap_or_default()
+                .map(|s| s.into())
+                .collect(),
+        });
+    }
+
+    if let Some(("dedupe", sub_matches)) = matches.subcommand() {
+        return ImgxCommand::Dedupe(DedupeArgs {
+            input_files: sub_matches
+                .get_many::<String>("input-files")
+                .unwrap_or_default()
+                .map(|s| s.into())
+                .collect(),
+            hash: sub_matches
+                .get_one::<HashAlgorithm>("hash")
+                .copied()
+                .unwrap_or(HashAlgorithm::Phash),
+            threshold: sub_matches
+                .get_one::<u32>("threshold")
+                .copied()
+                .unwrap_or(DEFAULT_DEDUPE_THRESHOLD),
+            move_to: sub_matches.get_one::<PathBuf>("move-to").cloned(),
+            jobs: sub_matches
+                .get_one::<usize>("jobs")
+                .copied()
+                .unwrap_or_else(num_cpus::get),
+        });
+    }
+
+    if let Some(("web-optimize", sub_matches)) = matches.subcommand() {
+        return ImgxCommand::WebOptimize(get_web_optimize_args(sub_matches));
+    }
 
     let convert = if let Some(convert) = matches.get_one::<String>("convert") {
         Some(convert.to_image_format())
@@ -88,7 +546,7 @@ pub fn get_cli_arguments() -> EditArgs {
         None
     };
 
-    EditArgs {
+    ImgxCommand::Edit(EditArgs {
         input_files: matches
             .get_many::<String>("input-files")
             .unwrap_or_default()
@@ -97,6 +555,311 @@ pub fn get_cli_arguments() -> EditArgs {
         resize: matches.get_one::<ResizeSpec>("resize").cloned(),
         grayscale: matches.get_flag("grayscale"),
         convert,
+        watermark: get_watermark_spec(&matches),
+        text: get_text_spec(&matches),
+        metadata: MetadataEdit {
+            strip: matches.get_flag("strip-metadata"),
+            artist: matches.get_one::<String>("artist").cloned(),
+            copyright: matches.get_one::<String>("copyright").cloned(),
+            datetime: matches.get_one::<String>("datetime").cloned(),
+        },
+        pdf: get_pdf_operation(&matches),
+        globs: matches
+            .get_many::<String>("glob")
+            .unwrap_or_default()
+            .cloned()
+            .collect(),
+        output_dir: matches.get_one::<PathBuf>("output-dir").cloned(),
+        jobs: matches
+            .get_one::<usize>("jobs")
+            .copied()
+            .unwrap_or_else(num_cpus::get),
+    })
+}
+
+fn get_web_optimize_args(matches: &clap::ArgMatches) -> WebOptimizeArgs {
+    let mut formats: Vec<WebFormat> = matches
+        .get_many::<WebFormat>("format")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+    if formats.is_empty() {
+        formats.push(WebFormat::Webp);
+    }
+    formats.sort();
+    formats.dedup();
+
+    let mut widths: Vec<u32> = matches
+        .get_many::<u32>("widths")
+        .unwrap_or_default()
+        .copied()
+        .collect();
+    if widths.is_empty() {
+        widths.extend(DEFAULT_WEB_WIDTHS);
+    }
+    widths.sort_unstable();
+    widths.dedup();
+
+    WebOptimizeArgs {
+        input_files: matches
+            .get_many::<String>("input-files")
+            .unwrap_or_default()
+            .map(|s| s.into())
+            .collect(),
+        formats,
+        quality: matches
+            .get_one::<u8>("quality")
+            .copied()
+            .unwrap_or(DEFAULT_WEB_QUALITY),
+        widths,
+        sizes: matches
+            .get_one::<String>("sizes")
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_WEB_SIZES.to_string()),
+        url_prefix: matches
+            .get_one::<String>("url-prefix")
+            .cloned()
+            .unwrap_or_default(),
+        output_dir: matches.get_one::<PathBuf>("output-dir").cloned(),
+        jobs: matches
+            .get_one::<usize>("jobs")
+            .copied()
+            .unwrap_or_else(num_cpus::get),
+    }
+}
+
+fn get_watermark_spec(matches: &clap::ArgMatches) -> Option<WatermarkSpec> {
+    let file = matches.get_one::<PathBuf>("watermark")?;
+
+    Some(WatermarkSpec {
+        file: file.clone(),
+        position: matches
+            .get_one::<OverlayPosition>("watermark-position")
+            .copied()
+            .unwrap_or(OverlayPosition::BottomRight),
+        opacity: matches
+            .get_one::<f64>("watermark-opacity")
+            .copied()
+            .unwrap_or(100.0),
+        scale: matches.get_one::<f64>("watermark-scale").copied(),
+    })
+}
+
+fn get_text_spec(matches: &clap::ArgMatches) -> Option<TextSpec> {
+    let text = matches.get_one::<String>("text")?;
+
+    Some(TextSpec {
+        // Shells make it hard to pass a real line break, so `\n` is accepted as well.
+        text: text.replace("\\n", "\n"),
+        font: matches.get_one::<PathBuf>("font").cloned(),
+        size: matches
+            .get_one::<f32>("text-size")
+            .copied()
+            .unwrap_or(DEFAULT_TEXT_SIZE),
+        color: matches
+            .get_one::<Rgba<u8>>("text-color")
+            .copied()
+            .unwrap_or(Rgba([255, 255, 255, 255])),
+        position: matches
+            .get_one::<OverlayPosition>("text-position")
+            .copied()
+            .unwrap_or(OverlayPosition::BottomLeft),
+    })
+}
+
+fn position_name(position: OverlayPosition) -> String {
+    position
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+fn get_pdf_operation(matches: &clap::ArgMatches) -> Option<PdfOperation> {
+    if matches.get_flag("rasterize") {
+        let dpi = matches
+            .get_one::<f32>("dpi")
+            .copied()
+            .unwrap_or(DEFAULT_DPI);
+        return Some(PdfOperation::Rasterize { dpi });
+    }
+
+    let output_file = matches.get_one::<PathBuf>("to-pdf")?;
+    let page_size = matches
+        .get_one::<PageSize>("page-size")
+        .copied()
+        .unwrap_or_else(|| parse_page_size(DEFAULT_PAGE_SIZE).unwrap());
+
+    Some(PdfOperation::Assemble(PdfAssembleOptions {
+        output_file: output_file.clone(),
+        page_size,
+        margin_mm: matches
+            .get_one::<f32>("margin")
+            .copied()
+            .unwrap_or(DEFAULT_MARGIN_MM),
+    }))
+}
+
+fn parse_page_size(value: &str) -> Result<PageSize, String> {
+    let fixed = |width_mm: f32, height_mm: f32| PageSize::Fixed {
+        width_mm,
+        height_mm,
+    };
+
+    let trimmed = value.trim().to_lowercase();
+    match trimmed.as_str() {
+        "fit" => return Ok(PageSize::Fit),
+        "a3" => return Ok(fixed(297.0, 420.0)),
+        "a4" => return Ok(fixed(210.0, 297.0)),
+        "a5" => return Ok(fixed(148.0, 210.0)),
+        "letter" => return Ok(fixed(215.9, 279.4)),
+        "legal" => return Ok(fixed(215.9, 355.6)),
+        _ => {}
+    }
+
+    let Some((width_str, height_str)) = trimmed.split_once('x') else {
+        return Err(format!(
+            "Invalid page size: '{}'. Use a3, a4, a5, letter, legal, fit, or WIDTHxHEIGHT in mm.",
+            value.trim()
+        ));
+    };
+
+    let width_mm = parse_positive_f32(width_str, "page width")?;
+    let height_mm = parse_positive_f32(height_str, "page height")?;
+
+    Ok(fixed(width_mm, height_mm))
+}
+
+fn parse_dpi(value: &str) -> Result<f32, String> {
+    parse_positive_f32(value, "DPI")
+}
+
+fn parse_margin(value: &str) -> Result<f32, String> {
+    let trimmed = value.trim();
+    match trimmed.parse::<f32>() {
+        Ok(margin) if margin >= 0.0 && margin.is_finite() => Ok(margin),
+        _ => Err(format!(
+            "Invalid margin: '{}'. Expected a number of millimeters, 0 or greater.",
+            trimmed
+        )),
+    }
+}
+
+fn parse_text_size(value: &str) -> Result<f32, String> {
+    parse_positive_f32(value, "text size")
+}
+
+fn parse_opacity(value: &str) -> Result<f64, String> {
+    let trimmed = value.trim();
+    let percent_str = trimmed.strip_suffix('%').unwrap_or(trimmed);
+    match percent_str.trim().parse::<f64>() {
+        Ok(opacity) if (0.0..=100.0).contains(&opacity) => Ok(opacity),
+        _ => Err(format!(
+            "Invalid opacity: '{}'. Expected a percentage from 0 to 100.",
+            trimmed
+        )),
+    }
+}
+
+fn parse_watermark_scale(value: &str) -> Result<f64, String> {
+    let trimmed = value.trim();
+    let percent_str = trimmed.strip_suffix('%').unwrap_or(trimmed);
+    match percent_str.trim().parse::<f64>() {
+        Ok(scale) if scale > 0.0 && scale <= 100.0 => Ok(scale),
+        _ => Err(format!(
+            "Invalid watermark scale: '{}'. Expected a percentage greater than 0, up to 100.",
+            trimmed
+        )),
+    }
+}
+
+fn parse_color(value: &str) -> Result<Rgba<u8>, String> {
+    let trimmed = value.trim().to_lowercase();
+    let named = match trimmed.as_str() {
+        "white" => Some([255, 255, 255, 255]),
+        "black" => Some([0, 0, 0, 255]),
+        "red" => Some([255, 0, 0, 255]),
+        "green" => Some([0, 128, 0, 255]),
+        "blue" => Some([0, 0, 255, 255]),
+        "yellow" => Some([255, 255, 0, 255]),
+        "gray" | "grey" => Some([128, 128, 128, 255]),
+        _ => None,
+    };
+    if let Some(color) = named {
+        return Ok(Rgba(color));
+    }
+
+    let invalid = || {
+        format!(
+            "Invalid color: '{}'. Use #RRGGBB, #RRGGBBAA or a color name.",
+            value.trim()
+        )
+    };
+
+    let hex = trimmed.strip_prefix('#').unwrap_or(&trimmed);
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let channel =
+        |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| invalid());
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+
+    Ok(Rgba([channel(0)?, channel(2)?, channel(4)?, alpha]))
+}
+
+/// Parses the `--datetime` value into the EXIF format (`YYYY:MM:DD HH:MM:SS`).
+fn parse_exif_datetime(value: &str) -> Result<String, String> {
+    let trimmed = value.trim();
+    if trimmed.eq_ignore_ascii_case("now") {
+        return Ok(Local::now().format(EXIF_DATETIME_FORMAT).to_string());
+    }
+
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        EXIF_DATETIME_FORMAT,
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+    .map(|datetime| datetime.format(EXIF_DATETIME_FORMAT).to_string())
+    .ok_or_else(|| {
+        format!(
+            "Invalid date/time: '{}'. Use 'YYYY-MM-DD HH:MM:SS', or 'now'.",
+            trimmed
+        )
+    })
+}
+
+fn parse_width(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+    let pixels = trimmed.strip_suffix("px").unwrap_or(trimmed);
+    match pixels.trim().parse::<u32>() {
+        Ok(width) if width > 0 => Ok(width),
+        _ => Err(format!(
+            "Invalid width: '{}'. Expected a number of pixels greater than 0.",
+            trimmed
+        )),
+    }
+}
+
+fn parse_jobs(value: &str) -> Result<usize, String> {
+    match value.trim().parse::<usize>() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(format!(
+            "Invalid number of jobs: '{}'. Expected a number greater than 0.",
+            value.trim()
+        )),
+    }
+}
+
+fn parse_positive_f32(value: &str, label: &str) -> Result<f32, String> {
+    let trimmed = value.trim();
+    match trimmed.parse::<f32>() {
+        Ok(parsed) if parsed > 0.0 && parsed.is_finite() => Ok(parsed),
+        _ => Err(format!(
+            "Invalid {}: '{}'. Expected a number greater than 0.",
+            label, trimmed
+        )),
     }
 }
 
@@ -139,3 +902,79 @@ fn parse_positive_decimal(value: &str, label: &str) -> Result<f64, String> {
 
     Ok(parsed)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_page_size() {
+        assert_eq!(parse_page_size("fit").unwrap(), PageSize::Fit);
+        assert_eq!(
+            parse_page_size(" A4 ").unwrap(),
+            PageSize::Fixed {
+                width_mm: 210.0,
+                height_mm: 297.0
+            }
+        );
+        assert_eq!(
+            parse_page_size("100x150.5").unwrap(),
+            PageSize::Fixed {
+                width_mm: 100.0,
+                height_mm: 150.5
+            }
+        );
+        assert!(parse_page_size("b4").is_err());
+        assert!(parse_page_size("0x150").is_err());
+    }
+
+    #[test]
+    fn test_parse_margin() {
+        assert_eq!(parse_margin("0").unwrap(), 0.0);
+        assert_eq!(parse_margin("12.5").unwrap(), 12.5);
+        assert!(parse_margin("-1").is_err());
+        assert!(parse_margin("wide").is_err());
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("White").unwrap(), Rgba([255, 255, 255, 255]));
+        assert_eq!(parse_color("#ff8000").unwrap(), Rgba([255, 128, 0, 255]));
+        assert_eq!(parse_color("00000080").unwrap(), Rgba([0, 0, 0, 128]));
+        assert!(parse_color("#fff").is_err());
+        assert!(parse_color("#gg0000").is_err());
+        assert!(parse_color("purple-ish").is_err());
+    }
+
+    #[test]
+    fn test_parse_exif_datetime() {
+        assert_eq!(
+            parse_exif_datetime("2024-07-01 18:30:05").unwrap(),
+            "2024:07:01 18:30:05"
+        );
+        assert_eq!(
+            parse_exif_datetime("2024-07-01T18:30:05").unwrap(),
+            "2024:07:01 18:30:05"
+        );
+        assert_eq!(parse_exif_datetime("NOW").unwrap().len(), 19);
+        assert!(parse_exif_datetime("2024-07-01").is_err());
+        assert!(parse_exif_datetime("2024-13-01 10:00:00").is_err());
+    }
+
+    #[test]
+    fn test_parse_width() {
+        assert_eq!(parse_width("480").unwrap(), 480);
+        assert_eq!(parse_width(" 960px ").unwrap(), 960);
+        assert!(parse_width("0").is_err());
+        assert!(parse_width("wide").is_err());
+    }
+
+    #[test]
+    fn test_parse_opacity() {
+        assert_eq!(parse_opacity("50").unwrap(), 50.0);
+        assert_eq!(parse_opacity("35%").unwrap(), 35.0);
+        assert_eq!(parse_opacity("0").unwrap(), 0.0);
+        assert!(parse_opacity("101").is_err());
+        assert!(parse_opacity("-5").is_err());
+    }
+}
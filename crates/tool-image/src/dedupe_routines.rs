@@ -0,0 +1,403 @@
+use crate::batch_inputs::expand_inputs;
+use crate::image_app::is_supported_image_file;
+use crate::models::{DedupeArgs, HashAlgorithm};
+use anyhow::{anyhow, Result};
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, GrayImage, ImageDecoder, ImageReader};
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use shared::system::pathbuf_extensions::PathBufExtensions;
+use shared::utils::format_bytes_to_string::format_bytes_to_string;
+use std::f64::consts::PI;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Both hashes have 8x8 = 64 bits.
+const HASH_SIDE: usize = 8;
+/// pHash takes the lowest frequencies of the DCT of a 32x32 version of the image.
+const PHASH_SIDE: usize = 32;
+
+/// An image, with its perceptual hash.
+#[derive(Debug, Clone)]
+pub struct HashedImage {
+    pub file: PathBuf,
+    pub hash: u64,
+    pub width: u32,
+    pub height: u32,
+    /// File size, in bytes.
+    pub size: u64,
+}
+
+/// `imgx dedupe`: hashes the inputs, prints the groups of images that look alike and, with
+/// `--move-to`, moves the duplicates away.
+pub fn find_duplicates(args: &DedupeArgs) -> Result<()> {
+    let files: Vec<PathBuf> = expand_inputs(&args.input_files, &[], None, is_supported_image_file)?
+        .into_iter()
+        .map(|input| input.file)
+        // Duplicates moved by a previous run aren't duplicates of themselves.
+        .filter(|file| {
+            !args
+                .move_to
+                .as_ref()
+                .is_some_and(|dir| is_inside(file, dir))
+        })
+        .collect();
+
+    if files.is_empty() {
+        return Err(anyhow!("No images found. Nothing to work with."));
+    }
+
+    let images = hash_images(files, args)?;
+    let groups = group_similar(&images, args.threshold);
+    print_groups(&images, &groups);
+
+    if let Some(dir) = &args.move_to {
+        if !groups.is_empty() {
+            move_duplicates(&images, &groups, dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn hash_images(files: Vec<PathBuf>, args: &DedupeArgs) -> Result<Vec<HashedImage>> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs)
+        .build()?;
+
+    let progress_bar = ProgressBar::new(files.len() as u64);
+    progress_bar.set_style(ProgressStyle::default_bar().template(
+        "{spinner:.blue} [{elapsed_precise}] [Hashing] {bar:50.green/black} {pos}/{len} {msg}",
+    )?);
+
+    let results: Vec<(PathBuf, Result<HashedImage>)> = pool.install(|| {
+        files
+            .into_par_iter()
+            .map(|file| {
+                let result = hash_image_file(&file, args.hash);
+                progress_bar.inc(1);
+                (file, result)
+            })
+            .collect()
+    });
+    progress_bar.finish_and_clear();
+
+    let mut images = Vec::with_capacity(results.len());
+    for (file, result) in results {
+        match result {
+            Ok(image) => images.push(image),
+            Err(e) => warn!("Skipping {}: {}", file.display(), e),
+        }
+    }
+
+    Ok(images)
+}
+
+fn hash_image_file(file: &Path, algorithm: HashAlgorithm) -> Result<HashedImage> {
+    let image = load_oriented_image(file)?;
+    let hash = perceptual_hash(&image, algorithm);
+    debug!("{}: {:016x}", file.display(), hash);
+
+    Ok(HashedImage {
+        file: file.to_path_buf(),
+        hash,
+        width: image.width(),
+        height: image.height(),
+        size: fs::metadata(file)?.len(),
+    })
+}
+
+/// The image (the first frame, for animations), with the EXIF orientation applied, so a rotated
+/// copy of a photo still matches the original.
+fn load_oriented_image(file: &Path) -> Result<DynamicImage> {
+    let mut decoder = ImageReader::open(file)?
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+pub fn perceptual_hash(image: &DynamicImage, algorithm: HashAlgorithm) -> u64 {
+    match algorithm {
+        HashAlgorithm::Ahash => average_hash(image),
+        HashAlgorithm::Phash => dct_hash(image),
+    }
+}
+
+/// One bit per pixel of the 8x8 grayscale image: set when the pixel is brighter than the average.
+fn average_hash(image: &DynamicImage) -> u64 {
+    let pixels: Vec<f64> = shrink(image, HASH_SIDE)
+        .pixels()
+        .map(|pixel| pixel.0[0] as f64)
+        .collect();
+
+    bits_above(&pixels, pixels.iter().sum::<f64>() / pixels.len() as f64)
+}
+
+/// One bit per coefficient of the 8x8 lowest frequencies of the DCT of the 32x32 grayscale image:
+/// set when the coefficient is above the median.
+fn dct_hash(image: &DynamicImage) -> u64 {
+    let pixels: Vec<f64> = shrink(image, PHASH_SIDE)
+        .pixels()
+        .map(|pixel| pixel.0[0] as f64)
+        .collect();
+
+    let cosines: Vec<f64> = (0..HASH_SIDE)
+        .flat_map(|frequency| {
+            (0..PHASH_SIDE).map(move |x| {
+                ((2 * x + 1) as f64 * frequency as f64 * PI / (2 * PHASH_SIDE) as f64).cos()
+            })
+        })
+        .collect();
+    let cosine = |frequency: usize, x: usize| cosines[frequency * PHASH_SIDE + x];
+
+    // Rows first, then columns, keeping only the frequencies the hash uses.
+    let mut rows = vec![0.0; PHASH_SIDE * HASH_SIDE];
+    for y in 0..PHASH_SIDE {
+        for u in 0..HASH_SIDE {
+            rows[y * HASH_SIDE + u] = (0..PHASH_SIDE)
+                .map(|x| pixels[y * PHASH_SIDE + x] * cosine(u, x))
+                .sum();
+        }
+    }
+
+    let mut coefficients = vec![0.0; HASH_SIDE * HASH_SIDE];
+    for v in 0..HASH_SIDE {
+        for u in 0..HASH_SIDE {
+            coefficients[v * HASH_SIDE + u] = (0..PHASH_SIDE)
+                .map(|y| rows[y * HASH_SIDE + u] * cosine(v, y))
+                .sum();
+        }
+    }
+
+    let mut sorted = coefficients.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0;
+
+    bits_above(&coefficients, median)
+}
+
+fn shrink(image: &DynamicImage, side: usize) -> GrayImage {
+    image
+        .resize_exact(side as u32, side as u32, FilterType::Triangle)
+        .to_luma8()
+}
+
+fn bits_above(values: &[f64], reference: f64) -> u64 {
+    values.iter().fold(0u64, |hash, value| {
+        (hash << 1) | (*value > reference) as u64
+    })
+}
+
+/// Number of different bits: 0 for images that look the same, up to 64.
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Groups (with 2 images or more) of images whose hashes differ by `threshold` bits or less,
+/// directly or through other images of the group.
+///
+/// The first image of each group is the one kept: the one with the most pixels, then the largest
+/// file. Groups are sorted by the path of that image.
+pub fn group_similar(images: &[HashedImage], threshold: u32) -> Vec<Vec<usize>> {
+    let mut parents: Vec<usize> = (0..images.len()).collect();
+
+    fn root(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
+
+    for a in 0..images.len() {
+        for b in a + 1..images.len() {
+            if hamming_distance(images[a].hash, images[b].hash) <= threshold {
+                let (root_a, root_b) = (root(&mut parents, a), root(&mut parents, b));
+                parents[root_b] = root_a;
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of_root = vec![usize::MAX; images.len()];
+    for index in 0..images.len() {
+        let group_root = root(&mut parents, index);
+        if group_of_root[group_root] == usize::MAX {
+            group_of_root[group_root] = groups.len();
+            groups.push(Vec::new());
+        }
+        groups[group_of_root[group_root]].push(index);
+    }
+
+    groups.retain(|group| group.len() > 1);
+    for group in groups.iter_mut() {
+        group.sort_by(|&a, &b| {
+            let (a, b) = (&images[a], &images[b]);
+            let pixels = |image: &HashedImage| image.width as u64 * image.height as u64;
+            pixels(b)
+                .cmp(&pixels(a))
+                .then(b.size.cmp(&a.size))
+                .then(a.file.cmp(&b.file))
+        });
+    }
+    groups.sort_by(|a, b| images[a[0]].file.cmp(&images[b[0]].file));
+
+    groups
+}
+
+fn print_groups(images: &[HashedImage], groups: &[Vec<usize>]) {
+    if groups.is_empty() {
+        println!("No duplicates found in {} images.", images.len());
+        return;
+    }
+
+    let describe = |image: &HashedImage| {
+        format!(
+            "{} ({}x{}, {})",
+            image.file.display(),
+            image.width,
+            image.height,
+            format_bytes_to_string(&image.size)
+        )
+    };
+
+    let mut duplicate_count = 0;
+    let mut duplicate_size = 0;
+    for (number, group) in groups.iter().enumerate() {
+        let kept = &images[group[0]];
+        println!("Group {} ({} images):", number + 1, group.len());
+        println!("  keep  {}", describe(kept));
+
+        for &index in &group[1..] {
+            let duplicate = &images[index];
+            println!(
+                "  dup   {} [distance: {}]",
+                describe(duplicate),
+                hamming_distance(kept.hash, duplicate.hash)
+            );
+            duplicate_count += 1;
+            duplicate_size += duplicate.size;
+        }
+        println!();
+    }
+
+    println!(
+        "Found {} duplicates ({}) in {} groups, out of {} images.",
+        duplicate_count,
+        format_bytes_to_string(&duplicate_size),
+        groups.len(),
+        images.len()
+    );
+}
+
+/// Moves every image of the groups, but the first one, to `dir`. Files with the same name get a
+/// counter (`photo (1).jpg`) instead of being overwritten.
+fn move_duplicates(images: &[HashedImage], groups: &[Vec<usize>], dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for group in groups {
+        for &index in &group[1..] {
+            let source = &images[index].file;
+            let Some(file_name) = source.file_name() else {
+                continue;
+            };
+
+            let target = dir.join(file_name).next_available_file();
+            move_file(source, &target).map_err(|e| {
+                anyhow!(
+                    "Failed to move {} to {}: {}",
+                    source.display(),
+                    target.display(),
+                    e
+                )
+            })?;
+            println!("Moved {} -> {}", source.display(), target.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Renames the file, or copies and deletes it when that fails (e.g.: another drive).
+fn move_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+
+    fs::copy(source, target)?;
+    fs::remove_file(source)
+}
+
+fn is_inside(file: &Path, dir: &Path) -> bool {
+    match (file.canonicalize(), dir.canonicalize()) {
+        (Ok(file), Ok(dir)) => file.starts_with(dir),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn pattern_image(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let fx = x as f64 / width as f64;
+            let fy = y as f64 / height as f64;
+            let wave = ((fx * 7.0).sin() * (fy * 5.0).cos() * 100.0) as i32;
+            let value = (fx * 120.0 + fy * 60.0) as i32 + wave;
+            Rgb([
+                value.clamp(0, 255) as u8,
+                (fx * 255.0) as u8,
+                (fy * 255.0) as u8,
+            ])
+        }))
+    }
+
+    fn hashed(file: &str, hash: u64, width: u32, size: u64) -> HashedImage {
+        HashedImage {
+            file: PathBuf::from(file),
+            hash,
+            width,
+            height: 100,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_hashes_match_resized_and_tell_different_images_apart() {
+        let original = pattern_image(400, 300);
+        let smaller = original.resize_exact(160, 120, FilterType::Lanczos3);
+        let mut inverted = original.clone();
+        inverted.invert();
+
+        for algorithm in [HashAlgorithm::Ahash, HashAlgorithm::Phash] {
+            let hash = perceptual_hash(&original, algorithm);
+            assert!(hamming_distance(hash, perceptual_hash(&smaller, algorithm)) <= 2);
+            assert!(hamming_distance(hash, perceptual_hash(&inverted, algorithm)) > 20);
+        }
+    }
+
+    #[test]
+    fn test_group_similar() {
+        let images = vec![
+            hashed("b.jpg", 0b0000, 100, 10),
+            hashed("a.jpg", 0b0011, 200, 10),
+            hashed("c.jpg", u64::MAX, 100, 10),
+            // Close to a.jpg, but not to b.jpg: still in the same group.
+            hashed("d.jpg", 0b1111, 100, 20),
+            hashed("e.jpg", u64::MAX - 1, 100, 10),
+        ];
+
+        let groups = group_similar(&images, 2);
+        assert_eq!(groups, vec![vec![1, 3, 0], vec![2, 4]]);
+        assert!(group_similar(&images, 0).is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::models::MetadataEdit;
+use anyhow::{anyhow, Context, Result};
+use exif::experimental::Writer;
+use exif::{Exif, Field, In, Reader, Tag, Value};
+use image::{ImageDecoder, ImageReader};
+use std::io::Cursor;
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// Tags pointing to other IFDs, or to the thumbnail. The writer creates the pointers on its own,
+/// and the thumbnail is dropped, since it would still show the original image.
+const POINTER_TAGS: &[Tag] = &[
+    Tag::ExifIFDPointer,
+    Tag::GPSInfoIFDPointer,
+    Tag::InteropIFDPointer,
+    Tag::JPEGInterchangeFormat,
+    Tag::JPEGInterchangeFormatLength,
+];
+
+/// Reads the raw EXIF (TIFF structure) of an image, without decoding the pixels.
+pub fn read_exif(path: &Path) -> Result<Option<Vec<u8>>> {
+    let mut decoder = ImageReader::open(path)
+        .context(format!("Failed to open image: {}", path.display()))?
+        .with_guessed_format()?
+        .into_decoder()?;
+
+    Ok(decoder.exif_metadata()?)
+}
+
+/// Parses raw EXIF, as returned by the decoders.
+pub fn parse_exif(raw: Vec<u8>) -> Result<Exif> {
+    Reader::new()
+        .read_raw(raw)
+        .map_err(|e| anyhow!("Invalid EXIF metadata: {}", e))
+}
+
+/// Fields of the main image as (tag, value) pairs, in the order they are stored. The maker note
+/// (a vendor-specific binary blob) is left out.
+pub fn describe_exif(exif: &Exif) -> Vec<(String, String)> {
+    exif.fields()
+        .filter(|field| is_primary_field(field) && field.tag != Tag::MakerNote)
+        .map(|field| {
+            (
+                field.tag.to_string(),
+                field.display_value().with_unit(exif).to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Builds the EXIF of an edited image, from the EXIF of the original:
+/// - Orientation is reset, since it was already applied to the pixels.
+/// - Pixel dimensions are updated to the size of the edited image.
+/// - With `strip`, nothing of the original is kept, only the fields set by the user.
+///
+/// Returns `None` when there's nothing to write.
+pub fn build_output_exif(
+    original: Option<&[u8]>,
+    edit: &MetadataEdit,
+    width: u32,
+    height: u32,
+) -> Result<Option<Vec<u8>>> {
+    let mut fields: Vec<Field> = match original {
+        Some(raw) if !edit.strip => match parse_exif(raw.to_vec()) {
+            Ok(exif) => exif
+                .fields()
+                .filter(|field| {
+                    is_primary_field(field) && !matches!(field.value, Value::Unknown(..))
+                })
+                .cloned()
+                .collect(),
+            Err(e) => {
+                warn!("Dropping EXIF metadata of the original image: {}", e);
+                Vec::new()
+            }
+        },
+        _ => Vec::new(),
+    };
+
+    update_field(&mut fields, Tag::Orientation, Value::Short(vec![1]));
+    update_field(&mut fields, Tag::PixelXDimension, Value::Long(vec![width]));
+    update_field(&mut fields, Tag::PixelYDimension, Value::Long(vec![height]));
+
+    if let Some(artist) = &edit.artist {
+        set_field(&mut fields, Tag::Artist, ascii(artist));
+    }
+
+    if let Some(copyright) = &edit.copyright {
+        set_field(&mut fields, Tag::Copyright, ascii(copyright));
+    }
+
+    if let Some(datetime) = &edit.datetime {
+        set_field(&mut fields, Tag::DateTime, ascii(datetime));
+        set_field(&mut fields, Tag::DateTimeOriginal, ascii(datetime));
+    }
+
+    if fields.is_empty() {
+        return Ok(None);
+    }
+
+    let mut writer = Writer::new();
+    for field in &fields {
+        writer.push_field(field);
+    }
+
+    let mut buffer = Cursor::new(Vec::new());
+    writer
+        .write(&mut buffer, false)
+        .map_err(|e| anyhow!("Failed to write EXIF metadata: {}", e))?;
+
+    debug!("EXIF metadata with {} field(s)", fields.len());
+    Ok(Some(buffer.into_inner()))
+}
+
+fn is_primary_field(field: &Field) -> bool {
+    field.ifd_num == In::PRIMARY && !POINTER_TAGS.contains(&field.tag)
+}
+
+fn ascii(value: &str) -> Value {
+    Value::Ascii(vec![value.as_bytes().to_vec()])
+}
+
+/// Replaces the value of a field, if the image has it.
+fn update_field(fields: &mut [Field], tag: Tag, value: Value) {
+    if let Some(field) = fields.iter_mut().find(|field| field.tag == tag) {
+        field.value = value;
+    }
+}
+
+/// Replaces the value of a field, adding it if the image doesn't have it.
+fn set_field(fields: &mut Vec<Field>, tag: Tag, value: Value) {
+    match fields.iter_mut().find(|field| field.tag == tag) {
+        Some(field) => field.value = value,
+        None => fields.push(Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn original_exif() -> Vec<u8> {
+        let fields = [
+            Field {
+                tag: Tag::Make,
+                ifd_num: In::PRIMARY,
+                value: ascii("Raccoon Cameras"),
+            },
+            Field {
+                tag: Tag::Orientation,
+                ifd_num: In::PRIMARY,
+                value: Value::Short(vec![6]),
+            },
+        ];
+
+        let mut writer = Writer::new();
+        for field in &fields {
+            writer.push_field(field);
+        }
+        let mut buffer = Cursor::new(Vec::new());
+        writer.write(&mut buffer, true).unwrap();
+        buffer.into_inner()
+    }
+
+    fn field_value(raw: Vec<u8>, tag: Tag) -> Option<String> {
+        let exif = parse_exif(raw).unwrap();
+        exif.get_field(tag, In::PRIMARY)
+            .map(|field| field.display_value().to_string())
+    }
+
+    #[test]
+    fn test_original_fields_are_kept_and_orientation_is_reset() {
+        let edit = MetadataEdit {
+            artist: Some("Jane Doe".to_string()),
+            ..Default::default()
+        };
+
+        let raw = build_output_exif(Some(&original_exif()), &edit, 640, 480)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            field_value(raw.clone(), Tag::Make),
+            Some("\"Raccoon Cameras\"".to_string())
+        );
+        assert_eq!(
+            field_value(raw.clone(), Tag::Artist),
+            Some("\"Jane Doe\"".to_string())
+        );
+        let exif = parse_exif(raw).unwrap();
+        let orientation = exif.get_field(Tag::Orientation, In::PRIMARY).unwrap();
+        assert_eq!(orientation.value.get_uint(0), Some(1));
+    }
+
+    #[test]
+    fn test_strip_keeps_only_the_fields_set() {
+        let strip = MetadataEdit {
+            strip: true,
+            ..Default::default()
+        };
+        assert!(build_output_exif(Some(&original_exif()), &strip, 640, 480)
+            .unwrap()
+            .is_none());
+
+        let strip_and_set = MetadataEdit {
+            strip: true,
+            copyright: Some("(c) 2026 Jane Doe".to_string()),
+            ..Default::default()
+        };
+        let raw = build_output_exif(Some(&original_exif()), &strip_and_set, 640, 480)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(field_value(raw.clone(), Tag::Make), None);
+        assert!(field_value(raw, Tag::Copyright).is_some());
+    }
+}
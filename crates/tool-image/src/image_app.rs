@@ -1,21 +1,33 @@
-use crate::image_edit_routines::{create_job_progress_bar, process_edit_job};
-use crate::models::{EditArgs, EditJob, ProcessingStatsInner};
+use crate::batch_inputs::{expand_inputs, BatchInput};
+use crate::exif_metadata::{describe_exif, parse_exif, read_exif};
+use crate::image_edit_routines::{
+    create_job_progress_bar, load_text_overlay, load_watermark, process_edit_job,
+};
+use crate::models::{EditArgs, EditJob, ExifArgs, ProcessingStatsInner, WebOptimizeArgs};
+use crate::web_optimize_routines::{
+    create_web_progress_bar, find_output_clash, optimize_for_web, render_srcset_snippet,
+};
 use anyhow::{anyhow, Result};
 use indicatif::MultiProgress;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
-use shared::system::folder_walkthrough::list_all_files_recursively;
-use std::collections::HashSet;
+use shared::constants::general::DASH_LINE;
+use shared::system::pathbuf_extensions::PathBufExtensions;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 pub fn run_image_edit_commands(args: &EditArgs) -> Result<()> {
-    let input_batch = expand_input_paths(&args.input_files)?;
+    let input_batch = expand_inputs(
+        &args.input_files,
+        &args.globs,
+        args.output_dir.as_deref(),
+        is_supported_image_file,
+    )?;
     let jobs = build_jobs(input_batch, args)?;
     let progress_bar = MultiProgress::new();
     let pool = rayon::ThreadPoolBuilder::new()
-        .num_threads(num_cpus::get())
+        .num_threads(args.jobs)
         .build()?;
 
     // Shared statistics
@@ -70,58 +82,134 @@ pub fn run_image_edit_commands(args: &EditArgs) -> Result<()> {
     Ok(())
 }
 
-fn expand_input_paths(paths: &Vec<PathBuf>) -> Result<HashSet<PathBuf>> {
-    let mut expanded_paths = HashSet::new();
+/// `imgx exif`: prints the EXIF metadata of each input, one block per file.
+pub fn print_exif_metadata(args: &ExifArgs) -> Result<()> {
+    let inputs = expand_inputs(&args.input_files, &[], None, is_supported_image_file)?;
 
-    for path in paths {
-        if !path.exists() {
-            debug!("Path does not exist: {}", path.display());
-            continue;
+    for (index, input) in inputs.iter().enumerate() {
+        if index > 0 {
+            println!();
         }
 
-        if path.is_file() && is_supported_image_file(path) {
-            expanded_paths.insert(path.to_path_buf());
-            continue;
-        }
+        println!("{}", input.file.display());
+        println!("{}", DASH_LINE);
 
-        for file in list_all_files_recursively(path) {
-            if !is_supported_image_file(&file) {
+        let exif = read_exif(&input.file).and_then(|raw| raw.map(parse_exif).transpose());
+        let fields = match exif {
+            Ok(Some(exif)) => describe_exif(&exif),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                println!("Failed to read metadata: {}", e);
                 continue;
             }
-            expanded_paths.insert(file);
+        };
+
+        if fields.is_empty() {
+            println!("No EXIF metadata.");
+            continue;
         }
-    }
 
-    if expanded_paths.is_empty() {
-        return Err(anyhow!(
-            "No supported image files found. Nothing to work with."
-        ));
+        let width = fields.iter().map(|(tag, _)| tag.len()).max().unwrap_or(0);
+        for (tag, value) in fields {
+            println!("{:<width$}  {}", tag, value, width = width);
+        }
     }
 
-    info!("Found {} supported image files.", expanded_paths.len());
-    Ok(expanded_paths)
+    Ok(())
 }
 
-fn is_supported_image_file(path: &PathBuf) -> bool {
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        let ext = ext.to_lowercase();
-        matches!(
-            ext.as_str(),
-            "jpg" | "jpeg" | "png" | "gif" | "webp" | "avif" | "tiff" | "tif" | "bmp"
-        )
-    } else {
-        false
+/// `imgx web-optimize`: writes every width, in every format, of each input, then prints the HTML
+/// snippets, in the order of the inputs.
+pub fn run_web_optimize(args: &WebOptimizeArgs) -> Result<()> {
+    let inputs = expand_inputs(
+        &args.input_files,
+        &[],
+        args.output_dir.as_deref(),
+        is_supported_image_file,
+    )?;
+    if inputs.is_empty() {
+        return Err(anyhow!("No images found. Nothing to work with."));
     }
+    find_output_clash(&inputs)?;
+
+    let progress_bar = MultiProgress::new();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs)
+        .build()?;
+
+    let results: Vec<(PathBuf, Result<_>)> = pool.install(|| {
+        inputs
+            .into_par_iter()
+            .map(|input| {
+                let result = create_web_progress_bar(&input, &progress_bar).and_then(|pb| {
+                    let outputs = optimize_for_web(&input, args, &pb);
+                    match outputs {
+                        Ok(_) => pb.finish_with_message("✓ Completed"),
+                        Err(_) => pb.finish_with_message("✗ Failed"),
+                    }
+                    outputs
+                });
+                (input.file, result)
+            })
+            .collect()
+    });
+
+    println!();
+    let mut error_count = 0;
+    for (file, result) in &results {
+        match result {
+            Ok(outputs) => {
+                let snippet =
+                    render_srcset_snippet(file, outputs, args, args.output_dir.as_deref());
+                println!(
+                    "{}
+",
+                    snippet
+                );
+            }
+            Err(e) => {
+                warn!("Failed to optimize {}: {}", file.display(), e);
+                error_count += 1;
+            }
+        }
+    }
+
+    info!(
+        "Finished optimizing {} images: {} succeeded, {} failed",
+        results.len(),
+        results.len() - error_count,
+        error_count
+    );
+
+    Ok(())
 }
 
-fn build_jobs(expanded_paths: HashSet<PathBuf>, args: &EditArgs) -> Result<Vec<EditJob>> {
+pub fn is_supported_image_file(path: &PathBuf) -> bool {
+    path.is_image()
+}
+
+fn build_jobs(input_batch: Vec<BatchInput>, args: &EditArgs) -> Result<Vec<EditJob>> {
+    // Loaded once, instead of once per image.
+    let watermark = match &args.watermark {
+        Some(spec) => Some(Arc::new(load_watermark(spec)?)),
+        None => None,
+    };
+    let text = match &args.text {
+        Some(spec) => Some(Arc::new(load_text_overlay(spec)?)),
+        None => None,
+    };
+
     let mut jobs = Vec::new();
-    for path in expanded_paths {
+    for input in input_batch {
         jobs.push(EditJob {
-            input_file: path,
+            input_file: input.file,
+            output_dir: input.output_dir,
             resize: args.resize.clone(),
             grayscale: args.grayscale.clone(),
             convert: args.convert.clone(),
+            watermark: watermark.clone(),
+            text: text.clone(),
+            metadata: args.metadata.clone(),
         })
     }
 
@@ -1,14 +1,26 @@
+use crate::exif_metadata::build_output_exif;
 use crate::image_encoders::{
-    encode_avif, encode_bmp, encode_gif, encode_jpeg, encode_png, encode_webp,
+    encode_animated_gif, encode_animated_webp, encode_avif, encode_bmp, encode_gif, encode_jpeg,
+    encode_png, encode_webp,
 };
 use crate::image_format_traits::ImageFormatTraits;
-use crate::models::{DecodedImage, EditJob, ImageMeta, ResizeSpec};
-use anyhow::Result;
+use crate::models::{
+    AnimationFrame, DecodedImage, EditJob, ImageMeta, OverlayPosition, ResizeSpec, TextOverlay,
+    TextSpec, Watermark, WatermarkSpec,
+};
+use ab_glyph::{point, Font, FontVec, GlyphId, PxScale, PxScaleFont, ScaleFont};
+use anyhow::{anyhow, Context, Result};
+use image::codecs::gif::GifDecoder;
+use image::codecs::webp::WebPDecoder;
 use image::imageops::FilterType;
 use image::metadata::Orientation;
 use image::ImageReader;
-use image::{DynamicImage, ImageDecoder, ImageFormat};
+use image::{
+    imageops, AnimationDecoder, DynamicImage, Frame, ImageDecoder, ImageFormat, Rgba, RgbaImage,
+};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::fs::File;
+use std::io::BufReader;
 use std::path::PathBuf;
 use tracing::{debug, error, info, warn};
 
@@ -33,7 +45,7 @@ pub fn process_edit_job(job: EditJob, progress_bar: &ProgressBar) -> Result<()>
         info!("Resizing image to {}", resize);
         progress_bar.set_message(format!("Resizing image to {}...", resize));
         progress_bar.inc(inc_step);
-        img_info.dynamic_image = apply_resize(img_info.dynamic_image, resize)?;
+        img_info.edit_frames(|image| apply_resize(image, resize))?;
         debug!("Image resized...");
     }
 
@@ -41,10 +53,38 @@ pub fn process_edit_job(job: EditJob, progress_bar: &ProgressBar) -> Result<()>
         info!("Converting image to grayscale");
         progress_bar.set_message("Converting to greyscale...");
         progress_bar.inc(inc_step);
-        img_info.dynamic_image = img_info.dynamic_image.grayscale();
+        img_info.edit_frames(|image| Ok(image.grayscale()))?;
         debug!("Image converted to grayscale...");
     }
 
+    if let Some(watermark) = &job.watermark {
+        info!("Applying watermark {}", watermark.spec.file.display());
+        progress_bar.set_message("Applying watermark...");
+        progress_bar.inc(inc_step);
+        img_info.edit_frames(|image| Ok(apply_watermark(image, watermark)))?;
+        debug!("Watermark applied...");
+    }
+
+    if let Some(text) = &job.text {
+        info!("Drawing text: {}", text.spec.text);
+        progress_bar.set_message("Drawing text...");
+        progress_bar.inc(inc_step);
+        img_info.edit_frames(|image| Ok(apply_text(image, text)))?;
+        debug!("Text drawn...");
+    }
+
+    if !job.metadata.is_empty() {
+        info!("Updating metadata");
+        progress_bar.set_message("Updating metadata...");
+        progress_bar.inc(inc_step);
+    }
+    img_info.image_meta.exif = build_output_exif(
+        img_info.image_meta.exif.as_deref(),
+        &job.metadata,
+        img_info.dynamic_image.width(),
+        img_info.dynamic_image.height(),
+    )?;
+
     info!("Determining output plan...");
     progress_bar.set_message("Determining output plan...");
     progress_bar.inc(inc_step);
@@ -57,12 +97,10 @@ pub fn process_edit_job(job: EditJob, progress_bar: &ProgressBar) -> Result<()>
     }
 
     progress_bar.set_message(format!("Saving image to {}...", output_path.display()));
-    encode_image(
-        &img_info.dynamic_image,
-        &output_path,
-        output_format,
-        &img_info.image_meta,
-    )?;
+    if let Some(output_dir) = &job.output_dir {
+        std::fs::create_dir_all(output_dir)?;
+    }
+    encode_image(&img_info, &output_path, output_format)?;
     progress_bar.inc(inc_step);
 
     debug!(
@@ -84,16 +122,31 @@ pub fn create_job_progress_bar(job: &EditJob, progress_bar: &MultiProgress) -> R
 }
 
 fn encode_image(
-    image: &DynamicImage,
+    img_info: &DecodedImage,
     output_path: &PathBuf,
     output_format: ImageFormat,
-    metadata: &ImageMeta,
 ) -> Result<()> {
+    let image = &img_info.dynamic_image;
+    let metadata = &img_info.image_meta;
+
+    if img_info.is_animated() {
+        match output_format {
+            ImageFormat::Gif => return encode_animated_gif(output_path, &img_info.frames),
+            ImageFormat::WebP => {
+                return encode_animated_webp(output_path, &img_info.frames, metadata)
+            }
+            _ => warn!(
+                "{:?} doesn't support animations. Only the first frame will be saved.",
+                output_format
+            ),
+        }
+    }
+
     match output_format {
         ImageFormat::Png => encode_png(output_path, image, metadata),
         ImageFormat::Jpeg => encode_jpeg(output_path, image, metadata),
         ImageFormat::Gif => encode_gif(output_path, image),
-        ImageFormat::WebP => encode_webp(output_path, image),
+        ImageFormat::WebP => encode_webp(output_path, image, metadata),
         ImageFormat::Bmp => encode_bmp(output_path, image),
         ImageFormat::Avif => encode_avif(output_path, image, metadata),
         _ => {
@@ -124,6 +177,18 @@ fn get_progress_step(job: &EditJob) -> u64 {
         step_count += 1;
     }
 
+    if job.watermark.is_some() {
+        step_count += 1;
+    }
+
+    if job.text.is_some() {
+        step_count += 1;
+    }
+
+    if !job.metadata.is_empty() {
+        step_count += 1;
+    }
+
     step_count
 }
 
@@ -141,6 +206,22 @@ fn determine_output_plan(job: &EditJob, metadata: &ImageMeta) -> Result<(ImageFo
         suffix_parts.push("grayscale".to_string());
     }
 
+    if job.watermark.is_some() {
+        suffix_parts.push("watermark".to_string());
+    }
+
+    if job.text.is_some() {
+        suffix_parts.push("text".to_string());
+    }
+
+    if job.metadata.strip {
+        suffix_parts.push("stripped".to_string());
+    }
+
+    if job.metadata.sets_fields() {
+        suffix_parts.push("exif".to_string());
+    }
+
     if let Some(target_format) = job.convert {
         suffix_parts.push(format!("convert{:?}", target_format));
     }
@@ -161,12 +242,16 @@ fn determine_output_plan(job: &EditJob, metadata: &ImageMeta) -> Result<(ImageFo
         format!("{}-{}.{}", stem, suffix, extension)
     };
 
-    let output_path = job.input_file.parent().unwrap().join(filename);
+    let output_dir = match &job.output_dir {
+        Some(output_dir) => output_dir.as_path(),
+        None => job.input_file.parent().unwrap(),
+    };
+    let output_path = output_dir.join(filename);
 
     Ok((output_format, output_path))
 }
 
-fn decode_image(image_path: &PathBuf) -> Result<DecodedImage> {
+pub fn decode_image(image_path: &PathBuf) -> Result<DecodedImage> {
     let reader = ImageReader::open(image_path)?.with_guessed_format()?;
 
     let format = reader.format().unwrap_or(ImageFormat::Png);
@@ -177,6 +262,10 @@ fn decode_image(image_path: &PathBuf) -> Result<DecodedImage> {
     // Trying to get metadata
     let orientation = decoder.orientation();
     let icc_profile = decoder.icc_profile().unwrap_or_default();
+    let exif = decoder.exif_metadata().unwrap_or_else(|e| {
+        warn!("Failed to read EXIF metadata: {}", e);
+        None
+    });
     let color_type = decoder.color_type();
     let (width, height) = decoder.dimensions();
 
@@ -192,11 +281,20 @@ fn decode_image(image_path: &PathBuf) -> Result<DecodedImage> {
     // Decode the image
     let mut image = DynamicImage::from_decoder(decoder)?;
 
+    let mut frames = if format.supports_animation() {
+        decode_animation_frames(image_path, format)?
+    } else {
+        Vec::new()
+    };
+
     // Applying orientation
     match orientation {
         Ok(orientation_data) => {
             if orientation_data != Orientation::NoTransforms {
                 image.apply_orientation(orientation_data);
+                for frame in frames.iter_mut() {
+                    frame.image.apply_orientation(orientation_data);
+                }
             }
         }
         Err(e) => {
@@ -204,18 +302,63 @@ fn decode_image(image_path: &PathBuf) -> Result<DecodedImage> {
         }
     };
 
+    if !frames.is_empty() {
+        debug!("Animated image: {} frames", frames.len());
+    }
+
     let image_meta = ImageMeta {
         icc: icc_profile,
+        exif,
         original_format: format,
     };
 
     Ok(DecodedImage {
         dynamic_image: image,
+        frames,
         image_meta,
     })
 }
 
-fn apply_resize(image: DynamicImage, resize: &ResizeSpec) -> Result<DynamicImage> {
+/// Frames of an animated GIF/WebP. Empty if the image has a single frame, so it's handled as a
+/// still image.
+///
+/// The file is read again: the generic decoder used for the still image only gives the first frame.
+fn decode_animation_frames(
+    image_path: &PathBuf,
+    format: ImageFormat,
+) -> Result<Vec<AnimationFrame>> {
+    let reader = BufReader::new(File::open(image_path)?);
+
+    let frames = match format {
+        ImageFormat::Gif => GifDecoder::new(reader)?.into_frames().collect_frames()?,
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(reader)?;
+            if !decoder.has_animation() {
+                return Ok(Vec::new());
+            }
+            decoder.into_frames().collect_frames()?
+        }
+        _ => return Ok(Vec::new()),
+    };
+
+    if frames.len() < 2 {
+        return Ok(Vec::new());
+    }
+
+    Ok(frames.into_iter().map(to_animation_frame).collect())
+}
+
+fn to_animation_frame(frame: Frame) -> AnimationFrame {
+    let (numerator, denominator) = frame.delay().numer_denom_ms();
+    let delay_ms = numerator.checked_div(denominator).unwrap_or(0);
+
+    AnimationFrame {
+        image: DynamicImage::ImageRgba8(frame.into_buffer()),
+        delay_ms,
+    }
+}
+
+pub fn apply_resize(image: DynamicImage, resize: &ResizeSpec) -> Result<DynamicImage> {
     match resize {
         ResizeSpec::Percent(percent) => {
             let new_width = (image.width() as f64 * (*percent / 100.0)).round() as u32;
@@ -271,3 +414,382 @@ fn warn_if_ratio_differs(original_width: u32, original_height: u32, width: f64,
         );
     }
 }
+
+/// Space between an overlay and the edges of the image, relative to the smallest side.
+const OVERLAY_MARGIN_RATIO: f64 = 0.02;
+
+/// Fonts used for `--text` when `--font` is not informed, in order of preference.
+const DEFAULT_FONTS: &[&str] = &[
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "/System/Library/Fonts/Helvetica.ttc",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+];
+
+/// Decodes the watermark once, so it can be shared by every job.
+pub fn load_watermark(spec: &WatermarkSpec) -> Result<Watermark> {
+    let image = decode_image(&spec.file)
+        .context(format!("Failed to load watermark: {}", spec.file.display()))?
+        .dynamic_image
+        .to_rgba8();
+
+    Ok(Watermark {
+        image,
+        spec: spec.clone(),
+    })
+}
+
+/// Loads the font of the caption once, so it can be shared by every job.
+pub fn load_text_overlay(spec: &TextSpec) -> Result<TextOverlay> {
+    let font_path = match &spec.font {
+        Some(font) => font.clone(),
+        None => DEFAULT_FONTS
+            .iter()
+            .map(PathBuf::from)
+            .find(|font| font.is_file())
+            .ok_or_else(|| {
+                anyhow!("No system font found. Use --font to inform a TrueType/OpenType font file.")
+            })?,
+    };
+    debug!("Using font: {}", font_path.display());
+
+    let data = std::fs::read(&font_path)
+        .context(format!("Failed to read font: {}", font_path.display()))?;
+    let font = FontVec::try_from_vec(data)
+        .map_err(|_| anyhow!("Invalid font file: {}", font_path.display()))?;
+
+    Ok(TextOverlay {
+        font,
+        spec: spec.clone(),
+    })
+}
+
+/// Blends the watermark over the image, at the configured position and opacity. Watermarks larger
+/// than the image (minus the margins) are scaled down to fit.
+pub fn apply_watermark(image: DynamicImage, watermark: &Watermark) -> DynamicImage {
+    let has_alpha = image.color().has_alpha();
+    let mut canvas = image.to_rgba8();
+    let margin = overlay_margin(canvas.width(), canvas.height());
+
+    let (mark_width, mark_height) = watermark.image.dimensions();
+    let target_width = match watermark.spec.scale {
+        Some(scale) => canvas.width() as f64 * scale / 100.0,
+        None => mark_width as f64,
+    };
+    let fit = (target_width / mark_width as f64)
+        .min((canvas.width() - margin * 2).max(1) as f64 / mark_width as f64)
+        .min((canvas.height() - margin * 2).max(1) as f64 / mark_height as f64);
+
+    let mark = if (fit - 1.0).abs() > f64::EPSILON {
+        let width = ((mark_width as f64 * fit).round() as u32).max(1);
+        let height = ((mark_height as f64 * fit).round() as u32).max(1);
+        debug!("Scaling watermark to {}x{}", width, height);
+        imageops::resize(&watermark.image, width, height, FilterType::Lanczos3)
+    } else {
+        watermark.image.clone()
+    };
+
+    let (x, y) = overlay_origin(
+        canvas.dimensions(),
+        mark.dimensions(),
+        watermark.spec.position,
+        margin,
+    );
+    let opacity = (watermark.spec.opacity / 100.0) as f32;
+
+    for (mark_x, mark_y, pixel) in mark.enumerate_pixels() {
+        blend_at(
+            &mut canvas,
+            x + mark_x as i64,
+            y + mark_y as i64,
+            *pixel,
+            opacity,
+        );
+    }
+
+    restore_alpha(canvas, has_alpha)
+}
+
+/// Draws the caption at the configured position. Each line is aligned to the side of the
+/// position (centered for `center`), with a soft shadow so it's readable on any background.
+pub fn apply_text(image: DynamicImage, overlay: &TextOverlay) -> DynamicImage {
+    let has_alpha = image.color().has_alpha();
+    let mut canvas = image.to_rgba8();
+    let margin = overlay_margin(canvas.width(), canvas.height());
+
+    let spec = &overlay.spec;
+    let scaled = overlay.font.as_scaled(PxScale::from(spec.size));
+    let lines: Vec<&str> = spec.text.lines().collect();
+    let line_height = scaled.height() + scaled.line_gap();
+    let widths: Vec<f32> = lines.iter().map(|line| line_width(&scaled, line)).collect();
+
+    let block_width = widths.iter().cloned().fold(0.0, f32::max).ceil() as u32;
+    let block_height = (line_height * lines.len() as f32 - scaled.line_gap()).ceil() as u32;
+    let (x, y) = overlay_origin(
+        canvas.dimensions(),
+        (block_width, block_height),
+        spec.position,
+        margin,
+    );
+
+    let shadow_offset = (spec.size / 16.0).max(1.0);
+    let shadow = Rgba([0, 0, 0, (spec.color[3] as f32 * 0.6) as u8]);
+
+    for (index, (line, width)) in lines.iter().zip(widths).enumerate() {
+        let line_x = x as f32
+            + match spec.position {
+                OverlayPosition::TopLeft | OverlayPosition::BottomLeft => 0.0,
+                OverlayPosition::TopRight | OverlayPosition::BottomRight => {
+                    block_width as f32 - width
+                }
+                OverlayPosition::Center => (block_width as f32 - width) / 2.0,
+            };
+        let baseline = y as f32 + line_height * index as f32 + scaled.ascent();
+
+        draw_line(
+            &mut canvas,
+            &scaled,
+            line,
+            line_x + shadow_offset,
+            baseline + shadow_offset,
+            shadow,
+        );
+        draw_line(&mut canvas, &scaled, line, line_x, baseline, spec.color);
+    }
+
+    restore_alpha(canvas, has_alpha)
+}
+
+fn line_width(scaled: &PxScaleFont<&FontVec>, line: &str) -> f32 {
+    let mut width = 0.0;
+    let mut previous: Option<GlyphId> = None;
+
+    for c in line.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            width += scaled.kern(previous, id);
+        }
+        width += scaled.h_advance(id);
+        previous = Some(id);
+    }
+
+    width
+}
+
+fn draw_line(
+    canvas: &mut RgbaImage,
+    scaled: &PxScaleFont<&FontVec>,
+    line: &str,
+    x: f32,
+    baseline: f32,
+    color: Rgba<u8>,
+) {
+    let mut caret = x;
+    let mut previous: Option<GlyphId> = None;
+
+    for c in line.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, id);
+        }
+
+        let glyph = id.with_scale_and_position(scaled.scale(), point(caret, baseline));
+        caret += scaled.h_advance(id);
+        previous = Some(id);
+
+        let Some(outlined) = scaled.font.outline_glyph(glyph) else {
+            continue;
+        };
+
+        let bounds = outlined.px_bounds();
+        outlined.draw(|glyph_x, glyph_y, coverage| {
+            blend_at(
+                canvas,
+                bounds.min.x as i64 + glyph_x as i64,
+                bounds.min.y as i64 + glyph_y as i64,
+                color,
+                coverage,
+            );
+        });
+    }
+}
+
+fn overlay_margin(width: u32, height: u32) -> u32 {
+    (width.min(height) as f64 * OVERLAY_MARGIN_RATIO).round() as u32
+}
+
+/// Top-left corner of an overlay of `size`, placed inside `canvas` at `position`.
+fn overlay_origin(
+    canvas: (u32, u32),
+    size: (u32, u32),
+    position: OverlayPosition,
+    margin: u32,
+) -> (i64, i64) {
+    let (canvas_width, canvas_height) = (canvas.0 as i64, canvas.1 as i64);
+    let (width, height) = (size.0 as i64, size.1 as i64);
+    let margin = margin as i64;
+
+    let left = margin;
+    let right = canvas_width - width - margin;
+    let top = margin;
+    let bottom = canvas_height - height - margin;
+
+    match position {
+        OverlayPosition::TopLeft => (left, top),
+        OverlayPosition::TopRight => (right, top),
+        OverlayPosition::BottomLeft => (left, bottom),
+        OverlayPosition::BottomRight => (right, bottom),
+        OverlayPosition::Center => ((canvas_width - width) / 2, (canvas_height - height) / 2),
+    }
+}
+
+/// Blends `color` over the pixel at (x, y), if it's inside the canvas ("over" compositing).
+fn blend_at(canvas: &mut RgbaImage, x: i64, y: i64, color: Rgba<u8>, opacity: f32) {
+    if x < 0 || y < 0 || x >= canvas.width() as i64 || y >= canvas.height() as i64 {
+        return;
+    }
+
+    let base = canvas.get_pixel_mut(x as u32, y as u32);
+    let source_alpha = color[3] as f32 / 255.0 * opacity.clamp(0.0, 1.0);
+    let base_alpha = base[3] as f32 / 255.0;
+    let alpha = source_alpha + base_alpha * (1.0 - source_alpha);
+    if alpha <= 0.0 {
+        return;
+    }
+
+    for channel in 0..3 {
+        let blended = (color[channel] as f32 * source_alpha
+            + base[channel] as f32 * base_alpha * (1.0 - source_alpha))
+            / alpha;
+        base[channel] = blended.round().clamp(0.0, 255.0) as u8;
+    }
+    base[3] = (alpha * 255.0).round() as u8;
+}
+
+/// Overlays are drawn on RGBA. Images without transparency go back to RGB, so the encoders keep
+/// producing the same kind of file.
+fn restore_alpha(canvas: RgbaImage, has_alpha: bool) -> DynamicImage {
+    let image = DynamicImage::ImageRgba8(canvas);
+    if has_alpha {
+        image
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_origin() {
+        let canvas = (200, 100);
+        let size = (50, 20);
+
+        assert_eq!(
+            overlay_origin(canvas, size, OverlayPosition::TopLeft, 5),
+            (5, 5)
+        );
+        assert_eq!(
+            overlay_origin(canvas, size, OverlayPosition::BottomRight, 5),
+            (145, 75)
+        );
+        assert_eq!(
+            overlay_origin(canvas, size, OverlayPosition::Center, 5),
+            (75, 40)
+        );
+    }
+
+    #[test]
+    fn test_blend_at_applies_opacity() {
+        let mut canvas = RgbaImage::from_pixel(2, 1, Rgba([0, 0, 0, 255]));
+
+        blend_at(&mut canvas, 0, 0, Rgba([255, 255, 255, 255]), 0.5);
+        blend_at(&mut canvas, 5, 5, Rgba([255, 255, 255, 255]), 1.0);
+
+        assert_eq!(canvas.get_pixel(0, 0), &Rgba([128, 128, 128, 255]));
+        assert_eq!(canvas.get_pixel(1, 0), &Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_watermark_keeps_images_without_alpha_as_rgb() {
+        let image = DynamicImage::ImageRgb8(image::RgbImage::new(100, 100));
+        let watermark = Watermark {
+            image: RgbaImage::from_pixel(400, 10, Rgba([255, 0, 0, 255])),
+            spec: WatermarkSpec {
+                file: PathBuf::from("logo.png"),
+                position: OverlayPosition::BottomRight,
+                opacity: 100.0,
+                scale: None,
+            },
+        };
+
+        let result = apply_watermark(image, &watermark).to_rgb8();
+
+        // The watermark is scaled down to the width of the image, minus the margins (2px each).
+        assert_eq!(result.get_pixel(97, 97), &image::Rgb([255, 0, 0]));
+        assert_eq!(result.get_pixel(1, 97), &image::Rgb([0, 0, 0]));
+    }
+
+    fn solid_frame(color: Rgba<u8>, delay_ms: u32) -> AnimationFrame {
+        AnimationFrame {
+            image: DynamicImage::ImageRgba8(RgbaImage::from_pixel(8, 8, color)),
+            delay_ms,
+        }
+    }
+
+    #[test]
+    fn test_edit_frames_applies_to_every_frame() {
+        let frames = vec![
+            solid_frame(Rgba([255, 0, 0, 255]), 100),
+            solid_frame(Rgba([0, 0, 255, 255]), 250),
+        ];
+        let mut img_info = DecodedImage {
+            dynamic_image: frames[0].image.clone(),
+            frames,
+            image_meta: ImageMeta {
+                icc: None,
+                exif: None,
+                original_format: ImageFormat::Gif,
+            },
+        };
+
+        img_info
+            .edit_frames(|image| apply_resize(image, &ResizeSpec::Percent(50.0)))
+            .unwrap();
+
+        assert!(img_info
+            .frames
+            .iter()
+            .all(|frame| frame.image.width() == 4 && frame.image.height() == 4));
+        assert_eq!(img_info.frames[1].delay_ms, 250);
+        assert_eq!(img_info.dynamic_image.width(), 4);
+    }
+
+    #[test]
+    fn test_animated_gif_round_trip_keeps_frames_and_timing() {
+        let path = std::env::temp_dir().join(format!("imgx-animation-{}.gif", std::process::id()));
+        let frames = vec![
+            solid_frame(Rgba([255, 0, 0, 255]), 100),
+            solid_frame(Rgba([0, 0, 255, 255]), 250),
+        ];
+
+        encode_animated_gif(&path, &frames).unwrap();
+        let decoded = decode_animation_frames(&path, ImageFormat::Gif);
+        let _ = std::fs::remove_file(&path);
+        let decoded = decoded.unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].delay_ms, 100);
+        assert_eq!(decoded[1].delay_ms, 250);
+        assert_eq!(
+            decoded[1].image.to_rgba8().get_pixel(3, 3),
+            &Rgba([0, 0, 255, 255])
+        );
+    }
+}
@@ -1,8 +1,7 @@
-use crate::models::ImageMeta;
-use anyhow::Result;
+use crate::models::{AnimationFrame, ImageMeta};
+use anyhow::{anyhow, Result};
 use image::{ColorType, DynamicImage, ImageEncoder};
 use std::fs::File;
-use std::io::Write;
 use std::path::PathBuf;
 use tracing::{debug, info, warn};
 
@@ -17,6 +16,13 @@ pub fn encode_png(output_path: &PathBuf, image: &DynamicImage, meta: &ImageMeta)
         }
     }
 
+    if let Some(ref exif) = meta.exif {
+        debug!("Embedding EXIF metadata ({} bytes) in PNG", exif.len());
+        if let Err(e) = encoder.set_exif_metadata(exif.clone()) {
+            warn!("Failed to set EXIF metadata: {}", e);
+        }
+    }
+
     encoder.write_image(
         image.as_bytes(),
         image.width(),
@@ -47,6 +53,13 @@ pub fn encode_jpeg(output_path: &PathBuf, image: &DynamicImage, meta: &ImageMeta
         }
     }
 
+    if let Some(ref exif) = meta.exif {
+        debug!("Embedding EXIF metadata ({} bytes) in JPEG", exif.len());
+        if let Err(e) = encoder.set_exif_metadata(exif.clone()) {
+            warn!("Failed to set EXIF metadata: {}", e);
+        }
+    }
+
     encoder.write_image(
         rgb_image.as_bytes(),
         rgb_image.width(),
@@ -57,10 +70,17 @@ pub fn encode_jpeg(output_path: &PathBuf, image: &DynamicImage, meta: &ImageMeta
     Ok(())
 }
 
-pub fn encode_webp(output_path: &PathBuf, image: &DynamicImage) -> Result<()> {
+pub fn encode_webp(output_path: &PathBuf, image: &DynamicImage, meta: &ImageMeta) -> Result<()> {
     debug!("Encoding WebP in lossless mode");
     let file = File::create(output_path)?;
-    let encoder = image::codecs::webp::WebPEncoder::new_lossless(file);
+    let mut encoder = image::codecs::webp::WebPEncoder::new_lossless(file);
+
+    if let Some(ref exif) = meta.exif {
+        debug!("Embedding EXIF metadata ({} bytes) in WebP", exif.len());
+        if let Err(e) = encoder.set_exif_metadata(exif.clone()) {
+            warn!("Failed to set EXIF metadata: {}", e);
+        }
+    }
 
     encoder.write_image(
         image.as_bytes(),
@@ -72,6 +92,52 @@ pub fn encode_webp(output_path: &PathBuf, image: &DynamicImage) -> Result<()> {
     Ok(())
 }
 
+/// Animated WebP, through libwebp. Lossless, like the still images.
+pub fn encode_animated_webp(
+    output_path: &PathBuf,
+    frames: &[AnimationFrame],
+    meta: &ImageMeta,
+) -> Result<()> {
+    use webp_animation::{Encoder, EncoderOptions, EncodingConfig, EncodingType};
+
+    debug!(
+        "Encoding animated WebP ({} frames) in lossless mode",
+        frames.len()
+    );
+
+    if meta.exif.is_some() {
+        warn!("EXIF metadata is not kept in animated WebP files");
+    }
+
+    let (width, height) = (frames[0].image.width(), frames[0].image.height());
+    let options = EncoderOptions {
+        encoding_config: Some(EncodingConfig {
+            encoding_type: EncodingType::Lossless,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut encoder = Encoder::new_with_options((width, height), options)
+        .map_err(|e| anyhow!("Failed to create the WebP encoder: {:?}", e))?;
+
+    // Frames are placed by their timestamp: the sum of the delays of the previous frames.
+    let mut timestamp_ms: i32 = 0;
+    for frame in frames {
+        encoder
+            .add_frame(frame.image.to_rgba8().as_raw(), timestamp_ms)
+            .map_err(|e| anyhow!("Failed to encode WebP frame: {:?}", e))?;
+        timestamp_ms = timestamp_ms.saturating_add(frame.delay_ms as i32);
+    }
+
+    let data = encoder
+        .finalize(timestamp_ms)
+        .map_err(|e| anyhow!("Failed to finish the WebP animation: {:?}", e))?;
+    std::fs::write(output_path, &*data)?;
+
+    Ok(())
+}
+
 pub fn encode_avif(output_path: &PathBuf, image: &DynamicImage, meta: &ImageMeta) -> Result<()> {
     debug!("Encoding AVIF...");
     let file = File::create(output_path)?;
@@ -94,6 +160,60 @@ pub fn encode_avif(output_path: &PathBuf, image: &DynamicImage, meta: &ImageMeta
     Ok(())
 }
 
+/// Lossy WebP, through libwebp, for `web-optimize`. Metadata isn't kept: every byte counts.
+pub fn encode_lossy_webp(output_path: &PathBuf, image: &DynamicImage, quality: u8) -> Result<()> {
+    debug!("Encoding WebP in lossy mode, quality {}", quality);
+    let encoded = if image.color().has_alpha() {
+        let rgba_image = image.to_rgba8();
+        webp::Encoder::from_rgba(rgba_image.as_raw(), image.width(), image.height())
+            .encode_simple(false, quality as f32)
+    } else {
+        let rgb_image = image.to_rgb8();
+        webp::Encoder::from_rgb(rgb_image.as_raw(), image.width(), image.height())
+            .encode_simple(false, quality as f32)
+    }
+    .map_err(|e| anyhow!("Failed to encode WebP: {:?}", e))?;
+
+    std::fs::write(output_path, &*encoded)?;
+
+    Ok(())
+}
+
+/// AVIF at a quality target, for `web-optimize`. Only the ICC profile is kept.
+pub fn encode_avif_with_quality(
+    output_path: &PathBuf,
+    image: &DynamicImage,
+    quality: u8,
+    meta: &ImageMeta,
+) -> Result<()> {
+    debug!("Encoding AVIF, quality {}", quality);
+    let file = File::create(output_path)?;
+    // Speed 4 (1 is the slowest, 10 the fastest) is the default of the encoder, like `cavif`.
+    let mut encoder = image::codecs::avif::AvifEncoder::new_with_speed_quality(file, 4, quality);
+
+    if let Some(ref icc) = meta.icc {
+        if let Err(e) = encoder.set_icc_profile(icc.clone()) {
+            warn!("Failed to set ICC profile: {}", e);
+        }
+    }
+
+    // The encoder takes 8-bit RGB(A) only.
+    let image = if image.color().has_alpha() {
+        DynamicImage::ImageRgba8(image.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+    };
+
+    encoder.write_image(
+        image.as_bytes(),
+        image.width(),
+        image.height(),
+        image.color().into(),
+    )?;
+
+    Ok(())
+}
+
 pub fn encode_bmp(output_path: &PathBuf, image: &DynamicImage) -> Result<()> {
     debug!("Encoding BMP...");
     let mut file = File::create(output_path)?;
@@ -129,13 +249,39 @@ pub fn encode_gif(output_path: &PathBuf, image: &DynamicImage) -> Result<()> {
     encoder.set_repeat(gif::Repeat::Infinite)?;
 
     // Quantize the image to 256 colors
-    encode_gif_with_quantization(&rgba_image, &mut encoder)
+    let frame = quantize_gif_frame(&rgba_image)?;
+    encoder.write_frame(&frame)?;
+
+    Ok(())
 }
 
-fn encode_gif_with_quantization<W: Write>(
-    rgba_image: &image::RgbaImage,
-    encoder: &mut gif::Encoder<W>,
-) -> Result<()> {
+pub fn encode_animated_gif(output_path: &PathBuf, frames: &[AnimationFrame]) -> Result<()> {
+    debug!("Encoding animated GIF ({} frames)", frames.len());
+
+    let mut file = File::create(output_path)?;
+    let (width, height) = (frames[0].image.width(), frames[0].image.height());
+
+    let mut encoder = gif::Encoder::new(&mut file, width as u16, height as u16, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for animation_frame in frames {
+        // Each frame is quantized on its own, so every frame gets the best 256 colors for it.
+        let mut frame = quantize_gif_frame(&animation_frame.image.to_rgba8())?;
+
+        // GIF delays are in hundredths of a second.
+        frame.delay = (animation_frame.delay_ms / 10).min(u16::MAX as u32) as u16;
+
+        // Frames are whole (already composited), so each one replaces the previous one, instead
+        // of being drawn on top of it.
+        frame.dispose = gif::DisposalMethod::Background;
+
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
+fn quantize_gif_frame(rgba_image: &image::RgbaImage) -> Result<gif::Frame<'static>> {
     use imagequant::{Attributes, RGBA};
 
     debug!("Using high-quality quantization for GIF");
@@ -156,21 +302,18 @@ fn encode_gif_with_quantization<W: Write>(
 
     let (palette, pixels) = result.remapped(&mut img)?;
 
-    // Convert palette to GIF format
-    let transparent_values = palette.iter().map(|p| p.a).collect::<Vec<_>>();
-    let first_available_alpha: u8 =
-        transparent_values.iter().position(|a| *a != 0).unwrap_or(0) as u8;
-    let transparent = if first_available_alpha > 0 {
-        Some(first_available_alpha)
-    } else {
-        None
-    };
+    // The first fully transparent palette entry (if any) becomes the transparent index.
+    let transparent = palette
+        .iter()
+        .position(|color| color.a == 0)
+        .map(|index| index as u8);
 
     let mut frame =
-        gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels.clone(), transparent);
-    frame.delay = 0; // Static image
+        gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels, transparent);
 
-    encoder.write_frame(&frame)?;
+    // Each frame carries its own (local) color table, since there is no global one.
+    frame.palette = Some(palette.iter().flat_map(|c| [c.r, c.g, c.b]).collect());
+    frame.delay = 0; // Static image
 
-    Ok(())
+    Ok(frame)
 }
@@ -2,6 +2,9 @@ use image::ImageFormat;
 
 pub trait ImageFormatTraits {
     fn to_file_extension(&self) -> String;
+
+    /// Whether the format can hold multiple frames (animations), and `imgx` can read and write them.
+    fn supports_animation(&self) -> bool;
 }
 
 impl ImageFormatTraits for ImageFormat {
@@ -25,4 +28,8 @@ impl ImageFormatTraits for ImageFormat {
             _ => "".to_string(),
         }
     }
+
+    fn supports_animation(&self) -> bool {
+        matches!(self, ImageFormat::Gif | ImageFormat::WebP)
+    }
 }
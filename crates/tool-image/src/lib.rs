@@ -0,0 +1,65 @@
+mod batch_inputs;
+mod cli_utils;
+mod dedupe_routines;
+mod exif_metadata;
+mod image_app;
+mod image_edit_routines;
+mod image_encoders;
+mod image_format_traits;
+mod models;
+mod pdf_routines;
+mod string_traits;
+mod web_optimize_routines;
+
+use anyhow::Result;
+
+use crate::cli_utils::{get_cli_arguments, print_runtime_info, validate_args};
+use crate::dedupe_routines::find_duplicates;
+use crate::image_app::{print_exif_metadata, run_image_edit_commands, run_web_optimize};
+use crate::models::{ImgxCommand, PdfOperation};
+use crate::pdf_routines::{assemble_pdf, rasterize_pdfs};
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+/// Image editor.
+///
+/// Parses arguments and runs the subcommand: the edits (the default), `exif`, `dedupe`, or
+/// `web-optimize`.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    match run_tool(argv.into_iter().map(Into::into).collect()) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Warn);
+    let args = match get_cli_arguments(argv) {
+        ImgxCommand::Edit(args) => args,
+        ImgxCommand::Exif(args) => return print_exif_metadata(&args),
+        ImgxCommand::Dedupe(args) => return find_duplicates(&args),
+        ImgxCommand::WebOptimize(args) => return run_web_optimize(&args),
+    };
+    validate_args(&args)?;
+    print_runtime_info(&args);
+
+    println!("Working:");
+    match &args.pdf {
+        None => run_image_edit_commands(&args)?,
+        Some(PdfOperation::Rasterize { dpi }) => rasterize_pdfs(&args, *dpi)?,
+        Some(PdfOperation::Assemble(options)) => assemble_pdf(&args, options)?,
+    }
+    Ok(())
+}
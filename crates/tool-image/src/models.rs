@@ -1,7 +1,10 @@
+use ab_glyph::FontVec;
+use anyhow::Result;
 use clap::ValueEnum;
-use image::{DynamicImage, ImageFormat};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[derive(ValueEnum, Clone, Debug)]
 pub enum FilterType {
@@ -12,18 +15,213 @@ pub enum FilterType {
     Lanczos3,
 }
 
+/// What `imgx` was asked to do: edit images (the default), print their metadata, find
+/// duplicates, or make web-ready copies.
+pub enum ImgxCommand {
+    Edit(EditArgs),
+    Exif(ExifArgs),
+    Dedupe(DedupeArgs),
+    WebOptimize(WebOptimizeArgs),
+}
+
+/// `imgx exif`: prints the EXIF metadata of the inputs.
+pub struct ExifArgs {
+    pub input_files: Vec<PathBuf>,
+}
+
+/// `imgx dedupe`: groups the inputs that look alike, by their perceptual hash.
+pub struct DedupeArgs {
+    pub input_files: Vec<PathBuf>,
+    pub hash: HashAlgorithm,
+    /// Largest number of different bits (out of 64) for two images to be in the same group.
+    pub threshold: u32,
+    /// If set, the duplicates (every image of a group but the one kept) are moved here.
+    pub move_to: Option<PathBuf>,
+    /// Number of images hashed in parallel.
+    pub jobs: usize,
+}
+
+/// `imgx web-optimize`: lossy copies of the inputs, in several widths, and the HTML that offers
+/// them to browsers.
+pub struct WebOptimizeArgs {
+    pub input_files: Vec<PathBuf>,
+    /// Sorted, without repetitions: the order browsers are offered them.
+    pub formats: Vec<WebFormat>,
+    /// 1 (smallest files) to 100 (best quality).
+    pub quality: u8,
+    /// Sorted, without repetitions.
+    pub widths: Vec<u32>,
+    /// `sizes` attribute of the snippets.
+    pub sizes: String,
+    /// Prepended to the paths of the outputs in the snippets (e.g.: `/images/`).
+    pub url_prefix: String,
+    /// If set, outputs go here, mirroring the structure of the input folders.
+    pub output_dir: Option<PathBuf>,
+    /// Number of images processed in parallel.
+    pub jobs: usize,
+}
+
+/// Output formats of `web-optimize`, in the order browsers are offered them: the smallest first.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WebFormat {
+    Avif,
+    Webp,
+}
+
+impl WebFormat {
+    pub fn image_format(&self) -> ImageFormat {
+        match self {
+            WebFormat::Avif => ImageFormat::Avif,
+            WebFormat::Webp => ImageFormat::WebP,
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        self.image_format().to_mime_type()
+    }
+}
+
+/// Perceptual hash used to compare images.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum HashAlgorithm {
+    /// Average hash: fast, but sensitive to changes in brightness and contrast.
+    Ahash,
+    /// DCT-based hash: slower, and more tolerant to compression, resizing and color changes.
+    Phash,
+}
+
 pub struct EditArgs {
     pub input_files: Vec<PathBuf>,
     pub resize: Option<ResizeSpec>,
     pub grayscale: bool,
     pub convert: Option<ImageFormat>,
+    pub watermark: Option<WatermarkSpec>,
+    pub text: Option<TextSpec>,
+    pub metadata: MetadataEdit,
+    pub pdf: Option<PdfOperation>,
+    /// Patterns the files inside input folders must match (any of them).
+    pub globs: Vec<String>,
+    /// If set, outputs go here, mirroring the structure of the input folders.
+    pub output_dir: Option<PathBuf>,
+    /// Number of images processed in parallel.
+    pub jobs: usize,
+}
+
+/// PDF operations. These replace the regular edit jobs.
+pub enum PdfOperation {
+    /// Renders the pages of the input PDFs to PNG.
+    Rasterize { dpi: f32 },
+    /// Bundles the input images into a single PDF.
+    Assemble(PdfAssembleOptions),
+}
+
+pub struct PdfAssembleOptions {
+    pub output_file: PathBuf,
+    pub page_size: PageSize,
+    pub margin_mm: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PageSize {
+    /// Each page has the size of its image (at 150 DPI), plus the margins.
+    Fit,
+    /// Portrait size. Pages are turned for landscape images.
+    Fixed { width_mm: f32, height_mm: f32 },
+}
+
+impl std::fmt::Display for PageSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PageSize::Fit => write!(f, "fit to image"),
+            PageSize::Fixed {
+                width_mm,
+                height_mm,
+            } => write!(
+                f,
+                "{}x{} mm",
+                format_decimal(*width_mm as f64),
+                format_decimal(*height_mm as f64)
+            ),
+        }
+    }
+}
+
+/// Corner (or center) where an overlay is placed.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OverlayPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+/// `--watermark` options, as informed in the command line.
+#[derive(Clone, Debug)]
+pub struct WatermarkSpec {
+    pub file: PathBuf,
+    pub position: OverlayPosition,
+    /// 0 to 100.
+    pub opacity: f64,
+    /// Width of the watermark, as a percentage of the image width. `None` keeps its size.
+    pub scale: Option<f64>,
+}
+
+/// `--text` options, as informed in the command line.
+#[derive(Clone, Debug)]
+pub struct TextSpec {
+    pub text: String,
+    /// TrueType/OpenType font. `None` looks for a common system font.
+    pub font: Option<PathBuf>,
+    /// Font size, in pixels.
+    pub size: f32,
+    pub color: Rgba<u8>,
+    pub position: OverlayPosition,
+}
+
+/// Watermark ready to be applied: the image is decoded once and shared by every job.
+pub struct Watermark {
+    pub image: RgbaImage,
+    pub spec: WatermarkSpec,
+}
+
+/// Text caption ready to be drawn: the font is loaded once and shared by every job.
+pub struct TextOverlay {
+    pub font: FontVec,
+    pub spec: TextSpec,
+}
+
+/// Changes to the EXIF metadata of the outputs.
+#[derive(Clone, Debug, Default)]
+pub struct MetadataEdit {
+    /// Drops the metadata of the original image (`--strip-metadata`).
+    pub strip: bool,
+    pub artist: Option<String>,
+    pub copyright: Option<String>,
+    /// Already in the EXIF format: `YYYY:MM:DD HH:MM:SS`.
+    pub datetime: Option<String>,
+}
+
+impl MetadataEdit {
+    pub fn sets_fields(&self) -> bool {
+        self.artist.is_some() || self.copyright.is_some() || self.datetime.is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.strip && !self.sets_fields()
+    }
 }
 
 pub struct EditJob {
     pub input_file: PathBuf,
+    /// Folder where the output is saved. `None` saves it next to the input file.
+    pub output_dir: Option<PathBuf>,
     pub resize: Option<ResizeSpec>,
     pub grayscale: bool,
     pub convert: Option<ImageFormat>,
+    pub watermark: Option<Arc<Watermark>>,
+    pub text: Option<Arc<TextOverlay>>,
+    pub metadata: MetadataEdit,
 }
 
 #[derive(Clone, Debug)]
@@ -76,12 +274,55 @@ fn format_decimal(value: f64) -> String {
 }
 
 pub struct DecodedImage {
+    /// The image or, for animations, the first frame.
     pub dynamic_image: DynamicImage,
+    /// Every frame of an animated GIF/WebP (the first one included). Empty for still images.
+    pub frames: Vec<AnimationFrame>,
     pub image_meta: ImageMeta,
 }
 
+impl DecodedImage {
+    pub fn is_animated(&self) -> bool {
+        !self.frames.is_empty()
+    }
+
+    /// Applies an edit to the image or, for animations, to each of its frames.
+    pub fn edit_frames(
+        &mut self,
+        edit: impl Fn(DynamicImage) -> Result<DynamicImage>,
+    ) -> Result<()> {
+        if !self.is_animated() {
+            let image = std::mem::replace(&mut self.dynamic_image, DynamicImage::new_rgba8(0, 0));
+            self.dynamic_image = edit(image)?;
+            return Ok(());
+        }
+
+        self.frames = std::mem::take(&mut self.frames)
+            .into_iter()
+            .map(|frame| {
+                Ok(AnimationFrame {
+                    image: edit(frame.image)?,
+                    delay_ms: frame.delay_ms,
+                })
+            })
+            .collect::<Result<_>>()?;
+        self.dynamic_image = self.frames[0].image.clone();
+
+        Ok(())
+    }
+}
+
+/// A frame of an animation, already composited: it has the size of the whole animation.
+pub struct AnimationFrame {
+    pub image: DynamicImage,
+    /// How long the frame is shown, in milliseconds.
+    pub delay_ms: u32,
+}
+
 pub struct ImageMeta {
     pub icc: Option<Vec<u8>>,
+    /// Raw EXIF (TIFF structure). Written to the outputs that support it (JPEG, PNG, WebP).
+    pub exif: Option<Vec<u8>>,
     pub original_format: ImageFormat,
 }
 
@@ -0,0 +1,366 @@
+use crate::image_app::is_supported_image_file;
+use crate::image_edit_routines::{apply_resize, decode_image};
+use crate::image_encoders::encode_png;
+use crate::models::{EditArgs, ImageMeta, PageSize, PdfAssembleOptions};
+use anyhow::{anyhow, Result};
+use image::{DynamicImage, ImageFormat};
+use indicatif::{ProgressBar, ProgressStyle};
+use pdfium_render::prelude::{PdfRenderConfig, Pdfium};
+use printpdf::{
+    ColorBits, ColorSpace, Image, ImageTransform, ImageXObject, Mm, PdfDocument,
+    PdfDocumentReference, Px,
+};
+use shared::system::folder_walkthrough::list_all_files_recursively;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// PDF coordinates are in points (1/72 inch).
+const PDF_POINTS_PER_INCH: f32 = 72.0;
+const MM_PER_INCH: f32 = 25.4;
+/// Resolution used to size the pages when `--page-size fit` is used.
+const FIT_PAGE_DPI: f32 = 150.0;
+
+/// Where an image goes in a page. All values are in millimeters, from the bottom-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageLayout {
+    pub page_width_mm: f32,
+    pub page_height_mm: f32,
+    pub x_mm: f32,
+    pub y_mm: f32,
+    pub image_width_mm: f32,
+    pub image_height_mm: f32,
+}
+
+/// Renders every page of the input PDFs to PNG, saved next to the PDF as `<name>-page001.png`.
+pub fn rasterize_pdfs(args: &EditArgs, dpi: f32) -> Result<()> {
+    let pdf_files = collect_files(&args.input_files, |path| is_pdf_file(path));
+    if pdf_files.is_empty() {
+        return Err(anyhow!("No PDF files found. Nothing to work with."));
+    }
+
+    let pdfium = bind_pdfium()?;
+    let config = PdfRenderConfig::new().scale_page_by_factor(dpi / PDF_POINTS_PER_INCH);
+
+    for pdf_file in pdf_files {
+        let document = pdfium
+            .load_pdf_from_file(&pdf_file, None)
+            .map_err(|e| anyhow!("Failed to open {}: {}", pdf_file.display(), e))?;
+
+        let pages = document.pages();
+        let page_count = pages.len() as u64;
+        let progress_bar = create_progress_bar(&pdf_file, page_count)?;
+
+        for (index, page) in pages.iter().enumerate() {
+            let page_number = index + 1;
+            progress_bar.set_message(format!("Rendering page {}...", page_number));
+
+            let bitmap = page.render_with_config(&config).map_err(|e| {
+                anyhow!(
+                    "Failed to render page {} of {}: {}",
+                    page_number,
+                    pdf_file.display(),
+                    e
+                )
+            })?;
+
+            let mut image = bitmap.as_image();
+            if args.grayscale {
+                image = image.grayscale();
+            }
+
+            let output_path = page_output_path(&pdf_file, page_number);
+            debug!(
+                "Saving page {} ({}x{}) to {}",
+                page_number,
+                image.width(),
+                image.height(),
+                output_path.display()
+            );
+
+            let meta = ImageMeta {
+                icc: None,
+                exif: None,
+                original_format: ImageFormat::Png,
+            };
+            encode_png(&output_path, &image, &meta)?;
+            progress_bar.inc(1);
+        }
+
+        progress_bar.finish_with_message(format!("✓ {} page(s) rendered", page_count));
+        info!(
+            "Rendered {} page(s) from {}",
+            page_count,
+            pdf_file.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Bundles the input images into a single PDF, one image per page, in the order they were
+/// informed. Resize and grayscale are applied to each image before it is added.
+pub fn assemble_pdf(args: &EditArgs, options: &PdfAssembleOptions) -> Result<()> {
+    let image_files = collect_files(&args.input_files, is_supported_image_file);
+    if image_files.is_empty() {
+        return Err(anyhow!(
+            "No supported image files found. Nothing to work with."
+        ));
+    }
+
+    let progress_bar = create_progress_bar(&options.output_file, image_files.len() as u64)?;
+    let title = options
+        .output_file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("images");
+
+    let mut document: Option<PdfDocumentReference> = None;
+
+    for image_file in &image_files {
+        progress_bar.set_message(format!("Adding {}...", image_file.display()));
+
+        let mut image = decode_image(image_file)?.dynamic_image;
+        if let Some(resize) = &args.resize {
+            image = apply_resize(image, resize)?;
+        }
+        if args.grayscale {
+            image = image.grayscale();
+        }
+
+        let layout = layout_page(
+            options.page_size,
+            options.margin_mm,
+            image.width(),
+            image.height(),
+        );
+        debug!("Page layout for {}: {:?}", image_file.display(), layout);
+
+        let page_width = Mm(layout.page_width_mm);
+        let page_height = Mm(layout.page_height_mm);
+        let layer = match &document {
+            None => {
+                let (doc, page, layer) = PdfDocument::new(title, page_width, page_height, "Image");
+                let layer = doc.get_page(page).get_layer(layer);
+                document = Some(doc);
+                layer
+            }
+            Some(doc) => {
+                let (page, layer) = doc.add_page(page_width, page_height, "Image");
+                doc.get_page(page).get_layer(layer)
+            }
+        };
+
+        // The image is drawn at its natural size for this DPI, which is the size in the layout.
+        let dpi = image.width() as f32 * MM_PER_INCH / layout.image_width_mm;
+        Image::from(to_image_xobject(&image, args.grayscale)).add_to_layer(
+            layer,
+            ImageTransform {
+                translate_x: Some(Mm(layout.x_mm)),
+                translate_y: Some(Mm(layout.y_mm)),
+                dpi: Some(dpi),
+                ..Default::default()
+            },
+        );
+
+        progress_bar.inc(1);
+    }
+
+    let bytes = document
+        .expect("at least one image was added")
+        .save_to_bytes()
+        .map_err(|e| anyhow!("Failed to create the PDF: {:?}", e))?;
+    fs::write(&options.output_file, bytes)?;
+
+    progress_bar.finish_with_message(format!("✓ {} page(s) saved", image_files.len()));
+    info!(
+        "Saved {} page(s) to {}",
+        image_files.len(),
+        options.output_file.display()
+    );
+
+    Ok(())
+}
+
+/// Fits the image inside the margins, keeping its aspect ratio, and centers it. Fixed page sizes
+/// are turned to landscape for landscape images.
+pub fn layout_page(
+    page_size: PageSize,
+    margin_mm: f32,
+    width_px: u32,
+    height_px: u32,
+) -> PageLayout {
+    let natural_width_mm = width_px as f32 * MM_PER_INCH / FIT_PAGE_DPI;
+    let natural_height_mm = height_px as f32 * MM_PER_INCH / FIT_PAGE_DPI;
+
+    let (page_width_mm, page_height_mm) = match page_size {
+        PageSize::Fit => (
+            natural_width_mm + margin_mm * 2.0,
+            natural_height_mm + margin_mm * 2.0,
+        ),
+        PageSize::Fixed {
+            width_mm,
+            height_mm,
+        } => {
+            let (short, long) = (width_mm.min(height_mm), width_mm.max(height_mm));
+            if width_px > height_px {
+                (long, short)
+            } else {
+                (short, long)
+            }
+        }
+    };
+
+    let available_width = page_width_mm - margin_mm * 2.0;
+    let available_height = page_height_mm - margin_mm * 2.0;
+    let scale = (available_width / natural_width_mm).min(available_height / natural_height_mm);
+    let image_width_mm = natural_width_mm * scale;
+    let image_height_mm = natural_height_mm * scale;
+
+    PageLayout {
+        page_width_mm,
+        page_height_mm,
+        x_mm: (page_width_mm - image_width_mm) / 2.0,
+        y_mm: (page_height_mm - image_height_mm) / 2.0,
+        image_width_mm,
+        image_height_mm,
+    }
+}
+
+fn to_image_xobject(image: &DynamicImage, grayscale: bool) -> ImageXObject {
+    let (color_space, image_data) = if grayscale {
+        (ColorSpace::Greyscale, image.to_luma8().into_raw())
+    } else {
+        (ColorSpace::Rgb, image.to_rgb8().into_raw())
+    };
+
+    ImageXObject {
+        width: Px(image.width() as usize),
+        height: Px(image.height() as usize),
+        color_space,
+        bits_per_component: ColorBits::Bit8,
+        interpolate: true,
+        image_data,
+        image_filter: None,
+        smask: None,
+        clipping_bbox: None,
+    }
+}
+
+/// Pdfium is loaded at runtime: first from the executable's folder, then from the system.
+fn bind_pdfium() -> Result<Pdfium> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("./"));
+
+    let bindings = Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path(&exe_dir))
+        .or_else(|_| Pdfium::bind_to_system_library())
+        .map_err(|e| {
+            anyhow!(
+                "Failed to load the Pdfium library ({}). Download it from \
+                 https://github.com/bblanchon/pdfium-binaries and place it next to the executable.",
+                e
+            )
+        })?;
+
+    Ok(Pdfium::new(bindings))
+}
+
+/// Keeps the order of the inputs. Files inside folders are sorted by path.
+fn collect_files(paths: &[PathBuf], filter: fn(&PathBuf) -> bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_file() {
+            if filter(path) {
+                files.push(path.clone());
+            }
+            continue;
+        }
+
+        let mut folder_files: Vec<PathBuf> =
+            list_all_files_recursively(path).filter(filter).collect();
+        folder_files.sort();
+        files.extend(folder_files);
+    }
+
+    files
+}
+
+fn is_pdf_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+}
+
+fn page_output_path(pdf_file: &Path, page_number: usize) -> PathBuf {
+    let stem = pdf_file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("document");
+
+    pdf_file.with_file_name(format!("{}-page{:03}.png", stem, page_number))
+}
+
+fn create_progress_bar(file: &Path, length: u64) -> Result<ProgressBar> {
+    let pb = ProgressBar::new(length);
+    pb.set_style(ProgressStyle::default_bar().template(
+        format!(
+            "{{spinner:.blue}} [{{elapsed_precise}}] [{}] {{bar:50.green/black}} {{pos}}/{{len}} {{msg}}",
+            file.display()
+        )
+        .as_str(),
+    )?);
+
+    Ok(pb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A4: PageSize = PageSize::Fixed {
+        width_mm: 210.0,
+        height_mm: 297.0,
+    };
+
+    #[test]
+    fn test_layout_centers_image_inside_margins() {
+        let layout = layout_page(A4, 10.0, 1000, 2000);
+
+        assert_eq!(layout.page_width_mm, 210.0);
+        assert_eq!(layout.page_height_mm, 297.0);
+        // Height is the limit: 277mm available, so the width is half of it.
+        assert!((layout.image_height_mm - 277.0).abs() < 0.01);
+        assert!((layout.image_width_mm - 138.5).abs() < 0.01);
+        assert!((layout.x_mm - 35.75).abs() < 0.01);
+        assert!((layout.y_mm - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_layout_turns_page_for_landscape_images() {
+        let layout = layout_page(A4, 0.0, 3000, 2000);
+
+        assert_eq!(layout.page_width_mm, 297.0);
+        assert_eq!(layout.page_height_mm, 210.0);
+    }
+
+    #[test]
+    fn test_layout_fit_uses_image_size() {
+        let layout = layout_page(PageSize::Fit, 5.0, 1500, 300);
+
+        assert!((layout.page_width_mm - 264.0).abs() < 0.01);
+        assert!((layout.page_height_mm - 60.8).abs() < 0.01);
+        assert!((layout.image_width_mm - 254.0).abs() < 0.01);
+        assert!((layout.x_mm - 5.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_page_output_path() {
+        assert_eq!(
+            page_output_path(Path::new("scans/contract.pdf"), 7),
+            PathBuf::from("scans/contract-page007.png")
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use crate::batch_inputs::BatchInput;
+use crate::image_edit_routines::decode_image;
+use crate::image_encoders::{encode_avif_with_quality, encode_lossy_webp};
+use crate::image_format_traits::ImageFormatTraits;
+use crate::models::{WebFormat, WebOptimizeArgs};
+use anyhow::{anyhow, Result};
+use image::imageops::FilterType;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// A file written by `web-optimize`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebOutput {
+    pub file: PathBuf,
+    pub format: WebFormat,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Writes every width in every format, as `<name>-<width>w.<ext>`, and returns them from the
+/// narrowest to the widest.
+pub fn optimize_for_web(
+    input: &BatchInput,
+    args: &WebOptimizeArgs,
+    progress_bar: &ProgressBar,
+) -> Result<Vec<WebOutput>> {
+    let decoded = decode_image(&input.file)?;
+    if decoded.is_animated() {
+        warn!(
+            "{} is animated. Only the first frame will be saved.",
+            input.file.display()
+        );
+    }
+
+    let image = &decoded.dynamic_image;
+    let output_dir = match &input.output_dir {
+        Some(output_dir) => output_dir.clone(),
+        None => input.file.parent().unwrap_or(Path::new("")).to_path_buf(),
+    };
+    fs::create_dir_all(&output_dir)?;
+
+    let stem = input
+        .file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("image-name");
+
+    let widths = target_widths(image.width(), &args.widths);
+    progress_bar.set_length((widths.len() * args.formats.len()) as u64);
+
+    let mut outputs = Vec::new();
+    for width in widths {
+        progress_bar.set_message(format!("{}px wide...", width));
+        let resized = if width == image.width() {
+            image.clone()
+        } else {
+            image.resize(width, u32::MAX, FilterType::Lanczos3)
+        };
+
+        for format in &args.formats {
+            let file = output_dir.join(format!(
+                "{}-{}w.{}",
+                stem,
+                width,
+                format.image_format().to_file_extension()
+            ));
+
+            match format {
+                WebFormat::Webp => encode_lossy_webp(&file, &resized, args.quality)?,
+                WebFormat::Avif => {
+                    encode_avif_with_quality(&file, &resized, args.quality, &decoded.image_meta)?
+                }
+            }
+            progress_bar.inc(1);
+
+            outputs.push(WebOutput {
+                file,
+                format: *format,
+                width: resized.width(),
+                height: resized.height(),
+            });
+        }
+    }
+
+    Ok(outputs)
+}
+
+/// The requested widths the image can fill. Images are never enlarged: the wider ones are replaced
+/// by the width of the image.
+pub fn target_widths(image_width: u32, widths: &[u32]) -> Vec<u32> {
+    let mut targets: Vec<u32> = widths
+        .iter()
+        .map(|width| (*width).min(image_width))
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+    targets
+}
+
+/// HTML offering the outputs to browsers: an `<img>` with a `srcset`, wrapped in a `<picture>`
+/// with a `<source>` per extra format when there are more than one. The last format is the
+/// fallback.
+///
+/// `output_root` is the folder the paths are relative to (`--output-dir`). Without one, the
+/// outputs are next to the image, and only their names are used.
+pub fn render_srcset_snippet(
+    input_file: &Path,
+    outputs: &[WebOutput],
+    args: &WebOptimizeArgs,
+    output_root: Option<&Path>,
+) -> String {
+    let url = |file: &Path| {
+        let relative = match output_root.and_then(|root| file.strip_prefix(root).ok()) {
+            Some(relative) => relative.to_path_buf(),
+            None => PathBuf::from(file.file_name().unwrap_or_default()),
+        };
+        let path = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        escape_attribute(&format!("{}{}", args.url_prefix, path).replace(' ', "%20"))
+    };
+
+    let srcset = |format: WebFormat| {
+        outputs
+            .iter()
+            .filter(|output| output.format == format)
+            .map(|output| format!("{} {}w", url(&output.file), output.width))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let sizes = escape_attribute(&args.sizes);
+    let Some((fallback, sources)) = args.formats.split_last() else {
+        return String::new();
+    };
+    let Some(largest) = outputs
+        .iter()
+        .rev()
+        .find(|output| output.format == *fallback)
+    else {
+        return String::new();
+    };
+
+    let img = format!(
+        r#"<img src="{}" srcset="{}" sizes="{}" width="{}" height="{}" alt="" loading="lazy" decoding="async">"#,
+        url(&largest.file),
+        srcset(*fallback),
+        sizes,
+        largest.width,
+        largest.height
+    );
+
+    let mut snippet = format!("<!-- {} -->\n", input_file.display());
+    if sources.is_empty() {
+        snippet.push_str(&img);
+        return snippet;
+    }
+
+    snippet.push_str("<picture>\n");
+    for format in sources {
+        snippet.push_str(&format!(
+            "  <source type=\"{}\" srcset=\"{}\" sizes=\"{}\">\n",
+            format.mime_type(),
+            srcset(*format),
+            sizes
+        ));
+    }
+    snippet.push_str(&format!("  {}\n</picture>", img));
+    snippet
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub fn create_web_progress_bar(
+    input: &BatchInput,
+    progress_bar: &MultiProgress,
+) -> Result<ProgressBar> {
+    let pb = progress_bar.add(ProgressBar::new(0));
+    pb.set_style(ProgressStyle::default_bar().template(
+        format!(
+            "{{spinner:.blue}} [{{elapsed_precise}}] [{}] {{bar:50.green/black}} {{pos}}/{{len}} {{msg}}",
+            input.file.display()
+        )
+        .as_str(),
+    )?);
+
+    Ok(pb)
+}
+
+/// Checks the outputs can be told apart: two inputs with the same name in the same folder, but
+/// different extensions (`logo.png` and `logo.jpg`), would write the same files.
+pub fn find_output_clash(inputs: &[BatchInput]) -> Result<()> {
+    let mut seen: HashMap<PathBuf, &Path> = HashMap::with_capacity(inputs.len());
+
+    for input in inputs {
+        let output_dir = match &input.output_dir {
+            Some(output_dir) => output_dir.clone(),
+            None => input.file.parent().unwrap_or(Path::new("")).to_path_buf(),
+        };
+        let key = output_dir.join(input.file.file_stem().unwrap_or_default());
+
+        if let Some(other) = seen.get(&key) {
+            return Err(anyhow!(
+                "{} and {} would write the same files. Rename one of them.",
+                other.display(),
+                input.file.display()
+            ));
+        }
+        seen.insert(key, &input.file);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(formats: Vec<WebFormat>) -> WebOptimizeArgs {
+        WebOptimizeArgs {
+            input_files: Vec::new(),
+            formats,
+            quality: 80,
+            widths: vec![480, 960],
+            sizes: "(max-width: 960px) 100vw, 960px".to_string(),
+            url_prefix: "/img/".to_string(),
+            output_dir: None,
+            jobs: 1,
+        }
+    }
+
+    fn output(file: &str, format: WebFormat, width: u32) -> WebOutput {
+        WebOutput {
+            file: PathBuf::from(file),
+            format,
+            width,
+            height: width * 3 / 4,
+        }
+    }
+
+    #[test]
+    fn test_target_widths() {
+        assert_eq!(target_widths(4000, &[480, 960, 1920]), vec![480, 960, 1920]);
+        assert_eq!(target_widths(1200, &[480, 960, 1920]), vec![480, 960, 1200]);
+        assert_eq!(target_widths(300, &[480, 960, 1920]), vec![300]);
+    }
+
+    #[test]
+    fn test_render_srcset_snippet() {
+        let outputs = vec![
+            output("dist/blog/beach-480w.avif", WebFormat::Avif, 480),
+            output("dist/blog/beach-480w.webp", WebFormat::Webp, 480),
+            output("dist/blog/beach-960w.avif", WebFormat::Avif, 960),
+            output("dist/blog/beach-960w.webp", WebFormat::Webp, 960),
+        ];
+
+        let snippet = render_srcset_snippet(
+            Path::new("photos/blog/beach.jpg"),
+            &outputs,
+            &args(vec![WebFormat::Avif, WebFormat::Webp]),
+            Some(Path::new("dist")),
+        );
+        assert_eq!(
+            snippet,
+            "<!-- photos/blog/beach.jpg -->\n\
+             <picture>\n  \
+             <source type=\"image/avif\" srcset=\"/img/blog/beach-480w.avif 480w, /img/blog/beach-960w.avif 960w\" sizes=\"(max-width: 960px) 100vw, 960px\">\n  \
+             <img src=\"/img/blog/beach-960w.webp\" srcset=\"/img/blog/beach-480w.webp 480w, /img/blog/beach-960w.webp 960w\" \
+             sizes=\"(max-width: 960px) 100vw, 960px\" width=\"960\" height=\"720\" alt=\"\" loading=\"lazy\" decoding=\"async\">\n\
+             </picture>"
+        );
+
+        let single = render_srcset_snippet(
+            Path::new("my beach.jpg"),
+            &[output("my beach-480w.webp", WebFormat::Webp, 480)],
+            &args(vec![WebFormat::Webp]),
+            None,
+        );
+        assert!(single.starts_with(
+            "<!-- my beach.jpg -->\n<img src=\"/img/my%20beach-480w.webp\" srcset=\"/img/my%20beach-480w.webp 480w\""
+        ));
+    }
+
+    #[test]
+    fn test_find_output_clash() {
+        let input = |file: &str| BatchInput {
+            file: PathBuf::from(file),
+            output_dir: None,
+        };
+
+        assert!(find_output_clash(&[input("a/logo.png"), input("b/logo.png")]).is_ok());
+        assert!(find_output_clash(&[input("a/logo.png"), input("a/logo.jpg")]).is_err());
+    }
+}
@@ -1,11 +1,21 @@
-use crate::models::{Claims, ExpirationStatus, TokenInfo};
+use crate::models::{ClaimType, Claims, ExpirationStatus, TokenInfo};
 use anyhow::{anyhow, Result};
-use colored::Colorize;
+use chrono::{DateTime, Duration, Local, Utc};
 use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 use serde_json::{Map, Value};
 use shared::utils::copy_string_to_clipboard::copy_to_clipboard;
+use shared::utils::table_output::Table;
 use std::borrow::Cow;
-use std::process;
+
+/// Registered (RFC 7519) and OpenID Connect claims that hold seconds since the epoch.
+const TIMESTAMP_CLAIMS: [&str; 5] = ["exp", "nbf", "iat", "auth_time", "updated_at"];
+
+/// Other integer claims are only read as dates when their name hints so (e.g.: `created_at`,
+/// `pwd_time`) and the value falls between 2000-01-01 and 2100-01-01. This keeps numeric ids
+/// and counters as numbers.
+const TIMESTAMP_NAME_HINTS: [&str; 2] = ["at", "time"];
+const MIN_HINTED_EPOCH: i64 = 946_684_800;
+const MAX_HINTED_EPOCH: i64 = 4_102_444_800;
 
 /// Decodes JWT token without signature verification.
 ///
@@ -105,22 +115,22 @@ pub fn print_token_csv(claims: &Map<String, Value>) {
 /// Prints JWT claims as formatted JSON.
 ///
 /// Outputs pretty-printed JSON to stdout.
-/// Exits with code 1 if JSON serialization fails.
-pub fn print_token_json(claims: &Map<String, Value>) {
-    match serde_json::to_string_pretty(&claims) {
-        Ok(json_output) => println!("{}", json_output),
-        Err(e) => {
-            eprintln!("Error formatting JSON: {}", e);
-            process::exit(1);
-        }
-    }
+/// Fails if JSON serialization fails.
+pub fn print_token_json(claims: &Map<String, Value>) -> Result<()> {
+    let json_output = serde_json::to_string_pretty(&claims)
+        .map_err(|e| anyhow!("Error formatting JSON: {}", e))?;
+    println!("{}", json_output);
+    Ok(())
 }
 
 /// Copies specific claim value to clipboard.
 ///
 /// Searches for claim key in token and copies its string value.
-/// Exits with error if claim not found or clipboard operation fails.
-pub fn copy_claim_to_clipboard(argument_to_copy: String, claims: &Map<String, Value>) {
+/// Warns if the claim is not found, and fails if the clipboard operation fails.
+pub fn copy_claim_to_clipboard(
+    argument_to_copy: String,
+    claims: &Map<String, Value>,
+) -> Result<()> {
     let mut value: &Value = &Value::Null;
 
     for (key, claim_value) in claims {
@@ -132,7 +142,7 @@ pub fn copy_claim_to_clipboard(argument_to_copy: String, claims: &Map<String, Va
 
     if value == &Value::Null {
         eprintln!("Claim not found: {}", argument_to_copy);
-        return;
+        return Ok(());
     }
 
     let text_to_copy: Cow<'_, str> = match value {
@@ -141,25 +151,125 @@ pub fn copy_claim_to_clipboard(argument_to_copy: String, claims: &Map<String, Va
         _ => Cow::Owned(value.to_string()),
     };
 
-    match copy_to_clipboard(text_to_copy.as_ref()) {
-        Ok(_) => {}
-        Err(e) => {
-            eprintln!("Error copying to clipboard: {}", e);
-            process::exit(1);
-        }
-    };
+    copy_to_clipboard(text_to_copy.as_ref())
+        .map_err(|e| anyhow!("Error copying to clipboard: {}", e))
 }
 
 /// Prints JWT claims in human-readable format.
 ///
-/// Shows expiration status with colors and lists all claims with formatted values.
+/// Shows expiration status with colors and a table with all claims. Timestamp claims also show
+/// the date in UTC and local time, and how long until (or since) it.
 pub fn print_token_pretty(claims: &Map<String, Value>, expiration_status: &ExpirationStatus) {
     println!("{}", expiration_status.format_colored());
-    println!("----{}Claims:{}------------", "".bold(), "".normal());
+    println!();
+
+    let now = Utc::now();
+    let rows: Vec<(Vec<String>, ClaimType)> = claims
+        .iter()
+        .map(|(key, value)| {
+            (
+                vec![key.clone(), format_claim_value(value)],
+                infer_claim_type(key, value),
+            )
+        })
+        .collect();
+
+    let has_timestamps = rows
+        .iter()
+        .any(|(_, claim_type)| matches!(claim_type, ClaimType::Timestamp(_)));
+
+    let mut table = if has_timestamps {
+        Table::new(&["CLAIM", "VALUE", "UTC", "LOCAL", "RELATIVE"])
+    } else {
+        Table::new(&["CLAIM", "VALUE"])
+    }
+    .with_title("Claims");
 
-    for (key, value) in claims {
-        println!("{}: {}", key, format_claim_value(value));
+    for (mut row, claim_type) in rows {
+        if let ClaimType::Timestamp(date) = claim_type {
+            row.push(date.format("%Y-%m-%d %H:%M:%S").to_string());
+            row.push(
+                date.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S %:z")
+                    .to_string(),
+            );
+            row.push(format_relative(&row[0], date - now));
+        }
+        table.add_row(row);
     }
+
+    print!("{}", table.render_text());
+}
+
+/// Infers what the claim holds. Integers are timestamps for the well-known date claims, and
+/// for claims named like one with a plausible value (see `TIMESTAMP_NAME_HINTS`).
+pub fn infer_claim_type(key: &str, value: &Value) -> ClaimType {
+    match value {
+        Value::Number(number) => {
+            let epoch = number.as_i64().or_else(|| {
+                number
+                    .as_f64()
+                    .filter(|f| f.fract() == 0.0)
+                    .map(|f| f as i64)
+            });
+
+            let is_timestamp = epoch.is_some_and(|epoch| {
+                let name = key.to_lowercase();
+                TIMESTAMP_CLAIMS.contains(&name.as_str())
+                    || (TIMESTAMP_NAME_HINTS.iter().any(|hint| name.ends_with(hint))
+                        && (MIN_HINTED_EPOCH..MAX_HINTED_EPOCH).contains(&epoch))
+            });
+
+            match epoch
+                .filter(|_| is_timestamp)
+                .and_then(|epoch| DateTime::from_timestamp(epoch, 0))
+            {
+                Some(date) => ClaimType::Timestamp(date),
+                None => ClaimType::Number,
+            }
+        }
+        Value::String(_) => ClaimType::Text,
+        Value::Bool(_) => ClaimType::Boolean,
+        Value::Array(_) => ClaimType::List,
+        Value::Object(_) => ClaimType::Object,
+        Value::Null => ClaimType::Null,
+    }
+}
+
+/// How far the date is from now, e.g.: `in 14m`, `2d 3h ago`. The `exp` claim reads as
+/// `expires in 14m`/`expired 2d 3h ago`.
+fn format_relative(key: &str, difference: Duration) -> String {
+    let span = format_span(difference.abs());
+    let is_future = difference > Duration::zero();
+
+    match (key == "exp", is_future) {
+        (true, true) => format!("expires in {}", span),
+        (true, false) => format!("expired {} ago", span),
+        (false, true) => format!("in {}", span),
+        (false, false) => format!("{} ago", span),
+    }
+}
+
+/// The two biggest units of the span (e.g.: `14m 30s`, `2d 3h`).
+fn format_span(span: Duration) -> String {
+    const UNITS: [(&str, i64); 4] = [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+    let seconds = span.num_seconds();
+
+    let Some(first) = UNITS.iter().position(|(_, size)| seconds >= *size) else {
+        return "0s".to_string();
+    };
+
+    let (unit, size) = UNITS[first];
+    let mut text = format!("{}{}", seconds / size, unit);
+
+    if let Some((next_unit, next_size)) = UNITS.get(first + 1) {
+        let next = (seconds % size) / next_size;
+        if next > 0 {
+            text.push_str(&format!(" {}{}", next, next_unit));
+        }
+    }
+
+    text
 }
 
 /// Formats JSON value for display.
@@ -180,3 +290,46 @@ fn format_claim_value(value: &Value) -> String {
         _ => value.to_string().trim_matches('"').to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_infer_claim_type() {
+        let date = DateTime::from_timestamp(1_516_239_022, 0).unwrap();
+
+        assert_eq!(
+            infer_claim_type("iat", &json!(1_516_239_022)),
+            ClaimType::Timestamp(date)
+        );
+        assert_eq!(
+            infer_claim_type("created_at", &json!(1_516_239_022.0)),
+            ClaimType::Timestamp(date)
+        );
+        // Big numbers without a date-like name, and date-like names with implausible dates,
+        // stay numbers.
+        assert_eq!(
+            infer_claim_type("user_id", &json!(1_516_239_022)),
+            ClaimType::Number
+        );
+        assert_eq!(infer_claim_type("retry_at", &json!(3)), ClaimType::Number);
+        assert_eq!(infer_claim_type("exp", &json!("soon")), ClaimType::Text);
+        assert_eq!(infer_claim_type("roles", &json!(["a"])), ClaimType::List);
+    }
+
+    #[test]
+    fn test_format_relative() {
+        assert_eq!(
+            format_relative("exp", Duration::seconds(14 * 60)),
+            "expires in 14m"
+        );
+        assert_eq!(
+            format_relative("exp", Duration::seconds(-(2 * 86_400 + 3 * 3_600 + 59))),
+            "expired 2d 3h ago"
+        );
+        assert_eq!(format_relative("nbf", Duration::seconds(90)), "in 1m 30s");
+        assert_eq!(format_relative("iat", Duration::zero()), "0s ago");
+    }
+}
@@ -0,0 +1,90 @@
+use crate::jwt_app::{
+    copy_claim_to_clipboard, decode_jwt_token, print_token_csv, print_token_json,
+    print_token_pretty,
+};
+use crate::models::{JwtArgs, JwtPrint, TokenInfo};
+use anyhow::Result;
+use shared::command_line::tool_cli::{colors_enabled, parse_tool_cli_from};
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod jwt_app;
+mod models;
+
+/// JWT decoding and processing tool.
+///
+/// Decodes JWT tokens, validates expiration, and outputs claims in various formats.
+/// Optionally copies specific claims to clipboard. Exits gracefully on empty tokens.
+/// Colors are disabled with `--no-color` or the `NO_COLOR` environment variable.
+///
+/// # Exit Codes
+/// - 0: Success or empty claims
+/// - 1: Decoding failure or errors
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<JwtArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    if !colors_enabled() {
+        colored::control::set_override(false);
+    }
+
+    invocation.print_runtime_info();
+    let args = invocation.args;
+
+    let token_info = match decode_jwt_token(&args.token) {
+        Ok(info) => info,
+        Err(e) => {
+            error!("Error decoding token: {}", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    if token_info.claims.is_empty() {
+        eprintln!("Token claims are empty");
+        return EXIT_CODE_SUCCESS;
+    }
+
+    match print_claims(args, &token_info) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+/// Prints the claims in the format chosen, then copies the claim chosen to the clipboard.
+fn print_claims(args: JwtArgs, token_info: &TokenInfo) -> Result<()> {
+    match args.print {
+        JwtPrint::Pretty => print_token_pretty(&token_info.claims, &token_info.expiration_status),
+        JwtPrint::Csv => print_token_csv(&token_info.claims),
+        JwtPrint::Json => print_token_json(&token_info.claims)?,
+    }
+
+    if let Some(argument_to_clipboard) = args.claim_to_clipboard {
+        copy_claim_to_clipboard(argument_to_clipboard, &token_info.claims)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_returns_error_when_the_token_cannot_be_decoded() {
+        assert_eq!(run(["jwt", "not-a-token"]), EXIT_CODE_ERROR);
+    }
+}
@@ -27,6 +27,19 @@ pub struct JwtArgs {
     pub claim_to_clipboard: Option<String>,
 }
 
+/// What a claim holds, inferred from its name and value.
+#[derive(Debug, PartialEq)]
+pub enum ClaimType {
+    /// Seconds since the epoch (e.g.: `exp`, `iat`, `nbf`).
+    Timestamp(DateTime<Utc>),
+    Text,
+    Number,
+    Boolean,
+    List,
+    Object,
+    Null,
+}
+
 #[derive(Debug)]
 pub enum ExpirationStatus {
     Valid { expires_in: Duration },
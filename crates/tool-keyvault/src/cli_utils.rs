@@ -0,0 +1,204 @@
+use crate::models::{ExportFormat, KeyvaultArgs, VaultCommand};
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command, ValueEnum};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{GlobalArgs, ToolCli};
+use shared::secrets::secret_vault::{default_vault_path, is_valid_secret_name};
+use std::path::PathBuf;
+
+impl ToolCli for KeyvaultArgs {
+    const DISPLAY_NAME: &'static str = "Keyvault";
+
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Stores key/value secrets in a local file, encrypted with a master passphrase (age: scrypt + ChaCha20-Poly1305). \
+                The passphrase is read from KEYVAULT_PASSPHRASE, or asked for. Other tools can reference the secrets in their \
+                config files as ${keyvault:NAME}.",
+            )
+            .arg_required_else_help(true)
+            .subcommand_required(true)
+            .arg(
+                Arg::new("vault")
+                    .long("vault")
+                    .value_name("FILE")
+                    .global(true)
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Vault file. (Default: KEYVAULT_FILE, or keyvault.age in the rusted-toolbox config folder)"),
+            )
+            .subcommand(
+                Command::new("set")
+                    .about("Adds or replaces a secret")
+                    .arg(Arg::new("name").value_name("NAME").required(true).help("Name of the secret (letters, digits and '_')"))
+                    .arg(
+                        Arg::new("value")
+                            .value_name("VALUE")
+                            .help("Value of the secret. Ends up in the shell history: prefer piping it, or typing it when asked. (Default: stdin, or asked for)"),
+                    ),
+            )
+            .subcommand(
+                Command::new("get")
+                    .about("Prints the value of a secret")
+                    .arg(Arg::new("name").value_name("NAME").required(true)),
+            )
+            .subcommand(Command::new("list").about("Lists the names of the secrets (never the values)"))
+            .subcommand(
+                Command::new("rm")
+                    .visible_alias("remove")
+                    .about("Removes a secret")
+                    .arg(Arg::new("name").value_name("NAME").required(true)),
+            )
+            .subcommand(
+                Command::new("export")
+                    .about("Prints secrets as environment variable assignments, to be evaluated by the shell")
+                    .arg(
+                        Arg::new("names")
+                            .value_name("NAME")
+                            .num_args(0..)
+                            .action(ArgAction::Append)
+                            .help("Secrets to export. (Default: all)"),
+                    )
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .short('f')
+                            .value_name("FORMAT")
+                            .value_parser(clap::value_parser!(ExportFormat))
+                            .help("Syntax of the output. (Default: powershell on Windows, sh elsewhere)"),
+                    ),
+            )
+    }
+
+    fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+        let name = |sub_matches: &ArgMatches| {
+            sub_matches
+                .get_one::<String>("name")
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let command = match matches.subcommand() {
+            Some(("set", sub_matches)) => VaultCommand::Set {
+                name: name(sub_matches),
+                value: sub_matches.get_one::<String>("value").cloned(),
+            },
+            Some(("get", sub_matches)) => VaultCommand::Get {
+                name: name(sub_matches),
+            },
+            Some(("list", _)) => VaultCommand::List,
+            Some(("rm", sub_matches)) => VaultCommand::Remove {
+                name: name(sub_matches),
+            },
+            Some(("export", sub_matches)) => VaultCommand::Export {
+                names: sub_matches
+                    .get_many::<String>("names")
+                    .unwrap_or_default()
+                    .cloned()
+                    .collect(),
+                format: sub_matches
+                    .get_one::<ExportFormat>("format")
+                    .copied()
+                    .unwrap_or_default(),
+            },
+            _ => unreachable!("clap enforces one of the known subcommands"),
+        };
+
+        let vault = match matches.get_one::<PathBuf>("vault") {
+            Some(vault) => vault.clone(),
+            None => default_vault_path()?,
+        };
+
+        Ok(KeyvaultArgs { vault, command })
+    }
+
+    /// Secret names become environment variable names on `export`, so `set` only accepts names
+    /// that are valid as such.
+    fn validate(&self) -> Result<()> {
+        let names: Vec<&String> = match &self.command {
+            VaultCommand::Set { name, .. } => vec![name],
+            VaultCommand::Export { names, .. } => names.iter().collect(),
+            _ => Vec::new(),
+        };
+
+        if let Some(invalid) = names.into_iter().find(|name| !is_valid_secret_name(name)) {
+            return Err(anyhow!(
+                "Invalid secret name: '{}'. Use letters, digits and '_', not starting with a digit.",
+                invalid
+            ));
+        }
+
+        if self.vault.is_dir() {
+            return Err(anyhow!("Vault '{}' is a directory", self.vault.display()));
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let command = match &self.command {
+            VaultCommand::Set { name, .. } => format!("set {}", name),
+            VaultCommand::Get { name } => format!("get {}", name),
+            VaultCommand::List => "list".to_string(),
+            VaultCommand::Remove { name } => format!("rm {}", name),
+            VaultCommand::Export { format, .. } => format!(
+                "export ({})",
+                format
+                    .to_possible_value()
+                    .map(|value| value.get_name().to_string())
+                    .unwrap_or_default()
+            ),
+        };
+
+        vec![
+            ("Vault".to_string(), self.vault.display().to_string()),
+            ("Command".to_string(), command),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    #[test]
+    fn test_parse_export() {
+        let invocation = try_parse_tool_cli_from::<KeyvaultArgs, _, _>([
+            "keyvault",
+            "export",
+            "DB_PASSWORD",
+            "API_KEY",
+            "--format",
+            "dotenv",
+            "--vault",
+            "secrets.age",
+        ])
+        .unwrap();
+
+        assert_eq!(invocation.args.vault, PathBuf::from("secrets.age"));
+        match invocation.args.command {
+            VaultCommand::Export { names, format } => {
+                assert_eq!(names, vec!["DB_PASSWORD", "API_KEY"]);
+                assert_eq!(format, ExportFormat::Dotenv);
+            }
+            _ => panic!("expected export"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_names_are_rejected() {
+        let error = try_parse_tool_cli_from::<KeyvaultArgs, _, _>([
+            "keyvault",
+            "--vault",
+            "secrets.age",
+            "set",
+            "db-password",
+        ])
+        .err()
+        .unwrap();
+
+        assert!(error.to_string().contains("'db-password'"), "{}", error);
+    }
+}
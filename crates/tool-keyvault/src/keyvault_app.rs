@@ -0,0 +1,173 @@
+use crate::models::{ExportFormat, KeyvaultArgs, VaultCommand};
+use anyhow::{anyhow, Context, Result};
+use shared::secrets::secret_vault::{read_vault_passphrase, SecretVault};
+use std::io::{IsTerminal, Read};
+use std::path::Path;
+use tracing::info;
+
+pub fn run(args: &KeyvaultArgs) -> Result<()> {
+    match &args.command {
+        VaultCommand::Set { name, value } => set_secret(&args.vault, name, value.as_deref()),
+        VaultCommand::Get { name } => {
+            let vault = open_existing_vault(&args.vault)?;
+            let value = vault
+                .get(name)
+                .ok_or_else(|| anyhow!("Secret '{}' not found", name))?;
+
+            println!("{}", value);
+            Ok(())
+        }
+        VaultCommand::List => {
+            let vault = open_existing_vault(&args.vault)?;
+            if vault.is_empty() {
+                println!("The vault is empty.");
+            }
+
+            for (name, _) in vault.iter() {
+                println!("{}", name);
+            }
+            Ok(())
+        }
+        VaultCommand::Remove { name } => {
+            let mut vault = open_existing_vault(&args.vault)?;
+            if !vault.remove(name) {
+                return Err(anyhow!("Secret '{}' not found", name));
+            }
+
+            vault.save()?;
+            info!("Removed secret {} from {}", name, args.vault.display());
+            println!("Removed '{}'.", name);
+            Ok(())
+        }
+        VaultCommand::Export { names, format } => {
+            let vault = open_existing_vault(&args.vault)?;
+            for line in export_lines(&vault, names, *format)? {
+                println!("{}", line);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn set_secret(path: &Path, name: &str, value: Option<&str>) -> Result<()> {
+    // Read before the passphrase, so a piped value is never mistaken for it.
+    let value = match value {
+        Some(value) => value.to_string(),
+        None => read_secret_value(name)?,
+    };
+
+    // A typo in the passphrase of a new vault would lock it, so it's asked twice.
+    let is_new = !path.is_file();
+    let mut vault = SecretVault::open(path, read_vault_passphrase(is_new)?)?;
+    let replaced = vault.set(name, &value)?;
+    vault.save()?;
+
+    info!("Saved secret {} to {}", name, path.display());
+    if replaced {
+        println!("Updated '{}'.", name);
+    } else {
+        println!("Added '{}'.", name);
+    }
+
+    Ok(())
+}
+
+/// Reads the value of `set` from stdin, without the trailing line break, or asks for it
+/// (hidden) when running in a terminal.
+fn read_secret_value(name: &str) -> Result<String> {
+    if std::io::stdin().is_terminal() {
+        return dialoguer::Password::new()
+            .with_prompt(format!("Value of {}", name))
+            .allow_empty_password(true)
+            .interact()
+            .context("Failed to read the secret value");
+    }
+
+    let mut value = String::new();
+    std::io::stdin()
+        .read_to_string(&mut value)
+        .context("Failed to read the secret value from stdin")?;
+
+    let trimmed_len = value.trim_end_matches(['\r', '\n']).len();
+    value.truncate(trimmed_len);
+    Ok(value)
+}
+
+fn open_existing_vault(path: &Path) -> Result<SecretVault> {
+    if !path.is_file() {
+        return Err(anyhow!(
+            "Vault not found: {}. Use 'keyvault set' to create it.",
+            path.display()
+        ));
+    }
+
+    SecretVault::open(path, read_vault_passphrase(false)?)
+}
+
+/// Lines assigning the secrets (all of them, if `names` is empty) to environment variables.
+fn export_lines(
+    vault: &SecretVault,
+    names: &[String],
+    format: ExportFormat,
+) -> Result<Vec<String>> {
+    if names.is_empty() {
+        return Ok(vault
+            .iter()
+            .map(|(name, value)| format_export_line(format, name, value))
+            .collect());
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            vault
+                .get(name)
+                .map(|value| format_export_line(format, name, value))
+                .ok_or_else(|| anyhow!("Secret '{}' not found", name))
+        })
+        .collect()
+}
+
+fn format_export_line(format: ExportFormat, name: &str, value: &str) -> String {
+    match format {
+        ExportFormat::Sh => format!("export {}='{}'", name, value.replace('\'', "'\\''")),
+        ExportFormat::Powershell => format!("$env:{} = '{}'", name, value.replace('\'', "''")),
+        ExportFormat::Cmd => format!("set \"{}={}\"", name, value),
+        ExportFormat::Dotenv => format!(
+            "{}=\"{}\"",
+            name,
+            value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_export_line_quotes_values() {
+        let value = "it's \"quoted\"\nline";
+
+        assert_eq!(
+            format_export_line(ExportFormat::Sh, "TOKEN", value),
+            "export TOKEN='it'\\''s \"quoted\"\nline'"
+        );
+        assert_eq!(
+            format_export_line(ExportFormat::Powershell, "TOKEN", value),
+            "$env:TOKEN = 'it''s \"quoted\"\nline'"
+        );
+        assert_eq!(
+            format_export_line(ExportFormat::Dotenv, "TOKEN", value),
+            "TOKEN=\"it's \\\"quoted\\\"\\nline\""
+        );
+        assert_eq!(
+            format_export_line(ExportFormat::Cmd, "TOKEN", "abc"),
+            "set \"TOKEN=abc\""
+        );
+    }
+}
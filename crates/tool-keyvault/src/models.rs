@@ -0,0 +1,58 @@
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+/// Vault file and the operation to run on it.
+pub struct KeyvaultArgs {
+    pub vault: PathBuf,
+    pub command: VaultCommand,
+}
+
+pub enum VaultCommand {
+    /// Adds or replaces a secret. `None` reads the value from stdin, or asks for it.
+    Set {
+        name: String,
+        value: Option<String>,
+    },
+    Get {
+        name: String,
+    },
+    List,
+    Remove {
+        name: String,
+    },
+    /// Prints the secrets (all of them, if `names` is empty) as environment variable assignments.
+    Export {
+        names: Vec<String>,
+        format: ExportFormat,
+    },
+}
+
+impl VaultCommand {
+    /// Commands whose output is meant for scripts (`$(keyvault get X)`), so no header is printed.
+    pub fn prints_secrets(&self) -> bool {
+        matches!(self, VaultCommand::Get { .. } | VaultCommand::Export { .. })
+    }
+}
+
+/// Syntax of the lines printed by `export`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// `export NAME='value'` (bash, zsh, sh)
+    Sh,
+    /// `$env:NAME = 'value'`
+    Powershell,
+    /// `set "NAME=value"`
+    Cmd,
+    /// `NAME="value"`
+    Dotenv,
+}
+
+impl Default for ExportFormat {
+    fn default() -> Self {
+        if cfg!(windows) {
+            ExportFormat::Powershell
+        } else {
+            ExportFormat::Sh
+        }
+    }
+}
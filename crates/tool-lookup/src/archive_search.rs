@@ -0,0 +1,146 @@
+use crate::lookup_shared::path_matches_allowed;
+use anyhow::{anyhow, Context, Result};
+use flate2::read::GzDecoder;
+use shared::system::pathbuf_extensions::CompressionType;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+// Separates the archive from the member in the output: `logs.zip!/app/server.log:12`.
+const MEMBER_SEPARATOR: &str = "!/";
+
+// Calls `on_member(location, reader)` for every file inside the archive that passes the extension
+// filter. Archives inside archives are not opened. Returns how many members were searched.
+pub fn search_archive(
+    archive_path: &Path,
+    compression: CompressionType,
+    normalized_extensions: &[String],
+    mut on_member: impl FnMut(&str, &mut dyn BufRead),
+) -> Result<u64> {
+    let file = File::open(archive_path)?;
+    let archive_location = archive_path.display().to_string();
+
+    let mut visit = |member: &str, reader: &mut dyn Read| -> bool {
+        if !path_matches_allowed(Path::new(member), normalized_extensions) {
+            return false;
+        }
+
+        let location = format!("{}{}{}", archive_location, MEMBER_SEPARATOR, member);
+        on_member(&location, &mut BufReader::new(reader));
+        true
+    };
+
+    match compression {
+        CompressionType::Zip => search_zip(file, &mut visit),
+        CompressionType::Tar => search_tar(file, &mut visit),
+        CompressionType::TarGz => search_tar(GzDecoder::new(file), &mut visit),
+        CompressionType::Gzip => {
+            // A single compressed file: the member is the file name without `.gz`.
+            let member = archive_path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mut decoder = GzDecoder::new(file);
+            Ok(u64::from(visit(&member, &mut decoder)))
+        }
+        // Not searched: their members can't be streamed with the zip/tar readers.
+        CompressionType::SevenZip | CompressionType::Rar => Ok(0),
+    }
+}
+
+fn search_zip(file: File, visit: &mut dyn FnMut(&str, &mut dyn Read) -> bool) -> Result<u64> {
+    let mut archive = zip::ZipArchive::new(BufReader::new(file)).context("Invalid zip file")?;
+    let mut searched = 0;
+
+    for index in 0..archive.len() {
+        let mut member = archive.by_index(index)?;
+        if member.is_dir() {
+            continue;
+        }
+
+        let name = member.name().to_string();
+        if visit(&name, &mut member) {
+            searched += 1;
+        }
+    }
+
+    Ok(searched)
+}
+
+fn search_tar(
+    reader: impl Read,
+    visit: &mut dyn FnMut(&str, &mut dyn Read) -> bool,
+) -> Result<u64> {
+    let mut archive = tar::Archive::new(reader);
+    let mut searched = 0;
+
+    for entry in archive.entries().context("Invalid tar file")? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let name = entry.path()?.to_string_lossy().to_string();
+        if visit(&name, &mut entry) {
+            searched += 1;
+        }
+    }
+
+    Ok(searched)
+}
+
+// Text of every page, in order. Scanned PDFs (images only) have no text to extract.
+pub fn extract_pdf_text(path: &Path) -> Result<String> {
+    pdf_extract::extract_text(path).map_err(|e| anyhow!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_search_zip_filters_members_by_extension() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("lookup-archive-{}.zip", nanos));
+
+        {
+            let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+            let options = zip::write::SimpleFileOptions::default();
+            writer.start_file("logs/app.log", options).unwrap();
+            writer.write_all(b"first\nerror here\n").unwrap();
+            writer.start_file("image.png", options).unwrap();
+            writer.write_all(b"error, but not searched").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let mut found = Vec::new();
+        let searched = search_archive(
+            &path,
+            CompressionType::Zip,
+            &["log".to_string()],
+            |location, reader| {
+                for line in reader.lines().map_while(Result::ok) {
+                    found.push(format!("{} {}", location, line));
+                }
+            },
+        )
+        .unwrap();
+
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(searched, 1);
+        let location = format!("{}!/logs/app.log", path.display());
+        assert_eq!(
+            found,
+            vec![
+                format!("{} first", location),
+                format!("{} error here", location)
+            ]
+        );
+    }
+}
@@ -1,23 +1,31 @@
-use crate::models::{FilesLookupConfig, LookupCommand, PatternMode, TextLookupConfig};
+use crate::file_index::default_index_path;
+use crate::models::{
+    FilesLookupConfig, FuzzyOptions, IndexConfig, LookupCommand, PatternMode, TextLookupConfig,
+};
 use anyhow::Result;
 use clap::{Arg, ArgAction, ArgGroup, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::system::get_current_working_dir::get_current_working_dir_str;
+use std::ffi::OsString;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 
-pub fn get_cli_arguments() -> Result<LookupCommand> {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<LookupCommand> {
     let text_cmd = Command::new("text")
         .about("Search for text (case-insensitive) inside files")
         .arg(
             Arg::new("TEXT")
-                .help("Text to search for")
+                .help("Text(s) to search for. Lines matching any of them are printed.")
                 .required_unless_present("text")
+                .num_args(1..)
                 .index(1),
         )
         .arg(
             Arg::new("text")
                 .long("text")
                 .short('t')
-                .help("Text to search for (alternative to positional)")
+                .action(ArgAction::Append)
+                .help("Text to search for (alternative to positional). May be specified multiple times.")
                 .conflicts_with("TEXT"),
         )
         .arg(
@@ -47,6 +55,63 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
                 .action(ArgAction::SetTrue)
                 .help("If true, won't search recursively. (Default: false)"),
         )
+        .arg(
+            Arg::new("columns")
+                .long("columns")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("line-only")
+                .help("Print results as aligned columns: file:line, matched term, and line. (Default: false)"),
+        )
+        .arg(
+            Arg::new("no-color")
+                .long("no-color")
+                .action(ArgAction::SetTrue)
+                .help("Disable highlighting of the matched terms. Colors are also disabled when the output is not a terminal."),
+        )
+        .arg(
+            Arg::new("fuzzy")
+                .long("fuzzy")
+                .action(ArgAction::SetTrue)
+                .help("Fuzzy match the text(s) and print the best scoring lines, ranked by score. (Default: false)"),
+        )
+        .arg(
+            Arg::new("threshold")
+                .long("threshold")
+                .value_name("SCORE")
+                .value_parser(clap::value_parser!(i64).range(0..))
+                .default_value("0")
+                .requires("fuzzy")
+                .help("Minimum fuzzy score a line needs to be listed."),
+        )
+        .arg(
+            Arg::new("limit")
+                .long("limit")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .default_value("20")
+                .requires("fuzzy")
+                .help("Maximum number of fuzzy results to print."),
+        )
+        .arg(
+            Arg::new("search-archives")
+                .long("search-archives")
+                .action(ArgAction::SetTrue)
+                .help("Also search inside zip, tar, tar.gz and gz files. Members are filtered by --extension. (Default: false)"),
+        )
+        .arg(
+            Arg::new("search-pdf")
+                .long("search-pdf")
+                .action(ArgAction::SetTrue)
+                .help("Also search the text extracted from PDF files, regardless of --extension. (Default: false)"),
+        )
+        .arg(
+            Arg::new("interactive")
+                .long("interactive")
+                .short('i')
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["line-only", "columns"])
+                .help("Browse the results in a terminal UI: filter them as you type, preview the lines around each match, and open it in $EDITOR. (Default: false)"),
+        )
         .arg(
             Arg::new("extension")
                 .long("extension")
@@ -56,6 +121,7 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
                 .required(true)
                 .help("File extension to look for. May be specified multiple times."),
         )
+        .preset_arg_profile()
         .group(ArgGroup::new("text_input").args(["TEXT", "text"]));
 
     let files_cmd = Command::new("files")
@@ -124,9 +190,74 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
                 .action(ArgAction::SetTrue)
                 .help("Suppress final summary output"),
         )
+        .arg(
+            Arg::new("index")
+                .long("index")
+                .action(ArgAction::SetTrue)
+                .help("Search the file index (built with `lookup index`) instead of scanning the folders"),
+        )
+        .arg(index_db_arg().requires("index"))
+        .arg(
+            Arg::new("group-duplicates")
+                .long("group-duplicates")
+                .short('d')
+                .action(ArgAction::SetTrue)
+                .help("After the search, hash the matches and group the identical copies"),
+        )
+        .arg(
+            Arg::new("interactive")
+                .long("interactive")
+                .short('i')
+                .action(ArgAction::SetTrue)
+                .conflicts_with("group-duplicates")
+                .help("Browse the matches in a terminal UI: filter them as you type, preview each file, and open it in $EDITOR"),
+        )
+        .preset_arg_profile()
         .group(ArgGroup::new("pattern-mode").args(["regex", "wildcard"]))
         ;
 
+    let index_cmd = Command::new("index")
+        .about("Build or refresh the file index used by `lookup files --index`")
+        .after_help("Examples:\n  lookup index ~/projects\n  lookup files \"*.rs\" --path ~/projects/toolbox --index")
+        .arg(
+            Arg::new("PATH")
+                .help("Folder to index. Only the changes since the last run are written. (Default: current directory)")
+                .index(1),
+        )
+        .arg(index_db_arg())
+        .arg(
+            Arg::new("rebuild")
+                .long("rebuild")
+                .action(ArgAction::SetTrue)
+                .help("Drop the entries of this folder and index it from scratch"),
+        )
+        .arg(
+            Arg::new("no-header")
+                .long("no-header")
+                .short('n')
+                .action(ArgAction::SetTrue)
+                .help("Suppress header output"),
+        )
+        .arg(
+            Arg::new("no-progress")
+                .long("no-progress")
+                .action(ArgAction::SetTrue)
+                .help("Suppress progress (current folder) updates"),
+        )
+        .arg(
+            Arg::new("no-errors")
+                .long("no-errors")
+                .action(ArgAction::SetTrue)
+                .help("Suppress error messages during traversal"),
+        )
+        .arg(
+            Arg::new("no-summary")
+                .long("no-summary")
+                .action(ArgAction::SetTrue)
+                .help("Suppress final summary output"),
+        )
+        .preset_arg_profile();
+
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -135,17 +266,20 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
         )
         .subcommand(text_cmd)
         .subcommand(files_cmd)
+        .subcommand(index_cmd)
         .subcommand_required(true)
         .arg_required_else_help(true)
-        .get_matches();
+        .get_matches_from(argv);
 
     match matches.subcommand() {
         Some(("text", sub_m)) => {
-            let text = sub_m
-                .get_one::<String>("TEXT")
+            let texts = sub_m
+                .get_many::<String>("TEXT")
+                .or_else(|| sub_m.get_many::<String>("text"))
+                .unwrap_or_default()
+                .filter(|t| !t.is_empty())
                 .cloned()
-                .or_else(|| sub_m.get_one::<String>("text").cloned())
-                .unwrap_or_default();
+                .collect::<Vec<String>>();
 
             let path = sub_m
                 .get_one::<String>("path")
@@ -161,14 +295,28 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
             let no_header = sub_m.get_flag("no-header");
             let current_only = sub_m.get_flag("current-only");
             let line_only = sub_m.get_flag("line-only");
+            let columns = sub_m.get_flag("columns");
+            let color = !sub_m.get_flag("no-color") && std::io::stdout().is_terminal();
+            let profile = sub_m.get_flag("profile");
+            let fuzzy = sub_m.get_flag("fuzzy").then(|| FuzzyOptions {
+                threshold: *sub_m.get_one::<i64>("threshold").unwrap_or(&0),
+                limit: *sub_m.get_one::<u64>("limit").unwrap_or(&20) as usize,
+            });
 
             Ok(LookupCommand::Text(TextLookupConfig::new(
                 path,
-                text,
+                texts,
                 file_extensions,
                 no_header,
                 current_only,
                 line_only,
+                columns,
+                color,
+                profile,
+                fuzzy,
+                sub_m.get_flag("search-archives"),
+                sub_m.get_flag("search-pdf"),
+                sub_m.get_flag("interactive"),
             )))
         }
         Some(("files", sub_m)) => {
@@ -195,6 +343,9 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
             let no_progress = sub_m.get_flag("no-progress");
             let no_errors = sub_m.get_flag("no-errors");
             let no_summary = sub_m.get_flag("no-summary");
+            let profile = sub_m.get_flag("profile");
+            let index = sub_m.get_flag("index").then(|| get_index_db_path(sub_m));
+            let group_duplicates = sub_m.get_flag("group-duplicates");
 
             Ok(LookupCommand::Files(FilesLookupConfig::new(
                 path,
@@ -206,8 +357,29 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
                 no_progress,
                 no_errors,
                 no_summary,
+                profile,
+                index,
+                group_duplicates,
+                sub_m.get_flag("interactive"),
             )))
         }
+        Some(("index", sub_m)) => {
+            let path = sub_m
+                .get_one::<String>("PATH")
+                .cloned()
+                .unwrap_or_else(|| get_current_working_dir_str().unwrap_or_default());
+
+            Ok(LookupCommand::Index(IndexConfig {
+                path,
+                db_path: get_index_db_path(sub_m),
+                rebuild: sub_m.get_flag("rebuild"),
+                no_header: sub_m.get_flag("no-header"),
+                no_progress: sub_m.get_flag("no-progress"),
+                no_errors: sub_m.get_flag("no-errors"),
+                no_summary: sub_m.get_flag("no-summary"),
+                profile: sub_m.get_flag("profile"),
+            }))
+        }
         _ => {
             // Default to help if no subcommand provided
             // Emulate `text` subcommand with help by returning an error
@@ -216,12 +388,34 @@ pub fn get_cli_arguments() -> Result<LookupCommand> {
             let path = get_current_working_dir_str().unwrap_or_default();
             Ok(LookupCommand::Text(TextLookupConfig::new(
                 path,
-                String::new(),
+                vec![],
                 vec![],
                 false,
                 false,
                 false,
+                false,
+                false,
+                false,
+                None,
+                false,
+                false,
+                false,
             )))
         }
     }
 }
+
+fn index_db_arg() -> Arg {
+    Arg::new("db")
+        .long("db")
+        .value_name("FILE")
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Index database file. (Default: lookup-index.db, next to the executable)")
+}
+
+fn get_index_db_path(matches: &clap::ArgMatches) -> PathBuf {
+    matches
+        .get_one::<PathBuf>("db")
+        .cloned()
+        .unwrap_or_else(default_index_path)
+}
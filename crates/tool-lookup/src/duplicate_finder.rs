@@ -0,0 +1,216 @@
+use crate::file_index::{format_index_age, unix_seconds};
+use crate::lookup_shared::clean_path_for_display;
+use sha2::{Digest, Sha256};
+use shared::utils::format_bytes_to_string::format_bytes_to_string;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+// A file that has at least one identical copy among the matches.
+#[derive(Debug, Clone)]
+pub struct DuplicateCopy {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+// Identical copies of the same content, oldest first: the first one is usually the original.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub hash: String,
+    pub copies: Vec<DuplicateCopy>,
+}
+
+#[derive(Debug, Default)]
+pub struct DuplicateReport {
+    pub groups: Vec<DuplicateGroup>,
+    pub hashed: u64,
+    pub errors: Vec<(PathBuf, io::Error)>,
+}
+
+impl DuplicateReport {
+    pub fn duplicate_files(&self) -> usize {
+        self.groups.iter().map(|group| group.copies.len()).sum()
+    }
+
+    // Space that would be freed by keeping a single copy of each group.
+    pub fn reclaimable_bytes(&self) -> u64 {
+        self.groups
+            .iter()
+            .map(|group| group.size * (group.copies.len() as u64 - 1))
+            .sum()
+    }
+}
+
+// Groups the files with identical content. Only files that share their size with another one are
+// hashed, so most of the matches are never read. Empty files are ignored.
+pub fn find_duplicates(paths: &[PathBuf]) -> DuplicateReport {
+    let mut report = DuplicateReport::default();
+    let mut by_size: HashMap<u64, Vec<(&Path, Option<SystemTime>)>> = HashMap::new();
+
+    for path in paths {
+        match fs::metadata(path) {
+            Ok(meta) if meta.len() > 0 => by_size
+                .entry(meta.len())
+                .or_default()
+                .push((path, meta.modified().ok())),
+            Ok(_) => {}
+            Err(e) => report.errors.push((path.clone(), e)),
+        }
+    }
+
+    let mut by_content: HashMap<(u64, String), Vec<DuplicateCopy>> = HashMap::new();
+    for (size, candidates) in by_size.into_iter().filter(|(_, c)| c.len() > 1) {
+        for (path, modified) in candidates {
+            report.hashed += 1;
+            match hash_file(path) {
+                Ok(hash) => by_content
+                    .entry((size, hash))
+                    .or_default()
+                    .push(DuplicateCopy {
+                        path: path.to_path_buf(),
+                        modified,
+                    }),
+                Err(e) => report.errors.push((path.to_path_buf(), e)),
+            }
+        }
+    }
+
+    report.groups = by_content
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|((size, hash), mut copies)| {
+            copies.sort_by(|a, b| a.modified.cmp(&b.modified).then(a.path.cmp(&b.path)));
+            DuplicateGroup { size, hash, copies }
+        })
+        .collect();
+
+    // Biggest waste first.
+    report.groups.sort_by(|a, b| {
+        let waste = |g: &DuplicateGroup| g.size * g.copies.len() as u64;
+        waste(b)
+            .cmp(&waste(a))
+            .then_with(|| a.copies[0].path.cmp(&b.copies[0].path))
+    });
+
+    report
+}
+
+pub fn print_duplicate_report(report: &DuplicateReport, no_errors: bool) {
+    if !no_errors {
+        for (path, e) in &report.errors {
+            println!("{}: {}", e, clean_path_for_display(path));
+        }
+    }
+
+    if report.groups.is_empty() {
+        println!("Duplicates: none");
+        return;
+    }
+
+    println!(
+        "Duplicates: {} group(s), {} file(s), {} reclaimable",
+        report.groups.len(),
+        report.duplicate_files(),
+        format_bytes_to_string(&report.reclaimable_bytes())
+    );
+
+    for (i, group) in report.groups.iter().enumerate() {
+        println!(
+            "[{}] {} copies, {} each, sha256 {}",
+            i + 1,
+            group.copies.len(),
+            format_bytes_to_string(&group.size),
+            &group.hash[..12]
+        );
+        for (j, copy) in group.copies.iter().enumerate() {
+            let modified = copy
+                .modified
+                .map(|m| format!("modified {}", format_index_age(unix_seconds(m))))
+                .unwrap_or_else(|| "modified unknown".to_string());
+            let marker = if j == 0 { " (oldest)" } else { "" };
+            println!(
+                "    {} [{}]{}",
+                clean_path_for_display(&copy.path),
+                modified,
+                marker
+            );
+        }
+    }
+}
+
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("lookup-dupes-{}-{}", name, nanos));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_identical_content_only() {
+        let root = temp_dir("groups");
+        let files = [
+            ("a.txt", "same content"),
+            ("b.txt", "same content"),
+            ("c.txt", "same-content"), // same size, different bytes
+            ("d.txt", "unique"),
+            ("e.txt", ""),
+            ("f.txt", ""),
+        ];
+        let paths = files
+            .iter()
+            .map(|(name, content)| {
+                let path = root.join(name);
+                fs::write(&path, content).unwrap();
+                path
+            })
+            .collect::<Vec<_>>();
+
+        let report = find_duplicates(&paths);
+
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.hashed, 3);
+        assert_eq!(report.reclaimable_bytes(), 12);
+        let mut grouped = report.groups[0]
+            .copies
+            .iter()
+            .map(|c| c.path.file_name().unwrap().to_str().unwrap())
+            .collect::<Vec<_>>();
+        grouped.sort();
+        assert_eq!(grouped, vec!["a.txt", "b.txt"]);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,336 @@
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+const DEFAULT_INDEX_FILE: &str = "lookup-index.db";
+
+// What changed in the index after a refresh.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RefreshStats {
+    pub folders: u64,
+    pub added: u64,
+    pub updated: u64,
+    pub removed: u64,
+    pub unchanged: u64,
+    pub errors: u64,
+}
+
+// Persistent index of file names, sizes and modification times, stored in SQLite.
+// Paths are stored absolute (canonical), so every folder below an indexed root can be queried
+// with a simple range over the path.
+pub struct FileIndex {
+    conn: Connection,
+}
+
+// The index is kept next to the executable by default, so it is shared by every working folder.
+pub fn default_index_path() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(DEFAULT_INDEX_FILE)
+}
+
+impl FileIndex {
+    pub fn open(db_path: &Path) -> Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).ok();
+            }
+        }
+
+        let conn = Connection::open(db_path)
+            .with_context(|| format!("Failed to open index '{}'", db_path.display()))?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS roots (
+                path TEXT PRIMARY KEY,
+                indexed_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS files (
+                path TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                modified INTEGER NOT NULL
+            );
+            "#,
+        )?;
+
+        Ok(Self { conn })
+    }
+
+    // Walks `root` and writes only what changed: new files, files with a different size or
+    // modification time, and files that no longer exist. With `rebuild`, the entries of the root
+    // are dropped first.
+    pub fn refresh(
+        &mut self,
+        root: &Path,
+        rebuild: bool,
+        mut on_folder: impl FnMut(&Path),
+        mut on_error: impl FnMut(&walkdir::Error),
+    ) -> Result<RefreshStats> {
+        let root_str = path_to_str(root)?;
+        let (lower, upper) = prefix_range(root_str);
+        let mut stats = RefreshStats::default();
+
+        let tx = self.conn.transaction()?;
+
+        if rebuild {
+            tx.execute(
+                "DELETE FROM files WHERE path >= ?1 AND path < ?2",
+                params![lower, upper],
+            )?;
+        }
+
+        let mut known: HashMap<String, (i64, i64)> = {
+            let mut stmt = tx
+                .prepare("SELECT path, size, modified FROM files WHERE path >= ?1 AND path < ?2")?;
+            let rows = stmt.query_map(params![lower, upper], |row| {
+                Ok((row.get(0)?, (row.get(1)?, row.get(2)?)))
+            })?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        {
+            let mut upsert = tx.prepare(
+                "INSERT OR REPLACE INTO files (path, name, size, modified) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+
+            for entry in WalkDir::new(root) {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        stats.errors += 1;
+                        on_error(&e);
+                        continue;
+                    }
+                };
+
+                if entry.file_type().is_dir() {
+                    stats.folders += 1;
+                    on_folder(entry.path());
+                    continue;
+                }
+
+                // Same as the live search: names that aren't valid UTF-8 are skipped.
+                let (Some(path), Some(name)) = (entry.path().to_str(), entry.file_name().to_str())
+                else {
+                    continue;
+                };
+
+                let metadata = match entry.metadata() {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        stats.errors += 1;
+                        on_error(&e);
+                        continue;
+                    }
+                };
+                let size = metadata.len() as i64;
+                let modified = metadata.modified().map(unix_seconds).unwrap_or(0);
+
+                match known.remove(path) {
+                    Some(previous) if previous == (size, modified) => stats.unchanged += 1,
+                    Some(_) => {
+                        upsert.execute(params![path, name, size, modified])?;
+                        stats.updated += 1;
+                    }
+                    None => {
+                        upsert.execute(params![path, name, size, modified])?;
+                        stats.added += 1;
+                    }
+                }
+            }
+
+            let mut delete = tx.prepare("DELETE FROM files WHERE path = ?1")?;
+            for path in known.keys() {
+                delete.execute([path])?;
+                stats.removed += 1;
+            }
+        }
+
+        tx.execute(
+            "INSERT OR REPLACE INTO roots (path, indexed_at) VALUES (?1, ?2)",
+            params![root_str, unix_seconds(SystemTime::now())],
+        )?;
+        tx.commit()?;
+
+        Ok(stats)
+    }
+
+    // The closest indexed root that contains `path`, and when it was indexed (unix seconds).
+    pub fn covering_root(&self, path: &Path) -> Result<Option<(String, i64)>> {
+        let path = path_to_str(path)?;
+        let mut stmt = self.conn.prepare("SELECT path, indexed_at FROM roots")?;
+        let roots = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(roots
+            .into_iter()
+            .filter(|(root, _)| is_same_or_below(path, root))
+            .max_by_key(|(root, _)| root.len()))
+    }
+
+    // Calls `on_file(path, name)` for every indexed file below `folder`. Returns how many files
+    // were visited.
+    pub fn for_each_file(
+        &self,
+        folder: &Path,
+        recursive: bool,
+        mut on_file: impl FnMut(&str, &str),
+    ) -> Result<u64> {
+        let folder_str = path_to_str(folder)?;
+        let (lower, upper) = prefix_range(folder_str);
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, name FROM files WHERE path >= ?1 AND path < ?2 ORDER BY path")?;
+        let mut rows = stmt.query(params![lower, upper])?;
+
+        let mut count = 0;
+        while let Some(row) = rows.next()? {
+            let path: String = row.get(0)?;
+            if !recursive && path[lower.len()..].contains(MAIN_SEPARATOR) {
+                continue;
+            }
+
+            let name: String = row.get(1)?;
+            count += 1;
+            on_file(&path, &name);
+        }
+
+        Ok(count)
+    }
+}
+
+// Human friendly age of an index, like "5m ago".
+pub fn format_index_age(indexed_at: i64) -> String {
+    let seconds = (unix_seconds(SystemTime::now()) - indexed_at).max(0);
+
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3_599 => format!("{}m ago", seconds / 60),
+        3_600..=86_399 => format!("{}h ago", seconds / 3_600),
+        _ => format!("{}d ago", seconds / 86_400),
+    }
+}
+
+fn path_to_str(path: &Path) -> Result<&str> {
+    path.to_str()
+        .ok_or_else(|| anyhow!("Path is not valid UTF-8: {}", path.display()))
+}
+
+pub fn unix_seconds(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Every path below `folder` sorts between `folder/` and `folder0` (`0` comes right after `/`;
+// on Windows, `]` comes right after `\`).
+fn prefix_range(folder: &str) -> (String, String) {
+    let lower = if folder.ends_with(MAIN_SEPARATOR) {
+        folder.to_string()
+    } else {
+        format!("{}{}", folder, MAIN_SEPARATOR)
+    };
+
+    let next = char::from_u32(MAIN_SEPARATOR as u32 + 1).unwrap_or(char::MAX);
+    let upper = format!("{}{}", &lower[..lower.len() - 1], next);
+
+    (lower, upper)
+}
+
+fn is_same_or_below(path: &str, root: &str) -> bool {
+    if path == root {
+        return true;
+    }
+
+    let (lower, _) = prefix_range(root);
+    path.starts_with(&lower)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("lookup-index-{}-{}", name, nanos));
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    fn refresh(index: &mut FileIndex, root: &Path) -> RefreshStats {
+        index.refresh(root, false, |_| {}, |_| {}).unwrap()
+    }
+
+    #[test]
+    fn test_refresh_only_writes_changes() {
+        let root = temp_dir("refresh");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+        fs::write(root.join("sub").join("b.txt"), "b").unwrap();
+
+        let mut index = FileIndex::open(&root.join("index.db")).unwrap();
+        let first = refresh(&mut index, &root.join("sub"));
+        assert_eq!(first.added, 1);
+
+        fs::write(root.join("sub").join("b.txt"), "bigger").unwrap();
+        fs::write(root.join("sub").join("c.txt"), "c").unwrap();
+        let second = refresh(&mut index, &root.join("sub"));
+        assert_eq!((second.added, second.updated, second.removed), (1, 1, 0));
+
+        fs::remove_file(root.join("sub").join("c.txt")).unwrap();
+        let third = refresh(&mut index, &root.join("sub"));
+        assert_eq!((third.removed, third.unchanged), (1, 1));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_for_each_file_respects_folder_and_recursion() {
+        let root = temp_dir("query");
+        fs::create_dir_all(root.join("docs").join("old")).unwrap();
+        fs::create_dir_all(root.join("docs2")).unwrap();
+        fs::write(root.join("docs").join("a.md"), "a").unwrap();
+        fs::write(root.join("docs").join("old").join("b.md"), "b").unwrap();
+        fs::write(root.join("docs2").join("c.md"), "c").unwrap();
+
+        let db_dir = temp_dir("query-db");
+        let mut index = FileIndex::open(&db_dir.join("index.db")).unwrap();
+        refresh(&mut index, &root);
+
+        let mut names = Vec::new();
+        let count = index
+            .for_each_file(&root.join("docs"), true, |_, name| {
+                names.push(name.to_string())
+            })
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(names, vec!["a.md", "b.md"]);
+
+        let count = index
+            .for_each_file(&root.join("docs"), false, |_, _| {})
+            .unwrap();
+        assert_eq!(count, 1);
+
+        assert!(index.covering_root(&root.join("docs")).unwrap().is_some());
+        assert!(index.covering_root(&db_dir).unwrap().is_none());
+
+        fs::remove_dir_all(&root).ok();
+        fs::remove_dir_all(&db_dir).ok();
+    }
+}
@@ -0,0 +1,195 @@
+use crate::text_matcher::MatchSpan;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+// Scores lines against the search terms, skim-style: the characters of a term must appear in
+// order, and consecutive characters, word starts and camelCase humps score higher.
+pub struct FuzzyScorer {
+    matcher: SkimMatcherV2,
+    needles: Vec<String>,
+}
+
+// The best scoring term of a line, with the matched characters.
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub term_index: usize,
+    pub spans: Vec<MatchSpan>,
+}
+
+impl FuzzyScorer {
+    pub fn new(needles: &[String]) -> Self {
+        Self {
+            matcher: SkimMatcherV2::default().ignore_case(),
+            needles: needles.to_vec(),
+        }
+    }
+
+    // Returns None if no term matches. With multiple terms, the highest score wins.
+    pub fn score_line(&self, line: &str) -> Option<FuzzyMatch> {
+        let mut best: Option<FuzzyMatch> = None;
+
+        for (term_index, needle) in self.needles.iter().enumerate() {
+            if needle.is_empty() {
+                continue;
+            }
+
+            let Some((score, indices)) = self.matcher.fuzzy_indices(line, needle) else {
+                continue;
+            };
+
+            if best.as_ref().is_some_and(|b| b.score >= score) {
+                continue;
+            }
+
+            best = Some(FuzzyMatch {
+                score,
+                term_index,
+                spans: char_indices_to_spans(line, &indices, term_index),
+            });
+        }
+
+        best
+    }
+}
+
+// The matcher returns char indices. Highlighting works with byte offsets, so they are converted,
+// and consecutive characters are merged into a single span.
+fn char_indices_to_spans(line: &str, indices: &[usize], term_index: usize) -> Vec<MatchSpan> {
+    let mut spans: Vec<MatchSpan> = Vec::new();
+    let mut wanted = indices.iter().peekable();
+
+    for (char_index, (start, c)) in line.char_indices().enumerate() {
+        if wanted.peek() != Some(&&char_index) {
+            continue;
+        }
+        wanted.next();
+
+        let end = start + c.len_utf8();
+        match spans.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => spans.push(MatchSpan {
+                start,
+                end,
+                term_index,
+            }),
+        }
+    }
+
+    spans
+}
+
+// Keeps only the best `limit` items seen, so memory doesn't grow with the number of matches.
+// Ties keep the item found first.
+pub struct TopMatches<T> {
+    limit: usize,
+    seen: u64,
+    heap: BinaryHeap<Reverse<Ranked<T>>>,
+}
+
+struct Ranked<T> {
+    score: i64,
+    seq: u64,
+    item: T,
+}
+
+impl<T> Ranked<T> {
+    fn key(&self) -> (i64, Reverse<u64>) {
+        (self.score, Reverse(self.seq))
+    }
+}
+
+impl<T> PartialEq for Ranked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<T> Eq for Ranked<T> {}
+
+impl<T> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl<T> TopMatches<T> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            seen: 0,
+            heap: BinaryHeap::with_capacity(limit + 1),
+        }
+    }
+
+    pub fn push(&mut self, score: i64, item: T) {
+        self.heap.push(Reverse(Ranked {
+            score,
+            seq: self.seen,
+            item,
+        }));
+        self.seen += 1;
+
+        if self.heap.len() > self.limit {
+            self.heap.pop();
+        }
+    }
+
+    // Best first.
+    pub fn into_sorted_vec(self) -> Vec<(i64, T)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ranked)| (ranked.score, ranked.item))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_line_ranks_closer_matches_higher() {
+        let scorer = FuzzyScorer::new(&["cfg".to_string()]);
+
+        let tight = scorer.score_line("let cfg = load();").unwrap();
+        let loose = scorer.score_line("ticket raft bag").unwrap();
+
+        assert!(tight.score > loose.score);
+        assert!(scorer.score_line("nothing here").is_none());
+    }
+
+    #[test]
+    fn test_score_line_spans_are_byte_offsets() {
+        let scorer = FuzzyScorer::new(&["ab".to_string()]);
+
+        let m = scorer.score_line("ção AB").unwrap();
+
+        assert_eq!(
+            m.spans,
+            vec![MatchSpan {
+                start: 6,
+                end: 8,
+                term_index: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_top_matches_keeps_best_in_order() {
+        let mut top = TopMatches::new(3);
+        for (score, name) in [(5, "a"), (9, "b"), (1, "c"), (9, "d"), (7, "e")] {
+            top.push(score, name);
+        }
+
+        assert_eq!(top.into_sorted_vec(), vec![(9, "b"), (9, "d"), (7, "e")]);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::cli_utils::get_cli_arguments;
+use crate::lookup_files_app::{print_header as print_files_header, run_files_lookup};
+use crate::lookup_index_app::{print_header as print_index_header, run_index};
+use crate::lookup_text_app::{print_header as print_text_header, run_text_lookup};
+use crate::models::LookupCommand;
+use anyhow::Result;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+mod archive_search;
+mod cli_utils;
+mod duplicate_finder;
+mod file_index;
+mod fuzzy_matcher;
+mod lookup_files_app;
+mod lookup_index_app;
+mod lookup_shared;
+mod lookup_text_app;
+mod models;
+mod text_matcher;
+mod ui;
+
+/// Text and file search.
+///
+/// Parses arguments and runs the subcommand: `text`, `files`, or `index`.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    match run_tool(argv.into_iter().map(Into::into).collect()) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    match get_cli_arguments(argv)? {
+        LookupCommand::Text(cfg) => {
+            if !cfg.no_header {
+                print_text_header(&cfg);
+            }
+            run_text_lookup(&cfg)?;
+        }
+        LookupCommand::Files(cfg) => {
+            if !cfg.no_header {
+                print_files_header(&cfg);
+            }
+            run_files_lookup(&cfg)?;
+        }
+        LookupCommand::Index(cfg) => {
+            if !cfg.no_header {
+                print_index_header(&cfg);
+            }
+            run_index(&cfg)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,39 +1,22 @@
+use crate::duplicate_finder::{find_duplicates, print_duplicate_report};
+use crate::file_index::{format_index_age, FileIndex};
+use crate::lookup_shared::{
+    clean_path_for_display, clear_progress_line, print_progress_once, CLEAR_LINE,
+};
 use crate::models::{FilesLookupConfig, PatternMode};
+use crate::ui::browser_item::BrowserItem;
+use crate::ui::result_browser::ResultBrowser;
 use anyhow::{anyhow, Result};
 use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
 use shared::constants::general::DASH_LINE;
+use shared::utils::phase_profiler::PhaseProfiler;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 use walkdir::WalkDir;
 
-const CLEAR_LINE: &str = "\r\x1b[2K";
-
-fn clear_progress_line() {
-    eprint!("{}", CLEAR_LINE);
-    let _ = std::io::stderr().flush();
-}
-
-fn clean_path_for_display(p: &Path) -> String {
-    #[cfg(windows)]
-    {
-        let s = p.display().to_string();
-        if let Some(rest) = s.strip_prefix(r"\\?\UNC\") {
-            return format!(r"\\{}", rest);
-        }
-        if let Some(rest) = s.strip_prefix(r"\\?\") {
-            return rest.to_string();
-        }
-        s
-    }
-    #[cfg(not(windows))]
-    {
-        p.display().to_string()
-    }
-}
-
 pub fn print_header(args: &FilesLookupConfig) {
     println!("Lookup v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
@@ -49,10 +32,23 @@ pub fn print_header(args: &FilesLookupConfig) {
         args.case_sensitive,
         args.recursive
     );
+    if let Some(db_path) = &args.index {
+        println!("Index: {}", db_path.display());
+    }
+    if args.group_duplicates {
+        println!("Group duplicates: enabled");
+    }
+    if args.interactive {
+        println!("Interactive: enabled");
+    }
+    if args.profile {
+        println!("Profiling: enabled");
+    }
 }
 
 pub fn run_files_lookup(cfg: &FilesLookupConfig) -> Result<()> {
     let start = Instant::now();
+    let mut profiler = PhaseProfiler::new(cfg.profile);
 
     let base_path = PathBuf::from(&cfg.path);
     if !base_path.exists() {
@@ -63,16 +59,25 @@ pub fn run_files_lookup(cfg: &FilesLookupConfig) -> Result<()> {
     }
 
     // Build matchers
-    let matcher = build_matcher(&cfg.patterns, cfg.pattern_mode, cfg.case_sensitive)?;
+    let matcher = profiler.measure("build matcher", || {
+        build_matcher(&cfg.patterns, cfg.pattern_mode, cfg.case_sensitive)
+    })?;
+
+    if let Some(db_path) = &cfg.index {
+        return run_indexed_files_lookup(cfg, db_path, &base_path, &matcher, start, profiler);
+    }
 
     let mut folders_count: u64 = 0;
     let mut files_count: u64 = 0;
     let mut matches_count: u64 = 0;
+    // Kept only when the duplicates are grouped, or the matches browsed, at the end.
+    let mut matched_paths: Vec<PathBuf> = Vec::new();
 
     if cfg.recursive {
         // Use WalkDir to report progress and errors
         let mut last_dir_printed: Option<PathBuf> = None;
-        for entry_res in WalkDir::new(&base_path).into_iter() {
+        let mut walker = WalkDir::new(&base_path).into_iter();
+        while let Some(entry_res) = profiler.measure("walk folders", || walker.next()) {
             match entry_res {
                 Ok(entry) => {
                     if entry.file_type().is_dir() {
@@ -89,14 +94,18 @@ pub fn run_files_lookup(cfg: &FilesLookupConfig) -> Result<()> {
                         Some(s) => s,
                         None => continue, // skip invalid utf-8 names
                     };
-                    if is_match(&matcher, name) {
+                    if profiler.measure("match names", || is_match(&matcher, name)) {
                         matches_count += 1;
                         // Clear progress line before printing a match to avoid overlap
                         if !cfg.no_progress {
                             clear_progress_line();
                         }
-                        let abs = absolute_path_str(entry.path());
-                        println!("{}", abs);
+                        if !cfg.interactive {
+                            println!("{}", absolute_path_str(entry.path()));
+                        }
+                        if cfg.group_duplicates || cfg.interactive {
+                            matched_paths.push(entry.into_path());
+                        }
                     }
                 }
                 Err(e) => {
@@ -126,7 +135,7 @@ pub fn run_files_lookup(cfg: &FilesLookupConfig) -> Result<()> {
             eprint!("{}Reading: {}", CLEAR_LINE, base_path.display());
             let _ = std::io::stderr().flush();
         }
-        let dir_iter = match fs::read_dir(&base_path) {
+        let dir_iter = match profiler.measure("walk folders", || fs::read_dir(&base_path)) {
             Ok(it) => it,
             Err(e) => {
                 if !cfg.no_errors {
@@ -146,13 +155,17 @@ pub fn run_files_lookup(cfg: &FilesLookupConfig) -> Result<()> {
                     Some(s) => s,
                     None => continue,
                 };
-                if is_match(&matcher, name) {
+                if profiler.measure("match names", || is_match(&matcher, name)) {
                     matches_count += 1;
                     if !cfg.no_progress {
                         clear_progress_line();
                     }
-                    let abs = absolute_path_str(&path);
-                    println!("{}", abs);
+                    if !cfg.interactive {
+                        println!("{}", absolute_path_str(&path));
+                    }
+                    if cfg.group_duplicates || cfg.interactive {
+                        matched_paths.push(path);
+                    }
                 }
             }
         }
@@ -161,29 +174,119 @@ pub fn run_files_lookup(cfg: &FilesLookupConfig) -> Result<()> {
         }
     }
 
+    let duplicates = group_duplicates(cfg, &matched_paths, &mut profiler);
+    browse_matches(cfg, matched_paths)?;
+
     if !cfg.no_summary {
         let elapsed = start.elapsed();
         println!(
-            "Summary: dirs={}, files={}, matches={}, elapsed={:?}",
-            folders_count, files_count, matches_count, elapsed
+            "Summary: dirs={}, files={}, matches={}{}, elapsed={:?}",
+            folders_count, files_count, matches_count, duplicates, elapsed
         );
     }
 
+    profiler.print_report();
+
     Ok(())
 }
 
-fn print_progress_once(last_dir_printed: &mut Option<PathBuf>, current: &Path) {
-    // Only print when the directory changes to reduce noise.
-    let cur_dir = if current.is_dir() {
-        current
-    } else {
-        current.parent().unwrap_or(current)
+// Same as the live search, but the names come from the index, so no folder is read.
+fn run_indexed_files_lookup(
+    cfg: &FilesLookupConfig,
+    db_path: &Path,
+    base_path: &Path,
+    matcher: &Matcher,
+    start: Instant,
+    mut profiler: PhaseProfiler,
+) -> Result<()> {
+    if !base_path.is_dir() {
+        return Err(anyhow!("--index expects a folder: {}", base_path.display()));
+    }
+    let folder = absolute_path(base_path);
+
+    let index = profiler.measure("open index", || FileIndex::open(db_path))?;
+    let Some((root, indexed_at)) = index.covering_root(&folder)? else {
+        return Err(anyhow!(
+            "'{}' is not in the index. Run: lookup index \"{}\"",
+            clean_path_for_display(&folder),
+            clean_path_for_display(&folder)
+        ));
     };
-    if last_dir_printed.as_ref().map(|p| p.as_path()) != Some(cur_dir) {
-        *last_dir_printed = Some(cur_dir.to_path_buf());
-        eprint!("{}Reading: {}", CLEAR_LINE, cur_dir.display());
-        let _ = std::io::stderr().flush();
+
+    let mut matches_count: u64 = 0;
+    let mut matched_paths: Vec<PathBuf> = Vec::new();
+    let files_count = profiler.measure("query index", || {
+        index.for_each_file(&folder, cfg.recursive, |path, name| {
+            if is_match(matcher, name) {
+                matches_count += 1;
+                if !cfg.interactive {
+                    println!("{}", clean_path_for_display(Path::new(path)));
+                }
+                if cfg.group_duplicates || cfg.interactive {
+                    matched_paths.push(PathBuf::from(path));
+                }
+            }
+        })
+    })?;
+
+    // The index has no content hashes, so the matches are read from disk.
+    let duplicates = group_duplicates(cfg, &matched_paths, &mut profiler);
+    browse_matches(cfg, matched_paths)?;
+
+    if !cfg.no_summary {
+        println!(
+            "Summary: files={}, matches={}{}, elapsed={:?} (index of {}, refreshed {})",
+            files_count,
+            matches_count,
+            duplicates,
+            start.elapsed(),
+            clean_path_for_display(Path::new(&root)),
+            format_index_age(indexed_at)
+        );
+    }
+
+    profiler.print_report();
+
+    Ok(())
+}
+
+// Prints the groups of identical matches and returns the summary fragment for them (empty when
+// the option is off).
+fn group_duplicates(
+    cfg: &FilesLookupConfig,
+    matched_paths: &[PathBuf],
+    profiler: &mut PhaseProfiler,
+) -> String {
+    if !cfg.group_duplicates {
+        return String::new();
+    }
+
+    let report = profiler.measure("hash files", || find_duplicates(matched_paths));
+    println!("{}", DASH_LINE);
+    print_duplicate_report(&report, cfg.no_errors);
+
+    format!(
+        ", duplicate groups={}, hashed={}",
+        report.groups.len(),
+        report.hashed
+    )
+}
+
+// With `--interactive`, opens the matches in the result browser (instead of printing them).
+fn browse_matches(cfg: &FilesLookupConfig, matched_paths: Vec<PathBuf>) -> Result<()> {
+    if !cfg.interactive {
+        return Ok(());
     }
+
+    let items = matched_paths
+        .into_iter()
+        .map(|path| {
+            let label = absolute_path_str(&path);
+            BrowserItem::file(path, label)
+        })
+        .collect();
+
+    ResultBrowser::new(format!("files: {}", cfg.patterns.join(", ")), items).run()
 }
 
 enum Matcher {
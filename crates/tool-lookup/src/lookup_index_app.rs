@@ -0,0 +1,84 @@
+use crate::file_index::FileIndex;
+use crate::lookup_shared::{clean_path_for_display, clear_progress_line, print_progress_once};
+use crate::models::IndexConfig;
+use anyhow::{anyhow, Result};
+use shared::constants::general::DASH_LINE;
+use shared::utils::phase_profiler::PhaseProfiler;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+pub fn print_header(args: &IndexConfig) {
+    println!("Lookup v{}", env!("CARGO_PKG_VERSION"));
+    println!("{}", DASH_LINE);
+    println!("Mode: index");
+    println!("Path: {}", args.path);
+    println!("Index: {}", args.db_path.display());
+    println!("Rebuild: {}", args.rebuild);
+    if args.profile {
+        println!("Profiling: enabled");
+    }
+}
+
+pub fn run_index(cfg: &IndexConfig) -> Result<()> {
+    let start = Instant::now();
+    let mut profiler = PhaseProfiler::new(cfg.profile);
+
+    let base_path = PathBuf::from(&cfg.path);
+    if !base_path.is_dir() {
+        return Err(anyhow!(
+            "Path does not exist or is not a folder: {}",
+            base_path.display()
+        ));
+    }
+    let root = fs::canonicalize(&base_path)?;
+
+    let mut index = profiler.measure("open index", || FileIndex::open(&cfg.db_path))?;
+
+    let mut last_dir_printed: Option<PathBuf> = None;
+    let stats = profiler.measure("refresh index", || {
+        index.refresh(
+            &root,
+            cfg.rebuild,
+            |folder: &Path| {
+                if !cfg.no_progress {
+                    print_progress_once(&mut last_dir_printed, folder);
+                }
+            },
+            |e: &walkdir::Error| {
+                if !cfg.no_errors {
+                    if !cfg.no_progress {
+                        clear_progress_line();
+                    }
+                    let p = e
+                        .path()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_else(|| "<unknown>".to_string());
+                    println!("{}: {}", e, p);
+                }
+            },
+        )
+    })?;
+
+    if !cfg.no_progress {
+        clear_progress_line();
+    }
+
+    if !cfg.no_summary {
+        println!(
+            "Indexed {}: dirs={}, added={}, updated={}, removed={}, unchanged={}, errors={}, elapsed={:?}",
+            clean_path_for_display(&root),
+            stats.folders,
+            stats.added,
+            stats.updated,
+            stats.removed,
+            stats.unchanged,
+            stats.errors,
+            start.elapsed()
+        );
+    }
+
+    profiler.print_report();
+
+    Ok(())
+}
@@ -1,7 +1,47 @@
 use anyhow::{Context, Result};
 use shared::system::folder_walkthrough::list_all_files_recursively;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
+pub const CLEAR_LINE: &str = "\r\x1b[2K";
+
+pub fn clear_progress_line() {
+    eprint!("{}", CLEAR_LINE);
+    let _ = std::io::stderr().flush();
+}
+
+pub fn clean_path_for_display(p: &Path) -> String {
+    #[cfg(windows)]
+    {
+        let s = p.display().to_string();
+        if let Some(rest) = s.strip_prefix(r"\\?\UNC\") {
+            return format!(r"\\{}", rest);
+        }
+        if let Some(rest) = s.strip_prefix(r"\\?\") {
+            return rest.to_string();
+        }
+        s
+    }
+    #[cfg(not(windows))]
+    {
+        p.display().to_string()
+    }
+}
+
+pub fn print_progress_once(last_dir_printed: &mut Option<PathBuf>, current: &Path) {
+    // Only print when the directory changes to reduce noise.
+    let cur_dir = if current.is_dir() {
+        current
+    } else {
+        current.parent().unwrap_or(current)
+    };
+    if last_dir_printed.as_ref().map(|p| p.as_path()) != Some(cur_dir) {
+        *last_dir_printed = Some(cur_dir.to_path_buf());
+        eprint!("{}Reading: {}", CLEAR_LINE, cur_dir.display());
+        let _ = std::io::stderr().flush();
+    }
+}
+
 // Accept patterns like "txt", ".txt", "*.txt", "Md", ".env", "*.env", "env"
 pub fn normalize_extensions(exts: &[String]) -> Vec<String> {
     exts.iter()
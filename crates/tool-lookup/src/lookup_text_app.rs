@@ -1,7 +1,14 @@
+use crate::archive_search::{extract_pdf_text, search_archive};
+use crate::fuzzy_matcher::{FuzzyScorer, TopMatches};
 use crate::lookup_shared::{list_files, normalize_extensions, path_matches_allowed};
 use crate::models::TextLookupConfig;
+use crate::text_matcher::{colorize_term, find_matches, highlight_line, MatchSpan};
+use crate::ui::browser_item::BrowserItem;
+use crate::ui::result_browser::ResultBrowser;
 use anyhow::{anyhow, Result};
 use shared::constants::general::DASH_LINE;
+use shared::system::pathbuf_extensions::PathBufExtensions;
+use shared::utils::phase_profiler::PhaseProfiler;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
@@ -11,7 +18,11 @@ use tracing::error;
 pub fn print_header(args: &TextLookupConfig) {
     println!("Lookup v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
-    println!("Text: {}", args.text);
+    if args.texts.len() == 1 {
+        println!("Text: {}", args.texts[0]);
+    } else {
+        println!("Texts: {:?}", args.texts);
+    }
     println!("Path: {}", args.path);
     println!("File extensions: {:?}", args.file_extensions);
     if args.current_only {
@@ -20,10 +31,144 @@ pub fn print_header(args: &TextLookupConfig) {
         println!("Search Mode: Recursive")
     }
     println!("Print Line data only: {}", args.line_only);
+    println!("Columns output: {}", args.columns);
+    if args.search_archives || args.search_pdf {
+        println!(
+            "Search inside: archives={}, pdf={}",
+            args.search_archives, args.search_pdf
+        );
+    }
+    if let Some(fuzzy) = &args.fuzzy {
+        println!(
+            "Fuzzy: top {} results, minimum score {}",
+            fuzzy.limit, fuzzy.threshold
+        );
+    }
+    if args.interactive {
+        println!("Interactive: enabled");
+    }
+    if args.profile {
+        println!("Profiling: enabled");
+    }
+}
+
+// A matching line kept until the end of the search, so columns can be aligned (or the lines
+// browsed, with `--interactive`).
+struct ColumnRow {
+    source: String,
+    line_number: usize,
+    term_index: usize,
+    line: String,
+    spans: Vec<MatchSpan>,
+}
+
+impl ColumnRow {
+    fn location(&self) -> String {
+        format!("{}:{}", self.source, self.line_number)
+    }
+}
+
+// Matches lines and keeps what is needed to print them, wherever the lines come from (files,
+// archive members, or text extracted from PDFs).
+struct LineSearch<'a> {
+    config: &'a TextLookupConfig,
+    needles: Vec<String>,
+    fuzzy_scorer: Option<FuzzyScorer>,
+    column_rows: Vec<ColumnRow>,
+    fuzzy_rows: TopMatches<ColumnRow>,
+    total_lines: u64,
+    matches_found: u64,
+}
+
+impl<'a> LineSearch<'a> {
+    fn new(config: &'a TextLookupConfig) -> Self {
+        Self {
+            config,
+            needles: config
+                .texts
+                .iter()
+                .map(|t| t.to_ascii_lowercase())
+                .collect(),
+            fuzzy_scorer: config
+                .fuzzy
+                .as_ref()
+                .map(|_| FuzzyScorer::new(&config.texts)),
+            column_rows: Vec::new(),
+            fuzzy_rows: TopMatches::new(config.fuzzy.as_ref().map_or(0, |f| f.limit)),
+            total_lines: 0,
+            matches_found: 0,
+        }
+    }
+
+    // `location` identifies the source in the output (`<location>:<line>`).
+    fn search_reader(&mut self, reader: impl BufRead, location: &str) {
+        let config = self.config;
+
+        for (idx, line_res) in reader.lines().enumerate() {
+            let line = match line_res {
+                Ok(l) => l,
+                Err(_) => continue, // Skip problematic lines
+            };
+            self.total_lines += 1;
+
+            if let (Some(scorer), Some(fuzzy)) = (&self.fuzzy_scorer, &config.fuzzy) {
+                let Some(m) = scorer.score_line(&line) else {
+                    continue;
+                };
+                if m.score < fuzzy.threshold {
+                    continue;
+                }
+
+                self.matches_found += 1;
+                self.fuzzy_rows.push(
+                    m.score,
+                    ColumnRow {
+                        source: location.to_string(),
+                        line_number: idx + 1,
+                        term_index: m.term_index,
+                        line,
+                        spans: m.spans,
+                    },
+                );
+                continue;
+            }
+
+            let spans = find_matches(&line.to_ascii_lowercase(), &self.needles);
+            if spans.is_empty() {
+                continue;
+            }
+
+            self.matches_found += 1;
+
+            if config.columns || config.interactive {
+                self.column_rows.push(ColumnRow {
+                    source: location.to_string(),
+                    line_number: idx + 1,
+                    term_index: spans[0].term_index,
+                    line,
+                    spans,
+                });
+                continue;
+            }
+
+            let line = if config.color {
+                highlight_line(&line, &spans)
+            } else {
+                line
+            };
+
+            if config.line_only {
+                println!("{}", line);
+            } else {
+                println!("{}:{}| {}", location, idx + 1, line);
+            }
+        }
+    }
 }
 
 pub fn run_text_lookup(config: &TextLookupConfig) -> Result<()> {
     let start = Instant::now();
+    let mut profiler = PhaseProfiler::new(config.profile);
 
     let base_path = PathBuf::from(&config.path);
     if !base_path.exists() {
@@ -33,18 +178,54 @@ pub fn run_text_lookup(config: &TextLookupConfig) -> Result<()> {
     }
 
     let normalized_extensions = normalize_extensions(&config.file_extensions);
-    let needle = config.text.to_ascii_lowercase();
+    let mut search = LineSearch::new(config);
 
-    let files_iter = list_files(&base_path, config.current_only)?;
+    let mut files_iter = list_files(&base_path, config.current_only)?;
     let mut files_read: u64 = 0;
-    let mut total_lines: u64 = 0;
-    let mut matches_found: u64 = 0;
 
-    for file_path in files_iter {
+    // The walk is lazy, so fetching the next file is timed apart from reading it.
+    while let Some(file_path) = profiler.measure("walk folders", || files_iter.next()) {
+        if config.search_archives {
+            if let Some(compression) = file_path.compression_type() {
+                let started_at = Instant::now();
+                match search_archive(
+                    &file_path,
+                    compression,
+                    &normalized_extensions,
+                    |location, reader| search.search_reader(reader, location),
+                ) {
+                    Ok(members) => files_read += members,
+                    Err(e) => error!("Failed to read archive '{}': {:#}", file_path.display(), e),
+                }
+                profiler.record_since("search archives", started_at);
+                continue;
+            }
+        }
+
+        if config.search_pdf && file_path.is_pdf() {
+            let started_at = Instant::now();
+            match extract_pdf_text(&file_path) {
+                Ok(text) => {
+                    files_read += 1;
+                    let location = file_path.display().to_string();
+                    search.search_reader(text.as_bytes(), &location);
+                }
+                Err(e) => error!(
+                    "Failed to extract text from '{}': {:#}",
+                    file_path.display(),
+                    e
+                ),
+            }
+            profiler.record_since("search pdfs", started_at);
+            continue;
+        }
+
         if !path_matches_allowed(&file_path, &normalized_extensions) {
             continue;
         }
 
+        let file_started_at = Instant::now();
+
         let file = match File::open(&file_path) {
             Ok(f) => f,
             Err(e) => {
@@ -54,25 +235,39 @@ pub fn run_text_lookup(config: &TextLookupConfig) -> Result<()> {
         };
         files_read += 1;
 
-        let reader = BufReader::new(file);
-        for (idx, line_res) in reader.lines().enumerate() {
-            let line = match line_res {
-                Ok(l) => l,
-                Err(_) => continue, // Skip problematic lines
-            };
-            total_lines += 1;
-
-            if line.to_ascii_lowercase().contains(&needle) {
-                matches_found += 1;
-                if config.line_only {
-                    println!("{}", line);
-                } else {
-                    println!("{}:{}| {}", file_path.display(), idx + 1, line);
-                }
-            }
+        let location = file_path.display().to_string();
+        search.search_reader(BufReader::new(file), &location);
+
+        profiler.record_since("search files", file_started_at);
+    }
+
+    let LineSearch {
+        mut column_rows,
+        fuzzy_rows,
+        total_lines,
+        matches_found,
+        ..
+    } = search;
+
+    if config.fuzzy.is_some() {
+        let ranked = fuzzy_rows.into_sorted_vec();
+        if config.columns || config.interactive {
+            column_rows = ranked.into_iter().map(|(_, row)| row).collect();
+        } else {
+            profiler.measure("print ranking", || print_ranked(config, &ranked));
         }
     }
 
+    if config.interactive {
+        let items = column_rows
+            .iter()
+            .map(|row| BrowserItem::line(&row.source, row.line_number, &row.line))
+            .collect();
+        ResultBrowser::new(format!("text: {}", config.texts.join(", ")), items).run()?;
+    } else if config.columns {
+        profiler.measure("print columns", || print_columns(config, &column_rows));
+    }
+
     if !config.no_header {
         let elapsed = start.elapsed();
         eprintln!(
@@ -81,5 +276,59 @@ pub fn run_text_lookup(config: &TextLookupConfig) -> Result<()> {
         );
     }
 
+    profiler.print_report();
+
     Ok(())
 }
+
+// Best scores first. The score is left out with `--line-only`.
+fn print_ranked(config: &TextLookupConfig, ranked: &[(i64, ColumnRow)]) {
+    let score_width = ranked
+        .iter()
+        .map(|(score, _)| score.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    for (score, row) in ranked {
+        let line = if config.color {
+            highlight_line(&row.line, &row.spans)
+        } else {
+            row.line.clone()
+        };
+
+        if config.line_only {
+            println!("{}", line);
+        } else {
+            println!("{:>score_width$}  {}| {}", score, row.location(), line);
+        }
+    }
+}
+
+fn print_columns(config: &TextLookupConfig, rows: &[ColumnRow]) {
+    let location_width = rows.iter().map(|r| r.location().len()).max().unwrap_or(0);
+    let term_width = config.texts.iter().map(|t| t.len()).max().unwrap_or(0);
+
+    for row in rows {
+        let term = &config.texts[row.term_index];
+        // Padding is computed on the plain text, so color codes don't break the alignment.
+        let term_padding = " ".repeat(term_width - term.len());
+
+        if config.color {
+            println!(
+                "{:<location_width$}  {}{}  {}",
+                row.location(),
+                colorize_term(term, row.term_index),
+                term_padding,
+                highlight_line(&row.line, &row.spans),
+            );
+        } else {
+            println!(
+                "{:<location_width$}  {}{}  {}",
+                row.location(),
+                term,
+                term_padding,
+                row.line,
+            );
+        }
+    }
+}
@@ -1,35 +1,67 @@
+use std::path::PathBuf;
+
 // High-level command selected from CLI
 pub enum LookupCommand {
     Text(TextLookupConfig),
     Files(FilesLookupConfig),
+    Index(IndexConfig),
+}
+
+// Ranking options for `lookup text --fuzzy`
+pub struct FuzzyOptions {
+    pub threshold: i64,
+    pub limit: usize,
 }
 
 // Config for `lookup text` subcommand
 pub struct TextLookupConfig {
     pub path: String,
-    pub text: String,
+    pub texts: Vec<String>,
     pub file_extensions: Vec<String>,
     pub no_header: bool,
     pub current_only: bool,
     pub line_only: bool,
+    pub columns: bool,
+    pub color: bool,
+    pub profile: bool,
+    pub fuzzy: Option<FuzzyOptions>,
+    pub search_archives: bool,
+    pub search_pdf: bool,
+    // Browse the results in a terminal UI instead of printing them.
+    pub interactive: bool,
 }
 
 impl TextLookupConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         path: String,
-        text: String,
+        texts: Vec<String>,
         file_extensions: Vec<String>,
         no_header: bool,
         current_only: bool,
         line_only: bool,
+        columns: bool,
+        color: bool,
+        profile: bool,
+        fuzzy: Option<FuzzyOptions>,
+        search_archives: bool,
+        search_pdf: bool,
+        interactive: bool,
     ) -> Self {
         Self {
             path,
-            text,
+            texts,
             file_extensions,
             no_header,
             current_only,
             line_only,
+            columns,
+            color,
+            profile,
+            fuzzy,
+            search_archives,
+            search_pdf,
+            interactive,
         }
     }
 }
@@ -52,6 +84,13 @@ pub struct FilesLookupConfig {
     pub no_progress: bool,
     pub no_errors: bool,
     pub no_summary: bool,
+    pub profile: bool,
+    // Path of the index database, when the lookup uses it instead of scanning the folders.
+    pub index: Option<PathBuf>,
+    // Hash the matches and group the identical copies after the search.
+    pub group_duplicates: bool,
+    // Browse the matches in a terminal UI instead of printing them.
+    pub interactive: bool,
 }
 
 impl FilesLookupConfig {
@@ -66,6 +105,10 @@ impl FilesLookupConfig {
         no_progress: bool,
         no_errors: bool,
         no_summary: bool,
+        profile: bool,
+        index: Option<PathBuf>,
+        group_duplicates: bool,
+        interactive: bool,
     ) -> Self {
         Self {
             path,
@@ -77,6 +120,22 @@ impl FilesLookupConfig {
             no_progress,
             no_errors,
             no_summary,
+            profile,
+            index,
+            group_duplicates,
+            interactive,
         }
     }
 }
+
+// Config for `lookup index` subcommand
+pub struct IndexConfig {
+    pub path: String,
+    pub db_path: PathBuf,
+    pub rebuild: bool,
+    pub no_header: bool,
+    pub no_progress: bool,
+    pub no_errors: bool,
+    pub no_summary: bool,
+    pub profile: bool,
+}
@@ -0,0 +1,127 @@
+// ANSI colors used to highlight each search term. Cycles if there are more terms than colors.
+const HIGHLIGHT_COLORS: [&str; 6] = [
+    "\x1b[1;31m", // red
+    "\x1b[1;32m", // green
+    "\x1b[1;33m", // yellow
+    "\x1b[1;34m", // blue
+    "\x1b[1;35m", // magenta
+    "\x1b[1;36m", // cyan
+];
+const COLOR_RESET: &str = "\x1b[0m";
+
+// A match of one of the search terms inside a line (byte offsets).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchSpan {
+    pub start: usize,
+    pub end: usize,
+    pub term_index: usize,
+}
+
+// Finds every (non-overlapping) occurrence of the terms in the line.
+// Both `line_lc` and `needles_lc` must already be ASCII-lowercased, which keeps byte offsets
+// valid for the original line.
+pub fn find_matches(line_lc: &str, needles_lc: &[String]) -> Vec<MatchSpan> {
+    let mut spans: Vec<MatchSpan> = Vec::new();
+
+    for (term_index, needle) in needles_lc.iter().enumerate() {
+        if needle.is_empty() {
+            continue;
+        }
+
+        spans.extend(
+            line_lc
+                .match_indices(needle.as_str())
+                .map(|(start, m)| MatchSpan {
+                    start,
+                    end: start + m.len(),
+                    term_index,
+                }),
+        );
+    }
+
+    // Earlier matches win; on ties, the longer one wins.
+    spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut result: Vec<MatchSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        if result.last().is_some_and(|last| span.start < last.end) {
+            continue;
+        }
+        result.push(span);
+    }
+
+    result
+}
+
+// Wraps each match with the color assigned to its term.
+pub fn highlight_line(line: &str, spans: &[MatchSpan]) -> String {
+    let mut output = String::with_capacity(line.len() + spans.len() * 12);
+    let mut cursor = 0usize;
+
+    for span in spans {
+        output.push_str(&line[cursor..span.start]);
+        output.push_str(term_color(span.term_index));
+        output.push_str(&line[span.start..span.end]);
+        output.push_str(COLOR_RESET);
+        cursor = span.end;
+    }
+
+    output.push_str(&line[cursor..]);
+    output
+}
+
+pub fn colorize_term(term: &str, term_index: usize) -> String {
+    format!("{}{}{}", term_color(term_index), term, COLOR_RESET)
+}
+
+fn term_color(term_index: usize) -> &'static str {
+    HIGHLIGHT_COLORS[term_index % HIGHLIGHT_COLORS.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn needles(terms: &[&str]) -> Vec<String> {
+        terms.iter().map(|t| t.to_ascii_lowercase()).collect()
+    }
+
+    #[test]
+    fn test_find_matches_multiple_terms() {
+        let spans = find_matches("todo: fix this error", &needles(&["error", "todo"]));
+
+        assert_eq!(
+            spans,
+            vec![
+                MatchSpan {
+                    start: 0,
+                    end: 4,
+                    term_index: 1
+                },
+                MatchSpan {
+                    start: 15,
+                    end: 20,
+                    term_index: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_matches_skips_overlaps() {
+        let spans = find_matches("errors", &needles(&["err", "errors"]));
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].term_index, 1);
+    }
+
+    #[test]
+    fn test_highlight_line_keeps_original_case() {
+        let line = "An ERROR happened";
+        let spans = find_matches(&line.to_ascii_lowercase(), &needles(&["error"]));
+
+        let highlighted = highlight_line(line, &spans);
+
+        assert_eq!(highlighted, "An \x1b[1;31mERROR\x1b[0m happened");
+    }
+}
@@ -0,0 +1,194 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Bytes read to tell if a file is binary before previewing it.
+const BINARY_SNIFF_LENGTH: usize = 8 * 1024;
+
+/// A result listed in the interactive browser: a matched line (`text`) or a matched file (`files`).
+pub struct BrowserItem {
+    /// What is listed, and what the filter matches against.
+    pub label: String,
+    pub path: PathBuf,
+    /// Line of the match, for `text` results.
+    pub line: Option<usize>,
+}
+
+impl BrowserItem {
+    pub fn file(path: PathBuf, label: String) -> Self {
+        Self {
+            label,
+            path,
+            line: None,
+        }
+    }
+
+    /// `location` is the file (or the archive member) the line came from.
+    pub fn line(location: &str, line_number: usize, content: &str) -> Self {
+        Self {
+            label: format!("{}:{}| {}", location, line_number, content.trim()),
+            path: PathBuf::from(location),
+            line: Some(line_number),
+        }
+    }
+}
+
+/// Indexes of the items whose label has every word of the filter (case-insensitive, in any
+/// order). An empty filter keeps everything.
+pub fn filter_items(items: &[BrowserItem], filter: &str) -> Vec<usize> {
+    let words: Vec<String> = filter.split_whitespace().map(str::to_lowercase).collect();
+
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            let label = item.label.to_lowercase();
+            words.iter().all(|word| label.contains(word))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Builds the command that opens the item in the editor. `editor` is the value of `$VISUAL` or
+/// `$EDITOR`, and may have arguments (e.g.: `code --wait`). VS Code (and its forks) goes to the
+/// line with `-g file:line`; everything else (vim, nano, emacs, micro...) with `+line file`.
+pub fn editor_command(editor: &str, item: &BrowserItem) -> Result<Command> {
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow!("Set $EDITOR to open the results"))?;
+
+    let mut command = Command::new(program);
+    command.args(parts);
+
+    let program_name = Path::new(program)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match item.line {
+        Some(line) if matches!(program_name.as_str(), "code" | "code-insiders" | "codium") => {
+            command
+                .arg("-g")
+                .arg(format!("{}:{}", item.path.display(), line));
+        }
+        Some(line) => {
+            command.arg(format!("+{}", line)).arg(&item.path);
+        }
+        None => {
+            command.arg(&item.path);
+        }
+    }
+
+    Ok(command)
+}
+
+/// The editor set in the environment: `$VISUAL`, then `$EDITOR`.
+pub fn get_editor() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+/// Lines shown in the preview pane, with their numbers: around the matched line (which ends up
+/// in the middle), or the start of the matched file. Returns why there's no preview otherwise.
+pub fn read_preview(item: &BrowserItem, height: usize) -> Result<Vec<(usize, String)>, String> {
+    if !item.path.is_file() {
+        return Err("No preview: the file is inside an archive, or no longer exists.".to_string());
+    }
+
+    let mut file = File::open(&item.path).map_err(|e| format!("Failed to open the file: {}", e))?;
+
+    let mut head = vec![0u8; BINARY_SNIFF_LENGTH];
+    let read = file.read(&mut head).unwrap_or(0);
+    if head[..read].contains(&0) {
+        return Err("No preview: binary file.".to_string());
+    }
+
+    let first = match item.line {
+        Some(line) => line.saturating_sub(height / 2).max(1),
+        None => 1,
+    };
+
+    let file = File::open(&item.path).map_err(|e| format!("Failed to open the file: {}", e))?;
+    let lines = BufReader::new(file)
+        .split(b'\n')
+        .map_while(Result::ok)
+        .enumerate()
+        .skip(first - 1)
+        .take(height)
+        .map(|(index, bytes)| {
+            let text = String::from_utf8_lossy(&bytes);
+            (index + 1, text.trim_end_matches('\r').replace('\t', "    "))
+        })
+        .collect();
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_filter_and_editor_command() {
+        let items = vec![
+            BrowserItem::line("src/main.rs", 12, "    // TODO: parse the args"),
+            BrowserItem::line("src/lib.rs", 3, "fn parse() {}"),
+            BrowserItem::file(
+                PathBuf::from("docs/readme.md"),
+                "docs/readme.md".to_string(),
+            ),
+        ];
+
+        assert_eq!(filter_items(&items, ""), vec![0, 1, 2]);
+        assert_eq!(filter_items(&items, "PARSE"), vec![0, 1]);
+        assert_eq!(filter_items(&items, "todo main"), vec![0]);
+        assert!(filter_items(&items, "missing").is_empty());
+
+        let command = editor_command("vim", &items[0]).unwrap();
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(command.get_program(), "vim");
+        assert_eq!(args, vec!["+12", "src/main.rs"]);
+
+        let command = editor_command("code --wait", &items[0]).unwrap();
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, vec!["--wait", "-g", "src/main.rs:12"]);
+
+        let command = editor_command("nano", &items[2]).unwrap();
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["docs/readme.md"]
+        );
+
+        assert!(editor_command(" ", &items[0]).is_err());
+    }
+
+    #[test]
+    fn test_preview_centers_the_matched_line() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("lookup-preview-{}.txt", nanos));
+        let content: String = (1..=20).map(|line| format!("line {}\n", line)).collect();
+        std::fs::write(&path, content).unwrap();
+        let location = path.display().to_string();
+
+        let centered = read_preview(&BrowserItem::line(&location, 10, "line 10"), 5).unwrap();
+        let start = read_preview(&BrowserItem::file(path.clone(), location), 3).unwrap();
+
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(centered.first(), Some(&(8, "line 8".to_string())));
+        assert_eq!(centered.len(), 5);
+        assert_eq!(start[0], (1, "line 1".to_string()));
+
+        let missing = BrowserItem::line("logs.zip!/app.log", 1, "x");
+        assert!(read_preview(&missing, 5).is_err());
+    }
+}
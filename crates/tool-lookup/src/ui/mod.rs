@@ -0,0 +1,2 @@
+pub mod browser_item;
+pub mod result_browser;
@@ -0,0 +1,316 @@
+use crate::ui::browser_item::{
+    editor_command, filter_items, get_editor, read_preview, BrowserItem,
+};
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::stdout;
+use tracing::{error, info};
+
+const HELP: &str =
+    "Type to filter | Up/Down/PgUp/PgDn: select | Enter: open in $EDITOR | Esc: exit";
+
+fn get_banner() -> String {
+    format!(
+        "{} | v{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+enum BrowserState {
+    Running,
+    Open,
+    Exit,
+}
+
+/// Lists the results of a lookup, filtered as the user types, with a preview of the selected
+/// one. Enter opens the selection in the editor and comes back to the list when it closes.
+pub struct ResultBrowser {
+    title: String,
+    items: Vec<BrowserItem>,
+    filter: String,
+    /// Indexes (in `items`) of the results that pass the filter.
+    visible: Vec<usize>,
+    list_state: ListState,
+    /// Items moved by PgUp/PgDn. Updated when the list is drawn.
+    page_size: usize,
+    /// Result of the last action (e.g.: the editor failed to start).
+    status: Option<String>,
+}
+
+impl ResultBrowser {
+    /// `title` describes the search (e.g.: the terms), and is shown in the banner.
+    pub fn new(title: String, items: Vec<BrowserItem>) -> Self {
+        let visible = (0..items.len()).collect();
+        let mut list_state = ListState::default();
+        if !items.is_empty() {
+            list_state.select(Some(0));
+        }
+
+        Self {
+            title,
+            items,
+            filter: String::new(),
+            visible,
+            list_state,
+            page_size: 10,
+            status: None,
+        }
+    }
+
+    /// Runs the UI until the user exits.
+    pub fn run(mut self) -> Result<()> {
+        info!("Starting result browser...");
+        let mut terminal = ratatui::init();
+        let result = self.browser_loop(&mut terminal);
+        ratatui::restore();
+        result
+    }
+
+    fn browser_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                match self.process_key(key) {
+                    BrowserState::Running => {}
+                    BrowserState::Open => self.open_selected(terminal)?,
+                    BrowserState::Exit => return Ok(()),
+                }
+            }
+        }
+    }
+
+    fn selected_item(&self) -> Option<&BrowserItem> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.visible.get(index))
+            .map(|index| &self.items[*index])
+    }
+
+    /// Leaves the alternate screen while the editor runs (it needs the terminal), then redraws.
+    fn open_selected(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        let Some(item) = self.selected_item() else {
+            return Ok(());
+        };
+
+        if !item.path.is_file() {
+            self.status = Some(format!(
+                "Can't open {}: it's inside an archive, or no longer exists.",
+                item.path.display()
+            ));
+            return Ok(());
+        }
+
+        let Some(editor) = get_editor() else {
+            self.status = Some("Set $EDITOR (or $VISUAL) to open the results.".to_string());
+            return Ok(());
+        };
+
+        let mut command = editor_command(&editor, item)?;
+        let path = item.path.display().to_string();
+
+        ratatui::restore();
+        let status = command.status();
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen)?;
+        terminal.clear()?;
+
+        self.status = match status {
+            Ok(status) if status.success() => None,
+            Ok(status) => Some(format!("{} exited with {}", editor, status)),
+            Err(e) => {
+                error!("Failed to open '{}' with '{}': {}", path, editor, e);
+                Some(format!("Failed to start '{}': {}", editor, e))
+            }
+        };
+
+        Ok(())
+    }
+
+    //region: Ui Logic
+    fn process_key(&mut self, key: KeyEvent) -> BrowserState {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc => return BrowserState::Exit,
+            KeyCode::Char('c') if ctrl => return BrowserState::Exit,
+            KeyCode::Enter => return BrowserState::Open,
+            KeyCode::Char('u') if ctrl => self.set_filter(String::new()),
+            KeyCode::Char(c) if !ctrl => {
+                let mut filter = self.filter.clone();
+                filter.push(c);
+                self.set_filter(filter);
+            }
+            KeyCode::Backspace => {
+                let mut filter = self.filter.clone();
+                filter.pop();
+                self.set_filter(filter);
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-(self.page_size as isize)),
+            KeyCode::PageDown => self.move_selection(self.page_size as isize),
+            KeyCode::Home => self.move_selection(isize::MIN),
+            KeyCode::End => self.move_selection(isize::MAX),
+            _ => {}
+        }
+
+        BrowserState::Running
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.visible = filter_items(&self.items, &self.filter);
+        self.status = None;
+
+        // Starts over from the best (first) result that is left.
+        self.list_state
+            .select((!self.visible.is_empty()).then_some(0));
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let last = self.visible.len() as isize - 1;
+        let selected = current.saturating_add(offset).clamp(0, last);
+        self.list_state.select(Some(selected as usize));
+    }
+    //endregion: Ui Logic
+
+    //region: Ui
+    fn draw(&mut self, frame: &mut Frame) {
+        // Layout:
+        // [banner (1)] - app name, version, search, and result count
+        // [filter (3)] - filter input
+        // [results (50%) | preview (50%)] - filtered results, and the selected one in its file
+        // [status (1)] - keys, or the result of the last action
+        let vertical = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ]);
+        let [banner_area, filter_area, body_area, status_area] = vertical.areas(frame.area());
+        let [results_area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(body_area);
+
+        let banner = format!(
+            "{} | {} | {} of {} result(s)",
+            get_banner(),
+            self.title,
+            self.visible.len(),
+            self.items.len()
+        );
+        frame.render_widget(
+            Paragraph::new(banner).style(Style::default().fg(Color::Cyan)),
+            banner_area,
+        );
+
+        self.draw_filter(frame, filter_area);
+        self.draw_results(frame, results_area);
+        self.draw_preview(frame, preview_area);
+
+        let status = match &self.status {
+            Some(status) => Paragraph::new(status.as_str()).style(Style::default().fg(Color::Red)),
+            None => Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+        };
+        frame.render_widget(status, status_area);
+    }
+
+    fn draw_filter(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered()
+            .title("Filter")
+            .border_style(Style::default().fg(Color::Yellow));
+        frame.render_widget(Paragraph::new(self.filter.as_str()).block(block), area);
+
+        // Cursor at the end of the filter, if it fits.
+        let column = self.filter.chars().count();
+        if column < area.width.saturating_sub(2) as usize {
+            #[allow(clippy::cast_possible_truncation)]
+            frame.set_cursor_position(Position::new(area.x + 1 + column as u16, area.y + 1));
+        }
+    }
+
+    fn draw_results(&mut self, frame: &mut Frame, area: Rect) {
+        self.page_size = area.height.saturating_sub(2).max(1) as usize;
+
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|index| ListItem::new(self.items[*index].label.as_str()))
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::bordered().title("Results"))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn draw_preview(&self, frame: &mut Frame, area: Rect) {
+        let Some(item) = self.selected_item() else {
+            frame.render_widget(Block::bordered().title("Preview"), area);
+            return;
+        };
+
+        let block = Block::bordered().title(format!("Preview ({})", item.path.display()));
+        let height = area.height.saturating_sub(2) as usize;
+
+        let lines = match read_preview(item, height) {
+            Ok(lines) => lines,
+            Err(reason) => {
+                let reason = Paragraph::new(reason)
+                    .style(Style::default().fg(Color::DarkGray))
+                    .wrap(Wrap { trim: false })
+                    .block(block);
+                frame.render_widget(reason, area);
+                return;
+            }
+        };
+
+        let number_width = lines
+            .last()
+            .map_or(1, |(number, _)| number.to_string().len());
+
+        let lines: Vec<Line> = lines
+            .into_iter()
+            .map(|(number, text)| {
+                let text = if item.line == Some(number) {
+                    Span::styled(text, Style::default().fg(Color::Black).bg(Color::Yellow))
+                } else {
+                    Span::raw(text)
+                };
+                let gutter = Span::styled(
+                    format!("{:>number_width$} ", number),
+                    Style::default().fg(Color::DarkGray),
+                );
+
+                Line::from(vec![gutter, text])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+    //endregion: Ui
+}
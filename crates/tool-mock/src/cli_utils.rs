@@ -1,48 +1,37 @@
 use crate::models::MockArgs;
+use crate::registry::GeneratorRegistry;
 use clap::{Arg, Command};
 use shared::command_line::cli_builder::CommandExt;
+use shared::system::load_global_config::user_data_dir;
+use std::ffi::OsString;
+use std::path::PathBuf;
 
 /// Get help text for available data types
-fn get_help_text() -> &'static str {
-    "Personal Information:
-  person.first-name     - Generate a random first name
-  person.last-name      - Generate a random last name
-  person.full-name      - Generate a full name (first + last)
-  person.email          - Generate a random email address
-  person.phone          - Generate a phone number
-  person.street         - Generate a street name
-  person.city           - Generate a city name
-  person.state          - Generate a state name
-  person.country        - Generate a country name
-  person.postal-code    - Generate postal/zip code
-  person.address        - Generate a full address
-  person.birthday       - Generate a birthday (with optional --age parameter)
-
-Internet & Tech:
-  internet.username     - Generate a username
-  internet.password     - Generate a password (with --length option)
-  internet.url          - Generate a URL
-  internet.image-url    - Generate an image URL
-  internet.file-url     - Generate a file URL
+fn get_help_text() -> String {
+    format!(
+        "{}\n\nPlugins:\n  \
+        Generators from .rhai and .wasm files, in the folders informed with --plugins, and in:\n  \
+        {}",
+        GeneratorRegistry::with_builtins().help_text(),
+        default_plugins_dir().map_or("(no user data folder)".to_string(), |dir| dir
+            .display()
+            .to_string())
+    )
+}
 
-Random Data:
-  random.date           - Generate a date (with --past, --future, or --range options)
-  random.time           - Generate a time (with --past, --future options)
-  random.datetime       - Generate a datetime (with --past, --future options)
-  random.timestamp      - Generate a timestamp (with --past, --future options)
-  random.color-hex      - Generate a hex color code
-  random.color-rgb      - Generate RGB color values
-  random.integer        - Generate an integer (with --min, --max options)
-  random.float          - Generate a float (with --min, --max, --precision options)
-  random.car-brand      - Generate a car brand name
+/// Folder plugins are loaded from by default: `<user data folder>/rusted-toolbox/mock/plugins`.
+fn default_plugins_dir() -> Option<PathBuf> {
+    user_data_dir(env!("CARGO_PKG_NAME")).map(|dir| dir.join("plugins"))
+}
 
-Commerce:
-  commerce.company      - Generate a company name
-  commerce.product      - Generate a product name
-  commerce.product-description - Generate a product description
-  commerce.job-title    - Generate a job title
-  commerce.industry     - Generate an industry name
-  commerce.buzzword     - Generate a business buzzword"
+/// Plugin files and folders to load: the default folder (when it exists), then the ones
+/// informed with `--plugins`.
+pub fn plugin_paths(args: &MockArgs) -> Vec<PathBuf> {
+    default_plugins_dir()
+        .filter(|dir| dir.is_dir())
+        .into_iter()
+        .chain(args.plugins.iter().cloned())
+        .collect()
 }
 
 /// Parses command-line arguments for mock data generator application.
@@ -60,7 +49,8 @@ Commerce:
 /// - Commerce: commerce.company, commerce.product, commerce.job-title, etc.
 ///
 /// # Global Options
-/// - `--locale <LOCALE>`: Set locale for region-specific data (default: en_US)
+/// - `--locale <LOCALE>`: Locale of names, addresses, phones, and companies: en-US (default),
+///   pt-BR, de-DE, or ja-JP
 ///
 /// # Data-specific Options
 /// - `--min <NUMBER>`: Minimum value (for numbers)
@@ -72,6 +62,16 @@ Commerce:
 /// - `--future`: Generate future dates/times
 /// - `--range <YEARS>`: Date range in years (default: 50)
 ///
+/// # OpenAPI Options
+/// - `--spec <FILE>`: OpenAPI spec (JSON or YAML)
+/// - `--schema <NAME>`: Schema to generate, from `components/schemas`
+/// - `--operation <ID>`: Operation whose request body is generated
+/// - `--count <NUMBER>`: Number of payloads to generate (default: 1)
+/// - `--post [BASE_URL]`: Sends the payloads to the operation's endpoint
+///
+/// # Plugin Options
+/// - `--plugins <PATH>`: Rhai script, WASM module, or folder with them (repeatable)
+///
 /// # Metadata
 ///
 /// - Name: `MOCK_APP_NAME` (constant).
@@ -88,8 +88,10 @@ Commerce:
 /// mock random.integer --min 1 --max 100
 /// mock internet.password --length 12
 /// mock random.date --past
+/// mock openapi.payload --spec petstore.yaml --schema Pet
+/// mock acme.order-id --plugins ./plugins
 /// ```
-pub fn get_cli_arguments() -> MockArgs {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> MockArgs {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -104,7 +106,9 @@ pub fn get_cli_arguments() -> MockArgs {
             mock person.email\n    \
             mock random.integer --min 1 --max 100\n    \
             mock internet.password --length 12\n    \
-            mock random.date --past\n\n\
+            mock random.date --past\n    \
+            mock openapi.payload --spec petstore.yaml --schema Pet\n    \
+            mock acme.order-id --plugins ./plugins\n\n\
             AVAILABLE DATA TYPES:\n\n",
         )
         .after_help(get_help_text())
@@ -119,8 +123,8 @@ pub fn get_cli_arguments() -> MockArgs {
             Arg::new("locale")
                 .long("locale")
                 .value_name("LOCALE")
-                .help("Set locale for region-specific data")
-                .default_value("en_US"),
+                .help("Locale of names, addresses, phones, and companies: en-US, pt-BR, de-DE, or ja-JP")
+                .default_value("en-US"),
         )
         .arg(
             Arg::new("min")
@@ -170,7 +174,48 @@ pub fn get_cli_arguments() -> MockArgs {
                 .value_name("YEARS")
                 .help("Date range in years (default: 50)"),
         )
-        .get_matches();
+        .arg(
+            Arg::new("spec")
+                .long("spec")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("OpenAPI spec, in JSON or YAML (for openapi.payload)"),
+        )
+        .arg(
+            Arg::new("schema")
+                .long("schema")
+                .value_name("NAME")
+                .help("Schema to generate, from components/schemas (for openapi.payload)"),
+        )
+        .arg(
+            Arg::new("operation")
+                .long("operation")
+                .value_name("OPERATION_ID")
+                .help("Operation whose request body is generated (for openapi.payload)"),
+        )
+        .arg(
+            Arg::new("count")
+                .long("count")
+                .value_name("NUMBER")
+                .help("Number of payloads to generate, one per line (default: 1)"),
+        )
+        .arg(
+            Arg::new("post")
+                .long("post")
+                .value_name("BASE_URL")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .help("Sends the payloads to the operation's endpoint. Uses the spec's server when no URL is informed"),
+        )
+        .arg(
+            Arg::new("plugins")
+                .long("plugins")
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(PathBuf))
+                .action(clap::ArgAction::Append)
+                .help("Rhai script (.rhai), WASM module (.wasm), or folder with them, adding custom generators. Can be repeated"),
+        )
+        .get_matches_from(argv);
 
     MockArgs::parse(&matches)
 }
@@ -1,13 +1,21 @@
+use crate::locales::{fake_localized, pick};
 use crate::models::MockOptions;
 use anyhow::Result;
-use fake::faker::company::en::*;
-use fake::faker::job::en::*;
-use fake::Fake;
+use fake::faker::company::raw::*;
+use fake::faker::job::raw::*;
+use fake::faker::name::raw::LastName;
 use rand::Rng;
 
-/// Generate a random company name
-pub fn generate_company(_options: &MockOptions) -> Result<String> {
-    Ok(CompanyName().fake::<String>())
+/// Generate a random company name, with the company types of the locale (e.g.: GmbH, Ltda.)
+pub fn generate_company(options: &MockOptions) -> Result<String> {
+    let Some(data) = options.locale.data() else {
+        return Ok(fake_localized!(options.locale, CompanyName));
+    };
+
+    Ok(pick(data.company_templates)
+        .replace("{last_name}", &fake_localized!(options.locale, LastName))
+        .replace("{last_name2}", &fake_localized!(options.locale, LastName))
+        .replace("{suffix}", pick(data.company_suffixes)))
 }
 
 /// Generate a random product name
@@ -128,13 +136,19 @@ pub fn generate_product_description(options: &MockOptions) -> Result<String> {
 }
 
 /// Generate a random job title
-pub fn generate_job_title(_options: &MockOptions) -> Result<String> {
-    Ok(Title().fake::<String>())
+pub fn generate_job_title(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => pick(data.job_titles).to_string(),
+        None => fake_localized!(options.locale, Title),
+    })
 }
 
 /// Generate a random industry name
-pub fn generate_industry(_options: &MockOptions) -> Result<String> {
-    Ok(Industry().fake::<String>())
+pub fn generate_industry(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => pick(data.industries).to_string(),
+        None => fake_localized!(options.locale, Industry),
+    })
 }
 
 /// Generate a random business buzzword
@@ -1,9 +1,11 @@
 pub mod commerce;
 pub mod internet;
+pub mod openapi;
 pub mod personal;
 pub mod random;
 
 pub use commerce::*;
 pub use internet::*;
+pub use openapi::*;
 pub use personal::*;
 pub use random::*;
@@ -0,0 +1,186 @@
+use crate::models::MockOptions;
+use crate::openapi::payload_generator::PayloadGenerator;
+use crate::openapi::spec::{OpenApiSpec, Operation};
+use crate::registry::GeneratorRegistry;
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Method;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Generate payloads for an OpenAPI schema or operation, optionally posting them to the API.
+///
+/// A single payload is pretty printed. With `--count`, each payload is printed in its own line.
+/// Schemas with `x-mock` are generated by the generator it names, from the registry.
+pub fn generate_openapi_payload(
+    options: &MockOptions,
+    registry: &GeneratorRegistry,
+) -> Result<String> {
+    let spec_path = options
+        .spec
+        .as_ref()
+        .context("An OpenAPI spec is required (--spec)")?;
+    let spec = OpenApiSpec::load(spec_path)?;
+
+    let operation = match &options.operation {
+        Some(operation_id) => Some(spec.operation(operation_id)?),
+        None => None,
+    };
+
+    let schema = match (&operation, &options.schema) {
+        (Some(operation), _) => operation.body_schema.ok_or_else(|| {
+            anyhow!(
+                "Operation {} {} has no request body",
+                operation.method,
+                operation.path
+            )
+        })?,
+        (None, Some(schema_name)) => spec.schema(schema_name)?,
+        (None, None) => anyhow::bail!("Inform a schema (--schema) or an operation (--operation)"),
+    };
+
+    // Request bodies don't carry read-only properties, like ids generated by the server.
+    let generator = PayloadGenerator::new(&spec, operation.is_some())
+        .with_registry(registry)
+        .with_locale(options.locale);
+
+    let payloads = (0..options.count.unwrap_or(1))
+        .map(|_| generator.generate(schema))
+        .collect::<Result<Vec<Value>>>()?;
+
+    if let (Some(base_url), Some(operation)) = (&options.post, &operation) {
+        let base_url = match base_url.trim() {
+            "" => spec
+                .server_url()
+                .filter(|url| url.starts_with("http"))
+                .context("The spec has no absolute server URL. Inform it: --post <BASE_URL>")?,
+            base_url => base_url.to_string(),
+        };
+
+        post_payloads(&generator, operation, &base_url, &payloads)?;
+    }
+
+    if payloads.len() == 1 {
+        return Ok(serde_json::to_string_pretty(&payloads[0])?);
+    }
+
+    let lines = payloads
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<serde_json::Result<Vec<_>>>()?;
+
+    Ok(lines.join("\n"))
+}
+
+/// Sends each payload to the operation's endpoint. Results go to stderr, so stdout only has the
+/// payloads.
+fn post_payloads(
+    generator: &PayloadGenerator,
+    operation: &Operation,
+    base_url: &str,
+    payloads: &[Value],
+) -> Result<()> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("Failed to build the HTTP client")?;
+    let method = Method::from_bytes(operation.method.as_bytes())?;
+
+    let mut failures = 0;
+    for payload in payloads {
+        let url = build_url(generator, operation, base_url)?;
+        let response = client
+            .request(method.clone(), &url)
+            .header(CONTENT_TYPE, &operation.content_type)
+            .body(payload.to_string())
+            .send()
+            .context(format!("Failed to send {} {}", method, url))?;
+
+        let status = response.status();
+        eprintln!("{} {} -> {}", method, url, status);
+
+        if !status.is_success() {
+            failures += 1;
+            let body = response.text().unwrap_or_default();
+            if !body.is_empty() {
+                eprintln!("  {}", body);
+            }
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!(
+            "{} of {} request(s) were not successful",
+            failures,
+            payloads.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Joins the base URL and the operation path, with generated values for the path parameters.
+fn build_url(
+    generator: &PayloadGenerator,
+    operation: &Operation,
+    base_url: &str,
+) -> Result<String> {
+    let mut path = operation.path.clone();
+
+    for (name, schema) in &operation.path_parameters {
+        let value = match generator.generate(schema)? {
+            Value::String(value) => value,
+            value => value.to_string(),
+        };
+        path = path.replace(&format!("{{{}}}", name), &encode_path_value(&value));
+    }
+
+    Ok(format!("{}{}", base_url.trim_end_matches('/'), path))
+}
+
+/// Percent-encodes everything but the unreserved characters (RFC 3986).
+fn encode_path_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_url_fills_path_parameters() {
+        let spec = OpenApiSpec::parse(
+            r#"{
+                "openapi": "3.0.3",
+                "paths": {
+                    "/stores/{storeId}/pets/{name}": {
+                        "post": {
+                            "operationId": "addPet",
+                            "parameters": [
+                                { "name": "storeId", "in": "path", "schema": { "type": "integer", "enum": [42] } },
+                                { "name": "name", "in": "path", "schema": { "type": "string", "enum": ["Mr Whiskers"] } }
+                            ]
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let operation = spec.operation("addPet").unwrap();
+        let generator = PayloadGenerator::new(&spec, true);
+
+        assert_eq!(
+            build_url(&generator, &operation, "https://api.example.com/v1/").unwrap(),
+            "https://api.example.com/v1/stores/42/pets/Mr%20Whiskers"
+        );
+    }
+}
@@ -1,85 +1,158 @@
+use crate::locales::{fake_localized, fill_pattern, pick};
 use crate::models::MockOptions;
 use anyhow::Result;
 use chrono::{Datelike, Local, NaiveDate};
-use fake::faker::address::en::*;
-use fake::faker::name::en::*;
-use fake::faker::phone_number::en::*;
+use fake::faker::address::raw::*;
+use fake::faker::name::raw::*;
+use fake::faker::phone_number::raw::*;
 use fake::Fake;
 use rand::Rng;
 
 /// Generate a random first name
-pub fn generate_first_name(_options: &MockOptions) -> Result<String> {
-    Ok(FirstName().fake::<String>())
+pub fn generate_first_name(options: &MockOptions) -> Result<String> {
+    Ok(fake_localized!(options.locale, FirstName))
 }
 
 /// Generate a random last name
-pub fn generate_last_name(_options: &MockOptions) -> Result<String> {
-    Ok(LastName().fake::<String>())
+pub fn generate_last_name(options: &MockOptions) -> Result<String> {
+    Ok(fake_localized!(options.locale, LastName))
 }
 
-/// Generate a full name (first + last)
-pub fn generate_full_name(_options: &MockOptions) -> Result<String> {
-    Ok(Name().fake::<String>())
+/// Generate a full name (first + last, or last + first in ja-JP)
+pub fn generate_full_name(options: &MockOptions) -> Result<String> {
+    Ok(fake_localized!(options.locale, Name))
 }
 
 /// Generate a random email address
-pub fn generate_email(_options: &MockOptions) -> Result<String> {
-    let first_name = FirstName().fake::<String>().to_lowercase();
-    let last_name = LastName().fake::<String>().to_lowercase();
-    let domains = [
-        "gmail.com",
-        "yahoo.com",
-        "hotmail.com",
-        "outlook.com",
-        "example.com",
-    ];
-    let domain = domains[rand::rng().random_range(0..domains.len())];
+pub fn generate_email(options: &MockOptions) -> Result<String> {
+    let (first_name, last_name, domain) = match options.locale.data() {
+        Some(data) if !data.romanized_names.is_empty() => (
+            pick(data.romanized_names).to_string(),
+            pick(data.romanized_names).to_string(),
+            pick(data.email_domains),
+        ),
+        Some(data) => (
+            to_email_name(&fake_localized!(options.locale, FirstName)),
+            to_email_name(&fake_localized!(options.locale, LastName)),
+            pick(data.email_domains),
+        ),
+        None => (
+            fake_localized!(options.locale, FirstName).to_lowercase(),
+            fake_localized!(options.locale, LastName).to_lowercase(),
+            pick(&[
+                "gmail.com",
+                "yahoo.com",
+                "hotmail.com",
+                "outlook.com",
+                "example.com",
+            ]),
+        ),
+    };
 
     Ok(format!("{}.{}@{}", first_name, last_name, domain))
 }
 
+/// Lowercase, without accents and anything that isn't a letter or digit: `Jürgen` is `juergen`.
+fn to_email_name(name: &str) -> String {
+    let mut email_name = String::new();
+
+    for c in name.to_lowercase().chars() {
+        match c {
+            'a'..='z' | '0'..='9' => email_name.push(c),
+            'ä' => email_name.push_str("ae"),
+            'ö' => email_name.push_str("oe"),
+            'ü' => email_name.push_str("ue"),
+            'ß' => email_name.push_str("ss"),
+            'á' | 'à' | 'â' | 'ã' => email_name.push('a'),
+            'é' | 'è' | 'ê' => email_name.push('e'),
+            'í' | 'ì' | 'î' => email_name.push('i'),
+            'ó' | 'ò' | 'ô' | 'õ' => email_name.push('o'),
+            'ú' | 'ù' | 'û' => email_name.push('u'),
+            'ç' => email_name.push('c'),
+            'ñ' => email_name.push('n'),
+            _ => {}
+        }
+    }
+
+    email_name
+}
+
 /// Generate a phone number
-pub fn generate_phone(_options: &MockOptions) -> Result<String> {
-    Ok(PhoneNumber().fake::<String>())
+pub fn generate_phone(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => fill_pattern(pick(data.phone_patterns)),
+        None => fake_localized!(options.locale, PhoneNumber),
+    })
 }
 
 /// Generate a street name
-pub fn generate_street(_options: &MockOptions) -> Result<String> {
-    Ok(StreetName().fake::<String>())
+pub fn generate_street(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => pick(data.streets).to_string(),
+        None => fake_localized!(options.locale, StreetName),
+    })
 }
 
 /// Generate a city name
-pub fn generate_city(_options: &MockOptions) -> Result<String> {
-    Ok(CityName().fake::<String>())
+pub fn generate_city(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => pick(data.cities).0.to_string(),
+        None => fake_localized!(options.locale, CityName),
+    })
 }
 
 /// Generate a state name
-pub fn generate_state(_options: &MockOptions) -> Result<String> {
-    Ok(StateName().fake::<String>())
+pub fn generate_state(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => pick(data.cities).1.to_string(),
+        None => fake_localized!(options.locale, StateName),
+    })
 }
 
 /// Generate a country name
-pub fn generate_country(_options: &MockOptions) -> Result<String> {
-    Ok(CountryName().fake::<String>())
+pub fn generate_country(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => pick(data.countries).to_string(),
+        None => fake_localized!(options.locale, CountryName),
+    })
 }
 
 /// Generate a postal/zip code
-pub fn generate_postal_code(_options: &MockOptions) -> Result<String> {
-    Ok(PostCode().fake::<String>())
+pub fn generate_postal_code(options: &MockOptions) -> Result<String> {
+    Ok(match options.locale.data() {
+        Some(data) => fill_pattern(data.postal_code_pattern),
+        None => fake_localized!(options.locale, PostCode),
+    })
 }
 
-/// Generate a full address
-pub fn generate_address(_options: &MockOptions) -> Result<String> {
-    let street_number: u32 = (1..9999).fake();
-    let street_name = StreetName().fake::<String>();
-    let city = CityName().fake::<String>();
-    let state = StateAbbr().fake::<String>();
-    let zip = PostCode().fake::<String>();
-
-    Ok(format!(
-        "{} {}, {}, {} {}",
-        street_number, street_name, city, state, zip
-    ))
+/// Generate a full address, in the format of the locale
+pub fn generate_address(options: &MockOptions) -> Result<String> {
+    let Some(data) = options.locale.data() else {
+        let street_number: u32 = (1..9999).fake();
+        let street_name = fake_localized!(options.locale, StreetName);
+        let city = fake_localized!(options.locale, CityName);
+        let state = fake_localized!(options.locale, StateAbbr);
+        let zip = fake_localized!(options.locale, PostCode);
+
+        return Ok(format!(
+            "{} {}, {}, {} {}",
+            street_number, street_name, city, state, zip
+        ));
+    };
+
+    let (city, state, state_abbr) = pick(data.cities);
+
+    Ok(data
+        .address_template
+        .replace("{street}", pick(data.streets))
+        .replace(
+            "{number}",
+            &fill_pattern(pick(data.building_number_patterns)),
+        )
+        .replace("{city}", city)
+        .replace("{state}", state)
+        .replace("{state_abbr}", state_abbr)
+        .replace("{postal_code}", &fill_pattern(data.postal_code_pattern)))
 }
 
 /// Generate a birthday
@@ -114,3 +187,38 @@ pub fn generate_birthday(options: &MockOptions) -> Result<String> {
 
     Ok(birthday.format("%Y-%m-%d").to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locales::Locale;
+
+    fn options(locale: Locale) -> MockOptions {
+        MockOptions {
+            locale,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_data_follows_the_locale_format() {
+        let address = generate_address(&options(Locale::PtBr)).unwrap();
+        let postal_code = address.rsplit(", ").next().unwrap();
+        assert_eq!(postal_code.len(), 9, "{}", address);
+        assert_eq!(&postal_code[5..6], "-", "{}", address);
+
+        let phone = generate_phone(&options(Locale::DeDe)).unwrap();
+        assert!(phone.starts_with('0'), "{}", phone);
+        assert_eq!(
+            generate_postal_code(&options(Locale::DeDe)).unwrap().len(),
+            5
+        );
+
+        let address = generate_address(&options(Locale::JaJp)).unwrap();
+        assert!(address.starts_with('〒'), "{}", address);
+        let email = generate_email(&options(Locale::JaJp)).unwrap();
+        assert!(email.is_ascii(), "{}", email);
+
+        assert_eq!(to_email_name("João Müller"), "joaomueller");
+    }
+}
@@ -0,0 +1,72 @@
+use crate::cli_utils::{get_cli_arguments, plugin_paths};
+use crate::mock_app::generate_mock_data;
+use crate::models::MockOptions;
+use crate::plugins::load_plugins;
+use crate::registry::GeneratorRegistry;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod generators;
+mod locales;
+mod mock_app;
+mod models;
+mod openapi;
+mod plugins;
+mod registry;
+
+/// Mock data generator.
+///
+/// Loads the plugins, validates the arguments, then prints the generated data.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    let args = get_cli_arguments(argv.into_iter().map(Into::into).collect());
+
+    // Built-in generators, plus the ones from the plugins
+    let mut registry = GeneratorRegistry::with_builtins();
+    for path in plugin_paths(&args) {
+        match load_plugins(&mut registry, &path) {
+            Ok(warnings) => warnings
+                .iter()
+                .for_each(|warning| eprintln!("Warning: {}", warning)),
+            Err(e) => {
+                eprintln!("Error: Failed to load plugins: {:#}", e);
+                return EXIT_CODE_ERROR;
+            }
+        }
+    }
+
+    // Validate arguments and create options
+    let options = match MockOptions::from_args(&args, &registry) {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("\nAvailable data types:\n{}", registry.help_text());
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    // Generate mock data
+    match generate_mock_data(&registry, &options) {
+        Ok(result) => {
+            println!("{}", result);
+            EXIT_CODE_SUCCESS
+        }
+        Err(e) => {
+            error!("Failed to generate mock data: {}", e);
+            eprintln!("Error: Failed to generate mock data: {}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
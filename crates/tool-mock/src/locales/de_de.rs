@@ -0,0 +1,101 @@
+use crate::locales::LocaleData;
+
+pub const DATA: LocaleData = LocaleData {
+    cities: &[
+        ("Berlin", "Berlin", "BE"),
+        ("Hamburg", "Hamburg", "HH"),
+        ("München", "Bayern", "BY"),
+        ("Nürnberg", "Bayern", "BY"),
+        ("Köln", "Nordrhein-Westfalen", "NW"),
+        ("Düsseldorf", "Nordrhein-Westfalen", "NW"),
+        ("Dortmund", "Nordrhein-Westfalen", "NW"),
+        ("Frankfurt am Main", "Hessen", "HE"),
+        ("Stuttgart", "Baden-Württemberg", "BW"),
+        ("Freiburg im Breisgau", "Baden-Württemberg", "BW"),
+        ("Leipzig", "Sachsen", "SN"),
+        ("Dresden", "Sachsen", "SN"),
+        ("Hannover", "Niedersachsen", "NI"),
+        ("Bremen", "Bremen", "HB"),
+        ("Mainz", "Rheinland-Pfalz", "RP"),
+        ("Kiel", "Schleswig-Holstein", "SH"),
+        ("Erfurt", "Thüringen", "TH"),
+        ("Rostock", "Mecklenburg-Vorpommern", "MV"),
+    ],
+    streets: &[
+        "Hauptstraße",
+        "Bahnhofstraße",
+        "Goethestraße",
+        "Schillerstraße",
+        "Gartenstraße",
+        "Dorfstraße",
+        "Lindenstraße",
+        "Kirchstraße",
+        "Bergstraße",
+        "Waldweg",
+        "Mozartweg",
+        "Am Marktplatz",
+        "Berliner Allee",
+        "Rosenweg",
+    ],
+    building_number_patterns: &["%", "%#", "%#", "%a"],
+    postal_code_pattern: "%####",
+    address_template: "{street} {number}, {postal_code} {city}",
+    countries: &[
+        "Deutschland",
+        "Österreich",
+        "Schweiz",
+        "Frankreich",
+        "Niederlande",
+        "Polen",
+        "Italien",
+        "Spanien",
+        "Vereinigte Staaten",
+        "Japan",
+        "Brasilien",
+        "Dänemark",
+    ],
+    phone_patterns: &[
+        "0%# ########",
+        "0%## #######",
+        "015# ########",
+        "017# #######",
+    ],
+    email_domains: &["gmail.com", "web.de", "gmx.de", "t-online.de", "outlook.de"],
+    romanized_names: &[],
+    company_templates: &[
+        "{last_name} {suffix}",
+        "{last_name} & {last_name2} {suffix}",
+        "{last_name}-{last_name2} {suffix}",
+    ],
+    company_suffixes: &["GmbH", "AG", "KG", "GmbH & Co. KG", "e.K."],
+    job_titles: &[
+        "Softwareentwickler",
+        "Projektleiter",
+        "Bauingenieur",
+        "Steuerberater",
+        "Rechtsanwalt",
+        "Lehrer",
+        "Krankenpfleger",
+        "Vertriebsmitarbeiter",
+        "Grafikdesigner",
+        "Bürokaufmann",
+        "Geschäftsführer",
+        "Finanzanalyst",
+        "Systemadministrator",
+        "Elektriker",
+    ],
+    industries: &[
+        "Automobilindustrie",
+        "Maschinenbau",
+        "Chemie",
+        "Informationstechnik",
+        "Einzelhandel",
+        "Finanzdienstleistungen",
+        "Gesundheitswesen",
+        "Logistik",
+        "Energieversorgung",
+        "Bauwesen",
+        "Telekommunikation",
+        "Tourismus",
+    ],
+};
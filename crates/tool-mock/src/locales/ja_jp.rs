@@ -0,0 +1,122 @@
+use crate::locales::LocaleData;
+
+pub const DATA: LocaleData = LocaleData {
+    cities: &[
+        ("千代田区", "東京都", "東京"),
+        ("新宿区", "東京都", "東京"),
+        ("渋谷区", "東京都", "東京"),
+        ("八王子市", "東京都", "東京"),
+        ("横浜市", "神奈川県", "神奈川"),
+        ("川崎市", "神奈川県", "神奈川"),
+        ("大阪市", "大阪府", "大阪"),
+        ("堺市", "大阪府", "大阪"),
+        ("名古屋市", "愛知県", "愛知"),
+        ("札幌市", "北海道", "北海道"),
+        ("福岡市", "福岡県", "福岡"),
+        ("京都市", "京都府", "京都"),
+        ("神戸市", "兵庫県", "兵庫"),
+        ("仙台市", "宮城県", "宮城"),
+        ("広島市", "広島県", "広島"),
+        ("さいたま市", "埼玉県", "埼玉"),
+        ("千葉市", "千葉県", "千葉"),
+        ("那覇市", "沖縄県", "沖縄"),
+    ],
+    streets: &[
+        "本町",
+        "中央",
+        "栄町",
+        "緑町",
+        "旭町",
+        "桜木町",
+        "青葉台",
+        "若葉町",
+        "駅前",
+        "昭和町",
+        "東町",
+        "南町",
+    ],
+    building_number_patterns: &["%-%-%", "%-%#-%", "%-%#-%#", "%丁目%-%"],
+    postal_code_pattern: "%##-####",
+    address_template: "〒{postal_code} {state}{city}{street}{number}",
+    countries: &[
+        "日本",
+        "アメリカ合衆国",
+        "中国",
+        "韓国",
+        "イギリス",
+        "ドイツ",
+        "フランス",
+        "ブラジル",
+        "オーストラリア",
+        "カナダ",
+        "イタリア",
+        "タイ",
+    ],
+    phone_patterns: &[
+        "0%-####-####",
+        "0%#-###-####",
+        "090-####-####",
+        "080-####-####",
+    ],
+    email_domains: &[
+        "gmail.com",
+        "yahoo.co.jp",
+        "docomo.ne.jp",
+        "icloud.com",
+        "outlook.jp",
+    ],
+    romanized_names: &[
+        "sato",
+        "suzuki",
+        "takahashi",
+        "tanaka",
+        "watanabe",
+        "ito",
+        "yamamoto",
+        "nakamura",
+        "kobayashi",
+        "kato",
+        "haruto",
+        "yuto",
+        "sota",
+        "ren",
+        "yui",
+        "hina",
+        "aoi",
+        "sakura",
+    ],
+    company_templates: &[
+        "{suffix}{last_name}",
+        "{last_name}{suffix}",
+        "{last_name}商事{suffix}",
+    ],
+    company_suffixes: &["株式会社", "有限会社", "合同会社"],
+    job_titles: &[
+        "ソフトウェアエンジニア",
+        "プロジェクトマネージャー",
+        "営業担当",
+        "経理担当",
+        "人事担当",
+        "デザイナー",
+        "看護師",
+        "教師",
+        "弁護士",
+        "システム管理者",
+        "代表取締役",
+        "マーケティング担当",
+    ],
+    industries: &[
+        "自動車",
+        "電機",
+        "情報通信",
+        "小売",
+        "金融",
+        "医療",
+        "教育",
+        "物流",
+        "建設",
+        "食品",
+        "観光",
+        "エネルギー",
+    ],
+};
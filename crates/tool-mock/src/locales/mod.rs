@@ -0,0 +1,140 @@
+pub mod de_de;
+pub mod ja_jp;
+pub mod pt_br;
+
+use rand::Rng;
+
+/// Locales mock generates region-specific data for.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Locale {
+    /// Uses the English data of `fake`.
+    #[default]
+    EnUs,
+    PtBr,
+    DeDe,
+    JaJp,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::EnUs, Locale::PtBr, Locale::DeDe, Locale::JaJp];
+
+    /// Parses a locale, in any case, with `-` or `_` (e.g.: `pt-BR`, `pt_br`), or just its
+    /// language (e.g.: `pt`).
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let normalized = value.trim().to_lowercase().replace('_', "-");
+
+        Locale::ALL
+            .into_iter()
+            .find(|locale| {
+                let name = locale.name().to_lowercase();
+                normalized == name || name.split('-').next() == Some(normalized.as_str())
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Unsupported locale: {}. Use one of: {}",
+                    value,
+                    Locale::ALL.map(|locale| locale.name()).join(", ")
+                )
+            })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "en-US",
+            Locale::PtBr => "pt-BR",
+            Locale::DeDe => "de-DE",
+            Locale::JaJp => "ja-JP",
+        }
+    }
+
+    /// Data tables of the locale. `None` for en-US, generated with the English data of `fake`.
+    pub fn data(&self) -> Option<&'static LocaleData> {
+        match self {
+            Locale::EnUs => None,
+            Locale::PtBr => Some(&pt_br::DATA),
+            Locale::DeDe => Some(&de_de::DATA),
+            Locale::JaJp => Some(&ja_jp::DATA),
+        }
+    }
+}
+
+/// Region-specific data of a locale, for what `fake` doesn't have (or has in English only).
+///
+/// Patterns replace `#` with a digit and `%` with a non-zero digit.
+pub struct LocaleData {
+    /// Cities, with the name and the abbreviation of their state.
+    pub cities: &'static [(&'static str, &'static str, &'static str)],
+    pub streets: &'static [&'static str],
+    pub building_number_patterns: &'static [&'static str],
+    pub postal_code_pattern: &'static str,
+    /// Uses `{street}`, `{number}`, `{city}`, `{state}`, `{state_abbr}`, and `{postal_code}`.
+    pub address_template: &'static str,
+    /// Country names, in the language of the locale.
+    pub countries: &'static [&'static str],
+    pub phone_patterns: &'static [&'static str],
+    pub email_domains: &'static [&'static str],
+    /// Names used in emails, when the names of the locale aren't written in the Latin alphabet.
+    pub romanized_names: &'static [&'static str],
+    /// Use `{last_name}`, `{last_name2}`, and `{suffix}`.
+    pub company_templates: &'static [&'static str],
+    pub company_suffixes: &'static [&'static str],
+    pub job_titles: &'static [&'static str],
+    pub industries: &'static [&'static str],
+}
+
+/// Picks a random item.
+pub fn pick<T: Copy>(items: &[T]) -> T {
+    items[rand::rng().random_range(0..items.len())]
+}
+
+/// Fills a pattern: `#` becomes a digit and `%` a non-zero digit.
+pub fn fill_pattern(pattern: &str) -> String {
+    pattern
+        .chars()
+        .map(|c| match c {
+            '#' => char::from(b'0' + rand::rng().random_range(0..10)),
+            '%' => char::from(b'0' + rand::rng().random_range(1..10)),
+            other => other,
+        })
+        .collect()
+}
+
+/// Generates a value with a `fake` faker, using the data of the locale.
+///
+/// ```ignore
+/// let name: String = fake_localized!(options.locale, FirstName);
+/// ```
+macro_rules! fake_localized {
+    ($locale:expr, $faker:ident) => {{
+        use fake::locales::{DE_DE, EN, JA_JP, PT_BR};
+        use fake::Fake;
+
+        match $locale {
+            $crate::locales::Locale::EnUs => $faker(EN).fake::<String>(),
+            $crate::locales::Locale::PtBr => $faker(PT_BR).fake::<String>(),
+            $crate::locales::Locale::DeDe => $faker(DE_DE).fake::<String>(),
+            $crate::locales::Locale::JaJp => $faker(JA_JP).fake::<String>(),
+        }
+    }};
+}
+
+pub(crate) use fake_localized;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(Locale::parse("pt-BR").unwrap(), Locale::PtBr);
+        assert_eq!(Locale::parse("de_de").unwrap(), Locale::DeDe);
+        assert_eq!(Locale::parse("ja").unwrap(), Locale::JaJp);
+        assert_eq!(Locale::parse("en_US").unwrap(), Locale::EnUs);
+        assert!(Locale::parse("fr-FR").is_err());
+
+        let filled = fill_pattern("%####-###");
+        assert_eq!(filled.len(), 9);
+        assert!(!filled.starts_with('0'));
+        assert!(filled.chars().all(|c| c.is_ascii_digit() || c == '-'));
+    }
+}
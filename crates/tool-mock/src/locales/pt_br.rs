@@ -0,0 +1,107 @@
+use crate::locales::LocaleData;
+
+pub const DATA: LocaleData = LocaleData {
+    cities: &[
+        ("São Paulo", "São Paulo", "SP"),
+        ("Campinas", "São Paulo", "SP"),
+        ("Santos", "São Paulo", "SP"),
+        ("Rio de Janeiro", "Rio de Janeiro", "RJ"),
+        ("Niterói", "Rio de Janeiro", "RJ"),
+        ("Belo Horizonte", "Minas Gerais", "MG"),
+        ("Uberlândia", "Minas Gerais", "MG"),
+        ("Salvador", "Bahia", "BA"),
+        ("Fortaleza", "Ceará", "CE"),
+        ("Recife", "Pernambuco", "PE"),
+        ("Curitiba", "Paraná", "PR"),
+        ("Londrina", "Paraná", "PR"),
+        ("Porto Alegre", "Rio Grande do Sul", "RS"),
+        ("Florianópolis", "Santa Catarina", "SC"),
+        ("Joinville", "Santa Catarina", "SC"),
+        ("Brasília", "Distrito Federal", "DF"),
+        ("Goiânia", "Goiás", "GO"),
+        ("Manaus", "Amazonas", "AM"),
+        ("Belém", "Pará", "PA"),
+        ("Vitória", "Espírito Santo", "ES"),
+        ("Natal", "Rio Grande do Norte", "RN"),
+        ("São Luís", "Maranhão", "MA"),
+    ],
+    streets: &[
+        "Rua das Flores",
+        "Rua XV de Novembro",
+        "Rua Sete de Setembro",
+        "Rua Tiradentes",
+        "Rua Santos Dumont",
+        "Rua Dom Pedro II",
+        "Rua São João",
+        "Rua das Palmeiras",
+        "Avenida Paulista",
+        "Avenida Brasil",
+        "Avenida Getúlio Vargas",
+        "Avenida Rio Branco",
+        "Alameda Santos",
+        "Travessa da Paz",
+    ],
+    building_number_patterns: &["%", "%#", "%##", "%###"],
+    postal_code_pattern: "#####-###",
+    address_template: "{street}, {number} - {city}/{state_abbr}, {postal_code}",
+    countries: &[
+        "Brasil",
+        "Argentina",
+        "Portugal",
+        "Estados Unidos",
+        "Alemanha",
+        "Japão",
+        "França",
+        "Itália",
+        "Espanha",
+        "México",
+        "Chile",
+        "Canadá",
+    ],
+    phone_patterns: &["(%#) 9####-####", "(%#) %###-####"],
+    email_domains: &[
+        "gmail.com",
+        "hotmail.com",
+        "outlook.com",
+        "yahoo.com.br",
+        "uol.com.br",
+        "bol.com.br",
+    ],
+    romanized_names: &[],
+    company_templates: &[
+        "{last_name} {suffix}",
+        "{last_name} & {last_name2} {suffix}",
+        "Grupo {last_name}",
+    ],
+    company_suffixes: &["Ltda.", "S.A.", "ME", "Comércio Ltda.", "Serviços Ltda."],
+    job_titles: &[
+        "Analista de Sistemas",
+        "Desenvolvedor de Software",
+        "Gerente de Projetos",
+        "Engenheiro Civil",
+        "Contador",
+        "Advogado",
+        "Professor",
+        "Enfermeiro",
+        "Vendedor",
+        "Designer Gráfico",
+        "Assistente Administrativo",
+        "Diretor Comercial",
+        "Analista Financeiro",
+        "Técnico de Suporte",
+    ],
+    industries: &[
+        "Agronegócio",
+        "Tecnologia da Informação",
+        "Varejo",
+        "Construção Civil",
+        "Serviços Financeiros",
+        "Saúde",
+        "Educação",
+        "Logística",
+        "Telecomunicações",
+        "Energia",
+        "Mineração",
+        "Turismo",
+    ],
+};
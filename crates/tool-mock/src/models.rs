@@ -1,148 +1,13 @@
+use crate::locales::Locale;
+use crate::registry::GeneratorRegistry;
 use clap::ArgMatches;
-
-/// Available data types within each category
-#[derive(Debug, Clone)]
-pub enum DataType {
-    // Personal Information
-    FirstName,
-    LastName,
-    FullName,
-    Email,
-    Phone,
-    Street,
-    City,
-    State,
-    Country,
-    PostalCode,
-    Address,
-    Birthday,
-
-    // Internet & Tech
-    Username,
-    Password,
-    Url,
-    ImageUrl,
-    FileUrl,
-
-    // Random Data
-    Date,
-    Time,
-    DateTime,
-    Timestamp,
-    ColorHex,
-    ColorRgb,
-    Integer,
-    Float,
-    CarBrand,
-
-    // Commerce
-    Company,
-    Product,
-    ProductDescription,
-    JobTitle,
-    Industry,
-    Buzzword,
-}
-
-impl DataType {
-    /// Parse data type from string command (e.g., "person.first-name")
-    pub fn from_command(command: &str) -> Result<Self, String> {
-        match command {
-            // Personal Information
-            "person.first-name" => Ok(DataType::FirstName),
-            "person.last-name" => Ok(DataType::LastName),
-            "person.full-name" => Ok(DataType::FullName),
-            "person.email" => Ok(DataType::Email),
-            "person.phone" => Ok(DataType::Phone),
-            "person.street" => Ok(DataType::Street),
-            "person.city" => Ok(DataType::City),
-            "person.state" => Ok(DataType::State),
-            "person.country" => Ok(DataType::Country),
-            "person.postal-code" => Ok(DataType::PostalCode),
-            "person.address" => Ok(DataType::Address),
-            "person.birthday" => Ok(DataType::Birthday),
-
-            // Internet & Tech
-            "internet.username" => Ok(DataType::Username),
-            "internet.password" => Ok(DataType::Password),
-            "internet.url" => Ok(DataType::Url),
-            "internet.image-url" => Ok(DataType::ImageUrl),
-            "internet.file-url" => Ok(DataType::FileUrl),
-
-            // Random Data
-            "random.date" => Ok(DataType::Date),
-            "random.time" => Ok(DataType::Time),
-            "random.datetime" => Ok(DataType::DateTime),
-            "random.timestamp" => Ok(DataType::Timestamp),
-            "random.color-hex" => Ok(DataType::ColorHex),
-            "random.color-rgb" => Ok(DataType::ColorRgb),
-            "random.integer" => Ok(DataType::Integer),
-            "random.float" => Ok(DataType::Float),
-            "random.car-brand" => Ok(DataType::CarBrand),
-
-            // Commerce
-            "commerce.company" => Ok(DataType::Company),
-            "commerce.product" => Ok(DataType::Product),
-            "commerce.product-description" => Ok(DataType::ProductDescription),
-            "commerce.job-title" => Ok(DataType::JobTitle),
-            "commerce.industry" => Ok(DataType::Industry),
-            "commerce.buzzword" => Ok(DataType::Buzzword),
-
-            _ => Err(format!("Unknown data type: {}", command)),
-        }
-    }
-
-    /// Get all available commands as a formatted string
-    pub fn all_commands() -> String {
-        vec![
-            "Personal Information:",
-            "  person.first-name     - Generate a random first name",
-            "  person.last-name      - Generate a random last name",
-            "  person.full-name      - Generate a full name (first + last)",
-            "  person.email          - Generate a random email address",
-            "  person.phone          - Generate a phone number",
-            "  person.street         - Generate a street name",
-            "  person.city           - Generate a city name",
-            "  person.state          - Generate a state name",
-            "  person.country        - Generate a country name",
-            "  person.postal-code    - Generate postal/zip code",
-            "  person.address        - Generate a full address",
-            "  person.birthday       - Generate a birthday (with optional --age parameter)",
-            "",
-            "Internet & Tech:",
-            "  internet.username     - Generate a username",
-            "  internet.password     - Generate a password (with --length option)",
-            "  internet.url          - Generate a URL",
-            "  internet.image-url    - Generate an image URL",
-            "  internet.file-url     - Generate a file URL",
-            "",
-            "Random Data:",
-            "  random.date           - Generate a date (with --past, --future, or --range options)",
-            "  random.time           - Generate a time (with --past, --future options)",
-            "  random.datetime       - Generate a datetime (with --past, --future options)",
-            "  random.timestamp      - Generate a timestamp (with --past, --future options)",
-            "  random.color-hex      - Generate a hex color code",
-            "  random.color-rgb      - Generate RGB color values",
-            "  random.integer        - Generate an integer (with --min, --max options)",
-            "  random.float          - Generate a float (with --min, --max, --precision options)",
-            "  random.car-brand      - Generate a car brand name",
-            "",
-            "Commerce:",
-            "  commerce.company      - Generate a company name",
-            "  commerce.product      - Generate a product name",
-            "  commerce.product-description - Generate a product description",
-            "  commerce.job-title    - Generate a job title",
-            "  commerce.industry     - Generate an industry name",
-            "  commerce.buzzword     - Generate a business buzzword",
-        ]
-        .join("\n")
-    }
-}
+use std::path::PathBuf;
 
 /// Command-line arguments for mock data generator
 #[derive(Debug)]
 pub struct MockArgs {
     pub data_type: Option<String>,
+    pub locale: String,
     pub min: Option<i32>,
     pub max: Option<i32>,
     pub length: Option<usize>,
@@ -151,6 +16,12 @@ pub struct MockArgs {
     pub past: bool,
     pub future: bool,
     pub range: Option<u32>,
+    pub spec: Option<PathBuf>,
+    pub schema: Option<String>,
+    pub operation: Option<String>,
+    pub count: Option<usize>,
+    pub post: Option<String>,
+    pub plugins: Vec<PathBuf>,
 }
 
 impl MockArgs {
@@ -158,6 +29,10 @@ impl MockArgs {
     pub fn parse(args: &ArgMatches) -> Self {
         MockArgs {
             data_type: args.get_one::<String>("data_type").cloned(),
+            locale: args
+                .get_one::<String>("locale")
+                .cloned()
+                .unwrap_or_else(|| "en-US".to_string()),
             min: args.get_one::<String>("min").and_then(|s| s.parse().ok()),
             max: args.get_one::<String>("max").and_then(|s| s.parse().ok()),
             length: args
@@ -170,6 +45,15 @@ impl MockArgs {
             past: args.get_flag("past"),
             future: args.get_flag("future"),
             range: args.get_one::<String>("range").and_then(|s| s.parse().ok()),
+            spec: args.get_one::<PathBuf>("spec").cloned(),
+            schema: args.get_one::<String>("schema").cloned(),
+            operation: args.get_one::<String>("operation").cloned(),
+            count: args.get_one::<String>("count").and_then(|s| s.parse().ok()),
+            post: args.get_one::<String>("post").cloned(),
+            plugins: args
+                .get_many::<PathBuf>("plugins")
+                .map(|paths| paths.cloned().collect())
+                .unwrap_or_default(),
         }
     }
 
@@ -192,14 +76,40 @@ impl MockArgs {
             return Err("Data type must be specified".to_string());
         }
 
+        if self.data_type.as_deref() == Some("openapi.payload") {
+            self.validate_openapi()?;
+        }
+
+        Ok(())
+    }
+
+    /// Validate the options of `openapi.payload`
+    fn validate_openapi(&self) -> Result<(), String> {
+        if self.spec.is_none() {
+            return Err("An OpenAPI spec must be specified with --spec".to_string());
+        }
+
+        if self.schema.is_some() == self.operation.is_some() {
+            return Err("Specify either --schema or --operation".to_string());
+        }
+
+        if self.post.is_some() && self.operation.is_none() {
+            return Err("--post requires --operation".to_string());
+        }
+
+        if self.count == Some(0) {
+            return Err("Count must be greater than 0".to_string());
+        }
+
         Ok(())
     }
 }
 
 /// Configuration options for mock data generation
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct MockOptions {
-    pub data_type: DataType,
+    pub data_type: String,
+    pub locale: Locale,
     pub min: Option<i32>,
     pub max: Option<i32>,
     pub length: Option<usize>,
@@ -208,18 +118,26 @@ pub struct MockOptions {
     pub past: bool,
     pub future: bool,
     pub range: Option<u32>,
+    pub spec: Option<PathBuf>,
+    pub schema: Option<String>,
+    pub operation: Option<String>,
+    pub count: Option<usize>,
+    pub post: Option<String>,
 }
 
 impl MockOptions {
-    /// Create MockOptions from command-line arguments
-    pub fn from_args(args: &MockArgs) -> Result<Self, String> {
+    /// Create MockOptions from command-line arguments, for one of the registry's generators
+    pub fn from_args(args: &MockArgs, registry: &GeneratorRegistry) -> Result<Self, String> {
         args.validate()?;
 
-        let data_type_str = args.data_type.as_ref().unwrap();
-        let data_type = DataType::from_command(data_type_str)?;
+        let data_type = args.data_type.clone().unwrap();
+        if registry.get(&data_type).is_none() {
+            return Err(format!("Unknown data type: {}", data_type));
+        }
 
         Ok(MockOptions {
             data_type,
+            locale: Locale::parse(&args.locale)?,
             min: args.min,
             max: args.max,
             length: args.length,
@@ -228,6 +146,11 @@ impl MockOptions {
             past: args.past,
             future: args.future,
             range: args.range,
+            spec: args.spec.clone(),
+            schema: args.schema.clone(),
+            operation: args.operation.clone(),
+            count: args.count,
+            post: args.post.clone(),
         })
     }
 }
@@ -0,0 +1,2 @@
+pub mod payload_generator;
+pub mod spec;
@@ -0,0 +1,636 @@
+use crate::locales::Locale;
+use crate::models::MockOptions;
+use crate::openapi::spec::OpenApiSpec;
+use crate::registry::GeneratorRegistry;
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{Duration, SecondsFormat, Utc};
+use fake::faker::address::en::{CityName, CountryName, PostCode, StateName, StreetName};
+use fake::faker::company::en::CompanyName;
+use fake::faker::internet::en::{DomainSuffix, IPv4, IPv6, SafeEmail, Username};
+use fake::faker::lorem::en::{Sentence, Word};
+use fake::faker::name::en::{FirstName, LastName, Name};
+use fake::faker::phone_number::en::PhoneNumber;
+use fake::Fake;
+use rand::Rng;
+use serde_json::{Map, Number, Value};
+use shared::utils::new_guid::new_guid;
+
+/// Past this depth, optional properties are skipped and arrays get their minimum size, so
+/// recursive schemas (e.g.: a category with sub-categories) end.
+const MAX_DEPTH: usize = 8;
+const DEFAULT_MAX_ITEMS: u64 = 3;
+const DEFAULT_NUMBER_RANGE: f64 = 1000.0;
+
+/// Generates random values that are valid for a schema.
+///
+/// Respects `enum`/`const`, required properties, string formats, lengths, numeric ranges, and
+/// array sizes. `allOf` schemas are merged, and one option of `oneOf`/`anyOf` is picked.
+/// `pattern` is not supported.
+///
+/// With a registry, `x-mock: <generator>` picks the generator of a schema (e.g.:
+/// `x-mock: person.first-name`, or a plugin's `acme.order-id`).
+pub struct PayloadGenerator<'a> {
+    spec: &'a OpenApiSpec,
+    skip_read_only: bool,
+    registry: Option<&'a GeneratorRegistry>,
+    locale: Locale,
+}
+
+impl<'a> PayloadGenerator<'a> {
+    /// `skip_read_only` leaves out `readOnly` properties (e.g.: server generated ids), which
+    /// don't belong in request bodies.
+    pub fn new(spec: &'a OpenApiSpec, skip_read_only: bool) -> Self {
+        Self {
+            spec,
+            skip_read_only,
+            registry: None,
+            locale: Locale::default(),
+        }
+    }
+
+    /// Generators used by the schemas with `x-mock`.
+    pub fn with_registry(mut self, registry: &'a GeneratorRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Locale of the values generated with `x-mock`.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    pub fn generate(&self, schema: &Value) -> Result<Value> {
+        self.generate_value(schema, None, 0)
+    }
+
+    fn generate_value(
+        &self,
+        schema: &Value,
+        property_name: Option<&str>,
+        depth: usize,
+    ) -> Result<Value> {
+        let schema = self.spec.resolve(schema)?;
+
+        if let Some(value) = schema.get("const") {
+            return Ok(value.clone());
+        }
+
+        if let Some(value) = schema
+            .get("enum")
+            .and_then(Value::as_array)
+            .and_then(|e| pick(e))
+        {
+            return Ok(value.clone());
+        }
+
+        if let Some(name) = schema.get("x-mock").and_then(Value::as_str) {
+            return self.generate_with(name, schema);
+        }
+
+        if schema.get("allOf").is_some() {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            self.collect_all_of(schema, &mut properties, &mut required, 0)?;
+
+            let merged = serde_json::json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            });
+            return self.generate_object(&merged, depth);
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            if let Some(option) = schema
+                .get(key)
+                .and_then(Value::as_array)
+                .and_then(|o| pick(o))
+            {
+                return self.generate_value(option, property_name, depth);
+            }
+        }
+
+        match schema_type(schema) {
+            "object" => self.generate_object(schema, depth),
+            "array" => self.generate_array(schema, property_name, depth),
+            "integer" => generate_integer(schema),
+            "number" => generate_number(schema),
+            "boolean" => Ok(Value::Bool(rand::rng().random_bool(0.5))),
+            "null" => Ok(Value::Null),
+            _ => Ok(Value::String(generate_string(schema, property_name))),
+        }
+    }
+
+    /// Uses the generator `name` of the registry. For schemas that aren't strings, values that
+    /// are valid JSON (numbers, booleans, objects from plugins) are used as JSON.
+    fn generate_with(&self, name: &str, schema: &Value) -> Result<Value> {
+        let registry = self
+            .registry
+            .ok_or_else(|| anyhow!("x-mock ({}) needs the generator registry", name))?;
+        let value = registry
+            .generate(
+                name,
+                &MockOptions {
+                    locale: self.locale,
+                    ..Default::default()
+                },
+            )
+            .map_err(|e| anyhow!("x-mock ({}): {}", name, e))?;
+
+        if schema_type(schema) != "string" {
+            if let Ok(json) = serde_json::from_str::<Value>(&value) {
+                return Ok(json);
+            }
+        }
+
+        Ok(Value::String(value))
+    }
+
+    /// Required properties are always present. Optional ones are included half of the time.
+    fn generate_object(&self, schema: &Value, depth: usize) -> Result<Value> {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut object = Map::new();
+
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            return Ok(Value::Object(object));
+        };
+
+        for (name, property) in properties {
+            if self.skip_read_only && is_read_only(self.spec.resolve(property)?) {
+                continue;
+            }
+
+            let is_required = required.contains(&name.as_str());
+            if !is_required && (depth >= MAX_DEPTH || !rand::rng().random_bool(0.5)) {
+                continue;
+            }
+
+            object.insert(
+                name.clone(),
+                self.generate_value(property, Some(name), depth + 1)?,
+            );
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    fn generate_array(
+        &self,
+        schema: &Value,
+        property_name: Option<&str>,
+        depth: usize,
+    ) -> Result<Value> {
+        let min_items = schema.get("minItems").and_then(Value::as_u64).unwrap_or(1);
+        let max_items = schema
+            .get("maxItems")
+            .and_then(Value::as_u64)
+            .unwrap_or(min_items.max(DEFAULT_MAX_ITEMS));
+
+        if min_items > max_items {
+            return Err(anyhow!(
+                "Invalid array size: minItems {} is greater than maxItems {}",
+                min_items,
+                max_items
+            ));
+        }
+
+        let Some(items) = schema.get("items") else {
+            return Ok(Value::Array(vec![]));
+        };
+
+        let count = if depth >= MAX_DEPTH {
+            min_items
+        } else {
+            rand::rng().random_range(min_items..=max_items)
+        };
+
+        let values = (0..count)
+            .map(|_| self.generate_value(items, property_name, depth + 1))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Value::Array(values))
+    }
+
+    /// Gathers the properties and required lists of a schema and all of its `allOf` parts.
+    fn collect_all_of(
+        &self,
+        schema: &Value,
+        properties: &mut Map<String, Value>,
+        required: &mut Vec<Value>,
+        depth: usize,
+    ) -> Result<()> {
+        if depth > MAX_DEPTH {
+            return Err(anyhow!(
+                "allOf nested too deep. Is there a reference cycle?"
+            ));
+        }
+
+        let schema = self.spec.resolve(schema)?;
+
+        if let Some(part_properties) = schema.get("properties").and_then(Value::as_object) {
+            properties.extend(part_properties.clone());
+        }
+
+        if let Some(part_required) = schema.get("required").and_then(Value::as_array) {
+            required.extend(part_required.iter().cloned());
+        }
+
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            for part in all_of {
+                self.collect_all_of(part, properties, required, depth + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn pick(options: &[Value]) -> Option<&Value> {
+    if options.is_empty() {
+        return None;
+    }
+
+    Some(&options[rand::rng().random_range(0..options.len())])
+}
+
+/// OpenAPI 3.1 allows a list of types (e.g.: `["string", "null"]`). The first non-null one is
+/// used. Without a type, it is inferred from the other keywords.
+fn schema_type(schema: &Value) -> &str {
+    match schema.get("type") {
+        Some(Value::String(schema_type)) => schema_type.as_str(),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|schema_type| *schema_type != "null")
+            .unwrap_or("null"),
+        _ if schema.get("properties").is_some() => "object",
+        _ if schema.get("items").is_some() => "array",
+        _ => "string",
+    }
+}
+
+fn is_read_only(schema: &Value) -> bool {
+    schema
+        .get("readOnly")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// A numeric limit, and whether it is exclusive.
+type Bound = Option<(f64, bool)>;
+
+/// Minimum and maximum, with whether they are exclusive. `exclusiveMinimum` is a flag in
+/// OpenAPI 3.0 and the bound itself in 3.1; both are accepted.
+fn numeric_bounds(schema: &Value) -> (Bound, Bound) {
+    let bound = |inclusive_key: &str, exclusive_key: &str| match (
+        schema.get(inclusive_key).and_then(Value::as_f64),
+        schema.get(exclusive_key),
+    ) {
+        (_, Some(Value::Number(exclusive))) => exclusive.as_f64().map(|value| (value, true)),
+        (Some(value), Some(Value::Bool(exclusive))) => Some((value, *exclusive)),
+        (Some(value), _) => Some((value, false)),
+        (None, _) => None,
+    };
+
+    (
+        bound("minimum", "exclusiveMinimum"),
+        bound("maximum", "exclusiveMaximum"),
+    )
+}
+
+/// Fills in missing bounds, so the range has `DEFAULT_NUMBER_RANGE` values.
+fn default_range(low: Option<f64>, high: Option<f64>) -> (f64, f64) {
+    match (low, high) {
+        (Some(low), Some(high)) => (low, high),
+        (Some(low), None) => (low, low + DEFAULT_NUMBER_RANGE),
+        (None, Some(high)) if high >= 0.0 => (0.0, high),
+        (None, Some(high)) => (high - DEFAULT_NUMBER_RANGE, high),
+        (None, None) => (0.0, DEFAULT_NUMBER_RANGE),
+    }
+}
+
+fn generate_integer(schema: &Value) -> Result<Value> {
+    let (minimum, maximum) = numeric_bounds(schema);
+    let low = minimum.map(|(value, exclusive)| {
+        if exclusive {
+            value.floor() + 1.0
+        } else {
+            value.ceil()
+        }
+    });
+    let high = maximum.map(|(value, exclusive)| {
+        if exclusive {
+            value.ceil() - 1.0
+        } else {
+            value.floor()
+        }
+    });
+
+    let (low, high) = default_range(low, high);
+    let (mut low, mut high) = (low as i64, high as i64);
+
+    let multiple_of = schema
+        .get("multipleOf")
+        .and_then(Value::as_i64)
+        .filter(|multiple| *multiple > 0)
+        .unwrap_or(1);
+    low = low.div_euclid(multiple_of) + i64::from(low.rem_euclid(multiple_of) != 0);
+    high = high.div_euclid(multiple_of);
+
+    if low > high {
+        return Err(anyhow!(
+            "No integer fits the range of the schema: {}",
+            schema
+        ));
+    }
+
+    Ok(Value::from(
+        rand::rng().random_range(low..=high) * multiple_of,
+    ))
+}
+
+/// Values have 2 decimals, unless `multipleOf` asks for something else.
+fn generate_number(schema: &Value) -> Result<Value> {
+    const STEP: f64 = 0.01;
+
+    let (minimum, maximum) = numeric_bounds(schema);
+    let low = minimum.map(|(value, exclusive)| if exclusive { value + STEP } else { value });
+    let high = maximum.map(|(value, exclusive)| if exclusive { value - STEP } else { value });
+    let (low, high) = default_range(low, high);
+
+    if low > high {
+        return Err(anyhow!(
+            "No number fits the range of the schema: {}",
+            schema
+        ));
+    }
+
+    let value = match schema
+        .get("multipleOf")
+        .and_then(Value::as_f64)
+        .filter(|multiple| *multiple > 0.0)
+    {
+        Some(multiple) => {
+            let (first, last) = (
+                (low / multiple).ceil() as i64,
+                (high / multiple).floor() as i64,
+            );
+            if first > last {
+                return Err(anyhow!(
+                    "No number fits the range of the schema: {}",
+                    schema
+                ));
+            }
+
+            // Rounded, so 3 * 0.1 is 0.3 and not 0.30000000000000004.
+            let value = rand::rng().random_range(first..=last) as f64 * multiple;
+            (value * 1e9).round() / 1e9
+        }
+        None => {
+            let value = rand::rng().random_range(low..=high);
+            ((value / STEP).round() * STEP).clamp(low, high)
+        }
+    };
+
+    Ok(Number::from_f64(value)
+        .map(Value::Number)
+        .unwrap_or(Value::from(0)))
+}
+
+fn generate_string(schema: &Value, property_name: Option<&str>) -> String {
+    if let Some(value) = schema
+        .get("format")
+        .and_then(Value::as_str)
+        .and_then(generate_formatted_string)
+    {
+        return value;
+    }
+
+    let value = property_name
+        .and_then(generate_string_from_name)
+        .unwrap_or_else(|| Word().fake::<String>());
+
+    let min_length = schema.get("minLength").and_then(Value::as_u64).unwrap_or(0) as usize;
+    let max_length = schema
+        .get("maxLength")
+        .and_then(Value::as_u64)
+        .map(|max| max as usize);
+
+    fit_length(value, min_length, max_length)
+}
+
+fn generate_formatted_string(format: &str) -> Option<String> {
+    let mut rng = rand::rng();
+
+    let value = match format {
+        "date" => (Utc::now() - Duration::days(rng.random_range(0..3650)))
+            .format("%Y-%m-%d")
+            .to_string(),
+        "date-time" => (Utc::now() - Duration::seconds(rng.random_range(0..315_360_000)))
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+        "time" => format!(
+            "{:02}:{:02}:{:02}",
+            rng.random_range(0..24),
+            rng.random_range(0..60),
+            rng.random_range(0..60)
+        ),
+        "email" => SafeEmail().fake::<String>(),
+        "uuid" => new_guid(),
+        "uri" | "url" | "iri" => format!(
+            "https://{}.{}/{}",
+            Word().fake::<String>(),
+            DomainSuffix().fake::<String>(),
+            Word().fake::<String>()
+        ),
+        "hostname" => format!(
+            "{}.{}",
+            Word().fake::<String>(),
+            DomainSuffix().fake::<String>()
+        ),
+        "ipv4" => IPv4().fake::<String>(),
+        "ipv6" => IPv6().fake::<String>(),
+        "byte" => STANDARD.encode(Sentence(3..6).fake::<String>()),
+        "password" => (0..12)
+            .map(|_| {
+                let chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+                chars[rng.random_range(0..chars.len())] as char
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Realistic values for common property names (e.g.: `email`, `firstName`, `city`).
+fn generate_string_from_name(property_name: &str) -> Option<String> {
+    let name = property_name.to_lowercase().replace(['_', '-'], "");
+
+    let value = if name.contains("email") {
+        SafeEmail().fake::<String>()
+    } else if name.contains("firstname") || name.contains("givenname") {
+        FirstName().fake::<String>()
+    } else if name.contains("lastname") || name.contains("surname") || name.contains("familyname") {
+        LastName().fake::<String>()
+    } else if name.contains("username") || name == "login" {
+        Username().fake::<String>()
+    } else if name.contains("company") {
+        CompanyName().fake::<String>()
+    } else if name == "name" || name == "fullname" {
+        Name().fake::<String>()
+    } else if name.contains("city") {
+        CityName().fake::<String>()
+    } else if name.contains("country") {
+        CountryName().fake::<String>()
+    } else if name == "state" {
+        StateName().fake::<String>()
+    } else if name.contains("zip") || name.contains("postal") || name.contains("postcode") {
+        PostCode().fake::<String>()
+    } else if name.contains("street") || name.contains("address") {
+        StreetName().fake::<String>()
+    } else if name.contains("phone") {
+        PhoneNumber().fake::<String>()
+    } else if name.contains("description") || name.contains("summary") || name.contains("comment") {
+        Sentence(4..10).fake::<String>()
+    } else {
+        return None;
+    };
+
+    Some(value)
+}
+
+/// Pads with random words (or cuts) so the length is within the limits.
+fn fit_length(mut value: String, min_length: usize, max_length: Option<usize>) -> String {
+    while value.chars().count() < min_length {
+        value.push_str(&Word().fake::<String>());
+    }
+
+    match max_length {
+        Some(max_length) if value.chars().count() > max_length => {
+            value.chars().take(max_length).collect()
+        }
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"{
+  "openapi": "3.1.0",
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Pet": {
+        "allOf": [
+          { "$ref": "#/components/schemas/Entity" },
+          {
+            "type": "object",
+            "required": ["name", "status", "age", "tags", "contact"],
+            "properties": {
+              "name": { "type": "string", "minLength": 3, "maxLength": 5 },
+              "status": { "type": "string", "enum": ["available", "sold"] },
+              "age": { "type": "integer", "minimum": 1, "exclusiveMaximum": 4 },
+              "weight": { "type": "number", "minimum": 0.5, "maximum": 0.5 },
+              "tags": { "type": "array", "minItems": 2, "maxItems": 2, "items": { "type": "string" } },
+              "contact": { "type": ["string", "null"], "format": "email" },
+              "parent": { "$ref": "#/components/schemas/Pet" }
+            }
+          }
+        ]
+      },
+      "Entity": {
+        "type": "object",
+        "required": ["id"],
+        "properties": {
+          "id": { "type": "string", "format": "uuid", "readOnly": true }
+        }
+      },
+      "Customer": {
+        "type": "object",
+        "required": ["email", "age"],
+        "properties": {
+          "email": { "type": "string", "x-mock": "person.email" },
+          "age": { "type": "integer", "x-mock": "random.integer" }
+        }
+      },
+      "Unknown": { "type": "string", "x-mock": "person.nope" },
+      "Even": { "type": "integer", "minimum": 1, "maximum": 9, "multipleOf": 2 },
+      "Impossible": { "type": "integer", "minimum": 5, "maximum": 4 }
+    }
+  }
+}"##;
+
+    fn generate(name: &str, skip_read_only: bool) -> Result<Value> {
+        let spec = OpenApiSpec::parse(SPEC).unwrap();
+        let schema = spec.schema(name).unwrap();
+        PayloadGenerator::new(&spec, skip_read_only).generate(schema)
+    }
+
+    #[test]
+    fn test_generated_payload_respects_the_schema() {
+        for _ in 0..50 {
+            let pet = generate("Pet", false).unwrap();
+
+            assert_eq!(pet["id"].as_str().unwrap().len(), 36);
+            let name = pet["name"].as_str().unwrap();
+            assert!((3..=5).contains(&name.chars().count()), "{}", name);
+            assert!(["available", "sold"].contains(&pet["status"].as_str().unwrap()));
+            assert!((1..=3).contains(&pet["age"].as_i64().unwrap()));
+            assert_eq!(pet["tags"].as_array().unwrap().len(), 2);
+            assert!(pet["contact"].as_str().unwrap().contains('@'));
+            if let Some(weight) = pet.get("weight") {
+                assert_eq!(weight.as_f64(), Some(0.5));
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_only_properties_can_be_skipped() {
+        let pet = generate("Pet", true).unwrap();
+
+        assert!(pet.get("id").is_none());
+        assert!(pet.get("name").is_some());
+    }
+
+    #[test]
+    fn test_integer_ranges() {
+        for _ in 0..20 {
+            let value = generate("Even", false).unwrap().as_i64().unwrap();
+            assert!([2, 4, 6, 8].contains(&value), "{}", value);
+        }
+
+        assert!(generate("Impossible", false).is_err());
+    }
+
+    #[test]
+    fn test_x_mock_uses_the_named_generator() {
+        let spec = OpenApiSpec::parse(SPEC).unwrap();
+        let registry = GeneratorRegistry::with_builtins();
+        let generator = PayloadGenerator::new(&spec, false).with_registry(&registry);
+
+        let customer = generator
+            .generate(spec.schema("Customer").unwrap())
+            .unwrap();
+        assert!(customer["email"].as_str().unwrap().contains('@'));
+        assert!(customer["age"].is_i64());
+
+        assert!(generator.generate(spec.schema("Unknown").unwrap()).is_err());
+        assert!(generate("Customer", false).is_err());
+    }
+
+    #[test]
+    fn test_fit_length() {
+        assert!(fit_length("ab".to_string(), 10, None).chars().count() >= 10);
+        assert_eq!(fit_length("abcdef".to_string(), 0, Some(3)), "abc");
+    }
+}
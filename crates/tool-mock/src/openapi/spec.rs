@@ -0,0 +1,308 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+const HTTP_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// An OpenAPI 3.x (or Swagger 2.0) document, loaded from JSON or YAML.
+///
+/// The document is kept as JSON, so schemas can be read as they are written, and `$ref`s are
+/// resolved when needed. Only local references (`#/...`) are supported.
+pub struct OpenApiSpec {
+    document: Value,
+}
+
+/// An operation found by its `operationId`.
+pub struct Operation<'a> {
+    pub method: String,
+    pub path: String,
+    pub body_schema: Option<&'a Value>,
+    pub content_type: String,
+    /// Path parameters (`/pets/{petId}`) and their schemas.
+    pub path_parameters: Vec<(String, &'a Value)>,
+}
+
+impl OpenApiSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .context(format!("Failed to read OpenAPI spec: {}", path.display()))?;
+
+        Self::parse(&content).context(format!("Invalid OpenAPI spec: {}", path.display()))
+    }
+
+    /// JSON is tried first, since every JSON document is also valid YAML.
+    pub fn parse(content: &str) -> Result<Self> {
+        let document = match serde_json::from_str::<Value>(content) {
+            Ok(document) => document,
+            Err(_) => serde_yaml::from_str::<Value>(content)?,
+        };
+
+        if document.get("openapi").is_none() && document.get("swagger").is_none() {
+            anyhow::bail!("Missing the `openapi` (or `swagger`) version field");
+        }
+
+        Ok(Self { document })
+    }
+
+    /// Finds a schema in `components/schemas` (or `definitions`, for Swagger 2.0).
+    pub fn schema(&self, name: &str) -> Result<&Value> {
+        self.document
+            .pointer(&format!("/components/schemas/{}", escape_pointer(name)))
+            .or_else(|| {
+                self.document
+                    .pointer(&format!("/definitions/{}", escape_pointer(name)))
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "Schema not found: {}. Available schemas: {}",
+                    name,
+                    self.schema_names().join(", ")
+                )
+            })
+    }
+
+    pub fn schema_names(&self) -> Vec<String> {
+        ["/components/schemas", "/definitions"]
+            .iter()
+            .filter_map(|pointer| self.document.pointer(pointer))
+            .filter_map(Value::as_object)
+            .flat_map(|schemas| schemas.keys().cloned())
+            .collect()
+    }
+
+    pub fn operation(&self, operation_id: &str) -> Result<Operation<'_>> {
+        let paths = self
+            .document
+            .get("paths")
+            .and_then(Value::as_object)
+            .context("The spec has no paths")?;
+
+        for (path, path_item) in paths {
+            for method in HTTP_METHODS {
+                let Some(operation) = path_item.get(method) else {
+                    continue;
+                };
+
+                if operation.get("operationId").and_then(Value::as_str) != Some(operation_id) {
+                    continue;
+                }
+
+                let parameters = self.parameters(path_item, operation)?;
+                let (content_type, body_schema) = self.body_schema(operation, &parameters)?;
+
+                let path_parameters = parameters
+                    .into_iter()
+                    .filter(|parameter| parameter.get("in").and_then(Value::as_str) == Some("path"))
+                    .filter_map(|parameter| {
+                        let name = parameter.get("name")?.as_str()?.to_string();
+                        // Swagger 2.0 has the type in the parameter itself.
+                        let schema = parameter.get("schema").unwrap_or(parameter);
+                        Some((name, schema))
+                    })
+                    .collect();
+
+                return Ok(Operation {
+                    method: method.to_uppercase(),
+                    path: path.clone(),
+                    body_schema,
+                    content_type,
+                    path_parameters,
+                });
+            }
+        }
+
+        Err(anyhow!("Operation not found: {}", operation_id))
+    }
+
+    /// First server URL of the spec (`servers` in OpenAPI 3, `host`/`basePath` in Swagger 2.0).
+    pub fn server_url(&self) -> Option<String> {
+        if let Some(url) = self
+            .document
+            .pointer("/servers/0/url")
+            .and_then(Value::as_str)
+        {
+            return Some(url.to_string());
+        }
+
+        let host = self.document.get("host")?.as_str()?;
+        let scheme = self
+            .document
+            .pointer("/schemes/0")
+            .and_then(Value::as_str)
+            .unwrap_or("https");
+        let base_path = self
+            .document
+            .get("basePath")
+            .and_then(Value::as_str)
+            .unwrap_or("");
+
+        Some(format!("{}://{}{}", scheme, host, base_path))
+    }
+
+    /// Follows `$ref`s until a schema without one is found.
+    pub fn resolve<'a>(&'a self, schema: &'a Value) -> Result<&'a Value> {
+        let mut current = schema;
+
+        // A limit, so a reference cycle (A -> B -> A) doesn't hang the tool.
+        for _ in 0..32 {
+            let Some(reference) = current.get("$ref").and_then(Value::as_str) else {
+                return Ok(current);
+            };
+
+            let pointer = reference.strip_prefix('#').ok_or_else(|| {
+                anyhow!(
+                    "Unsupported reference: {}. Only local references (#/...) are supported.",
+                    reference
+                )
+            })?;
+
+            current = self
+                .document
+                .pointer(pointer)
+                .ok_or_else(|| anyhow!("Reference not found: {}", reference))?;
+        }
+
+        Err(anyhow!("Reference cycle found while resolving a schema"))
+    }
+
+    /// Path-level parameters, overridden by the operation's parameters with the same name and location.
+    fn parameters<'a>(
+        &'a self,
+        path_item: &'a Value,
+        operation: &'a Value,
+    ) -> Result<Vec<&'a Value>> {
+        let mut parameters: Vec<&Value> = Vec::new();
+
+        for source in [path_item, operation] {
+            let Some(list) = source.get("parameters").and_then(Value::as_array) else {
+                continue;
+            };
+
+            for parameter in list {
+                let parameter = self.resolve(parameter)?;
+                parameters.retain(|existing| {
+                    existing.get("name") != parameter.get("name")
+                        || existing.get("in") != parameter.get("in")
+                });
+                parameters.push(parameter);
+            }
+        }
+
+        Ok(parameters)
+    }
+
+    /// JSON bodies are preferred when the operation accepts more than one content type.
+    fn body_schema<'a>(
+        &'a self,
+        operation: &'a Value,
+        parameters: &[&'a Value],
+    ) -> Result<(String, Option<&'a Value>)> {
+        let Some(request_body) = operation.get("requestBody") else {
+            // Swagger 2.0 bodies are parameters.
+            let schema = parameters
+                .iter()
+                .copied()
+                .find(|parameter| parameter.get("in").and_then(Value::as_str) == Some("body"))
+                .and_then(|parameter| parameter.get("schema"));
+            return Ok(("application/json".to_string(), schema));
+        };
+
+        let content = self
+            .resolve(request_body)?
+            .get("content")
+            .and_then(Value::as_object);
+
+        let Some(content) = content else {
+            return Ok(("application/json".to_string(), None));
+        };
+
+        let media = content
+            .iter()
+            .find(|(content_type, _)| content_type.contains("json"))
+            .or_else(|| content.iter().next());
+
+        Ok(match media {
+            Some((content_type, media)) => (content_type.clone(), media.get("schema")),
+            None => ("application/json".to_string(), None),
+        })
+    }
+}
+
+fn escape_pointer(value: &str) -> String {
+    value.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+servers:
+  - url: https://api.example.com/v1
+paths:
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema:
+          type: integer
+    put:
+      operationId: updatePet
+      requestBody:
+        content:
+          application/xml:
+            schema:
+              type: string
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Pet'
+components:
+  schemas:
+    Pet:
+      $ref: '#/components/schemas/Animal'
+    Animal:
+      type: object
+"#;
+
+    #[test]
+    fn test_parse_yaml_and_find_operation() {
+        let spec = OpenApiSpec::parse(SPEC).unwrap();
+        let operation = spec.operation("updatePet").unwrap();
+
+        assert_eq!(operation.method, "PUT");
+        assert_eq!(operation.path, "/pets/{petId}");
+        assert_eq!(operation.content_type, "application/json");
+        assert_eq!(operation.path_parameters.len(), 1);
+        assert_eq!(operation.path_parameters[0].0, "petId");
+
+        let body = spec.resolve(operation.body_schema.unwrap()).unwrap();
+        assert_eq!(body.get("type").and_then(Value::as_str), Some("object"));
+        assert_eq!(
+            spec.server_url().as_deref(),
+            Some("https://api.example.com/v1")
+        );
+    }
+
+    #[test]
+    fn test_missing_schema_lists_available_ones() {
+        let spec = OpenApiSpec::parse(SPEC).unwrap();
+        let error = spec.schema("Dog").unwrap_err().to_string();
+
+        assert!(
+            error.contains("Pet, Animal") || error.contains("Animal, Pet"),
+            "{}",
+            error
+        );
+        assert!(spec.operation("deletePet").is_err());
+    }
+
+    #[test]
+    fn test_rejects_documents_that_are_not_specs() {
+        assert!(OpenApiSpec::parse(r#"{"name": "not a spec"}"#).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+pub mod rhai_plugin;
+pub mod wasm_plugin;
+
+use crate::plugins::rhai_plugin::RhaiPlugin;
+use crate::plugins::wasm_plugin::WasmPlugin;
+use crate::registry::{Generator, GeneratorKind, GeneratorRegistry, PLUGINS};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+/// A Rhai script or WASM module with generator functions.
+pub trait Plugin {
+    /// Calls the generator function `function` of the plugin.
+    ///
+    /// # Errors
+    /// Returns error if the function fails or doesn't return text.
+    fn call(&self, function: &str) -> Result<String>;
+}
+
+/// Registers the generators of a plugin file (`.rhai` or `.wasm`), or of every plugin file in a
+/// folder. Each generator is named `<file name>.<function>` (e.g.: `acme.order-id`, for the
+/// function `order_id` of `acme.rhai`).
+///
+/// In a folder, files that fail to load are skipped, so one broken plugin doesn't take the
+/// others down. Their errors are returned as warnings.
+///
+/// # Errors
+/// Returns error if the path doesn't exist, or if the plugin file fails to load.
+pub fn load_plugins(registry: &mut GeneratorRegistry, path: &Path) -> Result<Vec<String>> {
+    if path.is_file() {
+        load_plugin_file(registry, path)?;
+        return Ok(Vec::new());
+    }
+
+    if !path.is_dir() {
+        bail!("Plugin file or folder not found: {}", path.display());
+    }
+
+    let mut files: Vec<_> = fs::read_dir(path)
+        .context(format!(
+            "Unable to read the plugin folder: {}",
+            path.display()
+        ))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| is_plugin_file(file))
+        .collect();
+    files.sort();
+
+    let warnings = files
+        .iter()
+        .filter_map(|file| {
+            load_plugin_file(registry, file)
+                .err()
+                .map(|e| format!("Skipped plugin {}: {:#}", file.display(), e))
+        })
+        .collect();
+
+    Ok(warnings)
+}
+
+fn is_plugin_file(path: &Path) -> bool {
+    path.is_file()
+        && matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("rhai" | "wasm")
+        )
+}
+
+fn load_plugin_file(registry: &mut GeneratorRegistry, path: &Path) -> Result<()> {
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        bail!("Invalid plugin file name: {}", path.display());
+    };
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    let (plugin, functions): (Rc<dyn Plugin>, Vec<(String, String)>) =
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("rhai") => {
+                let plugin = RhaiPlugin::load(path)?;
+                let functions = plugin
+                    .functions()
+                    .into_iter()
+                    .map(|(function, doc)| {
+                        let description =
+                            doc.unwrap_or_else(|| format!("Rhai plugin ({})", file_name));
+                        (function, description)
+                    })
+                    .collect();
+                (Rc::new(plugin), functions)
+            }
+            Some("wasm") => {
+                let plugin = WasmPlugin::load(path)?;
+                let functions = plugin
+                    .functions()
+                    .iter()
+                    .map(|function| (function.clone(), format!("WASM plugin ({})", file_name)))
+                    .collect();
+                (Rc::new(plugin), functions)
+            }
+            _ => bail!("Plugins must be .rhai or .wasm files: {}", path.display()),
+        };
+
+    if functions.is_empty() {
+        bail!("No generator functions found in {}", path.display());
+    }
+
+    let generators: Vec<Generator> = functions
+        .into_iter()
+        .map(|(function, description)| Generator {
+            name: plugin_generator_name(stem, &function),
+            category: PLUGINS.to_string(),
+            description,
+            kind: GeneratorKind::Plugin {
+                plugin: plugin.clone(),
+                function,
+            },
+        })
+        .collect();
+
+    // All or nothing: a plugin with a clashing name is not registered in part.
+    if let Some(clash) = generators.iter().find(|g| registry.get(&g.name).is_some()) {
+        bail!("There is already a generator named {}", clash.name);
+    }
+
+    for generator in generators {
+        registry.register(generator)?;
+    }
+
+    Ok(())
+}
+
+/// `acme` and `order_id` become `acme.order-id`, like the built-in generators.
+fn plugin_generator_name(stem: &str, function: &str) -> String {
+    format!("{}.{}", stem.to_lowercase(), function.replace('_', "-"))
+}
@@ -0,0 +1,183 @@
+use crate::models::MockOptions;
+use crate::plugins::Plugin;
+use crate::registry::builtin_generator;
+use anyhow::{anyhow, Context, Result};
+use rand::Rng;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, FnAccess, Scope, AST};
+use std::fs;
+use std::path::Path;
+
+/// Operations a single call can run, so a script stuck in a loop fails instead of hanging.
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+/// A Rhai script whose public functions without parameters are generators.
+///
+/// Besides the Rhai standard library, scripts can call:
+/// - `mock(name)`: a value of a built-in generator, with the default options.
+/// - `rand_int(min, max)`, `rand_float(min, max)`: a number between `min` and `max` (inclusive).
+/// - `rand_bool()`: `true` or `false`.
+/// - `pick(array)`: a random item of the array.
+///
+/// Strings are used as they are. Arrays and object maps are converted to JSON.
+pub struct RhaiPlugin {
+    engine: Engine,
+    ast: AST,
+}
+
+impl RhaiPlugin {
+    /// # Errors
+    /// Returns error if the file can't be read or has syntax errors.
+    pub fn load(path: &Path) -> Result<Self> {
+        let script = fs::read_to_string(path)
+            .context(format!("Unable to read the script: {}", path.display()))?;
+
+        Self::compile(&script)
+    }
+
+    /// # Errors
+    /// Returns error if the script has syntax errors.
+    pub fn compile(script: &str) -> Result<Self> {
+        let engine = create_engine();
+        let ast = engine
+            .compile(script)
+            .map_err(|e| anyhow!("Invalid script: {}", e))?;
+
+        Ok(Self { engine, ast })
+    }
+
+    /// Public functions without parameters, with their doc comments (`///`), by name.
+    pub fn functions(&self) -> Vec<(String, Option<String>)> {
+        let mut functions: Vec<(String, Option<String>)> = self
+            .ast
+            .iter_functions()
+            .filter(|function| function.access == FnAccess::Public && function.params.is_empty())
+            .map(|function| {
+                let doc = function
+                    .comments
+                    .iter()
+                    .map(|line| line.trim_start_matches('/').trim())
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<&str>>()
+                    .join(" ");
+
+                (
+                    function.name.to_string(),
+                    Some(doc).filter(|d| !d.is_empty()),
+                )
+            })
+            .collect();
+
+        functions.sort();
+        functions
+    }
+}
+
+impl Plugin for RhaiPlugin {
+    fn call(&self, function: &str) -> Result<String> {
+        let value: Dynamic = self
+            .engine
+            .call_fn(&mut Scope::new(), &self.ast, function, ())
+            .map_err(|e| anyhow!("{}() failed: {}", function, e))?;
+
+        if value.is_array() || value.is_map() {
+            let json: serde_json::Value = rhai::serde::from_dynamic(&value)
+                .map_err(|e| anyhow!("{}() returned a value that isn't JSON: {}", function, e))?;
+            return Ok(json.to_string());
+        }
+
+        Ok(value.to_string())
+    }
+}
+
+fn create_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+
+    engine.register_fn("mock", |name: &str| -> Result<String, Box<EvalAltResult>> {
+        let generator =
+            builtin_generator(name).ok_or_else(|| format!("Unknown data type: {}", name))?;
+        generator(&MockOptions::default()).map_err(|e| e.to_string().into())
+    });
+
+    engine.register_fn(
+        "rand_int",
+        |min: i64, max: i64| -> Result<i64, Box<EvalAltResult>> {
+            if min > max {
+                return Err(format!("rand_int: {} is greater than {}", min, max).into());
+            }
+            Ok(rand::rng().random_range(min..=max))
+        },
+    );
+
+    engine.register_fn(
+        "rand_float",
+        |min: f64, max: f64| -> Result<f64, Box<EvalAltResult>> {
+            if min > max {
+                return Err(format!("rand_float: {} is greater than {}", min, max).into());
+            }
+            Ok(rand::rng().random_range(min..=max))
+        },
+    );
+
+    engine.register_fn("rand_bool", || rand::rng().random_bool(0.5));
+
+    engine.register_fn("pick", |items: Array| -> Dynamic {
+        if items.is_empty() {
+            return Dynamic::UNIT;
+        }
+        items[rand::rng().random_range(0..items.len())].clone()
+    });
+
+    engine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"
+        /// An order code, like ORD-4242
+        fn order_code() {
+            "ORD-" + rand_int(1000, 9999)
+        }
+
+        fn customer() {
+            #{ name: mock("person.first-name"), vip: rand_bool(), tier: pick(["gold", "silver"]) }
+        }
+
+        fn with_parameter(x) { x }
+
+        private fn helper() { 1 }
+
+        fn forever() { loop {} }
+    "#;
+
+    #[test]
+    fn test_script_functions_are_generators() {
+        let plugin = RhaiPlugin::compile(SCRIPT).unwrap();
+
+        assert_eq!(
+            plugin.functions(),
+            vec![
+                ("customer".to_string(), None),
+                ("forever".to_string(), None),
+                (
+                    "order_code".to_string(),
+                    Some("An order code, like ORD-4242".to_string())
+                ),
+            ]
+        );
+
+        let code = plugin.call("order_code").unwrap();
+        assert!(code.starts_with("ORD-") && code.len() == 8, "{}", code);
+
+        let customer: serde_json::Value =
+            serde_json::from_str(&plugin.call("customer").unwrap()).unwrap();
+        assert!(!customer["name"].as_str().unwrap().is_empty());
+        assert!(customer["vip"].is_boolean());
+        assert!(["gold", "silver"].contains(&customer["tier"].as_str().unwrap()));
+
+        assert!(plugin.call("forever").is_err());
+        assert!(RhaiPlugin::compile("fn broken( {").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::plugins::Plugin;
+use anyhow::{anyhow, bail, Context, Result};
+use rand::Rng;
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use wasmi::core::ValType;
+use wasmi::{Engine, ExternType, Instance, Linker, Module, Store};
+
+/// Module of the functions the host offers to plugins.
+const HOST_MODULE: &str = "mock";
+
+/// A WASM module whose exported `() -> i64` functions are generators.
+///
+/// Each function writes its value (UTF-8) to the exported `memory` and returns where it is,
+/// packed in an `i64`: the pointer in the high 32 bits and the length in the low 32 bits.
+///
+/// Modules can import, from `mock`:
+/// - `random_range(min: i64, max: i64) -> i64`: a number between `min` and `max` (inclusive).
+/// - `random() -> f64`: a number between 0 and 1.
+pub struct WasmPlugin {
+    store: RefCell<Store<()>>,
+    instance: Instance,
+    functions: Vec<String>,
+}
+
+impl WasmPlugin {
+    /// # Errors
+    /// Returns error if the file can't be read or isn't a valid module.
+    pub fn load(path: &Path) -> Result<Self> {
+        let wasm =
+            fs::read(path).context(format!("Unable to read the module: {}", path.display()))?;
+
+        Self::from_bytes(&wasm)
+    }
+
+    /// # Errors
+    /// Returns error if the module is invalid or imports something the host doesn't offer.
+    pub fn from_bytes(wasm: &[u8]) -> Result<Self> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, wasm).map_err(|e| anyhow!("Invalid module: {}", e))?;
+
+        let mut functions: Vec<String> = module
+            .exports()
+            .filter(|export| match export.ty() {
+                ExternType::Func(func) => {
+                    func.params().is_empty() && func.results() == [ValType::I64]
+                }
+                _ => false,
+            })
+            .map(|export| export.name().to_string())
+            .collect();
+        functions.sort();
+
+        let mut store = Store::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker
+            .func_wrap(HOST_MODULE, "random_range", |min: i64, max: i64| -> i64 {
+                if min >= max {
+                    return min;
+                }
+                rand::rng().random_range(min..=max)
+            })
+            .map_err(|e| anyhow!("{}", e))?;
+        linker
+            .func_wrap(HOST_MODULE, "random", || -> f64 { rand::rng().random() })
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .and_then(|instance| instance.start(&mut store))
+            .map_err(|e| anyhow!("Unable to instantiate the module: {}", e))?;
+
+        Ok(Self {
+            store: RefCell::new(store),
+            instance,
+            functions,
+        })
+    }
+
+    /// Exported functions that take no parameters and return an `i64`, by name.
+    pub fn functions(&self) -> &[String] {
+        &self.functions
+    }
+}
+
+impl Plugin for WasmPlugin {
+    fn call(&self, function: &str) -> Result<String> {
+        let mut store = self.store.borrow_mut();
+
+        let packed = self
+            .instance
+            .get_typed_func::<(), i64>(&*store, function)
+            .map_err(|e| anyhow!("{}() not found: {}", function, e))?
+            .call(&mut *store, ())
+            .map_err(|e| anyhow!("{}() failed: {}", function, e))?;
+
+        let memory = self
+            .instance
+            .get_memory(&*store, "memory")
+            .context("The module doesn't export its memory")?;
+
+        let pointer = (packed as u64 >> 32) as usize;
+        let length = (packed as u64 & 0xFFFF_FFFF) as usize;
+        let Some(bytes) = memory.data(&*store).get(pointer..pointer + length) else {
+            bail!(
+                "{}() returned a value outside the memory ({} bytes at {})",
+                function,
+                length,
+                pointer
+            );
+        };
+
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| anyhow!("{}() returned a value that isn't UTF-8", function))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODULE: &str = r#"
+        (module
+          (import "mock" "random_range" (func $random_range (param i64 i64) (result i64)))
+          (memory (export "memory") 1)
+          (data (i32.const 16) "ACME-1")
+          (data (i32.const 32) "heads")
+          (data (i32.const 48) "tails")
+          ;; Values are (pointer << 32) | length: "ACME-1" is (16 << 32) | 6.
+          (func (export "sku") (result i64) (i64.const 68719476742))
+          (func (export "coin_flip") (result i64)
+            (select
+              (i64.const 137438953477)
+              (i64.const 206158430213)
+              (i64.eqz (call $random_range (i64.const 0) (i64.const 1)))))
+          (func (export "out_of_bounds") (result i64) (i64.const 281474976710666))
+          (func (export "not_a_generator") (param i32) (result i32) (local.get 0)))
+    "#;
+
+    #[test]
+    fn test_module_functions_are_generators() {
+        let plugin = WasmPlugin::from_bytes(&wat::parse_str(MODULE).unwrap()).unwrap();
+
+        assert_eq!(
+            plugin.functions(),
+            vec!["coin_flip", "out_of_bounds", "sku"]
+        );
+        assert_eq!(plugin.call("sku").unwrap(), "ACME-1");
+        for _ in 0..10 {
+            assert!(["heads", "tails"].contains(&plugin.call("coin_flip").unwrap().as_str()));
+        }
+
+        assert!(plugin.call("out_of_bounds").is_err());
+        assert!(plugin.call("missing").is_err());
+        assert!(WasmPlugin::from_bytes(b"not wasm").is_err());
+    }
+}
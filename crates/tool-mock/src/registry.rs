@@ -0,0 +1,371 @@
+use crate::generators::*;
+use crate::models::MockOptions;
+use crate::plugins::Plugin;
+use anyhow::{bail, Result};
+use std::rc::Rc;
+
+/// Generates a value with the options informed in the command line.
+pub type GeneratorFn = fn(&MockOptions) -> Result<String>;
+
+/// Generates a value using other generators (e.g.: `x-mock` in OpenAPI schemas).
+pub type CompositeGeneratorFn = fn(&MockOptions, &GeneratorRegistry) -> Result<String>;
+
+/// Built-in generators: name, category, description, and function.
+const BUILT_INS: &[(&str, &str, &str, GeneratorFn)] = &[
+    // Personal Information
+    (
+        "person.first-name",
+        PERSONAL,
+        "Generate a random first name",
+        generate_first_name,
+    ),
+    (
+        "person.last-name",
+        PERSONAL,
+        "Generate a random last name",
+        generate_last_name,
+    ),
+    (
+        "person.full-name",
+        PERSONAL,
+        "Generate a full name (first + last)",
+        generate_full_name,
+    ),
+    (
+        "person.email",
+        PERSONAL,
+        "Generate a random email address",
+        generate_email,
+    ),
+    (
+        "person.phone",
+        PERSONAL,
+        "Generate a phone number",
+        generate_phone,
+    ),
+    (
+        "person.street",
+        PERSONAL,
+        "Generate a street name",
+        generate_street,
+    ),
+    (
+        "person.city",
+        PERSONAL,
+        "Generate a city name",
+        generate_city,
+    ),
+    (
+        "person.state",
+        PERSONAL,
+        "Generate a state name",
+        generate_state,
+    ),
+    (
+        "person.country",
+        PERSONAL,
+        "Generate a country name",
+        generate_country,
+    ),
+    (
+        "person.postal-code",
+        PERSONAL,
+        "Generate postal/zip code",
+        generate_postal_code,
+    ),
+    (
+        "person.address",
+        PERSONAL,
+        "Generate a full address",
+        generate_address,
+    ),
+    (
+        "person.birthday",
+        PERSONAL,
+        "Generate a birthday (with optional --age parameter)",
+        generate_birthday,
+    ),
+    // Internet & Tech
+    (
+        "internet.username",
+        INTERNET,
+        "Generate a username",
+        generate_username,
+    ),
+    (
+        "internet.password",
+        INTERNET,
+        "Generate a password (with --length option)",
+        generate_password,
+    ),
+    ("internet.url", INTERNET, "Generate a URL", generate_url),
+    (
+        "internet.image-url",
+        INTERNET,
+        "Generate an image URL",
+        generate_image_url,
+    ),
+    (
+        "internet.file-url",
+        INTERNET,
+        "Generate a file URL",
+        generate_file_url,
+    ),
+    // Random Data
+    (
+        "random.date",
+        RANDOM,
+        "Generate a date (with --past, --future, or --range options)",
+        generate_date,
+    ),
+    (
+        "random.time",
+        RANDOM,
+        "Generate a time (with --past, --future options)",
+        generate_time,
+    ),
+    (
+        "random.datetime",
+        RANDOM,
+        "Generate a datetime (with --past, --future options)",
+        generate_datetime,
+    ),
+    (
+        "random.timestamp",
+        RANDOM,
+        "Generate a timestamp (with --past, --future options)",
+        generate_timestamp,
+    ),
+    (
+        "random.color-hex",
+        RANDOM,
+        "Generate a hex color code",
+        generate_color_hex,
+    ),
+    (
+        "random.color-rgb",
+        RANDOM,
+        "Generate RGB color values",
+        generate_color_rgb,
+    ),
+    (
+        "random.integer",
+        RANDOM,
+        "Generate an integer (with --min, --max options)",
+        generate_integer,
+    ),
+    (
+        "random.float",
+        RANDOM,
+        "Generate a float (with --min, --max, --precision options)",
+        generate_float,
+    ),
+    (
+        "random.car-brand",
+        RANDOM,
+        "Generate a car brand name",
+        generate_car_brand,
+    ),
+    // Commerce
+    (
+        "commerce.company",
+        COMMERCE,
+        "Generate a company name",
+        generate_company,
+    ),
+    (
+        "commerce.product",
+        COMMERCE,
+        "Generate a product name",
+        generate_product,
+    ),
+    (
+        "commerce.product-description",
+        COMMERCE,
+        "Generate a product description",
+        generate_product_description,
+    ),
+    (
+        "commerce.job-title",
+        COMMERCE,
+        "Generate a job title",
+        generate_job_title,
+    ),
+    (
+        "commerce.industry",
+        COMMERCE,
+        "Generate an industry name",
+        generate_industry,
+    ),
+    (
+        "commerce.buzzword",
+        COMMERCE,
+        "Generate a business buzzword",
+        generate_buzzword,
+    ),
+];
+
+const PERSONAL: &str = "Personal Information";
+const INTERNET: &str = "Internet & Tech";
+const RANDOM: &str = "Random Data";
+const COMMERCE: &str = "Commerce";
+const OPENAPI: &str = "OpenAPI";
+pub const PLUGINS: &str = "Plugins";
+
+/// How a generator produces its values.
+pub enum GeneratorKind {
+    BuiltIn(GeneratorFn),
+    Composite(CompositeGeneratorFn),
+    /// A function of a Rhai script or WASM module.
+    Plugin {
+        plugin: Rc<dyn Plugin>,
+        function: String,
+    },
+}
+
+pub struct Generator {
+    /// Name used in the command line and in `x-mock` (e.g.: `person.first-name`).
+    pub name: String,
+    pub category: String,
+    pub description: String,
+    pub kind: GeneratorKind,
+}
+
+/// The generators available, by name: the built-in ones and the ones loaded from plugins.
+pub struct GeneratorRegistry {
+    generators: Vec<Generator>,
+}
+
+impl GeneratorRegistry {
+    /// A registry with the built-in generators.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self {
+            generators: Vec::new(),
+        };
+
+        for (name, category, description, function) in BUILT_INS {
+            registry.generators.push(Generator {
+                name: name.to_string(),
+                category: category.to_string(),
+                description: description.to_string(),
+                kind: GeneratorKind::BuiltIn(*function),
+            });
+        }
+
+        registry.generators.push(Generator {
+            name: "openapi.payload".to_string(),
+            category: OPENAPI.to_string(),
+            description:
+                "Generate a payload from an OpenAPI spec (with --spec, and --schema or --operation)"
+                    .to_string(),
+            kind: GeneratorKind::Composite(generate_openapi_payload),
+        });
+
+        registry
+    }
+
+    /// Adds a generator.
+    ///
+    /// # Errors
+    /// Returns error if there is already a generator with the same name.
+    pub fn register(&mut self, generator: Generator) -> Result<()> {
+        if self.get(&generator.name).is_some() {
+            bail!("There is already a generator named {}", generator.name);
+        }
+
+        self.generators.push(generator);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Generator> {
+        self.generators
+            .iter()
+            .find(|generator| generator.name == name)
+    }
+
+    /// Generates a value with the generator named `name`.
+    ///
+    /// # Errors
+    /// Returns error if there is no such generator, or if it fails.
+    pub fn generate(&self, name: &str, options: &MockOptions) -> Result<String> {
+        let Some(generator) = self.get(name) else {
+            bail!("Unknown data type: {}", name);
+        };
+
+        match &generator.kind {
+            GeneratorKind::BuiltIn(function) => function(options),
+            GeneratorKind::Composite(function) => function(options, self),
+            GeneratorKind::Plugin { plugin, function } => plugin.call(function),
+        }
+    }
+
+    /// The generators, grouped by category, in the order they were registered.
+    pub fn help_text(&self) -> String {
+        let mut categories: Vec<&str> = Vec::new();
+        for generator in &self.generators {
+            if !categories.contains(&generator.category.as_str()) {
+                categories.push(&generator.category);
+            }
+        }
+
+        categories
+            .iter()
+            .map(|category| {
+                let lines: Vec<String> = self
+                    .generators
+                    .iter()
+                    .filter(|generator| generator.category == *category)
+                    .map(|generator| {
+                        format!("  {:<21} - {}", generator.name, generator.description)
+                    })
+                    .collect();
+
+                format!("{}:\n{}", category, lines.join("\n"))
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+}
+
+/// The function of a built-in generator, for plugins that build on them.
+pub fn builtin_generator(name: &str) -> Option<GeneratorFn> {
+    BUILT_INS
+        .iter()
+        .find(|(builtin, ..)| *builtin == name)
+        .map(|(.., function)| *function)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::rhai_plugin::RhaiPlugin;
+
+    #[test]
+    fn test_registry_generates_by_name() {
+        let mut registry = GeneratorRegistry::with_builtins();
+        let options = MockOptions {
+            min: Some(7),
+            max: Some(7),
+            ..Default::default()
+        };
+        assert_eq!(registry.generate("random.integer", &options).unwrap(), "7");
+        assert!(registry.generate("random.nope", &options).is_err());
+
+        let generator = || Generator {
+            name: "acme.order-id".to_string(),
+            category: PLUGINS.to_string(),
+            description: "An order id".to_string(),
+            kind: GeneratorKind::Plugin {
+                plugin: Rc::new(RhaiPlugin::compile(r#"fn order_id() { "A-" + 1 }"#).unwrap()),
+                function: "order_id".to_string(),
+            },
+        };
+        registry.register(generator()).unwrap();
+        assert!(registry.register(generator()).is_err());
+        assert_eq!(registry.generate("acme.order-id", &options).unwrap(), "A-1");
+
+        let help = registry.help_text();
+        assert!(help.starts_with("Personal Information:\n  person.first-name     - "));
+        assert!(help.ends_with("Plugins:\n  acme.order-id         - An order id"));
+    }
+}
@@ -1,9 +1,17 @@
-use crate::models::{MqttArgs, MqttCommand};
+use crate::models::{MqttArgs, MqttCommand, TlsArgs, V5Properties};
 use crate::string_traits::StringExt;
 use anyhow::Result;
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_PORT: u16 = 1883;
+const DEFAULT_TLS_PORT: u16 = 8883;
+const DEFAULT_REPLAY_SPEED: f64 = 1.0;
+const DEFAULT_STATS_TOPIC: &str = "#";
 
 pub fn print_runtime_info(args: &MqttArgs) {
     println!("MQTT v{}", env!("CARGO_PKG_VERSION"));
@@ -18,23 +26,88 @@ pub fn print_runtime_info(args: &MqttArgs) {
     };
 
     println!("- Connection type: {}", connection_type);
-    println!("- Topic: {}", args.topic);
+
+    if args.mqtt5 {
+        println!("- Protocol: MQTT 5");
+    }
+
+    if args.tls.enabled {
+        let tls_type = if args.tls.is_mutual() {
+            "Mutual TLS"
+        } else {
+            "TLS"
+        };
+        println!("- Security: {}", tls_type);
+
+        if let Some(ca_cert) = &args.tls.ca_cert {
+            println!("  - CA certificate: {}", ca_cert.display());
+        }
+
+        if let Some(client_cert) = &args.tls.client_cert {
+            println!("  - Client certificate: {}", client_cert.display());
+        }
+    }
+
+    if !args.topic.is_empty() {
+        println!("- Topic: {}", args.topic);
+    }
 
     match args.command {
         MqttCommand::Unknown => {}
         MqttCommand::Read => {
             println!("- Command: Read");
+            if let Some(persist) = &args.persist {
+                println!("  - Persist to: {} (persistent session)", persist.display());
+            }
         }
         MqttCommand::Post => {
             println!("- Command: Post");
             if let Some(msg) = &args.message {
                 println!("  - Message: {}", msg);
             }
+            print_properties(&args.properties);
+            if let Some(timeout) = args.wait_response {
+                println!("  - Wait for response: {}s", timeout.as_secs());
+            }
+        }
+        MqttCommand::Record => {
+            println!("- Command: Record");
+            if let Some(file) = &args.file {
+                println!("  - File: {}", file.display());
+            }
+        }
+        MqttCommand::Replay => {
+            println!("- Command: Replay");
+            if let Some(file) = &args.file {
+                println!("  - File: {}", file.display());
+            }
+            println!("  - Speed: {}x", args.speed);
+        }
+        MqttCommand::Stats => {
+            println!("- Command: Stats");
         }
     }
 }
 
-pub fn get_cli_arguments() -> Result<MqttArgs> {
+fn print_properties(properties: &V5Properties) {
+    if let Some(content_type) = &properties.content_type {
+        println!("  - Content type: {}", content_type);
+    }
+
+    if let Some(response_topic) = &properties.response_topic {
+        println!("  - Response topic: {}", response_topic);
+    }
+
+    if let Some(correlation_data) = &properties.correlation_data {
+        println!("  - Correlation data: {}", correlation_data);
+    }
+
+    for (key, value) in &properties.user_properties {
+        println!("  - User property: {}={}", key, value);
+    }
+}
+
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<MqttArgs> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -43,7 +116,7 @@ pub fn get_cli_arguments() -> Result<MqttArgs> {
         )
         .arg(
             Arg::new("command")
-                .help("Command to execute.")
+                .help("Command to execute: read, post, record, replay, or stats.")
                 .num_args(1)
                 .required(false),
         )
@@ -52,22 +125,21 @@ pub fn get_cli_arguments() -> Result<MqttArgs> {
                 .long("host")
                 .short('o')
                 .required(true)
-                .help("Host to connect to."),
+                .help("Host to connect to. Use mqtts://host[:port] for TLS connections."),
         )
         .arg(
             Arg::new("port")
                 .long("port")
                 .short('p')
                 .required(false)
-                .default_value("1883")
-                .help("Port to connect to. (Default: 1883)"),
+                .help("Port to connect to. (Default: 1883, or 8883 with TLS)"),
         )
         .arg(
             Arg::new("topic")
                 .long("topic")
                 .short('t')
-                .required(true)
-                .help("Topic to post to or read from."),
+                .required(false)
+                .help("Topic to post to, read from, or record. Not used by replay (messages go to their recorded topics). Stats uses # when not informed."),
         )
         .arg(
             Arg::new("message")
@@ -90,7 +162,100 @@ pub fn get_cli_arguments() -> Result<MqttArgs> {
                 .required(false)
                 .help("Password for authentication."),
         )
-        .get_matches();
+        .arg(
+            Arg::new("tls")
+                .long("tls")
+                .action(ArgAction::SetTrue)
+                .help("Connects using TLS. (Implied by a mqtts:// host)"),
+        )
+        .arg(
+            Arg::new("ca-cert")
+                .long("ca-cert")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help(
+                    "PEM CA certificate used to verify the broker. (Default: system trust store)",
+                ),
+        )
+        .arg(
+            Arg::new("client-cert")
+                .long("client-cert")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("PEM client certificate, for mutual TLS. Requires --client-key."),
+        )
+        .arg(
+            Arg::new("client-key")
+                .long("client-key")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("PEM client private key (PKCS#8), for mutual TLS. Requires --client-cert."),
+        )
+        .arg(
+            Arg::new("file")
+                .long("file")
+                .short('f')
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Recording (JSONL) to append to (record) or to republish (replay)."),
+        )
+        .arg(
+            Arg::new("persist")
+                .long("persist")
+                .value_name("DB")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Read only: saves the messages to this database (same format as eh-read, exportable with eh-export --source-db), using a persistent session so messages sent while offline are collected on the next run."),
+        )
+        .arg(
+            Arg::new("speed")
+                .long("speed")
+                .value_name("FACTOR")
+                .value_parser(clap::value_parser!(f64))
+                .help("Replay speed: 1 keeps the original timing, 10 replays ten times faster. (Default: 1)"),
+        )
+        .arg(
+            Arg::new("mqtt5")
+                .long("mqtt5")
+                .action(ArgAction::SetTrue)
+                .help("Connects using MQTT 5. (Implied by the MQTT 5 properties below)"),
+        )
+        .arg(
+            Arg::new("user-property")
+                .long("user-property")
+                .value_name("KEY=VALUE")
+                .action(ArgAction::Append)
+                .value_parser(clap::builder::ValueParser::new(parse_user_property))
+                .help("MQTT 5 user property to post with the message. Can be used multiple times."),
+        )
+        .arg(
+            Arg::new("content-type")
+                .long("content-type")
+                .value_name("TYPE")
+                .help("MQTT 5 content type of the message (e.g.: application/json)."),
+        )
+        .arg(
+            Arg::new("response-topic")
+                .long("response-topic")
+                .value_name("TOPIC")
+                .help("MQTT 5 response topic, where the receiver should reply."),
+        )
+        .arg(
+            Arg::new("correlation-data")
+                .long("correlation-data")
+                .value_name("DATA")
+                .help("MQTT 5 correlation data, sent back by the receiver to match the reply to the request."),
+        )
+        .arg(
+            Arg::new("wait-response")
+                .long("wait-response")
+                .value_name("SECONDS")
+                .num_args(0..=1)
+                .default_missing_value("30")
+                .requires("response-topic")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("After posting, waits for the reply in the response topic. (Default timeout: 30 seconds)"),
+        )
+        .get_matches_from(argv);
 
     let command = match matches.get_one::<String>("command") {
         None => {
@@ -100,28 +265,103 @@ pub fn get_cli_arguments() -> Result<MqttArgs> {
         Some(cmd) => cmd.to_mqtt_command(),
     };
 
-    let host = matches.get_one::<String>("host").unwrap();
-    let port = matches.get_one::<String>("port").unwrap().parse::<u16>()?;
+    let (host, url_port, secure_scheme) =
+        parse_broker_address(matches.get_one::<String>("host").unwrap())?;
+    let use_tls = secure_scheme || matches.get_flag("tls");
+
+    let port = match matches.get_one::<String>("port") {
+        Some(port) => port.parse::<u16>()?,
+        None => url_port.unwrap_or(if use_tls {
+            DEFAULT_TLS_PORT
+        } else {
+            DEFAULT_PORT
+        }),
+    };
     let topic = matches
         .get_one::<String>("topic")
-        .unwrap()
-        .trim()
-        .to_string();
+        .map(|topic| topic.trim().to_string())
+        .unwrap_or_default();
+    let topic = if topic.is_empty() && matches!(command, MqttCommand::Stats) {
+        DEFAULT_STATS_TOPIC.to_string()
+    } else {
+        topic
+    };
     let message = matches.get_one::<String>("message");
     let username = matches.get_one::<String>("username");
     let password = matches.get_one::<String>("password");
 
+    let properties = V5Properties {
+        content_type: matches.get_one::<String>("content-type").cloned(),
+        response_topic: matches.get_one::<String>("response-topic").cloned(),
+        correlation_data: matches.get_one::<String>("correlation-data").cloned(),
+        user_properties: matches
+            .get_many::<(String, String)>("user-property")
+            .unwrap_or_default()
+            .cloned()
+            .collect(),
+    };
+
     Ok(MqttArgs {
         command,
-        host: host.clone(),
+        host,
         port,
         topic: topic.clone(),
         message: message.cloned(),
         username: username.cloned(),
         password: password.cloned(),
+        tls: TlsArgs {
+            enabled: use_tls,
+            ca_cert: matches.get_one::<PathBuf>("ca-cert").cloned(),
+            client_cert: matches.get_one::<PathBuf>("client-cert").cloned(),
+            client_key: matches.get_one::<PathBuf>("client-key").cloned(),
+        },
+        file: matches.get_one::<PathBuf>("file").cloned(),
+        speed: matches
+            .get_one::<f64>("speed")
+            .copied()
+            .unwrap_or(DEFAULT_REPLAY_SPEED),
+        mqtt5: matches.get_flag("mqtt5") || !properties.is_empty(),
+        properties,
+        wait_response: matches
+            .get_one::<u64>("wait-response")
+            .map(|secs| Duration::from_secs(*secs)),
+        persist: matches.get_one::<PathBuf>("persist").cloned(),
     })
 }
 
+/// Splits `[scheme://]host[:port]` into host, port, and whether the scheme requires TLS.
+///
+/// Accepted schemes are `mqtt://` and `tcp://` (plain), and `mqtts://` and `ssl://` (TLS).
+fn parse_broker_address(value: &str) -> Result<(String, Option<u16>, bool)> {
+    let value = value.trim();
+
+    let (address, secure) = match value.split_once("://") {
+        None => (value, false),
+        Some((scheme, address)) => match scheme.to_lowercase().as_str() {
+            "mqtt" | "tcp" => (address, false),
+            "mqtts" | "ssl" => (address, true),
+            _ => anyhow::bail!("Unsupported scheme: {}. Use mqtt:// or mqtts://.", scheme),
+        },
+    };
+
+    let address = address.trim_end_matches('/');
+
+    // Bracketed IPv6 addresses (e.g.: [::1]:8883) keep their colons.
+    let (host, port) = match address.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && (!host.contains(':') || host.ends_with(']')) => {
+            let port = port
+                .parse::<u16>()
+                .map_err(|_| anyhow::anyhow!("Invalid port in host: {}", value))?;
+            (host, Some(port))
+        }
+        _ => (address, None),
+    };
+
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    Ok((host.to_string(), port, secure))
+}
+
 pub fn validate_args(args: &MqttArgs) -> Result<()> {
     match args.command {
         MqttCommand::Unknown => {
@@ -133,11 +373,46 @@ pub fn validate_args(args: &MqttArgs) -> Result<()> {
                 anyhow::bail!("Message is required for post command.");
             }
         }
+        MqttCommand::Record => {
+            if args.file.is_none() {
+                anyhow::bail!("File is required for record command.");
+            }
+        }
+        MqttCommand::Replay => {
+            let Some(file) = &args.file else {
+                anyhow::bail!("File is required for replay command.");
+            };
+
+            validate_file_exists("--file", file)?;
+            validate_speed(args.speed)?;
+        }
+        MqttCommand::Stats => {}
     }
 
     validate_host_and_port(&args.host, args.port)?;
     validate_user_and_password(&args.username, &args.password)?;
-    validate_topic(&args.topic)?;
+    if !matches!(args.command, MqttCommand::Replay) {
+        validate_topic(&args.topic)?;
+    }
+    validate_tls(&args.tls)?;
+    validate_mqtt5(args)?;
+    validate_persist(args)?;
+
+    Ok(())
+}
+
+fn validate_persist(args: &MqttArgs) -> Result<()> {
+    if args.persist.is_none() {
+        return Ok(());
+    }
+
+    if !matches!(args.command, MqttCommand::Read) {
+        anyhow::bail!("--persist can only be used with the read command.");
+    }
+
+    if args.mqtt5 {
+        anyhow::bail!("--persist is not supported with MQTT 5 yet.");
+    }
 
     Ok(())
 }
@@ -162,6 +437,43 @@ fn validate_topic(topic: &String) -> Result<()> {
     Ok(())
 }
 
+fn validate_speed(speed: f64) -> Result<()> {
+    if !speed.is_finite() || speed <= 0.0 {
+        anyhow::bail!("Speed must be greater than 0.");
+    }
+
+    Ok(())
+}
+
+fn validate_mqtt5(args: &MqttArgs) -> Result<()> {
+    if !args.mqtt5 {
+        return Ok(());
+    }
+
+    match args.command {
+        MqttCommand::Read if !args.properties.is_empty() => {
+            anyhow::bail!("MQTT 5 properties can only be set when posting.");
+        }
+        MqttCommand::Read | MqttCommand::Post => {}
+        _ => anyhow::bail!("MQTT 5 is only supported by the read and post commands."),
+    }
+
+    Ok(())
+}
+
+/// Parses `key=value`. The value can contain `=`; the key can't be empty.
+fn parse_user_property(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!(
+            "Invalid user property: '{}'. Expected KEY=VALUE.",
+            value
+        )),
+    }
+}
+
 fn validate_user_and_password(username: &Option<String>, password: &Option<String>) -> Result<()> {
     if username.is_none() && password.is_none() {
         return Ok(());
@@ -173,3 +485,120 @@ fn validate_user_and_password(username: &Option<String>, password: &Option<Strin
 
     anyhow::bail!("Username and password are required together.");
 }
+
+fn validate_tls(tls: &TlsArgs) -> Result<()> {
+    let files = [
+        ("--ca-cert", &tls.ca_cert),
+        ("--client-cert", &tls.client_cert),
+        ("--client-key", &tls.client_key),
+    ];
+
+    if !tls.enabled {
+        if let Some((name, _)) = files.iter().find(|(_, file)| file.is_some()) {
+            anyhow::bail!("{} requires TLS. Use a mqtts:// host or --tls.", name);
+        }
+
+        return Ok(());
+    }
+
+    if tls.client_cert.is_some() != tls.client_key.is_some() {
+        anyhow::bail!("Client certificate and key are required together.");
+    }
+
+    for (name, file) in files {
+        if let Some(path) = file {
+            validate_file_exists(name, path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_file_exists(name: &str, path: &Path) -> Result<()> {
+    if !path.is_file() {
+        anyhow::bail!("File informed in {} not found: {}", name, path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_broker_address() {
+        assert_eq!(
+            parse_broker_address("broker.hivemq.com").unwrap(),
+            ("broker.hivemq.com".to_string(), None, false)
+        );
+        assert_eq!(
+            parse_broker_address("mqtts://abc.iot.us-east-1.amazonaws.com:8883").unwrap(),
+            (
+                "abc.iot.us-east-1.amazonaws.com".to_string(),
+                Some(8883),
+                true
+            )
+        );
+        assert_eq!(
+            parse_broker_address("mqtt://localhost:1884/").unwrap(),
+            ("localhost".to_string(), Some(1884), false)
+        );
+        assert_eq!(
+            parse_broker_address("ssl://[::1]:8883").unwrap(),
+            ("::1".to_string(), Some(8883), true)
+        );
+        assert_eq!(
+            parse_broker_address("::1").unwrap(),
+            ("::1".to_string(), None, false)
+        );
+        assert!(parse_broker_address("http://localhost").is_err());
+        assert!(parse_broker_address("localhost:port").is_err());
+    }
+
+    #[test]
+    fn test_parse_user_property() {
+        assert_eq!(
+            parse_user_property("source=cli").unwrap(),
+            ("source".to_string(), "cli".to_string())
+        );
+        assert_eq!(
+            parse_user_property("filter=a=b").unwrap(),
+            ("filter".to_string(), "a=b".to_string())
+        );
+        assert_eq!(
+            parse_user_property("empty=").unwrap(),
+            ("empty".to_string(), String::new())
+        );
+        assert!(parse_user_property("=value").is_err());
+        assert!(parse_user_property("no-separator").is_err());
+    }
+
+    #[test]
+    fn test_validate_speed() {
+        assert!(validate_speed(1.0).is_ok());
+        assert!(validate_speed(0.5).is_ok());
+        assert!(validate_speed(0.0).is_err());
+        assert!(validate_speed(-2.0).is_err());
+        assert!(validate_speed(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_validate_tls() {
+        let tls = |enabled: bool, client_cert: Option<&str>, client_key: Option<&str>| TlsArgs {
+            enabled,
+            ca_cert: None,
+            client_cert: client_cert.map(PathBuf::from),
+            client_key: client_key.map(PathBuf::from),
+        };
+
+        assert!(validate_tls(&tls(true, None, None)).is_ok());
+        assert!(validate_tls(&tls(false, Some("cert.pem"), Some("key.pem"))).is_err());
+        assert!(validate_tls(&tls(true, Some("cert.pem"), None)).is_err());
+
+        let error = validate_tls(&tls(true, Some("missing.pem"), Some("missing.key")))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("--client-cert"));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info, validate_args};
+use crate::models::MqttCommand;
+use crate::mqtt5_app::{post_message_v5, read_messages_v5};
+use crate::mqtt_app::{
+    post_message, read_messages, record_messages, replay_messages, stats_messages,
+};
+use anyhow::Result;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+mod cli_utils;
+mod message_store;
+mod models;
+mod mqtt5_app;
+mod mqtt_app;
+mod recording;
+mod string_traits;
+mod topic_stats;
+
+/// MQTT client.
+///
+/// Parses and validates arguments, then runs the command: read, post, record, replay, or stats.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    match runtime.block_on(run_tool(argv)) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+async fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Info);
+
+    let args = get_cli_arguments(argv)?;
+    validate_args(&args)?;
+    print_runtime_info(&args);
+
+    match args.command {
+        MqttCommand::Unknown => {}
+        MqttCommand::Read if args.mqtt5 => {
+            read_messages_v5(&args).await?;
+        }
+        MqttCommand::Read => {
+            read_messages(&args).await?;
+        }
+        MqttCommand::Post if args.mqtt5 => {
+            post_message_v5(&args).await?;
+        }
+        MqttCommand::Post => {
+            post_message(&args).await?;
+        }
+        MqttCommand::Record => {
+            record_messages(&args).await?;
+        }
+        MqttCommand::Replay => {
+            replay_messages(&args).await?;
+        }
+        MqttCommand::Stats => {
+            stats_messages(&args).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use shared_eventhub::eventhub_models::{EventHubCheckpoint, InboundMessage, MessageStatus};
+use sled::Db;
+use std::path::Path;
+
+/// Partition id of the checkpoint. MQTT has no partitions, so there's a single sequence per store.
+const CHECKPOINT_PARTITION: &str = "mqtt";
+const CLIENT_ID_KEY: &str = "session:client-id";
+
+/// Local store for `read --persist`, using the same database layout as `eh-read`: messages are
+/// saved as `InboundMessage` (status `Read`) under `msg:` keys, and the last sequence number under
+/// a `checkpoint:` key, so `eh-export --source-db` can export them in any of its formats.
+///
+/// The store also keeps the client id, so the broker recognizes the session across runs and
+/// queues the messages published while the reader was offline.
+pub struct MessageStore {
+    db: Db,
+    last_sequence: i64,
+}
+
+impl MessageStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        let db =
+            sled::open(path).context(format!("Failed to open database: {}", path.display()))?;
+
+        let last_sequence = match db
+            .get(checkpoint_key())
+            .context("Failed to load checkpoint")?
+        {
+            Some(data) => {
+                serde_json::from_slice::<EventHubCheckpoint>(&data)
+                    .context("Failed to deserialize checkpoint")?
+                    .sequence_number
+            }
+            None => 0,
+        };
+
+        Ok(Self { db, last_sequence })
+    }
+
+    /// Number of messages stored so far, in this and previous runs.
+    pub fn last_sequence(&self) -> i64 {
+        self.last_sequence
+    }
+
+    /// Returns the client id of the session, creating (and saving) it with `create` on first use.
+    pub fn client_id(&self, create: impl FnOnce() -> String) -> Result<String> {
+        if let Some(client_id) = self.db.get(CLIENT_ID_KEY)? {
+            return Ok(String::from_utf8_lossy(&client_id).to_string());
+        }
+
+        let client_id = create();
+        self.db
+            .insert(CLIENT_ID_KEY, client_id.as_bytes())
+            .context("Failed to save client id")?;
+        Ok(client_id)
+    }
+
+    /// Saves a message and moves the checkpoint forward. Flushed before returning, so the message
+    /// can be acknowledged to the broker.
+    ///
+    /// Payloads that are not valid UTF-8 are stored as base64.
+    pub fn save(&mut self, topic: &str, payload: &[u8]) -> Result<InboundMessage> {
+        let sequence = self.last_sequence + 1;
+        let now = Utc::now();
+        let msg_data = match std::str::from_utf8(payload) {
+            Ok(text) => text.to_string(),
+            Err(_) => STANDARD.encode(payload),
+        };
+
+        let message = InboundMessage {
+            id: sequence.to_string(),
+            event_id: sequence.to_string(),
+            partition_key: None,
+            partition_id: topic.to_string(),
+            queued_time: now,
+            event_seq_number: Some(sequence),
+            event_offset: None,
+            suggested_filename: None,
+            processed_at: now,
+            msg_data,
+            status: MessageStatus::Read,
+        };
+
+        let checkpoint = EventHubCheckpoint {
+            sequence_number: sequence,
+            offset: sequence.to_string(),
+            partition_id: CHECKPOINT_PARTITION.to_string(),
+            updated_at: now,
+        };
+
+        // Zero-padded, so the messages are exported in the order they arrived.
+        let message_key = format!("msg:{}:{:020}", topic, sequence);
+        self.db
+            .insert(
+                message_key,
+                serde_json::to_vec(&message).context("Failed to serialize message")?,
+            )
+            .context("Failed to insert message")?;
+        self.db
+            .insert(
+                checkpoint_key(),
+                serde_json::to_vec(&checkpoint).context("Failed to serialize checkpoint")?,
+            )
+            .context("Failed to save checkpoint")?;
+        self.db.flush().context("Failed to flush database")?;
+
+        self.last_sequence = sequence;
+        Ok(message)
+    }
+}
+
+fn checkpoint_key() -> String {
+    format!("checkpoint:{}", CHECKPOINT_PARTITION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_and_client_id_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.db");
+
+        {
+            let mut store = MessageStore::open(&path).unwrap();
+            assert_eq!(
+                store.client_id(|| "mqtt-reader-1".to_string()).unwrap(),
+                "mqtt-reader-1"
+            );
+            store.save("sensors/temperature", b"22.5").unwrap();
+            let binary = store.save("sensors/raw", &[0xff, 0x00]).unwrap();
+            assert_eq!(binary.msg_data, "/wA=");
+        }
+
+        let mut store = MessageStore::open(&path).unwrap();
+        assert_eq!(store.last_sequence(), 2);
+        assert_eq!(
+            store.client_id(|| "mqtt-reader-2".to_string()).unwrap(),
+            "mqtt-reader-1"
+        );
+
+        let message = store.save("sensors/temperature", b"23").unwrap();
+        assert_eq!(message.event_seq_number, Some(3));
+        assert_eq!(store.db.scan_prefix("msg:").count(), 3);
+    }
+}
@@ -1,3 +1,6 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
 pub struct MqttArgs {
     pub command: MqttCommand,
     pub host: String,
@@ -6,6 +9,18 @@ pub struct MqttArgs {
     pub message: Option<String>,
     pub username: Option<String>,
     pub password: Option<String>,
+    pub tls: TlsArgs,
+    /// Recording (JSONL) written by `record` and read by `replay`.
+    pub file: Option<PathBuf>,
+    /// Replay speed factor: 1 keeps the original timing, 2 is twice as fast.
+    pub speed: f64,
+    /// Connects with MQTT 5 instead of 3.1.1. Implied by any of the MQTT 5 properties.
+    pub mqtt5: bool,
+    pub properties: V5Properties,
+    /// How long `post` waits for a reply in the response topic. `None` doesn't wait.
+    pub wait_response: Option<Duration>,
+    /// Database where `read` saves the messages, with a persistent session. `None` only logs them.
+    pub persist: Option<PathBuf>,
 }
 
 impl MqttArgs {
@@ -14,8 +29,51 @@ impl MqttArgs {
     }
 }
 
+/// TLS settings. Enabled by a `mqtts://` host or `--tls`.
+///
+/// # Fields
+/// - `enabled`: Whether the connection uses TLS
+/// - `ca_cert`: PEM CA certificate used to verify the broker (default: system trust store)
+/// - `client_cert`/`client_key`: PEM client certificate and PKCS#8 key, for mutual TLS
+pub struct TlsArgs {
+    pub enabled: bool,
+    pub ca_cert: Option<PathBuf>,
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+}
+
+impl TlsArgs {
+    pub fn is_mutual(&self) -> bool {
+        self.client_cert.is_some() && self.client_key.is_some()
+    }
+}
+
+/// MQTT 5 properties sent with `post`.
+///
+/// # Fields
+/// - `content_type`: MIME type of the payload (e.g.: `application/json`)
+/// - `response_topic`/`correlation_data`: Where the receiver should reply, and the value it should
+///   send back, so the reply can be matched to the request
+/// - `user_properties`: Key/value pairs, in the order informed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct V5Properties {
+    pub content_type: Option<String>,
+    pub response_topic: Option<String>,
+    pub correlation_data: Option<String>,
+    pub user_properties: Vec<(String, String)>,
+}
+
+impl V5Properties {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
 pub enum MqttCommand {
     Unknown,
     Read,
     Post,
+    Record,
+    Replay,
+    Stats,
 }
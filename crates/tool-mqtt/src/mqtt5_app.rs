@@ -0,0 +1,277 @@
+use crate::models::{MqttArgs, V5Properties};
+use crate::mqtt_app::{create_client_id, create_tls_connector};
+use anyhow::Result;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use bytes::Bytes;
+use rumqttc::v5::mqttbytes::v5::{Packet, Publish, PublishProperties};
+use rumqttc::v5::mqttbytes::QoS;
+use rumqttc::v5::{AsyncClient, Event, EventLoop, MqttOptions};
+use rumqttc::{TlsConfiguration, Transport};
+use std::time::Duration;
+use tokio::time::{sleep, sleep_until, Instant};
+use tracing::{debug, error, info};
+
+/// Same as `read_messages`, over MQTT 5, showing the properties of each message.
+pub async fn read_messages_v5(args: &MqttArgs) -> Result<()> {
+    let (client, mut event_loop) = create_connection_options("reader".to_string(), args)?;
+
+    info!("Subscribing to topic: {}", args.topic);
+    client.subscribe(&args.topic, QoS::AtMostOnce).await?;
+    info!("Subscribed to topic: {}", args.topic);
+
+    info!("Waiting for messages...");
+    loop {
+        match event_loop.poll().await {
+            Ok(Event::Incoming(Packet::Publish(message))) => {
+                debug!("Publish received: {:?}", message);
+                log_message(&message);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Error = {:?}", e);
+                sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+/// Same as `post_message`, over MQTT 5, with the properties informed in the command line.
+///
+/// With `--wait-response`, subscribes to the response topic before publishing and waits for the
+/// reply (the first message with the same correlation data, when informed).
+pub async fn post_message_v5(args: &MqttArgs) -> Result<()> {
+    let Some(message) = &args.message else {
+        anyhow::bail!("No message provided");
+    };
+
+    let (client, mut event_loop) = create_connection_options("sender".to_string(), args)?;
+
+    let response_topic = args
+        .wait_response
+        .and(args.properties.response_topic.as_ref());
+
+    if let Some(response_topic) = response_topic {
+        info!("Subscribing to response topic: {}", response_topic);
+        client.subscribe(response_topic, QoS::AtLeastOnce).await?;
+    }
+
+    info!("Publishing message to topic: {}", args.topic);
+    client
+        .publish_with_properties(
+            &args.topic,
+            QoS::AtLeastOnce,
+            false,
+            message.clone(),
+            build_publish_properties(&args.properties),
+        )
+        .await?;
+
+    let deadline = args.wait_response.map(|timeout| Instant::now() + timeout);
+
+    info!("Message published. Waiting for ack...");
+    loop {
+        let event = match deadline {
+            None => event_loop.poll().await,
+            Some(deadline) => tokio::select! {
+                _ = sleep_until(deadline) => {
+                    anyhow::bail!(
+                        "No response received in {} second(s)",
+                        args.wait_response.unwrap_or_default().as_secs()
+                    );
+                }
+                event = event_loop.poll() => event,
+            },
+        };
+
+        match event {
+            Ok(Event::Incoming(Packet::PubAck(_))) => {
+                info!("Message publication acknowledged!");
+                if response_topic.is_none() {
+                    break;
+                }
+                info!("Waiting for the response...");
+            }
+            Ok(Event::Incoming(Packet::Publish(response))) if response_topic.is_some() => {
+                if is_response(&response, &args.properties) {
+                    info!("Response received.");
+                    log_message(&response);
+                    break;
+                }
+
+                debug!("Ignoring message with a different correlation data");
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Error = {:?}", e);
+                sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+
+    client.disconnect().await?;
+    Ok(())
+}
+
+fn create_connection_options(
+    client_id: String,
+    args: &MqttArgs,
+) -> Result<(AsyncClient, EventLoop)> {
+    debug!("Creating MQTT 5 connection options");
+    let mut mqtt_options =
+        MqttOptions::new(create_client_id(&client_id), args.host.clone(), args.port);
+
+    mqtt_options.set_keep_alive(Duration::from_secs(5));
+
+    if let (Some(username), Some(password)) = (&args.username, &args.password) {
+        mqtt_options.set_credentials(username.clone(), password.clone());
+    }
+
+    if args.tls.enabled {
+        debug!("Creating TLS configuration");
+        let connector = create_tls_connector(&args.tls)?;
+        mqtt_options.set_transport(Transport::tls_with_config(
+            TlsConfiguration::NativeConnector(connector),
+        ));
+    }
+
+    debug!("Connecting to broker");
+    Ok(AsyncClient::new(mqtt_options, 10))
+}
+
+fn build_publish_properties(properties: &V5Properties) -> PublishProperties {
+    PublishProperties {
+        content_type: properties.content_type.clone(),
+        response_topic: properties.response_topic.clone(),
+        correlation_data: properties
+            .correlation_data
+            .as_ref()
+            .map(|data| Bytes::from(data.clone().into_bytes())),
+        user_properties: properties.user_properties.clone(),
+        ..Default::default()
+    }
+}
+
+/// Without correlation data, any message in the response topic is the response.
+fn is_response(message: &Publish, properties: &V5Properties) -> bool {
+    let Some(expected) = &properties.correlation_data else {
+        return true;
+    };
+
+    message
+        .properties
+        .as_ref()
+        .and_then(|props| props.correlation_data.as_ref())
+        .is_some_and(|data| data.as_ref() == expected.as_bytes())
+}
+
+fn log_message(message: &Publish) {
+    info!(
+        "Message received from {}: {:?}",
+        String::from_utf8_lossy(&message.topic),
+        String::from_utf8_lossy(&message.payload)
+    );
+
+    if let Some(properties) = &message.properties {
+        for line in describe_properties(properties) {
+            info!("  - {}", line);
+        }
+    }
+}
+
+/// The MQTT 5 properties of a message, one per line. Correlation data that is not valid UTF-8 is
+/// shown as base64.
+fn describe_properties(properties: &PublishProperties) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if let Some(content_type) = &properties.content_type {
+        lines.push(format!("Content type: {}", content_type));
+    }
+
+    if let Some(response_topic) = &properties.response_topic {
+        lines.push(format!("Response topic: {}", response_topic));
+    }
+
+    if let Some(data) = &properties.correlation_data {
+        let data = match std::str::from_utf8(data) {
+            Ok(text) => text.to_string(),
+            Err(_) => format!("{} (base64)", STANDARD.encode(data)),
+        };
+        lines.push(format!("Correlation data: {}", data));
+    }
+
+    for (key, value) in &properties.user_properties {
+        lines.push(format!("User property: {}={}", key, value));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn properties(correlation_data: Option<&str>) -> V5Properties {
+        V5Properties {
+            content_type: Some("application/json".to_string()),
+            response_topic: Some("replies/cli".to_string()),
+            correlation_data: correlation_data.map(str::to_string),
+            user_properties: vec![("source".to_string(), "cli".to_string())],
+        }
+    }
+
+    fn response(correlation_data: Option<&[u8]>) -> Publish {
+        let mut message = Publish::new("replies/cli", QoS::AtLeastOnce, "ok", None);
+        message.properties = Some(PublishProperties {
+            correlation_data: correlation_data.map(|data| Bytes::from(data.to_vec())),
+            ..Default::default()
+        });
+        message
+    }
+
+    #[test]
+    fn test_build_publish_properties() {
+        let built = build_publish_properties(&properties(Some("req-1")));
+
+        assert_eq!(built.content_type.as_deref(), Some("application/json"));
+        assert_eq!(built.response_topic.as_deref(), Some("replies/cli"));
+        assert_eq!(built.correlation_data, Some(Bytes::from("req-1")));
+        assert_eq!(
+            built.user_properties,
+            vec![("source".to_string(), "cli".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_is_response_matches_correlation_data() {
+        assert!(is_response(
+            &response(Some(b"req-1")),
+            &properties(Some("req-1"))
+        ));
+        assert!(!is_response(
+            &response(Some(b"req-2")),
+            &properties(Some("req-1"))
+        ));
+        assert!(!is_response(&response(None), &properties(Some("req-1"))));
+        assert!(is_response(&response(None), &properties(None)));
+    }
+
+    #[test]
+    fn test_describe_properties() {
+        let lines = describe_properties(&PublishProperties {
+            content_type: Some("text/plain".to_string()),
+            correlation_data: Some(Bytes::from(vec![0xff, 0x01])),
+            user_properties: vec![("a".to_string(), "1".to_string())],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            lines,
+            vec![
+                "Content type: text/plain",
+                "Correlation data: /wE= (base64)",
+                "User property: a=1",
+            ]
+        );
+    }
+}
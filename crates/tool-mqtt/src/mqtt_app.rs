@@ -1,13 +1,32 @@
-use crate::models::MqttArgs;
-use anyhow::Result;
-use rumqttc::{AsyncClient, Event, EventLoop, Incoming, MqttOptions, Outgoing, QoS};
+use crate::message_store::MessageStore;
+use crate::models::{MqttArgs, TlsArgs};
+use crate::recording::{load_recording, replay_delay, RecordedMessage};
+use crate::topic_stats::TopicStatsTable;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use crossterm::cursor::MoveTo;
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::QueueableCommand;
+use native_tls::{Certificate, Identity, TlsConnector};
+use rumqttc::{
+    AsyncClient, Event, EventLoop, Incoming, MqttOptions, Outgoing, QoS, TlsConfiguration,
+    Transport,
+};
 use shared::utils::new_guid::new_guid;
-use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{debug, error, info};
+use std::fs;
+use std::io::{stdout, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::time::{interval, sleep, MissedTickBehavior};
+use tracing::{debug, error, info, warn};
 
 pub async fn read_messages(args: &MqttArgs) -> Result<()> {
-    let (topic, client, mut event_loop) = create_connection_options("reader".to_string(), args);
+    if let Some(path) = &args.persist {
+        return read_messages_persisted(args, path).await;
+    }
+
+    let (topic, client, mut event_loop) = create_connection_options("reader".to_string(), args)?;
 
     info!("Subscribing to topic: {}", topic);
     client.subscribe(&topic, QoS::AtMostOnce).await?;
@@ -36,21 +55,77 @@ pub async fn read_messages(args: &MqttArgs) -> Result<()> {
     }
 }
 
+/// Subscribes with a persistent session (same client id on every run, no clean session, QoS 1),
+/// so the broker queues the messages published while the reader is offline. Every message is
+/// saved to the store before it's acknowledged, so nothing is lost if the tool is killed.
+async fn read_messages_persisted(args: &MqttArgs, path: &Path) -> Result<()> {
+    let mut store = MessageStore::open(path)?;
+    let client_id = store.client_id(|| create_client_id("reader"))?;
+    debug!("Using persistent session: {}", client_id);
+
+    let mut mqtt_options = create_mqtt_options(client_id, args)?;
+    mqtt_options.set_clean_session(false);
+    mqtt_options.set_manual_acks(true);
+    let (client, mut event_loop) = AsyncClient::new(mqtt_options, 10);
+
+    info!("Subscribing to topic: {}", args.topic);
+    client.subscribe(&args.topic, QoS::AtLeastOnce).await?;
+
+    info!(
+        "Saving messages to {} ({} stored so far). Press Ctrl+C to stop...",
+        path.display(),
+        store.last_sequence()
+    );
+    let mut saved = 0usize;
+    loop {
+        let event = tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            event = event_loop.poll() => event,
+        };
+
+        match event {
+            Ok(Event::Incoming(Incoming::ConnAck(ack))) if ack.session_present => {
+                info!("Session resumed. Messages queued while offline will be delivered now.");
+            }
+            Ok(Event::Incoming(Incoming::Publish(message))) => {
+                let stored = store.save(&message.topic, &message.payload)?;
+                client.ack(&message).await?;
+
+                saved += 1;
+                info!("Saved message #{} from {}", stored.event_id, message.topic);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Error = {:?}", e);
+                sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+
+    info!("Saved {} message(s) to {}", saved, path.display());
+    Ok(())
+}
+
 fn create_connection_options(
     client_id: String,
     args: &MqttArgs,
-) -> (String, AsyncClient, EventLoop) {
-    debug!("Creating connection options");
+) -> Result<(String, AsyncClient, EventLoop)> {
     let topic = args.topic.clone();
+    let mqtt_options = create_mqtt_options(create_client_id(&client_id), args)?;
+
+    debug!("Creating connection");
+    let (client, event_loop) = AsyncClient::new(mqtt_options, 10);
+
+    debug!("Connecting to broker");
+    Ok((topic, client, event_loop))
+}
+
+fn create_mqtt_options(client_id: String, args: &MqttArgs) -> Result<MqttOptions> {
+    debug!("Creating connection options");
     let host = args.host.clone();
     let port = args.port;
 
-    let mut mqtt_options = MqttOptions::new(
-        //Adding a guid to the id so that we can have multiple instances of the same client
-        format!("{}-{}-{}", env!("CARGO_PKG_NAME"), client_id, new_guid()),
-        host,
-        port,
-    );
+    let mut mqtt_options = MqttOptions::new(client_id, host, port);
 
     mqtt_options.set_keep_alive(Duration::from_secs(5));
 
@@ -60,11 +135,56 @@ fn create_connection_options(
         mqtt_options.set_credentials(username, password);
     }
 
-    debug!("Creating connection");
-    let (client, event_loop) = AsyncClient::new(mqtt_options, 10);
+    if args.tls.enabled {
+        debug!("Creating TLS configuration");
+        let connector = create_tls_connector(&args.tls)?;
+        mqtt_options.set_transport(Transport::tls_with_config(
+            TlsConfiguration::NativeConnector(connector),
+        ));
+    }
 
-    debug!("Connecting to broker");
-    (topic, client, event_loop)
+    Ok(mqtt_options)
+}
+
+pub fn create_client_id(client_id: &str) -> String {
+    //Adding a guid to the id so that we can have multiple instances of the same client
+    format!("{}-{}-{}", env!("CARGO_PKG_NAME"), client_id, new_guid())
+}
+
+/// Builds the TLS connector. The system trust store is always used; `--ca-cert` adds to it.
+pub fn create_tls_connector(tls: &TlsArgs) -> Result<TlsConnector> {
+    let mut builder = TlsConnector::builder();
+
+    if let Some(ca_cert) = &tls.ca_cert {
+        let pem = fs::read(ca_cert).context(format!(
+            "Failed to read CA certificate: {}",
+            ca_cert.display()
+        ))?;
+        let certificate = Certificate::from_pem(&pem)
+            .context(format!("Invalid CA certificate: {}", ca_cert.display()))?;
+        builder.add_root_certificate(certificate);
+    }
+
+    if let (Some(client_cert), Some(client_key)) = (&tls.client_cert, &tls.client_key) {
+        let cert_pem = fs::read(client_cert).context(format!(
+            "Failed to read client certificate: {}",
+            client_cert.display()
+        ))?;
+        let key_pem = fs::read(client_key).context(format!(
+            "Failed to read client key: {}",
+            client_key.display()
+        ))?;
+
+        let identity = Identity::from_pkcs8(&cert_pem, &key_pem).context(
+            "Invalid client certificate or key. The key must be PEM PKCS#8 (BEGIN PRIVATE KEY). \
+             Convert it with: openssl pkcs8 -topk8 -nocrypt -in key.pem -out key-pkcs8.pem",
+        )?;
+        builder.identity(identity);
+    }
+
+    builder
+        .build()
+        .context("Failed to create the TLS configuration")
 }
 
 pub async fn post_message(args: &MqttArgs) -> Result<()> {
@@ -75,7 +195,7 @@ pub async fn post_message(args: &MqttArgs) -> Result<()> {
         Some(msg) => msg,
     };
 
-    let (topic, client, mut event_loop) = create_connection_options("sender".to_string(), args);
+    let (topic, client, mut event_loop) = create_connection_options("sender".to_string(), args)?;
 
     info!("Publishing message to topic: {}", topic);
     client
@@ -110,3 +230,195 @@ pub async fn post_message(args: &MqttArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// Subscribes to the topic and appends every message to the recording (JSONL), until Ctrl+C.
+pub async fn record_messages(args: &MqttArgs) -> Result<()> {
+    let path = args
+        .file
+        .as_ref()
+        .context("A recording file is required for the record command")?;
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context(format!("Failed to open recording: {}", path.display()))?;
+
+    let (topic, client, mut event_loop) = create_connection_options("recorder".to_string(), args)?;
+
+    info!("Subscribing to topic: {}", topic);
+    client.subscribe(&topic, QoS::AtLeastOnce).await?;
+
+    info!(
+        "Recording messages to {}. Press Ctrl+C to stop...",
+        path.display()
+    );
+    let mut recorded = 0usize;
+    loop {
+        let event = tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            event = event_loop.poll() => event,
+        };
+
+        match event {
+            Ok(Event::Incoming(Incoming::Publish(message))) => {
+                let record = RecordedMessage::new(
+                    Utc::now(),
+                    &message.topic,
+                    message.qos,
+                    message.retain,
+                    &message.payload,
+                );
+
+                // Flushed on every message, so nothing is lost if the tool is killed.
+                writeln!(file, "{}", record.to_json_line()?)?;
+                file.flush()?;
+
+                recorded += 1;
+                info!("Recorded message #{} from {}", recorded, message.topic);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Error = {:?}", e);
+                sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+
+    info!("Recorded {} message(s) to {}", recorded, path.display());
+    Ok(())
+}
+
+/// Republishes a recording, keeping the original gaps between the messages (divided by `--speed`).
+pub async fn replay_messages(args: &MqttArgs) -> Result<()> {
+    let path = args
+        .file
+        .as_ref()
+        .context("A recording file is required for the replay command")?;
+
+    let messages = load_recording(path)?;
+    if messages.is_empty() {
+        warn!("Recording is empty: {}", path.display());
+        return Ok(());
+    }
+
+    let (_, client, mut event_loop) = create_connection_options("replayer".to_string(), args)?;
+
+    // The event loop must be polled for the publications to be sent.
+    let poller = tokio::spawn(async move {
+        loop {
+            match event_loop.poll().await {
+                Ok(Event::Outgoing(Outgoing::Disconnect)) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    error!("Error = {:?}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    info!(
+        "Replaying {} message(s) from {} ({}x speed)",
+        messages.len(),
+        path.display(),
+        args.speed
+    );
+
+    let mut previous: Option<&RecordedMessage> = None;
+    for (index, message) in messages.iter().enumerate() {
+        if let Some(previous) = previous {
+            let delay = replay_delay(previous, message, args.speed);
+            if !delay.is_zero() {
+                sleep(delay).await;
+            }
+        }
+
+        client
+            .publish(
+                &message.topic,
+                message.qos()?,
+                message.retain,
+                message.payload_bytes()?,
+            )
+            .await?;
+
+        info!(
+            "Replayed message #{} of {} to {}",
+            index + 1,
+            messages.len(),
+            message.topic
+        );
+        previous = Some(message);
+    }
+
+    client.disconnect().await?;
+    poller.await?;
+
+    info!("Replay finished.");
+    Ok(())
+}
+
+/// Subscribes to the topic filter and shows a table of the topics seen, refreshed every second,
+/// until Ctrl+C.
+pub async fn stats_messages(args: &MqttArgs) -> Result<()> {
+    let (topic, client, mut event_loop) = create_connection_options("stats".to_string(), args)?;
+
+    info!("Subscribing to topic: {}", topic);
+    client.subscribe(&topic, QoS::AtMostOnce).await?;
+
+    let mut table = TopicStatsTable::new(Instant::now());
+    let mut refresh = interval(Duration::from_secs(1));
+    refresh.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = refresh.tick() => {
+                let now = Instant::now();
+                table.tick(now);
+                render_stats(&topic, &table, now);
+            }
+            event = event_loop.poll() => match event {
+                Ok(Event::Incoming(Incoming::Publish(message))) => {
+                    table.record(&message.topic, message.payload.len(), Instant::now());
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!("Error = {:?}", e);
+                    sleep(Duration::from_secs(1)).await;
+                }
+            },
+        }
+    }
+
+    client.disconnect().await?;
+    println!();
+    info!(
+        "Stopped. {} topic(s) seen on {}",
+        table.topic_count(),
+        topic
+    );
+    Ok(())
+}
+
+/// Redraws the whole screen. Rows that don't fit in the terminal are summarized in the last line.
+fn render_stats(topic: &str, table: &TopicStatsTable, now: Instant) {
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    // Title, summary, blank line, header, and the "more topics" line.
+    let max_rows = (height as usize).saturating_sub(6).max(1);
+
+    let mut stdout = stdout();
+    let _ = stdout.queue(Clear(ClearType::All));
+    let _ = stdout.queue(MoveTo(0, 0));
+    let _ = stdout.queue(Print(format!(
+        "MQTT stats for {} (Ctrl+C to stop)\r\n",
+        topic
+    )));
+
+    for line in table.render(now, max_rows, width as usize) {
+        let _ = stdout.queue(Print(format!("{}\r\n", line)));
+    }
+
+    let _ = stdout.flush();
+}
@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rumqttc::QoS;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// One recorded message, stored as a line of the JSONL file.
+///
+/// Payloads that are valid UTF-8 are stored as text, so the file is easy to read and edit.
+/// Anything else is stored as base64, with `encoding` set to `base64`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    pub timestamp: DateTime<Utc>,
+    pub topic: String,
+    pub qos: u8,
+    #[serde(default)]
+    pub retain: bool,
+    pub payload: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+impl RecordedMessage {
+    pub fn new(
+        timestamp: DateTime<Utc>,
+        topic: &str,
+        qos: QoS,
+        retain: bool,
+        payload: &[u8],
+    ) -> Self {
+        let (payload, encoding) = match std::str::from_utf8(payload) {
+            Ok(text) => (text.to_string(), None),
+            Err(_) => (STANDARD.encode(payload), Some("base64".to_string())),
+        };
+
+        Self {
+            timestamp,
+            topic: topic.to_string(),
+            qos: qos as u8,
+            retain,
+            payload,
+            encoding,
+        }
+    }
+
+    pub fn payload_bytes(&self) -> Result<Vec<u8>> {
+        match self.encoding.as_deref() {
+            None | Some("utf8") => Ok(self.payload.clone().into_bytes()),
+            Some("base64") => STANDARD
+                .decode(&self.payload)
+                .context(format!("Invalid base64 payload for topic {}", self.topic)),
+            Some(encoding) => anyhow::bail!("Unsupported payload encoding: {}", encoding),
+        }
+    }
+
+    pub fn qos(&self) -> Result<QoS> {
+        match self.qos {
+            0 => Ok(QoS::AtMostOnce),
+            1 => Ok(QoS::AtLeastOnce),
+            2 => Ok(QoS::ExactlyOnce),
+            qos => anyhow::bail!("Invalid QoS {} for topic {}", qos, self.topic),
+        }
+    }
+
+    pub fn to_json_line(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Reads a recording. Empty lines are skipped; errors point to the line number.
+pub fn load_recording(path: &Path) -> Result<Vec<RecordedMessage>> {
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read recording: {}", path.display()))?;
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str::<RecordedMessage>(line).context(format!(
+                "Invalid message in {}, line {}",
+                path.display(),
+                index + 1
+            ))
+        })
+        .collect()
+}
+
+/// Time to wait before republishing `next`, keeping the original gap between the messages
+/// divided by `speed` (e.g.: 2 replays twice as fast).
+pub fn replay_delay(previous: &RecordedMessage, next: &RecordedMessage, speed: f64) -> Duration {
+    let gap = (next.timestamp - previous.timestamp)
+        .to_std()
+        .unwrap_or_default();
+
+    gap.div_f64(speed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn message(second: u32, payload: &[u8]) -> RecordedMessage {
+        RecordedMessage::new(
+            Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, second).unwrap(),
+            "sensors/temperature",
+            QoS::AtLeastOnce,
+            false,
+            payload,
+        )
+    }
+
+    #[test]
+    fn test_text_payloads_round_trip() {
+        let recorded = message(0, b"22.5");
+        let line = recorded.to_json_line().unwrap();
+
+        assert_eq!(
+            line,
+            r#"{"timestamp":"2026-10-16T12:00:00Z","topic":"sensors/temperature","qos":1,"retain":false,"payload":"22.5"}"#
+        );
+
+        let parsed: RecordedMessage = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed, recorded);
+        assert_eq!(parsed.payload_bytes().unwrap(), b"22.5");
+        assert_eq!(parsed.qos().unwrap(), QoS::AtLeastOnce);
+    }
+
+    #[test]
+    fn test_binary_payloads_use_base64() {
+        let recorded = message(0, &[0xff, 0x00, 0x10]);
+
+        assert_eq!(recorded.encoding.as_deref(), Some("base64"));
+        assert_eq!(recorded.payload, "/wAQ");
+        assert_eq!(recorded.payload_bytes().unwrap(), vec![0xff, 0x00, 0x10]);
+    }
+
+    #[test]
+    fn test_replay_delay_applies_speed() {
+        let first = message(0, b"a");
+        let second = message(10, b"b");
+
+        assert_eq!(replay_delay(&first, &second, 1.0), Duration::from_secs(10));
+        assert_eq!(
+            replay_delay(&first, &second, 4.0),
+            Duration::from_millis(2500)
+        );
+        // Out of order messages are replayed right away.
+        assert_eq!(replay_delay(&second, &first, 1.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_load_recording_reports_line_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.jsonl");
+        let valid = message(0, b"a").to_json_line().unwrap();
+        fs::write(&path, format!("{}\n\n{}\n", valid, valid)).unwrap();
+
+        assert_eq!(load_recording(&path).unwrap().len(), 2);
+
+        fs::write(&path, format!("{}\nnot json\n", valid)).unwrap();
+        let error = load_recording(&path).unwrap_err().to_string();
+        assert!(error.contains("line 2"), "{}", error);
+    }
+}
@@ -9,6 +9,9 @@ impl StringExt for String {
         match self.trim().to_lowercase().as_str() {
             "reads" | "read" => MqttCommand::Read,
             "post" | "send" => MqttCommand::Post,
+            "record" => MqttCommand::Record,
+            "replay" => MqttCommand::Replay,
+            "stats" => MqttCommand::Stats,
             _ => MqttCommand::Unknown,
         }
     }
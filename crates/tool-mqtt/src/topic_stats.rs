@@ -0,0 +1,263 @@
+use shared::utils::format_bytes_to_string::format_bytes_to_string;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Counters for a single topic.
+#[derive(Debug, Clone)]
+pub struct TopicStats {
+    pub messages: u64,
+    pub total_bytes: u64,
+    pub last_size: u64,
+    pub max_size: u64,
+    pub last_seen: Instant,
+    /// Messages per second, measured over the last refresh window.
+    pub rate: f64,
+    window_messages: u64,
+}
+
+impl TopicStats {
+    fn new(now: Instant) -> Self {
+        Self {
+            messages: 0,
+            total_bytes: 0,
+            last_size: 0,
+            max_size: 0,
+            last_seen: now,
+            rate: 0.0,
+            window_messages: 0,
+        }
+    }
+
+    pub fn average_size(&self) -> u64 {
+        if self.messages == 0 {
+            return 0;
+        }
+
+        self.total_bytes / self.messages
+    }
+}
+
+/// Aggregates the messages received by the `stats` command, per topic.
+///
+/// Messages are added with `record`. Every refresh calls `tick`, which turns the messages received
+/// since the previous tick into a per-second rate.
+pub struct TopicStatsTable {
+    topics: HashMap<String, TopicStats>,
+    started_at: Instant,
+    window_start: Instant,
+    total_messages: u64,
+    total_bytes: u64,
+    total_rate: f64,
+    window_messages: u64,
+}
+
+impl TopicStatsTable {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            topics: HashMap::new(),
+            started_at: now,
+            window_start: now,
+            total_messages: 0,
+            total_bytes: 0,
+            total_rate: 0.0,
+            window_messages: 0,
+        }
+    }
+
+    pub fn record(&mut self, topic: &str, payload_size: usize, now: Instant) {
+        let size = payload_size as u64;
+        let stats = self
+            .topics
+            .entry(topic.to_string())
+            .or_insert_with(|| TopicStats::new(now));
+
+        stats.messages += 1;
+        stats.total_bytes += size;
+        stats.last_size = size;
+        stats.max_size = stats.max_size.max(size);
+        stats.last_seen = now;
+        stats.window_messages += 1;
+
+        self.total_messages += 1;
+        self.total_bytes += size;
+        self.window_messages += 1;
+    }
+
+    /// Closes the current window and updates the rates.
+    pub fn tick(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.window_start).as_secs_f64();
+        if elapsed <= 0.0 {
+            return;
+        }
+
+        for stats in self.topics.values_mut() {
+            stats.rate = stats.window_messages as f64 / elapsed;
+            stats.window_messages = 0;
+        }
+
+        self.total_rate = self.window_messages as f64 / elapsed;
+        self.window_messages = 0;
+        self.window_start = now;
+    }
+
+    /// Topics sorted by rate, then by message count, so the busiest ones stay on top.
+    pub fn rows(&self) -> Vec<(&String, &TopicStats)> {
+        let mut rows: Vec<_> = self.topics.iter().collect();
+
+        rows.sort_by(|(a_topic, a), (b_topic, b)| {
+            b.rate
+                .total_cmp(&a.rate)
+                .then(b.messages.cmp(&a.messages))
+                .then(a_topic.cmp(b_topic))
+        });
+
+        rows
+    }
+
+    pub fn topic_count(&self) -> usize {
+        self.topics.len()
+    }
+
+    /// Renders the table as lines, limited to `max_rows` topics and `width` characters.
+    pub fn render(&self, now: Instant, max_rows: usize, width: usize) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "Topics: {} | Messages: {} ({:.1}/s) | Received: {} | Running for: {}s",
+                self.topic_count(),
+                self.total_messages,
+                self.total_rate,
+                format_bytes_to_string(&self.total_bytes),
+                now.duration_since(self.started_at).as_secs()
+            ),
+            String::new(),
+            format!(
+                "{:>10} {:>8} {:>12} {:>12} {:>12} {:>9}  {}",
+                "Messages", "Rate/s", "Last size", "Avg size", "Max size", "Last seen", "Topic"
+            ),
+        ];
+
+        let rows = self.rows();
+        let hidden = rows.len().saturating_sub(max_rows);
+
+        for (topic, stats) in rows.into_iter().take(max_rows) {
+            lines.push(format!(
+                "{:>10} {:>8.1} {:>12} {:>12} {:>12} {:>9}  {}",
+                stats.messages,
+                stats.rate,
+                format_bytes_to_string(&stats.last_size),
+                format_bytes_to_string(&stats.average_size()),
+                format_bytes_to_string(&stats.max_size),
+                format_last_seen(now.duration_since(stats.last_seen)),
+                topic
+            ));
+        }
+
+        if self.topics.is_empty() {
+            lines.push("Waiting for messages...".to_string());
+        }
+
+        if hidden > 0 {
+            lines.push(format!("... and {} more topic(s)", hidden));
+        }
+
+        lines
+            .into_iter()
+            .map(|line| truncate(line, width))
+            .collect()
+    }
+}
+
+fn format_last_seen(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else {
+        format!("{}h ago", secs / 3600)
+    }
+}
+
+fn truncate(line: String, width: usize) -> String {
+    if line.chars().count() <= width {
+        return line;
+    }
+
+    let mut truncated: String = line.chars().take(width.saturating_sub(3)).collect();
+    truncated.push_str("...");
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_aggregates_per_topic() {
+        let start = Instant::now();
+        let mut table = TopicStatsTable::new(start);
+
+        table.record("sensors/a", 10, start);
+        table.record("sensors/a", 30, start);
+        table.record("sensors/b", 5, start);
+
+        let rows = table.rows();
+        let (_, a) = rows
+            .iter()
+            .find(|(topic, _)| *topic == "sensors/a")
+            .unwrap();
+        assert_eq!(a.messages, 2);
+        assert_eq!(a.last_size, 30);
+        assert_eq!(a.max_size, 30);
+        assert_eq!(a.average_size(), 20);
+        assert_eq!(table.topic_count(), 2);
+    }
+
+    #[test]
+    fn test_tick_computes_rates() {
+        let start = Instant::now();
+        let mut table = TopicStatsTable::new(start);
+
+        for _ in 0..4 {
+            table.record("busy", 1, start);
+        }
+        table.record("quiet", 1, start);
+        table.tick(start + Duration::from_secs(2));
+
+        let rows = table.rows();
+        assert_eq!(rows[0].0, "busy");
+        assert_eq!(rows[0].1.rate, 2.0);
+        assert_eq!(rows[1].1.rate, 0.5);
+
+        // A window without messages brings the rate back to zero.
+        table.tick(start + Duration::from_secs(3));
+        assert!(table.rows().iter().all(|(_, stats)| stats.rate == 0.0));
+    }
+
+    #[test]
+    fn test_render_limits_rows_and_width() {
+        let start = Instant::now();
+        let mut table = TopicStatsTable::new(start);
+        assert!(table
+            .render(start, 10, 200)
+            .contains(&"Waiting for messages...".to_string()));
+
+        for index in 0..5 {
+            table.record(&format!("devices/{}/telemetry", index), 100, start);
+        }
+
+        let lines = table.render(start, 3, 200);
+        assert_eq!(lines.len(), 3 + 3 + 1);
+        assert_eq!(lines.last().unwrap(), "... and 2 more topic(s)");
+
+        let lines = table.render(start, 3, 20);
+        assert!(lines.iter().all(|line| line.chars().count() <= 20));
+    }
+
+    #[test]
+    fn test_format_last_seen() {
+        assert_eq!(format_last_seen(Duration::from_secs(5)), "5s ago");
+        assert_eq!(format_last_seen(Duration::from_secs(125)), "2m ago");
+        assert_eq!(format_last_seen(Duration::from_secs(7200)), "2h ago");
+    }
+}
@@ -0,0 +1,283 @@
+use crate::models::{Mode, NetcatArgs, Protocol, TlsOptions};
+use anyhow::{anyhow, bail, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{GlobalArgs, GlobalFlag, ToolCli};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+
+/// Address used by `--listen` when only the port is informed.
+const DEFAULT_LISTEN_ADDRESS: &str = "0.0.0.0";
+
+impl ToolCli for NetcatArgs {
+    const DISPLAY_NAME: &'static str = "Netcatx";
+
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Connects to (or listens on) a TCP or UDP port, sending stdin to the other side and \
+                writing what it sends to stdout. TCP connections can be wrapped in TLS, and the \
+                traffic can be hex dumped to stderr, for protocol debugging.",
+            )
+            .arg_required_else_help(true)
+            .arg(
+                Arg::new("target")
+                    .value_name("HOST PORT")
+                    .num_args(1..=2)
+                    .required(true)
+                    .help("Host and port to connect to. With --listen: the port, optionally preceded by the address to listen on (Default address: 0.0.0.0)"),
+            )
+            .arg(
+                Arg::new("listen")
+                    .long("listen")
+                    .short('l')
+                    .action(ArgAction::SetTrue)
+                    .help("Listens for a connection, instead of connecting. (Default: false)"),
+            )
+            .arg(
+                Arg::new("udp")
+                    .long("udp")
+                    .short('u')
+                    .action(ArgAction::SetTrue)
+                    .help("Uses UDP instead of TCP. Each read from stdin is sent as a datagram. (Default: false)"),
+            )
+            .arg(
+                Arg::new("tls")
+                    .long("tls")
+                    .action(ArgAction::SetTrue)
+                    .help("Wraps the TCP connection in TLS. Listening requires --cert and --key. (Default: false)"),
+            )
+            .arg(
+                Arg::new("insecure")
+                    .long("insecure")
+                    .action(ArgAction::SetTrue)
+                    .help("With --tls: accepts any server certificate (self-signed, expired, for another host). (Default: false)"),
+            )
+            .arg(
+                Arg::new("cert")
+                    .long("cert")
+                    .value_name("FILE")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("With --tls --listen: certificate chain (PEM)"),
+            )
+            .arg(
+                Arg::new("key")
+                    .long("key")
+                    .value_name("FILE")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("With --tls --listen: private key of the certificate (PKCS#8 PEM)"),
+            )
+            .arg(
+                Arg::new("hexdump")
+                    .long("hexdump")
+                    .short('x')
+                    .action(ArgAction::SetTrue)
+                    .help("Prints a hex dump of the traffic (both ways) to stderr. (Default: false)"),
+            )
+            .arg(
+                Arg::new("keep-open")
+                    .long("keep-open")
+                    .short('k')
+                    .action(ArgAction::SetTrue)
+                    .help("With --listen (TCP): accepts another connection after the current one is closed. (Default: false)"),
+            )
+            .arg(
+                Arg::new("timeout")
+                    .long("timeout")
+                    .short('w')
+                    .value_name("SECONDS")
+                    .value_parser(clap::value_parser!(u64).range(1..))
+                    .help(format!(
+                        "Seconds to wait for the connection (and the TLS handshake). (Default: {})",
+                        DEFAULT_TIMEOUT_SECONDS
+                    )),
+            )
+            .arg(
+                Arg::new("verbose")
+                    .long("verbose")
+                    .short('v')
+                    .action(ArgAction::SetTrue)
+                    .help("Prints the settings and the connection status to stderr. (Default: false)"),
+            )
+    }
+
+    /// stdout carries the traffic, so there's no header to silence with `--quiet`: `--verbose`
+    /// prints it to stderr instead.
+    fn global_flags() -> &'static [GlobalFlag] {
+        &[]
+    }
+
+    fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+        let mode = if matches.get_flag("listen") {
+            Mode::Listen
+        } else {
+            Mode::Connect
+        };
+
+        let target: Vec<&String> = matches
+            .get_many::<String>("target")
+            .unwrap_or_default()
+            .collect();
+
+        let (host, port) = match (mode, target.as_slice()) {
+            (_, [host, port]) => (host.to_string(), parse_port(port)?),
+            (Mode::Listen, [port]) => (DEFAULT_LISTEN_ADDRESS.to_string(), parse_port(port)?),
+            (Mode::Connect, _) => bail!("Inform the host and the port to connect to"),
+            (Mode::Listen, _) => bail!("Inform the port to listen on"),
+        };
+
+        let protocol = if matches.get_flag("udp") {
+            Protocol::Udp
+        } else {
+            Protocol::Tcp
+        };
+
+        let cert = matches.get_one::<PathBuf>("cert").cloned();
+        let key = matches.get_one::<PathBuf>("key").cloned();
+        let insecure = matches.get_flag("insecure");
+
+        let tls = match (matches.get_flag("tls"), mode) {
+            (false, _) => {
+                if insecure || cert.is_some() || key.is_some() {
+                    bail!("--insecure, --cert and --key require --tls");
+                }
+                None
+            }
+            (true, Mode::Connect) => {
+                if cert.is_some() || key.is_some() {
+                    bail!("--cert and --key are only used with --listen");
+                }
+                Some(TlsOptions::Client { insecure })
+            }
+            (true, Mode::Listen) => {
+                if insecure {
+                    bail!("--insecure is only used when connecting");
+                }
+                let (Some(cert), Some(key)) = (cert, key) else {
+                    bail!("--tls --listen requires --cert and --key");
+                };
+                Some(TlsOptions::Server { cert, key })
+            }
+        };
+
+        let timeout = matches
+            .get_one::<u64>("timeout")
+            .copied()
+            .unwrap_or(DEFAULT_TIMEOUT_SECONDS);
+
+        Ok(NetcatArgs {
+            mode,
+            host,
+            port,
+            protocol,
+            tls,
+            hexdump: matches.get_flag("hexdump"),
+            keep_open: matches.get_flag("keep-open"),
+            timeout: Duration::from_secs(timeout),
+            verbose: matches.get_flag("verbose"),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.protocol == Protocol::Udp && self.tls.is_some() {
+            bail!("--tls can't be used with --udp");
+        }
+
+        if self.keep_open && (self.mode != Mode::Listen || self.protocol != Protocol::Tcp) {
+            bail!("--keep-open is only used with --listen, over TCP");
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let mode = match self.mode {
+            Mode::Connect => "Connect",
+            Mode::Listen => "Listen",
+        };
+        let protocol = match (self.protocol, &self.tls) {
+            (Protocol::Udp, _) => "UDP",
+            (Protocol::Tcp, None) => "TCP",
+            (Protocol::Tcp, Some(_)) => "TCP + TLS",
+        };
+
+        let mut rows = vec![
+            ("Mode".to_string(), mode.to_string()),
+            (
+                "Address".to_string(),
+                format!("{}:{}", self.host, self.port),
+            ),
+            ("Protocol".to_string(), protocol.to_string()),
+        ];
+
+        match &self.tls {
+            Some(TlsOptions::Client { insecure }) => {
+                rows.push(("Verify certificate".to_string(), (!insecure).to_string()))
+            }
+            Some(TlsOptions::Server { cert, .. }) => {
+                rows.push(("Certificate".to_string(), cert.display().to_string()))
+            }
+            None => {}
+        }
+
+        rows.push(("Hex dump".to_string(), self.hexdump.to_string()));
+        if self.mode == Mode::Listen {
+            rows.push(("Keep open".to_string(), self.keep_open.to_string()));
+        }
+        rows.push((
+            "Timeout".to_string(),
+            format!("{}s", self.timeout.as_secs()),
+        ));
+
+        rows
+    }
+}
+
+fn parse_port(value: &str) -> Result<u16> {
+    match value.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => Err(anyhow!("Invalid port: {}", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    fn parse(argv: &[&str]) -> Result<NetcatArgs> {
+        try_parse_tool_cli_from::<NetcatArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_connect_and_listen() {
+        let connect = parse(&["netcatx", "example.com", "443", "--tls", "-x"]).unwrap();
+        assert_eq!(connect.mode, Mode::Connect);
+        assert_eq!((connect.host.as_str(), connect.port), ("example.com", 443));
+        assert_eq!(connect.tls, Some(TlsOptions::Client { insecure: false }));
+        assert!(connect.hexdump);
+
+        let listen = parse(&["netcatx", "-l", "8080", "-k"]).unwrap();
+        assert_eq!(listen.mode, Mode::Listen);
+        assert_eq!((listen.host.as_str(), listen.port), ("0.0.0.0", 8080));
+        assert!(listen.keep_open);
+
+        let bound = parse(&["netcatx", "-l", "127.0.0.1", "9000", "-u"]).unwrap();
+        assert_eq!(bound.host, "127.0.0.1");
+        assert_eq!(bound.protocol, Protocol::Udp);
+    }
+
+    #[test]
+    fn test_invalid_combinations() {
+        assert!(parse(&["netcatx", "8080"]).is_err());
+        assert!(parse(&["netcatx", "host", "99999"]).is_err());
+        assert!(parse(&["netcatx", "-l", "8443", "--tls"]).is_err());
+        assert!(parse(&["netcatx", "host", "53", "-u", "--tls"]).is_err());
+        assert!(parse(&["netcatx", "host", "80", "-k"]).is_err());
+        assert!(parse(&["netcatx", "host", "80", "--insecure"]).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+/// Bytes shown in each line of the dump.
+const BYTES_PER_LINE: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Sent,
+    Received,
+}
+
+/// Prints the traffic of one direction to stderr, in the `hexdump -C` layout. Offsets count the
+/// bytes of that direction since the connection started.
+pub struct HexDumper {
+    direction: Direction,
+    offset: usize,
+}
+
+impl HexDumper {
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            offset: 0,
+        }
+    }
+
+    pub fn dump(&mut self, data: &[u8]) {
+        let header = match self.direction {
+            Direction::Sent => format!("> {} bytes sent", data.len()),
+            Direction::Received => format!("< {} bytes received", data.len()),
+        };
+
+        eprintln!("{}", header);
+        for line in format_hex_lines(data, self.offset) {
+            eprintln!("{}", line);
+        }
+
+        self.offset += data.len();
+    }
+}
+
+/// Formats the data as `offset  hex bytes  |ascii|` lines. Non-printable bytes are shown as `.`.
+pub fn format_hex_lines(data: &[u8], offset: usize) -> Vec<String> {
+    data.chunks(BYTES_PER_LINE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut hex = String::with_capacity(BYTES_PER_LINE * 3 + 1);
+            for position in 0..BYTES_PER_LINE {
+                match chunk.get(position) {
+                    Some(byte) => hex.push_str(&format!("{:02x} ", byte)),
+                    None => hex.push_str("   "),
+                }
+
+                // Extra space between the two halves of the line.
+                if position == BYTES_PER_LINE / 2 - 1 {
+                    hex.push(' ');
+                }
+            }
+
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+
+            format!(
+                "{:08x}  {} |{}|",
+                offset + index * BYTES_PER_LINE,
+                hex,
+                ascii
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_hex_lines() {
+        let lines = format_hex_lines(b"GET / HTTP/1.1\r\nHost: x\r\n", 32);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "00000020  47 45 54 20 2f 20 48 54  54 50 2f 31 2e 31 0d 0a  |GET / HTTP/1.1..|"
+        );
+        assert_eq!(
+            lines[1],
+            "00000030  48 6f 73 74 3a 20 78 0d  0a                       |Host: x..|"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::models::NetcatArgs;
+use shared::command_line::tool_cli::{parse_tool_cli_from, ToolCli, ToolInvocation};
+use shared::constants::general::{DASH_LINE, EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod hex_dump;
+mod models;
+mod netcat_app;
+
+/// Modern netcat.
+///
+/// Parses arguments, then connects (or listens) and pipes stdin/stdout through the connection.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<NetcatArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    let exit_code = runtime.block_on(run_invocation(invocation));
+
+    // Doesn't wait for the runtime: a read from stdin may still be pending, and would hold it.
+    runtime.shutdown_background();
+
+    exit_code
+}
+
+async fn run_invocation(invocation: ToolInvocation<NetcatArgs>) -> i32 {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    // stdout carries the traffic, so the runtime info goes to stderr, and only when asked for.
+    if invocation.args.verbose {
+        eprintln!(
+            "{} v{}",
+            NetcatArgs::DISPLAY_NAME,
+            env!("CARGO_PKG_VERSION")
+        );
+        eprintln!("{}", DASH_LINE);
+        for (key, value) in invocation.args.runtime_info() {
+            eprintln!("- {}: {}", key, value);
+        }
+        eprintln!();
+    }
+
+    if let Err(e) = netcat_app::run(&invocation.args).await {
+        eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+        error!("{}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
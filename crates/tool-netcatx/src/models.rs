@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Where the connection comes from, and how the traffic is handled.
+pub struct NetcatArgs {
+    pub mode: Mode,
+    /// Host to connect to, or the address to listen on.
+    pub host: String,
+    pub port: u16,
+    pub protocol: Protocol,
+    pub tls: Option<TlsOptions>,
+    /// Prints a hex dump of the traffic (both ways) to stderr.
+    pub hexdump: bool,
+    /// Listen mode: accepts a new connection after the current one is closed.
+    pub keep_open: bool,
+    /// How long to wait for the connection to be established.
+    pub timeout: Duration,
+    /// Prints the connection status to stderr.
+    pub verbose: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Connect,
+    Listen,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+/// TLS over TCP. Listening requires a certificate and its key; connecting only needs them to be
+/// trusted, unless `insecure` is set.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TlsOptions {
+    Client {
+        /// Accepts any certificate, even if expired, self-signed, or for another host.
+        insecure: bool,
+    },
+    Server {
+        /// Certificate chain (PEM).
+        cert: PathBuf,
+        /// Private key of the certificate (PKCS#8 PEM).
+        key: PathBuf,
+    },
+}
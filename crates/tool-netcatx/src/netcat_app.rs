@@ -0,0 +1,279 @@
+use crate::hex_dump::{Direction, HexDumper};
+use crate::models::{Mode, NetcatArgs, Protocol, TlsOptions};
+use anyhow::{anyhow, Context, Result};
+use std::fmt::Display;
+use std::net::SocketAddr;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{lookup_host, TcpListener, TcpStream, UdpSocket};
+use tokio::time::timeout;
+use tokio_native_tls::{TlsAcceptor, TlsConnector};
+use tracing::{error, info};
+
+/// Size of the reads from stdin and from the connection.
+const BUFFER_SIZE: usize = 16 * 1024;
+
+/// Largest UDP payload.
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+pub async fn run(args: &NetcatArgs) -> Result<()> {
+    match (args.mode, args.protocol) {
+        (Mode::Connect, Protocol::Tcp) => connect_tcp(args).await,
+        (Mode::Listen, Protocol::Tcp) => listen_tcp(args).await,
+        (Mode::Connect, Protocol::Udp) => connect_udp(args).await,
+        (Mode::Listen, Protocol::Udp) => listen_udp(args).await,
+    }
+}
+
+async fn connect_tcp(args: &NetcatArgs) -> Result<()> {
+    let address = format!("{}:{}", args.host, args.port);
+    let stream = timeout(args.timeout, TcpStream::connect(&address))
+        .await
+        .map_err(|_| anyhow!("Timed out connecting to {}", address))?
+        .context(format!("Failed to connect to {}", address))?;
+
+    report(args, format!("Connected to {}", stream.peer_addr()?));
+
+    match &args.tls {
+        Some(TlsOptions::Client { insecure }) => {
+            let connector = native_tls::TlsConnector::builder()
+                .danger_accept_invalid_certs(*insecure)
+                .danger_accept_invalid_hostnames(*insecure)
+                .build()
+                .context("Failed to create the TLS connector")?;
+
+            let stream = timeout(
+                args.timeout,
+                TlsConnector::from(connector).connect(&args.host, stream),
+            )
+            .await
+            .map_err(|_| anyhow!("Timed out on the TLS handshake"))?
+            .context("TLS handshake failed")?;
+
+            report(args, "TLS handshake completed");
+            pipe_stream(stream, args).await
+        }
+        _ => pipe_stream(stream, args).await,
+    }
+}
+
+async fn listen_tcp(args: &NetcatArgs) -> Result<()> {
+    let acceptor = match &args.tls {
+        Some(TlsOptions::Server { cert, key }) => Some(load_tls_acceptor(cert, key)?),
+        _ => None,
+    };
+
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .await
+        .context(format!("Failed to listen on {}:{}", args.host, args.port))?;
+
+    report(args, format!("Listening on {}", listener.local_addr()?));
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        report(args, format!("Connection from {}", peer));
+
+        let result = match &acceptor {
+            Some(acceptor) => match timeout(args.timeout, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => pipe_stream(stream, args).await,
+                Ok(Err(e)) => Err(anyhow!("TLS handshake with {} failed: {}", peer, e)),
+                Err(_) => Err(anyhow!("Timed out on the TLS handshake with {}", peer)),
+            },
+            None => pipe_stream(stream, args).await,
+        };
+
+        if !args.keep_open {
+            return result;
+        }
+
+        // With --keep-open, a broken connection doesn't stop the listener.
+        if let Err(e) = result {
+            eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+            error!("{}", e);
+        }
+        report(args, format!("Connection from {} closed", peer));
+    }
+}
+
+async fn connect_udp(args: &NetcatArgs) -> Result<()> {
+    let address = lookup_host((args.host.as_str(), args.port))
+        .await
+        .context(format!("Failed to resolve {}", args.host))?
+        .next()
+        .ok_or_else(|| anyhow!("No address found for {}", args.host))?;
+
+    let local_address: SocketAddr = if address.is_ipv4() {
+        "0.0.0.0:0".parse()?
+    } else {
+        "[::]:0".parse()?
+    };
+
+    let socket = UdpSocket::bind(local_address).await?;
+    socket.connect(address).await?;
+    report(args, format!("Sending datagrams to {}", address));
+
+    pipe_datagrams(&socket, Vec::new(), args).await
+}
+
+async fn listen_udp(args: &NetcatArgs) -> Result<()> {
+    let socket = UdpSocket::bind((args.host.as_str(), args.port))
+        .await
+        .context(format!("Failed to listen on {}:{}", args.host, args.port))?;
+
+    report(args, format!("Listening on {}", socket.local_addr()?));
+
+    // UDP has no connection: the first peer that sends something becomes the other side, and
+    // gets what is read from stdin.
+    let mut buffer = vec![0u8; MAX_DATAGRAM_SIZE];
+    let (read, peer) = socket.recv_from(&mut buffer).await?;
+    report(args, format!("Datagram from {}", peer));
+    socket.connect(peer).await?;
+
+    buffer.truncate(read);
+    pipe_datagrams(&socket, buffer, args).await
+}
+
+/// Sends stdin to the stream and writes what comes from it to stdout. When stdin ends, the
+/// sending side is shut down, and the output is written until the other side closes too.
+async fn pipe_stream<S>(stream: S, args: &NetcatArgs) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut reader, mut writer) = tokio::io::split(stream);
+
+    let upload = async {
+        let mut stdin = tokio::io::stdin();
+        let mut dumper = args.hexdump.then(|| HexDumper::new(Direction::Sent));
+        let mut buffer = vec![0u8; BUFFER_SIZE];
+
+        loop {
+            let read = stdin.read(&mut buffer).await?;
+            if read == 0 {
+                info!("stdin closed");
+                writer.shutdown().await?;
+                return Ok::<(), anyhow::Error>(());
+            }
+
+            if let Some(dumper) = dumper.as_mut() {
+                dumper.dump(&buffer[..read]);
+            }
+            writer.write_all(&buffer[..read]).await?;
+        }
+    };
+
+    let download = async {
+        let mut stdout = tokio::io::stdout();
+        let mut dumper = args.hexdump.then(|| HexDumper::new(Direction::Received));
+        let mut buffer = vec![0u8; BUFFER_SIZE];
+
+        loop {
+            let read = reader.read(&mut buffer).await?;
+            if read == 0 {
+                report(args, "Connection closed by the other side");
+                return Ok::<(), anyhow::Error>(());
+            }
+
+            if let Some(dumper) = dumper.as_mut() {
+                dumper.dump(&buffer[..read]);
+            }
+            stdout.write_all(&buffer[..read]).await?;
+            stdout.flush().await?;
+        }
+    };
+
+    tokio::pin!(upload, download);
+    tokio::select! {
+        result = &mut download => result,
+        result = &mut upload => {
+            result?;
+            download.await
+        }
+    }
+}
+
+/// Sends each read from stdin as a datagram, and writes the datagrams received to stdout. Runs
+/// until interrupted: the end of stdin only stops the sending side. `first_datagram` is written
+/// before anything else (the one that revealed the peer, when listening).
+async fn pipe_datagrams(
+    socket: &UdpSocket,
+    first_datagram: Vec<u8>,
+    args: &NetcatArgs,
+) -> Result<()> {
+    let upload = async {
+        let mut stdin = tokio::io::stdin();
+        let mut dumper = args.hexdump.then(|| HexDumper::new(Direction::Sent));
+        let mut buffer = vec![0u8; MAX_DATAGRAM_SIZE];
+
+        loop {
+            let read = stdin.read(&mut buffer).await?;
+            if read == 0 {
+                info!("stdin closed");
+                return Ok::<(), anyhow::Error>(());
+            }
+
+            if let Some(dumper) = dumper.as_mut() {
+                dumper.dump(&buffer[..read]);
+            }
+            socket.send(&buffer[..read]).await?;
+        }
+    };
+
+    let download = receive_datagrams(socket, first_datagram, args);
+
+    tokio::pin!(upload, download);
+    tokio::select! {
+        result = &mut download => result,
+        result = &mut upload => {
+            result?;
+            download.await
+        }
+    }
+}
+
+async fn receive_datagrams(
+    socket: &UdpSocket,
+    first_datagram: Vec<u8>,
+    args: &NetcatArgs,
+) -> Result<()> {
+    let mut stdout = tokio::io::stdout();
+    let mut dumper = args.hexdump.then(|| HexDumper::new(Direction::Received));
+    let mut read = first_datagram.len();
+    let mut buffer = first_datagram;
+    buffer.resize(MAX_DATAGRAM_SIZE, 0);
+
+    loop {
+        if read > 0 {
+            if let Some(dumper) = dumper.as_mut() {
+                dumper.dump(&buffer[..read]);
+            }
+            stdout.write_all(&buffer[..read]).await?;
+            stdout.flush().await?;
+        }
+
+        read = socket.recv(&mut buffer).await?;
+    }
+}
+
+fn load_tls_acceptor(cert: &Path, key: &Path) -> Result<TlsAcceptor> {
+    let cert_pem = std::fs::read(cert).context(format!(
+        "Failed to read the certificate: {}",
+        cert.display()
+    ))?;
+    let key_pem =
+        std::fs::read(key).context(format!("Failed to read the key: {}", key.display()))?;
+
+    let identity = native_tls::Identity::from_pkcs8(&cert_pem, &key_pem)
+        .context("Failed to load the certificate. The key must be in PKCS#8 PEM format")?;
+    let acceptor =
+        native_tls::TlsAcceptor::new(identity).context("Failed to create the TLS acceptor")?;
+
+    Ok(TlsAcceptor::from(acceptor))
+}
+
+/// Connection status, on stderr (stdout carries the traffic). Only with `--verbose`.
+fn report(args: &NetcatArgs, message: impl Display) {
+    info!("{}", message);
+    if args.verbose {
+        eprintln!("{}", message);
+    }
+}
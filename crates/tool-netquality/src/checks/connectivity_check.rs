@@ -65,8 +65,8 @@ pub async fn run_connectivity_check(
 mod tests {
     use super::*;
     use crate::models::{
-        ConnectivityConfig, NotificationConfig, SpeedConfig, StorageConfig, ThresholdCategory,
-        Thresholds,
+        ConnectivityConfig, NotificationConfig, SpeedConfig, SpeedProviderConfig, StorageConfig,
+        ThresholdCategory, Thresholds,
     };
     use std::io::{Read, Write};
     use std::net::TcpListener;
@@ -88,10 +88,12 @@ mod tests {
                 delay: Duration::from_secs(60),
                 download_thresholds: Thresholds::default_thresholds(),
                 upload_thresholds: Thresholds::default_thresholds(),
-                speedtest_cli_path: None,
+                provider: SpeedProviderConfig::Cloudflare,
             },
             notifications: NotificationConfig {
                 telegram: None,
+                webhooks: Vec::new(),
+                email: None,
                 min_download_threshold: ThresholdCategory::Medium,
                 min_upload_threshold: ThresholdCategory::Slow,
             },
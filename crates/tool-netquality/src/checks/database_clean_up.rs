@@ -6,8 +6,11 @@ pub fn run_database_cleanup(mut connection: &mut Connection) {
     match db::cleanup_old_activity(&mut connection) {
         Ok(stats) => {
             info!(
-                "Database cleanup complete: {} sessions, {} connectivity, {} speed rows removed.",
-                stats.sessions_deleted, stats.connectivity_deleted, stats.speed_deleted
+                "Database cleanup complete: {} sessions, {} connectivity, {} speed, {} diagnostics rows removed.",
+                stats.sessions_deleted,
+                stats.connectivity_deleted,
+                stats.speed_deleted,
+                stats.diagnostics_deleted
             );
         }
         Err(error) => {
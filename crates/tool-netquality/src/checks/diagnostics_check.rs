@@ -0,0 +1,550 @@
+use crate::models::{DnsCheck, GatewayCheck, InterfaceStatus, NetQualityConfig, OutageDiagnostics};
+use chrono::Utc;
+use reqwest::Url;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tracing::{debug, trace};
+
+/// Upper bound for every external command, so diagnostics never stall the monitor loop.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the gateway ping waits for a reply.
+const GATEWAY_PING_TIMEOUT_SECS: u64 = 2;
+
+/// `IFF_UP` flag from `/sys/class/net/<iface>/flags`.
+const LINUX_IFF_UP: u32 = 0x1;
+
+/// Runs the gateway ping, DNS resolution, and interface status checks concurrently.
+///
+/// Meant to run only when connectivity goes down. It never fails: a check that can't run is
+/// recorded as failed (gateway), missing (DNS), or empty (interfaces).
+pub async fn run_outage_diagnostics(config: &NetQualityConfig) -> OutageDiagnostics {
+    let timestamp = Utc::now();
+    trace!("Running outage diagnostics");
+
+    let (gateway, dns, interfaces) =
+        tokio::join!(check_gateway(), check_dns(config), read_interfaces());
+
+    OutageDiagnostics {
+        timestamp,
+        gateway,
+        dns,
+        interfaces,
+    }
+}
+
+/// One-line summary for the logs.
+pub fn describe_diagnostics(diagnostics: &OutageDiagnostics) -> String {
+    let gateway = match (&diagnostics.gateway.address, diagnostics.gateway.elapsed_ms) {
+        (None, _) => "no default gateway".to_string(),
+        (Some(address), Some(elapsed_ms)) if diagnostics.gateway.reachable => {
+            format!("gateway {} reachable ({} ms)", address, elapsed_ms)
+        }
+        (Some(address), _) => format!("gateway {} unreachable", address),
+    };
+
+    let dns = match &diagnostics.dns {
+        Some(dns) if dns.success => format!("DNS {} resolved ({} ms)", dns.host, dns.elapsed_ms),
+        Some(dns) => format!("DNS {} failed ({})", dns.host, dns.result),
+        None => "DNS not checked".to_string(),
+    };
+
+    let interfaces = if diagnostics.interfaces.is_empty() {
+        "unknown".to_string()
+    } else {
+        diagnostics
+            .interfaces
+            .iter()
+            .map(|interface| format!("{} {}", interface.name, interface.state))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    format!("{}; {}; interfaces: {}", gateway, dns, interfaces)
+}
+
+async fn check_gateway() -> GatewayCheck {
+    let Some((address, interface)) = find_default_gateway().await else {
+        return GatewayCheck {
+            address: None,
+            interface: None,
+            reachable: false,
+            elapsed_ms: None,
+        };
+    };
+
+    let start_time = Instant::now();
+    let reachable = ping(address).await;
+    let elapsed_ms = start_time.elapsed().as_millis() as i64;
+
+    GatewayCheck {
+        address: Some(address),
+        interface,
+        reachable,
+        elapsed_ms: reachable.then_some(elapsed_ms),
+    }
+}
+
+async fn check_dns(config: &NetQualityConfig) -> Option<DnsCheck> {
+    let host = config
+        .connectivity
+        .urls
+        .iter()
+        .find_map(|url| resolvable_host(url))?;
+
+    let start_time = Instant::now();
+    let lookup = tokio::time::timeout(
+        config.connectivity.timeout,
+        tokio::net::lookup_host((host.as_str(), 0)),
+    )
+    .await;
+
+    let (success, result) = match lookup {
+        Ok(Ok(mut addresses)) => match addresses.next() {
+            Some(address) => (true, address.ip().to_string()),
+            None => (false, "no addresses".to_string()),
+        },
+        Ok(Err(error)) => (false, error.to_string()),
+        Err(_) => (false, "timeout".to_string()),
+    };
+
+    Some(DnsCheck {
+        host,
+        success,
+        result,
+        elapsed_ms: start_time.elapsed().as_millis() as i64,
+    })
+}
+
+/// Host of the URL, unless it's an IP literal (nothing to resolve).
+fn resolvable_host(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?;
+
+    let is_ip = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .is_ok();
+
+    if is_ip {
+        None
+    } else {
+        Some(host.to_string())
+    }
+}
+
+/// Pings with the system `ping`, which doesn't need raw socket privileges.
+async fn ping(address: IpAddr) -> bool {
+    let address = address.to_string();
+    let timeout_secs = GATEWAY_PING_TIMEOUT_SECS.to_string();
+    let timeout_ms = (GATEWAY_PING_TIMEOUT_SECS * 1000).to_string();
+
+    let args: [&str; 5] = if cfg!(windows) {
+        ["-n", "1", "-w", &timeout_ms, &address]
+    } else if cfg!(target_os = "macos") {
+        ["-c", "1", "-t", &timeout_secs, &address]
+    } else {
+        ["-c", "1", "-W", &timeout_secs, &address]
+    };
+
+    run_command("ping", &args)
+        .await
+        .map(|output| ping_reply_received(&output))
+        .unwrap_or(false)
+}
+
+/// Every platform prints the TTL of a reply (`ttl=64` / `TTL=64`). Checking the output instead of
+/// the exit code also catches Windows reporting "Destination host unreachable" as success.
+fn ping_reply_received(output: &str) -> bool {
+    output.to_ascii_lowercase().contains("ttl=")
+}
+
+/// Returns the stdout of the command, regardless of its exit code.
+async fn run_command(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args).kill_on_drop(true);
+
+    match tokio::time::timeout(COMMAND_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) => Some(String::from_utf8_lossy(&output.stdout).to_string()),
+        Ok(Err(error)) => {
+            debug!("Failed to run {}: {}", program, error);
+            None
+        }
+        Err(_) => {
+            debug!("{} timed out", program);
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn find_default_gateway() -> Option<(IpAddr, Option<String>)> {
+    let routes = tokio::fs::read_to_string("/proc/net/route").await.ok()?;
+    parse_linux_default_route(&routes)
+}
+
+#[cfg(target_os = "macos")]
+async fn find_default_gateway() -> Option<(IpAddr, Option<String>)> {
+    let output = run_command("route", &["-n", "get", "default"]).await?;
+    parse_macos_default_route(&output)
+}
+
+#[cfg(windows)]
+async fn find_default_gateway() -> Option<(IpAddr, Option<String>)> {
+    let output = run_command("route", &["print", "-4", "0.0.0.0"]).await?;
+    parse_windows_default_route(&output)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+async fn find_default_gateway() -> Option<(IpAddr, Option<String>)> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+async fn read_interfaces() -> Vec<InterfaceStatus> {
+    read_linux_interfaces(Path::new("/sys/class/net"))
+}
+
+#[cfg(target_os = "macos")]
+async fn read_interfaces() -> Vec<InterfaceStatus> {
+    run_command("ifconfig", &[])
+        .await
+        .map(|output| parse_macos_interfaces(&output))
+        .unwrap_or_default()
+}
+
+#[cfg(windows)]
+async fn read_interfaces() -> Vec<InterfaceStatus> {
+    run_command("netsh", &["interface", "show", "interface"])
+        .await
+        .map(|output| parse_windows_interfaces(&output))
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+async fn read_interfaces() -> Vec<InterfaceStatus> {
+    Vec::new()
+}
+
+/// Parses `/proc/net/route`: the default route has destination `00000000`, and the gateway is a
+/// little-endian hex IPv4 address. The route with the lowest metric wins.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_linux_default_route(routes: &str) -> Option<(IpAddr, Option<String>)> {
+    routes
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 7 || fields[1] != "00000000" {
+                return None;
+            }
+
+            let gateway = u32::from_str_radix(fields[2], 16).ok()?;
+            if gateway == 0 {
+                return None;
+            }
+
+            let metric = fields[6].parse::<u32>().unwrap_or(u32::MAX);
+            let address = IpAddr::V4(Ipv4Addr::from(gateway.to_le_bytes()));
+            Some((metric, address, fields[0].to_string()))
+        })
+        .min_by_key(|(metric, _, _)| *metric)
+        .map(|(_, address, interface)| (address, Some(interface)))
+}
+
+/// Parses `route -n get default` (`gateway: 192.168.1.1` / `interface: en0`).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_macos_default_route(output: &str) -> Option<(IpAddr, Option<String>)> {
+    let value_of = |key: &str| {
+        output.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == key).then(|| value.trim().to_string())
+        })
+    };
+
+    let address = value_of("gateway")?.parse::<IpAddr>().ok()?;
+    Some((address, value_of("interface")))
+}
+
+/// Parses `route print -4 0.0.0.0`. The interface column holds the local address of the adapter.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_windows_default_route(output: &str) -> Option<(IpAddr, Option<String>)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 5 || fields[0] != "0.0.0.0" || fields[1] != "0.0.0.0" {
+                return None;
+            }
+
+            // On-link routes have no gateway.
+            let address = fields[2].parse::<IpAddr>().ok()?;
+            let metric = fields[4].parse::<u32>().unwrap_or(u32::MAX);
+            Some((metric, address, fields[3].to_string()))
+        })
+        .min_by_key(|(metric, _, _)| *metric)
+        .map(|(_, address, interface)| (address, Some(interface)))
+}
+
+/// Reads `<root>/<iface>/operstate` and `<root>/<iface>/flags`, skipping the loopback.
+///
+/// Virtual interfaces (e.g.: WireGuard) report `unknown`, so the `IFF_UP` flag decides for them.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn read_linux_interfaces(root: &Path) -> Vec<InterfaceStatus> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut interfaces: Vec<InterfaceStatus> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == "lo" {
+                return None;
+            }
+
+            let read = |file: &str| {
+                std::fs::read_to_string(entry.path().join(file))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+
+            let state = read("operstate");
+            let flags =
+                u32::from_str_radix(read("flags").trim_start_matches("0x"), 16).unwrap_or(0);
+            let up = state == "up" || (state == "unknown" && flags & LINUX_IFF_UP != 0);
+
+            Some(InterfaceStatus { name, up, state })
+        })
+        .collect();
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+/// Parses `ifconfig`: interfaces start at column 0 (`en0: flags=8863<UP,...>`), and may have an
+/// indented `status: active|inactive` line. Skips the loopback.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_macos_interfaces(output: &str) -> Vec<InterfaceStatus> {
+    let mut interfaces: Vec<InterfaceStatus> = Vec::new();
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let Some((name, rest)) = line.split_once(": flags=") else {
+                continue;
+            };
+
+            let up = rest
+                .split_once('<')
+                .and_then(|(_, flags)| flags.split_once('>'))
+                .map(|(flags, _)| flags.split(',').any(|flag| flag == "UP"))
+                .unwrap_or(false);
+
+            interfaces.push(InterfaceStatus {
+                name: name.to_string(),
+                up,
+                state: if up { "up" } else { "down" }.to_string(),
+            });
+            continue;
+        }
+
+        let Some(status) = line.trim().strip_prefix("status:") else {
+            continue;
+        };
+
+        if let Some(interface) = interfaces.last_mut() {
+            interface.state = status.trim().to_string();
+            interface.up = interface.up && interface.state == "active";
+        }
+    }
+
+    interfaces.retain(|interface| !interface.name.starts_with("lo"));
+    interfaces
+}
+
+/// Parses `netsh interface show interface` (`Admin State`, `State`, `Type`, `Interface Name`).
+/// Names may contain spaces, so everything after the third column is the name.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_windows_interfaces(output: &str) -> Vec<InterfaceStatus> {
+    output
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 4 {
+                return None;
+            }
+
+            let state = fields[1].to_lowercase();
+            Some(InterfaceStatus {
+                name: fields[3..].join(" "),
+                up: state == "connected",
+                state,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn linux_default_route_uses_lowest_metric() {
+        let routes = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+eth0\t00000000\t0102A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t0002A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+";
+
+        let (address, interface) = parse_linux_default_route(routes).expect("default route");
+
+        assert_eq!(address, "192.168.2.1".parse::<IpAddr>().unwrap());
+        assert_eq!(interface.as_deref(), Some("eth0"));
+        assert_eq!(parse_linux_default_route("Iface\tDestination\n"), None);
+    }
+
+    #[test]
+    fn macos_and_windows_default_routes_are_parsed() {
+        let macos =
+            "   route to: default\ndestination: default\n    gateway: 10.0.0.1\n  interface: en0\n";
+        let windows = "\
+IPv4 Route Table
+===========================================================================
+Active Routes:
+Network Destination        Netmask          Gateway       Interface  Metric
+          0.0.0.0          0.0.0.0      192.168.1.1    192.168.1.10     25
+          0.0.0.0          0.0.0.0         On-link      10.8.0.2        5
+===========================================================================
+";
+
+        assert_eq!(
+            parse_macos_default_route(macos),
+            Some(("10.0.0.1".parse().unwrap(), Some("en0".to_string())))
+        );
+        assert_eq!(
+            parse_windows_default_route(windows),
+            Some((
+                "192.168.1.1".parse().unwrap(),
+                Some("192.168.1.10".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn interface_listings_are_parsed() {
+        let macos = "\
+lo0: flags=8049<UP,LOOPBACK,RUNNING,MULTICAST> mtu 16384
+\tinet 127.0.0.1 netmask 0xff000000
+en0: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+\tstatus: inactive
+en1: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+\tstatus: active
+";
+        let windows = "
+Admin State    State          Type             Interface Name
+-------------------------------------------------------------------------
+Enabled        Connected      Dedicated        Ethernet
+Disabled       Disconnected   Dedicated        Wi-Fi 2
+";
+
+        let macos = parse_macos_interfaces(macos);
+        assert_eq!(macos.len(), 2);
+        assert_eq!((macos[0].name.as_str(), macos[0].up), ("en0", false));
+        assert_eq!(macos[0].state, "inactive");
+        assert_eq!((macos[1].name.as_str(), macos[1].up), ("en1", true));
+
+        let windows = parse_windows_interfaces(windows);
+        assert_eq!(windows.len(), 2);
+        assert!(windows[0].up);
+        assert_eq!(windows[1].name, "Wi-Fi 2");
+        assert_eq!(windows[1].state, "disconnected");
+    }
+
+    #[test]
+    fn linux_interfaces_are_read_from_sysfs() {
+        let root = std::env::temp_dir().join(format!(
+            "netquality-sysfs-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+
+        for (name, state, flags) in [
+            ("lo", "unknown", "0x9"),
+            ("eth0", "down", "0x1002"),
+            ("wg0", "unknown", "0x1091"),
+        ] {
+            let dir = root.join(name);
+            fs::create_dir_all(&dir).expect("create interface dir");
+            fs::write(dir.join("operstate"), format!("{}\n", state)).expect("write operstate");
+            fs::write(dir.join("flags"), format!("{}\n", flags)).expect("write flags");
+        }
+
+        let interfaces = read_linux_interfaces(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(
+            (interfaces[0].name.as_str(), interfaces[0].up),
+            ("eth0", false)
+        );
+        assert_eq!(
+            (interfaces[1].name.as_str(), interfaces[1].up),
+            ("wg0", true)
+        );
+    }
+
+    #[test]
+    fn helpers_handle_hosts_and_ping_output() {
+        assert_eq!(
+            resolvable_host("https://www.google.com/generate_204"),
+            Some("www.google.com".to_string())
+        );
+        assert_eq!(resolvable_host("http://1.1.1.1"), None);
+        assert_eq!(resolvable_host("http://[::1]:8080"), None);
+
+        assert!(ping_reply_received(
+            "64 bytes from 192.168.1.1: icmp_seq=1 ttl=64 time=1.2 ms"
+        ));
+        assert!(ping_reply_received(
+            "Reply from 192.168.1.1: bytes=32 time=1ms TTL=64"
+        ));
+        assert!(!ping_reply_received(
+            "Reply from 192.168.1.10: Destination host unreachable."
+        ));
+    }
+
+    #[test]
+    fn describe_summarizes_every_check() {
+        let diagnostics = OutageDiagnostics {
+            timestamp: Utc::now(),
+            gateway: GatewayCheck {
+                address: Some("192.168.1.1".parse().unwrap()),
+                interface: Some("eth0".to_string()),
+                reachable: true,
+                elapsed_ms: Some(3),
+            },
+            dns: Some(DnsCheck {
+                host: "example.com".to_string(),
+                success: false,
+                result: "timeout".to_string(),
+                elapsed_ms: 5000,
+            }),
+            interfaces: vec![InterfaceStatus {
+                name: "eth0".to_string(),
+                up: true,
+                state: "up".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            describe_diagnostics(&diagnostics),
+            "gateway 192.168.1.1 reachable (3 ms); DNS example.com failed (timeout); interfaces: eth0 up"
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod connectivity_check;
 pub mod database_clean_up;
+pub mod diagnostics_check;
+pub mod speed_providers;
 pub mod speed_test_check;
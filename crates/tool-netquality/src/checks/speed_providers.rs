@@ -0,0 +1,488 @@
+use crate::models::{HttpSpeedConfig, Iperf3Config, SpeedProviderConfig};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use cfspeedtest::measurements::Measurement;
+use cfspeedtest::speedtest::TestType;
+use cfspeedtest::{OutputFormat, SpeedTestCLIOptions};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tokio::task::spawn_blocking;
+use tokio::time::timeout;
+
+/// Extra time given to iperf3, on top of the test duration, to connect and exchange the results.
+const IPERF3_EXTRA_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy)]
+pub struct SpeedTestMeasurement {
+    pub download_mbps: f64,
+    pub upload_mbps: Option<f64>,
+    pub elapsed_ms: i64,
+}
+
+/// Measures the download (and optionally the upload) speed of the connection.
+#[async_trait]
+pub trait SpeedProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn measure(&self, measure_upload: bool) -> Result<SpeedTestMeasurement>;
+}
+
+/// Cloudflare speed endpoints, through the embedded `cfspeedtest` crate.
+pub struct CloudflareSpeedProvider;
+
+/// Ookla speedtest CLI, which must be installed separately.
+pub struct OoklaSpeedProvider {
+    cli_path: PathBuf,
+}
+
+/// Plain HTTP transfers: a GET of the download URL and a POST of generated data to the upload URL.
+pub struct HttpSpeedProvider {
+    client: reqwest::Client,
+    config: HttpSpeedConfig,
+}
+
+/// iperf3 client against a user provided server. Download uses reverse mode (`-R`).
+pub struct Iperf3SpeedProvider {
+    config: Iperf3Config,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpeedtestCliOutput {
+    download: SpeedtestCliTransfer,
+    upload: Option<SpeedtestCliTransfer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpeedtestCliTransfer {
+    bandwidth: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Iperf3Output {
+    end: Option<Iperf3End>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Iperf3End {
+    sum_received: Option<Iperf3Sum>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Iperf3Sum {
+    bits_per_second: f64,
+}
+
+/// Creates the provider selected in the speed configuration.
+pub fn create_speed_provider(config: &SpeedProviderConfig) -> Result<Box<dyn SpeedProvider>> {
+    let provider: Box<dyn SpeedProvider> = match config {
+        SpeedProviderConfig::Cloudflare => Box::new(CloudflareSpeedProvider),
+        SpeedProviderConfig::Ookla { cli_path } => Box::new(OoklaSpeedProvider {
+            cli_path: cli_path.clone(),
+        }),
+        SpeedProviderConfig::Http(http) => Box::new(HttpSpeedProvider::new(http.clone())?),
+        SpeedProviderConfig::Iperf3(iperf3) => Box::new(Iperf3SpeedProvider {
+            config: iperf3.clone(),
+        }),
+    };
+
+    Ok(provider)
+}
+
+#[async_trait]
+impl SpeedProvider for CloudflareSpeedProvider {
+    fn name(&self) -> &'static str {
+        "Cloudflare"
+    }
+
+    async fn measure(&self, measure_upload: bool) -> Result<SpeedTestMeasurement> {
+        spawn_blocking(move || -> Result<SpeedTestMeasurement> {
+            let client = reqwest::blocking::Client::builder()
+                .timeout(Duration::from_secs(240))
+                .build()
+                .context("Failed to build speed test client")?;
+
+            let options = SpeedTestCLIOptions {
+                nr_tests: 20,
+                nr_latency_tests: 25,
+                max_payload_size: cfspeedtest::speedtest::PayloadSize::M100,
+                output_format: OutputFormat::None,
+                verbose: true,
+                ipv4: None,
+                ipv6: None,
+                disable_dynamic_max_payload_size: true,
+                download_only: !measure_upload,
+                upload_only: false, // It is either only download or both.
+                completion: None,
+            };
+
+            let start = Instant::now();
+            let measurements = cfspeedtest::speedtest::speed_test(client, options);
+            let elapsed_ms = start.elapsed().as_millis() as i64;
+
+            let download_mbps = average_mbit(&measurements, TestType::Download)
+                .ok_or_else(|| anyhow!("Speed test did not return download measurements"))?;
+
+            let upload_mbps = if measure_upload {
+                average_mbit(&measurements, TestType::Upload)
+            } else {
+                None
+            };
+
+            Ok(SpeedTestMeasurement {
+                download_mbps,
+                upload_mbps,
+                elapsed_ms,
+            })
+        })
+        .await
+        .context("Speed check task failed")?
+    }
+}
+
+#[async_trait]
+impl SpeedProvider for OoklaSpeedProvider {
+    fn name(&self) -> &'static str {
+        "Speedtest CLI"
+    }
+
+    async fn measure(&self, measure_upload: bool) -> Result<SpeedTestMeasurement> {
+        let path = self.cli_path.clone();
+
+        spawn_blocking(move || -> Result<SpeedTestMeasurement> {
+            let mut command = Command::new(path);
+            command
+                .arg("--format")
+                .arg("json")
+                .arg("--accept-license")
+                .arg("--accept-gdpr");
+
+            if !measure_upload {
+                command.arg("--download-only");
+            }
+
+            let start = Instant::now();
+            let output = command.output().context("Failed to start speedtest CLI")?;
+            let elapsed_ms = start.elapsed().as_millis() as i64;
+
+            if !output.status.success() {
+                return Err(anyhow!(
+                    "Speedtest CLI exited with status {}",
+                    output.status
+                ));
+            }
+
+            let stdout = String::from_utf8(output.stdout)
+                .context("Speedtest CLI output was not valid UTF-8")?;
+            let parsed: SpeedtestCliOutput = serde_json::from_str(&stdout)
+                .context("Failed to parse speedtest CLI JSON output")?;
+
+            let download_mbps = bytes_per_sec_to_mbps(parsed.download.bandwidth);
+            let upload_mbps = parsed
+                .upload
+                .map(|upload| bytes_per_sec_to_mbps(upload.bandwidth));
+
+            Ok(SpeedTestMeasurement {
+                download_mbps,
+                upload_mbps,
+                elapsed_ms,
+            })
+        })
+        .await
+        .context("Speedtest CLI task failed")?
+    }
+}
+
+impl HttpSpeedProvider {
+    pub fn new(config: HttpSpeedConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .context("Failed to build speed test client")?;
+
+        Ok(Self { client, config })
+    }
+
+    async fn download(&self) -> Result<f64> {
+        let mut response = self
+            .client
+            .get(&self.config.download_url)
+            .send()
+            .await
+            .context("Failed to request the speed test download URL")?
+            .error_for_status()
+            .context("Speed test download URL returned an error")?;
+
+        // Only the body transfer is timed, so the connection setup doesn't lower the result.
+        let start = Instant::now();
+        let mut bytes = 0u64;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context("Failed to read the speed test download")?
+        {
+            bytes += chunk.len() as u64;
+        }
+
+        if bytes == 0 {
+            return Err(anyhow!("Speed test download URL returned an empty body"));
+        }
+
+        Ok(transfer_mbps(bytes, start.elapsed()))
+    }
+
+    async fn upload(&self, url: &str) -> Result<f64> {
+        let body = vec![0u8; self.config.upload_size_bytes as usize];
+
+        let start = Instant::now();
+        self.client
+            .post(url)
+            .body(body)
+            .send()
+            .await
+            .context("Failed to send the speed test upload")?
+            .error_for_status()
+            .context("Speed test upload URL returned an error")?;
+
+        Ok(transfer_mbps(
+            self.config.upload_size_bytes,
+            start.elapsed(),
+        ))
+    }
+}
+
+#[async_trait]
+impl SpeedProvider for HttpSpeedProvider {
+    fn name(&self) -> &'static str {
+        "HTTP"
+    }
+
+    async fn measure(&self, measure_upload: bool) -> Result<SpeedTestMeasurement> {
+        let start = Instant::now();
+        let download_mbps = self.download().await?;
+
+        let upload_mbps = match (measure_upload, self.config.upload_url.as_deref()) {
+            (true, Some(url)) => Some(self.upload(url).await?),
+            _ => None,
+        };
+
+        Ok(SpeedTestMeasurement {
+            download_mbps,
+            upload_mbps,
+            elapsed_ms: start.elapsed().as_millis() as i64,
+        })
+    }
+}
+
+impl Iperf3SpeedProvider {
+    async fn run(&self, reverse: bool) -> Result<f64> {
+        let mut command = tokio::process::Command::new(&self.config.binary_path);
+        command
+            .arg("-c")
+            .arg(&self.config.server)
+            .arg("-p")
+            .arg(self.config.port.to_string())
+            .arg("-t")
+            .arg(self.config.duration.as_secs().to_string())
+            .arg("-J")
+            .kill_on_drop(true);
+
+        if reverse {
+            command.arg("-R");
+        }
+
+        let limit = self.config.duration + IPERF3_EXTRA_TIMEOUT;
+        let output = timeout(limit, command.output())
+            .await
+            .map_err(|_| anyhow!("iperf3 timed out after {}s", limit.as_secs()))?
+            .context("Failed to start iperf3")?;
+
+        // With -J, iperf3 reports its errors in the JSON output as well.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let result = parse_iperf3_output(&stdout);
+
+        if result.is_ok() && !output.status.success() {
+            return Err(anyhow!("iperf3 exited with status {}", output.status));
+        }
+
+        result
+    }
+}
+
+#[async_trait]
+impl SpeedProvider for Iperf3SpeedProvider {
+    fn name(&self) -> &'static str {
+        "iperf3"
+    }
+
+    async fn measure(&self, measure_upload: bool) -> Result<SpeedTestMeasurement> {
+        let start = Instant::now();
+        let download_mbps = self.run(true).await?;
+
+        let upload_mbps = if measure_upload {
+            Some(self.run(false).await?)
+        } else {
+            None
+        };
+
+        Ok(SpeedTestMeasurement {
+            download_mbps,
+            upload_mbps,
+            elapsed_ms: start.elapsed().as_millis() as i64,
+        })
+    }
+}
+
+fn parse_iperf3_output(json: &str) -> Result<f64> {
+    let parsed: Iperf3Output =
+        serde_json::from_str(json).context("Failed to parse iperf3 JSON output")?;
+
+    if let Some(error) = parsed.error {
+        return Err(anyhow!("iperf3 failed: {}", error));
+    }
+
+    parsed
+        .end
+        .and_then(|end| end.sum_received)
+        .map(|sum| sum.bits_per_second / 1_000_000.0)
+        .ok_or_else(|| anyhow!("iperf3 output did not contain the received throughput"))
+}
+
+fn transfer_mbps(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    (bytes as f64 * 8.0) / secs / 1_000_000.0
+}
+
+fn bytes_per_sec_to_mbps(bytes_per_sec: f64) -> f64 {
+    (bytes_per_sec * 8.0) / 1_000_000.0
+}
+
+fn average_mbit(measurements: &[Measurement], test_type: TestType) -> Option<f64> {
+    let max_payload_size = measurements
+        .iter()
+        .filter(|measurement| measurement.test_type == test_type)
+        .map(|measurement| measurement.payload_size)
+        .max()?;
+
+    let values: Vec<f64> = measurements
+        .iter()
+        .filter(|measurement| measurement.test_type == test_type)
+        .filter(|measurement| measurement.payload_size == max_payload_size)
+        .map(|measurement| measurement.mbit)
+        .collect();
+
+    if values.is_empty() {
+        return None;
+    }
+
+    let sum: f64 = values.iter().sum();
+    Some(sum / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_per_sec_conversion_to_mbps() {
+        let mbps = bytes_per_sec_to_mbps(1_250_000.0);
+        assert!((mbps - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn transfer_mbps_uses_elapsed_time() {
+        let mbps = transfer_mbps(12_500_000, Duration::from_secs(2));
+        assert!((mbps - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn average_mbit_uses_max_payload_size() {
+        let measurements = vec![
+            Measurement {
+                test_type: TestType::Download,
+                payload_size: 1_000,
+                mbit: 5.0,
+            },
+            Measurement {
+                test_type: TestType::Download,
+                payload_size: 2_000,
+                mbit: 10.0,
+            },
+            Measurement {
+                test_type: TestType::Download,
+                payload_size: 2_000,
+                mbit: 20.0,
+            },
+            Measurement {
+                test_type: TestType::Upload,
+                payload_size: 2_000,
+                mbit: 30.0,
+            },
+        ];
+
+        let average = average_mbit(&measurements, TestType::Download).expect("average");
+        assert!((average - 15.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn parse_iperf3_output_reads_received_throughput() {
+        let json = r#"{
+            "start": {"connected": []},
+            "end": {
+                "sum_sent": {"bits_per_second": 94500000.0},
+                "sum_received": {"bits_per_second": 93800000.0}
+            }
+        }"#;
+
+        let mbps = parse_iperf3_output(json).unwrap();
+        assert!((mbps - 93.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_iperf3_output_surfaces_errors() {
+        let json = r#"{"start": {}, "end": {}, "error": "unable to connect to server: Connection refused"}"#;
+
+        let error = parse_iperf3_output(json).unwrap_err();
+        assert!(error.to_string().contains("Connection refused"));
+    }
+
+    #[tokio::test]
+    async fn http_provider_measures_download() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let addr = listener.local_addr().expect("server addr");
+        let handle = std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buffer = [0u8; 1024];
+                let _ = stream.read(&mut buffer);
+                let body = vec![b'x'; 200_000];
+                let header = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(header.as_bytes());
+                let _ = stream.write_all(&body);
+                let _ = stream.flush();
+            }
+        });
+
+        let provider = HttpSpeedProvider::new(HttpSpeedConfig {
+            download_url: format!("http://{}", addr),
+            upload_url: None,
+            upload_size_bytes: 1_000_000,
+            timeout: Duration::from_secs(10),
+        })
+        .unwrap();
+
+        let measurement = provider.measure(true).await.unwrap();
+        handle.join().expect("server thread");
+
+        assert!(measurement.download_mbps > 0.0);
+        // Without an upload URL, the upload isn't measured even when expected.
+        assert!(measurement.upload_mbps.is_none());
+    }
+}
@@ -1,19 +1,34 @@
-use crate::models::{NetQualityCliArgs, ThresholdCategory, Thresholds};
+use crate::models::{
+    IncidentFormat, IncidentsArgs, NetQualityCliArgs, NetQualityCommand, ReportArgs, ReportFormat,
+    ServeArgs, SpeedProviderKind, ThresholdCategory, Thresholds, WebhookConfig, WebhookFormat,
+};
 use anyhow::{anyhow, Context, Result};
-use clap::{Arg, ArgAction, Command};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, Utc};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
+use shared::logging::logging_helpers::LogSettings;
+use std::ffi::OsString;
+use std::net::IpAddr;
 use std::path::PathBuf;
 
-pub fn get_cli_arguments() -> Result<NetQualityCliArgs> {
+const DEFAULT_SERVE_PORT: u16 = 4280;
+const DEFAULT_SERVE_DAYS: u32 = 7;
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<NetQualityCliArgs> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
             env!("CARGO_PKG_DESCRIPTION"),
-            "Monitor connectivity and speed with notifications, saving the results to a SQLite database, allowing you to analyze them later. Notification to Telegram and/or Open Telemetry is also available.",
+            "Monitor connectivity and speed with notifications, saving the results to a SQLite database, allowing you to analyze them later. Notifications to Telegram, webhooks (generic, Slack, Discord), email, and/or Open Telemetry are also available.",
         )
         .preset_arg_config(None)
         .preset_arg_verbose(None)
+        .preset_args_log_file()
+        // Also accepted after the subcommands, so `netquality serve --db-path ...` works.
+        .mut_arg("config", |arg| arg.global(true))
+        .mut_arg("verbose", |arg| arg.global(true))
         .arg(
             Arg::new("url")
                 .long("url")
@@ -105,6 +120,7 @@ pub fn get_cli_arguments() -> Result<NetQualityCliArgs> {
                 .long("db-path")
                 .value_name("FILE")
                 .help("SQLite database path")
+                .global(true)
                 .value_parser(clap::value_parser!(PathBuf)),
         )
         .arg(
@@ -114,6 +130,31 @@ pub fn get_cli_arguments() -> Result<NetQualityCliArgs> {
                 .help("Path to Ookla speedtest CLI binary")
                 .value_parser(clap::value_parser!(PathBuf)),
         )
+        .arg(
+            Arg::new("speed-provider")
+                .long("speed-provider")
+                .value_name("PROVIDER")
+                .help("Speed test provider: cloudflare, ookla, http, or iperf3 (default: cloudflare, or ookla when --speedtest-cli-path is set)")
+                .value_parser(["cloudflare", "ookla", "http", "iperf3"]),
+        )
+        .arg(
+            Arg::new("speed-download-url")
+                .long("speed-download-url")
+                .value_name("URL")
+                .help("URL downloaded by the http speed provider"),
+        )
+        .arg(
+            Arg::new("speed-upload-url")
+                .long("speed-upload-url")
+                .value_name("URL")
+                .help("URL that receives the upload (POST) of the http speed provider"),
+        )
+        .arg(
+            Arg::new("iperf3-server")
+                .long("iperf3-server")
+                .value_name("HOST")
+                .help("Server used by the iperf3 speed provider"),
+        )
         .arg(
             Arg::new("telegram-token")
                 .long("telegram-token")
@@ -126,13 +167,139 @@ pub fn get_cli_arguments() -> Result<NetQualityCliArgs> {
                 .value_name("CHAT")
                 .help("Telegram chat ID"),
         )
+        .arg(
+            Arg::new("webhook-url")
+                .long("webhook-url")
+                .action(ArgAction::Append)
+                .value_name("URL")
+                .help("Webhook that receives notifications as JSON POST requests (repeatable)"),
+        )
+        .arg(
+            Arg::new("slack-webhook-url")
+                .long("slack-webhook-url")
+                .action(ArgAction::Append)
+                .value_name("URL")
+                .help("Slack incoming webhook URL (repeatable)"),
+        )
+        .arg(
+            Arg::new("discord-webhook-url")
+                .long("discord-webhook-url")
+                .action(ArgAction::Append)
+                .value_name("URL")
+                .help("Discord webhook URL (repeatable)"),
+        )
         .arg(
             Arg::new("otel-endpoint")
                 .long("otel-endpoint")
                 .value_name("URL")
                 .help("OpenTelemetry OTLP endpoint"),
         )
-        .get_matches();
+        .subcommand(
+            Command::new("serve")
+                .about("Starts a local web dashboard with charts (uptime, speed over time, outages) from the database")
+                .arg(
+                    Arg::new("host")
+                        .short('o')
+                        .long("host")
+                        .value_name("HOST")
+                        .help("Host that will be used to bind the server (default: 127.0.0.1)")
+                        .default_value("127.0.0.1")
+                        .value_parser(clap::value_parser!(IpAddr)),
+                )
+                .arg(
+                    Arg::new("port")
+                        .short('p')
+                        .long("port")
+                        .value_name("PORT")
+                        .help(format!("Port number to listen on (default: {})", DEFAULT_SERVE_PORT))
+                        .value_parser(clap::value_parser!(u16)),
+                )
+                .arg(
+                    Arg::new("days")
+                        .long("days")
+                        .value_name("DAYS")
+                        .help(format!("Time range shown when the dashboard opens, in days (default: {})", DEFAULT_SERVE_DAYS))
+                        .value_parser(clap::value_parser!(u32).range(1..)),
+                ),
+        )
+        .subcommand(
+            Command::new("report")
+                .about("Summarizes the checks in a time range: uptime, outages, and speed percentiles")
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .value_name("DATE")
+                        .help(format!("Start of the range (inclusive), as YYYY-MM-DD (UTC) or RFC3339 (default: {} days before --to)", DEFAULT_REPORT_DAYS)),
+                )
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .value_name("DATE")
+                        .help("End of the range, as YYYY-MM-DD (UTC, whole day included) or RFC3339 (exclusive) (default: now)"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("Output format: text, csv, or json (default: text)")
+                        .default_value("text"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Writes the report to this file instead of the console")
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("incidents")
+                .about("Compiles outages, degraded speed periods, and speed test evidence into a timeline for ISP support tickets")
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .value_name("DATE")
+                        .help(format!("Start of the range (inclusive), as YYYY-MM-DD (UTC) or RFC3339 (default: {} days before --to)", DEFAULT_REPORT_DAYS)),
+                )
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .value_name("DATE")
+                        .help("End of the range, as YYYY-MM-DD (UTC, whole day included) or RFC3339 (exclusive) (default: now)"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("Output format: pdf, md, or csv (default: md)")
+                        .default_value("md"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Writes the timeline to this file instead of the console (required for pdf)")
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        )
+        .get_matches_from(argv);
+
+    let command = match matches.subcommand() {
+        Some(("serve", serve_matches)) => {
+            NetQualityCommand::Serve(parse_serve_arguments(serve_matches))
+        }
+        Some(("report", report_matches)) => {
+            NetQualityCommand::Report(parse_report_arguments(report_matches, Utc::now())?)
+        }
+        Some(("incidents", incidents_matches)) => {
+            NetQualityCommand::Incidents(parse_incidents_arguments(incidents_matches, Utc::now())?)
+        }
+        _ => NetQualityCommand::Monitor,
+    };
 
     let urls: Vec<String> = matches
         .get_many::<String>("url")
@@ -168,6 +335,24 @@ pub fn get_cli_arguments() -> Result<NetQualityCliArgs> {
         ));
     }
 
+    let webhooks = [
+        ("webhook-url", WebhookFormat::Generic),
+        ("slack-webhook-url", WebhookFormat::Slack),
+        ("discord-webhook-url", WebhookFormat::Discord),
+    ]
+    .into_iter()
+    .flat_map(|(arg, format)| {
+        matches
+            .get_many::<String>(arg)
+            .into_iter()
+            .flatten()
+            .map(move |url| WebhookConfig {
+                url: url.clone(),
+                format,
+            })
+    })
+    .collect();
+
     Ok(NetQualityCliArgs {
         config_path: matches.get_one::<PathBuf>("config").cloned(),
         urls,
@@ -185,13 +370,137 @@ pub fn get_cli_arguments() -> Result<NetQualityCliArgs> {
         outage_backoff_max_secs: matches.get_one::<u64>("outage-backoff-max").copied(),
         db_path: matches.get_one::<PathBuf>("db-path").cloned(),
         speedtest_cli_path: matches.get_one::<PathBuf>("speedtest-cli-path").cloned(),
+        speed_provider: matches
+            .get_one::<String>("speed-provider")
+            .and_then(|value| SpeedProviderKind::from_str_value(value)),
+        speed_download_url: matches.get_one::<String>("speed-download-url").cloned(),
+        speed_upload_url: matches.get_one::<String>("speed-upload-url").cloned(),
+        iperf3_server: matches.get_one::<String>("iperf3-server").cloned(),
         telegram_token,
         telegram_chat_id,
+        webhooks,
         otel_endpoint: matches.get_one::<String>("otel-endpoint").cloned(),
         verbose: matches.get_flag("verbose"),
+        log_settings: LogSettings::from_matches(&matches)?,
+        command,
     })
 }
 
+fn parse_serve_arguments(matches: &ArgMatches) -> ServeArgs {
+    ServeArgs {
+        host: matches
+            .get_one::<IpAddr>("host")
+            .copied()
+            .unwrap_or_else(|| IpAddr::from([127, 0, 0, 1])),
+        port: matches
+            .get_one::<u16>("port")
+            .copied()
+            .unwrap_or(DEFAULT_SERVE_PORT),
+        days: matches
+            .get_one::<u32>("days")
+            .copied()
+            .unwrap_or(DEFAULT_SERVE_DAYS),
+    }
+}
+
+fn parse_report_arguments(matches: &ArgMatches, now: DateTime<Utc>) -> Result<ReportArgs> {
+    let (from, to) = parse_report_range(matches, now)?;
+
+    let format = matches
+        .get_one::<String>("format")
+        .map(|value| ReportFormat::from_str_value(value))
+        .transpose()?
+        .unwrap_or(ReportFormat::Text);
+
+    Ok(ReportArgs {
+        from,
+        to,
+        format,
+        output: matches.get_one::<PathBuf>("output").cloned(),
+    })
+}
+
+fn parse_incidents_arguments(matches: &ArgMatches, now: DateTime<Utc>) -> Result<IncidentsArgs> {
+    let (from, to) = parse_report_range(matches, now)?;
+
+    let format = matches
+        .get_one::<String>("format")
+        .map(|value| IncidentFormat::from_str_value(value))
+        .transpose()?
+        .unwrap_or(IncidentFormat::Markdown);
+
+    let output = matches.get_one::<PathBuf>("output").cloned();
+
+    if format == IncidentFormat::Pdf && output.is_none() {
+        return Err(anyhow!(
+            "The pdf format requires --output (e.g. --output incidents.pdf)."
+        ));
+    }
+
+    Ok(IncidentsArgs {
+        from,
+        to,
+        format,
+        output,
+    })
+}
+
+/// Reads `--from`/`--to`, shared by the `report` and `incidents` subcommands.
+fn parse_report_range(
+    matches: &ArgMatches,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let to = matches
+        .get_one::<String>("to")
+        .map(|value| parse_report_date(value, true))
+        .transpose()
+        .context("Invalid --to")?
+        .unwrap_or(now);
+
+    let from = matches
+        .get_one::<String>("from")
+        .map(|value| parse_report_date(value, false))
+        .transpose()
+        .context("Invalid --from")?
+        .unwrap_or(to - ChronoDuration::days(DEFAULT_REPORT_DAYS));
+
+    if from >= to {
+        return Err(anyhow!(
+            "--from ({}) must be before --to ({}).",
+            from.to_rfc3339(),
+            to.to_rfc3339()
+        ));
+    }
+
+    Ok((from, to))
+}
+
+/// Parses a report date. Accepts RFC3339 or `YYYY-MM-DD` (UTC).
+///
+/// A date-only `end` means the end of that day, so `--to 2026-01-31` includes the whole day.
+fn parse_report_date(value: &str, end: bool) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        anyhow!("Invalid date: {value}. Use YYYY-MM-DD or RFC3339 (e.g. 2026-01-31T18:00:00Z).")
+    })?;
+
+    let date = if end {
+        date.succ_opt()
+            .ok_or_else(|| anyhow!("Invalid date: {value}"))?
+    } else {
+        date
+    };
+
+    date.and_hms_opt(0, 0, 0)
+        .map(|midnight| midnight.and_utc())
+        .ok_or_else(|| anyhow!("Invalid date: {value}"))
+}
+
 pub fn print_runtime_info(config_label: &str, runtime_info: &[(&str, String)]) {
     println!("NetQuality v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
@@ -245,3 +554,20 @@ fn parse_threshold_category(value: &str) -> Result<ThresholdCategory> {
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_date_accepts_dates_and_rfc3339() {
+        let start = parse_report_date("2026-01-31", false).expect("date");
+        let end = parse_report_date("2026-01-31", true).expect("date");
+        let exact = parse_report_date("2026-01-31T18:30:00-03:00", true).expect("rfc3339");
+
+        assert_eq!(start.to_rfc3339(), "2026-01-31T00:00:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2026-02-01T00:00:00+00:00");
+        assert_eq!(exact.to_rfc3339(), "2026-01-31T21:30:00+00:00");
+        assert!(parse_report_date("31/01/2026", false).is_err());
+    }
+}
@@ -1,8 +1,10 @@
 use crate::models::{
-    dedupe_urls, ConfigFile, ConnectivityConfig, ConnectivityConfigFile, NetQualityCliArgs,
-    NetQualityConfig, NotificationConfig, NotificationConfigFile, SpeedConfig, SpeedConfigFile,
-    StorageConfig, StorageConfigFile, TelegramConfig, TelegramConfigFile, ThresholdCategory,
-    Thresholds, UrlMode, DEFAULT_URLS,
+    dedupe_urls, ConfigFile, ConnectivityConfig, ConnectivityConfigFile, EmailConfig,
+    EmailConfigFile, HttpSpeedConfig, HttpSpeedConfigFile, Iperf3Config, Iperf3ConfigFile,
+    NetQualityCliArgs, NetQualityConfig, NotificationConfig, NotificationConfigFile, SpeedConfig,
+    SpeedConfigFile, SpeedProviderConfig, SpeedProviderKind, StorageConfig, StorageConfigFile,
+    TelegramConfig, TelegramConfigFile, ThresholdCategory, Thresholds, UrlMode, WebhookConfig,
+    DEFAULT_URLS,
 };
 use anyhow::{anyhow, Context, Result};
 use shared::system::load_json_file_to_object::load_json_file_to_object;
@@ -18,8 +20,32 @@ const DEFAULT_MIN_DOWNLOAD_NOTIFY_THRESHOLD: ThresholdCategory = ThresholdCatego
 const DEFAULT_MIN_UPLOAD_NOTIFY_THRESHOLD: ThresholdCategory = ThresholdCategory::Slow;
 const DEFAULT_STORAGE_CLEANUP_ENABLED: bool = true;
 const DEFAULT_STORAGE_CLEANUP_INTERVAL_DAYS: u64 = 365;
+const SMTP_PASSWORD_ENV_VAR: &str = "NETQUALITY_SMTP_PASSWORD";
+const DEFAULT_HTTP_SPEED_UPLOAD_SIZE_MB: u64 = 10;
+const DEFAULT_HTTP_SPEED_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_IPERF3_PORT: u16 = 5201;
+const DEFAULT_IPERF3_DURATION_SECS: u64 = 10;
+const DEFAULT_IPERF3_BINARY: &str = "iperf3";
 
 pub(crate) async fn load_config(args: &NetQualityCliArgs) -> Result<(NetQualityConfig, String)> {
+    let (merged_config, config_label) = load_config_files(args).await?;
+
+    let config = build_config(merged_config, args)?;
+    Ok((config, config_label))
+}
+
+/// Loads only the storage settings. Used by `serve`, which doesn't need the monitoring settings
+/// (e.g.: the expected download speed) to be informed.
+pub(crate) async fn load_storage_config(
+    args: &NetQualityCliArgs,
+) -> Result<(StorageConfig, String)> {
+    let (merged_config, config_label) = load_config_files(args).await?;
+
+    let storage = build_storage_config(merged_config.and_then(|cfg| cfg.storage), args)?;
+    Ok((storage, config_label))
+}
+
+async fn load_config_files(args: &NetQualityCliArgs) -> Result<(Option<ConfigFile>, String)> {
     let config_paths = resolve_config_paths(args)?;
     let config_label = if config_paths.is_empty() {
         "defaults".to_string()
@@ -40,8 +66,7 @@ pub(crate) async fn load_config(args: &NetQualityCliArgs) -> Result<(NetQualityC
         });
     }
 
-    let config = build_config(merged_config, args)?;
-    Ok((config, config_label))
+    Ok((merged_config, config_label))
 }
 
 fn resolve_config_paths(args: &NetQualityCliArgs) -> Result<Vec<PathBuf>> {
@@ -120,6 +145,9 @@ fn merge_speed_config(
             download_thresholds: overlay.download_thresholds.or(base.download_thresholds),
             upload_thresholds: overlay.upload_thresholds.or(base.upload_thresholds),
             speedtest_cli_path: overlay.speedtest_cli_path.or(base.speedtest_cli_path),
+            provider: overlay.provider.or(base.provider),
+            http: overlay.http.or(base.http),
+            iperf3: overlay.iperf3.or(base.iperf3),
         }),
     }
 }
@@ -133,6 +161,8 @@ fn merge_notification_config(
         (Some(value), None) | (None, Some(value)) => Some(value),
         (Some(base), Some(overlay)) => Some(NotificationConfigFile {
             telegram: merge_telegram_config(base.telegram, overlay.telegram),
+            webhooks: overlay.webhooks.or(base.webhooks),
+            email: merge_email_config(base.email, overlay.email),
             min_download_threshold: overlay
                 .min_download_threshold
                 .or(base.min_download_threshold),
@@ -155,6 +185,25 @@ fn merge_telegram_config(
     }
 }
 
+fn merge_email_config(
+    base: Option<EmailConfigFile>,
+    overlay: Option<EmailConfigFile>,
+) -> Option<EmailConfigFile> {
+    match (base, overlay) {
+        (None, None) => None,
+        (Some(value), None) | (None, Some(value)) => Some(value),
+        (Some(base), Some(overlay)) => Some(EmailConfigFile {
+            smtp_host: overlay.smtp_host.or(base.smtp_host),
+            smtp_port: overlay.smtp_port.or(base.smtp_port),
+            security: overlay.security.or(base.security),
+            username: overlay.username.or(base.username),
+            password: overlay.password.or(base.password),
+            from: overlay.from.or(base.from),
+            to: overlay.to.or(base.to),
+        }),
+    }
+}
+
 fn merge_storage_config(
     base: Option<StorageConfigFile>,
     overlay: Option<StorageConfigFile>,
@@ -352,20 +401,7 @@ fn build_speed_config(
         .validate()
         .context("Invalid upload thresholds")?;
 
-    let speedtest_cli_path = args.speedtest_cli_path.clone().or_else(|| {
-        config_file
-            .as_ref()
-            .and_then(|cfg| cfg.speedtest_cli_path.clone())
-    });
-
-    if let Some(path) = &speedtest_cli_path {
-        if !path.exists() {
-            return Err(anyhow!(
-                "Speedtest CLI binary not found: {}",
-                path.display()
-            ));
-        }
-    }
+    let provider = resolve_speed_provider(config_file.as_ref(), args)?;
 
     Ok(SpeedConfig {
         expected_download_mbps: expected_download,
@@ -373,7 +409,153 @@ fn build_speed_config(
         delay: Duration::from_secs(delay_secs),
         download_thresholds,
         upload_thresholds,
-        speedtest_cli_path,
+        provider,
+    })
+}
+
+/// The provider comes from `--speed-provider` or `speed.provider`. When neither is set, the Ookla CLI
+/// is used if its path was informed (like in previous versions), otherwise Cloudflare.
+fn resolve_speed_provider(
+    config_file: Option<&SpeedConfigFile>,
+    args: &NetQualityCliArgs,
+) -> Result<SpeedProviderConfig> {
+    let speedtest_cli_path = args
+        .speedtest_cli_path
+        .clone()
+        .or_else(|| config_file.and_then(|cfg| cfg.speedtest_cli_path.clone()));
+
+    let default_kind = if speedtest_cli_path.is_some() {
+        SpeedProviderKind::Ookla
+    } else {
+        SpeedProviderKind::Cloudflare
+    };
+
+    let kind = args
+        .speed_provider
+        .or_else(|| config_file.and_then(|cfg| cfg.provider))
+        .unwrap_or(default_kind);
+
+    match kind {
+        SpeedProviderKind::Cloudflare => Ok(SpeedProviderConfig::Cloudflare),
+        SpeedProviderKind::Ookla => {
+            let cli_path = speedtest_cli_path.ok_or_else(|| {
+                anyhow!("The ookla speed provider requires speedtest_cli_path (or --speedtest-cli-path).")
+            })?;
+
+            if !cli_path.exists() {
+                return Err(anyhow!(
+                    "Speedtest CLI binary not found: {}",
+                    cli_path.display()
+                ));
+            }
+
+            Ok(SpeedProviderConfig::Ookla { cli_path })
+        }
+        SpeedProviderKind::Http => {
+            build_http_speed_config(config_file.and_then(|cfg| cfg.http.clone()), args)
+                .map(SpeedProviderConfig::Http)
+        }
+        SpeedProviderKind::Iperf3 => {
+            build_iperf3_config(config_file.and_then(|cfg| cfg.iperf3.clone()), args)
+                .map(SpeedProviderConfig::Iperf3)
+        }
+    }
+}
+
+fn build_http_speed_config(
+    config_file: Option<HttpSpeedConfigFile>,
+    args: &NetQualityCliArgs,
+) -> Result<HttpSpeedConfig> {
+    let download_url = args
+        .speed_download_url
+        .clone()
+        .or_else(|| config_file.as_ref().and_then(|cfg| cfg.download_url.clone()))
+        .ok_or_else(|| {
+            anyhow!("The http speed provider requires speed.http.download_url (or --speed-download-url).")
+        })?;
+
+    let upload_url = args
+        .speed_upload_url
+        .clone()
+        .or_else(|| config_file.as_ref().and_then(|cfg| cfg.upload_url.clone()));
+
+    for url in std::iter::once(&download_url).chain(upload_url.iter()) {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(anyhow!(
+                "Invalid speed test URL: {}. It must start with http:// or https://.",
+                url
+            ));
+        }
+    }
+
+    let upload_size_mb = config_file
+        .as_ref()
+        .and_then(|cfg| cfg.upload_size_mb)
+        .unwrap_or(DEFAULT_HTTP_SPEED_UPLOAD_SIZE_MB);
+
+    if upload_size_mb == 0 {
+        return Err(anyhow!("Speed test upload size must be greater than zero."));
+    }
+
+    let timeout_secs = config_file
+        .as_ref()
+        .and_then(|cfg| cfg.timeout_secs)
+        .unwrap_or(DEFAULT_HTTP_SPEED_TIMEOUT_SECS);
+
+    if timeout_secs == 0 {
+        return Err(anyhow!("Speed test timeout must be greater than zero."));
+    }
+
+    Ok(HttpSpeedConfig {
+        download_url,
+        upload_url,
+        upload_size_bytes: upload_size_mb * 1_000_000,
+        timeout: Duration::from_secs(timeout_secs),
+    })
+}
+
+fn build_iperf3_config(
+    config_file: Option<Iperf3ConfigFile>,
+    args: &NetQualityCliArgs,
+) -> Result<Iperf3Config> {
+    let server = args
+        .iperf3_server
+        .clone()
+        .or_else(|| config_file.as_ref().and_then(|cfg| cfg.server.clone()))
+        .ok_or_else(|| {
+            anyhow!("The iperf3 speed provider requires speed.iperf3.server (or --iperf3-server).")
+        })?;
+
+    let duration_secs = config_file
+        .as_ref()
+        .and_then(|cfg| cfg.duration_secs)
+        .unwrap_or(DEFAULT_IPERF3_DURATION_SECS);
+
+    if duration_secs == 0 {
+        return Err(anyhow!("iperf3 duration must be greater than zero."));
+    }
+
+    let binary_path = config_file
+        .as_ref()
+        .and_then(|cfg| cfg.binary_path.clone())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_IPERF3_BINARY));
+
+    // A bare name (e.g.: `iperf3`) is looked up in the PATH when the test runs.
+    if binary_path.components().count() > 1 && !binary_path.exists() {
+        return Err(anyhow!(
+            "iperf3 binary not found: {}",
+            binary_path.display()
+        ));
+    }
+
+    Ok(Iperf3Config {
+        server,
+        port: config_file
+            .as_ref()
+            .and_then(|cfg| cfg.port)
+            .unwrap_or(DEFAULT_IPERF3_PORT),
+        duration: Duration::from_secs(duration_secs),
+        binary_path,
     })
 }
 
@@ -386,6 +568,20 @@ fn build_notification_config(
         args,
     )?;
 
+    let webhooks = resolve_webhooks(
+        config_file
+            .as_ref()
+            .and_then(|cfg| cfg.webhooks.clone())
+            .unwrap_or_default(),
+        args,
+    )?;
+
+    let email = config_file
+        .as_ref()
+        .and_then(|cfg| cfg.email.clone())
+        .map(build_email_config)
+        .transpose()?;
+
     let min_download_threshold = args
         .min_download_notification_threshold
         .or_else(|| {
@@ -406,6 +602,8 @@ fn build_notification_config(
 
     Ok(NotificationConfig {
         telegram,
+        webhooks,
+        email,
         min_download_threshold,
         min_upload_threshold,
     })
@@ -436,6 +634,67 @@ fn resolve_telegram_config(
     }
 }
 
+/// Webhooks passed on the command line are added to the ones in the config file.
+fn resolve_webhooks(
+    from_config: Vec<WebhookConfig>,
+    args: &NetQualityCliArgs,
+) -> Result<Vec<WebhookConfig>> {
+    let mut webhooks: Vec<WebhookConfig> = Vec::new();
+
+    for webhook in from_config.into_iter().chain(args.webhooks.iter().cloned()) {
+        if !webhook.url.starts_with("http://") && !webhook.url.starts_with("https://") {
+            return Err(anyhow!(
+                "Invalid webhook URL: {}. It must start with http:// or https://.",
+                webhook.url
+            ));
+        }
+
+        if !webhooks.contains(&webhook) {
+            webhooks.push(webhook);
+        }
+    }
+
+    Ok(webhooks)
+}
+
+fn build_email_config(config_file: EmailConfigFile) -> Result<EmailConfig> {
+    let smtp_host = config_file
+        .smtp_host
+        .ok_or_else(|| anyhow!("Email configuration requires smtp_host."))?;
+    let from = config_file
+        .from
+        .ok_or_else(|| anyhow!("Email configuration requires from."))?;
+    let to = config_file.to.unwrap_or_default();
+    if to.is_empty() {
+        return Err(anyhow!(
+            "Email configuration requires at least one recipient in to."
+        ));
+    }
+
+    let security = config_file.security.unwrap_or_default();
+    let password = config_file
+        .password
+        .or_else(|| std::env::var(SMTP_PASSWORD_ENV_VAR).ok());
+
+    if password.is_some() && config_file.username.is_none() {
+        return Err(anyhow!(
+            "Email configuration has a password but no username."
+        ));
+    }
+
+    Ok(EmailConfig {
+        smtp_host,
+        smtp_port: config_file
+            .smtp_port
+            .unwrap_or_else(|| security.default_port()),
+        security,
+        username: config_file.username,
+        password,
+        from,
+        to,
+    })
+}
+
 fn build_storage_config(
     config_file: Option<StorageConfigFile>,
     args: &NetQualityCliArgs,
@@ -28,6 +28,7 @@ pub fn build_runtime_info(config: &NetQualityConfig) -> Vec<(&str, String)> {
             ),
         ),
         ("Speed delay", format!("{}s", config.speed.delay.as_secs())),
+        ("Speed provider", config.speed.provider.label()),
         (
             "Expected download",
             format!("{:.2} Mbps", config.speed.expected_download_mbps),
@@ -53,6 +54,29 @@ pub fn build_runtime_info(config: &NetQualityConfig) -> Vec<(&str, String)> {
                 .map(|_| "enabled".to_string())
                 .unwrap_or_else(|| "disabled".to_string()),
         ),
+        (
+            "Webhooks",
+            if config.notifications.webhooks.is_empty() {
+                "disabled".to_string()
+            } else {
+                config
+                    .notifications
+                    .webhooks
+                    .iter()
+                    .map(|webhook| webhook.format.label())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+        ),
+        (
+            "Email",
+            config
+                .notifications
+                .email
+                .as_ref()
+                .map(|email| format!("{} recipient(s) via {}", email.to.len(), email.smtp_host))
+                .unwrap_or_else(|| "disabled".to_string()),
+        ),
         (
             "Min notify download",
             config
@@ -0,0 +1,149 @@
+use crate::cli_utils::cli_utils::print_runtime_info;
+use crate::cli_utils::config_parser;
+use crate::models::{NetQualityCliArgs, ServeArgs};
+use crate::persistence::dashboard_queries;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde_json::{json, Value};
+use shared::web::request_logger::create_request_logger;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+const DASHBOARD_PAGE: &str = include_str!("dashboard_page.html");
+const DEFAULT_DAYS_PLACEHOLDER: &str = "__DEFAULT_DAYS__";
+
+/// Largest range the API accepts. Matches the retention of the database clean-up.
+const MAX_DAYS: u32 = 365;
+
+/// Starts the dashboard server and blocks until the process is stopped.
+///
+/// # Errors
+/// Returns an error if the config can't be loaded or the database doesn't exist yet.
+pub async fn run_dashboard(args: &NetQualityCliArgs, serve_args: &ServeArgs) -> Result<()> {
+    let (storage, config_label) = config_parser::load_storage_config(args).await?;
+
+    if !storage.db_path.exists() {
+        return Err(anyhow!(
+            "Database not found: {}. Run netquality to collect some data first.",
+            storage.db_path.display()
+        ));
+    }
+
+    let addr = SocketAddr::new(serve_args.host, serve_args.port);
+
+    print_runtime_info(
+        &config_label,
+        &[
+            ("Database", storage.db_path.display().to_string()),
+            ("Dashboard", format!("http://{}", addr)),
+            ("Default range", format!("{} day(s)", serve_args.days)),
+        ],
+    );
+
+    let routes =
+        build_routes(Arc::new(storage.db_path), serve_args.days).with(create_request_logger());
+
+    println!("Dashboard running at http://{}", addr);
+
+    warp::serve(routes).run(addr).await;
+
+    Ok(())
+}
+
+fn build_routes(
+    db_path: Arc<PathBuf>,
+    default_days: u32,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    let page = DASHBOARD_PAGE.replace(DEFAULT_DAYS_PLACEHOLDER, &default_days.to_string());
+    let index = warp::get()
+        .and(warp::path::end())
+        .map(move || warp::reply::html(page.clone()));
+
+    let api = warp::get()
+        .and(warp::path("api"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and_then(move |endpoint: String, query: String| {
+            let db_path = db_path.clone();
+            async move { handle_api_request(db_path, endpoint, &query, default_days).await }
+        });
+
+    index.or(api)
+}
+
+async fn handle_api_request(
+    db_path: Arc<PathBuf>,
+    endpoint: String,
+    query: &str,
+    default_days: u32,
+) -> Result<warp::reply::Response, Rejection> {
+    let days = parse_days_param(query).unwrap_or(default_days);
+    let since = Utc::now() - ChronoDuration::days(days as i64);
+
+    // rusqlite is blocking, so the queries run outside the async workers.
+    let result =
+        tokio::task::spawn_blocking(move || load_api_data(&db_path, &endpoint, since, days))
+            .await
+            .map_err(|e| anyhow!("Dashboard query task failed: {}", e))
+            .and_then(|result| result);
+
+    match result {
+        Ok(Some(data)) => Ok(warp::reply::json(&data).into_response()),
+        Ok(None) => Err(warp::reject::not_found()),
+        Err(error) => Ok(warp::reply::with_status(
+            warp::reply::json(&json!({ "error": error.to_string() })),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response()),
+    }
+}
+
+/// Runs the query behind `/api/<endpoint>`. Returns `None` for unknown endpoints.
+fn load_api_data(
+    db_path: &Path,
+    endpoint: &str,
+    since: DateTime<Utc>,
+    days: u32,
+) -> Result<Option<Value>> {
+    let conn = dashboard_queries::open_read_only(db_path)?;
+
+    let data = match endpoint {
+        "summary" => serde_json::to_value(dashboard_queries::query_summary(&conn, since, days)?)?,
+        "uptime" => {
+            serde_json::to_value(dashboard_queries::query_uptime_buckets(&conn, since, days)?)?
+        }
+        "speed" => serde_json::to_value(dashboard_queries::query_speed_samples(&conn, since)?)?,
+        "outages" => serde_json::to_value(dashboard_queries::query_outages(&conn, since)?)?,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(data))
+}
+
+/// Reads `days` from the query string, clamped to `1..=MAX_DAYS`. Invalid values are ignored.
+fn parse_days_param(query: &str) -> Option<u32> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "days")
+        .and_then(|(_, value)| value.parse::<u32>().ok())
+        .map(|days| days.clamp(1, MAX_DAYS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_days_param_reads_and_clamps_the_value() {
+        assert_eq!(parse_days_param("days=30"), Some(30));
+        assert_eq!(parse_days_param("foo=1&days=0"), Some(1));
+        assert_eq!(parse_days_param("days=9999"), Some(MAX_DAYS));
+        assert_eq!(parse_days_param("days=abc"), None);
+        assert_eq!(parse_days_param(""), None);
+    }
+}
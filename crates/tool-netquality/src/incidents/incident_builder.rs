@@ -0,0 +1,275 @@
+use crate::models::{
+    DiagnosticsSample, Incident, IncidentKind, IncidentTimeline, OutagePeriod, SpeedSample,
+    ThresholdCategory,
+};
+use crate::persistence::db;
+use crate::report::report_builder::detect_outages;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+
+/// Speed ratings that make a speed test count as degraded.
+const DEGRADED_RATINGS: [ThresholdCategory; 2] =
+    [ThresholdCategory::VerySlow, ThresholdCategory::Slow];
+
+/// Queries the checks in `[from, to)` and compiles them into a timeline.
+pub(crate) fn build_timeline(
+    conn: &Connection,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<IncidentTimeline> {
+    let connectivity = db::query_connectivity_between(conn, from, to)?;
+    let speed = db::query_speed_between(conn, from, to)?;
+    let diagnostics = db::query_diagnostics_between(conn, from, to)?;
+
+    Ok(compile_timeline(
+        from,
+        to,
+        &connectivity,
+        speed,
+        &diagnostics,
+        Utc::now(),
+    ))
+}
+
+/// Builds the timeline from the checks, oldest incident first. Incidents still ongoing at the
+/// end of the range are measured until `to` (or `now`, if the range ends in the future).
+pub(crate) fn compile_timeline(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    connectivity: &[(DateTime<Utc>, bool)],
+    speed: Vec<SpeedSample>,
+    diagnostics: &[DiagnosticsSample],
+    now: DateTime<Utc>,
+) -> IncidentTimeline {
+    let end = to.min(now);
+
+    let mut incidents: Vec<Incident> = detect_outages(connectivity, end)
+        .into_iter()
+        .map(|outage| outage_incident(outage, diagnostics))
+        .collect();
+
+    incidents.extend(detect_degraded_periods(&speed, end));
+    incidents.sort_by_key(|incident| incident.started_at);
+
+    IncidentTimeline {
+        from,
+        to,
+        generated_at: now,
+        connectivity_checks: connectivity.len() as u64,
+        failed_checks: connectivity.iter().filter(|(_, success)| !success).count() as u64,
+        incidents,
+        speed_tests: speed,
+    }
+}
+
+/// Groups consecutive degraded speed tests (download or upload rated Slow or Very Slow).
+///
+/// A degraded period starts at the first degraded test and ends at the next test that isn't
+/// degraded. Failed speed tests don't start or end a period.
+pub(crate) fn detect_degraded_periods(speed: &[SpeedSample], now: DateTime<Utc>) -> Vec<Incident> {
+    let mut incidents = Vec::new();
+    let mut current: Vec<&SpeedSample> = Vec::new();
+
+    for sample in speed.iter().filter(|sample| sample.success) {
+        if is_degraded(sample) {
+            current.push(sample);
+            continue;
+        }
+
+        if !current.is_empty() {
+            incidents.push(degraded_incident(&current, Some(sample.timestamp), now));
+            current.clear();
+        }
+    }
+
+    if !current.is_empty() {
+        incidents.push(degraded_incident(&current, None, now));
+    }
+
+    incidents
+}
+
+fn is_degraded(sample: &SpeedSample) -> bool {
+    let is_degraded_rating = |label: &str| {
+        DEGRADED_RATINGS
+            .iter()
+            .any(|rating| rating.label() == label)
+    };
+
+    is_degraded_rating(&sample.download_threshold)
+        || sample
+            .upload_threshold
+            .as_deref()
+            .is_some_and(is_degraded_rating)
+}
+
+fn degraded_incident(
+    samples: &[&SpeedSample],
+    ended_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Incident {
+    let started_at = samples[0].timestamp;
+    let lowest_download = samples
+        .iter()
+        .map(|sample| sample.download_mbps)
+        .fold(f64::INFINITY, f64::min);
+    let lowest_upload = samples
+        .iter()
+        .filter_map(|sample| sample.upload_mbps)
+        .reduce(f64::min);
+
+    let mut details = format!(
+        "{} speed test(s) rated Slow or Very Slow. Lowest download: {:.2} Mbps",
+        samples.len(),
+        lowest_download
+    );
+
+    if let Some(lowest_upload) = lowest_upload {
+        details.push_str(&format!(", lowest upload: {:.2} Mbps", lowest_upload));
+    }
+
+    Incident {
+        kind: IncidentKind::DegradedSpeed,
+        started_at,
+        ended_at,
+        duration_secs: (ended_at.unwrap_or(now) - started_at).num_seconds().max(0),
+        details,
+    }
+}
+
+fn outage_incident(outage: OutagePeriod, diagnostics: &[DiagnosticsSample]) -> Incident {
+    let mut details = format!("{} failed connectivity check(s)", outage.failed_checks);
+
+    // Diagnostics are captured right after the first failed check of the outage.
+    let captured = diagnostics.iter().find(|sample| {
+        sample.timestamp >= outage.started_at
+            && outage
+                .ended_at
+                .is_none_or(|ended_at| sample.timestamp < ended_at)
+    });
+
+    if let Some(sample) = captured {
+        details.push_str(". ");
+        details.push_str(&describe_diagnostics(sample));
+    }
+
+    Incident {
+        kind: IncidentKind::Outage,
+        started_at: outage.started_at,
+        ended_at: outage.ended_at,
+        duration_secs: outage.duration_secs,
+        details,
+    }
+}
+
+/// A reachable gateway with failing checks points to a problem outside the local network.
+fn describe_diagnostics(sample: &DiagnosticsSample) -> String {
+    let gateway = match (&sample.gateway, sample.gateway_reachable) {
+        (Some(address), true) => format!("Gateway {} reachable", address),
+        (Some(address), false) => format!("Gateway {} unreachable", address),
+        (None, _) => "Gateway not found".to_string(),
+    };
+
+    let dns = match sample.dns_success {
+        Some(true) => ", DNS resolving",
+        Some(false) => ", DNS failing",
+        None => "",
+    };
+
+    format!("{}{}", gateway, dns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 23, 18, minute, 0).unwrap()
+    }
+
+    fn speed(minute: u32, download: f64, rating: ThresholdCategory) -> SpeedSample {
+        SpeedSample {
+            timestamp: at(minute),
+            download_mbps: download,
+            upload_mbps: Some(download / 10.0),
+            download_threshold: rating.label().to_string(),
+            upload_threshold: Some(ThresholdCategory::Expected.label().to_string()),
+            success: true,
+        }
+    }
+
+    #[test]
+    fn degraded_periods_group_consecutive_slow_tests() {
+        let samples = vec![
+            speed(0, 100.0, ThresholdCategory::Expected),
+            speed(10, 40.0, ThresholdCategory::Slow),
+            speed(20, 20.0, ThresholdCategory::VerySlow),
+            speed(30, 90.0, ThresholdCategory::MediumFast),
+            speed(40, 30.0, ThresholdCategory::VerySlow),
+        ];
+
+        let incidents = detect_degraded_periods(&samples, at(50));
+
+        assert_eq!(incidents.len(), 2);
+        assert_eq!(incidents[0].started_at, at(10));
+        assert_eq!(incidents[0].ended_at, Some(at(30)));
+        assert_eq!(incidents[0].duration_secs, 1200);
+        assert!(incidents[0].details.starts_with("2 speed test(s)"));
+        assert!(incidents[0].details.contains("Lowest download: 20.00 Mbps"));
+        assert!(incidents[0].details.contains("lowest upload: 2.00 Mbps"));
+        assert_eq!(incidents[1].ended_at, None);
+        assert_eq!(incidents[1].duration_secs, 600);
+    }
+
+    #[test]
+    fn failed_speed_tests_do_not_end_a_degraded_period() {
+        let mut failed = speed(20, 0.0, ThresholdCategory::VerySlow);
+        failed.success = false;
+        let samples = vec![
+            speed(10, 40.0, ThresholdCategory::Slow),
+            failed,
+            speed(30, 100.0, ThresholdCategory::Expected),
+        ];
+
+        let incidents = detect_degraded_periods(&samples, at(50));
+
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].ended_at, Some(at(30)));
+    }
+
+    #[test]
+    fn timeline_sorts_incidents_and_attaches_diagnostics() {
+        let connectivity = vec![(at(0), true), (at(5), false), (at(6), false), (at(8), true)];
+        let speed_tests = vec![
+            speed(1, 30.0, ThresholdCategory::VerySlow),
+            speed(3, 100.0, ThresholdCategory::Expected),
+        ];
+        let diagnostics = vec![DiagnosticsSample {
+            timestamp: at(5),
+            gateway: Some("192.168.0.1".to_string()),
+            gateway_reachable: true,
+            dns_success: Some(false),
+        }];
+
+        let timeline = compile_timeline(
+            at(0),
+            at(10),
+            &connectivity,
+            speed_tests,
+            &diagnostics,
+            at(30),
+        );
+
+        assert_eq!(timeline.failed_checks, 2);
+        assert_eq!(timeline.speed_tests.len(), 2);
+        assert_eq!(timeline.incidents.len(), 2);
+        assert_eq!(timeline.incidents[0].kind, IncidentKind::DegradedSpeed);
+        assert_eq!(timeline.incidents[1].kind, IncidentKind::Outage);
+        assert_eq!(
+            timeline.incidents[1].details,
+            "2 failed connectivity check(s). Gateway 192.168.0.1 reachable, DNS failing"
+        );
+    }
+}
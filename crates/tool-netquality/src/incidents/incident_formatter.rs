@@ -0,0 +1,298 @@
+use crate::incidents::incident_pdf::render_pdf;
+use crate::models::{IncidentFormat, IncidentKind, IncidentTimeline, SpeedSample};
+use crate::report::report_builder::percent;
+use crate::report::report_formatter::format_duration;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// Renders the timeline. Markdown and CSV are UTF-8 text; PDF is binary.
+pub(crate) fn format_timeline(
+    timeline: &IncidentTimeline,
+    format: IncidentFormat,
+) -> Result<Vec<u8>> {
+    match format {
+        IncidentFormat::Markdown => Ok(format_markdown(timeline).into_bytes()),
+        IncidentFormat::Csv => Ok(format_csv(timeline).into_bytes()),
+        IncidentFormat::Pdf => render_pdf(timeline),
+    }
+}
+
+/// Summary lines shown at the top of the Markdown and PDF reports.
+pub(crate) fn summary_lines(timeline: &IncidentTimeline) -> Vec<String> {
+    let totals = |kind: IncidentKind| {
+        let incidents = timeline.incidents.iter().filter(|i| i.kind == kind);
+        incidents.fold((0usize, 0i64), |(count, secs), incident| {
+            (count + 1, secs + incident.duration_secs)
+        })
+    };
+
+    let (outages, downtime_secs) = totals(IncidentKind::Outage);
+    let (degraded, degraded_secs) = totals(IncidentKind::DegradedSpeed);
+    let failed_speed_tests = timeline.speed_tests.iter().filter(|s| !s.success).count();
+    let uptime = percent(
+        timeline.connectivity_checks - timeline.failed_checks,
+        timeline.connectivity_checks,
+    )
+    .map(|uptime| format!("{:.2}%", uptime))
+    .unwrap_or_else(|| "-".to_string());
+
+    vec![
+        format!(
+            "Period: {} to {}",
+            format_timestamp(timeline.from),
+            format_timestamp(timeline.to)
+        ),
+        format!("Generated at: {}", format_timestamp(timeline.generated_at)),
+        format!(
+            "Uptime: {} ({} connectivity checks, {} failed)",
+            uptime, timeline.connectivity_checks, timeline.failed_checks
+        ),
+        format!(
+            "Outages: {} (total downtime: {})",
+            outages,
+            format_duration(downtime_secs)
+        ),
+        format!(
+            "Degraded speed periods: {} (total: {})",
+            degraded,
+            format_duration(degraded_secs)
+        ),
+        format!(
+            "Speed tests: {} ({} failed)",
+            timeline.speed_tests.len(),
+            failed_speed_tests
+        ),
+    ]
+}
+
+/// Timestamps are always shown in UTC, so the support team doesn't need to guess the time zone.
+pub(crate) fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+pub(crate) fn format_end(ended_at: Option<DateTime<Utc>>) -> String {
+    ended_at
+        .map(format_timestamp)
+        .unwrap_or_else(|| "ongoing".to_string())
+}
+
+pub(crate) fn format_speed(mbps: Option<f64>) -> String {
+    mbps.map(|mbps| format!("{:.2} Mbps", mbps))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Result of a speed test, with its ratings (e.g.: `Slow / Expected`).
+pub(crate) fn format_rating(sample: &SpeedSample) -> String {
+    if !sample.success {
+        return "Failed".to_string();
+    }
+
+    match &sample.upload_threshold {
+        Some(upload) => format!("{} / {}", sample.download_threshold, upload),
+        None => sample.download_threshold.clone(),
+    }
+}
+
+fn format_markdown(timeline: &IncidentTimeline) -> String {
+    let mut md = String::new();
+
+    let _ = writeln!(md, "# Internet service incident report");
+    let _ = writeln!(md);
+    for line in summary_lines(timeline) {
+        let _ = writeln!(md, "- {}", line);
+    }
+
+    let _ = writeln!(md);
+    let _ = writeln!(md, "## Timeline");
+    let _ = writeln!(md);
+
+    if timeline.incidents.is_empty() {
+        let _ = writeln!(md, "No incidents in this period.");
+    } else {
+        let _ = writeln!(md, "| # | Type | Start | End | Duration | Details |");
+        let _ = writeln!(md, "|---|---|---|---|---|---|");
+        for (index, incident) in timeline.incidents.iter().enumerate() {
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | {} | {} | {} |",
+                index + 1,
+                incident.kind.label(),
+                format_timestamp(incident.started_at),
+                format_end(incident.ended_at),
+                format_duration(incident.duration_secs),
+                incident.details.replace('|', "\\|")
+            );
+        }
+    }
+
+    let _ = writeln!(md);
+    let _ = writeln!(md, "## Speed test evidence");
+    let _ = writeln!(md);
+
+    if timeline.speed_tests.is_empty() {
+        let _ = writeln!(md, "No speed tests in this period.");
+    } else {
+        let _ = writeln!(md, "| Timestamp | Download | Upload | Rating |");
+        let _ = writeln!(md, "|---|---|---|---|");
+        for sample in &timeline.speed_tests {
+            let _ = writeln!(
+                md,
+                "| {} | {} | {} | {} |",
+                format_timestamp(sample.timestamp),
+                format_speed(Some(sample.download_mbps).filter(|_| sample.success)),
+                format_speed(sample.upload_mbps.filter(|_| sample.success)),
+                format_rating(sample)
+            );
+        }
+    }
+
+    md
+}
+
+/// Incidents and speed tests in a single chronological list, one event per row.
+fn format_csv(timeline: &IncidentTimeline) -> String {
+    let mut rows: Vec<(DateTime<Utc>, [String; 5])> = timeline
+        .incidents
+        .iter()
+        .map(|incident| {
+            (
+                incident.started_at,
+                [
+                    incident_type(incident.kind).to_string(),
+                    incident.started_at.to_rfc3339(),
+                    incident
+                        .ended_at
+                        .map(|ended_at| ended_at.to_rfc3339())
+                        .unwrap_or_default(),
+                    incident.duration_secs.to_string(),
+                    incident.details.clone(),
+                ],
+            )
+        })
+        .collect();
+
+    rows.extend(timeline.speed_tests.iter().map(|sample| {
+        let details = if sample.success {
+            format!(
+                "Download {}, upload {} ({})",
+                format_speed(Some(sample.download_mbps)),
+                format_speed(sample.upload_mbps),
+                format_rating(sample)
+            )
+        } else {
+            "Speed test failed".to_string()
+        };
+
+        (
+            sample.timestamp,
+            [
+                "speed_test".to_string(),
+                sample.timestamp.to_rfc3339(),
+                String::new(),
+                String::new(),
+                details,
+            ],
+        )
+    }));
+
+    // Stable sort: an incident comes before the speed test that started it.
+    rows.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let mut csv = String::from("type,started_at,ended_at,duration_secs,details\n");
+    for (_, fields) in rows {
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        let _ = writeln!(csv, "{}", fields.join(","));
+    }
+
+    csv
+}
+
+fn incident_type(kind: IncidentKind) -> &'static str {
+    match kind {
+        IncidentKind::Outage => "outage",
+        IncidentKind::DegradedSpeed => "degraded_speed",
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Incident;
+    use chrono::TimeZone;
+
+    fn sample_timeline() -> IncidentTimeline {
+        let at = |hour: u32| Utc.with_ymd_and_hms(2026, 1, 23, hour, 0, 0).unwrap();
+
+        IncidentTimeline {
+            from: at(0),
+            to: at(23),
+            generated_at: at(23),
+            connectivity_checks: 10,
+            failed_checks: 2,
+            incidents: vec![
+                Incident {
+                    kind: IncidentKind::DegradedSpeed,
+                    started_at: at(4),
+                    ended_at: Some(at(8)),
+                    duration_secs: 14_400,
+                    details: "1 speed test(s) rated Slow or Very Slow. Lowest download: 20.00 Mbps"
+                        .to_string(),
+                },
+                Incident {
+                    kind: IncidentKind::Outage,
+                    started_at: at(10),
+                    ended_at: None,
+                    duration_secs: 3_725,
+                    details: "2 failed connectivity check(s)".to_string(),
+                },
+            ],
+            speed_tests: vec![SpeedSample {
+                timestamp: at(4),
+                download_mbps: 20.0,
+                upload_mbps: None,
+                download_threshold: "Very Slow".to_string(),
+                upload_threshold: None,
+                success: true,
+            }],
+        }
+    }
+
+    #[test]
+    fn markdown_has_summary_timeline_and_evidence() {
+        let md = String::from_utf8(
+            format_timeline(&sample_timeline(), IncidentFormat::Markdown).unwrap(),
+        )
+        .unwrap();
+
+        assert!(md.contains("- Uptime: 80.00% (10 connectivity checks, 2 failed)"));
+        assert!(md.contains("- Outages: 1 (total downtime: 01:02:05)"));
+        assert!(md.contains(
+            "| 2 | Outage | 2026-01-23 10:00:00 UTC | ongoing | 01:02:05 | 2 failed connectivity check(s) |"
+        ));
+        assert!(md.contains("| 2026-01-23 04:00:00 UTC | 20.00 Mbps | - | Very Slow |"));
+    }
+
+    #[test]
+    fn csv_is_chronological_and_quotes_details() {
+        let csv =
+            String::from_utf8(format_timeline(&sample_timeline(), IncidentFormat::Csv).unwrap())
+                .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "type,started_at,ended_at,duration_secs,details");
+        assert!(lines[1].starts_with("degraded_speed,2026-01-23T04:00:00+00:00,"));
+        assert!(lines[2].starts_with("speed_test,"));
+        assert!(lines[2].ends_with("\"Download 20.00 Mbps, upload - (Very Slow)\""));
+        assert!(lines[3].starts_with("outage,"));
+        assert_eq!(lines.len(), 4);
+    }
+}
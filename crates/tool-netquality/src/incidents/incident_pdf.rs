@@ -0,0 +1,267 @@
+use crate::incidents::incident_formatter::{
+    format_end, format_rating, format_speed, format_timestamp, summary_lines,
+};
+use crate::models::IncidentTimeline;
+use crate::report::report_formatter::format_duration;
+use anyhow::{anyhow, Result};
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+};
+
+const PAGE_WIDTH_MM: f32 = 210.0;
+const PAGE_HEIGHT_MM: f32 = 297.0;
+const MARGIN_MM: f32 = 15.0;
+const LINE_HEIGHT_MM: f32 = 4.5;
+const BODY_FONT_SIZE: f32 = 8.0;
+/// Courier glyphs are 0.6 em wide, so about 105 characters fit between the margins at 8pt.
+const MAX_LINE_CHARS: usize = 105;
+
+#[derive(Clone, Copy)]
+enum LineStyle {
+    Title,
+    Heading,
+    Body,
+}
+
+/// A4 pages with the built-in PDF fonts, so no font files are needed. Tables use Courier to keep
+/// the columns aligned.
+pub(crate) fn render_pdf(timeline: &IncidentTimeline) -> Result<Vec<u8>> {
+    let (doc, page, layer) = PdfDocument::new(
+        "Internet service incident report",
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Content",
+    );
+
+    let regular = add_font(&doc, BuiltinFont::Courier)?;
+    let bold = add_font(&doc, BuiltinFont::HelveticaBold)?;
+
+    let mut writer = PageWriter {
+        layer: doc.get_page(page).get_layer(layer),
+        y: PAGE_HEIGHT_MM - MARGIN_MM,
+        page_number: 1,
+    };
+
+    for (text, style) in build_lines(timeline) {
+        let (font, size) = match style {
+            LineStyle::Title => (&bold, 16.0),
+            LineStyle::Heading => (&bold, 11.0),
+            LineStyle::Body => (&regular, BODY_FONT_SIZE),
+        };
+
+        if writer.y < MARGIN_MM + LINE_HEIGHT_MM {
+            writer.finish_page(&regular);
+            let (page, layer) = doc.add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Content");
+            writer.layer = doc.get_page(page).get_layer(layer);
+            writer.y = PAGE_HEIGHT_MM - MARGIN_MM;
+            writer.page_number += 1;
+        }
+
+        writer
+            .layer
+            .use_text(text, size, Mm(MARGIN_MM), Mm(writer.y), font);
+        writer.y -= match style {
+            LineStyle::Title => LINE_HEIGHT_MM * 2.0,
+            LineStyle::Heading => LINE_HEIGHT_MM * 1.5,
+            LineStyle::Body => LINE_HEIGHT_MM,
+        };
+    }
+
+    writer.finish_page(&regular);
+
+    doc.save_to_bytes()
+        .map_err(|e| anyhow!("Failed to create the PDF: {:?}", e))
+}
+
+struct PageWriter {
+    layer: PdfLayerReference,
+    y: f32,
+    page_number: u32,
+}
+
+impl PageWriter {
+    fn finish_page(&self, font: &IndirectFontRef) {
+        self.layer.use_text(
+            format!("Page {}", self.page_number),
+            BODY_FONT_SIZE,
+            Mm(PAGE_WIDTH_MM - MARGIN_MM - 15.0),
+            Mm(MARGIN_MM / 2.0),
+            font,
+        );
+    }
+}
+
+fn add_font(doc: &PdfDocumentReference, font: BuiltinFont) -> Result<IndirectFontRef> {
+    doc.add_builtin_font(font)
+        .map_err(|e| anyhow!("Failed to load the PDF font: {:?}", e))
+}
+
+/// Lays out the report as lines. Details that don't fit are wrapped under the details column.
+fn build_lines(timeline: &IncidentTimeline) -> Vec<(String, LineStyle)> {
+    let mut lines = vec![(
+        "Internet service incident report".to_string(),
+        LineStyle::Title,
+    )];
+
+    lines.extend(
+        summary_lines(timeline)
+            .into_iter()
+            .map(|line| (to_pdf_text(&line), LineStyle::Body)),
+    );
+
+    lines.push((String::new(), LineStyle::Body));
+    lines.push(("Timeline".to_string(), LineStyle::Heading));
+
+    if timeline.incidents.is_empty() {
+        lines.push(("No incidents in this period.".to_string(), LineStyle::Body));
+    } else {
+        let header = format!(
+            "{:<3} {:<14} {:<23} {:<23} {:<9} {}",
+            "#", "Type", "Start", "End", "Duration", "Details"
+        );
+        let indent = header.find("Details").unwrap_or_default();
+        lines.push((header, LineStyle::Body));
+
+        for (index, incident) in timeline.incidents.iter().enumerate() {
+            let prefix = format!(
+                "{:<3} {:<14} {:<23} {:<23} {:<9} ",
+                index + 1,
+                incident.kind.label(),
+                format_timestamp(incident.started_at),
+                format_end(incident.ended_at),
+                format_duration(incident.duration_secs)
+            );
+
+            for (line_index, chunk) in
+                wrap(&to_pdf_text(&incident.details), MAX_LINE_CHARS - indent)
+                    .into_iter()
+                    .enumerate()
+            {
+                let start = if line_index == 0 {
+                    prefix.clone()
+                } else {
+                    " ".repeat(indent)
+                };
+                lines.push((format!("{}{}", start, chunk), LineStyle::Body));
+            }
+        }
+    }
+
+    lines.push((String::new(), LineStyle::Body));
+    lines.push(("Speed test evidence".to_string(), LineStyle::Heading));
+
+    if timeline.speed_tests.is_empty() {
+        lines.push((
+            "No speed tests in this period.".to_string(),
+            LineStyle::Body,
+        ));
+    } else {
+        lines.push((
+            format!(
+                "{:<23} {:<14} {:<14} {}",
+                "Timestamp", "Download", "Upload", "Rating"
+            ),
+            LineStyle::Body,
+        ));
+
+        for sample in &timeline.speed_tests {
+            lines.push((
+                format!(
+                    "{:<23} {:<14} {:<14} {}",
+                    format_timestamp(sample.timestamp),
+                    format_speed(Some(sample.download_mbps).filter(|_| sample.success)),
+                    format_speed(sample.upload_mbps.filter(|_| sample.success)),
+                    format_rating(sample)
+                ),
+                LineStyle::Body,
+            ));
+        }
+    }
+
+    lines
+}
+
+/// The built-in fonts don't cover every character, so anything outside ASCII is replaced.
+fn to_pdf_text(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii() { c } else { '?' })
+        .collect()
+}
+
+/// Splits on spaces so each line has at most `width` characters (longer words are cut).
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let needed = if current.is_empty() {
+            word.len()
+        } else {
+            current.len() + 1 + word.len()
+        };
+
+        if needed > width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+
+        while current.len() > width {
+            let rest = current.split_off(width);
+            lines.push(std::mem::replace(&mut current, rest));
+        }
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Incident, IncidentKind};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn wrap_splits_on_spaces() {
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("", 4), vec![""]);
+    }
+
+    #[test]
+    fn render_pdf_creates_a_pdf_document() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 23, 18, 0, 0).unwrap();
+        let incidents = (0..80)
+            .map(|_| Incident {
+                kind: IncidentKind::Outage,
+                started_at: at,
+                ended_at: None,
+                duration_secs: 60,
+                details: "1 failed connectivity check(s). Gateway 192.168.0.1 reachable".repeat(3),
+            })
+            .collect();
+
+        let timeline = IncidentTimeline {
+            from: at,
+            to: at,
+            generated_at: at,
+            connectivity_checks: 80,
+            failed_checks: 80,
+            incidents,
+            speed_tests: vec![],
+        };
+
+        let lines = build_lines(&timeline);
+        assert!(lines.iter().all(|(line, _)| line.len() <= MAX_LINE_CHARS));
+
+        let pdf = render_pdf(&timeline).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::cli_utils::cli_utils::print_runtime_info;
+use crate::cli_utils::config_parser;
+use crate::incidents::incident_builder::build_timeline;
+use crate::incidents::incident_formatter::format_timeline;
+use crate::models::{IncidentsArgs, NetQualityCliArgs};
+use crate::persistence::dashboard_queries::open_read_only;
+use anyhow::{anyhow, Context, Result};
+use std::io::Write;
+
+/// Compiles the incident timeline for the requested range and writes it to the output file or
+/// the console.
+///
+/// # Errors
+/// Returns an error if the config can't be loaded, the database doesn't exist yet, or the
+/// output file can't be written.
+pub async fn run_incidents(args: &NetQualityCliArgs, incidents_args: &IncidentsArgs) -> Result<()> {
+    let (storage, config_label) = config_parser::load_storage_config(args).await?;
+
+    if !storage.db_path.exists() {
+        return Err(anyhow!(
+            "Database not found: {}. Run netquality to collect some data first.",
+            storage.db_path.display()
+        ));
+    }
+
+    let conn = open_read_only(&storage.db_path)?;
+    let timeline = build_timeline(&conn, incidents_args.from, incidents_args.to)?;
+    let formatted = format_timeline(&timeline, incidents_args.format)?;
+
+    match &incidents_args.output {
+        Some(output) => {
+            print_runtime_info(
+                &config_label,
+                &[
+                    ("Database", storage.db_path.display().to_string()),
+                    ("From", incidents_args.from.to_rfc3339()),
+                    ("To", incidents_args.to.to_rfc3339()),
+                    ("Format", incidents_args.format.as_str().to_string()),
+                    ("Output", output.display().to_string()),
+                ],
+            );
+
+            std::fs::write(output, formatted)
+                .context(format!("Failed to write incidents: {}", output.display()))?;
+            println!(
+                "Incident timeline ({} incidents) saved to {}",
+                timeline.incidents.len(),
+                output.display()
+            );
+        }
+        None => std::io::stdout()
+            .write_all(&formatted)
+            .context("Failed to write the incident timeline")?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod incident_builder;
+pub mod incident_formatter;
+pub mod incident_pdf;
+pub mod incident_runner;
@@ -0,0 +1,91 @@
+mod checks;
+mod cli_utils;
+mod dashboard;
+mod incidents;
+mod models;
+mod netqualify_app;
+mod notifiers;
+mod persistence;
+mod report;
+mod runtime_state;
+
+use crate::cli_utils::cli_utils::get_cli_arguments;
+use crate::dashboard::dashboard_server::run_dashboard;
+use crate::incidents::incident_runner::run_incidents;
+use crate::models::NetQualityCommand;
+use crate::netqualify_app::run_app;
+use crate::report::report_runner::run_report;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log_with_otel;
+use std::ffi::OsString;
+
+/// Internet connectivity and speed monitor.
+///
+/// Parses arguments and runs the subcommand: the monitor (the default), `serve`, `report`, or
+/// `incidents`.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    runtime.block_on(run_tool(argv))
+}
+
+async fn run_tool(argv: Vec<OsString>) -> i32 {
+    let args = match get_cli_arguments(argv) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}");
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    let log_level = if args.verbose {
+        LogLevel::Debug
+    } else {
+        LogLevel::Info
+    };
+
+    let _otel_guard = initialize_log_with_otel(
+        env!("CARGO_PKG_NAME"),
+        log_level,
+        args.otel_endpoint.as_deref(),
+        &args.log_settings,
+    );
+
+    let result = match &args.command {
+        NetQualityCommand::Serve(serve_args) => run_dashboard(&args, serve_args).await,
+        NetQualityCommand::Report(report_args) => run_report(&args, report_args).await,
+        NetQualityCommand::Incidents(incidents_args) => run_incidents(&args, incidents_args).await,
+        NetQualityCommand::Monitor => run_app(&args).await,
+    };
+
+    // Drop the OTel guard so providers flush pending spans/logs before the binary exits.
+    drop(_otel_guard);
+
+    match result {
+        Ok(_) => EXIT_CODE_SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            EXIT_CODE_ERROR
+        }
+    }
+}
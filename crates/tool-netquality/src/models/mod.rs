@@ -2,7 +2,13 @@ mod types;
 
 pub use types::{
     dedupe_urls, ConfigFile, ConnectivityConfig, ConnectivityConfigFile, ConnectivityResult,
-    NetQualityCliArgs, NetQualityConfig, NotificationConfig, NotificationConfigFile, OutageInfo,
-    SpeedConfig, SpeedConfigFile, SpeedResult, StorageConfig, StorageConfigFile, TelegramConfig,
-    TelegramConfigFile, ThresholdCategory, Thresholds, UrlMode, DEFAULT_URLS,
+    DashboardSummary, DiagnosticsSample, DnsCheck, EmailConfig, EmailConfigFile, GatewayCheck,
+    HttpSpeedConfig, HttpSpeedConfigFile, Incident, IncidentFormat, IncidentKind, IncidentTimeline,
+    IncidentsArgs, InterfaceStatus, Iperf3Config, Iperf3ConfigFile, NetQualityCliArgs,
+    NetQualityCommand, NetQualityConfig, NetQualityReport, NotificationConfig,
+    NotificationConfigFile, OutageDiagnostics, OutageInfo, OutagePeriod, ReportArgs, ReportFormat,
+    ServeArgs, SmtpSecurity, SpeedConfig, SpeedConfigFile, SpeedPercentiles, SpeedProviderConfig,
+    SpeedProviderKind, SpeedResult, SpeedSample, StorageConfig, StorageConfigFile, TelegramConfig,
+    TelegramConfigFile, ThresholdCategory, Thresholds, UptimeBucket, UrlMode, WebhookConfig,
+    WebhookFormat, DEFAULT_URLS,
 };
@@ -1,6 +1,8 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shared::logging::logging_helpers::LogSettings;
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -22,10 +24,109 @@ pub struct NetQualityCliArgs {
     pub outage_backoff_max_secs: Option<u64>,
     pub db_path: Option<PathBuf>,
     pub speedtest_cli_path: Option<PathBuf>,
+    pub speed_provider: Option<SpeedProviderKind>,
+    pub speed_download_url: Option<String>,
+    pub speed_upload_url: Option<String>,
+    pub iperf3_server: Option<String>,
     pub telegram_token: Option<String>,
     pub telegram_chat_id: Option<String>,
+    pub webhooks: Vec<WebhookConfig>,
     pub otel_endpoint: Option<String>,
     pub verbose: bool,
+    pub log_settings: LogSettings,
+    pub command: NetQualityCommand,
+}
+
+/// What the tool was asked to do. Monitoring is the default, when no subcommand is used.
+#[derive(Debug, Clone)]
+pub enum NetQualityCommand {
+    Monitor,
+    Serve(ServeArgs),
+    Report(ReportArgs),
+    Incidents(IncidentsArgs),
+}
+
+/// Arguments of the `serve` subcommand (web dashboard).
+#[derive(Debug, Clone)]
+pub struct ServeArgs {
+    pub host: IpAddr,
+    pub port: u16,
+    /// Time range (in days) shown when the dashboard is opened.
+    pub days: u32,
+}
+
+/// Arguments of the `report` subcommand. The range is `[from, to)`.
+#[derive(Debug, Clone)]
+pub struct ReportArgs {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub format: ReportFormat,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Text,
+    Csv,
+    Json,
+}
+
+impl ReportFormat {
+    pub fn from_str_value(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(ReportFormat::Text),
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(anyhow!(
+                "Invalid report format: {value}. Use text, csv, or json."
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReportFormat::Text => "text",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+/// Arguments of the `incidents` subcommand. The range is `[from, to)`.
+#[derive(Debug, Clone)]
+pub struct IncidentsArgs {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub format: IncidentFormat,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IncidentFormat {
+    Pdf,
+    Markdown,
+    Csv,
+}
+
+impl IncidentFormat {
+    pub fn from_str_value(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pdf" => Ok(IncidentFormat::Pdf),
+            "md" | "markdown" => Ok(IncidentFormat::Markdown),
+            "csv" => Ok(IncidentFormat::Csv),
+            _ => Err(anyhow!(
+                "Invalid incidents format: {value}. Use pdf, md, or csv."
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IncidentFormat::Pdf => "pdf",
+            IncidentFormat::Markdown => "md",
+            IncidentFormat::Csv => "csv",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -52,12 +153,56 @@ pub struct SpeedConfig {
     pub delay: Duration,
     pub download_thresholds: Thresholds,
     pub upload_thresholds: Thresholds,
-    pub speedtest_cli_path: Option<PathBuf>,
+    pub provider: SpeedProviderConfig,
+}
+
+/// How the speed is measured. Every provider, except Cloudflare, falls back to Cloudflare when it fails.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpeedProviderConfig {
+    Cloudflare,
+    Ookla { cli_path: PathBuf },
+    Http(HttpSpeedConfig),
+    Iperf3(Iperf3Config),
+}
+
+impl SpeedProviderConfig {
+    pub fn label(&self) -> String {
+        match self {
+            SpeedProviderConfig::Cloudflare => "Cloudflare".to_string(),
+            SpeedProviderConfig::Ookla { cli_path } => {
+                format!("Ookla speedtest CLI ({})", cli_path.display())
+            }
+            SpeedProviderConfig::Http(http) => format!("HTTP ({})", http.download_url),
+            SpeedProviderConfig::Iperf3(iperf3) => {
+                format!("iperf3 ({}:{})", iperf3.server, iperf3.port)
+            }
+        }
+    }
+}
+
+/// Timed download (GET) and upload (POST) against user provided URLs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpSpeedConfig {
+    pub download_url: String,
+    pub upload_url: Option<String>,
+    pub upload_size_bytes: u64,
+    pub timeout: Duration,
+}
+
+/// Test against an iperf3 server, using the `iperf3` binary. Download runs in reverse mode (`-R`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Iperf3Config {
+    pub server: String,
+    pub port: u16,
+    pub duration: Duration,
+    pub binary_path: PathBuf,
 }
 
 #[derive(Debug, Clone)]
 pub struct NotificationConfig {
     pub telegram: Option<TelegramConfig>,
+    pub webhooks: Vec<WebhookConfig>,
+    pub email: Option<EmailConfig>,
     pub min_download_threshold: ThresholdCategory,
     pub min_upload_threshold: ThresholdCategory,
 }
@@ -68,6 +213,67 @@ pub struct TelegramConfig {
     pub chat_id: String,
 }
 
+/// Incoming webhook that receives the notifications as a JSON POST.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookConfig {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+/// Shape of the webhook payload: our own JSON, or the one expected by Slack/Discord incoming webhooks.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    #[default]
+    Generic,
+    Slack,
+    Discord,
+}
+
+impl WebhookFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            WebhookFormat::Generic => "webhook",
+            WebhookFormat::Slack => "Slack",
+            WebhookFormat::Discord => "Discord",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Implicit TLS (usually port 465).
+    Tls,
+    /// Plain connection upgraded with STARTTLS (usually port 587).
+    #[default]
+    Starttls,
+    /// No encryption. Only for local relays.
+    None,
+}
+
+impl SmtpSecurity {
+    pub fn default_port(self) -> u16 {
+        match self {
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::Starttls => 587,
+            SmtpSecurity::None => 25,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StorageConfig {
     pub db_path: PathBuf,
@@ -140,11 +346,54 @@ pub struct SpeedConfigFile {
     pub download_thresholds: Option<Thresholds>,
     pub upload_thresholds: Option<Thresholds>,
     pub speedtest_cli_path: Option<PathBuf>,
+    pub provider: Option<SpeedProviderKind>,
+    pub http: Option<HttpSpeedConfigFile>,
+    pub iperf3: Option<Iperf3ConfigFile>,
+}
+
+/// Speed test provider, as written in the config file and in `--speed-provider`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeedProviderKind {
+    Cloudflare,
+    Ookla,
+    Http,
+    Iperf3,
+}
+
+impl SpeedProviderKind {
+    pub fn from_str_value(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "cloudflare" => Some(SpeedProviderKind::Cloudflare),
+            "ookla" => Some(SpeedProviderKind::Ookla),
+            "http" => Some(SpeedProviderKind::Http),
+            "iperf3" => Some(SpeedProviderKind::Iperf3),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpSpeedConfigFile {
+    pub download_url: Option<String>,
+    pub upload_url: Option<String>,
+    pub upload_size_mb: Option<u64>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Iperf3ConfigFile {
+    pub server: Option<String>,
+    pub port: Option<u16>,
+    pub duration_secs: Option<u64>,
+    pub binary_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationConfigFile {
     pub telegram: Option<TelegramConfigFile>,
+    pub webhooks: Option<Vec<WebhookConfig>>,
+    pub email: Option<EmailConfigFile>,
     pub min_download_threshold: Option<ThresholdCategory>,
     pub min_upload_threshold: Option<ThresholdCategory>,
 }
@@ -155,6 +404,17 @@ pub struct TelegramConfigFile {
     pub chat_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfigFile {
+    pub smtp_host: Option<String>,
+    pub smtp_port: Option<u16>,
+    pub security: Option<SmtpSecurity>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfigFile {
     pub db_path: Option<PathBuf>,
@@ -254,6 +514,161 @@ pub struct OutageInfo {
     pub ended_at: DateTime<Utc>,
 }
 
+/// Extra checks captured when connectivity goes down, stored alongside the failed check.
+#[derive(Debug, Clone)]
+pub struct OutageDiagnostics {
+    pub timestamp: DateTime<Utc>,
+    pub gateway: GatewayCheck,
+    pub dns: Option<DnsCheck>,
+    pub interfaces: Vec<InterfaceStatus>,
+}
+
+/// Ping against the default gateway. `address` is `None` when no default route was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GatewayCheck {
+    pub address: Option<IpAddr>,
+    pub interface: Option<String>,
+    pub reachable: bool,
+    pub elapsed_ms: Option<i64>,
+}
+
+/// Name resolution of the host of the first connectivity URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DnsCheck {
+    pub host: String,
+    pub success: bool,
+    pub result: String,
+    pub elapsed_ms: i64,
+}
+
+/// Operational state of a network interface, as reported by the OS (e.g.: `up`, `down`, `dormant`).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct InterfaceStatus {
+    pub name: String,
+    pub up: bool,
+    pub state: String,
+}
+
+/// Connectivity checks aggregated by hour or day, for the dashboard uptime chart.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct UptimeBucket {
+    pub bucket: String,
+    pub checks: u64,
+    pub successes: u64,
+    pub uptime_percent: f64,
+}
+
+/// One speed test, for the dashboard speed chart.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeedSample {
+    pub timestamp: DateTime<Utc>,
+    pub download_mbps: f64,
+    pub upload_mbps: Option<f64>,
+    pub download_threshold: String,
+    pub upload_threshold: Option<String>,
+    pub success: bool,
+}
+
+/// Consecutive failed connectivity checks. `ended_at` is `None` while the outage is ongoing.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OutagePeriod {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_secs: i64,
+    pub failed_checks: u64,
+}
+
+/// Distribution of the measured speeds, in Mbps.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SpeedPercentiles {
+    pub min: f64,
+    pub p5: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+/// Summary of the checks stored in a time range, produced by the `report` subcommand.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetQualityReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub connectivity_checks: u64,
+    pub failed_checks: u64,
+    pub uptime_percent: Option<f64>,
+    pub outage_count: usize,
+    pub outage_total_secs: i64,
+    pub longest_outage_secs: i64,
+    pub speed_tests: usize,
+    pub failed_speed_tests: usize,
+    pub download: Option<SpeedPercentiles>,
+    pub upload: Option<SpeedPercentiles>,
+    pub outages: Vec<OutagePeriod>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IncidentKind {
+    Outage,
+    DegradedSpeed,
+}
+
+impl IncidentKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            IncidentKind::Outage => "Outage",
+            IncidentKind::DegradedSpeed => "Degraded speed",
+        }
+    }
+}
+
+/// An outage or a degraded speed period. `ended_at` is `None` while it's ongoing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incident {
+    pub kind: IncidentKind,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_secs: i64,
+    pub details: String,
+}
+
+/// Outage diagnostics, as used in the incident timeline.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsSample {
+    pub timestamp: DateTime<Utc>,
+    pub gateway: Option<String>,
+    pub gateway_reachable: bool,
+    pub dns_success: Option<bool>,
+}
+
+/// Incidents of a time range, with the speed tests used as evidence. Produced by the `incidents`
+/// subcommand.
+#[derive(Debug, Clone)]
+pub struct IncidentTimeline {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub connectivity_checks: u64,
+    pub failed_checks: u64,
+    pub incidents: Vec<Incident>,
+    pub speed_tests: Vec<SpeedSample>,
+}
+
+/// Numbers shown at the top of the dashboard.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardSummary {
+    pub days: u32,
+    pub connectivity_checks: u64,
+    pub failed_checks: u64,
+    pub uptime_percent: Option<f64>,
+    pub outages: usize,
+    pub downtime_secs: i64,
+    pub speed_tests: usize,
+    pub avg_download_mbps: Option<f64>,
+    pub avg_upload_mbps: Option<f64>,
+    pub last_check_at: Option<DateTime<Utc>>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
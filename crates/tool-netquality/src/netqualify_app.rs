@@ -1,5 +1,6 @@
 use crate::checks::connectivity_check::run_connectivity_check;
 use crate::checks::database_clean_up::run_database_cleanup;
+use crate::checks::diagnostics_check::{describe_diagnostics, run_outage_diagnostics};
 use crate::checks::speed_test_check::run_speed_check;
 
 use crate::cli_utils::cli_utils::print_runtime_info;
@@ -13,7 +14,7 @@ use anyhow::{Context, Result};
 use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
 use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
-use tracing::{info, trace};
+use tracing::{info, trace, warn};
 
 pub async fn run_app(args: &NetQualityCliArgs) -> Result<()> {
     let (config, config_label) = config_parser::load_config(args).await?;
@@ -51,12 +52,20 @@ pub async fn run_app(args: &NetQualityCliArgs) -> Result<()> {
                 .await
                 .context("Connectivity check failed")?;
 
-            connectivity_id = Some(
-                db::insert_connectivity_activity(&connection, &connectivity_result)
-                    .context("Failed to store connectivity activity")?,
-            );
+            let activity_id = db::insert_connectivity_activity(&connection, &connectivity_result)
+                .context("Failed to store connectivity activity")?;
+            connectivity_id = Some(activity_id);
 
-            runtime_state::handle_connectivity_state(&config, &mut state, &connectivity_result);
+            let outage_started =
+                runtime_state::handle_connectivity_state(&config, &mut state, &connectivity_result);
+
+            if outage_started {
+                let diagnostics = run_outage_diagnostics(&config).await;
+                warn!("Connectivity lost: {}", describe_diagnostics(&diagnostics));
+
+                db::insert_outage_diagnostics(&connection, activity_id, &diagnostics)
+                    .context("Failed to store outage diagnostics")?;
+            }
         }
 
         let should_run_speed = runtime_state::should_run_speed_check(&state);
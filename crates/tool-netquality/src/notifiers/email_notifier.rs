@@ -0,0 +1,70 @@
+use crate::models::{EmailConfig, SmtpSecurity};
+use crate::notifiers::notification::Notification;
+use anyhow::{Context, Result};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+pub(crate) struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailNotifier {
+    /// Validates the addresses and prepares the SMTP transport. Nothing is sent until `send`.
+    pub(crate) fn new(config: &EmailConfig) -> Result<Self> {
+        let from = config
+            .from
+            .parse::<Mailbox>()
+            .context(format!("Invalid email sender: {}", config.from))?;
+
+        let to = config
+            .to
+            .iter()
+            .map(|recipient| {
+                recipient
+                    .parse::<Mailbox>()
+                    .context(format!("Invalid email recipient: {}", recipient))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder = match config.security {
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)?,
+            SmtpSecurity::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)?
+            }
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+            }
+        }
+        .port(config.smtp_port);
+
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+            to,
+        })
+    }
+
+    pub(crate) async fn send(&self, notification: &Notification) -> Result<()> {
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .subject(format!("[NetQuality] {}", notification.title))
+            .header(ContentType::TEXT_PLAIN);
+
+        for recipient in &self.to {
+            message = message.to(recipient.clone());
+        }
+
+        let message = message.body(notification.to_text())?;
+        self.transport.send(message).await?;
+
+        Ok(())
+    }
+}
@@ -1,14 +1,22 @@
+pub(crate) mod email_notifier;
+pub(crate) mod notification;
 pub(crate) mod telegram_notifier;
+pub(crate) mod webhook_notifier;
 
 use crate::models::{NetQualityConfig, NotificationConfig, OutageInfo, SpeedResult};
 use anyhow::Result;
 use chrono::Duration as ChronoDuration;
 use tracing::{info_span, trace, warn};
 
+use self::email_notifier::EmailNotifier;
+use self::notification::Notification;
 use self::telegram_notifier::TelegramNotifier;
+use self::webhook_notifier::WebhookNotifier;
 
 pub(crate) struct Notifier {
     telegram: Option<TelegramNotifier>,
+    webhooks: Vec<WebhookNotifier>,
+    email: Option<EmailNotifier>,
 }
 
 impl Notifier {
@@ -18,7 +26,18 @@ impl Notifier {
             None => None,
         };
 
-        Ok(Self { telegram })
+        let webhooks = config.webhooks.iter().map(WebhookNotifier::new).collect();
+
+        let email = match &config.email {
+            Some(email) => Some(EmailNotifier::new(email)?),
+            None => None,
+        };
+
+        Ok(Self {
+            telegram,
+            webhooks,
+            email,
+        })
     }
 
     pub(crate) async fn send_outage_end(
@@ -34,16 +53,14 @@ impl Notifier {
             .map(|value| format!("{:.2} Mbps", value))
             .unwrap_or_else(|| "n/a".to_string());
 
-        let message = format!(
-            "Outage ended.\nStart: {}\nEnd: {}\nDuration: {}\nDownload: {:.2} Mbps\nUpload: {}",
-            outage.started_at.to_rfc3339(),
-            outage.ended_at.to_rfc3339(),
-            duration_str,
-            speed.download_mbps,
-            upload
-        );
+        let notification = Notification::new("outage_ended", "Outage ended")
+            .with_field("Start", outage.started_at.to_rfc3339())
+            .with_field("End", outage.ended_at.to_rfc3339())
+            .with_field("Duration", duration_str)
+            .with_field("Download", format!("{:.2} Mbps", speed.download_mbps))
+            .with_field("Upload", upload);
 
-        self.send_message(config, &message).await;
+        self.send(config, &notification).await;
     }
 
     pub(crate) async fn send_speed_change(
@@ -51,37 +68,64 @@ impl Notifier {
         config: &NetQualityConfig,
         speed: &SpeedResult,
     ) {
-        let mut message = format!(
-            "Speed change detected.\nDownload: {:.2} Mbps ({})",
-            speed.download_mbps,
-            speed.download_threshold.label()
-        );
+        let mut notification = Notification::new("speed_change", "Speed change detected")
+            .with_field(
+                "Download",
+                format!(
+                    "{:.2} Mbps ({})",
+                    speed.download_mbps,
+                    speed.download_threshold.label()
+                ),
+            );
 
         if let Some(upload) = speed.upload_mbps {
             let label = speed
                 .upload_threshold
                 .map(|threshold| threshold.label())
                 .unwrap_or("Unknown");
-            message.push_str(&format!("\nUpload: {:.2} Mbps ({})", upload, label));
+            notification =
+                notification.with_field("Upload", format!("{:.2} Mbps ({})", upload, label));
         }
 
-        self.send_message(config, &message).await;
+        self.send(config, &notification).await;
     }
 
-    pub(crate) async fn send_message(&mut self, config: &NetQualityConfig, message: &str) {
+    /// Sends the notification to every configured channel. A failing channel doesn't stop the others.
+    async fn send(&mut self, config: &NetQualityConfig, notification: &Notification) {
+        let message = notification.to_text();
+
         if let Some(telegram) = &self.telegram {
-            if let Err(error) = telegram.send(message).await {
+            if let Err(error) = telegram.send(&message).await {
                 warn!("Failed to send Telegram notification: {error}");
             }
         }
 
+        for webhook in &self.webhooks {
+            if let Err(error) = webhook.send(notification).await {
+                warn!("Failed to send {} notification: {error}", webhook.label());
+            }
+        }
+
+        if let Some(email) = &self.email {
+            if let Err(error) = email.send(notification).await {
+                warn!("Failed to send email notification: {error}");
+            }
+        }
+
         // Emit a tracing span that raccoon_otel's subscriber exports to the OTel collector.
         // This replaces the old OpenTelemetryNotifier — same data, no separate provider.
-        let span = info_span!("netquality.notification", "notification.message" = message,);
+        let span = info_span!(
+            "netquality.notification",
+            "notification.event" = notification.event,
+            "notification.message" = message.as_str(),
+        );
         let _guard = span.enter();
 
         trace!("Notification sent: {}", message);
-        if config.notifications.telegram.is_none() {
+        if config.notifications.telegram.is_none()
+            && config.notifications.webhooks.is_empty()
+            && config.notifications.email.is_none()
+        {
             warn!("No notification channels configured; message only sent via tracing.");
         }
     }
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+
+/// A notification, kept structured so each channel can format it its own way.
+#[derive(Debug, Clone)]
+pub(crate) struct Notification {
+    /// Stable identifier for automations, e.g.: `outage_ended`.
+    pub event: &'static str,
+    pub title: String,
+    pub fields: Vec<(String, String)>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Notification {
+    pub(crate) fn new(event: &'static str, title: impl Into<String>) -> Self {
+        Self {
+            event,
+            title: title.into(),
+            fields: Vec::new(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub(crate) fn with_field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push((name.into(), value.into()));
+        self
+    }
+
+    /// Plain text version, used by Telegram, email, and tracing.
+    pub(crate) fn to_text(&self) -> String {
+        let mut text = format!("{}.", self.title);
+        for (name, value) in &self.fields {
+            text.push_str(&format!("\n{}: {}", name, value));
+        }
+        text
+    }
+}
@@ -0,0 +1,155 @@
+use crate::models::{WebhookConfig, WebhookFormat};
+use crate::notifiers::notification::Notification;
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde_json::{json, Map, Value};
+
+/// Discord embed color for NetQuality messages (orange).
+const DISCORD_EMBED_COLOR: u32 = 0xFF7F0E;
+
+pub(crate) struct WebhookNotifier {
+    client: Client,
+    url: String,
+    format: WebhookFormat,
+}
+
+impl WebhookNotifier {
+    pub(crate) fn new(config: &WebhookConfig) -> Self {
+        Self {
+            client: Client::new(),
+            url: config.url.clone(),
+            format: config.format,
+        }
+    }
+
+    pub(crate) fn label(&self) -> &'static str {
+        self.format.label()
+    }
+
+    pub(crate) async fn send(&self, notification: &Notification) -> Result<()> {
+        let payload = build_payload(self.format, notification);
+
+        let response = self.client.post(&self.url).json(&payload).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "{} webhook response status {}",
+                self.label(),
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn build_payload(format: WebhookFormat, notification: &Notification) -> Value {
+    match format {
+        WebhookFormat::Generic => {
+            let fields: Map<String, Value> = notification
+                .fields
+                .iter()
+                .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+                .collect();
+
+            json!({
+                "source": "netquality",
+                "event": notification.event,
+                "title": notification.title,
+                "message": notification.to_text(),
+                "timestamp": notification.timestamp.to_rfc3339(),
+                "fields": fields,
+            })
+        }
+        WebhookFormat::Slack => {
+            let details = notification
+                .fields
+                .iter()
+                .map(|(name, value)| format!("*{}:* {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            json!({
+                "text": notification.to_text(),
+                "blocks": [
+                    {
+                        "type": "header",
+                        "text": { "type": "plain_text", "text": notification.title }
+                    },
+                    {
+                        "type": "section",
+                        "text": { "type": "mrkdwn", "text": details }
+                    }
+                ]
+            })
+        }
+        WebhookFormat::Discord => {
+            let fields: Vec<Value> = notification
+                .fields
+                .iter()
+                .map(|(name, value)| json!({ "name": name, "value": value, "inline": true }))
+                .collect();
+
+            json!({
+                "username": "NetQuality",
+                "embeds": [
+                    {
+                        "title": notification.title,
+                        "color": DISCORD_EMBED_COLOR,
+                        "fields": fields,
+                        "timestamp": notification.timestamp.to_rfc3339(),
+                    }
+                ]
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification() -> Notification {
+        Notification::new("speed_change", "Speed change detected")
+            .with_field("Download", "42.00 Mbps (Slow)")
+            .with_field("Upload", "10.00 Mbps (Expected)")
+    }
+
+    #[test]
+    fn generic_payload_has_event_and_fields() {
+        let payload = build_payload(WebhookFormat::Generic, &notification());
+
+        assert_eq!(payload["event"], "speed_change");
+        assert_eq!(payload["fields"]["Download"], "42.00 Mbps (Slow)");
+        assert_eq!(
+            payload["message"],
+            "Speed change detected.\nDownload: 42.00 Mbps (Slow)\nUpload: 10.00 Mbps (Expected)"
+        );
+    }
+
+    #[test]
+    fn slack_payload_uses_blocks_with_text_fallback() {
+        let payload = build_payload(WebhookFormat::Slack, &notification());
+
+        assert!(payload["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("Speed change detected."));
+        assert_eq!(
+            payload["blocks"][0]["text"]["text"],
+            "Speed change detected"
+        );
+        assert_eq!(
+            payload["blocks"][1]["text"]["text"],
+            "*Download:* 42.00 Mbps (Slow)\n*Upload:* 10.00 Mbps (Expected)"
+        );
+    }
+
+    #[test]
+    fn discord_payload_uses_an_embed() {
+        let payload = build_payload(WebhookFormat::Discord, &notification());
+
+        assert_eq!(payload["embeds"][0]["title"], "Speed change detected");
+        assert_eq!(payload["embeds"][0]["fields"][1]["name"], "Upload");
+        assert_eq!(payload["embeds"][0]["fields"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::models::{DashboardSummary, OutagePeriod, SpeedSample, UptimeBucket};
+use crate::persistence::db;
+use crate::report::report_builder::{detect_outages, percent, summarize};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OpenFlags};
+use std::path::Path;
+
+/// Ranges up to this many days are grouped by hour; longer ranges are grouped by day.
+const HOURLY_BUCKETS_MAX_DAYS: u32 = 2;
+
+/// Length of the RFC3339 timestamp prefix used as bucket: `2026-01-23T18` (hour) or `2026-01-23` (day).
+const HOUR_BUCKET_LEN: i64 = 13;
+const DAY_BUCKET_LEN: i64 = 10;
+
+/// Opens the database in read-only mode, so the dashboard never competes with the monitor for writes.
+pub(crate) fn open_read_only(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .context(format!("Failed to open database: {}", path.display()))
+}
+
+pub(crate) fn query_uptime_buckets(
+    conn: &Connection,
+    since: DateTime<Utc>,
+    days: u32,
+) -> Result<Vec<UptimeBucket>> {
+    let bucket_len = if days <= HOURLY_BUCKETS_MAX_DAYS {
+        HOUR_BUCKET_LEN
+    } else {
+        DAY_BUCKET_LEN
+    };
+
+    let mut statement = conn.prepare(
+        r#"
+        SELECT substr(timestamp, 1, ?2) AS bucket, COUNT(*), SUM(success)
+        FROM activity_connectivity
+        WHERE timestamp >= ?1
+        GROUP BY bucket
+        ORDER BY bucket
+        "#,
+    )?;
+
+    let rows = statement.query_map(params![since.to_rfc3339(), bucket_len], |row| {
+        let checks: i64 = row.get(1)?;
+        let successes: i64 = row.get(2)?;
+        Ok(UptimeBucket {
+            bucket: row.get(0)?,
+            checks: checks as u64,
+            successes: successes as u64,
+            uptime_percent: percent(successes as u64, checks as u64).unwrap_or(0.0),
+        })
+    })?;
+
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+pub(crate) fn query_speed_samples(
+    conn: &Connection,
+    since: DateTime<Utc>,
+) -> Result<Vec<SpeedSample>> {
+    db::query_speed_between(conn, since, Utc::now())
+}
+
+pub(crate) fn query_outages(conn: &Connection, since: DateTime<Utc>) -> Result<Vec<OutagePeriod>> {
+    let now = Utc::now();
+    let samples = db::query_connectivity_between(conn, since, now)?;
+    Ok(detect_outages(&samples, now))
+}
+
+pub(crate) fn query_summary(
+    conn: &Connection,
+    since: DateTime<Utc>,
+    days: u32,
+) -> Result<DashboardSummary> {
+    let now = Utc::now();
+    let connectivity = db::query_connectivity_between(conn, since, now)?;
+    let speed = db::query_speed_between(conn, since, now)?;
+    let report = summarize(since, now, &connectivity, &speed, now);
+
+    Ok(DashboardSummary {
+        days,
+        connectivity_checks: report.connectivity_checks,
+        failed_checks: report.failed_checks,
+        uptime_percent: report.uptime_percent,
+        outages: report.outage_count,
+        downtime_secs: report.outage_total_secs,
+        speed_tests: report.speed_tests,
+        avg_download_mbps: report.download.map(|download| download.avg),
+        avg_upload_mbps: report.upload.map(|upload| upload.avg),
+        last_check_at: connectivity.last().map(|(timestamp, _)| *timestamp),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConnectivityResult, SpeedResult, ThresholdCategory};
+    use crate::persistence::db::{
+        create_database, insert_connectivity_activity, insert_speed_activity,
+    };
+    use chrono::Duration as ChronoDuration;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn create_temp_db_path() -> std::path::PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        std::env::temp_dir().join(format!("netquality-dashboard-test-{}.db", nanos))
+    }
+
+    fn connectivity(timestamp: DateTime<Utc>, success: bool) -> ConnectivityResult {
+        ConnectivityResult {
+            timestamp,
+            url: "https://example.com/health".to_string(),
+            result: if success { "204" } else { "timeout" }.to_string(),
+            elapsed_ms: 42,
+            success,
+        }
+    }
+
+    #[test]
+    fn summary_and_buckets_are_built_from_the_tables() {
+        let path = create_temp_db_path();
+        let conn = create_database(&path).expect("create_database should succeed");
+        let now = Utc::now();
+
+        for (minutes_ago, success) in [(30, true), (20, false), (10, true), (5, true)] {
+            let result = connectivity(now - ChronoDuration::minutes(minutes_ago), success);
+            insert_connectivity_activity(&conn, &result).expect("insert connectivity");
+        }
+
+        insert_speed_activity(
+            &conn,
+            &SpeedResult {
+                timestamp: now - ChronoDuration::minutes(15),
+                download_mbps: 100.0,
+                upload_mbps: Some(20.0),
+                download_threshold: ThresholdCategory::Expected,
+                upload_threshold: Some(ThresholdCategory::Expected),
+                elapsed_ms: 800,
+                success: true,
+            },
+        )
+        .expect("insert speed");
+        drop(conn);
+
+        let conn = open_read_only(&path).expect("open read-only");
+        let since = now - ChronoDuration::days(1);
+
+        let summary = query_summary(&conn, since, 1).expect("summary");
+        assert_eq!(summary.connectivity_checks, 4);
+        assert_eq!(summary.failed_checks, 1);
+        assert_eq!(summary.uptime_percent, Some(75.0));
+        assert_eq!(summary.outages, 1);
+        assert_eq!(summary.speed_tests, 1);
+        assert_eq!(summary.avg_download_mbps, Some(100.0));
+
+        let buckets = query_uptime_buckets(&conn, since, 1).expect("buckets");
+        let total_checks: u64 = buckets.iter().map(|b| b.checks).sum();
+        assert_eq!(total_checks, 4);
+        assert!(buckets
+            .iter()
+            .all(|b| b.bucket.len() == HOUR_BUCKET_LEN as usize));
+
+        drop(conn);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -1,6 +1,8 @@
-use crate::models::{ConnectivityResult, SpeedResult};
-use anyhow::Result;
-use chrono::{Duration as ChronoDuration, Utc};
+use crate::models::{
+    ConnectivityResult, DiagnosticsSample, OutageDiagnostics, SpeedResult, SpeedSample,
+};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use rusqlite::{params, Connection};
 use std::path::Path;
 
@@ -11,6 +13,7 @@ pub(crate) struct CleanupStats {
     pub(crate) sessions_deleted: usize,
     pub(crate) connectivity_deleted: usize,
     pub(crate) speed_deleted: usize,
+    pub(crate) diagnostics_deleted: usize,
 }
 
 pub(crate) fn create_database(path: &Path) -> Result<Connection> {
@@ -50,6 +53,21 @@ pub(crate) fn create_database(path: &Path) -> Result<Connection> {
             FOREIGN KEY(speed_id) REFERENCES activity_speed(activity_id),
             FOREIGN KEY(parent_session_id) REFERENCES sessions(session_id)
         );
+        CREATE TABLE IF NOT EXISTS outage_diagnostics (
+            diagnostics_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            connectivity_id INTEGER NOT NULL,
+            timestamp TEXT NOT NULL,
+            gateway TEXT,
+            gateway_interface TEXT,
+            gateway_reachable INTEGER NOT NULL,
+            gateway_elapsed_time INTEGER,
+            dns_host TEXT,
+            dns_success INTEGER,
+            dns_result TEXT,
+            dns_elapsed_time INTEGER,
+            interfaces TEXT NOT NULL,
+            FOREIGN KEY(connectivity_id) REFERENCES activity_connectivity(activity_id)
+        );
         CREATE VIEW IF NOT EXISTS session_activity_view AS
         SELECT
             s.session_id,
@@ -124,6 +142,52 @@ pub(crate) fn insert_speed_activity(conn: &Connection, result: &SpeedResult) ->
     Ok(conn.last_insert_rowid())
 }
 
+/// Stores the diagnostics captured when an outage started, linked to the failed connectivity check.
+/// Interfaces are stored as a JSON array.
+pub(crate) fn insert_outage_diagnostics(
+    conn: &Connection,
+    connectivity_id: i64,
+    diagnostics: &OutageDiagnostics,
+) -> Result<i64> {
+    let gateway = &diagnostics.gateway;
+    let dns = diagnostics.dns.as_ref();
+    let interfaces = serde_json::to_string(&diagnostics.interfaces)?;
+
+    conn.execute(
+        r#"
+        INSERT INTO outage_diagnostics (
+            connectivity_id,
+            timestamp,
+            gateway,
+            gateway_interface,
+            gateway_reachable,
+            gateway_elapsed_time,
+            dns_host,
+            dns_success,
+            dns_result,
+            dns_elapsed_time,
+            interfaces
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+        "#,
+        params![
+            connectivity_id,
+            diagnostics.timestamp.to_rfc3339(),
+            gateway.address.map(|address| address.to_string()),
+            gateway.interface,
+            if gateway.reachable { 1 } else { 0 },
+            gateway.elapsed_ms,
+            dns.map(|dns| dns.host.clone()),
+            dns.map(|dns| if dns.success { 1 } else { 0 }),
+            dns.map(|dns| dns.result.clone()),
+            dns.map(|dns| dns.elapsed_ms),
+            interfaces
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
 pub(crate) fn insert_session(
     conn: &Connection,
     parent_session_id: Option<i64>,
@@ -141,10 +205,129 @@ pub(crate) fn insert_session(
     Ok(conn.last_insert_rowid())
 }
 
+/// Returns `(timestamp, success)` for every connectivity check in `[from, to)`, oldest first.
+pub(crate) fn query_connectivity_between(
+    conn: &Connection,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<(DateTime<Utc>, bool)>> {
+    let mut statement = conn.prepare(
+        r#"
+        SELECT timestamp, success
+        FROM activity_connectivity
+        WHERE timestamp >= ?1 AND timestamp < ?2
+        ORDER BY timestamp
+        "#,
+    )?;
+
+    let rows = statement.query_map(params![from.to_rfc3339(), to.to_rfc3339()], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+
+    let mut samples = Vec::new();
+    for row in rows {
+        let (timestamp, success) = row?;
+        samples.push((parse_stored_timestamp(&timestamp)?, success == 1));
+    }
+
+    Ok(samples)
+}
+
+/// Returns every speed test in `[from, to)`, oldest first.
+pub(crate) fn query_speed_between(
+    conn: &Connection,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<SpeedSample>> {
+    let mut statement = conn.prepare(
+        r#"
+        SELECT timestamp, download_speed, upload_speed, download_threshold, upload_threshold, success
+        FROM activity_speed
+        WHERE timestamp >= ?1 AND timestamp < ?2
+        ORDER BY timestamp
+        "#,
+    )?;
+
+    let rows = statement.query_map(params![from.to_rfc3339(), to.to_rfc3339()], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, f64>(1)?,
+            row.get::<_, Option<f64>>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, i64>(5)?,
+        ))
+    })?;
+
+    let mut samples = Vec::new();
+    for row in rows {
+        let (timestamp, download, upload, download_threshold, upload_threshold, success) = row?;
+        samples.push(SpeedSample {
+            timestamp: parse_stored_timestamp(&timestamp)?,
+            download_mbps: download,
+            upload_mbps: upload,
+            download_threshold,
+            upload_threshold,
+            success: success == 1,
+        });
+    }
+
+    Ok(samples)
+}
+
+/// Returns the outage diagnostics captured in `[from, to)`, oldest first.
+pub(crate) fn query_diagnostics_between(
+    conn: &Connection,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<DiagnosticsSample>> {
+    let mut statement = conn.prepare(
+        r#"
+        SELECT timestamp, gateway, gateway_reachable, dns_success
+        FROM outage_diagnostics
+        WHERE timestamp >= ?1 AND timestamp < ?2
+        ORDER BY timestamp
+        "#,
+    )?;
+
+    let rows = statement.query_map(params![from.to_rfc3339(), to.to_rfc3339()], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, Option<i64>>(3)?,
+        ))
+    })?;
+
+    let mut samples = Vec::new();
+    for row in rows {
+        let (timestamp, gateway, gateway_reachable, dns_success) = row?;
+        samples.push(DiagnosticsSample {
+            timestamp: parse_stored_timestamp(&timestamp)?,
+            gateway,
+            gateway_reachable: gateway_reachable == 1,
+            dns_success: dns_success.map(|success| success == 1),
+        });
+    }
+
+    Ok(samples)
+}
+
+fn parse_stored_timestamp(value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| anyhow!("Invalid timestamp in database ({}): {}", value, e))
+}
+
 pub(crate) fn cleanup_old_activity(conn: &mut Connection) -> Result<CleanupStats> {
     let cutoff = (Utc::now() - ChronoDuration::days(CLEANUP_RETENTION_DAYS)).to_rfc3339();
     let tx = conn.transaction()?;
 
+    let diagnostics_deleted = tx.execute(
+        "DELETE FROM outage_diagnostics WHERE timestamp < ?1",
+        params![cutoff],
+    )?;
+
     let sessions_deleted = tx.execute(
         r#"
         DELETE FROM sessions
@@ -192,13 +375,14 @@ pub(crate) fn cleanup_old_activity(conn: &mut Connection) -> Result<CleanupStats
         sessions_deleted,
         connectivity_deleted,
         speed_deleted,
+        diagnostics_deleted,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::ThresholdCategory;
+    use crate::models::{GatewayCheck, InterfaceStatus, ThresholdCategory};
     use chrono::Utc;
     use std::fs;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -315,4 +499,97 @@ mod tests {
         drop(conn);
         let _ = fs::remove_file(path);
     }
+
+    #[test]
+    fn outage_diagnostics_are_linked_to_the_failed_check() {
+        let (conn, path) = create_test_connection();
+
+        let mut connectivity = build_connectivity_result();
+        connectivity.success = false;
+        let connectivity_id =
+            insert_connectivity_activity(&conn, &connectivity).expect("insert connectivity");
+
+        let diagnostics = OutageDiagnostics {
+            timestamp: Utc::now(),
+            gateway: GatewayCheck {
+                address: Some("192.168.1.1".parse().unwrap()),
+                interface: Some("eth0".to_string()),
+                reachable: false,
+                elapsed_ms: None,
+            },
+            dns: None,
+            interfaces: vec![InterfaceStatus {
+                name: "eth0".to_string(),
+                up: true,
+                state: "up".to_string(),
+            }],
+        };
+
+        insert_outage_diagnostics(&conn, connectivity_id, &diagnostics)
+            .expect("insert diagnostics");
+
+        let (stored_id, gateway, reachable, dns_host, interfaces): (
+            i64,
+            Option<String>,
+            i64,
+            Option<String>,
+            String,
+        ) = conn
+            .query_row(
+                "SELECT connectivity_id, gateway, gateway_reachable, dns_host, interfaces FROM outage_diagnostics",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            )
+            .expect("query diagnostics");
+
+        assert_eq!(stored_id, connectivity_id);
+        assert_eq!(gateway.as_deref(), Some("192.168.1.1"));
+        assert_eq!(reachable, 0);
+        assert_eq!(dns_host, None);
+        assert_eq!(interfaces, r#"[{"name":"eth0","up":true,"state":"up"}]"#);
+
+        let samples = query_diagnostics_between(
+            &conn,
+            diagnostics.timestamp - ChronoDuration::minutes(1),
+            diagnostics.timestamp + ChronoDuration::minutes(1),
+        )
+        .expect("query diagnostics range");
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].gateway.as_deref(), Some("192.168.1.1"));
+        assert!(!samples[0].gateway_reachable);
+        assert_eq!(samples[0].dns_success, None);
+
+        drop(conn);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn range_queries_only_return_rows_inside_the_range() {
+        let (conn, path) = create_test_connection();
+        let now = Utc::now();
+
+        for minutes_ago in [90, 30, 10] {
+            let mut connectivity = build_connectivity_result();
+            connectivity.timestamp = now - ChronoDuration::minutes(minutes_ago);
+            insert_connectivity_activity(&conn, &connectivity).expect("insert connectivity");
+
+            let mut speed = build_speed_result();
+            speed.timestamp = now - ChronoDuration::minutes(minutes_ago);
+            insert_speed_activity(&conn, &speed).expect("insert speed");
+        }
+
+        let from = now - ChronoDuration::minutes(60);
+        let to = now - ChronoDuration::minutes(20);
+
+        let connectivity = query_connectivity_between(&conn, from, to).expect("query connectivity");
+        let speed = query_speed_between(&conn, from, to).expect("query speed");
+
+        assert_eq!(connectivity.len(), 1);
+        assert!(connectivity[0].1);
+        assert_eq!(speed.len(), 1);
+        assert_eq!(speed[0].download_threshold, "Expected");
+
+        drop(conn);
+        let _ = fs::remove_file(path);
+    }
 }
@@ -1 +1,2 @@
+pub mod dashboard_queries;
 pub mod db;
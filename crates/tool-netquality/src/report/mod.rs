@@ -0,0 +1,3 @@
+pub mod report_builder;
+pub mod report_formatter;
+pub mod report_runner;
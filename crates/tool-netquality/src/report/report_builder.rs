@@ -0,0 +1,226 @@
+use crate::models::{NetQualityReport, OutagePeriod, SpeedPercentiles, SpeedSample};
+use crate::persistence::db;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+
+/// Queries the checks in `[from, to)` and summarizes them.
+pub(crate) fn build_report(
+    conn: &Connection,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<NetQualityReport> {
+    let connectivity = db::query_connectivity_between(conn, from, to)?;
+    let speed = db::query_speed_between(conn, from, to)?;
+
+    Ok(summarize(from, to, &connectivity, &speed, Utc::now()))
+}
+
+/// Builds the report from the checks. Outages still ongoing at the end of the range are
+/// measured until `to` (or `now`, if the range ends in the future).
+pub(crate) fn summarize(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    connectivity: &[(DateTime<Utc>, bool)],
+    speed: &[SpeedSample],
+    now: DateTime<Utc>,
+) -> NetQualityReport {
+    let outages = detect_outages(connectivity, to.min(now));
+
+    let connectivity_checks = connectivity.len() as u64;
+    let failed_checks = connectivity.iter().filter(|(_, success)| !success).count() as u64;
+
+    let successful_speed: Vec<&SpeedSample> = speed.iter().filter(|s| s.success).collect();
+    let downloads: Vec<f64> = successful_speed.iter().map(|s| s.download_mbps).collect();
+    let uploads: Vec<f64> = successful_speed
+        .iter()
+        .filter_map(|s| s.upload_mbps)
+        .collect();
+
+    NetQualityReport {
+        from,
+        to,
+        connectivity_checks,
+        failed_checks,
+        uptime_percent: percent(connectivity_checks - failed_checks, connectivity_checks),
+        outage_count: outages.len(),
+        outage_total_secs: outages.iter().map(|o| o.duration_secs).sum(),
+        longest_outage_secs: outages.iter().map(|o| o.duration_secs).max().unwrap_or(0),
+        speed_tests: speed.len(),
+        failed_speed_tests: speed.len() - successful_speed.len(),
+        download: SpeedPercentiles::from_values(&downloads),
+        upload: SpeedPercentiles::from_values(&uploads),
+        outages,
+    }
+}
+
+/// Groups consecutive failed checks into outages.
+///
+/// An outage starts at the first failed check and ends at the next successful one. If the
+/// last checks failed, the outage is still ongoing and its duration is measured until `now`.
+pub(crate) fn detect_outages(
+    samples: &[(DateTime<Utc>, bool)],
+    now: DateTime<Utc>,
+) -> Vec<OutagePeriod> {
+    let mut outages = Vec::new();
+    let mut current: Option<(DateTime<Utc>, u64)> = None;
+
+    for (timestamp, success) in samples {
+        match (current, success) {
+            (None, false) => current = Some((*timestamp, 1)),
+            (Some((started_at, failed)), false) => current = Some((started_at, failed + 1)),
+            (Some((started_at, failed)), true) => {
+                outages.push(OutagePeriod {
+                    started_at,
+                    ended_at: Some(*timestamp),
+                    duration_secs: (*timestamp - started_at).num_seconds(),
+                    failed_checks: failed,
+                });
+                current = None;
+            }
+            (None, true) => {}
+        }
+    }
+
+    if let Some((started_at, failed)) = current {
+        outages.push(OutagePeriod {
+            started_at,
+            ended_at: None,
+            duration_secs: (now - started_at).num_seconds().max(0),
+            failed_checks: failed,
+        });
+    }
+
+    outages
+}
+
+impl SpeedPercentiles {
+    /// Returns `None` when there are no values.
+    pub(crate) fn from_values(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        Some(Self {
+            min: sorted[0],
+            p5: percentile(&sorted, 5.0),
+            p50: percentile(&sorted, 50.0),
+            p95: percentile(&sorted, 95.0),
+            max: sorted[sorted.len() - 1],
+            avg: average(sorted.iter().copied()).unwrap_or_default(),
+        })
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+pub(crate) fn percent(part: u64, total: u64) -> Option<f64> {
+    if total == 0 {
+        return None;
+    }
+
+    Some(part as f64 / total as f64 * 100.0)
+}
+
+pub(crate) fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+
+    if count == 0 {
+        return None;
+    }
+
+    Some(sum / count as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 23, 18, minute, 0).unwrap()
+    }
+
+    fn speed(download: f64, upload: Option<f64>, success: bool) -> SpeedSample {
+        SpeedSample {
+            timestamp: at(0),
+            download_mbps: download,
+            upload_mbps: upload,
+            download_threshold: "Expected".to_string(),
+            upload_threshold: None,
+            success,
+        }
+    }
+
+    #[test]
+    fn detect_outages_groups_consecutive_failures() {
+        let samples = vec![
+            (at(0), true),
+            (at(1), false),
+            (at(2), false),
+            (at(3), true),
+            (at(4), false),
+        ];
+
+        let outages = detect_outages(&samples, at(10));
+
+        assert_eq!(outages.len(), 2);
+        assert_eq!(outages[0].started_at, at(1));
+        assert_eq!(outages[0].ended_at, Some(at(3)));
+        assert_eq!(outages[0].duration_secs, 120);
+        assert_eq!(outages[0].failed_checks, 2);
+        assert_eq!(outages[1].ended_at, None);
+        assert_eq!(outages[1].duration_secs, 360);
+    }
+
+    #[test]
+    fn detect_outages_without_failures_is_empty() {
+        let samples = vec![(at(0), true), (at(1), true)];
+
+        assert!(detect_outages(&samples, at(2)).is_empty());
+    }
+
+    #[test]
+    fn speed_percentiles_use_nearest_rank() {
+        let values: Vec<f64> = (1..=20).map(|v| v as f64 * 10.0).collect();
+
+        let percentiles = SpeedPercentiles::from_values(&values).unwrap();
+
+        assert_eq!(percentiles.min, 10.0);
+        assert_eq!(percentiles.p5, 10.0);
+        assert_eq!(percentiles.p50, 100.0);
+        assert_eq!(percentiles.p95, 190.0);
+        assert_eq!(percentiles.max, 200.0);
+        assert_eq!(percentiles.avg, 105.0);
+        assert!(SpeedPercentiles::from_values(&[]).is_none());
+    }
+
+    #[test]
+    fn summarize_measures_ongoing_outage_until_the_end_of_the_range() {
+        let connectivity = vec![(at(0), true), (at(1), false), (at(2), false)];
+        let speed_tests = vec![
+            speed(100.0, Some(20.0), true),
+            speed(0.0, None, false),
+            speed(50.0, None, true),
+        ];
+
+        let report = summarize(at(0), at(5), &connectivity, &speed_tests, at(30));
+
+        assert_eq!(report.connectivity_checks, 3);
+        assert_eq!(report.failed_checks, 2);
+        assert_eq!(report.outage_count, 1);
+        assert_eq!(report.outage_total_secs, 240);
+        assert_eq!(report.longest_outage_secs, 240);
+        assert_eq!(report.speed_tests, 3);
+        assert_eq!(report.failed_speed_tests, 1);
+        assert_eq!(report.download.as_ref().map(|d| d.avg), Some(75.0));
+        assert_eq!(report.upload.as_ref().map(|u| u.max), Some(20.0));
+    }
+}
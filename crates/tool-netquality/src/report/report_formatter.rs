@@ -0,0 +1,228 @@
+use crate::models::{NetQualityReport, ReportFormat, SpeedPercentiles};
+use anyhow::{Context, Result};
+use shared::constants::general::DASH_LINE;
+use std::fmt::Write;
+
+pub(crate) fn format_report(report: &NetQualityReport, format: ReportFormat) -> Result<String> {
+    match format {
+        ReportFormat::Text => Ok(format_text(report)),
+        ReportFormat::Csv => Ok(format_csv(report)),
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(report).context("Failed to serialize the report to JSON")
+        }
+    }
+}
+
+fn format_text(report: &NetQualityReport) -> String {
+    let mut text = String::new();
+
+    let _ = writeln!(text, "NetQuality report");
+    let _ = writeln!(text, "{}", DASH_LINE);
+    let _ = writeln!(text, "- From: {}", report.from.to_rfc3339());
+    let _ = writeln!(text, "- To: {}", report.to.to_rfc3339());
+    let _ = writeln!(
+        text,
+        "- Uptime: {}",
+        format_optional(report.uptime_percent, "%")
+    );
+    let _ = writeln!(
+        text,
+        "- Connectivity checks: {} ({} failed)",
+        report.connectivity_checks, report.failed_checks
+    );
+    let _ = writeln!(
+        text,
+        "- Outages: {} (total: {}, longest: {})",
+        report.outage_count,
+        format_duration(report.outage_total_secs),
+        format_duration(report.longest_outage_secs)
+    );
+    let _ = writeln!(
+        text,
+        "- Speed tests: {} ({} failed)",
+        report.speed_tests, report.failed_speed_tests
+    );
+    let _ = writeln!(
+        text,
+        "- Download: {}",
+        format_percentiles(report.download.as_ref())
+    );
+    let _ = writeln!(
+        text,
+        "- Upload: {}",
+        format_percentiles(report.upload.as_ref())
+    );
+
+    if !report.outages.is_empty() {
+        let _ = writeln!(text, "{}", DASH_LINE);
+        let _ = writeln!(text, "Outages:");
+        for outage in &report.outages {
+            let ended_at = outage
+                .ended_at
+                .map(|ended_at| ended_at.to_rfc3339())
+                .unwrap_or_else(|| "ongoing".to_string());
+            let _ = writeln!(
+                text,
+                "- {} -> {} ({}, {} failed checks)",
+                outage.started_at.to_rfc3339(),
+                ended_at,
+                format_duration(outage.duration_secs),
+                outage.failed_checks
+            );
+        }
+    }
+
+    text
+}
+
+/// One `metric,value` row per number, so the file opens nicely in a spreadsheet.
+fn format_csv(report: &NetQualityReport) -> String {
+    let mut rows: Vec<(String, String)> = vec![
+        ("from".to_string(), report.from.to_rfc3339()),
+        ("to".to_string(), report.to.to_rfc3339()),
+        (
+            "connectivity_checks".to_string(),
+            report.connectivity_checks.to_string(),
+        ),
+        (
+            "failed_checks".to_string(),
+            report.failed_checks.to_string(),
+        ),
+        (
+            "uptime_percent".to_string(),
+            format_optional(report.uptime_percent, ""),
+        ),
+        ("outage_count".to_string(), report.outage_count.to_string()),
+        (
+            "outage_total_secs".to_string(),
+            report.outage_total_secs.to_string(),
+        ),
+        (
+            "longest_outage_secs".to_string(),
+            report.longest_outage_secs.to_string(),
+        ),
+        ("speed_tests".to_string(), report.speed_tests.to_string()),
+        (
+            "failed_speed_tests".to_string(),
+            report.failed_speed_tests.to_string(),
+        ),
+    ];
+
+    rows.extend(percentile_rows("download_mbps", report.download.as_ref()));
+    rows.extend(percentile_rows("upload_mbps", report.upload.as_ref()));
+
+    let mut csv = String::from("metric,value\n");
+    for (metric, value) in rows {
+        let _ = writeln!(csv, "{},{}", metric, value);
+    }
+
+    csv
+}
+
+fn percentile_rows(prefix: &str, percentiles: Option<&SpeedPercentiles>) -> Vec<(String, String)> {
+    let values = [
+        ("min", percentiles.map(|p| p.min)),
+        ("p5", percentiles.map(|p| p.p5)),
+        ("p50", percentiles.map(|p| p.p50)),
+        ("p95", percentiles.map(|p| p.p95)),
+        ("max", percentiles.map(|p| p.max)),
+        ("avg", percentiles.map(|p| p.avg)),
+    ];
+
+    values
+        .into_iter()
+        .map(|(name, value)| (format!("{}_{}", prefix, name), format_optional(value, "")))
+        .collect()
+}
+
+fn format_percentiles(percentiles: Option<&SpeedPercentiles>) -> String {
+    match percentiles {
+        Some(p) => format!(
+            "min {:.2} | p5 {:.2} | p50 {:.2} | p95 {:.2} | max {:.2} | avg {:.2} (Mbps)",
+            p.min, p.p5, p.p50, p.p95, p.max, p.avg
+        ),
+        None => "no successful speed tests".to_string(),
+    }
+}
+
+/// Formats with 2 decimals. Missing values are empty in CSV and `-` in text.
+fn format_optional(value: Option<f64>, suffix: &str) -> String {
+    match (value, suffix.is_empty()) {
+        (Some(value), _) => format!("{:.2}{}", value, suffix),
+        (None, true) => String::new(),
+        (None, false) => "-".to_string(),
+    }
+}
+
+pub(crate) fn format_duration(total_secs: i64) -> String {
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OutagePeriod;
+    use chrono::{TimeZone, Utc};
+
+    fn sample_report() -> NetQualityReport {
+        let from = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap();
+
+        NetQualityReport {
+            from,
+            to,
+            connectivity_checks: 4,
+            failed_checks: 1,
+            uptime_percent: Some(75.0),
+            outage_count: 1,
+            outage_total_secs: 3725,
+            longest_outage_secs: 3725,
+            speed_tests: 1,
+            failed_speed_tests: 0,
+            download: SpeedPercentiles::from_values(&[100.0]),
+            upload: None,
+            outages: vec![OutagePeriod {
+                started_at: from,
+                ended_at: None,
+                duration_secs: 3725,
+                failed_checks: 1,
+            }],
+        }
+    }
+
+    #[test]
+    fn csv_has_one_row_per_metric() {
+        let csv = format_report(&sample_report(), ReportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "metric,value");
+        assert!(lines.contains(&"uptime_percent,75.00"));
+        assert!(lines.contains(&"download_mbps_p50,100.00"));
+        assert!(lines.contains(&"upload_mbps_p50,"));
+        assert_eq!(lines.len(), 23);
+    }
+
+    #[test]
+    fn text_lists_outages() {
+        let text = format_report(&sample_report(), ReportFormat::Text).unwrap();
+
+        assert!(text.contains("- Uptime: 75.00%"));
+        assert!(text.contains("longest: 01:02:05"));
+        assert!(text.contains("- Upload: no successful speed tests"));
+        assert!(text.contains("-> ongoing (01:02:05, 1 failed checks)"));
+    }
+
+    #[test]
+    fn json_is_valid() {
+        let json = format_report(&sample_report(), ReportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["outage_count"], 1);
+        assert_eq!(value["download"]["p50"], 100.0);
+        assert!(value["upload"].is_null());
+    }
+}
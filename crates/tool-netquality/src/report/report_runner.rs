@@ -0,0 +1,50 @@
+use crate::cli_utils::cli_utils::print_runtime_info;
+use crate::cli_utils::config_parser;
+use crate::models::{NetQualityCliArgs, ReportArgs};
+use crate::persistence::dashboard_queries::open_read_only;
+use crate::report::report_builder::build_report;
+use crate::report::report_formatter::format_report;
+use anyhow::{anyhow, Context, Result};
+
+/// Builds the report for the requested range and writes it to the output file or the console.
+///
+/// # Errors
+/// Returns an error if the config can't be loaded, the database doesn't exist yet, or the
+/// output file can't be written.
+pub async fn run_report(args: &NetQualityCliArgs, report_args: &ReportArgs) -> Result<()> {
+    let (storage, config_label) = config_parser::load_storage_config(args).await?;
+
+    if !storage.db_path.exists() {
+        return Err(anyhow!(
+            "Database not found: {}. Run netquality to collect some data first.",
+            storage.db_path.display()
+        ));
+    }
+
+    let conn = open_read_only(&storage.db_path)?;
+    let report = build_report(&conn, report_args.from, report_args.to)?;
+    let formatted = format_report(&report, report_args.format)?;
+
+    match &report_args.output {
+        Some(output) => {
+            // Runtime info only goes to the console when it can't end up mixed with the report.
+            print_runtime_info(
+                &config_label,
+                &[
+                    ("Database", storage.db_path.display().to_string()),
+                    ("From", report_args.from.to_rfc3339()),
+                    ("To", report_args.to.to_rfc3339()),
+                    ("Format", report_args.format.as_str().to_string()),
+                    ("Output", output.display().to_string()),
+                ],
+            );
+
+            std::fs::write(output, formatted)
+                .context(format!("Failed to write report: {}", output.display()))?;
+            println!("Report saved to {}", output.display());
+        }
+        None => print!("{}", formatted),
+    }
+
+    Ok(())
+}
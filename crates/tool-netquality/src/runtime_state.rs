@@ -46,11 +46,14 @@ impl LoopState {
     }
 }
 
+/// Updates the outage tracking and the next check time.
+///
+/// Returns `true` only on the transition to "down" (first failed check of an outage).
 pub(crate) fn handle_connectivity_state(
     config: &NetQualityConfig,
     state: &mut LoopState,
     result: &ConnectivityResult,
-) {
+) -> bool {
     state.last_connectivity_success = result.success;
     state.next_connectivity_at = Instant::now() + state.current_connectivity_delay;
 
@@ -66,10 +69,11 @@ pub(crate) fn handle_connectivity_state(
         }
 
         state.current_connectivity_delay = config.connectivity.delay;
-        return;
+        return false;
     }
 
-    if !state.outage_active {
+    let outage_started = !state.outage_active;
+    if outage_started {
         state.outage_active = true;
         state.outage_start = Some(result.timestamp);
         state.current_connectivity_delay = config.connectivity.outage_backoff;
@@ -80,6 +84,7 @@ pub(crate) fn handle_connectivity_state(
     }
 
     state.next_connectivity_at = Instant::now() + state.current_connectivity_delay;
+    outage_started
 }
 
 pub(crate) fn should_run_speed_check(state: &LoopState) -> bool {
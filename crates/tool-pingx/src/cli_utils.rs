@@ -1,9 +1,13 @@
-use crate::models::{IpMode, OutputMode, PingxArgs, ResolvedTargetInfo};
+use crate::models::{IpMode, OutputMode, PingxArgs, ProbeMode, ResolvedTargetInfo};
 use clap::{Arg, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
+use std::ffi::OsString;
 
-pub fn get_cli_arguments() -> anyhow::Result<PingxArgs> {
+const MULTI_TARGET_DEFAULT_COUNT: i64 = 4;
+const DEFAULT_TCP_PORT: u16 = 80;
+
+pub fn get_cli_arguments(argv: Vec<OsString>) -> anyhow::Result<PingxArgs> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -12,10 +16,28 @@ pub fn get_cli_arguments() -> anyhow::Result<PingxArgs> {
         )
         .arg(
             Arg::new("target")
-                .help("Hostname or IP address to ping")
+                .help("Hostname(s) or IP address(es) to ping (URLs with --mode http). With more than one, they are pinged concurrently and compared.")
                 .index(1)
+                .num_args(1..)
                 .required(true),
         )
+        .arg(
+            Arg::new("mode")
+                .short('M')
+                .long("mode")
+                .value_name("MODE")
+                .help("Probe used to measure latency: icmp (default), tcp (connect time to --port), or http (HEAD request to the URL)")
+                .value_parser(["icmp", "tcp", "http"])
+                .default_value("icmp"),
+        )
+        .arg(
+            Arg::new("port")
+                .long("port")
+                .value_name("PORT")
+                .help("Port used by --mode tcp (default 80)")
+                .value_parser(clap::value_parser!(u16))
+                .required(false),
+        )
         .arg(
             Arg::new("count")
                 .short('c')
@@ -91,12 +113,40 @@ pub fn get_cli_arguments() -> anyhow::Result<PingxArgs> {
                 .value_parser(clap::value_parser!(f64))
                 .required(false),
         )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .action(clap::ArgAction::SetTrue)
+                .help("Continuous monitoring: replaces per-packet lines with a live summary (min/avg/max/p95, loss, jitter)"),
+        )
+        .arg(
+            Arg::new("window")
+                .long("window")
+                .value_name("N")
+                .help("Number of recent replies used for the rolling latency stats (default 100)")
+                .value_parser(clap::value_parser!(usize))
+                .required(false),
+        )
         .arg(Arg::new("beep").short('b').long("beep").action(clap::ArgAction::SetTrue).help("Beep on packet loss"))
         .arg(Arg::new("compact-header").short('m').long("compact-header").action(clap::ArgAction::SetTrue).help("Print compact header"))
         .arg(Arg::new("no-header").short('p').long("no-header").action(clap::ArgAction::SetTrue).help("Do not print header"))
-        .get_matches();
+        .get_matches_from(argv);
 
-    let target = matches.get_one::<String>("target").unwrap().to_string();
+    let targets = matches
+        .get_many::<String>("target")
+        .unwrap_or_default()
+        .cloned()
+        .collect::<Vec<String>>();
+    let is_multi_target = targets.len() > 1;
+    let mode = matches
+        .get_one::<String>("mode")
+        .and_then(|m| ProbeMode::from_str_value(m))
+        .unwrap_or(ProbeMode::Icmp);
+    let tcp_port = matches.get_one::<u16>("port").copied();
+    if tcp_port.is_some() && mode != ProbeMode::Tcp {
+        anyhow::bail!("--port can only be used with --mode tcp. For http, add the port to the URL");
+    }
+    let tcp_port = tcp_port.unwrap_or(DEFAULT_TCP_PORT);
     let interval_secs = matches.get_one::<f64>("interval").copied().unwrap_or(1.0);
     let payload_size_bytes = matches.get_one::<usize>("size").copied().unwrap_or(56);
     let per_reply_timeout_secs = matches.get_one::<f64>("timeout").copied().unwrap_or(2.0);
@@ -133,16 +183,45 @@ pub fn get_cli_arguments() -> anyhow::Result<PingxArgs> {
     let compact_header = matches.get_flag("compact-header");
     let no_header = matches.get_flag("no-header");
 
-    let count = matches.get_one::<i64>("count").copied().unwrap_or(-1);
+    // When comparing targets, a finite run is the useful default; the table is printed at the end.
+    let default_count = if is_multi_target && !matches.get_flag("continuous") {
+        MULTI_TARGET_DEFAULT_COUNT
+    } else {
+        -1
+    };
+    let count = matches
+        .get_one::<i64>("count")
+        .copied()
+        .unwrap_or(default_count);
     if count == 0 || count < -1 {
         anyhow::bail!("--count must be -1 (for infinite, but in this case you can also use --continuous) or >= 1");
     }
     let explicit_count_inf = matches.value_source("count").is_some() && count == -1;
     let continuous = matches.get_flag("continuous");
-    let stop_on_error = !continuous && !explicit_count_inf;
+    let watch = matches.get_flag("watch");
+    let stop_on_error = !continuous && !explicit_count_inf && !watch;
+
+    if watch && output != OutputMode::Default {
+        anyhow::bail!("--watch can only be used with the default output");
+    }
+
+    if is_multi_target && watch {
+        anyhow::bail!("--watch can only be used with a single target");
+    }
+
+    if is_multi_target && matches!(output, OutputMode::Template(_)) {
+        anyhow::bail!("Custom output templates can only be used with a single target");
+    }
+
+    let stats_window = matches.get_one::<usize>("window").copied().unwrap_or(100);
+    if stats_window == 0 {
+        anyhow::bail!("--window must be >= 1");
+    }
 
     Ok(PingxArgs {
-        target,
+        targets,
+        mode,
+        tcp_port,
         count,
         interval_secs,
         payload_size_bytes,
@@ -160,6 +239,8 @@ pub fn get_cli_arguments() -> anyhow::Result<PingxArgs> {
         compact_header,
         no_header,
         stop_on_error,
+        watch,
+        stats_window,
     })
 }
 
@@ -168,6 +249,19 @@ pub fn print_header(args: &PingxArgs, resolved: &ResolvedTargetInfo) {
         return;
     }
 
+    if args.compact_header && args.mode == ProbeMode::Tcp {
+        println!(
+            "TCP PING {} ({}) port {}.",
+            resolved.host, resolved.ip, args.tcp_port
+        );
+        return;
+    }
+
+    if args.compact_header && args.mode == ProbeMode::Http {
+        println!("HTTP PING {} ({}).", resolved.host, resolved.ip);
+        return;
+    }
+
     if args.compact_header {
         let header_size = if resolved.ip.is_ipv4() {
             20 + 8
@@ -192,17 +286,25 @@ pub fn print_header(args: &PingxArgs, resolved: &ResolvedTargetInfo) {
         "- Reverse DNS: {}",
         resolved.reverse_dns.as_deref().unwrap_or("(disabled)")
     );
-    let header_size = if resolved.ip.is_ipv4() {
-        20 + 8
-    } else {
-        40 + 8
-    };
-    println!(
-        "- Packet Size: {} (with headers: {})",
-        args.payload_size_bytes,
-        args.payload_size_bytes + header_size
-    );
-    if args.is_infinite() {
+    print_probe_mode(args);
+    if args.mode == ProbeMode::Icmp {
+        let header_size = if resolved.ip.is_ipv4() {
+            20 + 8
+        } else {
+            40 + 8
+        };
+        println!(
+            "- Packet Size: {} (with headers: {})",
+            args.payload_size_bytes,
+            args.payload_size_bytes + header_size
+        );
+    }
+    if args.watch {
+        println!(
+            "- Watch mode (rolling window: {} replies)",
+            args.stats_window
+        );
+    } else if args.is_infinite() {
         println!("- Continuous mode");
     } else {
         println!("- Count: {}", args.count);
@@ -239,3 +341,34 @@ fn template_has_any_tag(template: &str) -> bool {
     ];
     tags.iter().any(|tag| t.contains(tag))
 }
+
+pub fn print_multi_target_header(args: &PingxArgs) {
+    if args.no_header || args.quiet {
+        return;
+    }
+
+    println!("XPing v{}", env!("CARGO_PKG_VERSION"));
+    println!("{}", DASH_LINE);
+    println!("- Targets: {}", args.targets.join(", "));
+    print_probe_mode(args);
+    if args.is_infinite() {
+        println!("- Continuous mode (press Ctrl+C to stop and compare)");
+    } else {
+        println!("- Count: {} per target", args.count);
+    }
+    println!("- Interval: {} seconds", args.interval_secs);
+    println!("- Timeout: {} seconds", args.per_reply_timeout_secs);
+    if let Some(deadline) = args.overall_deadline_secs {
+        println!("- Stop after total elapsed: {} seconds", deadline);
+    }
+
+    println!();
+}
+
+fn print_probe_mode(args: &PingxArgs) {
+    match args.mode {
+        ProbeMode::Icmp => println!("- Mode: icmp"),
+        ProbeMode::Tcp => println!("- Mode: tcp (port {})", args.tcp_port),
+        ProbeMode::Http => println!("- Mode: http (HEAD request)"),
+    }
+}
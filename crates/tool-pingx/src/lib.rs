@@ -0,0 +1,60 @@
+mod cli_utils;
+mod models;
+mod multi_target;
+mod ping_stats;
+mod pingx_app;
+mod probes;
+
+use pingx_app::run_ping;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+/// Cross-platform ping.
+///
+/// Parses arguments, then pings the target (or compares the targets, when more than one).
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    runtime.block_on(run_tool(argv))
+}
+
+async fn run_tool(argv: Vec<OsString>) -> i32 {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    let args = match cli_utils::get_cli_arguments(argv) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    match run_ping(&args).await {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
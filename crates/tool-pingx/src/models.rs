@@ -7,6 +7,34 @@ pub enum IpMode {
     V6,
 }
 
+/// How each target is probed. ICMP is the classic ping; TCP and HTTP are fallbacks for
+/// networks where ICMP is blocked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProbeMode {
+    Icmp,
+    Tcp,
+    Http,
+}
+
+impl ProbeMode {
+    pub fn from_str_value(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "icmp" => Some(ProbeMode::Icmp),
+            "tcp" => Some(ProbeMode::Tcp),
+            "http" => Some(ProbeMode::Http),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProbeMode::Icmp => "icmp",
+            ProbeMode::Tcp => "tcp",
+            ProbeMode::Http => "http",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum OutputMode {
     Default,
@@ -17,7 +45,9 @@ pub enum OutputMode {
 
 #[derive(Clone, Debug)]
 pub struct PingxArgs {
-    pub target: String,
+    pub targets: Vec<String>,
+    pub mode: ProbeMode,
+    pub tcp_port: u16,
     pub count: i64,
     pub interval_secs: f64,
     pub payload_size_bytes: usize,
@@ -35,11 +65,17 @@ pub struct PingxArgs {
     pub compact_header: bool,
     pub no_header: bool,
     pub stop_on_error: bool, // stop on the first error when running with only default options
+    pub watch: bool,
+    pub stats_window: usize,
 }
 
 impl PingxArgs {
     pub fn is_infinite(&self) -> bool {
-        self.count < 0 || self.continuous
+        self.count < 0 || self.continuous || self.watch
+    }
+
+    pub fn is_multi_target(&self) -> bool {
+        self.targets.len() > 1
     }
 }
 
@@ -54,5 +90,6 @@ pub struct ResolvedTargetInfo {
 pub struct PacketResult {
     pub icmp_seq: u64,
     pub time_ms: f64,
+    pub detail: Option<String>,
     pub error: Option<String>,
 }
@@ -0,0 +1,316 @@
+use crate::cli_utils::print_multi_target_header;
+use crate::models::{OutputMode, PingxArgs, ResolvedTargetInfo};
+use crate::ping_stats::{PingStats, StatsSnapshot};
+use crate::pingx_app::resolve_target_once;
+use crate::probes::create_probe;
+use anyhow::Result;
+use serde::Serialize;
+use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tokio::time::{sleep, Duration, Instant};
+
+/// Final result for one of the targets being compared.
+struct TargetSummary {
+    host: String,
+    ip: Option<String>,
+    snapshot: Option<StatsSnapshot>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonTargetSummary<'a> {
+    host: &'a str,
+    ip: Option<&'a str>,
+    sent: u64,
+    received: u64,
+    loss_percent: f64,
+    min: Option<f64>,
+    avg: Option<f64>,
+    max: Option<f64>,
+    p95: Option<f64>,
+    jitter: Option<f64>,
+    error: Option<&'a str>,
+}
+
+/// Pings all targets concurrently (one task per target) and prints a comparison table sorted by
+/// average latency once every task is done.
+pub async fn run_multi_target_ping(args: &PingxArgs) -> Result<()> {
+    print_multi_target_header(args);
+
+    let shutdown = setup_graceful_shutdown(false);
+    let mut tasks = JoinSet::new();
+
+    for (index, host) in args.targets.iter().enumerate() {
+        let task_args = args.clone();
+        let task_host = host.clone();
+        let task_shutdown = Arc::clone(&shutdown);
+
+        tasks.spawn(async move {
+            let summary = ping_target(&task_args, &task_host, task_shutdown).await;
+            (index, summary)
+        });
+    }
+
+    let mut summaries: Vec<(usize, TargetSummary)> = Vec::with_capacity(args.targets.len());
+    while let Some(joined) = tasks.join_next().await {
+        summaries.push(joined?);
+    }
+
+    // Keeps the order stable for targets with the same latency (or no latency at all).
+    summaries.sort_by_key(|(index, _)| *index);
+    let mut summaries: Vec<TargetSummary> = summaries.into_iter().map(|(_, s)| s).collect();
+    sort_by_latency(&mut summaries);
+
+    match args.output {
+        OutputMode::Json => print_json(&summaries)?,
+        OutputMode::Csv => print_csv(&summaries),
+        _ => print_table(&summaries),
+    }
+
+    Ok(())
+}
+
+async fn ping_target(args: &PingxArgs, host: &str, shutdown: Arc<AtomicBool>) -> TargetSummary {
+    let resolved = match resolve_target_once(args, host).await {
+        Ok(resolved) => resolved,
+        Err(e) => return failed_summary(host, None, e.to_string()),
+    };
+
+    match collect_stats(args, &resolved, shutdown).await {
+        Ok(stats) => TargetSummary {
+            host: resolved.host,
+            ip: Some(resolved.ip.to_string()),
+            snapshot: Some(stats.snapshot()),
+            error: None,
+        },
+        Err(e) => failed_summary(host, Some(resolved.ip.to_string()), e.to_string()),
+    }
+}
+
+async fn collect_stats(
+    args: &PingxArgs,
+    resolved: &ResolvedTargetInfo,
+    shutdown: Arc<AtomicBool>,
+) -> Result<PingStats> {
+    let mut probe = create_probe(args, resolved).await?;
+    let mut stats = PingStats::new(args.stats_window);
+    let started = Instant::now();
+    let mut sequence: u64 = 0;
+
+    loop {
+        if let Some(deadline) = args.overall_deadline_secs {
+            if started.elapsed() >= Duration::from_secs_f64(deadline) {
+                break;
+            }
+        }
+
+        if !args.is_infinite() && stats.sent >= args.count as u64 {
+            break;
+        }
+
+        if shutdown.load(Ordering::Relaxed) {
+            break;
+        }
+
+        sequence += 1;
+
+        // Lost packets are part of the comparison, so we never stop on errors here.
+        match probe.probe(sequence).await {
+            Ok(reply) => stats.record_success(reply.time_ms),
+            Err(_) => stats.record_loss(),
+        }
+
+        sleep(Duration::from_secs_f64(args.interval_secs)).await;
+    }
+
+    Ok(stats)
+}
+
+fn failed_summary(host: &str, ip: Option<String>, error: String) -> TargetSummary {
+    TargetSummary {
+        host: host.to_string(),
+        ip,
+        snapshot: None,
+        error: Some(error),
+    }
+}
+
+/// Fastest average first; targets without any reply go to the bottom.
+fn sort_by_latency(summaries: &mut [TargetSummary]) {
+    summaries.sort_by(|a, b| {
+        let a_avg = a.snapshot.as_ref().and_then(|s| s.avg_ms);
+        let b_avg = b.snapshot.as_ref().and_then(|s| s.avg_ms);
+
+        match (a_avg, b_avg) {
+            (Some(a_avg), Some(b_avg)) => a_avg.total_cmp(&b_avg),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+    });
+}
+
+fn format_ms(value: Option<f64>) -> String {
+    value
+        .map(|ms| format!("{:.2}", ms))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn print_table(summaries: &[TargetSummary]) {
+    let headers = [
+        "#", "Host", "IP", "Sent", "Recv", "Loss%", "Min", "Avg", "Max", "P95", "Jitter",
+    ];
+
+    let rows: Vec<Vec<String>> = summaries
+        .iter()
+        .enumerate()
+        .map(|(position, summary)| {
+            let mut row = vec![
+                (position + 1).to_string(),
+                summary.host.clone(),
+                summary.ip.clone().unwrap_or_else(|| "-".to_string()),
+            ];
+
+            match &summary.snapshot {
+                Some(snapshot) => row.extend([
+                    snapshot.sent.to_string(),
+                    snapshot.received.to_string(),
+                    format!("{:.1}", snapshot.loss_percent),
+                    format_ms(snapshot.min_ms),
+                    format_ms(snapshot.avg_ms),
+                    format_ms(snapshot.max_ms),
+                    format_ms(snapshot.p95_ms),
+                    format_ms(snapshot.jitter_ms),
+                ]),
+                None => row.extend((0..8).map(|_| "-".to_string())),
+            }
+
+            row
+        })
+        .collect();
+
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(col, header)| {
+            rows.iter()
+                .map(|row| row[col].len())
+                .max()
+                .unwrap_or(0)
+                .max(header.len())
+        })
+        .collect();
+
+    let format_row = |cells: Vec<&str>| -> String {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ")
+    };
+
+    println!("--- comparison (latency in ms, sorted by avg) ---");
+    println!("{}", format_row(headers.to_vec()));
+    for row in &rows {
+        println!("{}", format_row(row.iter().map(|c| c.as_str()).collect()));
+    }
+
+    for summary in summaries {
+        if let Some(error) = &summary.error {
+            eprintln!("{}: {}", summary.host, error);
+        }
+    }
+}
+
+fn print_csv(summaries: &[TargetSummary]) {
+    println!("host,ip,sent,received,loss,min,avg,max,p95,jitter,error");
+    for summary in summaries {
+        let (sent, received, loss) = summary
+            .snapshot
+            .as_ref()
+            .map(|s| (s.sent, s.received, s.loss_percent))
+            .unwrap_or((0, 0, 100.0));
+        let snapshot = summary.snapshot.as_ref();
+        let csv_ms = |value: Option<f64>| value.map(|ms| format!("{:.2}", ms)).unwrap_or_default();
+
+        println!(
+            "{},{},{},{},{:.1},{},{},{},{},{},{}",
+            summary.host,
+            summary.ip.as_deref().unwrap_or(""),
+            sent,
+            received,
+            loss,
+            csv_ms(snapshot.and_then(|s| s.min_ms)),
+            csv_ms(snapshot.and_then(|s| s.avg_ms)),
+            csv_ms(snapshot.and_then(|s| s.max_ms)),
+            csv_ms(snapshot.and_then(|s| s.p95_ms)),
+            csv_ms(snapshot.and_then(|s| s.jitter_ms)),
+            summary.error.as_deref().unwrap_or("").replace(',', ";"),
+        );
+    }
+}
+
+fn print_json(summaries: &[TargetSummary]) -> Result<()> {
+    let json: Vec<JsonTargetSummary> = summaries
+        .iter()
+        .map(|summary| {
+            let snapshot = summary.snapshot.as_ref();
+            JsonTargetSummary {
+                host: &summary.host,
+                ip: summary.ip.as_deref(),
+                sent: snapshot.map(|s| s.sent).unwrap_or(0),
+                received: snapshot.map(|s| s.received).unwrap_or(0),
+                loss_percent: snapshot.map(|s| s.loss_percent).unwrap_or(100.0),
+                min: snapshot.and_then(|s| s.min_ms),
+                avg: snapshot.and_then(|s| s.avg_ms),
+                max: snapshot.and_then(|s| s.max_ms),
+                p95: snapshot.and_then(|s| s.p95_ms),
+                jitter: snapshot.and_then(|s| s.jitter_ms),
+                error: summary.error.as_deref(),
+            }
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&json)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(host: &str, avg: Option<f64>) -> TargetSummary {
+        let mut stats = PingStats::new(10);
+        if let Some(avg) = avg {
+            stats.record_success(avg);
+        } else {
+            stats.record_loss();
+        }
+
+        TargetSummary {
+            host: host.to_string(),
+            ip: None,
+            snapshot: Some(stats.snapshot()),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_sort_by_latency_puts_fastest_first_and_unreachable_last() {
+        let mut summaries = vec![
+            summary("slow", Some(50.0)),
+            summary("down", None),
+            summary("fast", Some(5.0)),
+            failed_summary("unknown", None, "DNS resolution failed".to_string()),
+        ];
+
+        sort_by_latency(&mut summaries);
+
+        let hosts: Vec<&str> = summaries.iter().map(|s| s.host.as_str()).collect();
+        assert_eq!(hosts, vec!["fast", "slow", "down", "unknown"]);
+    }
+}
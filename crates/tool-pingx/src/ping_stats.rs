@@ -0,0 +1,212 @@
+use std::collections::VecDeque;
+
+/// Accumulates per-packet results and computes rolling latency statistics.
+///
+/// Sent/received counters cover the whole run, while latency figures (min/avg/max/p95/jitter)
+/// are computed over the last `window_size` successful replies.
+#[derive(Clone, Debug)]
+pub struct PingStats {
+    pub sent: u64,
+    pub received: u64,
+    window_size: usize,
+    rtts: VecDeque<f64>,
+    last_rtt: Option<f64>,
+}
+
+/// A point-in-time view of the statistics, used for printing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatsSnapshot {
+    pub sent: u64,
+    pub received: u64,
+    pub loss_percent: f64,
+    pub last_ms: Option<f64>,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub jitter_ms: Option<f64>,
+}
+
+impl PingStats {
+    pub fn new(window_size: usize) -> Self {
+        let window_size = window_size.max(1);
+
+        Self {
+            sent: 0,
+            received: 0,
+            window_size,
+            rtts: VecDeque::with_capacity(window_size),
+            last_rtt: None,
+        }
+    }
+
+    pub fn record_success(&mut self, time_ms: f64) {
+        self.sent += 1;
+        self.received += 1;
+        self.last_rtt = Some(time_ms);
+
+        if self.rtts.len() == self.window_size {
+            self.rtts.pop_front();
+        }
+
+        self.rtts.push_back(time_ms);
+    }
+
+    pub fn record_loss(&mut self) {
+        self.sent += 1;
+    }
+
+    pub fn loss_percent(&self) -> f64 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+
+        ((self.sent - self.received) as f64) * 100.0 / (self.sent as f64)
+    }
+
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let (min_ms, avg_ms, max_ms) = if self.rtts.is_empty() {
+            (None, None, None)
+        } else {
+            let min = self.rtts.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = self.rtts.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let avg = self.rtts.iter().sum::<f64>() / self.rtts.len() as f64;
+            (Some(min), Some(avg), Some(max))
+        };
+
+        StatsSnapshot {
+            sent: self.sent,
+            received: self.received,
+            loss_percent: self.loss_percent(),
+            last_ms: self.last_rtt,
+            min_ms,
+            avg_ms,
+            max_ms,
+            p95_ms: self.percentile(95.0),
+            jitter_ms: self.jitter(),
+        }
+    }
+
+    /// Nearest-rank percentile over the current window.
+    fn percentile(&self, percentile: f64) -> Option<f64> {
+        if self.rtts.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = self.rtts.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+        let index = rank.clamp(1, sorted.len()) - 1;
+
+        Some(sorted[index])
+    }
+
+    /// Jitter as the mean absolute difference between consecutive replies in the window.
+    fn jitter(&self) -> Option<f64> {
+        if self.rtts.len() < 2 {
+            return None;
+        }
+
+        let total: f64 = self
+            .rtts
+            .iter()
+            .zip(self.rtts.iter().skip(1))
+            .map(|(previous, current)| (current - previous).abs())
+            .sum();
+
+        Some(total / (self.rtts.len() - 1) as f64)
+    }
+}
+
+impl StatsSnapshot {
+    /// Compact, single-line summary used by watch mode.
+    pub fn to_summary_line(&self) -> String {
+        format!(
+            "sent={} recv={} loss={:.1}% last={} min={} avg={} max={} p95={} jitter={}",
+            self.sent,
+            self.received,
+            self.loss_percent,
+            format_ms(self.last_ms),
+            format_ms(self.min_ms),
+            format_ms(self.avg_ms),
+            format_ms(self.max_ms),
+            format_ms(self.p95_ms),
+            format_ms(self.jitter_ms),
+        )
+    }
+}
+
+fn format_ms(value: Option<f64>) -> String {
+    match value {
+        Some(ms) => format!("{:.2}ms", ms),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_stats() {
+        let snapshot = PingStats::new(10).snapshot();
+
+        assert_eq!(snapshot.sent, 0);
+        assert_eq!(snapshot.loss_percent, 0.0);
+        assert_eq!(snapshot.avg_ms, None);
+        assert_eq!(snapshot.jitter_ms, None);
+    }
+
+    #[test]
+    fn test_min_avg_max_and_loss() {
+        let mut stats = PingStats::new(10);
+        stats.record_success(10.0);
+        stats.record_success(20.0);
+        stats.record_loss();
+        stats.record_success(30.0);
+
+        let snapshot = stats.snapshot();
+
+        assert_eq!(snapshot.sent, 4);
+        assert_eq!(snapshot.received, 3);
+        assert_eq!(snapshot.loss_percent, 25.0);
+        assert_eq!(snapshot.min_ms, Some(10.0));
+        assert_eq!(snapshot.avg_ms, Some(20.0));
+        assert_eq!(snapshot.max_ms, Some(30.0));
+        assert_eq!(snapshot.last_ms, Some(30.0));
+    }
+
+    #[test]
+    fn test_jitter_is_mean_absolute_difference() {
+        let mut stats = PingStats::new(10);
+        stats.record_success(10.0);
+        stats.record_success(14.0);
+        stats.record_success(12.0);
+
+        assert_eq!(stats.snapshot().jitter_ms, Some(3.0));
+    }
+
+    #[test]
+    fn test_p95_nearest_rank() {
+        let mut stats = PingStats::new(100);
+        for i in 1..=20 {
+            stats.record_success(i as f64);
+        }
+
+        assert_eq!(stats.snapshot().p95_ms, Some(19.0));
+    }
+
+    #[test]
+    fn test_window_discards_old_samples() {
+        let mut stats = PingStats::new(2);
+        stats.record_success(100.0);
+        stats.record_success(10.0);
+        stats.record_success(20.0);
+
+        let snapshot = stats.snapshot();
+
+        assert_eq!(snapshot.max_ms, Some(20.0));
+        assert_eq!(snapshot.received, 3);
+    }
+}
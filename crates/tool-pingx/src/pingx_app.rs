@@ -1,16 +1,20 @@
 use crate::cli_utils::print_header;
-use crate::models::{IpMode, OutputMode, PacketResult, PingxArgs, ResolvedTargetInfo};
+use crate::models::{IpMode, OutputMode, PacketResult, PingxArgs, ProbeMode, ResolvedTargetInfo};
+use crate::multi_target::run_multi_target_ping;
+use crate::ping_stats::PingStats;
+use crate::probes::{create_probe, host_to_resolve};
 use anyhow::Result;
 use chrono::Timelike;
 use dns_lookup::lookup_addr;
 use serde::Serialize;
 use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use std::io::Write;
 use std::net::{IpAddr, ToSocketAddrs};
-use surge_ping::{Client, ConfigBuilder, IcmpPacket, PingIdentifier, PingSequence, ICMP};
 use tokio::time::{sleep, Duration, Instant};
 
 #[derive(Serialize)]
 struct JsonOutput<'a> {
+    mode: &'static str,
     host: &'a str,
     ip: String,
     reverse_dns: Option<&'a str>,
@@ -27,102 +31,132 @@ struct PacketLine {
     time: f64,
 }
 
-pub fn resolve_target(args: &PingxArgs) -> Result<ResolvedTargetInfo> {
-    let host = args.target.clone();
-
+pub fn resolve_target(args: &PingxArgs, host: &str) -> Result<ResolvedTargetInfo> {
     let ip: IpAddr = loop {
-        let mut addrs: Vec<IpAddr> = Vec::new();
-        let mut last_err: Option<String> = None;
-
-        match (host.as_str(), 0).to_socket_addrs() {
-            Ok(iter) => {
-                for s in iter {
-                    let ip = s.ip();
-                    match args.ip_mode {
-                        IpMode::Auto => addrs.push(ip),
-                        IpMode::V4 => {
-                            if ip.is_ipv4() {
-                                addrs.push(ip)
-                            }
-                        }
-                        IpMode::V6 => {
-                            if ip.is_ipv6() {
-                                addrs.push(ip)
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                last_err = Some(format!("DNS resolution failed: {}", e));
-            }
-        }
-
-        // Fallback: direct parse
-        if addrs.is_empty() {
-            if let Ok(ip) = host.parse::<IpAddr>() {
-                let is_ok = match args.ip_mode {
-                    IpMode::Auto => true,
-                    IpMode::V4 => ip.is_ipv4(),
-                    IpMode::V6 => ip.is_ipv6(),
-                };
-                if is_ok {
-                    addrs.push(ip);
-                }
-            } else if last_err.is_none() {
-                last_err = Some("Failed to parse host as IP address".to_string());
-            }
-        }
-
-        if let Some(first) = addrs.first().copied() {
-            break first;
-        }
+        let last_err = match resolve_ip(args, host) {
+            Ok(ip) => break ip,
+            Err(e) => e,
+        };
 
         // No address resolved; decide whether to stop or keep trying
         if args.stop_on_error {
-            let msg = last_err.unwrap_or_else(|| "Failed to resolve target".to_string());
-            return Err(anyhow::anyhow!(msg));
+            return Err(anyhow::anyhow!(last_err));
         } else {
             if !args.quiet {
-                if let Some(msg) = &last_err {
-                    eprintln!("resolve error for '{}': {}", host, msg);
-                } else {
-                    eprintln!("resolve error for '{}': no addresses found", host);
-                }
+                eprintln!("resolve error for '{}': {}", host, last_err);
             }
             // simple retry delay
             std::thread::sleep(Duration::from_secs(1));
         }
     };
 
+    Ok(build_resolved_target(args, host, ip))
+}
+
+/// Resolves the target a single time, without retrying. Used when pinging multiple targets,
+/// where one bad host shouldn't hold the others back.
+///
+/// The lookups (and the reverse DNS) block, so they run on the blocking thread pool, keeping the
+/// runtime free for the targets being pinged.
+pub async fn resolve_target_once(args: &PingxArgs, host: &str) -> Result<ResolvedTargetInfo> {
+    let args = args.clone();
+    let host = host.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let ip = resolve_ip(&args, &host).map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(build_resolved_target(&args, &host, ip))
+    })
+    .await?
+}
+
+fn build_resolved_target(args: &PingxArgs, host: &str, ip: IpAddr) -> ResolvedTargetInfo {
     let reverse_dns = if args.numeric {
         None
     } else {
         lookup_addr(&ip).ok()
     };
 
-    Ok(ResolvedTargetInfo {
-        host,
+    ResolvedTargetInfo {
+        host: host.to_string(),
         ip,
         reverse_dns,
-    })
+    }
+}
+
+fn resolve_ip(args: &PingxArgs, target: &str) -> std::result::Result<IpAddr, String> {
+    let host = host_to_resolve(args.mode, target).map_err(|e| e.to_string())?;
+    let host = host.as_str();
+    let mut addrs: Vec<IpAddr> = Vec::new();
+    let mut last_err: Option<String> = None;
+
+    match (host, 0).to_socket_addrs() {
+        Ok(iter) => {
+            for s in iter {
+                let ip = s.ip();
+                match args.ip_mode {
+                    IpMode::Auto => addrs.push(ip),
+                    IpMode::V4 => {
+                        if ip.is_ipv4() {
+                            addrs.push(ip)
+                        }
+                    }
+                    IpMode::V6 => {
+                        if ip.is_ipv6() {
+                            addrs.push(ip)
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            last_err = Some(format!("DNS resolution failed: {}", e));
+        }
+    }
+
+    // Fallback: direct parse
+    if addrs.is_empty() {
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            let is_ok = match args.ip_mode {
+                IpMode::Auto => true,
+                IpMode::V4 => ip.is_ipv4(),
+                IpMode::V6 => ip.is_ipv6(),
+            };
+            if is_ok {
+                addrs.push(ip);
+            }
+        } else if last_err.is_none() {
+            last_err = Some("Failed to parse host as IP address".to_string());
+        }
+    }
+
+    match addrs.first().copied() {
+        Some(first) => Ok(first),
+        None => Err(last_err.unwrap_or_else(|| "no addresses found".to_string())),
+    }
 }
 
 pub async fn run_ping(args: &PingxArgs) -> Result<()> {
-    let resolved = resolve_target(args)?;
+    if args.is_multi_target() {
+        return run_multi_target_ping(args).await;
+    }
+
+    let resolved = resolve_target(args, &args.targets[0])?;
     print_header(args, &resolved);
 
     // Verbose info
     if args.verbose && !args.quiet {
-        println!("[verbose] target: {}", args.target);
+        println!("[verbose] target: {}", resolved.host);
         println!("[verbose] resolved ip: {}", resolved.ip);
         println!(
             "[verbose] reverse dns: {}",
             resolved.reverse_dns.as_deref().unwrap_or("(none)")
         );
         println!(
-            "[verbose] ip mode: {:?}, payload: {} bytes",
-            args.ip_mode, args.payload_size_bytes
+            "[verbose] probe mode: {}, ip mode: {:?}, payload: {} bytes",
+            args.mode.as_str(),
+            args.ip_mode,
+            args.payload_size_bytes
         );
     }
 
@@ -137,24 +171,10 @@ pub async fn run_ping(args: &PingxArgs) -> Result<()> {
 
     let shutdown = setup_graceful_shutdown(false);
 
-    let mut config_builder = ConfigBuilder::default();
-
-    let icmp = if resolved.ip.is_ipv4() {
-        ICMP::V4
-    } else {
-        ICMP::V6
-    };
-
-    config_builder = config_builder.kind(icmp);
-
-    let config = config_builder.build();
-
-    let client = Client::new(&config)?;
+    let mut probe = create_probe(args, &resolved).await?;
     let mut sequence: u64 = 0;
-    let identifier = PingIdentifier(rand_identifier());
     let deadline_start = Instant::now();
-    let mut sent: u64 = 0;
-    let mut received: u64 = 0;
+    let mut stats = PingStats::new(args.stats_window);
     let mut lines_for_json: Vec<PacketLine> = Vec::new();
 
     // stats timer
@@ -169,7 +189,7 @@ pub async fn run_ping(args: &PingxArgs) -> Result<()> {
             }
         }
 
-        if !args.is_infinite() && sent >= args.count as u64 {
+        if !args.is_infinite() && stats.sent >= args.count as u64 {
             break;
         }
 
@@ -178,54 +198,32 @@ pub async fn run_ping(args: &PingxArgs) -> Result<()> {
         }
 
         sequence += 1;
-        sent += 1;
-
-        let timeout = Duration::from_secs_f64(args.per_reply_timeout_secs);
-
-        let mut pinger = client.pinger(resolved.ip, identifier).await;
 
-        pinger.timeout(timeout);
-
-        let payload = vec![0u8; args.payload_size_bytes];
         let mut had_error = false;
-        match pinger.ping(PingSequence(sequence as u16), &payload).await {
-            Ok((IcmpPacket::V4(_packet), dur)) => {
-                received += 1;
-                let time_ms = dur.as_secs_f64() * 1000.0;
-                let packet_res = PacketResult {
-                    icmp_seq: sequence,
-                    time_ms,
-                    error: None,
-                };
-                print_packet_line(args, &resolved, &packet_res);
-                if matches!(args.output, OutputMode::Json) {
-                    lines_for_json.push(PacketLine {
-                        icmp_seq: sequence,
-                        time: time_ms,
-                    });
-                }
-            }
-            Ok((IcmpPacket::V6(_packet), dur)) => {
-                received += 1;
-                let time_ms = dur.as_secs_f64() * 1000.0;
+        match probe.probe(sequence).await {
+            Ok(reply) => {
+                stats.record_success(reply.time_ms);
                 let packet_res = PacketResult {
                     icmp_seq: sequence,
-                    time_ms,
+                    time_ms: reply.time_ms,
+                    detail: reply.detail,
                     error: None,
                 };
                 print_packet_line(args, &resolved, &packet_res);
                 if matches!(args.output, OutputMode::Json) {
                     lines_for_json.push(PacketLine {
                         icmp_seq: sequence,
-                        time: time_ms,
+                        time: reply.time_ms,
                     });
                 }
             }
             Err(e) => {
                 had_error = true;
+                stats.record_loss();
                 let packet_res = PacketResult {
                     icmp_seq: sequence,
                     time_ms: 0.0,
+                    detail: None,
                     error: Some(e.to_string()),
                 };
                 print_packet_line(args, &resolved, &packet_res);
@@ -241,34 +239,39 @@ pub async fn run_ping(args: &PingxArgs) -> Result<()> {
         if let Some(every) = args.stats_every_secs {
             if let Some(due) = next_stats_due {
                 if Instant::now() >= due {
-                    print_stats(args, sent, received);
+                    print_stats(args, &stats);
                     next_stats_due = Some(due + Duration::from_secs_f64(every));
                 }
             }
         }
 
+        if args.watch {
+            print_watch_line(&stats);
+        }
+
         sleep(Duration::from_secs_f64(args.interval_secs)).await;
     }
 
+    if args.watch {
+        // Move past the live summary line before printing the final stats.
+        println!();
+    }
+
     // Final stats
     if matches!(args.output, OutputMode::Default | OutputMode::Csv) {
-        print_stats(args, sent, received);
+        print_stats(args, &stats);
     }
 
     if matches!(args.output, OutputMode::Json) {
-        let loss = if sent == 0 {
-            0.0
-        } else {
-            ((sent - received) as f64) * 100.0 / (sent as f64)
-        };
         let json = JsonOutput {
+            mode: args.mode.as_str(),
             host: &resolved.host,
             ip: resolved.ip.to_string(),
             reverse_dns: resolved.reverse_dns.as_deref(),
             size: args.payload_size_bytes + 8,
-            sent,
-            received,
-            loss_percent: loss,
+            sent: stats.sent,
+            received: stats.received,
+            loss_percent: stats.loss_percent(),
             packets: lines_for_json,
         };
         println!("{}", serde_json::to_string_pretty(&json)?);
@@ -292,7 +295,7 @@ fn replace_ci(s: String, needle_lower: &str, replacement: &str) -> String {
 }
 
 fn print_packet_line(args: &PingxArgs, resolved: &ResolvedTargetInfo, res: &PacketResult) {
-    if args.quiet {
+    if args.quiet || args.watch {
         return;
     }
     let ip_str = resolved.ip.to_string();
@@ -308,20 +311,7 @@ fn print_packet_line(args: &PingxArgs, resolved: &ResolvedTargetInfo, res: &Pack
             if let Some(err) = &res.error {
                 println!("{}error: {}", ts, err);
             } else {
-                let from_name = if !args.numeric {
-                    resolved.reverse_dns.as_deref().unwrap_or(&resolved.host)
-                } else {
-                    &resolved.host
-                };
-                println!(
-                    "{}{} bytes from {} ({}): icmp_seq={} time={:.2} ms",
-                    ts,
-                    args.payload_size_bytes + 8,
-                    from_name,
-                    ip_str,
-                    res.icmp_seq,
-                    res.time_ms,
-                );
+                println!("{}{}", ts, format_default_reply(args, resolved, res));
             }
         }
         OutputMode::Csv => {
@@ -393,12 +383,51 @@ fn print_packet_line(args: &PingxArgs, resolved: &ResolvedTargetInfo, res: &Pack
     }
 }
 
-fn print_stats(args: &PingxArgs, sent: u64, received: u64) {
-    let loss = if sent == 0 {
-        0.0
+fn format_default_reply(
+    args: &PingxArgs,
+    resolved: &ResolvedTargetInfo,
+    res: &PacketResult,
+) -> String {
+    let from_name = if !args.numeric {
+        resolved.reverse_dns.as_deref().unwrap_or(&resolved.host)
     } else {
-        ((sent - received) as f64) * 100.0 / (sent as f64)
+        &resolved.host
     };
+
+    match args.mode {
+        ProbeMode::Icmp => format!(
+            "{} bytes from {} ({}): icmp_seq={} time={:.2} ms",
+            args.payload_size_bytes + 8,
+            from_name,
+            resolved.ip,
+            res.icmp_seq,
+            res.time_ms,
+        ),
+        ProbeMode::Tcp => format!(
+            "connected to {} ({}:{}): seq={} time={:.2} ms",
+            from_name, resolved.ip, args.tcp_port, res.icmp_seq, res.time_ms,
+        ),
+        ProbeMode::Http => format!(
+            "response from {} ({}): seq={} {} time={:.2} ms",
+            resolved.host,
+            resolved.ip,
+            res.icmp_seq,
+            res.detail.as_deref().unwrap_or_default(),
+            res.time_ms,
+        ),
+    }
+}
+
+fn print_watch_line(stats: &PingStats) {
+    // Carriage return + clear line, so the summary is refreshed in place.
+    print!("\r\x1b[2K{}", stats.snapshot().to_summary_line());
+    let _ = std::io::stdout().flush();
+}
+
+fn print_stats(args: &PingxArgs, stats: &PingStats) {
+    let sent = stats.sent;
+    let received = stats.received;
+    let loss = stats.loss_percent();
     match &args.output {
         OutputMode::Default => {
             println!("\n--- statistics ---");
@@ -406,6 +435,24 @@ fn print_stats(args: &PingxArgs, sent: u64, received: u64) {
                 "{} packets transmitted, {} received, {:.1}% packet loss",
                 sent, received, loss
             );
+            if args.watch {
+                let snapshot = stats.snapshot();
+                if let (Some(min), Some(avg), Some(max), Some(p95)) = (
+                    snapshot.min_ms,
+                    snapshot.avg_ms,
+                    snapshot.max_ms,
+                    snapshot.p95_ms,
+                ) {
+                    println!(
+                        "rtt min/avg/max/p95 = {:.2}/{:.2}/{:.2}/{:.2} ms, jitter = {:.2} ms",
+                        min,
+                        avg,
+                        max,
+                        p95,
+                        snapshot.jitter_ms.unwrap_or(0.0)
+                    );
+                }
+            }
         }
         OutputMode::Csv => {
             // Consistent, machine-readable stats line
@@ -418,7 +465,7 @@ fn print_stats(args: &PingxArgs, sent: u64, received: u64) {
     }
 }
 
-fn rand_identifier() -> u16 {
+pub fn rand_identifier() -> u16 {
     // Simple deterministic-ish identifier
     (std::process::id() as u16) ^ ((chrono::Utc::now().nanosecond() & 0xFFFF) as u16)
 }
@@ -0,0 +1,256 @@
+use crate::models::{PingxArgs, ProbeMode, ResolvedTargetInfo};
+use crate::pingx_app::rand_identifier;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use reqwest::Url;
+use std::net::{IpAddr, SocketAddr};
+use surge_ping::{Client, ConfigBuilder, PingIdentifier, PingSequence, Pinger, ICMP};
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Duration, Instant};
+
+/// A successful probe.
+pub struct ProbeReply {
+    pub time_ms: f64,
+    /// Extra information about the reply, shown next to the time (e.g.: the HTTP status).
+    pub detail: Option<String>,
+}
+
+/// Sends one probe to the target and measures how long it took to get an answer.
+#[async_trait]
+pub trait Probe: Send {
+    async fn probe(&mut self, sequence: u64) -> Result<ProbeReply>;
+}
+
+/// ICMP echo request, like the classic ping.
+pub struct IcmpProbe {
+    pinger: Pinger,
+    payload: Vec<u8>,
+}
+
+/// Time to complete a TCP handshake with the target. The connection is closed right away.
+pub struct TcpProbe {
+    address: SocketAddr,
+    timeout: Duration,
+}
+
+/// Time to get the response of an HTTP HEAD request. Any response counts as a reply,
+/// regardless of the status code, since the server was reached.
+pub struct HttpProbe {
+    client: reqwest::Client,
+    url: Url,
+}
+
+/// Creates the probe matching the selected `--mode` for an already resolved target.
+pub async fn create_probe(
+    args: &PingxArgs,
+    resolved: &ResolvedTargetInfo,
+) -> Result<Box<dyn Probe>> {
+    let probe: Box<dyn Probe> = match args.mode {
+        ProbeMode::Icmp => Box::new(IcmpProbe::new(args, resolved.ip).await?),
+        ProbeMode::Tcp => Box::new(TcpProbe::new(args, resolved.ip)),
+        ProbeMode::Http => Box::new(HttpProbe::new(args, resolved)?),
+    };
+
+    Ok(probe)
+}
+
+impl IcmpProbe {
+    pub async fn new(args: &PingxArgs, ip: IpAddr) -> Result<Self> {
+        let icmp = if ip.is_ipv4() { ICMP::V4 } else { ICMP::V6 };
+
+        let client = Client::new(&ConfigBuilder::default().kind(icmp).build()).context(
+            "Failed to create ICMP client. Try --mode tcp or --mode http if ICMP is not allowed.",
+        )?;
+
+        let mut pinger = client.pinger(ip, PingIdentifier(rand_identifier())).await;
+        pinger.timeout(Duration::from_secs_f64(args.per_reply_timeout_secs));
+
+        Ok(Self {
+            pinger,
+            payload: vec![0u8; args.payload_size_bytes],
+        })
+    }
+}
+
+#[async_trait]
+impl Probe for IcmpProbe {
+    async fn probe(&mut self, sequence: u64) -> Result<ProbeReply> {
+        let (_packet, duration) = self
+            .pinger
+            .ping(PingSequence(sequence as u16), &self.payload)
+            .await?;
+
+        Ok(ProbeReply {
+            time_ms: duration.as_secs_f64() * 1000.0,
+            detail: None,
+        })
+    }
+}
+
+impl TcpProbe {
+    pub fn new(args: &PingxArgs, ip: IpAddr) -> Self {
+        Self {
+            address: SocketAddr::new(ip, args.tcp_port),
+            timeout: Duration::from_secs_f64(args.per_reply_timeout_secs),
+        }
+    }
+}
+
+#[async_trait]
+impl Probe for TcpProbe {
+    async fn probe(&mut self, _sequence: u64) -> Result<ProbeReply> {
+        let started = Instant::now();
+
+        match timeout(self.timeout, TcpStream::connect(self.address)).await {
+            Ok(Ok(_stream)) => Ok(ProbeReply {
+                time_ms: started.elapsed().as_secs_f64() * 1000.0,
+                detail: None,
+            }),
+            Ok(Err(e)) => Err(anyhow!("connect to {} failed: {}", self.address, e)),
+            Err(_) => Err(anyhow!("connect to {} timed out", self.address)),
+        }
+    }
+}
+
+impl HttpProbe {
+    pub fn new(args: &PingxArgs, resolved: &ResolvedTargetInfo) -> Result<Self> {
+        let url = parse_http_target(&resolved.host)?;
+
+        let mut builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs_f64(args.per_reply_timeout_secs))
+            .redirect(reqwest::redirect::Policy::none())
+            // A new connection per probe, so every sample measures the same thing.
+            .pool_max_idle_per_host(0);
+
+        // Pins the address we already resolved, so --ipv4/--ipv6 are honored and DNS isn't timed.
+        if let Some(domain) = url.domain() {
+            builder = builder.resolve(domain, SocketAddr::new(resolved.ip, 0));
+        }
+
+        let client = builder.build().context("Failed to create HTTP client")?;
+
+        Ok(Self { client, url })
+    }
+}
+
+#[async_trait]
+impl Probe for HttpProbe {
+    async fn probe(&mut self, _sequence: u64) -> Result<ProbeReply> {
+        let started = Instant::now();
+
+        let response = self
+            .client
+            .head(self.url.clone())
+            .send()
+            .await
+            .map_err(|e| anyhow!("HEAD {} failed: {}", self.url, e))?;
+
+        Ok(ProbeReply {
+            time_ms: started.elapsed().as_secs_f64() * 1000.0,
+            detail: Some(format!("status={}", response.status().as_u16())),
+        })
+    }
+}
+
+/// Parses the target of an HTTP probe. When no scheme is informed, `http://` is assumed.
+pub fn parse_http_target(target: &str) -> Result<Url> {
+    let with_scheme = if target.contains("://") {
+        target.to_string()
+    } else {
+        format!("http://{}", target)
+    };
+
+    let url = Url::parse(&with_scheme).context(format!("Invalid URL: {}", target))?;
+
+    if url.scheme() != "http" && url.scheme() != "https" {
+        bail!(
+            "Only http and https URLs are supported. Got: {}",
+            url.scheme()
+        );
+    }
+
+    if url.host_str().is_none() {
+        bail!("URL has no host: {}", target);
+    }
+
+    Ok(url)
+}
+
+/// Returns the part of the target that must be resolved to an IP address.
+/// For HTTP probes that's the URL host; for the other modes, the target itself.
+pub fn host_to_resolve(mode: ProbeMode, target: &str) -> Result<String> {
+    if mode != ProbeMode::Http {
+        return Ok(target.to_string());
+    }
+
+    let url = parse_http_target(target)?;
+    let host = url.host_str().unwrap_or_default();
+
+    // IPv6 hosts come wrapped in brackets, which the resolver doesn't accept.
+    Ok(host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_parse_http_target_defaults_to_http() {
+        let url = parse_http_target("example.com/health").unwrap();
+
+        assert_eq!(url.as_str(), "http://example.com/health");
+    }
+
+    #[test]
+    fn test_parse_http_target_rejects_other_schemes() {
+        assert!(parse_http_target("ftp://example.com").is_err());
+    }
+
+    #[test]
+    fn test_host_to_resolve() {
+        assert_eq!(
+            host_to_resolve(ProbeMode::Http, "https://example.com:8443/x").unwrap(),
+            "example.com"
+        );
+        assert_eq!(
+            host_to_resolve(ProbeMode::Http, "http://[::1]:8080").unwrap(),
+            "::1"
+        );
+        assert_eq!(
+            host_to_resolve(ProbeMode::Tcp, "example.com").unwrap(),
+            "example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tcp_probe_measures_connect_time() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut probe = TcpProbe {
+            address: listener.local_addr().unwrap(),
+            timeout: Duration::from_secs(2),
+        };
+
+        let reply = probe.probe(1).await.unwrap();
+
+        assert!(reply.time_ms >= 0.0);
+        assert!(reply.detail.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_probe_fails_when_port_is_closed() {
+        let address = {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let mut probe = TcpProbe {
+            address,
+            timeout: Duration::from_secs(2),
+        };
+
+        assert!(probe.probe(1).await.is_err());
+    }
+}
@@ -1,37 +1,54 @@
-use crate::models::{HowMode, QrCodeConfig, QrCodePayload};
-use anyhow::Result;
-use clap::{Arg, Command};
+use crate::models::{HowMode, QrCodeConfig, TotpPayload, VCardPayload, WifiPayload};
+use crate::symbology::Symbology;
+use anyhow::{bail, Result};
+use clap::{Arg, ArgMatches, Command};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
-use shared::system::tool_exit_helpers::exit_error;
-use tracing::error;
+use std::ffi::OsString;
 
 pub fn print_runtime_info(args: &QrCodeConfig) {
     println!("QrCode Generator v{}", env!("CARGO_PKG_VERSION"));
     println!("{}", DASH_LINE);
+    println!("Symbology: {}", args.symbology.label());
 
     match args.get_payload() {
         HowMode::TextPayload(text_payload) => {
             println!("Generating Text QR code");
             println!("Text: {}", text_payload);
         }
-        HowMode::WifiPayload(wifi_ssid, wifi_pass, wifi_auth) => {
+        HowMode::WifiPayload(wifi) => {
             println!("Generating Wifi QR code");
-            println!("SSID: {}", wifi_ssid);
-            println!("Password: {}", wifi_pass);
-            println!("Auth: {}", wifi_auth);
+            println!("SSID: {}", wifi.ssid);
+            if let Some(password) = &wifi.password {
+                println!("Password: {}", password);
+            }
+            println!("Auth: {}", wifi.auth);
+            if wifi.hidden {
+                println!("Hidden: yes");
+            }
+        }
+        HowMode::VCardPayload(vcard) => {
+            println!("Generating vCard QR code");
+            println!("Name: {}", vcard.full_name());
+        }
+        HowMode::TotpPayload(totp) => {
+            println!("Generating TOTP QR code");
+            println!("Account: {}", totp.account);
+            if let Some(issuer) = &totp.issuer {
+                println!("Issuer: {}", issuer);
+            }
         }
     }
 
     println!();
 }
 
-pub fn get_cli_arguments() -> Result<QrCodeConfig> {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<QrCodeConfig> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
             env!("CARGO_PKG_DESCRIPTION"),
-            "This tool can generate QR codes for text, URLs, wifi payloads, or other types of data. The output can be printed to the console and/or saved to a file."
+            "This tool can generate QR codes for text, URLs, wifi payloads, or other types of data. The output can be printed to the console and/or saved to a file.\n\nOther 2D codes (Micro QR, DataMatrix, and Aztec) can be generated with --symbology."
         )
         .arg(Arg::new("text")
             .long("text")
@@ -54,65 +71,225 @@ pub fn get_cli_arguments() -> Result<QrCodeConfig> {
     .arg(Arg::new("no-header")
             .long("no-header")
             .short('n')
+            .global(true)
             .action(clap::ArgAction::SetTrue)
             .help("Do not print header."))
     .arg(Arg::new("dont-print")
             .long("dont-print")
             .short('x')
+            .global(true)
             .action(clap::ArgAction::SetTrue)
             .help("Skips printing QR code to console."))
+        .arg(Arg::new("symbology")
+            .long("symbology")
+            .short('y')
+            .global(true)
+            .value_name("symbology")
+            .default_value("qr")
+            .value_parser(["qr", "micro-qr", "datamatrix", "aztec"])
+            .help("Type of 2D code to generate: qr, micro-qr (tiny payloads), datamatrix, or aztec. (Default: qr)"))
         .arg(Arg::new("output-format")
             .long("output-format")
             .short('f')
+            .global(true)
             .help("Format of output file. This defines the actual format of the file, regardless of the filename."))
         .arg(Arg::new("output-file")
             .long("output-file")
             .short('o')
+            .global(true)
             .value_name("filename")
             .help("Output file name. If not specified, will generate random."))
-        .get_matches();
+        .subcommand(build_wifi_subcommand())
+        .subcommand(build_vcard_subcommand())
+        .subcommand(build_totp_subcommand())
+        .get_matches_from(argv);
 
-    let text_payload = matches.get_one::<String>("text");
-    let wifi_ssid = matches.get_one::<String>("wifi-ssid");
-    let wifi_password = matches.get_one::<String>("wifi-password");
-    let wifi_auth = match matches.get_one::<String>("wifi-auth") {
-        Some(auth) => Some(auth.to_string()),
-        None => Some("WPA".to_string()),
+    let payload = match matches.subcommand() {
+        Some(("wifi", sub_matches)) => parse_wifi_subcommand(sub_matches),
+        Some(("vcard", sub_matches)) => parse_vcard_subcommand(sub_matches),
+        Some(("totp", sub_matches)) => parse_totp_subcommand(sub_matches),
+        _ => parse_legacy_payload(&matches)?,
     };
+
+    let symbology = matches
+        .get_one::<String>("symbology")
+        .and_then(|value| Symbology::from_str_value(value))
+        .unwrap_or(Symbology::Qr);
+
     let no_header = matches.get_flag("no-header");
     let dont_print = matches.get_flag("dont-print");
     let output_format = matches.get_one::<String>("output-format");
     let output_file = matches.get_one::<String>("output-file");
 
+    Ok(QrCodeConfig::new(
+        payload,
+        symbology,
+        no_header,
+        dont_print,
+        output_format.map(|format| format.to_string()),
+        output_file.map(|file| file.to_string()),
+    ))
+}
+
+fn build_wifi_subcommand() -> Command {
+    Command::new("wifi")
+        .about("Generates a QR code with wifi network credentials")
+        .arg(
+            Arg::new("ssid")
+                .long("ssid")
+                .short('s')
+                .required(true)
+                .help("Network name (SSID)."),
+        )
+        .arg(
+            Arg::new("password")
+                .long("password")
+                .short('p')
+                .help("Network password. Not needed when auth is 'nopass'."),
+        )
+        .arg(
+            Arg::new("auth")
+                .long("auth")
+                .short('a')
+                .default_value("WPA")
+                .help("Authentication type: WPA, WEP, or nopass. (Default: WPA)"),
+        )
+        .arg(
+            Arg::new("hidden")
+                .long("hidden")
+                .action(clap::ArgAction::SetTrue)
+                .help("Marks the network as hidden."),
+        )
+}
+
+fn build_vcard_subcommand() -> Command {
+    Command::new("vcard")
+        .about("Generates a QR code with a contact card (vCard 3.0)")
+        .arg(
+            Arg::new("first-name")
+                .long("first-name")
+                .help("First name."),
+        )
+        .arg(Arg::new("last-name").long("last-name").help("Last name."))
+        .arg(Arg::new("phone").long("phone").help("Phone number."))
+        .arg(Arg::new("email").long("email").help("Email address."))
+        .arg(Arg::new("org").long("org").help("Organization/company."))
+        .arg(Arg::new("title").long("title").help("Job title."))
+        .arg(Arg::new("url").long("url").help("Website."))
+        .arg(Arg::new("address").long("address").help("Street address."))
+        .arg(Arg::new("note").long("note").help("Free text note."))
+}
+
+fn build_totp_subcommand() -> Command {
+    Command::new("totp")
+        .about("Generates a QR code for authenticator apps (otpauth://totp)")
+        .arg(
+            Arg::new("secret")
+                .long("secret")
+                .required(true)
+                .help("Shared secret, base32 encoded."),
+        )
+        .arg(
+            Arg::new("account")
+                .long("account")
+                .required(true)
+                .help("Account name (usually username or email)."),
+        )
+        .arg(
+            Arg::new("issuer")
+                .long("issuer")
+                .help("Issuer (service or company name)."),
+        )
+        .arg(
+            Arg::new("algorithm")
+                .long("algorithm")
+                .default_value("SHA1")
+                .value_parser(["SHA1", "SHA256", "SHA512"])
+                .help("Hash algorithm. (Default: SHA1)"),
+        )
+        .arg(
+            Arg::new("digits")
+                .long("digits")
+                .default_value("6")
+                .value_parser(clap::value_parser!(u8))
+                .help("Number of digits in the code: 6 or 8. (Default: 6)"),
+        )
+        .arg(
+            Arg::new("period")
+                .long("period")
+                .default_value("30")
+                .value_parser(clap::value_parser!(u32))
+                .help("How long (in seconds) each code is valid. (Default: 30)"),
+        )
+}
+
+fn parse_wifi_subcommand(matches: &ArgMatches) -> HowMode {
+    HowMode::WifiPayload(WifiPayload {
+        ssid: get_string(matches, "ssid").unwrap_or_default(),
+        password: get_string(matches, "password"),
+        auth: get_string(matches, "auth").unwrap_or_else(|| "WPA".to_string()),
+        hidden: matches.get_flag("hidden"),
+    })
+}
+
+fn parse_vcard_subcommand(matches: &ArgMatches) -> HowMode {
+    HowMode::VCardPayload(VCardPayload {
+        first_name: get_string(matches, "first-name"),
+        last_name: get_string(matches, "last-name"),
+        phone: get_string(matches, "phone"),
+        email: get_string(matches, "email"),
+        organization: get_string(matches, "org"),
+        title: get_string(matches, "title"),
+        url: get_string(matches, "url"),
+        address: get_string(matches, "address"),
+        note: get_string(matches, "note"),
+    })
+}
+
+fn parse_totp_subcommand(matches: &ArgMatches) -> HowMode {
+    HowMode::TotpPayload(TotpPayload {
+        secret: get_string(matches, "secret").unwrap_or_default(),
+        account: get_string(matches, "account").unwrap_or_default(),
+        issuer: get_string(matches, "issuer"),
+        algorithm: get_string(matches, "algorithm").unwrap_or_else(|| "SHA1".to_string()),
+        digits: matches.get_one::<u8>("digits").copied().unwrap_or(6),
+        period: matches.get_one::<u32>("period").copied().unwrap_or(30),
+    })
+}
+
+fn parse_legacy_payload(matches: &ArgMatches) -> Result<HowMode> {
+    let text_payload = matches.get_one::<String>("text");
+    let wifi_ssid = matches.get_one::<String>("wifi-ssid");
+    let wifi_password = matches.get_one::<String>("wifi-password");
+    let wifi_auth = match matches.get_one::<String>("wifi-auth") {
+        Some(auth) => auth.to_string(),
+        None => "WPA".to_string(),
+    };
+
     let is_text_payload_set = text_payload.is_some();
     let is_wifi_payload_set = wifi_ssid.is_some() && wifi_password.is_some();
 
     if !is_text_payload_set && !is_wifi_payload_set {
-        error!("Error: Either text or wifi payload must be provided.");
-        exit_error();
-        unreachable!();
+        bail!("Either text or wifi payload must be provided.");
     } else if !is_text_payload_set {
         if wifi_ssid.is_none() && wifi_password.is_some() {
-            error!("Error: Wifi payload doesn't have an SSID.");
-            exit_error();
-            unreachable!();
+            bail!("Wifi payload doesn't have an SSID.");
         } else if wifi_ssid.is_some() && wifi_password.is_none() {
-            error!("Error: Wifi payload doesn't have a password.");
-            exit_error();
-            unreachable!();
+            bail!("Wifi payload doesn't have a password.");
         }
     }
 
-    Ok(QrCodeConfig::new(
-        QrCodePayload::new(
-            text_payload.map(|text| text.to_string()),
-            wifi_ssid.map(|ssid| ssid.to_string()),
-            wifi_password.map(|password| password.to_string()),
-            wifi_auth.map(|auth| auth.to_string()),
-        ),
-        no_header,
-        dont_print,
-        output_format.map(|format| format.to_string()),
-        output_file.map(|file| file.to_string()),
-    ))
+    Ok(match text_payload {
+        Some(text) => HowMode::TextPayload(text.to_string()),
+        None => HowMode::WifiPayload(WifiPayload {
+            ssid: wifi_ssid.cloned().unwrap_or_default(),
+            password: wifi_password.cloned(),
+            auth: wifi_auth,
+            hidden: false,
+        }),
+    })
+}
+
+fn get_string(matches: &ArgMatches, id: &str) -> Option<String> {
+    matches.get_one::<String>(id).map(|value| value.to_string())
 }
@@ -0,0 +1,47 @@
+use crate::cli_utils::{get_cli_arguments, print_runtime_info};
+use crate::qrcode_app::generate_qrcode;
+use anyhow::Result;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+mod cli_utils;
+mod models;
+mod payload_builders;
+mod qrcode_app;
+mod symbology;
+
+/// QR code generator.
+///
+/// Parses arguments, then generates the code.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    match run_tool(argv.into_iter().map(Into::into).collect()) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    let config = get_cli_arguments(argv)?;
+
+    if !config.no_header {
+        print_runtime_info(&config);
+    }
+
+    generate_qrcode(&config)?;
+
+    Ok(())
+}
@@ -1,42 +1,68 @@
+use crate::symbology::Symbology;
+
 pub struct QrCodeConfig {
-    pub payload: QrCodePayload,
+    pub payload: HowMode,
+    pub symbology: Symbology,
     pub no_header: bool,
     pub dont_print: bool,
     pub output_format: Option<String>,
     pub output_file: Option<String>,
 }
 
-pub struct QrCodePayload {
-    pub text_payload: Option<String>,
-    pub wifi_ssid: Option<String>,
-    pub wifi_password: Option<String>,
-    pub wifi_auth: Option<String>,
-}
-
 pub enum HowMode {
     TextPayload(String),
-    WifiPayload(String, String, String),
+    WifiPayload(WifiPayload),
+    VCardPayload(VCardPayload),
+    TotpPayload(TotpPayload),
 }
 
-impl QrCodePayload {
-    pub fn new(
-        text_payload: Option<String>,
-        wifi_ssid: Option<String>,
-        wifi_password: Option<String>,
-        wifi_auth: Option<String>,
-    ) -> Self {
-        Self {
-            text_payload,
-            wifi_ssid,
-            wifi_password,
-            wifi_auth,
-        }
+/// Data used to build a `WIFI:` network credentials payload.
+pub struct WifiPayload {
+    pub ssid: String,
+    pub password: Option<String>,
+    pub auth: String,
+    pub hidden: bool,
+}
+
+/// Data used to build a vCard 3.0 contact payload.
+#[derive(Default)]
+pub struct VCardPayload {
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub organization: Option<String>,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub address: Option<String>,
+    pub note: Option<String>,
+}
+
+/// Data used to build an `otpauth://totp/` URI, as understood by authenticator apps.
+pub struct TotpPayload {
+    pub secret: String,
+    pub account: String,
+    pub issuer: Option<String>,
+    pub algorithm: String,
+    pub digits: u8,
+    pub period: u32,
+}
+
+impl VCardPayload {
+    pub fn full_name(&self) -> String {
+        [self.first_name.as_deref(), self.last_name.as_deref()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<&str>>()
+            .join(" ")
     }
 }
 
 impl QrCodeConfig {
     pub fn new(
-        payload: QrCodePayload,
+        payload: HowMode,
+        symbology: Symbology,
         no_header: bool,
         dont_print: bool,
         output_format: Option<String>,
@@ -44,6 +70,7 @@ impl QrCodeConfig {
     ) -> Self {
         Self {
             payload,
+            symbology,
             no_header,
             dont_print,
             output_format,
@@ -51,22 +78,7 @@ impl QrCodeConfig {
         }
     }
 
-    fn is_wifi_payload_set(&self) -> bool {
-        self.payload.wifi_ssid.is_some()
-            && self.payload.wifi_password.is_some()
-            && self.payload.wifi_auth.is_some()
-            && !self.payload.text_payload.is_some()
-    }
-
-    pub fn get_payload(&self) -> HowMode {
-        if self.is_wifi_payload_set() {
-            HowMode::WifiPayload(
-                self.payload.wifi_ssid.clone().unwrap(),
-                self.payload.wifi_password.clone().unwrap(),
-                self.payload.wifi_auth.clone().unwrap(),
-            )
-        } else {
-            HowMode::TextPayload(self.payload.text_payload.clone().unwrap_or_default())
-        }
+    pub fn get_payload(&self) -> &HowMode {
+        &self.payload
     }
 }
@@ -0,0 +1,293 @@
+use crate::models::{HowMode, TotpPayload, VCardPayload, WifiPayload};
+use anyhow::{bail, Result};
+
+const NO_PASSWORD_AUTH: &str = "nopass";
+
+/// Turns the selected payload into the string that will be encoded in the QR code.
+pub fn build_payload_string(payload: &HowMode) -> Result<String> {
+    match payload {
+        HowMode::TextPayload(text) => Ok(text.to_string()),
+        HowMode::WifiPayload(wifi) => build_wifi_payload(wifi),
+        HowMode::VCardPayload(vcard) => build_vcard_payload(vcard),
+        HowMode::TotpPayload(totp) => build_totp_payload(totp),
+    }
+}
+
+/// Builds a `WIFI:T:<auth>;S:<ssid>;P:<password>;H:<hidden>;;` payload.
+///
+/// Special characters (`\`, `;`, `,`, `:`, `"`) in the SSID and password are escaped with a
+/// backslash, as expected by the readers.
+pub fn build_wifi_payload(wifi: &WifiPayload) -> Result<String> {
+    if wifi.ssid.is_empty() {
+        bail!("Wifi payload doesn't have an SSID.");
+    }
+
+    let is_open_network = wifi.auth.eq_ignore_ascii_case(NO_PASSWORD_AUTH);
+
+    let mut payload = format!("WIFI:T:{};S:{};", wifi.auth, escape_wifi_value(&wifi.ssid));
+
+    match (&wifi.password, is_open_network) {
+        (Some(password), false) => {
+            payload.push_str(&format!("P:{};", escape_wifi_value(password)));
+        }
+        (None, false) => bail!("Wifi payload doesn't have a password."),
+        (_, true) => {}
+    }
+
+    if wifi.hidden {
+        payload.push_str("H:true;");
+    }
+
+    payload.push(';');
+
+    Ok(payload)
+}
+
+/// Builds a vCard 3.0 payload. At least the first or last name must be informed.
+pub fn build_vcard_payload(vcard: &VCardPayload) -> Result<String> {
+    let full_name = vcard.full_name();
+
+    if full_name.is_empty() {
+        bail!("vCard payload needs at least a first or last name.");
+    }
+
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!(
+            "N:{};{};;;",
+            escape_vcard_value(vcard.last_name.as_deref().unwrap_or_default()),
+            escape_vcard_value(vcard.first_name.as_deref().unwrap_or_default())
+        ),
+        format!("FN:{}", escape_vcard_value(&full_name)),
+    ];
+
+    let optional_fields = [
+        ("ORG", &vcard.organization),
+        ("TITLE", &vcard.title),
+        ("TEL", &vcard.phone),
+        ("EMAIL", &vcard.email),
+        ("URL", &vcard.url),
+        ("NOTE", &vcard.note),
+    ];
+
+    for (key, value) in optional_fields {
+        if let Some(value) = value {
+            lines.push(format!("{}:{}", key, escape_vcard_value(value)));
+        }
+    }
+
+    if let Some(address) = &vcard.address {
+        // Whole address goes into the street component; splitting it reliably isn't worth it.
+        lines.push(format!("ADR:;;{};;;;", escape_vcard_value(address)));
+    }
+
+    lines.push("END:VCARD".to_string());
+
+    Ok(lines.join("\n"))
+}
+
+/// Builds an `otpauth://totp/<issuer>:<account>?secret=...` URI.
+///
+/// The secret is normalized (spaces and padding removed, uppercased) and validated as base32.
+pub fn build_totp_payload(totp: &TotpPayload) -> Result<String> {
+    if totp.account.is_empty() {
+        bail!("TOTP payload needs an account name.");
+    }
+
+    let secret = normalize_base32_secret(&totp.secret)?;
+
+    let algorithm = totp.algorithm.to_uppercase();
+    if !matches!(algorithm.as_str(), "SHA1" | "SHA256" | "SHA512") {
+        bail!("TOTP algorithm must be one of: SHA1, SHA256, SHA512.");
+    }
+
+    if totp.digits != 6 && totp.digits != 8 {
+        bail!("TOTP digits must be 6 or 8.");
+    }
+
+    if totp.period == 0 {
+        bail!("TOTP period must be greater than 0.");
+    }
+
+    let label = match &totp.issuer {
+        Some(issuer) => format!(
+            "{}:{}",
+            percent_encode(issuer),
+            percent_encode(&totp.account)
+        ),
+        None => percent_encode(&totp.account),
+    };
+
+    let mut payload = format!("otpauth://totp/{}?secret={}", label, secret);
+
+    if let Some(issuer) = &totp.issuer {
+        payload.push_str(&format!("&issuer={}", percent_encode(issuer)));
+    }
+
+    payload.push_str(&format!(
+        "&algorithm={}&digits={}&period={}",
+        algorithm, totp.digits, totp.period
+    ));
+
+    Ok(payload)
+}
+
+fn normalize_base32_secret(secret: &str) -> Result<String> {
+    let normalized: String = secret
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_uppercase()
+        .trim_end_matches('=')
+        .to_string();
+
+    if normalized.is_empty() {
+        bail!("TOTP secret cannot be empty.");
+    }
+
+    if let Some(invalid) = normalized
+        .chars()
+        .find(|c| !matches!(c, 'A'..='Z' | '2'..='7'))
+    {
+        bail!(
+            "TOTP secret must be base32 encoded. Invalid character: '{}'",
+            invalid
+        );
+    }
+
+    Ok(normalized)
+}
+
+fn escape_wifi_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+fn escape_vcard_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wifi(ssid: &str, password: Option<&str>, auth: &str, hidden: bool) -> WifiPayload {
+        WifiPayload {
+            ssid: ssid.to_string(),
+            password: password.map(|p| p.to_string()),
+            auth: auth.to_string(),
+            hidden,
+        }
+    }
+
+    #[test]
+    fn test_wifi_payload_basic() {
+        let payload = build_wifi_payload(&wifi("MyNetwork", Some("pass"), "WPA", false)).unwrap();
+
+        assert_eq!(payload, "WIFI:T:WPA;S:MyNetwork;P:pass;;");
+    }
+
+    #[test]
+    fn test_wifi_payload_escapes_special_characters() {
+        let payload =
+            build_wifi_payload(&wifi("My;Net", Some(r#"a:b,c"d\e"#), "WPA", true)).unwrap();
+
+        assert_eq!(payload, r#"WIFI:T:WPA;S:My\;Net;P:a\:b\,c\"d\\e;H:true;;"#);
+    }
+
+    #[test]
+    fn test_wifi_payload_open_network_skips_password() {
+        let payload = build_wifi_payload(&wifi("Guest", None, "nopass", false)).unwrap();
+
+        assert_eq!(payload, "WIFI:T:nopass;S:Guest;;");
+    }
+
+    #[test]
+    fn test_wifi_payload_requires_password_when_secured() {
+        assert!(build_wifi_payload(&wifi("Guest", None, "WPA", false)).is_err());
+    }
+
+    #[test]
+    fn test_vcard_payload() {
+        let vcard = VCardPayload {
+            first_name: Some("John".to_string()),
+            last_name: Some("Smith".to_string()),
+            email: Some("john@example.com".to_string()),
+            organization: Some("ACME, Inc.".to_string()),
+            ..Default::default()
+        };
+
+        let payload = build_vcard_payload(&vcard).unwrap();
+
+        assert_eq!(
+            payload,
+            "BEGIN:VCARD\nVERSION:3.0\nN:Smith;John;;;\nFN:John Smith\nORG:ACME\\, Inc.\nEMAIL:john@example.com\nEND:VCARD"
+        );
+    }
+
+    #[test]
+    fn test_vcard_payload_requires_name() {
+        assert!(build_vcard_payload(&VCardPayload::default()).is_err());
+    }
+
+    #[test]
+    fn test_totp_payload() {
+        let totp = TotpPayload {
+            secret: "jbsw y3dp ehpk 3pxp".to_string(),
+            account: "john@example.com".to_string(),
+            issuer: Some("ACME Co".to_string()),
+            algorithm: "sha1".to_string(),
+            digits: 6,
+            period: 30,
+        };
+
+        let payload = build_totp_payload(&totp).unwrap();
+
+        assert_eq!(
+            payload,
+            "otpauth://totp/ACME%20Co:john%40example.com?secret=JBSWY3DPEHPK3PXP&issuer=ACME%20Co&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn test_totp_payload_rejects_invalid_secret() {
+        let totp = TotpPayload {
+            secret: "not-base32!".to_string(),
+            account: "john".to_string(),
+            issuer: None,
+            algorithm: "SHA1".to_string(),
+            digits: 6,
+            period: 30,
+        };
+
+        assert!(build_totp_payload(&totp).is_err());
+    }
+}
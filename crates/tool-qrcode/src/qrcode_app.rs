@@ -1,8 +1,9 @@
-use crate::models::{HowMode, QrCodeConfig};
+use crate::models::QrCodeConfig;
+use crate::payload_builders::build_payload_string;
+use crate::symbology::{encode, SymbolMatrix};
 use anyhow::Result;
 use chrono::Local;
 use image::{ImageBuffer, Luma};
-use qrcodegen::{QrCode, QrCodeEcc};
 use shared::utils::datetime_utc_utils::DateTimeUtilsExt;
 use std::fs::File;
 use std::io::Write;
@@ -11,24 +12,18 @@ use tracing::{debug, info};
 pub fn generate_qrcode(args: &QrCodeConfig) -> Result<()> {
     info!("Preparing QR code data...");
 
-    let data = match args.get_payload() {
-        HowMode::TextPayload(text) => text,
-        HowMode::WifiPayload(ssid, password, auth) => {
-            format!("WIFI:T:{};S:{};P:{};;", auth, ssid, password)
-        }
-    };
+    let data = build_payload_string(args.get_payload())?;
 
     let data_size = data.len();
 
     debug!("Data [{}]: {}", data_size, data);
-    info!("Generating QR code...");
+    info!("Generating {}...", args.symbology.label());
 
-    let qr_code_ecc = choose_ecc(data_size);
-    let qr_code = qrcodegen::QrCode::encode_text(&data, qr_code_ecc)?;
-    info!("QR code generated.");
+    let code = encode(&data, args.symbology)?;
+    info!("{} generated.", args.symbology.label());
 
     if !args.dont_print {
-        print_qr_to_console(&qr_code);
+        print_code_to_console(&code);
     }
 
     if args.output_format.is_none() && args.output_file.is_none() {
@@ -42,7 +37,8 @@ pub fn generate_qrcode(args: &QrCodeConfig) -> Result<()> {
 
     let mut filename = args.output_file.clone().unwrap_or_else(|| {
         format!(
-            "qrcode--{}.{}",
+            "{}--{}.{}",
+            args.symbology.file_prefix(),
             Local::now().get_datetime_as_filename_safe_string(),
             filename_ext
         )
@@ -52,33 +48,37 @@ pub fn generate_qrcode(args: &QrCodeConfig) -> Result<()> {
         filename.push_str(&format!(".{}", filename_ext));
     }
 
-    let qr_code_scale = 30;
+    let scale = 30;
+    let quiet_zone = args.symbology.quiet_zone();
 
     match filename_ext.to_lowercase().trim() {
         "svg" => {
-            info!("Saving QR code to SVG...");
-            save_qr_to_svg(&qr_code, qr_code_scale, &filename)?;
+            info!("Saving {} to SVG...", args.symbology.label());
+            save_code_to_svg(&code, scale, quiet_zone, &filename)?;
         }
         _ => {
-            info!("Saving QR code to image ({})...", filename_ext);
-            save_qr_to_image(&qr_code, qr_code_scale, &filename)?;
+            info!(
+                "Saving {} to image ({})...",
+                args.symbology.label(),
+                filename_ext
+            );
+            save_code_to_image(&code, scale, quiet_zone, &filename)?;
         }
     }
 
     Ok(())
 }
 
-fn save_qr_to_image(qr: &QrCode, scale: u32, path: &str) -> Result<()> {
-    let size = qr.size() as u32;
-    let border_modules: u32 = 2; // thin quiet zone (in modules)
-    let quiet = border_modules * scale;
-    let img_size = (size * scale) + (2 * quiet);
+fn save_code_to_image(code: &SymbolMatrix, scale: u32, quiet_zone: u32, path: &str) -> Result<()> {
+    let quiet = quiet_zone * scale;
+    let img_width = (code.width() * scale) + (2 * quiet);
+    let img_height = (code.height() * scale) + (2 * quiet);
 
-    let mut img = ImageBuffer::from_pixel(img_size, img_size, Luma([255u8])); // white background
+    let mut img = ImageBuffer::from_pixel(img_width, img_height, Luma([255u8])); // white background
 
-    for y in 0..size {
-        for x in 0..size {
-            if qr.get_module(x as i32, y as i32) {
+    for y in 0..code.height() {
+        for x in 0..code.width() {
+            if code.is_dark(x as i64, y as i64) {
                 let x0 = quiet + x * scale;
                 let y0 = quiet + y * scale;
                 for dy in 0..scale {
@@ -94,22 +94,21 @@ fn save_qr_to_image(qr: &QrCode, scale: u32, path: &str) -> Result<()> {
     Ok(())
 }
 
-fn save_qr_to_svg(qr: &QrCode, scale: u32, path: &str) -> Result<()> {
-    let size = qr.size() as u32;
-    let border_modules: u32 = 2;
-    let quiet = border_modules * scale;
-    let dim = (size * scale) + (2 * quiet);
+fn save_code_to_svg(code: &SymbolMatrix, scale: u32, quiet_zone: u32, path: &str) -> Result<()> {
+    let quiet = quiet_zone * scale;
+    let width = (code.width() * scale) + (2 * quiet);
+    let height = (code.height() * scale) + (2 * quiet);
 
     let mut file = File::create(path)?;
     writeln!(
         file,
-        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {dim} {dim}" width="{dim}" height="{dim}" shape-rendering="crispEdges">"#
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {height}" width="{width}" height="{height}" shape-rendering="crispEdges">"#
     )?;
     writeln!(file, r#"<rect width="100%" height="100%" fill="white"/>"#)?;
 
-    for y in 0..size {
-        for x in 0..size {
-            if qr.get_module(x as i32, y as i32) {
+    for y in 0..code.height() {
+        for x in 0..code.width() {
+            if code.is_dark(x as i64, y as i64) {
                 let sx = quiet + x * scale;
                 let sy = quiet + y * scale;
                 writeln!(
@@ -124,22 +123,13 @@ fn save_qr_to_svg(qr: &QrCode, scale: u32, path: &str) -> Result<()> {
     Ok(())
 }
 
-fn print_qr_to_console(qr: &QrCode) {
+fn print_code_to_console(code: &SymbolMatrix) {
     let border = 1;
-    for y in -border..qr.size() + border {
-        for x in -border..qr.size() + border {
-            let c = if qr.get_module(x, y) { "██" } else { "  " };
+    for y in -border..code.height() as i64 + border {
+        for x in -border..code.width() as i64 + border {
+            let c = if code.is_dark(x, y) { "██" } else { "  " };
             print!("{}", c);
         }
         println!();
     }
 }
-
-fn choose_ecc(len: usize) -> QrCodeEcc {
-    match len {
-        0..=50 => QrCodeEcc::High,
-        51..=100 => QrCodeEcc::Quartile,
-        101..=300 => QrCodeEcc::Medium,
-        _ => QrCodeEcc::Low,
-    }
-}
@@ -0,0 +1,235 @@
+use anyhow::{anyhow, Result};
+use micro_qr::{Color, EcLevel, Version};
+use qrcodegen::{QrCode, QrCodeEcc};
+use rxing::{BarcodeFormat, MultiFormatWriter, Writer};
+
+/// Largest Micro QR version (M4).
+const MICRO_QR_MAX_VERSION: i16 = 4;
+
+/// 2D code type used to encode the payload.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Symbology {
+    Qr,
+    MicroQr,
+    DataMatrix,
+    Aztec,
+}
+
+impl Symbology {
+    pub fn from_str_value(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "qr" => Some(Symbology::Qr),
+            "micro-qr" => Some(Symbology::MicroQr),
+            "datamatrix" => Some(Symbology::DataMatrix),
+            "aztec" => Some(Symbology::Aztec),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Symbology::Qr => "QR Code",
+            Symbology::MicroQr => "Micro QR",
+            Symbology::DataMatrix => "DataMatrix",
+            Symbology::Aztec => "Aztec",
+        }
+    }
+
+    /// Prefix used when the output filename is generated.
+    pub fn file_prefix(&self) -> &'static str {
+        match self {
+            Symbology::Qr => "qrcode",
+            Symbology::MicroQr => "micro-qr",
+            Symbology::DataMatrix => "datamatrix",
+            Symbology::Aztec => "aztec",
+        }
+    }
+
+    /// Quiet zone (in modules) around the code in the saved files.
+    /// Micro QR needs 2 modules, DataMatrix needs 1, and Aztec doesn't need any (1 just looks better).
+    pub fn quiet_zone(&self) -> u32 {
+        match self {
+            Symbology::Qr | Symbology::MicroQr => 2,
+            Symbology::DataMatrix | Symbology::Aztec => 1,
+        }
+    }
+}
+
+/// Encoded code, independent of the symbology, so every output backend can render it.
+/// DataMatrix codes may be rectangular, so width and height are kept apart.
+pub struct SymbolMatrix {
+    width: u32,
+    height: u32,
+    modules: Vec<bool>,
+}
+
+impl SymbolMatrix {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Whether the module is dark. Coordinates outside the code (the quiet zone) are light.
+    pub fn is_dark(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return false;
+        }
+
+        self.modules[(y as u32 * self.width + x as u32) as usize]
+    }
+
+    fn from_fn(width: u32, height: u32, is_dark: impl Fn(u32, u32) -> bool) -> Self {
+        let mut modules = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                modules.push(is_dark(x, y));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            modules,
+        }
+    }
+}
+
+/// Encodes the payload with the chosen symbology.
+///
+/// # Errors
+/// Returns an error when the payload doesn't fit the symbology (e.g.: more than 35 digits in Micro QR).
+pub fn encode(data: &str, symbology: Symbology) -> Result<SymbolMatrix> {
+    match symbology {
+        Symbology::Qr => encode_qr(data),
+        Symbology::MicroQr => encode_micro_qr(data),
+        Symbology::DataMatrix => encode_with_rxing(data, BarcodeFormat::DATA_MATRIX, symbology),
+        Symbology::Aztec => encode_with_rxing(data, BarcodeFormat::AZTEC, symbology),
+    }
+}
+
+fn encode_qr(data: &str) -> Result<SymbolMatrix> {
+    let qr_code = QrCode::encode_text(data, choose_ecc(data.len()))?;
+    let size = qr_code.size() as u32;
+
+    Ok(SymbolMatrix::from_fn(size, size, |x, y| {
+        qr_code.get_module(x as i32, y as i32)
+    }))
+}
+
+/// Uses the smallest Micro QR version that fits, with the strongest error correction available for it.
+fn encode_micro_qr(data: &str) -> Result<SymbolMatrix> {
+    for version in 1..=MICRO_QR_MAX_VERSION {
+        for ec_level in [EcLevel::Q, EcLevel::M, EcLevel::L] {
+            let Ok(code) = micro_qr::QrCode::with_version(data, Version::Micro(version), ec_level)
+            else {
+                continue;
+            };
+
+            let size = code.width() as u32;
+            let colors = code.to_colors();
+            return Ok(SymbolMatrix::from_fn(size, size, |x, y| {
+                colors[(y * size + x) as usize] == Color::Dark
+            }));
+        }
+    }
+
+    Err(anyhow!(
+        "Payload is too long for Micro QR ({} bytes). It fits up to 35 digits, 21 uppercase alphanumeric characters, or 15 bytes. Use --symbology qr instead.",
+        data.len()
+    ))
+}
+
+fn encode_with_rxing(
+    data: &str,
+    format: BarcodeFormat,
+    symbology: Symbology,
+) -> Result<SymbolMatrix> {
+    // A size of 0 returns the code without scaling or quiet zone: one pixel per module.
+    let matrix = MultiFormatWriter::default()
+        .encode(data, &format, 0, 0)
+        .map_err(|e| anyhow!("Failed to encode {}: {}", symbology.label(), e))?;
+
+    Ok(SymbolMatrix::from_fn(
+        matrix.width(),
+        matrix.height(),
+        |x, y| matrix.get(x, y),
+    ))
+}
+
+fn choose_ecc(len: usize) -> QrCodeEcc {
+    match len {
+        0..=50 => QrCodeEcc::High,
+        51..=100 => QrCodeEcc::Quartile,
+        101..=300 => QrCodeEcc::Medium,
+        _ => QrCodeEcc::Low,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbology_from_str_value() {
+        assert_eq!(Symbology::from_str_value("QR"), Some(Symbology::Qr));
+        assert_eq!(
+            Symbology::from_str_value("micro-qr"),
+            Some(Symbology::MicroQr)
+        );
+        assert_eq!(
+            Symbology::from_str_value("datamatrix"),
+            Some(Symbology::DataMatrix)
+        );
+        assert_eq!(Symbology::from_str_value("aztec"), Some(Symbology::Aztec));
+        assert_eq!(Symbology::from_str_value("pdf417"), None);
+    }
+
+    #[test]
+    fn test_micro_qr_uses_smallest_version() {
+        // M1 (11x11) holds up to 5 digits.
+        let small = encode("12345", Symbology::MicroQr).unwrap();
+        assert_eq!((small.width(), small.height()), (11, 11));
+
+        // M4 (17x17) holds up to 15 bytes.
+        let large = encode("hello, world!", Symbology::MicroQr).unwrap();
+        assert_eq!(large.width(), 17);
+    }
+
+    #[test]
+    fn test_micro_qr_rejects_long_payloads() {
+        let error = encode(
+            "https://github.com/brenordv/rusted-toolbox",
+            Symbology::MicroQr,
+        )
+        .err()
+        .unwrap();
+
+        assert!(error.to_string().contains("too long for Micro QR"));
+    }
+
+    #[test]
+    fn test_datamatrix_and_aztec_are_encoded() {
+        let datamatrix = encode("LOT-2026-10-16", Symbology::DataMatrix).unwrap();
+        assert!(datamatrix.width() >= 10);
+        // The "L" finder pattern: left column and bottom row are solid.
+        assert!((0..datamatrix.height() as i64).all(|y| datamatrix.is_dark(0, y)));
+
+        let aztec = encode("LOT-2026-10-16", Symbology::Aztec).unwrap();
+        assert_eq!(aztec.width(), aztec.height());
+        // The bullseye center is always dark.
+        let center = (aztec.width() / 2) as i64;
+        assert!(aztec.is_dark(center, center));
+    }
+
+    #[test]
+    fn test_outside_modules_are_light() {
+        let matrix = encode("hello", Symbology::Qr).unwrap();
+
+        assert!(matrix.is_dark(0, 0));
+        assert!(!matrix.is_dark(-1, 0));
+        assert!(!matrix.is_dark(0, matrix.height() as i64));
+    }
+}
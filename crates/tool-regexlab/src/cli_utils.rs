@@ -0,0 +1,132 @@
+use crate::models::{ExportFormat, RegexFlags, RegexLabArgs};
+use anyhow::{Context, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<RegexLabArgs> {
+    let matches = build_command().get_matches_from(argv);
+    parse_args(&matches)
+}
+
+fn build_command() -> Command {
+    Command::new(env!("CARGO_PKG_NAME"))
+        .add_basic_metadata(
+            env!("CARGO_PKG_VERSION"),
+            env!("CARGO_PKG_DESCRIPTION"),
+            "Interactive regex tester: type a pattern and some sample text (or load a file), and see \
+            the matches highlighted and their capture groups as you type.\n\
+            Press Ctrl+E to exit printing the pattern in Rust, PCRE or plain syntax, ready to be \
+            used in code or with get-lines, lookup and csvn.",
+        )
+        .arg(
+            Arg::new("pattern")
+                .value_name("PATTERN")
+                .help("Initial pattern"),
+        )
+        .arg(
+            Arg::new("text")
+                .long("text")
+                .short('t')
+                .value_name("TEXT")
+                .conflicts_with("file")
+                .help("Initial sample text"),
+        )
+        .arg(
+            Arg::new("file")
+                .long("file")
+                .short('f')
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("Loads the sample text from a file"),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .long("ignore-case")
+                .short('i')
+                .action(ArgAction::SetTrue)
+                .help("Starts with the case-insensitive flag (i) on"),
+        )
+        .arg(
+            Arg::new("multi-line")
+                .long("multi-line")
+                .short('m')
+                .action(ArgAction::SetTrue)
+                .help("Starts with the multi-line flag (m) on: ^ and $ match at each line"),
+        )
+        .arg(
+            Arg::new("dot-all")
+                .long("dot-all")
+                .short('s')
+                .action(ArgAction::SetTrue)
+                .help("Starts with the dot-all flag (s) on: . also matches new lines"),
+        )
+        .arg(
+            Arg::new("export")
+                .long("export")
+                .short('e')
+                .value_name("FORMAT")
+                .value_parser(clap::value_parser!(ExportFormat))
+                .default_value("rust")
+                .help("Syntax of the pattern printed on exit (can be changed in the UI with F5)"),
+        )
+}
+
+fn parse_args(matches: &ArgMatches) -> Result<RegexLabArgs> {
+    let file = matches.get_one::<PathBuf>("file").cloned();
+
+    let text = match &file {
+        Some(file) => {
+            let bytes = std::fs::read(file)
+                .with_context(|| format!("Failed to read file: {}", file.display()))?;
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        None => matches
+            .get_one::<String>("text")
+            .cloned()
+            .unwrap_or_default(),
+    };
+
+    Ok(RegexLabArgs {
+        pattern: matches
+            .get_one::<String>("pattern")
+            .cloned()
+            .unwrap_or_default(),
+        text,
+        file,
+        flags: RegexFlags {
+            ignore_case: matches.get_flag("ignore-case"),
+            multi_line: matches.get_flag("multi-line"),
+            dot_all: matches.get_flag("dot-all"),
+        },
+        export_format: *matches
+            .get_one::<ExportFormat>("export")
+            .expect("export has a default value"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let matches = build_command()
+            .try_get_matches_from([
+                "regexlab", r"(\d+)", "-t", "a1 b22", "-im", "--export", "pcre",
+            ])
+            .unwrap();
+
+        let args = parse_args(&matches).unwrap();
+
+        assert_eq!(args.pattern, r"(\d+)");
+        assert_eq!(args.text, "a1 b22");
+        assert_eq!(args.flags.letters(), "im");
+        assert_eq!(args.export_format, ExportFormat::Pcre);
+
+        assert!(build_command()
+            .try_get_matches_from(["regexlab", "-t", "a", "-f", "b.txt"])
+            .is_err());
+    }
+}
@@ -0,0 +1,47 @@
+mod cli_utils;
+mod models;
+mod regex_engine;
+mod ui;
+
+use crate::cli_utils::get_cli_arguments;
+use crate::ui::lab_ui::LabUi;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::get_default_log_builder;
+use std::ffi::OsString;
+use tracing::error;
+
+/// Interactive regex tester.
+///
+/// Opens the UI with the pattern and text from the command line, and prints the exported pattern
+/// if the user exits with Ctrl+E.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    // Change the boolean flags below to enable logging to console and/or file.
+    // This helps with debugging if you need.
+    get_default_log_builder(env!("CARGO_PKG_NAME"), LogLevel::Info)
+        .log_to_console(false)
+        .log_to_file(false, false)
+        .init();
+
+    let result = get_cli_arguments(argv.into_iter().map(Into::into).collect())
+        .and_then(|args| LabUi::new(args).run());
+
+    match result {
+        Ok(Some(exported)) => println!("{}", exported),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+            error!("{}", e);
+            return EXIT_CODE_ERROR;
+        }
+    }
+
+    EXIT_CODE_SUCCESS
+}
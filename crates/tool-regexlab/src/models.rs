@@ -0,0 +1,73 @@
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+pub struct RegexLabArgs {
+    /// Initial pattern.
+    pub pattern: String,
+    /// Initial sample text (from `--text` or `--file`).
+    pub text: String,
+    /// File the sample text was loaded from, shown in the title of the text box.
+    pub file: Option<PathBuf>,
+    pub flags: RegexFlags,
+    pub export_format: ExportFormat,
+}
+
+/// Regex options, toggled in the UI. Exported as inline flags (`(?ims)`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RegexFlags {
+    pub ignore_case: bool,
+    /// `^` and `$` match at the start/end of each line.
+    pub multi_line: bool,
+    /// `.` also matches `\n`.
+    pub dot_all: bool,
+}
+
+impl RegexFlags {
+    /// Inline flags, as used by Rust and PCRE (e.g.: `ims`). Empty if none is set.
+    pub fn letters(&self) -> String {
+        let mut letters = String::new();
+
+        if self.ignore_case {
+            letters.push('i');
+        }
+
+        if self.multi_line {
+            letters.push('m');
+        }
+
+        if self.dot_all {
+            letters.push('s');
+        }
+
+        letters
+    }
+}
+
+/// Syntax used to export the pattern.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// `Regex::new(r"(?i)pattern")`
+    Rust,
+    /// `/pattern/i`
+    Pcre,
+    /// `(?i)pattern`, ready for get-lines, lookup and csvn.
+    Plain,
+}
+
+impl ExportFormat {
+    pub fn next(&self) -> Self {
+        match self {
+            ExportFormat::Rust => ExportFormat::Pcre,
+            ExportFormat::Pcre => ExportFormat::Plain,
+            ExportFormat::Plain => ExportFormat::Rust,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Rust => "rust",
+            ExportFormat::Pcre => "pcre",
+            ExportFormat::Plain => "plain",
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::models::{ExportFormat, RegexFlags};
+use regex::{Regex, RegexBuilder};
+
+/// A match of the pattern and its capture groups. Positions are byte offsets in the text.
+#[derive(Debug, PartialEq)]
+pub struct MatchInfo {
+    pub start: usize,
+    pub end: usize,
+    pub groups: Vec<GroupInfo>,
+}
+
+/// A capture group of a match. `span` is `None` when the group didn't participate.
+#[derive(Debug, PartialEq)]
+pub struct GroupInfo {
+    pub index: usize,
+    pub name: Option<String>,
+    pub span: Option<(usize, usize)>,
+}
+
+/// Matches found in the text. With too many matches, only the first `limit` are kept, so the UI
+/// stays responsive on large files.
+pub struct MatchResult {
+    pub matches: Vec<MatchInfo>,
+    pub truncated: bool,
+}
+
+/// Compiles the pattern with the flags. The error is the message of the regex crate, which
+/// points to the invalid part of the pattern.
+pub fn compile(pattern: &str, flags: RegexFlags) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(flags.ignore_case)
+        .multi_line(flags.multi_line)
+        .dot_matches_new_line(flags.dot_all)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+pub fn find_matches(regex: &Regex, text: &str, limit: usize) -> MatchResult {
+    let names: Vec<Option<String>> = regex
+        .capture_names()
+        .map(|name| name.map(str::to_string))
+        .collect();
+
+    let mut matches = Vec::new();
+    let mut truncated = false;
+
+    for captures in regex.captures_iter(text) {
+        if matches.len() == limit {
+            truncated = true;
+            break;
+        }
+
+        let Some(whole) = captures.get(0) else {
+            continue;
+        };
+
+        let groups = (1..captures.len())
+            .map(|index| GroupInfo {
+                index,
+                name: names.get(index).cloned().flatten(),
+                span: captures
+                    .get(index)
+                    .map(|group| (group.start(), group.end())),
+            })
+            .collect();
+
+        matches.push(MatchInfo {
+            start: whole.start(),
+            end: whole.end(),
+            groups,
+        });
+    }
+
+    MatchResult { matches, truncated }
+}
+
+/// The pattern, with its flags, in the syntax of `format`.
+pub fn export_pattern(pattern: &str, flags: RegexFlags, format: ExportFormat) -> String {
+    let letters = flags.letters();
+    let inline = if letters.is_empty() {
+        pattern.to_string()
+    } else {
+        format!("(?{}){}", letters, pattern)
+    };
+
+    match format {
+        ExportFormat::Plain => inline,
+        ExportFormat::Rust => format!("Regex::new({})", rust_raw_string(&inline)),
+        ExportFormat::Pcre => format!("/{}/{}", escape_delimiter(pattern), letters),
+    }
+}
+
+/// Raw string literal with enough `#` so the pattern never closes it early.
+fn rust_raw_string(value: &str) -> String {
+    let mut hashes = 0;
+    while value.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+
+    let delimiter = "#".repeat(hashes);
+    format!("r{}\"{}\"{}", delimiter, value, delimiter)
+}
+
+/// Escapes the `/` that aren't escaped yet, since it's the PCRE delimiter.
+fn escape_delimiter(pattern: &str) -> String {
+    let mut escaped = String::with_capacity(pattern.len());
+    let mut previous_backslashes = 0;
+
+    for c in pattern.chars() {
+        if c == '/' && previous_backslashes % 2 == 0 {
+            escaped.push('\\');
+        }
+
+        previous_backslashes = if c == '\\' {
+            previous_backslashes + 1
+        } else {
+            0
+        };
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matches_with_groups() {
+        let regex = compile(r"(?P<key>\w+)=(\d+)?", RegexFlags::default()).unwrap();
+
+        let result = find_matches(&regex, "a=1 b=", 10);
+
+        assert!(!result.truncated);
+        assert_eq!(result.matches.len(), 2);
+        assert_eq!((result.matches[0].start, result.matches[0].end), (0, 3));
+        assert_eq!(result.matches[0].groups[0].name.as_deref(), Some("key"));
+        assert_eq!(result.matches[0].groups[1].span, Some((2, 3)));
+        assert_eq!(result.matches[1].groups[1].span, None);
+
+        let truncated = find_matches(&regex, "a=1 b=2 c=3", 2);
+        assert!(truncated.truncated);
+        assert_eq!(truncated.matches.len(), 2);
+    }
+
+    #[test]
+    fn test_compile_applies_flags() {
+        let flags = RegexFlags {
+            ignore_case: true,
+            ..Default::default()
+        };
+
+        assert!(compile("hello", flags).unwrap().is_match("HELLO"));
+        assert!(compile("(unclosed", flags).is_err());
+    }
+
+    #[test]
+    fn test_export_pattern() {
+        let flags = RegexFlags {
+            ignore_case: true,
+            multi_line: true,
+            dot_all: false,
+        };
+
+        assert_eq!(
+            export_pattern(r#"^say "\w+"$"#, flags, ExportFormat::Rust),
+            r##"Regex::new(r#"(?im)^say "\w+"$"#)"##
+        );
+        assert_eq!(
+            export_pattern(r"a/b\/c", flags, ExportFormat::Pcre),
+            r"/a\/b\/c/im"
+        );
+        assert_eq!(
+            export_pattern(r"\d+", RegexFlags::default(), ExportFormat::Plain),
+            r"\d+"
+        );
+    }
+}
@@ -0,0 +1,426 @@
+use crate::models::{ExportFormat, RegexFlags, RegexLabArgs};
+use crate::regex_engine::{compile, export_pattern, find_matches, MatchInfo, MatchResult};
+use crate::ui::text_input::TextInput;
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::path::PathBuf;
+use tracing::info;
+
+/// Matches kept for highlighting and listing. Past that, the count is shown as `5000+`.
+const MAX_MATCHES: usize = 5000;
+
+/// Background of the matches, alternated so adjacent matches can be told apart.
+const MATCH_COLORS: [Color; 2] = [Color::Yellow, Color::Cyan];
+
+const HELP: &str = "Tab: switch box | F2/F3/F4: toggle i/m/s | F5: export format | \
+    Ctrl+E: exit and print the pattern | Esc: exit";
+
+fn get_banner() -> String {
+    format!(
+        "{} | v{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Focus {
+    Pattern,
+    Text,
+}
+
+enum LabState {
+    Running,
+    /// Exits, with the exported pattern when the user asked for it.
+    Exit(Option<String>),
+}
+
+pub struct LabUi {
+    pattern: TextInput,
+    text: TextInput,
+    /// File the text was loaded from, shown in the title of the text box.
+    file: Option<PathBuf>,
+    flags: RegexFlags,
+    export_format: ExportFormat,
+    focus: Focus,
+    /// First line of the text shown in the text box.
+    text_scroll: usize,
+    /// Matches of the current pattern, or why it doesn't compile. Updated after each change.
+    result: Result<MatchResult, String>,
+}
+
+impl LabUi {
+    pub fn new(args: RegexLabArgs) -> Self {
+        // Starts where there's something to type.
+        let focus = if !args.pattern.is_empty() && args.text.is_empty() {
+            Focus::Text
+        } else {
+            Focus::Pattern
+        };
+
+        let mut lab_ui = Self {
+            pattern: TextInput::new(args.pattern),
+            text: TextInput::new(args.text),
+            file: args.file,
+            flags: args.flags,
+            export_format: args.export_format,
+            focus,
+            text_scroll: 0,
+            result: Ok(MatchResult {
+                matches: Vec::new(),
+                truncated: false,
+            }),
+        };
+        lab_ui.refresh();
+
+        lab_ui
+    }
+
+    /// Runs the UI until the user exits. Returns the exported pattern if the user exited with
+    /// Ctrl+E.
+    pub fn run(mut self) -> Result<Option<String>> {
+        info!("Starting regexlab UI...");
+        let mut terminal = ratatui::init();
+        let exported = self.lab_loop(&mut terminal);
+        ratatui::restore();
+        exported
+    }
+
+    fn lab_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<Option<String>> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                if let LabState::Exit(exported) = self.process_key(key) {
+                    return Ok(exported);
+                }
+            }
+        }
+    }
+
+    fn refresh(&mut self) {
+        let pattern = self.pattern.value();
+        self.result = if pattern.is_empty() {
+            // An empty pattern matches between every character, which is just noise.
+            Ok(MatchResult {
+                matches: Vec::new(),
+                truncated: false,
+            })
+        } else {
+            compile(pattern, self.flags)
+                .map(|regex| find_matches(&regex, self.text.value(), MAX_MATCHES))
+        };
+    }
+
+    fn exported_pattern(&self) -> String {
+        export_pattern(self.pattern.value(), self.flags, self.export_format)
+    }
+
+    //region: Ui Logic
+    fn process_key(&mut self, key: KeyEvent) -> LabState {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc => return LabState::Exit(None),
+            KeyCode::Char('c') if ctrl => return LabState::Exit(None),
+            // An invalid pattern is not exported: the error stays on screen instead.
+            KeyCode::Char('e')
+                if ctrl && self.result.is_ok() && !self.pattern.value().is_empty() =>
+            {
+                return LabState::Exit(Some(self.exported_pattern()));
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.focus = match self.focus {
+                    Focus::Pattern => Focus::Text,
+                    Focus::Text => Focus::Pattern,
+                };
+            }
+            KeyCode::F(2) => self.toggle_flag(|flags| &mut flags.ignore_case),
+            KeyCode::F(3) => self.toggle_flag(|flags| &mut flags.multi_line),
+            KeyCode::F(4) => self.toggle_flag(|flags| &mut flags.dot_all),
+            KeyCode::F(5) => self.export_format = self.export_format.next(),
+            KeyCode::Enter if self.focus == Focus::Text => self.edit(|input| input.insert('\n')),
+            KeyCode::Char(c) if !ctrl => self.edit(|input| input.insert(c)),
+            KeyCode::Backspace => self.edit(TextInput::backspace),
+            KeyCode::Delete => self.edit(TextInput::delete),
+            KeyCode::Left => self.focused_input().move_left(),
+            KeyCode::Right => self.focused_input().move_right(),
+            KeyCode::Up => self.focused_input().move_up(),
+            KeyCode::Down => self.focused_input().move_down(),
+            KeyCode::Home => self.focused_input().move_home(),
+            KeyCode::End => self.focused_input().move_end(),
+            _ => {}
+        }
+
+        LabState::Running
+    }
+
+    fn focused_input(&mut self) -> &mut TextInput {
+        match self.focus {
+            Focus::Pattern => &mut self.pattern,
+            Focus::Text => &mut self.text,
+        }
+    }
+
+    fn edit(&mut self, change: impl FnOnce(&mut TextInput)) {
+        change(self.focused_input());
+        self.refresh();
+    }
+
+    fn toggle_flag(&mut self, flag: impl FnOnce(&mut RegexFlags) -> &mut bool) {
+        let value = flag(&mut self.flags);
+        *value = !*value;
+        self.refresh();
+    }
+    //endregion: Ui Logic
+
+    //region: Ui
+    fn draw(&mut self, frame: &mut Frame) {
+        // Layout:
+        // [banner (1)] - app name, version, match count and flags
+        // [pattern (3)] - pattern input
+        // [text (flex) | matches (40%)] - sample text with the matches highlighted, and the
+        //                                 matches/capture groups from the cursor onward
+        // [export (1)] - the pattern in the selected export format
+        // [helper (1)] - keys
+        let vertical = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]);
+        let [banner_area, pattern_area, body_area, export_area, help_area] =
+            vertical.areas(frame.area());
+        let [text_area, matches_area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(body_area);
+
+        frame.render_widget(
+            Paragraph::new(self.banner_line()).style(Style::default().fg(Color::Cyan)),
+            banner_area,
+        );
+
+        self.draw_pattern(frame, pattern_area);
+        self.draw_text(frame, text_area);
+        self.draw_matches(frame, matches_area);
+
+        let export = Line::from(vec![
+            Span::styled(
+                format!("Export ({}): ", self.export_format.name()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(self.exported_pattern()),
+        ]);
+        frame.render_widget(Paragraph::new(export), export_area);
+
+        frame.render_widget(
+            Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+            help_area,
+        );
+    }
+
+    fn banner_line(&self) -> String {
+        let matches = match &self.result {
+            Ok(result) if result.truncated => format!("{}+ matches", MAX_MATCHES),
+            Ok(result) => format!("{} match(es)", result.matches.len()),
+            Err(_) => "invalid pattern".to_string(),
+        };
+        let flags = match self.flags.letters() {
+            letters if letters.is_empty() => "none".to_string(),
+            letters => letters,
+        };
+
+        format!("{} | {} | flags: {}", get_banner(), matches, flags)
+    }
+
+    fn box_block(&self, title: String, focus: Focus, color: Option<Color>) -> Block<'static> {
+        let color = match color {
+            Some(color) => color,
+            None if self.focus == focus => Color::Yellow,
+            None => Color::Reset,
+        };
+
+        Block::bordered()
+            .title(title)
+            .border_style(Style::default().fg(color))
+    }
+
+    fn draw_pattern(&self, frame: &mut Frame, area: Rect) {
+        let error_color = self.result.is_err().then_some(Color::Red);
+        let block = self.box_block("Pattern".to_string(), Focus::Pattern, error_color);
+        frame.render_widget(Paragraph::new(self.pattern.value()).block(block), area);
+
+        if self.focus == Focus::Pattern {
+            let (_, column) = self.pattern.cursor_position();
+            set_cursor(frame, area, column, 0);
+        }
+    }
+
+    fn draw_text(&mut self, frame: &mut Frame, area: Rect) {
+        let height = area.height.saturating_sub(2).max(1) as usize;
+        let (cursor_line, cursor_column) = self.text.cursor_position();
+
+        // Keeps the cursor visible.
+        if cursor_line < self.text_scroll {
+            self.text_scroll = cursor_line;
+        } else if cursor_line >= self.text_scroll + height {
+            self.text_scroll = cursor_line + 1 - height;
+        }
+
+        let matches: &[MatchInfo] = match &self.result {
+            Ok(result) => &result.matches,
+            Err(_) => &[],
+        };
+
+        let mut line_start = 0;
+        let mut lines = Vec::with_capacity(height);
+        for (index, line) in self.text.value().split('\n').enumerate() {
+            let line_end = line_start + line.len();
+            if index >= self.text_scroll {
+                lines.push(highlight_line(line, line_start, matches));
+            }
+
+            if lines.len() == height {
+                break;
+            }
+            line_start = line_end + 1;
+        }
+
+        let title = match &self.file {
+            Some(file) => format!("Text ({})", file.display()),
+            None => "Text".to_string(),
+        };
+        let block = self.box_block(title, Focus::Text, None);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+
+        if self.focus == Focus::Text {
+            set_cursor(frame, area, cursor_column, cursor_line - self.text_scroll);
+        }
+    }
+
+    fn draw_matches(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title("Matches");
+
+        let result = match &self.result {
+            Ok(result) => result,
+            Err(error) => {
+                let error = Paragraph::new(error.as_str())
+                    .style(Style::default().fg(Color::Red))
+                    .wrap(Wrap { trim: false })
+                    .block(block);
+                frame.render_widget(error, area);
+                return;
+            }
+        };
+
+        // Lists the matches from the one under (or after) the cursor, so moving through the text
+        // shows the groups of each match.
+        let text = self.text.value();
+        let cursor = self.text.cursor_byte();
+        let first = result.matches.partition_point(|m| m.end < cursor);
+        let height = area.height.saturating_sub(2) as usize;
+
+        let mut lines = Vec::with_capacity(height);
+        for (index, info) in result.matches.iter().enumerate().skip(first) {
+            if lines.len() >= height {
+                break;
+            }
+
+            let color = MATCH_COLORS[index % MATCH_COLORS.len()];
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("#{} ", index + 1),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    "{}..{} {:?}",
+                    info.start,
+                    info.end,
+                    &text[info.start..info.end]
+                )),
+            ]));
+
+            for group in &info.groups {
+                let name = match &group.name {
+                    Some(name) => format!("{} <{}>", group.index, name),
+                    None => group.index.to_string(),
+                };
+                let value = match group.span {
+                    Some((start, end)) => format!("{:?}", &text[start..end]),
+                    None => "(no match)".to_string(),
+                };
+                lines.push(Line::from(format!("   {}: {}", name, value)));
+            }
+        }
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+    //endregion: Ui
+}
+
+/// Splits a line of the text in spans, highlighting the parts inside matches. `line_start` is the
+/// byte offset of the line in the text. Tabs are shown as a single space, so the cursor column
+/// stays right.
+fn highlight_line<'a>(line: &str, line_start: usize, matches: &[MatchInfo]) -> Line<'a> {
+    let line_end = line_start + line.len();
+    let slice =
+        |start: usize, end: usize| line[start - line_start..end - line_start].replace('\t', " ");
+
+    let mut spans = Vec::new();
+    let mut position = line_start;
+    let first = matches.partition_point(|m| m.end <= line_start);
+
+    for (index, info) in matches.iter().enumerate().skip(first) {
+        if info.start >= line_end {
+            break;
+        }
+
+        // Empty matches have nothing to highlight.
+        if info.start == info.end {
+            continue;
+        }
+
+        let start = info.start.max(line_start);
+        let end = info.end.min(line_end);
+        if start > position {
+            spans.push(Span::raw(slice(position, start)));
+        }
+
+        let color = MATCH_COLORS[index % MATCH_COLORS.len()];
+        spans.push(Span::styled(
+            slice(start, end),
+            Style::default().fg(Color::Black).bg(color),
+        ));
+        position = end;
+    }
+
+    if position < line_end {
+        spans.push(Span::raw(slice(position, line_end)));
+    }
+
+    Line::from(spans)
+}
+
+/// Places the cursor inside a bordered box, if it fits.
+fn set_cursor(frame: &mut Frame, area: Rect, column: usize, line: usize) {
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let inner_height = area.height.saturating_sub(2) as usize;
+
+    if column < inner_width && line < inner_height {
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            area.x + 1 + column as u16,
+            area.y + 1 + line as u16,
+        ));
+    }
+}
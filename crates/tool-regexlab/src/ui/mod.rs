@@ -0,0 +1,2 @@
+pub mod lab_ui;
+pub mod text_input;
@@ -0,0 +1,157 @@
+/// Editable text and its cursor (a character index, not byte). Used by the pattern (single line)
+/// and by the sample text (multiple lines).
+#[derive(Default)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Starts with the cursor at the end of the value.
+    pub fn new(value: String) -> Self {
+        let cursor = value.chars().count();
+        Self { value, cursor }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Byte offset of the cursor in the value.
+    pub fn cursor_byte(&self) -> usize {
+        self.byte_index(self.cursor)
+    }
+
+    /// Line and column of the cursor, in characters.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let before = &self.value[..self.cursor_byte()];
+        let line = before.matches('\n').count();
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map_or(0, |current| current.chars().count());
+
+        (line, column)
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let index = self.cursor_byte();
+        self.value.insert(index, c);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.value.remove(self.cursor_byte());
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.value.chars().count() {
+            self.value.remove(self.cursor_byte());
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.value.chars().count());
+    }
+
+    /// Start of the current line.
+    pub fn move_home(&mut self) {
+        let (line, _) = self.cursor_position();
+        self.cursor = self.lines()[line].0;
+    }
+
+    /// End of the current line.
+    pub fn move_end(&mut self) {
+        let (line, _) = self.cursor_position();
+        let (start, length) = self.lines()[line];
+        self.cursor = start + length;
+    }
+
+    pub fn move_up(&mut self) {
+        let (line, column) = self.cursor_position();
+        if line == 0 {
+            self.cursor = 0;
+            return;
+        }
+
+        let (start, length) = self.lines()[line - 1];
+        self.cursor = start + column.min(length);
+    }
+
+    pub fn move_down(&mut self) {
+        let (line, column) = self.cursor_position();
+        let lines = self.lines();
+        match lines.get(line + 1) {
+            Some((start, length)) => self.cursor = start + column.min(*length),
+            None => self.move_end(),
+        }
+    }
+
+    /// Start (character index) and length of each line.
+    fn lines(&self) -> Vec<(usize, usize)> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        for line in self.value.split('\n') {
+            let length = line.chars().count();
+            lines.push((start, length));
+            start += length + 1;
+        }
+
+        lines
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .map(|(index, _)| index)
+            .nth(char_index)
+            .unwrap_or(self.value.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editing_multibyte_text() {
+        let mut input = TextInput::new("café".to_string());
+
+        input.backspace();
+        input.insert('é');
+        input.move_left();
+        input.insert('f');
+
+        assert_eq!(input.value(), "caffé");
+        assert_eq!(input.cursor_byte(), 4);
+    }
+
+    #[test]
+    fn test_moving_between_lines_keeps_the_column() {
+        let mut input = TextInput::new("first line\nab\nthird line".to_string());
+        assert_eq!(input.cursor_position(), (2, 10));
+
+        input.move_up();
+        assert_eq!(input.cursor_position(), (1, 2));
+
+        input.move_up();
+        assert_eq!(input.cursor_position(), (0, 2));
+
+        input.move_end();
+        input.move_down();
+        assert_eq!(input.cursor_position(), (1, 2));
+
+        input.move_home();
+        input.move_down();
+        input.move_down();
+        assert_eq!(input.cursor_position(), (2, 10));
+    }
+}
@@ -1,192 +1,291 @@
-use crate::models::{InputSource, OutputTarget, RemoveZwArgs};
-use anyhow::{anyhow, Result};
-use clap::{builder::NonEmptyStringValueParser, Arg, ArgAction, Command};
+use crate::models::{CharCategory, CleanOptions, InputSource, OutputTarget, RemoveZwArgs};
+use anyhow::{anyhow, bail, Result};
+use clap::{builder::NonEmptyStringValueParser, Arg, ArgAction, ArgMatches, Command};
 use shared::command_line::cli_builder::CommandExt;
-use shared::constants::general::DASH_LINE;
+use shared::command_line::tool_cli::{FlagAlias, GlobalArgs, GlobalFlag, ToolCli};
 use std::fs;
 use std::path::PathBuf;
 
-pub fn print_runtime_info(args: &RemoveZwArgs) {
-    println!("Remove-ZW v{}", env!("CARGO_PKG_VERSION"));
-    println!("{}", DASH_LINE);
+const FLAG_ALIASES: &[FlagAlias] = &[
+    FlagAlias::alias("-o", "--output"),
+    FlagAlias::deprecated("--no-header", "--quiet", "1.1.0"),
+    FlagAlias::deprecated("-n", "-q", "1.1.0"),
+];
 
-    println!("- Inputs:");
-    for input in &args.inputs {
-        match input {
-            InputSource::Stdin => println!("  - stdin"),
-            InputSource::File(path) => println!("  - {}", path.display()),
-            InputSource::Directory(path) => println!("  - {} (dir)", path.display()),
-        }
-    }
+impl ToolCli for RemoveZwArgs {
+    const DISPLAY_NAME: &'static str = "Remove-ZW";
 
-    println!("- Output:");
-    if args.in_place {
-        println!("  - In place");
-    } else if let Some(output) = &args.output {
-        match output {
-            OutputTarget::Stdout => println!("  - Stdout"),
-            OutputTarget::File(path) => println!("  - {}", path.display()),
-        }
-    } else if args
-        .inputs
-        .iter()
-        .all(|input| matches!(input, InputSource::Stdin))
-    {
-        println!("  - Stdout");
-    } else {
-        println!("  - Per-file cleaned output");
+    /// `--output` is the shared global flag. Use `-` for stdout.
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                "Remove zero-width and other invisible Unicode characters from text.",
+                "Removes Unicode format (Cf) characters from input text: zero-width characters, byte order marks, bidi \
+                controls, soft hyphens, and the rest. Use --classes/--keep to choose which, --spaces to replace \
+                non-breaking spaces, and --nfkc to normalize look-alike characters. With no FILE, or when FILE is -, \
+                read standard input.",
+            )
+            .preset_arg_verbose(None)
+            .arg(
+                Arg::new("in-place")
+                    .long("in-place")
+                    .action(ArgAction::SetTrue)
+                    .help("Overwrite input files in place (ignored for stdin)"),
+            )
+            .arg(
+                Arg::new("backup")
+                    .long("backup")
+                    .short('b')
+                    .action(ArgAction::SetTrue)
+                    .help("With --in-place: keep a copy of each changed file as <file>.bak"),
+            )
+            .arg(
+                Arg::new("report")
+                    .long("report")
+                    .action(ArgAction::SetTrue)
+                    .help("Print a summary of the files changed and the characters removed per category to stderr"),
+            )
+            .arg(
+                Arg::new("classes")
+                    .long("classes")
+                    .short('c')
+                    .value_name("CLASSES")
+                    .conflicts_with("keep")
+                    .help("Comma-separated classes to remove: zero-width, bom, bidi, soft-hyphen, other, all or none. (Default: all)"),
+            )
+            .arg(
+                Arg::new("keep")
+                    .long("keep")
+                    .short('k')
+                    .value_name("CLASSES")
+                    .help("Comma-separated classes to keep, removing the others (e.g. --keep bidi)"),
+            )
+            .arg(
+                Arg::new("spaces")
+                    .long("spaces")
+                    .action(ArgAction::SetTrue)
+                    .help("Replace non-breaking and other unusual spaces with a regular space"),
+            )
+            .arg(
+                Arg::new("nfkc")
+                    .long("nfkc")
+                    .action(ArgAction::SetTrue)
+                    .help("Apply NFKC normalization, turning look-alike characters (fullwidth letters, ligatures, ...) into plain ones"),
+            )
+            .arg(
+                Arg::new("recursive")
+                    .long("recursive")
+                    .short('r')
+                    .action(ArgAction::SetTrue)
+                    .help("When a directory is provided, process files recursively"),
+            )
+            .arg(
+                Arg::new("extensions")
+                    .long("extensions")
+                    .short('e')
+                    .value_name("EXTS")
+                    .value_parser(NonEmptyStringValueParser::new())
+                    .help("Comma-separated list of file extensions to include (e.g. txt,md,rs)"),
+            )
+            .arg(
+                Arg::new("files")
+                    .help("Files to process (reads from stdin if none or '-')")
+                    .action(ArgAction::Append)
+                    .num_args(0..),
+            )
     }
 
-    println!("- Verbose: {}", args.verbose);
-    println!("- Recursive: {}", args.recursive);
-    if args.extensions.is_empty() {
-        println!("- Extensions: (all)");
-    } else {
-        println!("- Extensions: {:?}", args.extensions);
+    fn global_flags() -> &'static [GlobalFlag] {
+        &[GlobalFlag::Quiet, GlobalFlag::NoColor, GlobalFlag::Output]
     }
-    println!();
-}
 
-pub fn get_cli_arguments() -> RemoveZwArgs {
-    let matches = Command::new(env!("CARGO_PKG_NAME"))
-        .add_basic_metadata(
-            env!("CARGO_PKG_VERSION"),
-            "Remove zero-width Unicode format characters from text.",
-            "Removes all Unicode format (Cf) characters from input text. With no FILE, or when FILE is -, read standard input.",
-        )
-        .preset_arg_verbose(None)
-        .arg(
-            Arg::new("no-header")
-                .long("no-header")
-                .short('n')
-                .action(ArgAction::SetTrue)
-                .help("Do not print header."),
-        )
-        .arg(
-            Arg::new("output")
-                .long("output")
-                .short('o')
-                .value_name("FILE")
-                .value_parser(NonEmptyStringValueParser::new())
-                .help("Write output to FILE instead of stdout (use '-' for stdout)"),
-        )
-        .arg(
-            Arg::new("in-place")
-                .long("in-place")
-                .action(ArgAction::SetTrue)
-                .help("Overwrite input files in place (ignored for stdin)"),
-        )
-        .arg(
-            Arg::new("recursive")
-                .long("recursive")
-                .short('r')
-                .action(ArgAction::SetTrue)
-                .help("When a directory is provided, process files recursively"),
-        )
-        .arg(
-            Arg::new("extensions")
-                .long("extensions")
-                .short('e')
-                .value_name("EXTS")
-                .value_parser(NonEmptyStringValueParser::new())
-                .help("Comma-separated list of file extensions to include (e.g. txt,md,rs)"),
-        )
-        .arg(
-            Arg::new("files")
-                .help("Files to process (reads from stdin if none or '-')")
-                .action(ArgAction::Append)
-                .num_args(0..),
-        )
-        .get_matches();
-
-    let inputs = matches
-        .get_many::<String>("files")
-        .unwrap_or_default()
-        .map(|value| map_input_value(value))
-        .collect::<Vec<_>>();
-
-    let inputs = if inputs.is_empty() {
-        vec![InputSource::Stdin]
-    } else {
-        inputs
-    };
-
-    let output = matches
-        .get_one::<String>("output")
-        .map(|value| value.as_str())
-        .map(parse_output_target);
-
-    let extensions = matches
-        .get_one::<String>("extensions")
-        .map(|value| parse_extensions(value))
-        .unwrap_or_default();
-
-    RemoveZwArgs {
-        inputs,
-        output,
-        in_place: matches.get_flag("in-place"),
-        recursive: matches.get_flag("recursive"),
-        extensions,
-        verbose: matches.get_flag("verbose"),
-        no_header: matches.get_flag("no-header"),
+    /// `--no-header`/`-n` became `--quiet`/`-q`, the same flag every tool uses.
+    fn flag_aliases() -> &'static [FlagAlias] {
+        FLAG_ALIASES
     }
-}
 
-pub fn validate_args(args: &RemoveZwArgs) -> Result<()> {
-    let stdin_count = args
-        .inputs
-        .iter()
-        .filter(|input| matches!(input, InputSource::Stdin))
-        .count();
+    fn from_matches(matches: &ArgMatches, global: &GlobalArgs) -> Result<Self> {
+        let inputs = matches
+            .get_many::<String>("files")
+            .unwrap_or_default()
+            .map(|value| map_input_value(value))
+            .collect::<Vec<_>>();
 
-    if stdin_count > 1 {
-        return Err(anyhow!("stdin can only be specified once"));
-    }
+        let inputs = if inputs.is_empty() {
+            vec![InputSource::Stdin]
+        } else {
+            inputs
+        };
 
-    let has_file_inputs = args
-        .inputs
-        .iter()
-        .any(|input| matches!(input, InputSource::File(_)));
+        let output = global
+            .output
+            .as_ref()
+            .map(|value| parse_output_target(&value.to_string_lossy()));
 
-    if args.in_place && !has_file_inputs {
-        return Err(anyhow!("--in-place requires at least one file input"));
-    }
+        let extensions = matches
+            .get_one::<String>("extensions")
+            .map(|value| parse_extensions(value))
+            .unwrap_or_default();
+
+        let classes = match (
+            matches.get_one::<String>("classes"),
+            matches.get_one::<String>("keep"),
+        ) {
+            (Some(classes), _) => parse_classes(classes)?,
+            (None, Some(keep)) => {
+                let keep = parse_classes(keep)?;
+                CharCategory::FORMAT
+                    .into_iter()
+                    .filter(|category| !keep.contains(category))
+                    .collect()
+            }
+            (None, None) => CharCategory::FORMAT.to_vec(),
+        };
 
-    if args.in_place && args.output.is_some() {
-        return Err(anyhow!("--in-place cannot be combined with --output"));
+        Ok(RemoveZwArgs {
+            inputs,
+            output,
+            in_place: matches.get_flag("in-place"),
+            backup: matches.get_flag("backup"),
+            recursive: matches.get_flag("recursive"),
+            extensions,
+            verbose: matches.get_flag("verbose"),
+            report: matches.get_flag("report"),
+            clean: CleanOptions {
+                classes,
+                normalize_spaces: matches.get_flag("spaces"),
+                nfkc: matches.get_flag("nfkc"),
+            },
+        })
     }
 
-    for input in &args.inputs {
-        if let InputSource::File(path) = input {
-            let metadata = fs::metadata(path)
-                .map_err(|_| anyhow!("Input file does not exist: {}", path.display()))?;
-            if !metadata.is_file() {
-                return Err(anyhow!("Input path is not a file: {}", path.display()));
+    fn validate(&self) -> Result<()> {
+        let stdin_count = self
+            .inputs
+            .iter()
+            .filter(|input| matches!(input, InputSource::Stdin))
+            .count();
+
+        if stdin_count > 1 {
+            return Err(anyhow!("stdin can only be specified once"));
+        }
+
+        let has_file_inputs = self
+            .inputs
+            .iter()
+            .any(|input| matches!(input, InputSource::File(_)));
+
+        if self.in_place && !has_file_inputs {
+            return Err(anyhow!("--in-place requires at least one file input"));
+        }
+
+        if self.clean.classes.is_empty() && !self.clean.normalize_spaces && !self.clean.nfkc {
+            bail!("Nothing to clean: select at least one class, --spaces or --nfkc");
+        }
+
+        if self.backup && !self.in_place {
+            return Err(anyhow!("--backup requires --in-place"));
+        }
+
+        if self.in_place && self.output.is_some() {
+            return Err(anyhow!("--in-place cannot be combined with --output"));
+        }
+
+        for input in &self.inputs {
+            if let InputSource::File(path) = input {
+                let metadata = fs::metadata(path)
+                    .map_err(|_| anyhow!("Input file does not exist: {}", path.display()))?;
+                if !metadata.is_file() {
+                    return Err(anyhow!("Input path is not a file: {}", path.display()));
+                }
+            }
+            if let InputSource::Directory(path) = input {
+                let metadata = fs::metadata(path)
+                    .map_err(|_| anyhow!("Input directory does not exist: {}", path.display()))?;
+                if !metadata.is_dir() {
+                    return Err(anyhow!("Input path is not a directory: {}", path.display()));
+                }
             }
         }
-        if let InputSource::Directory(path) = input {
-            let metadata = fs::metadata(path)
-                .map_err(|_| anyhow!("Input directory does not exist: {}", path.display()))?;
-            if !metadata.is_dir() {
-                return Err(anyhow!("Input path is not a directory: {}", path.display()));
+
+        if let Some(OutputTarget::File(_)) = self.output {
+            if self.inputs.len() > 1
+                || self
+                    .inputs
+                    .iter()
+                    .any(|i| matches!(i, InputSource::Directory(_)))
+            {
+                return Err(anyhow!(
+                    "--output FILE requires a single file input (use '-' for stdout)"
+                ));
             }
         }
+
+        Ok(())
     }
 
-    if let Some(OutputTarget::File(_)) = args.output {
-        if args.inputs.len() > 1
-            || args
-                .inputs
-                .iter()
-                .any(|i| matches!(i, InputSource::Directory(_)))
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| match input {
+                InputSource::Stdin => "stdin".to_string(),
+                InputSource::File(path) => path.display().to_string(),
+                InputSource::Directory(path) => format!("{} (dir)", path.display()),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let output = if self.in_place {
+            "In place".to_string()
+        } else if let Some(output) = &self.output {
+            match output {
+                OutputTarget::Stdout => "Stdout".to_string(),
+                OutputTarget::File(path) => path.display().to_string(),
+            }
+        } else if self
+            .inputs
+            .iter()
+            .all(|input| matches!(input, InputSource::Stdin))
         {
-            return Err(anyhow!(
-                "--output FILE requires a single file input (use '-' for stdout)"
-            ));
+            "Stdout".to_string()
+        } else {
+            "Per-file cleaned output".to_string()
+        };
+
+        let extensions = if self.extensions.is_empty() {
+            "(all)".to_string()
+        } else {
+            format!("{:?}", self.extensions)
+        };
+
+        let mut cleaning = if self.clean.classes.len() == CharCategory::FORMAT.len() {
+            vec!["all format chars".to_string()]
+        } else {
+            self.clean
+                .classes
+                .iter()
+                .map(|category| category.id().to_string())
+                .collect()
+        };
+        if self.clean.normalize_spaces {
+            cleaning.push("spaces".to_string());
+        }
+        if self.clean.nfkc {
+            cleaning.push("nfkc".to_string());
         }
-    }
 
-    Ok(())
+        vec![
+            ("Inputs".to_string(), inputs),
+            ("Cleaning".to_string(), cleaning.join(", ")),
+            ("Output".to_string(), output),
+            ("Backup".to_string(), self.backup.to_string()),
+            ("Verbose".to_string(), self.verbose.to_string()),
+            ("Recursive".to_string(), self.recursive.to_string()),
+            ("Extensions".to_string(), extensions),
+        ]
+    }
 }
 
 fn parse_output_target(value: &str) -> OutputTarget {
@@ -224,3 +323,99 @@ fn parse_extensions(value: &str) -> Vec<String> {
         .map(|ext| ext.trim_start_matches('.').to_lowercase())
         .collect()
 }
+
+/// `zero-width,bidi`, `all`, or `none`.
+fn parse_classes(value: &str) -> Result<Vec<CharCategory>> {
+    let mut classes = Vec::new();
+
+    for id in value.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        if id.eq_ignore_ascii_case("all") {
+            return Ok(CharCategory::FORMAT.to_vec());
+        }
+        if id.eq_ignore_ascii_case("none") {
+            continue;
+        }
+
+        let category = CharCategory::from_id(id).ok_or_else(|| {
+            anyhow!(
+                "Unknown character class: {} (use zero-width, bom, bidi, soft-hyphen, other, all or none)",
+                id
+            )
+        })?;
+        if !classes.contains(&category) {
+            classes.push(category);
+        }
+    }
+
+    Ok(classes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    #[test]
+    fn test_output_accepts_short_alias_and_stdout() {
+        let invocation =
+            try_parse_tool_cli_from::<RemoveZwArgs, _, _>(["remove-zw", "-o", "-"]).unwrap();
+
+        assert_eq!(invocation.args.output, Some(OutputTarget::Stdout));
+        assert_eq!(invocation.args.inputs, vec![InputSource::Stdin]);
+    }
+
+    #[test]
+    fn test_no_header_is_a_deprecated_alias_of_quiet() {
+        for flag in ["--no-header", "-n"] {
+            let invocation =
+                try_parse_tool_cli_from::<RemoveZwArgs, _, _>(["remove-zw", flag]).unwrap();
+
+            assert!(invocation.global.quiet, "{}", flag);
+        }
+    }
+
+    #[test]
+    fn test_backup_requires_in_place() {
+        let result = try_parse_tool_cli_from::<RemoveZwArgs, _, _>(["remove-zw", "--backup"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_classes_keep_and_normalization_flags() {
+        let parse = |argv: &[&str]| {
+            try_parse_tool_cli_from::<RemoveZwArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+        };
+
+        let default = parse(&["remove-zw"]).unwrap();
+        assert_eq!(default.clean, CleanOptions::default());
+
+        let classes = parse(&["remove-zw", "--classes", "bom, Bidi,bom"]).unwrap();
+        assert_eq!(
+            classes.clean.classes,
+            vec![CharCategory::ByteOrderMark, CharCategory::BidiControl]
+        );
+
+        let keep = parse(&["remove-zw", "-k", "bidi,soft-hyphen"]).unwrap();
+        assert_eq!(
+            keep.clean.classes,
+            vec![
+                CharCategory::ZeroWidth,
+                CharCategory::ByteOrderMark,
+                CharCategory::OtherFormat
+            ]
+        );
+
+        let only_spaces = parse(&["remove-zw", "--classes", "none", "--spaces", "--nfkc"]).unwrap();
+        assert!(only_spaces.clean.classes.is_empty());
+        assert!(only_spaces.clean.normalize_spaces && only_spaces.clean.nfkc);
+
+        assert!(parse(&["remove-zw", "--classes", "none"]).is_err());
+        assert!(parse(&["remove-zw", "--classes", "emoji"]).is_err());
+        assert!(parse(&["remove-zw", "--classes", "bom", "--keep", "bidi"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_extensions() {
+        assert_eq!(parse_extensions(" .TXT, md,,rs "), vec!["txt", "md", "rs"]);
+    }
+}
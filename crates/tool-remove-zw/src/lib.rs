@@ -0,0 +1,43 @@
+mod cli_utils;
+mod models;
+mod remove_zw_app;
+mod text_cleaner;
+
+use crate::models::RemoveZwArgs;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+/// Removes zero-width and other invisible characters from text.
+///
+/// Parses arguments, cleans the input, and writes the result.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<RemoveZwArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    invocation.print_runtime_info();
+    let args = invocation.args;
+
+    match remove_zw_app::run(&args) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(err) => {
+            eprintln!("{}: {}", env!("CARGO_PKG_NAME"), err);
+            error!("{}", err);
+            EXIT_CODE_ERROR
+        }
+    }
+}
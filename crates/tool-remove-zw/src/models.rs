@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -18,8 +19,167 @@ pub struct RemoveZwArgs {
     pub inputs: Vec<InputSource>,
     pub output: Option<OutputTarget>,
     pub in_place: bool,
+    pub backup: bool,
     pub recursive: bool,
     pub extensions: Vec<String>,
     pub verbose: bool,
-    pub no_header: bool,
+    pub report: bool,
+    pub clean: CleanOptions,
+}
+
+/// What gets cleaned. By default, every format character is removed, and nothing is normalized.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CleanOptions {
+    /// Format characters removed (`--classes`/`--keep`).
+    pub classes: Vec<CharCategory>,
+    /// Non-breaking and other unusual spaces become regular spaces (`--spaces`).
+    pub normalize_spaces: bool,
+    /// NFKC normalization (`--nfkc`).
+    pub nfkc: bool,
+}
+
+impl Default for CleanOptions {
+    fn default() -> Self {
+        Self {
+            classes: CharCategory::FORMAT.to_vec(),
+            normalize_spaces: false,
+            nfkc: false,
+        }
+    }
+}
+
+/// Kinds of characters cleaned, used to select what is removed and to break down the report.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CharCategory {
+    /// Zero-width space, joiners, and the word joiner (U+200B-U+200D, U+2060, U+180E).
+    ZeroWidth,
+    /// Byte order mark / zero-width no-break space (U+FEFF).
+    ByteOrderMark,
+    /// Direction marks, embeddings, overrides, and isolates (U+061C, U+200E-U+200F,
+    /// U+202A-U+202E, U+2066-U+2069).
+    BidiControl,
+    /// Soft hyphen (U+00AD).
+    SoftHyphen,
+    /// Any other format (Cf) character (e.g.: invisible math operators, tag characters).
+    OtherFormat,
+    /// Non-breaking and other unusual spaces (U+00A0, U+2000-U+200A, U+202F, U+205F, U+3000),
+    /// replaced with a regular space.
+    UnusualSpace,
+    /// Compatibility characters changed by NFKC normalization (fullwidth letters, ligatures,
+    /// superscripts, ...).
+    Compatibility,
+}
+
+impl CharCategory {
+    /// Categories of format (Cf) characters, selectable with `--classes` and `--keep`.
+    pub const FORMAT: [CharCategory; 5] = [
+        CharCategory::ZeroWidth,
+        CharCategory::ByteOrderMark,
+        CharCategory::BidiControl,
+        CharCategory::SoftHyphen,
+        CharCategory::OtherFormat,
+    ];
+
+    /// Category of a format (Cf) character.
+    pub fn of(c: char) -> Self {
+        match c {
+            '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{180E}' => CharCategory::ZeroWidth,
+            '\u{FEFF}' => CharCategory::ByteOrderMark,
+            '\u{061C}'
+            | '\u{200E}'
+            | '\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2066}'..='\u{2069}' => CharCategory::BidiControl,
+            '\u{00AD}' => CharCategory::SoftHyphen,
+            _ => CharCategory::OtherFormat,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CharCategory::ZeroWidth => "zero-width",
+            CharCategory::ByteOrderMark => "byte order mark",
+            CharCategory::BidiControl => "bidi control",
+            CharCategory::SoftHyphen => "soft hyphen",
+            CharCategory::OtherFormat => "other format",
+            CharCategory::UnusualSpace => "unusual space",
+            CharCategory::Compatibility => "compatibility (nfkc)",
+        }
+    }
+
+    /// Name used in `--classes` and `--keep`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            CharCategory::ZeroWidth => "zero-width",
+            CharCategory::ByteOrderMark => "bom",
+            CharCategory::BidiControl => "bidi",
+            CharCategory::SoftHyphen => "soft-hyphen",
+            CharCategory::OtherFormat => "other",
+            CharCategory::UnusualSpace => "spaces",
+            CharCategory::Compatibility => "nfkc",
+        }
+    }
+
+    /// Format category from its `--classes` name.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::FORMAT
+            .into_iter()
+            .find(|category| category.id().eq_ignore_ascii_case(id))
+    }
+}
+
+/// Characters removed (or replaced) in a text, per category.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RemovalCounts {
+    pub by_category: BTreeMap<CharCategory, usize>,
+}
+
+impl RemovalCounts {
+    pub fn add(&mut self, category: CharCategory) {
+        self.add_many(category, 1);
+    }
+
+    pub fn add_many(&mut self, category: CharCategory, count: usize) {
+        if count > 0 {
+            *self.by_category.entry(category).or_insert(0) += count;
+        }
+    }
+
+    pub fn merge(&mut self, other: &RemovalCounts) {
+        for (category, count) in &other.by_category {
+            *self.by_category.entry(*category).or_insert(0) += count;
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.by_category.values().sum()
+    }
+
+    /// `zero-width: 3, bidi control: 1`
+    pub fn describe(&self) -> String {
+        self.by_category
+            .iter()
+            .map(|(category, count)| format!("{}: {}", category.name(), count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Summary of a run, printed with `--report`.
+#[derive(Debug, Clone, Default)]
+pub struct CleanupReport {
+    pub files_scanned: usize,
+    pub files_changed: Vec<(String, RemovalCounts)>,
+    pub totals: RemovalCounts,
+}
+
+impl CleanupReport {
+    /// Records a processed input. Inputs without removals only count as scanned.
+    pub fn add(&mut self, source: impl Into<String>, removed: RemovalCounts) {
+        self.files_scanned += 1;
+        self.totals.merge(&removed);
+        if removed.total() > 0 {
+            self.files_changed.push((source.into(), removed));
+        }
+    }
 }
@@ -1,23 +1,26 @@
-use crate::models::{InputSource, OutputTarget, RemoveZwArgs};
+use crate::models::{CleanupReport, InputSource, OutputTarget, RemovalCounts, RemoveZwArgs};
+use crate::text_cleaner::clean_text;
 use anyhow::{anyhow, Context, Result};
-use once_cell::sync::Lazy;
-use regex::Regex;
 use shared::constants::general::SIZE_8KB;
+use shared::utils::table_output::Table;
 use std::borrow::Cow;
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 
-static FORMAT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\p{Cf}").unwrap());
+/// Extension of the copies kept by `--backup`.
+const BACKUP_EXTENSION: &str = "bak";
 
 pub fn run(args: &RemoveZwArgs) -> Result<()> {
     let expanded_inputs = expand_inputs(args)?;
+    let mut report = CleanupReport::default();
 
     for input in expanded_inputs {
         match input {
             InputSource::Stdin => {
                 let content = read_stdin().context("Failed to read from stdin")?;
-                process_and_write(args, &InputSource::Stdin, &content)?;
+                let removed = process_and_write(args, &InputSource::Stdin, &content)?;
+                report.add("stdin", removed);
             }
             InputSource::File(path) => {
                 if should_skip_by_extension(&path, &args.extensions) {
@@ -50,7 +53,9 @@ pub fn run(args: &RemoveZwArgs) -> Result<()> {
                     }
                 };
 
-                process_and_write(args, &InputSource::File(path), &content)?;
+                let label = path.display().to_string();
+                let removed = process_and_write(args, &InputSource::File(path), &content)?;
+                report.add(label, removed);
             }
             InputSource::Directory(_) => {
                 return Err(anyhow!(
@@ -60,39 +65,49 @@ pub fn run(args: &RemoveZwArgs) -> Result<()> {
         }
     }
 
+    if args.report {
+        eprint!("{}", render_report(&report));
+    }
+
     Ok(())
 }
 
-fn process_and_write(args: &RemoveZwArgs, input: &InputSource, content: &str) -> Result<()> {
+/// Cleans the content and writes it where the arguments say. Returns what was removed or replaced.
+fn process_and_write(
+    args: &RemoveZwArgs,
+    input: &InputSource,
+    content: &str,
+) -> Result<RemovalCounts> {
     let source_label = match input {
         InputSource::Stdin => "stdin".to_string(),
         InputSource::File(path) => path.display().to_string(),
         InputSource::Directory(path) => path.display().to_string(),
     };
 
-    let (cleaned, removed) = strip_format_chars(content);
+    let (cleaned, removed) = clean_text(content, &args.clean);
 
     if args.verbose {
         eprintln!(
-            "remove-zw: {} -> removed {} zero-width chars",
-            source_label, removed
+            "remove-zw: {} -> cleaned {} chars",
+            source_label,
+            removed.total()
         );
     }
 
-    if removed == 0 && should_skip_write(args, input) {
+    if removed.total() == 0 && should_skip_write(args, input) {
         if args.verbose {
             eprintln!(
                 "remove-zw: {} -> no changes, skipping output file",
                 source_label
             );
         }
-        return Ok(());
+        return Ok(removed);
     }
 
     write_output(args, input, cleaned.as_ref())
         .with_context(|| format!("Failed to write output for {}", source_label))?;
 
-    Ok(())
+    Ok(removed)
 }
 
 fn read_stdin() -> Result<String> {
@@ -106,7 +121,7 @@ fn read_stdin() -> Result<String> {
 fn write_output(args: &RemoveZwArgs, input: &InputSource, content: &str) -> Result<()> {
     if args.in_place {
         if let InputSource::File(path) = input {
-            return write_in_place(path, content);
+            return write_in_place(path, content, args.backup);
         }
         return Err(anyhow!("Cannot use --in-place with stdin"));
     }
@@ -143,7 +158,15 @@ fn write_file(path: &Path, content: &str) -> Result<()> {
     fs::write(path, content).with_context(|| format!("Failed to write file '{}'", path.display()))
 }
 
-fn write_in_place(path: &Path, content: &str) -> Result<()> {
+/// Replaces the file with the content. With `backup`, the original is copied to `<file>.bak`
+/// first (replacing an older backup).
+fn write_in_place(path: &Path, content: &str, backup: bool) -> Result<()> {
+    if backup {
+        let backup_path = build_backup_path(path);
+        fs::copy(path, &backup_path)
+            .with_context(|| format!("Failed to write backup file '{}'", backup_path.display()))?;
+    }
+
     let temp_path = build_temp_path(path);
 
     fs::write(&temp_path, content)
@@ -175,6 +198,14 @@ fn build_temp_path(path: &Path) -> PathBuf {
     path.with_file_name(temp_name)
 }
 
+fn build_backup_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_else(|| Cow::Borrowed("output"));
+    path.with_file_name(format!("{}.{}", file_name, BACKUP_EXTENSION))
+}
+
 fn build_output_path(path: &Path) -> PathBuf {
     let stem = path
         .file_stem()
@@ -189,14 +220,34 @@ fn build_output_path(path: &Path) -> PathBuf {
     path.with_file_name(new_name)
 }
 
-fn strip_format_chars(input: &str) -> (Cow<'_, str>, usize) {
-    let removed = FORMAT_RE.find_iter(input).count();
-    if removed == 0 {
-        return (Cow::Borrowed(input), 0);
+/// Files changed (with what was cleaned in each), and the totals per category.
+fn render_report(report: &CleanupReport) -> String {
+    let mut files = Table::new(&["FILE", "CHARS", "CATEGORIES"])
+        .with_title("Files changed")
+        .align_right(1);
+    for (file, removed) in &report.files_changed {
+        files.add_row(vec![
+            file.clone(),
+            removed.total().to_string(),
+            removed.describe(),
+        ]);
+    }
+
+    let mut categories = Table::new(&["CATEGORY", "CHARS"])
+        .with_title("Cleaned per category")
+        .align_right(1);
+    for (category, count) in &report.totals.by_category {
+        categories.add_row(vec![category.name().to_string(), count.to_string()]);
     }
 
-    let cleaned = FORMAT_RE.replace_all(input, "");
-    (cleaned, removed)
+    format!(
+        "\n{}\n{}\nScanned {} file(s), changed {}, cleaned {} character(s).\n",
+        files.render_text(),
+        categories.render_text(),
+        report.files_scanned,
+        report.files_changed.len(),
+        report.totals.total()
+    )
 }
 
 fn should_skip_write(args: &RemoveZwArgs, input: &InputSource) -> bool {
@@ -219,7 +270,8 @@ fn expand_inputs(args: &RemoveZwArgs) -> Result<Vec<InputSource>> {
             InputSource::Stdin => expanded.push(InputSource::Stdin),
             InputSource::File(path) => expanded.push(InputSource::File(path.clone())),
             InputSource::Directory(path) => {
-                let files = collect_files_in_dir(path, args.recursive, &args.extensions)?;
+                let files =
+                    collect_files_in_dir(path, args.recursive, &args.extensions, args.backup)?;
                 for file in files {
                     expanded.push(InputSource::File(file));
                 }
@@ -234,6 +286,7 @@ fn collect_files_in_dir(
     root: &Path,
     recursive: bool,
     extensions: &[String],
+    skip_backups: bool,
 ) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
     let mut stack = vec![root.to_path_buf()];
@@ -253,7 +306,10 @@ fn collect_files_in_dir(
                 continue;
             }
 
-            if file_type.is_file() && !should_skip_by_extension(&path, extensions) {
+            if file_type.is_file()
+                && !(skip_backups && is_backup_file(&path))
+                && !should_skip_by_extension(&path, extensions)
+            {
                 files.push(path);
             }
         }
@@ -262,6 +318,12 @@ fn collect_files_in_dir(
     Ok(files)
 }
 
+/// With `--backup`, the backups of earlier runs aren't cleaned again (nor backed up themselves).
+fn is_backup_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(BACKUP_EXTENSION))
+}
+
 fn should_skip_by_extension(path: &Path, extensions: &[String]) -> bool {
     if extensions.is_empty() {
         return false;
@@ -292,7 +354,7 @@ fn is_likely_binary(path: &Path) -> Result<bool> {
     }
 
     let sample = &buffer[..read];
-    if sample.iter().any(|byte| *byte == 0) {
+    if sample.contains(&0) {
         return Ok(true);
     }
 
@@ -304,19 +366,39 @@ mod tests {
     use super::*;
 
     #[test]
-    fn removes_format_chars() {
-        let input = "hello\u{200B}world\u{200C}";
-        let (cleaned, removed) = strip_format_chars(input);
-        assert_eq!(cleaned, "helloworld");
-        assert_eq!(removed, 2);
+    fn writes_in_place_with_backup() {
+        let folder = std::env::temp_dir().join(format!("remove-zw-test-{}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        let file = folder.join("notes.txt");
+        fs::write(&file, "a\u{200B}b").unwrap();
+
+        write_in_place(&file, "ab", true).unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "ab");
+        assert_eq!(
+            fs::read_to_string(folder.join("notes.txt.bak")).unwrap(),
+            "a\u{200B}b"
+        );
+        assert!(is_backup_file(&folder.join("notes.txt.bak")));
+
+        fs::remove_dir_all(&folder).unwrap();
     }
 
     #[test]
-    fn leaves_non_format_chars() {
-        let input = "hello world";
-        let (cleaned, removed) = strip_format_chars(input);
-        assert_eq!(cleaned, "hello world");
-        assert_eq!(removed, 0);
+    fn skips_backup_files_only_with_backup() {
+        let folder =
+            std::env::temp_dir().join(format!("remove-zw-scan-test-{}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("notes.txt"), "a").unwrap();
+        fs::write(folder.join("notes.txt.bak"), "a").unwrap();
+
+        let without_backup = collect_files_in_dir(&folder, false, &[], false).unwrap();
+        let with_backup = collect_files_in_dir(&folder, false, &[], true).unwrap();
+
+        assert_eq!(without_backup.len(), 2);
+        assert_eq!(with_backup, vec![folder.join("notes.txt")]);
+
+        fs::remove_dir_all(&folder).unwrap();
     }
 
     #[test]
@@ -0,0 +1,186 @@
+use crate::models::{CharCategory, CleanOptions, RemovalCounts};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use unicode_normalization::{is_nfkc_quick, IsNormalized, UnicodeNormalization};
+
+static FORMAT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\p{Cf}").unwrap());
+
+/// Cleans the text as the options say, in this order: removes the selected format characters,
+/// replaces unusual spaces with a regular space, and applies NFKC normalization.
+///
+/// Returns the input unchanged (borrowed) when there was nothing to clean.
+pub fn clean_text<'a>(input: &'a str, options: &CleanOptions) -> (Cow<'a, str>, RemovalCounts) {
+    let mut removed = RemovalCounts::default();
+    let mut text = remove_format_chars(input, &options.classes, &mut removed);
+
+    if options.normalize_spaces {
+        if let Some(normalized) = normalize_spaces(&text, &mut removed) {
+            text = Cow::Owned(normalized);
+        }
+    }
+
+    if options.nfkc {
+        if let Some(normalized) = normalize_compatibility(&text, &mut removed) {
+            text = Cow::Owned(normalized);
+        }
+    }
+
+    if removed.total() == 0 {
+        return (Cow::Borrowed(input), removed);
+    }
+
+    (text, removed)
+}
+
+fn remove_format_chars<'a>(
+    input: &'a str,
+    classes: &[CharCategory],
+    removed: &mut RemovalCounts,
+) -> Cow<'a, str> {
+    if classes.is_empty() {
+        return Cow::Borrowed(input);
+    }
+
+    FORMAT_RE.replace_all(input, |caps: &Captures| {
+        let found = &caps[0];
+        let selected = found
+            .chars()
+            .next()
+            .map(CharCategory::of)
+            .filter(|category| classes.contains(category));
+
+        match selected {
+            Some(category) => {
+                removed.add(category);
+                String::new()
+            }
+            None => found.to_string(),
+        }
+    })
+}
+
+/// Non-breaking and other fixed-width spaces that look like a regular space, but don't match it
+/// (in searches, diffs, or when splitting words).
+fn is_unusual_space(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}'
+    )
+}
+
+fn normalize_spaces(text: &str, removed: &mut RemovalCounts) -> Option<String> {
+    let count = text.chars().filter(|c| is_unusual_space(*c)).count();
+    if count == 0 {
+        return None;
+    }
+
+    removed.add_many(CharCategory::UnusualSpace, count);
+    Some(
+        text.chars()
+            .map(|c| if is_unusual_space(c) { ' ' } else { c })
+            .collect(),
+    )
+}
+
+/// Characters with a compatibility decomposition: fullwidth letters, ligatures, superscripts,
+/// math alphanumerics, and other look-alikes of plain characters.
+fn is_compatibility_char(c: char) -> bool {
+    !c.is_ascii() && c.nfkd().ne(c.nfd())
+}
+
+/// NFKC normalization. Only compatibility characters are counted: canonical recompositions
+/// (e.g.: `e` + combining acute into `é`) don't change how the text looks.
+fn normalize_compatibility(text: &str, removed: &mut RemovalCounts) -> Option<String> {
+    if is_nfkc_quick(text.chars()) == IsNormalized::Yes {
+        return None;
+    }
+
+    let normalized: String = text.nfkc().collect();
+    if normalized == text {
+        return None;
+    }
+
+    let count = text.chars().filter(|c| is_compatibility_char(*c)).count();
+    removed.add_many(CharCategory::Compatibility, count);
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean(input: &str, options: &CleanOptions) -> (String, String) {
+        let (cleaned, removed) = clean_text(input, options);
+        (cleaned.into_owned(), removed.describe())
+    }
+
+    #[test]
+    fn removes_format_chars() {
+        let input = "hello\u{200B}world\u{200C}";
+        let (cleaned, removed) = clean_text(input, &CleanOptions::default());
+        assert_eq!(cleaned, "helloworld");
+        assert_eq!(removed.total(), 2);
+    }
+
+    #[test]
+    fn leaves_non_format_chars() {
+        let input = "hello world";
+        let (cleaned, removed) = clean_text(input, &CleanOptions::default());
+        assert!(matches!(cleaned, Cow::Borrowed("hello world")));
+        assert_eq!(removed.total(), 0);
+    }
+
+    #[test]
+    fn counts_removed_chars_per_category() {
+        let input = "\u{FEFF}name\u{200B}\u{200D}\u{202E}txt\u{00AD}";
+        let (cleaned, removed) = clean(input, &CleanOptions::default());
+        assert_eq!(cleaned, "nametxt");
+        assert_eq!(
+            removed,
+            "zero-width: 2, byte order mark: 1, bidi control: 1, soft hyphen: 1"
+        );
+    }
+
+    #[test]
+    fn only_removes_selected_classes() {
+        let options = CleanOptions {
+            classes: vec![CharCategory::BidiControl],
+            ..CleanOptions::default()
+        };
+        let (cleaned, removed) = clean("\u{FEFF}a\u{202E}b\u{200B}", &options);
+        assert_eq!(cleaned, "\u{FEFF}ab\u{200B}");
+        assert_eq!(removed, "bidi control: 1");
+
+        let options = CleanOptions {
+            classes: Vec::new(),
+            ..CleanOptions::default()
+        };
+        let (cleaned, removed) = clean("a\u{200B}b", &options);
+        assert_eq!(cleaned, "a\u{200B}b");
+        assert_eq!(removed, "");
+    }
+
+    #[test]
+    fn normalizes_spaces_and_compatibility_chars() {
+        let options = CleanOptions {
+            normalize_spaces: true,
+            nfkc: true,
+            ..CleanOptions::default()
+        };
+        let (cleaned, removed) = clean(
+            "\u{FF21}\u{FF24}\u{FF2D}\u{FF29}\u{FF2E}\u{00A0}of\u{FB01}ce\u{200B} caf\u{00E9}",
+            &options,
+        );
+        assert_eq!(cleaned, "ADMIN office café");
+        assert_eq!(
+            removed,
+            "zero-width: 1, unusual space: 1, compatibility (nfkc): 6"
+        );
+
+        // Canonical recomposition alone isn't counted as a change.
+        let (cleaned, removed) = clean("cafe\u{0301}", &options);
+        assert_eq!(cleaned, "cafe\u{0301}");
+        assert_eq!(removed, "");
+    }
+}
@@ -0,0 +1,212 @@
+use std::ffi::OsString;
+use std::path::Path;
+
+/// Name of the launcher binary. Started under any other name, it runs the tool of that name.
+const LAUNCHER_NAME: &str = "rtb";
+
+/// A tool compiled into the launcher, with its `run(argv)` entry point. `run` returns the exit
+/// code of the tool.
+#[derive(Debug)]
+pub struct EmbeddedTool {
+    pub name: &'static str,
+    pub entry: fn(Vec<OsString>) -> i32,
+}
+
+const fn embedded(name: &'static str, entry: fn(Vec<OsString>) -> i32) -> EmbeddedTool {
+    EmbeddedTool { name, entry }
+}
+
+/// Tools compiled into the launcher, with their `run(argv)` entry points.
+pub const EMBEDDED_TOOLS: [EmbeddedTool; 38] = [
+    embedded("ai-chatbot", ai_chatbot::run::<Vec<OsString>, OsString>),
+    embedded("aiignore", aiignore::run::<Vec<OsString>, OsString>),
+    embedded("archive", archive::run::<Vec<OsString>, OsString>),
+    embedded("b64", b64::run::<Vec<OsString>, OsString>),
+    embedded("cat", cat::run::<Vec<OsString>, OsString>),
+    embedded("clip", clip::run::<Vec<OsString>, OsString>),
+    embedded("csvn", csvn::run::<Vec<OsString>, OsString>),
+    embedded("dirdiff", dirdiff::run::<Vec<OsString>, OsString>),
+    embedded("distro-cc", distro_cc::run::<Vec<OsString>, OsString>),
+    embedded("eh-export", eh_export::run::<Vec<OsString>, OsString>),
+    embedded("eh-read", eh_read::run::<Vec<OsString>, OsString>),
+    embedded("eh-send", eh_send::run::<Vec<OsString>, OsString>),
+    embedded("get-lines", get_lines::run::<Vec<OsString>, OsString>),
+    embedded("gitignore", gitignore::run::<Vec<OsString>, OsString>),
+    embedded("gitstat", gitstat::run::<Vec<OsString>, OsString>),
+    embedded("guid", guid::run::<Vec<OsString>, OsString>),
+    embedded("hashx", hashx::run::<Vec<OsString>, OsString>),
+    embedded("how", how::run::<Vec<OsString>, OsString>),
+    embedded("http", http::run::<Vec<OsString>, OsString>),
+    embedded("imgx", imgx::run::<Vec<OsString>, OsString>),
+    embedded("jwt", jwt::run::<Vec<OsString>, OsString>),
+    embedded("keyvault", keyvault::run::<Vec<OsString>, OsString>),
+    embedded("lookup", lookup::run::<Vec<OsString>, OsString>),
+    embedded("mock", mock::run::<Vec<OsString>, OsString>),
+    embedded("mqtt", mqtt::run::<Vec<OsString>, OsString>),
+    embedded("netcatx", netcatx::run::<Vec<OsString>, OsString>),
+    embedded("netquality", netquality::run::<Vec<OsString>, OsString>),
+    embedded("pingx", pingx::run::<Vec<OsString>, OsString>),
+    embedded("qrcode", qrcode::run::<Vec<OsString>, OsString>),
+    embedded("regexlab", regexlab::run::<Vec<OsString>, OsString>),
+    embedded("remove-zw", remove_zw::run::<Vec<OsString>, OsString>),
+    embedded("split", split::run::<Vec<OsString>, OsString>),
+    embedded(
+        "toolbox-update",
+        toolbox_update::run::<Vec<OsString>, OsString>,
+    ),
+    embedded("touch", touch::run::<Vec<OsString>, OsString>),
+    embedded("ts", ts::run::<Vec<OsString>, OsString>),
+    embedded("unitconv", unitconv::run::<Vec<OsString>, OsString>),
+    embedded("whisper", whisper::run::<Vec<OsString>, OsString>),
+    embedded("whurl", whurl::run::<Vec<OsString>, OsString>),
+];
+
+/// What the launcher was asked to do.
+#[derive(Debug)]
+pub enum Dispatch {
+    /// Run an embedded tool. `argv[0]` is the tool name.
+    Embedded(&'static EmbeddedTool, Vec<OsString>),
+    Help,
+    Version,
+    List,
+    Unknown(String),
+}
+
+/// Picks the tool to run from the name of the binary (`jwt` linked to `rtb`) or from the first
+/// argument (`rtb jwt ...`).
+pub fn resolve_dispatch(argv: Vec<OsString>) -> Dispatch {
+    let program = argv
+        .first()
+        .map(|arg0| program_name(Path::new(arg0)))
+        .unwrap_or_default();
+
+    if program != LAUNCHER_NAME {
+        if let Some(dispatch) = dispatch_tool(&program, argv.clone()) {
+            return dispatch;
+        }
+    }
+
+    let Some(first) = argv.get(1).map(|arg| arg.to_string_lossy().to_string()) else {
+        return Dispatch::Help;
+    };
+
+    match first.as_str() {
+        "-h" | "--help" | "help" => Dispatch::Help,
+        "-V" | "--version" => Dispatch::Version,
+        "-l" | "--list" => Dispatch::List,
+        tool => dispatch_tool(tool, argv[1..].to_vec())
+            .unwrap_or_else(|| Dispatch::Unknown(tool.to_string())),
+    }
+}
+
+pub fn print_help() {
+    println!("rtb v{}", env!("CARGO_PKG_VERSION"));
+    println!("{}", env!("CARGO_PKG_DESCRIPTION"));
+    println!();
+    println!("Usage: rtb <TOOL> [ARGS]...");
+    println!("       <TOOL> [ARGS]...    (with a link named after the tool pointing to rtb)");
+    println!();
+    println!("Options:");
+    println!("  -l, --list       Lists the tools");
+    println!("  -h, --help       Prints help (use 'rtb <TOOL> --help' for the help of a tool)");
+    println!("  -V, --version    Prints version");
+    println!();
+    print_tool_list();
+}
+
+pub fn print_tool_list() {
+    println!("Tools:");
+    for tool in &EMBEDDED_TOOLS {
+        println!("  {}", tool.name);
+    }
+}
+
+fn dispatch_tool(name: &str, argv: Vec<OsString>) -> Option<Dispatch> {
+    EMBEDDED_TOOLS
+        .iter()
+        .find(|tool| tool.name == name)
+        .map(|tool| Dispatch::Embedded(tool, with_program_name(argv, tool.name)))
+}
+
+/// Makes `argv[0]` the plain tool name, so the tools print their own name in help and errors.
+fn with_program_name(mut argv: Vec<OsString>, name: &str) -> Vec<OsString> {
+    match argv.first_mut() {
+        Some(arg0) => *arg0 = OsString::from(name),
+        None => argv.push(OsString::from(name)),
+    }
+    argv
+}
+
+/// File name without folder and extension (`/usr/local/bin/jwt.exe` -> `jwt`).
+fn program_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_dispatch_by_first_argument() {
+        match resolve_dispatch(argv(&["/usr/bin/rtb", "jwt", "--print", "json"])) {
+            Dispatch::Embedded(tool, args) => {
+                assert_eq!(tool.name, "jwt");
+                assert_eq!(args, argv(&["jwt", "--print", "json"]));
+            }
+            other => panic!("unexpected dispatch: {:?}", other),
+        }
+
+        match resolve_dispatch(argv(&["rtb.exe", "how", "-a", "list files"])) {
+            Dispatch::Embedded(tool, args) => {
+                assert_eq!(tool.name, "how");
+                assert_eq!(args, argv(&["how", "-a", "list files"]));
+            }
+            other => panic!("unexpected dispatch: {:?}", other),
+        }
+
+        assert!(matches!(resolve_dispatch(argv(&["rtb"])), Dispatch::Help));
+        assert!(matches!(
+            resolve_dispatch(argv(&["rtb", "--version"])),
+            Dispatch::Version
+        ));
+        assert!(matches!(
+            resolve_dispatch(argv(&["rtb", "nope"])),
+            Dispatch::Unknown(name) if name == "nope"
+        ));
+    }
+
+    #[test]
+    fn test_dispatch_by_binary_name() {
+        match resolve_dispatch(argv(&["/home/me/bin/guid", "-c", "3"])) {
+            Dispatch::Embedded(tool, args) => {
+                assert_eq!(tool.name, "guid");
+                assert_eq!(args, argv(&["guid", "-c", "3"]));
+            }
+            other => panic!("unexpected dispatch: {:?}", other),
+        }
+
+        // Renamed launchers fall back to the first argument.
+        assert!(matches!(
+            resolve_dispatch(argv(&["my-rtb", "ts", "0"])),
+            Dispatch::Embedded(tool, _) if tool.name == "ts"
+        ));
+    }
+
+    #[test]
+    fn test_embedded_tools_return_their_exit_code() {
+        let run = |args: &[&str]| match resolve_dispatch(argv(args)) {
+            Dispatch::Embedded(tool, args) => (tool.entry)(args),
+            other => panic!("unexpected dispatch: {:?}", other),
+        };
+
+        assert_eq!(run(&["rtb", "unitconv", "--quiet", "1 KiB to B"]), 0);
+        assert_eq!(run(&["rtb", "ts", "--version"]), 0);
+        assert_eq!(run(&["rtb", "ts", "--no-such-flag"]), 2);
+    }
+}
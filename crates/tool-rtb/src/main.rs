@@ -0,0 +1,37 @@
+use crate::launcher::{print_help, print_tool_list, resolve_dispatch, Dispatch};
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::system::tool_exit_helpers::exit_with_code;
+use std::env;
+
+mod launcher;
+
+/// Multicall launcher.
+///
+/// Runs the tool named by the binary (when started through a link named after the tool) or by
+/// the first argument, and exits with its exit code.
+fn main() {
+    let exit_code = match resolve_dispatch(env::args_os().collect()) {
+        Dispatch::Embedded(tool, argv) => (tool.entry)(argv),
+        Dispatch::Help => {
+            print_help();
+            EXIT_CODE_SUCCESS
+        }
+        Dispatch::Version => {
+            println!("rtb {}", env!("CARGO_PKG_VERSION"));
+            EXIT_CODE_SUCCESS
+        }
+        Dispatch::List => {
+            print_tool_list();
+            EXIT_CODE_SUCCESS
+        }
+        Dispatch::Unknown(name) => {
+            eprintln!(
+                "rtb: '{}' is not a toolbox tool. Use 'rtb --list' to see them all.",
+                name
+            );
+            EXIT_CODE_ERROR
+        }
+    };
+
+    exit_with_code(exit_code);
+}
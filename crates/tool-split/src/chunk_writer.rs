@@ -0,0 +1,126 @@
+use anyhow::{anyhow, Context, Result};
+use shared::constants::general::SIZE_64KB;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+/// Placeholder of the `--exec` command replaced by the chunk name.
+const CHUNK_NAME_PLACEHOLDER: &str = "{}";
+
+/// Destination of the lines of one chunk: a file in the output directory, or the stdin of the
+/// `--exec` command, which receives the lines as they are read.
+pub enum ChunkWriter {
+    File(BufWriter<File>),
+    Command {
+        child: Child,
+        stdin: BufWriter<ChildStdin>,
+        command: String,
+    },
+}
+
+impl ChunkWriter {
+    /// Creates the output file of a chunk.
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be created
+    pub fn create_file(path: &Path) -> Result<Self> {
+        let file = File::create(path).context(format!(
+            "Failed to create output file: [{}]",
+            path.display()
+        ))?;
+
+        // Use BufWriter with a large buffer (64KB) for better write performance
+        Ok(ChunkWriter::File(BufWriter::with_capacity(SIZE_64KB, file)))
+    }
+
+    /// Starts the `--exec` command for a chunk, through the system shell.
+    ///
+    /// `{}` in the command is replaced by the chunk name, which is also available (with the chunk
+    /// number) in the `SPLIT_CHUNK_NAME` and `SPLIT_CHUNK_NUMBER` environment variables.
+    ///
+    /// # Errors
+    /// Returns error if the command cannot be started
+    pub fn spawn_command(template: &str, chunk_name: &str, chunk_number: i32) -> Result<Self> {
+        let command = template.replace(CHUNK_NAME_PLACEHOLDER, chunk_name);
+
+        let mut child = shell_command(&command)
+            .env("SPLIT_CHUNK_NAME", chunk_name)
+            .env("SPLIT_CHUNK_NUMBER", chunk_number.to_string())
+            .stdin(Stdio::piped())
+            .spawn()
+            .context(format!("Failed to start command: [{}]", command))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("Failed to open the stdin of command: [{}]", command))?;
+
+        Ok(ChunkWriter::Command {
+            child,
+            stdin: BufWriter::with_capacity(SIZE_64KB, stdin),
+            command,
+        })
+    }
+
+    /// Flushes the chunk. For commands, closes their stdin and waits for them to exit.
+    ///
+    /// # Errors
+    /// Returns error if the data cannot be flushed, or if the command fails
+    pub fn finish(self) -> Result<()> {
+        match self {
+            ChunkWriter::File(mut writer) => writer.flush().context("Failed to flush output file"),
+            ChunkWriter::Command {
+                mut child,
+                stdin,
+                command,
+            } => {
+                // Dropping stdin sends EOF, so the command knows the chunk is complete.
+                let flushed = stdin.into_inner().map(drop).map_err(|e| e.into_error());
+
+                let status = child
+                    .wait()
+                    .context(format!("Failed to wait for command: [{}]", command))?;
+
+                if !status.success() {
+                    return Err(anyhow!("Command [{}] failed ({})", command, status));
+                }
+
+                flushed.context(format!("Failed to send data to command: [{}]", command))
+            }
+        }
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            ChunkWriter::File(writer) => writer.write(buf),
+            ChunkWriter::Command { stdin, .. } => stdin.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            ChunkWriter::File(writer) => writer.flush(),
+            ChunkWriter::Command { stdin, .. } => stdin.flush(),
+        }
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        shell
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
+        let mut shell = Command::new(shell);
+        shell.arg("-c").arg(command);
+        shell
+    }
+}
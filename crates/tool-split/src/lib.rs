@@ -0,0 +1,43 @@
+use crate::models::SplitArgs;
+use crate::split_app::process_input_file;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::system::setup_graceful_shutdown::setup_graceful_shutdown;
+use std::ffi::OsString;
+use tracing::error;
+
+mod chunk_writer;
+mod cli_utils;
+mod models;
+mod split_app;
+
+/// File splitting tool with graceful shutdown support.
+///
+/// Parses CLI arguments, validates configuration, and processes input file.
+/// Sets up signal handling for graceful termination during processing.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<SplitArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    invocation.print_runtime_info();
+    let args = invocation.args;
+
+    let shutdown_signal = setup_graceful_shutdown(false);
+
+    match process_input_file(&args, shutdown_signal) {
+        Ok(_) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            error!("Error splitting input file: {}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
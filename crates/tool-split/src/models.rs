@@ -2,11 +2,22 @@
 ///
 /// Contains input file, output directory, line count limits, and CSV mode settings.
 pub struct SplitArgs {
-    pub input_file: String,
+    /// Path of the input file, or `None` to read from stdin.
+    pub input_file: Option<String>,
     pub output_dir: String,
+    /// Command that receives each chunk on its stdin, instead of writing it to the output dir.
+    pub exec: Option<String>,
     pub input_filename_without_extension: String,
     pub lines_per_file: usize,
     pub prefix: String,
     pub csv_mode: bool,
     pub feedback_interval: usize,
+    pub profile: bool,
+}
+
+impl SplitArgs {
+    /// Name of the input shown to the user.
+    pub fn input_display_name(&self) -> &str {
+        self.input_file.as_deref().unwrap_or("<stdin>")
+    }
 }
@@ -1,60 +1,79 @@
+use crate::chunk_writer::ChunkWriter;
 use crate::models::SplitArgs;
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use log::error;
-use shared::constants::general::{SIZE_128KB, SIZE_64KB};
+use shared::constants::general::SIZE_128KB;
+use shared::ui::progress::{Progress, ProgressTotal};
 use shared::utils::datetime_utc_utils::DateTimeUtcUtils;
-use shared::utils::format_bytes_to_string::format_bytes_to_string;
 use shared::utils::format_duration_to_string::format_duration_to_string;
+use shared::utils::phase_profiler::PhaseProfiler;
 use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
-/// Creates a buffered file reader with 128KB buffer for input file.
+const LINES: &str = "Lines";
+const FILES: &str = "Files";
+
+/// Creates a buffered reader with 128KB buffer for the input file, or for stdin when no input
+/// file was given.
 ///
 /// # Errors
 /// Returns error if input file cannot be opened
-pub fn get_input_file_reader(args: &SplitArgs) -> Result<BufReader<File>> {
-    let input_file = File::open(&args.input_file).context("Failed to open input file")?;
+pub fn get_input_reader(args: &SplitArgs) -> Result<Box<dyn BufRead>> {
+    let Some(input_file) = &args.input_file else {
+        return Ok(Box::new(BufReader::with_capacity(
+            SIZE_128KB,
+            std::io::stdin(),
+        )));
+    };
+
+    let input_file = File::open(input_file).context("Failed to open input file")?;
 
     // Use a larger buffer size (128KB) for better performance with large files
-    Ok(BufReader::with_capacity(SIZE_128KB, input_file))
+    Ok(Box::new(BufReader::with_capacity(SIZE_128KB, input_file)))
 }
 
 /// Splits input file into multiple files based on line count with graceful shutdown.
 ///
 /// Reads file line by line, creates output files with specified prefix and numbering.
 /// In CSV mode, preserves headers in each output file. Provides progress feedback.
+/// With `--exec`, each chunk is piped into a new run of the command instead of a file, and the
+/// command must finish successfully before the next chunk starts.
+/// When profiling is enabled, prints the time spent in each phase at the end.
 ///
 /// # Errors
 /// Returns error if file operations fail
 pub fn process_input_file(args: &SplitArgs, shutdown_signal: Arc<AtomicBool>) -> Result<()> {
+    let mut profiler = PhaseProfiler::new(args.profile);
+    let phase_started_at = Instant::now();
+
     // Open the input file
-    let mut reader = get_input_file_reader(args)?;
+    let mut reader = get_input_reader(args)?;
 
     let start_time = Utc::now();
 
-    let feedback_interval = args.feedback_interval as f64;
+    let progress = create_progress(args);
 
     let mut current_file_number = 1;
 
     let mut current_line_count = 0;
 
-    let mut current_output_writer: Option<BufWriter<File>> = None;
-
-    let mut total_lines_processed: f64 = 0.0;
+    let mut current_output_writer: Option<ChunkWriter> = None;
 
     let mut output_filename = String::new();
 
-    let mut total_data_read: u64 = 0;
-
     // Pre-allocate string buffer for line reading to avoid repeated allocations
     let mut line_buffer = String::with_capacity(1024);
 
     // Get the CSV header, if in CSV mode.
-    let csv_header = try_get_csv_header(args, &mut reader)?;
+    let csv_header = try_get_csv_header(args, reader.as_mut())?;
+
+    profiler.record_since("open input", phase_started_at);
+    let phase_started_at = Instant::now();
 
     loop {
         // Clear the buffer and read the next line
@@ -69,7 +88,7 @@ pub fn process_input_file(args: &SplitArgs, shutdown_signal: Arc<AtomicBool>) ->
             }
         };
 
-        total_data_read += bytes_read as u64;
+        progress.add_bytes(bytes_read as u64);
 
         if shutdown_signal.load(Ordering::Relaxed) {
             println!("\n- Saving progress and exiting gracefully...");
@@ -96,19 +115,18 @@ pub fn process_input_file(args: &SplitArgs, shutdown_signal: Arc<AtomicBool>) ->
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| format!("file_{}", current_file_number));
 
-            let file = File::create(&output_path).context(format!(
-                "Failed to create output file: [{}]",
-                &output_path.display()
-            ))?;
-
-            // Use BufWriter with a large buffer (64KB) for better write performance
-            let mut writer = BufWriter::with_capacity(SIZE_64KB, file);
+            let mut writer = match &args.exec {
+                Some(command) => {
+                    ChunkWriter::spawn_command(command, &output_filename, current_file_number)?
+                }
+                None => ChunkWriter::create_file(&output_path)?,
+            };
 
             // Write CSV header if in CSV mode
             if let Some(ref header) = csv_header {
                 writeln!(writer, "{}", header).context(format!(
                     "Failed to write CSV header to output file: [{}]",
-                    &output_path.display()
+                    &output_filename
                 ))?;
             }
 
@@ -124,91 +142,109 @@ pub fn process_input_file(args: &SplitArgs, shutdown_signal: Arc<AtomicBool>) ->
         }
 
         current_line_count += 1;
-        total_lines_processed += 1.0;
+        progress.increment(LINES);
 
         // Check if we need to start a new file
         if current_line_count >= args.lines_per_file {
-            // Flush and close the current file
-            if let Some(mut writer) = current_output_writer.take() {
-                if let Err(e) = writer.flush() {
-                    eprintln!(
-                        "Warning: Failed to flush output file {}: {}",
-                        output_filename, e
-                    );
-                }
+            // Flush and close the current file (or wait for the command handling it)
+            if let Some(writer) = current_output_writer.take() {
+                finish_chunk(args, writer, &output_filename)?;
             }
             current_line_count = 0;
             current_file_number += 1;
         }
 
         // Update progress less frequently to avoid I/O overhead
-        if total_lines_processed % feedback_interval == 0.0 {
-            update_progress_feedback(
-                &start_time,
+        if args.feedback_interval > 0
+            && progress
+                .get(LINES)
+                .is_multiple_of(args.feedback_interval as u64)
+        {
+            update_progress(
+                &progress,
                 current_file_number,
                 current_line_count,
-                total_lines_processed,
                 &output_filename,
-                &total_data_read,
             );
+            progress.render();
         }
     }
 
+    profiler.record_since("split lines", phase_started_at);
+
     // Ensure final file is properly flushed
-    if let Some(mut writer) = current_output_writer {
-        if let Err(e) = writer.flush() {
-            eprintln!("Warning: Failed to flush final output file: {}", e);
-        }
+    if let Some(writer) = current_output_writer {
+        profiler.measure("flush output", || {
+            finish_chunk(args, writer, &output_filename)
+        })?;
     }
 
-    update_progress_feedback(
-        &start_time,
+    update_progress(
+        &progress,
         current_file_number,
         current_line_count,
-        total_lines_processed,
         &output_filename,
-        &total_data_read,
     );
+    progress.finish();
 
-    println!();
     println!(
         "\n- Elapsed time: {}",
         format_duration_to_string(start_time.get_elapsed_time())
     );
 
+    profiler.print_report();
+
     Ok(())
 }
 
-/// Displays progress feedback with lines/second, data processed, and current file info.
-///
-/// Overwrites console line with real-time progress information.
+/// Closes a chunk. Failing to flush a file is only a warning, as before, but a failed `--exec`
+/// command stops the split, so chunks are not lost silently.
 ///
-/// # Panics
-/// Panics if stdout flush fails
-fn update_progress_feedback(
-    start_time: &DateTime<Utc>,
+/// # Errors
+/// Returns error if the command of the chunk fails
+fn finish_chunk(args: &SplitArgs, writer: ChunkWriter, output_filename: &str) -> Result<()> {
+    match writer.finish() {
+        Ok(()) => Ok(()),
+        Err(e) if args.exec.is_some() => {
+            Err(e.context(format!("Failed to process chunk: [{}]", output_filename)))
+        }
+        Err(e) => {
+            eprintln!(
+                "Warning: Failed to flush output file {}: {}",
+                output_filename, e
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Creates the progress of the split. The size of the input file, when there is one, gives the
+/// percentage and the ETA.
+fn create_progress(args: &SplitArgs) -> Progress {
+    let progress = Progress::new("lines", &[LINES, FILES]);
+
+    match args
+        .input_file
+        .as_ref()
+        .and_then(|file| std::fs::metadata(file).ok())
+    {
+        Some(metadata) => progress.with_total(ProgressTotal::Bytes(metadata.len())),
+        None => progress,
+    }
+}
+
+/// Updates the number of files and the file being written, shown in the progress line.
+fn update_progress(
+    progress: &Progress,
     current_file_number: i32,
     current_line_count: usize,
-    total_lines_processed: f64,
     current_output_file: &str,
-    total_bytes_read: &u64,
 ) {
-    let elapsed = start_time.get_elapsed_time();
-    let lines_per_second = total_lines_processed / elapsed.as_seconds_f64();
-
-    let msg = format!(
-        "[L/s:{:.2}][Total Lines:{:.0} Data:{} Files:{}][Cur. File:{} - {}]                        ",
-        lines_per_second,
-        total_lines_processed,
-        format_bytes_to_string(total_bytes_read),
-        current_file_number,
-        current_line_count,
-        current_output_file
-    );
-
-    print!("\r{}", msg);
-
-    std::io::stdout().flush().expect("Failed to flush stdout");
+    progress.set(FILES, current_file_number as u64);
+    progress.set_detail(format!(
+        "Cur. File: {} - {}",
+        current_line_count, current_output_file
+    ));
 }
 
 /// Creates output file path with prefix, input name, and file number.
@@ -232,7 +268,7 @@ fn create_output_filename(args: &SplitArgs, current_file_number: i32) -> PathBuf
 ///
 /// # Errors
 /// Returns error if header line cannot be read
-fn try_get_csv_header(args: &SplitArgs, reader: &mut BufReader<File>) -> Result<Option<String>> {
+fn try_get_csv_header(args: &SplitArgs, reader: &mut dyn BufRead) -> Result<Option<String>> {
     if !args.csv_mode {
         return Ok(None);
     }
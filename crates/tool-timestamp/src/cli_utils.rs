@@ -1,53 +1,160 @@
-use crate::models::TsArgs;
-use clap::{Arg, Command};
+use crate::formats::TimestampFormat;
+use crate::models::{TsArgs, TsOperation};
+use anyhow::Result;
+use clap::{Arg, ArgMatches, Command};
 use shared::command_line::cli_builder::CommandExt;
-use shared::constants::general::DASH_LINE;
+use shared::command_line::tool_cli::{GlobalArgs, ToolCli};
 
-/// Displays runtime information for the timestamp converter.
-///
-/// Shows version, divider line, and input (or "(Current time)" if no input provided).
-pub fn print_runtime_info(args: &TsArgs) {
-    println!("Timestamp Converter v{}", env!("CARGO_PKG_VERSION"));
-    println!("{}", DASH_LINE);
+impl ToolCli for TsArgs {
+    const DISPLAY_NAME: &'static str = "Timestamp Converter";
 
-    let input = if args.input.is_empty() {
-        "(Current time)"
-    } else {
-        &args.input
-    };
+    /// Accepts optional input that can be Unix timestamp, datetime string, or empty for current time.
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                "A simple utility to convert Unix timestamps to date time and vice versa.",
+                "This tool receives a Unix timestamp and converts it to a date time (ISO8601) or vice versa.",
+            )
+            .arg(
+            Arg::new("input")
+                .value_name("input")
+                .action(clap::ArgAction::Append)
+                .required(false)
+                .help("Input that will be processed. Only one input is valid, but no need to use quotes.")
+        ).arg(
+            Arg::new("from")
+                .long("from")
+                .short('f')
+                .value_name("FORMAT")
+                .value_parser(format_names())
+                .global(true)
+                .help("Read the input as: unix, ms, ticks (.NET), filetime (Windows), excel (serial date), or cocoa (Apple).")
+        ).arg(
+            Arg::new("to")
+                .long("to")
+                .short('t')
+                .value_name("FORMATS")
+                .global(true)
+                .help("Also print the result in these formats (comma-separated, or 'all'): unix, ms, ticks, filetime, excel, cocoa.")
+        ).subcommand(
+            Command::new("diff")
+                .about("Prints the time between two timestamps, in several units")
+                .arg(timestamp_arg("start", "First timestamp (or 'now')."))
+                .arg(timestamp_arg("end", "Second timestamp (or 'now'). The result is negative if it comes before the first one."))
+        ).subcommand(
+            Command::new("add")
+                .about("Adds a duration to a timestamp (or subtracts it, with a minus sign)")
+                .arg(timestamp_arg("timestamp", "Timestamp to start from (or 'now')."))
+                .arg(
+                    Arg::new("duration")
+                        .value_name("DURATION")
+                        .required(true)
+                        .allow_hyphen_values(true)
+                        .help("Duration to add, like +2h30m, -1d, 1.5h, 90s, 01:30:00, or PT2H30M. Units: ms, s, m, h, d, w.")
+                )
+        )
+    }
+
+    /// Multiple input arguments are joined with spaces.
+    fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+        let input: String = matches
+            .get_many::<String>("input")
+            .unwrap_or_default()
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let from = matches
+            .get_one::<String>("from")
+            .map(|name| name.parse::<TimestampFormat>())
+            .transpose()?;
+
+        let to = matches
+            .get_one::<String>("to")
+            .map(|names| TimestampFormat::parse_list(names))
+            .transpose()?
+            .unwrap_or_default();
+
+        let operation = match matches.subcommand() {
+            Some(("diff", sub_matches)) => Some(TsOperation::Diff {
+                start: get_value(sub_matches, "start"),
+                end: get_value(sub_matches, "end"),
+            }),
+            Some(("add", sub_matches)) => Some(TsOperation::Add {
+                timestamp: get_value(sub_matches, "timestamp"),
+                duration: get_value(sub_matches, "duration"),
+            }),
+            _ => None,
+        };
+
+        Ok(TsArgs {
+            input,
+            from,
+            to,
+            operation,
+        })
+    }
+
+    /// Shows the input (or "(Current time)" if no input provided).
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let input = if self.input.is_empty() {
+            "(Current time)"
+        } else {
+            &self.input
+        };
 
-    println!("- Input: {}", input);
+        let mut info = match &self.operation {
+            Some(TsOperation::Diff { start, end }) => vec![
+                ("Operation".to_string(), "diff".to_string()),
+                ("Start".to_string(), start.clone()),
+                ("End".to_string(), end.clone()),
+            ],
+            Some(TsOperation::Add {
+                timestamp,
+                duration,
+            }) => vec![
+                ("Operation".to_string(), "add".to_string()),
+                ("Timestamp".to_string(), timestamp.clone()),
+                ("Duration".to_string(), duration.clone()),
+            ],
+            None => vec![("Input".to_string(), input.to_string())],
+        };
 
-    println!();
+        if let Some(from) = self.from {
+            info.push(("From".to_string(), from.name().to_string()));
+        }
+
+        if !self.to.is_empty() {
+            let names = self
+                .to
+                .iter()
+                .map(|format| format.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            info.push(("To".to_string(), names));
+        }
+
+        info
+    }
 }
 
-/// Parses command-line arguments for timestamp conversion.
-///
-/// Accepts optional input that can be Unix timestamp, datetime string, or empty for current time.
-/// Multiple input arguments are joined with spaces.
-///
-/// # Returns
-/// TsArgs struct containing the parsed input string
-pub fn get_cli_arguments() -> TsArgs {
-    let matches = Command::new(env!("CARGO_PKG_NAME"))
-        .add_basic_metadata(
-            env!("CARGO_PKG_VERSION"),
-            "A simple utility to convert Unix timestamps to date time and vice versa.",
-            "This tool receives a Unix timestamp and converts it to a date time (ISO8601) or vice versa.",
-        ).arg(
-        Arg::new("input")
-            .value_name("input")
-            .action(clap::ArgAction::Append)
-            .required(false)
-            .help("Input that will be processed. Only one input is valid, but no need to use quotes.")
-    ).get_matches();
-
-    let input: String = matches
-        .get_many::<String>("input")
-        .unwrap_or_default()
-        .cloned()
-        .collect::<Vec<String>>()
-        .join(" ");
-
-    TsArgs { input }
+/// Timestamp of the `diff` and `add` subcommands. Negative Unix timestamps are accepted.
+fn timestamp_arg(name: &'static str, help: &'static str) -> Arg {
+    Arg::new(name)
+        .value_name("TIMESTAMP")
+        .required(true)
+        .allow_negative_numbers(true)
+        .help(help)
+}
+
+fn get_value(matches: &ArgMatches, name: &str) -> String {
+    matches.get_one::<String>(name).cloned().unwrap_or_default()
+}
+
+fn format_names() -> Vec<&'static str> {
+    TimestampFormat::ALL
+        .iter()
+        .map(|format| format.name())
+        .collect()
 }
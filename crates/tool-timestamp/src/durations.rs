@@ -0,0 +1,284 @@
+use crate::formats::format_decimal;
+use anyhow::{bail, Context, Result};
+use chrono::TimeDelta;
+
+const MILLIS_PER_SECOND: f64 = 1_000.0;
+const MILLIS_PER_MINUTE: f64 = 60_000.0;
+const MILLIS_PER_HOUR: f64 = 3_600_000.0;
+const MILLIS_PER_DAY: f64 = 86_400_000.0;
+const MILLIS_PER_WEEK: f64 = 604_800_000.0;
+
+/// Units a duration is printed in, from the smallest to the largest.
+const UNITS: [(&str, f64); 6] = [
+    ("Milliseconds", 1.0),
+    ("Seconds", MILLIS_PER_SECOND),
+    ("Minutes", MILLIS_PER_MINUTE),
+    ("Hours", MILLIS_PER_HOUR),
+    ("Days", MILLIS_PER_DAY),
+    ("Weeks", MILLIS_PER_WEEK),
+];
+
+/// Parses a duration, with an optional sign (`+` or `-`):
+/// - Amounts with units, in any order and with or without spaces: `2h30m`, `1d 12h`,
+///   `1.5 hours`, `90min`. Units: `ms`, `s`, `m`/`min`, `h`, `d`, `w` (and their long names).
+/// - Clock time: `01:30` (hours and minutes) or `01:30:15`.
+/// - ISO 8601, without years and months (their length varies): `PT2H30M`, `P1DT12H`, `P2W`.
+///
+/// # Errors
+/// Returns error if the duration is empty, has an unknown unit, or is too large.
+pub fn parse_duration(input: &str) -> Result<TimeDelta> {
+    let trimmed = input.trim();
+    let (negative, body) = match trimmed.chars().next() {
+        Some('-') => (true, trimmed[1..].trim_start()),
+        Some('+') => (false, trimmed[1..].trim_start()),
+        _ => (false, trimmed),
+    };
+
+    if body.is_empty() {
+        bail!("Empty duration. Use something like: +2h30m, -1d, 90s, 01:30:00, or PT2H");
+    }
+
+    let millis = if body.contains(':') {
+        parse_clock(body)?
+    } else if body.starts_with(['P', 'p']) {
+        parse_iso8601(body)?
+    } else {
+        parse_units(body)?
+    };
+
+    let millis = if negative { -millis } else { millis };
+    if !millis.is_finite() || millis.abs() >= i64::MAX as f64 {
+        bail!("Duration is too large: [{}]", input);
+    }
+
+    TimeDelta::try_milliseconds(millis.round() as i64)
+        .with_context(|| format!("Duration is too large: [{}]", input))
+}
+
+fn unit_millis(unit: &str) -> Option<f64> {
+    let millis = match unit.to_lowercase().as_str() {
+        "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => 1.0,
+        "s" | "sec" | "secs" | "second" | "seconds" => MILLIS_PER_SECOND,
+        "m" | "min" | "mins" | "minute" | "minutes" => MILLIS_PER_MINUTE,
+        "h" | "hr" | "hrs" | "hour" | "hours" => MILLIS_PER_HOUR,
+        "d" | "day" | "days" => MILLIS_PER_DAY,
+        "w" | "wk" | "wks" | "week" | "weeks" => MILLIS_PER_WEEK,
+        _ => return None,
+    };
+
+    Some(millis)
+}
+
+/// `2h30m`, `1d 12h`, `1.5 hours`. A number without a unit is in seconds, like `sleep`.
+fn parse_units(input: &str) -> Result<f64> {
+    let mut total = 0.0;
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .unwrap_or(rest.len());
+        if number_end == 0 {
+            bail!(
+                "Expected a number at [{}], in the duration [{}]",
+                rest,
+                input
+            );
+        }
+
+        let amount: f64 = rest[..number_end]
+            .replace(',', ".")
+            .parse()
+            .with_context(|| format!("Invalid number in the duration [{}]", input))?;
+        rest = rest[number_end..].trim_start();
+
+        let unit_end = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_end];
+        let millis = if unit.is_empty() {
+            MILLIS_PER_SECOND
+        } else {
+            unit_millis(unit).with_context(|| {
+                format!(
+                    "Unknown unit [{}] in the duration [{}]. Use: ms, s, m, h, d, or w",
+                    unit, input
+                )
+            })?
+        };
+
+        total += amount * millis;
+        rest = rest[unit_end..].trim_start();
+    }
+
+    Ok(total)
+}
+
+/// `01:30` (hours and minutes) or `01:30:15.5`.
+fn parse_clock(input: &str) -> Result<f64> {
+    let parts: Vec<&str> = input.split(':').collect();
+    let invalid = || format!("Invalid clock time [{}]. Use hh:mm or hh:mm:ss", input);
+
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [hours, minutes] => (hours, minutes, "0"),
+        [hours, minutes, seconds] => (hours, minutes, *seconds),
+        _ => bail!(invalid()),
+    };
+
+    let hours: u64 = hours.trim().parse().with_context(invalid)?;
+    let minutes: u64 = minutes.trim().parse().with_context(invalid)?;
+    let seconds: f64 = seconds.trim().parse().with_context(invalid)?;
+    if minutes >= 60 || !(0.0..60.0).contains(&seconds) {
+        bail!(invalid());
+    }
+
+    Ok(hours as f64 * MILLIS_PER_HOUR
+        + minutes as f64 * MILLIS_PER_MINUTE
+        + seconds * MILLIS_PER_SECOND)
+}
+
+/// `PT2H30M`, `P1DT12H`, `P2W`.
+fn parse_iso8601(input: &str) -> Result<f64> {
+    let upper = input.to_uppercase();
+    let (date, time) = match upper[1..].split_once('T') {
+        Some((date, time)) => (date, time),
+        None => (&upper[1..], ""),
+    };
+
+    if date.is_empty() && time.is_empty() {
+        bail!("Empty ISO 8601 duration: [{}]", input);
+    }
+
+    let mut total = 0.0;
+    for (part, in_time) in [(date, false), (time, true)] {
+        let mut number = String::new();
+        for c in part.chars() {
+            if c.is_ascii_digit() || c == '.' || c == ',' {
+                number.push(if c == ',' { '.' } else { c });
+                continue;
+            }
+
+            let millis = match (c, in_time) {
+                ('W', false) => MILLIS_PER_WEEK,
+                ('D', false) => MILLIS_PER_DAY,
+                ('H', true) => MILLIS_PER_HOUR,
+                ('M', true) => MILLIS_PER_MINUTE,
+                ('S', true) => MILLIS_PER_SECOND,
+                ('Y' | 'M', false) => bail!(
+                    "Years and months have no fixed length, use days instead: [{}]",
+                    input
+                ),
+                _ => bail!("Invalid ISO 8601 duration: [{}]", input),
+            };
+
+            let amount: f64 = number
+                .parse()
+                .with_context(|| format!("Invalid ISO 8601 duration: [{}]", input))?;
+            total += amount * millis;
+            number.clear();
+        }
+
+        if !number.is_empty() {
+            bail!("Missing the unit after [{}] in [{}]", number, input);
+        }
+    }
+
+    Ok(total)
+}
+
+/// Compact form, sign first: `-3d 4h 5m 6.5s`. Zero is `0s`.
+pub fn format_duration(duration: &TimeDelta) -> String {
+    let sign = if *duration < TimeDelta::zero() {
+        "-"
+    } else {
+        ""
+    };
+    let total_millis = duration.num_milliseconds().unsigned_abs();
+
+    let days = total_millis / MILLIS_PER_DAY as u64;
+    let hours = total_millis % MILLIS_PER_DAY as u64 / MILLIS_PER_HOUR as u64;
+    let minutes = total_millis % MILLIS_PER_HOUR as u64 / MILLIS_PER_MINUTE as u64;
+    let millis = total_millis % MILLIS_PER_MINUTE as u64;
+
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{}d", days));
+    }
+    if hours > 0 {
+        parts.push(format!("{}h", hours));
+    }
+    if minutes > 0 {
+        parts.push(format!("{}m", minutes));
+    }
+    if millis > 0 || parts.is_empty() {
+        parts.push(format!(
+            "{}s",
+            format_decimal(millis as f64 / MILLIS_PER_SECOND, 3)
+        ));
+    }
+
+    format!("{}{}", sign, parts.join(" "))
+}
+
+/// The duration in each unit (milliseconds to weeks), with up to 6 decimals.
+pub fn duration_in_units(duration: &TimeDelta) -> Vec<(&'static str, String)> {
+    let millis = duration.num_milliseconds() as f64;
+
+    UNITS
+        .iter()
+        .map(|(name, unit_millis)| (*name, format_decimal(millis / unit_millis, 6)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        let cases = [
+            ("+2h30m", TimeDelta::minutes(150)),
+            ("2h 30m", TimeDelta::minutes(150)),
+            ("-1d", TimeDelta::days(-1)),
+            ("1.5 hours", TimeDelta::minutes(90)),
+            ("1w2d", TimeDelta::days(9)),
+            ("90", TimeDelta::seconds(90)),
+            ("250ms", TimeDelta::milliseconds(250)),
+            ("01:30", TimeDelta::minutes(90)),
+            ("- 00:00:15.5", TimeDelta::milliseconds(-15_500)),
+            ("PT2H30M", TimeDelta::minutes(150)),
+            ("P1DT12H", TimeDelta::hours(36)),
+            ("p2w", TimeDelta::weeks(2)),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(parse_duration(input).unwrap(), expected, "{}", input);
+        }
+
+        for invalid in [
+            "",
+            "+",
+            "2 fortnights",
+            "h",
+            "01:75",
+            "P1M",
+            "PT5",
+            "1:2:3:4",
+        ] {
+            assert!(parse_duration(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        let duration = TimeDelta::days(3) + TimeDelta::hours(4) + TimeDelta::milliseconds(6_500);
+        assert_eq!(format_duration(&duration), "3d 4h 6.5s");
+        assert_eq!(format_duration(&-TimeDelta::minutes(90)), "-1h 30m");
+        assert_eq!(format_duration(&TimeDelta::zero()), "0s");
+
+        let units = duration_in_units(&TimeDelta::hours(36));
+        assert_eq!(units[1], ("Seconds", "129600".to_string()));
+        assert_eq!(units[4], ("Days", "1.5".to_string()));
+        assert_eq!(units[5], ("Weeks", "0.214286".to_string()));
+    }
+}
@@ -0,0 +1,310 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+/// .NET ticks at the Unix epoch (ticks are 100 ns intervals since 0001-01-01 00:00:00 UTC).
+const DOTNET_TICKS_AT_UNIX_EPOCH: i128 = 621_355_968_000_000_000;
+
+/// Windows FILETIME at the Unix epoch (100 ns intervals since 1601-01-01 00:00:00 UTC).
+const FILETIME_AT_UNIX_EPOCH: i128 = 116_444_736_000_000_000;
+
+/// Seconds between the Unix epoch and the Apple Cocoa epoch (2001-01-01 00:00:00 UTC).
+const COCOA_EPOCH_UNIX_SECONDS: i64 = 978_307_200;
+
+/// Excel serial of the Unix epoch (1970-01-01), in the 1900 date system.
+const EXCEL_SERIAL_AT_UNIX_EPOCH: f64 = 25_569.0;
+
+/// Serial of 1900-02-29, a day that doesn't exist but Excel counts (inherited from Lotus 1-2-3).
+const EXCEL_PHANTOM_LEAP_DAY: f64 = 60.0;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const MILLIS_PER_DAY: f64 = 86_400_000.0;
+
+/// Numeric timestamp encodings that can be read (`--from`) and written (`--to`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampFormat {
+    Unix,
+    UnixMillis,
+    DotNetTicks,
+    FileTime,
+    ExcelSerial,
+    Cocoa,
+}
+
+impl TimestampFormat {
+    pub const ALL: [TimestampFormat; 6] = [
+        TimestampFormat::Unix,
+        TimestampFormat::UnixMillis,
+        TimestampFormat::DotNetTicks,
+        TimestampFormat::FileTime,
+        TimestampFormat::ExcelSerial,
+        TimestampFormat::Cocoa,
+    ];
+
+    /// Name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimestampFormat::Unix => "unix",
+            TimestampFormat::UnixMillis => "ms",
+            TimestampFormat::DotNetTicks => "ticks",
+            TimestampFormat::FileTime => "filetime",
+            TimestampFormat::ExcelSerial => "excel",
+            TimestampFormat::Cocoa => "cocoa",
+        }
+    }
+
+    /// Label printed next to the values, saying what the number counts and from when.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimestampFormat::Unix => "Unix timestamp (seconds since 1970-01-01 UTC)",
+            TimestampFormat::UnixMillis => "Unix milliseconds (since 1970-01-01 UTC)",
+            TimestampFormat::DotNetTicks => ".NET ticks (100 ns since 0001-01-01 UTC)",
+            TimestampFormat::FileTime => "Windows FILETIME (100 ns since 1601-01-01 UTC)",
+            TimestampFormat::ExcelSerial => {
+                "Excel serial date (days since 1899-12-30, read as UTC)"
+            }
+            TimestampFormat::Cocoa => "Apple Cocoa/Core Data (seconds since 2001-01-01 UTC)",
+        }
+    }
+
+    /// Reads a value of this format. Ticks and FILETIME also accept hex (`0x01DA...`), the way
+    /// they show up in registry exports and event logs.
+    pub fn decode(&self, input: &str) -> Result<DateTime<Utc>> {
+        let input = input.trim();
+        let datetime = match self {
+            TimestampFormat::Unix => DateTime::from_timestamp(parse_integer(input)?, 0),
+            TimestampFormat::UnixMillis => DateTime::from_timestamp_millis(parse_integer(input)?),
+            TimestampFormat::DotNetTicks => {
+                from_hundred_nanos(parse_ticks(input)? - DOTNET_TICKS_AT_UNIX_EPOCH)
+            }
+            TimestampFormat::FileTime => {
+                from_hundred_nanos(parse_ticks(input)? - FILETIME_AT_UNIX_EPOCH)
+            }
+            TimestampFormat::ExcelSerial => from_excel_serial(parse_decimal(input)?)?,
+            TimestampFormat::Cocoa => {
+                let seconds = parse_decimal(input)?;
+                DateTime::from_timestamp_millis(
+                    COCOA_EPOCH_UNIX_SECONDS * 1000 + (seconds * 1000.0).round() as i64,
+                )
+            }
+        };
+
+        datetime.with_context(|| format!("{} is out of range for {}", input, self.label()))
+    }
+
+    /// Writes the datetime in this format.
+    pub fn encode(&self, datetime: &DateTime<Utc>) -> String {
+        let nanos = datetime.timestamp() as i128 * NANOS_PER_SECOND
+            + datetime.timestamp_subsec_nanos() as i128;
+
+        match self {
+            TimestampFormat::Unix => datetime.timestamp().to_string(),
+            TimestampFormat::UnixMillis => datetime.timestamp_millis().to_string(),
+            TimestampFormat::DotNetTicks => (nanos / 100 + DOTNET_TICKS_AT_UNIX_EPOCH).to_string(),
+            TimestampFormat::FileTime => (nanos / 100 + FILETIME_AT_UNIX_EPOCH).to_string(),
+            TimestampFormat::ExcelSerial => {
+                let serial = datetime.timestamp_millis() as f64 / MILLIS_PER_DAY
+                    + EXCEL_SERIAL_AT_UNIX_EPOCH;
+                // Before March 1900, Excel is one day behind, since it counts 1900-02-29.
+                let serial = if serial < EXCEL_PHANTOM_LEAP_DAY + 1.0 {
+                    serial - 1.0
+                } else {
+                    serial
+                };
+                format_decimal(serial, 8)
+            }
+            TimestampFormat::Cocoa => format_decimal(
+                (datetime.timestamp_millis() - COCOA_EPOCH_UNIX_SECONDS * 1000) as f64 / 1000.0,
+                3,
+            ),
+        }
+    }
+
+    /// Parses a comma-separated list of format names. `all` selects every format.
+    pub fn parse_list(value: &str) -> Result<Vec<TimestampFormat>> {
+        let mut formats = Vec::new();
+
+        for name in value.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let selected = if name.eq_ignore_ascii_case("all") {
+                TimestampFormat::ALL.to_vec()
+            } else {
+                vec![name.parse()?]
+            };
+
+            for format in selected {
+                if !formats.contains(&format) {
+                    formats.push(format);
+                }
+            }
+        }
+
+        Ok(formats)
+    }
+}
+
+impl FromStr for TimestampFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        TimestampFormat::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown timestamp format '{}'. Valid options are: {}",
+                    value,
+                    TimestampFormat::ALL.map(|format| format.name()).join(", ")
+                )
+            })
+    }
+}
+
+/// Formats that a bare integer of this size is likely to be, used to hint at `--from`.
+pub fn guess_formats(input: &str) -> Vec<TimestampFormat> {
+    let digits = input.trim_start_matches('-').len();
+    match digits {
+        18 => vec![TimestampFormat::DotNetTicks, TimestampFormat::FileTime],
+        5 => vec![TimestampFormat::ExcelSerial],
+        _ => Vec::new(),
+    }
+}
+
+fn parse_integer(input: &str) -> Result<i64> {
+    input
+        .parse()
+        .with_context(|| format!("Expected a whole number, got: [{}]", input))
+}
+
+fn parse_decimal(input: &str) -> Result<f64> {
+    let value: f64 = input
+        .replace(',', ".")
+        .parse()
+        .with_context(|| format!("Expected a number, got: [{}]", input))?;
+
+    if !value.is_finite() {
+        bail!("Expected a number, got: [{}]", input);
+    }
+    Ok(value)
+}
+
+fn parse_ticks(input: &str) -> Result<i128> {
+    let parsed = match input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        Some(hex) => i128::from_str_radix(hex, 16),
+        None => input.parse(),
+    };
+
+    parsed.with_context(|| format!("Expected a whole number (or 0x hex), got: [{}]", input))
+}
+
+fn from_hundred_nanos(hundred_nanos: i128) -> Option<DateTime<Utc>> {
+    let nanos = hundred_nanos.checked_mul(100)?;
+    let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).ok()?;
+    let subsec = nanos.rem_euclid(NANOS_PER_SECOND) as u32;
+    DateTime::from_timestamp(seconds, subsec)
+}
+
+fn from_excel_serial(serial: f64) -> Result<Option<DateTime<Utc>>> {
+    if serial < 0.0 {
+        bail!("Excel serial dates can't be negative: {}", serial);
+    }
+    if (EXCEL_PHANTOM_LEAP_DAY..EXCEL_PHANTOM_LEAP_DAY + 1.0).contains(&serial) {
+        bail!("Excel serial 60 is 1900-02-29, a day that doesn't exist");
+    }
+
+    let serial = if serial < EXCEL_PHANTOM_LEAP_DAY {
+        serial + 1.0
+    } else {
+        serial
+    };
+
+    // Rounded to the millisecond, the precision Excel keeps, to drop floating point noise.
+    let millis = ((serial - EXCEL_SERIAL_AT_UNIX_EPOCH) * MILLIS_PER_DAY).round() as i64;
+    Ok(DateTime::from_timestamp_millis(millis))
+}
+
+/// Formats with up to `decimals` decimals, without trailing zeros.
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    if formatted.contains('.') {
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2023, 12, 28, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_round_trips_every_format() {
+        for format in TimestampFormat::ALL {
+            let encoded = format.encode(&sample());
+            assert_eq!(
+                format.decode(&encoded).unwrap(),
+                sample(),
+                "{}",
+                format.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        let cases = [
+            (TimestampFormat::DotNetTicks, "638393616000000000"),
+            (TimestampFormat::FileTime, "133482384000000000"),
+            (TimestampFormat::ExcelSerial, "45288.5"),
+            (TimestampFormat::Cocoa, "725457600"),
+        ];
+
+        for (format, expected) in cases {
+            assert_eq!(format.encode(&sample()), expected, "{}", format.name());
+        }
+
+        assert_eq!(
+            TimestampFormat::FileTime
+                .decode("0x01DA39856217A000")
+                .unwrap(),
+            sample()
+        );
+    }
+
+    #[test]
+    fn test_excel_dates_before_the_phantom_leap_day() {
+        let first = TimestampFormat::ExcelSerial.decode("1").unwrap();
+        assert_eq!(first, Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(TimestampFormat::ExcelSerial.encode(&first), "1");
+        assert!(TimestampFormat::ExcelSerial.decode("60").is_err());
+        assert_eq!(
+            TimestampFormat::ExcelSerial
+                .encode(&Utc.with_ymd_and_hms(1900, 2, 28, 0, 0, 0).unwrap()),
+            "59"
+        );
+        assert_eq!(
+            TimestampFormat::ExcelSerial.decode("61").unwrap(),
+            Utc.with_ymd_and_hms(1900, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            TimestampFormat::parse_list("ticks, excel,ticks").unwrap(),
+            vec![TimestampFormat::DotNetTicks, TimestampFormat::ExcelSerial]
+        );
+        assert_eq!(TimestampFormat::parse_list("all").unwrap().len(), 6);
+        assert!(TimestampFormat::parse_list("julian").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::models::TsArgs;
+use crate::ts_app::process_input;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod durations;
+mod formats;
+mod models;
+mod ts_app;
+
+/// Main entry point for the timestamp converter tool.
+///
+/// Parses CLI arguments, initializes logging, displays runtime info, and processes the input.
+/// Handles both Unix timestamp to datetime conversion and datetime to Unix timestamp conversion.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<TsArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    invocation.print_runtime_info();
+
+    match process_input(&invocation.args) {
+        Ok(_) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            error!("Error: {}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
@@ -1,7 +1,24 @@
+use crate::formats::TimestampFormat;
+
 /// Command-line arguments for timestamp conversion.
 ///
 /// # Fields
 /// * `input` - Input string that can be Unix timestamp, datetime string, or empty for current time
+/// * `from` - Format the numeric input is in, instead of guessing (e.g. .NET ticks)
+/// * `to` - Extra formats the instant is printed in
+/// * `operation` - Duration arithmetic (`diff` or `add`), instead of converting the input
 pub struct TsArgs {
     pub input: String,
+    pub from: Option<TimestampFormat>,
+    pub to: Vec<TimestampFormat>,
+    pub operation: Option<TsOperation>,
+}
+
+/// Duration arithmetic subcommands. The timestamps are read like the input (Unix seconds or
+/// milliseconds, datetime strings, `--from` formats), and `now` is the current time.
+pub enum TsOperation {
+    /// Time from `start` to `end` (negative when `end` comes first).
+    Diff { start: String, end: String },
+    /// `timestamp` moved by `duration` (e.g.: `+2h30m`, `-1d`).
+    Add { timestamp: String, duration: String },
 }
@@ -1,5 +1,8 @@
+use crate::durations::{duration_in_units, format_duration, parse_duration};
+use crate::formats::{guess_formats, TimestampFormat};
+use crate::models::{TsArgs, TsOperation};
 use anyhow::{Context, Result};
-use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use std::str::FromStr;
 
 /// Datetime format patterns for parsing various timestamp formats.
@@ -25,36 +28,161 @@ const LAYOUTS: &[&str] = &[
 
 /// Processes input for timestamp conversion.
 ///
-/// Handles three scenarios:
+/// Handles four scenarios:
 /// - Empty input: Shows current Unix timestamp and its datetime representation
+/// - `--from` set: Reads the input in that format and shows it as datetime (UTC and local)
 /// - Numeric input: Converts Unix timestamp to datetime (UTC and local)
 /// - String input: Converts datetime string to Unix timestamp
-pub fn process_input(input: &str) -> Result<()> {
-    if input.is_empty() {
-        let now = print_current_unix_timestamp();
-        convert_unix_to_datetime(now)?;
-        return Ok(());
+///
+/// In every case, the instant is then printed in each `--to` format.
+/// With a subcommand (`diff` or `add`), runs it instead.
+pub fn process_input(args: &TsArgs) -> Result<()> {
+    if let Some(operation) = &args.operation {
+        return process_operation(operation, args);
     }
 
-    // Try to parse the input as a Unix timestamp (integer)
-    if let Ok(unix_timestamp) = i64::from_str(input) {
+    let input = args.input.as_str();
+
+    let instant = if input.is_empty() {
+        let now = print_current_unix_timestamp();
+        convert_unix_to_datetime(now)?
+    } else if let Some(format) = args.from {
+        let datetime = format.decode(input)?;
+        println!("Read as: {}", format.label());
+        print_datetime(&datetime);
+        Some(datetime)
+    } else if let Ok(unix_timestamp) = i64::from_str(input) {
+        // Try to parse the input as a Unix timestamp (integer)
+        print_format_hint(input);
         if input.len() > 10 {
             // Update to > 11 after - November 20th, 2286.
             println!("- Not a standard Unix timestamp. Treating it as time in milliseconds.");
-            convert_unix_to_datetime(unix_timestamp / 1000)?;
+            convert_unix_to_datetime(unix_timestamp / 1000)?
         } else {
-            convert_unix_to_datetime(unix_timestamp)?;
+            convert_unix_to_datetime(unix_timestamp)?
         }
+    } else {
+        // If not a Unix timestamp, treat it as a date-time string
+        convert_datetime_to_unix(input)?
+    };
 
-        return Ok(());
+    if let Some(instant) = instant {
+        print_conversions(&instant, &args.to);
     }
 
-    // If not a Unix timestamp, treat it as a date-time string
-    convert_datetime_to_unix(input)?;
+    Ok(())
+}
+
+/// Runs the duration arithmetic: prints the time between two timestamps (in several units), or
+/// the timestamp moved by a duration.
+fn process_operation(operation: &TsOperation, args: &TsArgs) -> Result<()> {
+    match operation {
+        TsOperation::Diff { start, end } => {
+            let start = parse_instant(start, args.from)?;
+            let end = parse_instant(end, args.from)?;
+            let difference = end - start;
+
+            println!("Start: {}", format_utc(&start));
+            println!("End: {}", format_utc(&end));
+            println!("Difference: {}", format_duration(&difference));
+            println!();
+            for (unit, value) in duration_in_units(&difference) {
+                println!("{}: {}", unit, value);
+            }
+        }
+        TsOperation::Add {
+            timestamp,
+            duration,
+        } => {
+            let start = parse_instant(timestamp, args.from)?;
+            let duration = parse_duration(duration)?;
+            let result = start
+                .checked_add_signed(duration)
+                .context("The result is out of the supported date range")?;
+
+            println!("Start: {}", format_utc(&start));
+            println!("Duration: {}", format_duration(&duration));
+            println!();
+            print_datetime(&result);
+            println!("Unix timestamp: {}", result.timestamp());
+            print_conversions(&result, &args.to);
+        }
+    }
 
     Ok(())
 }
 
+/// Reads a timestamp of the `diff` and `add` subcommands, without printing it: `now`, a value in
+/// the `--from` format, Unix seconds (or milliseconds, past 10 digits), or a datetime string
+/// (local time).
+///
+/// # Errors
+/// Returns error if the timestamp can't be parsed.
+fn parse_instant(input: &str, from: Option<TimestampFormat>) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+
+    if input.eq_ignore_ascii_case("now") {
+        return Ok(Utc::now());
+    }
+
+    if let Some(format) = from {
+        return format.decode(input);
+    }
+
+    if i64::from_str(input).is_ok() {
+        let format = if input.trim_start_matches('-').len() > 10 {
+            TimestampFormat::UnixMillis
+        } else {
+            TimestampFormat::Unix
+        };
+        return format.decode(input);
+    }
+
+    let timestamp = guess_datetime_format(input)?;
+    DateTime::from_timestamp(timestamp, 0).context(format!("Invalid timestamp: [{}]", input))
+}
+
+/// ISO 8601 in UTC, with the sub-second precision only when there is some.
+fn format_utc(utc_time: &DateTime<Utc>) -> String {
+    let fraction = if utc_time.timestamp_subsec_nanos() == 0 {
+        ""
+    } else {
+        "%.f"
+    };
+
+    utc_time
+        .format(&format!("%Y-%m-%dT%H:%M:%S{fraction}Z"))
+        .to_string()
+}
+
+/// Prints the instant in each of the requested formats, one labeled line each.
+fn print_conversions(instant: &DateTime<Utc>, formats: &[TimestampFormat]) {
+    if formats.is_empty() {
+        return;
+    }
+
+    println!();
+    for format in formats {
+        println!("{}: {}", format.label(), format.encode(instant));
+    }
+}
+
+/// Numbers that are likely .NET ticks, FILETIME, or Excel serials would otherwise be read as
+/// (nonsense) Unix timestamps, so point at the `--from` that decodes them.
+fn print_format_hint(input: &str) {
+    let guesses = guess_formats(input);
+    if guesses.is_empty() {
+        return;
+    }
+
+    let names = guesses
+        .iter()
+        .map(|format| format!("--from {}", format.name()))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    println!("- Hint: this may not be a Unix timestamp. Try {}.", names);
+}
+
 /// Gets and prints the current Unix timestamp.
 ///
 /// Uses local time to calculate seconds elapsed since Unix epoch (1970-01-01 00:00:00 UTC).
@@ -70,18 +198,33 @@ fn print_current_unix_timestamp() -> i64 {
 /// Converts Unix timestamp to UTC and local datetime formats.
 ///
 /// Displays both UTC time (ISO 8601 with Z suffix) and local time (with timezone offset).
-fn convert_unix_to_datetime(unix_timestamp: i64) -> Result<()> {
+fn convert_unix_to_datetime(unix_timestamp: i64) -> Result<Option<DateTime<Utc>>> {
     let utc_time = Utc
         .timestamp_opt(unix_timestamp, 0)
         .single()
         .context(format!("Invalid Unix timestamp: {}", unix_timestamp))?;
 
-    let local_time = utc_time.with_timezone(&Local);
+    print_datetime(&utc_time);
 
-    println!("UTC Time: {}", utc_time.format("%Y-%m-%dT%H:%M:%SZ"));
-    println!("Local Time: {}", local_time.format("%Y-%m-%dT%H:%M:%S%z"));
+    Ok(Some(utc_time))
+}
 
-    Ok(())
+/// Prints the instant as UTC (ISO 8601 with Z suffix) and local time (with timezone offset).
+///
+/// Sub-second precision is only shown when there is some, e.g. for .NET ticks.
+fn print_datetime(utc_time: &DateTime<Utc>) {
+    let local_time = utc_time.with_timezone(&Local);
+    let fraction = if utc_time.timestamp_subsec_nanos() == 0 {
+        ""
+    } else {
+        "%.f"
+    };
+
+    println!("UTC Time: {}", format_utc(utc_time));
+    println!(
+        "Local Time: {}",
+        local_time.format(&format!("%Y-%m-%dT%H:%M:%S{fraction}%z"))
+    );
 }
 
 /// Attempts to parse datetime string using multiple format patterns.
@@ -122,7 +265,10 @@ fn guess_datetime_format(input: &str) -> Result<i64> {
 ///
 /// First tries the default format "YYYY-MM-DD HH:MM:SS", then attempts format guessing.
 /// Prints the resulting Unix timestamp or error message if parsing fails.
-fn convert_datetime_to_unix(datetime_str: &str) -> Result<()> {
+///
+/// # Returns
+/// The parsed instant, or `None` when the input couldn't be parsed
+fn convert_datetime_to_unix(datetime_str: &str) -> Result<Option<DateTime<Utc>>> {
     // First, try the default format
     if let Ok(dt) = NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%d %H:%M:%S") {
         let timestamp = Local
@@ -133,15 +279,15 @@ fn convert_datetime_to_unix(datetime_str: &str) -> Result<()> {
 
         println!("Unix Timestamp: {}", timestamp);
 
-        return Ok(());
+        return Ok(DateTime::from_timestamp(timestamp, 0));
     }
 
     // If the default format fails, attempt to guess the format
     if let Ok(timestamp) = guess_datetime_format(datetime_str) {
         println!("Unix Timestamp: {}", timestamp);
+        Ok(DateTime::from_timestamp(timestamp, 0))
     } else {
         println!("Invalid date-time format. Unable to parse the input.");
+        Ok(None)
     }
-
-    Ok(())
 }
@@ -0,0 +1,148 @@
+use crate::models::{ToolboxUpdateArgs, KNOWN_TOOLS, REPOSITORY_ENV};
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{GlobalArgs, ToolCli};
+use shared::selfupdate::github_releases::DEFAULT_REPOSITORY;
+use std::env;
+use std::path::PathBuf;
+
+impl ToolCli for ToolboxUpdateArgs {
+    const DISPLAY_NAME: &'static str = "Toolbox Update";
+
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Checks the GitHub releases for newer versions of the installed tools, and updates \
+                them in place. Each tool is released on its own (tagged <tool>-v<version>), with a \
+                binary per platform and its SHA-256 checksum: binaries without a checksum, or with \
+                one that doesn't match, are never installed. Without tool names, every tool found \
+                next to this binary is checked.",
+            )
+            .arg(
+                Arg::new("tools")
+                    .value_name("TOOL")
+                    .num_args(1..)
+                    .help("Tools to check (e.g.: jwt how). (Default: every installed tool)"),
+            )
+            .arg(
+                Arg::new("check-only")
+                    .long("check-only")
+                    .short('c')
+                    .action(ArgAction::SetTrue)
+                    .help("Only reports the available updates, without installing them. (Default: false)"),
+            )
+            .arg(
+                Arg::new("dir")
+                    .long("dir")
+                    .short('d')
+                    .value_name("FOLDER")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .help("Folder with the installed tools. (Default: the folder of this binary)"),
+            )
+            .arg(
+                Arg::new("repo")
+                    .long("repo")
+                    .short('r')
+                    .value_name("OWNER/NAME")
+                    .help(format!(
+                        "GitHub repository to update from. Can also be set with {}. (Default: {})",
+                        REPOSITORY_ENV, DEFAULT_REPOSITORY
+                    )),
+            )
+            .arg(
+                Arg::new("prerelease")
+                    .long("prerelease")
+                    .action(ArgAction::SetTrue)
+                    .help("Also considers pre-releases. (Default: false)"),
+            )
+    }
+
+    fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+        let repository = matches
+            .get_one::<String>("repo")
+            .cloned()
+            .or_else(|| env::var(REPOSITORY_ENV).ok())
+            .filter(|repository| !repository.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_REPOSITORY.to_string());
+
+        Ok(ToolboxUpdateArgs {
+            tools: matches
+                .get_many::<String>("tools")
+                .map(|tools| tools.cloned().collect())
+                .unwrap_or_default(),
+            check_only: matches.get_flag("check-only"),
+            install_dir: matches.get_one::<PathBuf>("dir").cloned(),
+            repository: repository.trim().to_string(),
+            include_prereleases: matches.get_flag("prerelease"),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(unknown) = self
+            .tools
+            .iter()
+            .find(|tool| !KNOWN_TOOLS.contains(&tool.as_str()))
+        {
+            bail!(
+                "'{}' is not a toolbox tool. Valid options are: {}",
+                unknown,
+                KNOWN_TOOLS.join(", ")
+            );
+        }
+
+        let parts: Vec<&str> = self.repository.split('/').collect();
+        if parts.len() != 2 || parts.iter().any(|part| part.is_empty()) {
+            bail!(
+                "--repo must be in the owner/name format (got '{}')",
+                self.repository
+            );
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        let tools = if self.tools.is_empty() {
+            "all installed".to_string()
+        } else {
+            self.tools.join(", ")
+        };
+
+        vec![
+            ("Tools".to_string(), tools),
+            ("Repository".to_string(), self.repository.clone()),
+            (
+                "Folder".to_string(),
+                self.install_dir
+                    .as_ref()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_else(|| "folder of this binary".to_string()),
+            ),
+            ("Check only".to_string(), self.check_only.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    fn parse(argv: &[&str]) -> Result<ToolboxUpdateArgs> {
+        try_parse_tool_cli_from::<ToolboxUpdateArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_tools_and_validation() {
+        let args = parse(&["toolbox-update", "jwt", "how", "-c", "--repo", "me/fork"]).unwrap();
+        assert_eq!(args.tools, vec!["jwt", "how"]);
+        assert!(args.check_only);
+        assert_eq!(args.repository, "me/fork");
+
+        assert!(parse(&["toolbox-update", "not-a-tool"]).is_err());
+        assert!(parse(&["toolbox-update", "--repo", "no-owner"]).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where the binary of `tool` is in `install_dir` (with `.exe` on Windows).
+pub fn binary_path(install_dir: &Path, tool: &str) -> PathBuf {
+    install_dir.join(format!("{}{}", tool, env::consts::EXE_SUFFIX))
+}
+
+/// Folder this binary was started from: where the build scripts install every tool.
+///
+/// # Errors
+/// Returns an error if the path of the running binary can't be found.
+pub fn default_install_dir() -> Result<PathBuf> {
+    let current_exe = env::current_exe().context("Failed to find the path of this binary")?;
+    current_exe
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("{} has no parent folder", current_exe.display()))
+}
+
+/// Version of the installed tool, from `<tool> --version`. This binary answers for itself.
+///
+/// # Errors
+/// Returns an error if the binary can't be started, or doesn't print a version.
+pub fn read_installed_version(path: &Path, tool: &str) -> Result<String> {
+    if tool == env!("CARGO_PKG_NAME") && is_current_exe(path) {
+        return Ok(env!("CARGO_PKG_VERSION").to_string());
+    }
+
+    let output = Command::new(path)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("Failed to run {} --version", path.display()))?;
+
+    parse_version_output(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| anyhow!("{} --version didn't print a version", path.display()))
+}
+
+/// Takes the version from the first line of `--version` (`jwt 1.1.0` -> `1.1.0`).
+fn parse_version_output(output: &str) -> Option<String> {
+    let version = output.lines().next()?.split_whitespace().next_back()?;
+    let version = version.strip_prefix('v').unwrap_or(version);
+
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| version.to_string())
+}
+
+fn is_current_exe(path: &Path) -> bool {
+    let canonical = |path: &Path| path.canonicalize().ok();
+
+    env::current_exe()
+        .ok()
+        .and_then(|current| canonical(&current))
+        .is_some_and(|current| Some(current) == canonical(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_output() {
+        assert_eq!(
+            parse_version_output("jwt 1.1.0\n").as_deref(),
+            Some("1.1.0")
+        );
+        assert_eq!(parse_version_output("how v2.0.0").as_deref(), Some("2.0.0"));
+        assert_eq!(
+            parse_version_output("touch (GNU coreutils) 9.4\nCopyright").as_deref(),
+            Some("9.4")
+        );
+        assert_eq!(parse_version_output("usage: tool [options]"), None);
+        assert_eq!(parse_version_output(""), None);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::models::ToolboxUpdateArgs;
+use shared::command_line::tool_cli::{parse_tool_cli_from, ToolInvocation};
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod installed_tools;
+mod models;
+mod toolbox_update_app;
+
+/// Updater for the toolbox tools.
+///
+/// Checks the GitHub releases for newer versions of the installed tools, and installs them (or
+/// only reports them, with `--check-only`).
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<ToolboxUpdateArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!(
+                "{}: failed to start the async runtime: {}",
+                env!("CARGO_PKG_NAME"),
+                e
+            );
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    runtime.block_on(run_invocation(invocation))
+}
+
+async fn run_invocation(invocation: ToolInvocation<ToolboxUpdateArgs>) -> i32 {
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    invocation.print_runtime_info();
+
+    match toolbox_update_app::run(&invocation.args).await {
+        Ok(0) => EXIT_CODE_SUCCESS,
+        Ok(failed) => {
+            eprintln!("{}: {} update(s) failed", env!("CARGO_PKG_NAME"), failed);
+            EXIT_CODE_ERROR
+        }
+        Err(e) => {
+            eprintln!("{}: {:#}", env!("CARGO_PKG_NAME"), e);
+            error!("{:#}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
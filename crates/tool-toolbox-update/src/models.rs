@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+/// Environment variable with the repository to update from (`owner/name`), for forks.
+pub const REPOSITORY_ENV: &str = "TOOLBOX_UPDATE_REPOSITORY";
+
+/// Binaries of the toolbox, as installed by the build scripts.
+pub const KNOWN_TOOLS: [&str; 39] = [
+    "ai-chatbot",
+    "aiignore",
+    "archive",
+    "b64",
+    "cat",
+    "clip",
+    "csvn",
+    "dirdiff",
+    "distro-cc",
+    "eh-export",
+    "eh-read",
+    "eh-send",
+    "get-lines",
+    "gitignore",
+    "gitstat",
+    "guid",
+    "hashx",
+    "how",
+    "http",
+    "imgx",
+    "jwt",
+    "keyvault",
+    "lookup",
+    "mock",
+    "mqtt",
+    "netcatx",
+    "netquality",
+    "pingx",
+    "qrcode",
+    "regexlab",
+    "remove-zw",
+    "rtb",
+    "split",
+    "toolbox-update",
+    "touch",
+    "ts",
+    "unitconv",
+    "whisper",
+    "whurl",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolboxUpdateArgs {
+    /// Tools to check. Empty checks every known tool found in `install_dir`.
+    pub tools: Vec<String>,
+    pub check_only: bool,
+    /// Folder with the installed tools. `None` uses the folder of this binary.
+    pub install_dir: Option<PathBuf>,
+    pub repository: String,
+    pub include_prereleases: bool,
+}
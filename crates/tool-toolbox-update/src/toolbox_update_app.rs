@@ -0,0 +1,144 @@
+use crate::installed_tools::{binary_path, default_install_dir, read_installed_version};
+use crate::models::{ToolboxUpdateArgs, KNOWN_TOOLS};
+use anyhow::{bail, Result};
+use shared::selfupdate::binary_swap::remove_leftover_binary;
+use shared::selfupdate::github_releases::platform_asset_name;
+use shared::selfupdate::updater::{SelfUpdater, UpdateCheck};
+use shared::utils::table_output::Table;
+use std::path::Path;
+
+/// Checks the tools for updates and, unless `--check-only`, installs them.
+///
+/// Prints a table with the installed and latest version of each tool, and what happened to it.
+/// Returns how many updates failed.
+///
+/// # Errors
+/// Returns an error if no tool is installed in the folder, or the releases can't be fetched.
+pub async fn run(args: &ToolboxUpdateArgs) -> Result<usize> {
+    let install_dir = match &args.install_dir {
+        Some(dir) => dir.clone(),
+        None => default_install_dir()?,
+    };
+
+    let tools: Vec<String> = if args.tools.is_empty() {
+        KNOWN_TOOLS
+            .iter()
+            .filter(|tool| binary_path(&install_dir, tool).exists())
+            .map(|tool| tool.to_string())
+            .collect()
+    } else {
+        args.tools.clone()
+    };
+
+    if tools.is_empty() {
+        bail!("No toolbox tools found in {}", install_dir.display());
+    }
+
+    let updater = SelfUpdater::connect(&args.repository, args.include_prereleases).await?;
+
+    let mut table = Table::new(&["TOOL", "INSTALLED", "LATEST", "STATUS"])
+        .with_title(format!("Tools in {}", install_dir.display()));
+    let mut available = 0;
+    let mut failed = 0;
+
+    for tool in &tools {
+        let path = binary_path(&install_dir, tool);
+        if !path.exists() {
+            table.add_row(vec![
+                tool.clone(),
+                "-".into(),
+                "-".into(),
+                "not installed".into(),
+            ]);
+            continue;
+        }
+
+        remove_leftover_binary(&path);
+
+        let check = match read_installed_version(&path, tool)
+            .and_then(|version| updater.check(tool, &version))
+        {
+            Ok(check) => check,
+            Err(e) => {
+                table.add_row(vec![
+                    tool.clone(),
+                    "?".into(),
+                    "-".into(),
+                    format!("skipped: {}", e),
+                ]);
+                continue;
+            }
+        };
+
+        if check.is_update_available() {
+            available += 1;
+        }
+
+        let status = match update_status(&check, args.check_only) {
+            Some(status) => status,
+            None => match install_update(&updater, &check, &path).await {
+                Ok(status) => status,
+                Err(e) => {
+                    failed += 1;
+                    format!("failed: {:#}", e)
+                }
+            },
+        };
+
+        table.add_row(vec![
+            tool.clone(),
+            check.current_version.to_string(),
+            check
+                .latest_version()
+                .map(|version| version.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            status,
+        ]);
+    }
+
+    print!("{}", table.render_text());
+
+    if args.check_only && available > 0 {
+        println!(
+            "\n{} update(s) available. Run without --check-only to install them.",
+            available
+        );
+    }
+
+    Ok(failed)
+}
+
+/// Status of a tool that doesn't need downloading, or `None` when the update should be installed.
+fn update_status(check: &UpdateCheck, check_only: bool) -> Option<String> {
+    if check.latest.is_none() {
+        return Some("not released".to_string());
+    }
+
+    if !check.is_update_available() {
+        return Some("up to date".to_string());
+    }
+
+    if check.platform_asset().is_none() {
+        return Some(format!(
+            "no binary for this platform ({})",
+            platform_asset_name(&check.tool)
+        ));
+    }
+
+    check_only.then(|| "update available".to_string())
+}
+
+async fn install_update(updater: &SelfUpdater, check: &UpdateCheck, path: &Path) -> Result<String> {
+    eprintln!(
+        "Updating {} from {} to {}...",
+        check.tool,
+        check.current_version,
+        check
+            .latest_version()
+            .map(|version| version.to_string())
+            .unwrap_or_default()
+    );
+
+    let version = updater.update(check, path).await?;
+    Ok(format!("updated to {}", version))
+}
@@ -1,8 +1,9 @@
-use crate::models::{TouchArgs, TouchTimeWord};
+use crate::models::{TouchArgs, TouchTimeWord, VerifyFormat};
 use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use clap::{Arg, Command};
 use filetime::FileTime;
 use shared::command_line::cli_builder::CommandExt;
+use std::ffi::OsString;
 use std::io;
 
 /// Parses command-line arguments for the touch utility.
@@ -22,12 +23,15 @@ use std::io;
 /// - `-r, --reference`: Use reference file's timestamps
 /// - `-t`: Use formatted timestamp string
 /// - `--time`: Specify which time to change (access/modify)
-/// - `files`: List of files to touch
+/// - `-R, --recursive`: Also touch everything inside the folders
+/// - `--verify`: Re-read and print the timestamps after touching
+/// - `--verify-format`: Format of the verification (table/json)
+/// - `files`: List of files, folders, or glob patterns to touch
 ///
 /// # Errors
-/// Exits with error on invalid arguments, date parsing failures,
+/// Fails with the message to show on invalid arguments, date parsing failures,
 /// or reference file access issues.
-pub fn get_cli_arguments() -> TouchArgs {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<TouchArgs, String> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -36,7 +40,9 @@ pub fn get_cli_arguments() -> TouchArgs {
             Update the access and modification times of each FILE to the current time.\n\n\
             A FILE argument that does not exist is created empty, unless -c or -h is supplied.\n\n\
             A FILE argument string of - is handled specially and causes touch to change the times \
-            of the file associated with standard output.",
+            of the file associated with standard output.\n\n\
+            FILE arguments with wildcards (*, ?, [...], **) are expanded by touch itself, and only \
+            touch the files and folders that exist.",
         )
         .arg(
             Arg::new("access")
@@ -97,14 +103,34 @@ pub fn get_cli_arguments() -> TouchArgs {
                 .value_name("WORD")
                 .help("Specify which time to change: access, atime, use, modify, mtime"),
         )
+        .arg(
+            Arg::new("recursive")
+                .short('R')
+                .long("recursive")
+                .action(clap::ArgAction::SetTrue)
+                .help("Also touch the files and folders inside each folder, recursively"),
+        )
+        .arg(
+            Arg::new("verify")
+                .long("verify")
+                .action(clap::ArgAction::SetTrue)
+                .help("Re-read and print the access, modification, and change times of each file after touching it"),
+        )
+        .arg(
+            Arg::new("verify-format")
+                .long("verify-format")
+                .value_name("FORMAT")
+                .default_value("table")
+                .help("Format of the --verify output: table, json"),
+        )
         .arg(
             Arg::new("files")
                 .value_name("FILE")
                 .action(clap::ArgAction::Append)
                 .required(true)
-                .help("Files to touch"),
+                .help("Files to touch. Folders and glob patterns (e.g.: \"logs/**/*.log\") are also accepted"),
         )
-        .get_matches();
+        .get_matches_from(argv);
 
     let access = matches.get_flag("access");
     let no_create = matches.get_flag("no-create");
@@ -118,8 +144,7 @@ pub fn get_cli_arguments() -> TouchArgs {
             "access" | "atime" | "use" => TouchTimeWord::AccessOnly,
             "modify" | "mtime" => TouchTimeWord::ModifyOnly,
             _ => {
-                eprintln!("Invalid time specification: {}", time_str);
-                std::process::exit(1);
+                return Err(format!("Invalid time specification: {}", time_str));
             }
         },
         None => {
@@ -139,8 +164,7 @@ pub fn get_cli_arguments() -> TouchArgs {
         Some(date_str) => match parse_date_string(date_str) {
             Ok(date_filetype) => Some(date_filetype),
             Err(_) => {
-                eprintln!("Error parsing date string: {}", date_str);
-                std::process::exit(1);
+                return Err(format!("Error parsing date string: {}", date_str));
             }
         },
         _ => None,
@@ -150,8 +174,7 @@ pub fn get_cli_arguments() -> TouchArgs {
         Some(time_spec_str) => match parse_time_spec(time_spec_str) {
             Ok(time_spec_filetime) => Some(time_spec_filetime),
             Err(_) => {
-                eprintln!("Error parsing time-spec string: {}", time_spec_str);
-                std::process::exit(1);
+                return Err(format!("Error parsing time-spec string: {}", time_spec_str));
             }
         },
         _ => None,
@@ -160,21 +183,37 @@ pub fn get_cli_arguments() -> TouchArgs {
     let reference: Option<(FileTime, FileTime)> = match matches.get_one::<String>("reference") {
         Some(reference_str) => match get_reference_times(reference_str, no_dereference) {
             Ok((atime, mtime)) => Some((atime, mtime)),
-            Err(_) => {
-                eprintln!("Error parsing reference string: {}", reference_str);
-                std::process::exit(1);
+            Err(e) => {
+                return Err(format!(
+                    "Failed to get the times of the reference file '{}': {}",
+                    reference_str, e
+                ));
             }
         },
         _ => None,
     };
 
+    let recursive = matches.get_flag("recursive");
+    let verify = matches.get_flag("verify");
+
+    let verify_format = match matches.get_one::<String>("verify-format") {
+        Some(format_str) => match format_str.to_lowercase().as_str() {
+            "table" => VerifyFormat::Table,
+            "json" => VerifyFormat::Json,
+            _ => {
+                return Err(format!("Invalid verify format: {}", format_str));
+            }
+        },
+        None => VerifyFormat::Table,
+    };
+
     let files: Vec<String> = matches
         .get_many::<String>("files")
         .unwrap_or_default()
         .cloned()
         .collect();
 
-    TouchArgs {
+    Ok(TouchArgs {
         access,
         no_create,
         date,
@@ -185,7 +224,10 @@ pub fn get_cli_arguments() -> TouchArgs {
         time_spec,
         time,
         files,
-    }
+        recursive,
+        verify,
+        verify_format,
+    })
 }
 
 /// Validates command-line arguments for consistency and completeness.
@@ -0,0 +1,230 @@
+use crate::models::TouchArgs;
+use anyhow::{anyhow, Result};
+use globset::{GlobBuilder, GlobMatcher};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Returns true if the argument has wildcards (`*`, `?`, `[`). Patterns are expanded by touch
+/// itself, so quoted patterns (and shells that don't expand them, like cmd.exe) work the same way.
+///
+/// # Parameters
+/// - `file`: FILE argument as informed
+pub fn is_glob_pattern(file: &str) -> bool {
+    file.chars().any(|c| matches!(c, '*' | '?' | '['))
+}
+
+/// Expands a FILE argument into the paths to touch.
+///
+/// # Parameters
+/// - `file`: FILE argument: a path, "-" for stdout, or a glob pattern
+/// - `args`: Touch arguments (recursive and no-create options)
+///
+/// # Returns
+/// - `Ok(paths)`: Paths to touch, in order (a folder comes before its content)
+/// - `Err`: Invalid pattern, or a pattern that matched nothing (unless -c)
+///
+/// # Behavior
+/// - Existing paths and paths that don't exist (to be created) are used as-is
+/// - Patterns match files and folders (e.g.: `logs/**/*.log`), walking from the folder before
+///   the first wildcard. A pattern that matches nothing is an error, instead of creating a file
+///   named after it
+/// - With `--recursive`, folders are expanded into themselves and everything inside them.
+///   Symbolic links to folders are touched, but not followed
+pub fn expand_file_argument(file: &str, args: &TouchArgs) -> Result<Vec<String>> {
+    if file == "-" {
+        return Ok(vec![file.to_string()]);
+    }
+
+    let path = Path::new(file);
+    let matches = if path.exists() || path.is_symlink() || !is_glob_pattern(file) {
+        vec![path.to_path_buf()]
+    } else {
+        let matches = expand_glob(file)?;
+        if matches.is_empty() {
+            if args.no_create {
+                return Ok(Vec::new());
+            }
+            return Err(anyhow!("No files match the pattern: {}", file));
+        }
+        matches
+    };
+
+    let mut paths = Vec::new();
+    for path in matches {
+        if args.recursive && path.is_dir() && !path.is_symlink() {
+            paths.extend(walk_folder(&path));
+        } else {
+            paths.push(path);
+        }
+    }
+
+    Ok(paths
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect())
+}
+
+/// Folder and everything inside it, sorted by name, each folder before its content.
+fn walk_folder(folder: &Path) -> impl Iterator<Item = PathBuf> {
+    WalkDir::new(folder)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+}
+
+/// Files and folders matching the pattern, sorted by path.
+fn expand_glob(pattern: &str) -> Result<Vec<PathBuf>> {
+    let (root, relative_pattern) = split_glob_pattern(Path::new(pattern));
+
+    let matcher = build_matcher(&relative_pattern)?;
+
+    // Without `**`, a pattern can't match deeper than its own number of components.
+    let mut walker = WalkDir::new(&root).min_depth(1).sort_by_file_name();
+    if !relative_pattern.contains("**") {
+        walker = walker.max_depth(relative_pattern.split('/').count());
+    }
+
+    Ok(walker
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+            matcher.is_match(relative)
+        })
+        .map(|entry| entry.into_path())
+        .collect())
+}
+
+/// Splits `logs/2024/**/*.log` into the folder to walk (`logs/2024`) and the pattern relative to
+/// it (`**/*.log`).
+fn split_glob_pattern(pattern: &Path) -> (PathBuf, String) {
+    let mut root = PathBuf::new();
+    let mut pattern_parts = Vec::new();
+
+    for component in pattern.components() {
+        let part = component.as_os_str().to_string_lossy();
+        if pattern_parts.is_empty()
+            && !matches!(component, Component::Normal(_) if is_glob_pattern(&part))
+        {
+            root.push(component.as_os_str());
+        } else {
+            pattern_parts.push(part.into_owned());
+        }
+    }
+
+    if root.as_os_str().is_empty() {
+        root.push(".");
+    }
+
+    (root, pattern_parts.join("/"))
+}
+
+/// Patterns follow the case sensitivity of the platform's file system.
+fn build_matcher(pattern: &str) -> Result<GlobMatcher> {
+    Ok(GlobBuilder::new(pattern)
+        .case_insensitive(cfg!(windows))
+        .literal_separator(true)
+        .build()
+        .map_err(|e| anyhow!("Invalid glob pattern '{}': {}", pattern, e))?
+        .compile_matcher())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TouchTimeWord, VerifyFormat};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_args(recursive: bool, no_create: bool) -> TouchArgs {
+        TouchArgs {
+            access: false,
+            no_create,
+            date: None,
+            ignore: false,
+            no_dereference: false,
+            modify: false,
+            reference: None,
+            time_spec: None,
+            time: TouchTimeWord::AccessAndModify,
+            files: vec![],
+            recursive,
+            verify: false,
+            verify_format: VerifyFormat::Table,
+        }
+    }
+
+    fn create_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("logs/2024")).unwrap();
+        fs::write(temp_dir.path().join("logs/app.log"), "").unwrap();
+        fs::write(temp_dir.path().join("logs/notes.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("logs/2024/old.log"), "").unwrap();
+        temp_dir
+    }
+
+    fn names(paths: &[String], root: &Path) -> Vec<String> {
+        paths
+            .iter()
+            .map(|path| {
+                Path::new(path)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_glob_patterns() {
+        let temp_dir = create_tree();
+        let root = temp_dir.path();
+        let args = create_args(false, false);
+
+        let shallow = format!("{}/logs/*.log", root.display());
+        let paths = expand_file_argument(&shallow, &args).unwrap();
+        assert_eq!(names(&paths, root), vec!["logs/app.log"]);
+
+        let deep = format!("{}/logs/**/*.log", root.display());
+        let paths = expand_file_argument(&deep, &args).unwrap();
+        assert_eq!(
+            names(&paths, root),
+            vec!["logs/2024/old.log", "logs/app.log"]
+        );
+
+        let nothing = format!("{}/logs/*.csv", root.display());
+        assert!(expand_file_argument(&nothing, &args).is_err());
+        assert!(expand_file_argument(&nothing, &create_args(false, true))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_expand_folder_recursively() {
+        let temp_dir = create_tree();
+        let root = temp_dir.path();
+        let folder = root.join("logs").to_string_lossy().into_owned();
+
+        let paths = expand_file_argument(&folder, &create_args(true, false)).unwrap();
+        assert_eq!(
+            names(&paths, root),
+            vec![
+                "logs",
+                "logs/2024",
+                "logs/2024/old.log",
+                "logs/app.log",
+                "logs/notes.txt"
+            ]
+        );
+
+        let paths = expand_file_argument(&folder, &create_args(false, false)).unwrap();
+        assert_eq!(names(&paths, root), vec!["logs"]);
+
+        let missing = root.join("new.txt").to_string_lossy().into_owned();
+        let paths = expand_file_argument(&missing, &create_args(true, false)).unwrap();
+        assert_eq!(names(&paths, root), vec!["new.txt"]);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::cli_utils::{get_cli_arguments, validate_cli_arguments};
+use crate::file_targets::expand_file_argument;
+use crate::models::VerifyFormat;
+use crate::touch_app::touch_file;
+use crate::verify::{print_json, print_table, verify_file};
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod file_targets;
+mod models;
+mod touch_app;
+mod verify;
+
+/// Updates file access and modification times, creating files if they don't exist.
+///
+/// Mimics Unix `touch` command behavior by setting timestamps to current time
+/// or user-specified values. Processes multiple files sequentially, expanding glob patterns
+/// and, with `--recursive`, the content of folders.
+/// With `--verify`, re-reads the timestamps of each file and prints them afterwards.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+///
+/// # Returns
+/// - `Ok(())` on successful completion of all file operations
+/// - `Err(Box<dyn std::error::Error>)` on initialization failures
+///
+/// # Errors
+/// - Logging initialization failures
+/// - File creation or timestamp update errors for individual files
+/// - Invalid command-line arguments cause program termination
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    let args = match get_cli_arguments(argv.into_iter().map(Into::into).collect()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            return EXIT_CODE_ERROR;
+        }
+    };
+
+    validate_cli_arguments(&args);
+
+    let mut success = true;
+    let mut reports = Vec::new();
+    for argument in &args.files {
+        let files = match expand_file_argument(argument, &args) {
+            Ok(files) => files,
+            Err(e) => {
+                error!("Error touching '{}': {}", argument, e);
+                success = false;
+                continue;
+            }
+        };
+
+        for file in &files {
+            let applied = match touch_file(file, &args) {
+                Ok(applied) => applied,
+                Err(e) => {
+                    error!("Error touching '{}': {}", file, e);
+                    success = false;
+                    continue;
+                }
+            };
+
+            // Nothing to verify for stdout or for missing files skipped by -c.
+            if !args.verify || applied.is_none() {
+                continue;
+            }
+
+            match verify_file(file, &args, applied) {
+                Ok(report) => reports.push(report),
+                Err(e) => {
+                    error!("Error verifying '{}': {}", file, e);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    if args.verify {
+        match args.verify_format {
+            VerifyFormat::Table => print_table(&reports),
+            VerifyFormat::Json => {
+                if let Err(e) = print_json(&reports) {
+                    error!("{}", e);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    if !success {
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_returns_error_on_invalid_arguments() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("not_touched.txt");
+
+        let exit_code = run(["touch", "--time", "yesterday", file_path.to_str().unwrap()]);
+
+        assert_eq!(exit_code, EXIT_CODE_ERROR);
+        assert!(!file_path.exists());
+    }
+}
@@ -27,7 +27,10 @@ pub enum TouchTimeWord {
 /// - `reference`: Reference file timestamps (-r)
 /// - `time_spec`: Formatted time specification (-t)
 /// - `time`: Resolved timestamp update strategy (from -a, -m, --time)
-/// - `files`: List of target files
+/// - `files`: List of target files, folders, or glob patterns
+/// - `recursive`: Also touch everything inside the folders (-R)
+/// - `verify`: Re-read and print the timestamps after touching (--verify)
+/// - `verify_format`: How the verification is printed (--verify-format)
 pub struct TouchArgs {
     // Used during CLI parsing to resolve TouchTimeWord, not directly in runtime logic
     #[allow(dead_code)]
@@ -45,28 +48,71 @@ pub struct TouchArgs {
     pub time_spec: Option<FileTime>,
     pub time: TouchTimeWord,
     pub files: Vec<String>,
+    pub recursive: bool,
+    pub verify: bool,
+    pub verify_format: VerifyFormat,
+}
+
+/// Output format of the `--verify` report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerifyFormat {
+    Table,
+    Json,
+}
+
+/// Timestamps that were requested for a file, used to check if the filesystem applied them.
+///
+/// # Fields
+/// - `atime`: Access time sent to the filesystem
+/// - `mtime`: Modification time sent to the filesystem
+/// - `update_access`: Whether the access time was meant to change
+/// - `update_modify`: Whether the modification time was meant to change
+#[derive(Debug, Clone, Copy)]
+pub struct AppliedTimes {
+    pub atime: FileTime,
+    pub mtime: FileTime,
+    pub update_access: bool,
+    pub update_modify: bool,
+}
+
+/// Where the new timestamps come from.
+///
+/// # Variants
+/// - `Now`: Current time, read when each file is touched
+/// - `Fixed`: Same time for access and modification (-d, -t)
+/// - `Reference`: Access and modification times of another file (-r)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeSource {
+    Now,
+    Fixed(FileTime),
+    Reference { atime: FileTime, mtime: FileTime },
+}
+
+impl TimeSource {
+    /// Returns the (access, modification) times to apply, or `None` to use the current time.
+    pub fn times(&self) -> Option<(FileTime, FileTime)> {
+        match self {
+            TimeSource::Now => None,
+            TimeSource::Fixed(time) => Some((*time, *time)),
+            TimeSource::Reference { atime, mtime } => Some((*atime, *mtime)),
+        }
+    }
 }
 
 impl TouchArgs {
-    /// Returns the appropriate file time based on available time sources.
+    /// Returns the source of the new timestamps.
     ///
     /// Checks time sources in priority order: date, time_spec, reference.
-    /// Returns the same time for both accesses and modify when using date or time_spec.
-    ///
-    /// # Returns
-    /// - `Some((access_time, modify_time))`: Time source found
-    /// - `None`: No time source specified, use current time
-    pub fn get_current_filetime(&self) -> Option<(FileTime, FileTime)> {
-        if self.date.is_some() {
-            let date_filetime = self.date.unwrap();
-            Some((date_filetime, date_filetime))
-        } else if self.time_spec.is_some() {
-            let time_spec_filetime = self.time_spec.unwrap();
-            Some((time_spec_filetime, time_spec_filetime))
-        } else if self.reference.is_some() {
-            Some(self.reference.unwrap())
+    /// Falls back to the current time when none was specified.
+    pub fn time_source(&self) -> TimeSource {
+        if let Some(date) = self.date {
+            TimeSource::Fixed(date)
+        } else if let Some(time_spec) = self.time_spec {
+            TimeSource::Fixed(time_spec)
+        } else if let Some((atime, mtime)) = self.reference {
+            TimeSource::Reference { atime, mtime }
         } else {
-            None
+            TimeSource::Now
         }
     }
 }
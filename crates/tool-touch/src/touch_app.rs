@@ -1,4 +1,4 @@
-use crate::models::{TouchArgs, TouchTimeWord};
+use crate::models::{AppliedTimes, TouchArgs, TouchTimeWord};
 use anyhow::{Context, Result};
 use filetime::{set_file_times, set_symlink_file_times, FileTime};
 use shared::system::get_full_filepath_from_string::get_full_filepath_from_string;
@@ -43,19 +43,20 @@ fn create_file_if_needed(file: &str, no_create: bool) -> Result<bool> {
 /// - `args`: Touch arguments containing timestamp and behavior options
 ///
 /// # Returns
-/// - `Ok(())` on successful completion
+/// - `Ok(Some(times))` with the timestamps sent to the filesystem
+/// - `Ok(None)` when nothing was touched (stdout, or missing file with `-c`)
 /// - `Err`: File creation, timestamp retrieval, or update failures
 ///
 /// # Behavior
 /// - Stdout ("-") is treated as no-op
 /// - Updates both timestamps by default unless -a or -m specified
 /// - Uses current time or user-specified time/reference file
-pub fn touch_file(file: &str, args: &TouchArgs) -> Result<()> {
+pub fn touch_file(file: &str, args: &TouchArgs) -> Result<Option<AppliedTimes>> {
     // Handle stdout specially
     if file == "-" {
         // On most systems, touching stdout is a no-op
         // We just return success without error like the real touch command
-        return Ok(());
+        return Ok(None);
     }
 
     // Create a file if needed
@@ -65,10 +66,10 @@ pub fn touch_file(file: &str, args: &TouchArgs) -> Result<()> {
 
     if !file_exists && args.no_create {
         // File doesn't exist, and we shouldn't create it, so it is ok.
-        return Ok(());
+        return Ok(None);
     }
 
-    let times = args.get_current_filetime();
+    let times = args.time_source().times();
 
     // Determine which timestamps to update based on the time field from TouchArgs
     // This properly respects the --time option behavior
@@ -91,7 +92,12 @@ pub fn touch_file(file: &str, args: &TouchArgs) -> Result<()> {
 
     update_file_times(args, &file_obj, final_atime, final_mtime)?;
 
-    Ok(())
+    Ok(Some(AppliedTimes {
+        atime: final_atime,
+        mtime: final_mtime,
+        update_access,
+        update_modify,
+    }))
 }
 
 /// Determines which timestamps should be updated based on TouchArgs configuration.
@@ -233,7 +239,7 @@ fn update_file_times(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{TouchArgs, TouchTimeWord};
+    use crate::models::{TouchArgs, TouchTimeWord, VerifyFormat};
     use filetime::FileTime;
     use std::fs;
     use tempfile::TempDir;
@@ -251,6 +257,9 @@ mod tests {
             time_spec: None,
             time: TouchTimeWord::AccessAndModify,
             files: vec!["test.txt".to_string()],
+            recursive: false,
+            verify: false,
+            verify_format: VerifyFormat::Table,
         }
     }
 
@@ -0,0 +1,340 @@
+use crate::models::{AppliedTimes, TouchArgs};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use filetime::FileTime;
+use serde::Serialize;
+use shared::system::get_full_filepath_from_string::get_full_filepath_from_string;
+use std::fs::Metadata;
+use std::path::Path;
+
+/// Largest difference accepted between a requested and a stored timestamp.
+/// Covers filesystems that store times with coarse precision (e.g.: 2 seconds on FAT).
+const ATIME_TOLERANCE_SECS: i64 = 2;
+
+/// Timestamps of a file, re-read from the filesystem after touching it.
+///
+/// # Fields
+/// - `file`: File as informed by the user
+/// - `accessed`/`modified`: Access and modification times
+/// - `changed`: Status change time (ctime) on Unix, creation time on Windows
+/// - `atime_applied`: Whether the requested access time was stored (None when not requested)
+/// - `atime_mount_option`: `noatime`/`relatime`/`strictatime` of the mount (Linux only)
+/// - `warnings`: Things that may surprise the user, like ignored atime updates
+#[derive(Debug, Serialize)]
+pub struct FileTimesReport {
+    pub file: String,
+    pub accessed: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    pub changed: Option<DateTime<Utc>>,
+    pub atime_applied: Option<bool>,
+    pub atime_mount_option: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Re-reads the timestamps of a touched file and checks them against what was requested.
+///
+/// # Parameters
+/// - `file`: File path as informed by the user
+/// - `args`: Touch arguments (for the dereference option)
+/// - `applied`: Timestamps sent to the filesystem, or None if the file wasn't touched
+///
+/// # Errors
+/// Returns an error if the file metadata can't be read.
+pub fn verify_file(
+    file: &str,
+    args: &TouchArgs,
+    applied: Option<AppliedTimes>,
+) -> Result<FileTimesReport> {
+    let path = get_full_filepath_from_string(&file.to_string());
+
+    let metadata = if args.no_dereference {
+        std::fs::symlink_metadata(&path)
+    } else {
+        std::fs::metadata(&path)
+    }
+    .context(format!("Failed to read metadata for: [{}]", path.display()))?;
+
+    let accessed = FileTime::from_last_access_time(&metadata);
+    let atime_mount_option = atime_mount_option(&path);
+    let mut warnings = Vec::new();
+
+    let atime_applied = applied
+        .filter(|times| times.update_access)
+        .map(|times| times_match(times.atime, accessed));
+
+    if atime_applied == Some(false) {
+        if let Some(times) = applied {
+            warnings.push(format!(
+                "Access time was not applied (requested {}, found {}). The filesystem may ignore or round access time updates.",
+                format_local(to_utc(times.atime)),
+                format_local(to_utc(accessed))
+            ));
+        }
+    }
+
+    let modified = FileTime::from_last_modification_time(&metadata);
+    if let Some(times) = applied.filter(|times| times.update_modify) {
+        if !times_match(times.mtime, modified) {
+            warnings.push(format!(
+                "Modification time was not applied (requested {}, found {}).",
+                format_local(to_utc(times.mtime)),
+                format_local(to_utc(modified))
+            ));
+        }
+    }
+
+    let touched_access = applied.map(|times| times.update_access).unwrap_or(false);
+    if let (true, Some(option)) = (touched_access, atime_mount_option.as_deref()) {
+        if let Some(warning) = mount_option_warning(option) {
+            warnings.push(warning.to_string());
+        }
+    }
+
+    Ok(FileTimesReport {
+        file: file.to_string(),
+        accessed: to_utc(accessed),
+        modified: to_utc(modified),
+        changed: status_change_time(&metadata).map(to_utc),
+        atime_applied,
+        atime_mount_option,
+        warnings,
+    })
+}
+
+/// Prints the reports as an aligned table (local time), followed by the warnings.
+pub fn print_table(reports: &[FileTimesReport]) {
+    let headers = ["FILE", "ACCESSED", "MODIFIED", CHANGED_LABEL];
+    let rows: Vec<[String; 4]> = reports
+        .iter()
+        .map(|report| {
+            [
+                report.file.clone(),
+                format_local(report.accessed),
+                format_local(report.modified),
+                report
+                    .changed
+                    .map(format_local)
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: [&str; 4]| {
+        println!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {:<w3$}",
+            cells[0],
+            cells[1],
+            cells[2],
+            cells[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3]
+        );
+    };
+
+    print_row(headers);
+    for row in &rows {
+        print_row([&row[0], &row[1], &row[2], &row[3]]);
+    }
+
+    for report in reports {
+        for warning in &report.warnings {
+            eprintln!("Warning ({}): {}", report.file, warning);
+        }
+    }
+}
+
+/// Prints the reports as a JSON array (UTC, RFC3339 with nanoseconds).
+///
+/// # Errors
+/// Returns an error if serialization fails.
+pub fn print_json(reports: &[FileTimesReport]) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(reports).context("Failed to serialize the verification")?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn times_match(expected: FileTime, actual: FileTime) -> bool {
+    (expected.unix_seconds() - actual.unix_seconds()).abs() <= ATIME_TOLERANCE_SECS
+}
+
+fn mount_option_warning(option: &str) -> Option<&'static str> {
+    match option {
+        "noatime" => Some(
+            "Mounted with noatime: reading the file won't update its access time, so it will keep the value set now.",
+        ),
+        "relatime" => Some(
+            "Mounted with relatime: reading the file only updates its access time when it's older than the modification/change time or more than 24h old.",
+        ),
+        _ => None,
+    }
+}
+
+fn to_utc(time: FileTime) -> DateTime<Utc> {
+    DateTime::from_timestamp(time.unix_seconds(), time.nanoseconds()).unwrap_or_default()
+}
+
+fn format_local(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S%.3f")
+        .to_string()
+}
+
+#[cfg(unix)]
+const CHANGED_LABEL: &str = "CHANGED (CTIME)";
+
+#[cfg(not(unix))]
+const CHANGED_LABEL: &str = "CREATED";
+
+#[cfg(unix)]
+fn status_change_time(metadata: &Metadata) -> Option<FileTime> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(FileTime::from_unix_time(
+        metadata.ctime(),
+        metadata.ctime_nsec() as u32,
+    ))
+}
+
+#[cfg(not(unix))]
+fn status_change_time(metadata: &Metadata) -> Option<FileTime> {
+    FileTime::from_creation_time(metadata)
+}
+
+#[cfg(target_os = "linux")]
+fn atime_mount_option(path: &Path) -> Option<String> {
+    let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+    let path = path.canonicalize().ok()?;
+    find_atime_mount_option(&mounts, &path)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn atime_mount_option(_path: &Path) -> Option<String> {
+    None
+}
+
+/// Finds the mount point that holds `path` (longest match) and returns its atime option.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn find_atime_mount_option(mounts: &str, path: &Path) -> Option<String> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?.replace("\\040", " ");
+            let options = fields.nth(1)?;
+            Some((mount_point, options.to_string()))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .and_then(|(_, options)| {
+            options
+                .split(',')
+                .find(|option| matches!(*option, "noatime" | "relatime" | "strictatime"))
+                .map(str::to_string)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TouchTimeWord, VerifyFormat};
+    use std::fs;
+    use tempfile::TempDir;
+
+    const MOUNTS: &str = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+/dev/sdb1 /data ext4 rw,noatime 0 0
+/dev/sdc1 /data/my\\040files xfs rw,strictatime 0 0
+tmpfs /tmp tmpfs rw,nosuid 0 0
+";
+
+    fn create_args() -> TouchArgs {
+        TouchArgs {
+            access: false,
+            no_create: false,
+            date: None,
+            ignore: false,
+            no_dereference: false,
+            modify: false,
+            reference: None,
+            time_spec: None,
+            time: TouchTimeWord::AccessAndModify,
+            files: vec![],
+            recursive: false,
+            verify: true,
+            verify_format: VerifyFormat::Table,
+        }
+    }
+
+    #[test]
+    fn test_find_atime_mount_option_uses_longest_mount_point() {
+        let option = |path: &str| find_atime_mount_option(MOUNTS, Path::new(path));
+
+        assert_eq!(option("/home/user/file.txt"), Some("relatime".to_string()));
+        assert_eq!(option("/data/file.txt"), Some("noatime".to_string()));
+        assert_eq!(
+            option("/data/my files/a.txt"),
+            Some("strictatime".to_string())
+        );
+        assert_eq!(option("/tmp/a.txt"), None);
+    }
+
+    #[test]
+    fn test_times_match_with_tolerance() {
+        let expected = FileTime::from_unix_time(1_700_000_000, 0);
+
+        assert!(times_match(
+            expected,
+            FileTime::from_unix_time(1_700_000_001, 0)
+        ));
+        assert!(!times_match(
+            expected,
+            FileTime::from_unix_time(1_700_000_100, 0)
+        ));
+    }
+
+    #[test]
+    fn test_verify_file_reports_mismatched_access_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("verify.txt");
+        fs::write(&file_path, "test").unwrap();
+        let file = file_path.to_str().unwrap();
+
+        let stored = FileTime::from_unix_time(1_700_000_000, 0);
+        filetime::set_file_times(&file_path, stored, stored).unwrap();
+
+        let requested = AppliedTimes {
+            atime: FileTime::from_unix_time(1_800_000_000, 0),
+            mtime: stored,
+            update_access: true,
+            update_modify: false,
+        };
+
+        let report = verify_file(file, &create_args(), Some(requested)).unwrap();
+
+        assert_eq!(report.modified.timestamp(), 1_700_000_000);
+        assert_eq!(report.atime_applied, Some(false));
+        assert!(report.warnings[0].starts_with("Access time was not applied"));
+    }
+
+    #[test]
+    fn test_verify_file_without_touch_has_no_atime_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("untouched.txt");
+        fs::write(&file_path, "test").unwrap();
+
+        let report = verify_file(file_path.to_str().unwrap(), &create_args(), None).unwrap();
+
+        assert_eq!(report.atime_applied, None);
+        assert!(report.warnings.is_empty());
+    }
+}
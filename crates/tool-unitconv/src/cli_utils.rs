@@ -0,0 +1,116 @@
+use crate::models::UnitConvArgs;
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use shared::command_line::cli_builder::CommandExt;
+use shared::command_line::tool_cli::{GlobalArgs, ToolCli};
+
+const DEFAULT_PRECISION: usize = 6;
+
+impl ToolCli for UnitConvArgs {
+    const DISPLAY_NAME: &'static str = "Unit Converter";
+
+    fn command() -> Command {
+        Command::new(env!("CARGO_PKG_NAME"))
+            .add_basic_metadata(
+                env!("CARGO_PKG_VERSION"),
+                env!("CARGO_PKG_DESCRIPTION"),
+                "Converts storage sizes, durations, temperatures, lengths, and number bases. \
+                Write the conversion as an expression: \"512MiB to MB\", \"90 min in h\", \
+                \"0xff to bin\". Without a target, the value is converted to every unit of its \
+                kind (or to every base).",
+            )
+            .arg(
+                Arg::new("expression")
+                    .value_name("EXPRESSION")
+                    .num_args(1..)
+                    .allow_negative_numbers(true)
+                    .help("What to convert (e.g.: 512MiB to MB). Can be quoted or not"),
+            )
+            .arg(
+                Arg::new("precision")
+                    .long("precision")
+                    .short('p')
+                    .value_name("DIGITS")
+                    .value_parser(clap::value_parser!(usize))
+                    .help(format!(
+                        "Maximum number of decimal places. (Default: {})",
+                        DEFAULT_PRECISION
+                    )),
+            )
+            .arg(
+                Arg::new("json")
+                    .long("json")
+                    .short('j')
+                    .action(ArgAction::SetTrue)
+                    .help("Prints the result as JSON. (Default: false)"),
+            )
+            .arg(
+                Arg::new("list")
+                    .long("list")
+                    .short('l')
+                    .action(ArgAction::SetTrue)
+                    .help("Lists the supported units and number bases. (Default: false)"),
+            )
+    }
+
+    fn from_matches(matches: &ArgMatches, _global: &GlobalArgs) -> Result<Self> {
+        let expression = matches
+            .get_many::<String>("expression")
+            .map(|words| words.cloned().collect::<Vec<_>>().join(" "));
+
+        Ok(UnitConvArgs {
+            expression,
+            precision: matches
+                .get_one::<usize>("precision")
+                .copied()
+                .unwrap_or(DEFAULT_PRECISION),
+            json: matches.get_flag("json"),
+            list: matches.get_flag("list"),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.expression.is_none() && !self.list {
+            bail!("Inform what to convert (e.g.: unitconv \"512MiB to MB\"), or use --list");
+        }
+
+        if self.precision > 15 {
+            bail!("--precision can't be more than 15 (the limit of 64-bit floats)");
+        }
+
+        Ok(())
+    }
+
+    fn runtime_info(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                "Expression".to_string(),
+                self.expression.clone().unwrap_or_else(|| "-".to_string()),
+            ),
+            ("Precision".to_string(), self.precision.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::command_line::tool_cli::try_parse_tool_cli_from;
+
+    fn parse(argv: &[&str]) -> Result<UnitConvArgs> {
+        try_parse_tool_cli_from::<UnitConvArgs, _, _>(argv.iter().copied()).map(|i| i.args)
+    }
+
+    #[test]
+    fn test_parse_expression_words() {
+        let quoted = parse(&["unitconv", "512MiB to MB", "-j"]).unwrap();
+        assert_eq!(quoted.expression.as_deref(), Some("512MiB to MB"));
+        assert!(quoted.json);
+
+        let words = parse(&["unitconv", "-40", "C", "to", "F"]).unwrap();
+        assert_eq!(words.expression.as_deref(), Some("-40 C to F"));
+
+        assert!(parse(&["unitconv"]).is_err());
+        assert!(parse(&["unitconv", "--list"]).unwrap().list);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::models::{Conversion, ConversionOutput, ConvertedValue, NumberBase};
+use crate::units::{units_of, Dimension};
+
+/// Runs the conversion. Without a target, converts to every unit of the dimension (or to every
+/// base).
+///
+/// # Parameters
+/// - `input`: Expression, as informed (echoed in the output)
+/// - `precision`: Maximum number of decimal places. Trailing zeros are dropped
+pub fn convert(input: &str, conversion: &Conversion, precision: usize) -> ConversionOutput {
+    match conversion {
+        Conversion::Unit { value, from, to } => {
+            let base_value = from.normalize(*value);
+            // Temperature offsets leave floating point noise (32 F is 1e-14 C), so they are
+            // always rounded. Other tiny results are real, and kept.
+            let keep_tiny = from.dimension != Dimension::Temperature;
+            let targets: Vec<_> = match to {
+                Some(to) => vec![*to],
+                None => units_of(from.dimension).collect(),
+            };
+
+            ConversionOutput {
+                input: input.to_string(),
+                dimension: from.dimension.name().to_string(),
+                results: targets
+                    .into_iter()
+                    .map(|unit| {
+                        let converted = round(unit.denormalize(base_value), precision, keep_tiny);
+                        ConvertedValue {
+                            unit: unit.symbol.to_string(),
+                            value: Some(converted),
+                            text: format_number(converted, precision),
+                        }
+                    })
+                    .collect(),
+            }
+        }
+        Conversion::Base { value, target } => {
+            let targets = match target {
+                Some(target) => vec![*target],
+                None => NumberBase::ALL.to_vec(),
+            };
+
+            ConversionOutput {
+                input: input.to_string(),
+                dimension: "number base".to_string(),
+                results: targets
+                    .into_iter()
+                    .map(|base| ConvertedValue {
+                        unit: base.name().to_string(),
+                        value: None,
+                        text: format_in_base(*value, base),
+                    })
+                    .collect(),
+            }
+        }
+    }
+}
+
+/// Rounds to `precision` decimal places. With `keep_tiny`, values too small to show at that
+/// precision are kept as-is (they are printed in scientific notation).
+fn round(value: f64, precision: usize, keep_tiny: bool) -> f64 {
+    let scale = 10f64.powi(precision as i32);
+    let rounded = (value * scale).round() / scale;
+
+    if !rounded.is_finite() || (keep_tiny && rounded == 0.0 && value != 0.0) {
+        return value;
+    }
+
+    // Adding 0 turns -0 into 0.
+    rounded + 0.0
+}
+
+/// Plain decimals, without trailing zeros (`536.870912`, `1024`), or scientific notation for
+/// values too small for the precision (`3.170979e-17`).
+pub fn format_number(value: f64, precision: usize) -> String {
+    let smallest = 10f64.powi(-(precision as i32));
+
+    if value != 0.0 && value.abs() < smallest {
+        let text = format!("{:.*e}", precision, value);
+        return match text.split_once('e') {
+            Some((mantissa, exponent)) => format!("{}e{}", trim_decimals(mantissa), exponent),
+            None => text,
+        };
+    }
+
+    trim_decimals(&format!("{:.*}", precision, value))
+}
+
+fn trim_decimals(text: &str) -> String {
+    if !text.contains('.') {
+        return text.to_string();
+    }
+
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn format_in_base(value: u128, base: NumberBase) -> String {
+    match base {
+        NumberBase::Binary => format!("0b{:b}", value),
+        NumberBase::Octal => format!("0o{:o}", value),
+        NumberBase::Decimal => value.to_string(),
+        NumberBase::Hexadecimal => format!("0x{:x}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::parse_expression;
+
+    fn convert_text(expression: &str) -> Vec<String> {
+        let conversion = parse_expression(expression).unwrap();
+        convert(expression, &conversion, 6)
+            .results
+            .into_iter()
+            .map(|result| format!("{} {}", result.text, result.unit))
+            .collect()
+    }
+
+    #[test]
+    fn test_convert_units() {
+        assert_eq!(convert_text("512MiB to MB"), vec!["536.870912 MB"]);
+        assert_eq!(convert_text("1 GB to GiB"), vec!["0.931323 GiB"]);
+        assert_eq!(convert_text("100 Mb to MB"), vec!["12.5 MB"]);
+        assert_eq!(convert_text("90 min to h"), vec!["1.5 h"]);
+        assert_eq!(convert_text("100 C to F"), vec!["212 F"]);
+        assert_eq!(convert_text("32 F to C"), vec!["0 C"]);
+        assert_eq!(convert_text("-40 C to F"), vec!["-40 F"]);
+        assert_eq!(convert_text("6 ft to cm"), vec!["182.88 cm"]);
+        assert_eq!(convert_text("1 ns to y"), vec!["3.170979e-17 y"]);
+
+        assert_eq!(convert_text("0 C").len(), 3);
+    }
+
+    #[test]
+    fn test_convert_bases() {
+        assert_eq!(
+            convert_text("0xff"),
+            vec!["0b11111111 bin", "0o377 oct", "255 dec", "0xff hex"]
+        );
+        assert_eq!(convert_text("1010 bin to hex"), vec!["0xa hex"]);
+    }
+}
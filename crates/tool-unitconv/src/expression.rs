@@ -0,0 +1,189 @@
+use crate::models::{Conversion, NumberBase};
+use crate::units::find_unit;
+use anyhow::{anyhow, bail, Result};
+
+/// Words between the value and the target unit: `512MiB to MB`, `3 ft in cm`, `255 as hex`.
+const CONNECTORS: [&str; 4] = ["to", "in", "as", "->"];
+
+/// Parses an expression: a value, its unit (or number base), and optionally the target unit.
+///
+/// # Examples of valid expressions
+/// - `512MiB to MB`, `512 MiB to MB`, `1.5 h in min`, `100 C to F`
+/// - `90 min` (no target: converts to every unit of the dimension)
+/// - `0xff to bin`, `255 to hex`, `1010 bin to dec`, `0o755` (no target: every base)
+pub fn parse_expression(expression: &str) -> Result<Conversion> {
+    let tokens = tokenize(expression);
+
+    let (source, target) = match tokens.as_slice() {
+        [] => bail!("Inform what to convert (e.g.: \"512MiB to MB\")"),
+        [.., connector, target] if tokens.len() >= 3 && is_connector(connector) => {
+            (&tokens[..tokens.len() - 2], Some(target.as_str()))
+        }
+        _ => (tokens.as_slice(), None),
+    };
+
+    let (value, source_unit) = match source {
+        [value] => (value.as_str(), None),
+        [value, unit] => (value.as_str(), Some(unit.as_str())),
+        _ => bail!("Invalid expression: {}", expression),
+    };
+
+    let target_base = target.and_then(NumberBase::from_name);
+    let source_base = source_unit.and_then(NumberBase::from_name);
+
+    // Number bases: a prefixed number (0x, 0o, 0b), a number followed by its base, or any
+    // integer with a base as the target.
+    if source_base.is_some() || target_base.is_some() || has_base_prefix(value) {
+        if let Some(unit) = source_unit.filter(|_| source_base.is_none()) {
+            bail!("{} is not a number base (bin, oct, dec, hex)", unit);
+        }
+        if let Some(target) = target.filter(|_| target_base.is_none()) {
+            bail!("{} is not a number base (bin, oct, dec, hex)", target);
+        }
+
+        return Ok(Conversion::Base {
+            value: parse_integer(value, source_base)?,
+            target: target_base,
+        });
+    }
+
+    let source_unit =
+        source_unit.ok_or_else(|| anyhow!("Inform the unit of {} (e.g.: 512MiB)", value))?;
+    let from = find_unit(source_unit)?;
+    let to = target.map(find_unit).transpose()?;
+
+    if let Some(to) = to {
+        if to.dimension != from.dimension {
+            bail!(
+                "Can't convert {} ({}) to {} ({})",
+                from.symbol,
+                from.dimension.name(),
+                to.symbol,
+                to.dimension.name()
+            );
+        }
+    }
+
+    let value = value
+        .replace('_', "")
+        .parse::<f64>()
+        .map_err(|_| anyhow!("Invalid number: {}", value))?;
+
+    Ok(Conversion::Unit { value, from, to })
+}
+
+/// Splits on whitespace, and the value from its unit when they are together (`512MiB`).
+fn tokenize(expression: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+
+    for (index, word) in expression.split_whitespace().enumerate() {
+        if index == 0 && !has_base_prefix(word) && !is_decimal_number(word) {
+            if let Some(split) = word.find(|c: char| c.is_alphabetic() || c == '°' || c == '"') {
+                if split > 0 && is_decimal_number(&word[..split]) {
+                    tokens.push(word[..split].to_string());
+                    tokens.push(word[split..].to_string());
+                    continue;
+                }
+            }
+        }
+        tokens.push(word.to_string());
+    }
+
+    tokens
+}
+
+fn is_connector(word: &str) -> bool {
+    CONNECTORS
+        .iter()
+        .any(|connector| connector.eq_ignore_ascii_case(word))
+}
+
+fn is_decimal_number(text: &str) -> bool {
+    text.replace('_', "").parse::<f64>().is_ok()
+}
+
+fn has_base_prefix(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    ["0x", "0o", "0b"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix) && lower.len() > 2)
+}
+
+/// Integer in the given base, or in the base of its prefix (`0x`, `0o`, `0b`), or decimal.
+/// `_` can be used as a digit separator (`0b1111_0000`).
+fn parse_integer(text: &str, base: Option<NumberBase>) -> Result<u128> {
+    let clean = text.replace('_', "");
+    let lower = clean.to_lowercase();
+
+    let (digits, radix) = match (base, lower.get(..2)) {
+        (Some(base), Some(prefix)) if prefix == base.prefix() => (&clean[2..], base.radix()),
+        (Some(base), _) => (clean.as_str(), base.radix()),
+        (None, Some("0x")) => (&clean[2..], 16),
+        (None, Some("0o")) => (&clean[2..], 8),
+        (None, Some("0b")) => (&clean[2..], 2),
+        (None, _) => (clean.as_str(), 10),
+    };
+
+    u128::from_str_radix(digits, radix).map_err(|_| {
+        anyhow!(
+            "Invalid base {} number: {}. Only non-negative integers can change base",
+            radix,
+            text
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unit_expressions() {
+        let Conversion::Unit { value, from, to } = parse_expression("512MiB to MB").unwrap() else {
+            panic!("Expected a unit conversion");
+        };
+        assert_eq!(value, 512.0);
+        assert_eq!(from.symbol, "MiB");
+        assert_eq!(to.unwrap().symbol, "MB");
+
+        let Conversion::Unit { from, to, .. } = parse_expression("5 in to cm").unwrap() else {
+            panic!("Expected a unit conversion");
+        };
+        assert_eq!((from.symbol, to.unwrap().symbol), ("in", "cm"));
+
+        let Conversion::Unit { from, to, .. } = parse_expression("90 min").unwrap() else {
+            panic!("Expected a unit conversion");
+        };
+        assert_eq!((from.symbol, to), ("min", None));
+
+        assert!(parse_expression("5 MB to km").is_err());
+        assert!(parse_expression("512").is_err());
+    }
+
+    #[test]
+    fn test_parse_base_expressions() {
+        assert!(matches!(
+            parse_expression("0xff to bin").unwrap(),
+            Conversion::Base {
+                value: 255,
+                target: Some(NumberBase::Binary)
+            }
+        ));
+        assert!(matches!(
+            parse_expression("1010 bin to dec").unwrap(),
+            Conversion::Base {
+                value: 10,
+                target: Some(NumberBase::Decimal)
+            }
+        ));
+        assert!(matches!(
+            parse_expression("0o755").unwrap(),
+            Conversion::Base {
+                value: 493,
+                target: None
+            }
+        ));
+        assert!(parse_expression("-5 to hex").is_err());
+        assert!(parse_expression("0xff to MB").is_err());
+    }
+}
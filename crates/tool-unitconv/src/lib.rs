@@ -0,0 +1,65 @@
+use crate::models::UnitConvArgs;
+use shared::command_line::tool_cli::parse_tool_cli_from;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+use tracing::error;
+
+mod cli_utils;
+mod converter;
+mod expression;
+mod models;
+mod unitconv_app;
+mod units;
+
+/// Unit and number base converter.
+///
+/// Parses the expression, converts it, and prints the result (as text or JSON).
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let invocation = match parse_tool_cli_from::<UnitConvArgs, _, _>(argv) {
+        Ok(invocation) => invocation,
+        Err(exit_code) => return exit_code,
+    };
+
+    initialize_log(env!("CARGO_PKG_NAME"), LogLevel::Error);
+
+    // JSON is meant to be piped, so the header would get in the way.
+    if !invocation.args.json {
+        invocation.print_runtime_info();
+    }
+
+    if let Err(e) = unitconv_app::run(&invocation.args) {
+        eprintln!("{}: {}", env!("CARGO_PKG_NAME"), e);
+        error!("{}", e);
+        return EXIT_CODE_ERROR;
+    }
+
+    EXIT_CODE_SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_returns_the_exit_code() {
+        assert_eq!(
+            run(["unitconv", "--quiet", "1 KiB to B"]),
+            EXIT_CODE_SUCCESS
+        );
+        assert_eq!(run(["unitconv", "--quiet", "1 KiB to kg"]), EXIT_CODE_ERROR);
+        assert_eq!(
+            run(["unitconv", "--precision", "16", "1 KiB to B"]),
+            EXIT_CODE_ERROR
+        );
+        assert_eq!(run(["unitconv", "--no-such-flag"]), 2);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::units::Unit;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitConvArgs {
+    pub expression: Option<String>,
+    pub precision: usize,
+    pub json: bool,
+    pub list: bool,
+}
+
+/// What an expression asks for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conversion {
+    /// A value in a unit, to another unit of the same dimension (or to all of them, without a
+    /// target).
+    Unit {
+        value: f64,
+        from: &'static Unit,
+        to: Option<&'static Unit>,
+    },
+    /// An integer, to another base (or to all of them, without a target).
+    Base {
+        value: u128,
+        target: Option<NumberBase>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberBase {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+impl NumberBase {
+    pub const ALL: [NumberBase; 4] = [
+        NumberBase::Binary,
+        NumberBase::Octal,
+        NumberBase::Decimal,
+        NumberBase::Hexadecimal,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "bin" | "binary" => Some(NumberBase::Binary),
+            "oct" | "octal" => Some(NumberBase::Octal),
+            "dec" | "decimal" => Some(NumberBase::Decimal),
+            "hex" | "hexadecimal" => Some(NumberBase::Hexadecimal),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NumberBase::Binary => "bin",
+            NumberBase::Octal => "oct",
+            NumberBase::Decimal => "dec",
+            NumberBase::Hexadecimal => "hex",
+        }
+    }
+
+    pub fn radix(&self) -> u32 {
+        match self {
+            NumberBase::Binary => 2,
+            NumberBase::Octal => 8,
+            NumberBase::Decimal => 10,
+            NumberBase::Hexadecimal => 16,
+        }
+    }
+
+    /// Prefix of the literals in this base. Decimal has none.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            NumberBase::Binary => "0b",
+            NumberBase::Octal => "0o",
+            NumberBase::Decimal => "",
+            NumberBase::Hexadecimal => "0x",
+        }
+    }
+}
+
+/// Result of a conversion, as printed with `--json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConversionOutput {
+    pub input: String,
+    pub dimension: String,
+    pub results: Vec<ConvertedValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConvertedValue {
+    pub unit: String,
+    /// Numeric value. Absent for number bases, where the text is the value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    pub text: String,
+}
+
+/// A unit in the `--list` output.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitInfo {
+    pub dimension: &'static str,
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub aliases: Vec<&'static str>,
+}
@@ -0,0 +1,83 @@
+use crate::converter::convert;
+use crate::expression::parse_expression;
+use crate::models::{ConversionOutput, NumberBase, UnitConvArgs, UnitInfo};
+use crate::units::UNITS;
+use anyhow::Result;
+use shared::utils::table_output::{render_json, Table};
+
+pub fn run(args: &UnitConvArgs) -> Result<()> {
+    if args.list {
+        return print_units(args.json);
+    }
+
+    let expression = args.expression.as_deref().unwrap_or_default();
+    let conversion = parse_expression(expression)?;
+    let output = convert(expression.trim(), &conversion, args.precision);
+
+    if args.json {
+        println!("{}", render_json(&output)?);
+    } else {
+        print_conversion(&output);
+    }
+
+    Ok(())
+}
+
+/// A single result as `input = result`, or a table with every unit of the dimension.
+fn print_conversion(output: &ConversionOutput) {
+    if let [result] = output.results.as_slice() {
+        println!("{} = {} {}", output.input, result.text, result.unit);
+        return;
+    }
+
+    let mut table = Table::new(&["UNIT", "VALUE"])
+        .with_title(format!("{} ({})", output.input, output.dimension))
+        .align_right(1);
+    for result in &output.results {
+        table.add_row(vec![result.unit.clone(), result.text.clone()]);
+    }
+
+    print!("{}", table.render_text());
+}
+
+fn print_units(json: bool) -> Result<()> {
+    let mut units: Vec<UnitInfo> = UNITS
+        .iter()
+        .map(|unit| UnitInfo {
+            dimension: unit.dimension.name(),
+            symbol: unit.symbol,
+            name: unit.name,
+            aliases: unit.aliases.to_vec(),
+        })
+        .collect();
+
+    units.extend(NumberBase::ALL.iter().map(|base| UnitInfo {
+        dimension: "number base",
+        symbol: base.name(),
+        name: match base {
+            NumberBase::Binary => "binary (0b)",
+            NumberBase::Octal => "octal (0o)",
+            NumberBase::Decimal => "decimal",
+            NumberBase::Hexadecimal => "hexadecimal (0x)",
+        },
+        aliases: Vec::new(),
+    }));
+
+    if json {
+        println!("{}", render_json(&units)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new(&["KIND", "UNIT", "NAME", "ALSO ACCEPTS"]);
+    for unit in &units {
+        table.add_row(vec![
+            unit.dimension.to_string(),
+            unit.symbol.to_string(),
+            unit.name.to_string(),
+            unit.aliases.join(", "),
+        ]);
+    }
+    print!("{}", table.render_text());
+
+    Ok(())
+}
@@ -0,0 +1,295 @@
+use anyhow::{anyhow, Result};
+
+/// What a unit measures. Only units of the same dimension convert to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Storage,
+    Duration,
+    Temperature,
+    Length,
+}
+
+impl Dimension {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dimension::Storage => "storage",
+            Dimension::Duration => "duration",
+            Dimension::Temperature => "temperature",
+            Dimension::Length => "length",
+        }
+    }
+}
+
+/// A unit, as `value * factor + offset` in the base unit of its dimension (bytes, seconds,
+/// kelvin, meters). Only temperatures have an offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub dimension: Dimension,
+    pub factor: f64,
+    pub offset: f64,
+    /// Other spellings. The symbol is always accepted.
+    pub aliases: &'static [&'static str],
+}
+
+impl Unit {
+    /// Value in this unit, in the base unit of the dimension.
+    pub fn normalize(&self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+
+    /// Value in the base unit of the dimension, in this unit.
+    pub fn denormalize(&self, value: f64) -> f64 {
+        (value - self.offset) / self.factor
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        self.symbol == text || self.aliases.contains(&text)
+    }
+
+    fn matches_ignoring_case(&self, text: &str) -> bool {
+        self.symbol.eq_ignore_ascii_case(text)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(text))
+    }
+}
+
+const fn unit(
+    symbol: &'static str,
+    name: &'static str,
+    dimension: Dimension,
+    factor: f64,
+    aliases: &'static [&'static str],
+) -> Unit {
+    Unit {
+        symbol,
+        name,
+        dimension,
+        factor,
+        offset: 0.0,
+        aliases,
+    }
+}
+
+const KIB: f64 = 1024.0;
+const DAY: f64 = 86_400.0;
+
+/// Every supported unit, grouped by dimension. Case matters for storage (`b` is a bit, `B` a
+/// byte), so exact matches win over case-insensitive ones.
+pub static UNITS: &[Unit] = &[
+    unit("b", "bit", Dimension::Storage, 0.125, &["bit", "bits"]),
+    unit("B", "byte", Dimension::Storage, 1.0, &["byte", "bytes"]),
+    unit(
+        "Kb",
+        "kilobit",
+        Dimension::Storage,
+        125.0,
+        &["kbit", "Kbit"],
+    ),
+    unit(
+        "Mb",
+        "megabit",
+        Dimension::Storage,
+        125e3,
+        &["Mbit", "mbit"],
+    ),
+    unit(
+        "Gb",
+        "gigabit",
+        Dimension::Storage,
+        125e6,
+        &["Gbit", "gbit"],
+    ),
+    unit("kB", "kilobyte", Dimension::Storage, 1e3, &["KB"]),
+    unit("MB", "megabyte", Dimension::Storage, 1e6, &[]),
+    unit("GB", "gigabyte", Dimension::Storage, 1e9, &[]),
+    unit("TB", "terabyte", Dimension::Storage, 1e12, &[]),
+    unit("PB", "petabyte", Dimension::Storage, 1e15, &[]),
+    unit("KiB", "kibibyte", Dimension::Storage, KIB, &[]),
+    unit("MiB", "mebibyte", Dimension::Storage, KIB * KIB, &[]),
+    unit("GiB", "gibibyte", Dimension::Storage, KIB * KIB * KIB, &[]),
+    unit(
+        "TiB",
+        "tebibyte",
+        Dimension::Storage,
+        KIB * KIB * KIB * KIB,
+        &[],
+    ),
+    unit(
+        "PiB",
+        "pebibyte",
+        Dimension::Storage,
+        KIB * KIB * KIB * KIB * KIB,
+        &[],
+    ),
+    unit(
+        "ns",
+        "nanosecond",
+        Dimension::Duration,
+        1e-9,
+        &["nanoseconds"],
+    ),
+    unit(
+        "us",
+        "microsecond",
+        Dimension::Duration,
+        1e-6,
+        &["µs", "μs", "microseconds"],
+    ),
+    unit(
+        "ms",
+        "millisecond",
+        Dimension::Duration,
+        1e-3,
+        &["milliseconds"],
+    ),
+    unit(
+        "s",
+        "second",
+        Dimension::Duration,
+        1.0,
+        &["sec", "secs", "second", "seconds"],
+    ),
+    unit(
+        "min",
+        "minute",
+        Dimension::Duration,
+        60.0,
+        &["mins", "minute", "minutes"],
+    ),
+    unit(
+        "h",
+        "hour",
+        Dimension::Duration,
+        3_600.0,
+        &["hr", "hrs", "hour", "hours"],
+    ),
+    unit("d", "day", Dimension::Duration, DAY, &["day", "days"]),
+    unit(
+        "w",
+        "week",
+        Dimension::Duration,
+        7.0 * DAY,
+        &["wk", "week", "weeks"],
+    ),
+    unit(
+        "y",
+        "year (365 days)",
+        Dimension::Duration,
+        365.0 * DAY,
+        &["yr", "year", "years"],
+    ),
+    Unit {
+        symbol: "C",
+        name: "degree Celsius",
+        dimension: Dimension::Temperature,
+        factor: 1.0,
+        offset: 273.15,
+        aliases: &["°C", "celsius"],
+    },
+    Unit {
+        symbol: "F",
+        name: "degree Fahrenheit",
+        dimension: Dimension::Temperature,
+        factor: 5.0 / 9.0,
+        offset: 459.67 * 5.0 / 9.0,
+        aliases: &["°F", "fahrenheit"],
+    },
+    unit("K", "kelvin", Dimension::Temperature, 1.0, &["kelvin"]),
+    unit(
+        "mm",
+        "millimeter",
+        Dimension::Length,
+        1e-3,
+        &["millimeters", "millimetres"],
+    ),
+    unit(
+        "cm",
+        "centimeter",
+        Dimension::Length,
+        1e-2,
+        &["centimeters", "centimetres"],
+    ),
+    unit(
+        "m",
+        "meter",
+        Dimension::Length,
+        1.0,
+        &["meter", "meters", "metre", "metres"],
+    ),
+    unit(
+        "km",
+        "kilometer",
+        Dimension::Length,
+        1e3,
+        &["kilometers", "kilometres"],
+    ),
+    unit("in", "inch", Dimension::Length, 0.0254, &["inch", "inches"]),
+    unit("ft", "foot", Dimension::Length, 0.3048, &["foot", "feet"]),
+    unit("yd", "yard", Dimension::Length, 0.9144, &["yard", "yards"]),
+    unit(
+        "mi",
+        "mile",
+        Dimension::Length,
+        1_609.344,
+        &["mile", "miles"],
+    ),
+    unit("nmi", "nautical mile", Dimension::Length, 1_852.0, &[]),
+];
+
+/// Finds a unit by symbol or alias. Exact matches come first; otherwise case is ignored, as long
+/// as that doesn't match different units (e.g.: `mb` could be `MB` or `Mb`).
+pub fn find_unit(text: &str) -> Result<&'static Unit> {
+    if let Some(unit) = UNITS.iter().find(|unit| unit.matches(text)) {
+        return Ok(unit);
+    }
+
+    let candidates: Vec<&Unit> = UNITS
+        .iter()
+        .filter(|unit| unit.matches_ignoring_case(text))
+        .collect();
+
+    match candidates.as_slice() {
+        [unit] => Ok(unit),
+        [] => Err(anyhow!(
+            "Unknown unit: {}. Use --list to see the supported units",
+            text
+        )),
+        _ => Err(anyhow!(
+            "Ambiguous unit: {}. Did you mean {}?",
+            text,
+            candidates
+                .iter()
+                .map(|unit| format!("{} ({})", unit.symbol, unit.name))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        )),
+    }
+}
+
+/// Units of a dimension, in the order they are declared.
+pub fn units_of(dimension: Dimension) -> impl Iterator<Item = &'static Unit> {
+    UNITS.iter().filter(move |unit| unit.dimension == dimension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_unit_by_case_and_alias() {
+        assert_eq!(find_unit("b").unwrap().name, "bit");
+        assert_eq!(find_unit("B").unwrap().name, "byte");
+        assert_eq!(find_unit("KB").unwrap().symbol, "kB");
+        assert_eq!(find_unit("mib").unwrap().symbol, "MiB");
+        assert_eq!(find_unit("Feet").unwrap().symbol, "ft");
+        assert_eq!(find_unit("°C").unwrap().symbol, "C");
+
+        let ambiguous = find_unit("mb").unwrap_err().to_string();
+        assert!(ambiguous.contains("Mb (megabit) or MB (megabyte)"));
+        assert!(find_unit("parsec").is_err());
+    }
+}
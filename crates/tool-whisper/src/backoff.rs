@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+const INITIAL_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Exponential backoff for the reconnection attempts: 1s, 2s, 4s, ... up to 30s.
+#[derive(Default)]
+pub struct Backoff {
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Number of the next attempt, starting at 1.
+    pub fn attempt(&self) -> u32 {
+        self.attempt + 1
+    }
+
+    /// How long to wait before the next attempt. Each call doubles the delay.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = INITIAL_DELAY
+            .checked_mul(2u32.saturating_pow(self.attempt))
+            .unwrap_or(MAX_DELAY)
+            .min(MAX_DELAY);
+        self.attempt = self.attempt.saturating_add(1);
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_up_to_the_limit() {
+        let mut backoff = Backoff::default();
+        assert_eq!(backoff.attempt(), 1);
+
+        let delays: Vec<u64> = (0..8).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30, 30]);
+        assert_eq!(backoff.attempt(), 9);
+
+        // Doesn't overflow after a long outage.
+        let mut backoff = Backoff { attempt: u32::MAX };
+        assert_eq!(backoff.next_delay(), MAX_DELAY);
+    }
+}
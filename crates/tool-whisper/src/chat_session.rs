@@ -3,10 +3,17 @@ use crate::encrypt::encryption::Encryption;
 use crate::encrypt::message_decrypter::MessageDecrypter;
 use crate::encrypt::message_encrypter::MessageEncrypter;
 use anyhow::Result;
+use std::net::TcpListener;
 use std::sync::mpsc::{Receiver, Sender};
 
 pub struct ChatSession {
     role: String,
+    /// Host only: kept open to accept the client again if the connection drops.
+    listener: Option<TcpListener>,
+    /// Host: the address listened on. Client: the address to reconnect to. Relayed: the relay.
+    address: String,
+    /// Relayed only: the room shared with the peer.
+    room: Option<String>,
     connection: Connection,
     my_encryption: Encryption,
     peer_encryption: MessageEncrypter,
@@ -17,8 +24,12 @@ pub struct ChatSession {
 }
 
 impl ChatSession {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         role: String,
+        listener: Option<TcpListener>,
+        address: String,
+        room: Option<String>,
         connection: Connection,
         my_encryption: Encryption,
         peer_encryption: MessageEncrypter,
@@ -29,6 +40,9 @@ impl ChatSession {
     ) -> Self {
         Self {
             role,
+            listener,
+            address,
+            room,
             connection,
             my_encryption,
             peer_encryption,
@@ -43,6 +57,22 @@ impl ChatSession {
         self.role.clone()
     }
 
+    pub fn get_address(&self) -> String {
+        self.address.clone()
+    }
+
+    pub fn get_room(&self) -> Option<String> {
+        self.room.clone()
+    }
+
+    pub fn take_listener(&mut self) -> Option<TcpListener> {
+        self.listener.take()
+    }
+
+    pub fn get_public_key(&self) -> Result<String> {
+        self.my_encryption.get_public_key()
+    }
+
     pub fn get_message_encrypter(&self) -> MessageEncrypter {
         self.peer_encryption.clone()
     }
@@ -72,7 +102,6 @@ impl ChatSession {
     }
 
     pub fn split_connection(&mut self) -> Result<Connection> {
-        let conn = self.connection.connection.try_clone()?;
-        Connection::new_from_connection(conn)
+        self.connection.split()
     }
 }
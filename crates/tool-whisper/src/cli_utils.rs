@@ -1,12 +1,15 @@
 use crate::models::shared_types::RuntimeType;
 use crate::models::whisper_args::WhisperArgs;
+use crate::relay::validate_room;
 use anyhow::Result;
-use clap::{Arg, Command};
+use clap::{Arg, ArgMatches, Command};
 use shared::command_line::cli_builder::CommandExt;
+use std::ffi::OsString;
 
 const DEFAULT_PORT: u16 = 2428; // The word chat in the old T9
+const DEFAULT_RELAY_LISTEN: &str = "0.0.0.0:7000";
 
-pub fn get_cli_arguments() -> Result<WhisperArgs> {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Result<WhisperArgs> {
     let matches = Command::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -32,6 +35,22 @@ pub fn get_cli_arguments() -> Result<WhisperArgs> {
                 .value_name("HOST:PORT")
                 .help("Client mode: Connect to the specified host and port (format: host:port)"),
         )
+        .arg(
+            Arg::new("relay")
+                .long("relay")
+                .short('r')
+                .value_name("HOST:PORT")
+                .conflicts_with_all(["wait", "connect"])
+                .requires("room")
+                .help("Relay mode: Chat through a whisper relay, with the peer that joins the same room"),
+        )
+        .arg(
+            Arg::new("room")
+                .long("room")
+                .value_name("CODE")
+                .requires("relay")
+                .help("Room code agreed with the peer (used with --relay)"),
+        )
         .arg(
             Arg::new("bind-to-all-interfaces")
                 .long("bind-to-all-interfaces")
@@ -39,7 +58,26 @@ pub fn get_cli_arguments() -> Result<WhisperArgs> {
                 .action(clap::ArgAction::SetTrue)
                 .help("Bind to all interfaces (default: bind to localhost)"),
         )
-        .get_matches();
+        .subcommand(build_relay_subcommand())
+        .get_matches_from(argv);
+
+    if let Some(("relay", sub_matches)) = matches.subcommand() {
+        return Ok(parse_relay_subcommand(sub_matches));
+    }
+
+    if let Some(relay) = matches.get_one::<String>("relay") {
+        let room = matches
+            .get_one::<String>("room")
+            .cloned()
+            .unwrap_or_default();
+        validate_room(&room)?;
+
+        return Ok(WhisperArgs {
+            host: relay.clone(),
+            runtime: RuntimeType::Relayed { room },
+            role: "PEER".to_string(),
+        });
+    }
 
     let ip = if *matches
         .get_one::<bool>("bind-to-all-interfaces")
@@ -65,7 +103,7 @@ pub fn get_cli_arguments() -> Result<WhisperArgs> {
     let connect_address = matches.get_one::<String>("connect").cloned();
 
     if connect_address.is_none() {
-        anyhow::bail!("You must specify either --wait or --connect");
+        anyhow::bail!("You must specify either --wait, --connect or --relay");
     }
 
     Ok(WhisperArgs {
@@ -74,3 +112,29 @@ pub fn get_cli_arguments() -> Result<WhisperArgs> {
         role: "CLIENT".to_string(),
     })
 }
+
+fn build_relay_subcommand() -> Command {
+    Command::new("relay")
+        .about("Runs a relay, so peers that can't reach each other directly (e.g.: behind NAT) can chat")
+        .arg(
+            Arg::new("listen")
+                .long("listen")
+                .short('l')
+                .value_name("HOST:PORT")
+                .default_value(DEFAULT_RELAY_LISTEN)
+                .help("Address to listen on"),
+        )
+}
+
+fn parse_relay_subcommand(matches: &ArgMatches) -> WhisperArgs {
+    let listen = matches
+        .get_one::<String>("listen")
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_RELAY_LISTEN.to_string());
+
+    WhisperArgs {
+        host: listen,
+        runtime: RuntimeType::RelayServer,
+        role: "RELAY".to_string(),
+    }
+}
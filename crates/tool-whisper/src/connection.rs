@@ -1,8 +1,15 @@
 use anyhow::Result;
 use std::io::{Read, Write};
 use std::net::TcpStream;
+use std::time::Duration;
 use tracing::{debug, error};
 
+/// How often an idle connection sends a heartbeat.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A connection that reads nothing (not even a heartbeat) for this long is considered dropped.
+pub const PEER_TIMEOUT: Duration = Duration::from_secs(15);
+
 pub struct Connection {
     pub connection: TcpStream,
     outgoing_count: usize,
@@ -18,6 +25,19 @@ impl Connection {
         })
     }
 
+    /// Wraps a new connection with the read/write timeouts set. Without them, a connection that
+    /// dies silently (e.g.: network cable unplugged) would never be noticed.
+    pub fn open(connection: TcpStream) -> Result<Self> {
+        connection.set_read_timeout(Some(PEER_TIMEOUT))?;
+        connection.set_write_timeout(Some(PEER_TIMEOUT))?;
+        Self::new_from_connection(connection)
+    }
+
+    /// Another handle to the same connection (e.g.: to read and write from different threads).
+    pub fn split(&self) -> Result<Self> {
+        Self::new_from_connection(self.connection.try_clone()?)
+    }
+
     pub fn read_message(&mut self) -> Result<Option<String>> {
         self.incoming_count += 1;
         match self.read_message_header()? {
@@ -73,6 +93,14 @@ impl Connection {
         Ok(())
     }
 
+    /// Sends an empty frame. `read_message` on the other side returns `None` for it, so it only
+    /// tells the peer the connection is still alive.
+    pub fn write_heartbeat(&mut self) -> Result<()> {
+        debug!("Sending heartbeat");
+        self.connection.write_all(&0u32.to_be_bytes())?;
+        Ok(())
+    }
+
     fn read_message_header(&mut self) -> Result<Option<usize>> {
         debug!("Reading message header...");
 
@@ -0,0 +1,68 @@
+mod backoff;
+mod chat_session;
+mod cli_utils;
+mod connection;
+mod encrypt;
+mod models;
+mod relay;
+mod ui;
+mod whisper_app;
+
+use crate::cli_utils::get_cli_arguments;
+use crate::models::shared_types::RuntimeType;
+use crate::relay::run_relay_server;
+use crate::ui::theme::ChatTheme;
+use crate::whisper_app::{create_handlers, start_chat_session};
+use anyhow::Result;
+use shared::constants::general::{EXIT_CODE_ERROR, EXIT_CODE_SUCCESS};
+use shared::logging::app_logger::LogLevel;
+use shared::logging::logging_helpers::get_default_log_builder;
+use std::ffi::OsString;
+
+/// P2P chat.
+///
+/// Parses arguments, then starts the chat session (or the relay server).
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    match run_tool(argv.into_iter().map(Into::into).collect()) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            EXIT_CODE_ERROR
+        }
+    }
+}
+
+fn run_tool(argv: Vec<OsString>) -> Result<()> {
+    // Change the boolean flags below to enable logging to console and/or file.
+    // This helps with debugging if you need.
+    get_default_log_builder(env!("CARGO_PKG_NAME"), LogLevel::Info)
+        .log_to_console(false)
+        .log_to_file(false, false)
+        .init();
+
+    let cli_args = get_cli_arguments(argv)?;
+
+    if let RuntimeType::RelayServer = cli_args.runtime {
+        return run_relay_server(&cli_args.host);
+    }
+
+    // Loaded before connecting, so a broken config doesn't end a session that just started.
+    let theme = ChatTheme::load()?;
+
+    let chat_session = start_chat_session(cli_args)?;
+
+    let (_connection_handler, ui_handler) = create_handlers(chat_session, theme)?;
+
+    // The session ends when the UI is closed. The connection handler isn't waited for: it may
+    // still be reconnecting, and it ends with the process.
+    let _ = ui_handler.join();
+
+    Ok(())
+}
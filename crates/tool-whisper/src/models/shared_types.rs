@@ -1,4 +1,21 @@
 pub enum RuntimeType {
     Host,
     Client,
+    /// Chat through a relay, with the peer that joins the same room.
+    Relayed {
+        room: String,
+    },
+    /// Relay server: pairs the peers by room code and forwards their frames.
+    RelayServer,
+}
+
+/// State of the connection with the peer, as shown in the UI.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionState {
+    /// Handshake done. The fingerprint of the peer's key tells if it's still the same peer.
+    Connected { peer_fingerprint: String },
+    /// Host: the connection dropped, waiting for the client to connect again.
+    WaitingForPeer,
+    /// Client: the connection dropped, trying to connect again.
+    Reconnecting { attempt: u32 },
 }
@@ -0,0 +1,257 @@
+use crate::connection::{Connection, HEARTBEAT_INTERVAL};
+use anyhow::{anyhow, bail, Result};
+use chrono::Local;
+use std::collections::HashMap;
+use std::io;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, RecvTimeoutError, SendError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tracing::{debug, info, warn};
+
+// Relay protocol. Everything uses the same length-prefixed frames as the chat:
+// 1. The peer sends `JOIN <room>`.
+// 2. While the other peer doesn't join, the relay sends heartbeats (empty frames).
+// 3. When both are in the room, the relay tells the first one it plays the host in the key
+//    exchange, and the second one it plays the client. From then on, the relay just forwards
+//    the bytes between them, without reading the (encrypted) messages.
+const JOIN_PREFIX: &str = "JOIN ";
+const PAIRED_AS_HOST: &str = "PAIRED HOST";
+const PAIRED_AS_CLIENT: &str = "PAIRED CLIENT";
+const ERROR_PREFIX: &str = "ERROR ";
+
+const MAX_ROOM_LENGTH: usize = 64;
+
+/// Room codes are what pairs two peers, so they can't be empty, and they can't have spaces or
+/// control characters.
+pub fn validate_room(room: &str) -> Result<()> {
+    if room.is_empty() || room.chars().count() > MAX_ROOM_LENGTH {
+        bail!(
+            "The room code must have between 1 and {} characters",
+            MAX_ROOM_LENGTH
+        );
+    }
+
+    if room.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        bail!("The room code can't have spaces or control characters");
+    }
+
+    Ok(())
+}
+
+/// Registers in the room of the relay and waits for the other peer to join.
+///
+/// Returns `true` if this peer plays the host in the key exchange (it joined first).
+///
+/// # Errors
+/// Returns error if the relay refuses the room, or the connection fails.
+pub fn join_room(chat_connection: &mut Connection, room: &str) -> Result<bool> {
+    chat_connection.write_message(&format!("{}{}", JOIN_PREFIX, room))?;
+
+    loop {
+        match chat_connection.read_message()? {
+            // Heartbeat: still waiting for the other peer.
+            None => continue,
+            Some(reply) if reply == PAIRED_AS_HOST => return Ok(true),
+            Some(reply) if reply == PAIRED_AS_CLIENT => return Ok(false),
+            Some(reply) => bail!(
+                "The relay refused to join the room: {}",
+                reply.strip_prefix(ERROR_PREFIX).unwrap_or(&reply)
+            ),
+        }
+    }
+}
+
+/// Peer waiting in a room. The second peer hands its connection over to it.
+struct Waiter {
+    id: u64,
+    partner_tx: Sender<TcpStream>,
+}
+
+type Rooms = Arc<Mutex<HashMap<String, Waiter>>>;
+
+/// Runs the relay until the process is stopped. Each room pairs two peers; a third peer with
+/// the same room code waits for a new partner.
+///
+/// # Errors
+/// Returns error if the address can't be listened on.
+pub fn run_relay_server(listen: &str) -> Result<()> {
+    let listener = TcpListener::bind(listen)?;
+    log_event(&format!("Relay listening on: {}", listen));
+
+    let rooms = Rooms::default();
+    let mut next_id = 0u64;
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept a connection: {}", e);
+                continue;
+            }
+        };
+
+        next_id += 1;
+        let id = next_id;
+        let rooms = rooms.clone();
+
+        thread::spawn(move || {
+            if let Err(e) = handle_peer(stream, id, &rooms) {
+                debug!("[peer {}] Disconnected: {}", id, e);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle_peer(stream: TcpStream, id: u64, rooms: &Rooms) -> Result<()> {
+    let peer_addr = stream.peer_addr()?;
+    let mut chat_connection = Connection::open(stream)?;
+
+    let room = chat_connection
+        .read_message()?
+        .and_then(|message| message.strip_prefix(JOIN_PREFIX).map(str::to_string))
+        .ok_or_else(|| anyhow!("{} did not join a room", peer_addr));
+
+    let room = match room.and_then(|room| validate_room(&room).map(|_| room)) {
+        Ok(room) => room,
+        Err(e) => {
+            let _ = chat_connection.write_message(&format!("{}{}", ERROR_PREFIX, e));
+            return Err(e);
+        }
+    };
+
+    // Hands the connection over to the peer waiting in the room, or becomes the one waiting.
+    let mut stream = chat_connection.connection;
+    let (partner_tx, partner_rx) = mpsc::channel();
+    {
+        let mut rooms = rooms
+            .lock()
+            .map_err(|_| anyhow!("Failed to lock the rooms"))?;
+
+        if let Some(waiter) = rooms.remove(&room) {
+            match waiter.partner_tx.send(stream) {
+                Ok(()) => return Ok(()),
+                // The waiting peer left in the meantime.
+                Err(SendError(returned)) => stream = returned,
+            }
+        }
+
+        rooms.insert(room.clone(), Waiter { id, partner_tx });
+    }
+
+    info!("[peer {}] {} is waiting for a partner", id, peer_addr);
+    let mut chat_connection = Connection::new_from_connection(stream)?;
+
+    let partner = loop {
+        match partner_rx.recv_timeout(HEARTBEAT_INTERVAL) {
+            Ok(partner) => break partner,
+            Err(RecvTimeoutError::Timeout) => {
+                if let Err(e) = chat_connection.write_heartbeat() {
+                    leave_room(rooms, &room, id);
+                    return Err(e);
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => bail!("The room was closed"),
+        }
+    };
+
+    let partner_addr = partner.peer_addr()?;
+    let mut partner_connection = Connection::new_from_connection(partner)?;
+    chat_connection.write_message(PAIRED_AS_HOST)?;
+    partner_connection.write_message(PAIRED_AS_CLIENT)?;
+
+    log_event(&format!("Paired {} and {}", peer_addr, partner_addr));
+    forward(chat_connection.connection, partner_connection.connection)?;
+    log_event(&format!(
+        "Session between {} and {} ended",
+        peer_addr, partner_addr
+    ));
+
+    Ok(())
+}
+
+fn leave_room(rooms: &Rooms, room: &str, id: u64) {
+    if let Ok(mut rooms) = rooms.lock() {
+        if rooms.get(room).is_some_and(|waiter| waiter.id == id) {
+            rooms.remove(room);
+        }
+    }
+}
+
+/// Copies the bytes both ways until either side closes (or goes silent for too long), then
+/// closes both, so the peers notice and reconnect.
+fn forward(first: TcpStream, second: TcpStream) -> Result<()> {
+    let mut first_reader = first.try_clone()?;
+    let mut second_writer = second.try_clone()?;
+
+    let first_to_second = thread::spawn(move || {
+        let _ = io::copy(&mut first_reader, &mut second_writer);
+        let _ = first_reader.shutdown(Shutdown::Both);
+        let _ = second_writer.shutdown(Shutdown::Both);
+    });
+
+    let (mut second_reader, mut first_writer) = (second, first);
+    let _ = io::copy(&mut second_reader, &mut first_writer);
+    let _ = second_reader.shutdown(Shutdown::Both);
+    let _ = first_writer.shutdown(Shutdown::Both);
+
+    let _ = first_to_second.join();
+    Ok(())
+}
+
+fn log_event(message: &str) {
+    info!("{}", message);
+    println!("[{}] {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_room_codes() {
+        assert!(validate_room("blue-otter-42").is_ok());
+        assert!(validate_room("").is_err());
+        assert!(validate_room("two words").is_err());
+        assert!(validate_room(&"x".repeat(MAX_ROOM_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_relay_pairs_peers_and_forwards_frames() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let listen = address.to_string();
+        thread::spawn(move || run_relay_server(&listen));
+        thread::sleep(std::time::Duration::from_millis(200));
+
+        let join = move |room: &'static str| {
+            thread::spawn(move || {
+                let mut chat_connection =
+                    Connection::open(TcpStream::connect(address).unwrap()).unwrap();
+                let is_host = join_room(&mut chat_connection, room).unwrap();
+                (chat_connection, is_host)
+            })
+        };
+
+        let first = join("room-1");
+        thread::sleep(std::time::Duration::from_millis(200));
+        let (mut second, second_is_host) = join("room-1").join().unwrap();
+        let (mut first, first_is_host) = first.join().unwrap();
+
+        assert!(first_is_host);
+        assert!(!second_is_host);
+
+        second.write_message("hello from the client").unwrap();
+        assert_eq!(
+            first.read_message().unwrap().as_deref(),
+            Some("hello from the client")
+        );
+
+        let mut refused = Connection::open(TcpStream::connect(address).unwrap()).unwrap();
+        assert!(join_room(&mut refused, "bad room").is_err());
+    }
+}
@@ -1,11 +1,14 @@
+use crate::models::shared_types::ConnectionState;
+use crate::ui::theme::{help_spans, ChatTheme};
 use anyhow::Result;
+use chrono::{DateTime, Local};
 use ratatui::crossterm::event::{poll, KeyEventKind};
 use ratatui::layout::Position;
 use ratatui::widgets::{List, ListItem};
 use ratatui::{
     crossterm::event::{self, Event, KeyCode},
     layout::{Constraint, Layout},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Paragraph},
     DefaultTerminal, Frame,
@@ -41,23 +44,37 @@ enum ChatState {
 struct Message {
     text: String,
     kind: MessageKind,
+    time: DateTime<Local>,
 }
 
 impl Message {
-    pub fn format(&'_ self) -> Line<'_> {
-        match self.kind {
-            MessageKind::Own => {
-                let color = Color::Green;
-                let text = format!("> {}", self.text);
-                Line::from(Span::styled(text, Style::default().fg(color)))
-            }
-            MessageKind::Peer => {
-                let color = Color::White;
-                let text = format!("< {}", self.text);
-                Line::from(Span::styled(text, Style::default().fg(color)))
-            }
+    fn new(text: String, kind: MessageKind) -> Self {
+        Self {
+            text,
+            kind,
+            time: Local::now(),
         }
     }
+
+    pub fn format(&'_ self, theme: &ChatTheme) -> Line<'_> {
+        let (prefix, color) = match self.kind {
+            MessageKind::Own => (&theme.strings.own_prefix, theme.colors.own_message),
+            MessageKind::Peer => (&theme.strings.peer_prefix, theme.colors.peer_message),
+        };
+
+        let mut spans = Vec::with_capacity(2);
+        if !theme.timestamp_format.is_empty() {
+            spans.push(Span::styled(
+                format!("[{}] ", self.time.format(&theme.timestamp_format)),
+                Style::default().fg(theme.colors.timestamp),
+            ));
+        }
+
+        let text = format!("{} {}", prefix, self.text);
+        spans.push(Span::styled(text, Style::default().fg(color)));
+
+        Line::from(spans)
+    }
 }
 
 pub struct ChatUi {
@@ -65,6 +82,7 @@ pub struct ChatUi {
     role_name: String,
     outgoing_tx: Sender<String>,
     incoming_rx: Receiver<String>,
+    state_rx: Receiver<ConnectionState>,
     // Ui properties
     /// Current value of the input box
     input: String,
@@ -74,6 +92,10 @@ pub struct ChatUi {
     input_mode: InputMode,
     /// History of messages (both sent and received)
     messages: Vec<Message>,
+    /// Colors, timestamp format and texts
+    theme: ChatTheme,
+    /// Last known state of the connection (none until the connection handler starts)
+    connection_state: Option<ConnectionState>,
 }
 
 impl PartialEq for ChatState {
@@ -95,16 +117,21 @@ impl ChatUi {
     pub fn new(
         outgoing_tx: Sender<String>,
         incoming_rx: Receiver<String>,
+        state_rx: Receiver<ConnectionState>,
         role_name: String,
+        theme: ChatTheme,
     ) -> Self {
         Self {
             role_name: format!("{}-Ui", role_name),
             outgoing_tx,
             incoming_rx,
+            state_rx,
             input: String::new(),
             character_index: 0,
             input_mode: InputMode::Editing,
             messages: Vec::new(),
+            theme,
+            connection_state: None,
         }
     }
 
@@ -121,10 +148,12 @@ impl ChatUi {
             // Processing received messages
             while let Ok(msg) = self.incoming_rx.try_recv() {
                 debug!("[{}] Received message: {}", self.role_name, msg);
-                self.messages.push(Message {
-                    text: msg,
-                    kind: MessageKind::Peer,
-                });
+                self.messages.push(Message::new(msg, MessageKind::Peer));
+            }
+
+            while let Ok(state) = self.state_rx.try_recv() {
+                debug!("[{}] Connection state: {:?}", self.role_name, state);
+                self.connection_state = Some(state);
             }
 
             terminal.draw(|frame| self.draw(frame))?;
@@ -150,15 +179,23 @@ impl ChatUi {
         ]);
         let [banner_area, messages_area, input_area, help_area] = vertical.areas(frame.area());
 
-        // Banner (top)
-        frame.render_widget(Paragraph::new(get_banner()), banner_area);
+        // Banner (top), with the connection state
+        let mut banner = vec![Span::styled(
+            get_banner(),
+            Style::default().fg(self.theme.colors.banner),
+        )];
+        if let Some(state) = &self.connection_state {
+            banner.push(Span::raw(" | "));
+            banner.push(self.format_connection_state(state));
+        }
+        frame.render_widget(Paragraph::new(Line::from(banner)), banner_area);
 
         // Messages (no borders)
         let items: Vec<ListItem> = self
             .messages
             .iter()
             .map(|m| {
-                let line = m.format();
+                let line = m.format(&self.theme);
                 ListItem::new(line)
             })
             .collect();
@@ -168,9 +205,9 @@ impl ChatUi {
         let input = Paragraph::new(self.input.as_str())
             .style(match self.input_mode {
                 InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().fg(Color::Green),
+                InputMode::Editing => Style::default().fg(self.theme.colors.input),
             })
-            .block(Block::bordered().title("What's on your mind?"));
+            .block(Block::bordered().title(self.theme.strings.input_title.as_str()));
         frame.render_widget(input, input_area);
 
         // Cursor in input field when editing
@@ -183,27 +220,13 @@ impl ChatUi {
         }
 
         // Helper (very bottom)
+        let help_style = Style::default().fg(self.theme.colors.help);
         let (helper_line, style) = match self.input_mode {
             InputMode::Normal => (
-                vec![
-                    "Press ".into(),
-                    "q".bold(),
-                    " to exit, ".into(),
-                    "e".bold(),
-                    " to start editing.".into(),
-                ],
-                Style::default().add_modifier(Modifier::RAPID_BLINK),
-            ),
-            InputMode::Editing => (
-                vec![
-                    "Press ".into(),
-                    "Esc".bold(),
-                    " to stop editing, ".into(),
-                    "Enter".bold(),
-                    " to send".into(),
-                ],
-                Style::default(),
+                help_spans(&self.theme.strings.help_normal),
+                help_style.add_modifier(Modifier::RAPID_BLINK),
             ),
+            InputMode::Editing => (help_spans(&self.theme.strings.help_editing), help_style),
         };
         frame.render_widget(
             Paragraph::new(Line::from(helper_line)).style(style),
@@ -211,6 +234,29 @@ impl ChatUi {
         );
     }
 
+    fn format_connection_state(&self, state: &ConnectionState) -> Span<'static> {
+        let strings = &self.theme.strings;
+        let colors = &self.theme.colors;
+
+        let (text, color) = match state {
+            ConnectionState::Connected { peer_fingerprint } => (
+                strings
+                    .status_connected
+                    .replace("{fingerprint}", peer_fingerprint),
+                colors.status_connected,
+            ),
+            ConnectionState::WaitingForPeer => (strings.status_waiting.clone(), colors.status_lost),
+            ConnectionState::Reconnecting { attempt } => (
+                strings
+                    .status_reconnecting
+                    .replace("{attempt}", &attempt.to_string()),
+                colors.status_lost,
+            ),
+        };
+
+        Span::styled(text, Style::default().fg(color))
+    }
+
     //region: Ui Logic
     fn process_key_inputs(&mut self) -> Result<ChatState> {
         if poll(Duration::from_millis(100))? {
@@ -255,10 +301,7 @@ impl ChatUi {
             "[{}] Message sent. Pushing to display history...",
             self.role_name
         );
-        self.messages.push(Message {
-            text: msg,
-            kind: MessageKind::Own,
-        });
+        self.messages.push(Message::new(msg, MessageKind::Own));
 
         self.reset_cursor();
 
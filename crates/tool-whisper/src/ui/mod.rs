@@ -1 +1,2 @@
 pub mod chat_ui;
+pub mod theme;
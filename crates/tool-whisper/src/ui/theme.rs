@@ -0,0 +1,336 @@
+use anyhow::{anyhow, Result};
+use chrono::format::{Item, StrftimeItems};
+use ratatui::style::{Color, Stylize};
+use ratatui::text::Span;
+use serde::Deserialize;
+use shared::system::load_global_config::load_global_config_section;
+use std::str::FromStr;
+
+/// Section of the global config (`rusted-toolbox.json`) read by whisper.
+const CONFIG_SECTION: &str = "whisper";
+
+const DEFAULT_TIMESTAMP_FORMAT: &str = "%H:%M";
+
+/// `whisper` section of the global config. Everything is optional: the `theme` and `language`
+/// presets are applied first, then the individual overrides.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChatUiConfig {
+    /// `dark` (default) or `light`.
+    pub theme: Option<String>,
+    /// `en` (default) or `pt-BR`.
+    pub language: Option<String>,
+    /// chrono format for the message timestamps (e.g.: `%H:%M:%S`). Empty hides them.
+    pub timestamp_format: Option<String>,
+    pub colors: ColorOverrides,
+    pub strings: StringOverrides,
+}
+
+/// Colors by name (`green`, `light-blue`, ...), hex (`#005f87`) or ANSI index (`33`).
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ColorOverrides {
+    pub own_message: Option<String>,
+    pub peer_message: Option<String>,
+    pub timestamp: Option<String>,
+    pub input: Option<String>,
+    pub banner: Option<String>,
+    pub help: Option<String>,
+    pub status_connected: Option<String>,
+    pub status_lost: Option<String>,
+}
+
+/// UI texts. In the help texts, `{...}` marks a key name, shown in bold. In the status texts,
+/// `{fingerprint}` and `{attempt}` are replaced by the peer's key fingerprint and the
+/// reconnection attempt.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StringOverrides {
+    pub input_title: Option<String>,
+    pub help_normal: Option<String>,
+    pub help_editing: Option<String>,
+    pub own_prefix: Option<String>,
+    pub peer_prefix: Option<String>,
+    pub status_connected: Option<String>,
+    pub status_waiting: Option<String>,
+    pub status_reconnecting: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeColors {
+    pub own_message: Color,
+    pub peer_message: Color,
+    pub timestamp: Color,
+    pub input: Color,
+    pub banner: Color,
+    pub help: Color,
+    pub status_connected: Color,
+    pub status_lost: Color,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiStrings {
+    pub input_title: String,
+    pub help_normal: String,
+    pub help_editing: String,
+    pub own_prefix: String,
+    pub peer_prefix: String,
+    pub status_connected: String,
+    pub status_waiting: String,
+    pub status_reconnecting: String,
+}
+
+/// Colors, timestamp format and texts used by `ChatUi`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatTheme {
+    pub colors: ThemeColors,
+    pub timestamp_format: String,
+    pub strings: UiStrings,
+}
+
+impl Default for ChatTheme {
+    fn default() -> Self {
+        Self {
+            colors: ThemeColors::dark(),
+            timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            strings: UiStrings::english(),
+        }
+    }
+}
+
+impl ChatTheme {
+    /// Builds the theme from the `whisper` section of the global config, or the default theme
+    /// when there is none.
+    pub fn load() -> Result<Self> {
+        let config = load_global_config_section::<ChatUiConfig>(CONFIG_SECTION)?;
+        Self::from_config(config.unwrap_or_default())
+    }
+
+    pub fn from_config(config: ChatUiConfig) -> Result<Self> {
+        let mut colors = match config.theme.as_deref().map(str::to_ascii_lowercase) {
+            None => ThemeColors::dark(),
+            Some(theme) if theme == "dark" => ThemeColors::dark(),
+            Some(theme) if theme == "light" => ThemeColors::light(),
+            Some(theme) => {
+                return Err(anyhow!(
+                    "Unknown theme '{}'. Valid options are: dark, light",
+                    theme
+                ))
+            }
+        };
+
+        let mut strings = match config.language.as_deref().map(str::to_ascii_lowercase) {
+            None => UiStrings::english(),
+            Some(language) if language == "en" => UiStrings::english(),
+            Some(language) if language == "pt-br" || language == "pt" => UiStrings::portuguese(),
+            Some(language) => {
+                return Err(anyhow!(
+                    "Unknown language '{}'. Valid options are: en, pt-BR",
+                    language
+                ))
+            }
+        };
+
+        let overrides = config.colors;
+        apply_color(&mut colors.own_message, overrides.own_message, "ownMessage")?;
+        apply_color(
+            &mut colors.peer_message,
+            overrides.peer_message,
+            "peerMessage",
+        )?;
+        apply_color(&mut colors.timestamp, overrides.timestamp, "timestamp")?;
+        apply_color(&mut colors.input, overrides.input, "input")?;
+        apply_color(&mut colors.banner, overrides.banner, "banner")?;
+        apply_color(&mut colors.help, overrides.help, "help")?;
+        apply_color(
+            &mut colors.status_connected,
+            overrides.status_connected,
+            "statusConnected",
+        )?;
+        apply_color(&mut colors.status_lost, overrides.status_lost, "statusLost")?;
+
+        let overrides = config.strings;
+        let text_fields = [
+            (&mut strings.input_title, overrides.input_title),
+            (&mut strings.help_normal, overrides.help_normal),
+            (&mut strings.help_editing, overrides.help_editing),
+            (&mut strings.own_prefix, overrides.own_prefix),
+            (&mut strings.peer_prefix, overrides.peer_prefix),
+            (&mut strings.status_connected, overrides.status_connected),
+            (&mut strings.status_waiting, overrides.status_waiting),
+            (
+                &mut strings.status_reconnecting,
+                overrides.status_reconnecting,
+            ),
+        ];
+        for (field, value) in text_fields {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+
+        let timestamp_format = config
+            .timestamp_format
+            .unwrap_or_else(|| DEFAULT_TIMESTAMP_FORMAT.to_string());
+        if StrftimeItems::new(&timestamp_format).any(|item| matches!(item, Item::Error)) {
+            return Err(anyhow!("Invalid timestamp format '{}'", timestamp_format));
+        }
+
+        Ok(Self {
+            colors,
+            timestamp_format,
+            strings,
+        })
+    }
+}
+
+impl ThemeColors {
+    /// The original colors. Made for dark terminals.
+    fn dark() -> Self {
+        Self {
+            own_message: Color::Green,
+            peer_message: Color::White,
+            timestamp: Color::DarkGray,
+            input: Color::Green,
+            banner: Color::Reset,
+            help: Color::Reset,
+            status_connected: Color::Green,
+            status_lost: Color::Yellow,
+        }
+    }
+
+    /// Readable on light terminals, where white and green text fade out.
+    fn light() -> Self {
+        Self {
+            own_message: Color::Blue,
+            peer_message: Color::Black,
+            timestamp: Color::DarkGray,
+            input: Color::Blue,
+            banner: Color::Reset,
+            help: Color::Reset,
+            status_connected: Color::Green,
+            status_lost: Color::Red,
+        }
+    }
+}
+
+impl UiStrings {
+    fn english() -> Self {
+        Self {
+            input_title: "What's on your mind?".to_string(),
+            help_normal: "Press {q} to exit, {e} to start editing.".to_string(),
+            help_editing: "Press {Esc} to stop editing, {Enter} to send".to_string(),
+            own_prefix: ">".to_string(),
+            peer_prefix: "<".to_string(),
+            status_connected: "Connected (peer key {fingerprint})".to_string(),
+            status_waiting: "Connection lost. Waiting for the peer to reconnect...".to_string(),
+            status_reconnecting: "Connection lost. Reconnecting (attempt {attempt})...".to_string(),
+        }
+    }
+
+    fn portuguese() -> Self {
+        Self {
+            input_title: "No que você está pensando?".to_string(),
+            help_normal: "Pressione {q} para sair, {e} para começar a editar.".to_string(),
+            help_editing: "Pressione {Esc} para parar de editar, {Enter} para enviar".to_string(),
+            own_prefix: ">".to_string(),
+            peer_prefix: "<".to_string(),
+            status_connected: "Conectado (chave do contato {fingerprint})".to_string(),
+            status_waiting: "Conexão perdida. Aguardando o contato reconectar...".to_string(),
+            status_reconnecting: "Conexão perdida. Reconectando (tentativa {attempt})..."
+                .to_string(),
+        }
+    }
+}
+
+fn apply_color(target: &mut Color, value: Option<String>, name: &str) -> Result<()> {
+    if let Some(value) = value {
+        *target = Color::from_str(value.trim())
+            .map_err(|_| anyhow!("Invalid color '{}' for '{}'", value, name))?;
+    }
+
+    Ok(())
+}
+
+/// Splits a help text into spans, with the `{...}` parts (key names) in bold.
+pub fn help_spans(template: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(length) = rest[start..].find('}') else {
+            break;
+        };
+
+        if start > 0 {
+            spans.push(Span::raw(rest[..start].to_string()));
+        }
+        spans.push(rest[start + 1..start + length].to_string().bold());
+        rest = &rest[start + length + 1..];
+    }
+
+    if !rest.is_empty() {
+        spans.push(Span::raw(rest.to_string()));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_and_overrides() {
+        let config: ChatUiConfig = serde_json::from_str(
+            r##"{
+                "theme": "light",
+                "language": "pt-BR",
+                "timestampFormat": "%H:%M:%S",
+                "strings": { "ownPrefix": "eu:" },
+                "colors": { "ownMessage": "#005f87", "statusLost": "magenta" }
+            }"##,
+        )
+        .unwrap();
+
+        let theme = ChatTheme::from_config(config).unwrap();
+
+        assert_eq!(theme.colors.own_message, Color::Rgb(0x00, 0x5f, 0x87));
+        assert_eq!(theme.colors.peer_message, Color::Black);
+        assert_eq!(theme.strings.input_title, "No que você está pensando?");
+        assert_eq!(theme.strings.own_prefix, "eu:");
+        assert_eq!(theme.colors.status_lost, Color::Magenta);
+        assert_eq!(
+            theme.strings.status_reconnecting,
+            "Conexão perdida. Reconectando (tentativa {attempt})..."
+        );
+        assert_eq!(theme.timestamp_format, "%H:%M:%S");
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        let invalid = [
+            r#"{ "theme": "solarized" }"#,
+            r#"{ "language": "xx" }"#,
+            r#"{ "colors": { "input": "not-a-color" } }"#,
+            r#"{ "timestampFormat": "%Q" }"#,
+        ];
+
+        for json in invalid {
+            let config: ChatUiConfig = serde_json::from_str(json).unwrap();
+            assert!(ChatTheme::from_config(config).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_help_spans_bold_the_keys() {
+        let spans = help_spans("Press {q} to exit, {e} to start editing.");
+        let texts: Vec<_> = spans.iter().map(|span| span.content.as_ref()).collect();
+
+        assert_eq!(
+            texts,
+            vec!["Press ", "q", " to exit, ", "e", " to start editing."]
+        );
+        assert_eq!(spans[1], "q".bold());
+    }
+}
@@ -1,36 +1,45 @@
+use crate::backoff::Backoff;
 use crate::chat_session::ChatSession;
-use crate::connection::Connection;
+use crate::connection::{Connection, HEARTBEAT_INTERVAL};
 use crate::encrypt::encryption::Encryption;
+use crate::encrypt::message_decrypter::MessageDecrypter;
 use crate::encrypt::message_encrypter::MessageEncrypter;
-use crate::models::shared_types::RuntimeType;
+use crate::models::shared_types::{ConnectionState, RuntimeType};
 use crate::models::whisper_args::WhisperArgs;
+use crate::relay::join_room;
 use crate::ui::chat_ui::ChatUi;
-use anyhow::Result;
-use shared::system::tool_exit_helpers::exit_success;
-use std::net::{TcpListener, TcpStream};
-use std::sync::mpsc;
+use crate::ui::theme::ChatTheme;
+use anyhow::{anyhow, Result};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
 use std::thread;
 use std::thread::{sleep, JoinHandle};
-use std::time::{Duration, SystemTime};
-use tracing::{debug, error, info};
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{debug, error, info, warn};
+
+/// Thread running one of the session handlers.
+type Handler = JoinHandle<Result<()>>;
 
 pub fn start_chat_session(cli_args: WhisperArgs) -> Result<ChatSession> {
     let (tx_own_messages, rx_own_messages) = mpsc::channel::<String>();
     let (tx_peer_message, rx_peer_message) = mpsc::channel::<String>();
-    let peer_encrypter: MessageEncrypter;
-    let mut chat_connection: Connection;
+    let mut listener: Option<TcpListener> = None;
 
     info!("Generating Keypair...");
     let keypair = Encryption::new_keypair()?;
     let pub_key = keypair.get_public_key()?;
 
-    match cli_args.runtime {
+    let mut room: Option<String> = None;
+
+    let stream = match cli_args.runtime {
         RuntimeType::Host => {
             info!("Initializing listener on: {}", cli_args.host);
-            let listener = TcpListener::bind(cli_args.host)?;
+            let host_listener = TcpListener::bind(&cli_args.host)?;
 
             info!("Waiting for someone to talk to...");
-            let stream = match listener.accept() {
+            let stream = match host_listener.accept() {
                 Ok((socket, addr)) => {
                     info!("Client connected! Client address: {addr:?}");
                     socket
@@ -40,63 +49,43 @@ pub fn start_chat_session(cli_args: WhisperArgs) -> Result<ChatSession> {
                 }
             };
 
-            info!("Creating connection manager...");
-            chat_connection = Connection::new_from_connection(stream)?;
-
-            info!("Starting handshake...");
-            info!("Waiting for client to send public key...");
-            let client_pub_key_string = match chat_connection.read_message()? {
-                None => {
-                    error!("Handshake failed! No public key received from client.");
-                    anyhow::bail!("Handshake failed! No public key received from client.");
-                }
-                Some(received_client_pub_key) => {
-                    info!("Received public key from client...");
-                    received_client_pub_key
-                }
-            };
-
-            let server_pub_key =
-                Encryption::create_pub_key_from_base64(client_pub_key_string.as_str())?;
-            peer_encrypter = MessageEncrypter::new(server_pub_key)?;
-
-            info!("Sending public key to client...");
-            chat_connection.write_message(pub_key.as_str())?;
-
-            info!("Handshake completed!");
+            listener = Some(host_listener);
+            stream
         }
         RuntimeType::Client => {
             info!("Connecting to: {}", cli_args.host);
-            let stream = TcpStream::connect(cli_args.host)?;
-
-            info!("Connected! Creating connection manager...");
-            chat_connection = Connection::new_from_connection(stream)?;
-
-            info!("Starting handshake...");
-            info!("Sending public key...");
-            chat_connection.write_message(pub_key.as_str())?;
-
-            info!("Waiting for server response to complete handshake...");
-            let server_pub_key_string = match chat_connection.read_message()? {
-                None => {
-                    error!("Handshake failed! No public key received from server.");
-                    anyhow::bail!("Handshake failed! No public key received from server.");
-                }
-                Some(received_server_pub_key) => {
-                    info!("Received public key from server. Completing handshake...");
-                    received_server_pub_key
-                }
-            };
+            let stream = TcpStream::connect(&cli_args.host)?;
+            info!("Connected!");
+            stream
+        }
+        RuntimeType::Relayed { room: relay_room } => {
+            info!("Connecting to relay: {}", cli_args.host);
+            let stream = TcpStream::connect(&cli_args.host)?;
+            info!("Connected to the relay!");
+            room = Some(relay_room);
+            stream
+        }
+        RuntimeType::RelayServer => {
+            anyhow::bail!("The relay server doesn't start a chat session");
+        }
+    };
 
-            let server_pub_key =
-                Encryption::create_pub_key_from_base64(server_pub_key_string.as_str())?;
-            peer_encrypter = MessageEncrypter::new(server_pub_key)?;
-            info!("Handshake completed!");
+    info!("Creating connection manager...");
+    let mut chat_connection = Connection::open(stream)?;
+    let is_host = match &room {
+        Some(room) => {
+            info!("Waiting for the other peer to join the room...");
+            join_room(&mut chat_connection, room)?
         }
+        None => listener.is_some(),
     };
+    let peer_encrypter = handshake(&mut chat_connection, &pub_key, is_host)?;
 
     Ok(ChatSession::new(
         cli_args.role.clone().to_string(),
+        listener,
+        cli_args.host,
+        room,
         chat_connection,
         keypair,
         peer_encrypter,
@@ -107,18 +96,121 @@ pub fn start_chat_session(cli_args: WhisperArgs) -> Result<ChatSession> {
     ))
 }
 
+/// Exchanges the public keys. The client sends its key first, then the host answers with its
+/// own. Returns the encrypter for the peer's key.
+fn handshake(
+    chat_connection: &mut Connection,
+    pub_key: &str,
+    is_host: bool,
+) -> Result<MessageEncrypter> {
+    info!("Starting handshake...");
+    let peer_name = if is_host { "client" } else { "server" };
+
+    if !is_host {
+        info!("Sending public key...");
+        chat_connection.write_message(pub_key)?;
+    }
+
+    info!("Waiting for {} to send public key...", peer_name);
+    let peer_pub_key_string = match chat_connection.read_message()? {
+        None => {
+            error!(
+                "Handshake failed! No public key received from {}.",
+                peer_name
+            );
+            anyhow::bail!(
+                "Handshake failed! No public key received from {}.",
+                peer_name
+            );
+        }
+        Some(received_pub_key) => {
+            info!("Received public key from {}...", peer_name);
+            received_pub_key
+        }
+    };
+
+    let peer_pub_key = Encryption::create_pub_key_from_base64(peer_pub_key_string.as_str())?;
+    let peer_encrypter = MessageEncrypter::new(peer_pub_key)?;
+
+    if is_host {
+        info!("Sending public key to client...");
+        chat_connection.write_message(pub_key)?;
+    }
+
+    info!("Handshake completed!");
+    Ok(peer_encrypter)
+}
+
+/// Connects to the peer again, re-running the handshake, until it works. The host accepts the
+/// next client on the same listener; the client retries with an exponential backoff. Through a
+/// relay, the peer connects to the relay again (with the backoff) and waits in the same room.
+fn reconnect(
+    listener: Option<&TcpListener>,
+    address: &str,
+    room: Option<&str>,
+    pub_key: &str,
+    state_tx: &Sender<ConnectionState>,
+) -> (Connection, MessageEncrypter) {
+    let mut backoff = Backoff::default();
+
+    loop {
+        let stream = match listener {
+            Some(listener) => {
+                let _ = state_tx.send(ConnectionState::WaitingForPeer);
+                info!("Waiting for the client to reconnect...");
+                listener.accept().map(|(socket, addr)| {
+                    info!("Client reconnected! Client address: {addr:?}");
+                    socket
+                })
+            }
+            None => {
+                let attempt = backoff.attempt();
+                let delay = backoff.next_delay();
+                let _ = state_tx.send(ConnectionState::Reconnecting { attempt });
+                info!(
+                    "Reconnecting to {} in {:?} (attempt {})",
+                    address, delay, attempt
+                );
+                sleep(delay);
+                TcpStream::connect(address)
+            }
+        };
+
+        let connected = stream
+            .map_err(|e| anyhow!(e))
+            .and_then(Connection::open)
+            .and_then(|mut chat_connection| {
+                let is_host = match room {
+                    Some(room) => {
+                        let _ = state_tx.send(ConnectionState::WaitingForPeer);
+                        join_room(&mut chat_connection, room)?
+                    }
+                    None => listener.is_some(),
+                };
+                let peer_encrypter = handshake(&mut chat_connection, pub_key, is_host)?;
+                Ok((chat_connection, peer_encrypter))
+            });
+
+        match connected {
+            Ok(link) => return link,
+            Err(e) => warn!("Reconnection failed: {}", e),
+        }
+    }
+}
+
 pub fn create_handlers(
     mut chat_session: ChatSession,
-) -> Result<(
-    JoinHandle<Result<()>>,
-    JoinHandle<Result<()>>,
-    JoinHandle<Result<()>>,
-)> {
+    theme: ChatTheme,
+) -> Result<(Handler, Handler)> {
     // Taking ownership of the required values before moving them into the threads.
     let outgoing_msg_receiver = chat_session.take_outgoing_receiver();
-    let outgoing_conn = chat_session.split_connection()?;
-    let incoming_conn = chat_session.split_connection()?;
+    let first_conn = chat_session.split_connection()?;
     let incoming_msg_receiver = chat_session.take_incoming_receiver();
+    let listener = chat_session.take_listener();
+    let address = chat_session.get_address();
+    let room = chat_session.get_room();
+    let pub_key = chat_session.get_public_key()?;
+    let (state_tx, state_rx) = mpsc::channel::<ConnectionState>();
 
     // Cloning the encrypters/decrypters
     // Note: Not a fan of cloning the private key object, but its ok for now.
@@ -126,84 +218,73 @@ pub fn create_handlers(
     let message_decrypter = chat_session.get_message_decrypter().clone();
 
     // Getting all the other values
-    let outgoing_role_name = chat_session.get_role_name().clone();
-    let incoming_role_name = chat_session.get_role_name().clone();
+    let connection_role_name = chat_session.get_role_name().clone();
     let ui_role_name = chat_session.get_role_name().clone();
     let incoming_msg_transmitter = chat_session.get_incoming_transmitter().clone();
     let outgoing_msg_transmitter = chat_session.get_outgoing_transmitter().clone();
 
     // Creating the threads
-    let outgoing_messages_handler = thread::spawn(move || -> Result<()> {
-        info!("Starting outgoing messages handler...");
-        let mut conn = outgoing_conn;
-        let encrypter = msg_encrypter;
-        let role_name = outgoing_role_name;
+    let connection_handler = thread::spawn(move || -> Result<()> {
+        info!("Starting connection handler...");
+        let role_name = connection_role_name;
         let out_msg_rx = outgoing_msg_receiver;
+        let mut conn = first_conn;
+        let mut encrypter = msg_encrypter;
+        let mut fingerprint = encrypter.get_pub_key_fingerprint()?;
+        // Message that was being sent when the connection dropped. Goes out after reconnecting.
+        let mut pending: Option<String> = None;
 
         loop {
-            match out_msg_rx.try_recv() {
-                Ok(plain_msg) => {
-                    if plain_msg.is_empty() {
-                        debug!("[{}] Empty message received. Ignoring...", role_name);
-                        continue;
-                    }
-
-                    let encrypted_msg = encrypter.encrypt_message(&plain_msg)?;
-                    debug!(
-                        "[{}] Sending encrypted message of size: {}. Msg: {}",
-                        role_name,
-                        encrypted_msg.len(),
-                        plain_msg
-                    );
-                    conn.write_message(encrypted_msg.as_str())?;
-                    debug!("[{}] Message sent successfully.", role_name);
-                }
-                Err(_) => {
-                    // Careful logging stuff here. No messages returned from the `try_recv` call
-                    // equals errors (even though it is now).
+            let _ = state_tx.send(ConnectionState::Connected {
+                peer_fingerprint: fingerprint.clone(),
+            });
+
+            let dropped = Arc::new(AtomicBool::new(false));
+            let reader = spawn_incoming_handler(
+                conn.split()?,
+                message_decrypter.clone(),
+                incoming_msg_transmitter.clone(),
+                dropped.clone(),
+                role_name.clone(),
+            );
+
+            let result = send_until_dropped(
+                &mut conn,
+                &encrypter,
+                &out_msg_rx,
+                &dropped,
+                &mut pending,
+                &role_name,
+            );
+
+            // Stops the incoming handler too, if it's still reading.
+            let _ = conn.connection.shutdown(Shutdown::Both);
+            let _ = reader.join();
+
+            match result {
+                Ok(()) => {
+                    debug!("[{}] UI closed. Stopping connection handler.", role_name);
+                    return Ok(());
                 }
+                Err(e) => warn!("[{}] Connection lost: {}", role_name, e),
             }
-            sleep(Duration::from_millis(100));
-        }
-    });
-
-    let incoming_message_handler = thread::spawn(move || -> Result<()> {
-        info!("Starting incoming messages handler...");
-        let mut conn = incoming_conn;
-        let decrypter = message_decrypter;
-        let incoming_msg_tx = incoming_msg_transmitter;
-        let role_name = incoming_role_name;
-
-        loop {
-            match conn.read_message()? {
-                None => {
-                    // Aaaaalll allooooone!
-                    // Nobody is talking to us now. :(
-                }
-                Some(encrypted_message) => {
-                    if encrypted_message.is_empty() {
-                        debug!("[{}] Empty message received. Ignoring...", role_name);
-                        continue;
-                    }
 
-                    debug!(
-                        "[{}] Received encrypted message of size: {}",
-                        role_name,
-                        encrypted_message.len()
-                    );
-
-                    let plain_message = decrypter.decrypt_message(encrypted_message.as_str())?;
-
-                    debug!(
-                        "[{}] Decrypted message size: {}",
-                        role_name,
-                        plain_message.len()
-                    );
-
-                    incoming_msg_tx.send(plain_message)?;
-                }
+            (conn, encrypter) = reconnect(
+                listener.as_ref(),
+                &address,
+                room.as_deref(),
+                &pub_key,
+                &state_tx,
+            );
+
+            let new_fingerprint = encrypter.get_pub_key_fingerprint()?;
+            if new_fingerprint != fingerprint {
+                warn!(
+                    "[{}] Peer key changed from {} to {}",
+                    role_name, fingerprint, new_fingerprint
+                );
             }
-            sleep(Duration::from_millis(100));
+            fingerprint = new_fingerprint;
         }
     });
 
@@ -213,7 +294,7 @@ pub fn create_handlers(
         let out_msg_tx = outgoing_msg_transmitter;
         let in_msg_rx = incoming_msg_receiver;
 
-        let ui = ChatUi::new(out_msg_tx, in_msg_rx, role_name);
+        let ui = ChatUi::new(out_msg_tx, in_msg_rx, state_rx, role_name, theme);
 
         let session_start = SystemTime::now();
 
@@ -223,15 +304,114 @@ pub fn create_handlers(
 
         info!("Disconnected. Session duration: {:?}", duration);
 
-        exit_success();
-
         Ok(())
     });
 
     // All set up.
-    Ok((
-        outgoing_messages_handler,
-        incoming_message_handler,
-        ui_handler,
-    ))
+    Ok((connection_handler, ui_handler))
+}
+
+/// Sends the messages typed in the UI, and heartbeats when idle, until the connection drops
+/// (error) or the UI is closed (`Ok`).
+fn send_until_dropped(
+    conn: &mut Connection,
+    encrypter: &MessageEncrypter,
+    out_msg_rx: &Receiver<String>,
+    dropped: &AtomicBool,
+    pending: &mut Option<String>,
+    role_name: &str,
+) -> Result<()> {
+    let mut last_write = Instant::now();
+
+    loop {
+        if dropped.load(Ordering::Relaxed) {
+            anyhow::bail!("the incoming messages handler stopped");
+        }
+
+        if let Some(plain_msg) = pending.as_ref() {
+            let encrypted_msg = encrypter.encrypt_message(plain_msg)?;
+            debug!(
+                "[{}] Sending encrypted message of size: {}. Msg: {}",
+                role_name,
+                encrypted_msg.len(),
+                plain_msg
+            );
+            conn.write_message(encrypted_msg.as_str())?;
+            debug!("[{}] Message sent successfully.", role_name);
+
+            *pending = None;
+            last_write = Instant::now();
+            continue;
+        }
+
+        match out_msg_rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(plain_msg) => {
+                if plain_msg.is_empty() {
+                    debug!("[{}] Empty message received. Ignoring...", role_name);
+                    continue;
+                }
+                *pending = Some(plain_msg);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if last_write.elapsed() >= HEARTBEAT_INTERVAL {
+                    conn.write_heartbeat()?;
+                    last_write = Instant::now();
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+    }
+}
+
+/// Reads, decrypts and hands the peer's messages to the UI. Sets `dropped` and stops when the
+/// connection fails (or times out).
+fn spawn_incoming_handler(
+    mut conn: Connection,
+    decrypter: MessageDecrypter,
+    incoming_msg_tx: Sender<String>,
+    dropped: Arc<AtomicBool>,
+    role_name: String,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        info!("Starting incoming messages handler...");
+
+        if let Err(e) = read_incoming_messages(&mut conn, &decrypter, &incoming_msg_tx, &role_name)
+        {
+            debug!("[{}] Incoming messages handler stopped: {}", role_name, e);
+        }
+        dropped.store(true, Ordering::Relaxed);
+    })
+}
+
+fn read_incoming_messages(
+    conn: &mut Connection,
+    decrypter: &MessageDecrypter,
+    incoming_msg_tx: &Sender<String>,
+    role_name: &str,
+) -> Result<()> {
+    loop {
+        match conn.read_message()? {
+            None => {
+                // Aaaaalll allooooone!
+                // Nobody is talking to us now. :( Just a heartbeat.
+            }
+            Some(encrypted_message) => {
+                debug!(
+                    "[{}] Received encrypted message of size: {}",
+                    role_name,
+                    encrypted_message.len()
+                );
+
+                let plain_message = decrypter.decrypt_message(encrypted_message.as_str())?;
+
+                debug!(
+                    "[{}] Decrypted message size: {}",
+                    role_name,
+                    plain_message.len()
+                );
+
+                incoming_msg_tx.send(plain_message)?;
+            }
+        }
+    }
 }
@@ -1,12 +1,18 @@
+use crate::files::resolve::{COOKIE_JAR_FILE_NAME, ENVS_DIR_NAME};
 use crate::files::ResolvedRunContext;
-use crate::models::{Cli, Command, DryRunArgs, ExecutionArgs, KeyValue, ListArgs, RunArgs};
+use crate::models::{
+    Cli, Command, CookieJarLocation, DryRunArgs, ExecutionArgs, ImportArgs, ImportSource, KeyValue,
+    ListArgs, ReportTarget, RunArgs, TransportArgs,
+};
+use crate::transport::{redact_proxy, CertificateFormat, TransportSettings};
 use camino::Utf8PathBuf;
 use clap::builder::ValueParser;
-use clap::{Arg, ArgAction, ArgMatches, Command as ClapCommand};
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command as ClapCommand};
 use shared::command_line::cli_builder::CommandExt;
 use shared::constants::general::DASH_LINE;
+use std::ffi::OsString;
 
-pub fn get_cli_arguments() -> Cli {
+pub fn get_cli_arguments(argv: Vec<OsString>) -> Cli {
     let matches = ClapCommand::new(env!("CARGO_PKG_NAME"))
         .add_basic_metadata(
             env!("CARGO_PKG_VERSION"),
@@ -18,19 +24,25 @@ pub fn get_cli_arguments() -> Cli {
         .subcommand(build_list_subcommand())
         .subcommand(build_run_subcommand())
         .subcommand(build_dry_run_subcommand())
-        .get_matches();
+        .subcommand(build_import_subcommand())
+        .get_matches_from(argv);
 
     let command = match matches.subcommand() {
         Some(("list", sub_matches)) => Command::List(parse_list_args(sub_matches)),
         Some(("run", sub_matches)) => Command::Run(parse_run_args(sub_matches)),
         Some(("dry-run", sub_matches)) => Command::DryRun(parse_dry_run_args(sub_matches)),
+        Some(("import", sub_matches)) => Command::Import(parse_import_args(sub_matches)),
         _ => unreachable!("clap enforces one of the known subcommands"),
     };
 
     Cli { command }
 }
 
-pub fn print_runtime_info(context: &ResolvedRunContext, args: &RunArgs) {
+pub fn print_runtime_info(
+    context: &ResolvedRunContext,
+    args: &RunArgs,
+    transport: &TransportSettings,
+) {
     println!(
         "{} v{}",
         env!("CARGO_PKG_NAME").to_uppercase(),
@@ -67,6 +79,82 @@ pub fn print_runtime_info(context: &ResolvedRunContext, args: &RunArgs) {
         println!("- JSON Output: {}", json_output);
     }
 
+    print_report_targets(args);
+
+    if args.test_mode {
+        println!("- Test Mode: enabled");
+    }
+
+    match args.cookie_jar.as_ref() {
+        Some(CookieJarLocation::ApiDefault) => println!(
+            "- Cookie Jar: {}/{ENVS_DIR_NAME}/{COOKIE_JAR_FILE_NAME}",
+            context.resolution.api
+        ),
+        Some(CookieJarLocation::Path(path)) => println!(
+            "- Cookie Jar: {}",
+            path.replace("{api}", &context.resolution.api)
+        ),
+        None => {}
+    }
+
+    if let Some(proxy) = transport.proxy.as_ref() {
+        println!("- Proxy: {}", redact_proxy(proxy));
+    }
+
+    if let Some(no_proxy) = transport.no_proxy.as_ref() {
+        println!("- No Proxy: {no_proxy}");
+    }
+
+    if let Some(cacert) = transport.cacert.as_ref() {
+        println!("- CA Bundle: {cacert}");
+    }
+
+    if let (Some(cert), Some(format)) = (
+        transport.client_cert.as_ref(),
+        transport.certificate_format(),
+    ) {
+        let format = match format {
+            CertificateFormat::Pem => "PEM",
+            CertificateFormat::Pkcs12 => "PKCS12",
+        };
+        println!("- Client Certificate: {cert} ({format})");
+    }
+
+    if transport.insecure {
+        println!("- TLS Verification: disabled (insecure)");
+    }
+
+    println!("{DASH_LINE}");
+    println!();
+}
+
+fn print_report_targets(args: &RunArgs) {
+    for report in &args.reports {
+        println!("- Report ({}): {}", report.format.name(), report.path);
+    }
+}
+
+pub fn print_batch_info(args: &RunArgs, requests: usize) {
+    println!(
+        "{} v{}",
+        env!("CARGO_PKG_NAME").to_uppercase(),
+        env!("CARGO_PKG_VERSION")
+    );
+    println!("{DASH_LINE}");
+    println!("- APIs: {}", args.exec.api);
+    println!("- Requests: {} ({requests} matched)", args.exec.file);
+    println!("- Parallel: {}", args.parallel);
+
+    if let Some(env_name) = args.exec.env.as_ref() {
+        println!("- Environment: {env_name}");
+    }
+
+    if let Some(json_output) = args.json_output.as_ref() {
+        println!("- JSON Output: {}", json_output);
+    }
+
+    print_report_targets(args);
+
     if args.test_mode {
         println!("- Test Mode: enabled");
     }
@@ -97,11 +185,19 @@ fn build_run_subcommand() -> ClapCommand {
                     .value_parser(clap::value_parser!(Utf8PathBuf))
                     .help("Write execution JSON report to the given path."),
             )
+            .arg(
+                Arg::new("report")
+                    .long("report")
+                    .value_name("FORMAT[=PATH]")
+                    .action(ArgAction::Append)
+                    .value_parser(clap::value_parser!(ReportTarget))
+                    .help("Also write a `junit` (XML) or `html` report. Repeatable. Default paths: whurl-report.xml / whurl-report.html."),
+            )
             .arg(
                 Arg::new("test")
                     .long("test")
                     .action(ArgAction::SetTrue)
-                    .help("Emit a concise test-style summary after execution."),
+                    .help("Emit a concise test-style summary after execution, and exit with code 4 when an `# @assert` fails."),
             )
             .arg(
                 Arg::new("print-only-full-response")
@@ -120,6 +216,71 @@ fn build_run_subcommand() -> ClapCommand {
                     .long("silent")
                     .action(ArgAction::SetTrue)
                     .help("Disable header/log output (behaves similar to legacy mode)."),
+            )
+            .arg(
+                Arg::new("parallel")
+                    .long("parallel")
+                    .short('p')
+                    .value_name("N")
+                    .default_value("1")
+                    .value_parser(clap::value_parser!(u16).range(1..))
+                    .help("When API/FILE have wildcards, run up to N of the selected requests at the same time."),
+            )
+            .arg(
+                Arg::new("cookie-jar")
+                    .long("cookie-jar")
+                    .value_name("PATH")
+                    .num_args(0..=1)
+                    .default_missing_value("")
+                    .help("Keep the cookies between runs. Defaults to <API>/_vars/.cookie-jar; `{api}` in PATH is replaced by the API name."),
+            )
+            .arg(
+                Arg::new("proxy")
+                    .long("proxy")
+                    .short('x')
+                    .value_name("URL")
+                    .help("Use a proxy for every request (http://, https://, socks4://, socks5://, socks5h://). Credentials can go in the URL."),
+            )
+            .arg(
+                Arg::new("no-proxy")
+                    .long("no-proxy")
+                    .value_name("HOSTS")
+                    .help("Comma-separated list of hosts that bypass the proxy."),
+            )
+            .arg(
+                Arg::new("cacert")
+                    .long("cacert")
+                    .value_name("PATH")
+                    .value_parser(clap::value_parser!(Utf8PathBuf))
+                    .help("CA bundle (PEM) used to verify the servers, e.g. a corporate root CA."),
+            )
+            .arg(
+                Arg::new("cert")
+                    .long("cert")
+                    .short('E')
+                    .value_name("PATH")
+                    .value_parser(clap::value_parser!(Utf8PathBuf))
+                    .help("Client certificate for mTLS. PEM, or PKCS12 when the file ends with .p12/.pfx."),
+            )
+            .arg(
+                Arg::new("key")
+                    .long("key")
+                    .value_name("PATH")
+                    .value_parser(clap::value_parser!(Utf8PathBuf))
+                    .help("Private key (PEM) of the client certificate, when not in the certificate file."),
+            )
+            .arg(
+                Arg::new("cert-password")
+                    .long("cert-password")
+                    .value_name("PASSWORD")
+                    .help("Password of the client certificate/key. Prefer the WHURL_CERT_PASSWORD environment variable."),
+            )
+            .arg(
+                Arg::new("insecure")
+                    .long("insecure")
+                    .short('k')
+                    .action(ArgAction::SetTrue)
+                    .help("Skip TLS certificate verification."),
             ),
     )
 }
@@ -141,6 +302,56 @@ fn build_dry_run_subcommand() -> ClapCommand {
     )
 }
 
+fn build_import_subcommand() -> ClapCommand {
+    ClapCommand::new("import")
+        .about("Generate request files from an OpenAPI spec or a Postman collection")
+        .arg(
+            Arg::new("openapi")
+                .long("openapi")
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(Utf8PathBuf))
+                .help("OpenAPI 3.x or Swagger 2.0 spec (JSON or YAML)."),
+        )
+        .arg(
+            Arg::new("postman")
+                .long("postman")
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(Utf8PathBuf))
+                .help("Postman collection (v2.0 or v2.1, JSON)."),
+        )
+        .group(
+            ArgGroup::new("source")
+                .args(["openapi", "postman"])
+                .required(true),
+        )
+        .arg(Arg::new("api").long("api").value_name("NAME").help(
+            "API directory to create the requests in. Defaults to the spec/collection title.",
+        ))
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .action(ArgAction::SetTrue)
+                .help("Overwrite request files that already exist."),
+        )
+}
+
+fn parse_import_args(matches: &ArgMatches) -> ImportArgs {
+    let source = match (
+        matches.get_one::<Utf8PathBuf>("openapi"),
+        matches.get_one::<Utf8PathBuf>("postman"),
+    ) {
+        (Some(path), _) => ImportSource::OpenApi(path.clone()),
+        (None, Some(path)) => ImportSource::Postman(path.clone()),
+        (None, None) => unreachable!("clap requires one of the import sources"),
+    };
+
+    ImportArgs {
+        source,
+        api: matches.get_one::<String>("api").cloned(),
+        force: matches.get_flag("force"),
+    }
+}
+
 fn parse_list_args(matches: &ArgMatches) -> ListArgs {
     ListArgs {
         api: matches.get_one::<String>("api").cloned(),
@@ -155,6 +366,35 @@ fn parse_run_args(matches: &ArgMatches) -> RunArgs {
         print_only_full_response: matches.get_flag("print-only-full-response"),
         print_only_response_body: matches.get_flag("print-only-response-body"),
         silent: matches.get_flag("silent"),
+        parallel: matches.get_one::<u16>("parallel").copied().unwrap_or(1) as usize,
+        transport: parse_transport_args(matches),
+        cookie_jar: parse_cookie_jar(matches),
+        reports: matches
+            .get_many::<ReportTarget>("report")
+            .unwrap_or_default()
+            .cloned()
+            .collect(),
+    }
+}
+
+fn parse_cookie_jar(matches: &ArgMatches) -> Option<CookieJarLocation> {
+    matches
+        .get_one::<String>("cookie-jar")
+        .map(|path| match path.trim() {
+            "" => CookieJarLocation::ApiDefault,
+            path => CookieJarLocation::Path(path.to_string()),
+        })
+}
+
+fn parse_transport_args(matches: &ArgMatches) -> TransportArgs {
+    TransportArgs {
+        proxy: matches.get_one::<String>("proxy").cloned(),
+        no_proxy: matches.get_one::<String>("no-proxy").cloned(),
+        cacert: matches.get_one::<Utf8PathBuf>("cacert").cloned(),
+        client_cert: matches.get_one::<Utf8PathBuf>("cert").cloned(),
+        client_key: matches.get_one::<Utf8PathBuf>("key").cloned(),
+        cert_password: matches.get_one::<String>("cert-password").cloned(),
+        insecure: matches.get_flag("insecure"),
     }
 }
 
@@ -199,13 +439,13 @@ fn add_execution_args(command: ClapCommand) -> ClapCommand {
         .arg(
             Arg::new("api")
                 .value_name("API")
-                .help("API directory containing the Hurl file.")
+                .help("API directory containing the Hurl file. `run` accepts wildcards (`*`, `?`).")
                 .required(true),
         )
         .arg(
             Arg::new("file")
                 .value_name("FILE")
-                .help("Name of the Hurl file to execute (extension optional, relative to the API directory).")
+                .help("Name of the Hurl file to execute (extension optional, relative to the API directory). `run` accepts wildcards (`*`, `?`) to run several requests.")
                 .required(true),
         )
         .arg(
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use hurl::http::Call;
+use hurl::runner::HurlResult;
+use regex::Regex;
+
+use crate::includer::{AssertDirective, IncludeResult};
+
+use super::subject::{split_token, unquote, Subject};
+use super::EngineError;
+
+/// A `# @assert` directive, ready to be checked against a response.
+///
+/// Syntax: `# @assert <subject> <predicate>`, where the subject is one of `status`, `header <name>`,
+/// `jsonpath <path>` or `latency` (milliseconds), and the predicate is `exists` or an operator
+/// (`==`, `!=`, `<`, `<=`, `>`, `>=`, `contains`, `matches`) followed by the expected value.
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    pub text: String,
+    pub line: u32,
+    subject: Subject,
+    predicate: Predicate,
+}
+
+#[derive(Debug, Clone)]
+enum Predicate {
+    Exists,
+    Equals(String),
+    NotEquals(String),
+    LessThan(f64),
+    LessOrEqual(f64),
+    GreaterThan(f64),
+    GreaterOrEqual(f64),
+    Contains(String),
+    Matches(Regex),
+}
+
+/// The assertions declared by one request file.
+#[derive(Debug)]
+pub struct FileAssertions {
+    pub source: Utf8PathBuf,
+    pub assertions: Vec<Assertion>,
+}
+
+#[derive(Debug)]
+pub struct AssertionOutcome {
+    pub text: String,
+    pub line: u32,
+    pub actual: Option<String>,
+    pub passed: bool,
+}
+
+/// The outcome of the assertions of one file, checked against its last request.
+/// `entry_index` is `None` when that request did not run (e.g. an earlier entry failed).
+#[derive(Debug)]
+pub struct AssertionReport {
+    pub source: Utf8PathBuf,
+    pub entry_index: Option<usize>,
+    pub outcomes: Vec<AssertionOutcome>,
+}
+
+impl AssertionReport {
+    pub fn passed(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.passed)
+    }
+}
+
+/// Parses the `# @assert` directives of every file in the include graph, so mistakes are reported
+/// before any request is sent. Files are sorted by path, to keep the reports in a stable order.
+pub fn prepare_assertions(includes: &IncludeResult) -> Result<Vec<FileAssertions>, EngineError> {
+    let mut prepared = includes
+        .assertions
+        .iter()
+        .map(|(path, directives)| parse_file_assertions(path, directives))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    prepared.sort_by(|left, right| left.source.cmp(&right.source));
+    Ok(prepared)
+}
+
+fn parse_file_assertions(
+    path: &Utf8Path,
+    directives: &[AssertDirective],
+) -> Result<FileAssertions, EngineError> {
+    let assertions = directives
+        .iter()
+        .map(|directive| {
+            Assertion::parse(&directive.expression, directive.line_number).map_err(|message| {
+                EngineError::InvalidAssertion {
+                    path: path.to_path_buf(),
+                    line: directive.line_number,
+                    message,
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(FileAssertions {
+        source: path.to_path_buf(),
+        assertions,
+    })
+}
+
+/// Checks the assertions of each file against the last call of the last entry that came from it.
+pub fn evaluate_assertions(
+    prepared: &[FileAssertions],
+    result: &HurlResult,
+    includes: &IncludeResult,
+) -> Vec<AssertionReport> {
+    let mut last_entry_by_file: HashMap<&Utf8Path, usize> = HashMap::new();
+    for (position, entry) in result.entries.iter().enumerate() {
+        if let Some(mapping) = includes.map_source(&entry.source_info) {
+            last_entry_by_file.insert(mapping.source.as_path(), position);
+        }
+    }
+
+    prepared
+        .iter()
+        .map(|file| {
+            let entry = last_entry_by_file
+                .get(file.source.as_path())
+                .map(|position| &result.entries[*position]);
+            let call = entry.and_then(|entry| entry.calls.last());
+
+            let outcomes = file
+                .assertions
+                .iter()
+                .map(|assertion| match call {
+                    Some(call) => assertion.check(call),
+                    None => AssertionOutcome {
+                        text: assertion.text.clone(),
+                        line: assertion.line,
+                        actual: None,
+                        passed: false,
+                    },
+                })
+                .collect();
+
+            AssertionReport {
+                source: file.source.clone(),
+                entry_index: call.and(entry.map(|entry| entry.entry_index)),
+                outcomes,
+            }
+        })
+        .collect()
+}
+
+impl Assertion {
+    pub fn parse(expression: &str, line: u32) -> Result<Self, String> {
+        let text = expression.trim().to_string();
+        if text.is_empty() {
+            return Err("empty assertion".to_string());
+        }
+
+        let (subject, rest) = Subject::parse(&text)?;
+
+        let predicate = parse_predicate(rest)?;
+        if matches!(subject, Subject::Status | Subject::Latency)
+            && matches!(predicate, Predicate::Exists)
+        {
+            return Err("`exists` only applies to `header` and `jsonpath`".to_string());
+        }
+
+        Ok(Self {
+            text,
+            line,
+            subject,
+            predicate,
+        })
+    }
+
+    fn check(&self, call: &Call) -> AssertionOutcome {
+        let actual = self.subject.value(call);
+        let passed = matches_predicate(actual.as_deref(), &self.predicate);
+
+        AssertionOutcome {
+            text: self.text.clone(),
+            line: self.line,
+            actual,
+            passed,
+        }
+    }
+}
+
+fn parse_predicate(raw: &str) -> Result<Predicate, String> {
+    let (operator, expected) = split_token(raw);
+    let expected = unquote(expected).to_string();
+
+    let number = |expected: &str| {
+        expected
+            .trim_end_matches("ms")
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("`{operator}` needs a number, got `{expected}`"))
+    };
+
+    let predicate = match operator {
+        "exists" => return Ok(Predicate::Exists),
+        "==" => Predicate::Equals(expected.clone()),
+        "!=" => Predicate::NotEquals(expected.clone()),
+        "<" => Predicate::LessThan(number(&expected)?),
+        "<=" => Predicate::LessOrEqual(number(&expected)?),
+        ">" => Predicate::GreaterThan(number(&expected)?),
+        ">=" => Predicate::GreaterOrEqual(number(&expected)?),
+        "contains" => Predicate::Contains(expected.clone()),
+        "matches" => Predicate::Matches(
+            Regex::new(&expected).map_err(|error| format!("invalid regex: {error}"))?,
+        ),
+        "" => return Err("missing predicate (e.g. `== 200`, `< 500`, `exists`)".to_string()),
+        other => return Err(format!("unknown operator `{other}`")),
+    };
+
+    if expected.is_empty() {
+        return Err(format!("`{operator}` needs an expected value"));
+    }
+
+    Ok(predicate)
+}
+
+fn matches_predicate(actual: Option<&str>, predicate: &Predicate) -> bool {
+    let Some(actual) = actual else {
+        return false;
+    };
+
+    let actual_number = actual.trim().parse::<f64>().ok();
+
+    match predicate {
+        Predicate::Exists => true,
+        Predicate::Equals(expected) => values_equal(actual, expected),
+        Predicate::NotEquals(expected) => !values_equal(actual, expected),
+        Predicate::LessThan(limit) => actual_number.is_some_and(|value| value < *limit),
+        Predicate::LessOrEqual(limit) => actual_number.is_some_and(|value| value <= *limit),
+        Predicate::GreaterThan(limit) => actual_number.is_some_and(|value| value > *limit),
+        Predicate::GreaterOrEqual(limit) => actual_number.is_some_and(|value| value >= *limit),
+        Predicate::Contains(expected) => actual.contains(expected.as_str()),
+        Predicate::Matches(regex) => regex.is_match(actual),
+    }
+}
+
+/// Numbers are compared by value, so `1.0 == 1`. Everything else is compared as text.
+fn values_equal(actual: &str, expected: &str) -> bool {
+    match (actual.trim().parse::<f64>(), expected.trim().parse::<f64>()) {
+        (Ok(left), Ok(right)) => left == right,
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_supported_assertions() {
+        for expression in [
+            "status == 200",
+            "header Content-Type contains json",
+            "header X-Request-Id exists",
+            r#"jsonpath $.slideshow.title == "Sample Slide Show""#,
+            "jsonpath $['items'][0].id >= 1",
+            "latency < 500ms",
+            r#"jsonpath $.url matches "^https://""#,
+        ] {
+            assert!(
+                Assertion::parse(expression, 1).is_ok(),
+                "failed to parse `{expression}`"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_assertions() {
+        for expression in [
+            "",
+            "body == 1",
+            "status",
+            "status exists",
+            "latency < fast",
+            "header",
+            "jsonpath items == 1",
+            "jsonpath $.items[x] == 1",
+        ] {
+            assert!(
+                Assertion::parse(expression, 1).is_err(),
+                "`{expression}` should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn compares_numbers_by_value_and_text_as_is() {
+        let predicate = |expression: &str| parse_predicate(expression).unwrap();
+
+        assert!(matches_predicate(Some("1.0"), &predicate("== 1")));
+        assert!(matches_predicate(Some("ok"), &predicate(r#"== "ok""#)));
+        assert!(!matches_predicate(Some("OK"), &predicate("== ok")));
+        assert!(matches_predicate(Some("120"), &predicate("<= 120")));
+        assert!(!matches_predicate(Some("abc"), &predicate("< 10")));
+        assert!(!matches_predicate(None, &predicate("!= 1")));
+    }
+}
@@ -0,0 +1,356 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use hurl::runner::HurlResult;
+use tracing::info;
+
+use crate::includer::{CaptureDirective, IncludeResult};
+use crate::models::VariableAccumulator;
+use crate::vars::VariableMap;
+
+use super::subject::Subject;
+use super::EngineError;
+
+/// A `# @capture` directive, ready to read its value from a response.
+///
+/// Syntax: `# @capture <name> = <subject>`, with the same subjects as `# @assert` (`status`,
+/// `header <name>`, `jsonpath <path>` or `latency`).
+#[derive(Debug, Clone)]
+pub struct Capture {
+    pub name: String,
+    pub line: u32,
+    subject: Subject,
+}
+
+/// The captures declared by one request file.
+#[derive(Debug)]
+pub struct FileCaptures {
+    pub source: Utf8PathBuf,
+    pub captures: Vec<Capture>,
+}
+
+/// A slice of the merged document, run on its own. `first_line` is the number of merged lines
+/// before it, so entry positions can be mapped back to the merged document.
+struct Stage<'a> {
+    text: String,
+    first_line: usize,
+    captures: Option<&'a FileCaptures>,
+}
+
+/// Parses the `# @capture` directives of every file in the include graph, so mistakes are reported
+/// before any request is sent. Files are sorted by where they end in the merged document, which is
+/// the order their captures become available.
+pub fn prepare_captures(includes: &IncludeResult) -> Result<Vec<FileCaptures>, EngineError> {
+    let mut prepared = includes
+        .captures
+        .iter()
+        .map(|(path, directives)| parse_file_captures(path, directives))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    prepared.sort_by_key(|file| last_line_index(includes, &file.source));
+    Ok(prepared)
+}
+
+fn parse_file_captures(
+    path: &Utf8Path,
+    directives: &[CaptureDirective],
+) -> Result<FileCaptures, EngineError> {
+    let captures = directives
+        .iter()
+        .map(|directive| {
+            Capture::parse(&directive.expression, directive.line_number).map_err(|message| {
+                EngineError::InvalidCapture {
+                    path: path.to_path_buf(),
+                    line: directive.line_number,
+                    message,
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(FileCaptures {
+        source: path.to_path_buf(),
+        captures,
+    })
+}
+
+/// Runs the merged document, feeding captured values to the requests that come after them.
+///
+/// Without captures, the document runs in one go. With captures, it is split right after each file
+/// that declares them: once that part runs, the values are read from the last response of the file
+/// and merged into `variables`, which the next part runs with. Values captured by Hurl's own
+/// `[Captures]` sections are carried over the same way. Entries are renumbered, so the combined
+/// result looks like a single run.
+pub fn run_with_captures<F>(
+    includes: &IncludeResult,
+    captures: &[FileCaptures],
+    variables: &mut VariableAccumulator,
+    log_captures: bool,
+    mut run: F,
+) -> Result<HurlResult, EngineError>
+where
+    F: FnMut(&str, &VariableMap) -> Result<HurlResult, EngineError>,
+{
+    let mut combined: Option<HurlResult> = None;
+
+    for stage in plan_stages(includes, captures) {
+        let mut result = run(&stage.text, variables.values())?;
+
+        let previous_entries = combined.as_ref().map_or(0, |c| c.entries.len());
+        relocate_entries(&mut result, stage.first_line, previous_entries);
+
+        if result.success {
+            if let Some(file) = stage.captures {
+                carry_hurl_captures(&result, variables);
+                apply_captures(file, &result, includes, variables, log_captures)?;
+            }
+        }
+
+        let success = result.success;
+        combined = Some(match combined {
+            None => result,
+            Some(mut combined) => {
+                combined.entries.extend(result.entries);
+                combined.cookies = result.cookies;
+                combined.success = success;
+                combined
+            }
+        });
+
+        // Hurl stops at the first failure; so do the stages.
+        if !success {
+            break;
+        }
+    }
+
+    combined.ok_or_else(|| EngineError::Runner {
+        message: "nothing to run".to_string(),
+    })
+}
+
+fn plan_stages<'a>(includes: &IncludeResult, captures: &'a [FileCaptures]) -> Vec<Stage<'a>> {
+    if captures.is_empty() {
+        return vec![Stage {
+            text: includes.merged.clone(),
+            first_line: 0,
+            captures: None,
+        }];
+    }
+
+    let lines = includes.merged.lines().collect::<Vec<_>>();
+    let stage_text = |start: usize, end: usize| format!("{}\n", lines[start..end].join("\n"));
+
+    let mut stages = Vec::new();
+    let mut start = 0;
+    for file in captures {
+        let end = last_line_index(includes, &file.source).map_or(start, |index| index + 1);
+        stages.push(Stage {
+            text: stage_text(start, end.max(start)),
+            first_line: start,
+            captures: Some(file),
+        });
+        start = end.max(start);
+    }
+
+    let has_requests = lines[start..].iter().any(|line| {
+        let line = line.trim();
+        !line.is_empty() && !line.starts_with('#')
+    });
+    if has_requests {
+        stages.push(Stage {
+            text: stage_text(start, lines.len()),
+            first_line: start,
+            captures: None,
+        });
+    }
+
+    stages
+}
+
+/// Index (in the merged document) of the last line that belongs to the file, including the
+/// closing include boundary.
+fn last_line_index(includes: &IncludeResult, path: &Utf8Path) -> Option<usize> {
+    includes
+        .line_map
+        .iter()
+        .rposition(|mapping| mapping.source == path)
+}
+
+fn relocate_entries(result: &mut HurlResult, first_line: usize, previous_entries: usize) {
+    for entry in &mut result.entries {
+        entry.entry_index += previous_entries;
+        entry.source_info.start.line += first_line;
+        entry.source_info.end.line += first_line;
+
+        for error in &mut entry.errors {
+            error.source_info.start.line += first_line;
+            error.source_info.end.line += first_line;
+        }
+    }
+}
+
+fn carry_hurl_captures(result: &HurlResult, variables: &mut VariableAccumulator) {
+    for entry in &result.entries {
+        for capture in &entry.captures {
+            variables.insert(
+                capture.name.clone(),
+                capture.value.to_string(),
+                format!("[Captures] of entry #{}", entry.entry_index),
+            );
+        }
+    }
+}
+
+fn apply_captures(
+    file: &FileCaptures,
+    result: &HurlResult,
+    includes: &IncludeResult,
+    variables: &mut VariableAccumulator,
+    log_captures: bool,
+) -> Result<(), EngineError> {
+    let call = result
+        .entries
+        .iter()
+        .rev()
+        .find(|entry| {
+            includes
+                .map_source(&entry.source_info)
+                .is_some_and(|mapping| mapping.source == file.source)
+        })
+        .and_then(|entry| entry.calls.last());
+
+    for capture in &file.captures {
+        let failure = |message: String| EngineError::CaptureFailed {
+            path: file.source.clone(),
+            line: capture.line,
+            message,
+        };
+
+        let Some(call) = call else {
+            return Err(failure("the file has no request that ran".to_string()));
+        };
+
+        let value = capture
+            .subject
+            .value(call)
+            .ok_or_else(|| failure(format!("`{}` is not in the response", capture.name)))?;
+
+        if log_captures {
+            info!(
+                "Captured `{}` from {}:{}",
+                capture.name, file.source, capture.line
+            );
+        }
+
+        variables.insert(
+            capture.name.clone(),
+            value,
+            format!("# @capture `{}` in `{}`", capture.name, file.source),
+        );
+    }
+
+    Ok(())
+}
+
+impl Capture {
+    pub fn parse(expression: &str, line: u32) -> Result<Self, String> {
+        let Some((name, subject)) = expression.split_once('=') else {
+            return Err("expected `<name> = <subject>`".to_string());
+        };
+
+        let name = name.trim();
+        if !is_valid_name(name) {
+            return Err(format!(
+                "invalid variable name `{name}` (use letters, digits, `_` and `-`)"
+            ));
+        }
+
+        let (subject, rest) = Subject::parse(subject)?;
+        if !rest.is_empty() {
+            return Err(format!("unexpected `{rest}` after the subject"));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            line,
+            subject,
+        })
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::FileResolver;
+    use crate::includer::Includer;
+    use std::fs;
+
+    #[test]
+    fn parses_capture_expressions() {
+        let capture = Capture::parse("token = jsonpath $.access_token", 3).unwrap();
+        assert_eq!(capture.name, "token");
+        assert_eq!(capture.line, 3);
+
+        for expression in [
+            "session_id = header X-Session-Id",
+            "login-status=status",
+            r#"first_id = jsonpath "$.items[0].id""#,
+        ] {
+            assert!(
+                Capture::parse(expression, 1).is_ok(),
+                "failed to parse `{expression}`"
+            );
+        }
+
+        for expression in [
+            "token jsonpath $.token",
+            "1token = status",
+            "token = body",
+            "token = status == 200",
+            " = header X-Id",
+        ] {
+            assert!(
+                Capture::parse(expression, 1).is_err(),
+                "`{expression}` should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn splits_stages_after_files_with_captures() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = Utf8PathBuf::from_path_buf(temp.path().join("requests")).expect("utf8 path");
+        fs::create_dir_all(root.join("auth")).unwrap();
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(
+            root.join("auth/login.hurl"),
+            "# @capture token = jsonpath $.token\nPOST https://example.com/login\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("api/me.hurl"),
+            "# @include auth/login\nGET https://example.com/me\nAuthorization: Bearer {{token}}\n",
+        )
+        .unwrap();
+
+        let includes = Includer::new(FileResolver::new(root.clone()))
+            .merge(root.join("api/me.hurl").as_path())
+            .expect("merge");
+        let captures = prepare_captures(&includes).unwrap();
+        let stages = plan_stages(&includes, &captures);
+
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].first_line, 0);
+        assert!(stages[0].text.contains("POST https://example.com/login"));
+        assert!(stages[0].captures.is_some());
+        assert_eq!(stages[1].first_line, 4);
+        assert!(stages[1].text.starts_with("# @include auth/login\nGET"));
+        assert!(stages[1].captures.is_none());
+    }
+}
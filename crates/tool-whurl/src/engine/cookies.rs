@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::sync::Mutex;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use hurl::runner::HurlResult;
+use tempfile::NamedTempFile;
+
+use crate::files::FileResolver;
+use crate::models::CookieJarLocation;
+
+use super::EngineError;
+
+const NETSCAPE_HEADER: &str = "# Netscape HTTP Cookie File\n# This file was generated by whurl\n\n";
+
+/// Cookies of one API, in the Netscape format curl reads and writes (one tab-separated line per
+/// cookie; `#HttpOnly_` prefixes the domain of HTTP-only cookies).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CookieJar {
+    lines: Vec<String>,
+}
+
+impl CookieJar {
+    pub fn parse(content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .filter(|line| !line.starts_with('#') || line.starts_with("#HttpOnly_"))
+            .filter(|line| line.split('\t').count() == 7)
+            .map(str::to_string)
+            .collect();
+
+        Self { lines }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Replaces the cookies with the ones curl held at the end of the run. Hurl starts from the
+    /// jar, so cookies the server didn't touch are still there, and the ones it expired are gone.
+    pub fn update(&mut self, result: &HurlResult) {
+        self.lines = result
+            .cookies
+            .iter()
+            .map(|cookie| cookie.to_string())
+            .collect();
+    }
+
+    pub fn to_netscape(&self) -> String {
+        let mut content = NETSCAPE_HEADER.to_string();
+        for line in &self.lines {
+            content.push_str(line);
+            content.push('\n');
+        }
+        content
+    }
+}
+
+#[derive(Debug)]
+struct ApiJar {
+    jar: CookieJar,
+    persist_to: Option<Utf8PathBuf>,
+}
+
+/// The cookie jars of a run, one per API, so a session opened by one request is reused by the
+/// next ones: capture stages, the other requests of a wildcard run and, with `--cookie-jar`, the
+/// next invocations.
+///
+/// Jars are loaded the first time a request of the API runs. With `--parallel`, requests of the
+/// same API share the jar, and the last one to finish decides what is kept.
+#[derive(Debug)]
+pub struct CookieSession {
+    resolver: FileResolver,
+    location: Option<CookieJarLocation>,
+    jars: Mutex<BTreeMap<String, ApiJar>>,
+}
+
+impl CookieSession {
+    pub fn new(resolver: FileResolver, location: Option<CookieJarLocation>) -> Self {
+        Self {
+            resolver,
+            location,
+            jars: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Writes the cookies of the API to a temporary file for curl to read, or returns `None` when
+    /// the jar is empty. The file is deleted once dropped.
+    pub fn input_file(&self, api: &str) -> Result<Option<NamedTempFile>, EngineError> {
+        let mut jars = self.lock();
+        let api_jar = self.load_jar(&mut jars, api)?;
+        if api_jar.jar.is_empty() {
+            return Ok(None);
+        }
+
+        let mut file = NamedTempFile::new().map_err(|source| EngineError::CookieJar {
+            path: Utf8PathBuf::from("<temporary cookie file>"),
+            source,
+        })?;
+        let path = Utf8PathBuf::from(file.path().to_string_lossy().into_owned());
+        file.write_all(api_jar.jar.to_netscape().as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|source| EngineError::CookieJar { path, source })?;
+
+        Ok(Some(file))
+    }
+
+    pub fn update(&self, api: &str, result: &HurlResult) -> Result<(), EngineError> {
+        let mut jars = self.lock();
+        self.load_jar(&mut jars, api)?.jar.update(result);
+        Ok(())
+    }
+
+    /// Saves the jars that came from `--cookie-jar` files. Returns the saved files.
+    pub fn save(&self) -> Result<Vec<Utf8PathBuf>, EngineError> {
+        let jars = self.lock();
+        let mut saved = Vec::new();
+
+        for api_jar in jars.values() {
+            let Some(path) = api_jar.persist_to.as_ref() else {
+                continue;
+            };
+
+            let write = || {
+                if let Some(parent) = path.parent().filter(|p| !p.as_str().is_empty()) {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, api_jar.jar.to_netscape())
+            };
+            write().map_err(|source| EngineError::CookieJar {
+                path: path.clone(),
+                source,
+            })?;
+            saved.push(path.clone());
+        }
+
+        Ok(saved)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, ApiJar>> {
+        self.jars.lock().expect("cookie jars lock poisoned")
+    }
+
+    fn load_jar<'a>(
+        &self,
+        jars: &'a mut BTreeMap<String, ApiJar>,
+        api: &str,
+    ) -> Result<&'a mut ApiJar, EngineError> {
+        if !jars.contains_key(api) {
+            let api_jar = self.open_jar(jars, api)?;
+            jars.insert(api.to_string(), api_jar);
+        }
+
+        Ok(jars.get_mut(api).expect("jar was just loaded"))
+    }
+
+    fn open_jar(&self, jars: &BTreeMap<String, ApiJar>, api: &str) -> Result<ApiJar, EngineError> {
+        let Some(location) = self.location.as_ref() else {
+            return Ok(ApiJar {
+                jar: CookieJar::default(),
+                persist_to: None,
+            });
+        };
+
+        let path = self.resolver.resolve_cookie_jar(api, location)?;
+
+        // Sessions of different APIs must not end up in the same file.
+        if let Some((other, _)) = jars
+            .iter()
+            .find(|(_, jar)| jar.persist_to.as_deref() == Some(path.as_path()))
+        {
+            return Err(EngineError::SharedCookieJar {
+                path,
+                first: other.clone(),
+                second: api.to_string(),
+            });
+        }
+
+        Ok(ApiJar {
+            jar: read_jar(&path)?,
+            persist_to: Some(path),
+        })
+    }
+}
+
+fn read_jar(path: &Utf8Path) -> Result<CookieJar, EngineError> {
+    if !path.is_file() {
+        return Ok(CookieJar::default());
+    }
+
+    fs::read_to_string(path)
+        .map(|content| CookieJar::parse(&content))
+        .map_err(|source| EngineError::CookieJar {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_netscape_lines_and_keeps_http_only_cookies() {
+        let content = "# Netscape HTTP Cookie File\n\
+            # This file was generated by Hurl\n\
+            \n\
+            example.com\tFALSE\t/\tFALSE\t0\tsession\tabc123\n\
+            #HttpOnly_api.example.com\tFALSE\t/\tTRUE\t1893456000\trefresh\txyz\n\
+            not a cookie line\n";
+
+        let jar = CookieJar::parse(content);
+
+        assert_eq!(jar.lines.len(), 2);
+        assert_eq!(CookieJar::parse(&jar.to_netscape()), jar);
+    }
+
+    #[test]
+    fn per_api_jars_load_and_save_their_own_file() {
+        let root = tempfile::tempdir().unwrap();
+        let root_path = Utf8PathBuf::from_path_buf(root.path().to_path_buf()).unwrap();
+        fs::create_dir_all(root_path.join("billing")).unwrap();
+        fs::create_dir_all(root_path.join("users")).unwrap();
+
+        let resolver = FileResolver::new(root_path.clone());
+        let billing_jar = resolver
+            .resolve_cookie_jar("billing", &CookieJarLocation::ApiDefault)
+            .unwrap();
+        fs::create_dir_all(billing_jar.parent().unwrap()).unwrap();
+        fs::write(
+            &billing_jar,
+            "example.com\tFALSE\t/\tFALSE\t0\tsession\tabc123\n",
+        )
+        .unwrap();
+
+        let session = CookieSession::new(resolver, Some(CookieJarLocation::ApiDefault));
+        assert!(session.input_file("billing").unwrap().is_some());
+        assert!(session.input_file("users").unwrap().is_none());
+
+        let saved = session.save().unwrap();
+        assert_eq!(saved.len(), 2);
+        assert!(root_path.join("users/_vars/.cookie-jar").is_file());
+    }
+
+    #[test]
+    fn rejects_one_file_for_several_apis() {
+        let root = tempfile::tempdir().unwrap();
+        let root_path = Utf8PathBuf::from_path_buf(root.path().to_path_buf()).unwrap();
+        fs::create_dir_all(root_path.join("billing")).unwrap();
+        fs::create_dir_all(root_path.join("users")).unwrap();
+
+        let shared_file = root_path.join("cookies.txt").to_string();
+        let session = CookieSession::new(
+            FileResolver::new(root_path),
+            Some(CookieJarLocation::Path(shared_file)),
+        );
+
+        assert!(session.input_file("billing").is_ok());
+        assert!(matches!(
+            session.input_file("users"),
+            Err(EngineError::SharedCookieJar { .. })
+        ));
+    }
+}
@@ -1,12 +1,14 @@
 use std::io;
+use std::path::Path;
 
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use hurl::runner::{self, RunnerOptionsBuilder, Value, VariableSet};
 use hurl::util::logger::{LoggerOptionsBuilder, Verbosity};
 use hurl::util::path::ContextDir;
 use hurl_core::input::Input;
 use thiserror::Error;
 
+use crate::transport::PreparedTransport;
 use crate::vars::VariableMap;
 
 #[derive(Debug, Error)]
@@ -18,6 +20,38 @@ pub enum EngineError {
     },
     #[error("Hurl runner error: {message}")]
     Runner { message: String },
+    #[error("invalid `# @assert` directive in {path} at line {line}: {message}")]
+    InvalidAssertion {
+        path: Utf8PathBuf,
+        line: u32,
+        message: String,
+    },
+    #[error("invalid `# @capture` directive in {path} at line {line}: {message}")]
+    InvalidCapture {
+        path: Utf8PathBuf,
+        line: u32,
+        message: String,
+    },
+    #[error("`# @capture` in {path} at line {line} failed: {message}")]
+    CaptureFailed {
+        path: Utf8PathBuf,
+        line: u32,
+        message: String,
+    },
+    #[error("failed to access cookie jar {path}: {source}")]
+    CookieJar {
+        path: Utf8PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("cookie jar {path} would be shared by APIs '{first}' and '{second}'; add `{{api}}` to the path")]
+    SharedCookieJar {
+        path: Utf8PathBuf,
+        first: String,
+        second: String,
+    },
+    #[error(transparent)]
+    Resolve(#[from] crate::files::ResolveError),
 }
 
 pub fn run_hurl(
@@ -26,11 +60,16 @@ pub fn run_hurl(
     variables: &VariableMap,
     verbosity: u8,
     file_root: Option<&Utf8Path>,
+    transport: &PreparedTransport,
+    cookie_file: Option<&Path>,
 ) -> Result<runner::HurlResult, EngineError> {
     let variable_set = build_variable_set(variables);
 
     let mut runner_options = RunnerOptionsBuilder::new();
     runner_options.follow_location(true);
+    apply_transport(&mut runner_options, transport);
+    // curl starts from these cookies; the ones it ends with come back in `HurlResult::cookies`.
+    runner_options.cookie_input_file(cookie_file.map(|path| path.to_string_lossy().into_owned()));
 
     if let Some(root) = file_root {
         let current_dir =
@@ -60,6 +99,17 @@ pub fn run_hurl(
     .map_err(|message| EngineError::Runner { message })
 }
 
+/// Entry-level `[Options]` (e.g. `proxy`, `cacert`, `cert`, `insecure`) still override these.
+fn apply_transport(runner_options: &mut RunnerOptionsBuilder, transport: &PreparedTransport) {
+    runner_options
+        .proxy(transport.proxy.clone())
+        .no_proxy(transport.no_proxy.clone())
+        .cacert_file(transport.cacert_file.clone())
+        .client_cert_file(transport.client_cert_file.clone())
+        .client_key_file(transport.client_key_file.clone())
+        .insecure(transport.insecure);
+}
+
 fn build_variable_set(values: &VariableMap) -> VariableSet {
     let mut set = VariableSet::new();
     for (key, value) in values {
@@ -1,3 +1,12 @@
+mod assertions;
+mod captures;
+mod cookies;
 mod embedded;
+mod pool;
+mod subject;
 
+pub use assertions::{evaluate_assertions, prepare_assertions, AssertionReport};
+pub use captures::{prepare_captures, run_with_captures};
+pub use cookies::CookieSession;
 pub use embedded::{run_hurl, EngineError};
+pub use pool::run_pool;
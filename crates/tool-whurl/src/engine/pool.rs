@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Runs `task` for every item, with at most `workers` of them running at the same time.
+///
+/// Workers take the next pending item as soon as they are free, so a slow request doesn't hold
+/// the others back. The results are returned in the order of `items`, whatever order the tasks
+/// finished in, which keeps reports and summaries stable between runs.
+pub fn run_pool<T, R, F>(items: &[T], workers: usize, task: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let slots = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<Option<R>>>());
+    let workers = workers.clamp(1, items.len().max(1));
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+
+                let result = task(item);
+                slots.lock().expect("task pool results lock poisoned")[index] = Some(result);
+            });
+        }
+    });
+
+    slots
+        .into_inner()
+        .expect("task pool results lock poisoned")
+        .into_iter()
+        .map(|slot| slot.expect("every task runs before the pool finishes"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn keeps_item_order_and_concurrency_limit() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items = (0..12u64).collect::<Vec<_>>();
+
+        let results = run_pool(&items, 3, |item| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            // Earlier items take longer, so they finish last.
+            thread::sleep(Duration::from_millis(12 - item));
+            running.fetch_sub(1, Ordering::SeqCst);
+            item * 10
+        });
+
+        assert_eq!(
+            results,
+            items.iter().map(|item| item * 10).collect::<Vec<_>>()
+        );
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn handles_empty_input() {
+        let results: Vec<u8> = run_pool(&Vec::<u8>::new(), 4, |item| *item);
+        assert!(results.is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+use hurl::http::Call;
+use serde_json::Value;
+
+/// The part of a response a directive reads: `status`, `header <name>`, `jsonpath <path>` or
+/// `latency` (milliseconds). Shared by `# @assert` and `# @capture`.
+#[derive(Debug, Clone)]
+pub enum Subject {
+    Status,
+    Header(String),
+    JsonPath(Vec<PathSegment>),
+    Latency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl Subject {
+    /// Parses the subject at the start of `raw`, returning it with the rest of the text.
+    pub fn parse(raw: &str) -> Result<(Self, &str), String> {
+        let (keyword, rest) = split_token(raw);
+
+        match keyword.to_ascii_lowercase().as_str() {
+            "status" => Ok((Subject::Status, rest)),
+            "latency" => Ok((Subject::Latency, rest)),
+            "header" => {
+                let (name, rest) = split_token(rest);
+                if name.is_empty() {
+                    return Err("`header` needs the header name".to_string());
+                }
+                Ok((Subject::Header(name.to_string()), rest))
+            }
+            "jsonpath" => {
+                let (path, rest) = split_token(rest);
+                let path = unquote(path);
+                Ok((Subject::JsonPath(parse_json_path(path)?), rest))
+            }
+            "" => Err("missing subject".to_string()),
+            other => Err(format!(
+                "unknown subject `{other}` (expected status, header, jsonpath or latency)"
+            )),
+        }
+    }
+
+    /// The value of the subject in the response, as text. `None` when it isn't there.
+    pub fn value(&self, call: &Call) -> Option<String> {
+        match self {
+            Subject::Status => Some(call.response.status.to_string()),
+            Subject::Header(name) => call
+                .response
+                .headers
+                .get(name)
+                .map(|header| header.value.clone()),
+            Subject::JsonPath(segments) => {
+                let body = serde_json::from_slice::<Value>(&call.response.body).ok()?;
+                select_json_path(&body, segments).map(json_to_string)
+            }
+            Subject::Latency => Some(call.timings.total.as_millis().to_string()),
+        }
+    }
+}
+
+/// Supports `$`, `.key`, `['key']` and `[0]`, e.g. `$.items[0].name`.
+fn parse_json_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let Some(mut rest) = path.strip_prefix('$') else {
+        return Err(format!("JSON path must start with `$`, got `{path}`"));
+    };
+
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            let key = &after_dot[..end];
+            if key.is_empty() {
+                return Err(format!("empty key in JSON path `{path}`"));
+            }
+            segments.push(PathSegment::Key(key.to_string()));
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = after_bracket
+                .find(']')
+                .ok_or_else(|| format!("missing `]` in JSON path `{path}`"))?;
+            let inner = after_bracket[..end].trim();
+            let segment = if let Some(key) = inner
+                .strip_prefix('\'')
+                .and_then(|key| key.strip_suffix('\''))
+            {
+                PathSegment::Key(key.to_string())
+            } else {
+                PathSegment::Index(
+                    inner
+                        .parse()
+                        .map_err(|_| format!("invalid index `{inner}` in JSON path `{path}`"))?,
+                )
+            };
+            segments.push(segment);
+            rest = &after_bracket[end + 1..];
+        } else {
+            return Err(format!("unexpected `{rest}` in JSON path `{path}`"));
+        }
+    }
+
+    Ok(segments)
+}
+
+fn select_json_path<'a>(value: &'a Value, segments: &[PathSegment]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |current, segment| match segment {
+            PathSegment::Key(key) => current.get(key.as_str()),
+            PathSegment::Index(index) => current.get(*index),
+        })
+}
+
+fn json_to_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub fn split_token(raw: &str) -> (&str, &str) {
+    let raw = raw.trim_start();
+    match raw.find(char::is_whitespace) {
+        Some(end) => (&raw[..end], raw[end..].trim()),
+        None => (raw, ""),
+    }
+}
+
+pub fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn selects_json_path_values() {
+        let body = json!({ "items": [{ "id": 7, "tags": ["a"] }], "odd key": true });
+
+        let select = |path: &str| {
+            select_json_path(&body, &parse_json_path(path).unwrap()).map(json_to_string)
+        };
+
+        assert_eq!(select("$.items[0].id").as_deref(), Some("7"));
+        assert_eq!(select("$.items[0].tags[0]").as_deref(), Some("a"));
+        assert_eq!(select("$['odd key']").as_deref(), Some("true"));
+        assert_eq!(select("$.items[3]"), None);
+    }
+}
@@ -20,6 +20,12 @@ pub enum DiscoverError {
     RequestsDirMissing(Utf8PathBuf),
     #[error("API '{api}' not found under {root}")]
     ApiNotFound { api: String, root: Utf8PathBuf },
+    #[error("no requests match '{api}/{file}' under {root}")]
+    NoMatchingRequests {
+        api: String,
+        file: String,
+        root: Utf8PathBuf,
+    },
     #[error("failed to read directory {path}: {source}")]
     Io {
         path: Utf8PathBuf,
@@ -121,6 +127,81 @@ pub fn list_requests(requests_root: &Utf8Path, api: &str) -> Result<Vec<String>,
     Ok(requests)
 }
 
+/// Whether an API or request name has wildcards (`*`, `?`), selecting several requests.
+pub fn is_pattern(name: &str) -> bool {
+    name.contains(['*', '?'])
+}
+
+/// Finds the requests matching an API pattern and a request pattern, as `(api, request)` pairs
+/// sorted by API then request name. Patterns without wildcards match only themselves.
+pub fn discover_requests(
+    requests_root: &Utf8Path,
+    api_pattern: &str,
+    file_pattern: &str,
+) -> Result<Vec<(String, String)>, DiscoverError> {
+    let file_pattern = file_pattern.strip_suffix(".hurl").unwrap_or(file_pattern);
+
+    let apis = if is_pattern(api_pattern) {
+        list_apis(requests_root)?
+            .into_iter()
+            .filter(|api| wildcard_match(api_pattern, api))
+            .collect()
+    } else {
+        vec![api_pattern.to_string()]
+    };
+
+    let mut selected = Vec::new();
+    for api in apis {
+        for request in list_requests(requests_root, &api)? {
+            if wildcard_match(file_pattern, &request) {
+                selected.push((api.clone(), request));
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(DiscoverError::NoMatchingRequests {
+            api: api_pattern.to_string(),
+            file: file_pattern.to_string(),
+            root: requests_root.to_path_buf(),
+        });
+    }
+
+    Ok(selected)
+}
+
+/// `*` matches any run of characters, `?` a single one. Case-sensitive, like the file names.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 pub fn resolve_vars_file_path(api_root: &Utf8Path, vars_file: &Utf8PathBuf) -> Utf8PathBuf {
     if vars_file.is_absolute() {
         vars_file.clone()
@@ -182,3 +263,48 @@ pub fn load_dynamic_vars_file(
         Err(error) => Err(error.into()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_wildcards() {
+        assert!(wildcard_match("*", "basic"));
+        assert!(wildcard_match("user*", "users-list"));
+        assert!(wildcard_match("*-v?", "orders-v2"));
+        assert!(wildcard_match("a*b*c", "a-x-b-y-c"));
+        assert!(!wildcard_match("user*", "basic-user"));
+        assert!(!wildcard_match("*-v?", "orders-v10"));
+        assert!(!wildcard_match("basic", "basic2"));
+    }
+
+    #[test]
+    fn discovers_requests_in_name_order() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = Utf8PathBuf::from_path_buf(temp.path().to_path_buf()).expect("utf8 path");
+        for (api, file) in [("orders", "list"), ("orders", "create"), ("users", "list")] {
+            fs::create_dir_all(root.join(api)).unwrap();
+            fs::write(root.join(api).join(format!("{file}.hurl")), "").unwrap();
+        }
+        fs::create_dir_all(root.join("orders/_vars")).unwrap();
+
+        let all = discover_requests(&root, "*", "*").unwrap();
+        assert_eq!(
+            all,
+            vec![
+                ("orders".to_string(), "create".to_string()),
+                ("orders".to_string(), "list".to_string()),
+                ("users".to_string(), "list".to_string()),
+            ]
+        );
+
+        let lists = discover_requests(&root, "*", "list.hurl").unwrap();
+        assert_eq!(lists.len(), 2);
+
+        assert!(matches!(
+            discover_requests(&root, "orders", "delete*"),
+            Err(DiscoverError::NoMatchingRequests { .. })
+        ));
+    }
+}
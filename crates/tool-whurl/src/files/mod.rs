@@ -1,5 +1,7 @@
 pub mod discover;
 pub mod resolve;
 
-pub use discover::{list_apis, list_requests, locate_requests_root, DiscoverError};
+pub use discover::{
+    discover_requests, is_pattern, list_apis, list_requests, locate_requests_root, DiscoverError,
+};
 pub use resolve::{FileResolver, ResolveError, ResolvedRunContext};
@@ -1,7 +1,10 @@
 use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
 use thiserror::Error;
 
+use crate::models::CookieJarLocation;
+
 pub const ENVS_DIR_NAME: &str = "_vars";
+pub const COOKIE_JAR_FILE_NAME: &str = ".cookie-jar";
 
 #[derive(Debug, Error)]
 pub enum ResolveError {
@@ -173,6 +176,25 @@ impl FileResolver {
         })
     }
 
+    /// Where the cookies of an API are persisted: `<API>/_vars/.cookie-jar` by default, or the
+    /// given path with `{api}` replaced by the API name (relative to the current directory).
+    pub fn resolve_cookie_jar(
+        &self,
+        api: &str,
+        location: &CookieJarLocation,
+    ) -> Result<Utf8PathBuf, ResolveError> {
+        match location {
+            CookieJarLocation::ApiDefault => Ok(self
+                .resolve_api_root(api)?
+                .join(ENVS_DIR_NAME)
+                .join(COOKIE_JAR_FILE_NAME)),
+            CookieJarLocation::Path(path) => {
+                validate_component(api)?;
+                Ok(Utf8PathBuf::from(path.replace("{api}", api)))
+            }
+        }
+    }
+
     fn resolve_api_root(&self, api: &str) -> Result<Utf8PathBuf, ResolveError> {
         validate_component(api)?;
         let candidate = self.requests_root.join(api);
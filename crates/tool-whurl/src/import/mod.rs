@@ -0,0 +1,414 @@
+mod openapi;
+mod postman;
+
+pub use openapi::import_openapi;
+pub use postman::import_postman;
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::io;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::files::resolve::ENVS_DIR_NAME;
+use crate::vars::parse_variables_file;
+
+/// File, under `_vars`, that receives the variables of the imported requests.
+const GLOBAL_VARS_FILE: &str = "_global.hurlvars";
+
+/// Variables Hurl generates by itself, so they never go to the vars file.
+const HURL_FUNCTIONS: [&str; 2] = ["newUuid", "newDate"];
+
+static TEMPLATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_\-]*)\s*\}\}").unwrap());
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("failed to read {path}: {source}")]
+    Read {
+        path: Utf8PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to parse {path}: {message}")]
+    Parse { path: Utf8PathBuf, message: String },
+    #[error("{path} is not supported: {message}")]
+    Unsupported { path: Utf8PathBuf, message: String },
+    #[error("failed to write {path}: {source}")]
+    Write {
+        path: Utf8PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    Vars(#[from] crate::vars::VariableError),
+}
+
+/// An API read from a spec or collection, ready to be written as whurl request files.
+#[derive(Debug, Default)]
+pub struct ImportedApi {
+    /// Default API directory name (the spec/collection title).
+    pub name: String,
+    /// Variables for `_vars/_global.hurlvars`, in the order they were found.
+    pub variables: Vec<(String, String)>,
+    pub requests: Vec<ImportedRequest>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportedRequest {
+    /// File name, without the extension.
+    pub name: String,
+    pub description: Option<String>,
+    pub method: String,
+    /// URL without the query string. Parameters are `{{variables}}`.
+    pub url: String,
+    pub headers: Vec<Param>,
+    pub query: Vec<Param>,
+    pub basic_auth: Option<(String, String)>,
+    pub body: Option<ImportedBody>,
+}
+
+/// A header, query, or form parameter. Disabled (optional) ones are written commented out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub value: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ImportedBody {
+    /// A JSON body, written as is (Hurl sends it as `application/json`).
+    Json(String),
+    /// Any other body, written as a multiline string.
+    Raw(String),
+    Form(Vec<Param>),
+    /// Multipart fields. File fields already have Hurl's `file,<path>;` value.
+    Multipart(Vec<Param>),
+}
+
+/// What `write_api` did.
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub api_root: Utf8PathBuf,
+    pub written: Vec<String>,
+    pub skipped: Vec<String>,
+    pub variables_added: Vec<String>,
+}
+
+impl ImportedApi {
+    /// Adds a variable, unless there is one with the same name already (the first value wins).
+    pub fn add_variable(&mut self, name: &str, value: &str) {
+        if !self.variables.iter().any(|(existing, _)| existing == name) {
+            self.variables.push((name.to_string(), value.to_string()));
+        }
+    }
+}
+
+impl Param {
+    pub fn new(name: impl Into<String>, value: impl Into<String>, enabled: bool) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            enabled,
+        }
+    }
+}
+
+/// Writes the requests to `<requests_root>/<api>/<name>.hurl`, and their variables to
+/// `<api>/_vars/_global.hurlvars`.
+///
+/// Existing request files are kept unless `force` is set. Variables already in the vars file are
+/// never overwritten: only the missing ones are appended. Variables used by the requests (e.g.
+/// `{{token}}` in a Postman header) but not defined by the source are added with an empty value,
+/// so the file lists everything that needs to be filled in.
+pub fn write_api(
+    requests_root: &Utf8Path,
+    api: &ImportedApi,
+    force: bool,
+) -> Result<ImportSummary, ImportError> {
+    let api_root = requests_root.join(file_safe_name(&api.name, "imported"));
+    let vars_dir = api_root.join(ENVS_DIR_NAME);
+    std::fs::create_dir_all(&vars_dir).map_err(|source| ImportError::Write {
+        path: vars_dir.clone(),
+        source,
+    })?;
+
+    let mut summary = ImportSummary {
+        api_root: api_root.clone(),
+        ..ImportSummary::default()
+    };
+
+    let mut variables = api.variables.clone();
+    let mut used_names = BTreeSet::new();
+
+    for request in &api.requests {
+        let name = unique_name(&file_safe_name(&request.name, "request"), &mut used_names);
+        let content = render_request(request);
+
+        for captures in TEMPLATE_RE.captures_iter(&content) {
+            let variable = &captures[1];
+            if !HURL_FUNCTIONS.contains(&variable)
+                && !variables.iter().any(|(existing, _)| existing == variable)
+            {
+                variables.push((variable.to_string(), String::new()));
+            }
+        }
+
+        let path = api_root.join(format!("{name}.hurl"));
+        if path.exists() && !force {
+            summary.skipped.push(name);
+            continue;
+        }
+
+        std::fs::write(&path, content).map_err(|source| ImportError::Write {
+            path: path.clone(),
+            source,
+        })?;
+        summary.written.push(name);
+    }
+
+    let vars_path = vars_dir.join(GLOBAL_VARS_FILE);
+    let existing = if vars_path.is_file() {
+        parse_variables_file(&vars_path)?
+    } else {
+        Default::default()
+    };
+
+    let missing = variables
+        .iter()
+        .filter(|(name, _)| !existing.contains_key(name))
+        .collect::<Vec<_>>();
+
+    if !missing.is_empty() {
+        let mut content = std::fs::read_to_string(&vars_path).unwrap_or_default();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str("# Added by `whurl import`. Empty values still need to be filled in.\n");
+        for (name, value) in &missing {
+            let _ = writeln!(content, "{name}={value}");
+        }
+
+        std::fs::write(&vars_path, content).map_err(|source| ImportError::Write {
+            path: vars_path.clone(),
+            source,
+        })?;
+        summary.variables_added = missing.into_iter().map(|(name, _)| name.clone()).collect();
+    }
+
+    Ok(summary)
+}
+
+/// Renders a request in Hurl's format: description comments, method and URL, headers, sections,
+/// and then the body.
+fn render_request(request: &ImportedRequest) -> String {
+    let mut out = String::new();
+
+    if let Some(description) = request.description.as_deref() {
+        for line in description.lines().filter(|line| !line.trim().is_empty()) {
+            let _ = writeln!(out, "# {}", line.trim());
+        }
+    }
+
+    let _ = writeln!(out, "{} {}", request.method.to_uppercase(), request.url);
+    write_params(&mut out, &request.headers);
+
+    if !request.query.is_empty() {
+        out.push_str("[QueryStringParams]\n");
+        write_params(&mut out, &request.query);
+    }
+
+    if let Some((user, password)) = request.basic_auth.as_ref() {
+        out.push_str("[BasicAuth]\n");
+        let _ = writeln!(out, "{}: {}", escape_value(user), escape_value(password));
+    }
+
+    match request.body.as_ref() {
+        None => {}
+        Some(ImportedBody::Form(params)) => {
+            out.push_str("[FormParams]\n");
+            write_params(&mut out, params);
+        }
+        Some(ImportedBody::Multipart(params)) => {
+            out.push_str("[MultipartFormData]\n");
+            write_params(&mut out, params);
+        }
+        Some(ImportedBody::Json(json)) => {
+            let _ = writeln!(out, "{}", json.trim_end());
+        }
+        Some(ImportedBody::Raw(text)) => {
+            let _ = writeln!(out, "```\n{}\n```", text.trim_end());
+        }
+    }
+
+    out
+}
+
+fn write_params(out: &mut String, params: &[Param]) {
+    for param in params {
+        let _ = writeln!(
+            out,
+            "{}{}: {}",
+            if param.enabled { "" } else { "# " },
+            escape_key(&param.name),
+            escape_value(&param.value)
+        );
+    }
+}
+
+fn escape_key(key: &str) -> String {
+    key.replace('\\', "\\\\")
+        .replace(':', "\\:")
+        .replace('#', "\\#")
+        .replace(' ', "\\ ")
+}
+
+fn escape_value(value: &str) -> String {
+    value.replace('#', "\\#")
+}
+
+/// A name usable as a file/directory name: no path separators or characters Windows rejects.
+fn file_safe_name(name: &str, fallback: &str) -> String {
+    let cleaned = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '{' | '}' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect::<String>();
+
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let cleaned = cleaned.trim_matches(['.', '_', ' ']);
+
+    if cleaned.is_empty() {
+        fallback.to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Appends ` 2`, ` 3`, ... to names already taken (case-insensitive, like some file systems).
+fn unique_name(name: &str, used: &mut BTreeSet<String>) -> String {
+    let mut candidate = name.to_string();
+    let mut counter = 2;
+    while !used.insert(candidate.to_lowercase()) {
+        candidate = format!("{name} {counter}");
+        counter += 1;
+    }
+    candidate
+}
+
+/// A valid Hurl variable name for a parameter name (e.g. `page[size]` → `page_size_`).
+pub(crate) fn variable_name(name: &str) -> String {
+    let mut cleaned = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if !cleaned.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        cleaned.insert(0, '_');
+    }
+    cleaned
+}
+
+/// Text of a JSON value for a parameter or vars file: strings as is, other values as JSON.
+pub(crate) fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Reads a JSON or YAML file (JSON is valid YAML).
+fn read_document(path: &Utf8Path) -> Result<Value, ImportError> {
+    let content = std::fs::read_to_string(path).map_err(|source| ImportError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let content = content.trim_start_matches('\u{FEFF}');
+
+    serde_yaml::from_str::<Value>(content).map_err(|error| ImportError::Parse {
+        path: path.to_path_buf(),
+        message: error.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_requests_and_fills_missing_variables() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = Utf8PathBuf::from_path_buf(temp.path().to_path_buf()).expect("utf8 path");
+        fs_write(
+            &root.join("Pets/_vars/_global.hurlvars"),
+            "base_url=https://kept\n",
+        );
+
+        let api = ImportedApi {
+            name: "Pets".to_string(),
+            variables: vec![("base_url".to_string(), "https://new".to_string())],
+            requests: vec![
+                ImportedRequest {
+                    name: "get/pet".to_string(),
+                    description: Some("Finds a pet".to_string()),
+                    method: "get".to_string(),
+                    url: "{{base_url}}/pets/{{petId}}".to_string(),
+                    headers: vec![Param::new("Authorization", "Bearer {{token}}", true)],
+                    query: vec![
+                        Param::new("fields", "{{fields}}", true),
+                        Param::new("color#", "{{color}}", false),
+                    ],
+                    ..ImportedRequest::default()
+                },
+                ImportedRequest {
+                    name: "get:pet".to_string(),
+                    method: "POST".to_string(),
+                    url: "{{base_url}}/pets".to_string(),
+                    body: Some(ImportedBody::Json("{\"id\": {{newUuid}}}".to_string())),
+                    ..ImportedRequest::default()
+                },
+            ],
+        };
+
+        let summary = write_api(&root, &api, false).unwrap();
+
+        assert_eq!(summary.written, vec!["get pet", "get pet 2"]);
+        assert_eq!(
+            summary.variables_added,
+            vec!["petId", "token", "fields", "color"]
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("Pets/get pet.hurl")).unwrap(),
+            "# Finds a pet\nGET {{base_url}}/pets/{{petId}}\nAuthorization: Bearer {{token}}\n\
+             [QueryStringParams]\nfields: {{fields}}\n# color\\#: {{color}}\n"
+        );
+
+        let vars = parse_variables_file(&root.join("Pets/_vars/_global.hurlvars")).unwrap();
+        assert_eq!(vars.get("base_url").unwrap(), "https://kept");
+        assert_eq!(vars.get("petId").unwrap(), "");
+
+        let again = write_api(&root, &api, false).unwrap();
+        assert_eq!(again.skipped.len(), 2);
+        assert!(again.variables_added.is_empty());
+    }
+
+    fn fs_write(path: &Utf8Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+}
@@ -0,0 +1,574 @@
+use camino::Utf8Path;
+use serde_json::{Map, Value};
+
+use super::{
+    read_document, value_to_text, variable_name, ImportError, ImportedApi, ImportedBody,
+    ImportedRequest, Param,
+};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// How deep example bodies are generated from schemas (guards against recursive schemas).
+const MAX_SCHEMA_DEPTH: usize = 6;
+
+/// Reads an OpenAPI 3.x or Swagger 2.0 spec (JSON or YAML) and turns every operation into a
+/// request. Path, query, and header parameters become `{{variables}}`, with their example or
+/// default value as the initial value. The server URL becomes `{{base_url}}`.
+pub fn import_openapi(path: &Utf8Path) -> Result<ImportedApi, ImportError> {
+    let spec = read_document(path)?;
+
+    let is_swagger = spec.get("swagger").is_some();
+    if !is_swagger && spec.get("openapi").is_none() {
+        return Err(ImportError::Unsupported {
+            path: path.to_path_buf(),
+            message: "expected an `openapi` (3.x) or `swagger` (2.0) document".to_string(),
+        });
+    }
+
+    let mut api = ImportedApi {
+        name: spec
+            .pointer("/info/title")
+            .and_then(Value::as_str)
+            .unwrap_or("openapi")
+            .to_string(),
+        ..ImportedApi::default()
+    };
+    api.add_variable("base_url", &base_url(&spec, is_swagger));
+
+    let Some(paths) = spec.get("paths").and_then(Value::as_object) else {
+        return Ok(api);
+    };
+
+    for (route, item) in paths {
+        let item = resolve_ref(&spec, item);
+        let shared_params = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let request =
+                import_operation(&spec, &mut api, route, method, operation, &shared_params);
+            api.requests.push(request);
+        }
+    }
+
+    Ok(api)
+}
+
+fn import_operation(
+    spec: &Value,
+    api: &mut ImportedApi,
+    route: &str,
+    method: &str,
+    operation: &Value,
+    shared_params: &[Value],
+) -> ImportedRequest {
+    let text = |key: &str| operation.get(key).and_then(Value::as_str).map(str::trim);
+
+    let name = text("operationId")
+        .or_else(|| text("summary"))
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{method} {route}"));
+
+    let mut request = ImportedRequest {
+        name,
+        description: text("summary")
+            .or_else(|| text("description"))
+            .map(|summary| format!("{summary}\n{} {route}", method.to_uppercase())),
+        method: method.to_uppercase(),
+        url: format!("{{{{base_url}}}}{route}"),
+        ..ImportedRequest::default()
+    };
+
+    let mut form_params = Vec::new();
+    for parameter in merge_parameters(spec, shared_params, operation) {
+        let Some(name) = parameter.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let location = parameter.get("in").and_then(Value::as_str).unwrap_or("");
+        let required = parameter
+            .get("required")
+            .and_then(Value::as_bool)
+            .unwrap_or(location == "path");
+        let variable = variable_name(name);
+        let template = format!("{{{{{variable}}}}}");
+
+        match location {
+            "path" => {
+                request.url = request.url.replace(&format!("{{{name}}}"), &template);
+            }
+            "query" => request.query.push(Param::new(name, &template, required)),
+            "header" => request.headers.push(Param::new(name, &template, required)),
+            // Swagger 2.0 bodies and forms.
+            "body" => {
+                let schema = parameter.get("schema").unwrap_or(&Value::Null);
+                request.body = Some(json_body(&example_from_schema(spec, schema, 0)));
+                continue;
+            }
+            "formData" if parameter.get("type").and_then(Value::as_str) == Some("file") => {
+                form_params.push(Param::new(name, format!("file,{name}.bin;"), required));
+                continue;
+            }
+            "formData" => form_params.push(Param::new(name, &template, required)),
+            _ => continue,
+        }
+
+        api.add_variable(&variable, &parameter_example(spec, &parameter));
+    }
+
+    if !form_params.is_empty() {
+        let has_files = form_params
+            .iter()
+            .any(|param| param.value.starts_with("file,"));
+        request.body = Some(if has_files {
+            ImportedBody::Multipart(form_params)
+        } else {
+            ImportedBody::Form(form_params)
+        });
+    }
+
+    if let Some(body) = operation.get("requestBody") {
+        request.body = request_body(spec, resolve_ref(spec, body), api);
+    }
+
+    apply_security(spec, operation, &mut request, api);
+    request
+}
+
+/// Path-level parameters, overridden by the operation's own (same name and location).
+fn merge_parameters(spec: &Value, shared: &[Value], operation: &Value) -> Vec<Value> {
+    let resolve_all = |parameters: &[Value]| {
+        parameters
+            .iter()
+            .map(|parameter| resolve_ref(spec, parameter).clone())
+            .collect::<Vec<_>>()
+    };
+    let key = |parameter: &Value| (parameter.get("name").cloned(), parameter.get("in").cloned());
+
+    let own = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(|parameters| resolve_all(parameters))
+        .unwrap_or_default();
+
+    let mut merged = resolve_all(shared);
+    merged.retain(|parameter| !own.iter().any(|other| key(other) == key(parameter)));
+    merged.extend(own);
+    merged
+}
+
+fn parameter_example(spec: &Value, parameter: &Value) -> String {
+    let schema = parameter
+        .get("schema")
+        .map(|schema| resolve_ref(spec, schema));
+
+    [
+        parameter.get("example"),
+        parameter
+            .get("examples")
+            .and_then(Value::as_object)
+            .and_then(|examples| examples.values().next())
+            .and_then(|example| example.get("value")),
+        parameter.get("default"),
+        schema.and_then(|schema| schema.get("example")),
+        schema.and_then(|schema| schema.get("default")),
+    ]
+    .into_iter()
+    .flatten()
+    .next()
+    .map(value_to_text)
+    .unwrap_or_default()
+}
+
+fn request_body(spec: &Value, body: &Value, api: &mut ImportedApi) -> Option<ImportedBody> {
+    let content = body.get("content").and_then(Value::as_object)?;
+
+    if let Some((_, media)) = content.iter().find(|(kind, _)| kind.contains("json")) {
+        return Some(json_body(&media_example(spec, media)));
+    }
+
+    for (kind, multipart) in [
+        ("application/x-www-form-urlencoded", false),
+        ("multipart/form-data", true),
+    ] {
+        let Some(media) = content.get(kind) else {
+            continue;
+        };
+        let schema = resolve_ref(spec, media.get("schema").unwrap_or(&Value::Null));
+        let required = required_names(schema);
+        let example = example_from_schema(spec, schema, 0);
+
+        let mut params = Vec::new();
+        for (name, property) in properties(spec, schema) {
+            let value = if property.get("format").and_then(Value::as_str) == Some("binary") {
+                format!("file,{name}.bin;")
+            } else {
+                let variable = variable_name(&name);
+                api.add_variable(
+                    &variable,
+                    &example.get(&name).map(value_to_text).unwrap_or_default(),
+                );
+                format!("{{{{{variable}}}}}")
+            };
+            params.push(Param::new(&name, value, required.contains(&name)));
+        }
+
+        return Some(if multipart {
+            ImportedBody::Multipart(params)
+        } else {
+            ImportedBody::Form(params)
+        });
+    }
+
+    let (_, media) = content.iter().next()?;
+    let example = media_example(spec, media);
+    Some(ImportedBody::Raw(value_to_text(&example)))
+}
+
+fn media_example(spec: &Value, media: &Value) -> Value {
+    if let Some(example) = media.get("example") {
+        return example.clone();
+    }
+
+    let first_example = media
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|examples| examples.values().next())
+        .map(|example| resolve_ref(spec, example))
+        .and_then(|example| example.get("value"));
+    if let Some(example) = first_example {
+        return example.clone();
+    }
+
+    let schema = media.get("schema").unwrap_or(&Value::Null);
+    example_from_schema(spec, schema, 0)
+}
+
+fn json_body(example: &Value) -> ImportedBody {
+    ImportedBody::Json(serde_json::to_string_pretty(example).unwrap_or_else(|_| "{}".into()))
+}
+
+/// Builds a sample value from a schema: its `example`/`default`/first `enum` value, or a
+/// placeholder of the right type.
+fn example_from_schema(spec: &Value, schema: &Value, depth: usize) -> Value {
+    let schema = resolve_ref(spec, schema);
+
+    for key in ["example", "default"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+
+    if depth >= MAX_SCHEMA_DEPTH {
+        return Value::Null;
+    }
+
+    for combinator in ["allOf", "oneOf", "anyOf"] {
+        let Some(schemas) = schema.get(combinator).and_then(Value::as_array) else {
+            continue;
+        };
+
+        if combinator != "allOf" {
+            return schemas
+                .first()
+                .map(|first| example_from_schema(spec, first, depth + 1))
+                .unwrap_or(Value::Null);
+        }
+
+        let mut merged = Map::new();
+        for part in schemas {
+            if let Value::Object(fields) = example_from_schema(spec, part, depth + 1) {
+                merged.extend(fields);
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    let kind = schema.get("type").and_then(|kind| match kind {
+        Value::Array(kinds) => kinds.iter().find(|kind| *kind != "null"),
+        other => Some(other),
+    });
+
+    match kind.and_then(Value::as_str) {
+        Some("array") => {
+            let items = schema.get("items").unwrap_or(&Value::Null);
+            Value::Array(vec![example_from_schema(spec, items, depth + 1)])
+        }
+        Some("string") => Value::String(
+            match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => "2024-01-01T00:00:00Z",
+                Some("date") => "2024-01-01",
+                Some("email") => "user@example.com",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                Some("uri") | Some("url") => "https://example.com",
+                _ => "string",
+            }
+            .to_string(),
+        ),
+        Some("integer") | Some("number") => Value::from(0),
+        Some("boolean") => Value::Bool(false),
+        Some("object") | None if schema.get("properties").is_some() => Value::Object(
+            properties(spec, schema)
+                .into_iter()
+                .map(|(name, property)| (name, example_from_schema(spec, &property, depth + 1)))
+                .collect(),
+        ),
+        Some("object") => Value::Object(Map::new()),
+        _ => Value::Null,
+    }
+}
+
+fn properties(spec: &Value, schema: &Value) -> Vec<(String, Value)> {
+    resolve_ref(spec, schema)
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| (name.clone(), resolve_ref(spec, property).clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn required_names(schema: &Value) -> Vec<String> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Adds the credentials of the first security requirement of the operation (or of the spec):
+/// bearer tokens and header API keys as headers, basic auth as `[BasicAuth]`.
+fn apply_security(
+    spec: &Value,
+    operation: &Value,
+    request: &mut ImportedRequest,
+    api: &mut ImportedApi,
+) {
+    let requirement = operation
+        .get("security")
+        .or_else(|| spec.get("security"))
+        .and_then(Value::as_array)
+        .and_then(|requirements| requirements.first())
+        .and_then(Value::as_object);
+    let Some(requirement) = requirement else {
+        return;
+    };
+
+    for scheme_name in requirement.keys() {
+        let scheme = spec
+            .pointer(&format!("/components/securitySchemes/{scheme_name}"))
+            .or_else(|| spec.pointer(&format!("/securityDefinitions/{scheme_name}")));
+        let Some(scheme) = scheme.map(|scheme| resolve_ref(spec, scheme)) else {
+            continue;
+        };
+
+        let field = |key: &str| scheme.get(key).and_then(Value::as_str).unwrap_or("");
+        match (field("type"), field("scheme").to_ascii_lowercase().as_str()) {
+            ("http", "bearer") | ("oauth2", _) | ("openIdConnect", _) => {
+                request
+                    .headers
+                    .push(Param::new("Authorization", "Bearer {{token}}", true));
+                api.add_variable("token", "");
+            }
+            ("http", "basic") | ("basic", _) => {
+                request.basic_auth = Some(("{{username}}".into(), "{{password}}".into()));
+                api.add_variable("username", "");
+                api.add_variable("password", "");
+            }
+            ("apiKey", _) if field("in") == "header" => {
+                let variable = variable_name(&field("name").to_ascii_lowercase());
+                request.headers.push(Param::new(
+                    field("name"),
+                    format!("{{{{{variable}}}}}"),
+                    true,
+                ));
+                api.add_variable(&variable, "");
+            }
+            ("apiKey", _) if field("in") == "query" => {
+                let variable = variable_name(&field("name").to_ascii_lowercase());
+                request.query.push(Param::new(
+                    field("name"),
+                    format!("{{{{{variable}}}}}"),
+                    true,
+                ));
+                api.add_variable(&variable, "");
+            }
+            _ => {}
+        }
+    }
+}
+
+fn base_url(spec: &Value, is_swagger: bool) -> String {
+    let url = if is_swagger {
+        let scheme = spec
+            .pointer("/schemes/0")
+            .and_then(Value::as_str)
+            .unwrap_or("https");
+        let host = spec
+            .get("host")
+            .and_then(Value::as_str)
+            .unwrap_or("localhost");
+        let base_path = spec.get("basePath").and_then(Value::as_str).unwrap_or("");
+        format!("{scheme}://{host}{base_path}")
+    } else {
+        let server = spec.pointer("/servers/0");
+        let mut url = server
+            .and_then(|server| server.get("url"))
+            .and_then(Value::as_str)
+            .unwrap_or("http://localhost")
+            .to_string();
+
+        let variables = server
+            .and_then(|server| server.get("variables"))
+            .and_then(Value::as_object);
+        for (name, variable) in variables.into_iter().flatten() {
+            let default = variable
+                .get("default")
+                .map(value_to_text)
+                .unwrap_or_default();
+            url = url.replace(&format!("{{{name}}}"), &default);
+        }
+
+        if url.starts_with('/') {
+            url = format!("http://localhost{url}");
+        }
+        url
+    };
+
+    url.trim_end_matches('/').to_string()
+}
+
+/// Follows local `$ref`s (`#/components/...`). Unknown or external refs are returned as is.
+fn resolve_ref<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    // Bounded, so a ref cycle can't loop forever.
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        let Some(target) = current
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| spec.pointer(pointer))
+        else {
+            break;
+        };
+        current = target;
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+
+    #[test]
+    fn imports_openapi_operations() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = Utf8PathBuf::from_path_buf(temp.path().join("pets.yaml")).expect("utf8 path");
+        std::fs::write(
+            &path,
+            r#"
+openapi: 3.0.3
+info:
+  title: Pet Store
+servers:
+  - url: https://{env}.example.com/v1/
+    variables:
+      env:
+        default: api
+components:
+  securitySchemes:
+    bearer:
+      type: http
+      scheme: bearer
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string, example: Rex }
+        tags: { type: array, items: { type: string } }
+paths:
+  /pets/{petId}:
+    parameters:
+      - { name: petId, in: path, required: true, schema: { type: integer, example: 7 } }
+    get:
+      operationId: getPet
+      summary: Finds a pet
+      security: [{ bearer: [] }]
+      parameters:
+        - { name: fields, in: query, schema: { type: string } }
+        - { name: X-Trace, in: header, required: true, schema: { type: string } }
+    put:
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/Pet' }
+"#,
+        )
+        .unwrap();
+
+        let api = import_openapi(&path).unwrap();
+
+        assert_eq!(api.name, "Pet Store");
+        assert_eq!(api.requests.len(), 2);
+        assert_eq!(
+            api.variables[..2],
+            [
+                (
+                    "base_url".to_string(),
+                    "https://api.example.com/v1".to_string()
+                ),
+                ("petId".to_string(), "7".to_string()),
+            ]
+        );
+
+        let get = &api.requests[0];
+        assert_eq!(get.name, "getPet");
+        assert_eq!(get.url, "{{base_url}}/pets/{{petId}}");
+        assert_eq!(get.query, vec![Param::new("fields", "{{fields}}", false)]);
+        assert_eq!(
+            get.headers,
+            vec![
+                Param::new("X-Trace", "{{X-Trace}}", true),
+                Param::new("Authorization", "Bearer {{token}}", true),
+            ]
+        );
+
+        let put = &api.requests[1];
+        assert_eq!(put.name, "put /pets/{petId}");
+        let Some(ImportedBody::Json(body)) = put.body.as_ref() else {
+            panic!("expected a JSON body, got {:?}", put.body);
+        };
+        let body: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "name": "Rex", "tags": ["string"] })
+        );
+    }
+}
@@ -0,0 +1,450 @@
+use camino::Utf8Path;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde_json::{json, Value};
+use tracing::warn;
+
+use super::{
+    read_document, value_to_text, variable_name, ImportError, ImportedApi, ImportedBody,
+    ImportedRequest, Param,
+};
+
+static POSTMAN_TEMPLATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([^{}]+?)\s*\}\}").unwrap());
+
+/// Reads a Postman collection (v2.0 or v2.1) and turns every request into a request file.
+///
+/// Folders become a name prefix (`Folder - Request`). Collection variables go to the vars file,
+/// `:name` path variables become `{{name}}`, and disabled query parameters, headers, and form
+/// fields are kept as comments. Auth (bearer, basic, API key) is inherited from folders and the
+/// collection, like in Postman.
+pub fn import_postman(path: &Utf8Path) -> Result<ImportedApi, ImportError> {
+    let collection = read_document(path)?;
+
+    let Some(items) = collection.get("item").and_then(Value::as_array) else {
+        return Err(ImportError::Unsupported {
+            path: path.to_path_buf(),
+            message: "expected a Postman collection (v2.0 or v2.1) with an `item` list".to_string(),
+        });
+    };
+
+    let mut api = ImportedApi {
+        name: collection
+            .pointer("/info/name")
+            .and_then(Value::as_str)
+            .unwrap_or("postman")
+            .to_string(),
+        ..ImportedApi::default()
+    };
+
+    for variable in array(&collection, "variable") {
+        if let Some(key) = variable.get("key").and_then(Value::as_str) {
+            let value = variable.get("value").map(value_to_text).unwrap_or_default();
+            api.add_variable(&variable_name(key), &convert_templates(&value));
+        }
+    }
+
+    import_items(&mut api, items, "", collection.get("auth"));
+    Ok(api)
+}
+
+fn import_items(api: &mut ImportedApi, items: &[Value], prefix: &str, auth: Option<&Value>) {
+    for item in items {
+        let name = item
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("request");
+        let full_name = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix} - {name}")
+        };
+        let auth = item.get("auth").or(auth);
+
+        if let Some(children) = item.get("item").and_then(Value::as_array) {
+            import_items(api, children, &full_name, auth);
+        } else if let Some(request) = item.get("request") {
+            let request = import_request(api, request, full_name, auth);
+            api.requests.push(request);
+        }
+    }
+}
+
+fn import_request(
+    api: &mut ImportedApi,
+    request: &Value,
+    name: String,
+    auth: Option<&Value>,
+) -> ImportedRequest {
+    // A request can be just its URL.
+    let request = match request {
+        Value::String(url) => json!({ "method": "GET", "url": url }),
+        other => other.clone(),
+    };
+
+    let description = match request.get("description") {
+        Some(Value::String(text)) => Some(text.clone()),
+        Some(other) => other
+            .get("content")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        None => None,
+    };
+
+    let (url, query) = import_url(api, request.get("url").unwrap_or(&Value::Null));
+
+    let mut imported = ImportedRequest {
+        name,
+        description,
+        method: request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or("GET")
+            .to_uppercase(),
+        url,
+        headers: params(array(&request, "header")),
+        query,
+        ..ImportedRequest::default()
+    };
+
+    if let Some(body) = request.get("body") {
+        imported.body = import_body(body, &mut imported.headers);
+    }
+
+    if let Some(auth) = request.get("auth").or(auth) {
+        apply_auth(auth, &mut imported);
+    }
+
+    imported
+}
+
+/// Splits the URL from its query string, turning `:name` path variables into `{{name}}`.
+fn import_url(api: &mut ImportedApi, url: &Value) -> (String, Vec<Param>) {
+    let raw = match url {
+        Value::String(raw) => raw.clone(),
+        Value::Object(_) => url
+            .get("raw")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| url_from_parts(url)),
+        _ => String::new(),
+    };
+
+    let raw = raw.split('#').next().unwrap_or_default();
+    let (base, query_string) = raw.split_once('?').unwrap_or((raw, ""));
+
+    let base = base
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) if !name.is_empty() => format!("{{{{{}}}}}", variable_name(name)),
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    for variable in array(url, "variable") {
+        if let Some(key) = variable.get("key").and_then(Value::as_str) {
+            let value = variable.get("value").map(value_to_text).unwrap_or_default();
+            api.add_variable(&variable_name(key), &convert_templates(&value));
+        }
+    }
+
+    let query = if url.get("query").is_some() {
+        params(array(url, "query"))
+    } else {
+        query_string
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                Param::new(name, convert_templates(value), true)
+            })
+            .collect()
+    };
+
+    (convert_templates(&base), query)
+}
+
+fn url_from_parts(url: &Value) -> String {
+    let join = |key: &str, separator: &str| match url.get(key) {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(value_to_text)
+            .collect::<Vec<_>>()
+            .join(separator),
+        Some(other) => value_to_text(other),
+        None => String::new(),
+    };
+
+    let protocol = url
+        .get("protocol")
+        .and_then(Value::as_str)
+        .unwrap_or("https");
+    format!("{protocol}://{}/{}", join("host", "."), join("path", "/"))
+}
+
+fn import_body(body: &Value, headers: &mut Vec<Param>) -> Option<ImportedBody> {
+    let mode = body.get("mode").and_then(Value::as_str)?;
+
+    match mode {
+        "raw" => {
+            let text = body.get("raw").and_then(Value::as_str).unwrap_or_default();
+            if text.trim().is_empty() {
+                return None;
+            }
+
+            let content_type = match body
+                .pointer("/options/raw/language")
+                .and_then(Value::as_str)
+            {
+                Some("json") => Some("application/json"),
+                Some("xml") => Some("application/xml"),
+                Some("html") => Some("text/html"),
+                Some("text") => Some("text/plain"),
+                _ => None,
+            };
+            let has_content_type = headers
+                .iter()
+                .any(|header| header.name.eq_ignore_ascii_case("content-type"));
+            if let (Some(content_type), false) = (content_type, has_content_type) {
+                headers.push(Param::new("Content-Type", content_type, true));
+            }
+
+            // Kept raw: Postman JSON bodies often have unquoted `{{variables}}`.
+            Some(ImportedBody::Raw(convert_templates(text)))
+        }
+        "urlencoded" => Some(ImportedBody::Form(params(array(body, "urlencoded")))),
+        "formdata" => {
+            let fields = array(body, "formdata")
+                .iter()
+                .map(|field| {
+                    let mut param = param(field);
+                    if field.get("type").and_then(Value::as_str) == Some("file") {
+                        let source = match field.get("src") {
+                            Some(Value::Array(sources)) => {
+                                sources.first().map(value_to_text).unwrap_or_default()
+                            }
+                            Some(source) => value_to_text(source),
+                            None => String::new(),
+                        };
+                        param.value = format!("file,{source};");
+                    }
+                    param
+                })
+                .collect();
+            Some(ImportedBody::Multipart(fields))
+        }
+        "graphql" => {
+            let query = body
+                .pointer("/graphql/query")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let variables = body
+                .pointer("/graphql/variables")
+                .and_then(Value::as_str)
+                .and_then(|variables| serde_json::from_str::<Value>(variables).ok())
+                .unwrap_or_else(|| json!({}));
+            let payload = json!({ "query": query, "variables": variables });
+            Some(ImportedBody::Json(convert_templates(
+                &serde_json::to_string_pretty(&payload).unwrap_or_default(),
+            )))
+        }
+        _ => None,
+    }
+}
+
+fn apply_auth(auth: &Value, request: &mut ImportedRequest) {
+    let kind = auth.get("type").and_then(Value::as_str).unwrap_or("noauth");
+    let field = |key: &str| {
+        array(auth, kind)
+            .iter()
+            .find(|entry| entry.get("key").and_then(Value::as_str) == Some(key))
+            .and_then(|entry| entry.get("value"))
+            .map(value_to_text)
+            .map(|value| convert_templates(&value))
+            .filter(|value| !value.is_empty())
+    };
+
+    let has_authorization = request
+        .headers
+        .iter()
+        .any(|header| header.name.eq_ignore_ascii_case("authorization"));
+
+    match kind {
+        "bearer" if !has_authorization => {
+            let token = field("token").unwrap_or_else(|| "{{token}}".to_string());
+            request
+                .headers
+                .push(Param::new("Authorization", format!("Bearer {token}"), true));
+        }
+        "basic" if !has_authorization => {
+            request.basic_auth = Some((
+                field("username").unwrap_or_else(|| "{{username}}".to_string()),
+                field("password").unwrap_or_else(|| "{{password}}".to_string()),
+            ));
+        }
+        "apikey" => {
+            let name = field("key").unwrap_or_else(|| "X-API-Key".to_string());
+            let value = field("value").unwrap_or_else(|| "{{api_key}}".to_string());
+            if field("in").as_deref() == Some("query") {
+                request.query.push(Param::new(name, value, true));
+            } else {
+                request.headers.push(Param::new(name, value, true));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rewrites Postman `{{variables}}` for Hurl: names are made valid Hurl variable names, and
+/// `{{$guid}}`/`{{$randomUUID}}`/`{{$isoTimestamp}}` become Hurl's `newUuid`/`newDate`. Other
+/// dynamic variables have no Hurl equivalent: they become regular variables, to fill in.
+fn convert_templates(text: &str) -> String {
+    POSTMAN_TEMPLATE_RE
+        .replace_all(text, |captures: &Captures| {
+            let name = &captures[1];
+            let converted = match name {
+                "$guid" | "$randomUUID" => "newUuid".to_string(),
+                "$isoTimestamp" => "newDate".to_string(),
+                dynamic if dynamic.starts_with('$') => {
+                    warn!(
+                        "Postman dynamic variable `{{{{{dynamic}}}}}` has no Hurl equivalent; it was imported as a regular variable."
+                    );
+                    variable_name(dynamic)
+                }
+                other => variable_name(other),
+            };
+            format!("{{{{{converted}}}}}")
+        })
+        .into_owned()
+}
+
+fn params(entries: &[Value]) -> Vec<Param> {
+    entries.iter().map(param).collect()
+}
+
+fn param(entry: &Value) -> Param {
+    let text = |key: &str| {
+        entry
+            .get(key)
+            .map(value_to_text)
+            .map(|value| convert_templates(&value))
+            .unwrap_or_default()
+    };
+
+    Param::new(
+        text("key"),
+        text("value"),
+        !entry
+            .get("disabled")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    )
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+
+    #[test]
+    fn imports_postman_collections() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = Utf8PathBuf::from_path_buf(temp.path().join("shop.json")).expect("utf8 path");
+        std::fs::write(
+            &path,
+            r#"{
+  "info": { "name": "Shop", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+  "auth": { "type": "bearer", "bearer": [{ "key": "token", "value": "{{access token}}" }] },
+  "variable": [{ "key": "baseUrl", "value": "https://shop.example.com" }],
+  "item": [
+    {
+      "name": "Orders",
+      "item": [
+        {
+          "name": "Get order",
+          "request": {
+            "method": "GET",
+            "url": {
+              "raw": "{{baseUrl}}/orders/:orderId?expand=items&debug=1",
+              "query": [
+                { "key": "expand", "value": "items" },
+                { "key": "debug", "value": "1", "disabled": true }
+              ],
+              "variable": [{ "key": "orderId", "value": "42" }]
+            }
+          }
+        },
+        {
+          "name": "Create order",
+          "request": {
+            "method": "POST",
+            "auth": { "type": "noauth" },
+            "header": [{ "key": "X-Request-Id", "value": "{{$guid}}" }],
+            "body": {
+              "mode": "raw",
+              "raw": "{ \"qty\": {{qty}} }",
+              "options": { "raw": { "language": "json" } }
+            },
+            "url": "{{baseUrl}}/orders"
+          }
+        }
+      ]
+    }
+  ]
+}"#,
+        )
+        .unwrap();
+
+        let api = import_postman(&path).unwrap();
+
+        assert_eq!(api.name, "Shop");
+        assert_eq!(
+            api.variables,
+            vec![
+                (
+                    "baseUrl".to_string(),
+                    "https://shop.example.com".to_string()
+                ),
+                ("orderId".to_string(), "42".to_string()),
+            ]
+        );
+
+        let get = &api.requests[0];
+        assert_eq!(get.name, "Orders - Get order");
+        assert_eq!(get.url, "{{baseUrl}}/orders/{{orderId}}");
+        assert_eq!(
+            get.query,
+            vec![
+                Param::new("expand", "items", true),
+                Param::new("debug", "1", false),
+            ]
+        );
+        assert_eq!(
+            get.headers,
+            vec![Param::new("Authorization", "Bearer {{access_token}}", true)]
+        );
+
+        let create = &api.requests[1];
+        assert_eq!(create.url, "{{baseUrl}}/orders");
+        assert_eq!(
+            create.headers,
+            vec![
+                Param::new("X-Request-Id", "{{newUuid}}", true),
+                Param::new("Content-Type", "application/json", true),
+            ]
+        );
+        assert_eq!(
+            create.body,
+            Some(ImportedBody::Raw("{ \"qty\": {{qty}} }".to_string()))
+        );
+    }
+}
@@ -6,7 +6,10 @@ use hurl_core::ast::SourceInfo;
 use crate::files::resolve::{FileResolver, ResolvedInclude};
 
 use super::graph::IncludeTracker;
-use super::parse::{parse_top_comment_directives, FileDirectives, IncludeDirective, VarsDirective};
+use super::parse::{
+    parse_top_comment_directives, AssertDirective, CaptureDirective, FileDirectives,
+    IncludeDirective, TransportDirective, VarsDirective,
+};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct IncludeBehavior {
@@ -26,6 +29,9 @@ pub struct IncludeResult {
     pub line_map: Vec<LineMapping>,
     pub behaviors: HashMap<Utf8PathBuf, IncludeBehavior>,
     pub vars: HashMap<Utf8PathBuf, Vec<VarsDirective>>,
+    pub transport: HashMap<Utf8PathBuf, Vec<TransportDirective>>,
+    pub assertions: HashMap<Utf8PathBuf, Vec<AssertDirective>>,
+    pub captures: HashMap<Utf8PathBuf, Vec<CaptureDirective>>,
 }
 
 impl IncludeResult {
@@ -49,6 +55,13 @@ impl IncludeResult {
     pub fn behavior_for(&self, path: &Utf8Path) -> IncludeBehavior {
         self.behaviors.get(path).copied().unwrap_or_default()
     }
+
+    pub fn transport_for(&self, path: &Utf8Path) -> &[TransportDirective] {
+        self.transport
+            .get(path)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -119,8 +132,17 @@ impl Includer {
             source,
         })?;
 
-        let FileDirectives { includes, vars } = parse_top_comment_directives(&contents);
+        let FileDirectives {
+            includes,
+            vars,
+            transport,
+            assertions,
+            captures,
+        } = parse_top_comment_directives(&contents);
         state.register_vars(file_path, &vars);
+        state.register_transport(file_path, transport);
+        state.register_assertions(file_path, assertions);
+        state.register_captures(file_path, captures);
 
         for directive in includes {
             let resolved = self
@@ -182,6 +204,9 @@ struct MergeState {
     trailing_newline: bool,
     behaviors: HashMap<Utf8PathBuf, IncludeBehavior>,
     vars: HashMap<Utf8PathBuf, Vec<VarsDirective>>,
+    transport: HashMap<Utf8PathBuf, Vec<TransportDirective>>,
+    assertions: HashMap<Utf8PathBuf, Vec<AssertDirective>>,
+    captures: HashMap<Utf8PathBuf, Vec<CaptureDirective>>,
 }
 
 impl MergeState {
@@ -192,6 +217,9 @@ impl MergeState {
             trailing_newline: false,
             behaviors: HashMap::new(),
             vars: HashMap::new(),
+            transport: HashMap::new(),
+            assertions: HashMap::new(),
+            captures: HashMap::new(),
         }
     }
 
@@ -238,6 +266,30 @@ impl MergeState {
         entry.extend(directives.iter().cloned());
     }
 
+    fn register_transport(&mut self, path: &Utf8Path, directives: Vec<TransportDirective>) {
+        if directives.is_empty() {
+            return;
+        }
+
+        self.transport.insert(path.to_path_buf(), directives);
+    }
+
+    fn register_assertions(&mut self, path: &Utf8Path, directives: Vec<AssertDirective>) {
+        if directives.is_empty() {
+            return;
+        }
+
+        self.assertions.insert(path.to_path_buf(), directives);
+    }
+
+    fn register_captures(&mut self, path: &Utf8Path, directives: Vec<CaptureDirective>) {
+        if directives.is_empty() {
+            return;
+        }
+
+        self.captures.insert(path.to_path_buf(), directives);
+    }
+
     fn finish(self) -> IncludeResult {
         let mut merged = self
             .lines
@@ -261,6 +313,9 @@ impl MergeState {
             line_map,
             behaviors: self.behaviors,
             vars: self.vars,
+            transport: self.transport,
+            assertions: self.assertions,
+            captures: self.captures,
         }
     }
 }
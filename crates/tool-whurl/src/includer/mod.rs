@@ -3,3 +3,4 @@ mod merge;
 mod parse;
 
 pub use merge::{IncludeResult, Includer, IncluderError};
+pub use parse::{AssertDirective, CaptureDirective, TransportDirective};
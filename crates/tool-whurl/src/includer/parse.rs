@@ -14,15 +14,44 @@ pub struct VarsDirective {
     pub _line_number: u32,
 }
 
+/// Connection setting (`# @proxy`, `# @cacert`, `# @cert`, ...) declared at the top of a request file.
+#[derive(Debug, Clone)]
+pub struct TransportDirective {
+    pub key: String,
+    pub value: Option<String>,
+    pub line_number: u32,
+}
+
+/// Response expectation (`# @assert status == 200`) declared at the top of a request file.
+#[derive(Debug, Clone)]
+pub struct AssertDirective {
+    pub expression: String,
+    pub line_number: u32,
+}
+
+/// Response value (`# @capture token = jsonpath $.access_token`) saved as a variable for the
+/// requests that run after the file.
+#[derive(Debug, Clone)]
+pub struct CaptureDirective {
+    pub expression: String,
+    pub line_number: u32,
+}
+
 #[derive(Debug, Default)]
 pub struct FileDirectives {
     pub includes: Vec<IncludeDirective>,
     pub vars: Vec<VarsDirective>,
+    pub transport: Vec<TransportDirective>,
+    pub assertions: Vec<AssertDirective>,
+    pub captures: Vec<CaptureDirective>,
 }
 
 pub fn parse_top_comment_directives(contents: &str) -> FileDirectives {
     static INCLUDE_RE: OnceCell<Regex> = OnceCell::new();
     static VARS_RE: OnceCell<Regex> = OnceCell::new();
+    static TRANSPORT_RE: OnceCell<Regex> = OnceCell::new();
+    static ASSERT_RE: OnceCell<Regex> = OnceCell::new();
+    static CAPTURE_RE: OnceCell<Regex> = OnceCell::new();
 
     let include_re = INCLUDE_RE.get_or_init(|| {
         Regex::new(r"(?i)^#\s*@include(?:\s*:\s*\[(?P<opts>[^\]]*)\])?\s+(?P<path>.+?)\s*$")
@@ -33,6 +62,21 @@ pub fn parse_top_comment_directives(contents: &str) -> FileDirectives {
         Regex::new(r"(?i)^#\s*@vars\s+(?P<name>.+?)\s*$").expect("invalid vars regex")
     });
 
+    let transport_re = TRANSPORT_RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)^#\s*@(?P<key>proxy|no-proxy|cacert|cert|key|insecure)(?:\s+(?P<value>.+?))?\s*$",
+        )
+        .expect("invalid transport regex")
+    });
+
+    let assert_re = ASSERT_RE.get_or_init(|| {
+        Regex::new(r"(?i)^#\s*@assert\s+(?P<expression>.+?)\s*$").expect("invalid assert regex")
+    });
+
+    let capture_re = CAPTURE_RE.get_or_init(|| {
+        Regex::new(r"(?i)^#\s*@capture\s+(?P<expression>.+?)\s*$").expect("invalid capture regex")
+    });
+
     let mut directives = FileDirectives::default();
 
     for (idx, line) in contents.lines().enumerate() {
@@ -76,6 +120,37 @@ pub fn parse_top_comment_directives(contents: &str) -> FileDirectives {
                     _line_number: idx as u32 + 1,
                 });
             }
+            continue;
+        }
+
+        if let Some(caps) = assert_re.captures(trimmed) {
+            if let Some(expression) = caps.name("expression") {
+                directives.assertions.push(AssertDirective {
+                    expression: expression.as_str().to_string(),
+                    line_number: idx as u32 + 1,
+                });
+            }
+            continue;
+        }
+
+        if let Some(caps) = capture_re.captures(trimmed) {
+            if let Some(expression) = caps.name("expression") {
+                directives.captures.push(CaptureDirective {
+                    expression: expression.as_str().to_string(),
+                    line_number: idx as u32 + 1,
+                });
+            }
+            continue;
+        }
+
+        if let Some(caps) = transport_re.captures(trimmed) {
+            if let Some(key) = caps.name("key") {
+                directives.transport.push(TransportDirective {
+                    key: key.as_str().to_ascii_lowercase(),
+                    value: caps.name("value").map(|m| m.as_str().trim().to_string()),
+                    line_number: idx as u32 + 1,
+                });
+            }
         }
     }
 
@@ -101,6 +176,88 @@ GET https://example.com
         assert_eq!(directives.vars[0].name, "session");
     }
 
+    #[test]
+    fn parses_transport_directives() {
+        let contents = "\
+# @include auth/login
+# @proxy socks5h://proxy.local:1080
+# @CaCert ../certs/corp-ca.pem
+# @cert ../certs/client.p12
+# @insecure
+# @certificate is not a directive
+
+GET https://staging.example.com
+";
+        let directives = parse_top_comment_directives(contents);
+        let keys: Vec<_> = directives
+            .transport
+            .iter()
+            .map(|d| (d.key.as_str(), d.value.as_deref()))
+            .collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                ("proxy", Some("socks5h://proxy.local:1080")),
+                ("cacert", Some("../certs/corp-ca.pem")),
+                ("cert", Some("../certs/client.p12")),
+                ("insecure", None),
+            ]
+        );
+        assert_eq!(directives.transport[0].line_number, 2);
+    }
+
+    #[test]
+    fn parses_assert_directives() {
+        let contents = "\
+# @include auth/login
+# @assert status == 200
+# @ASSERT jsonpath $.title == \"Sample Slide Show\"
+# @assertion is not a directive
+
+GET https://httpbin.org/json
+";
+        let directives = parse_top_comment_directives(contents);
+        let expressions: Vec<_> = directives
+            .assertions
+            .iter()
+            .map(|d| (d.expression.as_str(), d.line_number))
+            .collect();
+
+        assert_eq!(
+            expressions,
+            vec![
+                ("status == 200", 2),
+                ("jsonpath $.title == \"Sample Slide Show\"", 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_capture_directives() {
+        let contents = "\
+# @capture token = jsonpath $.access_token
+# @Capture request_id = header X-Request-Id
+# @captured is not a directive
+
+POST https://example.com/login
+";
+        let directives = parse_top_comment_directives(contents);
+        let expressions: Vec<_> = directives
+            .captures
+            .iter()
+            .map(|d| (d.expression.as_str(), d.line_number))
+            .collect();
+
+        assert_eq!(
+            expressions,
+            vec![
+                ("token = jsonpath $.access_token", 1),
+                ("request_id = header X-Request-Id", 2),
+            ]
+        );
+    }
+
     #[test]
     fn ignores_non_comment_lines() {
         let contents = "\
@@ -0,0 +1,41 @@
+mod cli_utils;
+mod engine;
+mod files;
+mod import;
+mod includer;
+mod models;
+mod output;
+mod transport;
+mod vars;
+mod whurl_app;
+mod whurl_utils;
+
+use crate::cli_utils::get_cli_arguments;
+use crate::whurl_app::{execute, print_error, resolve_log_level};
+use shared::constants::general::EXIT_CODE_SUCCESS;
+use shared::logging::logging_helpers::initialize_log;
+use std::ffi::OsString;
+
+/// Hurl wrapper.
+///
+/// Parses arguments, then runs the subcommand.
+///
+/// `argv[0]` is the binary name. Called by the binary, and by the `rtb` launcher, which exit
+/// with the exit code it returns.
+pub fn run<I, S>(argv: I) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    let cli = get_cli_arguments(argv.into_iter().map(Into::into).collect());
+    let log_level = resolve_log_level(&cli);
+    initialize_log(env!("CARGO_PKG_NAME"), log_level);
+
+    match execute(cli) {
+        Ok(()) => EXIT_CODE_SUCCESS,
+        Err(error) => {
+            print_error(&error);
+            error.exit_code().into()
+        }
+    }
+}
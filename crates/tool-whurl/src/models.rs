@@ -16,6 +16,7 @@ pub enum Command {
     List(ListArgs),
     Run(RunArgs),
     DryRun(DryRunArgs),
+    Import(ImportArgs),
 }
 
 #[derive(Debug)]
@@ -31,6 +32,102 @@ pub struct RunArgs {
     pub print_only_full_response: bool,
     pub print_only_response_body: bool,
     pub silent: bool,
+    pub parallel: usize,
+    pub transport: TransportArgs,
+    pub cookie_jar: Option<CookieJarLocation>,
+    pub reports: Vec<ReportTarget>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ReportFormat {
+    Junit,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportFormat::Junit => "junit",
+            ReportFormat::Html => "html",
+        }
+    }
+
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            ReportFormat::Junit => "whurl-report.xml",
+            ReportFormat::Html => "whurl-report.html",
+        }
+    }
+}
+
+/// A `--report FORMAT[=PATH]` output.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ReportTarget {
+    pub format: ReportFormat,
+    pub path: Utf8PathBuf,
+}
+
+impl std::str::FromStr for ReportTarget {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, path) = match value.split_once('=') {
+            Some((name, path)) => (name, Some(path)),
+            None => (value, None),
+        };
+
+        let format = match name.trim().to_ascii_lowercase().as_str() {
+            "junit" => ReportFormat::Junit,
+            "html" => ReportFormat::Html,
+            other => {
+                return Err(format!(
+                    "unknown report format `{other}` (expected junit or html)"
+                ))
+            }
+        };
+
+        let path = match path.map(str::trim) {
+            Some("") => return Err(format!("missing path after `{}=`", format.name())),
+            Some(path) => Utf8PathBuf::from(path),
+            None => Utf8PathBuf::from(format.default_path()),
+        };
+
+        Ok(Self { format, path })
+    }
+}
+
+/// Where `--cookie-jar` persists the cookies of each API between runs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CookieJarLocation {
+    /// `<API>/_vars/.cookie-jar`.
+    ApiDefault,
+    /// A path of the user's choice; `{api}` is replaced by the API name.
+    Path(String),
+}
+
+/// Per-run proxy and TLS options. They override the matching directives of the request file.
+#[derive(Debug, Clone, Default)]
+pub struct TransportArgs {
+    pub proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub cacert: Option<Utf8PathBuf>,
+    pub client_cert: Option<Utf8PathBuf>,
+    pub client_key: Option<Utf8PathBuf>,
+    pub cert_password: Option<String>,
+    pub insecure: bool,
+}
+
+#[derive(Debug)]
+pub struct ImportArgs {
+    pub source: ImportSource,
+    pub api: Option<String>,
+    pub force: bool,
+}
+
+#[derive(Debug)]
+pub enum ImportSource {
+    OpenApi(Utf8PathBuf),
+    Postman(Utf8PathBuf),
 }
 
 #[derive(Debug)]
@@ -70,6 +167,7 @@ pub enum ExitCode {
     ExecutionFailure = 1,
     IncludeFailure = 2,
     Unknown = 3,
+    AssertionFailure = 4,
 }
 
 #[derive(Debug, Error)]
@@ -86,10 +184,16 @@ pub enum ToolError {
     Engine(#[from] crate::engine::EngineError),
     #[error(transparent)]
     Output(#[from] crate::output::OutputError),
+    #[error(transparent)]
+    Transport(#[from] crate::transport::TransportError),
+    #[error(transparent)]
+    Import(#[from] crate::import::ImportError),
     #[error("{0}")]
     Other(#[from] anyhow::Error),
     #[error("Hurl execution reported one or more failures")]
     ExecutionFailure,
+    #[error("One or more `# @assert` directives failed")]
+    AssertionFailure,
 }
 
 impl ToolError {
@@ -97,6 +201,7 @@ impl ToolError {
         match self {
             ToolError::Include(_) | ToolError::Resolve(_) => ExitCode::IncludeFailure,
             ToolError::ExecutionFailure => ExitCode::ExecutionFailure,
+            ToolError::AssertionFailure => ExitCode::AssertionFailure,
             _ => ExitCode::Unknown,
         }
     }
@@ -109,6 +214,7 @@ impl From<ExitCode> for i32 {
             ExitCode::ExecutionFailure => 1,
             ExitCode::IncludeFailure => 2,
             ExitCode::Unknown => 3,
+            ExitCode::AssertionFailure => 4,
         }
     }
 }
@@ -155,6 +261,10 @@ impl VariableAccumulator {
         self.origins.insert(key, origin_desc);
     }
 
+    pub fn values(&self) -> &VariableMap {
+        &self.values
+    }
+
     pub fn finish(self) -> VariableMap {
         self.values
     }
@@ -0,0 +1,237 @@
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use camino::Utf8Path;
+use hurl::http::Call;
+use hurl::runner::EntryResult;
+use hurl_core::error::DisplaySourceError;
+
+use crate::engine::AssertionReport;
+
+use super::{describe_call, entry_time, escape_markup, seconds, NotRun, ReportInput};
+
+/// Bodies longer than this are cut, so a large download doesn't make the report unusable.
+const MAX_BODY_BYTES: usize = 16 * 1024;
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { margin-bottom: 0.25rem; }
+.summary { color: #59636e; margin-bottom: 1.5rem; }
+details { border: 1px solid #d1d9e0; border-radius: 6px; margin-bottom: 0.75rem; }
+details > summary { cursor: pointer; padding: 0.6rem 0.8rem; font-weight: 600; }
+details[open] > summary { border-bottom: 1px solid #d1d9e0; }
+.content { padding: 0.5rem 1rem 1rem; }
+.badge { display: inline-block; min-width: 3rem; text-align: center; border-radius: 4px; color: #fff; padding: 0 0.4rem; margin-right: 0.5rem; }
+.pass .badge, .badge.pass { background: #1a7f37; }
+.fail .badge, .badge.fail { background: #cf222e; }
+.skip .badge, .badge.skip { background: #9a6700; }
+.meta { color: #59636e; font-weight: normal; }
+table { border-collapse: collapse; margin: 0.5rem 0; }
+td, th { border: 1px solid #d1d9e0; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; }
+pre { background: #f6f8fa; padding: 0.5rem; overflow-x: auto; max-height: 24rem; }
+.error { color: #cf222e; }
+";
+
+/// Renders a standalone HTML page (no external assets): a summary of the run, then one
+/// collapsible section per request, with the timing, status, and (truncated) bodies of each call.
+/// Failed requests start expanded.
+pub(super) fn render(runs: &[ReportInput], not_run: &[NotRun], requests_root: &Utf8Path) -> String {
+    let passed = runs.iter().filter(|run| run_passed(run)).count();
+    let total = runs.len() + not_run.len();
+    let total_time = runs
+        .iter()
+        .flat_map(|run| run.result.entries.iter())
+        .map(entry_time)
+        .sum::<Duration>();
+
+    let mut body = String::new();
+    for run in runs {
+        render_run(&mut body, run, requests_root);
+    }
+
+    for failed in not_run {
+        let _ = write!(
+            body,
+            "<details class=\"fail\" open><summary><span class=\"badge\">ERROR</span>{}</summary>\
+             <div class=\"content\"><p class=\"error\">{}</p></div></details>\n",
+            escape_markup(&failed.name),
+            escape_markup(&failed.message)
+        );
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>whurl report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>whurl report</h1>\n\
+         <p class=\"summary\">Requests: {passed}/{total} passed &middot; Total time: {}s &middot; Generated {} by whurl v{}</p>\n\
+         {body}</body>\n</html>\n",
+        seconds(total_time),
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S %:z"),
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+fn render_run(out: &mut String, run: &ReportInput, requests_root: &Utf8Path) {
+    let passed = run_passed(run);
+    let time = run.result.entries.iter().map(entry_time).sum::<Duration>();
+
+    let _ = write!(
+        out,
+        "<details class=\"{}\"{}><summary><span class=\"badge\">{}</span>{} \
+         <span class=\"meta\">{} entries, {}s</span></summary>\n<div class=\"content\">\n",
+        if passed { "pass" } else { "fail" },
+        if passed { "" } else { " open" },
+        if passed { "PASS" } else { "FAIL" },
+        escape_markup(run.display_path),
+        run.result.entries.len(),
+        seconds(time)
+    );
+
+    for entry in &run.result.entries {
+        render_entry(out, entry);
+    }
+
+    render_assertions(out, run.assertions, requests_root);
+    out.push_str("</div>\n</details>\n");
+}
+
+fn render_entry(out: &mut String, entry: &EntryResult) {
+    let _ = writeln!(
+        out,
+        "<h3><span class=\"badge {}\">{}</span>Entry #{} <span class=\"meta\">{}s</span></h3>",
+        if entry.errors.is_empty() {
+            "pass"
+        } else {
+            "fail"
+        },
+        if entry.errors.is_empty() {
+            "OK"
+        } else {
+            "FAIL"
+        },
+        entry.entry_index,
+        seconds(entry_time(entry))
+    );
+
+    for error in &entry.errors {
+        let _ = writeln!(
+            out,
+            "<p class=\"error\">{}</p>",
+            escape_markup(&error.description())
+        );
+    }
+
+    for call in &entry.calls {
+        render_call(out, call);
+    }
+}
+
+fn render_call(out: &mut String, call: &Call) {
+    let _ = writeln!(
+        out,
+        "<table><tr><th>Request</th><td>{}</td></tr>\
+         <tr><th>Status</th><td>{} ({:?})</td></tr>\
+         <tr><th>Time</th><td>{} ms</td></tr></table>",
+        escape_markup(&describe_call(call)),
+        call.response.status,
+        call.response.version,
+        call.timings.total.as_millis()
+    );
+
+    if !call.request.body.is_empty() {
+        let _ = writeln!(
+            out,
+            "<details><summary>Request body</summary><pre>{}</pre></details>",
+            escape_markup(&format_body(&call.request.body))
+        );
+    }
+
+    if !call.response.body.is_empty() {
+        let _ = writeln!(
+            out,
+            "<details><summary>Response body ({} bytes)</summary><pre>{}</pre></details>",
+            call.response.body.len(),
+            escape_markup(&format_body(&call.response.body))
+        );
+    }
+}
+
+fn render_assertions(out: &mut String, reports: &[AssertionReport], requests_root: &Utf8Path) {
+    if reports.is_empty() {
+        return;
+    }
+
+    out.push_str("<h3>Assertions</h3>\n<table><tr><th></th><th>Assertion</th><th>Actual</th><th>Source</th></tr>\n");
+    for report in reports {
+        let source = report
+            .source
+            .strip_prefix(requests_root)
+            .unwrap_or(&report.source);
+
+        for outcome in &report.outcomes {
+            let (class, label) = match (report.entry_index, outcome.passed) {
+                (None, _) => ("skip", "SKIP"),
+                (Some(_), true) => ("pass", "PASS"),
+                (Some(_), false) => ("fail", "FAIL"),
+            };
+            let _ = writeln!(
+                out,
+                "<tr><td><span class=\"badge {class}\">{label}</span></td><td>{}</td><td>{}</td><td>{}:{}</td></tr>",
+                escape_markup(&outcome.text),
+                escape_markup(outcome.actual.as_deref().unwrap_or("")),
+                escape_markup(source.as_str()),
+                outcome.line
+            );
+        }
+    }
+    out.push_str("</table>\n");
+}
+
+/// Pretty-prints JSON bodies, shows other text as-is, and summarizes binary ones. Cut at
+/// `MAX_BODY_BYTES`.
+fn format_body(body: &[u8]) -> String {
+    let text = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(json) => serde_json::to_string_pretty(&json).unwrap_or_default(),
+        Err(_) => match std::str::from_utf8(body) {
+            Ok(text) => text.to_string(),
+            Err(_) => return format!("<{} bytes of binary data>", body.len()),
+        },
+    };
+
+    truncate(text, MAX_BODY_BYTES)
+}
+
+fn truncate(mut text: String, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text;
+    }
+
+    let total = text.len();
+    let mut cut = max_bytes;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    text.truncate(cut);
+    let _ = write!(text, "\n… truncated ({cut} of {total} bytes shown)");
+    text
+}
+
+fn run_passed(run: &ReportInput) -> bool {
+    run.result.success && run.assertions.iter().all(|report| report.passed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_long_bodies_on_a_char_boundary() {
+        let body = "é".repeat(10);
+
+        let cut = truncate(body.clone(), 5);
+
+        assert!(cut.starts_with("éé\n"));
+        assert!(cut.ends_with("(4 of 20 bytes shown)"));
+        assert_eq!(truncate(body.clone(), 64), body);
+    }
+}
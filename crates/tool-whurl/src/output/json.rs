@@ -1,10 +1,22 @@
-use camino::Utf8Path;
+use std::io;
+
+use camino::{Utf8Path, Utf8PathBuf};
 use hurl::output;
 use hurl::runner::{HurlResult, Output as RunnerOutput};
 use hurl::util::term::{Stdout, WriteMode};
 use hurl_core::input::Input;
 
-use super::OutputError;
+use crate::engine::AssertionReport;
+
+use super::{write_text_report, OutputError};
+
+/// One request of a run, for `write_combined_json_report` and the `--report` outputs.
+pub struct ReportInput<'a> {
+    pub result: &'a HurlResult,
+    pub merged: &'a str,
+    pub display_path: &'a str,
+    pub assertions: &'a [AssertionReport],
+}
 
 pub fn write_json_report(
     result: &HurlResult,
@@ -47,3 +59,41 @@ pub fn write_json_report(
         source,
     })
 }
+
+/// Writes the reports of several requests as a JSON array, in the order given. Each item is the
+/// report `write_json_report` would write for that request.
+pub fn write_combined_json_report(
+    reports: &[ReportInput],
+    target: &Utf8Path,
+) -> Result<(), OutputError> {
+    let temp = tempfile::tempdir().map_err(|source| OutputError::TempReport { source })?;
+    let temp_root = Utf8PathBuf::from_path_buf(temp.path().to_path_buf()).map_err(|_| {
+        OutputError::TempReport {
+            source: io::Error::new(io::ErrorKind::InvalidData, "non UTF-8 temporary path"),
+        }
+    })?;
+
+    let mut combined = Vec::with_capacity(reports.len());
+    for (index, report) in reports.iter().enumerate() {
+        let path = temp_root.join(format!("{index}.json"));
+        write_json_report(report.result, report.merged, report.display_path, &path)?;
+
+        let content = std::fs::read_to_string(&path).map_err(|source| OutputError::SourceRead {
+            path: path.clone(),
+            source,
+        })?;
+        let value = serde_json::from_str::<serde_json::Value>(&content)
+            .map_err(|source| OutputError::InvalidReport { path, source })?;
+        combined.push(value);
+    }
+
+    let content =
+        serde_json::to_string_pretty(&serde_json::Value::Array(combined)).map_err(|source| {
+            OutputError::InvalidReport {
+                path: target.to_path_buf(),
+                source,
+            }
+        })?;
+
+    write_text_report(target, &content)
+}
@@ -0,0 +1,182 @@
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use camino::Utf8Path;
+use hurl::runner::EntryResult;
+use hurl_core::error::DisplaySourceError;
+
+use crate::engine::AssertionReport;
+
+use super::{describe_call, entry_time, escape_markup, seconds, NotRun, ReportInput};
+
+#[derive(Default)]
+struct Counts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+}
+
+/// Renders a JUnit XML report: one `<testsuite>` per request file, with a `<testcase>` per Hurl
+/// entry and one per `# @assert`. Requests that could not run are reported as errors.
+pub(super) fn render(runs: &[ReportInput], not_run: &[NotRun], requests_root: &Utf8Path) -> String {
+    let mut suites = String::new();
+    let mut totals = Counts::default();
+    let mut total_time = Duration::ZERO;
+
+    for run in runs {
+        let (suite, counts, time) = render_suite(run, requests_root);
+        suites.push_str(&suite);
+        totals.tests += counts.tests;
+        totals.failures += counts.failures;
+        totals.errors += counts.errors;
+        totals.skipped += counts.skipped;
+        total_time += time;
+    }
+
+    for failed in not_run {
+        let name = escape_markup(&failed.name);
+        let _ = write!(
+            suites,
+            "  <testsuite name=\"{name}\" tests=\"1\" failures=\"0\" errors=\"1\" skipped=\"0\" time=\"0.000\">\n    \
+             <testcase name=\"{name}\" classname=\"{name}\" time=\"0.000\">\n      \
+             <error message=\"{message}\" type=\"NotRun\"/>\n    \
+             </testcase>\n  </testsuite>\n",
+            message = escape_markup(&failed.message),
+        );
+        totals.tests += 1;
+        totals.errors += 1;
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"whurl\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\">\n\
+         {suites}</testsuites>\n",
+        totals.tests,
+        totals.failures,
+        totals.errors,
+        totals.skipped,
+        seconds(total_time)
+    )
+}
+
+fn render_suite(run: &ReportInput, requests_root: &Utf8Path) -> (String, Counts, Duration) {
+    let mut cases = String::new();
+    let mut counts = Counts::default();
+    let mut suite_time = Duration::ZERO;
+    let classname = escape_markup(run.display_path);
+
+    for entry in &run.result.entries {
+        let time = entry_time(entry);
+        suite_time += time;
+        counts.tests += 1;
+
+        let name = match entry.calls.last() {
+            Some(call) => format!("Entry #{}: {}", entry.entry_index, describe_call(call)),
+            None => format!("Entry #{}", entry.entry_index),
+        };
+        let _ = write!(
+            cases,
+            "    <testcase name=\"{}\" classname=\"{classname}\" time=\"{}\"",
+            escape_markup(&name),
+            seconds(time)
+        );
+
+        if entry.errors.is_empty() {
+            cases.push_str("/>\n");
+            continue;
+        }
+
+        counts.failures += 1;
+        let descriptions = entry
+            .errors
+            .iter()
+            .map(|error| error.description())
+            .collect::<Vec<_>>();
+        let _ = write!(
+            cases,
+            ">\n      <failure message=\"{}\" type=\"HurlError\">{}</failure>\n    </testcase>\n",
+            escape_markup(&descriptions[0]),
+            escape_markup(&descriptions.join("\n"))
+        );
+    }
+
+    for report in run.assertions {
+        render_assertions(&mut cases, &mut counts, report, &classname, requests_root);
+    }
+
+    let suite = format!(
+        "  <testsuite name=\"{classname}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\">\n\
+         {cases}  </testsuite>\n",
+        counts.tests,
+        counts.failures,
+        counts.skipped,
+        seconds(suite_time)
+    );
+
+    (suite, counts, suite_time)
+}
+
+fn render_assertions(
+    cases: &mut String,
+    counts: &mut Counts,
+    report: &AssertionReport,
+    classname: &str,
+    requests_root: &Utf8Path,
+) {
+    let source = report
+        .source
+        .strip_prefix(requests_root)
+        .unwrap_or(&report.source);
+
+    for outcome in &report.outcomes {
+        counts.tests += 1;
+        let _ = write!(
+            cases,
+            "    <testcase name=\"{}\" classname=\"{classname}\" time=\"0.000\"",
+            escape_markup(&format!(
+                "@assert {} ({}:{})",
+                outcome.text, source, outcome.line
+            ))
+        );
+
+        if report.entry_index.is_none() {
+            counts.skipped += 1;
+            cases
+                .push_str(">\n      <skipped message=\"request did not run\"/>\n    </testcase>\n");
+        } else if outcome.passed {
+            cases.push_str("/>\n");
+        } else {
+            counts.failures += 1;
+            let message = format!(
+                "actual: {}",
+                outcome.actual.as_deref().unwrap_or("<missing>")
+            );
+            let _ = write!(
+                cases,
+                ">\n      <failure message=\"{}\" type=\"AssertionFailed\"/>\n    </testcase>\n",
+                escape_markup(&message)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_requests_that_did_not_run_as_errors() {
+        let not_run = [NotRun {
+            name: "billing/broken".to_string(),
+            message: "file not found: <missing> & more".to_string(),
+        }];
+
+        let xml = render(&[], &not_run, Utf8Path::new("/requests"));
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains("<testsuites name=\"whurl\" tests=\"1\" failures=\"0\" errors=\"1\""));
+        assert!(xml.contains("message=\"file not found: &lt;missing&gt; &amp; more\""));
+        assert!(xml.trim_end().ends_with("</testsuites>"));
+    }
+}
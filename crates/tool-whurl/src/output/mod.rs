@@ -1,14 +1,98 @@
+mod html;
 mod json;
+mod junit;
 mod summarize;
 
-pub use json::write_json_report;
+pub use json::{write_combined_json_report, write_json_report, ReportInput};
 pub use summarize::print_test_summary;
 
 use std::io;
+use std::time::Duration;
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
+use hurl::http::Call;
+use hurl::runner::EntryResult;
 use thiserror::Error;
 
+use crate::models::{ReportFormat, ReportTarget};
+
+/// A request of a wildcard run that could not run (e.g. a broken include), listed as an error in
+/// the JUnit and HTML reports.
+pub struct NotRun {
+    pub name: String,
+    pub message: String,
+}
+
+/// Writes every `--report` output for the requests of the run.
+pub fn write_reports(
+    targets: &[ReportTarget],
+    runs: &[ReportInput],
+    not_run: &[NotRun],
+    requests_root: &Utf8Path,
+) -> Result<(), OutputError> {
+    for target in targets {
+        let content = match target.format {
+            ReportFormat::Junit => junit::render(runs, not_run, requests_root),
+            ReportFormat::Html => html::render(runs, not_run, requests_root),
+        };
+        write_text_report(&target.path, &content)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a report to a file (creating its folder), or to stdout when the target is `-`.
+fn write_text_report(target: &Utf8Path, content: &str) -> Result<(), OutputError> {
+    if target.as_str() == "-" {
+        println!("{content}");
+        return Ok(());
+    }
+
+    if let Some(parent) = target.parent().filter(|parent| !parent.as_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|source| OutputError::CreateDir {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+
+    std::fs::write(target, content).map_err(|source| OutputError::StreamWrite {
+        target: target.to_string(),
+        source,
+    })
+}
+
+/// Escapes text for XML and HTML, both in content and in quoted attributes.
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            // Control characters are not allowed in XML 1.0, even escaped.
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => escaped.push('\u{FFFD}'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `METHOD URL` of a call, used to name entries in the reports.
+fn describe_call(call: &Call) -> String {
+    format!("{} {}", call.request.method, call.request.url)
+}
+
+/// Time spent in the calls of an entry (redirects included).
+fn entry_time(entry: &EntryResult) -> Duration {
+    entry.calls.iter().map(|call| call.timings.total).sum()
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
 #[derive(Debug, Error)]
 pub enum OutputError {
     #[error("failed to create directory {path}: {source}")]
@@ -29,4 +113,15 @@ pub enum OutputError {
         #[source]
         source: io::Error,
     },
+    #[error("failed to prepare temporary JSON report: {source}")]
+    TempReport {
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid JSON report {path}: {source}")]
+    InvalidReport {
+        path: Utf8PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
 }
@@ -5,6 +5,7 @@ use camino::{Utf8Path, Utf8PathBuf};
 use hurl::runner::HurlResult;
 use hurl_core::error::DisplaySourceError;
 
+use crate::engine::AssertionReport;
 use crate::includer::IncludeResult;
 
 use super::OutputError;
@@ -15,13 +16,17 @@ pub fn print_test_summary<W: Write>(
     writer: &mut W,
     result: &HurlResult,
     includes: &IncludeResult,
+    assertions: &[AssertionReport],
     requests_root: &Utf8Path,
 ) -> Result<(), OutputError> {
     let total = result.entries.len();
     let failed = result
         .entries
         .iter()
-        .filter(|entry| !entry.errors.is_empty())
+        .filter(|entry| {
+            !entry.errors.is_empty()
+                || reports_for_entry(assertions, entry.entry_index).any(|report| !report.passed())
+        })
         .count();
     let passed = total - failed;
 
@@ -35,20 +40,50 @@ pub fn print_test_summary<W: Write>(
         source,
     })?;
 
+    let outcomes = assertions.iter().flat_map(|report| report.outcomes.iter());
+    let assertions_total = outcomes.clone().count();
+    if assertions_total > 0 {
+        let assertions_passed = outcomes.filter(|outcome| outcome.passed).count();
+        writeln!(
+            writer,
+            "Assertions: {assertions_passed}/{assertions_total} passed ({} failed)",
+            assertions_total - assertions_passed
+        )
+        .map_err(|source| OutputError::StreamWrite {
+            target: "-".to_string(),
+            source,
+        })?;
+    }
+
     let mut cache: HashMap<Utf8PathBuf, Vec<String>> = HashMap::new();
 
     for entry in &result.entries {
+        let entry_reports = reports_for_entry(assertions, entry.entry_index).collect::<Vec<_>>();
+
         if entry.errors.is_empty() {
+            let assertions_passed = entry_reports.iter().all(|report| report.passed());
             writeln!(
                 writer,
-                "  ✓ Entry #{:>3} ({} requests)",
+                "  {} Entry #{:>3} ({} requests{})",
+                if assertions_passed { '✓' } else { '✗' },
                 entry.entry_index,
-                entry.calls.len()
+                entry.calls.len(),
+                if assertions_passed {
+                    ""
+                } else {
+                    ", assertions failed"
+                }
             )
             .map_err(|source| OutputError::StreamWrite {
                 target: "-".to_string(),
                 source,
             })?;
+            write_assertion_outcomes(writer, &entry_reports, requests_root).map_err(|source| {
+                OutputError::StreamWrite {
+                    target: "-".to_string(),
+                    source,
+                }
+            })?;
             continue;
         }
 
@@ -93,6 +128,69 @@ pub fn print_test_summary<W: Write>(
                 }
             }
         }
+
+        write_assertion_outcomes(writer, &entry_reports, requests_root).map_err(|source| {
+            OutputError::StreamWrite {
+                target: "-".to_string(),
+                source,
+            }
+        })?;
+    }
+
+    for report in assertions
+        .iter()
+        .filter(|report| report.entry_index.is_none())
+    {
+        writeln!(
+            writer,
+            "  ✗ {}: request did not run, {} assertion{} not checked",
+            format_path(requests_root, &report.source),
+            report.outcomes.len(),
+            if report.outcomes.len() == 1 { "" } else { "s" }
+        )
+        .map_err(|source| OutputError::StreamWrite {
+            target: "-".to_string(),
+            source,
+        })?;
+    }
+
+    Ok(())
+}
+
+fn reports_for_entry(
+    assertions: &[AssertionReport],
+    entry_index: usize,
+) -> impl Iterator<Item = &AssertionReport> {
+    assertions
+        .iter()
+        .filter(move |report| report.entry_index == Some(entry_index))
+}
+
+fn write_assertion_outcomes<W: Write>(
+    writer: &mut W,
+    reports: &[&AssertionReport],
+    requests_root: &Utf8Path,
+) -> Result<(), io::Error> {
+    for report in reports {
+        for outcome in &report.outcomes {
+            if outcome.passed {
+                writeln!(writer, "      ✓ {}", outcome.text)?;
+                continue;
+            }
+
+            writeln!(
+                writer,
+                "      ✗ {} (actual: {})",
+                outcome.text,
+                outcome.actual.as_deref().unwrap_or("<missing>")
+            )?;
+            writeln!(
+                writer,
+                "        at {}:{}",
+                format_path(requests_root, &report.source),
+                outcome.line
+            )?;
+        }
     }
 
     Ok(())
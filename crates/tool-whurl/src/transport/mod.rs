@@ -0,0 +1,34 @@
+mod pkcs12;
+mod settings;
+
+pub use settings::{redact_proxy, CertificateFormat, PreparedTransport, TransportSettings};
+
+use std::io;
+
+use camino::Utf8PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TransportError {
+    #[error("invalid `# @{key}` directive in {path} at line {line}: {message}")]
+    Directive {
+        path: Utf8PathBuf,
+        key: String,
+        line: u32,
+        message: String,
+    },
+    #[error("invalid proxy '{proxy}': {message}")]
+    InvalidProxy { proxy: String, message: String },
+    #[error("{kind} file not found: {path}")]
+    FileNotFound { kind: String, path: Utf8PathBuf },
+    #[error("{0}")]
+    InvalidCombination(String),
+    #[error("failed to read certificate {path}: {source}")]
+    Io {
+        path: Utf8PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to load PKCS12 certificate {path}: {message}")]
+    Pkcs12 { path: Utf8PathBuf, message: String },
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::io::Write;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use camino::Utf8Path;
+use p12_keystore::KeyStore;
+use tempfile::NamedTempFile;
+
+use super::TransportError;
+
+const PEM_LINE_WIDTH: usize = 64;
+
+/// Extracts the certificate chain and the private key of a PKCS12 file into a temporary PEM file.
+///
+/// The temporary file is only readable by the current user and is deleted when dropped.
+pub fn convert_pkcs12_to_pem(
+    path: &Utf8Path,
+    password: &str,
+) -> Result<NamedTempFile, TransportError> {
+    let data = fs::read(path).map_err(|source| TransportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let pkcs12_error = |message: String| TransportError::Pkcs12 {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    let keystore = KeyStore::from_pkcs12(&data, password)
+        .map_err(|error| pkcs12_error(format!("{error} (is the certificate password correct?)")))?;
+
+    let (_, key_chain) = keystore
+        .private_key_chain()
+        .ok_or_else(|| pkcs12_error("no private key found".to_string()))?;
+
+    if key_chain.chain().is_empty() {
+        return Err(pkcs12_error("no certificate found".to_string()));
+    }
+
+    let mut pem = String::new();
+    for certificate in key_chain.chain() {
+        pem.push_str(&pem_block("CERTIFICATE", certificate.as_der()));
+    }
+    pem.push_str(&pem_block("PRIVATE KEY", key_chain.key()));
+
+    let write_error = |source| TransportError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    let mut file = tempfile::Builder::new()
+        .prefix("whurl-client-cert-")
+        .suffix(".pem")
+        .tempfile()
+        .map_err(write_error)?;
+    file.write_all(pem.as_bytes()).map_err(write_error)?;
+    file.flush().map_err(write_error)?;
+
+    Ok(file)
+}
+
+fn pem_block(label: &str, der: &[u8]) -> String {
+    let encoded = STANDARD.encode(der);
+    let mut block = format!("-----BEGIN {label}-----\n");
+
+    for line in encoded.as_bytes().chunks(PEM_LINE_WIDTH) {
+        block.push_str(&String::from_utf8_lossy(line));
+        block.push('\n');
+    }
+
+    block.push_str(&format!("-----END {label}-----\n"));
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pem_block_wraps_lines_at_64_characters() {
+        let block = pem_block("CERTIFICATE", &[0u8; 60]);
+        let lines: Vec<&str> = block.lines().collect();
+
+        assert_eq!(lines.first(), Some(&"-----BEGIN CERTIFICATE-----"));
+        assert_eq!(lines[1].len(), 64);
+        assert_eq!(lines[2].len(), 16);
+        assert_eq!(lines.last(), Some(&"-----END CERTIFICATE-----"));
+    }
+
+    #[test]
+    fn invalid_pkcs12_is_reported() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path =
+            camino::Utf8PathBuf::from_path_buf(temp.path().join("client.p12")).expect("utf8 path");
+        fs::write(&path, b"not a pkcs12 file").expect("write");
+
+        let error = convert_pkcs12_to_pem(&path, "secret").expect_err("should fail");
+
+        assert!(matches!(error, TransportError::Pkcs12 { .. }));
+    }
+}
@@ -1,31 +1,43 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use crate::cli_utils::print_runtime_info;
-use crate::engine::run_hurl;
+use crate::cli_utils::{print_batch_info, print_runtime_info};
+use crate::engine::{
+    evaluate_assertions, prepare_assertions, prepare_captures, run_hurl, run_pool,
+    run_with_captures, AssertionReport, CookieSession,
+};
 use crate::files::discover::{
     load_dynamic_vars_file, load_env_file, resolve_file_root, resolve_vars_file_path,
 };
 use crate::files::{
-    list_apis, list_requests, locate_requests_root, FileResolver, ResolvedRunContext,
+    discover_requests, is_pattern, list_apis, list_requests, locate_requests_root, FileResolver,
+    ResolvedRunContext,
 };
+use crate::import::{import_openapi, import_postman, write_api};
 use crate::includer;
 use crate::includer::Includer;
 use crate::models::{
-    Cli, Command, DryRunArgs, ListArgs, RunArgs, ToolError, ToolResult, VariableAccumulator,
+    Cli, Command, DryRunArgs, ImportArgs, ImportSource, ListArgs, RunArgs, ToolError, ToolResult,
+    VariableAccumulator,
+};
+use crate::output::{
+    print_test_summary, write_combined_json_report, write_json_report, write_reports, NotRun,
+    ReportInput,
 };
-use crate::output::{print_test_summary, write_json_report};
-use crate::vars::{gather_process_env_variables, parse_variables_file, VariableMap};
+use crate::transport::TransportSettings;
+use crate::vars::{gather_process_env_variables, parse_variables_file};
 use crate::whurl_utils::display_relative_path;
 use anyhow::anyhow;
 use camino::Utf8PathBuf;
+use hurl::runner::HurlResult;
 use shared::logging::app_logger::LogLevel;
-use tracing::info;
+use tracing::{info, warn};
 
 pub fn execute(cli: Cli) -> ToolResult<()> {
     match cli.command {
         Command::List(args) => handle_list(args),
         Command::DryRun(args) => handle_dry_run(args),
         Command::Run(args) => handle_run(args),
+        Command::Import(args) => handle_import(args),
     }
 }
 
@@ -49,8 +61,11 @@ pub fn print_error(error: &ToolError) {
         ToolError::Vars(inner) => eprintln!("Variable error: {inner}"),
         ToolError::Output(inner) => eprintln!("Output error: {inner}"),
         ToolError::Engine(inner) => eprintln!("Engine error: {inner}"),
+        ToolError::Transport(inner) => eprintln!("Transport error: {inner}"),
+        ToolError::Import(inner) => eprintln!("Import failed: {inner}"),
         ToolError::Other(inner) => eprintln!("{inner}"),
         ToolError::ExecutionFailure => eprintln!("One or more requests failed."),
+        ToolError::AssertionFailure => eprintln!("One or more assertions failed."),
     }
 }
 
@@ -96,51 +111,114 @@ fn handle_dry_run(args: DryRunArgs) -> ToolResult<()> {
     Ok(())
 }
 
-fn handle_run(args: RunArgs) -> ToolResult<()> {
+fn handle_import(args: ImportArgs) -> ToolResult<()> {
     let requests_root = locate_requests_root()?;
-    let resolver = FileResolver::new(requests_root.clone());
 
-    let silent_mode = args.silent || args.print_only_full_response || args.print_only_response_body;
+    let (mut api, source) = match &args.source {
+        ImportSource::OpenApi(path) => (import_openapi(path)?, path),
+        ImportSource::Postman(path) => (import_postman(path)?, path),
+    };
+    if let Some(name) = args.api {
+        api.name = name;
+    }
 
-    let context = resolver.resolve_run_context(&args.exec.api, &args.exec.file)?;
-    let include_result =
-        Includer::new(resolver.clone()).merge(context.resolution.file_path.as_path())?;
+    let summary = write_api(&requests_root, &api, args.force)?;
 
-    if !silent_mode {
-        print_runtime_info(&context, &args);
+    println!(
+        "Imported {} request(s) from `{}` into `{}`.",
+        summary.written.len(),
+        source,
+        summary.api_root
+    );
+    for name in &summary.written {
+        println!("  + {name}");
     }
 
-    let variables = build_variables(&resolver, &context, &include_result, &args, silent_mode)?;
-    let file_root = resolve_file_root(&context, args.exec.file_root.as_ref());
+    if !summary.skipped.is_empty() {
+        println!(
+            "Skipped {} existing request(s) (use --force to overwrite):",
+            summary.skipped.len()
+        );
+        for name in &summary.skipped {
+            println!("  = {name}");
+        }
+    }
 
-    let result = run_hurl(
-        include_result.merged.as_str(),
-        &context.display_path,
-        &variables,
-        args.exec.verbosity,
-        file_root.as_deref(),
-    )?;
+    if !summary.variables_added.is_empty() {
+        println!(
+            "Added {} variable(s) to `_vars/_global.hurlvars`: {}",
+            summary.variables_added.len(),
+            summary.variables_added.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_run(args: RunArgs) -> ToolResult<()> {
+    let requests_root = locate_requests_root()?;
+    let resolver = FileResolver::new(requests_root.clone());
+    let cookies = CookieSession::new(resolver.clone(), args.cookie_jar.clone());
+
+    if is_pattern(&args.exec.api) || is_pattern(&args.exec.file) {
+        let outcome = handle_batch_run(&resolver, &args, &cookies);
+        save_cookie_jars(&cookies, args.silent)?;
+        return outcome;
+    }
+
+    let silent_mode = args.silent || args.print_only_full_response || args.print_only_response_body;
+
+    let run = execute_request(
+        &resolver,
+        &args,
+        &cookies,
+        &args.exec.api,
+        &args.exec.file,
+        silent_mode,
+    );
+    // A failed run may still have opened (or closed) a session.
+    save_cookie_jars(&cookies, silent_mode)?;
+    let run = run?;
+    let RequestRun {
+        context,
+        include_result,
+        result,
+        assertion_reports,
+    } = &run;
 
     if let Some(json_path) = args.json_output.as_ref() {
         write_json_report(
-            &result,
+            result,
             include_result.merged.as_str(),
             &context.display_path,
             json_path.as_path(),
         )?;
     }
 
+    if !args.reports.is_empty() {
+        let report = ReportInput {
+            result,
+            merged: include_result.merged.as_str(),
+            display_path: &context.display_path,
+            assertions: assertion_reports,
+        };
+        write_reports(&args.reports, &[report], &[], resolver.requests_root())?;
+    }
+
     if args.print_only_full_response {
         print_full_response_pretty(
-            &result,
+            result,
             include_result.merged.as_str(),
             &context.display_path,
         )?;
     } else if args.print_only_response_body {
-        print_only_response_body(&result);
+        print_only_response_body(result);
     } else {
         if !silent_mode {
-            log_execution_details(&result, &include_result);
+            log_execution_details(result, include_result);
+            if !args.test_mode {
+                log_failed_assertions(assertion_reports, &resolver);
+            }
         }
 
         if args.test_mode {
@@ -148,8 +226,9 @@ fn handle_run(args: RunArgs) -> ToolResult<()> {
             let mut handle = stdout.lock();
             print_test_summary(
                 &mut handle,
-                &result,
-                &include_result,
+                result,
+                include_result,
+                assertion_reports,
                 resolver.requests_root(),
             )?;
         }
@@ -159,9 +238,246 @@ fn handle_run(args: RunArgs) -> ToolResult<()> {
         return Err(ToolError::ExecutionFailure);
     }
 
+    if args.test_mode && run.assertions_failed() {
+        return Err(ToolError::AssertionFailure);
+    }
+
+    Ok(())
+}
+
+/// Runs every request matching the API/FILE wildcards, `--parallel` at a time. Each request runs
+/// on its own (includes, variables, captures, and assertions), and the results are reported in
+/// discovery order once all of them are done.
+fn handle_batch_run(
+    resolver: &FileResolver,
+    args: &RunArgs,
+    cookies: &CookieSession,
+) -> ToolResult<()> {
+    if args.print_only_full_response || args.print_only_response_body {
+        return Err(ToolError::Other(anyhow!(
+            "`--print-only-full-response` and `--print-only-response-body` need a single request, but `{}/{}` can select several",
+            args.exec.api,
+            args.exec.file
+        )));
+    }
+
+    let targets = discover_requests(resolver.requests_root(), &args.exec.api, &args.exec.file)?;
+
+    if !args.silent {
+        print_batch_info(args, targets.len());
+    }
+
+    let outcomes = run_pool(&targets, args.parallel, |(api, file)| {
+        execute_request(resolver, args, cookies, api, file, true)
+    });
+
+    let mut failed_to_run = false;
+    let mut passed = 0;
+
+    for ((api, file), outcome) in targets.iter().zip(&outcomes) {
+        let run = match outcome {
+            Ok(run) => run,
+            Err(error) => {
+                if !args.silent || args.test_mode {
+                    println!("✗ {api}/{file}: {error}");
+                }
+                failed_to_run = true;
+                continue;
+            }
+        };
+
+        let failed = !run.result.success || (args.test_mode && run.assertions_failed());
+        if !failed {
+            passed += 1;
+        }
+
+        if args.test_mode {
+            println!(
+                "{} {}",
+                if failed { '✗' } else { '✓' },
+                run.context.display_path
+            );
+            print_test_summary(
+                &mut std::io::stdout().lock(),
+                &run.result,
+                &run.include_result,
+                &run.assertion_reports,
+                resolver.requests_root(),
+            )?;
+        } else if !args.silent {
+            info!(
+                "{} {} ({} entries)",
+                if failed { '✗' } else { '✓' },
+                run.context.display_path,
+                run.result.entries.len()
+            );
+            log_failed_assertions(&run.assertion_reports, resolver);
+        }
+    }
+
+    if !args.silent || args.test_mode {
+        println!(
+            "Requests: {passed}/{} passed ({} failed)",
+            targets.len(),
+            targets.len() - passed
+        );
+    }
+
+    let reports = outcomes
+        .iter()
+        .flatten()
+        .map(|run| ReportInput {
+            result: &run.result,
+            merged: run.include_result.merged.as_str(),
+            display_path: &run.context.display_path,
+            assertions: &run.assertion_reports,
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(json_path) = args.json_output.as_ref() {
+        write_combined_json_report(&reports, json_path.as_path())?;
+    }
+
+    if !args.reports.is_empty() {
+        let not_run = targets
+            .iter()
+            .zip(&outcomes)
+            .filter_map(|((api, file), outcome)| {
+                outcome.as_ref().err().map(|error| NotRun {
+                    name: format!("{api}/{file}"),
+                    message: error.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        write_reports(&args.reports, &reports, &not_run, resolver.requests_root())?;
+    }
+
+    if failed_to_run {
+        // Already listed above; the first one decides the exit code.
+        return Err(outcomes
+            .into_iter()
+            .find_map(Result::err)
+            .expect("a request failed to run"));
+    }
+
+    let runs = outcomes.iter().flatten().collect::<Vec<_>>();
+    if runs.iter().any(|run| !run.result.success) {
+        return Err(ToolError::ExecutionFailure);
+    }
+
+    if args.test_mode && runs.iter().any(|run| run.assertions_failed()) {
+        return Err(ToolError::AssertionFailure);
+    }
+
+    Ok(())
+}
+
+fn save_cookie_jars(cookies: &CookieSession, quiet: bool) -> ToolResult<()> {
+    for path in cookies.save()? {
+        if !quiet {
+            info!("Cookies saved to {path}");
+        }
+    }
     Ok(())
 }
 
+/// Everything a run of one request produces.
+struct RequestRun {
+    context: ResolvedRunContext,
+    include_result: includer::IncludeResult,
+    result: HurlResult,
+    assertion_reports: Vec<AssertionReport>,
+}
+
+impl RequestRun {
+    fn assertions_failed(&self) -> bool {
+        self.assertion_reports.iter().any(|report| !report.passed())
+    }
+}
+
+/// Expands, prepares, and runs one request. With `quiet`, nothing is printed or logged, so
+/// requests running in parallel don't mix their output.
+fn execute_request(
+    resolver: &FileResolver,
+    args: &RunArgs,
+    cookies: &CookieSession,
+    api: &str,
+    file: &str,
+    quiet: bool,
+) -> ToolResult<RequestRun> {
+    let context = resolver.resolve_run_context(api, file)?;
+    let include_result =
+        Includer::new(resolver.clone()).merge(context.resolution.file_path.as_path())?;
+    let prepared_assertions = prepare_assertions(&include_result)?;
+    let prepared_captures = prepare_captures(&include_result)?;
+
+    let entry_file = context.resolution.file_path.as_path();
+    let transport = TransportSettings::resolve(
+        &args.transport,
+        include_result.transport_for(entry_file),
+        entry_file,
+    )?;
+
+    if !quiet {
+        print_runtime_info(&context, args, &transport);
+    }
+
+    let mut variables = build_variables(resolver, &context, &include_result, args, quiet)?;
+    let file_root = resolve_file_root(&context, args.exec.file_root.as_ref());
+
+    let cert_password = args
+        .transport
+        .cert_password
+        .clone()
+        .or_else(|| std::env::var("WHURL_CERT_PASSWORD").ok());
+    let prepared_transport = transport.prepare(cert_password.as_deref())?;
+
+    let result = run_with_captures(
+        &include_result,
+        &prepared_captures,
+        &mut variables,
+        !quiet,
+        |text, variables| {
+            let cookie_file = cookies.input_file(api)?;
+            let result = run_hurl(
+                text,
+                &context.display_path,
+                variables,
+                args.exec.verbosity,
+                file_root.as_deref(),
+                &prepared_transport,
+                cookie_file.as_ref().map(|file| file.path()),
+            )?;
+            cookies.update(api, &result)?;
+            Ok(result)
+        },
+    )?;
+
+    let assertion_reports = evaluate_assertions(&prepared_assertions, &result, &include_result);
+
+    Ok(RequestRun {
+        context,
+        include_result,
+        result,
+        assertion_reports,
+    })
+}
+
+fn log_failed_assertions(reports: &[AssertionReport], resolver: &FileResolver) {
+    for report in reports {
+        let source = display_relative_path(resolver, report.source.as_path());
+        for outcome in report.outcomes.iter().filter(|outcome| !outcome.passed) {
+            warn!(
+                "Assertion failed at {}:{}: {} (actual: {})",
+                source,
+                outcome.line,
+                outcome.text,
+                outcome.actual.as_deref().unwrap_or("<missing>")
+            );
+        }
+    }
+}
+
 fn log_execution_details(result: &hurl::runner::HurlResult, includes: &includer::IncludeResult) {
     for entry in &result.entries {
         let entry_behavior = includes
@@ -321,7 +637,7 @@ fn build_variables(
     include_result: &includer::IncludeResult,
     args: &RunArgs,
     silent_mode: bool,
-) -> ToolResult<VariableMap> {
+) -> ToolResult<VariableAccumulator> {
     let mut merger = VariableAccumulator::new(!silent_mode);
     let env_vars = gather_process_env_variables();
     merger.extend_from_map(env_vars, "process environment (HURL_*)");
@@ -497,7 +813,7 @@ fn build_variables(
         );
     }
 
-    Ok(merger.finish())
+    Ok(merger)
 }
 
 fn merge_dynamic_vars(